@@ -0,0 +1,99 @@
+//! Line reading throughput: buffered reads vs the `--mmap` fast path.
+//!
+//! The fixture is generated on first use in a temp directory. It defaults to
+//! 1 GiB; set `KELORA_BENCH_MMAP_MB` for a quicker run, e.g.
+//! `KELORA_BENCH_MMAP_MB=64 cargo bench --bench mmap_reader`.
+
+use std::hint::black_box;
+use std::io::{BufRead, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use tempfile::TempDir;
+
+use kelora::readers::{InputOptions, MmapReader, MultiFileReader};
+
+const DEFAULT_FIXTURE_MB: u64 = 1024;
+
+/// Write a JSON-lines fixture of roughly `bytes` bytes and return its path
+fn generate_fixture(dir: &Path, bytes: u64) -> PathBuf {
+    let path = dir.join("fixture.jsonl");
+    let mut out = BufWriter::new(std::fs::File::create(&path).expect("create fixture"));
+    let mut written = 0u64;
+    let mut i = 0u64;
+    while written < bytes {
+        let line = format!(
+            "{{\"ts\":\"2024-01-15T10:{:02}:{:02}Z\",\"level\":\"{}\",\"user\":\"u{}\",\"latency_ms\":{},\"msg\":\"request handled\"}}\n",
+            (i / 60) % 60,
+            i % 60,
+            ["INFO", "WARN", "ERROR", "DEBUG"][(i % 4) as usize],
+            i % 10_000,
+            i % 997,
+        );
+        out.write_all(line.as_bytes()).expect("write fixture");
+        written += line.len() as u64;
+        i += 1;
+    }
+    out.flush().expect("flush fixture");
+    path
+}
+
+/// Count lines read through `MultiFileReader`, which picks buffered or mapped
+/// input according to `mmap`
+fn count_lines_multi_file(path: &str, mmap: bool) -> usize {
+    let options = InputOptions {
+        mmap,
+        ..InputOptions::default()
+    };
+    let mut reader = MultiFileReader::new(vec![path.to_string()], options, true).expect("reader");
+    let mut line = String::new();
+    let mut count = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).expect("read line") == 0 {
+            return count;
+        }
+        black_box(&line);
+        count += 1;
+    }
+}
+
+/// Count lines read with `MmapReader::next_line` (the parallel path)
+fn count_lines_mapped(path: &str) -> usize {
+    let mut reader = MmapReader::open(path, &InputOptions::default()).expect("mappable");
+    let mut count = 0;
+    while let Some(line) = reader.next_line().expect("read line") {
+        black_box(line);
+        count += 1;
+    }
+    count
+}
+
+fn bench_line_reading(c: &mut Criterion) {
+    let fixture_mb = std::env::var("KELORA_BENCH_MMAP_MB")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_FIXTURE_MB);
+    let dir = TempDir::new().expect("temp dir");
+    let fixture = generate_fixture(dir.path(), fixture_mb * 1024 * 1024);
+    let fixture = fixture.to_str().expect("utf-8 path").to_string();
+    let size = std::fs::metadata(&fixture).expect("fixture metadata").len();
+
+    let mut group = c.benchmark_group("read_lines");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(size));
+
+    group.bench_function("buffered", |b| {
+        b.iter(|| count_lines_multi_file(&fixture, false));
+    });
+    group.bench_function("mmap", |b| {
+        b.iter(|| count_lines_multi_file(&fixture, true));
+    });
+    group.bench_function("mmap_next_line", |b| {
+        b.iter(|| count_lines_mapped(&fixture));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_line_reading);
+criterion_main!(benches);
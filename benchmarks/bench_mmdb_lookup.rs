@@ -0,0 +1,62 @@
+//! Per-event cost of `--mmdb` lookups.
+//!
+//! The fixture is a generated City-style database of 10,000 /24 networks, so
+//! the search tree is as deep as in the real GeoLite2 databases for IPv4.
+//! Run with `cargo bench --bench mmdb_lookup`.
+
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use maxminddb::Reader;
+use tempfile::TempDir;
+
+use kelora::rhai_functions::mmdb;
+
+#[path = "../tests/common/mmdb.rs"]
+mod mmdb_fixture;
+
+const NETWORKS: u32 = 10_000;
+
+/// Write a database of `NETWORKS` networks spread over 1.0.0.0/8..
+fn generate_fixture(dir: &Path) -> PathBuf {
+    let path = dir.join("bench-city.mmdb");
+    let networks: Vec<(String, serde_json::Value)> = (0..NETWORKS)
+        .map(|i| {
+            let network = format!("{}.{}.{}.0/24", 1 + i / 256, i % 256, (i * 7) % 256);
+            let record = serde_json::json!({
+                "city": {"names": {"en": format!("City {}", i), "de": format!("Stadt {}", i)}},
+                "continent": {"code": "EU", "names": {"en": "Europe"}},
+                "country": {"iso_code": "GB", "names": {"en": "United Kingdom"}},
+                "location": {"latitude": 51.5, "longitude": -0.1, "time_zone": "Europe/London"}
+            });
+            (network, record)
+        })
+        .collect();
+    let networks: Vec<(&str, serde_json::Value)> = networks
+        .iter()
+        .map(|(network, record)| (network.as_str(), record.clone()))
+        .collect();
+    mmdb_fixture::write_mmdb(&path, "GeoLite2-City", &networks);
+    path
+}
+
+fn bench_lookup(c: &mut Criterion) {
+    let dir = TempDir::new().expect("temp dir");
+    let reader = Reader::open_readfile(generate_fixture(dir.path())).expect("open fixture");
+
+    let mut group = c.benchmark_group("mmdb_lookup");
+    group.bench_function("hit", |b| {
+        b.iter(|| mmdb::lookup(&reader, black_box("20.123.93.9")));
+    });
+    group.bench_function("miss", |b| {
+        b.iter(|| mmdb::lookup(&reader, black_box("203.0.113.7")));
+    });
+    group.bench_function("invalid", |b| {
+        b.iter(|| mmdb::lookup(&reader, black_box("not-an-ip")));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_lookup);
+criterion_main!(benches);
@@ -7,11 +7,13 @@ use anyhow::Result;
 use clap::error::{ContextKind, ContextValue, ErrorKind};
 use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser};
 
-use crate::cli::{Cli, OutputFormat, ShellCompletion};
-use crate::config::MultilineJoin;
+use crate::cli::{Cli, LintFormat, OutputFormat, RhaiLintCli, ShellCompletion};
+use crate::config::{MultilineJoin, SpanOutput};
 use crate::config_file::{ConfigExpansionInfo, ConfigFile};
+use crate::engine::lint::{LintSource, ScriptKind};
+use crate::engine::RhaiEngine;
 use crate::help;
-use crate::platform::{ExitCode, SafeStderr};
+use crate::platform::{ExitCode, SafeStderr, SafeStdout};
 use crate::tty;
 
 /// Validate CLI arguments for early error detection
@@ -23,6 +25,43 @@ pub fn validate_cli_args(cli: &Cli) -> Result<()> {
         ));
     }
 
+    // --follow-glob supplies its own inputs
+    if cli.follow_glob.is_some() {
+        if !cli.files.is_empty() || cli.no_input {
+            return Err(anyhow::anyhow!(
+                "--follow-glob reads the files matching its pattern and cannot be combined with input files or --no-input. Put the files in the pattern instead (e.g. kelora --follow-glob 'logs/app-*.log')."
+            ));
+        }
+        if cli.merge_ts {
+            return Err(anyhow::anyhow!(
+                "--follow-glob cannot be combined with --merge-sorted; followed files are read as they grow, not merged by timestamp."
+            ));
+        }
+    }
+
+    // --input-cmd supplies its own inputs too
+    if !cli.input_cmds.is_empty() {
+        if !cli.files.is_empty() || cli.no_input || cli.follow_glob.is_some() {
+            return Err(anyhow::anyhow!(
+                "--input-cmd reads the output of its commands and cannot be combined with input files, --no-input or --follow-glob. Pass another --input-cmd instead (e.g. kelora --input-cmd 'cat app.log')."
+            ));
+        }
+        if cli.merge_ts {
+            return Err(anyhow::anyhow!(
+                "--input-cmd cannot be combined with --merge-sorted; command output is read as it arrives, not merged by timestamp."
+            ));
+        }
+        if cli
+            .input_cmds
+            .iter()
+            .any(|command| command.trim().is_empty())
+        {
+            return Err(anyhow::anyhow!(
+                "--input-cmd requires a command, e.g. --input-cmd 'journalctl -u nginx -f'"
+            ));
+        }
+    }
+
     // Check stdin usage
     let mut stdin_count = 0;
     for file_path in &cli.files {
@@ -50,6 +89,187 @@ pub fn validate_cli_args(cli: &Cli) -> Result<()> {
         }
     }
 
+    // Validate output chunking
+    if cli.chunk_size == Some(0) {
+        return Err(anyhow::anyhow!(
+            "--chunk-size must be greater than 0 (events per output array)"
+        ));
+    }
+    if cli.chunk_size_bytes == Some(0) {
+        return Err(anyhow::anyhow!(
+            "--chunk-size-bytes must be greater than 0 (bytes per output array)"
+        ));
+    }
+
+    // Validate table output options
+    if cli.pretty_tables
+        && !matches!(
+            cli.output_format,
+            OutputFormat::Default | OutputFormat::Table
+        )
+    {
+        return Err(anyhow::anyhow!(
+            "--pretty-tables selects table output and cannot be combined with another -F format. Drop -F, or use -F table for borderless columns."
+        ));
+    }
+    if cli.json_flat.is_some()
+        && !matches!(
+            cli.output_format,
+            OutputFormat::Default | OutputFormat::Json
+        )
+    {
+        return Err(anyhow::anyhow!(
+            "--json-flat selects JSON output and cannot be combined with another -F format"
+        ));
+    }
+    if cli.field_prefix_add.is_some()
+        && matches!(
+            cli.output_format,
+            OutputFormat::Csv | OutputFormat::Tsv | OutputFormat::Csvnh | OutputFormat::Tsvnh
+        )
+    {
+        return Err(anyhow::anyhow!(
+            "--field-prefix-add cannot be combined with CSV/TSV output: columns come from --keys, which names the unprefixed fields. Rename in an --exec stage instead."
+        ));
+    }
+    if cli.field_prefix_drop.iter().any(|prefix| prefix.is_empty()) {
+        return Err(anyhow::anyhow!(
+            "--field-prefix-drop requires a non-empty prefix, e.g. --field-prefix-drop log_"
+        ));
+    }
+    if let Some(samples) = cli.sample_run {
+        if samples == 0 {
+            return Err(anyhow::anyhow!(
+                "--sample-run needs at least one event, e.g. --sample-run 3"
+            ));
+        }
+        if cli.follow_glob.is_some() || cli.merge_ts || !cli.input_cmds.is_empty() {
+            return Err(anyhow::anyhow!(
+                "--sample-run reads its inputs once in order; it cannot be combined with --follow-glob, --input-cmd or --merge-sorted"
+            ));
+        }
+    }
+    if cli.diff_output && (cli.follow_glob.is_some() || cli.merge_ts || cli.sample_run.is_some()) {
+        return Err(anyhow::anyhow!(
+            "--diff-output reads --left and --right once in order; it cannot be combined with --follow-glob, --merge-sorted or --sample-run"
+        ));
+    }
+    if !cli.diff_against.is_empty() {
+        if cli.follow_glob.is_some()
+            || cli.merge_ts
+            || cli.sample_run.is_some()
+            || !cli.input_cmds.is_empty()
+        {
+            return Err(anyhow::anyhow!(
+                "--diff-against reads its inputs once in order; it cannot be combined with --follow-glob, --input-cmd, --merge-sorted or --sample-run"
+            ));
+        }
+        if cli
+            .diff_by
+            .as_deref()
+            .is_some_and(|expr| expr.trim().is_empty())
+        {
+            return Err(anyhow::anyhow!(
+                "--diff-by requires an expression, e.g. --diff-by 'e.msg.replace_regex(\"\\\\d+\", \"N\")'"
+            ));
+        }
+        if cli
+            .diff_threshold
+            .is_some_and(|pct| !pct.is_finite() || pct < 0.0)
+        {
+            return Err(anyhow::anyhow!(
+                "--diff-threshold must be a non-negative percentage, e.g. --diff-threshold 25"
+            ));
+        }
+    }
+    if let Some(ref url) = cli.output_http {
+        if !cfg!(feature = "output-http") {
+            return Err(anyhow::anyhow!(
+                "--output-http is not available: kelora was built without the `output-http` feature"
+            ));
+        }
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(anyhow::anyhow!(
+                "--output-http needs an http:// or https:// URL, e.g. --output-http https://collector/ingest"
+            ));
+        }
+        if matches!(
+            cli.output_format,
+            OutputFormat::Orc | OutputFormat::Cbor | OutputFormat::PrometheusPushgateway
+        ) {
+            return Err(anyhow::anyhow!(
+                "--output-http sends formatted events; it cannot be combined with -F orc, -F cbor or -F prometheus-pushgateway"
+            ));
+        }
+    }
+    if matches!(cli.output_format, OutputFormat::Orc) && !cfg!(feature = "orc") {
+        return Err(anyhow::anyhow!(
+            "-F orc is not available: kelora was built without the `orc` feature"
+        ));
+    }
+    if matches!(cli.output_format, OutputFormat::Orc) && cli.output_file.is_none() {
+        return Err(anyhow::anyhow!(
+            "-F orc writes a binary ORC file and needs a destination. Add --output-file OUT.orc."
+        ));
+    }
+    if matches!(cli.output_format, OutputFormat::Cbor) && cli.output_file.is_none() {
+        return Err(anyhow::anyhow!(
+            "-F cbor writes binary CBOR records and needs a destination. Add --output-file OUT.cbor."
+        ));
+    }
+    let pushgateway_shorthand = matches!(cli.output_format, OutputFormat::PrometheusPushgateway);
+    if !pushgateway_shorthand && (cli.metrics_endpoint.is_some() || cli.pushgateway_job.is_some()) {
+        if !matches!(
+            cli.metrics.as_ref().map(|spec| &spec.format),
+            Some(crate::cli::MetricsFormat::Prometheus)
+        ) {
+            return Err(anyhow::anyhow!(
+                "--metrics-endpoint and --pushgateway-job push Prometheus metrics; add --metrics=prometheus (or use -F prometheus-pushgateway)"
+            ));
+        }
+        if cli.metrics_endpoint.is_none() || cli.pushgateway_job.is_none() {
+            return Err(anyhow::anyhow!(
+                "pushing to a Pushgateway needs both --metrics-endpoint URL and --pushgateway-job JOB"
+            ));
+        }
+    }
+    if !cli.pushgateway_grouping.is_empty()
+        && !pushgateway_shorthand
+        && cli.metrics_endpoint.is_none()
+    {
+        return Err(anyhow::anyhow!(
+            "--pushgateway-grouping only applies when pushing metrics; add --metrics-endpoint URL"
+        ));
+    }
+    for spec in &cli.pushgateway_grouping {
+        crate::config::PushgatewayConfig::parse_grouping(spec).map_err(|e| anyhow::anyhow!(e))?;
+    }
+    if (pushgateway_shorthand || cli.metrics_endpoint.is_some()) && !cfg!(feature = "pushgateway") {
+        return Err(anyhow::anyhow!(
+            "pushing to a Pushgateway is not available: kelora was built without the `pushgateway` feature"
+        ));
+    }
+    if cli.window_emit_each && cli.window_script.is_none() && cli.window_emit_each_from.is_none() {
+        return Err(anyhow::anyhow!(
+            "--window-emit-each needs the expression to run, e.g. --window-script '#{{avg: window.pluck_as_nums(\"ms\").mean()}}' (or use --window-emit-each-from FILE)"
+        ));
+    }
+    if cli.orc_stripe_rows == 0 {
+        return Err(anyhow::anyhow!(
+            "--orc-stripe-rows must be greater than 0 (rows per ORC stripe)"
+        ));
+    }
+    if cli.max_field_length == Some(0) {
+        return Err(anyhow::anyhow!(
+            "--max-field-length must be greater than 0 (display columns per table cell)"
+        ));
+    }
+    if cli.on_parse_error_sample == Some(0) {
+        return Err(anyhow::anyhow!(
+            "--on-parse-error-sample must be greater than 0 (samples kept per error category)"
+        ));
+    }
+
     // Validate thread count
     if cli.threads > 1000 {
         return Err(anyhow::anyhow!("Thread count too high (max 1000)"));
@@ -61,6 +281,33 @@ pub fn validate_cli_args(cli: &Cli) -> Result<()> {
         ));
     }
 
+    if (cli.span_summary.is_some() || cli.span_output != SpanOutput::Members)
+        && cli.span.is_none()
+        && cli.span_idle.is_none()
+    {
+        return Err(anyhow::anyhow!(
+            "--span-output and --span-summary require --span or --span-idle."
+        ));
+    }
+
+    if cli.filename_ts_format.is_some() && cli.since.is_none() && cli.until.is_none() {
+        return Err(anyhow::anyhow!(
+            "--filename-ts-format requires --since or --until."
+        ));
+    }
+
+    if cli.span_markers && cli.span.is_none() && cli.span_idle.is_none() {
+        return Err(anyhow::anyhow!(
+            "--span-markers requires --span or --span-idle."
+        ));
+    }
+
+    if cli.span_summary.is_some() && !cli.span_output.emits_summary() {
+        return Err(anyhow::anyhow!(
+            "--span-summary requires --span-output summary or --span-output both."
+        ));
+    }
+
     if cli.multiline.is_none() && cli.multiline_join != MultilineJoin::Space {
         return Err(anyhow::anyhow!(
             "--multiline-join requires --multiline. Start with --multiline indent, --multiline blank, or see --help-multiline for regex/timestamp strategies."
@@ -111,6 +358,12 @@ pub fn validate_cli_args(cli: &Cli) -> Result<()> {
         ));
     }
 
+    if cli.cluster.is_some() && implies_parallel {
+        return Err(anyhow::anyhow!(
+            "--cluster is not supported with --parallel or thread overrides. Rerun without --parallel to cluster values."
+        ));
+    }
+
     if cli.drain.is_some() {
         // Calculate effective keys after applying exclusions
         let effective_keys: Vec<String> = cli
@@ -464,10 +717,78 @@ fn unknown_arg_hint(arg: &str) -> Option<String> {
     ))
 }
 
+/// Lint the script files named after `rhai-lint` and exit.
+fn run_rhai_lint_command(args: &[String], stderr: &mut SafeStderr) -> ! {
+    let lint_cli = RhaiLintCli::parse_from(args);
+
+    let mut sources = Vec::with_capacity(lint_cli.scripts.len());
+    for path in &lint_cli.scripts {
+        match std::fs::read_to_string(path) {
+            Ok(script) => sources.push(LintSource {
+                name: path.clone(),
+                script,
+                kind: ScriptKind::Statements,
+            }),
+            Err(e) => {
+                stderr
+                    .writeln(&crate::config::format_error_message_auto(&format!(
+                        "Error: cannot read script '{}': {}",
+                        path, e
+                    )))
+                    .unwrap_or(());
+                ExitCode::GeneralError.exit();
+            }
+        }
+    }
+
+    exit_with_lint_report(&sources, lint_cli.lint_format)
+}
+
+/// Lint `sources` with a freshly initialized engine, print the report to
+/// stdout and exit 0 when it is clean or 1 otherwise.
+pub fn exit_with_lint_report(sources: &[LintSource], format: LintFormat) -> ! {
+    let report = RhaiEngine::new().lint(sources);
+    let rendered = match format {
+        LintFormat::Text => report.to_text(),
+        LintFormat::Json => report.to_json(),
+    };
+    let mut stdout = SafeStdout::new();
+    stdout.writeln(rendered.trim_end()).unwrap_or(());
+    if report.is_clean() {
+        ExitCode::Success.exit();
+    }
+    ExitCode::GeneralError.exit();
+}
+
+/// Print a loaded config file's unknown keys and sections as warnings, or
+/// exit with invalid usage under --strict-config
+fn report_unknown_config_entries(config_file: &ConfigFile, strict: bool, stderr: &mut SafeStderr) {
+    match config_file.check_unknown_entries(strict) {
+        Ok(warnings) => {
+            for warning in warnings {
+                stderr
+                    .writeln(&format!("kelora: {}", warning))
+                    .unwrap_or(());
+            }
+        }
+        Err(e) => {
+            stderr
+                .writeln(&format!("kelora: Config error: {}", e))
+                .unwrap_or(());
+            ExitCode::InvalidUsage.exit();
+        }
+    }
+}
+
 pub fn process_args_with_config(stderr: &mut SafeStderr) -> (ArgMatches, Cli, ConfigExpansionInfo) {
     // Get raw command line arguments
     let raw_args: Vec<String> = std::env::args().collect();
 
+    // `kelora rhai-lint SCRIPT...` has its own arguments and ignores config files
+    if raw_args.get(1).map(String::as_str) == Some("rhai-lint") {
+        run_rhai_lint_command(&raw_args[1..], stderr);
+    }
+
     // Extract --config-file argument early for use by config commands
     let config_file_path = extract_config_file_arg(&raw_args);
 
@@ -508,6 +829,28 @@ pub fn process_args_with_config(stderr: &mut SafeStderr) -> (ArgMatches, Cli, Co
         std::process::exit(0);
     }
 
+    // Check for --help-function NAME (full entry for one function)
+    if let Some(pos) = raw_args
+        .iter()
+        .position(|arg| arg == "--help-function" || arg.starts_with("--help-function="))
+    {
+        let arg = &raw_args[pos];
+        let name = match arg.strip_prefix("--help-function=") {
+            Some(name) => Some(name.to_string()),
+            None => raw_args.get(pos + 1).cloned(),
+        };
+        match name.filter(|name| !name.is_empty()) {
+            Some(name) => help::print_function_help(&name),
+            None => {
+                stderr
+                    .writeln("kelora: Error: --help-function requires a function name")
+                    .unwrap_or(());
+                ExitCode::InvalidUsage.exit();
+            }
+        }
+        std::process::exit(0);
+    }
+
     // Check for --help-functions [KEYWORD] (optional case-insensitive search)
     if let Some(pos) = raw_args
         .iter()
@@ -523,7 +866,11 @@ pub fn process_args_with_config(stderr: &mut SafeStderr) -> (ArgMatches, Cli, Co
                 .filter(|next| !next.starts_with('-'))
                 .map(|next| next.to_string())
         };
-        help::print_functions_help(keyword.as_deref());
+        if raw_args.iter().any(|arg| arg == "--json") {
+            help::print_functions_json(keyword.as_deref());
+        } else {
+            help::print_functions_help(keyword.as_deref());
+        }
         std::process::exit(0);
     }
 
@@ -600,6 +947,7 @@ pub fn process_args_with_config(stderr: &mut SafeStderr) -> (ArgMatches, Cli, Co
 
     // Check for --ignore-config
     let ignore_config = has_ignore_config;
+    let strict_config = raw_args.iter().any(|arg| arg == "--strict-config");
     let disable_auto_config = std::env::var_os("KELORA_IGNORE_CONFIG").is_some();
 
     let (processed_args, expansion_info) = if ignore_config {
@@ -608,21 +956,24 @@ pub fn process_args_with_config(stderr: &mut SafeStderr) -> (ArgMatches, Cli, Co
     } else if let Some(path) = config_file_path.as_deref() {
         // Explicit config files still load even when automatic config discovery is disabled.
         match ConfigFile::load_with_custom_path(Some(path)) {
-            Ok((config_file, loaded_path)) => match config_file.process_args(raw_args) {
-                Ok((processed, mut info)) => {
-                    info.loaded_config_path = loaded_path;
-                    (processed, info)
-                }
-                Err(e) => {
-                    stderr
-                        .writeln(&format!("kelora: Config error: {}", e))
-                        .unwrap_or(());
-                    // A malformed config file is invalid usage (like a bad flag or
-                    // value), not a runtime failure — exit 2 to match the other
-                    // configuration-validation paths and the --help exit-code table.
-                    ExitCode::InvalidUsage.exit();
+            Ok((config_file, loaded_path)) => {
+                report_unknown_config_entries(&config_file, strict_config, stderr);
+                match config_file.process_args(raw_args) {
+                    Ok((processed, mut info)) => {
+                        info.loaded_config_path = loaded_path;
+                        (processed, info)
+                    }
+                    Err(e) => {
+                        stderr
+                            .writeln(&format!("kelora: Config error: {}", e))
+                            .unwrap_or(());
+                        // A malformed config file is invalid usage (like a bad flag or
+                        // value), not a runtime failure — exit 2 to match the other
+                        // configuration-validation paths and the --help exit-code table.
+                        ExitCode::InvalidUsage.exit();
+                    }
                 }
-            },
+            }
             Err(e) => {
                 stderr
                     .writeln(&format!("kelora: Config file error: {}", e))
@@ -636,21 +987,24 @@ pub fn process_args_with_config(stderr: &mut SafeStderr) -> (ArgMatches, Cli, Co
     } else {
         // Load config file and process aliases
         match ConfigFile::load_with_custom_path(config_file_path.as_deref()) {
-            Ok((config_file, loaded_path)) => match config_file.process_args(raw_args) {
-                Ok((processed, mut info)) => {
-                    info.loaded_config_path = loaded_path;
-                    (processed, info)
-                }
-                Err(e) => {
-                    stderr
-                        .writeln(&format!("kelora: Config error: {}", e))
-                        .unwrap_or(());
-                    // A malformed config file is invalid usage (like a bad flag or
-                    // value), not a runtime failure — exit 2 to match the other
-                    // configuration-validation paths and the --help exit-code table.
-                    ExitCode::InvalidUsage.exit();
+            Ok((config_file, loaded_path)) => {
+                report_unknown_config_entries(&config_file, strict_config, stderr);
+                match config_file.process_args(raw_args) {
+                    Ok((processed, mut info)) => {
+                        info.loaded_config_path = loaded_path;
+                        (processed, info)
+                    }
+                    Err(e) => {
+                        stderr
+                            .writeln(&format!("kelora: Config error: {}", e))
+                            .unwrap_or(());
+                        // A malformed config file is invalid usage (like a bad flag or
+                        // value), not a runtime failure — exit 2 to match the other
+                        // configuration-validation paths and the --help exit-code table.
+                        ExitCode::InvalidUsage.exit();
+                    }
                 }
-            },
+            }
             Err(e) => {
                 stderr
                     .writeln(&format!("kelora: Config file error: {}", e))
@@ -712,7 +1066,12 @@ pub fn process_args_with_config(stderr: &mut SafeStderr) -> (ArgMatches, Cli, Co
     // - no input files are provided
     // - --no-input is not specified
     // - no other arguments are provided (just the program name)
-    if crate::tty::is_stdin_tty() && cli.files.is_empty() && !cli.no_input {
+    if crate::tty::is_stdin_tty()
+        && cli.files.is_empty()
+        && !cli.no_input
+        && cli.follow_glob.is_none()
+        && cli.input_cmds.is_empty()
+    {
         // Check if this is truly no arguments (interactive mode) or just missing input files
         let raw_args: Vec<String> = std::env::args().collect();
 
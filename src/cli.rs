@@ -1,7 +1,9 @@
 // CLI-specific types and structures
 // This module contains the command-line interface definitions and parsing logic
 
-use crate::config::{MultilineJoin, ScriptStageType};
+use crate::config::{
+    CollisionPolicy, DupKeyPolicy, EveryClock, MultilineJoin, ScriptStageType, SpanOutput,
+};
 use anyhow::Result;
 use clap::{ArgMatches, Parser};
 
@@ -16,11 +18,13 @@ pub enum InputFormat {
     Logfmt,
     Syslog,
     Cef,
+    Leef,
     Csv,
     Tsv,
     Csvnh,
     Tsvnh,
     Combined,
+    Haproxy,
     Cols,
     Regex,
 }
@@ -39,6 +43,58 @@ pub enum OutputFormat {
     Tsv,
     Csvnh,
     Tsvnh,
+    Ecs,
+    FluentdJson,
+    FluentdForward,
+    Table,
+    Orc,
+    Cbor,
+    ApacheAccess,
+    NginxAccess,
+    Xml,
+    /// Push metrics to a Prometheus Pushgateway instead of printing events;
+    /// shorthand for --metrics=prometheus with default endpoint and job.
+    PrometheusPushgateway,
+}
+
+/// Elastic Common Schema version stamped by `-F ecs`. The mapped fields are
+/// the same in both versions, so only `ecs.version` differs.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EcsVersion {
+    #[value(name = "1")]
+    V1,
+    #[default]
+    #[value(name = "8")]
+    V8,
+}
+
+/// Named palette for colored output (`--color-theme`).
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorTheme {
+    #[default]
+    Default,
+    Solarized,
+    Mono,
+    Highcontrast,
+}
+
+/// Part of default output that `--color-parts` can color.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorPart {
+    /// Field names
+    Keys,
+    /// Field values other than levels, incl. --color-json-values highlighting
+    Values,
+    /// Values of level fields
+    Levels,
+    /// Context markers of -A/-B/-C
+    Context,
+}
+
+/// Output order restored by `--parallel-order-by`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParallelOrderBy {
+    Timestamp,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -56,6 +112,8 @@ pub enum MetricsFormat {
     /// Tab-separated record stream (one `metric<TAB>key<TAB>value` row per line,
     /// sorted by count/score descending) for piping to head/tail/sort/awk.
     Tsv,
+    /// Prometheus text exposition format (one gauge per metric)
+    Prometheus,
     /// Resolve at output time: the human report on a terminal, `tsv` when piped
     /// or redirected. The default for `-m` and `--freq`/`--describe`. Hidden
     /// because it is the implicit default rather than something to type.
@@ -69,12 +127,132 @@ pub enum StatsFormat {
     Json,
 }
 
+/// Where an end-of-run report (`--stats`, `--metrics`) is written
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReportRoute {
+    Stdout,
+    Stderr,
+    /// `file:PATH`; the file is replaced when the run ends
+    File(String),
+}
+
+/// `--stats[=FORMAT][,DEST]`
+#[derive(Clone, Debug)]
+pub struct StatsSpec {
+    pub format: StatsFormat,
+    pub route: Option<ReportRoute>,
+}
+
+/// `--metrics[=FORMAT][,DEST]`
+#[derive(Clone, Debug)]
+pub struct MetricsSpec {
+    pub format: MetricsFormat,
+    pub route: Option<ReportRoute>,
+}
+
+/// Report format for `kelora rhai-lint` and `--rhai-lint`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LintFormat {
+    /// `source:line:column: kind error: message` lines and a summary
+    #[default]
+    Text,
+    /// One JSON report, for CI
+    Json,
+}
+
+/// What --check-monotonic does with an event whose timestamp goes backwards
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MonotonicCheck {
+    /// Keep the event and set `_ts_regression = true` on it
+    #[default]
+    Flag,
+    /// Drop the event
+    Drop,
+}
+
+/// How --group-aggregate-numeric combines a numeric field across a group
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupAggregate {
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
 #[derive(clap::ValueEnum, Clone, Debug)]
 pub enum DiscoverFieldsFormat {
     Table,
     Json,
 }
 
+/// Values of --field-prefix-conflict, the older spelling of --collision
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldPrefixConflict {
+    /// Same as --collision first
+    Skip,
+    /// Same as --collision last
+    Overwrite,
+}
+
+/// What --schema-validate does with an event that fails the schema
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SchemaOnFail {
+    /// Drop the event and keep going
+    #[default]
+    Skip,
+    /// Output the event anyway; the failure is still reported
+    Emit,
+    /// Stop the run at the first failing event
+    Halt,
+}
+
+/// What happens to an event when a --filter or --exec stage errors on it
+/// (--on-stage-error)
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StageErrorPolicy {
+    /// Drop the event
+    SkipEvent,
+    /// Pass the event as it was before the failing stage to the next stage
+    SkipStage,
+    /// Stop the run
+    Abort,
+}
+
+/// Unit --duration-unit renders parsed durations in
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DurationUnit {
+    Ns,
+    Us,
+    Ms,
+    S,
+    M,
+    H,
+}
+
+impl DurationUnit {
+    pub fn suffix(self) -> &'static str {
+        match self {
+            DurationUnit::Ns => "ns",
+            DurationUnit::Us => "us",
+            DurationUnit::Ms => "ms",
+            DurationUnit::S => "s",
+            DurationUnit::M => "m",
+            DurationUnit::H => "h",
+        }
+    }
+
+    pub fn nanos(self) -> f64 {
+        match self {
+            DurationUnit::Ns => 1.0,
+            DurationUnit::Us => 1_000.0,
+            DurationUnit::Ms => 1_000_000.0,
+            DurationUnit::S => 1_000_000_000.0,
+            DurationUnit::M => 60.0 * 1_000_000_000.0,
+            DurationUnit::H => 3_600.0 * 1_000_000_000.0,
+        }
+    }
+}
+
 #[derive(clap::ValueEnum, Clone, Debug, Default)]
 pub enum DrainFormat {
     #[default]
@@ -94,6 +272,28 @@ pub enum ShellCompletion {
     Elvish,
 }
 
+/// `kelora rhai-lint SCRIPT...` - check Rhai script files without running them.
+/// Dispatched before the main CLI is parsed, since kelora's positional
+/// arguments are input files.
+#[derive(Parser, Debug)]
+#[command(name = "kelora rhai-lint")]
+#[command(about = "Check Rhai script files for syntax and type errors without processing any logs")]
+#[command(after_help = "Exit codes: 0 when every script is clean, 1 on any error.")]
+pub struct RhaiLintCli {
+    /// Script files to check
+    #[arg(value_name = "SCRIPT", required = true)]
+    pub scripts: Vec<String>,
+
+    /// Report format (text or json)
+    #[arg(
+        long = "lint-format",
+        value_enum,
+        value_name = "FORMAT",
+        default_value = "text"
+    )]
+    pub lint_format: LintFormat,
+}
+
 // CLI structure - contains all command-line arguments and options
 #[derive(Parser)]
 #[command(name = "kelora")]
@@ -130,10 +330,14 @@ pub struct Cli {
     #[arg(long = "no-input", help_heading = "Input Options")]
     pub no_input: bool,
 
-    /// Input format. Available formats: auto (default), auto-per-file, json, line, raw, logfmt, syslog, cef, csv, tsv, csvnh, tsvnh, combined, cols:<spec>, regex:<pattern>.
+    /// Input format. Available formats: auto (default), auto-per-file, json, line, raw, logfmt, syslog, cef, leef, csv, tsv, csvnh, tsvnh, combined, haproxy, ssv, netflow5, netflow9, ipfix, pcap, xml-records, sqlite, cols:<spec>, regex:<pattern>, fixed-width:<spec>.
     /// With 'auto', the format is detected from the first non-empty line and applied to every line; for files that mix formats use a cascade (below) instead.
     /// Use cols:<spec> for column parsing, regex:<pattern> for regex parsing with named groups, and csv/tsv with optional type annotations.
-    /// Built-in application-log formats: cri (Kubernetes container logs) plus glog, nginx-error, apache-error, log4j, python-logging, redis, s3, haproxy, iso8601-level (adapted from lnav). Select with -f <name>; most are also recognized by auto-detection. See --help-formats.
+    /// Use ssv for space-aligned command output (names from --ssv-header) and fixed-width:FIELD:start:end,... (or fixed:start-end:FIELD,...) for fixed character columns.
+    /// Use pcap for a libpcap capture; each HTTP/1.x request/response pair in its TCP streams becomes one event.
+    /// Use xml-records for an XML document whose root holds one element per record (see --xml-record-element).
+    /// Use sqlite to read the rows of a SQLite database file (with --sqlite-table or --sqlite-query).
+    /// Built-in application-log formats: cri (Kubernetes container logs) plus glog, nginx-error, apache-error, log4j, python-logging, redis, s3, iso8601-level (adapted from lnav). Select with -f <name>; most are also recognized by auto-detection. See --help-formats.
     /// Cascade mode: pass a comma-separated list (e.g. 'json,logfmt,line') to try each parser in order; the first success wins, so put catch-all fallbacks like 'line' or 'raw' last. Adds an '_format' field to each event.
     /// Repeat -f to build a cascade that includes spec-based parsers: -f json -f 'cols:ts(2) level *msg'. Each -f is tried in order; put catch-alls ('line', 'raw', 'cols:') last (regex declines non-matching lines, so it can sit earlier).
     /// Examples: -f json, -f json,line, -f json -f 'cols:ts level *msg', -f 'regex:(?P<code:int>\\d+) (?P<msg>.*)', -f 'csv status:int bytes:int'.
@@ -152,6 +356,27 @@ pub struct Cli {
     #[arg(short = 'j', help_heading = "Input Options", conflicts_with = "format")]
     pub json_input: bool,
 
+    /// Retry lines the input format rejects with this format instead of counting parse
+    /// errors, e.g. --fallback-format line for JSON logs with stray plain-text lines.
+    /// Fallback events get _fallback=true; lines both formats reject are still errors.
+    #[arg(
+        long = "fallback-format",
+        value_name = "FORMAT",
+        help_heading = "Input Options",
+        value_parser = parse_format_value
+    )]
+    pub fallback_format: Option<String>,
+
+    /// Detect the input format from the first N non-empty lines instead of the first one.
+    /// Banner and comment lines (#, //, --, ;, rules like =====) are skipped and the format most lines agree on wins. Also samples stdin, waiting for N lines or end of input.
+    #[arg(
+        long = "detect-lines",
+        visible_alias = "sample-lines",
+        value_name = "N",
+        help_heading = "Input Options"
+    )]
+    pub detect_lines: Option<usize>,
+
     /// File processing order.
     #[arg(
         long = "file-order",
@@ -166,6 +391,67 @@ pub struct Cli {
     #[arg(long = "merge-sorted", help_heading = "Input Options")]
     pub merge_ts: bool,
 
+    /// Follow every file matching a glob, picking up new files as they appear (e.g. 'app-*.log' rotated by name). Runs until interrupted.
+    #[arg(
+        long = "follow-glob",
+        value_name = "PATTERN",
+        help_heading = "Input Options"
+    )]
+    pub follow_glob: Option<String>,
+
+    /// Read input from the stdout of a shell command, with meta.filename set to the command (repeatable).
+    #[arg(
+        long = "input-cmd",
+        value_name = "COMMAND",
+        action = clap::ArgAction::Append,
+        help_heading = "Input Options",
+        help = "Run COMMAND through the shell and read its stdout as input, with meta.filename set to the command string (repeatable).\n\nSeveral commands are read concurrently and their lines interleave as they arrive. Lines the command writes to stderr are shown as warnings. The command runs with your privileges, exactly as typed, so only pass commands you would run yourself."
+    )]
+    pub input_cmds: Vec<String>,
+
+    /// Restart --input-cmd commands with backoff whenever they exit; runs until interrupted.
+    #[arg(
+        long = "follow",
+        requires = "input_cmds",
+        help_heading = "Input Options"
+    )]
+    pub follow: bool,
+
+    /// Read buffer size per input file (default 256KiB).
+    #[arg(
+        long = "read-buffer",
+        value_name = "SIZE",
+        help_heading = "Input Options",
+        help = "Size of the read buffer used for each input file and stdin (default 256KiB).\n\nLarger buffers mean fewer read syscalls on fast local disks; smaller ones lower memory use when many inputs are open. Accepts a byte count or an IEC/SI suffix (1MB, 4MiB, 65536)."
+    )]
+    pub read_buffer: Option<String>,
+
+    /// Memory-map regular input files instead of reading them through a buffer.
+    #[arg(
+        long = "mmap",
+        help_heading = "Input Options",
+        help = "Memory-map regular input files instead of copying them through a read buffer.\n\nLines are located directly in the page cache and copied out one at a time, skipping the read syscalls and the read buffer. Each line is still copied once, also under --parallel, whose batches carry owned lines. Output is identical to buffered reading. Stdin, compressed files, and binary capture formats are read as usual, as is everything on 32-bit platforms. A file that grows while it is read switches to buffered reads for the appended part. Do not truncate a file while it is mapped."
+    )]
+    pub mmap: bool,
+
+    /// Record every raw input line, with its source file, to a gzip file for --pipeline-replay
+    #[arg(
+        long = "pipeline-record",
+        value_name = "FILE",
+        help_heading = "Input Options",
+        conflicts_with_all = ["pipeline_replay", "follow_glob", "merge_ts", "input_cmds"]
+    )]
+    pub pipeline_record: Option<std::path::PathBuf>,
+
+    /// Replay input saved with --pipeline-record, in the original order, instead of reading files or stdin
+    #[arg(
+        long = "pipeline-replay",
+        value_name = "FILE",
+        help_heading = "Input Options",
+        conflicts_with_all = ["files", "no_input", "follow_glob", "merge_ts", "input_cmds"]
+    )]
+    pub pipeline_replay: Option<std::path::PathBuf>,
+
     /// Skip the first N input lines.
     #[arg(long = "skip-lines", value_name = "N", help_heading = "Input Options")]
     pub skip_lines: Option<usize>,
@@ -220,21 +506,41 @@ pub struct Cli {
     )]
     pub max_sections: i64,
 
-    /// Keep only input lines matching this regex pattern (applied before ignore-lines)
+    /// Keep only input lines matching any of these regex patterns (repeatable)
     #[arg(
         long = "keep-lines",
         value_name = "REGEX",
+        action = clap::ArgAction::Append,
+        help_heading = "Input Options"
+    )]
+    pub keep_lines: Vec<String>,
+
+    /// Read --keep-lines patterns from a file, one per line (# comments allowed)
+    #[arg(
+        long = "keep-lines-file",
+        value_name = "FILE",
+        action = clap::ArgAction::Append,
         help_heading = "Input Options"
     )]
-    pub keep_lines: Option<String>,
+    pub keep_lines_file: Vec<String>,
 
-    /// Ignore input lines matching this regex pattern.
+    /// Ignore input lines matching any of these regex patterns (repeatable; wins over --keep-lines)
     #[arg(
         long = "ignore-lines",
         value_name = "REGEX",
+        action = clap::ArgAction::Append,
+        help_heading = "Input Options"
+    )]
+    pub ignore_lines: Vec<String>,
+
+    /// Read --ignore-lines patterns from a file, one per line (# comments allowed)
+    #[arg(
+        long = "ignore-lines-file",
+        value_name = "FILE",
+        action = clap::ArgAction::Append,
         help_heading = "Input Options"
     )]
-    pub ignore_lines: Option<String>,
+    pub ignore_lines_file: Vec<String>,
 
     /// Custom timestamp field name for parsing.
     #[arg(
@@ -255,9 +561,34 @@ pub struct Cli {
     /// Assume timezone for input timestamps without timezone info (default: UTC).
     /// Use 'local' for system local time.
     /// Examples: 'Europe/Berlin', 'local', 'UTC'.
-    #[arg(long = "input-tz", value_name = "TZ", help_heading = "Input Options")]
+    #[arg(
+        long = "input-tz",
+        visible_alias = "assume-tz",
+        value_name = "TZ",
+        help_heading = "Input Options"
+    )]
     pub input_tz: Option<String>,
 
+    /// Year for timestamps that carry none (RFC3164 syslog 'Jan 10 12:00:00').
+    /// Default: infer the year closest to now that is not in the future.
+    #[arg(
+        long = "assume-year",
+        value_name = "YEAR",
+        value_parser = clap::value_parser!(i32).range(1..=9999),
+        help_heading = "Input Options"
+    )]
+    pub assume_year: Option<i32>,
+
+    /// Read the timezone for naive timestamps from this event field, per event.
+    /// Accepts offsets ('+0200', '-05:00') and IANA names ('Europe/Berlin');
+    /// falls back to --input-tz when the field is missing or not a valid zone.
+    #[arg(
+        long = "tz-field",
+        value_name = "FIELD",
+        help_heading = "Input Options"
+    )]
+    pub tz_field: Option<String>,
+
     /// Multi-line event detection strategy. Supply values like `timestamp`,
     /// `timestamp:format=%Y-%m-%d %H-%M-%S`, `regex:match=^START`, or
     /// `regex:match=^START:end=^END$`. See `kelora --help-multiline` for details.
@@ -279,6 +610,37 @@ pub struct Cli {
     )]
     pub multiline_join: MultilineJoin,
 
+    /// Frame -f raw input on separator lines matching REGEX instead of newlines.
+    #[arg(
+        long = "raw-separator",
+        value_name = "REGEX",
+        help_heading = "Input Options",
+        conflicts_with_all = ["multiline", "raw_bytes"],
+        help = "Frame -f raw input on separator lines matching REGEX (e.g. '^=====$').\n\nThe lines between two separators become one event whose `raw` field keeps their newlines; separator lines themselves are dropped."
+    )]
+    pub raw_separator: Option<String>,
+
+    /// Frame -f raw input into fixed-size records of SIZE bytes.
+    #[arg(
+        long = "raw-bytes",
+        value_name = "SIZE",
+        help_heading = "Input Options",
+        conflicts_with = "multiline",
+        help = "Frame -f raw input into records of SIZE bytes, ignoring line boundaries.\n\nNewlines count as one byte and stay inside the record; the last record holds whatever is left. A cut never splits a UTF-8 character. Accepts a byte count or an IEC/SI suffix (512, 4KiB)."
+    )]
+    pub raw_bytes: Option<String>,
+
+    /// Read records separated by blank lines or separator lines matching REGEX.
+    #[arg(
+        long = "record-separator",
+        visible_alias = "input-record-separator",
+        value_name = "blank|REGEX",
+        help_heading = "Input Options",
+        conflicts_with_all = ["multiline", "raw_separator", "raw_bytes"],
+        help = "Read records that span several lines, separated by blank lines (`blank`) or by separator lines matching REGEX (e.g. '^---$').\n\nThe lines between two separators, joined with newlines, are passed to the parser as one record, so `-f json` reads pretty-printed objects. Separator lines are dropped and back-to-back separators produce no empty records. Works with every input format; pass -f explicitly, as auto-detection only sees the first line."
+    )]
+    pub record_separator: Option<String>,
+
     /// Extract text before separator to specified field (runs before parsing)
     #[arg(
         long = "extract-prefix",
@@ -296,10 +658,112 @@ pub struct Cli {
     )]
     pub prefix_sep: String,
 
+    /// Strip a fixed-width prefix of N characters from each line before
+    /// parsing. Lines shorter than N are left as they are.
+    #[arg(
+        long = "strip-prefix",
+        value_name = "N",
+        help_heading = "Input Options",
+        conflicts_with_all = ["extract_prefix", "strip_prefix_re"]
+    )]
+    pub strip_prefix: Option<usize>,
+
+    /// Strip the text matched by REGEX at the start of each line before
+    /// parsing, e.g. '^\S+\s+' for a container-name prefix. Lines where it
+    /// does not match at the start are left as they are.
+    #[arg(
+        long = "strip-prefix-re",
+        value_name = "REGEX",
+        help_heading = "Input Options",
+        conflicts_with = "extract_prefix"
+    )]
+    pub strip_prefix_re: Option<String>,
+
+    /// Store the prefix removed by --strip-prefix/--strip-prefix-re in FIELD
+    /// (trimmed; the first capture group of the regex, if it has one)
+    #[arg(
+        long = "prefix-field",
+        value_name = "FIELD",
+        help_heading = "Input Options"
+    )]
+    pub prefix_field: Option<String>,
+
     /// Column separator for cols:<spec> format (default: whitespace)
     #[arg(long = "cols-sep", value_name = "SEP", help_heading = "Input Options")]
     pub cols_sep: Option<String>,
 
+    /// Column names for ssv format (comma-separated, `name:type` allowed); the
+    /// last column takes the rest of the line. Default: c1, c2, ...
+    #[arg(
+        long = "ssv-header",
+        value_name = "COLS",
+        value_delimiter = ',',
+        help_heading = "Input Options"
+    )]
+    pub ssv_header: Option<Vec<String>>,
+
+    /// Root element name for -f xml-records; other roots are an error. Default: any
+    #[arg(
+        long = "xml-root-element",
+        value_name = "ELEM",
+        help_heading = "Input Options"
+    )]
+    pub xml_root_element: Option<String>,
+
+    /// Record element name for -f xml-records; other children of the root are
+    /// skipped. Default: every child of the root is a record
+    #[arg(
+        long = "xml-record-element",
+        value_name = "ELEM",
+        help_heading = "Input Options"
+    )]
+    pub xml_record_element: Option<String>,
+
+    /// Table to read with -f sqlite, one event per row
+    #[arg(
+        long = "sqlite-table",
+        value_name = "TABLE",
+        conflicts_with = "sqlite_query",
+        help_heading = "Input Options"
+    )]
+    pub sqlite_table: Option<String>,
+
+    /// Query to run with -f sqlite instead of reading a whole table, one
+    /// event per result row
+    #[arg(
+        long = "sqlite-query",
+        value_name = "SQL",
+        help_heading = "Input Options"
+    )]
+    pub sqlite_query: Option<String>,
+
+    /// Keep the unparsed remainder of each line in a `_rest` field (logfmt,
+    /// regex and cols formats)
+    #[arg(long = "keep-rest", help_heading = "Input Options")]
+    pub keep_rest: bool,
+
+    /// Keep JSON numbers as their decimal text instead of int/float values
+    /// (protects 19-digit IDs from float rounding in scripts and output)
+    #[arg(long = "json-numbers-as-strings", help_heading = "Input Options")]
+    pub json_numbers_as_strings: bool,
+
+    /// Accept only JSON objects as events; top-level arrays and scalars
+    /// become parse errors instead of `{"value": ...}` events (json format)
+    #[arg(long = "strict-json", help_heading = "Input Options")]
+    pub strict_json: bool,
+
+    /// What to do with a key repeated within one json or logfmt event: keep
+    /// the first or last value, collect all values into an array, or treat
+    /// the event as a parse error
+    #[arg(
+        long = "dup-keys",
+        value_enum,
+        value_name = "POLICY",
+        default_value_t = DupKeyPolicy::Last,
+        help_heading = "Input Options"
+    )]
+    pub dup_keys: DupKeyPolicy,
+
     /// Pre-run a Rhai script before any other stage runs.
     #[arg(
         long = "begin",
@@ -335,6 +799,109 @@ pub struct Cli {
     )]
     pub exec_files: Vec<String>,
 
+    /// Name the next --exec/--exec-file stage in the --pipeline-profile report.
+    #[arg(
+        long = "stage-label",
+        value_name = "NAME",
+        help_heading = "Processing Options"
+    )]
+    pub stage_labels: Vec<String>,
+
+    /// Trace the first N parsed events through every stage instead of
+    /// producing normal output. Script side effects are suppressed.
+    #[arg(
+        long = "sample-run",
+        value_name = "N",
+        help_heading = "Processing Options"
+    )]
+    pub sample_run: Option<usize>,
+
+    /// Compare the --left and --right file sets and emit only the events one
+    /// side has, tagged with `_diff_side` ("left" or "right"). Both sides go
+    /// through the same parsing and script stages.
+    #[arg(
+        long = "diff-output",
+        visible_alias = "diff",
+        conflicts_with = "files",
+        requires = "diff_left",
+        requires = "diff_right",
+        help_heading = "Processing Options"
+    )]
+    pub diff_output: bool,
+
+    /// Files on the left side of --diff-output
+    #[arg(
+        long = "left",
+        value_name = "FILE",
+        num_args = 1..,
+        requires = "diff_output",
+        help_heading = "Processing Options"
+    )]
+    pub diff_left: Vec<String>,
+
+    /// Files on the right side of --diff-output
+    #[arg(
+        long = "right",
+        value_name = "FILE",
+        num_args = 1..,
+        requires = "diff_output",
+        help_heading = "Processing Options"
+    )]
+    pub diff_right: Vec<String>,
+
+    /// Compare --diff-output events on these fields only (comma-separated)
+    /// instead of their full content
+    #[arg(
+        long = "diff-key",
+        value_name = "FIELDS",
+        value_delimiter = ',',
+        requires = "diff_output",
+        help_heading = "Processing Options"
+    )]
+    pub diff_key: Option<Vec<String>>,
+
+    /// Also emit events both --diff-output sides have, tagged
+    /// `_diff_side: "both"`
+    #[arg(
+        long = "diff-show-common",
+        requires = "diff_output",
+        help_heading = "Processing Options"
+    )]
+    pub diff_show_common: bool,
+
+    /// Count events per --diff-by key in the input and in these baseline
+    /// files, and report the keys only one side has or whose count changed
+    /// by more than --diff-threshold
+    #[arg(
+        long = "diff-against",
+        value_name = "FILE",
+        num_args = 1..,
+        requires = "diff_by",
+        conflicts_with = "diff_output",
+        help_heading = "Processing Options"
+    )]
+    pub diff_against: Vec<String>,
+
+    /// Rhai expression grouping --diff-against events, e.g.
+    /// 'e.msg.replace_regex("\\d+", "N")'; events where it returns () are skipped
+    #[arg(
+        long = "diff-by",
+        value_name = "EXPR",
+        requires = "diff_against",
+        help_heading = "Processing Options"
+    )]
+    pub diff_by: Option<String>,
+
+    /// Report --diff-against keys whose count changed by more than PCT percent
+    /// (default 50)
+    #[arg(
+        long = "diff-threshold",
+        value_name = "PCT",
+        requires = "diff_against",
+        help_heading = "Processing Options"
+    )]
+    pub diff_threshold: Option<f64>,
+
     /// Assertion expressions that must evaluate to true. Violations are reported to stderr;
     /// processing continues unless --strict is enabled. See --help-rhai for expression syntax.
     #[arg(
@@ -356,14 +923,108 @@ pub struct Cli {
     #[arg(long = "end", value_name = "EXPR", help_heading = "Processing Options")]
     pub end: Option<String>,
 
+    /// Stop the --begin script after MS milliseconds of wall-clock time. The limit is
+    /// checked between script statements, so a blocking native call (a file read, a
+    /// network lookup) runs to completion before the script stops. The timeout is
+    /// recorded as an error; with --strict it aborts the run, otherwise processing goes on.
+    #[arg(
+        long = "begin-timeout-ms",
+        value_name = "MS",
+        help_heading = "Processing Options",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub begin_timeout_ms: Option<u64>,
+
+    /// Stop the --end script after MS milliseconds of wall-clock time. As with
+    /// --begin-timeout-ms, a blocking native call finishes before the script stops. The
+    /// timeout is recorded as an error; with --strict it aborts the run.
+    #[arg(
+        long = "end-timeout-ms",
+        value_name = "MS",
+        help_heading = "Processing Options",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub end_timeout_ms: Option<u64>,
+
+    /// Pass a value through to --begin/--end scripts as an entry of the `argv` array (repeatable).
+    #[arg(
+        long = "script-arg",
+        value_name = "VALUE",
+        help_heading = "Processing Options",
+        action = clap::ArgAction::Append
+    )]
+    pub script_args: Vec<String>,
+
+    /// Check the --begin/--filter/--exec/--assert/--end scripts for syntax and type errors, then exit without processing input.
+    #[arg(long = "rhai-lint", help_heading = "Processing Options")]
+    pub rhai_lint: bool,
+
+    /// Report format for --rhai-lint (text or json).
+    #[arg(
+        long = "lint-format",
+        value_enum,
+        value_name = "FORMAT",
+        default_value = "text",
+        requires = "rhai_lint",
+        help_heading = "Processing Options"
+    )]
+    pub lint_format: LintFormat,
+
     /// Allow Rhai scripts to create directories and write files on disk (required for file helpers like append_file or mkdir).
     #[arg(long = "allow-fs-writes", help_heading = "Processing Options")]
     pub allow_fs_writes: bool,
 
+    /// Run untrusted scripts safely: file helpers, read_file/read_lines, get_env and
+    /// module imports fail with a script error (even with --allow-fs-writes), and each
+    /// script run is capped at 1,000,000 operations and 1 second.
+    #[arg(long = "sandbox", help_heading = "Processing Options")]
+    pub sandbox: bool,
+
+    /// Register a Rhai function NAME(ip) that looks the address up in a MaxMind-format
+    /// .mmdb database and returns a map of country/city/asn fields (empty on a miss).
+    /// Repeatable, e.g. --mmdb geo=GeoLite2-City.mmdb --mmdb asn=GeoLite2-ASN.mmdb.
+    #[cfg(feature = "mmdb")]
+    #[arg(
+        long = "mmdb",
+        value_name = "NAME=PATH",
+        help_heading = "Processing Options",
+        action = clap::ArgAction::Append
+    )]
+    pub mmdb: Vec<String>,
+
     /// Enable access to a sliding window of N+1 recent events (needed for window_* functions).
     #[arg(long = "window", value_name = "N", help_heading = "Processing Options")]
     pub window_size: Option<usize>,
 
+    /// Emit one derived event per window position: each event is replaced by the map
+    /// --window-script returns for its window (e.g. a rolling average). Requires --window.
+    #[arg(
+        long = "window-emit-each",
+        help_heading = "Processing Options",
+        requires = "window_size"
+    )]
+    pub window_emit_each: bool,
+
+    /// Rhai expression run by --window-emit-each; reads `window` (current event first)
+    /// and returns a map, the event to emit.
+    #[arg(
+        long = "window-script",
+        value_name = "EXPR",
+        help_heading = "Processing Options",
+        requires = "window_emit_each",
+        conflicts_with = "window_emit_each_from"
+    )]
+    pub window_script: Option<String>,
+
+    /// Like --window-emit-each, with the --window-script read from a file.
+    #[arg(
+        long = "window-emit-each-from",
+        value_name = "FILE",
+        help_heading = "Processing Options",
+        requires = "window_size"
+    )]
+    pub window_emit_each_from: Option<String>,
+
     /// Aggregate events into fixed-size spans (count or duration) before running a span-close hook.
     #[arg(
         long = "span",
@@ -391,6 +1052,79 @@ pub struct Cli {
     )]
     pub span_close: Option<String>,
 
+    /// Which events span aggregation writes: member events, per-span summaries, or both.
+    #[arg(
+        long = "span-output",
+        value_enum,
+        default_value = "members",
+        value_name = "MODE",
+        help_heading = "Processing Options",
+        help = "What span aggregation emits: members (the events themselves, default), summary (one synthetic event per span with _span_id, _span_n, _span_first, _span_last, _span_duration_ms), or both. Summary events go through formatters and --take like normal events."
+    )]
+    pub span_output: SpanOutput,
+
+    /// Rhai snippet run on each span summary event (requires --span-output summary|both).
+    #[arg(
+        long = "span-summary",
+        value_name = "EXPR",
+        help_heading = "Processing Options",
+        help = "Run a Rhai snippet on each span summary event before it is emitted, e.g. --span-summary 'e.total_bytes = span_sum(\"bytes\")'. Aggregate the span's members with span_sum, span_min, span_max, span_count, and span_values. Requires --span-output summary or both."
+    )]
+    pub span_summary: Option<String>,
+
+    /// Emit a marker event between consecutive spans.
+    #[arg(
+        long = "span-markers",
+        visible_alias = "emit-on-span-boundary",
+        help_heading = "Processing Options",
+        help = "Emit a marker event each time a span closes and the next one opens, so downstream tools can tell buckets apart. Markers carry _span_marker=true, span_id, and for time and idle spans span_start and span_end. No marker follows the last span."
+    )]
+    pub span_markers: bool,
+
+    /// Roll tracked metrics up into tumbling windows of DURATION.
+    #[arg(
+        long = "every",
+        value_name = "DURATION",
+        conflicts_with_all = ["span", "span_idle"],
+        help_heading = "Processing Options",
+        help = "Roll tracked metrics up into tumbling windows of DURATION (e.g. 1m, 1h).\n\nWhen a window closes, its metrics are emitted and reset: as one summary event (_window_start, _window_end, _window_events, _window_late plus each metric) or by running --every-emit. Windows follow event timestamps unless --every-clock wall is set. --end and --metrics still see totals for the whole run. Sequential mode only."
+    )]
+    pub every: Option<String>,
+
+    /// Rhai snippet run when an --every window closes, instead of emitting a
+    /// summary event. `metrics` holds the window's metrics; `window.start`,
+    /// `window.end`, `window.events` and `window.late` describe it.
+    #[arg(
+        long = "every-emit",
+        value_name = "EXPR",
+        requires = "every",
+        help_heading = "Processing Options"
+    )]
+    pub every_emit: Option<String>,
+
+    /// Clock that --every windows follow: event (timestamps, default) or wall
+    /// (arrival time).
+    #[arg(
+        long = "every-clock",
+        value_enum,
+        value_name = "CLOCK",
+        default_value = "event",
+        requires = "every",
+        help_heading = "Processing Options"
+    )]
+    pub every_clock: EveryClock,
+
+    /// Keep an --every window open for DURATION past its end for
+    /// out-of-order events. Events for an already closed window are counted
+    /// as late and only reach the run totals.
+    #[arg(
+        long = "every-allowed-lateness",
+        value_name = "DURATION",
+        requires = "every",
+        help_heading = "Processing Options"
+    )]
+    pub every_allowed_lateness: Option<String>,
+
     /// Exit on first error (fail-fast behavior). Use --no-strict to force resilient mode, overriding a config default.
     #[arg(long = "strict", help_heading = "Error Handling")]
     pub strict: bool,
@@ -404,6 +1138,52 @@ pub struct Cli {
     )]
     pub no_strict: bool,
 
+    /// What a --filter/--exec error does to the event: skip-event drops it,
+    /// skip-stage passes it on unchanged to the next stage, abort stops the
+    /// run. Without it, a filter error drops the event and an exec error
+    /// rolls back just that stage (--strict: abort).
+    #[arg(
+        long = "on-stage-error",
+        value_enum,
+        value_name = "POLICY",
+        help_heading = "Error Handling"
+    )]
+    pub on_stage_error: Option<StageErrorPolicy>,
+
+    /// Keep up to K distinct failing lines per error category and show them, with
+    /// line numbers, in the error summary. Lines that differ only in numbers or
+    /// IDs count as one sample.
+    #[arg(
+        long = "on-parse-error-sample",
+        value_name = "K",
+        help_heading = "Error Handling"
+    )]
+    pub on_parse_error_sample: Option<usize>,
+
+    /// Keep only file and line number in the error samples written by
+    /// --metrics=json and --metrics-file; drop the original line and message.
+    #[arg(long = "redact-error-samples", help_heading = "Error Handling")]
+    pub redact_error_samples: bool,
+
+    /// Run a Rhai script for every parse, filter, exec or assert error. The script sees
+    /// error_type, error_message, raw_line, line_num and filename; its own errors are
+    /// reported and ignored.
+    #[arg(
+        long = "exec-on-error",
+        value_name = "SCRIPT",
+        help_heading = "Error Handling",
+        conflicts_with = "exec_on_error_from"
+    )]
+    pub exec_on_error: Option<String>,
+
+    /// Read the --exec-on-error script from a file.
+    #[arg(
+        long = "exec-on-error-from",
+        value_name = "FILE",
+        help_heading = "Error Handling"
+    )]
+    pub exec_on_error_from: Option<String>,
+
     /// Abort on invalid UTF-8 instead of decoding losslessly.
     #[arg(
         long = "strict-utf8",
@@ -430,40 +1210,222 @@ pub struct Cli {
         short = 'l',
         long = "levels",
         help_heading = "Filtering Options",
-        help = "Include only events with these log levels (comma-separated, case-insensitive).\n\nUse comma-separated values for OR logic: --levels ERROR,WARN\nMultiple flags create sequential AND filters (advanced)."
+        help = "Include only events with these log levels (comma-separated, case-insensitive).\n\nUse comma-separated values for OR logic: --levels ERROR,WARN\nMultiple flags create sequential AND filters (advanced).\nEvents without a level field are dropped. With --level, an event must pass both filters."
     )]
     pub levels: Vec<String>,
 
     /// Exclude events with these log levels.
     #[arg(
-        short = 'L',
-        long = "exclude-levels",
-        help_heading = "Filtering Options",
-        help = "Exclude events with these log levels (comma-separated, case-insensitive)."
+        short = 'L',
+        long = "exclude-levels",
+        help_heading = "Filtering Options",
+        help = "Exclude events with these log levels (comma-separated, case-insensitive)."
+    )]
+    pub exclude_levels: Vec<String>,
+
+    /// Keep events at these normalized levels, before any script stage.
+    #[arg(
+        long = "level",
+        value_name = "LEVELS",
+        help_heading = "Filtering Options",
+        help = "Keep only events at these levels, checked before any script stage (comma-separated).\n\nLevels are normalized (WARNING, W and 40 all mean warn), so: --level error, --level warn+ (warn and above), --level info,debug.\nEvents without a recognizable level are kept unless --level-strict is set.\n\nCombines with --levels/--exclude-levels: an event must pass both. --level runs first and compares normalized levels; --levels then matches the raw level text and drops events without one."
+    )]
+    pub level: Option<String>,
+
+    /// Drop events without a recognizable level when --level is set.
+    #[arg(
+        long = "level-strict",
+        requires = "level",
+        help_heading = "Filtering Options"
+    )]
+    pub level_strict: bool,
+
+    /// Validate each event against the JSON Schema in FILE.
+    #[arg(
+        long = "schema-validate",
+        visible_alias = "schema",
+        value_name = "FILE",
+        help_heading = "Filtering Options",
+        help = "Validate each event against the JSON Schema in FILE, after filters and before --keys.\n\nEach failure is reported with the field and the schema rule it broke, and any failure makes the run exit 1. What happens to the failing event is set by --schema-on-fail."
+    )]
+    pub schema_validate: Option<String>,
+
+    /// What to do with an event that fails --schema-validate: skip (drop it,
+    /// the default), emit (output it anyway) or halt (stop the run).
+    /// --strict implies halt.
+    #[arg(
+        long = "schema-on-fail",
+        value_enum,
+        value_name = "ACTION",
+        default_value = "skip",
+        requires = "schema_validate",
+        help_heading = "Filtering Options"
+    )]
+    pub schema_on_fail: SchemaOnFail,
+
+    /// Output only specific fields.
+    #[arg(
+        short = 'k',
+        long = "keys",
+        value_delimiter = ',',
+        help_heading = "Filtering Options",
+        help = "Output only these fields (comma-separated list).",
+        long_help = "Output only these fields, in the order given (comma-separated list).\n\nNested values are selected by path: request.headers.user-agent walks maps, and numeric segments index arrays (items.0.id or items[0].id; negative indices count from the end). The selected value is output under the path as its field name. Write a literal dot in a key as \\. (labels.app\\.kubernetes\\.io/name). A top-level field whose name matches exactly wins over walking the path."
+    )]
+    pub keys: Vec<String>,
+
+    /// Exclude specific fields from output.
+    #[arg(
+        short = 'K',
+        long = "exclude-keys",
+        value_delimiter = ',',
+        help_heading = "Filtering Options",
+        help = "Exclude these fields from output (comma-separated list).",
+        long_help = "Exclude these fields from output (comma-separated list).\n\nAccepts the same paths as --keys, so request.headers.cookie drops just that header and leaves the rest of request in place."
+    )]
+    pub exclude_keys: Vec<String>,
+
+    /// Drop fields whose value is null, an empty string, or an empty
+    /// array/map before formatting.
+    #[arg(long = "suppress-empty-fields", help_heading = "Filtering Options")]
+    pub suppress_empty_fields: bool,
+
+    /// Drop fields whose value is null before formatting.
+    #[arg(long = "suppress-null-fields", help_heading = "Filtering Options")]
+    pub suppress_null_fields: bool,
+
+    /// Drop fields whose value is an empty string before formatting.
+    #[arg(
+        long = "suppress-empty-string-fields",
+        help_heading = "Filtering Options"
+    )]
+    pub suppress_empty_string_fields: bool,
+
+    /// Strip PREFIX from field names (e.g. log_level -> level). Repeatable;
+    /// the first matching prefix is stripped. Runs before script stages.
+    #[arg(
+        long = "field-prefix-drop",
+        value_name = "PREFIX",
+        help_heading = "Processing Options"
+    )]
+    pub field_prefix_drop: Vec<String>,
+
+    /// Older spelling of --collision: overwrite is --collision last, skip is
+    /// --collision first.
+    #[arg(
+        long = "field-prefix-conflict",
+        value_enum,
+        value_name = "MODE",
+        conflicts_with = "collision",
+        help_heading = "Processing Options"
+    )]
+    pub field_prefix_conflict: Option<FieldPrefixConflict>,
+
+    /// When --field-prefix-drop, --field-expand-json, --merge-fields or
+    /// --json-flat produce a field name that already exists: last (the new
+    /// value wins, default), first (the existing value stays), suffix (store
+    /// the new value as name_2, name_3, ...) or error (reject the event).
+    #[arg(
+        long = "collision",
+        visible_alias = "rename-on-collision",
+        value_enum,
+        value_name = "POLICY",
+        help_heading = "Processing Options"
+    )]
+    pub collision: Option<CollisionPolicy>,
+
+    /// Prepend PREFIX to every output field name. Runs after --keys, so
+    /// scripts and --keys use the unprefixed names.
+    #[arg(
+        long = "field-prefix-add",
+        value_name = "PREFIX",
+        help_heading = "Processing Options"
+    )]
+    pub field_prefix_add: Option<String>,
+
+    /// Parse a string FIELD holding a JSON object and promote its keys to
+    /// top-level fields. Repeatable; values that are not a JSON object are
+    /// left untouched. Runs before script stages.
+    #[arg(
+        long = "field-expand-json",
+        value_name = "FIELD",
+        help_heading = "Processing Options"
+    )]
+    pub field_expand_json: Vec<String>,
+
+    /// Prepend PREFIX to the keys promoted by --field-expand-json.
+    #[arg(
+        long = "field-expand-prefix",
+        value_name = "PREFIX",
+        requires = "field_expand_json",
+        help_heading = "Processing Options"
+    )]
+    pub field_expand_prefix: Option<String>,
+
+    /// Keep the JSON string field after --field-expand-json expands it.
+    #[arg(
+        long = "field-expand-keep-original",
+        requires = "field_expand_json",
+        help_heading = "Processing Options"
+    )]
+    pub field_expand_keep_original: bool,
+
+    /// Add the named captures of REGEX, matched against each input file's
+    /// path, as fields on every event read from that file. Runs before
+    /// script stages; events from stdin are left as they are.
+    #[arg(
+        long = "field-from-filename",
+        value_name = "REGEX",
+        help_heading = "Processing Options"
+    )]
+    pub field_from_filename: Option<String>,
+
+    /// Concatenate fields and quoted literals into a new field, e.g.
+    /// 'host+":"+port=endpoint'. Repeatable; missing fields contribute an
+    /// empty segment. Runs before script stages.
+    #[arg(
+        long = "merge-fields",
+        value_name = "SPEC",
+        help_heading = "Processing Options"
+    )]
+    pub merge_fields: Vec<String>,
+
+    /// Leave the --merge-fields target unset when any source field is missing.
+    #[arg(
+        long = "merge-fields-skip-missing",
+        requires = "merge_fields",
+        help_heading = "Processing Options"
     )]
-    pub exclude_levels: Vec<String>,
+    pub merge_fields_skip_missing: bool,
 
-    /// Output only specific fields.
+    /// Convert duration-like values in the --duration-fields fields into
+    /// durations that compare with to_duration(), e.g.
+    /// `e.took > to_duration("100ms")`. Strings such as "45ms" or "1m 30s"
+    /// and bare numbers (taken as seconds) are converted; other values are
+    /// left as they are. Output renders them back as they were written.
+    #[arg(long = "parse-durations", help_heading = "Processing Options")]
+    pub parse_durations: bool,
+
+    /// Fields --parse-durations converts (comma-separated; default
+    /// took,elapsed,duration,latency).
     #[arg(
-        short = 'k',
-        long = "keys",
+        long = "duration-fields",
+        value_name = "FIELDS",
         value_delimiter = ',',
-        help_heading = "Filtering Options",
-        help = "Output only these fields (comma-separated list).",
-        long_help = "Output only these fields, in the order given (comma-separated list).\n\nActs on whole top-level fields. Nested values that --discover prints as dotted or bracketed paths (e.g. api.queries, tags[]) are NOT selectable here — flatten them first, e.g. --exec 'e.val = e.get_path(\"api.queries\")' then -k val. (A top-level field whose literal name contains a dot is matched as-is.)"
+        requires = "parse_durations",
+        help_heading = "Processing Options"
     )]
-    pub keys: Vec<String>,
+    pub duration_fields: Vec<String>,
 
-    /// Exclude specific fields from output.
+    /// Render duration values in this unit instead of their original text
+    /// (e.g. --duration-unit ms prints 0.123 as 123ms).
     #[arg(
-        short = 'K',
-        long = "exclude-keys",
-        value_delimiter = ',',
-        help_heading = "Filtering Options",
-        help = "Exclude these fields from output (comma-separated list).",
-        long_help = "Exclude these fields from output (comma-separated list).\n\nActs on whole top-level fields; nested values (the dotted/bracketed paths shown by --discover) cannot be dropped here — remove them in an --exec stage instead."
+        long = "duration-unit",
+        value_enum,
+        value_name = "UNIT",
+        help_heading = "Output Options"
     )]
-    pub exclude_keys: Vec<String>,
+    pub duration_unit: Option<DurationUnit>,
 
     /// Start showing entries on or newer than the specified date.
     #[arg(
@@ -485,6 +1447,27 @@ pub struct Cli {
     )]
     pub until: Option<String>,
 
+    /// Flag (or with =drop, drop) events whose timestamp is earlier than the previous event's.
+    #[arg(
+        long = "check-monotonic",
+        value_name = "MODE",
+        require_equals = true,
+        num_args = 0..=1,
+        default_missing_value = "flag",
+        help_heading = "Filtering Options",
+        long_help = "Detect clock regressions: events whose timestamp is earlier than the event before them, as left by clock skew, corrupted logs or out-of-order merges. With --check-monotonic (or =flag) such events get `_ts_regression = true`; with --check-monotonic=drop they are dropped, and the reference point stays at the last event kept, so the output is in time order. Events without a timestamp pass through and are not compared. Regressions are counted in --stats. Checked in input order, before any script runs. Sequential mode only."
+    )]
+    pub check_monotonic: Option<MonotonicCheck>,
+
+    /// Skip input files whose names place them outside --since/--until.
+    #[arg(
+        long = "filename-ts-format",
+        value_name = "PATTERN",
+        help_heading = "Filtering Options",
+        help = "Read each input file's start time from its name and skip files outside --since/--until without opening them, keeping one extra file on each side. PATTERN matches the base name with strftime parts (%Y %y %m %d %H %M %S %s) and shell wildcards, e.g. 'app-%Y-%m-%d-%H.log*'. Use 'auto' to try common date layouts. Nothing is skipped unless every file name matches."
+    )]
+    pub filename_ts_format: Option<String>,
+
     /// Limit output to the first N events.
     #[arg(
         short = 'n',
@@ -494,6 +1477,84 @@ pub struct Cli {
     )]
     pub take: Option<usize>,
 
+    /// Emit events while EXPR is true; stop at the first event where it is false.
+    #[arg(
+        long = "take-while",
+        value_name = "EXPR",
+        help_heading = "Filtering Options",
+        long_help = "Emit events while the Rhai expression is true and stop the stream at the first event where it is false (e.g. --take-while 'e.level != \"ERROR\"'). Input reading stops at the cutoff. Evaluated after --filter and level filters, like --take. An error evaluating the expression ends the stream (or aborts with --strict)."
+    )]
+    pub take_while: Option<String>,
+
+    /// Skip leading events while EXPR is true; emit everything from the first event where it is false.
+    #[arg(
+        long = "drop-while",
+        value_name = "EXPR",
+        help_heading = "Filtering Options",
+        long_help = "Skip leading events while the Rhai expression is true and emit every event from the first one where it is false (e.g. --drop-while 'e.msg != \"ready\"'). The expression is not evaluated again after that point. Evaluated after --filter and level filters, before --take-while and --take."
+    )]
+    pub drop_while: Option<String>,
+
+    /// Output a uniform random sample of exactly N events (all of them if fewer).
+    #[arg(
+        long = "reservoir",
+        value_name = "N",
+        help_heading = "Filtering Options",
+        conflicts_with_all = ["span", "span_idle"],
+        long_help = "Output a uniform random sample of exactly N events (all of them if fewer), chosen with reservoir sampling so the stream length need not be known. Events are held until input ends and then written in their original order. Applied after all filters and scripts; --take then limits the sample. Set KELORA_SEED for a reproducible sample. Not supported with --span."
+    )]
+    pub reservoir: Option<usize>,
+
+    /// Keep at most one event per DURATION of event time (per --thin-by value).
+    #[arg(
+        long = "thin",
+        value_name = "DURATION",
+        help_heading = "Filtering Options",
+        conflicts_with_all = ["span", "span_idle"],
+        long_help = "Keep at most one event per DURATION of event time (e.g. 5m), for plotting or skimming long, dense logs. Intervals are aligned like --span windows and taken from each event's timestamp; events without one pass through. By default the first event of an interval wins; see --thin-keep. Applied after all filters and scripts, before --reservoir and --take. Expects input that is ordered across intervals (order within an interval does not matter). Sequential mode only; not supported with --span."
+    )]
+    pub thin: Option<String>,
+
+    /// Thin each value of FIELD separately (e.g. one event per host per interval).
+    #[arg(
+        long = "thin-by",
+        value_name = "FIELD",
+        requires = "thin",
+        help_heading = "Filtering Options"
+    )]
+    pub thin_by: Option<String>,
+
+    /// Which event represents a --thin interval: first (default), last,
+    /// min:FIELD or max:FIELD. All but first hold one candidate per open
+    /// interval until the next interval starts.
+    #[arg(
+        long = "thin-keep",
+        value_name = "MODE",
+        requires = "thin",
+        help_heading = "Filtering Options"
+    )]
+    pub thin_keep: Option<String>,
+
+    /// Merge runs of consecutive events with the same FIELD value into one.
+    #[arg(
+        long = "group-consecutive-by",
+        value_name = "FIELD",
+        help_heading = "Filtering Options",
+        conflicts_with_all = ["span", "span_idle"],
+        long_help = "Merge each run of consecutive events with the same FIELD value into one event, to collapse repeated log lines. The merged event keeps the first event's fields and adds `_group_count`, `_group_start_ts` and `_group_end_ts` (earliest and latest timestamp in the run, when events have one) and `_group_fields_varied` (fields whose value differed within the run). Events without FIELD end the current run and pass through unchanged. Applied after all filters and scripts; the last run is written when input ends. Sequential mode only; not supported with --span."
+    )]
+    pub group_consecutive_by: Option<String>,
+
+    /// Replace each numeric field of a --group-consecutive-by group with
+    /// the sum, avg, min or max of its values across the group.
+    #[arg(
+        long = "group-aggregate-numeric",
+        value_name = "FUNC",
+        requires = "group_consecutive_by",
+        help_heading = "Filtering Options"
+    )]
+    pub group_aggregate: Option<GroupAggregate>,
+
     /// Show N lines before each match (requires filtering)
     #[arg(
         short = 'B',
@@ -528,11 +1589,124 @@ pub struct Cli {
         value_enum,
         value_name = "FORMAT",
         default_value = "default",
-        help = "Output format.\n\nFormats:\n  default   Colored key-value output\n  json      JSON Lines (one object per line)\n  logfmt    Key=value pairs on one line\n  inspect   Debug view with type information\n  levelmap  Compact level timeline\n  keymap    First-character map for one selected field\n  tailmap   Percentile map for one numeric field\n  csv       Comma-separated with header row\n  tsv       Tab-separated with header row\n  csvnh     CSV without header row\n  tsvnh     TSV without header row\n\nSee --help-formats for requirements, extracted fields, and examples.",
+        help = "Output format.\n\nFormats:\n  default   Colored key-value output\n  json      JSON Lines (one object per line)\n  logfmt    Key=value pairs on one line\n  inspect   Debug view with type information\n  levelmap  Compact level timeline\n  keymap    First-character map for one selected field\n  tailmap   Percentile map for one numeric field\n  csv       Comma-separated with header row\n  tsv       Tab-separated with header row\n  csvnh     CSV without header row\n  tsvnh     TSV without header row\n  ecs       Elastic Common Schema JSON Lines\n  fluentd-json     Fluentd [tag, time, record] arrays, one per line\n  fluentd-forward  One Fluentd forward-mode batch at the end\n  table     Aligned columns with a header row\n  orc       Apache ORC columnar file (requires --output-file)\n  cbor      Length-prefixed CBOR maps, one per event (requires --output-file)\n  apache-access  Apache combined access log lines built from event fields\n  nginx-access   Combined access log lines plus \"request_time\"\n  xml       One <event> element per event, fields as child elements\n  prometheus-pushgateway  Push metrics to a Pushgateway instead of printing events\n\nSee --help-formats for requirements, extracted fields, and examples.",
         help_heading = "Output Options"
     )]
     pub output_format: OutputFormat,
 
+    /// Elastic Common Schema version stamped as ecs.version by -F ecs (1 or 8). Only the stamp changes: the fields kelora maps have the same ECS paths in both versions.
+    #[arg(
+        long = "ecs-version",
+        value_enum,
+        value_name = "VERSION",
+        default_value = "8",
+        help_heading = "Output Options"
+    )]
+    pub ecs_version: EcsVersion,
+
+    /// Fluentd tag for -F fluentd-json / fluentd-forward.
+    #[arg(
+        long = "fluentd-tag",
+        value_name = "TAG",
+        default_value = "kelora",
+        help_heading = "Output Options"
+    )]
+    pub fluentd_tag: String,
+
+    /// Batch output into JSON arrays of N events, one array per line (e.g. for bulk APIs).
+    #[arg(long = "chunk-size", value_name = "N", help_heading = "Output Options")]
+    pub chunk_size: Option<usize>,
+
+    /// Batch output into JSON arrays of at most B bytes; combines with --chunk-size (first limit wins).
+    #[arg(
+        long = "chunk-size-bytes",
+        value_name = "B",
+        help_heading = "Output Options"
+    )]
+    pub chunk_size_bytes: Option<usize>,
+
+    /// Print events as an aligned table with box borders (implies -F table).
+    #[arg(
+        long = "pretty-tables",
+        help_heading = "Output Options",
+        conflicts_with = "json_output"
+    )]
+    pub pretty_tables: bool,
+
+    /// Flatten nested maps and arrays into one level of JSON keys joined by SEP
+    /// (default '.'; array elements become indexed keys). Implies -F json.
+    #[arg(
+        long = "json-flat",
+        visible_alias = "output-jsonl-flat",
+        value_name = "SEP",
+        require_equals = true,
+        num_args = 0..=1,
+        default_missing_value = ".",
+        help_heading = "Output Options",
+        conflicts_with = "pretty_tables"
+    )]
+    pub json_flat: Option<char>,
+
+    /// Truncate table cells wider than N columns, marking the cut with '…' (-F table).
+    #[arg(
+        long = "max-field-length",
+        value_name = "N",
+        help_heading = "Output Options"
+    )]
+    pub max_field_length: Option<usize>,
+
+    /// Emit one event per element of array FIELD. Map elements are merged into
+    /// the parent; scalar elements are stored under FIELD (or --explode-as).
+    #[arg(
+        long = "explode",
+        value_name = "FIELD",
+        help_heading = "Output Options"
+    )]
+    pub explode: Option<String>,
+
+    /// Field name for scalar elements exploded by --explode (default: the array field).
+    #[arg(
+        long = "explode-as",
+        value_name = "FIELD",
+        requires = "explode",
+        help_heading = "Output Options"
+    )]
+    pub explode_as: Option<String>,
+
+    /// Keep events whose --explode array is empty instead of dropping them.
+    #[arg(
+        long = "explode-keep-empty",
+        requires = "explode",
+        help_heading = "Output Options"
+    )]
+    pub explode_keep_empty: bool,
+
+    /// Rows per ORC stripe for -F orc (default 10000). The column schema always comes from the first 100 events.
+    #[arg(
+        long = "orc-stripe-rows",
+        value_name = "N",
+        default_value_t = crate::formatters::DEFAULT_ORC_STRIPE_ROWS,
+        help_heading = "Output Options"
+    )]
+    pub orc_stripe_rows: usize,
+
+    /// Prefix each -F cbor record with its 4-byte big-endian length (default).
+    #[arg(
+        long = "cbor-framed",
+        help_heading = "Output Options",
+        overrides_with_all = ["cbor_unframed", "cbor_framed"]
+    )]
+    pub cbor_framed: bool,
+
+    /// Write -F cbor records back to back as a plain CBOR sequence, without
+    /// length prefixes.
+    #[arg(
+        long = "cbor-unframed",
+        help_heading = "Output Options",
+        overrides_with_all = ["cbor_framed", "cbor_unframed"]
+    )]
+    pub cbor_unframed: bool,
+
     /// Shortcut for -F json.
     #[arg(
         short = 'J',
@@ -554,6 +1728,26 @@ pub struct Cli {
     )]
     pub output_file: Option<String>,
 
+    /// POST events to an HTTP collector as JSON arrays instead of printing them.
+    #[arg(
+        long = "output-http",
+        value_name = "URL",
+        help_heading = "Output Options",
+        conflicts_with = "output_file",
+        help = "POST events to an HTTP collector instead of printing them.\n\nEvents are sent as JSON arrays of up to --chunk-size events (default 500)\nand --chunk-size-bytes bytes (default 1 MiB), one request per array.\nImplies -F json unless another -F is given. 429 and 5xx responses and\nconnection errors are retried with backoff (--output-http-retries); a batch\nthat still cannot be delivered stops the run with an error."
+    )]
+    pub output_http: Option<String>,
+
+    /// Retries per batch for --output-http before giving up (default 5).
+    #[arg(
+        long = "output-http-retries",
+        value_name = "N",
+        default_value_t = crate::config::HttpSinkConfig::DEFAULT_MAX_RETRIES,
+        requires = "output_http",
+        help_heading = "Output Options"
+    )]
+    pub output_http_retries: u32,
+
     /// Suppress events (formatter output)
     #[arg(short = 'q', long = "quiet", help_heading = "Output Options")]
     pub quiet: bool,
@@ -663,6 +1857,37 @@ pub struct Cli {
     )]
     pub format_timestamps_utc: bool,
 
+    /// Hide the leading timestamp field in default output.
+    /// Display-only - --since/--until and other timestamp features still use it.
+    #[arg(
+        long = "no-ts",
+        help_heading = "Default Format Options",
+        conflicts_with_all = ["ts_width", "show_key_for_ts"]
+    )]
+    pub no_ts: bool,
+
+    /// Pad or truncate the leading timestamp value to N characters in default output.
+    #[arg(
+        long = "ts-width",
+        value_name = "N",
+        help_heading = "Default Format Options"
+    )]
+    pub ts_width: Option<usize>,
+
+    /// Field shown as the leading timestamp in default output, e.g. received_at.
+    /// Display-only - extraction for filtering still follows --ts-field.
+    #[arg(
+        long = "ts-key",
+        value_name = "FIELD",
+        help_heading = "Default Format Options"
+    )]
+    pub ts_key: Option<String>,
+
+    /// Print the leading timestamp as key=value in -b/--brief output, which
+    /// otherwise shows bare values.
+    #[arg(long = "show-key-for-ts", help_heading = "Default Format Options")]
+    pub show_key_for_ts: bool,
+
     /// Force colored output.
     #[arg(long = "force-color", help_heading = "Display Options", overrides_with_all = ["no_color", "force_color"])]
     pub force_color: bool,
@@ -671,6 +1896,32 @@ pub struct Cli {
     #[arg(long = "no-color", help_heading = "Display Options", overrides_with_all = ["force_color", "no_color"])]
     pub no_color: bool,
 
+    /// Color palette for colored output (default, solarized, mono, highcontrast).
+    #[arg(
+        long = "color-theme",
+        value_enum,
+        value_name = "THEME",
+        default_value = "default",
+        help_heading = "Display Options"
+    )]
+    pub color_theme: ColorTheme,
+
+    /// Color only these parts of default output: keys, values, levels,
+    /// context (comma-separated; default all).
+    #[arg(
+        long = "color-parts",
+        value_enum,
+        value_name = "PARTS",
+        value_delimiter = ',',
+        help_heading = "Display Options"
+    )]
+    pub color_parts: Vec<ColorPart>,
+
+    /// Syntax-highlight field values holding JSON objects or arrays in default
+    /// and logfmt output.
+    #[arg(long = "color-json-values", help_heading = "Display Options")]
+    pub color_json_values: bool,
+
     /// Insert a centered marker when time gaps grow large.
     #[arg(
         long = "mark-gaps",
@@ -728,23 +1979,48 @@ pub struct Cli {
     )]
     pub batch_timeout: u64,
 
+    /// Reuse compiled scripts across pipeline rebuilds (always on with --parallel).
+    #[arg(long = "cache-scripts", help_heading = "Performance Options")]
+    pub cache_scripts: bool,
+
+    /// Report time spent in each script stage on stderr when processing ends.
+    #[arg(long = "pipeline-profile", help_heading = "Performance Options")]
+    pub pipeline_profile: bool,
+
+    /// Report cumulative time in every pipeline stage (parse, each script
+    /// stage, format, write) on stderr when processing ends.
+    #[arg(long = "benchmark", help_heading = "Performance Options")]
+    pub benchmark: bool,
+
     /// Disable ordered output.
     #[arg(long = "unordered", help_heading = "Performance Options")]
     pub no_preserve_order: bool,
 
-    /// Show stats only (implies -q/--quiet). Use -s for default (table), or --stats=FORMAT for explicit format.
+    /// With --unordered, buffer results in the sink to write them in
+    /// approximately chronological order. Events arriving later than the
+    /// buffer (4 x threads x batch size events) allows are written as they come.
+    #[arg(
+        long = "parallel-order-by",
+        value_name = "KEY",
+        value_enum,
+        requires = "no_preserve_order",
+        help_heading = "Performance Options"
+    )]
+    pub parallel_order_by: Option<ParallelOrderBy>,
+
+    /// Show stats only (implies -q/--quiet). Use -s for default (table), or --stats=FORMAT,DEST for explicit format and destination.
     #[arg(
         short = 's',
         long = "stats",
-        value_enum,
-        value_name = "FORMAT",
+        value_name = "FORMAT,DEST",
         require_equals = true,
         num_args = 0..=1,
         default_missing_value = "table",
+        value_parser = parse_stats_spec,
         help_heading = "Metrics and Stats",
-        help = "Show stats only (implies -q/--quiet).\n\nFormats: table, json\n\nExamples:\n  -s              Default table format\n  --stats=json    JSON output\n\nUse --no-stats to override a config default. Note the '=': --stats=json (a space, as in '-s json', is read as a filename)."
+        help = "Show stats when processing ends.\n\nFormats: table, json. Destinations: stdout, stderr, file:PATH. Give either or both, comma-separated.\nStats on stdout replace the events (implies -q/--quiet); on stderr or in a file the events still print.\nThe default destination is stdout, or stderr with --with-stats.\n\nExamples:\n  -s                       Default table on stdout\n  --stats=json             JSON output\n  --stats=stderr           Table on stderr, events on stdout (same as --with-stats)\n  --stats=json,file:s.json JSON written to s.json\n\nUse --no-stats to override a config default. Note the '=': --stats=json (a space, as in '-s json', is read as a filename)."
     )]
-    pub stats: Option<StatsFormat>,
+    pub stats: Option<StatsSpec>,
 
     /// Disable processing statistics explicitly (default: off).
     #[arg(
@@ -755,23 +2031,38 @@ pub struct Cli {
     )]
     pub no_stats: bool,
 
-    /// Show stats alongside events (rare case).
+    /// Show stats alongside events, on stderr unless --stats names a destination.
     #[arg(long = "with-stats", help_heading = "Metrics and Stats")]
     pub with_stats: bool,
 
+    /// After the last event, run the processing stats through the pipeline
+    /// as one synthetic event (`_synthetic: "stats"`, `_kelora_stats: true`).
+    #[arg(long = "stats-to-event", help_heading = "Metrics and Stats")]
+    pub stats_to_event: bool,
+
+    /// Track the value types each output field takes and report fields seen
+    /// with more than one (e.g. int and string) in the stats block, or as a
+    /// warning at the end when --stats is off. Nulls are not a conflict.
+    #[arg(
+        long = "detect-type-conflicts",
+        visible_alias = "field-type-report",
+        help_heading = "Metrics and Stats"
+    )]
+    pub detect_type_conflicts: bool,
+
     /// Show metrics only (implies -q/--quiet). Use -m for default (table), or --metrics=FORMAT for explicit format.
     #[arg(
         short = 'm',
         long = "metrics",
-        value_enum,
-        value_name = "FORMAT",
+        value_name = "FORMAT,DEST",
         require_equals = true,
         num_args = 0..=1,
         default_missing_value = "auto",
+        value_parser = parse_metrics_spec,
         help_heading = "Metrics and Stats",
-        help = "Show metrics only (implies -q/--quiet).\n\nFormats: short (first 5), full, tsv, json. Bare -m auto-selects: the\nhuman-readable table on a terminal, tsv when piped or redirected (like ls).\n\ntsv emits one tab-separated 'metric<TAB>key<TAB>value' record per line, sorted\nby count descending, so | head is top-N and | tail is bottom-N.\n\nExamples:\n  -m               Auto (table on a TTY, tsv when piped)\n  --metrics=full   Force the table even through a pipe\n  --metrics=tsv    Force the record stream even to a TTY\n  --metrics=short  Abbreviated (first 5 items)\n  --metrics=json   JSON output\n\nUse --no-metrics to override a config default. Note the '=': --metrics=json (a space, as in '-m json', is read as a filename)."
+        help = "Show metrics only (implies -q/--quiet).\n\nFormats: short (first 5), full, tsv, json, prometheus. Bare -m auto-selects: the\nhuman-readable table on a terminal, tsv when piped or redirected (like ls).\n\ntsv emits one tab-separated 'metric<TAB>key<TAB>value' record per line, sorted\nby count descending, so | head is top-N and | tail is bottom-N.\n\nExamples:\n  -m               Auto (table on a TTY, tsv when piped)\n  --metrics=full   Force the table even through a pipe\n  --metrics=tsv    Force the record stream even to a TTY\n  --metrics=short  Abbreviated (first 5 items)\n  --metrics=json   JSON output\n  --metrics=prometheus  Prometheus text exposition format\n\nDestinations: stdout (default), stderr (default with --with-metrics), file:PATH.\nAdd one after the format, e.g. --metrics=json,file:m.json or --metrics=stderr.\nMetrics on stdout replace the events; on stderr or in a file the events still print.\n\nUse --no-metrics to override a config default. Note the '=': --metrics=json (a space, as in '-m json', is read as a filename)."
     )]
-    pub metrics: Option<MetricsFormat>,
+    pub metrics: Option<MetricsSpec>,
 
     /// Disable tracked metrics explicitly (default: off).
     #[arg(
@@ -782,10 +2073,20 @@ pub struct Cli {
     )]
     pub no_metrics: bool,
 
-    /// Show metrics alongside events (rare case).
+    /// Show metrics alongside events, on stderr unless --metrics names a destination.
     #[arg(long = "with-metrics", help_heading = "Metrics and Stats")]
     pub with_metrics: bool,
 
+    /// Run scripts for their side effects but write no events; print the
+    /// final metrics (and --stats, if requested) instead. Unlike -m, script
+    /// output and hints are kept.
+    #[arg(
+        long = "output-summary-only",
+        help_heading = "Metrics and Stats",
+        conflicts_with = "with_metrics"
+    )]
+    pub output_summary_only: bool,
+
     /// Write metrics to file (JSON format). Can combine with -m for both table and file.
     #[arg(
         long = "metrics-file",
@@ -795,6 +2096,32 @@ pub struct Cli {
     )]
     pub metrics_file: Option<String>,
 
+    /// Prometheus Pushgateway base URL to push metrics to when processing ends.
+    #[arg(
+        long = "metrics-endpoint",
+        value_name = "URL",
+        help_heading = "Metrics and Stats",
+        help = "Push metrics to this Prometheus Pushgateway (e.g. http://localhost:9091) when\nprocessing ends. Requires --metrics=prometheus and --pushgateway-job; the\nmetrics are PUT to URL/metrics/job/JOB/instance/HOSTNAME instead of printed."
+    )]
+    pub metrics_endpoint: Option<String>,
+
+    /// Pushgateway job name used in the push URL.
+    #[arg(
+        long = "pushgateway-job",
+        value_name = "JOB",
+        help_heading = "Metrics and Stats"
+    )]
+    pub pushgateway_job: Option<String>,
+
+    /// Extra Pushgateway grouping label (repeatable). instance=NAME overrides the hostname.
+    #[arg(
+        long = "pushgateway-grouping",
+        value_name = "KEY=VALUE",
+        action = clap::ArgAction::Append,
+        help_heading = "Metrics and Stats"
+    )]
+    pub pushgateway_grouping: Vec<String>,
+
     /// Frequency table: count occurrences per distinct value of FIELD. Shorthand for track_freq.
     #[arg(
         long = "freq",
@@ -835,6 +2162,16 @@ pub struct Cli {
     )]
     pub drain: Option<DrainFormat>,
 
+    /// Group similar values of FIELD by edit distance and print clusters with counts.
+    #[arg(
+        long = "cluster",
+        value_name = "FIELD[:THRESHOLD]",
+        value_parser = crate::cluster::ClusterConfig::parse,
+        help_heading = "Template Discovery",
+        help = "Group similar values of FIELD by edit distance and print representative clusters with counts (summary-only; sequential mode only).\n\nTHRESHOLD is the minimum similarity to join a cluster, 1 - distance/length,\nbetween 0 and 1 (default: 0.8). Only values sharing the same first word are\ncompared. A lighter alternative to --drain for a single free-text field.\n\nExamples:\n  --cluster msg\n  --cluster error:0.9"
+    )]
+    pub cluster: Option<crate::cluster::ClusterConfig>,
+
     /// Discover field names, types, and cardinality from the log stream.
     #[arg(
         short = 'd',
@@ -886,6 +2223,11 @@ pub struct Cli {
     #[arg(long = "ignore-config", help_heading = "Configuration Options")]
     pub ignore_config: bool,
 
+    /// Treat unknown configuration file keys and sections as errors instead
+    /// of warnings.
+    #[arg(long = "strict-config", help_heading = "Configuration Options")]
+    pub strict_config: bool,
+
     /// Use alias from configuration file.
     #[arg(
         short = 'a',
@@ -919,6 +2261,22 @@ pub struct Cli {
     #[arg(long = "help-functions", value_name = "KEYWORD", num_args = 0..=1, default_missing_value = "", help_heading = "Help Options")]
     pub help_functions: Option<String>,
 
+    /// Show all signatures, notes and examples of one Rhai function and exit.
+    #[arg(
+        long = "help-function",
+        value_name = "NAME",
+        help_heading = "Help Options"
+    )]
+    pub help_function: Option<String>,
+
+    /// With --help-functions, print the function catalogue as JSON.
+    #[arg(
+        long = "json",
+        requires = "help_functions",
+        help_heading = "Help Options"
+    )]
+    pub help_functions_json: bool,
+
     /// Show practical Rhai examples and exit.
     #[arg(long = "help-examples", help_heading = "Help Options")]
     pub help_examples: bool,
@@ -1052,6 +2410,48 @@ fn get_begin_end_includes(matches: &ArgMatches) -> Result<(Vec<String>, Vec<Stri
 }
 
 impl Cli {
+    /// Map each --stage-label to the --exec/--exec-file that follows it, returning
+    /// one entry per exec stage in command-line order.
+    pub fn get_exec_stage_labels(&self, matches: &ArgMatches) -> Result<Vec<Option<String>>> {
+        let mut exec_positions: Vec<usize> = Vec::new();
+        if let Some(exec_indices) = matches.indices_of("execs") {
+            exec_positions.extend(exec_indices);
+        }
+        if let Some(exec_file_indices) = matches.indices_of("exec_files") {
+            exec_positions.extend(exec_file_indices);
+        }
+        exec_positions.sort();
+
+        let mut labels = vec![None; exec_positions.len()];
+        if let Some(label_indices) = matches.indices_of("stage_labels") {
+            let label_values: Vec<&String> = matches
+                .get_many::<String>("stage_labels")
+                .unwrap()
+                .collect();
+            for (pos, label_index) in label_indices.enumerate() {
+                let label = label_values[pos];
+                let exec_ordinal = exec_positions
+                    .iter()
+                    .position(|&exec_pos| exec_pos > label_index)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "--stage-label '{}' must be followed by --exec or --exec-file",
+                            label
+                        )
+                    })?;
+                if labels[exec_ordinal].is_some() {
+                    return Err(anyhow::anyhow!(
+                        "--stage-label '{}' names an exec stage that already has a label",
+                        label
+                    ));
+                }
+                labels[exec_ordinal] = Some(label.clone());
+            }
+        }
+
+        Ok(labels)
+    }
+
     /// Extract filter and exec stages in the order they appeared on the command line
     pub fn get_ordered_script_stages(&self, matches: &ArgMatches) -> Result<Vec<ScriptStageType>> {
         use std::collections::HashMap;
@@ -1393,6 +2793,79 @@ fn synthesize_card_stage(field: &str) -> Result<String> {
     ))
 }
 
+/// Parse a --stats value: an optional table/json format and output route
+fn parse_stats_spec(s: &str) -> Result<StatsSpec, String> {
+    let (format, route) = parse_report_spec::<StatsFormat>(s, "--stats")?;
+    Ok(StatsSpec {
+        format: format.unwrap_or(StatsFormat::Table),
+        route,
+    })
+}
+
+/// Parse a --metrics value: an optional format and output route
+fn parse_metrics_spec(s: &str) -> Result<MetricsSpec, String> {
+    let (format, route) = parse_report_spec::<MetricsFormat>(s, "--metrics")?;
+    Ok(MetricsSpec {
+        format: format.unwrap_or(MetricsFormat::Auto),
+        route,
+    })
+}
+
+/// Split a `--stats`/`--metrics` value into an optional format and an
+/// optional destination, comma-separated in either order. `file:` takes the
+/// rest of the value, so paths may contain commas.
+fn parse_report_spec<F: clap::ValueEnum>(
+    s: &str,
+    flag: &str,
+) -> Result<(Option<F>, Option<ReportRoute>), String> {
+    let mut format = None;
+    let mut route = None;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let (part, tail) = if rest.starts_with("file:") {
+            (rest, "")
+        } else {
+            rest.split_once(',').unwrap_or((rest, ""))
+        };
+        rest = tail;
+
+        let part_route = match part {
+            "stdout" => Some(ReportRoute::Stdout),
+            "stderr" => Some(ReportRoute::Stderr),
+            _ => match part.strip_prefix("file:") {
+                Some("") => return Err(format!("{} file: needs a path, e.g. file:out.json", flag)),
+                Some(path) => Some(ReportRoute::File(path.to_string())),
+                None => None,
+            },
+        };
+        if let Some(part_route) = part_route {
+            if route.replace(part_route).is_some() {
+                return Err(format!("{} takes at most one destination", flag));
+            }
+            continue;
+        }
+
+        let parsed = F::from_str(part, true).map_err(|_| {
+            let formats: Vec<String> = F::value_variants()
+                .iter()
+                .filter_map(|v| v.to_possible_value())
+                .filter(|v| !v.is_hide_set())
+                .map(|v| v.get_name().to_string())
+                .collect();
+            format!(
+                "unknown {} value '{}'; expected a format ({}) or a destination (stdout, stderr, file:PATH)",
+                flag,
+                part,
+                formats.join(", ")
+            )
+        })?;
+        if format.replace(parsed).is_some() {
+            return Err(format!("{} takes at most one format", flag));
+        }
+    }
+    Ok((format, route))
+}
+
 /// Parse and validate format value - supports standard formats, cols:<spec>, regex:<pattern>, and csv/tsv with type annotations
 fn parse_format_value(s: &str) -> Result<String, String> {
     // Check if it's a regex format
@@ -1415,6 +2888,23 @@ fn parse_format_value(s: &str) -> Result<String, String> {
         return Ok(s.to_string());
     }
 
+    // Check if it's a fixed-width format; the column spec is validated with
+    // the rest of the config
+    if let Some(spec) = s
+        .strip_prefix("fixed-width:")
+        .or_else(|| s.strip_prefix("fixed-width "))
+        .or_else(|| s.strip_prefix("fixed:"))
+        .or_else(|| s.strip_prefix("fixed "))
+    {
+        if spec.trim().is_empty() {
+            return Err(
+                "fixed-width format requires a column spec, e.g., 'fixed-width:user:0:8,pid:9:15'"
+                    .to_string(),
+            );
+        }
+        return Ok(s.to_string());
+    }
+
     // Check if it's CSV/TSV with field specs (type annotations)
     if s.starts_with("csv:") || s.starts_with("csv ") {
         return Ok(s.to_string());
@@ -1427,7 +2917,9 @@ fn parse_format_value(s: &str) -> Result<String, String> {
     // Full validation happens in parse_input_format_spec; here we accept the
     // form and catch the most obvious mistakes early.
     if s.contains(',') {
-        let allowed = ["json", "line", "raw", "logfmt", "syslog", "cef", "combined"];
+        let allowed = [
+            "json", "line", "raw", "logfmt", "syslog", "cef", "leef", "combined",
+        ];
         for part in s.split(',') {
             let p = part.trim().to_lowercase();
             if p.is_empty() {
@@ -1447,7 +2939,7 @@ fn parse_format_value(s: &str) -> Result<String, String> {
                 };
                 return Err(format!(
                     "Unknown or unsupported format '{}' in cascade list '{}'. \
-Allowed in a comma list: json, line, raw, logfmt, syslog, cef, combined, and built-in application-log formats ({}).{}",
+Allowed in a comma list: json, line, raw, logfmt, syslog, cef, leef, combined, and built-in application-log formats ({}).{}",
                     part.trim(),
                     s,
                     crate::parsers::lnav_formats::names_csv(),
@@ -1461,14 +2953,17 @@ Allowed in a comma list: json, line, raw, logfmt, syslog, cef, combined, and bui
     // Check if it's a standard format
     match s.to_lowercase().as_str() {
         "auto" | "auto-per-file" | "json" | "line" | "raw" | "logfmt" | "syslog" | "cef"
-        | "csv" | "tsv" | "csvnh" | "tsvnh" | "combined" | "cols" => Ok(s.to_string()),
+        | "leef" | "csv" | "tsv" | "csvnh" | "tsvnh" | "combined" | "haproxy" | "cols" | "ssv"
+        | "netflow5" | "netflow9" | "ipfix" | "pcap" | "xml-records" | "sqlite" => {
+            Ok(s.to_string())
+        }
         other => {
             // Built-in application-log formats (adapted from lnav), e.g. -f log4j
             if crate::parsers::lnav_formats::by_name(other).is_some() {
                 return Ok(s.to_string());
             }
             Err(format!(
-                "Unknown format '{}'. Supported formats: auto, auto-per-file, json, line, raw, logfmt, syslog, cef, csv, tsv, csvnh, tsvnh, combined, cols:<spec>, regex:<pattern>, or a built-in application-log format ({})",
+                "Unknown format '{}'. Supported formats: auto, auto-per-file, json, line, raw, logfmt, syslog, cef, leef, csv, tsv, csvnh, tsvnh, combined, haproxy, ssv, netflow5, netflow9, ipfix, pcap, xml-records, sqlite, cols:<spec>, regex:<pattern>, fixed-width:<spec> (or fixed:<spec>), or a built-in application-log format ({})",
                 s,
                 crate::parsers::lnav_formats::names_csv()
             ))
@@ -1491,6 +2986,33 @@ mod tests {
         (cli, matches)
     }
 
+    #[test]
+    fn report_specs_take_format_and_destination_in_any_order() {
+        let spec = parse_stats_spec("stderr,json").unwrap();
+        assert!(matches!(spec.format, StatsFormat::Json));
+        assert_eq!(spec.route, Some(ReportRoute::Stderr));
+
+        let spec = parse_metrics_spec("tsv,file:out,v1.tsv").unwrap();
+        assert!(matches!(spec.format, MetricsFormat::Tsv));
+        assert_eq!(
+            spec.route,
+            Some(ReportRoute::File("out,v1.tsv".to_string()))
+        );
+
+        let spec = parse_metrics_spec("stdout").unwrap();
+        assert!(matches!(spec.format, MetricsFormat::Auto));
+
+        assert!(parse_stats_spec("json,table").is_err());
+        assert!(parse_stats_spec("stdout,stderr").is_err());
+        assert!(parse_stats_spec("file:").is_err());
+        let err = parse_metrics_spec("yaml").unwrap_err();
+        assert!(
+            err.contains("prometheus") && !err.contains("auto"),
+            "{}",
+            err
+        );
+    }
+
     #[test]
     fn ordered_script_stages_preserve_cli_sequence() {
         let mut exec_file = NamedTempFile::new().expect("temp file");
@@ -0,0 +1,288 @@
+//! Edit-distance clustering of a single field (`--cluster FIELD[:THRESHOLD]`).
+//!
+//! A lighter alternative to Drain: values are grouped greedily around the
+//! first value seen for each cluster, joining the first cluster whose
+//! representative is at least `threshold` similar, where similarity is
+//! `1 - edit_distance / max(len)`. To keep this from turning into an O(n²)
+//! scan, candidates are limited to a canopy sharing the value's leading
+//! token, and representatives whose length difference alone already exceeds
+//! the allowed distance are skipped without computing the distance.
+
+use crate::rhai_functions::strings::edit_distance_impl;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+pub const DEFAULT_CLUSTER_THRESHOLD: f64 = 0.8;
+
+/// Cap on remembered exact values, so the fast path stays bounded on
+/// high-cardinality fields
+const EXACT_CACHE_LIMIT: usize = 100_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterConfig {
+    pub field: String,
+    /// Minimum similarity (0.0 < t <= 1.0) for a value to join a cluster
+    pub threshold: f64,
+}
+
+impl ClusterConfig {
+    /// Parse `FIELD` or `FIELD:THRESHOLD`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (field, threshold) = match spec.rsplit_once(':') {
+            Some((field, raw)) => {
+                let threshold: f64 = raw.trim().parse().map_err(|_| {
+                    format!(
+                        "invalid --cluster threshold '{}': expected a number between 0 and 1, e.g. msg:0.8",
+                        raw
+                    )
+                })?;
+                (field, threshold)
+            }
+            None => (spec, DEFAULT_CLUSTER_THRESHOLD),
+        };
+        let field = field.trim();
+        if field.is_empty() {
+            return Err("--cluster requires a field name, e.g. --cluster msg:0.8".to_string());
+        }
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            return Err(format!(
+                "invalid --cluster threshold {}: must be greater than 0 and at most 1",
+                threshold
+            ));
+        }
+        Ok(Self {
+            field: field.to_string(),
+            threshold,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cluster {
+    pub representative: String,
+    pub count: usize,
+}
+
+#[derive(Debug)]
+struct ClusterState {
+    threshold: f64,
+    clusters: Vec<Cluster>,
+    /// Leading-token canopy -> indices into `clusters`
+    canopies: HashMap<String, Vec<usize>>,
+    /// Exact value -> cluster index, for repeated values
+    exact: HashMap<String, usize>,
+}
+
+impl ClusterState {
+    fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            clusters: Vec::new(),
+            canopies: HashMap::new(),
+            exact: HashMap::new(),
+        }
+    }
+
+    fn ingest(&mut self, text: &str) {
+        let idx = match self.exact.get(text) {
+            Some(&idx) => idx,
+            None => {
+                let idx = self.find_or_create(text);
+                if self.exact.len() < EXACT_CACHE_LIMIT {
+                    self.exact.insert(text.to_string(), idx);
+                }
+                idx
+            }
+        };
+
+        self.clusters[idx].count += 1;
+    }
+
+    fn find_or_create(&mut self, text: &str) -> usize {
+        let key = canopy_key(text);
+        let text_len = text.chars().count();
+
+        if let Some(members) = self.canopies.get(&key) {
+            for &idx in members {
+                let rep = &self.clusters[idx].representative;
+                let rep_len = rep.chars().count();
+                let max_len = text_len.max(rep_len);
+                // Epsilon absorbs float error, e.g. (1.0 - 0.8) * 30 = 5.999...
+                let max_distance =
+                    ((1.0 - self.threshold) * max_len as f64 + 1e-9).floor() as usize;
+                if text_len.abs_diff(rep_len) > max_distance {
+                    continue;
+                }
+                let distance = edit_distance_impl(text, rep) as usize;
+                if distance <= max_distance {
+                    return idx;
+                }
+            }
+        }
+
+        let idx = self.clusters.len();
+        self.clusters.push(Cluster {
+            representative: text.to_string(),
+            count: 0,
+        });
+        self.canopies.entry(key).or_default().push(idx);
+        idx
+    }
+
+    fn clusters(&self) -> Vec<Cluster> {
+        let mut clusters = self.clusters.clone();
+        // Stable sort keeps first-seen order among equal counts
+        clusters.sort_by(|a, b| b.count.cmp(&a.count));
+        clusters
+    }
+}
+
+/// Canopy key: the leading token with digits masked, so "retry 3 failed" and
+/// "retry 12 failed" share a canopy while unrelated messages do not meet.
+fn canopy_key(text: &str) -> String {
+    text.split_whitespace()
+        .next()
+        .unwrap_or("")
+        .chars()
+        .map(|c| if c.is_ascii_digit() { '#' } else { c })
+        .collect()
+}
+
+thread_local! {
+    static CLUSTER_STATE: RefCell<Option<ClusterState>> = const { RefCell::new(None) };
+}
+
+pub fn reset() {
+    CLUSTER_STATE.with(|state| {
+        *state.borrow_mut() = None;
+    });
+}
+
+pub fn cluster_value(text: &str, threshold: f64) {
+    CLUSTER_STATE.with(|state| {
+        state
+            .borrow_mut()
+            .get_or_insert_with(|| ClusterState::new(threshold))
+            .ingest(text);
+    });
+}
+
+pub fn clusters() -> Vec<Cluster> {
+    CLUSTER_STATE.with(|state| match state.borrow().as_ref() {
+        Some(state) => state.clusters(),
+        None => Vec::new(),
+    })
+}
+
+/// Format clusters as `count: representative` lines, largest first
+pub fn format_clusters_output(clusters: &[Cluster]) -> String {
+    if clusters.is_empty() {
+        return "No clusters found".to_string();
+    }
+
+    let mut output = String::new();
+    output.push_str(&format!("clusters ({} items):\n", clusters.len()));
+
+    let max_count_width = clusters
+        .iter()
+        .map(|c| c.count.to_string().len())
+        .max()
+        .unwrap_or(1);
+
+    for cluster in clusters {
+        output.push_str(&format!(
+            "  {:>width$}: {}\n",
+            cluster.count,
+            cluster
+                .representative
+                .replace('\n', "\\n")
+                .replace('\r', "\\r"),
+            width = max_count_width
+        ));
+    }
+
+    output.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(values: &[&str], threshold: f64) -> Vec<Cluster> {
+        let mut state = ClusterState::new(threshold);
+        for value in values {
+            state.ingest(value);
+        }
+        state.clusters()
+    }
+
+    #[test]
+    fn near_identical_messages_share_a_cluster() {
+        let clusters = run(
+            &[
+                "Connection timeout to 10.0.0.1",
+                "Connection timeout to 10.0.0.2",
+                "Connection timeout to 10.0.0.13",
+                "Disk quota exceeded for /var",
+                "Connection refused",
+                "Disk quota exceeded for /tmp",
+            ],
+            0.8,
+        );
+
+        let summary: Vec<(usize, &str)> = clusters
+            .iter()
+            .map(|c| (c.count, c.representative.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (3, "Connection timeout to 10.0.0.1"),
+                (2, "Disk quota exceeded for /var"),
+                (1, "Connection refused"),
+            ]
+        );
+    }
+
+    #[test]
+    fn threshold_of_one_only_groups_exact_matches() {
+        let clusters = run(&["user 1 login", "user 2 login", "user 1 login"], 1.0);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].count, 2);
+    }
+
+    #[test]
+    fn different_leading_tokens_never_compared() {
+        // Similar text, but the canopy (first token) differs
+        let clusters = run(&["alpha beta gamma", "alphx beta gamma"], 0.5);
+        assert_eq!(clusters.len(), 2);
+    }
+
+    #[test]
+    fn parse_spec_defaults_and_validates_threshold() {
+        assert_eq!(
+            ClusterConfig::parse("msg").unwrap(),
+            ClusterConfig {
+                field: "msg".to_string(),
+                threshold: DEFAULT_CLUSTER_THRESHOLD
+            }
+        );
+        assert_eq!(ClusterConfig::parse("msg:0.6").unwrap().threshold, 0.6);
+        assert!(ClusterConfig::parse("msg:0").is_err());
+        assert!(ClusterConfig::parse("msg:1.5").is_err());
+        assert!(ClusterConfig::parse("msg:high").is_err());
+        assert!(ClusterConfig::parse(":0.5").is_err());
+    }
+
+    #[test]
+    fn format_lists_counts_right_aligned() {
+        let clusters = run(
+            &[
+                "a b", "a b", "a b", "a b", "a b", "a b", "a b", "a b", "a b", "a b", "z",
+            ],
+            0.9,
+        );
+        let output = format_clusters_output(&clusters);
+        assert_eq!(output, "clusters (2 items):\n  10: a b\n   1: z");
+    }
+}
@@ -1,3 +1,5 @@
+use crate::cli::{ColorPart, ColorTheme};
+
 /// ANSI color codes for logfmt output formatting
 #[derive(Debug, Clone)]
 pub struct ColorScheme {
@@ -13,28 +15,119 @@ pub struct ColorScheme {
     pub context_match: &'static str,   // Bright magenta for context prefix match markers
     pub context_after: &'static str,   // Blue for context prefix after markers
     pub context_overlap: &'static str, // Cyan for overlapping context markers
+    pub json_key: &'static str,        // Yellow for keys in --color-json-values
+    pub json_string: &'static str,     // Green for JSON strings
+    pub json_number: &'static str,     // Cyan for JSON numbers
+    pub json_literal: &'static str,    // Magenta for null/true/false
     pub reset: &'static str,           // Reset to default color
 }
 
+/// Built-in palette: the original ANSI 16-color scheme
+const DEFAULT_THEME: ColorScheme = ColorScheme {
+    key: "\x1b[32m",             // Green for field names
+    equals: "",                  // No color for equals signs
+    string: "",                  // No color for quoted values
+    level_trace: "\x1b[36m",     // Cyan for trace/finest
+    level_debug: "\x1b[96m",     // Bright cyan for debug/finer/config
+    level_info: "\x1b[92m",      // Bright green for info/informational/notice
+    level_warn: "\x1b[93m",      // Bright yellow for warn/warning
+    level_error: "\x1b[91m",     // Bright red for error/fatal/panic/etc
+    context_before: "\x1b[34m",  // Blue for before context markers
+    context_match: "\x1b[95m",   // Bright magenta for match context markers
+    context_after: "\x1b[34m",   // Blue for after context markers
+    context_overlap: "\x1b[36m", // Cyan for overlapping context markers
+    json_key: "\x1b[33m",        // Yellow for JSON keys
+    json_string: "\x1b[32m",     // Green for JSON strings
+    json_number: "\x1b[36m",     // Cyan for JSON numbers
+    json_literal: "\x1b[35m",    // Magenta for null/true/false
+    reset: "\x1b[0m",            // Reset
+};
+
+/// Solarized accent colors (256-color approximations)
+const SOLARIZED_THEME: ColorScheme = ColorScheme {
+    key: "\x1b[38;5;33m", // blue
+    equals: "",
+    string: "",
+    level_trace: "\x1b[38;5;245m",     // base1
+    level_debug: "\x1b[38;5;37m",      // cyan
+    level_info: "\x1b[38;5;64m",       // green
+    level_warn: "\x1b[38;5;136m",      // yellow
+    level_error: "\x1b[38;5;160m",     // red
+    context_before: "\x1b[38;5;61m",   // violet
+    context_match: "\x1b[38;5;125m",   // magenta
+    context_after: "\x1b[38;5;61m",    // violet
+    context_overlap: "\x1b[38;5;166m", // orange
+    json_key: "\x1b[38;5;136m",        // yellow
+    json_string: "\x1b[38;5;64m",      // green
+    json_number: "\x1b[38;5;37m",      // cyan
+    json_literal: "\x1b[38;5;125m",    // magenta
+    reset: "\x1b[0m",
+};
+
+/// No hues at all, only weight and inverse video
+const MONO_THEME: ColorScheme = ColorScheme {
+    key: "\x1b[1m", // bold
+    equals: "",
+    string: "",
+    level_trace: "\x1b[2m", // dim
+    level_debug: "\x1b[2m", // dim
+    level_info: "",
+    level_warn: "\x1b[1m",    // bold
+    level_error: "\x1b[1;7m", // bold inverse
+    context_before: "\x1b[2m",
+    context_match: "\x1b[1m",
+    context_after: "\x1b[2m",
+    context_overlap: "\x1b[2m",
+    json_key: "\x1b[1m", // bold
+    json_string: "",
+    json_number: "",
+    json_literal: "\x1b[2m", // dim
+    reset: "\x1b[0m",
+};
+
+/// Bold bright colors, with backgrounds for warnings and errors
+const HIGHCONTRAST_THEME: ColorScheme = ColorScheme {
+    key: "\x1b[1;97m", // bold white
+    equals: "",
+    string: "",
+    level_trace: "\x1b[1;96m",    // bold bright cyan
+    level_debug: "\x1b[1;94m",    // bold bright blue
+    level_info: "\x1b[1;92m",     // bold bright green
+    level_warn: "\x1b[1;30;103m", // black on bright yellow
+    level_error: "\x1b[1;97;41m", // white on red
+    context_before: "\x1b[1;94m",
+    context_match: "\x1b[1;95m",
+    context_after: "\x1b[1;94m",
+    context_overlap: "\x1b[1;96m",
+    json_key: "\x1b[1;93m",     // bold bright yellow
+    json_string: "\x1b[1;92m",  // bold bright green
+    json_number: "\x1b[1;96m",  // bold bright cyan
+    json_literal: "\x1b[1;95m", // bold bright magenta
+    reset: "\x1b[0m",
+};
+
+/// Palette for each `--color-theme`. Adding a theme is a new table above plus
+/// a row here.
+const THEMES: &[(ColorTheme, &ColorScheme)] = &[
+    (ColorTheme::Default, &DEFAULT_THEME),
+    (ColorTheme::Solarized, &SOLARIZED_THEME),
+    (ColorTheme::Mono, &MONO_THEME),
+    (ColorTheme::Highcontrast, &HIGHCONTRAST_THEME),
+];
+
 impl ColorScheme {
     /// Create color scheme for readable logfmt output
     pub fn new(use_colors: bool) -> Self {
+        Self::themed(ColorTheme::Default, use_colors)
+    }
+
+    /// Create the color scheme for a named `--color-theme` palette
+    pub fn themed(theme: ColorTheme, use_colors: bool) -> Self {
         if use_colors {
-            Self {
-                key: "\x1b[32m",             // Green for field names
-                equals: "",                  // No color for equals signs
-                string: "",                  // No color for quoted values
-                level_trace: "\x1b[36m",     // Cyan for trace/finest
-                level_debug: "\x1b[96m",     // Bright cyan for debug/finer/config
-                level_info: "\x1b[92m",      // Bright green for info/informational/notice
-                level_warn: "\x1b[93m",      // Bright yellow for warn/warning
-                level_error: "\x1b[91m",     // Bright red for error/fatal/panic/etc
-                context_before: "\x1b[34m",  // Blue for before context markers
-                context_match: "\x1b[95m",   // Bright magenta for match context markers
-                context_after: "\x1b[34m",   // Blue for after context markers
-                context_overlap: "\x1b[36m", // Cyan for overlapping context markers
-                reset: "\x1b[0m",            // Reset
-            }
+            THEMES
+                .iter()
+                .find(|(name, _)| *name == theme)
+                .map_or(DEFAULT_THEME, |(_, palette)| (*palette).clone())
         } else {
             // All empty strings for no-color mode
             Self {
@@ -50,11 +143,44 @@ impl ColorScheme {
                 context_match: "",
                 context_after: "",
                 context_overlap: "",
+                json_key: "",
+                json_string: "",
+                json_number: "",
+                json_literal: "",
                 reset: "",
             }
         }
     }
 
+    /// Keep colors only for the `--color-parts` selection (empty keeps all)
+    pub fn with_parts(mut self, parts: &[ColorPart]) -> Self {
+        if !part_enabled(parts, ColorPart::Keys) {
+            self.key = "";
+            self.equals = "";
+        }
+        if !part_enabled(parts, ColorPart::Values) {
+            self.string = "";
+            self.json_key = "";
+            self.json_string = "";
+            self.json_number = "";
+            self.json_literal = "";
+        }
+        if !part_enabled(parts, ColorPart::Levels) {
+            self.level_trace = "";
+            self.level_debug = "";
+            self.level_info = "";
+            self.level_warn = "";
+            self.level_error = "";
+        }
+        if !part_enabled(parts, ColorPart::Context) {
+            self.context_before = "";
+            self.context_match = "";
+            self.context_after = "";
+            self.context_overlap = "";
+        }
+        self
+    }
+
     /// Map a log level string to its ANSI color (`""` when unrecognized).
     ///
     /// Recognizes full level words and their common synonyms, plus glog/klog's
@@ -80,6 +206,11 @@ impl ColorScheme {
     }
 }
 
+/// Whether a `--color-parts` selection colors `part` (empty selects all)
+pub fn part_enabled(parts: &[ColorPart], part: ColorPart) -> bool {
+    parts.is_empty() || parts.contains(&part)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,4 +242,60 @@ mod tests {
         assert_eq!(c.level_color("E"), "");
         assert_eq!(c.level_color("ERROR"), "");
     }
+
+    #[test]
+    fn every_theme_has_a_palette() {
+        for theme in [
+            ColorTheme::Default,
+            ColorTheme::Solarized,
+            ColorTheme::Mono,
+            ColorTheme::Highcontrast,
+        ] {
+            assert!(THEMES.iter().any(|(name, _)| *name == theme), "{theme:?}");
+        }
+        assert_eq!(
+            ColorScheme::themed(ColorTheme::Default, true).key,
+            ColorScheme::new(true).key
+        );
+    }
+
+    #[test]
+    fn themes_use_different_codes_for_the_same_element() {
+        let default = ColorScheme::themed(ColorTheme::Default, true);
+        let solarized = ColorScheme::themed(ColorTheme::Solarized, true);
+        let highcontrast = ColorScheme::themed(ColorTheme::Highcontrast, true);
+        assert_ne!(default.level_color("ERROR"), solarized.level_color("ERROR"));
+        assert_ne!(default.key, solarized.key);
+        assert_ne!(solarized.level_warn, highcontrast.level_warn);
+    }
+
+    #[test]
+    fn color_parts_keep_only_selected_codes() {
+        let c = ColorScheme::new(true).with_parts(&[ColorPart::Keys]);
+        assert_eq!(c.key, DEFAULT_THEME.key);
+        assert_eq!(c.level_color("ERROR"), "");
+        assert_eq!(c.context_match, "");
+
+        let all = ColorScheme::new(true).with_parts(&[]);
+        assert_eq!(all.level_color("ERROR"), DEFAULT_THEME.level_error);
+        assert_eq!(all.context_match, DEFAULT_THEME.context_match);
+    }
+
+    #[test]
+    fn json_value_colors_follow_the_theme() {
+        let default = ColorScheme::themed(ColorTheme::Default, true);
+        let mono = ColorScheme::themed(ColorTheme::Mono, true);
+        assert_eq!(default.json_key, "\x1b[33m");
+        assert_ne!(default.json_key, mono.json_key);
+        assert_eq!(mono.json_string, "");
+        assert_eq!(ColorScheme::themed(ColorTheme::Default, false).json_key, "");
+    }
+
+    #[test]
+    fn no_color_wins_over_theme() {
+        let c = ColorScheme::themed(ColorTheme::Highcontrast, false);
+        assert_eq!(c.key, "");
+        assert_eq!(c.level_color("ERROR"), "");
+        assert_eq!(c.reset, "");
+    }
 }
@@ -16,15 +16,35 @@ pub struct KeloraConfig {
 #[derive(Debug, Clone)]
 pub struct InputConfig {
     pub files: Vec<String>,
+    /// Inputs dropped from `files` by --filename-ts-format, for the stats
+    pub files_skipped_by_name: Vec<String>,
     pub no_input: bool,
     pub format: InputFormat,
     pub file_order: FileOrder,
     pub merge_ts: bool,
+    /// Glob pattern followed for new and growing files (--follow-glob)
+    pub follow_glob: Option<String>,
+    /// Shell commands whose stdout is read as input (--input-cmd)
+    pub input_cmds: Vec<String>,
+    /// Restart --input-cmd commands when they exit (--follow)
+    pub follow: bool,
+    /// Read buffer per input file in bytes (--read-buffer)
+    pub read_buffer: usize,
+    /// Memory-map regular input files instead of buffered reads (--mmap)
+    pub mmap: bool,
+    /// Copy every raw input line to this gzip recording (--pipeline-record)
+    pub pipeline_record: Option<std::path::PathBuf>,
+    /// Read input from a recording instead of files or stdin (--pipeline-replay)
+    pub pipeline_replay: Option<std::path::PathBuf>,
+    /// Non-empty lines sampled by format auto-detection (--detect-lines)
+    pub detect_lines: Option<usize>,
+    /// Parser retried on lines the input format rejects (--fallback-format)
+    pub fallback_format: Option<InputFormat>,
     pub skip_lines: usize,
     pub head_lines: Option<usize>,
     pub section: Option<SectionConfig>,
-    pub ignore_lines: Option<regex::Regex>,
-    pub keep_lines: Option<regex::Regex>,
+    pub ignore_lines: Option<regex::RegexSet>,
+    pub keep_lines: Option<regex::RegexSet>,
     pub multiline: Option<MultilineConfig>,
     /// Custom timestamp field name (reserved for --since/--until features)
     pub ts_field: Option<String>,
@@ -32,6 +52,10 @@ pub struct InputConfig {
     pub ts_format: Option<String>,
     /// Default timezone for naive timestamps (None = local time)
     pub default_timezone: Option<String>,
+    /// Field carrying a per-event timezone for naive timestamps (--tz-field)
+    pub tz_field: Option<String>,
+    /// Year for year-less timestamps such as RFC3164 syslog (--assume-year)
+    pub assume_year: Option<i32>,
     /// True when the UTC default for naive timestamps is a *silent* assumption:
     /// neither `--input-tz` nor a non-empty `TZ` was provided. Gates the #287
     /// naive-timestamp diagnostic so it never fires when the user chose a zone.
@@ -40,20 +64,155 @@ pub struct InputConfig {
     pub extract_prefix: Option<String>,
     /// Separator string for prefix extraction (default: pipe '|')
     pub prefix_sep: String,
+    /// Prefix cut off each line before parsing (--strip-prefix/--strip-prefix-re)
+    pub strip_prefix: Option<PrefixStrip>,
+    /// Field receiving the stripped prefix (--prefix-field)
+    pub prefix_field: Option<String>,
     /// Column separator for cols format (None = whitespace)
     pub cols_sep: Option<String>,
+    /// Column names for ssv format, with optional `:type` (None = c1, c2, ...)
+    pub ssv_header: Option<Vec<String>>,
+    /// Store unparsed line content in `_rest` (logfmt, regex, cols)
+    pub keep_rest: bool,
+    /// Keep JSON numbers as decimal strings (json format)
+    pub json_numbers_as_strings: bool,
+    /// Reject top-level arrays and scalars (json format, --strict-json)
+    pub strict_json: bool,
+    /// Handling of keys repeated within one event (json, logfmt)
+    pub dup_keys: DupKeyPolicy,
     /// Per-line byte cap (circuit breaker; 0 = unlimited). Guards against a
     /// newline-free stream growing the read buffer without bound. Default is
     /// `DEFAULT_MAX_LINE_BYTES`. See SECURITY.md ("Input-pipeline limits").
     pub max_line_bytes: usize,
 }
 
+impl InputConfig {
+    /// Blank lines are skipped before parsing, except for the line format, which
+    /// keeps them as events, and separator framing, which may split records on them
+    pub fn keeps_blank_lines(&self, format: &InputFormat) -> bool {
+        matches!(format, InputFormat::Line)
+            || matches!(
+                self.multiline,
+                Some(MultilineConfig {
+                    strategy: MultilineStrategy::Separator { .. },
+                    ..
+                })
+            )
+    }
+}
+
 /// Default per-line byte cap (64 MiB). Derived from real log-line sizes
 /// (Docker/CRI split at 16 KB; fat JSON tops out in low single-digit MB) with
 /// generous headroom, while staying small enough that even many parallel reader
 /// buffers can't exhaust RAM. Designed for ~zero false positives.
 pub const DEFAULT_MAX_LINE_BYTES: usize = 64 * 1024 * 1024;
 
+/// File sets compared by --diff-output
+#[derive(Debug, Clone)]
+pub struct DiffMode {
+    pub left: Vec<String>,
+    pub right: Vec<String>,
+    /// Compare on these fields instead of the full event (--diff-key)
+    pub keys: Option<Vec<String>>,
+    /// Also emit events both sides have (--diff-show-common)
+    pub show_common: bool,
+}
+
+/// Default --diff-threshold: counts that moved by more than half are reported
+pub const DEFAULT_DIFF_THRESHOLD_PCT: f64 = 50.0;
+
+/// Baseline comparison by key (--diff-against)
+#[derive(Debug, Clone)]
+pub struct DiffAgainst {
+    pub baseline: Vec<String>,
+    /// Rhai expression giving each event's key (--diff-by)
+    pub key_expr: String,
+    /// Report keys whose count changed by more than this percentage
+    pub threshold_pct: f64,
+}
+
+/// The flags that decide where `--stats` and `--metrics` reports go
+#[derive(Debug, Clone, Default)]
+pub struct ReportFlags {
+    /// A stats report was requested (-s, --stats=..., --with-stats)
+    pub stats: bool,
+    /// Destination named in --stats=...
+    pub stats_route: Option<crate::cli::ReportRoute>,
+    pub with_stats: bool,
+    /// A metrics report was requested (-m, --metrics=..., --freq, ...)
+    pub metrics: bool,
+    /// Destination named in --metrics=...
+    pub metrics_route: Option<crate::cli::ReportRoute>,
+    pub with_metrics: bool,
+    /// --output-summary-only
+    pub summary_only: bool,
+    pub silent: bool,
+}
+
+/// Where the end-of-run reports go and what that does to the event stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportRouting {
+    /// Destination of the stats block; None when off or silenced
+    pub stats: Option<crate::cli::ReportRoute>,
+    /// Destination of the metrics report; None when off or silenced
+    pub metrics: Option<crate::cli::ReportRoute>,
+    /// Events are not written (a report took stdout, or --output-summary-only)
+    pub suppress_events: bool,
+    /// A report replaced the events on stdout, so script output and hints
+    /// are hushed as in the other data-only modes
+    pub data_only: bool,
+}
+
+/// The one place that decides report routing; the success and signal paths
+/// in main only read the result.
+///
+/// A report goes where --stats=/--metrics= names, else to stderr with
+/// --with-stats/--with-metrics, else to stdout. A report on stdout replaces
+/// the events unless its --with-* flag asks to keep them. --silent drops the
+/// terminal destinations but still writes files.
+pub fn resolve_report_routing(flags: &ReportFlags) -> ReportRouting {
+    use crate::cli::ReportRoute;
+
+    let route = |requested: bool, explicit: &Option<ReportRoute>, with_events: bool| {
+        requested.then(|| {
+            explicit.clone().unwrap_or(if with_events {
+                ReportRoute::Stderr
+            } else {
+                ReportRoute::Stdout
+            })
+        })
+    };
+    let stats = route(flags.stats, &flags.stats_route, flags.with_stats);
+    let metrics = route(flags.metrics, &flags.metrics_route, flags.with_metrics);
+
+    let takes_stdout = |route: &Option<ReportRoute>, with_events: bool| {
+        !with_events && matches!(route, Some(ReportRoute::Stdout))
+    };
+    let stats_only = takes_stdout(&stats, flags.with_stats);
+    // --output-summary-only drops the events itself and keeps script
+    // output and hints, so its metrics never count as data-only
+    let metrics_only = !flags.summary_only && takes_stdout(&metrics, flags.with_metrics);
+
+    let audible = |route: Option<ReportRoute>| {
+        route.filter(|route| !flags.silent || matches!(route, ReportRoute::File(_)))
+    };
+    ReportRouting {
+        stats: audible(stats),
+        metrics: audible(metrics),
+        suppress_events: stats_only || metrics_only || flags.summary_only,
+        data_only: stats_only || metrics_only,
+    }
+}
+
+/// How a prefix is cut off each line before parsing
+#[derive(Debug, Clone)]
+pub enum PrefixStrip {
+    /// The first N characters (--strip-prefix)
+    Width(usize),
+    /// A regex match anchored at the start of the line (--strip-prefix-re)
+    Regex(regex::Regex),
+}
+
 /// Output configuration
 #[derive(Debug, Clone)]
 pub struct OutputConfig {
@@ -65,21 +224,325 @@ pub struct OutputConfig {
     pub wrap: WrapMode,
     pub pretty: bool,
     pub color: ColorMode,
+    /// Palette used when colors are enabled (--color-theme)
+    pub color_theme: crate::cli::ColorTheme,
+    /// Parts of default output to color; empty colors all (--color-parts)
+    pub color_parts: Vec<crate::cli::ColorPart>,
+    /// Highlight JSON held in string field values (--color-json-values)
+    pub color_json_values: bool,
     pub emoji: EmojiMode,
     /// Whether map formatters append a trailing legend
     pub legend: LegendMode,
     pub stats: Option<crate::cli::StatsFormat>,
     pub stats_with_events: bool,
+    /// Where the stats block is written; None when off or silenced
+    pub stats_route: Option<crate::cli::ReportRoute>,
+    /// Emit the processing stats as a final synthetic event (--stats-to-event)
+    pub stats_to_event: bool,
+    /// Report output fields seen with mixed value types (--detect-type-conflicts)
+    pub detect_type_conflicts: bool,
+    /// Unit to render duration values in (--duration-unit)
+    pub duration_unit: Option<crate::cli::DurationUnit>,
     pub metrics: Option<crate::cli::MetricsFormat>,
     pub metrics_with_events: bool,
+    /// Where the metrics report is written; None when off or silenced
+    pub metrics_route: Option<crate::cli::ReportRoute>,
     pub metrics_file: Option<String>,
     pub drain: Option<crate::cli::DrainFormat>,
+    /// Edit-distance clustering of one field (--cluster)
+    pub cluster: Option<crate::cluster::ClusterConfig>,
     pub discover_fields: Option<crate::cli::DiscoverFieldsFormat>,
     pub discover_final: bool,
     pub discover_depth: usize,
     pub mark_gaps: Option<chrono::Duration>,
     /// Timestamp formatting configuration (display-only)
     pub timestamp_formatting: TimestampFormatConfig,
+    /// Schema version for the ECS output format
+    pub ecs_version: crate::cli::EcsVersion,
+    /// Tag for the Fluentd output formats
+    pub fluentd_tag: String,
+    /// Output batching into JSON arrays (--chunk-size / --chunk-size-bytes)
+    pub chunk_size: Option<usize>,
+    pub chunk_size_bytes: Option<usize>,
+    /// Table output: draw box borders (--pretty-tables) and cap cell width
+    pub table_boxed: bool,
+    pub max_field_length: Option<usize>,
+    /// JSON output: flatten nested values, joining keys with this separator
+    pub json_flat: Option<char>,
+    /// ORC output (-F orc): destination file and rows per stripe
+    pub output_file: Option<String>,
+    pub orc_stripe_rows: usize,
+    /// CBOR output (-F cbor): length-prefix each record (--cbor-unframed turns off)
+    pub cbor_framed: bool,
+    /// Empty values dropped from events before formatting
+    pub suppress_empty_fields: EmptyFieldSuppression,
+    /// Array field to fan out into one event per element (--explode)
+    pub explode: Option<ExplodeConfig>,
+    /// Push Prometheus metrics to a Pushgateway at the end (--metrics-endpoint)
+    pub pushgateway: Option<PushgatewayConfig>,
+    /// POST formatted events to an HTTP collector (--output-http)
+    pub http_sink: Option<HttpSinkConfig>,
+}
+
+/// Configuration for streaming events to an HTTP collector
+#[derive(Debug, Clone)]
+pub struct HttpSinkConfig {
+    pub url: String,
+    /// Batch limits used when --chunk-size / --chunk-size-bytes are not given
+    pub batch_size: usize,
+    pub batch_bytes: usize,
+    /// Retries per batch after 429/5xx responses or connection errors
+    pub max_retries: u32,
+}
+
+impl HttpSinkConfig {
+    pub const DEFAULT_BATCH_SIZE: usize = 500;
+    pub const DEFAULT_BATCH_BYTES: usize = 1024 * 1024;
+    pub const DEFAULT_MAX_RETRIES: u32 = 5;
+}
+
+/// Default Pushgateway URL and job for `-F prometheus-pushgateway`
+pub const DEFAULT_PUSHGATEWAY_ENDPOINT: &str = "http://localhost:9091";
+pub const DEFAULT_PUSHGATEWAY_JOB: &str = "kelora";
+
+/// Fields `--parse-durations` converts when `--duration-fields` isn't given
+pub const DEFAULT_DURATION_FIELDS: &[&str] = &["took", "elapsed", "duration", "latency"];
+
+/// Configuration for pushing metrics to a Prometheus Pushgateway
+#[derive(Debug, Clone)]
+pub struct PushgatewayConfig {
+    /// Pushgateway base URL, e.g. `http://localhost:9091`
+    pub endpoint: String,
+    pub job: String,
+    /// Extra grouping labels from --pushgateway-grouping, in CLI order
+    pub grouping: Vec<(String, String)>,
+}
+
+impl PushgatewayConfig {
+    /// Parse a `KEY=VALUE` grouping label
+    pub fn parse_grouping(spec: &str) -> Result<(String, String), String> {
+        let (key, value) = spec.split_once('=').ok_or_else(|| {
+            format!(
+                "invalid --pushgateway-grouping '{}': expected KEY=VALUE, e.g. branch=main",
+                spec
+            )
+        })?;
+        let key = key.trim();
+        let valid_name = key
+            .chars()
+            .enumerate()
+            .all(|(i, c)| c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit()));
+        if key.is_empty() || !valid_name {
+            return Err(format!(
+                "invalid --pushgateway-grouping label name '{}': use letters, digits and underscores",
+                key
+            ));
+        }
+        if key == "job" {
+            return Err(
+                "--pushgateway-grouping cannot set 'job'; use --pushgateway-job instead"
+                    .to_string(),
+            );
+        }
+        Ok((key.to_string(), value.to_string()))
+    }
+}
+
+/// A MaxMind database registered with `--mmdb NAME=PATH`
+#[cfg(feature = "mmdb")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MmdbConfig {
+    /// Name of the Rhai lookup function, e.g. `geo`
+    pub name: String,
+    pub path: std::path::PathBuf,
+}
+
+#[cfg(feature = "mmdb")]
+impl MmdbConfig {
+    /// Parse a `NAME=PATH` spec
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (name, path) = spec.split_once('=').ok_or_else(|| {
+            format!(
+                "invalid --mmdb '{}': expected NAME=PATH, e.g. geo=GeoLite2-City.mmdb",
+                spec
+            )
+        })?;
+        let name = name.trim();
+        let valid_name = name
+            .chars()
+            .enumerate()
+            .all(|(i, c)| c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit()));
+        if name.is_empty() || !valid_name {
+            return Err(format!(
+                "invalid --mmdb function name '{}': use letters, digits and underscores",
+                name
+            ));
+        }
+        if path.is_empty() {
+            return Err(format!("invalid --mmdb '{}': missing database path", spec));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            path: std::path::PathBuf::from(path),
+        })
+    }
+}
+
+/// Configuration for --explode
+#[derive(Debug, Clone)]
+pub struct ExplodeConfig {
+    /// Array field whose elements become separate output events
+    pub field: String,
+    /// Field that receives scalar elements (--explode-as; default: `field`)
+    pub scalar_field: Option<String>,
+    /// Emit the parent unchanged when the array is empty (--explode-keep-empty)
+    pub keep_empty: bool,
+}
+
+impl ExplodeConfig {
+    /// Field name scalar elements are stored under
+    pub fn as_field(&self) -> &str {
+        self.scalar_field.as_deref().unwrap_or(&self.field)
+    }
+}
+
+/// Configuration for --field-expand-json
+#[derive(Debug, Clone)]
+pub struct FieldExpandConfig {
+    /// String fields holding a JSON object to promote into the event
+    pub fields: Vec<String>,
+    /// Prepended to every promoted key (--field-expand-prefix)
+    pub prefix: Option<String>,
+    /// Keep the original string field (--field-expand-keep-original)
+    pub keep_original: bool,
+}
+
+/// One --merge-fields rule: concatenate fields and literals into `target`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeFieldsSpec {
+    pub parts: Vec<MergePart>,
+    pub target: String,
+}
+
+/// A segment of a --merge-fields expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergePart {
+    Field(String),
+    Literal(String),
+}
+
+impl MergeFieldsSpec {
+    /// Parse `PART+PART...=TARGET`, where each PART is a field name or a
+    /// quoted literal (`"..."` or `'...'`, with `\` escaping the next char),
+    /// e.g. `host+":"+port=endpoint`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut chars = spec.chars().peekable();
+        loop {
+            skip_whitespace(&mut chars);
+            match chars.peek() {
+                Some(&quote) if quote == '"' || quote == '\'' => {
+                    chars.next();
+                    let mut literal = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('\\') => {
+                                if let Some(c) = chars.next() {
+                                    literal.push(c);
+                                }
+                            }
+                            Some(c) if c == quote => break,
+                            Some(c) => literal.push(c),
+                            None => {
+                                return Err(format!("unterminated {quote} literal in '{spec}'"))
+                            }
+                        }
+                    }
+                    parts.push(MergePart::Literal(literal));
+                }
+                _ => {
+                    let mut field = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if c == '+' || c == '=' {
+                            break;
+                        }
+                        field.push(c);
+                        chars.next();
+                    }
+                    let field = field.trim();
+                    if field.is_empty() {
+                        return Err(format!("empty field name in '{spec}'"));
+                    }
+                    parts.push(MergePart::Field(field.to_string()));
+                }
+            }
+            skip_whitespace(&mut chars);
+            match chars.next() {
+                Some('+') => continue,
+                Some('=') => break,
+                Some(c) => return Err(format!("expected '+' or '=' before '{c}' in '{spec}'")),
+                None => return Err(format!("missing '=TARGET' in '{spec}'")),
+            }
+        }
+        let target = chars.collect::<String>().trim().to_string();
+        if target.is_empty() {
+            return Err(format!("missing target field after '=' in '{spec}'"));
+        }
+        Ok(Self { parts, target })
+    }
+}
+
+fn skip_whitespace(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Level selection for --level: exact canonical levels plus an optional
+/// "this rank and above" threshold
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LevelSelection {
+    /// Indices into `event::CANONICAL_LEVELS` matched exactly
+    pub exact: Vec<usize>,
+    /// Lowest index kept by a `LEVEL+` entry
+    pub min: Option<usize>,
+}
+
+impl LevelSelection {
+    /// Parse a comma-separated list like `error`, `warn+` or `info,debug`.
+    /// Names go through the same normalization as event levels, so `WARNING`
+    /// and `E` are accepted.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut selection = Self::default();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, at_least) = match entry.strip_suffix('+') {
+                Some(name) => (name.trim(), true),
+                None => (entry, false),
+            };
+            let rank = crate::event::normalize_level(
+                &rhai::Dynamic::from(name.to_string()),
+                crate::event::LevelScheme::Standard,
+            )
+            .filter(|_| name.parse::<i64>().is_err())
+            .ok_or_else(|| {
+                format!(
+                    "unknown level '{name}' (expected one of {})",
+                    crate::event::CANONICAL_LEVELS.join(", ")
+                )
+            })?;
+            if at_least {
+                selection.min = Some(selection.min.map_or(rank, |min| min.min(rank)));
+            } else if !selection.exact.contains(&rank) {
+                selection.exact.push(rank);
+            }
+        }
+        if selection.exact.is_empty() && selection.min.is_none() {
+            return Err("expected at least one level, e.g. --level warn+".to_string());
+        }
+        Ok(selection)
+    }
+
+    pub fn matches(&self, rank: usize) -> bool {
+        self.exact.contains(&rank) || self.min.is_some_and(|min| rank >= min)
+    }
 }
 
 /// Ordered script stages that preserve CLI order
@@ -107,6 +570,12 @@ pub struct IncludeFile {
 #[derive(Debug, Clone)]
 pub struct ErrorReportConfig {
     pub style: ErrorReportStyle,
+    /// Distinct failing lines kept per error category for the summary
+    /// (--on-parse-error-sample); None keeps the default handful
+    pub sample_limit: Option<usize>,
+    /// Omit line text and messages from samples in --metrics=json
+    /// (--redact-error-samples)
+    pub redact_samples: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -160,24 +629,52 @@ pub struct ProcessingConfig {
     pub begin: Option<String>,
     pub stages: Vec<ScriptStageType>,
     pub end: Option<String>,
+    /// Wall-clock limit for the --begin script (--begin-timeout-ms)
+    pub begin_timeout_ms: Option<u64>,
+    /// Wall-clock limit for the --end script (--end-timeout-ms)
+    pub end_timeout_ms: Option<u64>,
+    /// Values passed to --begin/--end scripts as `argv` (--script-arg)
+    pub script_args: Vec<String>,
+    /// Script run for each tracked parse/script error (--exec-on-error)
+    pub exec_on_error: Option<String>,
     pub error_report: ErrorReportConfig,
     pub levels: Vec<String>,
     pub exclude_levels: Vec<String>,
     /// Window size for sliding window functionality (0 = disabled)
     pub window_size: usize,
+    /// Whether each window position is reduced to one event (--window-emit-each)
+    pub window_emit: WindowEmitMode,
     /// Timestamp filtering configuration
     pub timestamp_filter: Option<TimestampFilterConfig>,
     /// Normalize the primary timestamp field to RFC3339 output
     pub normalize_timestamps: bool,
     /// Limit output to the first N events (None = no limit)
     pub take_limit: Option<usize>,
+    /// Stop the stream at the first event failing this predicate (--take-while)
+    pub take_while: Option<String>,
+    /// Flag or drop events whose timestamp goes backwards (--check-monotonic)
+    pub check_monotonic: Option<crate::cli::MonotonicCheck>,
+    /// Merge runs of consecutive events sharing this field's value (--group-consecutive-by)
+    pub group_consecutive_by: Option<String>,
+    /// How merged runs combine numeric fields (--group-aggregate-numeric)
+    pub group_aggregate: Option<crate::cli::GroupAggregate>,
+    /// Skip leading events while this predicate holds (--drop-while)
+    pub drop_while: Option<String>,
+    /// Output a uniform random sample of N events at end of input (--reservoir)
+    pub reservoir: Option<usize>,
     /// Exit on first error (fail-fast behavior) - new resiliency model
     pub strict: bool,
+    /// How --filter/--exec errors are handled (--on-stage-error)
+    pub on_stage_error: Option<crate::cli::StageErrorPolicy>,
     /// Abort on invalid UTF-8 instead of lossy decoding (--strict-utf8). Default
     /// (false) decodes non-UTF-8 input with U+FFFD substitution; see issue #239.
     pub strict_utf8: bool,
     /// Span aggregation configuration (--span / --span-close)
     pub span: Option<SpanConfig>,
+    /// Tumbling metric windows (--every)
+    pub every: Option<EveryConfig>,
+    /// One event per interval (--thin)
+    pub thin: Option<ThinConfig>,
     /// Show detailed error information (levels: 0-3) - new resiliency model
     pub verbose: u8,
     /// Suppress formatter/event output (-q/--quiet, -s, -m)
@@ -204,6 +701,48 @@ pub struct ProcessingConfig {
     pub context: ContextConfig,
     /// Allow Rhai scripts to create directories and write files on disk
     pub allow_fs_writes: bool,
+    /// Run scripts under the sandbox policy (`--sandbox`)
+    pub sandbox: bool,
+    /// MaxMind databases exposed as lookup functions (`--mmdb`), in CLI order
+    #[cfg(feature = "mmdb")]
+    pub mmdb: Vec<MmdbConfig>,
+    /// Time each script stage and report the totals (--pipeline-profile)
+    pub pipeline_profile: bool,
+    /// Also time parse, format and write and report the breakdown (--benchmark)
+    pub benchmark: bool,
+    /// Optional --stage-label for each exec stage, in exec order
+    pub exec_stage_labels: Vec<Option<String>>,
+    /// Trace this many events through the stages instead of normal output (--sample-run)
+    pub sample_run: Option<usize>,
+    /// Compare two file sets instead of normal output (--diff-output)
+    pub diff: Option<DiffMode>,
+    /// Compare per-key counts against baseline files (--diff-against)
+    pub diff_against: Option<DiffAgainst>,
+    /// Prefixes stripped from field names before script stages (--field-prefix-drop)
+    pub field_prefix_drop: Vec<String>,
+    /// How --field-prefix-drop, --field-expand-json, --merge-fields and
+    /// --json-flat resolve a taken name (--collision, or its older form
+    /// --field-prefix-conflict)
+    pub collision: CollisionPolicy,
+    /// Prefix prepended to output field names (--field-prefix-add)
+    pub field_prefix_add: Option<String>,
+    /// JSON string fields expanded into top-level fields (--field-expand-json)
+    pub field_expand: Option<FieldExpandConfig>,
+    /// Named captures taken from each input file's path (--field-from-filename)
+    pub field_from_filename: Option<regex::Regex>,
+    /// Field concatenation rules (--merge-fields)
+    pub merge_fields: Vec<MergeFieldsSpec>,
+    /// Leave the target unset when a source field is missing (--merge-fields-skip-missing)
+    pub merge_fields_skip_missing: bool,
+    /// Fields whose duration-like values are converted (--parse-durations)
+    pub duration_fields: Option<Vec<String>>,
+    /// Native level filter (--level)
+    pub level: Option<LevelSelection>,
+    /// Drop events without a recognizable level under --level (--level-strict)
+    pub level_strict: bool,
+    /// Compiled JSON Schema events must satisfy (--schema-validate)
+    pub schema: Option<crate::pipeline::schema::SchemaValidator>,
+    pub schema_on_fail: crate::cli::SchemaOnFail,
 }
 
 /// Performance configuration
@@ -214,6 +753,9 @@ pub struct PerformanceConfig {
     pub batch_size: Option<usize>,
     pub batch_timeout: u64,
     pub no_preserve_order: bool,
+    /// `--parallel-order-by`: restore this order in the unordered sink
+    pub parallel_order_by: Option<crate::cli::ParallelOrderBy>,
+    pub cache_scripts: bool,
 }
 
 /// Span aggregation mode (--span)
@@ -225,11 +767,74 @@ pub enum SpanMode {
     Idle { timeout_ms: i64 },
 }
 
+/// Which events span aggregation writes (--span-output)
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpanOutput {
+    /// Emit the member events unchanged
+    #[default]
+    Members,
+    /// Suppress member events and emit one summary event per span
+    Summary,
+    /// Emit member events followed by a summary event per span
+    Both,
+}
+
+impl SpanOutput {
+    pub fn emits_members(self) -> bool {
+        matches!(self, SpanOutput::Members | SpanOutput::Both)
+    }
+
+    pub fn emits_summary(self) -> bool {
+        matches!(self, SpanOutput::Summary | SpanOutput::Both)
+    }
+}
+
 /// Span aggregation configuration (--span / --span-close)
 #[derive(Debug, Clone)]
 pub struct SpanConfig {
     pub mode: SpanMode,
     pub close_script: Option<String>,
+    pub output: SpanOutput,
+    pub summary_script: Option<String>,
+    /// Emit a marker event at each span boundary (--span-markers)
+    pub markers: bool,
+}
+
+/// Which clock --every windows follow (--every-clock)
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EveryClock {
+    /// Event timestamps
+    #[default]
+    Event,
+    /// Time each event arrives
+    Wall,
+}
+
+/// Tumbling metric windows (--every)
+#[derive(Debug, Clone)]
+pub struct EveryConfig {
+    pub interval_ms: i64,
+    pub clock: EveryClock,
+    pub allowed_lateness_ms: i64,
+    pub emit_script: Option<String>,
+}
+
+/// Which event represents a --thin interval (--thin-keep)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThinKeep {
+    First,
+    Last,
+    Min(String),
+    Max(String),
+}
+
+/// One event per interval of event time (--thin)
+#[derive(Debug, Clone)]
+pub struct ThinConfig {
+    pub interval_ms: i64,
+    /// Field whose values are thinned separately (--thin-by)
+    pub by: Option<String>,
+    pub keep: ThinKeep,
 }
 
 /// Input format enumeration
@@ -243,17 +848,37 @@ pub enum InputFormat {
     Logfmt,
     Syslog,
     Cef,
+    /// IBM QRadar Log Event Extended Format (LEEF 1.0/2.0)
+    Leef,
     Csv(Option<String>), // Optional field spec with type annotations
     Tsv(Option<String>), // Optional field spec with type annotations
     Csvnh,               // No type annotations (no field names)
     Tsvnh,               // No type annotations (no field names)
     Combined,
+    Haproxy,
     Cols(String),  // Contains the column spec
     Regex(String), // Contains the regex pattern with optional type annotations
+    /// Space-separated columns (`ps`, `netstat`, `df` output); names come from
+    /// `--ssv-header`, otherwise `c1`, `c2`, ...
+    Ssv,
+    /// Fixed character columns; contains the `FIELD:start:end,...` spec
+    FixedWidth(String),
     /// Built-in application-log format adapted from lnav (e.g. log4j, glog). Backed by a
     /// static regex definition; selectable via `-f <name>` and produced by
     /// auto-detection. See `crate::parsers::lnav_formats`.
     Named(&'static crate::parsers::lnav_formats::LnavFormat),
+    /// NetFlow v5/v9 or IPFIX binary capture, decoded to one event per flow record
+    /// by `crate::parsers::NetflowReader` before line splitting.
+    Netflow(crate::parsers::NetflowVersion),
+    /// pcap network capture, decoded to one event per HTTP request/response
+    /// pair by `crate::parsers::PcapReader` before line splitting.
+    Pcap,
+    /// XML document whose root holds one element per record, decoded to one
+    /// event per record by `crate::parsers::XmlRecordsReader` before line splitting.
+    XmlRecords(crate::parsers::XmlRecordsOptions),
+    /// SQLite database file, read as one event per row of a table or query by
+    /// `crate::parsers::SqliteReader` before line splitting.
+    Sqlite(crate::parsers::SqliteOptions),
     /// Cascade: try each format in order, first success wins.
     /// Only contains formats that are safe to try per-line (no CSV/cols/regex/auto).
     Cascade(Vec<InputFormat>),
@@ -271,14 +896,22 @@ impl InputFormat {
             InputFormat::Logfmt => "logfmt".to_string(),
             InputFormat::Syslog => "syslog".to_string(),
             InputFormat::Cef => "cef".to_string(),
+            InputFormat::Leef => "leef".to_string(),
             InputFormat::Csv(_) => "csv".to_string(),
             InputFormat::Tsv(_) => "tsv".to_string(),
             InputFormat::Csvnh => "csvnh".to_string(),
             InputFormat::Tsvnh => "tsvnh".to_string(),
             InputFormat::Combined => "combined".to_string(),
+            InputFormat::Haproxy => "haproxy".to_string(),
             InputFormat::Cols(_) => "cols".to_string(),
             InputFormat::Regex(_) => "regex".to_string(),
+            InputFormat::Ssv => "ssv".to_string(),
+            InputFormat::FixedWidth(_) => "fixed-width".to_string(),
             InputFormat::Named(fmt) => fmt.name.to_string(),
+            InputFormat::Netflow(version) => version.name().to_string(),
+            InputFormat::Pcap => "pcap".to_string(),
+            InputFormat::XmlRecords(_) => "xml-records".to_string(),
+            InputFormat::Sqlite(_) => "sqlite".to_string(),
             InputFormat::Cascade(formats) => {
                 let names: Vec<String> = formats.iter().map(|f| f.to_display_string()).collect();
                 format!("cascade({})", names.join(","))
@@ -286,6 +919,30 @@ impl InputFormat {
         }
     }
 
+    /// The binary export protocol to decode, for the NetFlow/IPFIX formats.
+    pub fn netflow_version(&self) -> Option<crate::parsers::NetflowVersion> {
+        match self {
+            InputFormat::Netflow(version) => Some(*version),
+            _ => None,
+        }
+    }
+
+    /// The record elements to accept, for the XML record export format.
+    pub fn xml_records_options(&self) -> Option<crate::parsers::XmlRecordsOptions> {
+        match self {
+            InputFormat::XmlRecords(options) => Some(options.clone()),
+            _ => None,
+        }
+    }
+
+    /// The table or query to read, for the SQLite format.
+    pub fn sqlite_options(&self) -> Option<crate::parsers::SqliteOptions> {
+        match self {
+            InputFormat::Sqlite(options) => Some(options.clone()),
+            _ => None,
+        }
+    }
+
     /// Returns true if this format is a cascade (multi-format per-line dispatch).
     pub fn is_cascade(&self) -> bool {
         matches!(self, InputFormat::Cascade(_))
@@ -314,7 +971,9 @@ impl InputFormat {
                 | InputFormat::Logfmt
                 | InputFormat::Syslog
                 | InputFormat::Cef
+                | InputFormat::Leef
                 | InputFormat::Combined
+                | InputFormat::Haproxy
                 | InputFormat::Named(_)
         )
     }
@@ -340,14 +999,22 @@ impl InputFormat {
             InputFormat::Logfmt => "logfmt",
             InputFormat::Syslog => "syslog",
             InputFormat::Cef => "cef",
+            InputFormat::Leef => "leef",
             InputFormat::Csv(_) => "csv",
             InputFormat::Tsv(_) => "tsv",
             InputFormat::Csvnh => "csvnh",
             InputFormat::Tsvnh => "tsvnh",
             InputFormat::Combined => "combined",
+            InputFormat::Haproxy => "haproxy",
             InputFormat::Cols(_) => "cols",
             InputFormat::Regex(_) => "regex",
+            InputFormat::Ssv => "ssv",
+            InputFormat::FixedWidth(_) => "fixed-width",
             InputFormat::Named(fmt) => fmt.name,
+            InputFormat::Netflow(version) => version.name(),
+            InputFormat::Pcap => "pcap",
+            InputFormat::XmlRecords(_) => "xml-records",
+            InputFormat::Sqlite(_) => "sqlite",
             InputFormat::Cascade(_) => "cascade",
         }
     }
@@ -368,6 +1035,16 @@ pub enum OutputFormat {
     Tsv,
     Csvnh,
     Tsvnh,
+    Ecs,
+    FluentdJson,
+    FluentdForward,
+    Table,
+    Orc,
+    Cbor,
+    ApacheAccess,
+    NginxAccess,
+    Xml,
+    PrometheusPushgateway,
 }
 
 /// File processing order
@@ -405,6 +1082,17 @@ pub enum LegendMode {
     Never,
 }
 
+/// What the sliding window (--window) produces besides the `window` variable.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum WindowEmitMode {
+    /// Events pass through; scripts only read `window`
+    #[default]
+    Off,
+    /// Each event is replaced by the map `script` returns for its window
+    /// position (--window-emit-each)
+    EachPosition { script: String },
+}
+
 /// Word-wrap mode for the default output format.
 #[derive(Clone, Debug)]
 pub enum WrapMode {
@@ -438,6 +1126,17 @@ pub struct TimestampFormatConfig {
     pub parse_format_hint: Option<String>,
     /// Default timezone hint reused when parsing timestamps for display
     pub parse_timezone_hint: Option<String>,
+    /// Field shown as the leading timestamp column (`--ts-key`); `None` uses
+    /// the first known timestamp field
+    pub column_key: Option<String>,
+    /// Leave the timestamp column out (`--no-ts`)
+    pub hide_column: bool,
+    /// Pad or truncate the timestamp column value to this many characters
+    /// (`--ts-width`)
+    pub column_width: Option<usize>,
+    /// Print the timestamp column as key=value in brief output
+    /// (`--show-key-for-ts`)
+    pub show_column_key: bool,
 }
 
 /// Multi-line event detection configuration
@@ -458,6 +1157,11 @@ pub enum MultilineStrategy {
     Regex { start: String, end: Option<String> },
     /// Read entire input as a single event
     All,
+    /// Records end at lines matching a separator regex; the separator line is
+    /// dropped (`--record-separator`, `-f raw --raw-separator`)
+    Separator { pattern: String },
+    /// Fixed-size records cut from the input stream (`-f raw --raw-bytes`)
+    Bytes { count: usize },
 }
 
 /// How multiline events join buffered lines
@@ -469,6 +1173,132 @@ pub enum MultilineJoin {
     Empty,
 }
 
+/// What the json and logfmt parsers do with a key that repeats within one
+/// event (`--dup-keys`)
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DupKeyPolicy {
+    /// Keep the first value
+    First,
+    /// Keep the last value
+    #[default]
+    Last,
+    /// Keep every value, in order, as an array
+    Collect,
+    /// Reject the event as a parse error
+    Error,
+}
+
+impl DupKeyPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DupKeyPolicy::First => "first",
+            DupKeyPolicy::Last => "last",
+            DupKeyPolicy::Collect => "collect",
+            DupKeyPolicy::Error => "error",
+        }
+    }
+}
+
+/// How a stage that renames or adds fields resolves a name that is already
+/// taken (`--collision`): by --field-prefix-drop, --field-expand-json,
+/// --merge-fields and the --json-flat output
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollisionPolicy {
+    /// The new value replaces the existing one
+    #[default]
+    Last,
+    /// The existing value stays; a renamed field keeps its old name
+    First,
+    /// The new value goes to the first free name of `name_2`, `name_3`, ...
+    Suffix,
+    /// Reject the event as a stage error
+    Error,
+}
+
+impl CollisionPolicy {
+    /// Policy named by the older --field-prefix-conflict spelling
+    pub fn from_prefix_conflict(conflict: crate::cli::FieldPrefixConflict) -> Self {
+        match conflict {
+            crate::cli::FieldPrefixConflict::Overwrite => CollisionPolicy::Last,
+            crate::cli::FieldPrefixConflict::Skip => CollisionPolicy::First,
+        }
+    }
+
+    /// Name for a value headed to `name`, which `is_taken` already. `None`
+    /// means the value is not stored under a new name (`First`).
+    pub fn resolve(
+        self,
+        name: &str,
+        is_taken: impl Fn(&str) -> bool,
+    ) -> Result<Option<String>, String> {
+        match self {
+            CollisionPolicy::Last => Ok(Some(name.to_string())),
+            CollisionPolicy::First => Ok(None),
+            CollisionPolicy::Suffix => Ok((2..)
+                .map(|n| format!("{}_{}", name, n))
+                .find(|candidate| !is_taken(candidate))),
+            CollisionPolicy::Error => Err(format!(
+                "field '{}' already exists (--collision error)",
+                name
+            )),
+        }
+    }
+
+    /// Set `key` in `fields`, resolving an existing key per the policy
+    pub fn insert(
+        self,
+        fields: &mut crate::event::FieldMap,
+        key: String,
+        value: rhai::Dynamic,
+    ) -> Result<(), String> {
+        if !fields.contains_key(&key) {
+            fields.insert(key, value);
+            return Ok(());
+        }
+        if let Some(name) = self.resolve(&key, |name| fields.contains_key(name))? {
+            fields.insert(name, value);
+        }
+        Ok(())
+    }
+}
+
+/// Which empty values are removed from events just before formatting
+/// (`--suppress-empty-fields` and its narrower variants)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmptyFieldSuppression {
+    /// Unit `()` values, output as `null`
+    pub nulls: bool,
+    /// Zero-length strings
+    pub empty_strings: bool,
+    /// Arrays and maps without elements
+    pub empty_collections: bool,
+}
+
+impl EmptyFieldSuppression {
+    pub fn is_active(&self) -> bool {
+        self.nulls || self.empty_strings || self.empty_collections
+    }
+
+    pub fn matches(&self, value: &rhai::Dynamic) -> bool {
+        if value.is_unit() {
+            return self.nulls;
+        }
+        if let Some(s) = value.read_lock::<rhai::ImmutableString>() {
+            return self.empty_strings && s.is_empty();
+        }
+        if !self.empty_collections {
+            return false;
+        }
+        if let Some(arr) = value.read_lock::<rhai::Array>() {
+            return arr.is_empty();
+        }
+        if let Some(map) = value.read_lock::<rhai::Map>() {
+            return map.is_empty();
+        }
+        false
+    }
+}
+
 /// Section selection configuration
 #[derive(Debug, Clone)]
 pub struct SectionConfig {
@@ -657,6 +1487,15 @@ impl KeloraConfig {
         !self.processing.silent && !self.processing.suppress_hints
     }
 
+    /// Whether the stats block reaches the terminal, so details it already
+    /// carries (errors, decode and CSV-shape counts) aren't repeated on stderr
+    pub fn stats_on_terminal(&self) -> bool {
+        matches!(
+            self.output.stats_route,
+            Some(crate::cli::ReportRoute::Stdout | crate::cli::ReportRoute::Stderr)
+        )
+    }
+
     /// Whether *all* advisory output (both warnings and hints) is suppressed —
     /// the legacy `--no-diagnostics` umbrella. Used to gate informational output
     /// (config expansion) and per-line verbose error detail.
@@ -705,6 +1544,27 @@ impl KeloraConfig {
         }
     }
 
+    /// Format the --pipeline-profile report with its "⏱️ Pipeline profile:" header
+    pub fn format_profile_message(&self, message: &str) -> String {
+        self.format_timing_message("Pipeline profile", message)
+    }
+
+    /// Format the --benchmark breakdown with the same prefix as the profile
+    pub fn format_benchmark_message(&self, message: &str) -> String {
+        self.format_timing_message("Benchmark", message)
+    }
+
+    fn format_timing_message(&self, title: &str, message: &str) -> String {
+        let use_emoji =
+            crate::tty::should_use_emoji_with_mode(&self.output.emoji, &self.output.color);
+
+        if use_emoji {
+            format!("\n⏱️ {}:\n{}", title, message)
+        } else {
+            format!("\nkelora: {}:\n{}", title, message)
+        }
+    }
+
     /// Format a metrics message with appropriate prefix (emoji or "Metrics:")
     /// If `with_header` is true, includes the "📊 Tracked metrics:" header
     pub fn format_metrics_message(&self, message: &str, with_header: bool) -> String {
@@ -979,7 +1839,7 @@ impl KeloraConfig {
         let tz_from_env = std::env::var("TZ")
             .map(|tz| !tz.is_empty())
             .unwrap_or(false);
-        let timezone_assumed = cli.input_tz.is_none() && !tz_from_env;
+        let timezone_assumed = cli.input_tz.is_none() && cli.tz_field.is_none() && !tz_from_env;
         let mut quiet_events = cli.quiet;
         // Advisory tiers: warnings (🔸) and hints (💡). Each resolves independently
         // with precedence: explicit per-tier flag > --diagnostics/--no-diagnostics
@@ -1042,29 +1902,32 @@ impl KeloraConfig {
         let include_levels = flatten_levels(&cli.levels);
         let exclude_levels = flatten_levels(&cli.exclude_levels);
 
-        // Stats logic: determine format and whether events should be shown
-        // Check no_stats first to handle flag conflicts
+        // Stats and metrics: pick the formats here, then let
+        // resolve_report_routing decide where they go and whether they replace
+        // the events. Check no_stats/no_metrics first to handle flag conflicts.
         let stats_format = if cli.no_stats {
             None
-        } else if cli.stats.is_some() {
-            cli.stats.clone()
+        } else if let Some(ref spec) = cli.stats {
+            Some(spec.format.clone())
         } else if cli.with_stats {
             Some(crate::cli::StatsFormat::Table)
         } else {
             None
         };
         let stats_with_events = cli.with_stats;
-        let suppress_events_for_stats = stats_format.is_some() && !stats_with_events;
 
-        // Metrics logic: determine format and whether events should be shown
-        // Check no_metrics first to handle flag conflicts
         let has_metric_sugar =
             !cli.freq.is_empty() || !cli.describe.is_empty() || !cli.card.is_empty();
         let metrics_format = if cli.no_metrics {
             None
-        } else if cli.metrics.is_some() {
-            cli.metrics.clone()
-        } else if cli.with_metrics {
+        } else if let Some(ref spec) = cli.metrics {
+            Some(spec.format.clone())
+        } else if matches!(
+            cli.output_format,
+            crate::OutputFormat::PrometheusPushgateway
+        ) {
+            Some(crate::cli::MetricsFormat::Prometheus)
+        } else if cli.with_metrics || cli.output_summary_only {
             Some(crate::cli::MetricsFormat::Auto)
         } else if has_metric_sugar {
             // --freq/--describe synthesize tracking; default to the auto view
@@ -1075,25 +1938,44 @@ impl KeloraConfig {
             None
         };
         let metrics_with_events = cli.with_metrics;
-        let suppress_events_for_metrics = metrics_format.is_some() && !metrics_with_events;
-        let suppress_events_for_drain = cli.drain.is_some();
+
+        let routing = resolve_report_routing(&ReportFlags {
+            stats: stats_format.is_some(),
+            stats_route: cli.stats.as_ref().and_then(|spec| spec.route.clone()),
+            with_stats: cli.with_stats,
+            metrics: metrics_format.is_some(),
+            metrics_route: cli.metrics.as_ref().and_then(|spec| spec.route.clone()),
+            with_metrics: cli.with_metrics,
+            summary_only: cli.output_summary_only,
+            silent,
+        });
+        let suppress_events_for_reports = routing.suppress_events;
+        if suppress_events_for_reports {
+            quiet_events = true;
+        }
+        let suppress_events_for_drain = cli.drain.is_some() || cli.cluster.is_some();
         let discover_fields = cli
             .discover_fields
             .clone()
             .or(cli.discover_final_fields.clone());
         let suppress_events_for_discover = discover_fields.is_some();
 
-        // Combine suppressions from stats/metrics data-only modes
-        if suppress_events_for_stats
-            || suppress_events_for_metrics
-            || suppress_events_for_drain
-            || suppress_events_for_discover
-        {
+        // Combine suppressions from the other data-only modes
+        if suppress_events_for_drain || suppress_events_for_discover {
             quiet_events = true;
         }
 
         let output_format = if cli.json_output {
             OutputFormat::Json
+        } else if cli.pretty_tables {
+            OutputFormat::Table
+        } else if cli.json_flat.is_some() {
+            OutputFormat::Json
+        } else if cli.output_http.is_some()
+            && matches!(cli.output_format, crate::OutputFormat::Default)
+        {
+            // Collectors expect JSON, so the HTTP sink defaults to it
+            OutputFormat::Json
         } else {
             cli.output_format.clone().into()
         };
@@ -1105,12 +1987,8 @@ impl KeloraConfig {
         // recovered exec errors — that a stuck user needs to see (#239). Hide
         // them explicitly with --no-warnings or --silent. An explicit
         // --hints/--diagnostics re-enables hints even in these modes.
-        let data_only_mode = suppress_events_for_metrics
-            || suppress_events_for_drain
-            || suppress_events_for_discover;
-        if suppress_events_for_stats {
-            suppress_script_output = true;
-        }
+        let data_only_mode =
+            routing.data_only || suppress_events_for_drain || suppress_events_for_discover;
         if data_only_mode {
             if !force_show_hints {
                 suppress_hints = true;
@@ -1124,6 +2002,36 @@ impl KeloraConfig {
 
         let metrics_file = cli.metrics_file.clone();
 
+        // -F prometheus-pushgateway fills in the endpoint and job; an explicit
+        // --metrics-endpoint/--pushgateway-job still wins.
+        let pushgateway_shorthand = matches!(
+            cli.output_format,
+            crate::OutputFormat::PrometheusPushgateway
+        );
+        let pushgateway = if matches!(metrics_format, Some(crate::cli::MetricsFormat::Prometheus)) {
+            let endpoint = cli.metrics_endpoint.clone().or_else(|| {
+                pushgateway_shorthand.then(|| DEFAULT_PUSHGATEWAY_ENDPOINT.to_string())
+            });
+            let job = cli
+                .pushgateway_job
+                .clone()
+                .or_else(|| pushgateway_shorthand.then(|| DEFAULT_PUSHGATEWAY_JOB.to_string()));
+            match (endpoint, job) {
+                (Some(endpoint), Some(job)) => Some(PushgatewayConfig {
+                    endpoint,
+                    job,
+                    grouping: cli
+                        .pushgateway_grouping
+                        .iter()
+                        .filter_map(|spec| PushgatewayConfig::parse_grouping(spec).ok())
+                        .collect(),
+                }),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
         // The legacy "all advisory suppressed" umbrella, used for derived quiet
         // levels and per-line verbose error detail.
         let diagnostics_suppressed = suppress_warnings && suppress_hints;
@@ -1143,6 +2051,7 @@ impl KeloraConfig {
         Ok(Self {
             input: InputConfig {
                 files: cli.files.clone(),
+                files_skipped_by_name: Vec::new(),
                 no_input: cli.no_input,
                 format: if cli.json_input {
                     InputFormat::Json
@@ -1151,6 +2060,30 @@ impl KeloraConfig {
                 },
                 file_order: cli.file_order.clone().into(),
                 merge_ts: cli.merge_ts,
+                follow_glob: cli.follow_glob.clone(),
+                input_cmds: cli.input_cmds.clone(),
+                follow: cli.follow,
+                read_buffer: match &cli.read_buffer {
+                    Some(s) => match crate::byte_size::parse_byte_size(s)
+                        .map_err(|e| anyhow::anyhow!("--read-buffer: {e}"))?
+                    {
+                        0 => anyhow::bail!("--read-buffer: size must be greater than zero"),
+                        n => n,
+                    },
+                    None => crate::readers::DEFAULT_READ_BUFFER_SIZE,
+                },
+                mmap: cli.mmap,
+                pipeline_record: cli.pipeline_record.clone(),
+                pipeline_replay: cli.pipeline_replay.clone(),
+                fallback_format: cli
+                    .fallback_format
+                    .as_deref()
+                    .map(parse_fallback_format)
+                    .transpose()?,
+                detect_lines: match cli.detect_lines {
+                    Some(0) => anyhow::bail!("--detect-lines: N must be greater than zero"),
+                    n => n,
+                },
                 skip_lines: cli.skip_lines.unwrap_or(0),
                 head_lines: cli.head,
                 section: None,      // Will be set after CLI parsing
@@ -1160,10 +2093,27 @@ impl KeloraConfig {
                 ts_field: cli.ts_field.clone(),
                 ts_format: cli.ts_format.clone(),
                 default_timezone: default_timezone.clone(),
+                tz_field: cli.tz_field.clone(),
+                assume_year: cli.assume_year,
                 timezone_assumed,
                 extract_prefix: cli.extract_prefix.clone(),
                 prefix_sep: cli.prefix_sep.clone(),
+                // The regex form is compiled after CLI parsing
+                strip_prefix: cli.strip_prefix.map(PrefixStrip::Width),
+                prefix_field: cli.prefix_field.clone(),
                 cols_sep: cli.cols_sep.clone(),
+                ssv_header: match &cli.ssv_header {
+                    Some(header) => {
+                        crate::parsers::SsvParser::new(Some(header.clone()))
+                            .map_err(|e| anyhow::anyhow!("--ssv-header: {}", e))?;
+                        Some(header.clone())
+                    }
+                    None => None,
+                },
+                keep_rest: cli.keep_rest,
+                json_numbers_as_strings: cli.json_numbers_as_strings,
+                strict_json: cli.strict_json,
+                dup_keys: cli.dup_keys,
                 max_line_bytes: match &cli.max_line_bytes {
                     Some(s) => crate::byte_size::parse_byte_size(s)
                         .map_err(|e| anyhow::anyhow!("--max-line-bytes: {e}"))?,
@@ -1187,14 +2137,23 @@ impl KeloraConfig {
                 },
                 pretty: cli.expand_nested,
                 color: color_mode,
+                color_theme: cli.color_theme,
+                color_parts: cli.color_parts.clone(),
+                color_json_values: cli.color_json_values,
                 emoji: emoji_mode,
                 legend: legend_mode,
                 stats: stats_format,
                 stats_with_events,
+                stats_route: routing.stats,
+                stats_to_event: cli.stats_to_event,
+                detect_type_conflicts: cli.detect_type_conflicts,
+                duration_unit: cli.duration_unit,
                 metrics: metrics_format,
                 metrics_with_events,
+                metrics_route: routing.metrics,
                 metrics_file,
                 drain: cli.drain.clone(),
+                cluster: cli.cluster.clone(),
                 discover_fields,
                 discover_final: cli.discover_final_fields.is_some(),
                 discover_depth: cli
@@ -1202,20 +2161,81 @@ impl KeloraConfig {
                     .unwrap_or(crate::field_discovery::DEFAULT_FLATTEN_DEPTH),
                 mark_gaps: None,
                 timestamp_formatting: create_timestamp_format_config(cli, default_timezone.clone()),
+                ecs_version: cli.ecs_version,
+                fluentd_tag: cli.fluentd_tag.clone(),
+                chunk_size: cli.chunk_size,
+                chunk_size_bytes: cli.chunk_size_bytes,
+                table_boxed: cli.pretty_tables,
+                max_field_length: cli.max_field_length,
+                json_flat: cli.json_flat,
+                output_file: cli.output_file.clone(),
+                orc_stripe_rows: cli.orc_stripe_rows,
+                cbor_framed: !cli.cbor_unframed,
+                suppress_empty_fields: EmptyFieldSuppression {
+                    nulls: cli.suppress_empty_fields || cli.suppress_null_fields,
+                    empty_strings: cli.suppress_empty_fields || cli.suppress_empty_string_fields,
+                    empty_collections: cli.suppress_empty_fields,
+                },
+                pushgateway,
+                http_sink: cli.output_http.clone().map(|url| HttpSinkConfig {
+                    url,
+                    batch_size: HttpSinkConfig::DEFAULT_BATCH_SIZE,
+                    batch_bytes: HttpSinkConfig::DEFAULT_BATCH_BYTES,
+                    max_retries: cli.output_http_retries,
+                }),
+                explode: cli.explode.clone().map(|field| ExplodeConfig {
+                    field,
+                    scalar_field: cli.explode_as.clone(),
+                    keep_empty: cli.explode_keep_empty,
+                }),
             },
             processing: ProcessingConfig {
                 begin: cli.begin.clone(),
                 stages: Vec::new(), // Will be set by main() after CLI parsing
                 end: cli.end.clone(),
+                begin_timeout_ms: cli.begin_timeout_ms,
+                end_timeout_ms: cli.end_timeout_ms,
+                script_args: cli.script_args.clone(),
+                exec_on_error: match (&cli.exec_on_error, &cli.exec_on_error_from) {
+                    (Some(script), _) => Some(script.clone()),
+                    (None, Some(path)) => Some(std::fs::read_to_string(path).map_err(|e| {
+                        anyhow::anyhow!("Failed to read --exec-on-error-from '{}': {}", path, e)
+                    })?),
+                    (None, None) => None,
+                },
                 error_report: parse_error_report_config(cli),
                 levels: include_levels,
                 exclude_levels,
                 span: parse_span_config(cli)?,
+                every: parse_every_config(cli)?,
+                thin: parse_thin_config(cli)?,
                 window_size: cli.window_size.unwrap_or(0),
+                window_emit: match (&cli.window_script, &cli.window_emit_each_from) {
+                    (Some(script), _) => WindowEmitMode::EachPosition {
+                        script: script.clone(),
+                    },
+                    (None, Some(path)) => WindowEmitMode::EachPosition {
+                        script: std::fs::read_to_string(path).map_err(|e| {
+                            anyhow::anyhow!(
+                                "Failed to read --window-emit-each-from '{}': {}",
+                                path,
+                                e
+                            )
+                        })?,
+                    },
+                    (None, None) => WindowEmitMode::Off,
+                },
                 timestamp_filter: None, // Will be set in main() after parsing since/until
                 normalize_timestamps: cli.normalize_ts,
                 take_limit: cli.take,
+                take_while: cli.take_while.clone(),
+                check_monotonic: cli.check_monotonic,
+                group_consecutive_by: cli.group_consecutive_by.clone(),
+                group_aggregate: cli.group_aggregate,
+                drop_while: cli.drop_while.clone(),
+                reservoir: cli.reservoir,
                 strict: cli.strict,
+                on_stage_error: cli.on_stage_error,
                 strict_utf8: cli.strict_utf8,
                 verbose: verbose_level,
                 quiet_events,
@@ -1227,6 +2247,71 @@ impl KeloraConfig {
                 quiet_level,
                 context: create_context_config(cli)?,
                 allow_fs_writes: cli.allow_fs_writes,
+                sandbox: cli.sandbox,
+                #[cfg(feature = "mmdb")]
+                mmdb: parse_mmdb_specs(&cli.mmdb)?,
+                pipeline_profile: cli.pipeline_profile,
+                benchmark: cli.benchmark,
+                exec_stage_labels: Vec::new(), // Will be set by main() after CLI parsing
+                sample_run: cli.sample_run,
+                diff: cli.diff_output.then(|| DiffMode {
+                    left: cli.diff_left.clone(),
+                    right: cli.diff_right.clone(),
+                    keys: cli.diff_key.clone(),
+                    show_common: cli.diff_show_common,
+                }),
+                diff_against: (!cli.diff_against.is_empty()).then(|| DiffAgainst {
+                    baseline: cli.diff_against.clone(),
+                    key_expr: cli.diff_by.clone().unwrap_or_default(),
+                    threshold_pct: cli.diff_threshold.unwrap_or(DEFAULT_DIFF_THRESHOLD_PCT),
+                }),
+                field_prefix_drop: cli.field_prefix_drop.clone(),
+                collision: cli
+                    .collision
+                    .or(cli
+                        .field_prefix_conflict
+                        .map(CollisionPolicy::from_prefix_conflict))
+                    .unwrap_or_default(),
+                field_prefix_add: cli.field_prefix_add.clone(),
+                field_expand: if cli.field_expand_json.is_empty() {
+                    None
+                } else {
+                    Some(FieldExpandConfig {
+                        fields: cli.field_expand_json.clone(),
+                        prefix: cli.field_expand_prefix.clone(),
+                        keep_original: cli.field_expand_keep_original,
+                    })
+                },
+                field_from_filename: None, // Will be set after CLI parsing
+                merge_fields: cli
+                    .merge_fields
+                    .iter()
+                    .map(|spec| {
+                        MergeFieldsSpec::parse(spec)
+                            .map_err(|e| anyhow::anyhow!("--merge-fields: {e}"))
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+                merge_fields_skip_missing: cli.merge_fields_skip_missing,
+                duration_fields: cli.parse_durations.then(|| {
+                    if cli.duration_fields.is_empty() {
+                        DEFAULT_DURATION_FIELDS
+                            .iter()
+                            .map(|field| field.to_string())
+                            .collect()
+                    } else {
+                        cli.duration_fields.clone()
+                    }
+                }),
+                level: cli
+                    .level
+                    .as_deref()
+                    .map(|spec| {
+                        LevelSelection::parse(spec).map_err(|e| anyhow::anyhow!("--level: {e}"))
+                    })
+                    .transpose()?,
+                level_strict: cli.level_strict,
+                schema: None, // Will be set after CLI parsing
+                schema_on_fail: cli.schema_on_fail,
             },
             performance: PerformanceConfig {
                 parallel: cli.parallel,
@@ -1234,6 +2319,8 @@ impl KeloraConfig {
                 batch_size: cli.batch_size,
                 batch_timeout: cli.batch_timeout,
                 no_preserve_order: cli.no_preserve_order,
+                parallel_order_by: cli.parallel_order_by,
+                cache_scripts: cli.cache_scripts,
             },
         })
     }
@@ -1251,11 +2338,17 @@ impl KeloraConfig {
         // case the window only ever holds the current event, so parallel and
         // sequential agree and there is nothing to protect.
         if self.processing.span.is_some()
+            || self.processing.every.is_some()
+            || self.processing.thin.is_some()
             || self.processing.window_size > 0
             || self.processing.context.is_active()
         {
             return false;
         }
+        // Recording and replay follow the one sequential read order
+        if self.input.pipeline_record.is_some() || self.input.pipeline_replay.is_some() {
+            return false;
+        }
         self.performance.parallel
             || self.performance.threads > 0
             || self.performance.batch_size.is_some()
@@ -1281,10 +2374,20 @@ impl Default for KeloraConfig {
         Self {
             input: InputConfig {
                 files: Vec::new(),
+                files_skipped_by_name: Vec::new(),
                 no_input: false,
                 format: InputFormat::Auto,
                 file_order: FileOrder::Cli,
                 merge_ts: false,
+                follow_glob: None,
+                input_cmds: Vec::new(),
+                follow: false,
+                read_buffer: crate::readers::DEFAULT_READ_BUFFER_SIZE,
+                mmap: false,
+                pipeline_record: None,
+                pipeline_replay: None,
+                detect_lines: None,
+                fallback_format: None,
                 skip_lines: 0,
                 head_lines: None,
                 section: None,
@@ -1294,10 +2397,19 @@ impl Default for KeloraConfig {
                 ts_field: None,
                 ts_format: None,
                 default_timezone: None,
+                tz_field: None,
+                assume_year: None,
                 timezone_assumed: false,
                 extract_prefix: None,
                 prefix_sep: "|".to_string(),
+                strip_prefix: None,
+                prefix_field: None,
                 cols_sep: None,
+                ssv_header: None,
+                keep_rest: false,
+                json_numbers_as_strings: false,
+                strict_json: false,
+                dup_keys: DupKeyPolicy::Last,
                 max_line_bytes: DEFAULT_MAX_LINE_BYTES,
             },
             output: OutputConfig {
@@ -1309,35 +2421,74 @@ impl Default for KeloraConfig {
                 wrap: WrapMode::Auto,
                 pretty: false,
                 color: ColorMode::Auto,
+                color_theme: crate::cli::ColorTheme::default(),
+                color_parts: Vec::new(),
+                color_json_values: false,
                 emoji: EmojiMode::Auto,
                 legend: LegendMode::Auto,
                 stats: None,
                 stats_with_events: false,
+                stats_route: None,
+                stats_to_event: false,
+                detect_type_conflicts: false,
+                duration_unit: None,
                 metrics: None,
                 metrics_with_events: false,
+                metrics_route: None,
                 metrics_file: None,
                 drain: None,
+                cluster: None,
                 discover_fields: None,
                 discover_final: false,
                 discover_depth: crate::field_discovery::DEFAULT_FLATTEN_DEPTH,
                 mark_gaps: None,
                 timestamp_formatting: TimestampFormatConfig::default(),
+                ecs_version: crate::cli::EcsVersion::default(),
+                fluentd_tag: "kelora".to_string(),
+                chunk_size: None,
+                chunk_size_bytes: None,
+                table_boxed: false,
+                max_field_length: None,
+                json_flat: None,
+                output_file: None,
+                orc_stripe_rows: crate::formatters::DEFAULT_ORC_STRIPE_ROWS,
+                cbor_framed: true,
+                suppress_empty_fields: EmptyFieldSuppression::default(),
+                explode: None,
+                pushgateway: None,
+                http_sink: None,
             },
             processing: ProcessingConfig {
                 begin: None,
                 stages: Vec::new(),
                 end: None,
+                begin_timeout_ms: None,
+                end_timeout_ms: None,
+                script_args: Vec::new(),
+                exec_on_error: None,
                 error_report: ErrorReportConfig {
                     style: ErrorReportStyle::Summary,
+                    sample_limit: None,
+                    redact_samples: false,
                 },
                 span: None,
+                every: None,
+                thin: None,
                 levels: Vec::new(),
                 exclude_levels: Vec::new(),
                 window_size: 0,
+                window_emit: WindowEmitMode::Off,
                 timestamp_filter: None,
                 normalize_timestamps: false,
                 take_limit: None,
+                take_while: None,
+                check_monotonic: None,
+                group_consecutive_by: None,
+                group_aggregate: None,
+                drop_while: None,
+                reservoir: None,
                 strict: false,
+                on_stage_error: None,
                 strict_utf8: false,
                 verbose: 0,
                 quiet_events: false,
@@ -1349,6 +2500,27 @@ impl Default for KeloraConfig {
                 quiet_level: 0,
                 context: ContextConfig::disabled(),
                 allow_fs_writes: false,
+                sandbox: false,
+                #[cfg(feature = "mmdb")]
+                mmdb: Vec::new(),
+                pipeline_profile: false,
+                benchmark: false,
+                exec_stage_labels: Vec::new(),
+                sample_run: None,
+                diff: None,
+                diff_against: None,
+                field_prefix_drop: Vec::new(),
+                collision: CollisionPolicy::default(),
+                field_prefix_add: None,
+                field_expand: None,
+                field_from_filename: None,
+                merge_fields: Vec::new(),
+                merge_fields_skip_missing: false,
+                duration_fields: None,
+                level: None,
+                level_strict: false,
+                schema: None,
+                schema_on_fail: crate::cli::SchemaOnFail::default(),
             },
             performance: PerformanceConfig {
                 parallel: false,
@@ -1356,6 +2528,8 @@ impl Default for KeloraConfig {
                 batch_size: None,
                 batch_timeout: 200,
                 no_preserve_order: false,
+                parallel_order_by: None,
+                cache_scripts: false,
             },
         }
     }
@@ -1369,11 +2543,42 @@ impl Default for KeloraConfig {
 /// to put spec-based parsers (`cols:`, `regex:`) into a cascade — commas can't
 /// safely delimit them because a regex pattern may itself contain commas.
 fn parse_input_format_from_cli(cli: &crate::Cli) -> anyhow::Result<InputFormat> {
-    match cli.format.as_slice() {
-        [] => parse_input_format_spec("auto"),
-        [single] => parse_input_format_spec(single),
-        many => parse_repeated_format_specs(many),
+    let mut format = match cli.format.as_slice() {
+        [] => parse_input_format_spec("auto")?,
+        [single] => parse_input_format_spec(single)?,
+        many => parse_repeated_format_specs(many)?,
+    };
+    if cli.xml_root_element.is_some() || cli.xml_record_element.is_some() {
+        let InputFormat::XmlRecords(options) = &mut format else {
+            anyhow::bail!("--xml-root-element and --xml-record-element require -f xml-records");
+        };
+        options.root = cli.xml_root_element.clone();
+        options.record = cli.xml_record_element.clone();
+    }
+    if cli.sqlite_table.is_some() || cli.sqlite_query.is_some() {
+        let InputFormat::Sqlite(options) = &mut format else {
+            anyhow::bail!("--sqlite-table and --sqlite-query require -f sqlite");
+        };
+        options.table = cli.sqlite_table.clone();
+        options.query = cli.sqlite_query.clone();
+    }
+    if matches!(format, InputFormat::Sqlite(_)) {
+        if !cfg!(feature = "sqlite") {
+            anyhow::bail!(
+                "-f sqlite is not available: kelora was built without the `sqlite` feature"
+            );
+        }
+        if cli.sqlite_table.is_none() && cli.sqlite_query.is_none() {
+            anyhow::bail!("-f sqlite requires --sqlite-table TABLE or --sqlite-query SQL");
+        }
+        if cli.files.is_empty() || cli.files.iter().any(|file| file == "-") {
+            anyhow::bail!("-f sqlite reads database files; it cannot read stdin");
+        }
+        if cli.merge_ts {
+            anyhow::bail!("-f sqlite cannot be combined with --merge-ts");
+        }
     }
+    Ok(format)
 }
 
 /// Build a cascade from repeated `-f` specs. Each spec is parsed on its own
@@ -1397,7 +2602,9 @@ fn parse_repeated_format_specs(specs: &[String]) -> anyhow::Result<InputFormat>
             | InputFormat::Logfmt
             | InputFormat::Syslog
             | InputFormat::Cef
+            | InputFormat::Leef
             | InputFormat::Combined
+            | InputFormat::Haproxy
             | InputFormat::Cols(_)
             | InputFormat::Regex(_)
             | InputFormat::Named(_) => {}
@@ -1407,12 +2614,29 @@ fn parse_repeated_format_specs(specs: &[String]) -> anyhow::Result<InputFormat>
                     fmt.cascade_name()
                 ));
             }
-            InputFormat::Csv(_) | InputFormat::Tsv(_) | InputFormat::Csvnh | InputFormat::Tsvnh => {
+            InputFormat::Csv(_)
+            | InputFormat::Tsv(_)
+            | InputFormat::Csvnh
+            | InputFormat::Tsvnh
+            | InputFormat::Ssv
+            | InputFormat::FixedWidth(_) => {
                 return Err(anyhow::anyhow!(
                     "'{}' is a schema-based format and cannot be mixed per-line in a cascade",
                     fmt.cascade_name()
                 ));
             }
+            InputFormat::Netflow(_) | InputFormat::Pcap => {
+                return Err(anyhow::anyhow!(
+                    "'{}' is a binary capture format and cannot be mixed per-line in a cascade",
+                    fmt.cascade_name()
+                ));
+            }
+            InputFormat::XmlRecords(_) | InputFormat::Sqlite(_) => {
+                return Err(anyhow::anyhow!(
+                    "'{}' decodes whole documents and cannot be mixed per-line in a cascade",
+                    fmt.cascade_name()
+                ));
+            }
             InputFormat::Cascade(_) => unreachable!("cascades were flattened above"),
         }
     }
@@ -1448,6 +2672,22 @@ fn validate_cascade_order(formats: &[InputFormat]) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Parse --fallback-format. The fallback retries single lines, so it must be a
+/// format a cascade could hold.
+fn parse_fallback_format(spec: &str) -> anyhow::Result<InputFormat> {
+    let format = parse_input_format_spec(spec)?;
+    if format.is_cascade_eligible()
+        || matches!(format, InputFormat::Cols(_) | InputFormat::Regex(_))
+    {
+        Ok(format)
+    } else {
+        anyhow::bail!(
+            "--fallback-format {}: the fallback parses one line at a time; use json, line, raw, logfmt, syslog, cef, leef, combined, haproxy, a named format, cols:<spec> or regex:<pattern>",
+            spec
+        )
+    }
+}
+
 /// Parse input format specification string (e.g., "cols:ts(2) level - *msg")
 pub(crate) fn parse_input_format_spec(spec: &str) -> anyhow::Result<InputFormat> {
     // Cascade mode: comma-separated list of simple formats.
@@ -1461,6 +2701,10 @@ pub(crate) fn parse_input_format_spec(spec: &str) -> anyhow::Result<InputFormat>
         && !spec.starts_with("csv ")
         && !spec.starts_with("tsv:")
         && !spec.starts_with("tsv ")
+        && !spec.starts_with("fixed-width:")
+        && !spec.starts_with("fixed-width ")
+        && !spec.starts_with("fixed:")
+        && !spec.starts_with("fixed ")
     {
         return parse_cascade_spec(spec);
     }
@@ -1496,6 +2740,15 @@ pub(crate) fn parse_input_format_spec(spec: &str) -> anyhow::Result<InputFormat>
         return Ok(InputFormat::Cols(cols_spec.to_string()));
     }
 
+    // Check for fixed-width format with its column spec (`fixed:` is short
+    // for `fixed-width:`)
+    if let Some(columns) =
+        parse_field_spec(spec, "fixed-width").or_else(|| parse_field_spec(spec, "fixed"))
+    {
+        crate::parsers::FixedWidthParser::new(&columns)?;
+        return Ok(InputFormat::FixedWidth(columns));
+    }
+
     // Check for CSV/TSV variants with optional field specs (only for formats with headers)
     if let Some(field_spec) = parse_field_spec(spec, "csv") {
         return Ok(InputFormat::Csv(Some(field_spec)));
@@ -1514,17 +2767,30 @@ pub(crate) fn parse_input_format_spec(spec: &str) -> anyhow::Result<InputFormat>
         "logfmt" => Ok(InputFormat::Logfmt),
         "syslog" => Ok(InputFormat::Syslog),
         "cef" => Ok(InputFormat::Cef),
+        "leef" => Ok(InputFormat::Leef),
         "csv" => Ok(InputFormat::Csv(None)),
         "tsv" => Ok(InputFormat::Tsv(None)),
         "csvnh" => Ok(InputFormat::Csvnh),
         "tsvnh" => Ok(InputFormat::Tsvnh),
         "combined" => Ok(InputFormat::Combined),
+        "haproxy" => Ok(InputFormat::Haproxy),
+        "ssv" => Ok(InputFormat::Ssv),
+        "fixed-width" | "fixed" => Err(anyhow::anyhow!(
+            "fixed-width format requires a column spec, e.g., 'fixed-width:user:0:8,pid:9:15'"
+        )),
+        "netflow5" | "netflow9" | "ipfix" => Ok(InputFormat::Netflow(
+            crate::parsers::NetflowVersion::from_name(&spec.to_lowercase())
+                .expect("matched NetFlow format name"),
+        )),
+        "pcap" => Ok(InputFormat::Pcap),
+        "xml-records" => Ok(InputFormat::XmlRecords(Default::default())),
+        "sqlite" => Ok(InputFormat::Sqlite(Default::default())),
         other => {
             // Built-in application-log formats (adapted from lnav), e.g. -f log4j
             if let Some(fmt) = crate::parsers::lnav_formats::by_name(other) {
                 return Ok(InputFormat::Named(fmt));
             }
-            Err(anyhow::anyhow!("Unknown input format: '{}'. Supported formats: auto, auto-per-file, json, line, raw, logfmt, syslog, cef, csv, tsv, csvnh, tsvnh, combined, cols:<spec>, regex:<pattern>, or a built-in application-log format ({})", spec, crate::parsers::lnav_formats::names_csv()))
+            Err(anyhow::anyhow!("Unknown input format: '{}'. Supported formats: auto, auto-per-file, json, line, raw, logfmt, syslog, cef, leef, csv, tsv, csvnh, tsvnh, combined, haproxy, ssv, netflow5, netflow9, ipfix, pcap, xml-records, sqlite, cols:<spec>, regex:<pattern>, fixed-width:<spec> (or fixed:<spec>), or a built-in application-log format ({})", spec, crate::parsers::lnav_formats::names_csv()))
         }
     }
 }
@@ -1554,7 +2820,9 @@ fn parse_cascade_spec(spec: &str) -> anyhow::Result<InputFormat> {
             "logfmt" => InputFormat::Logfmt,
             "syslog" => InputFormat::Syslog,
             "cef" => InputFormat::Cef,
+            "leef" => InputFormat::Leef,
             "combined" => InputFormat::Combined,
+            "haproxy" => InputFormat::Haproxy,
             "auto" => {
                 return Err(anyhow::anyhow!(
                     "'auto' is not allowed inside a cascade list; list the formats explicitly"
@@ -1565,12 +2833,24 @@ fn parse_cascade_spec(spec: &str) -> anyhow::Result<InputFormat> {
                     "'auto-per-file' is not allowed inside a cascade list; list the formats explicitly"
                 ));
             }
-            "csv" | "tsv" | "csvnh" | "tsvnh" => {
+            "csv" | "tsv" | "csvnh" | "tsvnh" | "ssv" => {
                 return Err(anyhow::anyhow!(
                     "'{}' is not allowed inside a cascade list (schema-based formats cannot be mixed per-line)",
                     part
                 ));
             }
+            "netflow5" | "netflow9" | "ipfix" | "pcap" => {
+                return Err(anyhow::anyhow!(
+                    "'{}' is not allowed inside a cascade list (binary capture formats cannot be mixed per-line)",
+                    part
+                ));
+            }
+            "xml-records" | "sqlite" => {
+                return Err(anyhow::anyhow!(
+                    "'{}' is not allowed inside a cascade list (whole-document formats cannot be mixed per-line)",
+                    part
+                ));
+            }
             "cols" | "regex" | "cascade" => {
                 return Err(anyhow::anyhow!(
                     "'{}' can't be a member of a comma-separated cascade (a regex pattern may contain commas). Use repeated -f flags instead, e.g. -f json -f 'cols:ts level *msg'",
@@ -1613,11 +2893,21 @@ fn create_timestamp_format_config(
 ) -> TimestampFormatConfig {
     let auto_format_all = cli.format_timestamps_local || cli.format_timestamps_utc;
 
+    let column_key = cli
+        .ts_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(str::to_string);
+
     let mut format_fields = Vec::new();
     if auto_format_all {
-        if let Some(ref ts_field) = cli.ts_field {
-            let trimmed = ts_field.trim();
-            if !trimmed.is_empty() {
+        for field in [cli.ts_field.as_deref(), column_key.as_deref()]
+            .into_iter()
+            .flatten()
+        {
+            let trimmed = field.trim();
+            if !trimmed.is_empty() && !format_fields.iter().any(|f| f == trimmed) {
                 format_fields.push(trimmed.to_string());
             }
         }
@@ -1630,6 +2920,10 @@ fn create_timestamp_format_config(
         format_as_utc,
         parse_format_hint: cli.ts_format.clone(),
         parse_timezone_hint: default_timezone,
+        column_key,
+        hide_column: cli.no_ts,
+        column_width: cli.ts_width,
+        show_column_key: cli.show_key_for_ts,
     }
 }
 
@@ -1642,7 +2936,28 @@ fn parse_error_report_config(cli: &crate::Cli) -> ErrorReportConfig {
         ErrorReportStyle::Summary // Show summary in resilient mode
     };
 
-    ErrorReportConfig { style }
+    ErrorReportConfig {
+        style,
+        sample_limit: cli.on_parse_error_sample,
+        redact_samples: cli.redact_error_samples,
+    }
+}
+
+/// Parse the `--mmdb NAME=PATH` specs, rejecting a name given twice
+#[cfg(feature = "mmdb")]
+fn parse_mmdb_specs(specs: &[String]) -> anyhow::Result<Vec<MmdbConfig>> {
+    let mut databases: Vec<MmdbConfig> = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let database = MmdbConfig::parse(spec).map_err(|e| anyhow::anyhow!(e))?;
+        if databases.iter().any(|d| d.name == database.name) {
+            return Err(anyhow::anyhow!(
+                "--mmdb function name '{}' is given more than once",
+                database.name
+            ));
+        }
+        databases.push(database);
+    }
+    Ok(databases)
 }
 
 /// Create context configuration from CLI arguments
@@ -1707,6 +3022,97 @@ fn determine_default_timezone(cli: &crate::Cli) -> anyhow::Result<Option<String>
     Ok(Some("UTC".to_string()))
 }
 
+fn parse_every_config(cli: &crate::Cli) -> anyhow::Result<Option<EveryConfig>> {
+    let Some(spec) = cli.every.as_deref().map(str::trim) else {
+        return Ok(None);
+    };
+
+    let parse_ms = |flag: &str, value: &str| -> anyhow::Result<i64> {
+        let duration = humantime::parse_duration(value).map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid {} duration '{}': {}. Use formats like 30s, 5m, 1h.",
+                flag,
+                value,
+                e
+            )
+        })?;
+        duration
+            .as_millis()
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("{} duration is too large", flag))
+    };
+
+    let interval_ms = parse_ms("--every", spec)?;
+    if interval_ms == 0 {
+        return Err(anyhow::anyhow!(
+            "--every duration must be greater than zero"
+        ));
+    }
+    let allowed_lateness_ms = match cli.every_allowed_lateness.as_deref() {
+        Some(value) => parse_ms("--every-allowed-lateness", value.trim())?,
+        None => 0,
+    };
+
+    Ok(Some(EveryConfig {
+        interval_ms,
+        clock: cli.every_clock,
+        allowed_lateness_ms,
+        emit_script: cli.every_emit.clone(),
+    }))
+}
+
+fn parse_thin_config(cli: &crate::Cli) -> anyhow::Result<Option<ThinConfig>> {
+    let Some(spec) = cli.thin.as_deref().map(str::trim) else {
+        return Ok(None);
+    };
+
+    let interval_ms: i64 = humantime::parse_duration(spec)
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid --thin duration '{}': {}. Use formats like 30s, 5m, 1h.",
+                spec,
+                e
+            )
+        })?
+        .as_millis()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("--thin duration is too large"))?;
+    if interval_ms == 0 {
+        return Err(anyhow::anyhow!("--thin duration must be greater than zero"));
+    }
+
+    // Accept `e.host` as well as `host`, the way the field reads in scripts
+    let field_name = |value: &str| {
+        let value = value.trim();
+        value.strip_prefix("e.").unwrap_or(value).to_string()
+    };
+
+    let keep = match cli.thin_keep.as_deref().map(str::trim) {
+        None | Some("first") => ThinKeep::First,
+        Some("last") => ThinKeep::Last,
+        Some(mode) => match mode.split_once(':') {
+            Some(("min", field)) if !field.trim().is_empty() => ThinKeep::Min(field_name(field)),
+            Some(("max", field)) if !field.trim().is_empty() => ThinKeep::Max(field_name(field)),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Invalid --thin-keep '{}'. Use first, last, min:FIELD or max:FIELD.",
+                    mode
+                ))
+            }
+        },
+    };
+
+    Ok(Some(ThinConfig {
+        interval_ms,
+        by: cli
+            .thin_by
+            .as_deref()
+            .map(field_name)
+            .filter(|field| !field.is_empty()),
+        keep,
+    }))
+}
+
 fn parse_span_config(cli: &crate::Cli) -> anyhow::Result<Option<SpanConfig>> {
     let span_spec = cli
         .span
@@ -1725,9 +3131,25 @@ fn parse_span_config(cli: &crate::Cli) -> anyhow::Result<Option<SpanConfig>> {
                 "--span-close requires --span or --span-idle. Use --span N for fixed-size spans or --span-idle 30s for inactivity-based spans."
             ));
         }
+        if cli.span_output != SpanOutput::Members || cli.span_summary.is_some() {
+            return Err(anyhow::anyhow!(
+                "--span-output and --span-summary require --span or --span-idle."
+            ));
+        }
+        if cli.span_markers {
+            return Err(anyhow::anyhow!(
+                "--span-markers requires --span or --span-idle."
+            ));
+        }
         return Ok(None);
     }
 
+    if cli.span_summary.is_some() && !cli.span_output.emits_summary() {
+        return Err(anyhow::anyhow!(
+            "--span-summary requires --span-output summary or --span-output both."
+        ));
+    }
+
     if span_spec.is_some() && idle_spec.is_some() {
         return Err(anyhow::anyhow!(
             "--span and --span-idle cannot be used together. Use --span N for fixed-size spans or --span-idle 30s for inactivity-based spans."
@@ -1757,6 +3179,9 @@ fn parse_span_config(cli: &crate::Cli) -> anyhow::Result<Option<SpanConfig>> {
         return Ok(Some(SpanConfig {
             mode: SpanMode::Idle { timeout_ms },
             close_script: cli.span_close.clone(),
+            output: cli.span_output,
+            summary_script: cli.span_summary.clone(),
+            markers: cli.span_markers,
         }));
     }
 
@@ -1774,6 +3199,9 @@ fn parse_span_config(cli: &crate::Cli) -> anyhow::Result<Option<SpanConfig>> {
                 events_per_span: count,
             },
             close_script: cli.span_close.clone(),
+            output: cli.span_output,
+            summary_script: cli.span_summary.clone(),
+            markers: cli.span_markers,
         }));
     }
 
@@ -1790,6 +3218,9 @@ fn parse_span_config(cli: &crate::Cli) -> anyhow::Result<Option<SpanConfig>> {
         return Ok(Some(SpanConfig {
             mode: SpanMode::Time { duration_ms },
             close_script: cli.span_close.clone(),
+            output: cli.span_output,
+            summary_script: cli.span_summary.clone(),
+            markers: cli.span_markers,
         }));
     }
 
@@ -1805,6 +3236,9 @@ fn parse_span_config(cli: &crate::Cli) -> anyhow::Result<Option<SpanConfig>> {
             field_name: span_spec.to_string(),
         },
         close_script: cli.span_close.clone(),
+        output: cli.span_output,
+        summary_script: cli.span_summary.clone(),
+        markers: cli.span_markers,
     }))
 }
 
@@ -1830,11 +3264,13 @@ impl From<crate::InputFormat> for InputFormat {
             crate::InputFormat::Logfmt => InputFormat::Logfmt,
             crate::InputFormat::Syslog => InputFormat::Syslog,
             crate::InputFormat::Cef => InputFormat::Cef,
+            crate::InputFormat::Leef => InputFormat::Leef,
             crate::InputFormat::Csv => InputFormat::Csv(None),
             crate::InputFormat::Tsv => InputFormat::Tsv(None),
             crate::InputFormat::Csvnh => InputFormat::Csvnh,
             crate::InputFormat::Tsvnh => InputFormat::Tsvnh,
             crate::InputFormat::Combined => InputFormat::Combined,
+            crate::InputFormat::Haproxy => InputFormat::Haproxy,
             crate::InputFormat::Cols => {
                 // This should not happen since CLI Cols enum has no parameters
                 // But if it does, create an empty spec as fallback
@@ -1860,16 +3296,30 @@ impl From<InputFormat> for crate::InputFormat {
             InputFormat::Logfmt => crate::InputFormat::Logfmt,
             InputFormat::Syslog => crate::InputFormat::Syslog,
             InputFormat::Cef => crate::InputFormat::Cef,
+            InputFormat::Leef => crate::InputFormat::Leef,
             InputFormat::Csv(_) => crate::InputFormat::Csv,
             InputFormat::Tsv(_) => crate::InputFormat::Tsv,
             InputFormat::Csvnh => crate::InputFormat::Csvnh,
             InputFormat::Tsvnh => crate::InputFormat::Tsvnh,
             InputFormat::Combined => crate::InputFormat::Combined,
+            InputFormat::Haproxy => crate::InputFormat::Haproxy,
             InputFormat::Cols(_) => crate::InputFormat::Cols,
             InputFormat::Regex(_) => crate::InputFormat::Regex,
+            // SSV and fixed-width are column layouts; map to Cols in the
+            // (unused) legacy CLI-enum conversion path.
+            InputFormat::Ssv | InputFormat::FixedWidth(_) => crate::InputFormat::Cols,
             // Named formats are regex-backed; map to Regex in the (unused) legacy
             // CLI-enum conversion path.
             InputFormat::Named(_) => crate::InputFormat::Regex,
+            // NetFlow input has no CLI-enum equivalent; the lines its reader
+            // produces are closest to raw text in the (unused) legacy path.
+            InputFormat::Netflow(_) => crate::InputFormat::Raw,
+            // pcap captures are decoded to JSON lines, as are XML record exports.
+            InputFormat::Pcap => crate::InputFormat::Json,
+            // Likewise for XML record exports, whose reader emits JSON lines.
+            InputFormat::XmlRecords(_) => crate::InputFormat::Json,
+            // And SQLite rows, which are read as JSON lines too.
+            InputFormat::Sqlite(_) => crate::InputFormat::Json,
             // Cascade has no direct equivalent in the CLI enum; fall back to Auto
             // for the (unused) legacy conversion path.
             InputFormat::Cascade(_) => crate::InputFormat::Auto,
@@ -1891,6 +3341,16 @@ impl From<crate::OutputFormat> for OutputFormat {
             crate::OutputFormat::Tsv => OutputFormat::Tsv,
             crate::OutputFormat::Csvnh => OutputFormat::Csvnh,
             crate::OutputFormat::Tsvnh => OutputFormat::Tsvnh,
+            crate::OutputFormat::Ecs => OutputFormat::Ecs,
+            crate::OutputFormat::FluentdJson => OutputFormat::FluentdJson,
+            crate::OutputFormat::FluentdForward => OutputFormat::FluentdForward,
+            crate::OutputFormat::Table => OutputFormat::Table,
+            crate::OutputFormat::Orc => OutputFormat::Orc,
+            crate::OutputFormat::Cbor => OutputFormat::Cbor,
+            crate::OutputFormat::ApacheAccess => OutputFormat::ApacheAccess,
+            crate::OutputFormat::NginxAccess => OutputFormat::NginxAccess,
+            crate::OutputFormat::Xml => OutputFormat::Xml,
+            crate::OutputFormat::PrometheusPushgateway => OutputFormat::PrometheusPushgateway,
         }
     }
 }
@@ -1909,6 +3369,16 @@ impl From<OutputFormat> for crate::OutputFormat {
             OutputFormat::Tsv => crate::OutputFormat::Tsv,
             OutputFormat::Csvnh => crate::OutputFormat::Csvnh,
             OutputFormat::Tsvnh => crate::OutputFormat::Tsvnh,
+            OutputFormat::Ecs => crate::OutputFormat::Ecs,
+            OutputFormat::FluentdJson => crate::OutputFormat::FluentdJson,
+            OutputFormat::FluentdForward => crate::OutputFormat::FluentdForward,
+            OutputFormat::Table => crate::OutputFormat::Table,
+            OutputFormat::Orc => crate::OutputFormat::Orc,
+            OutputFormat::Cbor => crate::OutputFormat::Cbor,
+            OutputFormat::ApacheAccess => crate::OutputFormat::ApacheAccess,
+            OutputFormat::NginxAccess => crate::OutputFormat::NginxAccess,
+            OutputFormat::Xml => crate::OutputFormat::Xml,
+            OutputFormat::PrometheusPushgateway => crate::OutputFormat::PrometheusPushgateway,
         }
     }
 }
@@ -2131,6 +3601,56 @@ mod tests {
         assert!(matches!(parsed, InputFormat::Cascade(_)));
     }
 
+    #[test]
+    fn level_selection_parses_exact_and_threshold_entries() {
+        let selection = LevelSelection::parse("warn+").unwrap();
+        assert_eq!(selection.min, Some(3));
+        assert!(selection.matches(3) && selection.matches(5) && !selection.matches(2));
+
+        let selection = LevelSelection::parse("INFO, debug").unwrap();
+        assert_eq!(selection.exact, vec![2, 1]);
+        assert!(!selection.matches(3));
+
+        // Synonyms normalize like event levels do
+        assert_eq!(LevelSelection::parse("warning").unwrap().exact, vec![3]);
+
+        for bad in ["", "loud", "30", "+"] {
+            assert!(LevelSelection::parse(bad).is_err(), "accepted {bad:?}");
+        }
+    }
+
+    #[test]
+    fn merge_fields_spec_parses_fields_literals_and_escapes() {
+        let spec = MergeFieldsSpec::parse(r#"host + ":" + port = endpoint"#).unwrap();
+        assert_eq!(
+            spec,
+            MergeFieldsSpec {
+                parts: vec![
+                    MergePart::Field("host".to_string()),
+                    MergePart::Literal(":".to_string()),
+                    MergePart::Field("port".to_string()),
+                ],
+                target: "endpoint".to_string(),
+            }
+        );
+
+        let spec = MergeFieldsSpec::parse(r#"a+'it\'s = "x"'+b=c"#).unwrap();
+        assert_eq!(
+            spec.parts[1],
+            MergePart::Literal("it's = \"x\"".to_string())
+        );
+
+        for bad in [
+            "host+port",
+            "host+=x",
+            "host=",
+            r#"host+"open=x"#,
+            "+host=x",
+        ] {
+            assert!(MergeFieldsSpec::parse(bad).is_err(), "accepted {bad:?}");
+        }
+    }
+
     #[test]
     fn parse_named_format_by_name() {
         match parse_input_format_spec("log4j").expect("log4j is a named format") {
@@ -2172,4 +3692,116 @@ mod tests {
             "error should list named formats: {msg}"
         );
     }
+
+    #[test]
+    fn test_report_routing_truth_table() {
+        use crate::cli::ReportRoute::{self, File, Stderr, Stdout};
+        let file = || Some(File("report.json".to_string()));
+
+        // One requested report: (destination named in --stats=/--metrics=,
+        // --with-*, --silent) -> (resolved destination, replaces the events)
+        type Row = (Option<ReportRoute>, bool, bool, Option<ReportRoute>, bool);
+        let table: Vec<Row> = vec![
+            (None, false, false, Some(Stdout), true),
+            (None, true, false, Some(Stderr), false),
+            (Some(Stdout), false, false, Some(Stdout), true),
+            (Some(Stdout), true, false, Some(Stdout), false),
+            (Some(Stderr), false, false, Some(Stderr), false),
+            (Some(Stderr), true, false, Some(Stderr), false),
+            (file(), false, false, file(), false),
+            (file(), true, false, file(), false),
+            (None, false, true, None, true),
+            (None, true, true, None, false),
+            (Some(Stdout), false, true, None, true),
+            (Some(Stdout), true, true, None, false),
+            (Some(Stderr), false, true, None, false),
+            (Some(Stderr), true, true, None, false),
+            (file(), false, true, file(), false),
+            (file(), true, true, file(), false),
+        ];
+
+        // A report that wasn't requested has no destination and never
+        // touches the events, whatever else is set
+        let mut rows: Vec<(bool, Row)> = table.iter().cloned().map(|row| (true, row)).collect();
+        for (explicit, with_events, silent, _, _) in &table {
+            rows.push((
+                false,
+                (explicit.clone(), *with_events, *silent, None, false),
+            ));
+        }
+
+        // Every combination of the stats and metrics rows, with and without
+        // --output-summary-only, under a shared --silent
+        for (stats_on, stats_row) in &rows {
+            for (metrics_on, metrics_row) in &rows {
+                if stats_row.2 != metrics_row.2 {
+                    continue;
+                }
+                for summary_only in [false, true] {
+                    let flags = ReportFlags {
+                        stats: *stats_on,
+                        stats_route: stats_row.0.clone(),
+                        with_stats: stats_row.1,
+                        metrics: *metrics_on,
+                        metrics_route: metrics_row.0.clone(),
+                        with_metrics: metrics_row.1,
+                        summary_only,
+                        silent: stats_row.2,
+                    };
+                    let data_only = stats_row.4 || (metrics_row.4 && !summary_only);
+                    assert_eq!(
+                        resolve_report_routing(&flags),
+                        ReportRouting {
+                            stats: stats_row.3.clone(),
+                            metrics: metrics_row.3.clone(),
+                            suppress_events: data_only || summary_only,
+                            data_only,
+                        },
+                        "{:?}",
+                        flags
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stats_only_hushes_hints_but_stderr_stats_do_not() {
+        with_env_lock(&["TZ", "KELORA_NO_HINTS", "KELORA_NO_WARNINGS"], || {
+            std::env::remove_var("KELORA_NO_HINTS");
+            std::env::remove_var("KELORA_NO_WARNINGS");
+
+            let config = KeloraConfig::from_cli(&Cli::parse_from(["kelora", "-s"])).unwrap();
+            assert!(config.processing.quiet_events);
+            assert!(!config.hints_allowed());
+            assert_eq!(
+                config.output.stats_route,
+                Some(crate::cli::ReportRoute::Stdout)
+            );
+
+            let config =
+                KeloraConfig::from_cli(&Cli::parse_from(["kelora", "--stats=json,stderr"]))
+                    .unwrap();
+            assert!(!config.processing.quiet_events);
+            assert!(config.hints_allowed());
+            assert!(matches!(
+                config.output.stats,
+                Some(crate::cli::StatsFormat::Json)
+            ));
+
+            // --silent keeps file destinations only
+            let config = KeloraConfig::from_cli(&Cli::parse_from([
+                "kelora",
+                "--silent",
+                "--metrics=json,file:m.json",
+                "--with-stats",
+            ]))
+            .unwrap();
+            assert_eq!(config.output.stats_route, None);
+            assert_eq!(
+                config.output.metrics_route,
+                Some(crate::cli::ReportRoute::File("m.json".to_string()))
+            );
+        });
+    }
 }
@@ -1,14 +1,21 @@
 #![allow(dead_code)] // Config file helpers for future CLI edits are not exercised by current binary paths
 use anyhow::{anyhow, Context, Result};
+use clap::CommandFactory;
 use std::collections::HashMap;
 use std::env;
 use std::path::{Path, PathBuf};
 
+use crate::cli::Cli;
+use crate::rhai_functions::strings::edit_distance_impl;
+
 /// Configuration file handler for kelora
 #[derive(Default, Debug)]
 pub struct ConfigFile {
     pub defaults: Option<String>,
     pub aliases: HashMap<String, String>,
+    /// Unknown keys and sections, as `file:line: message`; warnings unless
+    /// --strict-config is given
+    pub unknown_entries: Vec<String>,
 }
 
 /// Information about config file loading and expansion
@@ -22,6 +29,52 @@ pub struct ConfigExpansionInfo {
     pub expanded_aliases: Vec<(String, String)>,
 }
 
+/// Root-level keys accepted outside any section.
+const KNOWN_ROOT_KEYS: &[&str] = &["defaults"];
+
+/// Section names accepted in `[...]` headers.
+const KNOWN_SECTIONS: &[&str] = &["aliases"];
+
+/// Return the candidate closest to `name` by edit distance, if it is close
+/// enough to plausibly be a typo (at most 2 edits, or a third of the length
+/// for longer names).
+fn closest_match<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(2) as i64;
+    candidates
+        .into_iter()
+        .map(|candidate| (candidate, edit_distance_impl(name, candidate)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// Match a root key against the CLI's long flags. Users often write a flag
+/// as an INI key (`parallel = true`) when it belongs on the `defaults` line.
+fn closest_cli_flag(key: &str) -> Option<String> {
+    let command = Cli::command();
+    let flags: Vec<&str> = command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set())
+        .filter_map(|arg| arg.get_long())
+        .collect();
+    let key = key.trim_start_matches('-');
+    closest_match(key, flags.iter().copied()).map(str::to_string)
+}
+
+/// Reject values that cannot be split into arguments (e.g. an unbalanced
+/// quote) at load time, pointing at the offending line instead of failing
+/// later when the value is expanded.
+fn check_arg_list(value: &str, what: &str, location: &str) -> Result<()> {
+    shell_words::split(value).map(|_| ()).map_err(|e| {
+        anyhow!(
+            "config error in {}: invalid value for {}: expected a shell-style argument list ({})",
+            location,
+            what,
+            e
+        )
+    })
+}
+
 impl ConfigFile {
     /// Find project-level .kelora.ini by walking up directory tree
     pub fn find_project_config() -> Option<PathBuf> {
@@ -141,14 +194,16 @@ impl ConfigFile {
 
     /// Parse INI content from string.
     ///
-    /// Validation is strict: unknown root keys, unknown sections, and lines
-    /// that are neither a comment, a `[section]` header, nor a `key = value`
-    /// pair are errors that name the file and line. Silently ignoring them
-    /// would let a typo (e.g. `defualts` or `[alias]`) change pipeline
-    /// behavior without any visible signal.
+    /// Lines that are neither a comment, a `[section]` header, nor a
+    /// `key = value` pair are errors that name the file and line. Unknown
+    /// root keys and sections (and the keys inside them) are skipped and
+    /// collected in `unknown_entries` with a suggestion, so a typo such as
+    /// `defualts` or `[alias]` is reported instead of silently changing
+    /// pipeline behavior; see `check_unknown_entries`.
     fn parse_ini_content(content: &str, path: &Path) -> Result<Self> {
         let mut defaults = None;
         let mut aliases = HashMap::new();
+        let mut unknown_entries = Vec::new();
         let mut current_section = String::new();
 
         let loc = |line_no: usize| format!("{}:{}", path.display(), line_no);
@@ -178,15 +233,18 @@ impl ConfigFile {
                     _ => {
                         let hint = if name.eq_ignore_ascii_case("aliases") {
                             " (did you mean '[aliases]'? section names are case-sensitive)"
+                        } else if closest_match(name, KNOWN_SECTIONS.iter().copied()).is_some() {
+                            " (did you mean '[aliases]'?)"
                         } else {
                             " (the only supported section is '[aliases]')"
                         };
-                        return Err(anyhow!(
-                            "config error in {}: unknown section '[{}]'{}",
+                        unknown_entries.push(format!(
+                            "{}: unknown section '[{}]'{}",
                             loc(line_no),
                             name,
                             hint
                         ));
+                        current_section = name.to_string();
                     }
                 }
                 continue;
@@ -214,30 +272,62 @@ impl ConfigFile {
             if current_section.is_empty() {
                 // Root-level configuration: only `defaults` is supported.
                 if key == "defaults" {
+                    check_arg_list(value, "defaults", &loc(line_no))?;
                     defaults = Some(value.to_string());
                 } else {
                     let hint = if key.eq_ignore_ascii_case("defaults") {
                         " (did you mean 'defaults'? keys are case-sensitive)".to_string()
+                    } else if closest_match(key, KNOWN_ROOT_KEYS.iter().copied()).is_some() {
+                        " (did you mean 'defaults'?)".to_string()
+                    } else if let Some(flag) = closest_cli_flag(key) {
+                        format!(
+                            " (CLI options are set through the 'defaults' line; did you mean 'defaults = --{}'?)",
+                            flag
+                        )
                     } else {
                         " (the only supported root key is 'defaults'; put aliases under an '[aliases]' section)".to_string()
                     };
-                    return Err(anyhow!(
-                        "config error in {}: unknown key '{}'{}",
+                    unknown_entries.push(format!(
+                        "{}: unknown key '{}'{}",
                         loc(line_no),
                         key,
                         hint
                     ));
                 }
-            } else {
-                // Inside a known section. Currently only `[aliases]`, whose
-                // keys are user-defined alias names, so any non-empty key is
+            } else if current_section == "aliases" {
+                // Alias names are user-defined, so any non-empty key is
                 // accepted.
-                debug_assert_eq!(current_section, "aliases");
+                check_arg_list(value, &format!("alias '{}'", key), &loc(line_no))?;
                 aliases.insert(key.to_string(), value.to_string());
             }
+            // Keys of an unknown section were reported with its header.
         }
 
-        Ok(Self { defaults, aliases })
+        Ok(Self {
+            defaults,
+            aliases,
+            unknown_entries,
+        })
+    }
+
+    /// Unknown keys and sections as warning lines to print, or an error
+    /// naming all of them under --strict-config
+    pub fn check_unknown_entries(&self, strict: bool) -> Result<Vec<String>> {
+        if strict && !self.unknown_entries.is_empty() {
+            return Err(anyhow!(
+                "{} (--strict-config)",
+                self.unknown_entries
+                    .iter()
+                    .map(|entry| format!("config error in {}", entry))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ));
+        }
+        Ok(self
+            .unknown_entries
+            .iter()
+            .map(|entry| format!("config warning in {}", entry))
+            .collect())
     }
 
     /// Merge two configuration objects, with the second taking precedence
@@ -251,6 +341,11 @@ impl ConfigFile {
                 merged.extend(overlay.aliases);
                 merged
             },
+            unknown_entries: {
+                let mut merged = base.unknown_entries;
+                merged.extend(overlay.unknown_entries);
+                merged
+            },
         }
     }
 
@@ -898,6 +993,7 @@ mod tests {
                 aliases.insert("shared-alias".to_string(), "--user-shared".to_string());
                 aliases
             },
+            unknown_entries: Vec::new(),
         };
 
         let project_config = ConfigFile {
@@ -908,6 +1004,7 @@ mod tests {
                 aliases.insert("shared-alias".to_string(), "--project-shared".to_string());
                 aliases
             },
+            unknown_entries: Vec::new(),
         };
 
         let merged = ConfigFile::merge_configs(user_config, project_config);
@@ -935,6 +1032,7 @@ mod tests {
         let base_config = ConfigFile {
             defaults: Some("--base-defaults".to_string()),
             aliases: HashMap::new(),
+            unknown_entries: Vec::new(),
         };
 
         let overlay_config = ConfigFile {
@@ -944,6 +1042,7 @@ mod tests {
                 aliases.insert("test-alias".to_string(), "--test-value".to_string());
                 aliases
             },
+            unknown_entries: Vec::new(),
         };
 
         let merged = ConfigFile::merge_configs(base_config, overlay_config);
@@ -1040,6 +1139,13 @@ mod tests {
         ConfigFile::load_from_path(&file.path().to_path_buf())
     }
 
+    /// Load under --strict-config, where unknown entries fail the load
+    fn load_str_strict(content: &str) -> Result<ConfigFile> {
+        let config = load_str(content)?;
+        config.check_unknown_entries(true)?;
+        Ok(config)
+    }
+
     #[test]
     fn strict_config_accepts_valid_content() {
         let config = load_str(
@@ -1054,48 +1160,83 @@ mod tests {
         assert_eq!(config.aliases.get("errors"), Some(&"-l error".to_string()));
     }
 
+    /// Unknown-entry messages of a config that otherwise loads
+    fn unknown_entries(content: &str) -> String {
+        load_str(content)
+            .expect("unknown entries are not load errors")
+            .unknown_entries
+            .join("\n")
+    }
+
     #[test]
-    fn strict_config_rejects_unknown_root_key() {
-        let err = load_str("defualts = -f json\n").unwrap_err().to_string();
-        assert!(err.contains("unknown key 'defualts'"), "got: {}", err);
-        assert!(err.contains("'defaults'"), "got: {}", err);
-        assert!(err.contains(":1"), "should name the line, got: {}", err);
+    fn unknown_root_key_is_reported_not_applied() {
+        let config = load_str("defualts = -f json\n").unwrap();
+        assert_eq!(config.defaults, None);
+        let msg = config.unknown_entries.join("\n");
+        assert!(msg.contains("unknown key 'defualts'"), "got: {}", msg);
+        assert!(msg.contains("'defaults'"), "got: {}", msg);
+        assert!(msg.contains(":1"), "should name the line, got: {}", msg);
     }
 
     #[test]
-    fn strict_config_rejects_unknown_section() {
-        let err = load_str("[alias]\nerrors = -l error\n")
-            .unwrap_err()
-            .to_string();
-        assert!(err.contains("unknown section '[alias]'"), "got: {}", err);
-        assert!(err.contains("'[aliases]'"), "got: {}", err);
+    fn unknown_section_is_reported_and_its_keys_skipped() {
+        let config = load_str("[alias]\nerrors = -l error\n[aliases]\nwarn = -l warn\n").unwrap();
+        assert_eq!(config.unknown_entries.len(), 1);
+        let msg = &config.unknown_entries[0];
+        assert!(msg.contains("unknown section '[alias]'"), "got: {}", msg);
+        assert!(msg.contains("'[aliases]'"), "got: {}", msg);
+        assert!(!config.aliases.contains_key("errors"));
+        assert_eq!(config.aliases.get("warn"), Some(&"-l warn".to_string()));
     }
 
     #[test]
-    fn strict_config_hints_case_mismatch() {
-        let key_err = load_str("Defaults = -f json\n").unwrap_err().to_string();
+    fn unknown_entries_hint_case_mismatch() {
+        let key_msg = unknown_entries("Defaults = -f json\n");
         assert!(
-            key_err.contains("did you mean 'defaults'") && key_err.contains("case-sensitive"),
+            key_msg.contains("did you mean 'defaults'") && key_msg.contains("case-sensitive"),
             "got: {}",
-            key_err
+            key_msg
         );
 
-        let section_err = load_str("[Aliases]\nerrors = -l error\n")
-            .unwrap_err()
-            .to_string();
+        let section_msg = unknown_entries("[Aliases]\nerrors = -l error\n");
         assert!(
-            section_err.contains("did you mean '[aliases]'")
-                && section_err.contains("case-sensitive"),
+            section_msg.contains("did you mean '[aliases]'")
+                && section_msg.contains("case-sensitive"),
             "got: {}",
-            section_err
+            section_msg
         );
     }
 
     #[test]
-    fn strict_config_rejects_truly_unknown_section() {
-        let err = load_str("[settings]\nfoo = bar\n").unwrap_err().to_string();
-        assert!(err.contains("unknown section '[settings]'"), "got: {}", err);
-        assert!(err.contains("'[aliases]'"), "got: {}", err);
+    fn truly_unknown_section_names_the_supported_one() {
+        let msg = unknown_entries("[settings]\nfoo = bar\n");
+        assert!(msg.contains("unknown section '[settings]'"), "got: {}", msg);
+        assert!(msg.contains("'[aliases]'"), "got: {}", msg);
+    }
+
+    #[test]
+    fn unknown_entries_warn_by_default_and_fail_under_strict_config() {
+        let config = load_str("defaults = -f json\nparalell = true\n[alias]\n").unwrap();
+
+        let warnings = config.check_unknown_entries(false).unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(
+            warnings[0].starts_with("config warning in ") && warnings[0].contains("'paralell'"),
+            "got: {:?}",
+            warnings
+        );
+
+        let err = config.check_unknown_entries(true).unwrap_err().to_string();
+        assert!(err.contains("config error in "), "got: {}", err);
+        assert!(
+            err.contains("'paralell'") && err.contains("'[alias]'"),
+            "got: {}",
+            err
+        );
+        assert!(err.contains("--strict-config"), "got: {}", err);
+
+        let clean = load_str("defaults = -f json\n").unwrap();
+        assert!(clean.check_unknown_entries(true).unwrap().is_empty());
     }
 
     #[test]
@@ -1112,4 +1253,54 @@ mod tests {
         let err = load_str("[aliases]\n= -l error\n").unwrap_err().to_string();
         assert!(err.contains("missing key before '='"), "got: {}", err);
     }
+
+    #[test]
+    fn strict_config_suggests_nearest_key_and_section() {
+        let key_err = load_str_strict("defauts = -f json\n")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            key_err.contains("did you mean 'defaults'?"),
+            "got: {}",
+            key_err
+        );
+
+        let section_err = load_str_strict("[aliasses]\nerrors = -l error\n")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            section_err.contains("did you mean '[aliases]'?"),
+            "got: {}",
+            section_err
+        );
+    }
+
+    #[test]
+    fn strict_config_suggests_defaults_line_for_cli_flag_key() {
+        let err = load_str_strict("defaults = -f json\nparalell = true\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown key 'paralell'"), "got: {}", err);
+        assert!(err.contains("defaults = --parallel"), "got: {}", err);
+        assert!(err.contains(":2"), "should point at line 2, got: {}", err);
+    }
+
+    #[test]
+    fn strict_config_rejects_unbalanced_quotes() {
+        let err = load_str("defaults = --filter 'e.level == \"ERROR\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid value for defaults"), "got: {}", err);
+        assert!(err.contains(":1"), "got: {}", err);
+
+        let err = load_str("[aliases]\nbroken = --exec \"e.x = 1\n")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("invalid value for alias 'broken'"),
+            "got: {}",
+            err
+        );
+        assert!(err.contains(":2"), "got: {}", err);
+    }
 }
@@ -1,14 +1,207 @@
 use anyhow::{anyhow, Result};
-use flate2::read::MultiGzDecoder;
+use flate2::bufread::GzDecoder;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Chain, Cursor, Read};
 use std::path::Path;
+use std::sync::Arc;
 
-type ChainReader = Chain<Cursor<Vec<u8>>, File>;
-type GzipReader = BufReader<MultiGzDecoder<ChainReader>>;
-type ZstdReader = BufReader<zstd::Decoder<'static, BufReader<ChainReader>>>;
+use crate::stats::FileByteCounter;
+
+type ChainReader = CountingReader<Chain<Cursor<Vec<u8>>, File>>;
+type MemberSource = PositionedReader<BufReader<ChainReader>>;
+type GzipReader = BufReader<CountingReader<MultiMemberReader<GzDecoder<MemberSource>>>>;
+type ZstdReader =
+    BufReader<CountingReader<MultiMemberReader<zstd::Decoder<'static, MemberSource>>>>;
 type PlainReader = BufReader<ChainReader>;
 
+/// Source name used in warnings for compressed stdin
+const STDIN_SOURCE: &str = "<stdin>";
+
+/// Which `--stats` input byte counters a [`CountingReader`] feeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteCount {
+    /// Not counted (detection peeks that are re-read later)
+    Off,
+    /// Bytes as stored, before decompression
+    Raw,
+    /// Bytes after decompression
+    Decoded,
+    /// Uncompressed input: raw and decoded bytes are the same
+    Both,
+}
+
+/// Read adapter that adds the bytes it passes through to the process-wide
+/// input byte counters. Counting happens per `read` call, i.e. once per buffer
+/// refill rather than per line.
+pub struct CountingReader<R> {
+    inner: R,
+    count: ByteCount,
+    /// Per-file counter for compressed inputs, fed alongside the totals
+    file: Option<Arc<FileByteCounter>>,
+}
+
+impl<R> CountingReader<R> {
+    fn new(inner: R, count: ByteCount) -> Self {
+        Self {
+            inner,
+            count,
+            file: None,
+        }
+    }
+
+    fn with_file_counter(mut self, file: Option<Arc<FileByteCounter>>) -> Self {
+        self.file = file;
+        self
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        let (raw, decoded) = match self.count {
+            ByteCount::Off => return Ok(n),
+            ByteCount::Raw => (n, 0),
+            ByteCount::Decoded => (0, n),
+            ByteCount::Both => (n, n),
+        };
+        crate::stats::stats_add_input_bytes(raw, decoded);
+        if let Some(file) = &self.file {
+            file.add(raw, decoded);
+        }
+        Ok(n)
+    }
+}
+
+/// One gzip member or zstd frame, decoded from a `BufRead` that is handed
+/// back afterwards positioned just past it.
+pub trait MemberDecoder: Read + Sized {
+    type Reader: BufRead;
+
+    /// Leading bytes that identify the start of another member
+    const MAGIC: &'static [u8];
+
+    fn start(reader: Self::Reader) -> std::io::Result<Self>;
+
+    fn into_reader(self) -> Self::Reader;
+}
+
+impl<R: BufRead> MemberDecoder for GzDecoder<R> {
+    type Reader = R;
+
+    const MAGIC: &'static [u8] = &[0x1F, 0x8B];
+
+    fn start(reader: R) -> std::io::Result<Self> {
+        Ok(GzDecoder::new(reader))
+    }
+
+    fn into_reader(self) -> R {
+        self.into_inner()
+    }
+}
+
+impl<R: BufRead> MemberDecoder for zstd::Decoder<'static, R> {
+    type Reader = R;
+
+    const MAGIC: &'static [u8] = &[0x28, 0xB5, 0x2F, 0xFD];
+
+    fn start(reader: R) -> std::io::Result<Self> {
+        Ok(zstd::Decoder::with_buffer(reader)?.single_frame())
+    }
+
+    fn into_reader(self) -> R {
+        self.finish()
+    }
+}
+
+/// Decodes every concatenated member (`cat a.gz b.gz > c.gz`) and stops at
+/// trailing bytes that do not start another member, recording them as a
+/// warning with their byte offset instead of failing the whole file.
+///
+/// Corruption inside a member, including one that starts with valid magic
+/// bytes, is still an error.
+pub struct MultiMemberReader<D> {
+    decoder: Option<D>,
+    /// Compressed bytes consumed by completed members
+    offset: u64,
+    /// Input name for the trailing-garbage warning; `None` for uncounted
+    /// detection peeks, so a re-read file is not reported twice
+    source: Option<String>,
+}
+
+impl<R: BufRead, D: MemberDecoder<Reader = PositionedReader<R>>> MultiMemberReader<D> {
+    fn new(reader: R, source: Option<&str>) -> std::io::Result<Self> {
+        Ok(Self {
+            decoder: Some(D::start(PositionedReader::new(reader))?),
+            offset: 0,
+            source: source.map(str::to_string),
+        })
+    }
+}
+
+impl<R: BufRead, D: MemberDecoder<Reader = PositionedReader<R>>> Read for MultiMemberReader<D> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let Some(decoder) = self.decoder.as_mut() else {
+                return Ok(0);
+            };
+            let n = decoder.read(buf)?;
+            if n > 0 || buf.is_empty() {
+                return Ok(n);
+            }
+
+            // Member finished; look at what follows it
+            let mut reader = match self.decoder.take() {
+                Some(decoder) => decoder.into_reader(),
+                None => return Ok(0),
+            };
+            self.offset = reader.position;
+            let next = reader.fill_buf()?;
+            if next.is_empty() {
+                return Ok(0);
+            }
+            let compared = next.len().min(D::MAGIC.len());
+            if next[..compared] != D::MAGIC[..compared] {
+                if let Some(source) = &self.source {
+                    crate::stats::stats_record_trailing_garbage(source, self.offset);
+                }
+                return Ok(0);
+            }
+            self.decoder = Some(D::start(reader)?);
+        }
+    }
+}
+
+/// `BufRead` pass-through that tracks how many bytes have been consumed
+pub struct PositionedReader<R> {
+    inner: R,
+    position: u64,
+}
+
+impl<R> PositionedReader<R> {
+    fn new(inner: R) -> Self {
+        Self { inner, position: 0 }
+    }
+}
+
+impl<R: BufRead> Read for PositionedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for PositionedReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.position += amt as u64;
+    }
+}
+
 /// Streaming decompression wrapper that implements BufRead
 /// Detects gzip (1F 8B 08) and zstd (28 B5 2F FD) compression using magic bytes
 pub enum DecompressionReader {
@@ -61,7 +254,11 @@ impl Read for DecompressionReader {
 
 /// Detect compression format by magic bytes and return appropriate reader
 /// Reads first 4 bytes to check for gzip (1F 8B 08) or zstd (28 B5 2F FD) magic signatures
-fn detect_compression_file(mut file: File) -> std::io::Result<DecompressionReader> {
+fn detect_compression_file(
+    mut file: File,
+    path: &Path,
+    counted: bool,
+) -> std::io::Result<DecompressionReader> {
     let mut head = [0u8; 4];
     let n = file.read(&mut head)?;
 
@@ -76,16 +273,39 @@ fn detect_compression_file(mut file: File) -> std::io::Result<DecompressionReade
     let is_zstd =
         n >= 4 && head[0] == 0x28 && head[1] == 0xB5 && head[2] == 0x2F && head[3] == 0xFD;
 
-    if is_gzip {
-        let decoder = MultiGzDecoder::new(chained);
-        Ok(DecompressionReader::Gzip(BufReader::new(decoder)))
-    } else if is_zstd {
-        // zstd::Decoder wraps input in BufReader automatically
-        let decoder = zstd::Decoder::new(chained)?;
-        Ok(DecompressionReader::Zstd(BufReader::new(decoder)))
+    let (raw, decoded, both) = if counted {
+        (ByteCount::Raw, ByteCount::Decoded, ByteCount::Both)
     } else {
+        (ByteCount::Off, ByteCount::Off, ByteCount::Off)
+    };
+
+    if !(is_gzip || is_zstd) {
         // For non-compressed files, use the chain directly as the source
-        Ok(DecompressionReader::Plain(BufReader::new(chained)))
+        return Ok(DecompressionReader::Plain(BufReader::new(
+            CountingReader::new(chained, both),
+        )));
+    }
+
+    let source = path.display().to_string();
+    let reported_source = counted.then_some(source.as_str());
+    let file_counter = if counted {
+        crate::stats::stats_register_compressed_file(&source)
+    } else {
+        None
+    };
+    let compressed =
+        BufReader::new(CountingReader::new(chained, raw).with_file_counter(file_counter.clone()));
+
+    if is_gzip {
+        let decoder = MultiMemberReader::<GzDecoder<_>>::new(compressed, reported_source)?;
+        Ok(DecompressionReader::Gzip(BufReader::new(
+            CountingReader::new(decoder, decoded).with_file_counter(file_counter),
+        )))
+    } else {
+        let decoder = MultiMemberReader::<zstd::Decoder<_>>::new(compressed, reported_source)?;
+        Ok(DecompressionReader::Zstd(BufReader::new(
+            CountingReader::new(decoder, decoded).with_file_counter(file_counter),
+        )))
     }
 }
 
@@ -109,18 +329,32 @@ pub fn maybe_decompress<R: Read + Send + 'static>(
         n >= 4 && head[0] == 0x28 && head[1] == 0xB5 && head[2] == 0x2F && head[3] == 0xFD;
 
     if is_gzip {
-        Ok(Box::new(MultiGzDecoder::new(chained)))
+        let compressed = BufReader::new(CountingReader::new(chained, ByteCount::Raw));
+        let decoder = MultiMemberReader::<GzDecoder<_>>::new(compressed, Some(STDIN_SOURCE))?;
+        Ok(Box::new(CountingReader::new(decoder, ByteCount::Decoded)))
     } else if is_zstd {
-        Ok(Box::new(zstd::Decoder::new(chained)?))
+        let compressed = BufReader::new(CountingReader::new(chained, ByteCount::Raw));
+        let decoder = MultiMemberReader::<zstd::Decoder<_>>::new(compressed, Some(STDIN_SOURCE))?;
+        Ok(Box::new(CountingReader::new(decoder, ByteCount::Decoded)))
     } else {
-        Ok(Box::new(chained))
+        Ok(Box::new(CountingReader::new(chained, ByteCount::Both)))
     }
 }
 
 impl DecompressionReader {
     /// Create a new decompression reader with auto-detection based on magic bytes
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path_ref = path.as_ref();
+        Self::open(path.as_ref(), true)
+    }
+
+    /// Like [`DecompressionReader::new`], but the bytes read are not added to
+    /// the `--stats` input byte counters. For format-detection peeks whose
+    /// file is opened and read again for processing.
+    pub fn new_uncounted<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::open(path.as_ref(), false)
+    }
+
+    fn open(path_ref: &Path, counted: bool) -> Result<Self> {
         let file = File::open(path_ref)?;
 
         // Check file extension for known unsupported formats
@@ -131,7 +365,7 @@ impl DecompressionReader {
         }
 
         // Use magic bytes detection for all files
-        detect_compression_file(file)
+        detect_compression_file(file, path_ref, counted)
             .map_err(|e| anyhow!("Failed to detect compression format: {}", e))
     }
 }
@@ -297,6 +531,70 @@ mod tests {
         Ok(())
     }
 
+    fn gzip_member(data: &[u8]) -> Vec<u8> {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_gzip_trailing_garbage_is_a_warning() -> Result<()> {
+        let first = gzip_member(b"first member\n");
+        let second = gzip_member(b"second member\n");
+        let mut temp_file = NamedTempFile::new()?;
+        temp_file.write_all(&first)?;
+        temp_file.write_all(&second)?;
+        temp_file.write_all(&[0xAB; 16])?;
+        temp_file.flush()?;
+
+        let mut reader = DecompressionReader::new(temp_file.path())?;
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        assert_eq!(content, "first member\nsecond member\n");
+
+        let path = temp_file.path().display().to_string();
+        let offset = (first.len() + second.len()) as u64;
+        assert!(crate::stats::trailing_garbage_snapshot().contains(&(path.clone(), offset)));
+
+        let bytes = crate::stats::compressed_file_bytes()
+            .into_iter()
+            .find(|file| file.path == path)
+            .expect("compressed file registered");
+        assert_eq!(bytes.compressed, offset + 16);
+        assert_eq!(bytes.decompressed, content.len() as u64);
+        Ok(())
+    }
+
+    #[test]
+    fn test_zstd_frames_and_trailing_garbage() -> Result<()> {
+        let mut data = zstd::encode_all(&b"frame one\n"[..], 0)?;
+        data.extend(zstd::encode_all(&b"frame two\n"[..], 0)?);
+        let offset = data.len() as u64;
+        data.extend_from_slice(b"not zstd at all!");
+
+        let mut reader = maybe_decompress(Cursor::new(data))?;
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        assert_eq!(content, "frame one\nframe two\n");
+        assert!(
+            crate::stats::trailing_garbage_snapshot().contains(&(STDIN_SOURCE.to_string(), offset))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_garbage_with_member_magic_is_still_an_error() {
+        let mut data = gzip_member(b"ok\n");
+        data.extend_from_slice(&[0x1F, 0x8B, 0x08, 0x00, 0xFF, 0xFF]);
+
+        let mut reader = maybe_decompress(Cursor::new(data)).unwrap();
+        let mut content = String::new();
+        assert!(reader.read_to_string(&mut content).is_err());
+    }
+
     #[test]
     fn test_corrupted_gzip_data() {
         use flate2::write::GzEncoder;
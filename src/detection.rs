@@ -12,6 +12,7 @@ use crate::decompression;
 use crate::parsers;
 use crate::pipeline;
 use crate::readers;
+use crate::run_scope;
 use crate::stats;
 use crate::stats::ProcessingStats;
 
@@ -34,6 +35,10 @@ impl std::fmt::Display for AllInputsUnopenable {
 
 impl std::error::Error for AllInputsUnopenable {}
 
+/// Lines sampled to recognize SSV command output when the first line alone
+/// only looks like plain text
+const SSV_DETECTION_SAMPLE_LINES: usize = 20;
+
 /// Result of format detection
 #[derive(Debug, Clone)]
 pub struct DetectedFormat {
@@ -66,7 +71,42 @@ pub fn detect_format_from_peekable_reader<R: std::io::BufRead>(
         Some(line) => {
             // Remove newline for detection
             let trimmed_line = line.trim_end_matches(&['\r', '\n'][..]);
-            let detected = parsers::detect_format(trimmed_line)?;
+            // --detect-lines and multi-line sampling come from the current run
+            let (detect_lines, multi_line_sampling) = run_scope::with_current(|scope| {
+                (
+                    scope.settings.detect_lines.unwrap_or(0),
+                    scope.settings.multi_line_sampling,
+                )
+            });
+            let mut detected = if detect_lines > 0 {
+                let sample = reader.peek_non_empty_lines(detect_lines)?;
+                let sample: Vec<&str> = sample.iter().map(String::as_str).collect();
+                parsers::detect_format_from_sample(&sample)?
+            } else {
+                parsers::detect_format(trimmed_line)?
+            };
+            // An XML declaration alone doesn't say whether records follow, so
+            // look at the first children of the root. A declaration opens a
+            // whole document rather than a live stream, so stdin is sampled too.
+            if trimmed_line.trim_start().starts_with("<?xml") {
+                let sample =
+                    reader.peek_non_empty_lines(parsers::xml_records::DETECTION_SAMPLE_LINES)?;
+                let sample: Vec<&str> = sample.iter().map(String::as_str).collect();
+                if parsers::xml_records::looks_like_xml_records(&sample) {
+                    detected = config::InputFormat::XmlRecords(Default::default());
+                }
+            }
+            // Aligned command output (ps, netstat, df) is only recognizable
+            // across lines, so give plain-text input a second look
+            if matches!(detected, config::InputFormat::Line)
+                && (multi_line_sampling || detect_lines > 0)
+            {
+                let sample = reader.peek_non_empty_lines(SSV_DETECTION_SAMPLE_LINES)?;
+                let sample: Vec<&str> = sample.iter().map(String::as_str).collect();
+                if parsers::ssv::detect_ssv(&sample) {
+                    detected = config::InputFormat::Ssv;
+                }
+            }
             Ok(DetectedFormat {
                 format: detected,
                 had_input: true,
@@ -75,12 +115,30 @@ pub fn detect_format_from_peekable_reader<R: std::io::BufRead>(
     }
 }
 
+/// Put a reader that detection peeked at behind the decoder its detected
+/// format needs. XML record exports are decoded ahead of line splitting;
+/// every other format reads the lines it was detected from.
+pub fn decode_detected_input(
+    reader: Box<dyn BufRead + Send>,
+    format: &config::InputFormat,
+    buffer_size: usize,
+) -> Box<dyn BufRead + Send> {
+    match format.xml_records_options() {
+        Some(options) => Box::new(std::io::BufReader::with_capacity(
+            buffer_size,
+            parsers::XmlRecordsReader::new(reader, options),
+        )),
+        None => reader,
+    }
+}
+
 /// Detect format for parallel mode processing
 /// Returns the detected format and optionally a reader to reuse for stdin
 pub fn detect_format_for_parallel_mode(
     files: &[String],
     no_input: bool,
     strict: bool,
+    buffer_size: usize,
 ) -> Result<(DetectedFormat, Option<Box<dyn BufRead + Send>>)> {
     use std::io;
 
@@ -105,7 +163,9 @@ pub fn detect_format_for_parallel_mode(
         let detected = detect_format_from_peekable_reader(&mut peekable_reader)?;
 
         // Reuse the peekable reader so we don't consume stdin twice
-        Ok((detected, Some(Box::new(peekable_reader))))
+        let reader =
+            decode_detected_input(Box::new(peekable_reader), &detected.format, buffer_size);
+        Ok((detected, Some(reader)))
     } else {
         // For files, read first line from first file
         let sorted_files = pipeline::builders::sort_files(files, &config::FileOrder::Cli)?;
@@ -128,7 +188,7 @@ pub fn detect_format_for_parallel_mode(
                 }
             }
 
-            match decompression::DecompressionReader::new(file_path) {
+            match decompression::DecompressionReader::new_uncounted(file_path) {
                 Ok(decompressed) => {
                     let mut peekable_reader = readers::PeekableLineReader::new(decompressed);
                     detected = Some(detect_format_from_peekable_reader(&mut peekable_reader)?);
@@ -255,7 +315,15 @@ pub fn parse_failure_warning_message(
         || (events_created == 0 && parse_errors >= 3);
 
     if should_warn {
-        let text = mixed_format_suggestion(stats).unwrap_or_else(|| {
+        // With --fallback-format, mixed input is already handled and only
+        // lines neither format accepts are errors, so suggesting a cascade
+        // would be beside the point
+        let suggestion = if config.input.fallback_format.is_some() {
+            Some("Parsing mostly failed with both the input format and --fallback-format. Check the formats, or see --help-formats / --help-multiline.".to_string())
+        } else {
+            mixed_format_suggestion(stats)
+        };
+        let text = suggestion.unwrap_or_else(|| {
             "Parsing mostly failed. The input may use the wrong format, contain mixed formats, or require multiline parsing. Try -f line, specify -f <fmt>, or see --help-formats / --help-multiline.".to_string()
         });
         let mut message = config.format_warning_message(&text);
@@ -458,6 +526,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_failure_warning_skips_cascade_hint_with_fallback_format() {
+        let mut cfg = base_config();
+        cfg.input.fallback_format = Some(config::InputFormat::Logfmt);
+        let stats = ProcessingStats {
+            lines_errors: 10,
+            events_created: 5,
+            detected_format: Some("json".to_string()),
+            first_parse_error_sample: Some("just a plain text line".to_string()),
+            ..Default::default()
+        };
+
+        let message = parse_failure_warning_message(&cfg, Some(&stats), true, false)
+            .expect("expected warning");
+
+        assert!(
+            message.contains("--fallback-format"),
+            "message was {message}"
+        );
+        assert!(!message.contains("-f json,line"), "message was {message}");
+    }
+
     #[test]
     fn mixed_format_suggestion_skips_same_format_secondary() {
         // Defensive: if a failing line re-detects as the already-active format,
@@ -0,0 +1,524 @@
+#![allow(dead_code)] // The binary drives its runs through the builder but not every embedding setter
+
+//! Embedding API: build and run a kelora pipeline from Rust code.
+//!
+//! [`PipelineBuilder`] (also exported as `kelora::PipelineBuilder`) wraps the
+//! same configuration, pipeline construction and stage types the `kelora`
+//! binary uses. It reads from a reader, a list of files or an iterator of
+//! lines, and writes formatted events to an [`OutputWriter`]. It never prints
+//! diagnostics, installs signal handlers or exits the process. Counters and
+//! `track_*()` metrics are returned in a [`RunResult`], and a
+//! [`CancellationToken`] stops a run early. The `kelora` binary runs its
+//! pipelines through the same builder, with [`Input::Configured`].
+//!
+//! ```
+//! use kelora::embed::{CollectOutput, Input, PipelineBuilder};
+//! use kelora::{InputFormat, OutputFormat};
+//!
+//! let logs = vec![
+//!     r#"{"level":"INFO","msg":"started"}"#.to_string(),
+//!     r#"{"level":"ERROR","msg":"oom"}"#.to_string(),
+//! ];
+//! let output = CollectOutput::new();
+//! let result = PipelineBuilder::new()
+//!     .input(Input::lines(logs))
+//!     .input_format(InputFormat::Json)
+//!     .output_format(OutputFormat::Logfmt)
+//!     .filter(r#"e.level == "ERROR""#)
+//!     .exec(r#"track_inc("errors")"#)
+//!     .output(output.clone())
+//!     .run()
+//!     .unwrap();
+//!
+//! assert_eq!(output.lines(), vec!["level=ERROR msg=oom"]);
+//! assert_eq!(result.stats.events_output, 1);
+//! assert_eq!(result.tracking.user["errors"].as_int().unwrap(), 1);
+//! ```
+//!
+//! Script `print()`/`eprint()` lines go to the
+//! [`on_print`](PipelineBuilder::on_print) and
+//! [`on_eprint`](PipelineBuilder::on_eprint) handlers and are dropped when
+//! none is set. A script `exit(code)` stops the run, which then returns
+//! `code` in [`RunResult::exit_code`].
+//!
+//! Each run keeps its settings and counters to itself, including those read
+//! on reader and worker threads (error samples, `--benchmark`, format
+//! detection, files and bytes read), so runs on concurrent threads report
+//! independent [`RunResult::stats`].
+
+use anyhow::Result;
+use crossbeam_channel::Receiver;
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::config::{ColorMode, KeloraConfig, ScriptStageType};
+use crate::pipeline::{Formatter, OutputWriter};
+use crate::platform::Ctrl;
+use crate::rhai_functions::capture::{self, ScriptOutput};
+use crate::rhai_functions::tracking::TrackingSnapshot;
+use crate::runner::{self, RunOverrides};
+use crate::stats::ProcessingStats;
+
+/// Where a pipeline reads its lines from.
+pub enum Input {
+    /// A buffered reader, e.g. a socket or an in-memory buffer
+    Reader(Box<dyn BufRead + Send>),
+    /// Files read in order; compressed files are decompressed, and each
+    /// event's `meta.filename` is the file it came from
+    Files(Vec<PathBuf>),
+    /// Lines without their trailing newline
+    Lines(Box<dyn Iterator<Item = String> + Send>),
+    /// What the configuration names, read as the CLI reads it: its files,
+    /// globs, input commands or recording, or stdin, sequentially or in
+    /// parallel as configured
+    Configured,
+}
+
+impl Input {
+    pub fn reader<R: BufRead + Send + 'static>(reader: R) -> Self {
+        Input::Reader(Box::new(reader))
+    }
+
+    pub fn files<I, P>(files: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        Input::Files(files.into_iter().map(Into::into).collect())
+    }
+
+    pub fn lines<I>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+        I::IntoIter: Send + 'static,
+    {
+        Input::Lines(Box::new(lines.into_iter()))
+    }
+}
+
+/// Caller-controlled stop signal for a running pipeline.
+///
+/// Cancelling stops reading input after the current line; buffered output,
+/// open spans and the `end` script are still finished. Clones share the same
+/// flag, so one can be handed to another thread, and cancelling one run's
+/// token leaves other runs in the process untouched.
+pub use crate::platform::CancelToken as CancellationToken;
+
+/// [`OutputWriter`] that keeps every output line in memory. Clones share the
+/// same buffer: pass a clone to [`PipelineBuilder::output`] and read the lines
+/// back from the original after the run.
+#[derive(Clone, Debug, Default)]
+pub struct CollectOutput {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+impl CollectOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+impl OutputWriter for CollectOutput {
+    fn write(&mut self, line: &str) -> io::Result<()> {
+        self.lines
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(line.to_string());
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// [`OutputWriter`] over any [`io::Write`], one line per formatted event.
+pub struct WriteOutput<W: io::Write + Send>(pub W);
+
+impl<W: io::Write + Send> OutputWriter for WriteOutput<W> {
+    fn write(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.0, "{}", line)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Where formatted events go: line by line to an [`OutputWriter`], or as the
+/// raw stream the CLI writes to stdout.
+enum Output {
+    Lines(Box<dyn OutputWriter>),
+    Stream(Box<dyn io::Write + Send>),
+}
+
+/// What a finished run reports instead of printing it.
+#[derive(Debug)]
+pub struct RunResult {
+    /// Line, event and error counters (what `--stats` prints)
+    pub stats: ProcessingStats,
+    /// `track_*()` state; `tracking.user` is what `--metrics` prints
+    pub tracking: TrackingSnapshot,
+    /// The run stopped because its [`CancellationToken`] was cancelled
+    pub cancelled: bool,
+    /// The code a script passed to `exit()`, if that stopped the run
+    pub exit_code: Option<i32>,
+}
+
+/// Builder for an embedded pipeline run. See the [module docs](self).
+///
+/// Settings without a dedicated method are reachable through
+/// [`config_mut`](Self::config_mut), which exposes the same
+/// [`KeloraConfig`] the CLI builds from its flags.
+pub struct PipelineBuilder {
+    config: KeloraConfig,
+    input: Option<Input>,
+    formatter: Option<Box<dyn Formatter>>,
+    output: Option<Output>,
+    script_output: ScriptOutput,
+    cancellation: CancellationToken,
+    control: Option<Receiver<Ctrl>>,
+    collect_stats: bool,
+}
+
+impl PipelineBuilder {
+    /// Start from the CLI defaults, with plain-line input and colors off.
+    pub fn new() -> Self {
+        let mut config = KeloraConfig::default();
+        config.input.format = crate::config::InputFormat::Line;
+        config.output.color = ColorMode::Never;
+        Self::from_config(config)
+    }
+
+    /// Start from an existing configuration, e.g. one loaded the way the CLI
+    /// does. Input files, commands, globs and parallel settings in it are
+    /// ignored unless the input is [`Input::Configured`].
+    pub fn from_config(config: KeloraConfig) -> Self {
+        Self {
+            config,
+            input: None,
+            formatter: None,
+            output: None,
+            script_output: ScriptOutput {
+                print: Arc::new(|_| {}),
+                eprint: Arc::new(|_| {}),
+            },
+            cancellation: CancellationToken::new(),
+            control: None,
+            collect_stats: true,
+        }
+    }
+
+    pub fn input(mut self, input: Input) -> Self {
+        self.input = Some(input);
+        self
+    }
+
+    /// Input format, as `-f` selects it. `auto` is detected only for
+    /// [`Input::Files`]; readers and lines need a concrete format.
+    pub fn input_format(mut self, format: impl Into<crate::config::InputFormat>) -> Self {
+        self.config.input.format = format.into();
+        self
+    }
+
+    /// Built-in output format, as `-F` selects it.
+    pub fn output_format(mut self, format: impl Into<crate::config::OutputFormat>) -> Self {
+        self.config.output.format = format.into();
+        self
+    }
+
+    /// Custom formatter, used instead of the output format.
+    pub fn formatter(mut self, formatter: impl Formatter + 'static) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Destination for formatted events. This or
+    /// [`output_stream`](Self::output_stream) is required.
+    pub fn output(mut self, output: impl OutputWriter + 'static) -> Self {
+        self.output = Some(Output::Lines(Box::new(output)));
+        self
+    }
+
+    /// Destination for the formatted stream as written, newlines included,
+    /// the way the CLI writes stdout or an `-o` file.
+    pub fn output_stream(mut self, output: impl io::Write + Send + 'static) -> Self {
+        self.output = Some(Output::Stream(Box::new(output)));
+        self
+    }
+
+    /// Receive each line a script passes to `print()`.
+    pub fn on_print(mut self, handler: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.script_output.print = Arc::new(handler);
+        self
+    }
+
+    /// Receive each line a script passes to `eprint()`, and the message of
+    /// an `exit(code, message)`.
+    pub fn on_eprint(mut self, handler: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.script_output.eprint = Arc::new(handler);
+        self
+    }
+
+    pub fn begin(mut self, script: &str) -> Self {
+        self.config.processing.begin = Some(script.to_string());
+        self
+    }
+
+    /// Add a `--filter` stage. Stages run in the order they are added.
+    pub fn filter(mut self, script: &str) -> Self {
+        self.config.processing.stages.push(ScriptStageType::Filter {
+            script: script.to_string(),
+            includes: Vec::new(),
+        });
+        self
+    }
+
+    /// Add an `--exec` stage. Stages run in the order they are added.
+    pub fn exec(mut self, script: &str) -> Self {
+        self.config
+            .processing
+            .stages
+            .push(ScriptStageType::Exec(script.to_string()));
+        self
+    }
+
+    /// Add an `--assert` stage. Stages run in the order they are added.
+    pub fn assert(mut self, script: &str) -> Self {
+        self.config
+            .processing
+            .stages
+            .push(ScriptStageType::Assert(script.to_string()));
+        self
+    }
+
+    pub fn end(mut self, script: &str) -> Self {
+        self.config.processing.end = Some(script.to_string());
+        self
+    }
+
+    /// Stop after `limit` output events (`--take`).
+    pub fn take(mut self, limit: usize) -> Self {
+        self.config.processing.take_limit = Some(limit);
+        self
+    }
+
+    /// Fail the run on the first parse or script error (`--strict`).
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.config.processing.strict = strict;
+        self
+    }
+
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Shutdown and stats requests for the run, as the CLI's signal handler
+    /// sends them.
+    pub fn control(mut self, ctrl_rx: Receiver<Ctrl>) -> Self {
+        self.control = Some(ctrl_rx);
+        self
+    }
+
+    /// Collect counters even when no setting asks for them (the default).
+    /// The CLI turns this off, so runs without stats skip the bookkeeping.
+    pub fn collect_stats(mut self, collect: bool) -> Self {
+        self.collect_stats = collect;
+        self
+    }
+
+    pub fn config_mut(&mut self) -> &mut KeloraConfig {
+        &mut self.config
+    }
+
+    /// Run the pipeline on the calling thread until the input ends, the take
+    /// limit is reached, a script calls `exit()` or the run is cancelled.
+    /// This is the CLI's sequential driver, fed from [`Input`] instead of
+    /// stdin and the command line.
+    pub fn run(self) -> Result<RunResult> {
+        let cancellation = self.cancellation.clone();
+        let result = self.run_pipeline()?;
+
+        Ok(RunResult {
+            stats: result.stats.unwrap_or_default(),
+            tracking: result.tracking_data,
+            cancelled: cancellation.is_cancelled() && result.exit_code.is_none(),
+            exit_code: result.exit_code,
+        })
+    }
+
+    /// [`run`](Self::run), returning the runner's full result: what the CLI
+    /// reports after a run.
+    pub fn run_pipeline(self) -> Result<runner::PipelineResult> {
+        let PipelineBuilder {
+            mut config,
+            input,
+            formatter,
+            output,
+            script_output,
+            cancellation,
+            control,
+            collect_stats,
+        } = self;
+        let input = input.ok_or_else(|| anyhow::anyhow!("PipelineBuilder has no input"))?;
+        let output =
+            output.ok_or_else(|| anyhow::anyhow!("PipelineBuilder has no output writer"))?;
+
+        if !matches!(input, Input::Configured) {
+            // Only the given input is read, in order, on this thread
+            config.performance.parallel = false;
+            config.performance.threads = 0;
+            config.performance.batch_size = None;
+            config.input.files.clear();
+            config.input.follow_glob = None;
+            config.input.input_cmds.clear();
+            config.input.pipeline_replay = None;
+        }
+        let reader: Option<Box<dyn BufRead + Send>> = match input {
+            Input::Reader(reader) => Some(reader),
+            Input::Lines(lines) => Some(Box::new(io::BufReader::new(LinesReader {
+                lines,
+                current: io::Cursor::new(Vec::new()),
+            }))),
+            Input::Files(files) => {
+                config.input.no_input = files.is_empty();
+                config.input.files = files
+                    .iter()
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect();
+                None
+            }
+            Input::Configured => None,
+        };
+
+        // Without a control channel nothing sends on this one, but dropping
+        // the sender would read as a shutdown request, so it lives until the
+        // run returns
+        let (_ctrl_tx, default_ctrl_rx) = crossbeam_channel::unbounded::<Ctrl>();
+        let ctrl_rx = control.unwrap_or(default_ctrl_rx);
+        let overrides = RunOverrides {
+            reader,
+            formatter,
+            collect_stats,
+        };
+        let (writer, lines): (Box<dyn io::Write + Send>, _) = match output {
+            Output::Lines(output) => {
+                let output = Arc::new(Mutex::new(output));
+                let writer = LineWriter {
+                    output: Arc::clone(&output),
+                    pending: Vec::new(),
+                };
+                (Box::new(writer), Some(output))
+            }
+            Output::Stream(stream) => (stream, None),
+        };
+
+        // Scripts on this thread print through the handlers for this run only
+        let previous_output = capture::set_script_output(Some(script_output));
+        let result = runner::run_pipeline_with_overrides(
+            &config,
+            writer,
+            &ctrl_rx,
+            &cancellation,
+            overrides,
+        );
+        capture::set_script_output(previous_output);
+        let result = result?;
+        if let Some(lines) = lines {
+            lines.lock().unwrap_or_else(|e| e.into_inner()).flush()?;
+        }
+        Ok(result)
+    }
+}
+
+impl Default for PipelineBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hands the runner's output to an [`OutputWriter`], one line at a time.
+/// Output that does not end in a newline (a formatter's tail, a custom
+/// [`Formatter`]) is passed on as a last line when the runner flushes.
+struct LineWriter {
+    output: Arc<Mutex<Box<dyn OutputWriter>>>,
+    pending: Vec<u8>,
+}
+
+impl io::Write for LineWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        let mut output = self.output.lock().unwrap_or_else(|e| e.into_inner());
+        while let Some(end) = self.pending.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            output.write(&String::from_utf8_lossy(&line[..end]))?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut output = self.output.lock().unwrap_or_else(|e| e.into_inner());
+        if !self.pending.is_empty() {
+            let rest = std::mem::take(&mut self.pending);
+            output.write(&String::from_utf8_lossy(&rest))?;
+        }
+        output.flush()
+    }
+}
+
+/// [`Input::Lines`] as the byte stream the runner's readers expect.
+struct LinesReader {
+    lines: Box<dyn Iterator<Item = String> + Send>,
+    current: io::Cursor<Vec<u8>>,
+}
+
+impl io::Read for LinesReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let read = self.current.read(buf)?;
+            if read > 0 || buf.is_empty() {
+                return Ok(read);
+            }
+            let Some(line) = self.lines.next() else {
+                return Ok(0);
+            };
+            let mut bytes = line.into_bytes();
+            bytes.push(b'\n');
+            self.current = io::Cursor::new(bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn line_writer_passes_on_an_unterminated_tail_when_flushed() {
+        let collected = CollectOutput::new();
+        let output: Arc<Mutex<Box<dyn OutputWriter>>> =
+            Arc::new(Mutex::new(Box::new(collected.clone())));
+        let mut writer = LineWriter {
+            output,
+            pending: Vec::new(),
+        };
+
+        writer.write_all(b"first\nsec").unwrap();
+        writer.write_all(b"ond\n-- tail --").unwrap();
+        assert_eq!(collected.lines(), vec!["first", "second"]);
+
+        writer.flush().unwrap();
+        assert_eq!(collected.lines(), vec!["first", "second", "-- tail --"]);
+
+        // Nothing is left over for a second flush
+        writer.flush().unwrap();
+        assert_eq!(collected.lines().len(), 3);
+    }
+}
@@ -0,0 +1,162 @@
+//! Shared cache of compiled Rhai ASTs.
+//!
+//! Every `RhaiEngine` compiles its scripts from scratch, so rebuilding a
+//! pipeline (one per parallel worker, or again whenever the pipeline is
+//! restarted) recompiles identical script text. The cache maps a hash of the
+//! script kind and text to the compiled AST so only the first build pays for
+//! compilation. Keys are content hashes, so editing a script never reuses a
+//! stale AST; the old entry is simply no longer looked up.
+
+use crate::run_scope;
+use rhai::AST;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+
+static GLOBAL_AST_CACHE: OnceLock<AstCache> = OnceLock::new();
+
+/// The process-wide cache used by `RhaiEngine` compile methods, or `None`
+/// when the current run doesn't cache scripts.
+pub fn global_ast_cache() -> Option<&'static AstCache> {
+    if run_scope::with_current(|scope| scope.settings.cache_scripts) {
+        Some(GLOBAL_AST_CACHE.get_or_init(AstCache::default))
+    } else {
+        None
+    }
+}
+
+/// Thread-safe map from script hash to compiled AST
+#[derive(Clone, Default)]
+pub struct AstCache {
+    entries: Arc<RwLock<HashMap<u64, Arc<AST>>>>,
+    compilations: Arc<AtomicUsize>,
+}
+
+impl AstCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the cached AST for `source`, compiling and storing it on a miss.
+    ///
+    /// `kind` separates scripts compiled differently from the same text
+    /// (e.g. a filter expression vs. an exec statement block). Errors are not
+    /// cached, so a failing script reports its diagnostic every time.
+    pub fn get_or_compile<E>(
+        &self,
+        kind: &str,
+        source: &str,
+        compile: impl FnOnce() -> Result<AST, E>,
+    ) -> Result<Arc<AST>, E> {
+        let key = Self::key(kind, source);
+        if let Some(ast) = self.read_entries().get(&key) {
+            return Ok(Arc::clone(ast));
+        }
+
+        let ast = Arc::new(compile()?);
+        self.compilations.fetch_add(1, Ordering::Relaxed);
+
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        // Another thread may have compiled the same script meanwhile; keep the
+        // first entry so all callers share one AST.
+        Ok(Arc::clone(entries.entry(key).or_insert(ast)))
+    }
+
+    /// Drop the entry for `source`, forcing the next lookup to recompile.
+    pub fn invalidate(&self, kind: &str, source: &str) {
+        let key = Self::key(kind, source);
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&key);
+    }
+
+    /// Number of compilations performed through this cache (misses)
+    pub fn compilations(&self) -> usize {
+        self.compilations.load(Ordering::Relaxed)
+    }
+
+    pub fn len(&self) -> usize {
+        self.read_entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn read_entries(&self) -> std::sync::RwLockReadGuard<'_, HashMap<u64, Arc<AST>>> {
+        self.entries.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn key(kind: &str, source: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        kind.hash(&mut hasher);
+        source.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhai::Engine;
+
+    #[test]
+    fn identical_scripts_compile_once() {
+        let cache = AstCache::new();
+        let engine = Engine::new();
+        let script = "let x = e.status + 1; e.next = x;";
+
+        for _ in 0..100 {
+            cache
+                .get_or_compile("exec", script, || engine.compile(script))
+                .unwrap();
+        }
+
+        assert_eq!(cache.compilations(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn changed_text_and_kind_get_separate_entries() {
+        let cache = AstCache::new();
+        let engine = Engine::new();
+
+        cache
+            .get_or_compile("exec", "1 + 1", || engine.compile("1 + 1"))
+            .unwrap();
+        cache
+            .get_or_compile("exec", "1 + 2", || engine.compile("1 + 2"))
+            .unwrap();
+        cache
+            .get_or_compile("filter", "1 + 1", || engine.compile_expression("1 + 1"))
+            .unwrap();
+
+        assert_eq!(cache.compilations(), 3);
+    }
+
+    #[test]
+    fn invalidate_forces_recompile_and_errors_are_not_cached() {
+        let cache = AstCache::new();
+        let engine = Engine::new();
+
+        cache
+            .get_or_compile("exec", "40 + 2", || engine.compile("40 + 2"))
+            .unwrap();
+        cache.invalidate("exec", "40 + 2");
+        cache
+            .get_or_compile("exec", "40 + 2", || engine.compile("40 + 2"))
+            .unwrap();
+        assert_eq!(cache.compilations(), 2);
+
+        for _ in 0..2 {
+            assert!(cache
+                .get_or_compile("exec", "let = ;", || engine.compile("let = ;"))
+                .is_err());
+        }
+        assert_eq!(cache.compilations(), 2);
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,302 @@
+//! Static checks behind `kelora rhai-lint` and `--rhai-lint`.
+//!
+//! Scripts are compiled with the same engine setup the pipeline uses, so a
+//! script that lints clean also compiles at run time. On top of syntax errors,
+//! operators whose operands are all literals are evaluated once: Rhai is
+//! dynamically typed and only notices `"total: " - 1` when the line runs, which
+//! for a rarely taken branch can be hours into a job.
+
+use rhai::{ASTNode, Dynamic, EvalAltResult, Expr, Position, Scope, AST};
+
+use super::RhaiEngine;
+
+/// How a script is compiled: filters and assertions are single expressions,
+/// everything else is a statement block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptKind {
+    Expression,
+    Statements,
+}
+
+/// A script to lint, with the name its issues are reported under (a file
+/// path, or the flag it came from such as `--filter #1`).
+#[derive(Debug, Clone)]
+pub struct LintSource {
+    pub name: String,
+    pub script: String,
+    pub kind: ScriptKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintIssueKind {
+    /// The script does not compile
+    Syntax,
+    /// An operator is applied to literals it can never accept
+    Type,
+}
+
+#[derive(Debug, Clone)]
+pub struct LintIssue {
+    pub source: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub kind: LintIssueKind,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn new(source: &str, pos: Position, kind: LintIssueKind, message: String) -> Self {
+        Self {
+            source: source.to_string(),
+            line: pos.line(),
+            column: pos.position(),
+            kind,
+            message,
+        }
+    }
+}
+
+impl LintIssueKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            LintIssueKind::Syntax => "syntax",
+            LintIssueKind::Type => "type",
+        }
+    }
+}
+
+impl std::fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = format!("{} error", self.kind.as_str());
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(
+                    f,
+                    "{}:{}:{}: {}: {}",
+                    self.source, line, column, kind, self.message
+                )
+            }
+            (Some(line), None) => write!(f, "{}:{}: {}: {}", self.source, line, kind, self.message),
+            _ => write!(f, "{}: {}: {}", self.source, kind, self.message),
+        }
+    }
+}
+
+/// Result of linting a set of scripts.
+#[derive(Debug)]
+pub struct LintReport {
+    pub scripts: usize,
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Compiler-style `source:line:column: kind: message` lines plus a summary.
+    pub fn to_text(&self) -> String {
+        let mut output = String::new();
+        for issue in &self.issues {
+            output.push_str(&issue.to_string());
+            output.push('\n');
+        }
+        let noun = if self.scripts == 1 {
+            "script"
+        } else {
+            "scripts"
+        };
+        match self.issues.len() {
+            0 => output.push_str(&format!("{} {} checked, no issues\n", self.scripts, noun)),
+            1 => output.push_str(&format!("{} {} checked, 1 issue\n", self.scripts, noun)),
+            n => output.push_str(&format!(
+                "{} {} checked, {} issues\n",
+                self.scripts, noun, n
+            )),
+        }
+        output
+    }
+
+    pub fn to_json(&self) -> String {
+        let issues: Vec<serde_json::Value> = self
+            .issues
+            .iter()
+            .map(|issue| {
+                serde_json::json!({
+                    "source": issue.source,
+                    "line": issue.line,
+                    "column": issue.column,
+                    "kind": issue.kind.as_str(),
+                    "message": issue.message,
+                })
+            })
+            .collect();
+        let report = serde_json::json!({
+            "scripts": self.scripts,
+            "ok": self.is_clean(),
+            "issues": issues,
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+impl RhaiEngine {
+    /// Compile each script without running it and collect every problem found.
+    pub fn lint(&self, sources: &[LintSource]) -> LintReport {
+        let mut issues = Vec::new();
+        for source in sources {
+            let compiled = match source.kind {
+                ScriptKind::Expression => self.engine.compile_expression(&source.script),
+                ScriptKind::Statements => self.engine.compile(&source.script),
+            };
+            match compiled {
+                Ok(ast) => issues.extend(self.check_literal_operators(&source.name, &ast)),
+                Err(err) => issues.push(LintIssue::new(
+                    &source.name,
+                    err.position(),
+                    LintIssueKind::Syntax,
+                    err.err_type().to_string(),
+                )),
+            }
+        }
+        LintReport {
+            scripts: sources.len(),
+            issues,
+        }
+    }
+
+    /// Evaluate operators whose operands are all literals and report the ones
+    /// that fail. Operators have no side effects, so this is safe to do at
+    /// lint time; calls to named functions are never evaluated.
+    fn check_literal_operators(&self, source: &str, ast: &AST) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        ast.walk(&mut |path| {
+            let Some(ASTNode::Expr(Expr::FnCall(call, pos))) = path.last() else {
+                return true;
+            };
+            if call.op_token.is_none() {
+                return true;
+            }
+            let Some(operands) = call
+                .args
+                .iter()
+                .map(literal_value)
+                .collect::<Option<Vec<Dynamic>>>()
+            else {
+                return true;
+            };
+
+            let mut scope = Scope::new();
+            let expr = match operands.as_slice() {
+                [operand] => {
+                    scope.push_dynamic("a", operand.clone());
+                    format!("{}a", call.name)
+                }
+                [lhs, rhs] => {
+                    scope.push_dynamic("a", lhs.clone());
+                    scope.push_dynamic("b", rhs.clone());
+                    format!("a {} b", call.name)
+                }
+                _ => return true,
+            };
+
+            if let Err(err) = self
+                .engine
+                .eval_expression_with_scope::<Dynamic>(&mut scope, &expr)
+            {
+                let message = match *err {
+                    EvalAltResult::ErrorFunctionNotFound(..) => {
+                        let types: Vec<&str> = operands.iter().map(|v| v.type_name()).collect();
+                        format!(
+                            "operator '{}' cannot be applied to {}; this always fails when it runs",
+                            call.name,
+                            types.join(" and ")
+                        )
+                    }
+                    EvalAltResult::ErrorArithmetic(ref msg, _) => {
+                        format!("{}; this always fails when it runs", msg)
+                    }
+                    _ => return true,
+                };
+                issues.push(LintIssue::new(source, *pos, LintIssueKind::Type, message));
+            }
+            true
+        });
+        issues
+    }
+}
+
+fn literal_value(expr: &Expr) -> Option<Dynamic> {
+    match expr {
+        Expr::IntegerConstant(value, _) => Some(Dynamic::from(*value)),
+        Expr::FloatConstant(value, _) => Some(Dynamic::from(**value)),
+        Expr::StringConstant(value, _) => Some(Dynamic::from(value.clone())),
+        Expr::BoolConstant(value, _) => Some(Dynamic::from(*value)),
+        Expr::CharConstant(value, _) => Some(Dynamic::from(*value)),
+        Expr::Unit(_) => Some(Dynamic::UNIT),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint_one(script: &str, kind: ScriptKind) -> LintReport {
+        RhaiEngine::new().lint(&[LintSource {
+            name: "test.rhai".to_string(),
+            script: script.to_string(),
+            kind,
+        }])
+    }
+
+    #[test]
+    fn clean_script_has_no_issues() {
+        let report = lint_one(
+            "let total = 1 + 2;\ne.msg = \"n=\" + total;",
+            ScriptKind::Statements,
+        );
+        assert!(report.is_clean(), "{:?}", report.issues);
+        assert_eq!(report.to_text(), "1 script checked, no issues\n");
+    }
+
+    #[test]
+    fn syntax_error_reports_position() {
+        let report = lint_one("let x = 1;\nlet y = (x + ;", ScriptKind::Statements);
+        assert_eq!(report.issues.len(), 1);
+        let issue = &report.issues[0];
+        assert_eq!(issue.kind, LintIssueKind::Syntax);
+        assert_eq!(issue.line, Some(2));
+        assert!(issue.to_string().starts_with("test.rhai:2:"), "{}", issue);
+    }
+
+    #[test]
+    fn literal_type_mismatch_is_reported() {
+        let report = lint_one(
+            "if e.status >= 500 {\n    e.label = \"errors: \" - 1;\n}",
+            ScriptKind::Statements,
+        );
+        assert_eq!(report.issues.len(), 1, "{:?}", report.issues);
+        let issue = &report.issues[0];
+        assert_eq!(issue.kind, LintIssueKind::Type);
+        assert_eq!(issue.line, Some(2));
+        assert!(
+            issue.message.contains("string and i64"),
+            "{}",
+            issue.message
+        );
+    }
+
+    #[test]
+    fn expressions_are_compiled_as_expressions() {
+        assert!(lint_one("e.level == \"ERROR\"", ScriptKind::Expression).is_clean());
+        // A statement is not a valid filter
+        let report = lint_one("let x = 1;", ScriptKind::Expression);
+        assert_eq!(report.issues[0].kind, LintIssueKind::Syntax);
+    }
+
+    #[test]
+    fn operators_on_fields_are_not_evaluated() {
+        assert!(lint_one("e.a - 1", ScriptKind::Expression).is_clean());
+    }
+}
@@ -8,8 +8,11 @@ use rhai::debugger::{DebuggerCommand, DebuggerEvent};
 use crate::event::Event;
 use crate::rhai_functions;
 use crate::rhai_functions::datetime::DateTimeWrapper;
+use crate::rhai_functions::safety::SandboxPolicy;
 
+pub mod ast_cache;
 mod debug;
+pub mod lint;
 pub use debug::{DebugConfig, DebugTracker, ErrorEnhancer};
 
 use rhai::Map;
@@ -93,7 +96,6 @@ fn maps_equal(lhs: &rhai::Map, rhs: &rhai::Map) -> bool {
     })
 }
 
-use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 
 // Execution Tracer for step-by-step debugging
@@ -1016,20 +1018,57 @@ impl CompiledExpression {
     }
 }
 
+/// A tracked error handed to the --exec-on-error script
+pub struct ErrorHookInfo<'a> {
+    pub error_type: &'a str,
+    pub message: &'a str,
+    pub raw_line: Option<&'a str>,
+    pub line_num: Option<usize>,
+    pub filename: Option<&'a str>,
+}
+
 pub struct RhaiEngine {
     engine: Engine,
     compiled_filters: Vec<CompiledExpression>,
     compiled_execs: Vec<CompiledExpression>,
     compiled_begin: Option<CompiledExpression>,
     compiled_end: Option<CompiledExpression>,
+    /// Script run for every tracked parse/script error (--exec-on-error)
+    compiled_error_hook: Option<CompiledExpression>,
     scope_template: Scope<'static>,
     suppress_side_effects: bool,
     conf_map: Option<rhai::Map>,
     state_map: Option<crate::rhai_functions::state::StateMap>,
     state_available: bool,
+    /// Input file names and --script-arg values for begin/end scripts
+    script_inputs: rhai::Array,
+    script_argv: rhai::Array,
     debug_tracker: Option<DebugTracker>,
     execution_tracer: Option<ExecutionTracer>,
     use_emoji: bool,
+    /// Token of the run this engine belongs to; scripts abort once it is cancelled
+    cancel: crate::platform::CancelToken,
+    /// `--sandbox` policy of the run this engine belongs to
+    sandbox: SandboxPolicy,
+}
+
+/// Abort running scripts once `cancel` is cancelled or a script on this
+/// thread called `exit()`, by returning a termination sentinel from the
+/// progress callback. The same callback
+/// enforces the `--sandbox` time cap and the `--begin`/`--end` timeouts.
+fn register_cancel_check(
+    engine: &mut Engine,
+    cancel: crate::platform::CancelToken,
+    sandbox: SandboxPolicy,
+) {
+    engine.on_progress(move |operations| {
+        if cancel.is_cancelled() || crate::rhai_functions::process::is_exit_requested() {
+            Some(rhai::Dynamic::UNIT)
+        } else {
+            crate::rhai_functions::safety::check_script_time(sandbox, operations)
+                .or_else(|| crate::rhai_functions::safety::check_script_deadline(operations))
+        }
+    });
 }
 
 impl Clone for RhaiEngine {
@@ -1040,15 +1079,8 @@ impl Clone for RhaiEngine {
         // compile time when their arguments are constants. These functions MUST run at runtime.
         engine.set_optimization_level(rhai::OptimizationLevel::Simple);
 
-        // Check for shutdown signal during script execution (cooperative cancellation)
-        engine.on_progress(|_| {
-            if crate::platform::SHOULD_TERMINATE.load(Ordering::Relaxed) {
-                // Abort script execution by returning a termination sentinel
-                Some(rhai::Dynamic::UNIT)
-            } else {
-                None
-            }
-        });
+        // Check for run cancellation during script execution (cooperative cancellation)
+        register_cancel_check(&mut engine, self.cancel.clone(), self.sandbox);
 
         // Apply the same on_print override as in new(), respecting suppress_side_effects
         let suppress_side_effects = self.suppress_side_effects;
@@ -1061,11 +1093,12 @@ impl Clone for RhaiEngine {
             if crate::rhai_functions::strings::is_parallel_mode() {
                 crate::rhai_functions::strings::capture_print(text.to_string());
             } else {
-                println!("{}", text);
+                crate::rhai_functions::capture::emit_print(text);
             }
         });
 
         rhai_functions::register_all_functions(&mut engine);
+        self.sandbox.apply_limits(&mut engine);
 
         Self {
             engine,
@@ -1073,20 +1106,26 @@ impl Clone for RhaiEngine {
             compiled_execs: self.compiled_execs.clone(),
             compiled_begin: self.compiled_begin.clone(),
             compiled_end: self.compiled_end.clone(),
+            compiled_error_hook: self.compiled_error_hook.clone(),
             scope_template: self.scope_template.clone(),
             suppress_side_effects,
             conf_map: self.conf_map.clone(),
             state_map: self.state_map.clone(),
             state_available: self.state_available,
+            script_inputs: self.script_inputs.clone(),
+            script_argv: self.script_argv.clone(),
             debug_tracker: self.debug_tracker.clone(),
             execution_tracer: self.execution_tracer.clone(),
             use_emoji: self.use_emoji,
+            cancel: self.cancel.clone(),
+            sandbox: self.sandbox,
         }
     }
 }
 
 impl RhaiEngine {
     /// Render a short diagnostic with stage/name, position, snippet, and the raw Rhai message.
+    #[allow(clippy::too_many_arguments)]
     fn format_rhai_diagnostic(
         err: Box<EvalAltResult>,
         stage: &str,
@@ -1095,9 +1134,11 @@ impl RhaiEngine {
         scope: Option<&Scope>,
         debug_tracker: Option<&DebugTracker>,
         use_emoji: bool,
+        sandbox: SandboxPolicy,
     ) -> String {
         let call_stack = Self::collect_call_stack(err.as_ref());
-        let err_display = format!("{}", err);
+        let err_display = crate::rhai_functions::safety::describe_sandbox_error(sandbox, &err)
+            .unwrap_or_else(|| format!("{}", err));
 
         if let Some(tracker) = debug_tracker {
             let enhancer = ErrorEnhancer::new(tracker.config.clone());
@@ -1469,15 +1510,9 @@ impl RhaiEngine {
         // compile time when their arguments are constants. These functions MUST run at runtime.
         engine.set_optimization_level(rhai::OptimizationLevel::Simple);
 
-        // Check for shutdown signal during script execution (cooperative cancellation)
-        engine.on_progress(|_| {
-            if crate::platform::SHOULD_TERMINATE.load(Ordering::Relaxed) {
-                // Abort script execution by returning a termination sentinel
-                Some(rhai::Dynamic::UNIT)
-            } else {
-                None
-            }
-        });
+        // Check for run cancellation during script execution (cooperative cancellation)
+        let cancel = crate::platform::CancelToken::new();
+        register_cancel_check(&mut engine, cancel.clone(), SandboxPolicy::default());
 
         // Override the built-in print function to support capture in parallel mode
         // Note: suppress_side_effects is false by default in new()
@@ -1487,7 +1522,7 @@ impl RhaiEngine {
                 crate::rhai_functions::strings::capture_print(text.to_string());
                 crate::rhai_functions::strings::capture_stdout(text.to_string());
             } else {
-                println!("{}", text);
+                crate::rhai_functions::capture::emit_print(text);
             }
         });
 
@@ -1506,17 +1541,65 @@ impl RhaiEngine {
             compiled_execs: Vec::new(),
             compiled_begin: None,
             compiled_end: None,
+            compiled_error_hook: None,
             scope_template,
             suppress_side_effects: false,
             conf_map: None,
             state_map: Some(crate::rhai_functions::state::StateMap::new()),
             state_available: true,
+            script_inputs: rhai::Array::new(),
+            script_argv: rhai::Array::new(),
             debug_tracker: None,
             execution_tracer: None,
             use_emoji: true,
+            cancel,
+            sandbox: SandboxPolicy::default(),
         }
     }
 
+    /// Tie script execution to a run's cancellation token
+    pub fn set_cancel_token(&mut self, cancel: crate::platform::CancelToken) {
+        register_cancel_check(&mut self.engine, cancel.clone(), self.sandbox);
+        self.cancel = cancel;
+    }
+
+    /// Put this engine under the run's `--sandbox` policy: resource caps, no
+    /// module loading and the time cap. Call before compiling scripts.
+    pub fn set_sandbox_policy(&mut self, sandbox: SandboxPolicy) {
+        self.sandbox = sandbox;
+        sandbox.apply_limits(&mut self.engine);
+        register_cancel_check(&mut self.engine, self.cancel.clone(), sandbox);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// True once a script of this run called `exit()`
+    pub fn exit_requested(&self) -> bool {
+        self.cancel.exit_code().is_some()
+    }
+
+    /// Evaluate a compiled script. A script `exit()` is recorded on the run's
+    /// token, and the script it aborted, like any other script the token then
+    /// stops, yields `Ok(None)` rather than an error.
+    fn eval_until_exit<T: rhai::Variant + Clone>(
+        &self,
+        scope: &mut Scope,
+        ast: &AST,
+    ) -> Result<Option<T>, Box<EvalAltResult>> {
+        let result = self.engine.eval_ast_with_scope::<T>(scope, ast);
+        if crate::rhai_functions::process::is_exit_requested() {
+            let code = crate::rhai_functions::process::get_exit_code();
+            self.cancel
+                .cancel_with(crate::platform::CancelReason::Exit(code));
+        }
+        if self.exit_requested() {
+            return Ok(None);
+        }
+        result.map(Some)
+    }
+
     pub fn set_use_emoji(&mut self, use_emoji: bool) {
         self.use_emoji = use_emoji;
     }
@@ -1534,6 +1617,17 @@ impl RhaiEngine {
         self.state_available = available;
     }
 
+    /// Set the `inputs` and `argv` arrays seen by begin/end scripts.
+    pub fn set_script_inputs(&mut self, inputs: &[String], argv: &[String]) {
+        self.script_inputs = inputs.iter().cloned().map(Dynamic::from).collect();
+        self.script_argv = argv.iter().cloned().map(Dynamic::from).collect();
+    }
+
+    fn push_script_inputs_to_scope(&self, scope: &mut Scope) {
+        scope.push_constant("inputs", self.script_inputs.clone());
+        scope.push_constant("argv", self.script_argv.clone());
+    }
+
     fn push_state_to_scope(&self, scope: &mut Scope) {
         if !self.state_available || crate::rhai_functions::strings::is_parallel_mode() {
             scope.push("state", crate::rhai_functions::state::StateNotAvailable);
@@ -1668,11 +1762,29 @@ impl RhaiEngine {
             if crate::rhai_functions::strings::is_parallel_mode() {
                 crate::rhai_functions::strings::capture_print(text.to_string());
             } else {
-                println!("{}", text);
+                crate::rhai_functions::capture::emit_print(text);
             }
         });
     }
 
+    /// Compile `source` as a statement block, or as a single expression when
+    /// `kind` is "filter", going through the shared AST cache when enabled.
+    fn compile_cached(&self, kind: &str, source: &str) -> Result<AST, rhai::ParseError> {
+        let compile = || {
+            if kind == "filter" {
+                self.engine.compile_expression(source)
+            } else {
+                self.engine.compile(source)
+            }
+        };
+        match ast_cache::global_ast_cache() {
+            Some(cache) => cache
+                .get_or_compile(kind, source, compile)
+                .map(|ast| AST::clone(&ast)),
+            None => compile(),
+        }
+    }
+
     // Individual compilation methods for pipeline stages
     pub fn compile_filter(&mut self, filter: &str) -> Result<CompiledExpression> {
         self.compile_filter_with_includes(filter, &[])
@@ -1683,7 +1795,7 @@ impl RhaiEngine {
         filter: &str,
         includes: &[crate::config::IncludeFile],
     ) -> Result<CompiledExpression> {
-        let mut ast = self.engine.compile_expression(filter).map_err(|e| {
+        let mut ast = self.compile_cached("filter", filter).map_err(|e| {
             let msg = Self::format_rhai_diagnostic(
                 e.into(),
                 "filter compilation",
@@ -1692,23 +1804,27 @@ impl RhaiEngine {
                 None,
                 None,
                 self.use_emoji,
+                self.sandbox,
             );
             anyhow::anyhow!(msg)
         })?;
 
         for include in includes {
-            let mut include_ast = self.engine.compile(&include.content).map_err(|e| {
-                let msg = Self::format_rhai_diagnostic(
-                    e.into(),
-                    "filter include compilation",
-                    "include script",
-                    &include.content,
-                    None,
-                    None,
-                    self.use_emoji,
-                );
-                anyhow::anyhow!("{} (in {})", msg, include.path)
-            })?;
+            let mut include_ast =
+                self.compile_cached("include", &include.content)
+                    .map_err(|e| {
+                        let msg = Self::format_rhai_diagnostic(
+                            e.into(),
+                            "filter include compilation",
+                            "include script",
+                            &include.content,
+                            None,
+                            None,
+                            self.use_emoji,
+                            self.sandbox,
+                        );
+                        anyhow::anyhow!("{} (in {})", msg, include.path)
+                    })?;
 
             include_ast.set_source(include.path.clone());
 
@@ -1741,7 +1857,7 @@ impl RhaiEngine {
     }
 
     pub fn compile_exec(&mut self, exec: &str) -> Result<CompiledExpression> {
-        let ast = self.engine.compile(exec).map_err(|e| {
+        let ast = self.compile_cached("exec", exec).map_err(|e| {
             let msg = Self::format_rhai_diagnostic(
                 e.into(),
                 "exec compilation",
@@ -1750,6 +1866,7 @@ impl RhaiEngine {
                 None,
                 None,
                 self.use_emoji,
+                self.sandbox,
             );
             anyhow::anyhow!(msg)
         })?;
@@ -1771,7 +1888,7 @@ impl RhaiEngine {
     }
 
     pub fn compile_begin(&mut self, begin: &str) -> Result<CompiledExpression> {
-        let ast = self.engine.compile(begin).map_err(|e| {
+        let ast = self.compile_cached("begin", begin).map_err(|e| {
             let msg = Self::format_rhai_diagnostic(
                 e.into(),
                 "begin compilation",
@@ -1780,6 +1897,7 @@ impl RhaiEngine {
                 None,
                 None,
                 self.use_emoji,
+                self.sandbox,
             );
             anyhow::anyhow!(msg)
         })?;
@@ -1800,7 +1918,7 @@ impl RhaiEngine {
     }
 
     pub fn compile_end(&mut self, end: &str) -> Result<CompiledExpression> {
-        let ast = self.engine.compile(end).map_err(|e| {
+        let ast = self.compile_cached("end", end).map_err(|e| {
             let msg = Self::format_rhai_diagnostic(
                 e.into(),
                 "end compilation",
@@ -1809,6 +1927,7 @@ impl RhaiEngine {
                 None,
                 None,
                 self.use_emoji,
+                self.sandbox,
             );
             anyhow::anyhow!(msg)
         })?;
@@ -1829,7 +1948,7 @@ impl RhaiEngine {
     }
 
     pub fn compile_span_close(&mut self, script: &str) -> Result<CompiledExpression> {
-        let ast = self.engine.compile(script).map_err(|e| {
+        let ast = self.compile_cached("span-close", script).map_err(|e| {
             let msg = Self::format_rhai_diagnostic(
                 e.into(),
                 "span-close compilation",
@@ -1838,6 +1957,7 @@ impl RhaiEngine {
                 None,
                 None,
                 self.use_emoji,
+                self.sandbox,
             );
             anyhow::anyhow!(msg)
         })?;
@@ -1857,6 +1977,102 @@ impl RhaiEngine {
         })
     }
 
+    pub fn compile_every_emit(&mut self, script: &str) -> Result<CompiledExpression> {
+        let ast = self.compile_cached("every-emit", script).map_err(|e| {
+            let msg = Self::format_rhai_diagnostic(
+                e.into(),
+                "every-emit compilation",
+                "every-emit script",
+                script,
+                None,
+                None,
+                self.use_emoji,
+                self.sandbox,
+            );
+            anyhow::anyhow!(msg)
+        })?;
+        let field_accesses = extract_field_accesses(&ast);
+        let var_usage = detect_variable_usage(&ast);
+        Ok(CompiledExpression {
+            ast,
+            expr: script.to_string(),
+            field_accesses,
+            native_predicate: None,
+            mutates_event: false,
+            meta_usage: var_usage.meta_usage,
+            uses_meta: var_usage.uses_meta,
+            uses_conf: var_usage.uses_conf,
+            uses_line: var_usage.uses_line,
+            uses_window: var_usage.uses_window,
+        })
+    }
+
+    pub fn compile_window_script(&mut self, script: &str) -> Result<CompiledExpression> {
+        let ast = self.compile_cached("window-script", script).map_err(|e| {
+            let msg = Self::format_rhai_diagnostic(
+                e.into(),
+                "window-script compilation",
+                "window script",
+                script,
+                None,
+                None,
+                self.use_emoji,
+                self.sandbox,
+            );
+            anyhow::anyhow!(msg)
+        })?;
+        let field_accesses = extract_field_accesses(&ast);
+        let var_usage = detect_variable_usage(&ast);
+        Ok(CompiledExpression {
+            ast,
+            expr: script.to_string(),
+            field_accesses,
+            native_predicate: None,
+            mutates_event: false,
+            meta_usage: var_usage.meta_usage,
+            uses_meta: var_usage.uses_meta,
+            uses_conf: var_usage.uses_conf,
+            uses_line: var_usage.uses_line,
+            uses_window: true,
+        })
+    }
+
+    /// Compile the --exec-on-error script and install it on this engine
+    pub fn compile_error_hook(&mut self, script: &str) -> Result<()> {
+        let ast = self.compile_cached("exec-on-error", script).map_err(|e| {
+            let msg = Self::format_rhai_diagnostic(
+                e.into(),
+                "exec-on-error compilation",
+                "exec-on-error script",
+                script,
+                None,
+                None,
+                self.use_emoji,
+                self.sandbox,
+            );
+            anyhow::anyhow!(msg)
+        })?;
+        let field_accesses = extract_field_accesses(&ast);
+        let var_usage = detect_variable_usage(&ast);
+        self.compiled_error_hook = Some(CompiledExpression {
+            ast,
+            expr: script.to_string(),
+            field_accesses,
+            native_predicate: None,
+            mutates_event: false,
+            meta_usage: var_usage.meta_usage,
+            uses_meta: var_usage.uses_meta,
+            uses_conf: var_usage.uses_conf,
+            uses_line: var_usage.uses_line,
+            uses_window: var_usage.uses_window,
+        });
+        Ok(())
+    }
+
+    pub fn has_error_hook(&self) -> bool {
+        self.compiled_error_hook.is_some()
+    }
+
     // Individual execution methods for pipeline stages
     pub fn execute_compiled_filter(
         &mut self,
@@ -1907,8 +2123,7 @@ impl RhaiEngine {
         }
 
         let result = self
-            .engine
-            .eval_ast_with_scope::<bool>(&mut scope, &compiled.ast)
+            .eval_until_exit::<bool>(&mut scope, &compiled.ast)
             .map_err(|e| {
                 let detailed_msg = Self::format_rhai_diagnostic(
                     e,
@@ -1918,9 +2133,12 @@ impl RhaiEngine {
                     Some(&scope),
                     self.debug_tracker.as_ref(),
                     self.use_emoji,
+                    self.sandbox,
                 );
                 anyhow::anyhow!("{}", detailed_msg)
-            })?;
+            })?
+            // A stage stopped by exit() drops its event
+            .unwrap_or(false);
 
         self.assert_conf_not_mutated(&scope, compiled.uses_conf)
             .map_err(anyhow::Error::from)?;
@@ -1988,8 +2206,7 @@ impl RhaiEngine {
         }
 
         let _ = self
-            .engine
-            .eval_ast_with_scope::<Dynamic>(&mut scope, &compiled.ast)
+            .eval_until_exit::<Dynamic>(&mut scope, &compiled.ast)
             .map_err(|e| {
                 let detailed_msg = Self::format_rhai_diagnostic(
                     e,
@@ -1999,6 +2216,7 @@ impl RhaiEngine {
                     Some(&scope),
                     self.debug_tracker.as_ref(),
                     self.use_emoji,
+                    self.sandbox,
                 );
                 anyhow::anyhow!("{}", detailed_msg)
             })?;
@@ -2046,10 +2264,10 @@ impl RhaiEngine {
         let mut scope = self.scope_template.clone();
 
         self.push_state_to_scope(&mut scope);
+        self.push_script_inputs_to_scope(&mut scope);
 
         let _ = self
-            .engine
-            .eval_ast_with_scope::<Dynamic>(&mut scope, &compiled.ast)
+            .eval_until_exit::<Dynamic>(&mut scope, &compiled.ast)
             .map_err(|e| {
                 let detailed_msg = Self::format_rhai_diagnostic(
                     e,
@@ -2059,6 +2277,7 @@ impl RhaiEngine {
                     Some(&scope),
                     self.debug_tracker.as_ref(),
                     self.use_emoji,
+                    self.sandbox,
                 );
                 anyhow::anyhow!("{}", detailed_msg)
             })?;
@@ -2086,6 +2305,7 @@ impl RhaiEngine {
         compiled: &CompiledExpression,
         metrics: &HashMap<String, Dynamic>,
         internal: &HashMap<String, Dynamic>,
+        stats: rhai::Map,
     ) -> Result<()> {
         let mut scope = self.scope_template.clone();
 
@@ -2102,10 +2322,11 @@ impl RhaiEngine {
         }
 
         self.push_state_to_scope(&mut scope);
+        self.push_script_inputs_to_scope(&mut scope);
+        scope.push_constant("stats", stats);
 
         let _ = self
-            .engine
-            .eval_ast_with_scope::<Dynamic>(&mut scope, &compiled.ast)
+            .eval_until_exit::<Dynamic>(&mut scope, &compiled.ast)
             .map_err(|e| {
                 let detailed_msg = Self::format_rhai_diagnostic(
                     e,
@@ -2115,6 +2336,7 @@ impl RhaiEngine {
                     Some(&scope),
                     self.debug_tracker.as_ref(),
                     self.use_emoji,
+                    self.sandbox,
                 );
                 anyhow::anyhow!("{}", detailed_msg)
             })?;
@@ -2153,8 +2375,7 @@ impl RhaiEngine {
         crate::rhai_functions::file_ops::clear_pending_ops();
 
         let _ = self
-            .engine
-            .eval_ast_with_scope::<Dynamic>(&mut scope, &compiled.ast)
+            .eval_until_exit::<Dynamic>(&mut scope, &compiled.ast)
             .map_err(|e| {
                 let detailed_msg = Self::format_rhai_diagnostic(
                     e,
@@ -2164,6 +2385,130 @@ impl RhaiEngine {
                     Some(&scope),
                     self.debug_tracker.as_ref(),
                     self.use_emoji,
+                    self.sandbox,
+                );
+                anyhow::anyhow!("{}", detailed_msg)
+            })?;
+
+        self.assert_conf_not_mutated(&scope, compiled.uses_conf)
+            .map_err(anyhow::Error::from)?;
+
+        let ops = crate::rhai_functions::file_ops::take_pending_ops();
+        crate::rhai_functions::file_ops::execute_ops(&ops)?;
+
+        *metrics = Self::get_thread_tracking_state();
+        *internal = Self::get_thread_internal_state();
+
+        Ok(())
+    }
+
+    /// Run the --every-emit script for one closed window. `metrics` holds only
+    /// that window's metrics; `window` carries its start, end, events and
+    /// late count.
+    pub fn execute_compiled_every_emit(
+        &mut self,
+        compiled: &CompiledExpression,
+        metrics: &mut HashMap<String, Dynamic>,
+        internal: &mut HashMap<String, Dynamic>,
+        window: rhai::Map,
+    ) -> Result<()> {
+        Self::set_thread_tracking_state(metrics, internal);
+
+        let mut scope = self.scope_template.clone();
+        let metrics_map =
+            crate::rhai_functions::tracking::finalize_metrics_for_script(metrics, internal);
+        scope.set_value("metrics", metrics_map);
+        scope.push_constant("window", window);
+
+        if let Some(ref conf_map) = self.conf_map {
+            scope.set_value("conf", conf_map.clone());
+        }
+
+        self.push_state_to_scope(&mut scope);
+
+        crate::rhai_functions::file_ops::clear_pending_ops();
+
+        let _ = self
+            .eval_until_exit::<Dynamic>(&mut scope, &compiled.ast)
+            .map_err(|e| {
+                let detailed_msg = Self::format_rhai_diagnostic(
+                    e,
+                    "every-emit",
+                    "every-emit script",
+                    &compiled.expr,
+                    Some(&scope),
+                    self.debug_tracker.as_ref(),
+                    self.use_emoji,
+                    self.sandbox,
+                );
+                anyhow::anyhow!("{}", detailed_msg)
+            })?;
+
+        self.assert_conf_not_mutated(&scope, compiled.uses_conf)
+            .map_err(anyhow::Error::from)?;
+
+        let ops = crate::rhai_functions::file_ops::take_pending_ops();
+        crate::rhai_functions::file_ops::execute_ops(&ops)?;
+
+        *metrics = Self::get_thread_tracking_state();
+        *internal = Self::get_thread_internal_state();
+
+        Ok(())
+    }
+
+    /// Run the --exec-on-error script for one tracked error. The error is
+    /// exposed as `error_type`, `error_message`, `raw_line`, `line_num` and
+    /// `filename`; file writes are executed immediately.
+    pub fn execute_error_hook(
+        &self,
+        error: &ErrorHookInfo<'_>,
+        metrics: &mut HashMap<String, Dynamic>,
+        internal: &mut HashMap<String, Dynamic>,
+    ) -> Result<()> {
+        let Some(compiled) = self.compiled_error_hook.as_ref() else {
+            return Ok(());
+        };
+
+        Self::set_thread_tracking_state(metrics, internal);
+
+        let mut scope = self.scope_template.clone();
+        scope.push_constant("error_type", error.error_type.to_string());
+        scope.push_constant("error_message", error.message.to_string());
+        scope.push_constant("raw_line", error.raw_line.unwrap_or_default().to_string());
+        scope.push_constant(
+            "line_num",
+            error
+                .line_num
+                .map_or(Dynamic::UNIT, |n| Dynamic::from(n as i64)),
+        );
+        scope.push_constant(
+            "filename",
+            error
+                .filename
+                .map_or(Dynamic::UNIT, |f| Dynamic::from(f.to_string())),
+        );
+
+        // Set the frozen conf map (read-only)
+        if let Some(ref conf_map) = self.conf_map {
+            scope.set_value("conf", conf_map.clone());
+        }
+
+        self.push_state_to_scope(&mut scope);
+
+        crate::rhai_functions::file_ops::clear_pending_ops();
+
+        let _ = self
+            .eval_until_exit::<Dynamic>(&mut scope, &compiled.ast)
+            .map_err(|e| {
+                let detailed_msg = Self::format_rhai_diagnostic(
+                    e,
+                    "exec-on-error",
+                    "exec-on-error script",
+                    &compiled.expr,
+                    Some(&scope),
+                    self.debug_tracker.as_ref(),
+                    self.use_emoji,
+                    self.sandbox,
                 );
                 anyhow::anyhow!("{}", detailed_msg)
             })?;
@@ -2228,8 +2573,7 @@ impl RhaiEngine {
         }
 
         let result = self
-            .engine
-            .eval_ast_with_scope::<bool>(&mut scope, &compiled.ast)
+            .eval_until_exit::<bool>(&mut scope, &compiled.ast)
             .map_err(|e| {
                 let detailed_msg = Self::format_rhai_diagnostic(
                     e,
@@ -2239,9 +2583,12 @@ impl RhaiEngine {
                     Some(&scope),
                     self.debug_tracker.as_ref(),
                     self.use_emoji,
+                    self.sandbox,
                 );
                 anyhow::anyhow!("{}", detailed_msg)
-            })?;
+            })?
+            // A stage stopped by exit() drops its event
+            .unwrap_or(false);
 
         self.assert_conf_not_mutated(&scope, compiled.uses_conf)
             .map_err(anyhow::Error::from)?;
@@ -2316,8 +2663,7 @@ impl RhaiEngine {
         }
 
         let _ = self
-            .engine
-            .eval_ast_with_scope::<Dynamic>(&mut scope, &compiled.ast)
+            .eval_until_exit::<Dynamic>(&mut scope, &compiled.ast)
             .map_err(|e| {
                 let detailed_msg = Self::format_rhai_diagnostic(
                     e,
@@ -2327,6 +2673,7 @@ impl RhaiEngine {
                     Some(&scope),
                     self.debug_tracker.as_ref(),
                     self.use_emoji,
+                    self.sandbox,
                 );
                 anyhow::anyhow!("{}", detailed_msg)
             })?;
@@ -2359,6 +2706,44 @@ impl RhaiEngine {
         Ok(())
     }
 
+    /// Run the --window-script for one window position and return its value.
+    /// The script sees `window` (current event first) alongside `e`.
+    pub fn execute_compiled_window_script(
+        &mut self,
+        compiled: &CompiledExpression,
+        event: &Event,
+        window: &[Event],
+        metrics: &mut HashMap<String, Dynamic>,
+        internal: &mut HashMap<String, Dynamic>,
+    ) -> Result<Dynamic> {
+        Self::set_thread_tracking_state(metrics, internal);
+        let mut scope = self.create_scope_for_event_with_window(event, window, compiled.meta_usage);
+
+        let result = self
+            .eval_until_exit::<Dynamic>(&mut scope, &compiled.ast)
+            .map_err(|e| {
+                let detailed_msg = Self::format_rhai_diagnostic(
+                    e,
+                    "window-script",
+                    "window script",
+                    &compiled.expr,
+                    Some(&scope),
+                    self.debug_tracker.as_ref(),
+                    self.use_emoji,
+                    self.sandbox,
+                );
+                anyhow::anyhow!("{}", detailed_msg)
+            })?
+            .unwrap_or(Dynamic::UNIT);
+
+        self.assert_conf_not_mutated(&scope, compiled.uses_conf)
+            .map_err(anyhow::Error::from)?;
+
+        *metrics = Self::get_thread_tracking_state();
+        *internal = Self::get_thread_internal_state();
+        Ok(result)
+    }
+
     fn assert_conf_not_mutated(
         &self,
         scope: &Scope,
@@ -3037,6 +3422,7 @@ mod tests {
             Some(&scope),
             Some(&tracker),
             false,
+            SandboxPolicy::default(),
         );
 
         assert!(
@@ -3404,7 +3790,14 @@ mod tests {
         ));
 
         let msg = RhaiEngine::format_rhai_diagnostic(
-            outer, "filter", "script", "child()", None, None, true,
+            outer,
+            "filter",
+            "script",
+            "child()",
+            None,
+            None,
+            true,
+            SandboxPolicy::default(),
         );
 
         assert!(
@@ -31,6 +31,8 @@ pub enum FlattenStyle {
     Dot,
     /// Use underscores everywhere: "user_name", "items_0_value"
     Underscore,
+    /// Use the given character everywhere: '/' gives "user/name", "items/0/value"
+    Separator(char),
 }
 
 impl FlattenStyle {
@@ -51,6 +53,13 @@ impl FlattenStyle {
                     format!("{}_{}", parent, key)
                 }
             }
+            FlattenStyle::Separator(sep) => {
+                if parent.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}{}{}", parent, sep, key)
+                }
+            }
         }
     }
 
@@ -78,6 +87,13 @@ impl FlattenStyle {
                     format!("{}_{}", parent, index)
                 }
             }
+            FlattenStyle::Separator(sep) => {
+                if parent.is_empty() {
+                    index.to_string()
+                } else {
+                    format!("{}{}{}", parent, sep, index)
+                }
+            }
         }
     }
 }
@@ -235,9 +251,11 @@ pub fn json_to_dynamic_owned(value: serde_json::Value) -> Dynamic {
             // Try i64 first for signed integers
             if let Some(i) = n.as_i64() {
                 Dynamic::from(i)
-            // Try u64 for large unsigned integers (prevents precision loss)
+            // Larger unsigned integers keep their exact text
             } else if let Some(u) = n.as_u64() {
-                Dynamic::from(u)
+                Dynamic::from(crate::rhai_functions::json_number::JsonNumber::new(
+                    u.to_string(),
+                ))
             // Fall back to f64 for floating-point numbers
             } else if let Some(f) = n.as_f64() {
                 Dynamic::from(f)
@@ -302,6 +320,76 @@ pub const LEVEL_FIELD_NAMES: &[&str] = &[
     "@l",
 ];
 
+/// Canonical severities, lowest first. `--level warn+` keeps a rank and above.
+pub const CANONICAL_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error", "fatal"];
+
+/// How a source encodes numeric levels. Picked per input format, so parsers
+/// keep their raw values and `normalize_level` does the mapping for all of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LevelScheme {
+    /// Words and single letters (glog/klog `I`/`W`/`E`/`F`, logcat `V`/`D`/`A`);
+    /// numbers are pino/bunyan levels (10 trace ... 60 fatal)
+    #[default]
+    Standard,
+    /// As `Standard`, but numbers are syslog severities (0 emerg ... 7 debug)
+    Syslog,
+}
+
+impl LevelScheme {
+    pub fn for_format(format: &crate::config::InputFormat) -> Self {
+        match format {
+            crate::config::InputFormat::Syslog => LevelScheme::Syslog,
+            _ => LevelScheme::Standard,
+        }
+    }
+}
+
+/// Map a level value to its index in `CANONICAL_LEVELS`, or `None` when it
+/// isn't a recognizable level
+pub fn normalize_level(value: &Dynamic, scheme: LevelScheme) -> Option<usize> {
+    if let Some(number) = value.as_int().ok().or_else(|| {
+        value
+            .clone()
+            .into_string()
+            .ok()
+            .and_then(|text| text.trim().parse::<i64>().ok())
+    }) {
+        return match scheme {
+            LevelScheme::Standard => (10..70).contains(&number).then(|| number as usize / 10 - 1),
+            LevelScheme::Syslog => match number {
+                0..=2 => Some(5),
+                3 => Some(4),
+                4 => Some(3),
+                5 | 6 => Some(2),
+                7 => Some(1),
+                _ => None,
+            },
+        };
+    }
+
+    let text = value.clone().into_string().ok()?;
+    let rank = match text.trim().to_ascii_lowercase().as_str() {
+        "trace" | "finest" | "verbose" | "t" | "v" => 0,
+        "debug" | "dbg" | "fine" | "finer" | "config" | "d" => 1,
+        "info" | "inf" | "information" | "informational" | "notice" | "i" | "n" => 2,
+        "warn" | "warning" | "wrn" | "w" => 3,
+        "error" | "err" | "severe" | "e" => 4,
+        "fatal" | "critical" | "crit" | "alert" | "emerg" | "emergency" | "panic" | "f" | "a"
+        | "c" => 5,
+        _ => return None,
+    };
+    Some(rank)
+}
+
+/// The event's canonical level: the first `LEVEL_FIELD_NAMES` field whose value
+/// normalizes
+pub fn event_level_rank(event: &Event, scheme: LevelScheme) -> Option<usize> {
+    LEVEL_FIELD_NAMES
+        .iter()
+        .filter_map(|name| event.fields.get(*name))
+        .find_map(|value| normalize_level(value, scheme))
+}
+
 pub const MESSAGE_FIELD_NAMES: &[&str] = &[
     "msg",
     "message",
@@ -514,19 +602,31 @@ impl Event {
             if let Some((field_name, ts_str)) =
                 crate::timestamp::identify_timestamp_field(&self.fields, ts_config)
             {
+                // --tz-field: a valid per-event zone wins over the global default.
+                let event_timezone = ts_config
+                    .tz_field
+                    .as_ref()
+                    .and_then(|field| self.fields.get(field))
+                    .and_then(crate::timestamp::event_timezone);
+                let timezone = event_timezone
+                    .as_deref()
+                    .or(ts_config.default_timezone.as_deref());
+
                 let mut parsed = false;
                 let parsed_ts = if let Some(parser) = parser {
-                    parser.parse_ts_with_config(
+                    parser.parse_ts_with_year(
                         &ts_str,
                         ts_config.custom_format.as_deref(),
-                        ts_config.default_timezone.as_deref(),
+                        timezone,
+                        ts_config.assume_year,
                     )
                 } else {
                     crate::timestamp::with_thread_local_parser(|default_parser| {
-                        default_parser.parse_ts_with_config(
+                        default_parser.parse_ts_with_year(
                             &ts_str,
                             ts_config.custom_format.as_deref(),
-                            ts_config.default_timezone.as_deref(),
+                            timezone,
+                            ts_config.assume_year,
                         )
                     })
                 };
@@ -720,6 +820,8 @@ mod tests {
             custom_field: Some("custom_ts".to_string()),
             custom_format: None,
             default_timezone: None,
+            tz_field: None,
+            assume_year: None,
         };
 
         event.extract_timestamp_with_config(None, &config);
@@ -742,6 +844,8 @@ mod tests {
             custom_field: Some("custom_ts".to_string()),
             custom_format: None,
             default_timezone: None,
+            tz_field: None,
+            assume_year: None,
         };
 
         event.extract_timestamp_with_config(None, &config);
@@ -766,6 +870,8 @@ mod tests {
             custom_field: Some("custom_ts".to_string()),
             custom_format: None,
             default_timezone: None,
+            tz_field: None,
+            assume_year: None,
         };
 
         event.extract_timestamp_with_config(None, &config);
@@ -1029,15 +1135,12 @@ mod tests {
         let json: serde_json::Value = serde_json::from_str(&json_str).unwrap();
         let dynamic = json_to_dynamic(&json);
 
-        // Should preserve the exact u64 value, not convert to lossy f64
-        // Rhai supports u64, so we should be able to extract it
-        assert!(
-            dynamic.is::<u64>(),
-            "Expected u64 type, got: {:?}",
-            dynamic.type_name()
-        );
-        let extracted: u64 = dynamic.clone().try_cast().unwrap();
-        assert_eq!(extracted, large_u64);
+        // Should preserve the exact value as text, not convert to lossy f64
+        let extracted = dynamic
+            .clone()
+            .try_cast::<crate::rhai_functions::json_number::JsonNumber>()
+            .unwrap_or_else(|| panic!("Expected JsonNumber, got: {:?}", dynamic.type_name()));
+        assert_eq!(extracted.text, large_u64.to_string());
     }
 
     #[test]
@@ -1049,13 +1152,12 @@ mod tests {
         let json: serde_json::Value = serde_json::from_str(&json_str).unwrap();
         let dynamic = json_to_dynamic(&json);
 
-        // Should be stored as u64, not f64 (which would lose precision)
-        assert!(
-            dynamic.is::<u64>(),
-            "Expected u64 type for value > i64::MAX"
-        );
-        let extracted: u64 = dynamic.clone().try_cast().unwrap();
-        assert_eq!(extracted, value);
+        // Should be kept exactly, not as f64 (which would lose precision)
+        let extracted = dynamic
+            .clone()
+            .try_cast::<crate::rhai_functions::json_number::JsonNumber>()
+            .expect("Expected JsonNumber for value > i64::MAX");
+        assert_eq!(extracted.text, "9223372036854775808");
     }
 
     // Tests for Event field operations
@@ -1338,4 +1440,27 @@ mod tests {
         assert_eq!(style.format_array_key("", 0), "0");
         assert_eq!(style.format_array_key("arr", 5), "arr_5");
     }
+
+    #[test]
+    fn test_normalize_level_schemes() {
+        let rank = |value: Dynamic, scheme| normalize_level(&value, scheme);
+        let standard = LevelScheme::Standard;
+
+        assert_eq!(rank(Dynamic::from("WARNING"), standard), Some(3));
+        assert_eq!(rank(Dynamic::from("E"), standard), Some(4));
+        assert_eq!(rank(Dynamic::from("V"), standard), Some(0));
+        assert_eq!(rank(Dynamic::from("crit"), standard), Some(5));
+        assert_eq!(rank(Dynamic::from("verbose-ish"), standard), None);
+
+        // pino/bunyan numbers, as ints or numeric strings
+        assert_eq!(rank(Dynamic::from(30_i64), standard), Some(2));
+        assert_eq!(rank(Dynamic::from("50"), standard), Some(4));
+        assert_eq!(rank(Dynamic::from(3_i64), standard), None);
+
+        // syslog severities only under the syslog scheme
+        assert_eq!(rank(Dynamic::from(3_i64), LevelScheme::Syslog), Some(4));
+        assert_eq!(rank(Dynamic::from(0_i64), LevelScheme::Syslog), Some(5));
+        assert_eq!(rank(Dynamic::from(30_i64), LevelScheme::Syslog), None);
+        assert_eq!(rank(Dynamic::from("warn"), LevelScheme::Syslog), Some(3));
+    }
 }
@@ -0,0 +1,298 @@
+//! Skipping input files by the time in their names (`--filename-ts-format`).
+//!
+//! Rotated logs are often named after the period they cover
+//! (`app-2024-01-10-13.log.gz`). When `--since`/`--until` is set, the start
+//! time read from each name lets whole files be dropped before they are
+//! opened. A file is taken to cover the time from its own name up to the next
+//! later name, and one extra file is kept on each side of the window as a
+//! safety margin for events that straddle a rotation. Nothing is skipped
+//! unless every input name matches the pattern.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::Regex;
+use std::path::Path;
+
+/// Value of `--filename-ts-format` that tries the built-in patterns
+pub const AUTO: &str = "auto";
+
+/// Patterns tried by `--filename-ts-format auto`, most specific first
+const AUTO_PATTERNS: &[&str] = &[
+    "*%Y-%m-%dT%H:%M*",
+    "*%Y-%m-%d-%H-%M*",
+    "*%Y-%m-%d-%H*",
+    "*%Y-%m-%d_%H*",
+    "*%Y-%m-%dT%H*",
+    "*%Y%m%d%H%M*",
+    "*%Y%m%d%H*",
+    "*%Y-%m-%d*",
+    "*%Y%m%d*",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    Year,
+    ShortYear,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Epoch,
+}
+
+/// A compiled filename pattern: strftime-style date parts (`%Y %y %m %d %H
+/// %M %S %s`) and shell wildcards (`*`, `?`), matched against the file's
+/// base name.
+#[derive(Debug)]
+pub struct FilenameTsPattern {
+    regex: Regex,
+    parts: Vec<Part>,
+}
+
+impl FilenameTsPattern {
+    pub fn new(spec: &str) -> Result<Self> {
+        let mut source = String::from("^");
+        let mut parts = Vec::new();
+        let mut chars = spec.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '%' => {
+                    let spec_char = chars
+                        .next()
+                        .ok_or_else(|| anyhow!("pattern ends with a bare '%'"))?;
+                    let (part, digits) = match spec_char {
+                        'Y' => (Part::Year, r"(\d{4})"),
+                        'y' => (Part::ShortYear, r"(\d{2})"),
+                        'm' => (Part::Month, r"(\d{2})"),
+                        'd' => (Part::Day, r"(\d{2})"),
+                        'H' => (Part::Hour, r"(\d{2})"),
+                        'M' => (Part::Minute, r"(\d{2})"),
+                        'S' => (Part::Second, r"(\d{2})"),
+                        's' => (Part::Epoch, r"(\d{9,11})"),
+                        '%' => {
+                            source.push('%');
+                            continue;
+                        }
+                        other => {
+                            return Err(anyhow!(
+                                "unsupported specifier '%{}' (use %Y %y %m %d %H %M %S %s)",
+                                other
+                            ))
+                        }
+                    };
+                    source.push_str(digits);
+                    parts.push(part);
+                }
+                '*' => source.push_str(".*?"),
+                '?' => source.push('.'),
+                other => source.push_str(&regex::escape(&other.to_string())),
+            }
+        }
+        source.push('$');
+
+        let has = |part| parts.contains(&part);
+        let has_date =
+            (has(Part::Year) || has(Part::ShortYear)) && has(Part::Month) && has(Part::Day);
+        if !has_date && !has(Part::Epoch) {
+            return Err(anyhow!(
+                "pattern needs a full date (%Y or %y, %m and %d) or %s"
+            ));
+        }
+
+        Ok(Self {
+            regex: Regex::new(&source)?,
+            parts,
+        })
+    }
+
+    /// Start time encoded in the file's name, read in `timezone` (the
+    /// `--input-tz` zone, local time when unset)
+    pub fn timestamp(&self, path: &str, timezone: Option<&str>) -> Option<DateTime<Utc>> {
+        let name = Path::new(path).file_name()?.to_str()?;
+        let captures = self.regex.captures(name)?;
+
+        let (mut year, mut month, mut day) = (None, None, None);
+        let (mut hour, mut minute, mut second) = (0, 0, 0);
+        for (part, value) in self.parts.iter().zip(captures.iter().skip(1)) {
+            let value = value?.as_str();
+            let number: i64 = value.parse().ok()?;
+            match part {
+                Part::Year => year = Some(number as i32),
+                Part::ShortYear => year = Some(2000 + number as i32),
+                Part::Month => month = Some(number as u32),
+                Part::Day => day = Some(number as u32),
+                Part::Hour => hour = number as u32,
+                Part::Minute => minute = number as u32,
+                Part::Second => second = number as u32,
+                Part::Epoch => return DateTime::from_timestamp(number, 0),
+            }
+        }
+
+        let naive =
+            NaiveDate::from_ymd_opt(year?, month?, day?)?.and_hms_opt(hour, minute, second)?;
+        crate::timestamp::apply_timezone_to_naive(naive, timezone)
+    }
+}
+
+/// Files to read and files skipped, in input order
+#[derive(Debug, Default, PartialEq)]
+pub struct FileSelection {
+    pub kept: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// Split `files` into the ones that may hold events between `since` and
+/// `until` and the ones that can't. Returns `None`, meaning read everything,
+/// when any name fails to yield a time.
+pub fn select_files(
+    files: &[String],
+    spec: &str,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    timezone: Option<&str>,
+) -> Result<Option<FileSelection>> {
+    if spec == AUTO {
+        for candidate in AUTO_PATTERNS {
+            let pattern = FilenameTsPattern::new(candidate)?;
+            if let Some(selection) = select_with(files, &pattern, since, until, timezone) {
+                return Ok(Some(selection));
+            }
+        }
+        return Ok(None);
+    }
+
+    let pattern = FilenameTsPattern::new(spec)
+        .map_err(|e| anyhow!("Invalid --filename-ts-format '{}': {}", spec, e))?;
+    Ok(select_with(files, &pattern, since, until, timezone))
+}
+
+fn select_with(
+    files: &[String],
+    pattern: &FilenameTsPattern,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    timezone: Option<&str>,
+) -> Option<FileSelection> {
+    if files.is_empty() {
+        return None;
+    }
+    let stamps = files
+        .iter()
+        .map(|file| pattern.timestamp(file, timezone))
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut starts = stamps.clone();
+    starts.sort();
+    starts.dedup();
+
+    // File period `i` runs from starts[i] up to starts[i + 1]
+    let first = match since {
+        Some(since) => starts
+            .partition_point(|start| *start <= since)
+            .saturating_sub(1),
+        None => 0,
+    };
+    let last = match until {
+        Some(until) => starts.partition_point(|start| *start <= until) as isize - 1,
+        None => starts.len() as isize - 1,
+    };
+    if last < first as isize - 1 {
+        return None;
+    }
+
+    // One period of margin on each side
+    let lowest = starts[first.saturating_sub(1)];
+    let highest = starts[((last + 1).max(0) as usize).min(starts.len() - 1)];
+
+    let mut selection = FileSelection::default();
+    for (file, stamp) in files.iter().zip(stamps) {
+        if (lowest..=highest).contains(&stamp) {
+            selection.kept.push(file.clone());
+        } else {
+            selection.skipped.push(file.clone());
+        }
+    }
+    Some(selection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn hourly_files() -> Vec<String> {
+        (0..24)
+            .map(|hour| format!("/var/log/app-2024-01-10-{:02}.log.gz", hour))
+            .collect()
+    }
+
+    fn at(hour: u32, minute: u32) -> Option<DateTime<Utc>> {
+        Some(Utc.with_ymd_and_hms(2024, 1, 10, hour, minute, 0).unwrap())
+    }
+
+    #[test]
+    fn test_select_files_keeps_window_plus_margin() {
+        let files = hourly_files();
+        let selection = select_files(
+            &files,
+            "app-%Y-%m-%d-%H.log*",
+            at(10, 0),
+            at(11, 59),
+            Some("UTC"),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(selection.kept, files[9..=12]);
+        assert_eq!(selection.skipped.len(), 20);
+
+        // Open-ended windows only trim one side
+        let selection = select_files(&files, "auto", at(20, 30), None, Some("UTC"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(selection.kept, files[19..]);
+    }
+
+    #[test]
+    fn test_select_files_never_skips_on_unmatched_names() {
+        let mut files = hourly_files();
+        files.push("/var/log/app.log".to_string());
+        assert_eq!(
+            select_files(
+                &files,
+                "app-%Y-%m-%d-%H.log*",
+                at(10, 0),
+                at(11, 0),
+                Some("UTC")
+            )
+            .unwrap(),
+            None
+        );
+        assert_eq!(
+            select_files(&files, "auto", at(10, 0), at(11, 0), Some("UTC")).unwrap(),
+            None
+        );
+        // An impossible date is no match either
+        let files = vec!["app-2024-13-40.log".to_string()];
+        assert_eq!(
+            select_files(&files, "app-%Y-%m-%d.log", at(10, 0), None, Some("UTC")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_filename_ts_pattern_parts() {
+        let pattern = FilenameTsPattern::new("syslog.%Y%m%d?%H%M%S").unwrap();
+        assert_eq!(
+            pattern.timestamp("syslog.20240110_134502", Some("+01:00")),
+            Some(Utc.with_ymd_and_hms(2024, 1, 10, 12, 45, 2).unwrap())
+        );
+        let pattern = FilenameTsPattern::new("dump-%s.json").unwrap();
+        assert_eq!(
+            pattern.timestamp("dump-1704895200.json", None),
+            Some(Utc.with_ymd_and_hms(2024, 1, 10, 14, 0, 0).unwrap())
+        );
+        assert!(FilenameTsPattern::new("app-%H.log").is_err());
+        assert!(FilenameTsPattern::new("app-%Y-%m-%d-%q.log").is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::event::Event;
+use crate::pipeline;
+use crate::rhai_functions::json_number::JsonNumber;
+
+use super::utils::format_dynamic_value;
+
+use ciborium::value::{Integer, Value};
+use rhai::Dynamic;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::sync::Mutex;
+
+// CBOR formatter - writes each event as one CBOR map straight to the output
+// file. Framed output (the default) puts a 4-byte big-endian length before
+// every record so a reader can stream records without a CBOR-aware splitter;
+// unframed output is a plain CBOR sequence (RFC 8742). format() never returns
+// text; close() flushes the file.
+pub struct CborFormatter {
+    state: Mutex<CborState>,
+    framed: bool,
+}
+
+struct CborState {
+    file: Option<BufWriter<File>>,
+    /// Reused encode buffer
+    buffer: Vec<u8>,
+    error: Option<io::Error>,
+}
+
+impl CborFormatter {
+    /// Create the output file up front so an unwritable path fails before
+    /// any input is read.
+    pub fn create(path: &str, framed: bool) -> io::Result<Self> {
+        let file = File::create(path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("cannot create CBOR file '{}': {}", path, e),
+            )
+        })?;
+        Ok(Self {
+            state: Mutex::new(CborState {
+                file: Some(BufWriter::new(file)),
+                buffer: Vec::new(),
+                error: None,
+            }),
+            framed,
+        })
+    }
+
+    fn write_event(&self, state: &mut CborState, event: &Event) -> io::Result<()> {
+        let record = Value::Map(
+            event
+                .fields
+                .iter()
+                .map(|(key, value)| (Value::Text(key.clone()), dynamic_to_cbor(value)))
+                .collect(),
+        );
+
+        state.buffer.clear();
+        ciborium::ser::into_writer(&record, &mut state.buffer).map_err(io::Error::other)?;
+
+        let file = state
+            .file
+            .as_mut()
+            .ok_or_else(|| io::Error::other("CBOR output file already closed"))?;
+        if self.framed {
+            let len = u32::try_from(state.buffer.len()).map_err(|_| {
+                io::Error::other("CBOR record larger than 4 GiB cannot be length-prefixed")
+            })?;
+            file.write_all(&len.to_be_bytes())?;
+        }
+        file.write_all(&state.buffer)
+    }
+}
+
+/// Convert a Dynamic to a CBOR value. Datetimes and durations become their
+/// text rendering; anything else without a CBOR counterpart becomes text too.
+pub(crate) fn dynamic_to_cbor(value: &Dynamic) -> Value {
+    if value.is_unit() {
+        Value::Null
+    } else if let Ok(b) = value.as_bool() {
+        Value::Bool(b)
+    } else if let Ok(i) = value.as_int() {
+        Value::Integer(Integer::from(i))
+    } else if let Ok(f) = value.as_float() {
+        Value::Float(f)
+    } else if value.is::<u64>() {
+        Value::Integer(Integer::from(value.clone().cast::<u64>()))
+    } else if let Some(number) = value.read_lock::<JsonNumber>() {
+        // Integers CBOR can hold stay exact; the rest become floats
+        number
+            .text
+            .parse::<i128>()
+            .ok()
+            .and_then(|wide| Integer::try_from(wide).ok())
+            .map(Value::Integer)
+            .unwrap_or_else(|| Value::Float(number.to_f64()))
+    } else if value.is_string() {
+        Value::Text(value.clone().into_string().unwrap_or_default())
+    } else if value.is_blob() {
+        Value::Bytes(value.clone().cast::<rhai::Blob>())
+    } else if let Some(arr) = value.clone().try_cast::<rhai::Array>() {
+        Value::Array(arr.iter().map(dynamic_to_cbor).collect())
+    } else if let Some(map) = value.clone().try_cast::<rhai::Map>() {
+        Value::Map(
+            map.iter()
+                .map(|(key, val)| (Value::Text(key.to_string()), dynamic_to_cbor(val)))
+                .collect(),
+        )
+    } else {
+        Value::Text(format_dynamic_value(value).0)
+    }
+}
+
+/// Convert a decoded CBOR value to a Dynamic. Map keys that are not text are
+/// rendered as their diagnostic text; tags are unwrapped to their content.
+pub(crate) fn cbor_to_dynamic(value: Value) -> Dynamic {
+    match value {
+        Value::Null => Dynamic::UNIT,
+        Value::Bool(b) => Dynamic::from(b),
+        Value::Integer(i) => {
+            let wide = i128::from(i);
+            match i64::try_from(wide) {
+                Ok(i) => Dynamic::from(i),
+                Err(_) => match u64::try_from(wide) {
+                    Ok(u) => Dynamic::from(u),
+                    Err(_) => Dynamic::from(wide as f64),
+                },
+            }
+        }
+        Value::Float(f) => Dynamic::from(f),
+        Value::Text(s) => Dynamic::from(s),
+        Value::Bytes(b) => Dynamic::from_blob(b),
+        Value::Array(items) => Dynamic::from(
+            items
+                .into_iter()
+                .map(cbor_to_dynamic)
+                .collect::<rhai::Array>(),
+        ),
+        Value::Map(entries) => Dynamic::from(cbor_map_to_rhai(entries)),
+        Value::Tag(_, inner) => cbor_to_dynamic(*inner),
+        _ => Dynamic::UNIT,
+    }
+}
+
+pub(crate) fn cbor_map_to_rhai(entries: Vec<(Value, Value)>) -> rhai::Map {
+    let mut map = rhai::Map::new();
+    for (key, val) in entries {
+        let key = match key {
+            Value::Text(s) => s,
+            Value::Integer(i) => i128::from(i).to_string(),
+            other => format!("{:?}", other),
+        };
+        map.insert(key.into(), cbor_to_dynamic(val));
+    }
+    map
+}
+
+impl pipeline::Formatter for CborFormatter {
+    fn format(&self, event: &Event) -> String {
+        let mut state = self.state.lock().expect("cbor formatter mutex poisoned");
+        if state.error.is_none() {
+            if let Err(err) = self.write_event(&mut state, event) {
+                state.error = Some(err);
+            }
+        }
+        String::new()
+    }
+
+    fn close(&self) -> io::Result<()> {
+        let mut state = self.state.lock().expect("cbor formatter mutex poisoned");
+        if let Some(err) = state.error.take() {
+            return Err(err);
+        }
+        match state.file.take() {
+            Some(mut file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use crate::event::Event;
+use crate::pipeline;
+use chrono::{DateTime, Utc};
+use rhai::Dynamic;
+
+use super::utils::format_dynamic_value;
+
+/// Timestamp layout of the Common/Combined Log Format
+const CLF_TIMESTAMP_FORMAT: &str = "%d/%b/%Y:%H:%M:%S %z";
+
+/// Timestamp fields read for the bracketed time, in priority order. `ts` is
+/// what `-f combined` produces.
+const TIMESTAMP_FIELDS: &[&str] = &["timestamp", "ts"];
+
+// Apache/NGINX combined access log formatter. The inverse of `-f combined`:
+// `ip identity user [time] "method path protocol" status bytes "referer"
+// "user_agent"`, with `-` for missing fields. The nginx variant appends
+// `"request_time"`.
+pub struct ApacheAccessFormatter {
+    request_time: bool,
+}
+
+impl ApacheAccessFormatter {
+    /// Apache combined log format
+    pub fn new() -> Self {
+        Self {
+            request_time: false,
+        }
+    }
+
+    /// Combined log format followed by NGINX's `$request_time`
+    pub fn nginx() -> Self {
+        Self { request_time: true }
+    }
+}
+
+impl Default for ApacheAccessFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Field value as plain text, or `None` when missing, empty or `-`
+fn field_text(event: &Event, key: &str) -> Option<String> {
+    let value = event.fields.get(key)?;
+    if value.is_unit() {
+        return None;
+    }
+    let (text, _) = format_dynamic_value(value);
+    (!text.is_empty() && text != "-").then_some(text)
+}
+
+fn bare(event: &Event, key: &str) -> String {
+    field_text(event, key)
+        .map(|text| text.replace(char::is_whitespace, "_"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn quoted(event: &Event, key: &str, output: &mut String) {
+    output.push('"');
+    match field_text(event, key) {
+        Some(text) => escape_quoted_into(&text, output),
+        None => output.push('-'),
+    }
+    output.push('"');
+}
+
+/// Escape the way Apache does inside quoted fields
+fn escape_quoted_into(text: &str, output: &mut String) {
+    for ch in text.chars() {
+        match ch {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            _ => output.push(ch),
+        }
+    }
+}
+
+/// Parse an ISO 8601 or CLF timestamp value
+fn parse_timestamp(value: &Dynamic) -> Option<DateTime<Utc>> {
+    let (text, _) = format_dynamic_value(value);
+    let text = text.trim();
+    DateTime::parse_from_rfc3339(text)
+        .or_else(|_| DateTime::parse_from_str(text, CLF_TIMESTAMP_FORMAT))
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+}
+
+fn clf_timestamp(event: &Event) -> Option<String> {
+    TIMESTAMP_FIELDS
+        .iter()
+        .find_map(|key| event.fields.get(*key).and_then(parse_timestamp))
+        .or(event.parsed_ts)
+        .map(|dt| dt.format("%d/%b/%Y:%H:%M:%S +0000").to_string())
+}
+
+impl pipeline::Formatter for ApacheAccessFormatter {
+    fn format(&self, event: &Event) -> String {
+        if event.fields.is_empty() {
+            return String::new();
+        }
+
+        let mut output = String::with_capacity(160);
+        output.push_str(&bare(event, "ip"));
+        output.push(' ');
+        output.push_str(&bare(event, "identity"));
+        output.push(' ');
+        output.push_str(&bare(event, "user"));
+
+        output.push_str(" [");
+        output.push_str(clf_timestamp(event).as_deref().unwrap_or("-"));
+        output.push_str("] \"");
+
+        // The request line comes from its parts; a bare `request` field is
+        // used only when none of them are set
+        let parts = ["method", "path", "protocol"].map(|key| field_text(event, key));
+        if parts.iter().any(Option::is_some) {
+            let request = parts
+                .map(|part| part.unwrap_or_else(|| "-".to_string()))
+                .join(" ");
+            escape_quoted_into(&request, &mut output);
+        } else {
+            match field_text(event, "request") {
+                Some(request) => escape_quoted_into(&request, &mut output),
+                None => output.push('-'),
+            }
+        }
+        output.push_str("\" ");
+
+        output.push_str(&bare(event, "status"));
+        output.push(' ');
+        output.push_str(&bare(event, "bytes"));
+        output.push(' ');
+        quoted(event, "referer", &mut output);
+        output.push(' ');
+        quoted(event, "user_agent", &mut output);
+        if self.request_time {
+            output.push(' ');
+            quoted(event, "request_time", &mut output);
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::CombinedParser;
+    use crate::pipeline::{EventParser, Formatter};
+
+    const APACHE_LINE: &str = r#"192.168.1.1 - alice [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326 "http://www.example.com/start.html" "Mozilla/4.08 [en] (Win98; I ;Nav)""#;
+    const NGINX_LINE: &str = r#"10.0.0.5 - - [25/Dec/1995:10:00:00 +0000] "POST /api/login HTTP/1.1" 401 - "-" "curl/8.0" "0.123""#;
+
+    fn round_trip(line: &str, formatter: &ApacheAccessFormatter) -> (Event, Event) {
+        let parser = CombinedParser::new().unwrap();
+        let first = parser.parse(line).unwrap();
+        let formatted = formatter.format(&first);
+        let second = parser.parse(&formatted).unwrap();
+        (first, second)
+    }
+
+    #[test]
+    fn test_apache_access_round_trip_through_combined_parser() {
+        let formatter = ApacheAccessFormatter::new();
+        let (first, second) = round_trip(APACHE_LINE, &formatter);
+        assert_eq!(
+            formatter.format(&first),
+            r#"192.168.1.1 - alice [10/Oct/2000:20:55:36 +0000] "GET /apache_pb.gif HTTP/1.0" 200 2326 "http://www.example.com/start.html" "Mozilla/4.08 [en] (Win98; I ;Nav)""#
+        );
+        assert_eq!(first.parsed_ts, second.parsed_ts);
+        for key in [
+            "ip", "user", "method", "path", "protocol", "status", "bytes",
+        ] {
+            assert_eq!(
+                first.fields.get(key).map(|v| v.to_string()),
+                second.fields.get(key).map(|v| v.to_string()),
+                "field {}",
+                key
+            );
+        }
+        assert_eq!(
+            second.fields.get("user_agent").unwrap().to_string(),
+            "Mozilla/4.08 [en] (Win98; I ;Nav)"
+        );
+    }
+
+    #[test]
+    fn test_nginx_access_round_trip_keeps_request_time() {
+        let formatter = ApacheAccessFormatter::nginx();
+        let (first, second) = round_trip(NGINX_LINE, &formatter);
+        assert_eq!(formatter.format(&first), NGINX_LINE);
+        assert_eq!(
+            second.fields.get("request_time").unwrap().as_float(),
+            Ok(0.123)
+        );
+        assert!(second.fields.get("bytes").is_none());
+    }
+
+    #[test]
+    fn test_apache_access_missing_fields_and_iso_timestamp() {
+        let mut event = Event::default();
+        event.set_field(
+            "timestamp".to_string(),
+            Dynamic::from("2024-03-05T07:08:09.500+01:00"),
+        );
+        event.set_field("path".to_string(), Dynamic::from("/health"));
+        event.set_field("user_agent".to_string(), Dynamic::from("probe \"v2\""));
+        assert_eq!(
+            ApacheAccessFormatter::new().format(&event),
+            r#"- - - [05/Mar/2024:06:08:09 +0000] "- /health -" - - "-" "probe \"v2\"""#
+        );
+    }
+}
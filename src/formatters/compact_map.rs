@@ -247,6 +247,12 @@ impl LevelmapFormatter {
         }
     }
 
+    /// Replace the palette, e.g. with a `--color-theme` scheme
+    pub fn with_colors(mut self, colors: ColorScheme) -> Self {
+        self.colors = colors;
+        self
+    }
+
     #[cfg(test)]
     pub fn with_width(width: usize) -> Self {
         Self::with_width_and_legend(width, false, false)
@@ -441,8 +447,8 @@ impl KeymapFormatter {
     }
 
     fn extract_field_string(&self, event: &Event) -> Option<String> {
-        if let Some(value) = event.fields.get(&self.field_name) {
-            compact_map_utils::dynamic_to_trimmed_string(value)
+        if let Some(value) = super::utils::resolve_field_path(event, &self.field_name) {
+            compact_map_utils::dynamic_to_trimmed_string(&value)
         } else {
             None
         }
@@ -1,6 +1,8 @@
 use crate::event::{flatten_dynamic, Event, FlattenStyle};
 use crate::pipeline;
 
+use super::utils::resolve_field_path;
+
 use rhai::Dynamic;
 use std::collections::HashMap;
 use std::sync::LazyLock;
@@ -169,8 +171,8 @@ impl CsvFormatter {
         self.keys
             .iter()
             .map(|key| {
-                if let Some(value) = event.fields.get(key) {
-                    let string_value = self.format_csv_value(value);
+                if let Some(value) = resolve_field_path(event, key) {
+                    let string_value = self.format_csv_value(&value);
                     escape_csv_value(&string_value, self.delimiter)
                 } else {
                     String::new() // Empty field for missing values
@@ -229,4 +231,14 @@ impl pipeline::Formatter for CsvFormatter {
         output.push_str(&self.format_data_row(event));
         output
     }
+
+    /// A run that formats no events still gets its header row, matching
+    /// parallel mode, which writes the header before any results arrive.
+    fn finish(&self) -> Option<String> {
+        if !self.worker_mode && self.include_header && self.mark_header_written_globally() {
+            Some(self.format_header())
+        } else {
+            None
+        }
+    }
 }
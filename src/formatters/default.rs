@@ -86,6 +86,12 @@ impl DefaultFormatter {
         }
     }
 
+    /// Replace the palette, e.g. with a `--color-theme` scheme
+    pub fn with_colors(mut self, colors: ColorScheme) -> Self {
+        self.colors = colors;
+        self
+    }
+
     /// Format a Dynamic value directly into buffer for performance (zero-allocation when possible)
     fn format_dynamic_value_into(&self, key: &str, value: &Dynamic, output: &mut String) {
         // Check if this field should be formatted as a timestamp
@@ -437,12 +443,15 @@ impl DefaultFormatter {
 
         let mut first_on_line = true;
         let mut first_overall = true;
+        let (fields, ts_column) = self.display_fields(event);
 
-        for (key, value) in crate::event::ordered_fields(event) {
+        for (index, (key, value)) in fields.into_iter().enumerate() {
             // Build the field string first to measure its length
             let mut field_output = String::new();
 
-            if self.brief {
+            if ts_column == Some(index) && self.has_ts_column_layout() {
+                self.format_ts_column_into(key, value, &mut field_output);
+            } else if self.brief {
                 // Brief mode: only values (no keys, no quotes)
                 self.format_dynamic_value_brief_into(key, value, &mut field_output);
             } else {
@@ -512,14 +521,17 @@ impl DefaultFormatter {
         let estimated_capacity = event.fields.len() * 32;
         let mut output = String::with_capacity(estimated_capacity);
         let mut first = true;
+        let (fields, ts_column) = self.display_fields(event);
 
-        for (key, value) in crate::event::ordered_fields(event) {
+        for (index, (key, value)) in fields.into_iter().enumerate() {
             if !first {
                 output.push(' ');
             }
             first = false;
 
-            if self.brief {
+            if ts_column == Some(index) && self.has_ts_column_layout() {
+                self.format_ts_column_into(key, value, &mut output);
+            } else if self.brief {
                 // Brief mode: only values (no keys, no quotes)
                 self.format_dynamic_value_brief_into(key, value, &mut output);
             } else {
@@ -550,6 +562,96 @@ impl DefaultFormatter {
         output
     }
 
+    /// Fields in display order and the index of the timestamp column: the
+    /// `--ts-key` field, or else the first known timestamp field. The column
+    /// leads unless `--keys` fixed the order, and is dropped with `--no-ts`.
+    fn display_fields<'a>(
+        &self,
+        event: &'a Event,
+    ) -> (Vec<(&'a String, &'a Dynamic)>, Option<usize>) {
+        let mut fields = crate::event::ordered_fields(event);
+        let column_key = self.timestamp_formatting.column_key.as_deref();
+        let position = fields.iter().position(|(key, _)| match column_key {
+            Some(column_key) => key.as_str() == column_key,
+            None => crate::event::TIMESTAMP_FIELD_NAMES.contains(&key.as_str()),
+        });
+        let Some(position) = position else {
+            return (fields, None);
+        };
+        if self.timestamp_formatting.hide_column {
+            fields.remove(position);
+            return (fields, None);
+        }
+        if event.key_filtered {
+            return (fields, Some(position));
+        }
+        let column = fields.remove(position);
+        fields.insert(0, column);
+        (fields, Some(0))
+    }
+
+    /// Whether the timestamp column is laid out differently from other fields
+    fn has_ts_column_layout(&self) -> bool {
+        self.timestamp_formatting.column_width.is_some()
+            || (self.brief && self.timestamp_formatting.show_column_key)
+    }
+
+    /// Render the timestamp column with `--ts-width` and `--show-key-for-ts`
+    fn format_ts_column_into(&self, key: &str, value: &Dynamic, output: &mut String) {
+        if !self.brief || self.timestamp_formatting.show_column_key {
+            if !self.colors.key.is_empty() {
+                output.push_str(self.colors.key);
+            }
+            output.push_str(key);
+            if !self.colors.key.is_empty() {
+                output.push_str(self.colors.reset);
+            }
+            if !self.colors.equals.is_empty() {
+                output.push_str(self.colors.equals);
+            }
+            output.push('=');
+            if !self.colors.equals.is_empty() {
+                output.push_str(self.colors.reset);
+            }
+        }
+
+        let formatted = self
+            .should_format_as_timestamp(key)
+            .then(|| self.try_format_timestamp(value))
+            .flatten();
+        let (mut text, is_string) = match formatted {
+            Some(formatted) => (formatted, true),
+            None => self.format_default_value(value),
+        };
+        let mut padding = 0;
+        if let Some(width) = self.timestamp_formatting.column_width {
+            if let Some((cut, _)) = text.char_indices().nth(width) {
+                text.truncate(cut);
+            }
+            padding = width.saturating_sub(text.chars().count());
+        }
+
+        let quoted = is_string && !self.brief;
+        if quoted {
+            output.push('\'');
+        }
+        if !self.colors.string.is_empty() {
+            output.push_str(self.colors.string);
+        }
+        if quoted {
+            escape_single_quote_into(&text, output);
+        } else {
+            output.push_str(&text);
+        }
+        if !self.colors.string.is_empty() {
+            output.push_str(self.colors.reset);
+        }
+        if quoted {
+            output.push('\'');
+        }
+        output.push_str(&" ".repeat(padding));
+    }
+
     /// Calculate display length of a string, ignoring ANSI escape codes
     fn display_length(&self, text: &str) -> usize {
         let mut length = 0;
@@ -0,0 +1,152 @@
+use crate::cli::EcsVersion;
+use crate::event::Event;
+use crate::pipeline;
+
+use super::utils::dynamic_to_json;
+
+/// Field-name → ECS dotted-path mapping. When an event carries several aliases
+/// for the same ECS field (e.g. `msg` and `message`), the first one in output
+/// order wins and the rest fall through to `labels`. Every path here is the
+/// same in ECS 1.x and 8.x, so `--ecs-version` only changes `ecs.version`.
+const ECS_FIELD_MAP: &[(&str, &str)] = &[
+    ("level", "log.level"),
+    ("message", "message"),
+    ("msg", "message"),
+    ("ip", "source.ip"),
+    ("remote_addr", "source.ip"),
+    ("method", "http.request.method"),
+    ("status", "http.response.status_code"),
+    ("user_agent", "user_agent.original"),
+];
+
+/// Timestamp field names folded into `@timestamp`.
+const ECS_TIMESTAMP_FIELDS: &[&str] = &["ts", "timestamp"];
+
+// Elastic Common Schema formatter (NDJSON)
+pub struct EcsFormatter {
+    version: EcsVersion,
+}
+
+impl EcsFormatter {
+    pub fn new(version: EcsVersion) -> Self {
+        Self { version }
+    }
+
+    fn schema_version(&self) -> &'static str {
+        match self.version {
+            EcsVersion::V1 => "1.12.0",
+            EcsVersion::V8 => "8.11.0",
+        }
+    }
+}
+
+/// Insert `value` at a dotted path, creating intermediate objects. An existing
+/// non-object value on the way is left untouched and the insert is skipped.
+fn insert_dotted(
+    root: &mut serde_json::Map<String, serde_json::Value>,
+    path: &str,
+    value: serde_json::Value,
+) {
+    let mut parts = path.split('.').peekable();
+    let mut current = root;
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            current.entry(part.to_string()).or_insert(value);
+            return;
+        }
+        let next = current
+            .entry(part.to_string())
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        match next {
+            serde_json::Value::Object(map) => current = map,
+            _ => return,
+        }
+    }
+}
+
+impl pipeline::Formatter for EcsFormatter {
+    fn format(&self, event: &Event) -> String {
+        let mut root = serde_json::Map::new();
+        let mut labels = serde_json::Map::new();
+
+        // @timestamp comes from the parsed timestamp so it is always ISO 8601
+        // UTC, whatever layout the source used. Without one, the raw `ts` /
+        // `timestamp` values fall through to labels like any other field.
+        if let Some(ts) = event.parsed_ts {
+            root.insert(
+                "@timestamp".to_string(),
+                serde_json::Value::String(ts.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+            );
+        }
+
+        for (key, value) in crate::event::ordered_fields(event) {
+            if event.parsed_ts.is_some() && ECS_TIMESTAMP_FIELDS.contains(&key.as_str()) {
+                continue;
+            }
+
+            let json_value = dynamic_to_json(value);
+            let target = ECS_FIELD_MAP
+                .iter()
+                .find(|(name, _)| *name == key.as_str())
+                .map(|(_, path)| *path);
+
+            match target {
+                Some(path) if !has_dotted(&root, path) => {
+                    insert_dotted(&mut root, path, json_value)
+                }
+                _ => insert_label(&mut labels, key.clone(), json_value),
+            }
+        }
+
+        if !labels.is_empty() {
+            root.insert("labels".to_string(), serde_json::Value::Object(labels));
+        }
+        insert_dotted(
+            &mut root,
+            "ecs.version",
+            serde_json::Value::String(self.schema_version().to_string()),
+        );
+
+        serde_json::to_string(&serde_json::Value::Object(root)).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+/// Add an unmapped field to `labels`. ECS labels are flat keyword fields, so
+/// scalars are stored as strings, nested objects are flattened into dotted
+/// keys (`extra.x`) and arrays keep their JSON text. Nulls are left out.
+fn insert_label(
+    labels: &mut serde_json::Map<String, serde_json::Value>,
+    key: String,
+    value: serde_json::Value,
+) {
+    match value {
+        serde_json::Value::Null => {}
+        serde_json::Value::String(_) => {
+            labels.insert(key, value);
+        }
+        serde_json::Value::Object(map) => {
+            for (name, nested) in map {
+                insert_label(labels, format!("{}.{}", key, name), nested);
+            }
+        }
+        other => {
+            labels.insert(key, serde_json::Value::String(other.to_string()));
+        }
+    }
+}
+
+/// Whether a dotted path is already populated (used to keep the first alias
+/// that maps onto an ECS field).
+fn has_dotted(root: &serde_json::Map<String, serde_json::Value>, path: &str) -> bool {
+    let mut current = root;
+    let mut parts = path.split('.').peekable();
+    while let Some(part) = parts.next() {
+        match current.get(part) {
+            None => return false,
+            Some(_) if parts.peek().is_none() => return true,
+            Some(serde_json::Value::Object(map)) => current = map,
+            Some(_) => return true,
+        }
+    }
+    false
+}
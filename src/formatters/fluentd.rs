@@ -0,0 +1,91 @@
+use crate::event::Event;
+use crate::pipeline;
+
+use super::utils::dynamic_to_json;
+
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Fluentd forward-protocol layout emitted by the fluentd formatter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FluentdMode {
+    /// Message mode: one `[tag, time, record]` array per line
+    Message,
+    /// Forward mode: a single `[tag, [[time, record], ...], {}]` batch at the end
+    Forward,
+}
+
+// Fluentd forward protocol formatter (JSON encoding, as read by `fluent-cat --json`)
+pub struct FluentdFormatter {
+    tag: String,
+    mode: FluentdMode,
+    entries: Mutex<Vec<serde_json::Value>>,
+}
+
+impl FluentdFormatter {
+    pub fn new(tag: String, mode: FluentdMode) -> Self {
+        Self {
+            tag,
+            mode,
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Event time as integer Unix seconds; events without a parsed timestamp
+    /// are stamped with the time they are formatted.
+    fn event_time(event: &Event) -> i64 {
+        match event.parsed_ts {
+            Some(ts) => ts.timestamp(),
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0),
+        }
+    }
+
+    fn record(event: &Event) -> serde_json::Value {
+        let mut record = serde_json::Map::new();
+        for (key, value) in crate::event::ordered_fields(event) {
+            record.insert(key.clone(), dynamic_to_json(value));
+        }
+        serde_json::Value::Object(record)
+    }
+}
+
+impl pipeline::Formatter for FluentdFormatter {
+    fn format(&self, event: &Event) -> String {
+        let time = Self::event_time(event);
+        let record = Self::record(event);
+        match self.mode {
+            FluentdMode::Message => {
+                serde_json::to_string(&serde_json::json!([self.tag, time, record]))
+                    .unwrap_or_else(|_| "[]".to_string())
+            }
+            FluentdMode::Forward => {
+                // Buffered until finish(), which emits them as one batch
+                self.entries
+                    .lock()
+                    .expect("fluentd formatter mutex poisoned")
+                    .push(serde_json::json!([time, record]));
+                String::new()
+            }
+        }
+    }
+
+    fn finish(&self) -> Option<String> {
+        if self.mode != FluentdMode::Forward {
+            return None;
+        }
+        let entries = std::mem::take(
+            &mut *self
+                .entries
+                .lock()
+                .expect("fluentd formatter mutex poisoned"),
+        );
+        if entries.is_empty() {
+            return None;
+        }
+        let message = serde_json::json!([self.tag, entries, {}]);
+        serde_json::to_string(&message).ok()
+    }
+}
@@ -1,28 +1,156 @@
-use crate::event::Event;
+use crate::config::CollisionPolicy;
+use crate::event::{Event, FlattenStyle};
 use crate::pipeline;
+use indexmap::IndexMap;
+use rhai::Dynamic;
+use serde::Serializer;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::Mutex;
 
-use super::utils::dynamic_to_json;
+use super::utils::LosslessJson;
 
 // JSON formatter
-pub struct JsonFormatter;
+pub struct JsonFormatter {
+    /// Flatten nested maps and arrays into one level, joining keys with this
+    /// separator (--json-flat)
+    flatten: Option<char>,
+    /// How a flattened key that another field already has is resolved
+    /// (--collision)
+    collision: CollisionPolicy,
+    /// Flattened keys already reported as clashing with another field
+    warned_collisions: Mutex<HashSet<String>>,
+}
 
 impl JsonFormatter {
     pub fn new() -> Self {
-        Self
+        Self {
+            flatten: None,
+            collision: CollisionPolicy::default(),
+            warned_collisions: Mutex::new(HashSet::new()),
+        }
     }
-}
 
-impl pipeline::Formatter for JsonFormatter {
-    fn format(&self, event: &Event) -> String {
-        // Convert Dynamic values to JSON manually
-        let mut json_obj = serde_json::Map::new();
+    pub fn with_flatten(mut self, separator: Option<char>) -> Self {
+        self.flatten = separator;
+        self
+    }
+
+    pub fn with_collision(mut self, collision: CollisionPolicy) -> Self {
+        self.collision = collision;
+        self
+    }
+
+    /// Insert into a flattened object; both a literal dotted field and a
+    /// flattened nested one can claim the same name
+    fn insert_flat<'a>(
+        &self,
+        json_obj: &mut IndexMap<String, Cow<'a, Dynamic>>,
+        key: String,
+        value: Cow<'a, Dynamic>,
+        collision: CollisionPolicy,
+    ) -> Result<(), String> {
+        if !json_obj.contains_key(&key) {
+            json_obj.insert(key, value);
+            return Ok(());
+        }
+        if collision == CollisionPolicy::Last {
+            self.warn_collision(&key);
+        }
+        if let Some(name) = collision.resolve(&key, |name| json_obj.contains_key(name))? {
+            json_obj.insert(name, value);
+        }
+        Ok(())
+    }
+
+    /// Report once per key that flattening produced a name another field
+    /// already has, e.g. a literal `a.b` next to `{"a":{"b":..}}`
+    fn warn_collision(&self, key: &str) {
+        let mut warned = self
+            .warned_collisions
+            .lock()
+            .expect("json collision warnings poisoned");
+        if warned.insert(key.to_string()) {
+            eprintln!(
+                "{}",
+                crate::config::format_warning_message_auto(&format!(
+                    "--json-flat: field '{}' appears twice after flattening; the later value wins (see --collision)",
+                    key
+                ))
+            );
+        }
+    }
+
+    /// Serialize `event`, resolving flattened key collisions per `collision`
+    fn render(&self, event: &Event, collision: CollisionPolicy) -> Result<String, String> {
+        // Flattening yields owned values; plain fields are borrowed
+        let mut json_obj: IndexMap<String, Cow<'_, Dynamic>> = IndexMap::new();
 
         for (key, value) in crate::event::ordered_fields(event) {
-            let json_value = dynamic_to_json(value);
-            json_obj.insert(key.clone(), json_value);
+            match self.flatten {
+                Some(sep) if value.is_map() || value.is_array() => {
+                    let is_empty = value
+                        .read_lock::<rhai::Map>()
+                        .map(|map| map.is_empty())
+                        .or_else(|| value.read_lock::<rhai::Array>().map(|arr| arr.is_empty()))
+                        .unwrap_or(false);
+                    if is_empty {
+                        // flatten_dynamic maps empty containers to null under
+                        // a placeholder key; keep the field's own name instead
+                        self.insert_flat(
+                            &mut json_obj,
+                            key.clone(),
+                            Cow::Owned(Dynamic::UNIT),
+                            collision,
+                        )?;
+                        continue;
+                    }
+                    let style = FlattenStyle::Separator(sep);
+                    for (flat_key, flat_value) in crate::event::flatten_dynamic(value, style, 0) {
+                        self.insert_flat(
+                            &mut json_obj,
+                            format!("{}{}{}", key, sep, flat_key),
+                            Cow::Owned(flat_value),
+                            collision,
+                        )?;
+                    }
+                }
+                Some(_) => {
+                    self.insert_flat(&mut json_obj, key.clone(), Cow::Borrowed(value), collision)?;
+                }
+                None => {
+                    json_obj.insert(key.clone(), Cow::Borrowed(value));
+                }
+            }
         }
 
-        serde_json::to_string(&serde_json::Value::Object(json_obj))
+        let mut serializer = serde_json::Serializer::new(Vec::with_capacity(128));
+        let written = (&mut serializer).collect_map(
+            json_obj
+                .iter()
+                .map(|(key, value)| (key, LosslessJson(value.as_ref()))),
+        );
+        Ok(match written {
+            Ok(()) => {
+                String::from_utf8(serializer.into_inner()).unwrap_or_else(|_| "{}".to_string())
+            }
+            Err(_) => "{}".to_string(),
+        })
+    }
+}
+
+impl pipeline::Formatter for JsonFormatter {
+    fn format(&self, event: &Event) -> String {
+        // Callers that cannot reject an event get the default resolution
+        let collision = match self.collision {
+            CollisionPolicy::Error => CollisionPolicy::Last,
+            collision => collision,
+        };
+        self.render(event, collision)
             .unwrap_or_else(|_| "{}".to_string())
     }
+
+    fn try_format(&self, event: &Event) -> Result<String, String> {
+        self.render(event, self.collision)
+    }
 }
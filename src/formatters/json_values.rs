@@ -0,0 +1,352 @@
+//! Syntax highlighting for JSON held in field values (`--color-json-values`).
+//!
+//! Audit payloads and HTTP bodies often arrive as JSON text inside a string
+//! field and print as one opaque run of characters. [`JsonValueHighlighter`]
+//! wraps the default or logfmt formatter, finds those values in its output and
+//! colors their JSON syntax with the `--color-theme` palette (by default keys
+//! yellow, strings green, numbers cyan and `null`/`true`/`false` magenta). The
+//! coloring is done in one pass over the characters, without parsing into a
+//! tree.
+
+use crate::colors::ColorScheme;
+use crate::event::Event;
+use crate::pipeline::Formatter;
+
+/// How the wrapped formatter escapes string values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueEscaping {
+    /// `'...'` values of the default formatter
+    SingleQuoted,
+    /// `"..."` values of the logfmt formatter
+    Logfmt,
+}
+
+impl ValueEscaping {
+    fn escape_into(self, ch: char, output: &mut String) {
+        match (self, ch) {
+            (_, '\\') => output.push_str("\\\\"),
+            (_, '\n') => output.push_str("\\n"),
+            (_, '\t') => output.push_str("\\t"),
+            (_, '\r') => output.push_str("\\r"),
+            (Self::SingleQuoted, '\'') => output.push_str("\\'"),
+            (Self::Logfmt, '"') => output.push_str("\\\""),
+            _ => output.push(ch),
+        }
+    }
+
+    fn escape(self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len() + 8);
+        for ch in text.chars() {
+            self.escape_into(ch, &mut output);
+        }
+        output
+    }
+}
+
+/// Formatter decorator that highlights JSON objects and arrays found in the
+/// string fields of each event
+pub struct JsonValueHighlighter {
+    inner: Box<dyn Formatter>,
+    escaping: ValueEscaping,
+    colors: ColorScheme,
+}
+
+impl JsonValueHighlighter {
+    /// Wrap a [`super::DefaultFormatter`]
+    pub fn default_output(inner: Box<dyn Formatter>, colors: ColorScheme) -> Self {
+        Self {
+            inner,
+            escaping: ValueEscaping::SingleQuoted,
+            colors,
+        }
+    }
+
+    /// Wrap a [`super::LogfmtFormatter`]
+    pub fn logfmt(inner: Box<dyn Formatter>, colors: ColorScheme) -> Self {
+        Self {
+            inner,
+            escaping: ValueEscaping::Logfmt,
+            colors,
+        }
+    }
+}
+
+/// Whether a field value is JSON worth highlighting: an object or an array
+fn is_json_container(text: &str) -> bool {
+    let trimmed = text.trim();
+    (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+}
+
+/// Scalar token being colored outside a string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scalar {
+    None,
+    Number,
+    Literal,
+}
+
+/// Open `color` for the next token; returns whether a reset is then owed
+/// (themes may leave a token kind uncolored)
+fn open_color(color: &str, output: &mut String) -> bool {
+    output.push_str(color);
+    !color.is_empty()
+}
+
+/// Append `json` to `output` with its syntax colored, escaping every
+/// character the way the wrapped formatter does.
+///
+/// Works character by character. The only state kept is whether a string or
+/// scalar is open and, per nesting level, whether it is an object, which is
+/// enough to tell keys from string values.
+fn colorize_json_into(
+    json: &str,
+    escaping: ValueEscaping,
+    colors: &ColorScheme,
+    output: &mut String,
+) {
+    let mut containers: Vec<bool> = Vec::new();
+    let mut expect_key = false;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut scalar = Scalar::None;
+    let mut color_open = false;
+
+    let close_color = |output: &mut String, color_open: &mut bool| {
+        if std::mem::take(color_open) {
+            output.push_str(colors.reset);
+        }
+    };
+
+    for ch in json.chars() {
+        if in_string {
+            escaping.escape_into(ch, output);
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                close_color(output, &mut color_open);
+                in_string = false;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' => {
+                if scalar != Scalar::None {
+                    close_color(output, &mut color_open);
+                    scalar = Scalar::None;
+                }
+                let color = if expect_key {
+                    colors.json_key
+                } else {
+                    colors.json_string
+                };
+                color_open = open_color(color, output);
+                escaping.escape_into(ch, output);
+                in_string = true;
+            }
+            '-' | '0'..='9' if scalar == Scalar::None => {
+                color_open = open_color(colors.json_number, output);
+                output.push(ch);
+                scalar = Scalar::Number;
+            }
+            'a'..='z' | 'A'..='Z' if scalar == Scalar::None => {
+                color_open = open_color(colors.json_literal, output);
+                output.push(ch);
+                scalar = Scalar::Literal;
+            }
+            // Rest of a number (`.`, exponent) or literal
+            '-' | '+' | '.' | '0'..='9' | 'a'..='z' | 'A'..='Z' => output.push(ch),
+            _ => {
+                if scalar != Scalar::None {
+                    close_color(output, &mut color_open);
+                    scalar = Scalar::None;
+                }
+                match ch {
+                    '{' => {
+                        containers.push(true);
+                        expect_key = true;
+                    }
+                    '[' => {
+                        containers.push(false);
+                        expect_key = false;
+                    }
+                    '}' | ']' => {
+                        containers.pop();
+                        expect_key = false;
+                    }
+                    ',' => expect_key = containers.last() == Some(&true),
+                    ':' => expect_key = false,
+                    _ => {}
+                }
+                escaping.escape_into(ch, output);
+            }
+        }
+    }
+
+    close_color(output, &mut color_open);
+}
+
+impl Formatter for JsonValueHighlighter {
+    fn format(&self, event: &Event) -> String {
+        let mut output = self.inner.format(event);
+        if output.is_empty() {
+            return output;
+        }
+
+        for value in event.fields.values() {
+            if !value.is_string() {
+                continue;
+            }
+            let text = value.clone().into_string().unwrap_or_default();
+            if !is_json_container(&text) {
+                continue;
+            }
+            // Values the formatter truncated or wrapped are left alone
+            let rendered = self.escaping.escape(&text);
+            if let Some(start) = output.find(&rendered) {
+                let mut colored = String::with_capacity(rendered.len() * 2);
+                colorize_json_into(&text, self.escaping, &self.colors, &mut colored);
+                output.replace_range(start..start + rendered.len(), &colored);
+            }
+        }
+
+        output
+    }
+
+    fn finish(&self) -> Option<String> {
+        self.inner.finish()
+    }
+
+    fn close(&self) -> std::io::Result<()> {
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatters::{DefaultFormatter, LogfmtFormatter};
+    use rhai::Dynamic;
+
+    fn payload_event() -> Event {
+        let mut event = Event::default();
+        event.set_field("action".to_string(), Dynamic::from("login"));
+        event.set_field(
+            "payload".to_string(),
+            Dynamic::from(r#"{"user":"alice","ok":true,"tries":3,"tags":["a"]}"#),
+        );
+        event
+    }
+
+    fn unwrapped_default() -> DefaultFormatter {
+        DefaultFormatter::new_with_wrapping(
+            false,
+            false,
+            false,
+            crate::config::TimestampFormatConfig::default(),
+            false,
+            false,
+            0,
+        )
+    }
+
+    #[test]
+    fn test_colorize_json_marks_each_token_kind() {
+        let mut output = String::new();
+        colorize_json_into(
+            r#"{"user":"alice","n":-1.5e3,"x":null}"#,
+            ValueEscaping::SingleQuoted,
+            &ColorScheme::new(true),
+            &mut output,
+        );
+        assert_eq!(
+            output,
+            "{\x1b[33m\"user\"\x1b[0m:\x1b[32m\"alice\"\x1b[0m,\
+             \x1b[33m\"n\"\x1b[0m:\x1b[36m-1.5e3\x1b[0m,\
+             \x1b[33m\"x\"\x1b[0m:\x1b[35mnull\x1b[0m}"
+        );
+
+        // Strings in arrays are values, and escaped quotes don't end a string
+        let mut output = String::new();
+        colorize_json_into(
+            r#"["a\"b",1]"#,
+            ValueEscaping::Logfmt,
+            &ColorScheme::new(true),
+            &mut output,
+        );
+        assert_eq!(
+            output,
+            "[\x1b[32m\\\"a\\\\\\\"b\\\"\x1b[0m,\x1b[36m1\x1b[0m]"
+        );
+    }
+
+    #[test]
+    fn test_highlighter_colors_json_in_logfmt_output() {
+        let formatter =
+            JsonValueHighlighter::logfmt(Box::new(LogfmtFormatter::new()), ColorScheme::new(true));
+        let output = formatter.format(&payload_event());
+        assert!(output.starts_with("payload=\"{"), "{}", output);
+        assert!(output.ends_with(" action=login"), "{}", output);
+        assert!(
+            output.contains("\x1b[33m\\\"user\\\"\x1b[0m:"),
+            "{}",
+            output
+        );
+        assert!(
+            output.contains("\x1b[32m\\\"alice\\\"\x1b[0m"),
+            "{}",
+            output
+        );
+        assert!(output.contains("\x1b[35mtrue\x1b[0m"), "{}", output);
+        assert!(output.contains("\x1b[36m3\x1b[0m"), "{}", output);
+        assert!(output.contains("[\x1b[32m\\\"a\\\"\x1b[0m]"), "{}", output);
+    }
+
+    #[test]
+    fn test_highlighter_colors_json_in_default_output_only() {
+        let inner = unwrapped_default();
+        let formatter =
+            JsonValueHighlighter::default_output(Box::new(inner), ColorScheme::new(true));
+        let output = formatter.format(&payload_event());
+        assert!(output.contains("action='login'"), "{}", output);
+        assert!(output.contains("\x1b[32m\"alice\"\x1b[0m"), "{}", output);
+
+        // Plain strings and invalid JSON pass through untouched
+        let mut event = Event::default();
+        event.set_field("body".to_string(), Dynamic::from("{not json}"));
+        let inner = unwrapped_default();
+        let formatter =
+            JsonValueHighlighter::default_output(Box::new(inner), ColorScheme::new(true));
+        assert_eq!(formatter.format(&event), "body='{not json}'");
+    }
+
+    #[test]
+    fn test_highlighter_uses_theme_colors_and_skips_uncolored_tokens() {
+        let colors = ColorScheme::themed(crate::cli::ColorTheme::Mono, true);
+        let mut output = String::new();
+        colorize_json_into(
+            r#"{"k":"v","n":1,"b":true}"#,
+            ValueEscaping::SingleQuoted,
+            &colors,
+            &mut output,
+        );
+        // Mono leaves strings and numbers plain, so no reset follows them
+        assert_eq!(
+            output,
+            "{\x1b[1m\"k\"\x1b[0m:\"v\",\x1b[1m\"n\"\x1b[0m:1,\
+             \x1b[1m\"b\"\x1b[0m:\x1b[2mtrue\x1b[0m}"
+        );
+
+        let mut output = String::new();
+        colorize_json_into(
+            r#"{"k":[1,null]}"#,
+            ValueEscaping::SingleQuoted,
+            &ColorScheme::new(false),
+            &mut output,
+        );
+        assert_eq!(output, r#"{"k":[1,null]}"#);
+    }
+}
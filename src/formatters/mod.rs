@@ -1,24 +1,48 @@
+mod cbor;
+mod combined;
 mod compact_map;
 mod csv;
 mod default;
+mod ecs;
+mod fluentd;
 mod gap;
 mod hide;
 mod inspect;
 mod json;
+mod json_values;
 mod logfmt;
+#[cfg(feature = "orc")]
+mod orc;
+mod table;
 mod tailmap;
 mod utils;
+mod xml;
 
+pub(crate) use cbor::cbor_map_to_rhai;
+pub use cbor::CborFormatter;
+pub use combined::ApacheAccessFormatter;
 pub use compact_map::{KeymapFormatter, LevelmapFormatter};
 pub use csv::CsvFormatter;
 pub use default::DefaultFormatter;
+pub use ecs::EcsFormatter;
+pub use fluentd::{FluentdFormatter, FluentdMode};
 pub use gap::GapTracker;
 pub use hide::HideFormatter;
 pub use inspect::InspectFormatter;
 pub use json::JsonFormatter;
+pub use json_values::JsonValueHighlighter;
 pub use logfmt::LogfmtFormatter;
+#[cfg(feature = "orc")]
+pub use orc::OrcFormatter;
+pub use table::TableFormatter;
 pub use tailmap::TailmapFormatter;
-pub(crate) use utils::escape_for_display;
+pub(crate) use utils::{
+    dynamic_to_json, escape_for_display, remove_field_path, resolve_field_path,
+};
+pub use xml::XmlFormatter;
+
+/// Default rows per stripe for `-F orc` (--orc-stripe-rows)
+pub const DEFAULT_ORC_STRIPE_ROWS: usize = 10_000;
 
 #[cfg(test)]
 pub(crate) use csv::{escape_csv_value, needs_csv_quoting};
@@ -0,0 +1,358 @@
+use crate::event::{Event, FieldMap};
+use crate::pipeline;
+
+use super::utils::{dynamic_to_json, format_dynamic_value};
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, RecordBatchOptions, StringArray,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use orc_rust::arrow_writer::ArrowWriterBuilder;
+use rhai::Dynamic;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+// ORC formatter - writes events as an Apache ORC file instead of text lines.
+// Rows are encoded into Arrow record batches of `stripe_rows` rows, one ORC
+// stripe each, and written as soon as a stripe fills, so memory stays bounded
+// by one stripe (or the schema sample, if larger). The column schema is
+// inferred from the first SCHEMA_SAMPLE_ROWS events; later fields that do not
+// fit it are warned about once each. format() never returns text; close()
+// writes the last stripe and the footer.
+/// Events the column schema is inferred from
+const SCHEMA_SAMPLE_ROWS: usize = 100;
+
+pub struct OrcFormatter {
+    state: Mutex<OrcState>,
+    stripe_rows: usize,
+}
+
+struct OrcState {
+    rows: Vec<FieldMap>,
+    file: Option<BufWriter<File>>,
+    schema: Option<SchemaRef>,
+    columns: Vec<(String, ColumnType)>,
+    writer: Option<StripeWriter>,
+    /// Fields already reported as not fitting the fixed schema
+    unfit_fields: HashSet<String>,
+    error: Option<io::Error>,
+}
+
+/// The ORC writer is not Send, so it cannot live inside the shared formatter;
+/// it runs on its own thread and receives each stripe as it fills.
+struct StripeWriter {
+    sender: crossbeam_channel::Sender<RecordBatch>,
+    handle: JoinHandle<io::Result<()>>,
+}
+
+impl StripeWriter {
+    fn spawn(file: BufWriter<File>, schema: SchemaRef) -> Self {
+        let (sender, receiver) = crossbeam_channel::bounded::<RecordBatch>(1);
+        let handle = std::thread::spawn(move || {
+            // Every batch holds exactly one stripe's rows; a 1-byte stripe
+            // budget makes the writer flush a stripe after each write().
+            let mut writer = ArrowWriterBuilder::new(file, schema)
+                .with_stripe_byte_size(1)
+                .try_build()
+                .map_err(io::Error::other)?;
+            for batch in receiver {
+                writer.write(&batch).map_err(io::Error::other)?;
+            }
+            writer.close().map_err(io::Error::other)
+        });
+        Self { sender, handle }
+    }
+
+    /// Close the stream and wait for the footer to be written.
+    fn finish(self) -> io::Result<()> {
+        drop(self.sender);
+        self.handle
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("ORC writer thread panicked")))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ColumnType {
+    Boolean,
+    Int64,
+    Float64,
+    Utf8,
+}
+
+impl ColumnType {
+    fn of(value: &Dynamic) -> Option<Self> {
+        if value.is_unit() {
+            None
+        } else if value.is_bool() {
+            Some(ColumnType::Boolean)
+        } else if value.is_int() {
+            Some(ColumnType::Int64)
+        } else if value.is_float() {
+            Some(ColumnType::Float64)
+        } else {
+            Some(ColumnType::Utf8)
+        }
+    }
+
+    /// Widen two observed types to one both fit: int + float is float,
+    /// any other disagreement falls back to string.
+    fn reconcile(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (ColumnType::Int64, ColumnType::Float64) | (ColumnType::Float64, ColumnType::Int64) => {
+                ColumnType::Float64
+            }
+            _ => ColumnType::Utf8,
+        }
+    }
+
+    /// Whether `value` can be stored in a column of this type (ints widen
+    /// into float columns, anything renders into a string column).
+    fn fits(self, value: &Dynamic) -> bool {
+        match (self, ColumnType::of(value)) {
+            (_, None) | (ColumnType::Utf8, _) => true,
+            (ColumnType::Float64, Some(ColumnType::Int64)) => true,
+            (column, Some(observed)) => column == observed,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ColumnType::Boolean => "boolean",
+            ColumnType::Int64 => "int64",
+            ColumnType::Float64 => "float64",
+            ColumnType::Utf8 => "string",
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            ColumnType::Boolean => DataType::Boolean,
+            ColumnType::Int64 => DataType::Int64,
+            ColumnType::Float64 => DataType::Float64,
+            ColumnType::Utf8 => DataType::Utf8,
+        }
+    }
+}
+
+impl OrcFormatter {
+    /// Create the output file up front so an unwritable path fails before
+    /// any input is read.
+    pub fn create(path: &str, stripe_rows: usize) -> io::Result<Self> {
+        let file = File::create(path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("cannot create ORC file '{}': {}", path, e),
+            )
+        })?;
+        Ok(Self {
+            state: Mutex::new(OrcState {
+                rows: Vec::new(),
+                file: Some(BufWriter::new(file)),
+                schema: None,
+                columns: Vec::new(),
+                writer: None,
+                unfit_fields: HashSet::new(),
+                error: None,
+            }),
+            stripe_rows: stripe_rows.max(1),
+        })
+    }
+
+    fn infer_columns(rows: &[FieldMap]) -> Vec<(String, ColumnType)> {
+        let mut columns: Vec<(String, Option<ColumnType>)> = Vec::new();
+        for row in rows {
+            for (key, value) in row {
+                let observed = ColumnType::of(value);
+                match columns.iter_mut().find(|(name, _)| name == key) {
+                    Some((_, existing)) => {
+                        *existing = match (*existing, observed) {
+                            (Some(a), Some(b)) => Some(a.reconcile(b)),
+                            (a, b) => a.or(b),
+                        };
+                    }
+                    None => columns.push((key.clone(), observed)),
+                }
+            }
+        }
+        // A column that only ever held () carries no type information.
+        columns
+            .into_iter()
+            .map(|(name, ty)| (name, ty.unwrap_or(ColumnType::Utf8)))
+            .collect()
+    }
+
+    /// Infer the schema from the buffered rows and start the file writer.
+    fn fix_schema(state: &mut OrcState) -> io::Result<()> {
+        let columns = Self::infer_columns(&state.rows);
+        let schema: SchemaRef = Arc::new(Schema::new(
+            columns
+                .iter()
+                .map(|(name, ty)| Field::new(name, ty.data_type(), true))
+                .collect::<Vec<_>>(),
+        ));
+        let file = state
+            .file
+            .take()
+            .ok_or_else(|| io::Error::other("ORC output file already closed"))?;
+        state.writer = Some(StripeWriter::spawn(file, schema.clone()));
+        state.schema = Some(schema);
+        state.columns = columns;
+        Ok(())
+    }
+
+    /// Warn, once per field, about a value the fixed schema cannot hold: a
+    /// field first seen after the schema sample is dropped, and a value of
+    /// another type is written as null.
+    fn check_fit(state: &mut OrcState, fields: &FieldMap) {
+        for (key, value) in fields {
+            if state.unfit_fields.contains(key) {
+                continue;
+            }
+            let problem = match state.columns.iter().find(|(name, _)| name == key) {
+                None if value.is_unit() => continue,
+                None => "first appeared after the schema was fixed and is dropped".to_string(),
+                Some((_, ty)) if ty.fits(value) => continue,
+                Some((_, ty)) => format!(
+                    "has {} values in its {} column, written as null",
+                    value.type_name(),
+                    ty.name()
+                ),
+            };
+            eprintln!(
+                "{}",
+                crate::config::format_warning_message_auto(&format!(
+                    "ORC field '{}' {}; the schema comes from the first {} events",
+                    key, problem, SCHEMA_SAMPLE_ROWS
+                ))
+            );
+            state.unfit_fields.insert(key.clone());
+        }
+    }
+
+    fn column_array(rows: &[FieldMap], name: &str, ty: ColumnType) -> ArrayRef {
+        let values = rows
+            .iter()
+            .map(|row| row.get(name).filter(|v| !v.is_unit()));
+        match ty {
+            ColumnType::Boolean => Arc::new(BooleanArray::from(
+                values
+                    .map(|v| v.and_then(|v| v.as_bool().ok()))
+                    .collect::<Vec<_>>(),
+            )),
+            ColumnType::Int64 => Arc::new(Int64Array::from(
+                values
+                    .map(|v| v.and_then(|v| v.as_int().ok()))
+                    .collect::<Vec<_>>(),
+            )),
+            ColumnType::Float64 => Arc::new(Float64Array::from(
+                values
+                    .map(|v| {
+                        v.and_then(|v| {
+                            v.as_float()
+                                .ok()
+                                .or_else(|| v.as_int().ok().map(|i| i as f64))
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            ColumnType::Utf8 => Arc::new(StringArray::from(
+                values.map(|v| v.map(cell_text)).collect::<Vec<_>>(),
+            )),
+        }
+    }
+
+    /// Encode the first `count` buffered rows as one stripe and write it.
+    fn encode_stripe(state: &mut OrcState, count: usize) -> io::Result<()> {
+        let rows: Vec<FieldMap> = state.rows.drain(..count).collect();
+        let arrays: Vec<ArrayRef> = state
+            .columns
+            .iter()
+            .map(|(name, ty)| Self::column_array(&rows, name, *ty))
+            .collect();
+        let schema = state.schema.clone().expect("schema fixed before encoding");
+        let options = RecordBatchOptions::new().with_row_count(Some(rows.len()));
+        let batch = RecordBatch::try_new_with_options(schema, arrays, &options)
+            .map_err(io::Error::other)?;
+        let writer = state
+            .writer
+            .as_ref()
+            .ok_or_else(|| io::Error::other("ORC output file already closed"))?;
+        if writer.sender.send(batch).is_err() {
+            // The writer thread stopped on an error; surface it
+            let writer = state.writer.take().expect("writer checked above");
+            return writer
+                .finish()
+                .and(Err(io::Error::other("ORC writer stopped early")));
+        }
+        Ok(())
+    }
+
+    fn buffer(&self, event: &Event) -> io::Result<()> {
+        let mut state = self.state.lock().expect("orc formatter mutex poisoned");
+        if state.schema.is_some() {
+            Self::check_fit(&mut state, &event.fields);
+        }
+        state.rows.push(event.fields.clone());
+
+        if state.schema.is_none() {
+            if state.rows.len() < SCHEMA_SAMPLE_ROWS {
+                return Ok(());
+            }
+            Self::fix_schema(&mut state)?;
+        }
+        // A stripe smaller than the sample leaves several full stripes
+        // buffered once the schema is fixed
+        while state.rows.len() >= self.stripe_rows {
+            Self::encode_stripe(&mut state, self.stripe_rows)?;
+        }
+        Ok(())
+    }
+
+    fn write_file(&self, state: &mut OrcState) -> io::Result<()> {
+        if state.schema.is_none() {
+            // Fewer events than the schema sample (possibly none at all).
+            Self::fix_schema(state)?;
+        }
+        while !state.rows.is_empty() {
+            let count = state.rows.len().min(self.stripe_rows);
+            Self::encode_stripe(state, count)?;
+        }
+        state
+            .writer
+            .take()
+            .ok_or_else(|| io::Error::other("ORC output file already closed"))?
+            .finish()
+    }
+}
+
+/// String rendering for Utf8 cells: nested maps/arrays become JSON text.
+fn cell_text(value: &Dynamic) -> String {
+    if value.is_map() || value.is_array() {
+        dynamic_to_json(value).to_string()
+    } else {
+        format_dynamic_value(value).0
+    }
+}
+
+impl pipeline::Formatter for OrcFormatter {
+    fn format(&self, event: &Event) -> String {
+        if let Err(err) = self.buffer(event) {
+            let mut state = self.state.lock().expect("orc formatter mutex poisoned");
+            state.error.get_or_insert(err);
+        }
+        String::new()
+    }
+
+    fn close(&self) -> io::Result<()> {
+        let mut state = self.state.lock().expect("orc formatter mutex poisoned");
+        if let Some(err) = state.error.take() {
+            return Err(err);
+        }
+        self.write_file(&mut state)
+    }
+}
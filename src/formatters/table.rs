@@ -0,0 +1,243 @@
+use crate::event::Event;
+use crate::pipeline;
+
+use super::utils::{escape_for_display, format_dynamic_value};
+
+use std::sync::Mutex;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+// Table formatter - buffers a batch of events and prints them as aligned columns
+// with a header row. Column widths are measured per batch, so a streaming run
+// prints one table per batch and each table is sized to its own contents.
+pub struct TableFormatter {
+    state: Mutex<TableState>,
+    keys: Vec<String>,
+    boxed: bool,
+    max_field_length: Option<usize>,
+    batch_size: usize,
+}
+
+struct TableCell {
+    text: String,
+    numeric: bool,
+}
+
+struct TableState {
+    rows: Vec<Vec<(String, TableCell)>>,
+    tables_emitted: usize,
+}
+
+impl TableFormatter {
+    /// Number of events buffered before a table is printed
+    pub const BATCH_SIZE: usize = 100;
+
+    pub fn new(keys: Vec<String>, boxed: bool, max_field_length: Option<usize>) -> Self {
+        Self {
+            state: Mutex::new(TableState {
+                rows: Vec::new(),
+                tables_emitted: 0,
+            }),
+            keys,
+            boxed,
+            max_field_length,
+            batch_size: Self::BATCH_SIZE,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    fn event_to_row(&self, event: &Event) -> Vec<(String, TableCell)> {
+        event
+            .fields
+            .iter()
+            .map(|(key, value)| {
+                let numeric = value.is_int() || value.is_float();
+                let (text, _) = format_dynamic_value(value);
+                let text = self.truncate(escape_for_display(&text));
+                (key.clone(), TableCell { text, numeric })
+            })
+            .collect()
+    }
+
+    fn truncate(&self, text: String) -> String {
+        let Some(max) = self.max_field_length else {
+            return text;
+        };
+        if text.width() <= max {
+            return text;
+        }
+
+        // Reserve one column for the ellipsis marker
+        let budget = max.saturating_sub(1);
+        let mut truncated = String::new();
+        let mut used = 0;
+        for ch in text.chars() {
+            let w = ch.width().unwrap_or(0);
+            if used + w > budget {
+                break;
+            }
+            truncated.push(ch);
+            used += w;
+        }
+        truncated.push('…');
+        truncated
+    }
+
+    /// Column order: --keys when given, otherwise every field seen in the batch
+    /// in first-seen order.
+    fn columns(&self, rows: &[Vec<(String, TableCell)>]) -> Vec<String> {
+        if !self.keys.is_empty() {
+            return self.keys.clone();
+        }
+        let mut columns: Vec<String> = Vec::new();
+        for row in rows {
+            for (key, _) in row {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+        }
+        columns
+    }
+
+    fn render(&self, rows: &[Vec<(String, TableCell)>]) -> String {
+        let columns = self.columns(rows);
+        if columns.is_empty() {
+            return String::new();
+        }
+
+        let cells: Vec<Vec<Option<&TableCell>>> = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| row.iter().find(|(key, _)| key == column).map(|(_, c)| c))
+                    .collect()
+            })
+            .collect();
+
+        let headers: Vec<String> = columns
+            .iter()
+            .map(|column| self.truncate(escape_for_display(column)))
+            .collect();
+        let widths: Vec<usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(idx, header)| {
+                cells
+                    .iter()
+                    .filter_map(|row| row[idx].map(|cell| cell.text.width()))
+                    .fold(header.width(), usize::max)
+            })
+            .collect();
+        // Right-align a column when every value present in it is numeric
+        let right_aligned: Vec<bool> = (0..columns.len())
+            .map(|idx| {
+                let mut present = cells.iter().filter_map(|row| row[idx]).peekable();
+                present.peek().is_some() && present.all(|cell| cell.numeric)
+            })
+            .collect();
+
+        let mut lines = Vec::with_capacity(rows.len() + 4);
+        let header_cells: Vec<&str> = headers.iter().map(String::as_str).collect();
+        if self.boxed {
+            lines.push(self.border(&widths, '┌', '┬', '┐'));
+            lines.push(self.row_line(&header_cells, &widths, &right_aligned));
+            lines.push(self.border(&widths, '├', '┼', '┤'));
+        } else {
+            lines.push(self.row_line(&header_cells, &widths, &right_aligned));
+            lines.push(
+                widths
+                    .iter()
+                    .map(|w| "-".repeat(*w))
+                    .collect::<Vec<_>>()
+                    .join("  "),
+            );
+        }
+        for row in &cells {
+            let texts: Vec<&str> = row
+                .iter()
+                .map(|cell| cell.map(|c| c.text.as_str()).unwrap_or(""))
+                .collect();
+            lines.push(self.row_line(&texts, &widths, &right_aligned));
+        }
+        if self.boxed {
+            lines.push(self.border(&widths, '└', '┴', '┘'));
+        }
+
+        lines.join("\n")
+    }
+
+    fn row_line(&self, texts: &[&str], widths: &[usize], right_aligned: &[bool]) -> String {
+        let padded: Vec<String> = texts
+            .iter()
+            .zip(widths)
+            .zip(right_aligned)
+            .map(|((text, width), right)| {
+                let fill = " ".repeat(width.saturating_sub(text.width()));
+                if *right {
+                    format!("{}{}", fill, text)
+                } else {
+                    format!("{}{}", text, fill)
+                }
+            })
+            .collect();
+
+        if self.boxed {
+            format!("│ {} │", padded.join(" │ "))
+        } else {
+            padded.join("  ").trim_end().to_string()
+        }
+    }
+
+    fn border(&self, widths: &[usize], left: char, middle: char, right: char) -> String {
+        let segments: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+        format!("{}{}{}", left, segments.join(&middle.to_string()), right)
+    }
+
+    fn flush_batch(&self, state: &mut TableState) -> String {
+        if state.rows.is_empty() {
+            return String::new();
+        }
+        let rows = std::mem::take(&mut state.rows);
+        let table = self.render(&rows);
+        if table.is_empty() {
+            return table;
+        }
+        state.tables_emitted += 1;
+        if state.tables_emitted > 1 {
+            // Blank line between consecutive tables
+            format!("\n{}", table)
+        } else {
+            table
+        }
+    }
+}
+
+impl pipeline::Formatter for TableFormatter {
+    fn format(&self, event: &Event) -> String {
+        let mut state = self.state.lock().expect("table formatter mutex poisoned");
+        let row = self.event_to_row(event);
+        state.rows.push(row);
+
+        if state.rows.len() >= self.batch_size {
+            self.flush_batch(&mut state)
+        } else {
+            String::new()
+        }
+    }
+
+    fn finish(&self) -> Option<String> {
+        let mut state = self.state.lock().expect("table formatter mutex poisoned");
+        let table = self.flush_batch(&mut state);
+        if table.is_empty() {
+            None
+        } else {
+            Some(table)
+        }
+    }
+}
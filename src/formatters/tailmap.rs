@@ -109,7 +109,7 @@ impl TailmapFormatter {
     }
 
     fn extract_numeric_value(&self, event: &Event) -> Option<f64> {
-        event.fields.get(&self.field_name).and_then(|value| {
+        super::utils::resolve_field_path(event, &self.field_name).and_then(|value| {
             if value.is_float() {
                 value.as_float().ok()
             } else if value.is_int() {
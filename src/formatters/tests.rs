@@ -35,6 +35,108 @@ fn test_json_formatter_with_fields() {
     assert!(result.contains("\"status\":200"));
 }
 
+#[test]
+fn test_ecs_formatter_maps_known_fields() {
+    let mut event = Event::default();
+    event.set_field(
+        "ts".to_string(),
+        Dynamic::from("2025-10-04T08:00:00Z".to_string()),
+    );
+    event.set_field("level".to_string(), Dynamic::from("ERROR".to_string()));
+    event.set_field("msg".to_string(), Dynamic::from("boom".to_string()));
+    event.set_field("ip".to_string(), Dynamic::from("10.0.0.1".to_string()));
+    event.set_field("method".to_string(), Dynamic::from("GET".to_string()));
+    event.set_field("status".to_string(), Dynamic::from(500i64));
+    event.set_field(
+        "user_agent".to_string(),
+        Dynamic::from("curl/8".to_string()),
+    );
+    event.set_field("path".to_string(), Dynamic::from("/api".to_string()));
+    event.parsed_ts = Some(Utc.with_ymd_and_hms(2025, 10, 4, 8, 0, 0).unwrap());
+
+    let result = EcsFormatter::new(crate::cli::EcsVersion::V8).format(&event);
+    let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+    assert_eq!(json["@timestamp"], "2025-10-04T08:00:00.000Z");
+    assert_eq!(json["log"]["level"], "ERROR");
+    assert_eq!(json["message"], "boom");
+    assert_eq!(json["source"]["ip"], "10.0.0.1");
+    assert_eq!(json["http"]["request"]["method"], "GET");
+    assert_eq!(json["http"]["response"]["status_code"], 500);
+    assert_eq!(json["user_agent"]["original"], "curl/8");
+    assert_eq!(json["labels"]["path"], "/api");
+    assert_eq!(json["ecs"]["version"], "8.11.0");
+    assert!(json.get("ts").is_none() && json["labels"].get("ts").is_none());
+
+    // --ecs-version only changes the stamp
+    let v1 = EcsFormatter::new(crate::cli::EcsVersion::V1).format(&event);
+    let mut v1: serde_json::Value = serde_json::from_str(&v1).unwrap();
+    v1["ecs"]["version"] = "8.11.0".into();
+    assert_eq!(v1, json);
+}
+
+#[test]
+fn test_ecs_formatter_version_and_unparsed_timestamp() {
+    let mut event = Event::default();
+    event.set_field("ts".to_string(), Dynamic::from("not a time".to_string()));
+    event.set_field("msg".to_string(), Dynamic::from("first".to_string()));
+    event.set_field("message".to_string(), Dynamic::from("second".to_string()));
+
+    let result = EcsFormatter::new(crate::cli::EcsVersion::V1).format(&event);
+    let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+    // No parsed timestamp: no @timestamp, raw value kept under labels
+    assert!(json.get("@timestamp").is_none());
+    assert_eq!(json["labels"]["ts"], "not a time");
+    // First message alias wins; the other is preserved under labels
+    assert_eq!(json["message"], "first");
+    assert_eq!(json["labels"]["message"], "second");
+    assert_eq!(json["ecs"]["version"], "1.12.0");
+}
+
+#[test]
+fn test_fluentd_formatter_message_mode() {
+    let mut event = Event::default();
+    event.set_field("msg".to_string(), Dynamic::from("boom".to_string()));
+    event.set_field("status".to_string(), Dynamic::from(500i64));
+    event.parsed_ts = Some(Utc.with_ymd_and_hms(2025, 10, 4, 8, 0, 0).unwrap());
+
+    let formatter = FluentdFormatter::new("app.web".to_string(), FluentdMode::Message);
+    let json: serde_json::Value = serde_json::from_str(&formatter.format(&event)).unwrap();
+
+    assert_eq!(json[0], "app.web");
+    assert_eq!(json[1], 1759564800);
+    assert_eq!(json[2]["msg"], "boom");
+    assert_eq!(json[2]["status"], 500);
+    assert!(formatter.finish().is_none());
+
+    // Events without a parsed timestamp are stamped with the current time
+    event.parsed_ts = None;
+    let json: serde_json::Value = serde_json::from_str(&formatter.format(&event)).unwrap();
+    assert!(json[1].as_i64().unwrap() >= Utc::now().timestamp() - 60);
+}
+
+#[test]
+fn test_fluentd_formatter_forward_mode_batches_on_finish() {
+    let formatter = FluentdFormatter::new("kelora".to_string(), FluentdMode::Forward);
+    for (i, sec) in [(1i64, 0u32), (2, 5)] {
+        let mut event = Event::default();
+        event.set_field("n".to_string(), Dynamic::from(i));
+        event.parsed_ts = Some(Utc.with_ymd_and_hms(2025, 10, 4, 8, 0, sec).unwrap());
+        assert_eq!(formatter.format(&event), "");
+    }
+
+    let json: serde_json::Value = serde_json::from_str(&formatter.finish().unwrap()).unwrap();
+    assert_eq!(json[0], "kelora");
+    assert_eq!(json[1][0][0], 1759564800);
+    assert_eq!(json[1][0][1]["n"], 1);
+    assert_eq!(json[1][1][0], 1759564805);
+    assert_eq!(json[1][1][1]["n"], 2);
+    assert_eq!(json[2], serde_json::json!({}));
+    // The batch is only emitted once
+    assert!(formatter.finish().is_none());
+}
+
 #[test]
 fn test_inspect_formatter_basic() {
     let mut event = Event::default();
@@ -212,6 +314,7 @@ fn test_default_formatter_uses_ts_format_hint() {
             format_as_utc: true,
             parse_format_hint: Some("%Y/%m/%d %H.%M.%S,%f".to_string()),
             parse_timezone_hint: Some("UTC".to_string()),
+            ..Default::default()
         },
         false,
         false,
@@ -222,6 +325,83 @@ fn test_default_formatter_uses_ts_format_hint() {
     assert!(result.contains("ts='2000-01-01T17:59:55.210+00:00'"));
 }
 
+fn ts_column_event() -> Event {
+    let mut event = Event::default();
+    event.set_field("msg".to_string(), Dynamic::from("hello"));
+    event.set_field("ts".to_string(), Dynamic::from("2024-01-01T10:00:00Z"));
+    event.set_field(
+        "received_at".to_string(),
+        Dynamic::from("2024-01-01T10:00:03Z"),
+    );
+    event
+}
+
+fn ts_column_formatter(
+    brief: bool,
+    timestamp_formatting: crate::config::TimestampFormatConfig,
+) -> DefaultFormatter {
+    DefaultFormatter::new_with_wrapping(false, false, brief, timestamp_formatting, false, false, 0)
+}
+
+#[test]
+fn test_default_formatter_ts_key_moves_column_and_no_ts_hides_it() {
+    let event = ts_column_event();
+
+    let formatter = ts_column_formatter(
+        false,
+        crate::config::TimestampFormatConfig {
+            column_key: Some("received_at".to_string()),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        formatter.format(&event),
+        "received_at='2024-01-01T10:00:03Z' ts='2024-01-01T10:00:00Z' msg='hello'"
+    );
+
+    let formatter = ts_column_formatter(
+        false,
+        crate::config::TimestampFormatConfig {
+            hide_column: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        formatter.format(&event),
+        "msg='hello' received_at='2024-01-01T10:00:03Z'"
+    );
+}
+
+#[test]
+fn test_default_formatter_ts_width_and_brief_key() {
+    let event = ts_column_event();
+
+    let formatter = ts_column_formatter(
+        false,
+        crate::config::TimestampFormatConfig {
+            column_width: Some(10),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        formatter.format(&event),
+        "ts='2024-01-01' msg='hello' received_at='2024-01-01T10:00:03Z'"
+    );
+
+    let formatter = ts_column_formatter(
+        true,
+        crate::config::TimestampFormatConfig {
+            column_width: Some(22),
+            show_column_key: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        formatter.format(&event),
+        "ts=2024-01-01T10:00:00Z   hello 2024-01-01T10:00:03Z"
+    );
+}
+
 #[test]
 fn test_default_formatter_nested_values_render_as_json() {
     let mut meta = Map::new();
@@ -1187,6 +1367,62 @@ fn test_custom_scalar_types_render_without_type_name_leak() {
     );
 }
 
+fn parsed_duration_event() -> Event {
+    use crate::parsers::type_conversion::detect_duration;
+
+    let mut event = Event::default();
+    for (key, value) in [
+        ("took", Dynamic::from("45ms".to_string())),
+        ("request_time", Dynamic::from(0.123f64)),
+        ("elapsed", Dynamic::from("1m 30s".to_string())),
+    ] {
+        let duration = detect_duration(&value).expect("duration-like value");
+        event.set_field(key.to_string(), Dynamic::from(duration));
+    }
+    event
+}
+
+#[test]
+fn test_parsed_durations_round_trip_json() {
+    let result = JsonFormatter::new().format(&parsed_duration_event());
+    let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(json["took"], "45ms");
+    assert_eq!(json["request_time"], 0.123);
+    assert_eq!(json["elapsed"], "1m 30s");
+}
+
+#[test]
+fn test_parsed_durations_round_trip_logfmt() {
+    let result = LogfmtFormatter::new().format(&parsed_duration_event());
+    assert_eq!(result, "took=45ms request_time=0.123 elapsed=\"1m 30s\"");
+}
+
+#[test]
+fn test_parsed_durations_round_trip_csv() {
+    let keys = vec![
+        "took".to_string(),
+        "request_time".to_string(),
+        "elapsed".to_string(),
+    ];
+    let result = CsvFormatter::new(keys).format(&parsed_duration_event());
+    assert!(result.contains("45ms,0.123,1m 30s"), "csv: {result}");
+}
+
+#[test]
+fn test_parsed_durations_round_trip_default() {
+    let formatter = DefaultFormatter::new(
+        false,
+        false,
+        false,
+        crate::config::TimestampFormatConfig::default(),
+        false,
+        0,
+    );
+    let result = formatter.format(&parsed_duration_event());
+    assert!(result.contains("took='45ms'"), "default: {result}");
+    assert!(result.contains("request_time='0.123'"), "default: {result}");
+}
+
 #[test]
 fn test_csv_formatter_basic() {
     let keys = vec!["name".to_string(), "age".to_string(), "city".to_string()];
@@ -1273,6 +1509,22 @@ fn test_csv_formatter_no_header() {
     assert_eq!(result, "Alice,25");
 }
 
+#[test]
+fn test_csv_formatter_finish_emits_header_for_empty_run() {
+    let keys = vec!["finish_only_a".to_string(), "finish_only_b".to_string()];
+    let formatter = CsvFormatter::new(keys.clone());
+    assert_eq!(
+        formatter.finish().as_deref(),
+        Some("finish_only_a,finish_only_b")
+    );
+    // Header goes out once; no-header and worker variants never emit one
+    assert!(formatter.finish().is_none());
+    assert!(CsvFormatter::new_csv_no_header(keys.clone())
+        .finish()
+        .is_none());
+    assert!(CsvFormatter::new_worker(keys).finish().is_none());
+}
+
 #[test]
 fn test_csv_formatter_missing_fields() {
     let keys = vec!["name".to_string(), "age".to_string(), "city".to_string()];
@@ -1607,3 +1859,169 @@ fn test_gap_tracker_formats_fractional_microseconds_compactly() {
     let marker = tracker.check(second).expect("fractional marker");
     assert!(marker.contains("time gap: 1.23 seconds"));
 }
+
+fn table_event(fields: &[(&str, Dynamic)]) -> Event {
+    let mut event = Event::default();
+    for (key, value) in fields {
+        event.set_field(key.to_string(), value.clone());
+    }
+    event
+}
+
+#[test]
+fn test_table_formatter_aligns_columns() {
+    let formatter = TableFormatter::new(Vec::new(), false, None);
+
+    let first = table_event(&[
+        ("level", Dynamic::from("info")),
+        ("msg", Dynamic::from("started")),
+        ("n", Dynamic::from(5_i64)),
+    ]);
+    let second = table_event(&[
+        ("level", Dynamic::from("error")),
+        ("msg", Dynamic::from("x")),
+        ("n", Dynamic::from(120_i64)),
+    ]);
+    assert!(formatter.format(&first).is_empty());
+    assert!(formatter.format(&second).is_empty());
+
+    let output = formatter.finish().expect("table output");
+    assert_eq!(
+        output,
+        "level  msg        n\n\
+         -----  -------  ---\n\
+         info   started    5\n\
+         error  x        120"
+    );
+    assert!(formatter.finish().is_none());
+}
+
+#[test]
+fn test_table_formatter_truncates_wide_values_in_boxed_mode() {
+    let formatter = TableFormatter::new(vec!["msg".to_string()], true, Some(6));
+
+    let event = table_event(&[
+        ("msg", Dynamic::from("a very long message")),
+        ("ignored", Dynamic::from("not a column")),
+    ]);
+    assert!(formatter.format(&event).is_empty());
+
+    let output = formatter.finish().expect("table output");
+    assert_eq!(
+        output,
+        "┌────────┐\n\
+         │ msg    │\n\
+         ├────────┤\n\
+         │ a ver… │\n\
+         └────────┘"
+    );
+}
+
+#[test]
+fn test_table_formatter_measures_each_batch() {
+    let formatter = TableFormatter::new(Vec::new(), false, None).with_batch_size(2);
+
+    assert!(formatter
+        .format(&table_event(&[("id", Dynamic::from("a"))]))
+        .is_empty());
+    let first_table = formatter.format(&table_event(&[("id", Dynamic::from("bbbb"))]));
+    assert_eq!(first_table, "id\n----\na\nbbbb");
+
+    assert!(formatter
+        .format(&table_event(&[("id", Dynamic::from("c"))]))
+        .is_empty());
+    let last_table = formatter.finish().expect("partial batch");
+    assert_eq!(last_table, "\nid\n--\nc");
+}
+
+fn nested_event() -> Event {
+    let json = serde_json::json!({
+        "request": {"method": "GET", "headers": {"user-agent": "curl/8", "x.trace": "abc"}},
+        "items": [{"id": 7}, {"id": 9}],
+        "a.b": "flat"
+    });
+    let mut event = Event::default();
+    for (key, value) in json.as_object().unwrap() {
+        event.set_field(key.clone(), crate::event::json_to_dynamic(value));
+    }
+    event
+}
+
+#[test]
+fn test_resolve_field_path_walks_maps_and_arrays() {
+    let event = nested_event();
+    let resolve = |path: &str| resolve_field_path(&event, path).map(|v| v.to_string());
+
+    assert_eq!(
+        resolve("request.headers.user-agent").as_deref(),
+        Some("curl/8")
+    );
+    assert_eq!(resolve("items.1.id").as_deref(), Some("9"));
+    assert_eq!(resolve("items.-2.id").as_deref(), Some("7"));
+    assert_eq!(resolve("items[1].id").as_deref(), Some("9"));
+    assert_eq!(resolve(r"request.headers.x\.trace").as_deref(), Some("abc"));
+    // An exact top-level key wins over walking the path
+    assert_eq!(resolve("a.b").as_deref(), Some("flat"));
+    assert_eq!(resolve("items.5.id"), None);
+    assert_eq!(resolve("request.method.x"), None);
+}
+
+#[test]
+fn test_remove_field_path_keeps_parents() {
+    let mut event = nested_event();
+    assert!(remove_field_path(&mut event, "request.headers.user-agent"));
+    assert!(remove_field_path(&mut event, "items.0"));
+    assert!(!remove_field_path(&mut event, "request.missing"));
+
+    assert!(resolve_field_path(&event, "request.headers.user-agent").is_none());
+    assert_eq!(
+        resolve_field_path(&event, r"request.headers.x\.trace")
+            .unwrap()
+            .to_string(),
+        "abc"
+    );
+    assert_eq!(
+        resolve_field_path(&event, "items.0.id")
+            .unwrap()
+            .to_string(),
+        "9"
+    );
+}
+
+#[test]
+fn test_json_formatter_flattens_deeply_nested_event() {
+    let mut event = nested_event();
+    event.set_field(
+        "deep".to_string(),
+        crate::event::json_to_dynamic(
+            &serde_json::json!({"a": {"b": {"c": [1, {"d": true}]}}, "none": {}}),
+        ),
+    );
+
+    let nested: serde_json::Value =
+        serde_json::from_str(&JsonFormatter::new().format(&event)).unwrap();
+    assert_eq!(nested["deep"]["a"]["b"]["c"][1]["d"], true);
+    assert_eq!(nested["items"][1]["id"], 9);
+
+    let flat: serde_json::Value =
+        serde_json::from_str(&JsonFormatter::new().with_flatten(Some('.')).format(&event)).unwrap();
+    assert_eq!(
+        flat,
+        serde_json::json!({
+            "request.method": "GET",
+            "request.headers.user-agent": "curl/8",
+            "request.headers.x.trace": "abc",
+            "items.0.id": 7,
+            "items.1.id": 9,
+            "a.b": "flat",
+            "deep.a.b.c.0": 1,
+            "deep.a.b.c.1.d": true,
+            "deep.none": null,
+        })
+    );
+
+    let flat: serde_json::Value =
+        serde_json::from_str(&JsonFormatter::new().with_flatten(Some('_')).format(&event)).unwrap();
+    assert_eq!(flat["deep_a_b_c_1_d"], true);
+    assert_eq!(flat["items_0_id"], 7);
+}
@@ -1,4 +1,7 @@
+use crate::event::Event;
+use crate::rhai_functions::json_number::JsonNumber;
 use rhai::Dynamic;
+use serde::{Serialize, Serializer};
 
 /// Escape a string for single-line display: backslashes and common control
 /// characters become visible escape sequences, other control characters are
@@ -30,6 +33,8 @@ pub(crate) fn format_dynamic_value(value: &Dynamic) -> (String, bool) {
         } else {
             (value.to_string(), false)
         }
+    } else if let Some(number) = value.read_lock::<JsonNumber>() {
+        (number.text.clone(), false)
     } else if let Some(s) = crate::rhai_functions::datetime::render_custom_scalar(value) {
         // Custom wrapper scalars (datetime, duration) render via their Display;
         // flag as quotable so formats that quote on spaces (e.g. "1m 30s") do so.
@@ -58,7 +63,7 @@ pub(super) fn indent_multiline_value(value: &str, indent: &str) -> String {
 }
 
 /// Convert rhai::Dynamic to serde_json::Value recursively
-pub(super) fn dynamic_to_json(value: &Dynamic) -> serde_json::Value {
+pub(crate) fn dynamic_to_json(value: &Dynamic) -> serde_json::Value {
     if value.is_string() {
         if let Ok(s) = value.clone().into_string() {
             serde_json::Value::String(s)
@@ -87,6 +92,19 @@ pub(super) fn dynamic_to_json(value: &Dynamic) -> serde_json::Value {
         }
     } else if value.is_unit() {
         serde_json::Value::Null
+    } else if value.is::<u64>() {
+        // Integers above i64::MAX are parsed as u64; keep them numbers
+        serde_json::Value::Number(serde_json::Number::from(value.clone().cast::<u64>()))
+    } else if let Some(number) = value.read_lock::<JsonNumber>() {
+        // Exact when the text fits u64; use LosslessJson to keep the rest
+        number
+            .text
+            .parse::<u64>()
+            .map(serde_json::Number::from)
+            .ok()
+            .or_else(|| serde_json::Number::from_f64(number.to_f64()))
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|| serde_json::Value::String(number.text.clone()))
     } else if let Some(arr) = value.clone().try_cast::<rhai::Array>() {
         // Convert Rhai array to JSON array recursively
         let json_array: Vec<serde_json::Value> = arr.iter().map(dynamic_to_json).collect();
@@ -98,6 +116,8 @@ pub(super) fn dynamic_to_json(value: &Dynamic) -> serde_json::Value {
             json_obj.insert(key.to_string(), dynamic_to_json(&val));
         }
         serde_json::Value::Object(json_obj)
+    } else if let Some(number) = numeric_duration(value) {
+        serde_json::Value::Number(number)
     } else if let Some(s) = crate::rhai_functions::datetime::render_custom_scalar(value) {
         serde_json::Value::String(s)
     } else {
@@ -105,3 +125,188 @@ pub(super) fn dynamic_to_json(value: &Dynamic) -> serde_json::Value {
         serde_json::Value::String(value.to_string())
     }
 }
+
+/// Serializes a Dynamic like [`dynamic_to_json`], except that a
+/// [`JsonNumber`] is written as its original text, so numbers read from JSON
+/// come back out digit for digit
+pub(crate) struct LosslessJson<'a>(pub &'a Dynamic);
+
+impl Serialize for LosslessJson<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = self.0;
+        if let Some(number) = value.read_lock::<JsonNumber>() {
+            let raw = serde_json::value::RawValue::from_string(number.text.clone())
+                .map_err(serde::ser::Error::custom)?;
+            return raw.serialize(serializer);
+        }
+        if let Some(arr) = value.read_lock::<rhai::Array>() {
+            return serializer.collect_seq(arr.iter().map(LosslessJson));
+        }
+        if let Some(map) = value.read_lock::<rhai::Map>() {
+            return serializer.collect_map(
+                map.iter()
+                    .map(|(key, val)| (key.as_str(), LosslessJson(val))),
+            );
+        }
+        dynamic_to_json(value).serialize(serializer)
+    }
+}
+
+/// A duration parsed from a bare number by --parse-durations stays a JSON
+/// number, unless --duration-unit asks for another rendering.
+fn numeric_duration(value: &Dynamic) -> Option<serde_json::Number> {
+    let dur = value.read_lock::<crate::rhai_functions::datetime::DurationWrapper>()?;
+    if crate::rhai_functions::datetime::duration_output_unit().is_some() {
+        return None;
+    }
+    let original = dur.original.as_deref()?;
+    if !crate::parsers::type_conversion::looks_like_json_number(original) {
+        return None;
+    }
+    original
+        .parse::<i64>()
+        .ok()
+        .map(serde_json::Number::from)
+        .or_else(|| {
+            original
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+        })
+}
+
+/// Split a field path into its segments. Segments are separated by `.`, and
+/// array positions may also be written as `[N]` (`items[0].id` is
+/// `items.0.id`). A key that itself contains a dot or bracket is written with
+/// a backslash escape (`\.`, `\[`, `\\`), e.g. `labels.app\.kubernetes\.io/name`.
+fn field_path_segments(path: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    // Set after `[N]`, which ends a segment without a following `.`
+    let mut closed_index = false;
+    let mut chars = path.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some(next @ ('.' | '[' | '\\')) => current.push(next),
+                Some(next) => {
+                    current.push('\\');
+                    current.push(next);
+                }
+                None => current.push('\\'),
+            },
+            // The `.` in `[0].id` continues the path; it starts no empty segment
+            '.' if closed_index && current.is_empty() => {}
+            '.' => segments.push(std::mem::take(&mut current)),
+            '[' => {
+                if !current.is_empty() || segments.is_empty() {
+                    segments.push(std::mem::take(&mut current));
+                }
+                segments.push(chars.by_ref().take_while(|&c| c != ']').collect());
+                closed_index = true;
+                continue;
+            }
+            c => current.push(c),
+        }
+        closed_index = false;
+    }
+    if !closed_index || !current.is_empty() {
+        segments.push(current);
+    }
+    segments
+}
+
+/// Look up a field by the name used in field-selecting options (`--keys`,
+/// `--exclude-keys`, column and map fields). A top-level field with exactly
+/// that name wins; otherwise the name is walked as a path through nested maps,
+/// with numeric segments indexing arrays (`items.0.id`, `items.-1.id`).
+pub(crate) fn resolve_field_path(event: &Event, path: &str) -> Option<Dynamic> {
+    if let Some(value) = event.fields.get(path) {
+        return Some(value.clone());
+    }
+
+    let segments = field_path_segments(path);
+    let (first, rest) = segments.split_first()?;
+    if rest.is_empty() {
+        // Only differs from the lookup above when the name contained escapes
+        return event.fields.get(first.as_str()).cloned();
+    }
+    let mut current = event.fields.get(first.as_str())?.clone();
+    for segment in rest {
+        current = child_value(&current, segment)?;
+    }
+    Some(current)
+}
+
+fn child_value(value: &Dynamic, segment: &str) -> Option<Dynamic> {
+    if let Some(map) = value.read_lock::<rhai::Map>() {
+        return map.get(segment).cloned();
+    }
+    let array = value.read_lock::<rhai::Array>()?;
+    let index = array_index(segment, array.len())?;
+    array.get(index).cloned()
+}
+
+/// Array position for a numeric path segment; negative values count from the end.
+fn array_index(segment: &str, len: usize) -> Option<usize> {
+    let index: i64 = segment.parse().ok()?;
+    if index < 0 {
+        len.checked_sub(index.unsigned_abs() as usize)
+    } else {
+        Some(index as usize)
+    }
+}
+
+/// Remove the value a field path points at (see [`resolve_field_path`]),
+/// leaving the containing maps in place. Returns whether anything was removed.
+pub(crate) fn remove_field_path(event: &mut Event, path: &str) -> bool {
+    if event.fields.shift_remove(path).is_some() {
+        return true;
+    }
+
+    let segments = field_path_segments(path);
+    let Some((first, rest)) = segments.split_first() else {
+        return false;
+    };
+    let Some((last, parents)) = rest.split_last() else {
+        return event.fields.shift_remove(first.as_str()).is_some();
+    };
+    let Some(root) = event.fields.get_mut(first.as_str()) else {
+        return false;
+    };
+    remove_child(root, parents, last)
+}
+
+fn remove_child(value: &mut Dynamic, parents: &[String], last: &str) -> bool {
+    match parents.split_first() {
+        Some((segment, rest)) => {
+            if let Some(mut map) = value.write_lock::<rhai::Map>() {
+                return map
+                    .get_mut(segment.as_str())
+                    .is_some_and(|child| remove_child(child, rest, last));
+            }
+            let Some(mut array) = value.write_lock::<rhai::Array>() else {
+                return false;
+            };
+            let len = array.len();
+            array_index(segment, len)
+                .and_then(|index| array.get_mut(index))
+                .is_some_and(|child| remove_child(child, rest, last))
+        }
+        None => {
+            if let Some(mut map) = value.write_lock::<rhai::Map>() {
+                return map.remove(last).is_some();
+            }
+            let Some(mut array) = value.write_lock::<rhai::Array>() else {
+                return false;
+            };
+            match array_index(last, array.len()) {
+                Some(index) if index < array.len() => {
+                    let _ = array.remove(index);
+                    true
+                }
+                _ => false,
+            }
+        }
+    }
+}
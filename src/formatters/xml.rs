@@ -0,0 +1,172 @@
+use crate::event::Event;
+use crate::pipeline;
+use rhai::Dynamic;
+
+use super::utils::format_dynamic_value;
+
+/// Element wrapping each event
+const EVENT_ELEMENT: &str = "event";
+
+/// Element used for items of an array nested directly in another array
+const ITEM_ELEMENT: &str = "item";
+
+// Simple XML formatter for SIEMs that ingest XML: one `<event>` element per
+// line, with one child element per field. Nested maps become nested elements
+// and arrays repeat their element once per item.
+pub struct XmlFormatter;
+
+impl XmlFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for XmlFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Turn a field name into a valid XML element name. Characters not allowed in
+/// names become `_`, and names that would start with a digit, `-`, `.` or the
+/// reserved `xml` prefix get a leading `_`.
+fn element_name(key: &str) -> String {
+    let mut name: String = key
+        .chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let needs_prefix = match name.chars().next() {
+        None => true,
+        Some(first) => !(first.is_alphabetic() || first == '_'),
+    } || name.to_ascii_lowercase().starts_with("xml");
+    if needs_prefix {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Escape element text. Control characters XML 1.0 cannot hold become U+FFFD.
+fn escape_text_into(text: &str, output: &mut String) {
+    for ch in text.chars() {
+        match ch {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '\t' | '\n' | '\r' => output.push(ch),
+            c if (c as u32) < 0x20 => output.push('\u{FFFD}'),
+            c => output.push(c),
+        }
+    }
+}
+
+fn write_element(name: &str, value: &Dynamic, output: &mut String) {
+    if let Some(items) = value.read_lock::<rhai::Array>() {
+        for item in items.iter() {
+            if item.is::<rhai::Array>() {
+                // An array of arrays keeps its inner items under one element
+                output.push('<');
+                output.push_str(name);
+                output.push('>');
+                write_element(ITEM_ELEMENT, item, output);
+                output.push_str("</");
+                output.push_str(name);
+                output.push('>');
+            } else {
+                write_element(name, item, output);
+            }
+        }
+        return;
+    }
+
+    if value.is_unit() {
+        output.push('<');
+        output.push_str(name);
+        output.push_str("/>");
+        return;
+    }
+
+    output.push('<');
+    output.push_str(name);
+    output.push('>');
+    if let Some(map) = value.read_lock::<rhai::Map>() {
+        for (key, child) in map.iter() {
+            write_element(&element_name(key), child, output);
+        }
+    } else {
+        let (text, _) = format_dynamic_value(value);
+        escape_text_into(&text, output);
+    }
+    output.push_str("</");
+    output.push_str(name);
+    output.push('>');
+}
+
+impl pipeline::Formatter for XmlFormatter {
+    fn format(&self, event: &Event) -> String {
+        let mut output = String::with_capacity(64 + event.fields.len() * 32);
+        output.push('<');
+        output.push_str(EVENT_ELEMENT);
+        output.push('>');
+        for (key, value) in &event.fields {
+            write_element(&element_name(key), value, &mut output);
+        }
+        output.push_str("</");
+        output.push_str(EVENT_ELEMENT);
+        output.push('>');
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::Formatter;
+
+    #[test]
+    fn test_xml_escapes_text_and_sanitizes_names() {
+        let mut event = Event::default();
+        event.set_field("msg".to_string(), Dynamic::from("a < b && c > \"d\"\u{7}"));
+        event.set_field("http status".to_string(), Dynamic::from(404_i64));
+        event.set_field("1st".to_string(), Dynamic::from(true));
+        event.set_field("xmlns".to_string(), Dynamic::from("x"));
+        event.set_field("<tag>".to_string(), Dynamic::UNIT);
+
+        assert_eq!(
+            XmlFormatter::new().format(&event),
+            "<event><msg>a &lt; b &amp;&amp; c &gt; \"d\"\u{FFFD}</msg>\
+             <http_status>404</http_status><_1st>true</_1st>\
+             <_xmlns>x</_xmlns><_tag_/></event>"
+        );
+    }
+
+    #[test]
+    fn test_xml_nests_maps_and_repeats_arrays() {
+        let mut user = rhai::Map::new();
+        user.insert("name".into(), Dynamic::from("alice"));
+        user.insert(
+            "roles".into(),
+            Dynamic::from(vec![Dynamic::from("admin"), Dynamic::from("ops")]),
+        );
+        let mut event = Event::default();
+        event.set_field("user".to_string(), Dynamic::from(user));
+        event.set_field(
+            "matrix".to_string(),
+            Dynamic::from(vec![Dynamic::from(vec![
+                Dynamic::from(1_i64),
+                Dynamic::from(2_i64),
+            ])]),
+        );
+
+        assert_eq!(
+            XmlFormatter::new().format(&event),
+            "<event><user><name>alice</name><roles>admin</roles><roles>ops</roles></user>\
+             <matrix><item>1</item><item>2</item></matrix></event>"
+        );
+    }
+}
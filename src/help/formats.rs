@@ -15,6 +15,14 @@ cef
           [ts, host - from optional syslog prefix]
           + all extension key=value pairs become top-level fields
 
+leef
+  IBM QRadar Log Event Extended Format, LEEF 1.0 and 2.0
+  Fields: leefver, vendor, product, version, event_id
+          [ts, host - from optional syslog prefix]
+          + all key=value attributes become top-level fields
+  Note: 1.0 attributes are tab-separated; 2.0 names its delimiter in the
+  header ('^', 'x5E', or empty for tab). Header values use CEF's \| escape.
+
 cols:<spec>
   Custom column-based parsing with whitespace or custom separator
   Fields: User-defined via spec
@@ -34,6 +42,17 @@ combined
           [identity, user, bytes, referer, agent, request_time]
   Note: Fields in brackets are optional (omitted if value is "-")
 
+haproxy
+  HAProxy traffic logs (option httplog / option tcplog), syslog-wrapped or raw
+  Fields: client_ip, client_port, accept_date, frontend, backend, server,
+          Tq, Tw, Tc, Tr, Tt, status_code, bytes_read, termination_state,
+          actconn, feconn, beconn, srv_conn, retries, srv_queue, backend_queue,
+          request, method, path, protocol, ts
+          [host, pid, request_cookie, response_cookie, request_headers,
+          response_headers]
+  Note: TCP lines have no Tq/Tr, status_code or request; timers are -1 for a
+  phase that never completed. Detected under -f auto ahead of syslog.
+
 csv / tsv / csvnh / tsvnh
   Comma/tab-separated values, with/without headers
   Fields: Header names or c1, c2, c3...
@@ -57,6 +76,22 @@ logfmt
   Heroku-style key=value pairs
   Fields: All parsed keys
 
+netflow5 / netflow9 / ipfix
+  Binary NetFlow v5, NetFlow v9, or IPFIX export packets (a file of raw
+  PDUs or a UDP payload dump, optionally gzip/zstd compressed)
+  Fields: src_ip, dst_ip, src_port, dst_port, protocol, bytes, packets,
+          start_ms, end_ms, tcp_flags, tos (one event per flow record)
+  Note: v9/IPFIX data records are decoded once their template has been
+        seen; start_ms/end_ms are Unix epoch milliseconds
+
+pcap
+  Classic libpcap capture (tcpdump -w); TCP streams are reassembled and
+  each HTTP/1.x request/response pair becomes one event
+  Fields: timestamp_us, src_ip, src_port, dst_ip, dst_port, method, path,
+          http_version, status_code, request_headers, response_headers
+  Note: src is the client; headers are lower-cased, at most 50 per
+        direction; pcapng and HTTPS/HTTP2 traffic are not decoded
+
 raw
   Plain text, one event per line, preserved verbatim — unlike 'line', no
   trailing newline/CR is trimmed and backslashes and other artifacts are
@@ -81,7 +116,6 @@ Built-in application-log formats
     apache-error    Apache error log ("[Fri Oct 11 14:32:52 2024] [core:error] ... msg")
     cri             Kubernetes CRI/containerd log (2024-07-17T12:12:05.0Z stdout F msg)
     glog            Go/glog and Kubernetes klog (I0102 15:04:05.123 1 f.go:42] msg)
-    haproxy         HAProxy http/tcp traffic log (via syslog); use -f haproxy
     iso8601-level   ISO-8601 timestamp + level + message (2024-01-02T15:04:05Z INFO msg)
     log4j           log4j / Java (2024-01-02 15:04:05,123 INFO [main] logger - msg)
     nginx-error     nginx error log (2024/01/02 15:04:05 [error] 29#29: msg)
@@ -95,10 +129,8 @@ Built-in application-log formats
   one matches, it emits 'ts' (timestamp), 'level', 'msg', and format-specific
   extras (thread, logger, pid, ...).
   Notes: glog/redis omit the year, so 'ts' assumes the current year (like
-  syslog). haproxy lines are syslog-wrapped, so under -f auto they are detected
-  as 'syslog' — pass -f haproxy to extract the structured fields. The access-log
-  formats ('s3', 'haproxy') keep only a curated set of useful fields and may
-  drop a long, version-dependent tail; the full raw line is still available in
+  syslog). The access-log format 's3' keeps only a curated set of useful fields
+  and may drop a long, version-dependent tail; the full raw line is still available in
   a script as 'line' / 'meta.line', so a dropped column can be recovered with a
   second-stage parse, e.g.:
     kelora -f s3 access.log --exec 'e.tail = meta.line.extract_regex("\"[^\"]*\"\\s*$", 0)'
@@ -182,6 +214,43 @@ csv       - Comma-separated with header row
 tsv       - Tab-separated with header row
 csvnh     - CSV without header
 tsvnh     - TSV without header
+ecs       - Elastic Common Schema JSON Lines: level -> log.level, msg/message -> message,
+            ts/timestamp -> @timestamp (ISO 8601 UTC), ip/remote_addr -> source.ip,
+            method -> http.request.method, status -> http.response.status_code,
+            user_agent -> user_agent.original; everything else under labels.
+            --ecs-version 1|8 sets the ecs.version stamp (default 8); the mapped
+            fields are the same in both versions
+fluentd-json
+          - Fluentd forward protocol, message mode: one [tag, time, record] array per
+            line, as read by `fluent-cat --json`. time is integer Unix seconds from the
+            parsed timestamp (the current time when there is none).
+            --fluentd-tag TAG sets the tag (default "kelora")
+fluentd-forward
+          - Fluentd forward mode: a single [tag, [[time, record], ...], {}] batch written
+            when processing ends. Not supported with --parallel
+table     - Aligned columns with a header row; columns from -k/--keys or the fields seen.
+            Prints one table per 100 events, each sized to its own rows.
+            --pretty-tables adds box borders, --max-field-length N truncates wide cells
+orc       - Apache ORC columnar file; requires --output-file. Column types are inferred
+            from the first 100 events (int + float -> float, other mixes -> string);
+            each stripe is written as it fills. --orc-stripe-rows N sets rows per
+            stripe (default 10000)
+cbor      - One CBOR map per event; requires --output-file. Each record is preceded by
+            its 4-byte big-endian length; --cbor-unframed writes a plain CBOR sequence.
+            Not supported with --parallel
+apache-access
+          - Apache combined access log lines, the inverse of -f combined:
+            ip identity user [time] "method path protocol" status bytes "referer" "user_agent".
+            Time comes from timestamp/ts (ISO 8601 or CLF), written as UTC; missing fields are '-'
+nginx-access
+          - Same as apache-access with "request_time" appended as the last field
+xml       - One <event>...</event> element per line with a child element per field.
+            Text is escaped, names are sanitized to valid XML names, nested maps become
+            nested elements and arrays repeat their element once per item
+prometheus-pushgateway
+          - No event output; tracked metrics are PUT to a Prometheus Pushgateway when
+            processing ends (default http://localhost:9091, job "kelora").
+            Override with --metrics-endpoint, --pushgateway-job, --pushgateway-grouping
 
 Use -q/--quiet to suppress output (implied by -s/--stats and -m/--metrics).
 
@@ -45,6 +45,28 @@ pub fn print_functions_help(filter: Option<&str>) {
     }
 }
 
+/// Print the function registry as JSON (`--help-functions --json`), limited to
+/// entries matching `filter` when given.
+pub fn print_functions_json(filter: Option<&str>) {
+    println!("{}", rhai_functions::docs::functions_json(filter));
+}
+
+/// Print every signature, the notes and examples of one function
+/// (`--help-function NAME`).
+pub fn print_function_help(name: &str) {
+    match rhai_functions::docs::function_detail_text(name) {
+        Some(detail) => println!("{}", detail),
+        None => {
+            println!("No function named \"{name}\".");
+            let similar = rhai_functions::docs::similar_function_names(name);
+            if !similar.is_empty() {
+                println!("Similar: {}", similar.join(", "));
+            }
+            println!("Run --help-functions {name} to search the catalogue.");
+        }
+    }
+}
+
 /// Print the full CLI reference filtered to entries matching `keyword`.
 ///
 /// This is the search counterpart to plain `--help`: bare `--help` is left to
@@ -24,7 +24,7 @@ Quick Examples:
   tail -f app.log | kelora -j -l error,warn
 
 Common Options:
-  -f, --input-format <FORMAT>   Choose parser (auto, auto-per-file, json, line, raw, logfmt, syslog, cef, csv, tsv, csvnh, tsvnh, combined, cols:<spec>, regex:<pattern>, built-in application-log formats like glog/nginx-error (see --help-formats), or cascade list e.g. json,line)
+  -f, --input-format <FORMAT>   Choose parser (auto, auto-per-file, json, line, raw, logfmt, syslog, cef, leef, csv, tsv, csvnh, tsvnh, combined, haproxy, ssv, netflow5/netflow9/ipfix, pcap, cols:<spec>, regex:<pattern>, fixed-width:<spec>, built-in application-log formats like glog/nginx-error (see --help-formats), or cascade list e.g. json,line)
   -j                            Shortcut for -f json
   -d, --discover                Profile field names, types, and sample values — start here on unknown files (sequential only)
   -D, --discover-final          Like -d, but profiles the fields your pipeline emits (after filters/transforms)
@@ -34,13 +34,15 @@ Common Options:
   -e, --exec <EXPR>             Transform events or emit metrics (can repeat; run in the order given)
   -k, --keys <KEYS>             Pick or reorder output fields
   -b, --brief                   Output only field values (omit keys)
-  -F, --output-format <FORMAT>  Output format (default/json/logfmt/inspect/levelmap/keymap/tailmap/csv/tsv/csvnh/tsvnh)
+  -F, --output-format <FORMAT>  Output format (default/json/logfmt/inspect/levelmap/keymap/tailmap/csv/tsv/csvnh/tsvnh/ecs/fluentd-json/fluentd-forward/table/orc/cbor/apache-access/nginx-access/xml/prometheus-pushgateway)
   -q, --quiet                   Suppress event output (-s/--stats and -m/--metrics imply this)
   -n, --take <N>                Limit output to first N events
   -s, --stats                   Show only the statistics, with discovered fields
   -m, --metrics                 Show only the tracked metrics
   --drain                       Summarize log templates (requires -k/--keys, sequential only)
   --merge-sorted                Merge already-sorted files by timestamp; aborts on missing timestamps, parse failures, or disorder (sequential only)
+  --follow-glob <PATTERN>       Follow files matching a glob, including newly created ones (runs until Ctrl+C)
+  --input-cmd <COMMAND>         Read a command's output as input; add --follow to restart it when it exits
   -P, --parallel                Process in parallel for high-throughput batch analysis (sequential by default)
 
 More Help:
@@ -221,6 +221,8 @@ GLOBAL CONTEXT:
                                        Note: Accessing state in --parallel mode will cause a runtime error
   conf                                 Global config map (read-only after --begin)
   metrics                              Global metrics map (from track_* calls, read in --end)
+  stats                                Final run counters in --end (lines_read, parse_errors, events_output, ...)
+  inputs / argv                        Input file names and --script-arg values (--begin/--end)
   get_env("VAR", "default")            Environment variable access
 
 ERROR HANDLING MODES:
@@ -6,6 +6,9 @@ Time Format Reference for --ts-format:
 Use with:
   --ts-format <FMT>     Describe how timestamps are parsed
   --input-tz <TZ>       Supply a timezone for inputs without offsets (e.g., --input-tz UTC)
+  --tz-field <FIELD>    Take the timezone per event from FIELD (+0200 or IANA name; falls back to --input-tz)
+  --assume-year <YEAR>  Year for year-less timestamps (syslog 'Jan 15 14:30:45');
+                        default: closest to now, never more than 24h ahead
   --multiline timestamp:format=FMT  Use the same chrono format for header detection
 
 Basic date/time components:
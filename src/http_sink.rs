@@ -0,0 +1,208 @@
+//! Stream formatted events to an HTTP collector (`--output-http URL`).
+//!
+//! Batching is done by [`ChunkingOutputWriter`]: it groups the formatted lines
+//! into JSON arrays, and every array it emits is POSTed as one request by
+//! [`HttpBatchPoster`]. Requests are sent synchronously, so a slow collector
+//! slows the pipeline down instead of letting batches pile up in memory, and
+//! the partial last batch is sent when the writer is finished or dropped at
+//! the end of the run.
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::config::HttpSinkConfig;
+use crate::pipeline::ChunkingOutputWriter;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// First delivery failure, shared between the sink and `main` so a batch that
+/// fails while the writer is being dropped still fails the run.
+#[derive(Clone, Default)]
+pub struct DeliveryStatus {
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl DeliveryStatus {
+    fn record(&self, message: &str) {
+        let mut slot = self.error.lock().unwrap_or_else(|e| e.into_inner());
+        if slot.is_none() {
+            *slot = Some(message.to_string());
+        }
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// Build the `--output-http` writer and the handle that reports its failures.
+pub fn http_output_writer(
+    config: &HttpSinkConfig,
+    chunk_size: Option<usize>,
+    chunk_size_bytes: Option<usize>,
+) -> (ChunkingOutputWriter<HttpBatchPoster>, DeliveryStatus) {
+    let status = DeliveryStatus::default();
+    let poster = HttpBatchPoster::new(config, status.clone());
+    let writer = ChunkingOutputWriter::new(
+        poster,
+        Some(chunk_size.unwrap_or(config.batch_size)),
+        Some(chunk_size_bytes.unwrap_or(config.batch_bytes)),
+    );
+    (writer, status)
+}
+
+/// Writer that POSTs each complete line it receives (one JSON array) to the
+/// collector, retrying transient failures with exponential backoff.
+pub struct HttpBatchPoster {
+    url: String,
+    agent: ureq::Agent,
+    max_retries: u32,
+    initial_backoff: Duration,
+    pending: Vec<u8>,
+    status: DeliveryStatus,
+    /// Set after a batch could not be delivered; later batches are not sent
+    failed: bool,
+}
+
+impl HttpBatchPoster {
+    fn new(config: &HttpSinkConfig, status: DeliveryStatus) -> Self {
+        Self {
+            url: config.url.clone(),
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            max_retries: config.max_retries,
+            initial_backoff: INITIAL_BACKOFF,
+            pending: Vec::new(),
+            status,
+            failed: false,
+        }
+    }
+
+    fn post(&self, body: &str) -> Result<(), String> {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            let error = match self
+                .agent
+                .post(&self.url)
+                .set("Content-Type", "application/json")
+                .send_string(body)
+            {
+                Ok(_) => return Ok(()),
+                Err(ureq::Error::Status(code, response)) => {
+                    let detail = response.into_string().unwrap_or_default();
+                    let message = format!(
+                        "HTTP sink {} rejected a batch ({}): {}",
+                        self.url,
+                        code,
+                        detail.trim()
+                    );
+                    // Other client errors will fail the same way on every retry
+                    if code != 429 && code < 500 {
+                        return Err(message);
+                    }
+                    message
+                }
+                Err(e) => format!("HTTP sink {} is unreachable: {}", self.url, e),
+            };
+
+            if attempt >= self.max_retries {
+                return Err(format!("{} (gave up after {} retries)", error, attempt));
+            }
+            attempt += 1;
+            std::thread::sleep(backoff);
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    fn deliver(&mut self, body: &str) -> io::Result<()> {
+        if self.failed {
+            return Err(io::Error::other(
+                "HTTP sink stopped after a failed delivery",
+            ));
+        }
+        self.post(body).map_err(|message| {
+            self.failed = true;
+            self.status.record(&message);
+            io::Error::other(message)
+        })
+    }
+}
+
+impl Write for HttpBatchPoster {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        while let Some(pos) = self.pending.iter().position(|&b| b == b'\n') {
+            let mut line: Vec<u8> = self.pending.drain(..=pos).collect();
+            line.pop();
+            if !line.is_empty() {
+                self.deliver(&String::from_utf8_lossy(&line))?;
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(url: String, max_retries: u32) -> HttpSinkConfig {
+        HttpSinkConfig {
+            url,
+            batch_size: 2,
+            batch_bytes: 1024,
+            max_retries,
+        }
+    }
+
+    #[test]
+    fn client_errors_are_not_retried() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/ingest")
+            .with_status(400)
+            .with_body("bad payload")
+            .expect(1)
+            .create();
+
+        let status = DeliveryStatus::default();
+        let mut poster = HttpBatchPoster::new(
+            &config(format!("{}/ingest", server.url()), 3),
+            status.clone(),
+        );
+        assert!(poster.write_all(b"[1]\n").is_err());
+
+        mock.assert();
+        assert!(status.error().unwrap().contains("bad payload"));
+        // Nothing more is sent once a batch was lost
+        assert!(poster.write_all(b"[2]\n").is_err());
+    }
+
+    #[test]
+    fn retries_give_up_after_max_retries() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/ingest")
+            .with_status(503)
+            .expect(3)
+            .create();
+
+        let status = DeliveryStatus::default();
+        let mut poster = HttpBatchPoster::new(
+            &config(format!("{}/ingest", server.url()), 2),
+            status.clone(),
+        );
+        poster.initial_backoff = Duration::from_millis(1);
+        assert!(poster.write_all(b"[1]\n").is_err());
+
+        mock.assert();
+        assert!(status.error().unwrap().contains("gave up after 2 retries"));
+    }
+}
@@ -2,21 +2,28 @@
 
 pub mod byte_size;
 pub mod cli;
+pub mod cluster;
 pub mod colors;
 pub mod config;
 pub mod config_file;
 pub mod decompression;
+pub mod detection;
 pub mod drain;
+pub mod embed;
 pub mod engine;
 pub mod event;
 pub mod field_discovery;
+pub mod filename_ts;
 pub mod formatters;
 pub mod parallel;
 pub mod parsers;
 pub mod pipeline;
 pub mod platform;
 pub mod readers;
+pub mod recording;
 pub mod rhai_functions;
+pub mod run_scope;
+pub mod runner;
 pub mod stats;
 pub mod timestamp;
 pub mod tty;
@@ -25,3 +32,4 @@ pub mod tty;
 mod test_env;
 
 pub use cli::{Cli, FileOrder, InputFormat, OutputFormat};
+pub use embed::PipelineBuilder;
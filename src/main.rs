@@ -7,7 +7,6 @@ use std::collections::BTreeSet;
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 use std::io::IsTerminal;
-use std::sync::atomic::Ordering;
 
 #[cfg(unix)]
 use signal_hook::consts::{SIGINT, SIGTERM};
@@ -15,24 +14,33 @@ use signal_hook::consts::{SIGINT, SIGTERM};
 mod args;
 mod byte_size;
 mod cli;
+mod cluster;
 mod colors;
 mod config;
 mod config_file;
 mod decompression;
 mod detection;
 mod drain;
+mod embed;
 mod engine;
 mod event;
 mod field_discovery;
+mod filename_ts;
 mod formatters;
 mod help;
+#[cfg(feature = "output-http")]
+mod http_sink;
 mod interactive;
 mod parallel;
 mod parsers;
 mod pipeline;
 mod platform;
+#[cfg(feature = "pushgateway")]
+mod pushgateway;
 mod readers;
+mod recording;
 mod rhai_functions;
+mod run_scope;
 mod runner;
 mod stats;
 #[cfg(test)]
@@ -50,17 +58,22 @@ use config::{
     KeloraConfig, MultilineConfig, ScriptStageType, SectionEnd, SectionStart, SpanMode,
     TimestampFilterConfig,
 };
+use embed::{Input, PipelineBuilder};
 use platform::{
-    install_broken_pipe_panic_hook, Ctrl, ExitCode, ProcessCleanup, SafeFileOut, SafeStderr,
-    SafeStdout, SignalHandler, SHOULD_TERMINATE, TERMINATED_BY_SIGNAL, TERMINATION_SIGNAL,
+    install_broken_pipe_panic_hook, CancelToken, Ctrl, ExitCode, ProcessCleanup, SafeFileOut,
+    SafeStderr, SafeStdout, SignalHandler,
 };
-use runner::{run_pipeline_with_kelora_config, PipelineResult};
+use runner::PipelineResult;
 
 fn main() -> Result<()> {
     install_broken_pipe_panic_hook();
     // Broadcast channel for shutdown requests from signal handler or other sources
     let (ctrl_tx, ctrl_rx) = unbounded::<Ctrl>();
 
+    // The signal handler cancels this run's token
+    let cancel = CancelToken::new();
+    cancel.set_active();
+
     // Initialize signal handling early
     let _signal_handler = match SignalHandler::new(ctrl_tx.clone()) {
         Ok(handler) => handler,
@@ -111,6 +124,16 @@ fn main() -> Result<()> {
         }
     };
 
+    let exec_stage_labels = match cli.get_exec_stage_labels(&matches) {
+        Ok(labels) => labels,
+        Err(e) => {
+            stderr
+                .writeln(&config::format_error_message_auto(&format!("Error: {}", e)))
+                .unwrap_or(());
+            ExitCode::InvalidUsage.exit();
+        }
+    };
+
     // Create configuration from CLI and set stages (using lib config directly)
     let mut config = match KeloraConfig::from_cli(&cli) {
         Ok(cfg) => cfg,
@@ -130,10 +153,14 @@ fn main() -> Result<()> {
 
     // Set the ordered stages directly
     config.processing.stages = ordered_stages;
+    config.processing.exec_stage_labels = exec_stage_labels;
+
+    // --rhai-lint checks the scripts and exits before any input is read
+    if cli.rhai_lint {
+        args::exit_with_lint_report(&rhai_lint_sources(&config), cli.lint_format);
+    }
+
     let warnings_allowed = config.warnings_allowed();
-    // Runtime warnings emitted from inside tracking functions (e.g. the
-    // track_unique size warning) honor the same gate as other warnings.
-    crate::rhai_functions::tracking::set_tracking_warnings_enabled(warnings_allowed);
 
     let parallel_requested = config.performance.parallel
         || config.performance.threads > 0
@@ -144,6 +171,24 @@ fn main() -> Result<()> {
             "span aggregation requires sequential mode; ignoring --parallel settings. Rerun without --parallel if you need span aggregation.",
         );
         stderr.writeln(&warning).unwrap_or(());
+    } else if config.processing.every.is_some() && warnings_allowed && parallel_requested {
+        let warning = config.format_warning_message(
+            "--every windows require sequential mode; ignoring --parallel settings. Rerun without --parallel if you need --every.",
+        );
+        stderr.writeln(&warning).unwrap_or(());
+    } else if config.processing.thin.is_some() && warnings_allowed && parallel_requested {
+        let warning = config.format_warning_message(
+            "--thin requires sequential mode; ignoring --parallel settings. Rerun without --parallel if you need --thin.",
+        );
+        stderr.writeln(&warning).unwrap_or(());
+    } else if (config.input.pipeline_record.is_some() || config.input.pipeline_replay.is_some())
+        && warnings_allowed
+        && parallel_requested
+    {
+        let warning = config.format_warning_message(
+            "--pipeline-record/--pipeline-replay require sequential mode; ignoring --parallel settings.",
+        );
+        stderr.writeln(&warning).unwrap_or(());
     } else if (config.processing.window_size > 0 || config.processing.context.is_active())
         && warnings_allowed
         && parallel_requested
@@ -198,38 +243,114 @@ fn main() -> Result<()> {
         };
 
         config.processing.timestamp_filter = Some(TimestampFilterConfig { since, until });
+
+        // Drop whole files the window can't reach before anything opens them
+        if let Some(ref spec) = cli.filename_ts_format {
+            match crate::filename_ts::select_files(
+                &config.input.files,
+                spec,
+                since,
+                until,
+                config.input.default_timezone.as_deref(),
+            ) {
+                Ok(Some(selection)) if !selection.skipped.is_empty() => {
+                    config.input.files = selection.kept;
+                    config.input.files_skipped_by_name = selection.skipped;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    stderr
+                        .writeln(&config.format_error_message(&format!("{:#}", e)))
+                        .unwrap_or(());
+                    ExitCode::InvalidUsage.exit();
+                }
+            }
+        }
+    }
+
+    // Compile ignore-lines / keep-lines pattern sets (inline and from files)
+    match pipeline::line_patterns::compile_line_patterns(
+        "ignore-lines",
+        &cli.ignore_lines,
+        &cli.ignore_lines_file,
+    ) {
+        Ok(set) => config.input.ignore_lines = set,
+        Err(msg) => {
+            stderr
+                .writeln(&config.format_error_message(&msg))
+                .unwrap_or(());
+            ExitCode::InvalidUsage.exit();
+        }
+    }
+    match pipeline::line_patterns::compile_line_patterns(
+        "keep-lines",
+        &cli.keep_lines,
+        &cli.keep_lines_file,
+    ) {
+        Ok(set) => config.input.keep_lines = set,
+        Err(msg) => {
+            stderr
+                .writeln(&config.format_error_message(&msg))
+                .unwrap_or(());
+            ExitCode::InvalidUsage.exit();
+        }
     }
 
-    // Compile ignore-lines regex if provided
-    if let Some(ignore_pattern) = &cli.ignore_lines {
-        match regex::Regex::new(ignore_pattern) {
-            Ok(regex) => {
-                config.input.ignore_lines = Some(regex);
+    // Compile the --field-from-filename pattern; without named groups it
+    // would never add a field
+    if let Some(ref pattern) = cli.field_from_filename {
+        let error = match regex::Regex::new(pattern) {
+            Ok(regex) if regex.capture_names().flatten().next().is_some() => {
+                config.processing.field_from_filename = Some(regex);
+                None
             }
+            Ok(_) => Some(format!(
+                "--field-from-filename pattern '{}' has no named groups, e.g. '(?P<env>\\w+)-(?P<date>\\d{{4}}-\\d{{2}}-\\d{{2}})'",
+                pattern
+            )),
+            Err(e) => Some(format!(
+                "Invalid --field-from-filename regex pattern '{}': {}",
+                pattern, e
+            )),
+        };
+        if let Some(msg) = error {
+            stderr
+                .writeln(&config.format_error_message(&msg))
+                .unwrap_or(());
+            ExitCode::InvalidUsage.exit();
+        }
+    }
+
+    if let Some(ref pattern) = cli.strip_prefix_re {
+        match regex::Regex::new(pattern) {
+            Ok(regex) => config.input.strip_prefix = Some(config::PrefixStrip::Regex(regex)),
             Err(e) => {
+                let msg = format!(
+                    "Invalid --strip-prefix-re regex pattern '{}': {}",
+                    pattern, e
+                );
                 stderr
-                    .writeln(&config.format_error_message(&format!(
-                        "Invalid ignore-lines regex pattern '{}': {}",
-                        ignore_pattern, e
-                    )))
+                    .writeln(&config.format_error_message(&msg))
                     .unwrap_or(());
                 ExitCode::InvalidUsage.exit();
             }
         }
     }
+    if cli.prefix_field.is_some() && config.input.strip_prefix.is_none() {
+        stderr
+            .writeln(&config.format_error_message(
+                "--prefix-field requires --strip-prefix or --strip-prefix-re",
+            ))
+            .unwrap_or(());
+        ExitCode::InvalidUsage.exit();
+    }
 
-    // Compile keep-lines regex if provided
-    if let Some(keep_pattern) = &cli.keep_lines {
-        match regex::Regex::new(keep_pattern) {
-            Ok(regex) => {
-                config.input.keep_lines = Some(regex);
-            }
-            Err(e) => {
+    if let Some(ref path) = cli.schema_validate {
+        match pipeline::schema::SchemaValidator::load(path) {
+            Ok(validator) => config.processing.schema = Some(validator),
+            Err(msg) => {
                 stderr
-                    .writeln(&config.format_error_message(&format!(
-                        "Invalid keep-lines regex pattern '{}': {}",
-                        keep_pattern, e
-                    )))
+                    .writeln(&config.format_error_message(&msg))
                     .unwrap_or(());
                 ExitCode::InvalidUsage.exit();
             }
@@ -324,6 +445,81 @@ fn main() -> Result<()> {
         }
     }
 
+    // Raw record framing reuses the multiline chunker, so it works on every
+    // reader path (sequential, parallel, merged)
+    if cli.raw_separator.is_some() || cli.raw_bytes.is_some() {
+        if !matches!(config.input.format, config::InputFormat::Raw) {
+            stderr
+                .writeln(
+                    &config.format_error_message("--raw-separator and --raw-bytes require -f raw"),
+                )
+                .unwrap_or(());
+            ExitCode::InvalidUsage.exit();
+        }
+
+        let strategy =
+            if let Some(pattern) = &cli.raw_separator {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    stderr
+                        .writeln(&config.format_error_message(&format!(
+                            "Invalid --raw-separator regex pattern '{}': {}",
+                            pattern, e
+                        )))
+                        .unwrap_or(());
+                    ExitCode::InvalidUsage.exit();
+                }
+                config::MultilineStrategy::Separator {
+                    pattern: pattern.clone(),
+                }
+            } else {
+                let size = cli.raw_bytes.as_deref().unwrap_or_default();
+                match crate::byte_size::parse_byte_size(size) {
+                    Ok(count) if count > 0 => config::MultilineStrategy::Bytes { count },
+                    Ok(_) => {
+                        stderr
+                            .writeln(&config.format_error_message(
+                                "--raw-bytes: size must be greater than zero",
+                            ))
+                            .unwrap_or(());
+                        ExitCode::InvalidUsage.exit();
+                    }
+                    Err(e) => {
+                        stderr
+                            .writeln(&config.format_error_message(&format!("--raw-bytes: {}", e)))
+                            .unwrap_or(());
+                        ExitCode::InvalidUsage.exit();
+                    }
+                }
+            };
+
+        config.input.multiline = Some(MultilineConfig {
+            strategy,
+            join: config::MultilineJoin::Newline,
+        });
+    }
+
+    // --record-separator frames any format the same way as --raw-separator
+    if let Some(separator) = &cli.record_separator {
+        let pattern = if separator == "blank" {
+            r"^\s*$".to_string()
+        } else {
+            if let Err(e) = regex::Regex::new(separator) {
+                stderr
+                    .writeln(&config.format_error_message(&format!(
+                        "Invalid --record-separator regex pattern '{}': {}",
+                        separator, e
+                    )))
+                    .unwrap_or(());
+                ExitCode::InvalidUsage.exit();
+            }
+            separator.clone()
+        };
+        config.input.multiline = Some(MultilineConfig {
+            strategy: config::MultilineStrategy::Separator { pattern },
+            join: config::MultilineJoin::Newline,
+        });
+    }
+
     if let Some(ref gap_str) = cli.mark_gaps {
         match crate::rhai_functions::datetime::to_duration(gap_str) {
             Ok(duration) => {
@@ -349,11 +545,63 @@ fn main() -> Result<()> {
         }
     }
 
+    // --sample-run replaces the normal run with a per-stage trace. Metrics,
+    // stats and hints describe a full run, so none of them are reported.
+    if let Some(samples) = config.processing.sample_run {
+        if let Err(e) = runner::run_sample_run(&config, SafeStdout::new(), samples, &cancel) {
+            emit_fatal_line(&mut stderr, &config, &format!("Pipeline error: {}", e));
+            ExitCode::GeneralError.exit();
+        }
+        ExitCode::Success.exit();
+    }
+
+    // --diff-output compares the --left and --right file sets in place of
+    // the normal run; like --sample-run it reports no metrics or stats.
+    if let Some(ref diff) = config.processing.diff {
+        if let Err(e) = runner::run_diff(&config, SafeStdout::new(), diff, &cancel) {
+            emit_fatal_line(&mut stderr, &config, &format!("Pipeline error: {}", e));
+            ExitCode::GeneralError.exit();
+        }
+        ExitCode::Success.exit();
+    }
+
+    // --diff-against reports per-key count changes against baseline files,
+    // also in place of the normal run
+    if let Some(ref diff) = config.processing.diff_against {
+        if let Err(e) = runner::run_diff_against(&config, SafeStdout::new(), diff, &cancel) {
+            emit_fatal_line(&mut stderr, &config, &format!("Pipeline error: {}", e));
+            ExitCode::GeneralError.exit();
+        }
+        ExitCode::Success.exit();
+    }
+
     // Handle output destination and run pipeline
     let hints_allowed_runtime = config.hints_allowed();
     let terminal_allowed = !config.processing.silent;
 
-    let result = if let Some(ref output_file_path) = cli.output_file {
+    #[cfg(feature = "output-http")]
+    let mut http_delivery = None;
+    let result = if let Some(ref http_config) = config.output.http_sink {
+        // The sink batches on its own, using --chunk-size/--chunk-size-bytes
+        // as its limits, so it bypasses run_pipeline_with_output
+        #[cfg(feature = "output-http")]
+        {
+            let (writer, status) = http_sink::http_output_writer(
+                http_config,
+                config.output.chunk_size,
+                config.output.chunk_size_bytes,
+            );
+            http_delivery = Some(status);
+            cli_pipeline(&config, &ctrl_rx, &cancel)
+                .output_stream(writer)
+                .run_pipeline()
+        }
+        #[cfg(not(feature = "output-http"))]
+        {
+            let _ = http_config;
+            unreachable!("validate_cli_args rejects --output-http without the output-http feature")
+        }
+    } else if let Some(ref output_file_path) = cli.output_file {
         // Guardrail: `-o`/`--output-file` takes a FILE, but it is easy to
         // mistake it for an output-FORMAT selector (which is `-F`). A bare
         // value that exactly matches a known format name (no path, no
@@ -366,7 +614,7 @@ fn main() -> Result<()> {
         {
             const FORMAT_NAMES: &[&str] = &[
                 "default", "json", "logfmt", "inspect", "levelmap", "keymap", "tailmap", "csv",
-                "tsv", "csvnh", "tsvnh", "line", "raw", "syslog", "cef", "combined",
+                "tsv", "csvnh", "tsvnh", "line", "raw", "syslog", "cef", "leef", "combined",
             ];
             if FORMAT_NAMES.contains(&output_file_path.to_ascii_lowercase().as_str()) {
                 stderr
@@ -377,23 +625,41 @@ fn main() -> Result<()> {
                     .unwrap_or(());
             }
         }
-        // Use file output
-        let file_output = match SafeFileOut::new(output_file_path) {
-            Ok(file) => file,
-            Err(e) => {
-                stderr
-                    .writeln(&config.format_error_message(&e.to_string()))
-                    .unwrap_or(());
-                ExitCode::GeneralError.exit();
-            }
-        };
-        run_pipeline_with_kelora_config(&config, file_output, &ctrl_rx)
+        if matches!(
+            config.output.format,
+            config::OutputFormat::Orc | config::OutputFormat::Cbor
+        ) {
+            // The ORC and CBOR formatters own the output file; their text channel is empty.
+            run_pipeline_with_output(&config, std::io::sink(), &ctrl_rx, &cancel)
+        } else {
+            // Use file output
+            let file_output = match SafeFileOut::new(output_file_path) {
+                Ok(file) => file,
+                Err(e) => {
+                    stderr
+                        .writeln(&config.format_error_message(&e.to_string()))
+                        .unwrap_or(());
+                    ExitCode::GeneralError.exit();
+                }
+            };
+            run_pipeline_with_output(&config, file_output, &ctrl_rx, &cancel)
+        }
     } else {
         // Use stdout output
         let stdout_output = SafeStdout::new();
-        run_pipeline_with_kelora_config(&config, stdout_output, &ctrl_rx)
+        run_pipeline_with_output(&config, stdout_output, &ctrl_rx, &cancel)
     };
 
+    // A script exit() ended the run: the process ends with its code, without
+    // the end-of-run summaries
+    if let Ok(PipelineResult {
+        exit_code: Some(code),
+        ..
+    }) = &result
+    {
+        platform::exit_with_code(*code);
+    }
+
     let (final_stats, tracking_data) = match result {
         Ok(pipeline_result) => handle_pipeline_success(
             &config,
@@ -402,6 +668,7 @@ fn main() -> Result<()> {
             &mut stderr,
             hints_allowed_runtime,
             terminal_allowed,
+            &cancel,
         ),
         Err(e) => {
             // When every input failed to open, auto-detection already printed the
@@ -422,13 +689,14 @@ fn main() -> Result<()> {
         .is_some_and(|s| !config.processing.quiet_events && s.events_output > 0);
 
     // Check if we were terminated by a signal and print output
-    if TERMINATED_BY_SIGNAL.load(Ordering::Relaxed) {
+    if let Some(signal) = cancel.signal() {
         handle_signal_termination(
+            signal,
             &config,
             final_stats.as_ref(),
             events_were_output,
+            &mut stdout,
             &mut stderr,
-            terminal_allowed,
         );
     }
 
@@ -479,8 +747,20 @@ fn main() -> Result<()> {
         tracking_errors || stats_errors
     };
 
+    // The last batch is sent as the sink is dropped at the end of the run, so
+    // its delivery failure cannot surface as a pipeline error
+    #[cfg(feature = "output-http")]
+    if let Some(error) = http_delivery.as_ref().and_then(|status| status.error()) {
+        if terminal_allowed {
+            stderr
+                .writeln(&config.format_error_message(&error))
+                .unwrap_or(());
+        }
+        had_errors = true;
+    }
+
     if config.processing.strict && override_failed {
-        if hints_allowed_runtime && config.output.stats.is_none() {
+        if hints_allowed_runtime && !config.stats_on_terminal() {
             if let Some(message) = override_message.clone() {
                 let formatted = config.format_error_message(&message);
                 stderr.writeln(&formatted).unwrap_or(());
@@ -512,6 +792,14 @@ fn main() -> Result<()> {
             };
             eprintln!("{}", config.format_error_message(&failure_text));
         }
+        if stats.schema_failures > 0 {
+            let failure_text = if stats.schema_failures == 1 {
+                "1 event failed --schema-validate".to_string()
+            } else {
+                format!("{} events failed --schema-validate", stats.schema_failures)
+            };
+            eprintln!("{}", config.format_error_message(&failure_text));
+        }
     }
 
     if had_errors {
@@ -521,6 +809,123 @@ fn main() -> Result<()> {
     }
 }
 
+/// Every script given on the command line, named after the flag it came from.
+fn rhai_lint_sources(config: &KeloraConfig) -> Vec<engine::lint::LintSource> {
+    use engine::lint::{LintSource, ScriptKind};
+
+    let source = |name: String, script: &str, kind| LintSource {
+        name,
+        script: script.to_string(),
+        kind,
+    };
+    let mut sources = Vec::new();
+    if let Some(begin) = &config.processing.begin {
+        sources.push(source("--begin".to_string(), begin, ScriptKind::Statements));
+    }
+
+    let (mut filters, mut execs, mut asserts) = (0, 0, 0);
+    for stage in &config.processing.stages {
+        match stage {
+            ScriptStageType::Filter { script, includes } => {
+                filters += 1;
+                for include in includes {
+                    sources.push(source(
+                        include.path.clone(),
+                        &include.content,
+                        ScriptKind::Statements,
+                    ));
+                }
+                sources.push(source(
+                    format!("--filter #{}", filters),
+                    script,
+                    ScriptKind::Expression,
+                ));
+            }
+            ScriptStageType::Exec(script) => {
+                execs += 1;
+                sources.push(source(
+                    format!("--exec #{}", execs),
+                    script,
+                    ScriptKind::Statements,
+                ));
+            }
+            ScriptStageType::Assert(script) => {
+                asserts += 1;
+                sources.push(source(
+                    format!("--assert #{}", asserts),
+                    script,
+                    ScriptKind::Expression,
+                ));
+            }
+            ScriptStageType::LevelFilter { .. } => {}
+        }
+    }
+
+    if let Some(close_script) = config
+        .processing
+        .span
+        .as_ref()
+        .and_then(|span| span.close_script.as_deref())
+    {
+        sources.push(source(
+            "--span-close".to_string(),
+            close_script,
+            ScriptKind::Statements,
+        ));
+    }
+    if let Some(emit_script) = config
+        .processing
+        .every
+        .as_ref()
+        .and_then(|every| every.emit_script.as_deref())
+    {
+        sources.push(source(
+            "--every-emit".to_string(),
+            emit_script,
+            ScriptKind::Statements,
+        ));
+    }
+    if let Some(end) = &config.processing.end {
+        sources.push(source("--end".to_string(), end, ScriptKind::Statements));
+    }
+    sources
+}
+
+/// The builder every CLI run goes through: the configured input, the signal
+/// handler's control channel and token, and script output on stdout/stderr.
+fn cli_pipeline(
+    config: &KeloraConfig,
+    ctrl_rx: &crossbeam_channel::Receiver<Ctrl>,
+    cancel: &CancelToken,
+) -> PipelineBuilder {
+    PipelineBuilder::from_config(config.clone())
+        .input(Input::Configured)
+        .control(ctrl_rx.clone())
+        .cancellation(cancel.clone())
+        .collect_stats(false)
+        .on_print(|line| println!("{}", line))
+        .on_eprint(|line| eprintln!("{}", line))
+}
+
+/// Run the pipeline, batching its output into JSON arrays when --chunk-size or
+/// --chunk-size-bytes is set.
+fn run_pipeline_with_output<W: std::io::Write + Send + 'static>(
+    config: &KeloraConfig,
+    output: W,
+    ctrl_rx: &crossbeam_channel::Receiver<Ctrl>,
+    cancel: &CancelToken,
+) -> Result<PipelineResult> {
+    let pipeline = cli_pipeline(config, ctrl_rx, cancel);
+    let chunk_size = config.output.chunk_size;
+    let chunk_size_bytes = config.output.chunk_size_bytes;
+    if chunk_size.is_some() || chunk_size_bytes.is_some() {
+        let chunked = pipeline::ChunkingOutputWriter::new(output, chunk_size, chunk_size_bytes);
+        pipeline.output_stream(chunked).run_pipeline()
+    } else {
+        pipeline.output_stream(output).run_pipeline()
+    }
+}
+
 fn collect_filter_field_references(config: &KeloraConfig) -> BTreeSet<String> {
     let mut fields = BTreeSet::new();
     let re = regex::Regex::new(r"\be\.([A-Za-z_][A-Za-z0-9_]*)").expect("valid filter regex");
@@ -628,6 +1033,9 @@ fn maybe_print_no_input_hint(
     // --no-input is an explicit opt-out (begin/end-only scripting); respect it.
     if !config.input.files.is_empty()
         || config.input.no_input
+        || config.input.follow_glob.is_some()
+        || !config.input.input_cmds.is_empty()
+        || config.input.pipeline_replay.is_some()
         || crate::tty::is_stdin_tty()
         || stats.lines_read != 0
         || stats.events_created != 0
@@ -673,7 +1081,8 @@ fn maybe_print_naive_tz_hint(
     let normalize = config.processing.normalize_timestamps;
     let time_op_active = normalize
         || config.processing.timestamp_filter.is_some()
-        || config.processing.span.is_some();
+        || config.processing.span.is_some()
+        || config.processing.every.is_some();
     if !time_op_active {
         return;
     }
@@ -858,14 +1267,24 @@ fn maybe_print_key_typo_hint(
         .cloned()
         .collect();
 
+    // A nested path kept by -k becomes an output field of that name, so it is
+    // known once it resolved; one removed by --exclude-keys leaves no trace.
     let messages = [
-        key_typo_message("-k/--keys", "field", "", &config.output.keys, &known_keys),
+        key_typo_message(
+            "-k/--keys",
+            "field",
+            "",
+            &config.output.keys,
+            &known_keys,
+            true,
+        ),
         key_typo_message(
             "--exclude-keys",
             "field",
             ", so it was not removed",
             &config.output.exclude_keys,
             &known_keys,
+            false,
         ),
     ];
 
@@ -881,12 +1300,15 @@ fn maybe_print_key_typo_hint(
 /// Build the typo hint for one key flag, or `None` when every requested key was
 /// seen at least once. `consequence` is appended after the field name to explain
 /// the effect (empty for `-k`, where empty output already speaks for itself).
+/// Without `nested_paths_known`, a nested path under a present field can't be
+/// judged and is left out.
 fn key_typo_message(
     flag: &str,
     label: &str,
     consequence: &str,
     requested: &[String],
     discovered: &BTreeSet<String>,
+    nested_paths_known: bool,
 ) -> Option<String> {
     if requested.is_empty() {
         return None;
@@ -895,6 +1317,7 @@ fn key_typo_message(
     let unseen: Vec<&String> = requested
         .iter()
         .filter(|key| !discovered.contains(*key))
+        .filter(|key| nested_paths_known || nested_path_head(key, discovered).is_none())
         .collect();
 
     match unseen.as_slice() {
@@ -934,23 +1357,18 @@ fn unseen_key_suggestion(key: &str, discovered: &BTreeSet<String>) -> String {
 /// ends with `[]`, the way `--discover` prints nested fields (`api.queries`,
 /// `tags[]`) — and its leading segment *is* a present top-level field, the user
 /// almost certainly copied a nested name from `--discover`. `-k`/`--exclude-keys`
-/// select or drop whole top-level fields and can't address nested values, so we
-/// point at the `get_path` idiom instead of `nearest_field` guessing the bare
-/// parent (which silently drops the nesting they asked for). This never blocks a
-/// top-level field whose name literally contains a dot: such a field would be
-/// present, so this "never present" hint wouldn't fire for it at all.
+/// resolve such paths, so an unseen one is missing below a field that exists;
+/// say so instead of `nearest_field` guessing the bare parent (which silently
+/// drops the nesting they asked for). This never blocks a top-level field whose
+/// name literally contains a dot: such a field would be present, so this "never
+/// present" hint wouldn't fire for it at all.
 fn nested_path_suggestion(key: &str, discovered: &BTreeSet<String>) -> Option<String> {
-    // Head of the path: text before the first `.` or `[`.
-    let head_end = key.find(['.', '['])?;
-    let head = &key[..head_end];
-    if head.is_empty() || !discovered.contains(head) {
-        return None;
-    }
+    let head = nested_path_head(key, discovered)?;
 
     // `field[]` is discover's notation for "elements of the array `field`", not
     // an addressable path. The array itself is a selectable top-level field, so
     // the fix there is simply `-k field`. Deeper paths (`a.b`, `a.b[]`) name a
-    // value nested inside a map, reachable only via get_path on the container.
+    // value nested inside a map, which no event had.
     let container = key.strip_suffix("[]").unwrap_or(key);
     if container == head {
         Some(format!(
@@ -958,11 +1376,19 @@ fn nested_path_suggestion(key: &str, discovered: &BTreeSet<String>) -> Option<St
         ))
     } else {
         Some(format!(
-            "'{head}' is present, but -k/--keys and --exclude-keys act on whole top-level fields and can't reach nested values. Flatten it first, e.g. --exec 'e.val = e.get_path(\"{container}\")' then -k val."
+            "'{head}' is present, but no event had a value at '{container}'. Run --discover to list the fields nested in it."
         ))
     }
 }
 
+/// Head of a nested path (text before the first `.` or `[`), when it names a
+/// present top-level field
+fn nested_path_head<'a>(key: &'a str, discovered: &BTreeSet<String>) -> Option<&'a str> {
+    let head_end = key.find(['.', '['])?;
+    let head = &key[..head_end];
+    (!head.is_empty() && discovered.contains(head)).then_some(head)
+}
+
 /// List the fields the run actually saw when the set is small enough to read at
 /// a glance; otherwise point at `--discover`, which is purpose-built for naming
 /// fields (`-s` buries them in general stats).
@@ -1166,6 +1592,7 @@ fn handle_pipeline_success(
     stderr: &mut SafeStderr,
     hints_allowed_runtime: bool,
     terminal_allowed: bool,
+    cancel: &CancelToken,
 ) -> (Option<stats::ProcessingStats>, Option<TrackingSnapshot>) {
     let auto_detected_non_line = pipeline_result.auto_detected_non_line;
     // Determine if any events were output (to conditionally suppress leading newlines)
@@ -1180,83 +1607,77 @@ fn handle_pipeline_success(
     // unbounded stream (`tail -f … -m`) that end only ever arrives via Ctrl-C,
     // so it must flush on signal termination — otherwise accumulated track_*
     // data is dropped and the run yields nothing. This mirrors how `--stats`
-    // already flushes on signal (handle_signal_termination). Only --silent
-    // (terminal_allowed) suppresses it; a second Ctrl-C still bails immediately
-    // via the signal handler before reaching this point.
-    if let Some(ref metrics_format) = config.output.metrics {
-        if terminal_allowed {
-            use crate::cli::MetricsFormat;
-            // Route to stdout in data-only mode, stderr when showing with events
-            let use_stdout = !config.output.metrics_with_events;
-            // Resolve the auto default like `ls`: the human table on a terminal,
-            // the tsv record stream when stdout is piped or redirected. An
-            // explicit --metrics=full forces the table through a pipe.
-            let resolved_format = match metrics_format {
-                MetricsFormat::Auto => {
-                    if use_stdout && !std::io::stdout().is_terminal() {
-                        MetricsFormat::Tsv
-                    } else {
-                        MetricsFormat::Full
-                    }
-                }
-                other => other.clone(),
-            };
-            match &resolved_format {
-                MetricsFormat::Auto => unreachable!("Auto is resolved above"),
-                MetricsFormat::Tsv => {
-                    let tsv_output = crate::rhai_functions::tracking::format_metrics_tsv(
-                        &pipeline_result.tracking_data.user,
-                        &pipeline_result.tracking_data.internal,
-                    );
-                    // A record stream is written verbatim: no header, no leading
-                    // newline, so downstream head/tail/awk see only data rows.
-                    if !tsv_output.is_empty() {
-                        if use_stdout {
-                            stdout.writeln(&tsv_output).unwrap_or(());
-                        } else {
-                            stderr.writeln(&tsv_output).unwrap_or(());
-                        }
-                    }
-                }
-                MetricsFormat::Short | MetricsFormat::Full => {
-                    let metrics_level = match &resolved_format {
-                        MetricsFormat::Short => 1,
-                        MetricsFormat::Full => 2,
-                        _ => 1,
-                    };
-                    let metrics_output = crate::rhai_functions::tracking::format_metrics_output(
-                        &pipeline_result.tracking_data.user,
-                        &pipeline_result.tracking_data.internal,
-                        metrics_level,
+    // already flushes on signal (handle_signal_termination). --silent drops
+    // its terminal destinations (see config::resolve_report_routing); a second
+    // Ctrl-C still bails immediately via the signal handler before reaching
+    // this point.
+    if let (Some(metrics_format), Some(route)) = (
+        config.output.metrics.as_ref(),
+        config.output.metrics_route.as_ref(),
+    ) {
+        use crate::cli::{MetricsFormat, ReportRoute};
+        // Resolve the auto default like `ls`: the human table on a terminal,
+        // the tsv record stream when stdout is piped or redirected or the
+        // report goes to a file. An explicit --metrics=full forces the table.
+        let resolved_format = match metrics_format {
+            MetricsFormat::Auto => match route {
+                ReportRoute::Stderr => MetricsFormat::Full,
+                ReportRoute::Stdout if std::io::stdout().is_terminal() => MetricsFormat::Full,
+                _ => MetricsFormat::Tsv,
+            },
+            other => other.clone(),
+        };
+        let report = match &resolved_format {
+            MetricsFormat::Auto => unreachable!("Auto is resolved above"),
+            MetricsFormat::Tsv => {
+                // A record stream is written verbatim: no header, no leading
+                // newline, so downstream head/tail/awk see only data rows.
+                Some(crate::rhai_functions::tracking::format_metrics_tsv(
+                    &pipeline_result.tracking_data.user,
+                    &pipeline_result.tracking_data.internal,
+                ))
+            }
+            MetricsFormat::Short | MetricsFormat::Full => {
+                let metrics_level = match &resolved_format {
+                    MetricsFormat::Short => 1,
+                    _ => 2,
+                };
+                let metrics_output = crate::rhai_functions::tracking::format_metrics_output(
+                    &pipeline_result.tracking_data.user,
+                    &pipeline_result.tracking_data.internal,
+                    metrics_level,
+                );
+                (!metrics_output.is_empty()).then(|| {
+                    let formatted = config.format_metrics_message(
+                        &metrics_output,
+                        config.output.metrics_with_events, // Show header only for --with-metrics
                     );
-                    if !metrics_output.is_empty() {
-                        let mut formatted = config.format_metrics_message(
-                            &metrics_output,
-                            config.output.metrics_with_events, // Show header only for --with-metrics
-                        );
-                        if !events_were_output {
-                            formatted = formatted.trim_start_matches('\n').to_string();
-                        }
-                        if use_stdout {
-                            stdout.writeln(&formatted).unwrap_or(());
-                        } else {
-                            stderr.writeln(&formatted).unwrap_or(());
-                        }
-                    }
-                }
-                MetricsFormat::Json => {
-                    if let Ok(json_output) = crate::rhai_functions::tracking::format_metrics_json(
-                        &pipeline_result.tracking_data.user,
-                        &pipeline_result.tracking_data.internal,
-                    ) {
-                        if use_stdout {
-                            stdout.writeln(&json_output).unwrap_or(());
-                        } else {
-                            stderr.writeln(&json_output).unwrap_or(());
-                        }
+                    if events_were_output {
+                        formatted
+                    } else {
+                        formatted.trim_start_matches('\n').to_string()
                     }
-                }
+                })
             }
+            MetricsFormat::Json => crate::rhai_functions::tracking::format_metrics_json(
+                &pipeline_result.tracking_data.user,
+                &pipeline_result.tracking_data.internal,
+            )
+            .ok(),
+            // With a Pushgateway configured the exposition is pushed below
+            // instead of printed.
+            MetricsFormat::Prometheus if config.output.pushgateway.is_some() => None,
+            MetricsFormat::Prometheus => Some(
+                crate::rhai_functions::tracking::format_metrics_prometheus(
+                    &pipeline_result.tracking_data.user,
+                    &pipeline_result.tracking_data.internal,
+                )
+                .trim_end()
+                .to_string(),
+            ),
+        };
+        if let Some(report) = report.filter(|report| !report.is_empty()) {
+            write_report(config, route, &report, "metrics", stdout, stderr);
         }
     }
 
@@ -1280,6 +1701,15 @@ fn handle_pipeline_success(
         }
     }
 
+    if config.output.cluster.is_some() && terminal_allowed {
+        let clusters = crate::cluster::clusters();
+        if !clusters.is_empty() {
+            stdout
+                .writeln(&crate::cluster::format_clusters_output(&clusters))
+                .unwrap_or(());
+        }
+    }
+
     // Write metrics to file if configured
     if let Some(ref metrics_file) = config.output.metrics_file {
         if let Ok(json_output) = crate::rhai_functions::tracking::format_metrics_json(
@@ -1297,6 +1727,21 @@ fn handle_pipeline_success(
         }
     }
 
+    // Push metrics to a Prometheus Pushgateway if configured
+    #[cfg(feature = "pushgateway")]
+    if let Some(ref pushgateway) = config.output.pushgateway {
+        let body = crate::rhai_functions::tracking::format_metrics_prometheus(
+            &pipeline_result.tracking_data.user,
+            &pipeline_result.tracking_data.internal,
+        );
+        let writer = pushgateway::PushgatewayWriter::new(pushgateway);
+        if let Err(e) = writer.push(&body) {
+            stderr
+                .writeln(&config.format_error_message(&e.to_string()))
+                .unwrap_or(());
+        }
+    }
+
     // Surface per-metric counts of skipped Unit () values (missing fields).
     // The track_* functions skip missing values silently; a metric whose field
     // is missing from *every* event usually means a field-name typo, so it
@@ -1316,7 +1761,7 @@ fn handle_pipeline_success(
     // event — the strong typo signal that a field is missing everywhere.
     let skip_hint_allowed = !config.processing.silent
         && !config.processing.hints_user_suppressed
-        && !SHOULD_TERMINATE.load(Ordering::Relaxed);
+        && !cancel.is_cancelled();
     if skip_hint_allowed {
         let user = &pipeline_result.tracking_data.user;
         // A metric that recorded at least one value keys either directly on its
@@ -1368,7 +1813,7 @@ fn handle_pipeline_success(
     if !metrics_were_requested
         && !pipeline_result.tracking_data.user.is_empty()
         && hints_allowed_runtime
-        && !SHOULD_TERMINATE.load(Ordering::Relaxed)
+        && !cancel.is_cancelled()
     {
         let mut hint = config
             .format_hint_message("Metrics recorded; rerun with -m or --metrics=json to view them.");
@@ -1400,6 +1845,9 @@ fn handle_pipeline_success(
                     || proc.span.is_some()
                     || proc.timestamp_filter.is_some()
                     || proc.take_limit.is_some()
+                    || proc.take_while.is_some()
+                    || proc.drop_while.is_some()
+                    || proc.reservoir.is_some()
                     || !proc.levels.is_empty()
                     || !proc.exclude_levels.is_empty());
             let formatted = match config.output.discover_fields {
@@ -1416,8 +1864,22 @@ fn handle_pipeline_success(
         }
     }
 
+    if config.processing.benchmark && terminal_allowed {
+        // The breakdown already includes every script stage's time.
+        let report = pipeline::format_benchmark();
+        stderr
+            .writeln(&config.format_benchmark_message(&report))
+            .unwrap_or(());
+    } else if config.processing.pipeline_profile && terminal_allowed {
+        if let Some(profile) = pipeline::format_stage_profile() {
+            stderr
+                .writeln(&config.format_profile_message(&profile))
+                .unwrap_or(());
+        }
+    }
+
     // Print output based on configuration (only if not terminated)
-    if !SHOULD_TERMINATE.load(Ordering::Relaxed) {
+    if !cancel.is_cancelled() {
         // Script/parse error summaries are correctness signals, not informational
         // diagnostics. They go to stderr (never polluting machine-readable stdout),
         // so they survive the data-only modes (--metrics/--drain/--discover) that
@@ -1436,28 +1898,8 @@ fn handle_pipeline_success(
         };
 
         if let Some(ref s) = pipeline_result.stats {
-            if config.output.stats.is_some() && terminal_allowed {
-                // Full stats when --stats flag is used (unless suppressed)
-                // Route to stdout in data-only mode, stderr when showing with events
-                let use_stdout = !config.output.stats_with_events;
-                let json_stats = matches!(config.output.stats, Some(cli::StatsFormat::Json));
-                let mut formatted = if json_stats {
-                    s.format_stats_json()
-                } else {
-                    config.format_stats_message(
-                        &s.format_stats(config.input.multiline.is_some()),
-                        config.output.stats_with_events, // Show header only for --with-stats
-                    )
-                };
-                if !events_were_output {
-                    formatted = formatted.trim_start_matches('\n').to_string();
-                }
-                if use_stdout {
-                    stdout.writeln(&formatted).unwrap_or(());
-                } else {
-                    stderr.writeln(&formatted).unwrap_or(());
-                }
-            } else if errors_allowed {
+            emit_stats_report(config, Some(s), events_were_output, stdout, stderr);
+            if errors_allowed && !config.stats_on_terminal() {
                 // Error summary by default when errors occur (survives data-only modes;
                 // only --silent suppresses it)
                 let mut summaries = Vec::new();
@@ -1513,7 +1955,7 @@ fn handle_pipeline_success(
             // user should see that invalid bytes were replaced rather than the
             // stream silently truncated (#239). With --stats it's already in the
             // stats block.
-            if config.warnings_allowed() && config.output.stats.is_none() {
+            if config.warnings_allowed() && !config.stats_on_terminal() {
                 if let Some(message) = s.format_decode_warning() {
                     let formatted = config.format_warning_message(&message);
                     stderr.writeln(&formatted).unwrap_or(());
@@ -1524,6 +1966,14 @@ fn handle_pipeline_success(
                     let formatted = config.format_warning_message(&message);
                     stderr.writeln(&formatted).unwrap_or(());
                 }
+                if let Some(message) = s.format_trailing_garbage_warning() {
+                    let formatted = config.format_warning_message(&message);
+                    stderr.writeln(&formatted).unwrap_or(());
+                }
+                if let Some(message) = s.format_type_conflict_warning() {
+                    let formatted = config.format_warning_message(&message);
+                    stderr.writeln(&formatted).unwrap_or(());
+                }
             }
 
             if hints_allowed_runtime && terminal_allowed {
@@ -1538,7 +1988,7 @@ fn handle_pipeline_success(
                 // typo leaves output intact but silently fails to drop the field.
                 maybe_print_key_typo_hint(config, s, stderr);
                 // With --stats the ragged-row count is already in the stats block.
-                if config.output.stats.is_none() {
+                if !config.stats_on_terminal() {
                     maybe_print_csv_shape_hint(config, s, stderr);
                 }
             }
@@ -1561,51 +2011,87 @@ fn handle_pipeline_success(
     (pipeline_result.stats, Some(pipeline_result.tracking_data))
 }
 
+/// Write the --stats block to its routed destination. Shared by the normal
+/// and the signal exit so both honour the same format and destination;
+/// without final stats (interrupted before any were gathered) it says so.
+fn emit_stats_report(
+    config: &KeloraConfig,
+    final_stats: Option<&stats::ProcessingStats>,
+    events_were_output: bool,
+    stdout: &mut SafeStdout,
+    stderr: &mut SafeStderr,
+) {
+    let Some(route) = config.output.stats_route.as_ref() else {
+        return;
+    };
+    let json_stats = matches!(config.output.stats, Some(cli::StatsFormat::Json));
+    let mut formatted = match final_stats {
+        Some(s) if json_stats => s.format_stats_json(),
+        Some(s) => config.format_stats_message(
+            &s.format_stats(config.input.multiline.is_some()),
+            config.output.stats_with_events, // Show header only for --with-stats
+        ),
+        None => {
+            config.format_stats_message("Processing interrupted", config.output.stats_with_events)
+        }
+    };
+    if !events_were_output {
+        formatted = formatted.trim_start_matches('\n').to_string();
+    }
+    write_report(config, route, &formatted, "stats", stdout, stderr);
+}
+
+/// Write an end-of-run report where config::resolve_report_routing sent it
+fn write_report(
+    config: &KeloraConfig,
+    route: &cli::ReportRoute,
+    report: &str,
+    what: &str,
+    stdout: &mut SafeStdout,
+    stderr: &mut SafeStderr,
+) {
+    match route {
+        cli::ReportRoute::Stdout => stdout.writeln(report).unwrap_or(()),
+        cli::ReportRoute::Stderr => stderr.writeln(report).unwrap_or(()),
+        cli::ReportRoute::File(path) => {
+            let contents = format!("{}\n", report.trim_start_matches('\n'));
+            if let Err(e) = std::fs::write(path, contents) {
+                stderr
+                    .writeln(&config.format_error_message(&format!(
+                        "Failed to write {} file '{}': {}",
+                        what, path, e
+                    )))
+                    .unwrap_or(());
+            }
+        }
+    }
+}
+
 /// Handle signal termination - print stats and exit with appropriate code
 fn handle_signal_termination(
+    signal: i32,
     config: &KeloraConfig,
     final_stats: Option<&stats::ProcessingStats>,
     events_were_output: bool,
+    stdout: &mut SafeStdout,
     stderr: &mut SafeStderr,
-    terminal_allowed: bool,
 ) -> ! {
+    emit_stats_report(config, final_stats, events_were_output, stdout, stderr);
     if let Some(stats) = final_stats {
-        if config.output.stats.is_some() && terminal_allowed {
-            // Full stats when --stats flag is used (unless suppressed)
-            let mut formatted = config.format_stats_message(
-                &stats.format_stats(config.input.multiline.is_some()),
-                config.output.stats_with_events, // Show header only for --with-stats
-            );
-            if !events_were_output {
-                formatted = formatted.trim_start_matches('\n').to_string();
-            }
-            stderr.writeln(&formatted).unwrap_or(());
-        } else if stats.has_errors()
-            && !config.processing.silent
-            && !config.diagnostics_suppressed()
-        {
-            // Error summary by default when errors occur (unless suppressed)
+        // Error summary when errors occurred and the stats block didn't
+        // already show them; like the normal exit, only --silent hides it
+        if stats.has_errors() && !config.processing.silent && !config.stats_on_terminal() {
             let mut formatted = config.format_error_message(&stats.format_error_summary());
             if !events_were_output {
                 formatted = formatted.trim_start_matches('\n').to_string();
             }
             stderr.writeln(&formatted).unwrap_or(());
         }
-    } else if config.output.stats.is_some() && terminal_allowed {
-        let mut formatted = config.format_stats_message(
-            "Processing interrupted",
-            config.output.stats_with_events, // Show header only for --with-stats
-        );
-        if !events_were_output {
-            formatted = formatted.trim_start_matches('\n').to_string();
-        }
-        stderr.writeln(&formatted).unwrap_or(());
     }
 
     // Exit with the correct code based on which signal was received
     #[cfg(unix)]
     {
-        let signal = TERMINATION_SIGNAL.load(Ordering::Relaxed);
         match signal {
             sig if sig == SIGTERM => ExitCode::SignalTerm.exit(),
             sig if sig == SIGINT => ExitCode::SignalInt.exit(),
@@ -1615,6 +2101,7 @@ fn handle_signal_termination(
     #[cfg(not(unix))]
     {
         // Windows only supports SIGINT
+        let _ = signal;
         ExitCode::SignalInt.exit();
     }
 }
@@ -10,7 +10,8 @@ use crate::platform::Ctrl;
 
 use super::tracker::GlobalTracker;
 use super::types::{
-    Batch, BatcherThreadConfig, FileAwareLineContext, LineMessage, PlainLineContext,
+    Batch, BatcherThreadConfig, FileAwareLineContext, LineFilterDrops, LineMessage,
+    PlainLineContext,
 };
 use crate::parsers::type_conversion::TypeMap;
 
@@ -117,6 +118,129 @@ pub(crate) fn file_aware_io_reader_thread(
     Ok(())
 }
 
+/// Memory-mapped IO reader thread (`--mmap`) - reads lines of regular files
+/// through each file's mapping, with filename tracking. Files that can't be
+/// mapped (compressed, empty, special) are read through the buffered reader
+/// instead, exactly as `file_aware_io_reader_thread` would.
+pub(crate) fn mapped_io_reader_thread(
+    files: Vec<String>,
+    options: crate::readers::InputOptions,
+    strict: bool,
+    line_sender: Sender<LineMessage>,
+    ctrl_rx: Receiver<Ctrl>,
+) -> Result<()> {
+    for file in files {
+        let filename = Some(file.clone());
+        let sent = match crate::readers::MmapReader::open(&file, &options) {
+            Some(reader) => send_mapped_lines(reader, &filename, &line_sender, &ctrl_rx),
+            None => {
+                let reader = match crate::readers::open_input_reader(&file, &options, strict) {
+                    Ok(Some(reader)) => reader,
+                    Ok(None) => continue,
+                    Err(error) => {
+                        let _ = line_sender.send(LineMessage::Error { error, filename });
+                        return Ok(());
+                    }
+                };
+                send_buffered_lines(reader, &filename, &line_sender, &ctrl_rx)
+            }
+        };
+        if !sent {
+            return Ok(());
+        }
+    }
+    let _ = line_sender.send(LineMessage::Eof);
+    Ok(())
+}
+
+/// Whether the reader should keep going: false (after queueing `Eof`) once
+/// shutdown was requested.
+fn mapped_reader_running(line_sender: &Sender<LineMessage>, ctrl_rx: &Receiver<Ctrl>) -> bool {
+    if let Ok(Ctrl::Shutdown { .. }) = ctrl_rx.try_recv() {
+        let _ = line_sender.send(LineMessage::Eof);
+        return false;
+    }
+    true
+}
+
+/// Send every line of a mapped file; false when the reader must stop.
+fn send_mapped_lines(
+    mut reader: crate::readers::MmapReader,
+    filename: &Option<String>,
+    line_sender: &Sender<LineMessage>,
+    ctrl_rx: &Receiver<Ctrl>,
+) -> bool {
+    loop {
+        if !mapped_reader_running(line_sender, ctrl_rx) {
+            return false;
+        }
+        // Batches carry owned lines, so each line is copied out of the mapping
+        // once; trim that copy in place rather than copying it again
+        let line = match reader.next_line() {
+            Ok(None) => return true,
+            Ok(Some(mut line)) => {
+                line.truncate(line.trim_end().len());
+                line
+            }
+            Err(error) => {
+                let _ = line_sender.send(LineMessage::Error {
+                    error,
+                    filename: filename.clone(),
+                });
+                return false;
+            }
+        };
+        if line_sender
+            .send(LineMessage::Line {
+                line,
+                filename: filename.clone(),
+            })
+            .is_err()
+        {
+            return false;
+        }
+    }
+}
+
+/// Send every line of a file that could not be mapped; false when the reader
+/// must stop.
+fn send_buffered_lines(
+    mut reader: Box<dyn std::io::BufRead + Send>,
+    filename: &Option<String>,
+    line_sender: &Sender<LineMessage>,
+    ctrl_rx: &Receiver<Ctrl>,
+) -> bool {
+    let mut buffer = String::new();
+    loop {
+        if !mapped_reader_running(line_sender, ctrl_rx) {
+            return false;
+        }
+        buffer.clear();
+        match crate::readers::read_line_lossy(&mut reader, &mut buffer) {
+            Ok(0) => return true,
+            Ok(_) => {
+                let line = buffer.trim_end().to_string();
+                if line_sender
+                    .send(LineMessage::Line {
+                        line,
+                        filename: filename.clone(),
+                    })
+                    .is_err()
+                {
+                    return false;
+                }
+            }
+            Err(error) => {
+                let _ = line_sender.send(LineMessage::Error {
+                    error,
+                    filename: filename.clone(),
+                });
+                return false;
+            }
+        }
+    }
+}
+
 /// Batcher thread - collects lines into batches for parallel processing
 pub(crate) fn batcher_thread(
     line_receiver: Receiver<LineMessage>,
@@ -130,6 +254,7 @@ pub(crate) fn batcher_thread(
     let mut pending_deadline: Option<Instant> = None;
     let mut skipped_lines_count = 0usize;
     let mut filtered_lines = 0usize;
+    let mut line_filter_drops = LineFilterDrops::default();
     let mut csv_quote_open = false;
     let mut section_selector = config
         .section_config
@@ -205,10 +330,12 @@ pub(crate) fn batcher_thread(
                                     line_num: &mut line_num,
                                     skipped_lines_count: &mut skipped_lines_count,
                                     filtered_lines: &mut filtered_lines,
+                                    line_filter_drops: &mut line_filter_drops,
                                     skip_lines: config.skip_lines,
                                     head_lines: config.head_lines,
                                     section_selector: &mut section_selector,
                                     input_format: &config.input_format,
+                                    keep_blank_lines: config.keep_blank_lines,
                                     ignore_lines: &config.ignore_lines,
                                     keep_lines: &config.keep_lines,
                                     pending_deadline: &mut pending_deadline,
@@ -325,10 +452,12 @@ pub(crate) fn batcher_thread(
                                     line_num: &mut line_num,
                                     skipped_lines_count: &mut skipped_lines_count,
                                     filtered_lines: &mut filtered_lines,
+                                    line_filter_drops: &mut line_filter_drops,
                                     skip_lines: config.skip_lines,
                                     head_lines: config.head_lines,
                                     section_selector: &mut section_selector,
                                     input_format: &config.input_format,
+                                    keep_blank_lines: config.keep_blank_lines,
                                     ignore_lines: &config.ignore_lines,
                                     keep_lines: &config.keep_lines,
                                     pending_deadline: &mut pending_deadline,
@@ -389,6 +518,9 @@ pub(crate) fn batcher_thread(
 
     config.global_tracker.set_total_lines_read(line_num)?;
     config.global_tracker.add_lines_filtered(filtered_lines)?;
+    config
+        .global_tracker
+        .add_line_filter_drops(line_filter_drops)?;
 
     Ok(())
 }
@@ -401,12 +533,13 @@ pub(crate) fn file_aware_batcher_thread(
     batch_size: usize,
     batch_timeout: Duration,
     global_tracker: GlobalTracker,
-    ignore_lines: Option<regex::Regex>,
-    keep_lines: Option<regex::Regex>,
+    ignore_lines: Option<regex::RegexSet>,
+    keep_lines: Option<regex::RegexSet>,
     skip_lines: usize,
     head_lines: Option<usize>,
     section_config: Option<crate::config::SectionConfig>,
     input_format: crate::config::InputFormat,
+    keep_blank_lines: bool,
     strict: bool,
     ctrl_rx: Receiver<Ctrl>,
 ) -> Result<()> {
@@ -418,6 +551,7 @@ pub(crate) fn file_aware_batcher_thread(
     let mut pending_deadline: Option<Instant> = None;
     let mut skipped_lines_count = 0usize;
     let mut filtered_lines = 0usize;
+    let mut line_filter_drops = LineFilterDrops::default();
     let mut last_filename: Option<String> = None;
     let mut current_headers: Option<Vec<String>> = None;
     let mut current_type_map: Option<TypeMap> = None;
@@ -502,10 +636,12 @@ pub(crate) fn file_aware_batcher_thread(
                                 line_num: &mut line_num,
                                 skipped_lines_count: &mut skipped_lines_count,
                                 filtered_lines: &mut filtered_lines,
+                                line_filter_drops: &mut line_filter_drops,
                                 skip_lines,
                                 head_lines,
                                 section_selector: &mut section_selector,
                                 input_format: &input_format,
+                                keep_blank_lines,
                                 strict,
                                 ignore_lines: &ignore_lines,
                                 keep_lines: &keep_lines,
@@ -643,10 +779,12 @@ pub(crate) fn file_aware_batcher_thread(
                                 line_num: &mut line_num,
                                 skipped_lines_count: &mut skipped_lines_count,
                                 filtered_lines: &mut filtered_lines,
+                                line_filter_drops: &mut line_filter_drops,
                                 skip_lines,
                                 head_lines,
                                 section_selector: &mut section_selector,
                                 input_format: &input_format,
+                                keep_blank_lines,
                                 strict,
                                 ignore_lines: &ignore_lines,
                                 keep_lines: &keep_lines,
@@ -720,6 +858,7 @@ pub(crate) fn file_aware_batcher_thread(
 
     global_tracker.set_total_lines_read(line_num)?;
     global_tracker.add_lines_filtered(filtered_lines)?;
+    global_tracker.add_line_filter_drops(line_filter_drops)?;
 
     Ok(())
 }
@@ -766,13 +905,14 @@ pub(crate) fn handle_plain_line(line: String, ctx: PlainLineContext<'_>) -> Resu
             }
         }
 
-        if line.is_empty() && !matches!(ctx.input_format, crate::config::InputFormat::Line) {
+        if line.is_empty() && !ctx.keep_blank_lines {
             return Ok(());
         }
 
         if let Some(keep_regex) = ctx.keep_lines.as_ref() {
             if !keep_regex.is_match(&line) {
                 *ctx.filtered_lines += 1;
+                ctx.line_filter_drops.keep += 1;
                 return Ok(());
             }
         }
@@ -780,6 +920,7 @@ pub(crate) fn handle_plain_line(line: String, ctx: PlainLineContext<'_>) -> Resu
         if let Some(ignore_regex) = ctx.ignore_lines.as_ref() {
             if ignore_regex.is_match(&line) {
                 *ctx.filtered_lines += 1;
+                ctx.line_filter_drops.ignore += 1;
                 return Ok(());
             }
         }
@@ -871,13 +1012,14 @@ pub(crate) fn handle_file_aware_line(
             }
         }
 
-        if line.is_empty() && !matches!(ctx.input_format, crate::config::InputFormat::Line) {
+        if line.is_empty() && !ctx.keep_blank_lines {
             return Ok(());
         }
 
         if let Some(ref keep_regex) = ctx.keep_lines {
             if !keep_regex.is_match(&line) {
                 *ctx.filtered_lines += 1;
+                ctx.line_filter_drops.keep += 1;
                 return Ok(());
             }
         }
@@ -885,6 +1027,7 @@ pub(crate) fn handle_file_aware_line(
         if let Some(ref ignore_regex) = ctx.ignore_lines {
             if ignore_regex.is_match(&line) {
                 *ctx.filtered_lines += 1;
+                ctx.line_filter_drops.ignore += 1;
                 return Ok(());
             }
         }
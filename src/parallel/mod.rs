@@ -10,10 +10,12 @@
 //! - `batching`: Line batching and I/O reader threads
 //! - `worker`: Worker thread for processing batches
 //! - `sink`: Result sink thread for ordered output
+//! - `reorder`: Timestamp reorder buffer for `--parallel-order-by timestamp`
 //! - `processor`: Main ParallelProcessor orchestration
 
 mod batching;
 mod processor;
+mod reorder;
 mod sink;
 mod tracker;
 mod types;
@@ -21,4 +23,5 @@ mod worker;
 
 // Re-export public types
 pub use processor::ParallelProcessor;
+pub(crate) use tracker::merge_tracking_state;
 pub use types::ParallelConfig;
@@ -5,16 +5,17 @@
 use anyhow::Result;
 use crossbeam_channel::{bounded, unbounded};
 use std::io::Read;
-use std::thread;
 use std::time::Duration;
 
 use crate::pipeline::{PipelineBuilder, DEFAULT_MULTILINE_FLUSH_TIMEOUT_MS};
-use crate::platform::Ctrl;
+use crate::platform::{CancelToken, Ctrl};
 use crate::rhai_functions::tracking::TrackingSnapshot;
+use crate::run_scope;
 use crate::stats::ProcessingStats;
 
 use super::batching::{
-    batcher_thread, file_aware_batcher_thread, file_aware_io_reader_thread, plain_io_reader_thread,
+    batcher_thread, file_aware_batcher_thread, file_aware_io_reader_thread,
+    mapped_io_reader_thread, plain_io_reader_thread,
 };
 use super::sink::pipeline_result_sink_thread;
 use super::tracker::GlobalTracker;
@@ -26,6 +27,7 @@ pub struct ParallelProcessor {
     config: ParallelConfig,
     global_tracker: GlobalTracker,
     take_limit: Option<usize>,
+    cancel: CancelToken,
 }
 
 impl ParallelProcessor {
@@ -34,6 +36,7 @@ impl ParallelProcessor {
             config,
             global_tracker: GlobalTracker::new(),
             take_limit: None,
+            cancel: CancelToken::new(),
         }
     }
 
@@ -42,6 +45,13 @@ impl ParallelProcessor {
         self
     }
 
+    /// Share the run's token: the sink stops on cancellation and cancels it
+    /// itself once --take is satisfied
+    pub fn with_cancel_token(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
     /// Process input using the parallel pipeline
     pub fn process_with_pipeline<
         R: std::io::BufRead + Send + 'static,
@@ -55,6 +65,11 @@ impl ParallelProcessor {
         output: W,
         ctrl_rx: crossbeam_channel::Receiver<Ctrl>,
     ) -> Result<()> {
+        self.global_tracker.set_files_skipped_by_name(
+            config.input.files_skipped_by_name.clone(),
+            config.processing.verbose > 0,
+        );
+
         // For file processing, try to use file-aware reader if available
         if !config.input.files.is_empty() {
             return self.process_with_file_aware_pipeline(
@@ -132,7 +147,7 @@ impl ParallelProcessor {
 
         let io_handle = {
             let ctrl_for_io = ctrl_rx.clone();
-            thread::spawn(move || plain_io_reader_thread(reader, line_sender, ctrl_for_io))
+            run_scope::spawn(move || plain_io_reader_thread(reader, line_sender, ctrl_for_io))
         };
 
         let batch_handle = {
@@ -145,9 +160,10 @@ impl ParallelProcessor {
             let section_config = config.input.section.clone();
             let global_tracker_clone = self.global_tracker.clone();
             let input_format = config.input.format.clone();
+            let keep_blank_lines = config.input.keeps_blank_lines(&input_format);
             let ctrl_for_batcher = ctrl_rx.clone();
 
-            thread::spawn(move || {
+            run_scope::spawn(move || {
                 batcher_thread(
                     line_receiver,
                     BatcherThreadConfig {
@@ -161,6 +177,7 @@ impl ParallelProcessor {
                         head_lines,
                         section_config,
                         input_format,
+                        keep_blank_lines,
                         preprocessing_line_count,
                     },
                     ctrl_for_batcher,
@@ -174,7 +191,7 @@ impl ParallelProcessor {
             let chunker_multiline_config = multiline_config.clone();
             let chunker_input_format = config.input.format.clone();
 
-            let handle = thread::spawn(move || {
+            let handle = run_scope::spawn(move || {
                 chunker_thread(
                     batch_receiver,
                     work_sender,
@@ -188,7 +205,7 @@ impl ParallelProcessor {
             // For non-multiline mode, workers receive line batches directly
             // Convert line batches to work messages
             let converter_ctrl = ctrl_rx.clone();
-            let handle = thread::spawn(move || -> Result<()> {
+            let handle = run_scope::spawn(move || -> Result<()> {
                 while let Ok(batch) = batch_receiver.recv() {
                     if let Ok(Ctrl::Shutdown { .. }) = converter_ctrl.try_recv() {
                         break;
@@ -218,7 +235,7 @@ impl ParallelProcessor {
             let worker_ctrl = ctrl_rx.clone();
             let worker_timeout = worker_multiline_timeout;
 
-            let handle = thread::spawn(move || {
+            let handle = run_scope::spawn(move || {
                 worker_thread(
                     worker_id,
                     work_receiver,
@@ -235,6 +252,9 @@ impl ParallelProcessor {
         // Drop senders to signal completion
         drop(batch_sender);
         drop(result_sender);
+        // Only the workers receive work, so once they have all stopped (e.g.
+        // after a script exit()) the feeding threads' sends fail and they stop
+        drop(work_receiver);
 
         // Start result sink thread
         let sink_handle = {
@@ -245,8 +265,12 @@ impl ParallelProcessor {
             let config_clone = config.clone();
             let take_limit = self.take_limit;
             let ctrl_for_sink = ctrl_rx.clone();
+            let cancel = self.cancel.clone();
+            // The sink writes the workers' captured print()/eprint() lines
+            let script_output = crate::rhai_functions::capture::script_output();
 
-            thread::spawn(move || {
+            run_scope::spawn(move || {
+                crate::rhai_functions::capture::set_script_output(script_output);
                 pipeline_result_sink_thread(
                     result_receiver,
                     preserve_order,
@@ -255,6 +279,7 @@ impl ParallelProcessor {
                     &config_clone,
                     take_limit,
                     ctrl_for_sink,
+                    cancel,
                 )
             })
         };
@@ -295,9 +320,6 @@ impl ParallelProcessor {
         output: W,
         ctrl_rx: crossbeam_channel::Receiver<Ctrl>,
     ) -> Result<()> {
-        // Create file-aware reader
-        let file_aware_reader = crate::pipeline::builders::create_file_aware_input_reader(config)?;
-
         // Create channels - conditionally use chunker thread for multiline mode
         let (batch_sender, batch_receiver) = if let Some(size) = self.config.buffer_size {
             bounded(size)
@@ -337,9 +359,24 @@ impl ParallelProcessor {
 
         let io_handle = {
             let ctrl_for_io = ctrl_rx.clone();
-            thread::spawn(move || {
-                file_aware_io_reader_thread(file_aware_reader, line_sender, ctrl_for_io)
-            })
+            if config.input.mmap {
+                // Regular files are read through their maps
+                let files = crate::pipeline::builders::sort_files(
+                    &config.input.files,
+                    &config.input.file_order,
+                )?;
+                let options = crate::readers::InputOptions::from_config(config);
+                let strict = config.processing.strict;
+                run_scope::spawn(move || {
+                    mapped_io_reader_thread(files, options, strict, line_sender, ctrl_for_io)
+                })
+            } else {
+                let file_aware_reader =
+                    crate::pipeline::builders::create_file_aware_input_reader(config)?;
+                run_scope::spawn(move || {
+                    file_aware_io_reader_thread(file_aware_reader, line_sender, ctrl_for_io)
+                })
+            }
         };
 
         let batch_handle = {
@@ -352,10 +389,11 @@ impl ParallelProcessor {
             let section_config = config.input.section.clone();
             let global_tracker_clone = self.global_tracker.clone();
             let input_format = config.input.format.clone();
+            let keep_blank_lines = config.input.keeps_blank_lines(&input_format);
             let strict = config.processing.strict;
             let ctrl_for_batcher = ctrl_rx.clone();
 
-            thread::spawn(move || {
+            run_scope::spawn(move || {
                 file_aware_batcher_thread(
                     line_receiver,
                     batch_sender,
@@ -368,6 +406,7 @@ impl ParallelProcessor {
                     head_lines,
                     section_config,
                     input_format,
+                    keep_blank_lines,
                     strict,
                     ctrl_for_batcher,
                 )
@@ -380,7 +419,7 @@ impl ParallelProcessor {
             let chunker_multiline_config = multiline_config.clone();
             let chunker_input_format = config.input.format.clone();
 
-            let handle = thread::spawn(move || {
+            let handle = run_scope::spawn(move || {
                 chunker_thread(
                     batch_receiver,
                     work_sender,
@@ -394,7 +433,7 @@ impl ParallelProcessor {
             // For non-multiline mode, workers receive line batches directly
             // Convert line batches to work messages
             let converter_ctrl = ctrl_rx.clone();
-            let handle = thread::spawn(move || -> Result<()> {
+            let handle = run_scope::spawn(move || -> Result<()> {
                 while let Ok(batch) = batch_receiver.recv() {
                     if let Ok(Ctrl::Shutdown { .. }) = converter_ctrl.try_recv() {
                         break;
@@ -424,7 +463,7 @@ impl ParallelProcessor {
             let worker_ctrl = ctrl_rx.clone();
             let worker_timeout = worker_multiline_timeout;
 
-            let handle = thread::spawn(move || {
+            let handle = run_scope::spawn(move || {
                 worker_thread(
                     worker_id,
                     work_receiver,
@@ -441,6 +480,9 @@ impl ParallelProcessor {
         // Drop senders to signal completion
         drop(batch_sender);
         drop(result_sender);
+        // Only the workers receive work, so once they have all stopped (e.g.
+        // after a script exit()) the feeding threads' sends fail and they stop
+        drop(work_receiver);
 
         // Start result sink thread
         let sink_handle = {
@@ -451,8 +493,12 @@ impl ParallelProcessor {
             let config_clone = config.clone();
             let take_limit = self.take_limit;
             let ctrl_for_sink = ctrl_rx.clone();
+            let cancel = self.cancel.clone();
+            // The sink writes the workers' captured print()/eprint() lines
+            let script_output = crate::rhai_functions::capture::script_output();
 
-            thread::spawn(move || {
+            run_scope::spawn(move || {
+                crate::rhai_functions::capture::set_script_output(script_output);
                 pipeline_result_sink_thread(
                     result_receiver,
                     preserve_order,
@@ -461,6 +507,7 @@ impl ParallelProcessor {
                     &config_clone,
                     take_limit,
                     ctrl_for_sink,
+                    cancel,
                 )
             })
         };
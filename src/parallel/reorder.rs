@@ -0,0 +1,176 @@
+//! Timestamp reordering for `--parallel-order-by timestamp`
+//!
+//! With `--unordered`, batches reach the sink in whatever order the workers
+//! finish them. The buffer holds up to `capacity` events and always releases
+//! the earliest one first, which restores chronological order as long as no
+//! event arrives more than `capacity` events behind where it belongs. An event
+//! older than the last one written is late: it is written right away, in
+//! arrival order, and counted so the sink can warn.
+
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use super::types::ProcessedEvent;
+
+struct Pending {
+    ts: DateTime<Utc>,
+    /// Arrival order, so equal timestamps keep it
+    seq: u64,
+    event: ProcessedEvent,
+}
+
+impl PartialEq for Pending {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pending {}
+
+impl PartialOrd for Pending {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pending {
+    /// Reversed, so the max-heap pops the earliest event
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.ts, other.seq).cmp(&(self.ts, self.seq))
+    }
+}
+
+pub(crate) struct TimestampReorder {
+    capacity: usize,
+    pending: BinaryHeap<Pending>,
+    next_seq: u64,
+    /// Timestamp of the latest event released; earlier ones are late
+    watermark: Option<DateTime<Utc>>,
+    /// Untimestamped output of worker flush batches (formatter tails),
+    /// written after everything else
+    tail: Vec<ProcessedEvent>,
+    late_events: usize,
+}
+
+impl TimestampReorder {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            pending: BinaryHeap::new(),
+            next_seq: 0,
+            watermark: None,
+            tail: Vec::new(),
+            late_events: 0,
+        }
+    }
+
+    /// Buffer a batch's results and return the ones to write now.
+    ///
+    /// Untimestamped results (prints, continuation output) stay behind the
+    /// event before them in the batch. Leading ones are written at once, or
+    /// held for the end when `flush_batch` marks a worker's final batch.
+    pub(crate) fn push(
+        &mut self,
+        results: Vec<ProcessedEvent>,
+        flush_batch: bool,
+    ) -> Vec<ProcessedEvent> {
+        let mut ready = Vec::new();
+        let mut previous_ts = None;
+        for event in results {
+            let ts = event.timestamp.or(previous_ts);
+            previous_ts = ts;
+            match ts {
+                None if flush_batch => self.tail.push(event),
+                None => ready.push(event),
+                Some(ts) if self.watermark.is_some_and(|watermark| ts < watermark) => {
+                    if event.timestamp.is_some() {
+                        self.late_events += 1;
+                    }
+                    ready.push(event);
+                }
+                Some(ts) => {
+                    self.pending.push(Pending {
+                        ts,
+                        seq: self.next_seq,
+                        event,
+                    });
+                    self.next_seq += 1;
+                }
+            }
+        }
+        while self.pending.len() > self.capacity {
+            ready.extend(self.pop());
+        }
+        ready
+    }
+
+    /// Release everything still buffered, in timestamp order, followed by
+    /// the held flush-batch tails.
+    pub(crate) fn finish(&mut self) -> Vec<ProcessedEvent> {
+        let mut ready = Vec::with_capacity(self.pending.len() + self.tail.len());
+        while let Some(event) = self.pop() {
+            ready.push(event);
+        }
+        ready.append(&mut self.tail);
+        ready
+    }
+
+    /// Events written out of order because they arrived too late
+    pub(crate) fn late_events(&self) -> usize {
+        self.late_events
+    }
+
+    fn pop(&mut self) -> Option<ProcessedEvent> {
+        let pending = self.pending.pop()?;
+        self.watermark = Some(self.watermark.map_or(pending.ts, |w| w.max(pending.ts)));
+        Some(pending.event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+    use chrono::TimeZone;
+
+    fn event(secs: Option<i64>) -> ProcessedEvent {
+        let line = secs.map_or_else(|| "-".to_string(), |s| s.to_string());
+        ProcessedEvent {
+            event: Event::default_with_line(line),
+            captured_prints: Vec::new(),
+            captured_eprints: Vec::new(),
+            captured_messages: Vec::new(),
+            timestamp: secs.map(|s| Utc.timestamp_opt(s, 0).unwrap()),
+            file_ops: Vec::new(),
+        }
+    }
+
+    fn lines(events: Vec<ProcessedEvent>) -> Vec<String> {
+        events.into_iter().map(|e| e.event.original_line).collect()
+    }
+
+    #[test]
+    fn test_reorder_restores_order_within_capacity() {
+        let mut reorder = TimestampReorder::new(3);
+        let mut out = Vec::new();
+        out.extend(reorder.push(vec![event(Some(4)), event(Some(5))], false));
+        out.extend(reorder.push(vec![event(Some(1)), event(None), event(Some(2))], false));
+        out.extend(reorder.push(vec![event(Some(6)), event(Some(3))], false));
+        out.extend(reorder.finish());
+        assert_eq!(lines(out), ["1", "-", "2", "3", "4", "5", "6"]);
+        assert_eq!(reorder.late_events(), 0);
+    }
+
+    #[test]
+    fn test_reorder_writes_late_events_in_arrival_order() {
+        let mut reorder = TimestampReorder::new(1);
+        let mut out = reorder.push(vec![event(Some(5)), event(Some(7))], false);
+        assert_eq!(lines(std::mem::take(&mut out)), ["5"]);
+        out.extend(reorder.push(vec![event(Some(2))], false));
+        out.extend(reorder.push(vec![event(None)], true));
+        out.extend(reorder.finish());
+        assert_eq!(lines(out), ["2", "7", "-"]);
+        assert_eq!(reorder.late_events(), 1);
+    }
+}
@@ -5,14 +5,17 @@
 use anyhow::{anyhow, Result};
 use crossbeam_channel::Receiver;
 use std::collections::HashMap;
-use std::sync::atomic::Ordering;
 
 use crate::formatters::GapTracker;
-use crate::platform::{Ctrl, SHOULD_TERMINATE};
+use crate::platform::{CancelReason, CancelToken, Ctrl};
 use crate::rhai_functions::file_ops;
 
+use super::reorder::TimestampReorder;
 use super::tracker::GlobalTracker;
-use super::types::{BatchResult, ProcessedEvent};
+use super::types::{BatchResult, ProcessedEvent, ReservoirPartial};
+
+/// Batches per worker held by the --parallel-order-by timestamp buffer
+const REORDER_BATCHES_PER_WORKER: usize = 4;
 
 /// Write CSV header if the output format requires it
 pub(crate) fn write_csv_header_if_needed<W: std::io::Write>(
@@ -49,6 +52,7 @@ pub(crate) fn write_csv_header_if_needed<W: std::io::Write>(
 
 /// Pipeline result sink thread: handles output ordering and merges global state
 /// Results are already formatted by the pipeline, so we just need to output them
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn pipeline_result_sink_thread<W: std::io::Write>(
     result_receiver: Receiver<BatchResult>,
     preserve_order: bool,
@@ -57,6 +61,7 @@ pub(crate) fn pipeline_result_sink_thread<W: std::io::Write>(
     config: &crate::config::KeloraConfig,
     take_limit: Option<usize>,
     ctrl_rx: Receiver<Ctrl>,
+    cancel: CancelToken,
 ) -> Result<()> {
     // Write CSV header if needed (before any worker results)
     write_csv_header_if_needed(output, config)?;
@@ -81,6 +86,7 @@ pub(crate) fn pipeline_result_sink_thread<W: std::io::Write>(
             &mut gap_tracker,
             ctrl_rx,
             config,
+            &cancel,
         )
     } else {
         pipeline_unordered_result_sink(
@@ -91,11 +97,13 @@ pub(crate) fn pipeline_result_sink_thread<W: std::io::Write>(
             &mut gap_tracker,
             ctrl_rx,
             config,
+            &cancel,
         )
     }
 }
 
 /// Ordered result sink - maintains batch order for deterministic output
+#[allow(clippy::too_many_arguments)]
 fn pipeline_ordered_result_sink<W: std::io::Write>(
     result_receiver: Receiver<BatchResult>,
     global_tracker: GlobalTracker,
@@ -103,17 +111,19 @@ fn pipeline_ordered_result_sink<W: std::io::Write>(
     take_limit: Option<usize>,
     gap_tracker: &mut Option<GapTracker>,
     _ctrl_rx: Receiver<Ctrl>,
-    _config: &crate::config::KeloraConfig,
+    config: &crate::config::KeloraConfig,
+    cancel: &CancelToken,
 ) -> Result<()> {
     let mut pending_batches: HashMap<u64, BatchResult> = HashMap::new();
     let mut next_expected_id = 0u64;
     let mut events_output = 0usize;
+    let mut reservoir_parts = Vec::new();
 
     let mut termination_detected = false;
     while let Ok(mut batch_result) = result_receiver.recv() {
         // Check for termination signal, but don't break immediately
         // Continue processing to collect final stats from workers
-        if SHOULD_TERMINATE.load(Ordering::Relaxed) {
+        if cancel.is_cancelled() {
             termination_detected = true;
         }
 
@@ -137,24 +147,26 @@ fn pipeline_ordered_result_sink<W: std::io::Write>(
             }
             continue;
         } else if batch_id == u64::MAX - 1 {
-            // This is a flush batch from a worker - process it immediately
-            if !termination_detected {
-                let remaining_limit = take_limit.map(|limit| limit.saturating_sub(events_output));
-                let events_this_batch = pipeline_output_batch_results(
-                    output,
-                    &batch_result.results,
-                    remaining_limit,
-                    gap_tracker,
-                )?;
-                events_output += events_this_batch;
-
-                // Check if we've reached the take limit
-                if let Some(limit) = take_limit {
-                    if events_output >= limit {
-                        // Set termination signal to stop further processing
-                        SHOULD_TERMINATE.store(true, Ordering::Relaxed);
-                        break;
-                    }
+            reservoir_parts.extend(batch_result.reservoir.take());
+            // This is a flush batch from a worker - process it immediately.
+            // It carries the worker's pending multiline chunk and formatter
+            // finish() tail, so it is written even after a graceful shutdown
+            // signal, like the sequential end-of-run flush.
+            let remaining_limit = take_limit.map(|limit| limit.saturating_sub(events_output));
+            let events_this_batch = pipeline_output_batch_results(
+                output,
+                &batch_result.results,
+                remaining_limit,
+                gap_tracker,
+            )?;
+            events_output += events_this_batch;
+
+            // Check if we've reached the take limit
+            if let Some(limit) = take_limit {
+                if events_output >= limit {
+                    // Set termination signal to stop further processing
+                    cancel.cancel_with(CancelReason::TakeLimit);
+                    break;
                 }
             }
             continue;
@@ -184,15 +196,23 @@ fn pipeline_ordered_result_sink<W: std::io::Write>(
             if let Some(limit) = take_limit {
                 if events_output >= limit {
                     // Set termination signal to stop further processing
-                    SHOULD_TERMINATE.store(true, Ordering::Relaxed);
+                    cancel.cancel_with(CancelReason::TakeLimit);
                     break;
                 }
             }
         }
     }
 
-    // Output any remaining batches (shouldn't happen with proper shutdown)
-    for (_, batch) in pending_batches {
+    // Output any remaining batches in order. After a cancellation only the
+    // run that continues what was already written goes out, so the output
+    // stays an in-order prefix of the input.
+    let mut remaining: Vec<(u64, BatchResult)> = pending_batches.into_iter().collect();
+    remaining.sort_by_key(|(batch_id, _)| *batch_id);
+    for (batch_id, batch) in remaining {
+        if termination_detected && batch_id != next_expected_id {
+            break;
+        }
+        next_expected_id = batch_id + 1;
         let remaining_limit = take_limit.map(|limit| limit.saturating_sub(events_output));
         events_output +=
             pipeline_output_batch_results(output, &batch.results, remaining_limit, gap_tracker)?;
@@ -205,10 +225,20 @@ fn pipeline_ordered_result_sink<W: std::io::Write>(
         }
     }
 
+    pipeline_output_reservoir(
+        output,
+        reservoir_parts,
+        config,
+        &global_tracker,
+        take_limit.map(|limit| limit.saturating_sub(events_output)),
+        gap_tracker,
+    )?;
+
     Ok(())
 }
 
 /// Unordered result sink - outputs batches as they arrive
+#[allow(clippy::too_many_arguments)]
 fn pipeline_unordered_result_sink<W: std::io::Write>(
     result_receiver: Receiver<BatchResult>,
     global_tracker: GlobalTracker,
@@ -217,9 +247,19 @@ fn pipeline_unordered_result_sink<W: std::io::Write>(
     gap_tracker: &mut Option<GapTracker>,
     ctrl_rx: Receiver<Ctrl>,
     config: &crate::config::KeloraConfig,
+    cancel: &CancelToken,
 ) -> Result<()> {
     let mut termination_detected = false;
     let mut events_output = 0usize;
+    let mut reservoir_parts = Vec::new();
+    // Each worker holds one batch at a time, but a descheduled worker can fall
+    // a few batches behind the others, more so with more threads than cores
+    let mut reorder = config.performance.parallel_order_by.map(|_| {
+        TimestampReorder::new(
+            REORDER_BATCHES_PER_WORKER * config.effective_threads() * config.effective_batch_size(),
+        )
+    });
+    let mut take_limit_reached = false;
 
     loop {
         // Check for control messages first (non-blocking)
@@ -249,7 +289,7 @@ fn pipeline_unordered_result_sink<W: std::io::Write>(
 
         // Check for termination signal, but don't break immediately
         // Continue processing to collect final stats from workers
-        if SHOULD_TERMINATE.load(Ordering::Relaxed) {
+        if cancel.is_cancelled() {
             termination_detected = true;
         }
 
@@ -270,24 +310,27 @@ fn pipeline_unordered_result_sink<W: std::io::Write>(
             }
             continue;
         } else if batch_result.batch_id == u64::MAX - 1 {
-            // This is a flush batch from a worker - process it immediately
-            if !termination_detected {
-                let remaining_limit = take_limit.map(|limit| limit.saturating_sub(events_output));
-                let events_this_batch = pipeline_output_batch_results(
-                    output,
-                    &batch_result.results,
-                    remaining_limit,
-                    gap_tracker,
-                )?;
-                events_output += events_this_batch;
-
-                // Check if we've reached the take limit
-                if let Some(limit) = take_limit {
-                    if events_output >= limit {
-                        // Set termination signal to stop further processing
-                        SHOULD_TERMINATE.store(true, Ordering::Relaxed);
-                        break;
-                    }
+            reservoir_parts.extend(batch_result.reservoir.take());
+            // This is a flush batch from a worker - process it immediately.
+            // It carries the worker's pending multiline chunk and formatter
+            // finish() tail, so it is written even after a graceful shutdown
+            // signal, like the sequential end-of-run flush.
+            let results = match reorder.as_mut() {
+                Some(reorder) => reorder.push(std::mem::take(&mut batch_result.results), true),
+                None => std::mem::take(&mut batch_result.results),
+            };
+            let remaining_limit = take_limit.map(|limit| limit.saturating_sub(events_output));
+            let events_this_batch =
+                pipeline_output_batch_results(output, &results, remaining_limit, gap_tracker)?;
+            events_output += events_this_batch;
+
+            // Check if we've reached the take limit
+            if let Some(limit) = take_limit {
+                if events_output >= limit {
+                    // Set termination signal to stop further processing
+                    cancel.cancel_with(CancelReason::TakeLimit);
+                    take_limit_reached = true;
+                    break;
                 }
             }
             continue;
@@ -298,26 +341,95 @@ fn pipeline_unordered_result_sink<W: std::io::Write>(
             continue;
         }
 
-        // Output immediately
+        // Output immediately, or whatever the reorder buffer releases
+        let results = match reorder.as_mut() {
+            Some(reorder) => reorder.push(std::mem::take(&mut batch_result.results), false),
+            None => std::mem::take(&mut batch_result.results),
+        };
         let remaining_limit = take_limit.map(|limit| limit.saturating_sub(events_output));
-        let events_this_batch = pipeline_output_batch_results(
-            output,
-            &batch_result.results,
-            remaining_limit,
-            gap_tracker,
-        )?;
+        let events_this_batch =
+            pipeline_output_batch_results(output, &results, remaining_limit, gap_tracker)?;
         events_output += events_this_batch;
 
         // Check if we've reached the take limit
         if let Some(limit) = take_limit {
             if events_output >= limit {
                 // Set termination signal to stop further processing
-                SHOULD_TERMINATE.store(true, Ordering::Relaxed);
+                cancel.cancel_with(CancelReason::TakeLimit);
+                take_limit_reached = true;
                 break;
             }
         }
     }
 
+    if let Some(mut reorder) = reorder {
+        if !take_limit_reached {
+            let remaining_limit = take_limit.map(|limit| limit.saturating_sub(events_output));
+            events_output += pipeline_output_batch_results(
+                output,
+                &reorder.finish(),
+                remaining_limit,
+                gap_tracker,
+            )?;
+        }
+        if reorder.late_events() > 0 && config.warnings_allowed() {
+            let message = crate::config::format_warning_message_auto(&format!(
+                "--parallel-order-by timestamp: {} event(s) arrived after later events had been \
+                 written and were output in arrival order; lower --threads or --batch-size to \
+                 narrow the gap",
+                reorder.late_events()
+            ));
+            let _ = crate::platform::SafeStderr::new().writeln(&message);
+        }
+    }
+
+    pipeline_output_reservoir(
+        output,
+        reservoir_parts,
+        config,
+        &global_tracker,
+        take_limit.map(|limit| limit.saturating_sub(events_output)),
+        gap_tracker,
+    )?;
+
+    Ok(())
+}
+
+/// Merge the workers' partial --reservoir samples and write the result in
+/// input order. Sampled events were not counted by the workers, so the merged
+/// ones are counted as output here and the rest as filtered.
+fn pipeline_output_reservoir<W: std::io::Write>(
+    output: &mut W,
+    parts: Vec<ReservoirPartial>,
+    config: &crate::config::KeloraConfig,
+    global_tracker: &GlobalTracker,
+    remaining_limit: Option<usize>,
+    gap_tracker: &mut Option<GapTracker>,
+) -> Result<()> {
+    let Some(capacity) = config.processing.reservoir else {
+        return Ok(());
+    };
+    if parts.is_empty() {
+        return Ok(());
+    }
+
+    let parts = parts
+        .into_iter()
+        .map(|part| (part.seen, part.sample))
+        .collect();
+    let mut rng = crate::rhai_functions::random::seeded_rng();
+    let (mut merged, dropped) = crate::pipeline::merge_reservoirs(parts, capacity, &mut rng);
+    merged.sort_by_key(|(line_num, _)| *line_num);
+
+    let results: Vec<ProcessedEvent> = merged.into_iter().map(|(_, event)| event).collect();
+    let written = pipeline_output_batch_results(output, &results, remaining_limit, gap_tracker)?;
+
+    let counts = crate::pipeline::InternalStats {
+        events_output: written as u64,
+        events_filtered: (dropped.len() + results.len() - written) as u64,
+        ..Default::default()
+    };
+    global_tracker.merge_internal_stats(&counts)?;
     Ok(())
 }
 
@@ -348,10 +460,10 @@ fn pipeline_output_batch_results<W: std::io::Write>(
             for message in &processed.captured_messages {
                 match message {
                     crate::rhai_functions::strings::CapturedMessage::Stdout(msg) => {
-                        println!("{}", msg);
+                        crate::rhai_functions::capture::emit_print(msg);
                     }
                     crate::rhai_functions::strings::CapturedMessage::Stderr(msg) => {
-                        eprintln!("{}", msg);
+                        crate::rhai_functions::capture::emit_eprint(msg);
                     }
                 }
             }
@@ -359,12 +471,12 @@ fn pipeline_output_batch_results<W: std::io::Write>(
             // Fallback to old system for compatibility
             // First output any captured prints for this specific event (to stdout, not file)
             for print_msg in &processed.captured_prints {
-                println!("{}", print_msg);
+                crate::rhai_functions::capture::emit_print(print_msg);
             }
 
             // Output any captured eprints for this specific event (to stderr)
             for eprint_msg in &processed.captured_eprints {
-                eprintln!("{}", eprint_msg);
+                crate::rhai_functions::capture::emit_eprint(eprint_msg);
             }
         }
 
@@ -375,11 +487,13 @@ fn pipeline_output_batch_results<W: std::io::Write>(
                 None => None,
             };
 
+            let write_started = crate::pipeline::phase_timer();
             if let Some(marker_line) = marker {
                 writeln!(output, "{}", marker_line).unwrap_or(());
             }
 
             writeln!(output, "{}", &processed.event.original_line).unwrap_or(());
+            crate::pipeline::record_phase_time(crate::pipeline::Phase::Write, write_started);
             events_output += 1;
         }
     }
@@ -387,3 +501,76 @@ fn pipeline_output_batch_results<W: std::io::Write>(
     output.flush().unwrap_or(());
     Ok(events_output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KeloraConfig;
+    use crate::event::Event;
+    use crate::pipeline::InternalStats;
+    use crate::stats::ProcessingStats;
+    use crossbeam_channel::unbounded;
+
+    fn batch(batch_id: u64, lines: &[&str]) -> BatchResult {
+        BatchResult {
+            batch_id,
+            results: lines
+                .iter()
+                .map(|line| ProcessedEvent {
+                    event: Event::default_with_line(line.to_string()),
+                    captured_prints: Vec::new(),
+                    captured_eprints: Vec::new(),
+                    captured_messages: Vec::new(),
+                    timestamp: None,
+                    file_ops: Vec::new(),
+                })
+                .collect(),
+            user_tracked_updates: HashMap::new(),
+            internal_tracked_updates: HashMap::new(),
+            internal_stats: InternalStats::default(),
+            worker_stats: ProcessingStats::new(),
+            reservoir: None,
+        }
+    }
+
+    #[test]
+    fn test_ordered_sink_flushes_in_order_prefix_on_cancel() {
+        let (sender, receiver) = unbounded();
+        let (_ctrl_tx, ctrl_rx) = unbounded();
+        let cancel = CancelToken::new();
+
+        // Batch 1 arrives before 0 and is held back; batch 3 is still
+        // waiting for 2 when the run is cancelled mid-stream
+        sender.send(batch(1, &["b", "c"])).unwrap();
+        sender.send(batch(0, &["a"])).unwrap();
+        sender.send(batch(3, &["e"])).unwrap();
+
+        let sink_cancel = cancel.clone();
+        let sink = crate::run_scope::spawn(move || {
+            let mut output = Vec::new();
+            pipeline_result_sink_thread(
+                receiver,
+                true,
+                GlobalTracker::new(),
+                &mut output,
+                &KeloraConfig::default(),
+                None,
+                ctrl_rx,
+                sink_cancel,
+            )
+            .unwrap();
+            output
+        });
+
+        // Let the sink write what it has before cancelling
+        while !sender.is_empty() {
+            std::thread::yield_now();
+        }
+        cancel.cancel();
+        sender.send(batch(2, &["d"])).unwrap();
+        drop(sender);
+
+        let output = String::from_utf8(sink.join().unwrap()).unwrap();
+        assert_eq!(output, "a\nb\nc\n");
+    }
+}
@@ -88,6 +88,22 @@ pub(crate) fn deserialize_hll(bytes: &[u8]) -> Option<HyperLogLog> {
     serde_json::from_slice(&bytes[4..]).ok()
 }
 
+/// Merge one tracking map into another by each key's recorded operation
+/// (`__op_{key}` in `ops`), the way worker deltas are merged. Used to fold
+/// closed `--every` windows into the run totals.
+pub(crate) fn merge_tracking_state(
+    target: &mut HashMap<String, Dynamic>,
+    source: &HashMap<String, Dynamic>,
+    ops: &HashMap<String, Dynamic>,
+) {
+    GlobalTracker::merge_state_with_lookup(
+        target,
+        source,
+        |op_key| ops.get(op_key).cloned(),
+        false,
+    );
+}
+
 /// Thread-safe statistics tracker for merging worker states
 #[derive(Debug, Default, Clone)]
 pub struct GlobalTracker {
@@ -149,6 +165,7 @@ impl GlobalTracker {
         global_stats.lines_errors += worker_stats.lines_errors;
         global_stats.errors += worker_stats.errors;
         global_stats.assertion_failures += worker_stats.assertion_failures;
+        global_stats.schema_failures += worker_stats.schema_failures;
         // Merge per-expression assertion failures
         for (expr, count) in &worker_stats.assertion_failures_by_expr {
             *global_stats
@@ -166,6 +183,8 @@ impl GlobalTracker {
         global_stats.naive_timestamps += worker_stats.naive_timestamps;
         global_stats.csv_rows_extra_columns += worker_stats.csv_rows_extra_columns;
         global_stats.csv_rows_missing_columns += worker_stats.csv_rows_missing_columns;
+        global_stats.events_level_filtered += worker_stats.events_level_filtered;
+        global_stats.events_with_dup_keys += worker_stats.events_with_dup_keys;
         global_stats.csv_overflow_start_column = match (
             global_stats.csv_overflow_start_column,
             worker_stats.csv_overflow_start_column,
@@ -198,12 +217,23 @@ impl GlobalTracker {
             entry.detected += worker_field_stats.detected;
             entry.parsed += worker_field_stats.parsed;
         }
+        global_stats.events_parsed_primary += worker_stats.events_parsed_primary;
+        global_stats.events_parsed_fallback += worker_stats.events_parsed_fallback;
         for (name, count) in &worker_stats.cascade_format_counts {
             *global_stats
                 .cascade_format_counts
                 .entry(name.clone())
                 .or_insert(0) += count;
         }
+        for (stage, count) in &worker_stats.stage_errors {
+            *global_stats.stage_errors.entry(stage.clone()).or_insert(0) += count;
+        }
+        for (field, types) in &worker_stats.field_types {
+            let global_types = global_stats.field_types.entry(field.clone()).or_default();
+            for (type_name, count) in types {
+                *global_types.entry(type_name.clone()).or_insert(0) += count;
+            }
+        }
         // Calculate total processing time from global start time
         if let Some(start_time) = self.start_time {
             global_stats.processing_time = start_time.elapsed();
@@ -211,6 +241,14 @@ impl GlobalTracker {
         Ok(())
     }
 
+    /// Record the inputs the run skipped by `--filename-ts-format`; `list`
+    /// names them in the text stats.
+    pub fn set_files_skipped_by_name(&self, files: Vec<String>, list: bool) {
+        let mut stats = self.lock_stats();
+        stats.files_skipped_by_name = files;
+        stats.list_skipped_files = list;
+    }
+
     pub fn merge_internal_stats(&self, internal_stats: &InternalStats) -> Result<()> {
         let mut stats = self.lock_stats();
         stats.lines_output += internal_stats.lines_output as usize;
@@ -254,7 +292,17 @@ impl GlobalTracker {
         // pattern as decode warnings) to keep the structural-failure exit code
         // correct in parallel mode.
         stats.files_failed_to_open = crate::stats::files_failed_to_open_count();
+        stats.files_processed = crate::stats::files_processed_count();
         stats.failed_file_samples = crate::stats::failed_file_samples_snapshot();
+        // Input bytes and --input-cmd stderr lines are counted by the reader
+        // threads in the run's counters. CPU/RSS are sampled here once, and
+        // each worker records its utilization when it exits.
+        (stats.input_bytes_raw, stats.input_bytes) = crate::stats::input_byte_counts();
+        stats.input_cmd_stderr_lines = crate::stats::input_cmd_stderr_line_count();
+        stats.compressed_files = crate::stats::compressed_file_bytes();
+        stats.trailing_garbage = crate::stats::trailing_garbage_snapshot();
+        stats.resource_usage = crate::platform::resource_usage();
+        stats.worker_utilization = crate::stats::worker_utilization_snapshot();
         stats
     }
 
@@ -270,6 +318,13 @@ impl GlobalTracker {
         Ok(())
     }
 
+    pub fn add_line_filter_drops(&self, drops: super::types::LineFilterDrops) -> Result<()> {
+        let mut global_stats = self.lock_stats();
+        global_stats.lines_dropped_keep += drops.keep;
+        global_stats.lines_dropped_ignore += drops.ignore;
+        Ok(())
+    }
+
     pub fn merge_worker_state(
         &self,
         user_state: HashMap<String, Dynamic>,
@@ -545,6 +600,37 @@ impl GlobalTracker {
         Some(Dynamic::from(merged))
     }
 
+    /// Merge per-category error samples, alternating between the two sides so
+    /// every worker stays represented, up to the configured sample cap.
+    fn merge_error_samples(existing: &Dynamic, value: &Dynamic) -> Option<Dynamic> {
+        let existing_arr = existing.clone().into_array().ok()?;
+        let new_arr = value.clone().into_array().ok()?;
+        let cap = crate::rhai_functions::tracking::error_sample_cap();
+
+        let identity = |sample: &Dynamic| {
+            sample
+                .read_lock::<rhai::Map>()
+                .and_then(|map| map.get("template").map(|t| t.to_string()))
+                .unwrap_or_else(|| sample.to_string())
+        };
+        let mut merged = rhai::Array::with_capacity(cap);
+        let mut seen = std::collections::HashSet::new();
+        let mut left = existing_arr.into_iter();
+        let mut right = new_arr.into_iter();
+        loop {
+            let (a, b) = (left.next(), right.next());
+            if a.is_none() && b.is_none() {
+                break;
+            }
+            for sample in [a, b].into_iter().flatten() {
+                if merged.len() < cap && seen.insert(identity(&sample)) {
+                    merged.push(sample);
+                }
+            }
+        }
+        Some(Dynamic::from(merged))
+    }
+
     /// Merge error_examples arrays (limit to 3 unique items)
     fn merge_error_examples(existing: &Dynamic, value: &Dynamic) -> Option<Dynamic> {
         let existing_arr = existing.clone().into_array().ok()?;
@@ -576,6 +662,26 @@ impl GlobalTracker {
         Some(Dynamic::from_blob(bytes))
     }
 
+    /// Standard t-digest merge: pool both digests' centroids, then compress.
+    /// Workers compressed with the script's setting, so keep at least as many
+    /// centroids as the larger input holds.
+    fn merge_percentile_approx(existing: &Dynamic, value: &Dynamic) -> Option<Dynamic> {
+        let existing_digest = deserialize_tdigest(&existing.clone().into_blob().ok()?)?;
+        let new_digest = deserialize_tdigest(&value.clone().into_blob().ok()?)?;
+
+        let compression = existing_digest
+            .centroids()
+            .len()
+            .max(new_digest.centroids().len())
+            .max(crate::rhai_functions::tracking::DEFAULT_TDIGEST_COMPRESSION);
+        let merged_digest = crate::rhai_functions::tracking::compress_tdigest(
+            existing_digest.merge(&new_digest),
+            compression,
+        );
+
+        Some(Dynamic::from_blob(serialize_tdigest(&merged_digest)))
+    }
+
     fn merge_cardinality(existing: &Dynamic, value: &Dynamic) -> Option<Dynamic> {
         let existing_blob = existing.clone().into_blob().ok()?;
         let new_blob = value.clone().into_blob().ok()?;
@@ -685,6 +791,12 @@ impl GlobalTracker {
                             continue;
                         }
                     }
+                    "error_samples" => {
+                        if let Some(merged) = Self::merge_error_samples(existing, value) {
+                            target.insert(key.clone(), merged);
+                            continue;
+                        }
+                    }
                     "error_examples" => {
                         if let Some(merged) = Self::merge_error_examples(existing, value) {
                             target.insert(key.clone(), merged);
@@ -697,6 +809,12 @@ impl GlobalTracker {
                             continue;
                         }
                     }
+                    "percentile_approx" => {
+                        if let Some(merged) = Self::merge_percentile_approx(existing, value) {
+                            target.insert(key.clone(), merged);
+                            continue;
+                        }
+                    }
                     "cardinality" => {
                         if let Some(merged) = Self::merge_cardinality(existing, value) {
                             target.insert(key.clone(), merged);
@@ -923,6 +1041,48 @@ mod tests {
         assert_eq!(global.get("max_latency").unwrap().as_int().unwrap(), 90);
     }
 
+    #[test]
+    fn test_merge_worker_state_error_samples_capped_across_workers() {
+        let tracker = GlobalTracker::new();
+        let samples = |lines: &[i64]| {
+            let arr: rhai::Array = lines
+                .iter()
+                .map(|line| {
+                    let mut sample = rhai::Map::new();
+                    sample.insert("line_num".into(), Dynamic::from(*line));
+                    Dynamic::from(sample)
+                })
+                .collect();
+            Dynamic::from(arr)
+        };
+
+        for lines in [&[1, 2, 3][..], &[101, 102, 103][..]] {
+            let mut internal = HashMap::new();
+            internal.insert("__kelora_error_samples_parse".to_string(), samples(lines));
+            internal.insert(
+                "__op___kelora_error_samples_parse".to_string(),
+                make_op("error_samples"),
+            );
+            tracker
+                .merge_worker_state(HashMap::new(), internal)
+                .unwrap();
+        }
+
+        let global = tracker.internal_tracked.lock().unwrap();
+        let merged = global
+            .get("__kelora_error_samples_parse")
+            .unwrap()
+            .clone()
+            .into_array()
+            .unwrap();
+        let lines: Vec<i64> = merged
+            .into_iter()
+            .map(|s| s.cast::<rhai::Map>()["line_num"].as_int().unwrap())
+            .collect();
+        // Capped at the default three, with both workers represented.
+        assert_eq!(lines, vec![1, 101, 2]);
+    }
+
     #[test]
     fn test_merge_worker_state_unique_operation() {
         let tracker = GlobalTracker::new();
@@ -13,6 +13,13 @@ use crate::pipeline::InternalStats;
 use crate::rhai_functions::file_ops::FileOp;
 use crate::stats::ProcessingStats;
 
+/// Per-filter counts of lines dropped by --keep-lines / --ignore-lines
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct LineFilterDrops {
+    pub keep: usize,
+    pub ignore: usize,
+}
+
 /// Context for processing plain lines (stdin or single file)
 pub(crate) struct PlainLineContext<'a> {
     pub batch_sender: &'a Sender<Batch>,
@@ -24,12 +31,14 @@ pub(crate) struct PlainLineContext<'a> {
     pub line_num: &'a mut usize,
     pub skipped_lines_count: &'a mut usize,
     pub filtered_lines: &'a mut usize,
+    pub line_filter_drops: &'a mut LineFilterDrops,
     pub skip_lines: usize,
     pub head_lines: Option<usize>,
     pub section_selector: &'a mut Option<crate::pipeline::SectionSelector>,
     pub input_format: &'a crate::config::InputFormat,
-    pub ignore_lines: &'a Option<regex::Regex>,
-    pub keep_lines: &'a Option<regex::Regex>,
+    pub keep_blank_lines: bool,
+    pub ignore_lines: &'a Option<regex::RegexSet>,
+    pub keep_lines: &'a Option<regex::RegexSet>,
     pub pending_deadline: &'a mut Option<Instant>,
     /// True while a quoted CSV/TSV field is open across physical lines, so the
     /// batcher can defer a size-based cut until the record is complete.
@@ -48,13 +57,15 @@ pub(crate) struct FileAwareLineContext<'a> {
     pub line_num: &'a mut usize,
     pub skipped_lines_count: &'a mut usize,
     pub filtered_lines: &'a mut usize,
+    pub line_filter_drops: &'a mut LineFilterDrops,
     pub skip_lines: usize,
     pub head_lines: Option<usize>,
     pub section_selector: &'a mut Option<crate::pipeline::SectionSelector>,
     pub input_format: &'a crate::config::InputFormat,
+    pub keep_blank_lines: bool,
     pub strict: bool,
-    pub ignore_lines: &'a Option<regex::Regex>,
-    pub keep_lines: &'a Option<regex::Regex>,
+    pub ignore_lines: &'a Option<regex::RegexSet>,
+    pub keep_lines: &'a Option<regex::RegexSet>,
     pub pending_deadline: &'a mut Option<Instant>,
     pub current_headers: &'a mut Option<Vec<String>>,
     pub current_type_map: &'a mut Option<TypeMap>,
@@ -71,12 +82,13 @@ pub(crate) struct BatcherThreadConfig {
     pub batch_size: usize,
     pub batch_timeout: Duration,
     pub global_tracker: super::tracker::GlobalTracker,
-    pub ignore_lines: Option<regex::Regex>,
-    pub keep_lines: Option<regex::Regex>,
+    pub ignore_lines: Option<regex::RegexSet>,
+    pub keep_lines: Option<regex::RegexSet>,
     pub skip_lines: usize,
     pub head_lines: Option<usize>,
     pub section_config: Option<crate::config::SectionConfig>,
     pub input_format: crate::config::InputFormat,
+    pub keep_blank_lines: bool,
     pub preprocessing_line_count: usize,
 }
 
@@ -154,6 +166,17 @@ pub struct BatchResult {
     pub internal_tracked_updates: HashMap<String, Dynamic>,
     pub internal_stats: InternalStats,
     pub worker_stats: ProcessingStats,
+    /// The worker's partial --reservoir sample, only on its final flush
+    pub reservoir: Option<ReservoirPartial>,
+}
+
+/// A worker's share of a --reservoir sample, merged by the sink
+#[derive(Debug)]
+pub struct ReservoirPartial {
+    /// Events this worker offered to its reservoir
+    pub seen: usize,
+    /// Sampled events, formatted, with their input line numbers
+    pub sample: Vec<(usize, ProcessedEvent)>,
 }
 
 /// An event that has been processed and is ready for output
@@ -14,7 +14,7 @@ use crate::platform::Ctrl;
 use crate::rhai_functions::tracking;
 use crate::stats::{get_thread_stats, stats_finish_processing, stats_start_timer};
 
-use super::types::{Batch, BatchResult, EventBatch, ProcessedEvent, WorkMessage};
+use super::types::{Batch, BatchResult, EventBatch, ProcessedEvent, ReservoirPartial, WorkMessage};
 
 fn processing_stats_delta(
     before: &crate::stats::ProcessingStats,
@@ -26,6 +26,7 @@ fn processing_stats_delta(
         assertion_failures: after
             .assertion_failures
             .saturating_sub(before.assertion_failures),
+        schema_failures: after.schema_failures.saturating_sub(before.schema_failures),
         files_processed: after.files_processed.saturating_sub(before.files_processed),
         script_executions: after
             .script_executions
@@ -51,11 +52,23 @@ fn processing_stats_delta(
         csv_rows_missing_columns: after
             .csv_rows_missing_columns
             .saturating_sub(before.csv_rows_missing_columns),
+        events_level_filtered: after
+            .events_level_filtered
+            .saturating_sub(before.events_level_filtered),
+        events_with_dup_keys: after
+            .events_with_dup_keys
+            .saturating_sub(before.events_with_dup_keys),
         csv_overflow_start_column: after.csv_overflow_start_column,
         timestamp_override_failed: after.timestamp_override_failed,
         timestamp_override_field: after.timestamp_override_field.clone(),
         timestamp_override_format: after.timestamp_override_format.clone(),
         timestamp_override_warning: after.timestamp_override_warning.clone(),
+        events_parsed_primary: after
+            .events_parsed_primary
+            .saturating_sub(before.events_parsed_primary),
+        events_parsed_fallback: after
+            .events_parsed_fallback
+            .saturating_sub(before.events_parsed_fallback),
         ..Default::default()
     };
 
@@ -99,6 +112,32 @@ fn processing_stats_delta(
         }
     }
 
+    for (stage, count) in &after.stage_errors {
+        let before_count = before.stage_errors.get(stage).copied().unwrap_or(0);
+        let delta_count = count.saturating_sub(before_count);
+        if delta_count > 0 {
+            delta.stage_errors.insert(stage.clone(), delta_count);
+        }
+    }
+
+    for (field, types) in &after.field_types {
+        let before_types = before.field_types.get(field);
+        for (type_name, count) in types {
+            let before_count = before_types
+                .and_then(|t| t.get(type_name))
+                .copied()
+                .unwrap_or(0);
+            let delta_count = count.saturating_sub(before_count);
+            if delta_count > 0 {
+                delta
+                    .field_types
+                    .entry(field.clone())
+                    .or_default()
+                    .insert(type_name.clone(), delta_count);
+            }
+        }
+    }
+
     delta
 }
 
@@ -107,6 +146,9 @@ fn processing_stats_is_empty(stats: &crate::stats::ProcessingStats) -> bool {
         && stats.errors == 0
         && stats.assertion_failures == 0
         && stats.assertion_failures_by_expr.is_empty()
+        && stats.stage_errors.is_empty()
+        && stats.field_types.is_empty()
+        && stats.schema_failures == 0
         && stats.files_processed == 0
         && stats.script_executions == 0
         && stats.timestamp_detected_events == 0
@@ -119,7 +161,11 @@ fn processing_stats_is_empty(stats: &crate::stats::ProcessingStats) -> bool {
         && stats.timestamp_override_warning.is_none()
         && stats.yearless_timestamps == 0
         && stats.naive_timestamps == 0
+        && stats.events_level_filtered == 0
+        && stats.events_with_dup_keys == 0
         && stats.cascade_format_counts.is_empty()
+        && stats.events_parsed_primary == 0
+        && stats.events_parsed_fallback == 0
 }
 
 fn internal_stats_is_empty(stats: &pipeline::InternalStats) -> bool {
@@ -136,7 +182,7 @@ fn internal_stats_is_empty(stats: &pipeline::InternalStats) -> bool {
 
 /// Worker thread: processes batches in parallel
 pub(crate) fn worker_thread(
-    _worker_id: usize,
+    worker_id: usize,
     work_receiver: Receiver<WorkMessage>,
     result_sender: Sender<BatchResult>,
     pipeline_builder: PipelineBuilder,
@@ -148,8 +194,12 @@ pub(crate) fn worker_thread(
     // Fresh per-run gate-success flags for this worker thread (defensive: workers
     // are spawned per run, but a reused thread must not carry a stale flag).
     crate::rhai_functions::tracking::reset_stage_success_flags();
+    crate::rhai_functions::process::reset_exit_state();
 
     stats_start_timer();
+    // Utilization is timed per batch, never per event
+    let worker_started = Instant::now();
+    let mut busy_time = Duration::ZERO;
 
     let (mut pipeline, mut ctx) = pipeline_builder.clone().build_worker(stages.clone())?;
 
@@ -187,6 +237,7 @@ pub(crate) fn worker_thread(
                 recv(work_receiver) -> msg => {
                     match msg {
                         Ok(work_msg) => {
+                            let batch_started = Instant::now();
                             let continue_processing = match work_msg {
                                 WorkMessage::LineBatch(batch) => {
                                     worker_process_batch(
@@ -213,6 +264,7 @@ pub(crate) fn worker_thread(
                                     )?
                                 }
                             };
+                            busy_time += batch_started.elapsed();
                             if !continue_processing {
                                 break 'worker_loop;
                             }
@@ -245,6 +297,7 @@ pub(crate) fn worker_thread(
                 recv(work_receiver) -> msg => {
                     match msg {
                         Ok(work_msg) => {
+                            let batch_started = Instant::now();
                             let continue_processing = match work_msg {
                                 WorkMessage::LineBatch(batch) => {
                                     worker_process_batch(
@@ -271,6 +324,7 @@ pub(crate) fn worker_thread(
                                     )?
                                 }
                             };
+                            busy_time += batch_started.elapsed();
                             if !continue_processing {
                                 break 'worker_loop;
                             }
@@ -287,6 +341,7 @@ pub(crate) fn worker_thread(
     }
 
     stats_finish_processing();
+    crate::stats::stats_record_worker_utilization(worker_id, busy_time, worker_started.elapsed());
 
     Ok(())
 }
@@ -322,6 +377,26 @@ fn handle_worker_ctrl(
 }
 
 /// Flush the worker pipeline and send any pending results
+/// Wrap flushed pipeline output (no source event) for the sink
+fn flushed_output(formatted: pipeline::FormattedOutput) -> ProcessedEvent {
+    let pipeline::FormattedOutput {
+        line,
+        timestamp,
+        file_ops,
+    } = formatted;
+    let mut dummy_event = Event::default_with_line(line);
+    dummy_event.set_metadata(0, None);
+
+    ProcessedEvent {
+        event: dummy_event,
+        captured_prints: Vec::new(),
+        captured_eprints: Vec::new(),
+        captured_messages: Vec::new(),
+        timestamp,
+        file_ops,
+    }
+}
+
 fn worker_flush_pipeline(
     pipeline: &mut pipeline::Pipeline,
     ctx: &mut pipeline::PipelineContext,
@@ -340,25 +415,23 @@ fn worker_flush_pipeline(
                 }
             }
 
-            let mut flush_batch_results = Vec::with_capacity(flush_results.len());
-            for formatted_result in flush_results {
-                let pipeline::FormattedOutput {
-                    line,
-                    timestamp,
-                    file_ops,
-                } = formatted_result;
-                let mut dummy_event = Event::default_with_line(line);
-                dummy_event.set_metadata(0, None);
-
-                flush_batch_results.push(ProcessedEvent {
-                    event: dummy_event,
-                    captured_prints: Vec::new(),
-                    captured_eprints: Vec::new(),
-                    captured_messages: Vec::new(),
-                    timestamp,
-                    file_ops,
-                });
-            }
+            let flush_batch_results: Vec<ProcessedEvent> =
+                flush_results.into_iter().map(flushed_output).collect();
+
+            // The partial --reservoir sample is complete only once input ends
+            let reservoir = if final_flush {
+                pipeline
+                    .take_reservoir_partial()
+                    .map(|(seen, sample)| ReservoirPartial {
+                        seen,
+                        sample: sample
+                            .into_iter()
+                            .map(|(line_num, formatted)| (line_num, flushed_output(formatted)))
+                            .collect(),
+                    })
+            } else {
+                None
+            };
 
             let flush_internal_stats = std::mem::take(&mut ctx.internal_stats);
             let flush_worker_stats =
@@ -381,6 +454,7 @@ fn worker_flush_pipeline(
             }
 
             if flush_batch_results.is_empty()
+                && reservoir.is_none()
                 && internal_stats_is_empty(&flush_internal_stats)
                 && processing_stats_is_empty(&flush_worker_stats)
                 && flush_internal_updates.is_empty()
@@ -420,6 +494,7 @@ fn worker_flush_pipeline(
                 internal_tracked_updates: flush_internal_updates,
                 internal_stats: flush_internal_stats,
                 worker_stats: flush_worker_stats,
+                reservoir,
             };
 
             let _ = result_sender.send(flush_batch_result);
@@ -480,7 +555,7 @@ fn worker_process_batch(
 
     let mut batch_results = Vec::with_capacity(batch.lines.len());
 
-    for (line_idx, line) in batch.lines.iter().enumerate() {
+    for (line_idx, line) in batch.lines.into_iter().enumerate() {
         let current_line_num = batch.start_line_num + line_idx;
         ctx.meta.line_num = Some(current_line_num);
         ctx.meta.filename = batch.filenames.get(line_idx).cloned().flatten();
@@ -488,7 +563,7 @@ fn worker_process_batch(
         crate::rhai_functions::strings::clear_captured_prints();
         crate::rhai_functions::strings::clear_captured_eprints();
 
-        match pipeline.process_line(line.clone(), ctx) {
+        match pipeline.process_line(line, ctx) {
             Ok(formatted_results) => {
                 if !formatted_results.is_empty() {
                     ctx.internal_stats.lines_output += 1;
@@ -557,9 +632,10 @@ fn worker_process_batch(
             }
         }
 
-        if crate::rhai_functions::process::is_exit_requested() {
-            let exit_code = crate::rhai_functions::process::get_exit_code();
-            std::process::exit(exit_code);
+        // exit() in any worker ends the run: stop at this event and hand on
+        // what the batch produced before it
+        if ctx.rhai.exit_requested() {
+            break;
         }
     }
 
@@ -586,6 +662,7 @@ fn worker_process_batch(
         internal_tracked_updates: internal_deltas,
         internal_stats: std::mem::take(&mut ctx.internal_stats),
         worker_stats: processing_stats_delta(&before_worker_stats, &get_thread_stats()),
+        reservoir: None,
     };
 
     if result_sender.send(batch_result).is_err() {
@@ -597,7 +674,7 @@ fn worker_process_batch(
     ctx.tracker.clear();
     tracking::set_thread_tracking_state(&HashMap::new());
 
-    Ok(true)
+    Ok(!ctx.rhai.exit_requested())
 }
 
 /// Process a batch of pre-chunked events (for multiline processing)
@@ -717,9 +794,10 @@ fn worker_process_event_batch(
             }
         }
 
-        if crate::rhai_functions::process::is_exit_requested() {
-            let exit_code = crate::rhai_functions::process::get_exit_code();
-            std::process::exit(exit_code);
+        // exit() in any worker ends the run: stop at this event and hand on
+        // what the batch produced before it
+        if ctx.rhai.exit_requested() {
+            break;
         }
     }
 
@@ -749,6 +827,7 @@ fn worker_process_event_batch(
         internal_tracked_updates: internal_deltas,
         internal_stats: std::mem::take(&mut ctx.internal_stats),
         worker_stats: processing_stats_delta(&before_worker_stats, &get_thread_stats()),
+        reservoir: None,
     };
 
     if result_sender.send(batch_result).is_err() {
@@ -760,7 +839,7 @@ fn worker_process_event_batch(
     ctx.tracker.clear();
     tracking::set_thread_tracking_state(&HashMap::new());
 
-    Ok(true)
+    Ok(!ctx.rhai.exit_requested())
 }
 
 /// Chunker thread: converts line batches to event batches for multiline processing
@@ -801,7 +880,7 @@ pub(crate) fn chunker_thread(
         let mut event_filenames = Vec::new();
 
         // Process each line through chunker
-        for (line_idx, line) in batch.lines.iter().enumerate() {
+        for (line_idx, line) in batch.lines.into_iter().enumerate() {
             let line_filename = batch.filenames.get(line_idx).cloned().flatten();
 
             if pending_event_filename.is_none() || !chunker.has_pending() {
@@ -809,7 +888,7 @@ pub(crate) fn chunker_thread(
             }
 
             // Feed line to chunker and collect complete events
-            if let Some(chunk) = chunker.feed_line(line.clone()) {
+            if let Some(chunk) = chunker.feed_line(line) {
                 let event_filename = pending_event_filename
                     .take()
                     .unwrap_or_else(|| line_filename.clone());
@@ -817,6 +896,12 @@ pub(crate) fn chunker_thread(
                 events.push(chunk);
                 event_filenames.push(event_filename);
 
+                // Records cut from the rest of this line came from its file
+                while let Some(chunk) = chunker.take_ready() {
+                    events.push(chunk);
+                    event_filenames.push(line_filename.clone());
+                }
+
                 // Current line becomes the first line of the next buffered event
                 pending_event_filename = Some(line_filename.clone());
             }
@@ -844,16 +929,21 @@ pub(crate) fn chunker_thread(
         }
     }
 
-    // Flush any remaining buffered event after input closes or shutdown
+    // Flush any remaining buffered events after input closes or shutdown
     if chunker.has_pending() {
-        if let Some(chunk) = chunker.flush() {
+        let mut events = Vec::new();
+        while let Some(chunk) = chunker.flush() {
+            events.push(chunk);
+        }
+        if !events.is_empty() {
             let flushed_filename = pending_event_filename.take().unwrap_or(None);
+            let filenames = vec![flushed_filename; events.len()];
 
             let event_batch = EventBatch {
                 id: next_event_batch_id,
-                events: vec![chunk],
+                events,
                 start_line_num: last_start_line_num,
-                filenames: vec![flushed_filename],
+                filenames,
                 csv_headers: last_csv_headers,
                 csv_type_map: last_csv_type_map,
             };
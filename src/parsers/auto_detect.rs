@@ -1,5 +1,7 @@
 use crate::config::InputFormat as ConfigInputFormat;
-use crate::parsers::{CefParser, CombinedParser, LogfmtParser, SyslogParser};
+use crate::parsers::{
+    CefParser, CombinedParser, HaproxyParser, LeefParser, LogfmtParser, SyslogParser,
+};
 use crate::pipeline::EventParser;
 use anyhow::Result;
 
@@ -9,12 +11,15 @@ use anyhow::Result;
 /// Format detection priority:
 /// 1. JSON - starts with '{' and valid JSON
 /// 2. CEF - starts with "CEF:"
-/// 3. Syslog - matches RFC5424 or RFC3164 patterns
-/// 4. Combined - contains common Apache/Nginx log patterns
-/// 5. Logfmt - contains key=value pairs
-/// 6. CSV/TSV - contains delimiters with reasonable structure
-/// 7. Named application-log formats adapted from lnav (regex-based)
-/// 8. Line - fallback for everything else
+/// 3. LEEF - starts with "LEEF:"
+/// 4. HAProxy - HTTP/TCP traffic logs, checked before syslog since they are
+///    usually syslog-wrapped
+/// 5. Syslog - matches RFC5424 or RFC3164 patterns
+/// 6. Combined - contains common Apache/Nginx log patterns
+/// 7. Logfmt - contains key=value pairs
+/// 8. CSV/TSV - contains delimiters with reasonable structure
+/// 9. Named application-log formats adapted from lnav (regex-based)
+/// 10. Line - fallback for everything else
 pub fn detect_format(sample_line: &str) -> Result<ConfigInputFormat> {
     let trimmed = sample_line.trim();
 
@@ -33,17 +38,28 @@ pub fn detect_format(sample_line: &str) -> Result<ConfigInputFormat> {
         return Ok(ConfigInputFormat::Cef);
     }
 
-    // 3. Syslog detection - structured patterns
+    // 3. LEEF detection - the QRadar counterpart of CEF, same kind of prefix
+    if detect_leef(trimmed) {
+        return Ok(ConfigInputFormat::Leef);
+    }
+
+    // 4. HAProxy detection - the `haproxy[pid]: client:port [accept_date]` shape
+    //    is a syslog line too, so it has to be claimed before the syslog step
+    if detect_haproxy(trimmed) {
+        return Ok(ConfigInputFormat::Haproxy);
+    }
+
+    // 5. Syslog detection - structured patterns
     if detect_syslog(trimmed) {
         return Ok(ConfigInputFormat::Syslog);
     }
 
-    // 4. Combined log format detection (Apache/Nginx)
+    // 6. Combined log format detection (Apache/Nginx)
     if detect_combined_logs(trimmed) {
         return Ok(ConfigInputFormat::Combined);
     }
 
-    // 5. Kubernetes CRI / containerd container log: `<RFC3339Nano> <stream> <F|P> msg`.
+    // 7. Kubernetes CRI / containerd container log: `<RFC3339Nano> <stream> <F|P> msg`.
     //    This prefix is highly specific, but the message after it is frequently
     //    JSON or logfmt, so it must be claimed *before* the logfmt and CSV steps
     //    (a JSON message's commas would otherwise trip CSV; key=value pairs would
@@ -54,17 +70,17 @@ pub fn detect_format(sample_line: &str) -> Result<ConfigInputFormat> {
         return Ok(ConfigInputFormat::Named(fmt));
     }
 
-    // 6. Logfmt detection - key=value patterns
+    // 8. Logfmt detection - key=value patterns
     if detect_logfmt(trimmed) {
         return Ok(ConfigInputFormat::Logfmt);
     }
 
-    // 7. CSV/TSV detection
+    // 9. CSV/TSV detection
     if let Some(csv_format) = detect_csv_variants(trimmed) {
         return Ok(csv_format);
     }
 
-    // 8. Built-in named application-log formats adapted from lnav.
+    // 10. Built-in named application-log formats adapted from lnav.
     //    Tried last (just before the line fallback) so it can only reclassify
     //    input that would otherwise become `line` — never a format already
     //    detected above. Returns the named format (regex-backed) so the notice
@@ -73,10 +89,44 @@ pub fn detect_format(sample_line: &str) -> Result<ConfigInputFormat> {
         return Ok(ConfigInputFormat::Named(fmt));
     }
 
-    // 9. Fallback to line format
+    // 11. Fallback to line format
     Ok(ConfigInputFormat::Line)
 }
 
+/// Detect the format of a multi-line sample (`--detect-lines`).
+///
+/// Banner and comment lines are skipped, every other line is detected on its
+/// own, and the format most lines agree on wins (the earliest on a tie). Falls
+/// back to the first line's format when no line matches anything but `line`.
+pub fn detect_format_from_sample(lines: &[&str]) -> Result<ConfigInputFormat> {
+    let mut votes: Vec<(ConfigInputFormat, usize)> = Vec::new();
+    for line in lines.iter().filter(|line| !is_banner_line(line)) {
+        let format = detect_format(line)?;
+        if matches!(format, ConfigInputFormat::Line) {
+            continue;
+        }
+        match votes.iter_mut().find(|(seen, _)| *seen == format) {
+            Some((_, count)) => *count += 1,
+            None => votes.push((format, 1)),
+        }
+    }
+
+    // max_by_key keeps the last maximum, so walk the votes backwards
+    match votes.into_iter().rev().max_by_key(|(_, count)| *count) {
+        Some((format, _)) => Ok(format),
+        None => detect_format(lines.first().copied().unwrap_or_default()),
+    }
+}
+
+/// Lines that introduce a log rather than belong to it: comments and rules
+fn is_banner_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    ["#", "//", "--", ";", "/*", "*"]
+        .iter()
+        .any(|prefix| trimmed.starts_with(prefix))
+        || trimmed.chars().all(|c| !c.is_alphanumeric())
+}
+
 /// Detect JSON format - starts with '{' and is valid JSON
 fn detect_json(line: &str) -> bool {
     if !line.starts_with('{') {
@@ -94,6 +144,21 @@ fn detect_cef(line: &str) -> bool {
     parser.parse(line).is_ok()
 }
 
+/// Detect LEEF format using actual parser for 100% accuracy
+fn detect_leef(line: &str) -> bool {
+    let parser = LeefParser::new_without_auto_timestamp().with_strict(true);
+    parser.parse(line).is_ok()
+}
+
+/// Detect HAProxy traffic logs using actual parser for 100% accuracy
+fn detect_haproxy(line: &str) -> bool {
+    if let Ok(parser) = HaproxyParser::new_without_auto_timestamp() {
+        parser.parse(line).is_ok()
+    } else {
+        false // Regex compilation failed (shouldn't happen)
+    }
+}
+
 /// Detect Syslog format using actual parser for 100% accuracy
 fn detect_syslog(line: &str) -> bool {
     // SyslogParser::new() compiles regexes, returns Result
@@ -198,6 +263,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_leef() {
+        assert_eq!(
+            detect_format("LEEF:1.0|Microsoft|MSExchange|4.0|15345|src=192.0.2.0\tsev=5").unwrap(),
+            ConfigInputFormat::Leef
+        );
+        assert_eq!(
+            detect_format(
+                "Jan 18 11:07:53 fw01 LEEF:2.0|Acme|Wall|1.0|deny|^|src=10.0.0.1^act=drop"
+            )
+            .unwrap(),
+            ConfigInputFormat::Leef
+        );
+    }
+
     #[test]
     fn test_detect_syslog() {
         assert_eq!(
@@ -220,6 +300,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_haproxy() {
+        assert_eq!(
+            detect_format(r#"Feb  6 12:14:14 localhost haproxy[14389]: 10.0.1.2:33317 [06/Feb/2024:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 "GET /index.html HTTP/1.1""#)
+                .unwrap(),
+            ConfigInputFormat::Haproxy
+        );
+        assert_eq!(
+            detect_format("10.0.1.2:33320 [06/Feb/2024:12:14:15.123] tcp-in mysql/db1 0/0/5007 1230 -- 1/1/1/1/0 0/0")
+                .unwrap(),
+            ConfigInputFormat::Haproxy
+        );
+        // HAProxy's own startup/health messages are plain syslog
+        assert_eq!(
+            detect_format("Feb  6 12:14:10 localhost haproxy[14389]: Proxy http-in started.")
+                .unwrap(),
+            ConfigInputFormat::Syslog
+        );
+    }
+
     #[test]
     fn test_detect_combined() {
         assert_eq!(
@@ -366,6 +466,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_format_from_sample_skips_banners() {
+        let sample = [
+            "# Export generated by collector v2.1, host=web-1",
+            "# ================================",
+            "Starting log stream",
+            r#"{"level":"info","msg":"a"}"#,
+            r#"{"level":"warn","msg":"b"}"#,
+        ];
+        assert_eq!(
+            detect_format_from_sample(&sample).unwrap(),
+            ConfigInputFormat::Json
+        );
+        assert_eq!(
+            detect_format_from_sample(&["-----", "plain text"]).unwrap(),
+            ConfigInputFormat::Line
+        );
+    }
+
+    #[test]
+    fn test_detect_format_from_sample_majority_wins() {
+        let sample = ["a=1 b=2", r#"{"a":1}"#, "c=3 d=4", r#"{"c":3}"#];
+        assert_eq!(
+            detect_format_from_sample(&sample).unwrap(),
+            ConfigInputFormat::Logfmt
+        );
+        let sample = ["a=1 b=2", r#"{"a":1}"#, r#"{"c":3}"#];
+        assert_eq!(
+            detect_format_from_sample(&sample).unwrap(),
+            ConfigInputFormat::Json
+        );
+    }
+
     fn lower_ascii(len: std::ops::RangeInclusive<usize>) -> BoxedStrategy<String> {
         prop::collection::vec(proptest::char::range('a', 'z'), len)
             .prop_map(|chars| chars.into_iter().collect())
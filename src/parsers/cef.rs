@@ -144,8 +144,8 @@ impl CefParser {
         Ok(("", pairs))
     }
 
-    /// Convert string to appropriate Dynamic type
-    fn parse_value_to_dynamic(&self, value: String) -> Dynamic {
+    /// Convert string to appropriate Dynamic type (shared with the LEEF parser)
+    pub(crate) fn parse_value_to_dynamic(value: String) -> Dynamic {
         // Only coerce values that are syntactically valid JSON numbers, so
         // zero-padded IDs, signed values, and inf/nan stay strings.
         if looks_like_json_number(&value) {
@@ -248,7 +248,7 @@ impl EventParser for CefParser {
 
         // Set extension fields with type conversion
         for (key, value) in extension_pairs {
-            let dynamic_value = self.parse_value_to_dynamic(value);
+            let dynamic_value = Self::parse_value_to_dynamic(value);
             event.set_field(key, dynamic_value);
         }
 
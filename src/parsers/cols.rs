@@ -1,5 +1,6 @@
 use crate::event::Event;
 use crate::parsers::type_conversion::{convert_value_to_type, FieldType, TypeMap};
+use crate::parsers::REST_FIELD;
 use crate::pipeline::EventParser;
 use crate::rhai_functions::columns::{
     parse_cols_whitespace, parse_cols_with_sep, set_parse_cols_strict,
@@ -12,6 +13,7 @@ pub struct ColsParser {
     separator: Option<String>,
     type_map: TypeMap,
     strict: bool,
+    keep_rest: bool,
 }
 
 impl ColsParser {
@@ -21,6 +23,7 @@ impl ColsParser {
             separator,
             type_map: TypeMap::new(),
             strict: false,
+            keep_rest: false,
         }
     }
 
@@ -96,12 +99,27 @@ impl ColsParser {
         self.strict = strict;
         self
     }
+
+    /// Keep columns beyond the named ones in a `_rest` field (verbatim, with
+    /// surrounding whitespace trimmed). No-op when the spec has a `*field`.
+    pub fn with_keep_rest(mut self, keep_rest: bool) -> Self {
+        self.keep_rest = keep_rest;
+        self
+    }
 }
 
 impl EventParser for ColsParser {
     fn parse(&self, line: &str) -> Result<Event> {
         // Extract type annotations from spec and get clean spec
-        let (clean_spec, extracted_types) = Self::extract_type_annotations(&self.spec);
+        let (mut clean_spec, extracted_types) = Self::extract_type_annotations(&self.spec);
+
+        // Collect leftover columns through an implicit rest token, which keeps
+        // the original separators between them
+        let collect_rest =
+            self.keep_rest && !clean_spec.split_whitespace().any(|t| t.starts_with('*'));
+        if collect_rest {
+            clean_spec.push_str(&format!(" *{}", REST_FIELD));
+        }
 
         // Merge extracted types with explicitly set type_map (explicit takes precedence)
         let mut combined_types = extracted_types;
@@ -148,6 +166,17 @@ impl EventParser for ColsParser {
 
                     event.fields.insert(key.to_string(), converted_value);
                 }
+                if collect_rest {
+                    let rest = event
+                        .fields
+                        .shift_remove(REST_FIELD)
+                        .and_then(|value| value.into_string().ok())
+                        .map(|value| value.trim().to_string())
+                        .filter(|value| !value.is_empty());
+                    if let Some(rest) = rest {
+                        event.fields.insert(REST_FIELD.to_string(), rest.into());
+                    }
+                }
                 Ok(event)
             }
             Err(err) => {
@@ -328,4 +357,70 @@ mod tests {
             "hello|world"
         );
     }
+
+    fn rest_of(event: &Event) -> Option<String> {
+        event
+            .fields
+            .get(REST_FIELD)
+            .map(|value| value.clone().into_string().unwrap())
+    }
+
+    #[test]
+    fn test_cols_keep_rest_whitespace_columns() {
+        let parser = ColsParser::new("ts level".to_string(), None).with_keep_rest(true);
+        let result = parser.parse("  12:00 INFO  cache   warmed up \t").unwrap();
+
+        assert_eq!(
+            result
+                .fields
+                .get("level")
+                .unwrap()
+                .clone()
+                .into_string()
+                .unwrap(),
+            "INFO"
+        );
+        // Original spacing between leftover columns is kept, the edges trimmed
+        assert_eq!(rest_of(&result).as_deref(), Some("cache   warmed up"));
+    }
+
+    #[test]
+    fn test_cols_keep_rest_custom_separator() {
+        let parser =
+            ColsParser::new("a - b".to_string(), Some("|".to_string())).with_keep_rest(true);
+        let result = parser.parse("1|skip|2| x |y ").unwrap();
+
+        assert_eq!(
+            result
+                .fields
+                .get("b")
+                .unwrap()
+                .clone()
+                .into_string()
+                .unwrap(),
+            "2"
+        );
+        assert_eq!(rest_of(&result).as_deref(), Some("x |y"));
+    }
+
+    #[test]
+    fn test_cols_keep_rest_omitted_when_nothing_left() {
+        let parser = ColsParser::new("a b".to_string(), None).with_keep_rest(true);
+        assert_eq!(rest_of(&parser.parse("1 2   ").unwrap()), None);
+
+        // An explicit *field already claims the remainder
+        let parser = ColsParser::new("a *msg".to_string(), None).with_keep_rest(true);
+        let result = parser.parse("1 two three").unwrap();
+        assert_eq!(rest_of(&result), None);
+        assert_eq!(
+            result
+                .fields
+                .get("msg")
+                .unwrap()
+                .clone()
+                .into_string()
+                .unwrap(),
+            "two three"
+        );
+    }
 }
@@ -0,0 +1,123 @@
+use crate::config::DupKeyPolicy;
+use crate::event::FieldMap;
+use rhai::Dynamic;
+
+/// Applies the `--dup-keys` policy while a parser fills one event's fields.
+///
+/// Create one per event. The first duplicate seen is counted in stats
+/// (`events_with_dup_keys`), whatever the policy does with it.
+pub struct DupKeyInserter {
+    policy: DupKeyPolicy,
+    /// Keys already turned into arrays by `Collect`, so a value that was an
+    /// array to begin with is wrapped rather than appended to
+    collected: Vec<String>,
+    seen_duplicate: bool,
+}
+
+impl DupKeyInserter {
+    pub fn new(policy: DupKeyPolicy) -> Self {
+        Self {
+            policy,
+            collected: Vec::new(),
+            seen_duplicate: false,
+        }
+    }
+
+    /// Insert `key`, resolving a repeat per the policy. Only `Error` fails.
+    pub fn insert(
+        &mut self,
+        fields: &mut FieldMap,
+        key: String,
+        value: Dynamic,
+    ) -> Result<(), String> {
+        let Some(existing) = fields.get_mut(&key) else {
+            fields.insert(key, value);
+            return Ok(());
+        };
+
+        if !self.seen_duplicate {
+            self.seen_duplicate = true;
+            crate::stats::stats_add_dup_key_event();
+        }
+
+        match self.policy {
+            DupKeyPolicy::First => {}
+            DupKeyPolicy::Last => *existing = value,
+            DupKeyPolicy::Collect => {
+                if self.collected.contains(&key) {
+                    let mut values = std::mem::take(existing).into_array().unwrap_or_default();
+                    values.push(value);
+                    *existing = Dynamic::from(values);
+                } else {
+                    let first = std::mem::take(existing);
+                    *existing = Dynamic::from(vec![first, value]);
+                    self.collected.push(key);
+                }
+            }
+            DupKeyPolicy::Error => {
+                return Err(format!("duplicate key '{}' (--dup-keys error)", key));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(policy: DupKeyPolicy, pairs: &[(&str, Dynamic)]) -> Result<FieldMap, String> {
+        let mut fields = FieldMap::default();
+        let mut inserter = DupKeyInserter::new(policy);
+        for (key, value) in pairs {
+            inserter.insert(&mut fields, key.to_string(), value.clone())?;
+        }
+        Ok(fields)
+    }
+
+    #[test]
+    fn test_dup_key_policies() {
+        let pairs = [
+            ("key", Dynamic::from(1_i64)),
+            ("other", Dynamic::from("x")),
+            ("key", Dynamic::from(2_i64)),
+            ("key", Dynamic::from(3_i64)),
+        ];
+
+        let first = fill(DupKeyPolicy::First, &pairs).unwrap();
+        assert_eq!(first.get("key").unwrap().as_int().unwrap(), 1);
+
+        let last = fill(DupKeyPolicy::Last, &pairs).unwrap();
+        assert_eq!(last.get("key").unwrap().as_int().unwrap(), 3);
+        // Replacing keeps the key where it first appeared
+        assert_eq!(last.get_index_of("key"), Some(0));
+
+        let collect = fill(DupKeyPolicy::Collect, &pairs).unwrap();
+        let values: Vec<i64> = collect
+            .get("key")
+            .unwrap()
+            .clone()
+            .into_array()
+            .unwrap()
+            .into_iter()
+            .map(|v| v.as_int().unwrap())
+            .collect();
+        assert_eq!(values, vec![1, 2, 3]);
+
+        let err = fill(DupKeyPolicy::Error, &pairs).unwrap_err();
+        assert!(err.contains("duplicate key 'key'"), "{err}");
+    }
+
+    #[test]
+    fn test_collect_wraps_array_values() {
+        let array = Dynamic::from(vec![Dynamic::from(1_i64)]);
+        let fields = fill(
+            DupKeyPolicy::Collect,
+            &[("tags", array.clone()), ("tags", array)],
+        )
+        .unwrap();
+        let values = fields.get("tags").unwrap().clone().into_array().unwrap();
+        assert_eq!(values.len(), 2);
+        assert!(values[0].is_array());
+    }
+}
@@ -0,0 +1,78 @@
+//! Fallback parser for `--fallback-format`: lines the input format rejects are
+//! retried with a second parser instead of becoming parse errors.
+//!
+//! Unlike a cascade, the primary parser's events are left as they are; only
+//! events produced by the fallback carry the `_fallback` marker field.
+
+use crate::event::Event;
+use crate::pipeline::EventParser;
+use anyhow::Result;
+use rhai::Dynamic;
+
+/// Field set to `true` on events produced by the fallback parser.
+pub const FALLBACK_FIELD: &str = "_fallback";
+
+pub struct FallbackParser {
+    primary: Box<dyn EventParser>,
+    fallback: Box<dyn EventParser>,
+}
+
+impl FallbackParser {
+    pub fn new(primary: Box<dyn EventParser>, fallback: Box<dyn EventParser>) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+impl EventParser for FallbackParser {
+    fn parse(&self, line: &str) -> Result<Event> {
+        let primary_err = match self.primary.parse(line) {
+            Ok(event) => {
+                crate::stats::stats_add_fallback_parse(false);
+                return Ok(event);
+            }
+            Err(e) => e,
+        };
+        match self.fallback.parse(line) {
+            Ok(mut event) => {
+                event.set_field(FALLBACK_FIELD.to_string(), Dynamic::from(true));
+                crate::stats::stats_add_fallback_parse(true);
+                Ok(event)
+            }
+            // The primary format's error says more about what the line should
+            // have looked like
+            Err(_) => Err(primary_err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{JsonlParser, LineParser, LogfmtParser};
+
+    #[test]
+    fn test_fallback_marks_only_fallback_events() {
+        let parser = FallbackParser::new(Box::new(JsonlParser::new()), Box::new(LineParser::new()));
+        let primary = parser.parse(r#"{"msg":"hi"}"#).unwrap();
+        assert!(!primary.fields.contains_key(FALLBACK_FIELD));
+
+        let fallback = parser.parse("sidecar: config reloaded").unwrap();
+        assert_eq!(
+            fallback.fields.get(FALLBACK_FIELD).unwrap().as_bool(),
+            Ok(true)
+        );
+        assert_eq!(
+            fallback.fields.get("line").unwrap().to_string(),
+            "sidecar: config reloaded"
+        );
+    }
+
+    #[test]
+    fn test_fallback_failure_keeps_primary_error() {
+        let parser =
+            FallbackParser::new(Box::new(JsonlParser::new()), Box::new(LogfmtParser::new()));
+        assert!(parser.parse("level=warn msg=slow").is_ok());
+        let err = parser.parse("{not json").unwrap_err();
+        assert!(err.to_string().to_lowercase().contains("json"), "{}", err);
+    }
+}
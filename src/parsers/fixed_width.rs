@@ -0,0 +1,247 @@
+use crate::event::Event;
+use crate::parsers::type_conversion::{convert_value_to_type, FieldType};
+use crate::pipeline::EventParser;
+use anyhow::Result;
+
+/// One column of a fixed-width spec: characters `start..end` of the line
+#[derive(Debug, Clone, PartialEq)]
+struct FixedColumn {
+    name: String,
+    field_type: Option<FieldType>,
+    start: usize,
+    /// Exclusive end; `None` runs to the end of the line
+    end: Option<usize>,
+}
+
+/// Parser for logs laid out in fixed character columns.
+///
+/// The spec is a comma-separated list of `FIELD:start:end` entries with
+/// zero-based, end-exclusive character positions (e.g.
+/// `user:0:8,pid:9:15,msg:16:`). An empty end runs to the end of the line and
+/// `FIELD:type:start:end` converts the value. Entries can also be written as
+/// ranges, `start-end:FIELD` or `start-end:FIELD:type` (e.g.
+/// `0-8:date,9-17:time,18-:message`). Values are trimmed; blank columns and
+/// columns past the end of a short line are left unset.
+pub struct FixedWidthParser {
+    columns: Vec<FixedColumn>,
+    strict: bool,
+}
+
+impl FixedWidthParser {
+    pub fn new(spec: &str) -> Result<Self> {
+        let mut columns = Vec::new();
+        for entry in spec.split(',') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            columns.push(Self::parse_column(entry)?);
+        }
+        if columns.is_empty() {
+            return Err(anyhow::anyhow!(
+                "fixed-width format requires a column spec, e.g. 'fixed-width:user:0:8,pid:9:15'"
+            ));
+        }
+        Ok(Self {
+            columns,
+            strict: false,
+        })
+    }
+
+    fn parse_column(entry: &str) -> Result<FixedColumn> {
+        let parts: Vec<&str> = entry.split(':').map(str::trim).collect();
+        let range = parts.first().and_then(|first| Self::split_range(first));
+        let (name, type_str, start, end) = match (range, parts.as_slice()) {
+            (Some((start, end)), [_, name]) => (*name, None, start, end),
+            (Some((start, end)), [_, name, type_str]) => (*name, Some(*type_str), start, end),
+            (None, [name, start, end]) => (*name, None, *start, *end),
+            (None, [name, type_str, start, end]) => (*name, Some(*type_str), *start, *end),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Invalid fixed-width column '{}': expected FIELD:start:end or start-end:FIELD",
+                    entry
+                ));
+            }
+        };
+        let field_type = match type_str {
+            Some(type_str) => Some(FieldType::from_str(type_str).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown type annotation '{}' in fixed-width column '{}'",
+                    type_str,
+                    entry
+                )
+            })?),
+            None => None,
+        };
+        if name.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Fixed-width column '{}' has an empty field name",
+                entry
+            ));
+        }
+
+        let start: usize = start.parse().map_err(|_| {
+            anyhow::anyhow!(
+                "Invalid start position '{}' in fixed-width column '{}'",
+                start,
+                entry
+            )
+        })?;
+        let end = if end.is_empty() {
+            None
+        } else {
+            let end: usize = end.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid end position '{}' in fixed-width column '{}'",
+                    end,
+                    entry
+                )
+            })?;
+            if end <= start {
+                return Err(anyhow::anyhow!(
+                    "Fixed-width column '{}' must end after it starts",
+                    entry
+                ));
+            }
+            Some(end)
+        };
+
+        Ok(FixedColumn {
+            name: name.to_string(),
+            field_type,
+            start,
+            end,
+        })
+    }
+
+    /// Split a `start-end` range (end optional) into its two positions
+    fn split_range(part: &str) -> Option<(&str, &str)> {
+        let (start, end) = part.split_once('-')?;
+        let is_position = |text: &str| text.bytes().all(|b| b.is_ascii_digit());
+        (!start.is_empty() && is_position(start) && is_position(end)).then_some((start, end))
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+}
+
+impl EventParser for FixedWidthParser {
+    fn parse(&self, line: &str) -> Result<Event> {
+        // Byte offset of every character boundary, so columns count characters
+        let offsets: Vec<usize> = line
+            .char_indices()
+            .map(|(idx, _)| idx)
+            .chain(std::iter::once(line.len()))
+            .collect();
+        let char_count = offsets.len() - 1;
+
+        let mut event = Event::default_with_line(line.to_string());
+        for column in &self.columns {
+            if column.start >= char_count {
+                if self.strict {
+                    return Err(anyhow::anyhow!(
+                        "Line is {} characters; fixed-width column '{}' starts at {}",
+                        char_count,
+                        column.name,
+                        column.start
+                    ));
+                }
+                continue;
+            }
+            let end = column.end.map_or(char_count, |end| end.min(char_count));
+            let value = line[offsets[column.start]..offsets[end]].trim();
+            if value.is_empty() {
+                continue;
+            }
+
+            let value = match column.field_type {
+                Some(ref field_type) => convert_value_to_type(value, field_type, self.strict)
+                    .map_err(|e| {
+                        anyhow::anyhow!(
+                            "Type conversion error for field '{}' (value: '{}'): {}",
+                            column.name,
+                            value,
+                            e
+                        )
+                    })?,
+                None => rhai::Dynamic::from(value.to_string()),
+            };
+            event.fields.insert(column.name.clone(), value);
+        }
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_width_extracts_trimmed_columns() {
+        let parser = FixedWidthParser::new("date:0:10,level:11:16,pid:int:17:22,msg:23:").unwrap();
+        let event = parser
+            .parse("2024-01-15 WARN  4121  disk almost full")
+            .unwrap();
+        assert_eq!(event.fields.get("date").unwrap().to_string(), "2024-01-15");
+        assert_eq!(event.fields.get("level").unwrap().to_string(), "WARN");
+        assert_eq!(event.fields.get("pid").unwrap().as_int().unwrap(), 4121);
+        assert_eq!(
+            event.fields.get("msg").unwrap().to_string(),
+            "disk almost full"
+        );
+    }
+
+    #[test]
+    fn test_fixed_width_counts_characters_not_bytes() {
+        let parser = FixedWidthParser::new("city:0:6,temp:7:").unwrap();
+        let event = parser.parse("Zürich 21C").unwrap();
+        assert_eq!(event.fields.get("city").unwrap().to_string(), "Zürich");
+        assert_eq!(event.fields.get("temp").unwrap().to_string(), "21C");
+    }
+
+    #[test]
+    fn test_fixed_width_short_lines() {
+        let parser = FixedWidthParser::new("a:0:3,b:4:8").unwrap();
+        let event = parser.parse("abc").unwrap();
+        assert_eq!(event.fields.get("a").unwrap().to_string(), "abc");
+        assert!(!event.fields.contains_key("b"));
+
+        assert!(parser.with_strict(true).parse("abc").is_err());
+    }
+
+    #[test]
+    fn test_fixed_width_range_spec() {
+        let parser = FixedWidthParser::new("0-8:date,9-17:time,18-21:pid:int,22-:message").unwrap();
+        let event = parser
+            .parse("20240115 10:30:00 042 job   finished  ")
+            .unwrap();
+        assert_eq!(event.fields.get("date").unwrap().to_string(), "20240115");
+        assert_eq!(event.fields.get("time").unwrap().to_string(), "10:30:00");
+        assert_eq!(event.fields.get("pid").unwrap().as_int().unwrap(), 42);
+        assert_eq!(
+            event.fields.get("message").unwrap().to_string(),
+            "job   finished"
+        );
+    }
+
+    #[test]
+    fn test_fixed_width_rejects_bad_specs() {
+        for spec in [
+            "",
+            "a:0",
+            "a:x:4",
+            "a:4:2",
+            ":0:4",
+            "a:number:0:4",
+            "4-2:a",
+            "0-4:",
+            "-4:a",
+            "0-4:a:number",
+        ] {
+            assert!(FixedWidthParser::new(spec).is_err(), "accepted {spec:?}");
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use crate::event::Event;
+use crate::pipeline::EventParser;
+use anyhow::{Context, Result};
+use regex::{Captures, Regex};
+use rhai::Dynamic;
+
+/// Optional syslog wrapper HAProxy adds when logging to a syslog socket:
+/// `[<PRI>]Mmm DD HH:MM:SS host proc[pid]: `. Lines written with
+/// `log stdout format raw` start directly at the client address.
+const SYSLOG_PREFIX: &str =
+    r"^(?:(?:<\d{1,3}>)?\w{3} +\d{1,2} \d{2}:\d{2}:\d{2} (?P<host>\S+) [\w.-]+\[(?P<pid>\d+)\]: )?";
+
+/// Shared `client:port [accept_date] frontend backend/server` head. A trailing
+/// `~` on the frontend marks an SSL listener and is not part of its name.
+const CONNECTION_HEAD: &str = r"(?P<client_ip>\S+):(?P<client_port>\d+) \[(?P<accept_date>[^\]]+)\] (?P<frontend>\S+?)~? (?P<backend>[^ /]+)/(?P<server>\S+) ";
+
+/// Shared `actconn/feconn/beconn/srv_conn/retries srv_queue/backend_queue` tail.
+/// `retries` is prefixed with `+` when the connection was redispatched.
+const CONNECTION_COUNTS: &str = r"(?P<actconn>\d+)/(?P<feconn>\d+)/(?P<beconn>\d+)/(?P<srv_conn>\d+)/(?P<retries>\+?\d+) (?P<srv_queue>\d+)/(?P<backend_queue>\d+)";
+
+const INTEGER_FIELDS: &[&str] = &[
+    "pid",
+    "client_port",
+    "Tq",
+    "Tw",
+    "Tc",
+    "Tr",
+    "Tt",
+    "status_code",
+    "bytes_read",
+    "actconn",
+    "feconn",
+    "beconn",
+    "srv_conn",
+    "retries",
+    "srv_queue",
+    "backend_queue",
+];
+
+const STRING_FIELDS: &[&str] = &[
+    "host",
+    "client_ip",
+    "accept_date",
+    "frontend",
+    "backend",
+    "server",
+    "request_cookie",
+    "response_cookie",
+    "termination_state",
+    "request_headers",
+    "response_headers",
+];
+
+/// Parser for HAProxy traffic logs in the default HTTP (`option httplog`) and
+/// TCP (`option tcplog`) layouts, with or without the syslog wrapper.
+///
+/// Timer and counter fields use HAProxy's own names (`Tq`, `Tw`, `Tc`, `Tr`,
+/// `Tt`); `-1` marks a phase that never completed and `+` (logasap) prefixes
+/// are dropped. `accept_date` is also copied to `ts`, since it carries the year
+/// and milliseconds the syslog header lacks.
+pub struct HaproxyParser {
+    http_regex: Regex,
+    tcp_regex: Regex,
+    auto_timestamp: bool,
+}
+
+impl HaproxyParser {
+    fn build(auto_timestamp: bool) -> Result<Self> {
+        // HTTP log format
+        // Example: 10.0.1.2:33317 [06/Feb/2024:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 "GET /index.html HTTP/1.1"
+        let http_regex = Regex::new(&format!(
+            r#"{SYSLOG_PREFIX}{CONNECTION_HEAD}(?P<Tq>-?\d+)/(?P<Tw>-?\d+)/(?P<Tc>-?\d+)/(?P<Tr>-?\d+)/(?P<Tt>\+?-?\d+) (?P<status_code>-1|\d{{3}}) (?P<bytes_read>\+?\d+) (?P<request_cookie>\S+) (?P<response_cookie>\S+) (?P<termination_state>\S{{4}}) {CONNECTION_COUNTS}(?: \{{(?P<request_headers>[^}}]*)\}})?(?: \{{(?P<response_headers>[^}}]*)\}})? "(?P<request>[^"]*)"?\s*$"#
+        ))
+        .context("Failed to compile HAProxy HTTP log regex")?;
+
+        // TCP log format
+        // Example: 10.0.1.2:33320 [06/Feb/2024:12:14:15.123] tcp-in mysql/db1 0/0/5007 1230 -- 1/1/1/1/0 0/0
+        let tcp_regex = Regex::new(&format!(
+            r#"{SYSLOG_PREFIX}{CONNECTION_HEAD}(?P<Tw>-?\d+)/(?P<Tc>-?\d+)/(?P<Tt>\+?-?\d+) (?P<bytes_read>\+?\d+) (?P<termination_state>\S{{2}}) {CONNECTION_COUNTS}\s*$"#
+        ))
+        .context("Failed to compile HAProxy TCP log regex")?;
+
+        Ok(Self {
+            http_regex,
+            tcp_regex,
+            auto_timestamp,
+        })
+    }
+
+    pub fn new() -> Result<Self> {
+        Self::build(true)
+    }
+
+    pub fn new_without_auto_timestamp() -> Result<Self> {
+        Self::build(false)
+    }
+
+    fn event_from_captures(&self, line: &str, captures: &Captures) -> Event {
+        let mut event = Event::with_capacity(line.to_string(), 24);
+
+        for name in STRING_FIELDS {
+            if let Some(value) = captures.name(name) {
+                // Captured cookies and headers log "-" when nothing was captured
+                if value.as_str() != "-" {
+                    event.set_field(name.to_string(), Dynamic::from(value.as_str().to_string()));
+                }
+            }
+        }
+
+        for name in INTEGER_FIELDS {
+            if let Some(value) = captures.name(name) {
+                if let Ok(num) = value.as_str().trim_start_matches('+').parse::<i64>() {
+                    event.set_field(name.to_string(), Dynamic::from(num));
+                }
+            }
+        }
+
+        if let Some(request) = captures.name("request") {
+            let request = request.as_str();
+            event.set_field("request".to_string(), Dynamic::from(request.to_string()));
+            let parts: Vec<&str> = request.splitn(3, ' ').collect();
+            // "<BADREQ>" and friends are a single token; only split real requests
+            if parts.len() >= 2 {
+                event.set_field("method".to_string(), Dynamic::from(parts[0].to_string()));
+                event.set_field("path".to_string(), Dynamic::from(parts[1].to_string()));
+                if let Some(protocol) = parts.get(2) {
+                    event.set_field("protocol".to_string(), Dynamic::from(protocol.to_string()));
+                }
+            }
+        }
+
+        if let Some(accept_date) = captures.name("accept_date") {
+            event.set_field(
+                "ts".to_string(),
+                Dynamic::from(accept_date.as_str().to_string()),
+            );
+        }
+
+        if self.auto_timestamp {
+            event.extract_timestamp();
+        }
+        event
+    }
+}
+
+impl EventParser for HaproxyParser {
+    fn parse(&self, line: &str) -> Result<Event> {
+        let line = line.trim_end_matches(['\n', '\r']);
+        if let Some(captures) = self.http_regex.captures(line) {
+            Ok(self.event_from_captures(line, &captures))
+        } else if let Some(captures) = self.tcp_regex.captures(line) {
+            Ok(self.event_from_captures(line, &captures))
+        } else {
+            Err(anyhow::anyhow!("Invalid HAProxy log format"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string_field(event: &Event, name: &str) -> String {
+        event
+            .fields
+            .get(name)
+            .unwrap()
+            .clone()
+            .into_string()
+            .unwrap()
+    }
+
+    fn int_field(event: &Event, name: &str) -> i64 {
+        event.fields.get(name).unwrap().as_int().unwrap()
+    }
+
+    #[test]
+    fn test_http_log_with_syslog_prefix() {
+        let parser = HaproxyParser::new().unwrap();
+        let line = r#"Feb  6 12:14:14 localhost haproxy[14389]: 10.0.1.2:33317 [06/Feb/2024:12:14:14.655] http-in~ static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 "GET /index.html HTTP/1.1""#;
+        let event = parser.parse(line).unwrap();
+
+        assert_eq!(string_field(&event, "host"), "localhost");
+        assert_eq!(int_field(&event, "pid"), 14389);
+        assert_eq!(string_field(&event, "client_ip"), "10.0.1.2");
+        assert_eq!(int_field(&event, "client_port"), 33317);
+        assert_eq!(
+            string_field(&event, "accept_date"),
+            "06/Feb/2024:12:14:14.655"
+        );
+        assert_eq!(string_field(&event, "frontend"), "http-in");
+        assert_eq!(string_field(&event, "backend"), "static");
+        assert_eq!(string_field(&event, "server"), "srv1");
+        assert_eq!(int_field(&event, "Tq"), 10);
+        assert_eq!(int_field(&event, "Tr"), 69);
+        assert_eq!(int_field(&event, "Tt"), 109);
+        assert_eq!(int_field(&event, "status_code"), 200);
+        assert_eq!(int_field(&event, "bytes_read"), 2750);
+        assert_eq!(string_field(&event, "termination_state"), "----");
+        assert_eq!(string_field(&event, "method"), "GET");
+        assert_eq!(string_field(&event, "path"), "/index.html");
+        assert_eq!(string_field(&event, "protocol"), "HTTP/1.1");
+        assert!(event.fields.get("request_cookie").is_none());
+        assert!(event.parsed_ts.is_some());
+    }
+
+    #[test]
+    fn test_http_log_raw_with_headers_and_aborted_timers() {
+        let parser = HaproxyParser::new().unwrap();
+        let line = r#"192.168.1.20:51288 [09/Mar/2024:08:01:02.003] fe_main be_app/<NOSRV> 0/-1/-1/-1/+3 503 +212 - - SC-- 2/2/0/0/+1 0/0 {api.example.com} {} "POST /v1/orders HTTP/1.1""#;
+        let event = parser.parse(line).unwrap();
+
+        assert!(event.fields.get("host").is_none());
+        assert_eq!(string_field(&event, "server"), "<NOSRV>");
+        assert_eq!(int_field(&event, "Tw"), -1);
+        assert_eq!(int_field(&event, "Tt"), 3);
+        assert_eq!(int_field(&event, "status_code"), 503);
+        assert_eq!(int_field(&event, "bytes_read"), 212);
+        assert_eq!(int_field(&event, "retries"), 1);
+        assert_eq!(string_field(&event, "request_headers"), "api.example.com");
+        assert_eq!(string_field(&event, "response_headers"), "");
+        assert_eq!(string_field(&event, "path"), "/v1/orders");
+    }
+
+    #[test]
+    fn test_tcp_log() {
+        let parser = HaproxyParser::new().unwrap();
+        let line = "<134>Feb  6 12:14:15 lb01 haproxy[14389]: 10.0.1.2:33320 [06/Feb/2024:12:14:15.123] tcp-in mysql/db1 0/0/5007 1230 -- 1/1/1/1/0 0/0";
+        let event = parser.parse(line).unwrap();
+
+        assert_eq!(string_field(&event, "host"), "lb01");
+        assert_eq!(string_field(&event, "frontend"), "tcp-in");
+        assert_eq!(string_field(&event, "backend"), "mysql");
+        assert_eq!(int_field(&event, "Tc"), 0);
+        assert_eq!(int_field(&event, "Tt"), 5007);
+        assert_eq!(int_field(&event, "bytes_read"), 1230);
+        assert_eq!(string_field(&event, "termination_state"), "--");
+        assert!(event.fields.get("status_code").is_none());
+        assert!(event.fields.get("Tq").is_none());
+    }
+
+    #[test]
+    fn test_rejects_other_formats() {
+        let parser = HaproxyParser::new().unwrap();
+        assert!(parser
+            .parse("Jan 15 10:30:45 server1 sshd[1234]: Accepted publickey for user")
+            .is_err());
+        assert!(parser
+            .parse(r#"192.168.1.1 - - [25/Dec/1995:10:00:00 +0000] "GET / HTTP/1.0" 200 1234"#)
+            .is_err());
+    }
+}
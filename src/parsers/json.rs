@@ -1,8 +1,13 @@
+use crate::config::DupKeyPolicy;
 use crate::event::{Event, FieldMap};
+use crate::parsers::DupKeyInserter;
 use crate::pipeline::EventParser;
+use crate::rhai_functions::json_number::{f64_loses_precision, JsonNumber};
 use anyhow::Result;
 use rhai::Dynamic;
 use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::fmt;
 
 /// Tidy up a `serde_json` parse error for display. Each input line is parsed
@@ -16,12 +21,14 @@ fn clean_json_error(e: &serde_json::Error) -> String {
 
 /// A `rhai::Dynamic` deserialized directly from JSON, skipping the
 /// `serde_json::Value` intermediate tree. Number/nesting semantics mirror
-/// [`crate::event::json_to_dynamic_owned`] exactly.
-struct DynamicValue(Dynamic);
+/// [`crate::event::json_to_dynamic_owned`] exactly, except that decimals with
+/// more digits than an f64 holds become a [`JsonNumber`] too. With `NUM_STR`
+/// set every number is kept as its decimal text (`--json-numbers-as-strings`).
+struct DynamicValue<const NUM_STR: bool>(Dynamic);
 
-struct DynVisitor;
+struct DynVisitor<const NUM_STR: bool>;
 
-impl<'de> Visitor<'de> for DynVisitor {
+impl<'de, const NUM_STR: bool> Visitor<'de> for DynVisitor<NUM_STR> {
     type Value = Dynamic;
 
     fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -33,20 +40,34 @@ impl<'de> Visitor<'de> for DynVisitor {
     }
 
     fn visit_i64<E>(self, v: i64) -> Result<Dynamic, E> {
+        if NUM_STR {
+            return Ok(Dynamic::from(v.to_string()));
+        }
         Ok(Dynamic::from(v))
     }
 
     fn visit_u64<E>(self, v: u64) -> Result<Dynamic, E> {
-        // Match json_to_dynamic_owned: prefer i64, fall back to u64 to avoid precision loss.
+        if NUM_STR {
+            return Ok(Dynamic::from(v.to_string()));
+        }
+        // Match json_to_dynamic_owned: ints when they fit i64, exact text beyond
         Ok(if v <= i64::MAX as u64 {
             Dynamic::from(v as i64)
         } else {
-            Dynamic::from(v)
+            Dynamic::from(JsonNumber::new(v.to_string()))
         })
     }
 
     fn visit_f64<E>(self, v: f64) -> Result<Dynamic, E> {
-        Ok(Dynamic::from(v))
+        // Source text, when the line was scanned and the f64 lost digits
+        let precise = next_precise_number().filter(|text| f64_loses_precision(text, v));
+        Ok(match (precise, NUM_STR) {
+            (Some(text), true) => Dynamic::from(text),
+            (Some(text), false) => Dynamic::from(JsonNumber::new(text)),
+            // Shortest text that round-trips
+            (None, true) => Dynamic::from(v.to_string()),
+            (None, false) => Dynamic::from(v),
+        })
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Dynamic, E> {
@@ -77,7 +98,7 @@ impl<'de> Visitor<'de> for DynVisitor {
         A: SeqAccess<'de>,
     {
         let mut arr = rhai::Array::with_capacity(seq.size_hint().unwrap_or(0));
-        while let Some(DynamicValue(v)) = seq.next_element()? {
+        while let Some(DynamicValue::<NUM_STR>(v)) = seq.next_element()? {
             arr.push(v);
         }
         Ok(Dynamic::from(arr))
@@ -89,29 +110,124 @@ impl<'de> Visitor<'de> for DynVisitor {
     {
         let mut m = rhai::Map::new();
         while let Some(k) = map.next_key::<String>()? {
-            let DynamicValue(v) = map.next_value()?;
+            let DynamicValue::<NUM_STR>(v) = map.next_value()?;
             m.insert(k.into(), v);
         }
         Ok(Dynamic::from(m))
     }
 }
 
-impl<'de> Deserialize<'de> for DynamicValue {
+impl<'de, const NUM_STR: bool> Deserialize<'de> for DynamicValue<NUM_STR> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_any(DynVisitor).map(DynamicValue)
+        deserializer
+            .deserialize_any(DynVisitor::<NUM_STR>)
+            .map(DynamicValue)
+    }
+}
+
+thread_local! {
+    /// Source text of the numbers serde_json hands the visitor as f64, in
+    /// document order, for the line being parsed. Only filled for lines that
+    /// may hold numbers an f64 can't represent exactly.
+    static PRECISE_NUMBERS: RefCell<VecDeque<String>> = const { RefCell::new(VecDeque::new()) };
+}
+
+fn next_precise_number() -> Option<String> {
+    PRECISE_NUMBERS.with(|numbers| numbers.borrow_mut().pop_front())
+}
+
+/// Whether `line` has a run of 16 or more digits, the least that can exceed
+/// f64 precision. Cheap enough to run on every line.
+fn may_hold_precise_numbers(line: &str) -> bool {
+    let mut run = 0;
+    for b in line.bytes() {
+        match b {
+            b'0'..=b'9' => {
+                run += 1;
+                if run >= 16 {
+                    return true;
+                }
+            }
+            b'.' => {}
+            _ => run = 0,
+        }
     }
+    false
+}
+
+/// Number tokens outside strings that serde_json parses as f64: decimals,
+/// exponents, and integers beyond the u64 (or, negative, i64) range
+fn f64_number_tokens(line: &str) -> VecDeque<String> {
+    let bytes = line.as_bytes();
+    let mut tokens = VecDeque::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+            i += 1;
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'-' | b'0'..=b'9' => {
+                let start = i;
+                while i < bytes.len()
+                    && matches!(bytes[i], b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+                {
+                    i += 1;
+                }
+                let token = &line[start..i];
+                let is_float = if token.contains(['.', 'e', 'E']) {
+                    true
+                } else if token.starts_with('-') {
+                    token.parse::<i64>().is_err()
+                } else {
+                    token.parse::<u64>().is_err()
+                };
+                if is_float {
+                    tokens.push_back(token.to_string());
+                }
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    tokens
+}
+
+/// Run a parse of `line` with its f64 number tokens available to the visitor
+fn with_precise_numbers<T>(line: &str, parse: impl FnOnce() -> T) -> T {
+    if !may_hold_precise_numbers(line) {
+        return parse();
+    }
+    PRECISE_NUMBERS.with(|numbers| *numbers.borrow_mut() = f64_number_tokens(line));
+    let result = parse();
+    PRECISE_NUMBERS.with(|numbers| numbers.borrow_mut().clear());
+    result
 }
 
 /// Top-level object: deserialized straight into a `FieldMap`, avoiding both the
 /// `serde_json::Value::Object` indexmap and a second pass to build our map.
-struct EventFields(FieldMap);
-
-struct FieldMapVisitor;
+/// Keys go through the `--dup-keys` policy as they arrive, so a repeated key
+/// is seen rather than silently overwritten.
+struct FieldMapVisitor<const NUM_STR: bool> {
+    dup_keys: DupKeyPolicy,
+}
 
-impl<'de> Visitor<'de> for FieldMapVisitor {
+impl<'de, const NUM_STR: bool> Visitor<'de> for FieldMapVisitor<NUM_STR> {
     type Value = FieldMap;
 
     fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -126,28 +242,46 @@ impl<'de> Visitor<'de> for FieldMapVisitor {
             map.size_hint().unwrap_or(0),
             ahash::RandomState::default(),
         );
+        let mut inserter = DupKeyInserter::new(self.dup_keys);
         while let Some(k) = map.next_key::<String>()? {
-            let DynamicValue(v) = map.next_value()?;
-            fields.insert(k, v);
+            let DynamicValue::<NUM_STR>(v) = map.next_value()?;
+            inserter
+                .insert(&mut fields, k, v)
+                .map_err(serde::de::Error::custom)?;
         }
         Ok(fields)
     }
 }
 
-impl<'de> Deserialize<'de> for EventFields {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        deserializer
-            .deserialize_map(FieldMapVisitor)
-            .map(EventFields)
-    }
+/// Deserialize any JSON value (used for non-object lines)
+fn parse_value<const NUM_STR: bool>(line: &str) -> serde_json::Result<Dynamic> {
+    with_precise_numbers(line, || {
+        serde_json::from_str::<DynamicValue<NUM_STR>>(line).map(|DynamicValue(value)| value)
+    })
+}
+
+/// Field holding a top-level array or scalar line, unless `--strict-json`
+pub const WRAPPED_VALUE_FIELD: &str = "value";
+
+/// Deserialize one JSON object line into a `FieldMap`
+fn parse_fields<const NUM_STR: bool>(
+    line: &str,
+    dup_keys: DupKeyPolicy,
+) -> serde_json::Result<FieldMap> {
+    with_precise_numbers(line, || {
+        let mut deserializer = serde_json::Deserializer::from_str(line);
+        let fields = deserializer.deserialize_map(FieldMapVisitor::<NUM_STR> { dup_keys })?;
+        deserializer.end()?;
+        Ok(fields)
+    })
 }
 
 pub struct JsonlParser {
     auto_timestamp: bool,
     strict: bool,
+    numbers_as_strings: bool,
+    dup_keys: DupKeyPolicy,
+    objects_only: bool,
 }
 
 impl JsonlParser {
@@ -155,6 +289,9 @@ impl JsonlParser {
         Self {
             auto_timestamp: true,
             strict: false,
+            numbers_as_strings: false,
+            dup_keys: DupKeyPolicy::Last,
+            objects_only: false,
         }
     }
 
@@ -162,6 +299,9 @@ impl JsonlParser {
         Self {
             auto_timestamp: false,
             strict: false,
+            numbers_as_strings: false,
+            dup_keys: DupKeyPolicy::Last,
+            objects_only: false,
         }
     }
 
@@ -169,6 +309,26 @@ impl JsonlParser {
         self.strict = strict;
         self
     }
+
+    /// Keep numbers as their decimal text instead of int/float values, so
+    /// IDs are never rounded by a float conversion downstream
+    pub fn with_numbers_as_strings(mut self, numbers_as_strings: bool) -> Self {
+        self.numbers_as_strings = numbers_as_strings;
+        self
+    }
+
+    /// How a key repeated within one object is resolved (`--dup-keys`)
+    pub fn with_dup_keys(mut self, dup_keys: DupKeyPolicy) -> Self {
+        self.dup_keys = dup_keys;
+        self
+    }
+
+    /// Reject top-level arrays and scalars instead of wrapping them in a
+    /// `value` field (`--strict-json`)
+    pub fn with_objects_only(mut self, objects_only: bool) -> Self {
+        self.objects_only = objects_only;
+        self
+    }
 }
 
 impl EventParser for JsonlParser {
@@ -177,11 +337,14 @@ impl EventParser for JsonlParser {
 
         // Fast path: objects (the overwhelmingly common case) deserialize
         // straight into the FieldMap, skipping the serde_json::Value tree.
-        // Non-objects fall through to the slow path purely to reproduce the
-        // exact "Expected JSON object" error.
+        // Arrays and scalars take the slow path below.
         if line.trim_start().as_bytes().first() == Some(&b'{') {
-            let EventFields(fields) = serde_json::from_str(line)
-                .map_err(|e| anyhow::anyhow!("Invalid JSON: {}", clean_json_error(&e)))?;
+            let fields = if self.numbers_as_strings {
+                parse_fields::<true>(line, self.dup_keys)
+            } else {
+                parse_fields::<false>(line, self.dup_keys)
+            }
+            .map_err(|e| anyhow::anyhow!("Invalid JSON: {}", clean_json_error(&e)))?;
             let mut event = Event::with_fields(line.to_string(), fields);
             if self.auto_timestamp {
                 event.extract_timestamp();
@@ -189,9 +352,21 @@ impl EventParser for JsonlParser {
             return Ok(event);
         }
 
-        let json_value: serde_json::Value = serde_json::from_str(line)
-            .map_err(|e| anyhow::anyhow!("Invalid JSON: {}", clean_json_error(&e)))?;
-        Err(anyhow::anyhow!("Expected JSON object, got: {}", json_value))
+        if self.objects_only {
+            let json_value: serde_json::Value = serde_json::from_str(line)
+                .map_err(|e| anyhow::anyhow!("Invalid JSON: {}", clean_json_error(&e)))?;
+            return Err(anyhow::anyhow!("Expected JSON object, got: {}", json_value));
+        }
+
+        let value = if self.numbers_as_strings {
+            parse_value::<true>(line)
+        } else {
+            parse_value::<false>(line)
+        }
+        .map_err(|e| anyhow::anyhow!("Invalid JSON: {}", clean_json_error(&e)))?;
+        let mut event = Event::default_with_line(line.to_string());
+        event.set_field(WRAPPED_VALUE_FIELD.to_string(), value);
+        Ok(event)
     }
 }
 
@@ -200,6 +375,26 @@ mod tests {
     use super::*;
     use crate::pipeline::EventParser;
 
+    #[test]
+    fn test_json_parser_keeps_precise_numbers_as_text() {
+        let parser = JsonlParser::new();
+        let event = parser
+            .parse(r#"{"note":"1.0000000000000000001","id":18446744073709551616,"n":[1.5,0.30000000000000000004]}"#)
+            .unwrap();
+        assert_eq!(
+            event.fields.get("note").unwrap().to_string(),
+            "1.0000000000000000001"
+        );
+        let id = event.fields["id"].clone().try_cast::<JsonNumber>().unwrap();
+        assert_eq!(id.text, "18446744073709551616");
+        let n = event.fields["n"].clone().into_array().unwrap();
+        assert_eq!(n[0].as_float().unwrap(), 1.5);
+        assert_eq!(
+            n[1].clone().try_cast::<JsonNumber>().unwrap().text,
+            "0.30000000000000000004"
+        );
+    }
+
     #[test]
     fn test_json_parser_basic() {
         let parser = JsonlParser::new();
@@ -263,4 +458,85 @@ mod tests {
         assert!(result.fields.get("status").is_some());
         assert_eq!(result.fields.get("status").unwrap().as_int().unwrap(), 404);
     }
+
+    #[test]
+    fn test_json_parser_keeps_large_integers_exact() {
+        let line = r#"{"id":1234567890123456789,"user":{"id":9876543210987654321},"ratio":0.5}"#;
+
+        let parser = JsonlParser::new();
+        let event = EventParser::parse(&parser, line).unwrap();
+        assert_eq!(
+            event.fields.get("id").unwrap().as_int().unwrap(),
+            1234567890123456789
+        );
+
+        let parser = JsonlParser::new().with_numbers_as_strings(true);
+        let event = EventParser::parse(&parser, line).unwrap();
+        let text = |v: &Dynamic| v.clone().into_string().unwrap();
+        assert_eq!(text(event.fields.get("id").unwrap()), "1234567890123456789");
+        let user = event
+            .fields
+            .get("user")
+            .unwrap()
+            .clone()
+            .cast::<rhai::Map>();
+        assert_eq!(text(user.get("id").unwrap()), "9876543210987654321");
+        assert_eq!(text(event.fields.get("ratio").unwrap()), "0.5");
+    }
+
+    #[test]
+    fn test_json_parser_dup_keys_policy() {
+        let line = r#"{"tag":"a","n":1,"tag":"b","tag":"c"}"#;
+
+        let event = EventParser::parse(&JsonlParser::new(), line).unwrap();
+        assert_eq!(event.fields.get("tag").unwrap().to_string(), "c");
+
+        let parser = JsonlParser::new().with_dup_keys(DupKeyPolicy::First);
+        let event = EventParser::parse(&parser, line).unwrap();
+        assert_eq!(event.fields.get("tag").unwrap().to_string(), "a");
+
+        let parser = JsonlParser::new().with_dup_keys(DupKeyPolicy::Collect);
+        let event = EventParser::parse(&parser, line).unwrap();
+        let tags = event
+            .fields
+            .get("tag")
+            .unwrap()
+            .clone()
+            .into_array()
+            .unwrap();
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags[2].to_string(), "c");
+
+        let parser = JsonlParser::new().with_dup_keys(DupKeyPolicy::Error);
+        let err = EventParser::parse(&parser, line).unwrap_err().to_string();
+        assert!(err.contains("duplicate key 'tag'"), "{err}");
+    }
+
+    #[test]
+    fn test_json_parser_wraps_non_objects_unless_objects_only() {
+        let event = EventParser::parse(&JsonlParser::new(), "[1, 2, 3]").unwrap();
+        let values = event
+            .fields
+            .get(WRAPPED_VALUE_FIELD)
+            .unwrap()
+            .clone()
+            .into_array()
+            .unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(event.original_line, "[1, 2, 3]");
+
+        let event = EventParser::parse(&JsonlParser::new(), "\"ping\"").unwrap();
+        assert_eq!(
+            event.fields.get(WRAPPED_VALUE_FIELD).unwrap().to_string(),
+            "ping"
+        );
+
+        let strict = JsonlParser::new().with_objects_only(true);
+        let err = EventParser::parse(&strict, "[1, 2, 3]")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Expected JSON object"), "{err}");
+        assert!(EventParser::parse(&strict, "42").is_err());
+        assert!(EventParser::parse(&JsonlParser::new(), "[1, 2").is_err());
+    }
 }
@@ -0,0 +1,263 @@
+use crate::event::Event;
+use crate::parsers::CefParser;
+use crate::pipeline::EventParser;
+use anyhow::{anyhow, Result};
+use rhai::Dynamic;
+
+/// Parser for IBM QRadar's Log Event Extended Format (LEEF).
+///
+/// LEEF 1.0: `LEEF:1.0|Vendor|Product|Version|EventID|key=value<TAB>key=value`
+/// LEEF 2.0: `LEEF:2.0|Vendor|Product|Version|EventID|Delimiter|key=value...`,
+/// where the delimiter is a single character or its hex code (`^`, `x5E`,
+/// `0x5E`); an empty delimiter field means tab.
+pub struct LeefParser {
+    auto_timestamp: bool,
+    strict: bool,
+}
+
+impl LeefParser {
+    pub fn new() -> Self {
+        Self {
+            auto_timestamp: true,
+            strict: false,
+        }
+    }
+
+    pub fn new_without_auto_timestamp() -> Self {
+        Self {
+            auto_timestamp: false,
+            strict: false,
+        }
+    }
+
+    /// In strict mode, attributes without a `key=` part are an error instead
+    /// of being skipped.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Split the optional syslog prefix in front of `LEEF:` into timestamp
+    /// and hostname, the same way the CEF parser does.
+    fn parse_syslog_prefix(prefix: &str) -> (Option<&str>, Option<&str>) {
+        let prefix = prefix.trim();
+        let tokens: Vec<&str> = prefix.split_whitespace().collect();
+        match tokens.len() {
+            0 => (None, None),
+            1 => (None, Some(tokens[0])),
+            _ => {
+                let hostname = tokens[tokens.len() - 1];
+                let timestamp = prefix[..prefix.len() - hostname.len()].trim_end();
+                (Some(timestamp), Some(hostname))
+            }
+        }
+    }
+
+    /// Read one pipe-terminated header field, undoing CEF-style `\|` and
+    /// `\\` escapes. Returns the field and the text after its pipe, or `None`
+    /// for the rest when the input ends without one.
+    fn parse_header_field(input: &str) -> (String, Option<&str>) {
+        let mut field = String::new();
+        let mut chars = input.char_indices();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => field.push(escaped),
+                    None => field.push('\\'),
+                },
+                '|' => return (field, Some(&input[idx + 1..])),
+                _ => field.push(c),
+            }
+        }
+        (field, None)
+    }
+
+    /// Resolve the LEEF 2.0 delimiter field: a literal character or a hex
+    /// code point (`x09`, `0x5E`). Empty means tab.
+    fn parse_delimiter(spec: &str) -> Result<char> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Ok('\t');
+        }
+        let hex = spec
+            .strip_prefix("0x")
+            .or_else(|| spec.strip_prefix("0X"))
+            .or_else(|| spec.strip_prefix('x'))
+            .or_else(|| spec.strip_prefix('X'));
+        if let Some(hex) = hex.filter(|h| !h.is_empty()) {
+            return u32::from_str_radix(hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| anyhow!("Invalid LEEF delimiter '{}'", spec));
+        }
+        let mut chars = spec.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(anyhow!("Invalid LEEF delimiter '{}'", spec)),
+        }
+    }
+}
+
+impl EventParser for LeefParser {
+    fn parse(&self, line: &str) -> Result<Event> {
+        let line = line.trim_end_matches('\n').trim_end_matches('\r');
+        let line = line.trim();
+
+        let marker = line
+            .find("LEEF:")
+            .ok_or_else(|| anyhow!("Invalid LEEF header: missing 'LEEF:' marker"))?;
+        let (timestamp, hostname) = Self::parse_syslog_prefix(&line[..marker]);
+
+        let mut rest = Some(&line[marker + "LEEF:".len()..]);
+        let mut header = Vec::with_capacity(5);
+        for _ in 0..5 {
+            let Some(input) = rest else {
+                return Err(anyhow!(
+                    "Invalid LEEF header: expected Version|Vendor|Product|Version|EventID"
+                ));
+            };
+            let (field, remaining) = Self::parse_header_field(input);
+            header.push(field);
+            rest = remaining;
+        }
+
+        let leef_version = header[0].trim();
+        let delimiter = if leef_version.starts_with('1') {
+            '\t'
+        } else if leef_version.starts_with('2') {
+            let (spec, remaining) = Self::parse_header_field(rest.unwrap_or_default());
+            rest = remaining;
+            Self::parse_delimiter(&spec)?
+        } else {
+            return Err(anyhow!("Unsupported LEEF version '{}'", leef_version));
+        };
+
+        let mut attributes = Vec::new();
+        for attribute in rest.unwrap_or_default().split(delimiter) {
+            if attribute.trim().is_empty() {
+                continue;
+            }
+            match attribute.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    attributes.push((key.trim().to_string(), value.to_string()));
+                }
+                _ if self.strict => {
+                    return Err(anyhow!("Invalid LEEF attribute '{}'", attribute));
+                }
+                _ => {}
+            }
+        }
+
+        let capacity = 5
+            + attributes.len()
+            + if timestamp.is_some() { 1 } else { 0 }
+            + if hostname.is_some() { 1 } else { 0 };
+        let mut event = Event::with_capacity(line.to_string(), capacity);
+
+        if let Some(ts) = timestamp {
+            event.set_field("ts".to_string(), Dynamic::from(ts.to_string()));
+        }
+        if let Some(host) = hostname {
+            event.set_field("host".to_string(), Dynamic::from(host.to_string()));
+        }
+
+        let mut header = header.into_iter();
+        for name in ["leefver", "vendor", "product", "version", "event_id"] {
+            let value = header.next().unwrap_or_default();
+            event.set_field(name.to_string(), Dynamic::from(value));
+        }
+
+        for (key, value) in attributes {
+            event.set_field(key, CefParser::parse_value_to_dynamic(value));
+        }
+
+        if self.auto_timestamp {
+            event.extract_timestamp();
+        }
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(event: &Event, name: &str) -> String {
+        event
+            .fields
+            .get(name)
+            .unwrap_or_else(|| panic!("missing field {}", name))
+            .to_string()
+    }
+
+    #[test]
+    fn test_leef_1_tab_attributes() {
+        let parser = LeefParser::new();
+        let line = "LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=192.0.2.0\tdst=172.50.123.1\tsev=5\tcat=anomaly\tmsg=this is a message";
+        let event = parser.parse(line).unwrap();
+
+        assert_eq!(field(&event, "leefver"), "1.0");
+        assert_eq!(field(&event, "vendor"), "Microsoft");
+        assert_eq!(field(&event, "product"), "MSExchange");
+        assert_eq!(field(&event, "version"), "4.0 SP1");
+        assert_eq!(field(&event, "event_id"), "15345");
+        assert_eq!(field(&event, "src"), "192.0.2.0");
+        assert_eq!(event.fields.get("sev").unwrap().as_int().unwrap(), 5);
+        assert_eq!(field(&event, "msg"), "this is a message");
+    }
+
+    #[test]
+    fn test_leef_2_custom_and_hex_delimiters() {
+        let parser = LeefParser::new();
+        let event = parser
+            .parse("LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^proto=6")
+            .unwrap();
+        assert_eq!(field(&event, "dst"), "10.0.0.5");
+        assert_eq!(event.fields.get("proto").unwrap().as_int().unwrap(), 6);
+
+        let event = parser
+            .parse("LEEF:2.0|Lancope|StealthWatch|1.0|41|x5E|src=10.0.1.8^dst=10.0.0.5")
+            .unwrap();
+        assert_eq!(field(&event, "dst"), "10.0.0.5");
+
+        // Empty delimiter field falls back to tab
+        let event = parser
+            .parse("LEEF:2.0|Lancope|StealthWatch|1.0|41||src=10.0.1.8\tdst=10.0.0.5")
+            .unwrap();
+        assert_eq!(field(&event, "src"), "10.0.1.8");
+    }
+
+    #[test]
+    fn test_leef_escaped_pipe_and_syslog_prefix() {
+        let parser = LeefParser::new();
+        let line = r"Jan 18 11:07:53 fw01 LEEF:1.0|Acme|Fire\|Wall|2.1|deny|src=10.0.0.1";
+        let event = parser.parse(line).unwrap();
+        assert_eq!(field(&event, "ts"), "Jan 18 11:07:53");
+        assert_eq!(field(&event, "host"), "fw01");
+        assert_eq!(field(&event, "product"), "Fire|Wall");
+        assert_eq!(field(&event, "event_id"), "deny");
+    }
+
+    #[test]
+    fn test_leef_values_may_contain_equals_and_pipes() {
+        let parser = LeefParser::new();
+        let line = "LEEF:1.0|Acme|App|1|login|url=/a?b=c|d\tusrName=alice";
+        let event = parser.parse(line).unwrap();
+        assert_eq!(field(&event, "url"), "/a?b=c|d");
+        assert_eq!(field(&event, "usrName"), "alice");
+    }
+
+    #[test]
+    fn test_leef_rejects_bad_input() {
+        let parser = LeefParser::new();
+        assert!(parser.parse("CEF:0|a|b|c|d|e|f|").is_err());
+        assert!(parser.parse("LEEF:1.0|Acme|App").is_err());
+        assert!(parser.parse("LEEF:3.0|Acme|App|1|x|").is_err());
+        assert!(parser.parse("LEEF:2.0|Acme|App|1|x|ab|k=v").is_err());
+
+        let strict = LeefParser::new().with_strict(true);
+        assert!(strict.parse("LEEF:1.0|Acme|App|1|x|k=v\tjunk").is_err());
+        assert!(parser.parse("LEEF:1.0|Acme|App|1|x|k=v\tjunk").is_ok());
+    }
+}
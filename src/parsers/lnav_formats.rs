@@ -27,15 +27,15 @@
 //!   weighted scoring engine here; the first definition that matches wins, and
 //!   within a definition the first pattern that matches wins.
 //! - Multiple patterns per format exist only for sources that emit structurally
-//!   distinct layouts (e.g. AWS S3 `std`/`std-v2`, HAProxy http/tcp), which
-//!   cannot be folded into one regex because Rust's engine forbids reusing a
-//!   capture-group name across alternation branches.
+//!   distinct layouts (e.g. AWS S3 `std`/`std-v2`), which cannot be folded
+//!   into one regex because Rust's engine forbids reusing a capture-group name
+//!   across alternation branches.
 //! - Detection runs *after* every existing detector and immediately before the
 //!   `line` fallback (see [`crate::parsers::auto_detect`]). It can therefore only
 //!   reclassify input that would otherwise have become `line`, so it never
-//!   changes a format Kelora already detected. (Consequence: syslog-transported
-//!   formats like HAProxy are claimed by the syslog detector under `-f auto`;
-//!   reach them with `-f <name>`.)
+//!   changes a format Kelora already detected. (Consequence: a syslog-transported
+//!   format defined here is claimed by the syslog detector under `-f auto`; such
+//!   sources get a dedicated parser instead, like `-f haproxy`.)
 //! - A match is returned as `InputFormat::Named`, carrying the format's name and
 //!   patterns, so it can be displayed, selected via `-f <name>`, and reused
 //!   across the parser-build/timestamp/strict pipeline unchanged.
@@ -261,24 +261,6 @@ pub static LNAV_FORMATS: &[LnavFormat] = &[
             r#"79a59df900b949e55d96a1e698fbacedfd6e09d98eacf8f8d5218e7cd47ef2be mybucket [06/Feb/2024:00:01:00 +0000] 192.0.2.3 - 891CE47D2EXAMPLE REST.GET.BUCKET - "GET /?list-type=2 HTTP/1.1" 200 - 1024 - 7 6 "-" "aws-sdk-go/1.0""#,
         ],
     },
-    // HAProxy traffic logs (HTTP and TCP), as emitted through syslog:
-    // `Mmm DD HH:MM:SS host haproxy[pid]: client:port [accept] frontend backend/server ...`.
-    // NOTE: these carry a syslog timestamp, so auto-detection classifies them as
-    // `syslog` first — use `-f haproxy` explicitly to get the structured fields.
-    LnavFormat {
-        name: "haproxy",
-        patterns: &[
-            // HTTP log format
-            r#"(?P<ts>\w{3} +\d{1,2} \d{2}:\d{2}:\d{2}) (?P<host>\S+) (?P<proc>\w+)\[(?P<pid:int>\d+)\]: (?P<client_ip>[^:]+):(?P<client_port:int>\d+) \[(?P<accept_date>[^\]]+)\] (?P<frontend>\S+?)~? (?P<backend>[^ /]+)/(?P<server>\S+) (?P<tq>-?\d+)/(?P<tw>-?\d+)/(?P<tc>-?\d+)/(?P<tr>-?\d+)/(?P<tt>\d+) (?P<status>\d{3}|-1) (?P<bytes_read:int>\d+) \S+ \S+ (?P<termination_state>....) (?P<actconn:int>\d+)/(?P<feconn:int>\d+)/(?P<beconn:int>\d+)/(?P<srv_conn:int>\d+)/(?P<retries:int>\d+) (?P<srv_queue:int>\d+)/(?P<backend_queue:int>\d+)(?: \{(?P<req_headers>.*?)\} \{(?P<resp_headers>.*?)\})? "(?P<msg>[^"]*)""#,
-            // TCP log format
-            r#"(?P<ts>\w{3} +\d{1,2} \d{2}:\d{2}:\d{2}) (?P<host>\S+) (?P<proc>\w+)\[(?P<pid:int>\d+)\]: (?P<client_ip>[^:]+):(?P<client_port:int>\d+) \[(?P<accept_date>[^\]]+)\] (?P<frontend>\S+) (?P<backend>[^ /]+)/(?P<server>\S+) (?P<tw>-?\d+)/(?P<tc>-?\d+)/(?P<tt>\d+) (?P<bytes_read:int>\d+) (?P<termination_state>..) (?P<actconn:int>\d+)/(?P<feconn:int>\d+)/(?P<beconn:int>\d+)/(?P<srv_conn:int>\d+)/(?P<retries:int>\d+) (?P<srv_queue:int>\d+)/(?P<backend_queue:int>\d+)"#,
-        ],
-        ts_format: None,
-        samples: &[
-            r#"Feb 06 12:14:14 localhost haproxy[14389]: 10.0.1.2:33317 [06/Feb/2024:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 "GET /index.html HTTP/1.1""#,
-            r#"Feb 06 12:14:15 localhost haproxy[14389]: 10.0.1.2:33320 [06/Feb/2024:12:14:15.123] tcp-in mysql/db1 0/0/5007 1230 -- 1/1/1/1/0 0/0"#,
-        ],
-    },
     // Generic ISO-8601 prefixed application log (catch-all, kept last):
     // `2024-01-02T15:04:05.123Z INFO message` or `2024-01-02 15:04:05 ERROR message`,
     // with the timestamp optionally wrapped in brackets: `[2024-01-02 15:04:05] WARN message`.
@@ -537,6 +519,7 @@ mod tests {
             "logfmt",
             "syslog",
             "cef",
+            "leef",
             "csv",
             "tsv",
             "csvnh",
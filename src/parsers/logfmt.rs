@@ -1,72 +1,116 @@
+use crate::config::DupKeyPolicy;
 use crate::event::Event;
 use crate::parsers::type_conversion::looks_like_json_number;
+use crate::parsers::{DupKeyInserter, REST_FIELD};
 use crate::pipeline::EventParser;
 use anyhow::Result;
 use rhai::Dynamic;
 
 pub struct LogfmtParser {
     auto_timestamp: bool,
+    keep_rest: bool,
+    dup_keys: DupKeyPolicy,
 }
 
 impl LogfmtParser {
     pub fn new() -> Self {
         Self {
             auto_timestamp: true,
+            keep_rest: false,
+            dup_keys: DupKeyPolicy::Last,
         }
     }
 
     pub fn new_without_auto_timestamp() -> Self {
         Self {
             auto_timestamp: false,
+            keep_rest: false,
+            dup_keys: DupKeyPolicy::Last,
         }
     }
 
+    /// Collect tokens that are not `key=value` pairs into a `_rest` field
+    /// instead of rejecting the line
+    pub fn with_keep_rest(mut self, keep_rest: bool) -> Self {
+        self.keep_rest = keep_rest;
+        self
+    }
+
+    /// How a key repeated within one line is resolved (`--dup-keys`)
+    pub fn with_dup_keys(mut self, dup_keys: DupKeyPolicy) -> Self {
+        self.dup_keys = dup_keys;
+        self
+    }
+
     /// Parse logfmt line: key1=value1 key2="value with spaces" key3=value3
     /// Adapted from Stelp but converted to work with Kelora's Dynamic system
-    fn parse_logfmt_pairs(&self, line: &str) -> Result<Vec<(String, String)>, String> {
+    ///
+    /// When `rest` is given, whitespace-delimited tokens that are not pairs
+    /// are recorded there as byte spans; consecutive tokens share one span.
+    fn parse_logfmt_pairs(
+        &self,
+        line: &str,
+        mut rest: Option<&mut Vec<(usize, usize)>>,
+    ) -> Result<Vec<(String, String)>, String> {
         let mut pairs = Vec::new();
-        let mut chars = line.chars().peekable();
+        let mut chars = line.char_indices().peekable();
+        let mut prev_was_rest = false;
 
         while chars.peek().is_some() {
             // Skip whitespace
-            while chars.peek() == Some(&' ') || chars.peek() == Some(&'\t') {
+            while matches!(chars.peek(), Some(&(_, ' ')) | Some(&(_, '\t'))) {
                 chars.next();
             }
 
-            if chars.peek().is_none() {
-                break;
-            }
+            let token_start = match chars.peek() {
+                Some(&(pos, _)) => pos,
+                None => break,
+            };
 
             // Parse key
             let mut key = String::new();
-            while let Some(&ch) = chars.peek() {
-                if ch == '=' {
+            while let Some(&(_, ch)) = chars.peek() {
+                if ch == '=' || ch == ' ' || ch == '\t' {
                     break;
-                } else if ch == ' ' || ch == '\t' {
-                    return Err("Key cannot contain spaces".to_string());
-                } else {
-                    key.push(chars.next().unwrap());
                 }
-            }
-
-            if key.is_empty() {
-                return Err("Empty key found".to_string());
+                key.push(ch);
+                chars.next();
             }
 
             // Expect '='
-            if chars.next() != Some('=') {
+            if key.is_empty() || !matches!(chars.peek(), Some(&(_, '='))) {
+                if let Some(spans) = rest.as_deref_mut() {
+                    while !matches!(chars.peek(), None | Some(&(_, ' ')) | Some(&(_, '\t'))) {
+                        chars.next();
+                    }
+                    let token_end = chars.peek().map_or(line.len(), |&(pos, _)| pos);
+                    match spans.last_mut() {
+                        Some(span) if prev_was_rest => span.1 = token_end,
+                        _ => spans.push((token_start, token_end)),
+                    }
+                    prev_was_rest = true;
+                    continue;
+                }
+                if key.is_empty() {
+                    return Err("Empty key found".to_string());
+                }
+                if chars.peek().is_some() {
+                    return Err("Key cannot contain spaces".to_string());
+                }
                 return Err(format!("Expected '=' after key '{}'", key));
             }
+            chars.next(); // consume '='
+            prev_was_rest = false;
 
             // Parse value
             let mut value = String::new();
-            if chars.peek() == Some(&'"') {
+            if matches!(chars.peek(), Some(&(_, '"'))) {
                 // Quoted value
                 chars.next(); // consume opening quote
-                while let Some(ch) = chars.next() {
+                while let Some((_, ch)) = chars.next() {
                     if ch == '"' {
                         // Check for escaped quote
-                        if chars.peek() == Some(&'"') {
+                        if matches!(chars.peek(), Some(&(_, '"'))) {
                             chars.next(); // consume escaped quote
                             value.push('"');
                         } else {
@@ -74,7 +118,7 @@ impl LogfmtParser {
                         }
                     } else if ch == '\\' {
                         // Handle escape sequences
-                        if let Some(escaped_ch) = chars.next() {
+                        if let Some((_, escaped_ch)) = chars.next() {
                             match escaped_ch {
                                 'n' => value.push('\n'),
                                 't' => value.push('\t'),
@@ -93,12 +137,12 @@ impl LogfmtParser {
                 }
             } else {
                 // Unquoted value - read until space or end
-                while let Some(&ch) = chars.peek() {
+                while let Some(&(_, ch)) = chars.peek() {
                     if ch == ' ' || ch == '\t' {
                         break;
-                    } else {
-                        value.push(chars.next().unwrap());
                     }
+                    value.push(ch);
+                    chars.next();
                 }
             }
 
@@ -140,17 +184,30 @@ impl LogfmtParser {
 impl EventParser for LogfmtParser {
     fn parse(&self, line: &str) -> Result<Event> {
         let line = line.trim_end_matches('\n').trim_end_matches('\r');
+        let content = line.trim();
+        let mut rest_spans = Vec::new();
         let pairs = self
-            .parse_logfmt_pairs(line.trim())
+            .parse_logfmt_pairs(content, self.keep_rest.then_some(&mut rest_spans))
             .map_err(|e| anyhow::anyhow!("{}", e))?;
 
         // Pre-allocate Event with capacity based on number of pairs
         let mut event = Event::with_capacity(line.to_string(), pairs.len());
 
+        let mut inserter = DupKeyInserter::new(self.dup_keys);
         for (key, value) in pairs {
             // Convert string values to appropriate Dynamic types
             let dynamic_value = self.parse_value_to_dynamic(value);
-            event.set_field(key, dynamic_value);
+            inserter
+                .insert(&mut event.fields, key, dynamic_value)
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+        }
+
+        if !rest_spans.is_empty() {
+            let rest: Vec<&str> = rest_spans
+                .iter()
+                .map(|&(start, end)| &content[start..end])
+                .collect();
+            event.set_field(REST_FIELD.to_string(), Dynamic::from(rest.join(" ")));
         }
 
         // Extract timestamp from the parsed data
@@ -400,4 +457,95 @@ mod tests {
         // Key with spaces
         assert!(EventParser::parse(&parser, "key with spaces=value").is_err());
     }
+
+    fn rest_of(event: &Event) -> Option<String> {
+        event
+            .fields
+            .get(REST_FIELD)
+            .map(|value| value.clone().into_string().unwrap())
+    }
+
+    #[test]
+    fn test_logfmt_keep_rest_collects_non_pair_tokens() {
+        let parser = LogfmtParser::new().with_keep_rest(true);
+        let result = EventParser::parse(
+            &parser,
+            "  WARN level=warn msg=\"disk almost full\" retrying  in   5s pid=42 done \t\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            result
+                .fields
+                .get("msg")
+                .unwrap()
+                .clone()
+                .into_string()
+                .unwrap(),
+            "disk almost full"
+        );
+        assert_eq!(result.fields.get("pid").unwrap().as_int().unwrap(), 42);
+        // Runs of adjacent tokens keep their inner whitespace; separate runs
+        // are joined with one space; the line's outer whitespace is dropped
+        assert_eq!(
+            rest_of(&result).as_deref(),
+            Some("WARN retrying  in   5s done")
+        );
+    }
+
+    #[test]
+    fn test_logfmt_keep_rest_handles_malformed_tokens() {
+        let parser = LogfmtParser::new().with_keep_rest(true);
+        let result = EventParser::parse(&parser, "=orphan key with spaces=value").unwrap();
+
+        assert_eq!(
+            result
+                .fields
+                .get("spaces")
+                .unwrap()
+                .clone()
+                .into_string()
+                .unwrap(),
+            "value"
+        );
+        assert_eq!(rest_of(&result).as_deref(), Some("=orphan key with"));
+    }
+
+    #[test]
+    fn test_logfmt_keep_rest_omitted_when_everything_parses() {
+        let parser = LogfmtParser::new().with_keep_rest(true);
+        let result = EventParser::parse(&parser, " level=info count=1 ").unwrap();
+        assert_eq!(rest_of(&result), None);
+
+        // Without the flag the same leftovers are still an error
+        let strict = LogfmtParser::new();
+        assert!(EventParser::parse(&strict, "level=info trailing words").is_err());
+    }
+
+    #[test]
+    fn test_logfmt_parser_dup_keys_policy() {
+        let line = "key=1 other=x key=2 key=3";
+
+        let event = EventParser::parse(&LogfmtParser::new(), line).unwrap();
+        assert_eq!(event.fields.get("key").unwrap().as_int().unwrap(), 3);
+
+        let parser = LogfmtParser::new().with_dup_keys(DupKeyPolicy::First);
+        let event = EventParser::parse(&parser, line).unwrap();
+        assert_eq!(event.fields.get("key").unwrap().as_int().unwrap(), 1);
+
+        let parser = LogfmtParser::new().with_dup_keys(DupKeyPolicy::Collect);
+        let event = EventParser::parse(&parser, line).unwrap();
+        let keys = event
+            .fields
+            .get("key")
+            .unwrap()
+            .clone()
+            .into_array()
+            .unwrap();
+        let keys: Vec<i64> = keys.iter().map(|v| v.as_int().unwrap()).collect();
+        assert_eq!(keys, vec![1, 2, 3]);
+
+        let parser = LogfmtParser::new().with_dup_keys(DupKeyPolicy::Error);
+        assert!(EventParser::parse(&parser, line).is_err());
+    }
 }
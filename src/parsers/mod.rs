@@ -4,26 +4,50 @@ pub mod cef;
 pub mod cols;
 pub mod combined;
 pub mod csv;
+pub mod dup_keys;
+pub mod fallback;
+pub mod fixed_width;
+pub mod haproxy;
 pub mod json;
+pub mod leef;
 pub mod line;
 pub mod lnav_formats;
 pub mod logfmt;
+pub mod netflow;
+pub mod pcap;
 pub mod raw;
 pub mod regex;
+pub mod sqlite;
+pub mod ssv;
 pub mod syslog;
 pub mod type_conversion;
+pub mod xml_records;
+
+/// Field that receives the unparsed remainder of a line under `--keep-rest`
+/// (logfmt, regex and cols parsers). Never set when the remainder is empty.
+pub const REST_FIELD: &str = "_rest";
 
 #[allow(unused_imports)] // Used by lib.rs for format auto-detection
-pub use auto_detect::detect_format;
+pub use auto_detect::{detect_format, detect_format_from_sample};
 #[allow(unused_imports)] // FORMAT_FIELD re-exported for external access
 pub use cascade::{CascadingParser, FORMAT_FIELD};
 pub use cef::CefParser;
 pub use cols::ColsParser;
 pub use combined::CombinedParser;
 pub use csv::CsvParser;
+pub use dup_keys::DupKeyInserter;
+pub use fallback::FallbackParser;
+pub use fixed_width::FixedWidthParser;
+pub use haproxy::HaproxyParser;
 pub use json::JsonlParser;
+pub use leef::LeefParser;
 pub use line::LineParser;
 pub use logfmt::LogfmtParser;
+pub use netflow::{NetflowParser, NetflowReader, NetflowVersion};
+pub use pcap::{PcapParser, PcapReader};
 pub use raw::RawParser;
 pub use regex::{MultiRegexParser, RegexParser};
+pub use sqlite::{SqliteOptions, SqliteReader};
+pub use ssv::SsvParser;
 pub use syslog::SyslogParser;
+pub use xml_records::{XmlRecordsOptions, XmlRecordsParser, XmlRecordsReader};
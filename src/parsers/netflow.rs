@@ -0,0 +1,687 @@
+//! NetFlow v5 / v9 and IPFIX binary input (`-f netflow5|netflow9|ipfix`).
+//!
+//! The pipeline reads input line by line, so the binary capture is decoded at
+//! the reader level: [`NetflowReader`] turns a stream of concatenated export
+//! packets (a file of raw PDUs or a UDP payload dump) into one `key=value` text
+//! line per flow record, and [`NetflowParser`] turns each line into a typed
+//! event with the fields `src_ip`, `dst_ip`, `src_port`, `dst_port`,
+//! `protocol`, `bytes`, `packets`, `start_ms`, `end_ms`, `tcp_flags`, `tos`.
+//! Flow times are Unix epoch milliseconds.
+
+use crate::event::Event;
+use crate::pipeline::EventParser;
+use anyhow::Result;
+use byteorder::{BigEndian, ByteOrder, ReadBytesExt};
+use rhai::Dynamic;
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+const V5_HEADER_LEN: usize = 24;
+const V5_RECORD_LEN: usize = 48;
+const V9_HEADER_LEN: usize = 20;
+const IPFIX_HEADER_LEN: usize = 16;
+const IPFIX_VARIABLE_LENGTH: u16 = 65535;
+
+// Information elements (IANA IPFIX numbering, shared by NetFlow v9)
+const IE_OCTETS: u16 = 1;
+const IE_PACKETS: u16 = 2;
+const IE_PROTOCOL: u16 = 4;
+const IE_TOS: u16 = 5;
+const IE_TCP_FLAGS: u16 = 6;
+const IE_SRC_PORT: u16 = 7;
+const IE_SRC_IPV4: u16 = 8;
+const IE_DST_PORT: u16 = 11;
+const IE_DST_IPV4: u16 = 12;
+const IE_LAST_SWITCHED: u16 = 21;
+const IE_FIRST_SWITCHED: u16 = 22;
+const IE_SRC_IPV6: u16 = 27;
+const IE_DST_IPV6: u16 = 28;
+const IE_FLOW_START_SECONDS: u16 = 150;
+const IE_FLOW_END_SECONDS: u16 = 151;
+const IE_FLOW_START_MILLISECONDS: u16 = 152;
+const IE_FLOW_END_MILLISECONDS: u16 = 153;
+const IE_SYSTEM_INIT_MILLISECONDS: u16 = 160;
+
+/// Which export protocol a binary capture holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetflowVersion {
+    V5,
+    V9,
+    Ipfix,
+}
+
+impl NetflowVersion {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "netflow5" => Some(Self::V5),
+            "netflow9" => Some(Self::V9),
+            "ipfix" => Some(Self::Ipfix),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::V5 => "netflow5",
+            Self::V9 => "netflow9",
+            Self::Ipfix => "ipfix",
+        }
+    }
+
+    /// Version number carried in the first two bytes of every packet
+    fn wire_version(self) -> u16 {
+        match self {
+            Self::V5 => 5,
+            Self::V9 => 9,
+            Self::Ipfix => 10,
+        }
+    }
+}
+
+/// One decoded flow record
+#[derive(Debug, Default, Clone, PartialEq)]
+struct FlowRecord {
+    src_ip: Option<IpAddr>,
+    dst_ip: Option<IpAddr>,
+    src_port: u16,
+    dst_port: u16,
+    protocol: u8,
+    bytes: u64,
+    packets: u64,
+    start_ms: i64,
+    end_ms: i64,
+    tcp_flags: u8,
+    tos: u8,
+}
+
+impl FlowRecord {
+    fn to_line(&self) -> String {
+        let unspecified = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        format!(
+            "src_ip={} dst_ip={} src_port={} dst_port={} protocol={} bytes={} packets={} start_ms={} end_ms={} tcp_flags={} tos={}\n",
+            self.src_ip.unwrap_or(unspecified),
+            self.dst_ip.unwrap_or(unspecified),
+            self.src_port,
+            self.dst_port,
+            self.protocol,
+            self.bytes,
+            self.packets,
+            self.start_ms,
+            self.end_ms,
+            self.tcp_flags,
+            self.tos
+        )
+    }
+}
+
+/// A v9/IPFIX template field: information element and its encoded length.
+/// Enterprise-specific elements are kept only so records can be skipped over.
+#[derive(Debug, Clone, Copy)]
+struct TemplateField {
+    element: u16,
+    length: u16,
+    enterprise: bool,
+}
+
+/// Per-record time base for v9/IPFIX sysUptime-relative timestamps
+struct TimeBase {
+    /// Unix ms at which the exporter's uptime clock read zero
+    boot_ms: Option<i64>,
+}
+
+/// Wraps a binary capture and yields one text line per flow record.
+pub struct NetflowReader<R: Read> {
+    inner: R,
+    version: NetflowVersion,
+    /// Decoded lines not yet handed out
+    buffer: Vec<u8>,
+    position: usize,
+    /// A v9 packet has no length field, so its end is found by reading the
+    /// next packet's version number in place of a flowset ID
+    pending_version: Option<u16>,
+    /// Templates keyed by (source ID / observation domain, template ID)
+    templates: HashMap<(u32, u16), Vec<TemplateField>>,
+    finished: bool,
+}
+
+impl<R: Read> NetflowReader<R> {
+    pub fn new(inner: R, version: NetflowVersion) -> Self {
+        Self {
+            inner,
+            version,
+            buffer: Vec::new(),
+            position: 0,
+            pending_version: None,
+            templates: HashMap::new(),
+            finished: false,
+        }
+    }
+
+    /// Decode the next export packet into `buffer`. Returns false at end of input.
+    fn decode_packet(&mut self) -> io::Result<bool> {
+        let version = match self.pending_version.take() {
+            Some(version) => version,
+            None => match read_u16_or_eof(&mut self.inner)? {
+                Some(version) => version,
+                None => return Ok(false),
+            },
+        };
+
+        if version != self.version.wire_version() {
+            return Err(invalid_data(format!(
+                "expected {} packet (version {}), found version {}",
+                self.version.name(),
+                self.version.wire_version(),
+                version
+            )));
+        }
+
+        let records = match self.version {
+            NetflowVersion::V5 => self.decode_v5()?,
+            NetflowVersion::V9 => self.decode_v9()?,
+            NetflowVersion::Ipfix => self.decode_ipfix()?,
+        };
+        for record in records {
+            self.buffer.extend_from_slice(record.to_line().as_bytes());
+        }
+        Ok(true)
+    }
+
+    fn decode_v5(&mut self) -> io::Result<Vec<FlowRecord>> {
+        let mut header = [0u8; V5_HEADER_LEN - 2];
+        read_packet_bytes(&mut self.inner, &mut header, "a NetFlow v5 header")?;
+        let count = BigEndian::read_u16(&header[0..2]) as usize;
+        let sys_uptime = BigEndian::read_u32(&header[2..6]) as i64;
+        let unix_secs = BigEndian::read_u32(&header[6..10]) as i64;
+        let unix_nsecs = BigEndian::read_u32(&header[10..14]) as i64;
+        let boot_ms = unix_secs * 1000 + unix_nsecs / 1_000_000 - sys_uptime;
+
+        let mut body = vec![0u8; count * V5_RECORD_LEN];
+        read_packet_bytes(&mut self.inner, &mut body, "the NetFlow v5 flow records")?;
+
+        Ok(body
+            .chunks_exact(V5_RECORD_LEN)
+            .map(|rec| FlowRecord {
+                src_ip: Some(IpAddr::V4(Ipv4Addr::from(BigEndian::read_u32(&rec[0..4])))),
+                dst_ip: Some(IpAddr::V4(Ipv4Addr::from(BigEndian::read_u32(&rec[4..8])))),
+                packets: BigEndian::read_u32(&rec[16..20]) as u64,
+                bytes: BigEndian::read_u32(&rec[20..24]) as u64,
+                start_ms: boot_ms + BigEndian::read_u32(&rec[24..28]) as i64,
+                end_ms: boot_ms + BigEndian::read_u32(&rec[28..32]) as i64,
+                src_port: BigEndian::read_u16(&rec[32..34]),
+                dst_port: BigEndian::read_u16(&rec[34..36]),
+                tcp_flags: rec[37],
+                protocol: rec[38],
+                tos: rec[39],
+            })
+            .collect())
+    }
+
+    fn decode_v9(&mut self) -> io::Result<Vec<FlowRecord>> {
+        let mut header = [0u8; V9_HEADER_LEN - 2];
+        read_packet_bytes(&mut self.inner, &mut header, "a NetFlow v9 header")?;
+        let sys_uptime = BigEndian::read_u32(&header[2..6]) as i64;
+        let unix_secs = BigEndian::read_u32(&header[6..10]) as i64;
+        let source_id = BigEndian::read_u32(&header[14..18]);
+        let time_base = TimeBase {
+            boot_ms: Some(unix_secs * 1000 - sys_uptime),
+        };
+
+        let mut records = Vec::new();
+        loop {
+            // FlowSet IDs 2-255 are reserved, so a 9 here is the next packet
+            let flowset_id = match read_u16_or_eof(&mut self.inner)? {
+                None => break,
+                Some(id) if id == NetflowVersion::V9.wire_version() => {
+                    self.pending_version = Some(id);
+                    break;
+                }
+                Some(id) => id,
+            };
+            let length = self.inner.read_u16::<BigEndian>()? as usize;
+            if length < 4 {
+                return Err(invalid_data(format!(
+                    "NetFlow v9 flowset {} has invalid length {}",
+                    flowset_id, length
+                )));
+            }
+            let mut body = vec![0u8; length - 4];
+            read_packet_bytes(&mut self.inner, &mut body, "a NetFlow v9 flowset")?;
+
+            match flowset_id {
+                0 => self.store_templates(source_id, &body, false)?,
+                1 => {} // Options templates describe exporter metadata, not flows
+                id if id >= 256 => {
+                    if let Some(fields) = self.templates.get(&(source_id, id)) {
+                        records.extend(decode_data_set(&body, fields, &time_base));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(records)
+    }
+
+    fn decode_ipfix(&mut self) -> io::Result<Vec<FlowRecord>> {
+        let mut header = [0u8; IPFIX_HEADER_LEN - 2];
+        read_packet_bytes(&mut self.inner, &mut header, "an IPFIX message header")?;
+        let length = BigEndian::read_u16(&header[0..2]) as usize;
+        let domain_id = BigEndian::read_u32(&header[10..14]);
+        if length < IPFIX_HEADER_LEN {
+            return Err(invalid_data(format!(
+                "IPFIX message has invalid length {}",
+                length
+            )));
+        }
+
+        let mut message = vec![0u8; length - IPFIX_HEADER_LEN];
+        read_packet_bytes(&mut self.inner, &mut message, "an IPFIX message")?;
+        let time_base = TimeBase { boot_ms: None };
+
+        let mut records = Vec::new();
+        let mut offset = 0;
+        while offset + 4 <= message.len() {
+            let set_id = BigEndian::read_u16(&message[offset..offset + 2]);
+            let set_len = BigEndian::read_u16(&message[offset + 2..offset + 4]) as usize;
+            if set_len < 4 || offset + set_len > message.len() {
+                return Err(invalid_data(format!(
+                    "IPFIX set {} has invalid length {}",
+                    set_id, set_len
+                )));
+            }
+            let body = &message[offset + 4..offset + set_len];
+            match set_id {
+                2 => self.store_templates(domain_id, body, true)?,
+                3 => {} // Options templates describe exporter metadata, not flows
+                id if id >= 256 => {
+                    if let Some(fields) = self.templates.get(&(domain_id, id)) {
+                        records.extend(decode_data_set(body, fields, &time_base));
+                    }
+                }
+                _ => {}
+            }
+            offset += set_len;
+        }
+        Ok(records)
+    }
+
+    /// Parse a template (flow)set body and remember each template it defines.
+    fn store_templates(&mut self, domain: u32, body: &[u8], ipfix: bool) -> io::Result<()> {
+        let mut offset = 0;
+        while offset + 4 <= body.len() {
+            let template_id = BigEndian::read_u16(&body[offset..offset + 2]);
+            let field_count = BigEndian::read_u16(&body[offset + 2..offset + 4]) as usize;
+            offset += 4;
+            if template_id < 256 {
+                // Trailing padding
+                break;
+            }
+
+            let mut fields = Vec::with_capacity(field_count);
+            for _ in 0..field_count {
+                if offset + 4 > body.len() {
+                    return Err(invalid_data(format!(
+                        "template {} is truncated",
+                        template_id
+                    )));
+                }
+                let raw_element = BigEndian::read_u16(&body[offset..offset + 2]);
+                let length = BigEndian::read_u16(&body[offset + 2..offset + 4]);
+                offset += 4;
+                let enterprise = ipfix && raw_element & 0x8000 != 0;
+                if enterprise {
+                    // Enterprise number follows the field specifier
+                    offset += 4;
+                }
+                fields.push(TemplateField {
+                    element: raw_element & 0x7fff,
+                    length,
+                    enterprise,
+                });
+            }
+            self.templates.insert((domain, template_id), fields);
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for NetflowReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position >= self.buffer.len() {
+            self.buffer.clear();
+            self.position = 0;
+            if self.finished || !self.decode_packet()? {
+                self.finished = true;
+                return Ok(0);
+            }
+        }
+
+        let available = &self.buffer[self.position..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Decode every record of a data (flow)set using its template.
+fn decode_data_set(body: &[u8], fields: &[TemplateField], time_base: &TimeBase) -> Vec<FlowRecord> {
+    // Smallest possible record: fixed fields plus one length byte per
+    // variable-length field. Anything shorter at the end is padding.
+    let min_len: usize = fields
+        .iter()
+        .map(|f| {
+            if f.length == IPFIX_VARIABLE_LENGTH {
+                1
+            } else {
+                f.length as usize
+            }
+        })
+        .sum();
+    if min_len == 0 {
+        return Vec::new();
+    }
+
+    let mut records = Vec::new();
+    let mut offset = 0;
+    while body.len() - offset >= min_len {
+        let mut values: Vec<(TemplateField, &[u8])> = Vec::with_capacity(fields.len());
+        for field in fields {
+            let mut length = field.length as usize;
+            if field.length == IPFIX_VARIABLE_LENGTH {
+                let Some(&short) = body.get(offset) else {
+                    return records;
+                };
+                offset += 1;
+                length = short as usize;
+                if short == 255 {
+                    let Some(long) = body.get(offset..offset + 2) else {
+                        return records;
+                    };
+                    length = BigEndian::read_u16(long) as usize;
+                    offset += 2;
+                }
+            }
+            let Some(value) = body.get(offset..offset + length) else {
+                return records;
+            };
+            values.push((*field, value));
+            offset += length;
+        }
+        records.push(record_from_fields(&values, time_base));
+    }
+    records
+}
+
+fn record_from_fields(values: &[(TemplateField, &[u8])], time_base: &TimeBase) -> FlowRecord {
+    let mut record = FlowRecord::default();
+    let mut boot_ms = time_base.boot_ms;
+    let mut first_switched = None;
+    let mut last_switched = None;
+
+    for (field, value) in values {
+        if field.enterprise {
+            continue;
+        }
+        let number = be_uint(value);
+        match field.element {
+            IE_OCTETS => record.bytes = number,
+            IE_PACKETS => record.packets = number,
+            IE_PROTOCOL => record.protocol = number as u8,
+            IE_TOS => record.tos = number as u8,
+            IE_TCP_FLAGS => record.tcp_flags = number as u8,
+            IE_SRC_PORT => record.src_port = number as u16,
+            IE_DST_PORT => record.dst_port = number as u16,
+            IE_SRC_IPV4 | IE_SRC_IPV6 => record.src_ip = ip_from_bytes(value),
+            IE_DST_IPV4 | IE_DST_IPV6 => record.dst_ip = ip_from_bytes(value),
+            IE_FIRST_SWITCHED => first_switched = Some(number as i64),
+            IE_LAST_SWITCHED => last_switched = Some(number as i64),
+            IE_FLOW_START_SECONDS => record.start_ms = number as i64 * 1000,
+            IE_FLOW_END_SECONDS => record.end_ms = number as i64 * 1000,
+            IE_FLOW_START_MILLISECONDS => record.start_ms = number as i64,
+            IE_FLOW_END_MILLISECONDS => record.end_ms = number as i64,
+            IE_SYSTEM_INIT_MILLISECONDS => boot_ms = Some(number as i64),
+            _ => {}
+        }
+    }
+
+    if let Some(boot_ms) = boot_ms {
+        if let Some(first) = first_switched {
+            record.start_ms = boot_ms + first;
+        }
+        if let Some(last) = last_switched {
+            record.end_ms = boot_ms + last;
+        }
+    }
+    record
+}
+
+/// Unsigned big-endian integer of any width up to 8 bytes (reduced-size
+/// encoding lets exporters send counters in fewer bytes than their type).
+fn be_uint(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .take(8)
+        .rev()
+        .fold(0u64, |acc, &b| (acc << 8) | b as u64)
+}
+
+fn ip_from_bytes(bytes: &[u8]) -> Option<IpAddr> {
+    match bytes.len() {
+        4 => Some(IpAddr::V4(Ipv4Addr::from(BigEndian::read_u32(bytes)))),
+        16 => Some(IpAddr::V6(Ipv6Addr::from(BigEndian::read_u128(bytes)))),
+        _ => None,
+    }
+}
+
+/// Read a big-endian u16, or None when the input ends cleanly before it.
+fn read_u16_or_eof<R: Read>(reader: &mut R) -> io::Result<Option<u16>> {
+    let mut buf = [0u8; 2];
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(invalid_data("capture ends in the middle of a packet")),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(Some(BigEndian::read_u16(&buf)))
+}
+
+fn read_packet_bytes<R: Read>(reader: &mut R, buf: &mut [u8], what: &str) -> io::Result<()> {
+    reader.read_exact(buf).map_err(|e| {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            invalid_data(format!("capture ends inside {}", what))
+        } else {
+            e
+        }
+    })
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("netflow: {}", message.into()),
+    )
+}
+
+/// Parses the lines produced by [`NetflowReader`] into typed flow events.
+pub struct NetflowParser;
+
+impl NetflowParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl EventParser for NetflowParser {
+    fn parse(&self, line: &str) -> Result<Event> {
+        let mut event = Event::with_capacity(line.to_string(), 11);
+        for pair in line.split_whitespace() {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("not a decoded flow record: '{}'", line))?;
+            let value = match key {
+                "src_ip" | "dst_ip" => Dynamic::from(value.to_string()),
+                _ => Dynamic::from(value.parse::<i64>().map_err(|_| {
+                    anyhow::anyhow!("flow field '{}' is not an integer: '{}'", key, value)
+                })?),
+            };
+            event.set_field(key.to_string(), value);
+        }
+        if event.fields.is_empty() {
+            return Err(anyhow::anyhow!("empty flow record"));
+        }
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(bytes: Vec<u8>, version: NetflowVersion) -> io::Result<String> {
+        let mut out = String::new();
+        NetflowReader::new(io::Cursor::new(bytes), version).read_to_string(&mut out)?;
+        Ok(out)
+    }
+
+    fn v9_packet(flowsets: &[u8]) -> Vec<u8> {
+        let mut packet = Vec::new();
+        packet.extend_from_slice(&9u16.to_be_bytes());
+        packet.extend_from_slice(&1u16.to_be_bytes()); // count
+        packet.extend_from_slice(&10_000u32.to_be_bytes()); // sys_uptime ms
+        packet.extend_from_slice(&1_700_000_000u32.to_be_bytes()); // unix_secs
+        packet.extend_from_slice(&1u32.to_be_bytes()); // sequence
+        packet.extend_from_slice(&7u32.to_be_bytes()); // source_id
+        packet.extend_from_slice(flowsets);
+        packet
+    }
+
+    #[test]
+    fn test_v9_template_then_data() {
+        // Template 256: src ipv4, dst ipv4, src port, dst port, proto, bytes(4), first, last
+        let spec: [(u16, u16); 8] = [
+            (8, 4),
+            (12, 4),
+            (7, 2),
+            (11, 2),
+            (4, 1),
+            (1, 4),
+            (22, 4),
+            (21, 4),
+        ];
+        let mut template = Vec::new();
+        template.extend_from_slice(&0u16.to_be_bytes());
+        template.extend_from_slice(&((4 + 4 + spec.len() * 4) as u16).to_be_bytes());
+        template.extend_from_slice(&256u16.to_be_bytes());
+        template.extend_from_slice(&(spec.len() as u16).to_be_bytes());
+        for (element, length) in spec {
+            template.extend_from_slice(&element.to_be_bytes());
+            template.extend_from_slice(&length.to_be_bytes());
+        }
+
+        let mut record = Vec::new();
+        record.extend_from_slice(&[192, 168, 1, 10, 10, 0, 0, 1]);
+        record.extend_from_slice(&5353u16.to_be_bytes());
+        record.extend_from_slice(&53u16.to_be_bytes());
+        record.push(17);
+        record.extend_from_slice(&512u32.to_be_bytes());
+        record.extend_from_slice(&4_000u32.to_be_bytes());
+        record.extend_from_slice(&9_000u32.to_be_bytes());
+        let mut data = Vec::new();
+        data.extend_from_slice(&256u16.to_be_bytes());
+        data.extend_from_slice(&((4 + record.len() + 3) as u16).to_be_bytes());
+        data.extend_from_slice(&record);
+        data.extend_from_slice(&[0, 0, 0]); // padding
+
+        let mut flowsets = template;
+        flowsets.extend_from_slice(&data);
+        let mut capture = v9_packet(&flowsets);
+        // A second packet reuses the template
+        capture.extend_from_slice(&v9_packet(&data));
+
+        let output = read_all(capture, NetflowVersion::V9).unwrap();
+        let boot = 1_700_000_000_000i64 - 10_000;
+        let expected = format!(
+            "src_ip=192.168.1.10 dst_ip=10.0.0.1 src_port=5353 dst_port=53 protocol=17 bytes=512 packets=0 start_ms={} end_ms={} tcp_flags=0 tos=0\n",
+            boot + 4_000,
+            boot + 9_000
+        );
+        assert_eq!(output, expected.repeat(2));
+    }
+
+    #[test]
+    fn test_ipfix_variable_length_and_absolute_times() {
+        // Template 300: src ipv6, a variable-length field, bytes(8), start/end ms
+        let spec: [(u16, u16); 5] = [(27, 16), (82, 65535), (1, 8), (152, 8), (153, 8)];
+        let mut template = Vec::new();
+        template.extend_from_slice(&2u16.to_be_bytes());
+        template.extend_from_slice(&((4 + 4 + spec.len() * 4) as u16).to_be_bytes());
+        template.extend_from_slice(&300u16.to_be_bytes());
+        template.extend_from_slice(&(spec.len() as u16).to_be_bytes());
+        for (element, length) in spec {
+            template.extend_from_slice(&element.to_be_bytes());
+            template.extend_from_slice(&length.to_be_bytes());
+        }
+
+        let mut record = Vec::new();
+        record.extend_from_slice(&"2001:db8::1".parse::<Ipv6Addr>().unwrap().octets());
+        record.push(4);
+        record.extend_from_slice(b"eth0");
+        record.extend_from_slice(&4096u64.to_be_bytes());
+        record.extend_from_slice(&1_700_000_000_123u64.to_be_bytes());
+        record.extend_from_slice(&1_700_000_001_456u64.to_be_bytes());
+        let mut data = Vec::new();
+        data.extend_from_slice(&300u16.to_be_bytes());
+        data.extend_from_slice(&((4 + record.len()) as u16).to_be_bytes());
+        data.extend_from_slice(&record);
+
+        let mut message = Vec::new();
+        message.extend_from_slice(&10u16.to_be_bytes());
+        message.extend_from_slice(&((16 + template.len() + data.len()) as u16).to_be_bytes());
+        message.extend_from_slice(&1_700_000_002u32.to_be_bytes());
+        message.extend_from_slice(&1u32.to_be_bytes());
+        message.extend_from_slice(&42u32.to_be_bytes());
+        message.extend_from_slice(&template);
+        message.extend_from_slice(&data);
+
+        let output = read_all(message, NetflowVersion::Ipfix).unwrap();
+        assert_eq!(
+            output,
+            "src_ip=2001:db8::1 dst_ip=0.0.0.0 src_port=0 dst_port=0 protocol=0 bytes=4096 packets=0 start_ms=1700000000123 end_ms=1700000001456 tcp_flags=0 tos=0\n"
+        );
+    }
+
+    #[test]
+    fn test_version_mismatch_is_an_error() {
+        let err = read_all(vec![0, 9, 0, 0], NetflowVersion::V5).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("expected netflow5"));
+    }
+
+    #[test]
+    fn test_parser_types_fields() {
+        let event = NetflowParser::new()
+            .parse("src_ip=10.0.0.1 dst_ip=10.0.0.2 src_port=80 dst_port=443 protocol=6 bytes=1000 packets=3 start_ms=1 end_ms=2 tcp_flags=27 tos=0")
+            .unwrap();
+        assert_eq!(
+            event
+                .fields
+                .get("src_ip")
+                .unwrap()
+                .clone()
+                .into_string()
+                .unwrap(),
+            "10.0.0.1"
+        );
+        assert_eq!(event.fields.get("bytes").unwrap().as_int().unwrap(), 1000);
+        assert_eq!(event.fields.get("tcp_flags").unwrap().as_int().unwrap(), 27);
+
+        assert!(NetflowParser::new().parse("not a flow").is_err());
+    }
+}
@@ -0,0 +1,838 @@
+//! PCAP network captures (`-f pcap`).
+//!
+//! As with the NetFlow formats, the capture is decoded at the reader level:
+//! [`PcapReader`] reads a classic libpcap file, reassembles each TCP stream
+//! (keyed by `(src_ip:src_port, dst_ip:dst_port)`), finds the HTTP/1.x
+//! requests and responses in it, and turns every request/response pair into a
+//! single-line JSON object, which [`PcapParser`] then parses into an event
+//! with the fields `timestamp_us`, `src_ip`, `src_port`, `dst_ip`, `dst_port`,
+//! `method`, `path`, `http_version`, `status_code`, `request_headers` and
+//! `response_headers`. The source is always the client.
+//!
+//! Message bodies are skipped as they arrive (by `Content-Length` or chunked
+//! encoding), so memory per connection stays bounded. A stream that doesn't
+//! parse as HTTP (TLS, other protocols, a capture that starts mid-message) is
+//! ignored from that point on.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io::{self, Read};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use anyhow::Result;
+use byteorder::{BigEndian, ByteOrder};
+use pcap_file::pcap::PcapReader as CaptureReader;
+use pcap_file::DataLink;
+use serde_json::{Map, Value};
+
+use crate::event::Event;
+use crate::parsers::JsonlParser;
+use crate::pipeline::EventParser;
+
+/// Headers kept per direction of an exchange; later ones are dropped
+pub const MAX_HEADERS: usize = 50;
+
+/// Headers a message may have before it is no longer treated as HTTP
+const HEADER_PARSE_LIMIT: usize = 256;
+
+/// Bytes a message head may span before the stream is given up on
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+/// Out-of-order bytes held per stream while waiting for a gap to fill
+const MAX_OUT_OF_ORDER_BYTES: usize = 1024 * 1024;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86DD;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const ETHERTYPE_QINQ: u16 = 0x88A8;
+const IP_PROTO_TCP: u8 = 6;
+
+const TCP_FIN: u8 = 0x01;
+const TCP_SYN: u8 = 0x02;
+const TCP_RST: u8 = 0x04;
+
+/// One TCP segment taken from a captured packet
+#[derive(Debug)]
+struct TcpSegment<'a> {
+    src: SocketAddr,
+    dst: SocketAddr,
+    seq: u32,
+    flags: u8,
+    payload: &'a [u8],
+}
+
+/// Strip the link layer, returning the IP packet it carries
+fn ip_packet(link: DataLink, data: &[u8]) -> Option<&[u8]> {
+    match link {
+        DataLink::ETHERNET => {
+            let mut offset = 12;
+            let mut ethertype = BigEndian::read_u16(data.get(offset..offset + 2)?);
+            while ethertype == ETHERTYPE_VLAN || ethertype == ETHERTYPE_QINQ {
+                offset += 4;
+                ethertype = BigEndian::read_u16(data.get(offset..offset + 2)?);
+            }
+            match ethertype {
+                ETHERTYPE_IPV4 | ETHERTYPE_IPV6 => data.get(offset + 2..),
+                _ => None,
+            }
+        }
+        DataLink::LINUX_SLL => match BigEndian::read_u16(data.get(14..16)?) {
+            ETHERTYPE_IPV4 | ETHERTYPE_IPV6 => data.get(16..),
+            _ => None,
+        },
+        // BSD loopback: a 4-byte address family in host byte order
+        DataLink::NULL | DataLink::LOOP => data.get(4..),
+        DataLink::RAW | DataLink::IPV4 | DataLink::IPV6 => Some(data),
+        _ => None,
+    }
+}
+
+/// Decode the TCP segment in an IPv4/IPv6 packet. Fragments and other
+/// protocols give `None`.
+fn tcp_segment(packet: &[u8]) -> Option<TcpSegment<'_>> {
+    let (src_ip, dst_ip, tcp) = match packet.first()? >> 4 {
+        4 => {
+            let header_len = (packet[0] & 0x0F) as usize * 4;
+            let total_len = BigEndian::read_u16(packet.get(2..4)?) as usize;
+            let fragment = BigEndian::read_u16(packet.get(6..8)?) & 0x3FFF;
+            if *packet.get(9)? != IP_PROTO_TCP || fragment != 0 || header_len < 20 {
+                return None;
+            }
+            let src = Ipv4Addr::from(BigEndian::read_u32(packet.get(12..16)?));
+            let dst = Ipv4Addr::from(BigEndian::read_u32(packet.get(16..20)?));
+            let end = total_len.clamp(header_len, packet.len());
+            (
+                IpAddr::V4(src),
+                IpAddr::V4(dst),
+                packet.get(header_len..end)?,
+            )
+        }
+        6 => {
+            if *packet.get(6)? != IP_PROTO_TCP {
+                return None;
+            }
+            let payload_len = BigEndian::read_u16(packet.get(4..6)?) as usize;
+            let src: [u8; 16] = packet.get(8..24)?.try_into().ok()?;
+            let dst: [u8; 16] = packet.get(24..40)?.try_into().ok()?;
+            let end = (40 + payload_len).min(packet.len());
+            (
+                IpAddr::V6(Ipv6Addr::from(src)),
+                IpAddr::V6(Ipv6Addr::from(dst)),
+                packet.get(40..end)?,
+            )
+        }
+        _ => return None,
+    };
+
+    let data_offset = (*tcp.get(12)? >> 4) as usize * 4;
+    Some(TcpSegment {
+        src: SocketAddr::new(src_ip, BigEndian::read_u16(tcp.get(0..2)?)),
+        dst: SocketAddr::new(dst_ip, BigEndian::read_u16(tcp.get(2..4)?)),
+        seq: BigEndian::read_u32(tcp.get(4..8)?),
+        flags: *tcp.get(13)?,
+        payload: tcp.get(data_offset.max(20)..)?,
+    })
+}
+
+/// Where a stream is within the current HTTP message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BodyState {
+    /// Waiting for a request or status line and headers
+    Head,
+    /// Skipping this many more body bytes
+    Length(usize),
+    /// Expecting a chunk-size line
+    ChunkSize,
+    /// Skipping this many bytes of chunk data and its CRLF
+    ChunkData(usize),
+    /// Expecting the trailer section after the last chunk
+    ChunkTrailer,
+    /// The body runs to the end of the connection
+    UntilClose,
+    /// Not HTTP, or lost track; the rest of the stream is ignored
+    Ignored,
+}
+
+/// One direction of a TCP connection
+#[derive(Debug)]
+struct Stream {
+    next_seq: Option<u32>,
+    /// Reassembled bytes not yet consumed
+    data: Vec<u8>,
+    /// Capture time of the packet that started the unconsumed data
+    data_timestamp_us: i64,
+    /// Segments that arrived ahead of a gap, by sequence number
+    out_of_order: BTreeMap<u32, Vec<u8>>,
+    out_of_order_bytes: usize,
+    state: BodyState,
+    closed: bool,
+}
+
+impl Stream {
+    fn new() -> Self {
+        Self {
+            next_seq: None,
+            data: Vec::new(),
+            data_timestamp_us: 0,
+            out_of_order: BTreeMap::new(),
+            out_of_order_bytes: 0,
+            state: BodyState::Head,
+            closed: false,
+        }
+    }
+
+    /// Add a segment's payload, in sequence order. Retransmitted bytes are
+    /// dropped and early segments wait for the gap before them to fill.
+    fn push(&mut self, seq: u32, payload: &[u8], timestamp_us: i64) {
+        if self.state == BodyState::Ignored || payload.is_empty() {
+            return;
+        }
+        let next = *self.next_seq.get_or_insert(seq);
+        if (seq.wrapping_sub(next) as i32) > 0 {
+            self.out_of_order_bytes += payload.len();
+            if self.out_of_order_bytes > MAX_OUT_OF_ORDER_BYTES {
+                self.ignore();
+            } else {
+                self.out_of_order.insert(seq, payload.to_vec());
+            }
+            return;
+        }
+
+        if self.data.is_empty() {
+            self.data_timestamp_us = timestamp_us;
+        }
+        self.append(seq, payload);
+        loop {
+            let next = self.next_seq.unwrap_or(seq);
+            let ready = self
+                .out_of_order
+                .keys()
+                .copied()
+                .find(|&seq| (seq.wrapping_sub(next) as i32) <= 0);
+            let Some(seq) = ready else {
+                break;
+            };
+            let payload = self.out_of_order.remove(&seq).unwrap_or_default();
+            self.out_of_order_bytes -= payload.len();
+            self.append(seq, &payload);
+        }
+    }
+
+    /// Append the part of `payload` past the bytes already seen
+    fn append(&mut self, seq: u32, payload: &[u8]) {
+        let next = self.next_seq.unwrap_or(seq);
+        let overlap = next.wrapping_sub(seq) as usize;
+        if overlap < payload.len() {
+            self.data.extend_from_slice(&payload[overlap..]);
+            self.next_seq = Some(next.wrapping_add((payload.len() - overlap) as u32));
+        }
+    }
+
+    fn ignore(&mut self) {
+        self.state = BodyState::Ignored;
+        self.data = Vec::new();
+        self.out_of_order.clear();
+        self.out_of_order_bytes = 0;
+    }
+
+    /// Skip up to `count` body bytes, returning how many were skipped
+    fn skip(&mut self, count: usize) -> usize {
+        let skipped = count.min(self.data.len());
+        self.data.drain(..skipped);
+        skipped
+    }
+}
+
+/// The head of a parsed HTTP message
+enum Message {
+    Request {
+        method: String,
+        path: String,
+        http_version: String,
+        headers: Map<String, Value>,
+        body: BodyState,
+    },
+    Response {
+        status_code: u16,
+        http_version: String,
+        headers: Map<String, Value>,
+        chunked: bool,
+        content_length: Option<usize>,
+    },
+}
+
+/// Lower-cased header names to values, keeping the first [`MAX_HEADERS`]
+/// names. Repeated headers are joined with `, `.
+fn header_map(headers: &[httparse::Header<'_>]) -> Map<String, Value> {
+    let mut map = Map::new();
+    for header in headers {
+        let name = header.name.to_ascii_lowercase();
+        let value = String::from_utf8_lossy(header.value).into_owned();
+        if let Some(Value::String(existing)) = map.get_mut(&name) {
+            existing.push_str(", ");
+            existing.push_str(&value);
+        } else if map.len() < MAX_HEADERS {
+            map.insert(name, Value::String(value));
+        }
+    }
+    map
+}
+
+fn header_value<'a>(headers: &'a [httparse::Header<'_>], name: &str) -> Option<&'a [u8]> {
+    headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value)
+}
+
+fn is_chunked(headers: &[httparse::Header<'_>]) -> bool {
+    header_value(headers, "transfer-encoding").is_some_and(|value| {
+        String::from_utf8_lossy(value)
+            .to_ascii_lowercase()
+            .contains("chunked")
+    })
+}
+
+fn content_length(headers: &[httparse::Header<'_>]) -> Option<usize> {
+    header_value(headers, "content-length")
+        .and_then(|value| std::str::from_utf8(value).ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// Parse the message head at the start of `data`. `Ok(None)` means more data
+/// is needed; an error means the stream is not HTTP.
+fn parse_head(data: &[u8]) -> std::result::Result<Option<(usize, Message)>, ()> {
+    let mut headers = [httparse::EMPTY_HEADER; HEADER_PARSE_LIMIT];
+    if data.starts_with(b"HTTP/") {
+        let mut response = httparse::Response::new(&mut headers);
+        let length = match response.parse(data) {
+            Ok(httparse::Status::Complete(length)) => length,
+            Ok(httparse::Status::Partial) => return Ok(None),
+            Err(_) => return Err(()),
+        };
+        let message = Message::Response {
+            status_code: response.code.ok_or(())?,
+            http_version: format!("1.{}", response.version.ok_or(())?),
+            headers: header_map(response.headers),
+            chunked: is_chunked(response.headers),
+            content_length: content_length(response.headers),
+        };
+        Ok(Some((length, message)))
+    } else {
+        let mut request = httparse::Request::new(&mut headers);
+        let length = match request.parse(data) {
+            Ok(httparse::Status::Complete(length)) => length,
+            Ok(httparse::Status::Partial) => return Ok(None),
+            Err(_) => return Err(()),
+        };
+        let body = if is_chunked(request.headers) {
+            BodyState::ChunkSize
+        } else {
+            match content_length(request.headers) {
+                Some(length) if length > 0 => BodyState::Length(length),
+                _ => BodyState::Head,
+            }
+        };
+        let message = Message::Request {
+            method: request.method.ok_or(())?.to_string(),
+            path: request.path.ok_or(())?.to_string(),
+            http_version: format!("1.{}", request.version.ok_or(())?),
+            headers: header_map(request.headers),
+            body,
+        };
+        Ok(Some((length, message)))
+    }
+}
+
+/// A request and, once seen, its response
+#[derive(Debug, Default)]
+struct Exchange {
+    timestamp_us: i64,
+    client: Option<SocketAddr>,
+    server: Option<SocketAddr>,
+    method: Option<String>,
+    path: Option<String>,
+    http_version: Option<String>,
+    status_code: Option<u16>,
+    request_headers: Option<Map<String, Value>>,
+    response_headers: Option<Map<String, Value>>,
+}
+
+impl Exchange {
+    fn to_line(&self) -> String {
+        let mut object = Map::new();
+        object.insert("timestamp_us".into(), self.timestamp_us.into());
+        if let Some(client) = self.client {
+            object.insert("src_ip".into(), client.ip().to_string().into());
+            object.insert("src_port".into(), client.port().into());
+        }
+        if let Some(server) = self.server {
+            object.insert("dst_ip".into(), server.ip().to_string().into());
+            object.insert("dst_port".into(), server.port().into());
+        }
+        let mut put = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                object.insert(key.into(), value);
+            }
+        };
+        put("method", self.method.clone().map(Value::from));
+        put("path", self.path.clone().map(Value::from));
+        put("http_version", self.http_version.clone().map(Value::from));
+        put("status_code", self.status_code.map(Value::from));
+        put(
+            "request_headers",
+            self.request_headers.clone().map(Value::Object),
+        );
+        put(
+            "response_headers",
+            self.response_headers.clone().map(Value::Object),
+        );
+        let mut line = Value::Object(object).to_string();
+        line.push('\n');
+        line
+    }
+}
+
+/// TCP stream reassembly and HTTP request/response pairing
+#[derive(Default)]
+struct HttpSessions {
+    streams: HashMap<(SocketAddr, SocketAddr), Stream>,
+    /// Requests awaiting a response, keyed by (client, server)
+    pending: HashMap<(SocketAddr, SocketAddr), VecDeque<Exchange>>,
+}
+
+impl HttpSessions {
+    /// Feed one segment, appending a line to `out` for every finished exchange
+    fn handle(&mut self, segment: &TcpSegment<'_>, timestamp_us: i64, out: &mut Vec<u8>) {
+        let key = (segment.src, segment.dst);
+        let stream = self.streams.entry(key).or_insert_with(Stream::new);
+        if segment.flags & TCP_SYN != 0 {
+            *stream = Stream::new();
+            stream.next_seq = Some(segment.seq.wrapping_add(1));
+        }
+        stream.push(segment.seq, segment.payload, timestamp_us);
+        self.process(key, out);
+
+        if segment.flags & (TCP_FIN | TCP_RST) != 0 {
+            if let Some(stream) = self.streams.get_mut(&key) {
+                stream.closed = true;
+            }
+            let reverse = (segment.dst, segment.src);
+            let both_closed = self.streams.get(&reverse).is_none_or(|s| s.closed);
+            if segment.flags & TCP_RST != 0 || both_closed {
+                self.close(key, out);
+            }
+        }
+    }
+
+    /// Consume every complete message head buffered in a stream
+    fn process(&mut self, key: (SocketAddr, SocketAddr), out: &mut Vec<u8>) {
+        let Some(stream) = self.streams.get_mut(&key) else {
+            return;
+        };
+        loop {
+            match stream.state {
+                BodyState::Ignored => return,
+                BodyState::UntilClose => {
+                    stream.data.clear();
+                    return;
+                }
+                BodyState::Length(remaining) => {
+                    let left = remaining - stream.skip(remaining);
+                    if left > 0 {
+                        stream.state = BodyState::Length(left);
+                        return;
+                    }
+                    stream.state = BodyState::Head;
+                }
+                BodyState::ChunkData(remaining) => {
+                    let left = remaining - stream.skip(remaining);
+                    if left > 0 {
+                        stream.state = BodyState::ChunkData(left);
+                        return;
+                    }
+                    stream.state = BodyState::ChunkSize;
+                }
+                BodyState::ChunkSize => match httparse::parse_chunk_size(&stream.data) {
+                    Ok(httparse::Status::Complete((length, 0))) => {
+                        stream.skip(length);
+                        stream.state = BodyState::ChunkTrailer;
+                    }
+                    Ok(httparse::Status::Complete((length, size))) => {
+                        stream.skip(length);
+                        stream.state = BodyState::ChunkData(size as usize + 2);
+                    }
+                    Ok(httparse::Status::Partial) => return,
+                    Err(_) => {
+                        stream.ignore();
+                        return;
+                    }
+                },
+                BodyState::ChunkTrailer => {
+                    if stream.data.starts_with(b"\r\n") {
+                        stream.skip(2);
+                    } else if let Some(end) = stream
+                        .data
+                        .windows(4)
+                        .position(|window| window == b"\r\n\r\n")
+                    {
+                        stream.skip(end + 4);
+                    } else {
+                        return;
+                    }
+                    stream.state = BodyState::Head;
+                }
+                BodyState::Head => {
+                    if stream.data.is_empty() {
+                        return;
+                    }
+                    let (length, message) = match parse_head(&stream.data) {
+                        Ok(Some(parsed)) => parsed,
+                        Ok(None) if stream.data.len() <= MAX_HEAD_BYTES => return,
+                        Ok(None) | Err(()) => {
+                            stream.ignore();
+                            return;
+                        }
+                    };
+                    let timestamp_us = stream.data_timestamp_us;
+                    stream.skip(length);
+                    stream.state = match message {
+                        Message::Request {
+                            method,
+                            path,
+                            http_version,
+                            headers,
+                            body,
+                        } => {
+                            self.pending.entry(key).or_default().push_back(Exchange {
+                                timestamp_us,
+                                client: Some(key.0),
+                                server: Some(key.1),
+                                method: Some(method),
+                                path: Some(path),
+                                http_version: Some(http_version),
+                                request_headers: Some(headers),
+                                ..Exchange::default()
+                            });
+                            body
+                        }
+                        Message::Response {
+                            status_code,
+                            http_version,
+                            headers,
+                            chunked,
+                            content_length,
+                        } => {
+                            // Interim 1xx responses precede the real one
+                            if (100..200).contains(&status_code) && status_code != 101 {
+                                continue;
+                            }
+                            let (server, client) = key;
+                            let mut exchange = self
+                                .pending
+                                .get_mut(&(client, server))
+                                .and_then(VecDeque::pop_front)
+                                .unwrap_or_else(|| Exchange {
+                                    timestamp_us,
+                                    client: Some(client),
+                                    server: Some(server),
+                                    http_version: Some(http_version),
+                                    ..Exchange::default()
+                                });
+                            let bodyless = exchange.method.as_deref() == Some("HEAD")
+                                || matches!(status_code, 204 | 304);
+                            exchange.status_code = Some(status_code);
+                            exchange.response_headers = Some(headers);
+                            out.extend_from_slice(exchange.to_line().as_bytes());
+
+                            if status_code == 101 {
+                                BodyState::UntilClose
+                            } else if bodyless {
+                                BodyState::Head
+                            } else if chunked {
+                                BodyState::ChunkSize
+                            } else {
+                                match content_length {
+                                    Some(0) => BodyState::Head,
+                                    Some(length) => BodyState::Length(length),
+                                    None => BodyState::UntilClose,
+                                }
+                            }
+                        }
+                    };
+                    if !stream.data.is_empty() {
+                        stream.data_timestamp_us = timestamp_us;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Forget a closed connection, writing out requests that never got a response
+    fn close(&mut self, key: (SocketAddr, SocketAddr), out: &mut Vec<u8>) {
+        let reverse = (key.1, key.0);
+        self.streams.remove(&key);
+        self.streams.remove(&reverse);
+        for connection in [key, reverse] {
+            for exchange in self.pending.remove(&connection).into_iter().flatten() {
+                out.extend_from_slice(exchange.to_line().as_bytes());
+            }
+        }
+    }
+
+    /// Write out every request still waiting for a response, oldest first
+    fn finish(&mut self, out: &mut Vec<u8>) {
+        let mut unanswered: Vec<Exchange> = self.pending.drain().flat_map(|(_, q)| q).collect();
+        unanswered.sort_by_key(|exchange| exchange.timestamp_us);
+        for exchange in unanswered {
+            out.extend_from_slice(exchange.to_line().as_bytes());
+        }
+        self.streams.clear();
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Wraps a pcap capture and yields one JSON line per HTTP exchange.
+pub struct PcapReader<R: Read> {
+    /// The raw input until the capture header has been read
+    inner: Option<R>,
+    capture: Option<CaptureReader<R>>,
+    sessions: HttpSessions,
+    /// Decoded lines not yet handed out
+    buffer: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl<R: Read> PcapReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner: Some(inner),
+            capture: None,
+            sessions: HttpSessions::default(),
+            buffer: Vec::new(),
+            position: 0,
+            finished: false,
+        }
+    }
+
+    /// Decode the next packet into `buffer`. Returns false at end of input.
+    fn decode_packet(&mut self) -> io::Result<bool> {
+        if let Some(inner) = self.inner.take() {
+            let capture = CaptureReader::new(inner).map_err(|err| {
+                invalid_data(format!(
+                    "not a pcap capture ({}); pcapng files can be converted with 'editcap -F pcap'",
+                    err
+                ))
+            })?;
+            self.capture = Some(capture);
+        }
+        let Some(capture) = self.capture.as_mut() else {
+            return Ok(false);
+        };
+        let link = capture.header().datalink;
+
+        let packet = match capture.next_packet() {
+            Some(packet) => packet.map_err(|err| invalid_data(format!("pcap capture: {}", err)))?,
+            None => {
+                self.sessions.finish(&mut self.buffer);
+                self.capture = None;
+                return Ok(false);
+            }
+        };
+        let timestamp_us = packet.timestamp.as_micros() as i64;
+        if let Some(segment) = ip_packet(link, &packet.data).and_then(tcp_segment) {
+            self.sessions
+                .handle(&segment, timestamp_us, &mut self.buffer);
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for PcapReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position >= self.buffer.len() {
+            self.buffer.clear();
+            self.position = 0;
+            if self.finished {
+                return Ok(0);
+            }
+            if !self.decode_packet()? {
+                // Requests left unanswered at the end are flushed into the
+                // buffer alongside the end-of-capture signal
+                self.finished = true;
+            }
+        }
+
+        let available = &self.buffer[self.position..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Parser for the JSON lines written by [`PcapReader`]
+pub struct PcapParser {
+    inner: JsonlParser,
+}
+
+impl PcapParser {
+    pub fn new() -> Self {
+        Self {
+            inner: JsonlParser::new(),
+        }
+    }
+
+    pub fn new_without_auto_timestamp() -> Self {
+        Self {
+            inner: JsonlParser::new_without_auto_timestamp(),
+        }
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.inner = self.inner.with_strict(strict);
+        self
+    }
+}
+
+impl Default for PcapParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventParser for PcapParser {
+    fn parse(&self, line: &str) -> Result<Event> {
+        self.inner.parse(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT: [u8; 4] = [10, 0, 0, 1];
+    const SERVER: [u8; 4] = [10, 0, 0, 2];
+
+    /// An Ethernet/IPv4/TCP packet record
+    fn packet(ts_us: u64, from_client: bool, seq: u32, flags: u8, payload: &[u8]) -> Vec<u8> {
+        let (src, dst, sport, dport) = if from_client {
+            (CLIENT, SERVER, 40000u16, 80u16)
+        } else {
+            (SERVER, CLIENT, 80, 40000)
+        };
+        let mut frame = vec![0u8; 12];
+        frame.extend(ETHERTYPE_IPV4.to_be_bytes());
+        frame.extend([0x45, 0]);
+        frame.extend(((40 + payload.len()) as u16).to_be_bytes());
+        frame.extend([0, 0, 0x40, 0, 64, IP_PROTO_TCP, 0, 0]);
+        frame.extend(src);
+        frame.extend(dst);
+        frame.extend(sport.to_be_bytes());
+        frame.extend(dport.to_be_bytes());
+        frame.extend(seq.to_be_bytes());
+        frame.extend([0, 0, 0, 0, 0x50, flags, 0xFF, 0xFF, 0, 0, 0, 0]);
+        frame.extend(payload);
+
+        let mut record = Vec::new();
+        record.extend(((ts_us / 1_000_000) as u32).to_le_bytes());
+        record.extend(((ts_us % 1_000_000) as u32).to_le_bytes());
+        record.extend((frame.len() as u32).to_le_bytes());
+        record.extend((frame.len() as u32).to_le_bytes());
+        record.extend(frame);
+        record
+    }
+
+    fn capture(packets: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend(0xA1B2_C3D4u32.to_le_bytes());
+        bytes.extend(2u16.to_le_bytes());
+        bytes.extend(4u16.to_le_bytes());
+        bytes.extend([0; 8]);
+        bytes.extend(65535u32.to_le_bytes());
+        bytes.extend(1u32.to_le_bytes());
+        for packet in packets {
+            bytes.extend(packet);
+        }
+        bytes
+    }
+
+    fn decode(bytes: Vec<u8>) -> io::Result<Vec<Value>> {
+        let mut out = String::new();
+        PcapReader::new(io::Cursor::new(bytes)).read_to_string(&mut out)?;
+        Ok(out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect())
+    }
+
+    #[test]
+    fn test_reassembles_out_of_order_segments_and_skips_bodies() {
+        let request = b"POST /upload HTTP/1.1\r\nHost: a\r\nContent-Length: 5\r\n\r\nhello";
+        let response_head = b"HTTP/1.1 201 Created\r\nTransfer-Encoding: chunked\r\n\r\n";
+        let response_body = b"3\r\nabc\r\n0\r\n\r\n";
+        let second = b"GET /next HTTP/1.1\r\n\r\n";
+        let bytes = capture(&[
+            packet(1_000_000, true, 99, TCP_SYN, b""),
+            // The request arrives split, second half first
+            packet(1_000_200, true, 120, 0x18, &request[20..]),
+            packet(1_000_100, true, 100, 0x18, &request[..20]),
+            // A retransmission of the first half is ignored
+            packet(1_000_300, true, 100, 0x18, &request[..20]),
+            packet(1_000_400, false, 500, 0x18, response_head),
+            packet(
+                1_000_500,
+                false,
+                500 + response_head.len() as u32,
+                0x18,
+                response_body,
+            ),
+            packet(1_000_600, true, 100 + request.len() as u32, 0x18, second),
+        ]);
+
+        let events = decode(bytes).unwrap();
+        assert_eq!(events.len(), 2, "{:?}", events);
+        assert_eq!(events[0]["method"], "POST");
+        assert_eq!(events[0]["path"], "/upload");
+        assert_eq!(events[0]["status_code"], 201);
+        assert_eq!(events[0]["src_ip"], "10.0.0.1");
+        assert_eq!(events[0]["dst_port"], 80);
+        assert_eq!(events[0]["request_headers"]["content-length"], "5");
+        assert_eq!(
+            events[0]["response_headers"]["transfer-encoding"],
+            "chunked"
+        );
+        // Unanswered at the end of the capture
+        assert_eq!(events[1]["path"], "/next");
+        assert!(events[1].get("status_code").is_none());
+        assert_eq!(events[1]["timestamp_us"], 1_000_600);
+    }
+
+    #[test]
+    fn test_header_map_keeps_first_fifty_names() {
+        let mut request = String::from("GET / HTTP/1.0\r\nX-Dup: a\r\nx-dup: b\r\n");
+        for i in 0..60 {
+            request.push_str(&format!("X-H{}: {}\r\n", i, i));
+        }
+        request.push_str("\r\n");
+        let Ok(Some((_, Message::Request { headers, .. }))) = parse_head(request.as_bytes()) else {
+            panic!("request did not parse");
+        };
+        assert_eq!(headers.len(), MAX_HEADERS);
+        assert_eq!(headers["x-dup"], "a, b");
+        assert!(headers.contains_key("x-h48"));
+        assert!(!headers.contains_key("x-h49"));
+    }
+
+    #[test]
+    fn test_non_http_streams_and_bad_captures() {
+        let bytes = capture(&[packet(1, true, 1, 0x18, b"\x16\x03\x01\x02\x00 tls")]);
+        assert!(decode(bytes).unwrap().is_empty());
+
+        let err = decode(b"definitely not a capture".to_vec()).unwrap_err();
+        assert!(err.to_string().contains("not a pcap capture"), "{}", err);
+    }
+}
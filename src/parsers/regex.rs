@@ -1,5 +1,6 @@
 use crate::event::Event;
 use crate::parsers::type_conversion::{convert_value_to_type, FieldType, TypeMap};
+use crate::parsers::REST_FIELD;
 use crate::pipeline::EventParser;
 use anyhow::{Context, Result};
 use regex::Regex;
@@ -9,8 +10,11 @@ use rhai::Dynamic;
 #[derive(Debug)]
 pub struct RegexParser {
     regex: Regex,
+    /// Pattern with type annotations stripped, before anchoring
+    clean_pattern: String,
     type_map: TypeMap,
     strict: bool,
+    keep_rest: bool,
 }
 
 impl RegexParser {
@@ -43,11 +47,30 @@ impl RegexParser {
 
         Ok(Self {
             regex,
+            clean_pattern,
             type_map,
             strict: false,
+            keep_rest: false,
         })
     }
 
+    /// Keep the text after the match in a `_rest` field
+    ///
+    /// The pattern stays anchored at the start of the line but no longer has
+    /// to reach its end; the unmatched tail, with surrounding whitespace
+    /// trimmed, becomes `_rest`.
+    pub fn with_keep_rest(mut self, keep_rest: bool) -> Result<Self> {
+        let anchored_pattern = if keep_rest {
+            format!("^(?:{})", self.clean_pattern)
+        } else {
+            format!("^{}$", self.clean_pattern)
+        };
+        self.regex = Regex::new(&anchored_pattern)
+            .with_context(|| format!("Failed to compile regex pattern: {}", self.clean_pattern))?;
+        self.keep_rest = keep_rest;
+        Ok(self)
+    }
+
     /// Set strict mode for type conversions
     ///
     /// In strict mode:
@@ -228,7 +251,7 @@ impl RegexParser {
 
 impl EventParser for RegexParser {
     fn parse(&self, line: &str) -> Result<Event> {
-        // Regex is already anchored with ^...$ from constructor
+        // Regex is already anchored from constructor (^...$, or ^... with keep_rest)
         let captures = match self.regex.captures(line) {
             Some(caps) => caps,
             None => {
@@ -274,6 +297,18 @@ impl EventParser for RegexParser {
             }
         }
 
+        if self.keep_rest {
+            let rest = captures
+                .get(0)
+                .map_or("", |matched| &line[matched.end()..])
+                .trim();
+            if !rest.is_empty() {
+                event
+                    .fields
+                    .insert(REST_FIELD.to_string(), Dynamic::from(rest.to_string()));
+            }
+        }
+
         Ok(event)
     }
 }
@@ -671,4 +706,54 @@ mod tests {
         assert_eq!(event.fields.get("status").unwrap().as_int().unwrap(), 200);
         assert_eq!(event.fields.get("bytes").unwrap().as_int().unwrap(), 1234);
     }
+
+    fn rest_of(event: &Event) -> Option<String> {
+        event
+            .fields
+            .get(REST_FIELD)
+            .map(|value| value.clone().into_string().unwrap())
+    }
+
+    #[test]
+    fn test_keep_rest_stores_trimmed_unmatched_tail() {
+        let parser = RegexParser::new(r"(?P<ip>\S+) (?P<method:int>\d+)")
+            .unwrap()
+            .with_keep_rest(true)
+            .unwrap();
+        let event = parser.parse("10.0.0.1 404   /missing  page \t").unwrap();
+
+        assert_eq!(event.fields.get("method").unwrap().as_int().unwrap(), 404);
+        assert_eq!(rest_of(&event).as_deref(), Some("/missing  page"));
+    }
+
+    #[test]
+    fn test_keep_rest_still_anchors_at_line_start() {
+        let parser = RegexParser::new(r"(?P<level>[A-Z]+):")
+            .unwrap()
+            .with_keep_rest(true)
+            .unwrap();
+
+        assert!(parser.parse("x ERROR: boom").is_err());
+        let event = parser.parse("ERROR: boom").unwrap();
+        assert_eq!(rest_of(&event).as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_keep_rest_omitted_for_full_or_whitespace_tail() {
+        let parser = RegexParser::new(r"(?P<ip>\S+) (?P<msg>.*)")
+            .unwrap()
+            .with_keep_rest(true)
+            .unwrap();
+        assert_eq!(rest_of(&parser.parse("10.0.0.1 all of it").unwrap()), None);
+
+        let parser = RegexParser::new(r"(?P<word>\w+)")
+            .unwrap()
+            .with_keep_rest(true)
+            .unwrap();
+        assert_eq!(rest_of(&parser.parse("hello   ").unwrap()), None);
+
+        // Without keep_rest, a trailing remainder is still a mismatch
+        let parser = RegexParser::new(r"(?P<word>\w+)").unwrap();
+        assert!(parser.parse("hello world").is_err());
+    }
 }
@@ -0,0 +1,250 @@
+use crate::event::Event;
+use crate::parsers::type_conversion::{convert_value_to_type, parse_field_with_type, TypeMap};
+use crate::pipeline::EventParser;
+use anyhow::Result;
+
+/// Minimum number of sampled lines before SSV auto-detection is attempted
+const SSV_MIN_SAMPLE_LINES: usize = 3;
+/// Minimum column count for a line to count as tabular
+const SSV_MIN_COLUMNS: usize = 3;
+
+/// Parser for space-separated values, the layout of `ps`, `netstat`, `df` and
+/// most other Unix command output.
+///
+/// Columns are split on runs of whitespace. With a header (`--ssv-header`) the
+/// last named column takes the rest of the line verbatim, so a trailing
+/// free-text column like `ps aux`'s COMMAND keeps its spaces. Without one,
+/// every column becomes `c1`, `c2`, ... like `csvnh`.
+pub struct SsvParser {
+    header: Option<Vec<String>>,
+    type_map: TypeMap,
+    strict: bool,
+}
+
+impl SsvParser {
+    /// Create a parser; header entries accept `name:type` annotations
+    pub fn new(header: Option<Vec<String>>) -> Result<Self> {
+        let mut type_map = TypeMap::new();
+        let header = match header {
+            Some(specs) => {
+                let mut names = Vec::with_capacity(specs.len());
+                for spec in specs {
+                    let (name, field_type) =
+                        parse_field_with_type(&spec).map_err(|e| anyhow::anyhow!("{}", e))?;
+                    if name.is_empty() {
+                        return Err(anyhow::anyhow!("SSV header contains an empty column name"));
+                    }
+                    if let Some(field_type) = field_type {
+                        type_map.insert(name.clone(), field_type);
+                    }
+                    names.push(name);
+                }
+                if names.is_empty() {
+                    return Err(anyhow::anyhow!("SSV header needs at least one column"));
+                }
+                Some(names)
+            }
+            None => None,
+        };
+        Ok(Self {
+            header,
+            type_map,
+            strict: false,
+        })
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    fn convert(&self, name: &str, value: &str) -> Result<rhai::Dynamic> {
+        match self.type_map.get(name) {
+            Some(field_type) => {
+                convert_value_to_type(value, field_type, self.strict).map_err(|e| {
+                    anyhow::anyhow!(
+                        "Type conversion error for field '{}' (value: '{}'): {}",
+                        name,
+                        value,
+                        e
+                    )
+                })
+            }
+            None => Ok(rhai::Dynamic::from(value.to_string())),
+        }
+    }
+}
+
+/// Split off the next whitespace-delimited column, returning it and the
+/// remainder with leading whitespace removed
+fn next_column(text: &str) -> Option<(&str, &str)> {
+    if text.is_empty() {
+        return None;
+    }
+    match text.find(char::is_whitespace) {
+        Some(end) => Some((&text[..end], text[end..].trim_start())),
+        None => Some((text, "")),
+    }
+}
+
+impl EventParser for SsvParser {
+    fn parse(&self, line: &str) -> Result<Event> {
+        let mut event = Event::default_with_line(line.to_string());
+        let mut rest = line.trim();
+
+        match self.header {
+            Some(ref names) => {
+                for (idx, name) in names.iter().enumerate() {
+                    let value = if idx + 1 == names.len() {
+                        // Last column keeps the remainder, spaces and all
+                        (!rest.is_empty()).then_some(rest)
+                    } else {
+                        next_column(rest).map(|(value, remainder)| {
+                            rest = remainder;
+                            value
+                        })
+                    };
+                    match value {
+                        Some(value) => {
+                            event
+                                .fields
+                                .insert(name.clone(), self.convert(name, value)?);
+                        }
+                        None if self.strict => {
+                            return Err(anyhow::anyhow!(
+                                "SSV line has {} of {} columns",
+                                idx,
+                                names.len()
+                            ));
+                        }
+                        None => break,
+                    }
+                }
+            }
+            None => {
+                let mut idx = 0;
+                while let Some((value, remainder)) = next_column(rest) {
+                    idx += 1;
+                    event
+                        .fields
+                        .insert(format!("c{}", idx), rhai::Dynamic::from(value.to_string()));
+                    rest = remainder;
+                }
+            }
+        }
+
+        Ok(event)
+    }
+}
+
+/// True when the sampled lines look like aligned command output: at least 90%
+/// of them split into the same number (three or more) of whitespace-separated
+/// columns, and most are padded with runs of spaces or tabs.
+pub fn detect_ssv(lines: &[&str]) -> bool {
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+    if lines.len() < SSV_MIN_SAMPLE_LINES {
+        return false;
+    }
+
+    let mut counts: std::collections::HashMap<usize, usize> = std::collections::HashMap::new();
+    for line in &lines {
+        *counts.entry(line.split_whitespace().count()).or_insert(0) += 1;
+    }
+    let (columns, hits) = counts
+        .into_iter()
+        .max_by_key(|&(columns, hits)| (hits, columns))
+        .expect("at least one sampled line");
+    if columns < SSV_MIN_COLUMNS || hits * 10 < lines.len() * 9 {
+        return false;
+    }
+
+    let aligned = lines
+        .iter()
+        .filter(|line| line.contains("  ") || line.contains('\t'))
+        .count();
+    aligned * 2 > lines.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(event: &Event, name: &str) -> String {
+        event.fields.get(name).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_ssv_without_header_numbers_columns() {
+        let parser = SsvParser::new(None).unwrap();
+        let event = parser
+            .parse("tcp        0      0 0.0.0.0:22     0.0.0.0:*     LISTEN")
+            .unwrap();
+        assert_eq!(event.fields.len(), 6);
+        assert_eq!(field(&event, "c1"), "tcp");
+        assert_eq!(field(&event, "c4"), "0.0.0.0:22");
+        assert_eq!(field(&event, "c6"), "LISTEN");
+    }
+
+    #[test]
+    fn test_ssv_header_last_column_takes_rest() {
+        let parser = SsvParser::new(Some(vec![
+            "user".to_string(),
+            "pid:int".to_string(),
+            "command".to_string(),
+        ]))
+        .unwrap();
+        let event = parser
+            .parse("root         1  /sbin/init splash --debug")
+            .unwrap();
+        assert_eq!(field(&event, "user"), "root");
+        assert_eq!(event.fields.get("pid").unwrap().as_int().unwrap(), 1);
+        assert_eq!(field(&event, "command"), "/sbin/init splash --debug");
+    }
+
+    #[test]
+    fn test_ssv_short_line_resilient_and_strict() {
+        let header = Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        let event = SsvParser::new(header.clone())
+            .unwrap()
+            .parse("one two")
+            .unwrap();
+        assert_eq!(field(&event, "b"), "two");
+        assert!(!event.fields.contains_key("c"));
+
+        let strict = SsvParser::new(header).unwrap().with_strict(true);
+        assert!(strict.parse("one two").is_err());
+    }
+
+    #[test]
+    fn test_ssv_rejects_bad_header() {
+        assert!(SsvParser::new(Some(vec!["pid:number".to_string()])).is_err());
+        assert!(SsvParser::new(Some(vec![])).is_err());
+    }
+
+    #[test]
+    fn test_detect_ssv() {
+        let df = [
+            "Filesystem     1K-blocks     Used Available Use% Mounted",
+            "/dev/sda1       41152736 18356104  20683812  48% /",
+            "tmpfs            8156132        0   8156132   0% /dev/shm",
+            "/dev/sdb1      103081248 60123412  37698572  62% /data",
+        ];
+        assert!(detect_ssv(&df));
+
+        let prose = [
+            "Starting the service",
+            "Listening on port 8080 for requests",
+            "Shutting down",
+        ];
+        assert!(!detect_ssv(&prose));
+
+        // Consistent counts alone are not enough without column padding
+        let words = ["alpha beta gamma", "one two three", "red green blue"];
+        assert!(!detect_ssv(&words));
+        assert!(!detect_ssv(&df[..2]));
+    }
+}
@@ -1,6 +1,9 @@
+use chrono::Duration;
 use rhai::Dynamic;
 use std::collections::HashMap;
 
+use crate::rhai_functions::datetime::{to_duration, DurationWrapper};
+
 /// Supported type annotations for field conversion
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FieldType {
@@ -208,6 +211,46 @@ pub fn convert_value_to_type(
     }
 }
 
+/// Interpret a field value as a duration for `--parse-durations`.
+///
+/// Strings in `to_duration()` syntax (`45ms`, `1m 30s`) and bare numbers,
+/// taken as seconds (`request_time=0.123`), are converted; the original text
+/// is kept so output renders the value the way it was written. Returns `None`
+/// for anything else, including values that already are durations.
+pub fn detect_duration(value: &Dynamic) -> Option<DurationWrapper> {
+    let seconds_to_duration = |seconds: f64| {
+        let nanos = (seconds * 1_000_000_000.0).round();
+        (nanos.is_finite() && nanos.abs() <= i64::MAX as f64)
+            .then(|| DurationWrapper::new(Duration::nanoseconds(nanos as i64)))
+    };
+
+    if value.is_int() {
+        let seconds = value.as_int().ok()?;
+        return Duration::try_seconds(seconds)
+            .map(|dur| DurationWrapper::new(dur).with_original(seconds.to_string()));
+    }
+    if value.is_float() {
+        let seconds = value.as_float().ok()?;
+        return seconds_to_duration(seconds).map(|dur| dur.with_original(seconds.to_string()));
+    }
+
+    if !value.is_string() {
+        return None;
+    }
+    let text = value.clone().into_string().ok()?;
+    let trimmed = text.trim();
+    if looks_like_json_number(trimmed) {
+        let seconds = trimmed.parse::<f64>().ok()?;
+        return seconds_to_duration(seconds).map(|dur| dur.with_original(trimmed));
+    }
+    if !trimmed.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    to_duration(trimmed)
+        .ok()
+        .map(|dur| dur.with_original(trimmed))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,6 +437,36 @@ mod tests {
         assert_eq!(result.clone().into_string().unwrap(), "anything");
     }
 
+    #[test]
+    fn test_detect_duration() {
+        let took = detect_duration(&Dynamic::from("45ms".to_string())).unwrap();
+        assert_eq!(took.inner, Duration::milliseconds(45));
+        assert_eq!(took.to_string(), "45ms");
+
+        let elapsed = detect_duration(&Dynamic::from("1m 30s".to_string())).unwrap();
+        assert_eq!(elapsed.inner, Duration::seconds(90));
+
+        // Bare numbers are seconds, whether typed or still text
+        let request_time = detect_duration(&Dynamic::from(0.123f64)).unwrap();
+        assert_eq!(request_time.inner, Duration::milliseconds(123));
+        assert_eq!(request_time.to_string(), "0.123");
+        let latency = detect_duration(&Dynamic::from("2".to_string())).unwrap();
+        assert_eq!(latency.inner, Duration::seconds(2));
+        assert_eq!(
+            detect_duration(&Dynamic::from(5i64)).unwrap().inner,
+            Duration::seconds(5)
+        );
+
+        for value in ["slow", "ms", "", "45 parsecs", "007"] {
+            assert!(
+                detect_duration(&Dynamic::from(value.to_string())).is_none(),
+                "{value:?} should not be a duration"
+            );
+        }
+        assert!(detect_duration(&Dynamic::from(true)).is_none());
+        assert!(detect_duration(&Dynamic::from(took)).is_none());
+    }
+
     proptest! {
         #[test]
         fn prop_convert_int_roundtrip(value in any::<i64>()) {
@@ -0,0 +1,436 @@
+//! XML record exports (`-f xml-records`).
+//!
+//! Many systems export logs as one XML document whose root holds a run of
+//! record elements: `<Records><Record>...</Record><Record>...</Record></Records>`.
+//! As with the binary capture formats, the document is decoded at the reader
+//! level: [`XmlRecordsReader`] streams the children of the root element and
+//! turns each one into a single-line JSON object, which [`XmlRecordsParser`]
+//! then parses into an event.
+//!
+//! Within a record, attributes become fields, text content becomes `_text`,
+//! and child elements become nested maps keyed by element name (repeated
+//! names collect into an array). A child with only text and no attributes or
+//! children collapses to its text. Nesting deeper than [`MAX_DEPTH`] levels is
+//! kept as the text it contains.
+
+use std::io::{self, BufRead, BufReader, Read};
+
+use anyhow::Result;
+use quick_xml::events::{BytesStart, Event as XmlEvent};
+use serde_json::{Map, Value};
+
+use crate::event::Event;
+use crate::parsers::JsonlParser;
+use crate::pipeline::EventParser;
+
+/// Field holding an element's text content
+pub const TEXT_FIELD: &str = "_text";
+
+/// Element levels below a record that are turned into nested maps
+pub const MAX_DEPTH: usize = 10;
+
+/// Lines sampled when auto-detecting an XML record export
+pub const DETECTION_SAMPLE_LINES: usize = 100;
+
+/// Which elements hold the records (`--xml-root-element`,
+/// `--xml-record-element`). `None` accepts any name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XmlRecordsOptions {
+    pub root: Option<String>,
+    pub record: Option<String>,
+}
+
+/// Streams an XML record export as one JSON object line per record.
+pub struct XmlRecordsReader<R: Read> {
+    xml: quick_xml::Reader<BufReader<R>>,
+    options: XmlRecordsOptions,
+    in_root: bool,
+    /// Decoded line not yet handed out
+    buffer: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl<R: Read> XmlRecordsReader<R> {
+    pub fn new(inner: R, options: XmlRecordsOptions) -> Self {
+        Self {
+            xml: quick_xml::Reader::from_reader(BufReader::new(inner)),
+            options,
+            in_root: false,
+            buffer: Vec::new(),
+            position: 0,
+            finished: false,
+        }
+    }
+
+    /// Decode the next record into `buffer`. Returns false at end of input.
+    fn decode_record(&mut self) -> io::Result<bool> {
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            let (start, empty) = match self.xml.read_event_into(&mut buf).map_err(xml_error)? {
+                XmlEvent::Start(start) => (start.into_owned(), false),
+                XmlEvent::Empty(start) => (start.into_owned(), true),
+                // The root closed; a concatenated document may follow
+                XmlEvent::End(_) => {
+                    self.in_root = false;
+                    continue;
+                }
+                XmlEvent::Eof => return Ok(false),
+                _ => continue,
+            };
+            let name = element_name(&start);
+
+            if !self.in_root {
+                if let Some(root) = self.options.root.as_deref() {
+                    if name != root {
+                        return Err(invalid_data(format!(
+                            "expected root element <{}>, found <{}>",
+                            root, name
+                        )));
+                    }
+                }
+                // An empty root holds no records
+                self.in_root = !empty;
+                continue;
+            }
+
+            if self
+                .options
+                .record
+                .as_deref()
+                .is_some_and(|record| name != record)
+            {
+                if !empty {
+                    self.xml
+                        .read_to_end_into(start.to_end().name(), &mut Vec::new())
+                        .map_err(xml_error)?;
+                }
+                continue;
+            }
+
+            let record = if empty {
+                element_value(&start, Map::new(), String::new(), false)?
+            } else {
+                read_element(&mut self.xml, &start, 0, false)?
+            };
+            self.buffer.extend_from_slice(record.to_string().as_bytes());
+            self.buffer.push(b'\n');
+            return Ok(true);
+        }
+    }
+}
+
+impl<R: Read> Read for XmlRecordsReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position >= self.buffer.len() {
+            self.buffer.clear();
+            self.position = 0;
+            if self.finished || !self.decode_record()? {
+                self.finished = true;
+                return Ok(0);
+            }
+        }
+
+        let available = &self.buffer[self.position..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Read the content of `start` up to its end tag. `depth` counts the levels
+/// below the record; records themselves never collapse to plain text.
+fn read_element<B: BufRead>(
+    xml: &mut quick_xml::Reader<B>,
+    start: &BytesStart,
+    depth: usize,
+    collapse: bool,
+) -> io::Result<Value> {
+    let mut children = Map::new();
+    let mut text = String::new();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf).map_err(xml_error)? {
+            XmlEvent::Start(child) => {
+                let child = child.into_owned();
+                let value = if depth + 1 >= MAX_DEPTH {
+                    Value::String(read_text(xml, &child)?)
+                } else {
+                    read_element(xml, &child, depth + 1, true)?
+                };
+                insert_child(&mut children, element_name(&child), value);
+            }
+            XmlEvent::Empty(child) => {
+                let value = element_value(&child, Map::new(), String::new(), true)?;
+                insert_child(&mut children, element_name(&child), value);
+            }
+            XmlEvent::Text(t) => text.push_str(&t.unescape().map_err(xml_error)?),
+            XmlEvent::CData(data) => text.push_str(&String::from_utf8_lossy(&data)),
+            XmlEvent::End(_) => break,
+            XmlEvent::Eof => {
+                return Err(invalid_data(format!(
+                    "input ended inside <{}>",
+                    element_name(start)
+                )))
+            }
+            _ => {}
+        }
+    }
+    element_value(start, children, text, collapse)
+}
+
+/// Everything inside an element too deep to map, as plain text.
+fn read_text<B: BufRead>(xml: &mut quick_xml::Reader<B>, start: &BytesStart) -> io::Result<String> {
+    let mut text = String::new();
+    let mut depth = 0usize;
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf).map_err(xml_error)? {
+            XmlEvent::Start(_) => depth += 1,
+            XmlEvent::End(_) if depth == 0 => break,
+            XmlEvent::End(_) => depth -= 1,
+            XmlEvent::Text(t) => text.push_str(&t.unescape().map_err(xml_error)?),
+            XmlEvent::CData(data) => text.push_str(&String::from_utf8_lossy(&data)),
+            XmlEvent::Eof => {
+                return Err(invalid_data(format!(
+                    "input ended inside <{}>",
+                    element_name(start)
+                )))
+            }
+            _ => {}
+        }
+    }
+    Ok(text.trim().to_string())
+}
+
+/// Combine an element's attributes, children and text into its value.
+fn element_value(
+    start: &BytesStart,
+    children: Map<String, Value>,
+    text: String,
+    collapse: bool,
+) -> io::Result<Value> {
+    let mut fields = Map::new();
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(xml_error)?;
+        let key = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+        let value = attribute.unescape_value().map_err(xml_error)?.into_owned();
+        fields.insert(key, Value::String(value));
+    }
+    let text = text.trim();
+    if collapse && fields.is_empty() && children.is_empty() {
+        return Ok(Value::String(text.to_string()));
+    }
+    for (name, value) in children {
+        fields.insert(name, value);
+    }
+    if !text.is_empty() {
+        fields.insert(TEXT_FIELD.to_string(), Value::String(text.to_string()));
+    }
+    Ok(Value::Object(fields))
+}
+
+/// Add a child under its name; a repeated name turns the entry into an array.
+fn insert_child(children: &mut Map<String, Value>, name: String, value: Value) {
+    match children.get_mut(&name) {
+        Some(Value::Array(items)) => items.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            children.insert(name, value);
+        }
+    }
+}
+
+fn element_name(start: &BytesStart) -> String {
+    String::from_utf8_lossy(start.name().as_ref()).into_owned()
+}
+
+fn xml_error(err: impl std::fmt::Display) -> io::Error {
+    invalid_data(format!("invalid XML: {}", err))
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("xml-records: {}", message),
+    )
+}
+
+/// Whether the opening lines of an input look like an XML record export: an
+/// XML declaration, then a root element whose first children repeat a name.
+pub fn looks_like_xml_records(lines: &[&str]) -> bool {
+    if !lines
+        .first()
+        .is_some_and(|line| line.trim_start().starts_with("<?xml"))
+    {
+        return false;
+    }
+
+    let sample = lines.join("\n");
+    let mut xml = quick_xml::Reader::from_str(&sample);
+    let mut depth = 0usize;
+    let mut child_names: Vec<String> = Vec::new();
+    // The sample usually ends mid-document, so stop at the first error
+    while let Ok(event) = xml.read_event() {
+        match event {
+            XmlEvent::Start(start) => {
+                if depth == 1 {
+                    child_names.push(element_name(&start));
+                }
+                depth += 1;
+            }
+            XmlEvent::Empty(start) if depth == 1 => child_names.push(element_name(&start)),
+            XmlEvent::End(_) => depth = depth.saturating_sub(1),
+            XmlEvent::Eof => break,
+            _ => {}
+        }
+        if child_names.len() >= 2 {
+            break;
+        }
+    }
+    child_names.len() >= 2 && child_names[0] == child_names[1]
+}
+
+/// Parses the JSON lines produced by [`XmlRecordsReader`].
+pub struct XmlRecordsParser {
+    inner: JsonlParser,
+}
+
+impl XmlRecordsParser {
+    pub fn new() -> Self {
+        Self {
+            inner: JsonlParser::new(),
+        }
+    }
+
+    pub fn new_without_auto_timestamp() -> Self {
+        Self {
+            inner: JsonlParser::new_without_auto_timestamp(),
+        }
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.inner = self.inner.with_strict(strict);
+        self
+    }
+}
+
+impl EventParser for XmlRecordsParser {
+    fn parse(&self, line: &str) -> Result<Event> {
+        self.inner.parse(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(xml: &str, options: XmlRecordsOptions) -> io::Result<Vec<Value>> {
+        let mut out = String::new();
+        XmlRecordsReader::new(io::Cursor::new(xml.as_bytes().to_vec()), options)
+            .read_to_string(&mut out)?;
+        Ok(out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect())
+    }
+
+    const EXPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Records exported="2024-05-01">
+  <Record id="1" level="INFO">
+    <Time>2024-05-01T10:00:00Z</Time>
+    <Message>service &amp; workers started</Message>
+    <Source host="web-1"><Port>8080</Port></Source>
+  </Record>
+  <!-- maintenance window -->
+  <Record id="2" level="WARN">
+    <Time>2024-05-01T10:00:05Z</Time>
+    <Tag>disk</Tag>
+    <Tag>slow</Tag>
+    <Message><![CDATA[latency > 2s]]></Message>
+  </Record>
+  <Record id="3"/>
+</Records>
+"#;
+
+    #[test]
+    fn test_xml_records_map_attributes_text_and_children() {
+        let records = decode(EXPORT, XmlRecordsOptions::default()).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            records[0],
+            serde_json::json!({
+                "id": "1",
+                "level": "INFO",
+                "Time": "2024-05-01T10:00:00Z",
+                "Message": "service & workers started",
+                "Source": {"host": "web-1", "Port": "8080"},
+            })
+        );
+        assert_eq!(records[1]["Tag"], serde_json::json!(["disk", "slow"]));
+        assert_eq!(records[1]["Message"], "latency > 2s");
+        assert_eq!(records[2], serde_json::json!({"id": "3"}));
+    }
+
+    #[test]
+    fn test_xml_records_text_content_and_depth_limit() {
+        let mut deep = String::from("<Root><Rec>note");
+        for level in 0..12 {
+            deep.push_str(&format!("<L{}>", level));
+        }
+        deep.push_str("bottom");
+        for level in (0..12).rev() {
+            deep.push_str(&format!("</L{}>", level));
+        }
+        deep.push_str("</Rec><Rec>x</Rec></Root>");
+
+        let records = decode(&deep, XmlRecordsOptions::default()).unwrap();
+        assert_eq!(records[0][TEXT_FIELD], "note");
+        let mut value = &records[0]["L0"];
+        for level in 1..MAX_DEPTH {
+            value = &value[format!("L{}", level)];
+        }
+        // Level 10 and below are flattened to their text
+        assert_eq!(value, &Value::String("bottom".to_string()));
+        assert_eq!(records[1], serde_json::json!({ TEXT_FIELD: "x" }));
+    }
+
+    #[test]
+    fn test_xml_records_root_and_record_element_options() {
+        let xml = "<Log><Event n=\"1\"/><Header v=\"2\"/><Event n=\"3\"/></Log>";
+        let options = XmlRecordsOptions {
+            root: Some("Log".to_string()),
+            record: Some("Event".to_string()),
+        };
+        let records = decode(xml, options).unwrap();
+        let ns: Vec<_> = records.iter().map(|r| r["n"].clone()).collect();
+        assert_eq!(ns, vec!["1", "3"]);
+
+        let options = XmlRecordsOptions {
+            root: Some("Records".to_string()),
+            record: None,
+        };
+        let err = decode(xml, options).unwrap_err();
+        assert!(err.to_string().contains("expected root element <Records>"));
+    }
+
+    #[test]
+    fn test_looks_like_xml_records() {
+        let lines: Vec<&str> = EXPORT.lines().collect();
+        assert!(looks_like_xml_records(&lines));
+        assert!(!looks_like_xml_records(&[
+            "<?xml version=\"1.0\"?>",
+            "<config><a/><b/></config>"
+        ]));
+        assert!(!looks_like_xml_records(&[
+            "<Records><Record/><Record/></Records>"
+        ]));
+    }
+}
@@ -19,6 +19,8 @@ impl TimestampConfiguredParser {
         ts_field: Option<String>,
         ts_format: Option<String>,
         default_timezone: Option<String>,
+        tz_field: Option<String>,
+        assume_year: Option<i32>,
     ) -> Self {
         Self {
             inner,
@@ -26,6 +28,8 @@ impl TimestampConfiguredParser {
                 custom_field: ts_field,
                 custom_format: ts_format,
                 default_timezone,
+                tz_field,
+                assume_year,
             },
         }
     }
@@ -41,16 +45,20 @@ impl EventParser for TimestampConfiguredParser {
 }
 
 use super::{
-    create_multiline_chunker, AssertStage, BeginStage, CsvChunker, DrainStage, EndStage,
-    EventLimiter, EventParser, ExecStage, FilterStage, Formatter, KeyFilterStage, LevelFilterStage,
-    MetaData, Pipeline, PipelineConfig, PipelineContext, ScriptStage, SimpleChunker,
-    SimpleWindowManager, SlidingWindowManager, StdoutWriter, TakeNLimiter,
-    TimestampConversionStage, TimestampFilterStage,
+    create_multiline_chunker, register_profiled_stages, AssertStage, BeginStage, ClusterStage,
+    ConsecutiveGroupStage, CsvChunker, DrainStage, DropWhileStage, DurationFieldsStage, EndStage,
+    EventLimiter, EventParser, ExecStage, ExplodeStage, FieldPrefixAddStage, FieldPrefixDropStage,
+    FilenameFieldsStage, FilterStage, Formatter, JsonFieldExpandStage, KeyFilterStage,
+    LevelFilterStage, LevelSelectStage, MergeFieldsStage, MetaData, MonotonicCheckStage, Pipeline,
+    PipelineConfig, PipelineContext, ReservoirSampler, SchemaValidateStage, ScriptStage,
+    SimpleChunker, SimpleWindowManager, SlidingWindowManager, StdoutWriter, TakeNLimiter,
+    TakeWhileStage, TimestampConversionStage, TimestampFilterStage, WindowEmitStage,
 };
 use crate::engine::{DebugConfig, RhaiEngine};
 use crate::readers::MultiFileReader;
 use crate::rhai_functions::file_ops::{self, RuntimeConfig};
 use crate::rhai_functions::hashing;
+use crate::rhai_functions::safety::SandboxPolicy;
 
 /// Build a parser for a single cascade member. Handles the schema-less formats
 /// plus the spec-based `cols:`/`regex:` parsers (reachable via repeated `-f`).
@@ -65,10 +73,16 @@ fn build_cascade_member_parser(
         crate::config::InputFormat::Json => {
             if custom_ts_config {
                 Box::new(
-                    crate::parsers::JsonlParser::new_without_auto_timestamp().with_strict(strict),
+                    crate::parsers::JsonlParser::new_without_auto_timestamp()
+                        .with_strict(strict)
+                        .with_objects_only(true),
                 )
             } else {
-                Box::new(crate::parsers::JsonlParser::new().with_strict(strict))
+                Box::new(
+                    crate::parsers::JsonlParser::new()
+                        .with_strict(strict)
+                        .with_objects_only(true),
+                )
             }
         }
         crate::config::InputFormat::Line => Box::new(crate::parsers::LineParser::new()),
@@ -96,6 +110,15 @@ fn build_cascade_member_parser(
                 Box::new(crate::parsers::CefParser::new().with_strict(strict))
             }
         }
+        crate::config::InputFormat::Leef => {
+            if custom_ts_config {
+                Box::new(
+                    crate::parsers::LeefParser::new_without_auto_timestamp().with_strict(strict),
+                )
+            } else {
+                Box::new(crate::parsers::LeefParser::new().with_strict(strict))
+            }
+        }
         crate::config::InputFormat::Combined => {
             if custom_ts_config {
                 Box::new(crate::parsers::CombinedParser::new_without_auto_timestamp()?)
@@ -103,6 +126,13 @@ fn build_cascade_member_parser(
                 Box::new(crate::parsers::CombinedParser::new()?)
             }
         }
+        crate::config::InputFormat::Haproxy => {
+            if custom_ts_config {
+                Box::new(crate::parsers::HaproxyParser::new_without_auto_timestamp()?)
+            } else {
+                Box::new(crate::parsers::HaproxyParser::new()?)
+            }
+        }
         crate::config::InputFormat::Named(fmt) => {
             Box::new(crate::parsers::MultiRegexParser::new(fmt.patterns, strict)?)
         }
@@ -150,9 +180,18 @@ pub struct PipelineBuilder {
     config: PipelineConfig,
     begin: Option<String>,
     end: Option<String>,
+    begin_timeout_ms: Option<u64>,
+    end_timeout_ms: Option<u64>,
     input_format: crate::config::InputFormat,
+    fallback_format: Option<crate::config::InputFormat>,
     output_format: crate::OutputFormat,
     take_limit: Option<usize>,
+    take_while: Option<String>,
+    check_monotonic: Option<crate::cli::MonotonicCheck>,
+    group_consecutive_by: Option<String>,
+    group_aggregate: Option<crate::cli::GroupAggregate>,
+    drop_while: Option<String>,
+    reservoir: Option<usize>,
     keys: Vec<String>,
     exclude_keys: Vec<String>,
     // Fallback level filters when stages don't include explicit level entries
@@ -160,6 +199,7 @@ pub struct PipelineBuilder {
     exclude_levels: Vec<String>,
     multiline: Option<crate::config::MultilineConfig>,
     window_size: usize,
+    window_emit: crate::config::WindowEmitMode,
     csv_headers: Option<Vec<String>>, // Pre-processed CSV headers for parallel mode
     timestamp_filter: Option<crate::config::TimestampFilterConfig>,
     normalize_timestamps: bool,
@@ -168,15 +208,58 @@ pub struct PipelineBuilder {
     ts_field: Option<String>,
     ts_format: Option<String>,
     default_timezone: Option<String>,
+    tz_field: Option<String>,
+    assume_year: Option<i32>,
     extract_prefix: Option<String>,
     prefix_sep: String,
+    strip_prefix: Option<crate::config::PrefixStrip>,
+    prefix_field: Option<String>,
     cols_spec: Option<String>,
     cols_sep: Option<String>,
+    ssv_header: Option<Vec<String>>,
+    keep_rest: bool,
+    json_numbers_as_strings: bool,
+    strict_json: bool,
+    dup_keys: crate::config::DupKeyPolicy,
     context_config: crate::config::ContextConfig,
     span: Option<crate::config::SpanConfig>,
+    every: Option<crate::config::EveryConfig>,
+    thin: Option<crate::config::ThinConfig>,
     strict: bool,
     state_available: bool,
     csv_type_map: Option<TypeMap>,
+    ecs_version: crate::cli::EcsVersion,
+    fluentd_tag: String,
+    color_theme: crate::cli::ColorTheme,
+    color_parts: Vec<crate::cli::ColorPart>,
+    color_json_values: bool,
+    table_boxed: bool,
+    max_field_length: Option<usize>,
+    json_flat: Option<char>,
+    output_file: Option<String>,
+    orc_stripe_rows: usize,
+    cbor_framed: bool,
+    explode: Option<crate::config::ExplodeConfig>,
+    cluster: Option<crate::cluster::ClusterConfig>,
+    field_prefix_drop: Vec<String>,
+    collision: crate::config::CollisionPolicy,
+    field_prefix_add: Option<String>,
+    field_expand: Option<crate::config::FieldExpandConfig>,
+    field_from_filename: Option<regex::Regex>,
+    merge_fields: Vec<crate::config::MergeFieldsSpec>,
+    merge_fields_skip_missing: bool,
+    duration_fields: Option<Vec<String>>,
+    level_selection: Option<crate::config::LevelSelection>,
+    level_strict: bool,
+    schema: Option<super::schema::SchemaValidator>,
+    schema_on_fail: crate::cli::SchemaOnFail,
+    /// --stage-label names, indexed by exec stage position
+    exec_labels: Vec<Option<String>>,
+    /// --script-arg values exposed to begin/end scripts as `argv`
+    script_args: Vec<String>,
+    exec_on_error: Option<String>,
+    /// Cancellation token of the run this pipeline belongs to
+    cancel: crate::platform::CancelToken,
 }
 
 impl PipelineBuilder {
@@ -194,7 +277,9 @@ impl PipelineBuilder {
 
         let custom_ts_config = self.ts_field.is_some()
             || effective_ts_format.is_some()
-            || self.default_timezone.is_some();
+            || self.default_timezone.is_some()
+            || self.tz_field.is_some()
+            || self.assume_year.is_some();
 
         let base_parser: Box<dyn EventParser> = match self.input_format {
             crate::config::InputFormat::Auto => {
@@ -204,23 +289,32 @@ impl PipelineBuilder {
             }
             crate::config::InputFormat::AutoPerFile => Box::new(crate::parsers::LineParser::new()),
             crate::config::InputFormat::Json => {
-                if custom_ts_config {
-                    Box::new(
-                        crate::parsers::JsonlParser::new_without_auto_timestamp()
-                            .with_strict(self.strict),
-                    )
+                let parser = if custom_ts_config {
+                    crate::parsers::JsonlParser::new_without_auto_timestamp()
                 } else {
-                    Box::new(crate::parsers::JsonlParser::new().with_strict(self.strict))
-                }
+                    crate::parsers::JsonlParser::new()
+                };
+                Box::new(
+                    parser
+                        .with_strict(self.strict)
+                        .with_numbers_as_strings(self.json_numbers_as_strings)
+                        .with_dup_keys(self.dup_keys)
+                        .with_objects_only(self.strict_json),
+                )
             }
             crate::config::InputFormat::Line => Box::new(crate::parsers::LineParser::new()),
             crate::config::InputFormat::Raw => Box::new(crate::parsers::RawParser::new()),
             crate::config::InputFormat::Logfmt => {
-                if custom_ts_config {
-                    Box::new(crate::parsers::LogfmtParser::new_without_auto_timestamp())
+                let parser = if custom_ts_config {
+                    crate::parsers::LogfmtParser::new_without_auto_timestamp()
                 } else {
-                    Box::new(crate::parsers::LogfmtParser::new())
-                }
+                    crate::parsers::LogfmtParser::new()
+                };
+                Box::new(
+                    parser
+                        .with_keep_rest(self.keep_rest)
+                        .with_dup_keys(self.dup_keys),
+                )
             }
             crate::config::InputFormat::Syslog => {
                 if custom_ts_config {
@@ -239,6 +333,16 @@ impl PipelineBuilder {
                     Box::new(crate::parsers::CefParser::new().with_strict(self.strict))
                 }
             }
+            crate::config::InputFormat::Leef => {
+                if custom_ts_config {
+                    Box::new(
+                        crate::parsers::LeefParser::new_without_auto_timestamp()
+                            .with_strict(self.strict),
+                    )
+                } else {
+                    Box::new(crate::parsers::LeefParser::new().with_strict(self.strict))
+                }
+            }
             crate::config::InputFormat::Csv(ref field_spec) => {
                 let mut parser = if let Some(ref headers) = self.csv_headers {
                     crate::parsers::CsvParser::new_csv_with_headers(headers.clone())
@@ -342,22 +446,65 @@ impl PipelineBuilder {
                     Box::new(crate::parsers::CombinedParser::new()?)
                 }
             }
+            crate::config::InputFormat::Haproxy => {
+                if custom_ts_config {
+                    Box::new(crate::parsers::HaproxyParser::new_without_auto_timestamp()?)
+                } else {
+                    Box::new(crate::parsers::HaproxyParser::new()?)
+                }
+            }
             crate::config::InputFormat::Cols(_) => {
                 if let Some(ref spec) = self.cols_spec {
                     Box::new(
                         crate::parsers::ColsParser::new(spec.clone(), self.cols_sep.clone())
-                            .with_strict(self.strict),
+                            .with_strict(self.strict)
+                            .with_keep_rest(self.keep_rest),
                     )
                 } else {
                     return Err(anyhow::anyhow!("Cols format requires a specification"));
                 }
             }
-            crate::config::InputFormat::Regex(ref pattern) => {
-                Box::new(crate::parsers::RegexParser::new(pattern)?.with_strict(self.strict))
+            crate::config::InputFormat::Regex(ref pattern) => Box::new(
+                crate::parsers::RegexParser::new(pattern)?
+                    .with_strict(self.strict)
+                    .with_keep_rest(self.keep_rest)?,
+            ),
+            crate::config::InputFormat::Ssv => Box::new(
+                crate::parsers::SsvParser::new(self.ssv_header.clone())?.with_strict(self.strict),
+            ),
+            crate::config::InputFormat::FixedWidth(ref spec) => {
+                Box::new(crate::parsers::FixedWidthParser::new(spec)?.with_strict(self.strict))
             }
             crate::config::InputFormat::Named(fmt) => Box::new(
                 crate::parsers::MultiRegexParser::new(fmt.patterns, self.strict)?,
             ),
+            crate::config::InputFormat::Netflow(_) => {
+                Box::new(crate::parsers::NetflowParser::new())
+            }
+            crate::config::InputFormat::Pcap => {
+                let parser = if custom_ts_config {
+                    crate::parsers::PcapParser::new_without_auto_timestamp()
+                } else {
+                    crate::parsers::PcapParser::new()
+                };
+                Box::new(parser.with_strict(self.strict))
+            }
+            crate::config::InputFormat::XmlRecords(_) => {
+                let parser = if custom_ts_config {
+                    crate::parsers::XmlRecordsParser::new_without_auto_timestamp()
+                } else {
+                    crate::parsers::XmlRecordsParser::new()
+                };
+                Box::new(parser.with_strict(self.strict))
+            }
+            crate::config::InputFormat::Sqlite(_) => {
+                let parser = if custom_ts_config {
+                    crate::parsers::JsonlParser::new_without_auto_timestamp()
+                } else {
+                    crate::parsers::JsonlParser::new()
+                };
+                Box::new(parser.with_strict(self.strict))
+            }
             crate::config::InputFormat::Cascade(ref formats) => build_cascading_parser(
                 formats,
                 custom_ts_config,
@@ -366,14 +513,33 @@ impl PipelineBuilder {
             )?,
         };
 
-        let parser_with_prefix: Box<dyn EventParser> = if self.extract_prefix.is_some() {
-            let prefix_extractor = super::PrefixExtractor::new(
-                self.extract_prefix.clone().unwrap(),
-                self.prefix_sep.clone(),
-            );
+        let base_parser: Box<dyn EventParser> = match self.fallback_format {
+            Some(ref fallback) => Box::new(crate::parsers::FallbackParser::new(
+                base_parser,
+                build_cascade_member_parser(
+                    fallback,
+                    custom_ts_config,
+                    self.strict,
+                    self.cols_sep.as_deref(),
+                )?,
+            )),
+            None => base_parser,
+        };
+
+        let prefix_extractor = if let Some(ref strip) = self.strip_prefix {
+            Some(super::PrefixExtractor::stripping(
+                strip.clone(),
+                self.prefix_field.clone(),
+            ))
+        } else {
+            self.extract_prefix
+                .clone()
+                .map(|field| super::PrefixExtractor::new(field, self.prefix_sep.clone()))
+        };
+        let parser_with_prefix: Box<dyn EventParser> = if prefix_extractor.is_some() {
             Box::new(super::PrefixExtractingParser::new(
                 base_parser,
-                Some(prefix_extractor),
+                prefix_extractor,
             ))
         } else {
             base_parser
@@ -385,6 +551,8 @@ impl PipelineBuilder {
                 self.ts_field.clone(),
                 effective_ts_format,
                 self.default_timezone.clone(),
+                self.tz_field.clone(),
+                self.assume_year,
             ))
         } else {
             parser_with_prefix
@@ -407,6 +575,9 @@ impl PipelineBuilder {
                 color_mode: crate::config::ColorMode::Auto,
                 timestamp_formatting: crate::config::TimestampFormatConfig::default(),
                 format_name: None,
+                profile_stages: false,
+                suppress_empty_fields: crate::config::EmptyFieldSuppression::default(),
+                on_stage_error: None,
                 strict: false,
                 verbose: 0,
                 quiet_events: false,
@@ -419,18 +590,29 @@ impl PipelineBuilder {
                 legend_mode: crate::config::LegendMode::Auto,
                 input_files: Vec::new(),
                 allow_fs_writes: false,
+                sandbox: false,
             },
             begin: None,
+            begin_timeout_ms: None,
+            end_timeout_ms: None,
             end: None,
             input_format: crate::config::InputFormat::Json,
+            fallback_format: None,
             output_format: crate::OutputFormat::Default,
             take_limit: None,
+            take_while: None,
+            check_monotonic: None,
+            group_consecutive_by: None,
+            group_aggregate: None,
+            drop_while: None,
+            reservoir: None,
             keys: Vec::new(),
             exclude_keys: Vec::new(),
             levels: Vec::new(),
             exclude_levels: Vec::new(),
             multiline: None,
             window_size: 0,
+            window_emit: crate::config::WindowEmitMode::Off,
             csv_headers: None,
             timestamp_filter: None,
             normalize_timestamps: false,
@@ -439,18 +621,64 @@ impl PipelineBuilder {
             ts_field: None,
             ts_format: None,
             default_timezone: None,
+            tz_field: None,
+            assume_year: None,
             extract_prefix: None,
             prefix_sep: "|".to_string(),
+            strip_prefix: None,
+            prefix_field: None,
             cols_spec: None,
             cols_sep: None,
+            ssv_header: None,
+            keep_rest: false,
+            json_numbers_as_strings: false,
+            strict_json: false,
+            dup_keys: crate::config::DupKeyPolicy::Last,
             context_config: crate::config::ContextConfig::disabled(),
             span: None,
+            every: None,
+            thin: None,
             strict: false,
             state_available: true,
             csv_type_map: None,
+            ecs_version: crate::cli::EcsVersion::default(),
+            fluentd_tag: "kelora".to_string(),
+            color_theme: crate::cli::ColorTheme::default(),
+            color_parts: Vec::new(),
+            color_json_values: false,
+            table_boxed: false,
+            max_field_length: None,
+            json_flat: None,
+            output_file: None,
+            orc_stripe_rows: crate::formatters::DEFAULT_ORC_STRIPE_ROWS,
+            cbor_framed: true,
+            explode: None,
+            cluster: None,
+            field_prefix_drop: Vec::new(),
+            collision: crate::config::CollisionPolicy::default(),
+            field_prefix_add: None,
+            field_expand: None,
+            field_from_filename: None,
+            merge_fields: Vec::new(),
+            merge_fields_skip_missing: false,
+            duration_fields: None,
+            level_selection: None,
+            level_strict: false,
+            schema: None,
+            schema_on_fail: crate::cli::SchemaOnFail::default(),
+            exec_labels: Vec::new(),
+            script_args: Vec::new(),
+            exec_on_error: None,
+            cancel: crate::platform::CancelToken::new(),
         }
     }
 
+    /// Abort scripts of this pipeline when `cancel` is cancelled
+    pub fn with_cancel_token(mut self, cancel: crate::platform::CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
     pub fn with_config(mut self, config: PipelineConfig) -> Self {
         self.config = config;
         self
@@ -462,6 +690,10 @@ impl PipelineBuilder {
         stages: Vec<crate::config::ScriptStageType>,
     ) -> Result<(Pipeline, BeginStage, EndStage, PipelineContext)> {
         let mut rhai_engine = RhaiEngine::new();
+        rhai_engine.set_cancel_token(self.cancel.clone());
+        rhai_engine.set_sandbox_policy(SandboxPolicy {
+            enabled: self.config.sandbox,
+        });
         rhai_engine.set_state_available(self.state_available);
         let use_emoji = crate::tty::should_use_emoji_with_mode(
             &self.config.emoji_mode,
@@ -504,9 +736,64 @@ impl PipelineBuilder {
             Box::new(crate::formatters::HideFormatter::new())
         } else {
             match self.output_format {
-                crate::OutputFormat::Json => Box::new(crate::formatters::JsonFormatter::new()),
-                crate::OutputFormat::Default => {
-                    Box::new(crate::formatters::DefaultFormatter::new_with_wrapping(
+                crate::OutputFormat::Json => Box::new(
+                    crate::formatters::JsonFormatter::new()
+                        .with_flatten(self.json_flat)
+                        .with_collision(self.collision),
+                ),
+                crate::OutputFormat::Ecs => {
+                    Box::new(crate::formatters::EcsFormatter::new(self.ecs_version))
+                }
+                crate::OutputFormat::FluentdJson => {
+                    Box::new(crate::formatters::FluentdFormatter::new(
+                        self.fluentd_tag.clone(),
+                        crate::formatters::FluentdMode::Message,
+                    ))
+                }
+                crate::OutputFormat::FluentdForward => {
+                    Box::new(crate::formatters::FluentdFormatter::new(
+                        self.fluentd_tag.clone(),
+                        crate::formatters::FluentdMode::Forward,
+                    ))
+                }
+                crate::OutputFormat::Table => Box::new(crate::formatters::TableFormatter::new(
+                    self.keys.clone(),
+                    self.table_boxed,
+                    self.max_field_length,
+                )),
+                // Events are not printed; the metrics are pushed at the end
+                crate::OutputFormat::PrometheusPushgateway => {
+                    Box::new(crate::formatters::HideFormatter::new())
+                }
+                #[cfg(feature = "orc")]
+                crate::OutputFormat::Orc => {
+                    let path = self
+                        .output_file
+                        .as_deref()
+                        .ok_or_else(|| anyhow::anyhow!("-F orc requires --output-file"))?;
+                    Box::new(crate::formatters::OrcFormatter::create(
+                        path,
+                        self.orc_stripe_rows,
+                    )?)
+                }
+                #[cfg(not(feature = "orc"))]
+                crate::OutputFormat::Orc => {
+                    return Err(anyhow::anyhow!(
+                        "-F orc is not available: kelora was built without the `orc` feature"
+                    ));
+                }
+                crate::OutputFormat::Cbor => {
+                    let path = self
+                        .output_file
+                        .as_deref()
+                        .ok_or_else(|| anyhow::anyhow!("-F cbor requires --output-file"))?;
+                    Box::new(crate::formatters::CborFormatter::create(
+                        path,
+                        self.cbor_framed,
+                    )?)
+                }
+                crate::OutputFormat::Default => Box::new(
+                    crate::formatters::DefaultFormatter::new_with_wrapping(
                         use_colors,
                         use_emoji,
                         self.config.brief,
@@ -514,14 +801,29 @@ impl PipelineBuilder {
                         crate::tty::should_wrap(&self.config.wrap),
                         self.config.pretty,
                         self.config.quiet_level,
-                    ))
-                }
+                    )
+                    .with_colors(
+                        crate::colors::ColorScheme::themed(self.color_theme, use_colors)
+                            .with_parts(&self.color_parts),
+                    ),
+                ),
                 crate::OutputFormat::Inspect => Box::new(crate::formatters::InspectFormatter::new(
                     self.config.verbose,
                 )),
                 crate::OutputFormat::Logfmt => Box::new(crate::formatters::LogfmtFormatter::new()),
+                crate::OutputFormat::ApacheAccess => {
+                    Box::new(crate::formatters::ApacheAccessFormatter::new())
+                }
+                crate::OutputFormat::NginxAccess => {
+                    Box::new(crate::formatters::ApacheAccessFormatter::nginx())
+                }
+                crate::OutputFormat::Xml => Box::new(crate::formatters::XmlFormatter::new()),
                 crate::OutputFormat::Levelmap => Box::new(
-                    crate::formatters::LevelmapFormatter::new(use_colors, use_emoji, show_legend),
+                    crate::formatters::LevelmapFormatter::new(use_colors, use_emoji, show_legend)
+                        .with_colors(crate::colors::ColorScheme::themed(
+                            self.color_theme,
+                            use_colors,
+                        )),
                 ),
                 crate::OutputFormat::Keymap => {
                     if self.keys.len() != 1 {
@@ -586,10 +888,50 @@ impl PipelineBuilder {
                 }
             }
         };
+        let formatter = self.highlight_json_values(formatter, use_colors);
 
         // Create script stages with numbering
         let mut script_stages: Vec<Box<dyn ScriptStage>> = Vec::new();
+
+        // Clock regressions are judged in input order, before anything can
+        // drop or rewrite an event
+        if let Some(mode) = self.check_monotonic {
+            script_stages.push(Box::new(MonotonicCheckStage::new(mode)));
+        }
+
+        // Strip field-name prefixes first so scripts and level filters see
+        // the short names
+        if !self.field_prefix_drop.is_empty() {
+            script_stages.push(Box::new(FieldPrefixDropStage::new(
+                self.field_prefix_drop.clone(),
+                self.collision,
+            )));
+        }
+        if let Some(expand) = self.field_expand.clone() {
+            script_stages.push(Box::new(JsonFieldExpandStage::new(expand, self.collision)));
+        }
+        if let Some(pattern) = self.field_from_filename.clone() {
+            script_stages.push(Box::new(FilenameFieldsStage::new(pattern)));
+        }
+        if !self.merge_fields.is_empty() {
+            script_stages.push(Box::new(MergeFieldsStage::new(
+                self.merge_fields.clone(),
+                self.merge_fields_skip_missing,
+                self.collision,
+            )));
+        }
+        if let Some(fields) = self.duration_fields.clone() {
+            script_stages.push(Box::new(DurationFieldsStage::new(fields)));
+        }
+        if let Some(selection) = self.level_selection.clone() {
+            script_stages.push(Box::new(LevelSelectStage::new(
+                selection,
+                crate::event::LevelScheme::for_format(&self.input_format),
+                self.level_strict,
+            )));
+        }
         let mut stage_number = 1;
+        let mut exec_index = 0;
 
         let has_script_filters = stages
             .iter()
@@ -613,10 +955,12 @@ impl PipelineBuilder {
                     stage_number += 1;
                 }
                 crate::config::ScriptStageType::Exec(exec) => {
-                    let exec_stage =
-                        ExecStage::new(exec, &mut rhai_engine)?.with_stage_number(stage_number);
+                    let exec_stage = ExecStage::new(exec, &mut rhai_engine)?
+                        .with_stage_number(stage_number)
+                        .with_label(self.exec_labels.get(exec_index).cloned().flatten());
                     script_stages.push(Box::new(exec_stage));
                     stage_number += 1;
+                    exec_index += 1;
                 }
                 crate::config::ScriptStageType::Assert(assertion) => {
                     let assert_stage = AssertStage::new(assertion, &mut rhai_engine)?
@@ -648,6 +992,10 @@ impl PipelineBuilder {
             }
         }
 
+        if let crate::config::WindowEmitMode::EachPosition { script } = &self.window_emit {
+            script_stages.push(Box::new(WindowEmitStage::new(script, &mut rhai_engine)?));
+        }
+
         // Add timestamp filtering stage (runs after script stages, before level filtering)
         if let Some(timestamp_filter_config) = self.timestamp_filter {
             let timestamp_filter_stage = TimestampFilterStage::new(timestamp_filter_config);
@@ -659,10 +1007,20 @@ impl PipelineBuilder {
                 self.ts_field.clone(),
                 self.ts_format.clone(),
                 self.default_timezone.clone(),
+                self.tz_field.clone(),
+                self.assume_year,
             );
             script_stages.push(Box::new(conversion_stage));
         }
 
+        // Stream predicates see events that survived filtering, like --take
+        if let Some(predicate) = self.drop_while.as_deref() {
+            script_stages.push(Box::new(DropWhileStage::new(predicate, &mut rhai_engine)?));
+        }
+        if let Some(predicate) = self.take_while.as_deref() {
+            script_stages.push(Box::new(TakeWhileStage::new(predicate, &mut rhai_engine)?));
+        }
+
         if self.drain_enabled {
             let field = self.drain_field.clone().ok_or_else(|| {
                 anyhow::anyhow!(
@@ -672,12 +1030,42 @@ impl PipelineBuilder {
             script_stages.push(Box::new(DrainStage::new(field)));
         }
 
+        if let Some(cluster) = self.cluster.clone() {
+            script_stages.push(Box::new(ClusterStage::new(cluster)));
+        }
+
+        // Explode array fields at the output boundary, before key selection so
+        // --keys can pick the merged element fields
+        if let Some(explode) = self.explode.clone() {
+            script_stages.push(Box::new(ExplodeStage::new(explode)));
+        }
+
+        // Validate the complete event, before --keys trims it
+        if let Some(validator) = self.schema.clone() {
+            script_stages.push(Box::new(SchemaValidateStage::new(
+                validator,
+                self.schema_on_fail,
+            )));
+        }
+
+        // Merge runs on complete events, before --keys trims them
+        if let Some(field) = self.group_consecutive_by.clone() {
+            script_stages.push(Box::new(ConsecutiveGroupStage::new(
+                field,
+                self.group_aggregate,
+            )));
+        }
+
         // Add key filtering stage (runs after level filtering, before context processing)
         let key_filter_stage = KeyFilterStage::new(self.keys.clone(), self.exclude_keys.clone());
         if key_filter_stage.is_active() {
             script_stages.push(Box::new(key_filter_stage));
         }
 
+        if let Some(prefix) = self.field_prefix_add.clone() {
+            script_stages.push(Box::new(FieldPrefixAddStage::new(prefix)));
+        }
+
         // Context processing is now handled within FilterStage
 
         // Create limiter if specified
@@ -688,8 +1076,14 @@ impl PipelineBuilder {
         };
 
         // Create begin and end stages
-        let begin_stage = BeginStage::new(self.begin, &mut rhai_engine)?;
-        let end_stage = EndStage::new(self.end, &mut rhai_engine)?;
+        rhai_engine.set_script_inputs(&self.config.input_files, &self.script_args);
+        let begin_stage =
+            BeginStage::new(self.begin, &mut rhai_engine)?.with_timeout_ms(self.begin_timeout_ms);
+        let end_stage =
+            EndStage::new(self.end, &mut rhai_engine)?.with_timeout_ms(self.end_timeout_ms);
+        if let Some(script) = self.exec_on_error.as_deref() {
+            rhai_engine.compile_error_hook(script)?;
+        }
 
         let span_processor = if let Some(ref span_config) = self.span {
             let compiled = if let Some(ref script) = span_config.close_script {
@@ -697,14 +1091,37 @@ impl PipelineBuilder {
             } else {
                 None
             };
+            let compiled_summary = match span_config.summary_script {
+                Some(ref script) => Some(rhai_engine.compile_exec(script)?),
+                None => None,
+            };
             Some(crate::pipeline::span::SpanProcessor::new(
                 span_config.clone(),
                 compiled,
+                compiled_summary,
             ))
         } else {
             None
         };
 
+        let every = match self.every {
+            Some(ref every_config) => {
+                let compiled_emit = match every_config.emit_script {
+                    Some(ref script) => Some(rhai_engine.compile_every_emit(script)?),
+                    None => None,
+                };
+                Some(crate::pipeline::every::EveryProcessor::new(
+                    every_config.clone(),
+                    compiled_emit,
+                ))
+            }
+            None => None,
+        };
+
+        if self.config.profile_stages {
+            register_profiled_stages(script_stages.iter().map(|stage| stage.label()).collect());
+        }
+
         // Create pipeline context
         let ctx = PipelineContext {
             config: self.config,
@@ -746,6 +1163,8 @@ impl PipelineBuilder {
             custom_field: self.ts_field.clone(),
             custom_format: self.ts_format.clone(),
             default_timezone: self.default_timezone.clone(),
+            tz_field: self.tz_field.clone(),
+            assume_year: self.assume_year,
         };
 
         // Window maintenance is only needed if --window was set or a stage
@@ -759,17 +1178,54 @@ impl PipelineBuilder {
             parser,
             script_stages,
             limiter,
+            reservoir: self.reservoir.map(ReservoirSampler::new),
             formatter,
             output: Box::new(StdoutWriter),
             window_manager,
             span_processor,
+            every,
+            thin: self
+                .thin
+                .clone()
+                .map(crate::pipeline::thin::ThinProcessor::new),
             ts_config,
             window_active,
+            line_recycler: None,
         };
 
         Ok((pipeline, begin_stage, end_stage, ctx))
     }
 
+    /// Wrap the default and logfmt formatters in the `--color-json-values`
+    /// highlighter when colors are on
+    fn highlight_json_values(
+        &self,
+        formatter: Box<dyn Formatter>,
+        use_colors: bool,
+    ) -> Box<dyn Formatter> {
+        if !self.color_json_values || !use_colors || self.config.quiet_events {
+            return formatter;
+        }
+        let colors = crate::colors::ColorScheme::themed(self.color_theme, use_colors);
+        match self.output_format {
+            crate::OutputFormat::Default
+                if !crate::colors::part_enabled(
+                    &self.color_parts,
+                    crate::cli::ColorPart::Values,
+                ) =>
+            {
+                formatter
+            }
+            crate::OutputFormat::Default => Box::new(
+                crate::formatters::JsonValueHighlighter::default_output(formatter, colors),
+            ),
+            crate::OutputFormat::Logfmt => Box::new(
+                crate::formatters::JsonValueHighlighter::logfmt(formatter, colors),
+            ),
+            _ => formatter,
+        }
+    }
+
     pub fn with_begin(mut self, begin: Option<String>) -> Self {
         self.begin = begin;
         self
@@ -811,7 +1267,41 @@ impl PipelineBuilder {
                 "--drain summary is not supported with --parallel. Rerun without --parallel to use Drain template mining."
             ));
         }
+        if self.cluster.is_some() {
+            return Err(anyhow::anyhow!(
+                "--cluster is not supported with --parallel. Rerun without --parallel to cluster values."
+            ));
+        }
+        if self.take_while.is_some() || self.drop_while.is_some() {
+            return Err(anyhow::anyhow!(
+                "--take-while/--drop-while is not supported with --parallel. Rerun without --parallel to cut the stream in order."
+            ));
+        }
+        if self.schema.is_some() && matches!(self.schema_on_fail, crate::cli::SchemaOnFail::Halt) {
+            return Err(anyhow::anyhow!(
+                "--schema-on-fail halt is not supported with --parallel. Rerun without --parallel to stop at the first failing event."
+            ));
+        }
+        if self.config.on_stage_error == Some(crate::cli::StageErrorPolicy::Abort) {
+            return Err(anyhow::anyhow!(
+                "--on-stage-error abort is not supported with --parallel. Rerun without --parallel to stop at the first stage error."
+            ));
+        }
+        if self.check_monotonic.is_some() {
+            return Err(anyhow::anyhow!(
+                "--check-monotonic is not supported with --parallel. Rerun without --parallel to compare each event with the one before it."
+            ));
+        }
+        if self.group_consecutive_by.is_some() {
+            return Err(anyhow::anyhow!(
+                "--group-consecutive-by is not supported with --parallel. Rerun without --parallel to merge runs of consecutive events."
+            ));
+        }
         let mut rhai_engine = RhaiEngine::new();
+        rhai_engine.set_cancel_token(self.cancel.clone());
+        rhai_engine.set_sandbox_policy(SandboxPolicy {
+            enabled: self.config.sandbox,
+        });
         rhai_engine.set_state_available(self.state_available);
 
         // Set up debugging if enabled
@@ -853,9 +1343,45 @@ impl PipelineBuilder {
             Box::new(crate::formatters::HideFormatter::new())
         } else {
             match self.output_format {
-                crate::OutputFormat::Json => Box::new(crate::formatters::JsonFormatter::new()),
-                crate::OutputFormat::Default => {
-                    Box::new(crate::formatters::DefaultFormatter::new_with_wrapping(
+                crate::OutputFormat::Json => Box::new(
+                    crate::formatters::JsonFormatter::new()
+                        .with_flatten(self.json_flat)
+                        .with_collision(self.collision),
+                ),
+                crate::OutputFormat::Ecs => {
+                    Box::new(crate::formatters::EcsFormatter::new(self.ecs_version))
+                }
+                crate::OutputFormat::FluentdJson => {
+                    Box::new(crate::formatters::FluentdFormatter::new(
+                        self.fluentd_tag.clone(),
+                        crate::formatters::FluentdMode::Message,
+                    ))
+                }
+                crate::OutputFormat::FluentdForward => {
+                    return Err(anyhow::anyhow!(
+                        "fluentd-forward output format is not supported with --parallel or thread overrides"
+                    ));
+                }
+                crate::OutputFormat::Table => Box::new(crate::formatters::TableFormatter::new(
+                    self.keys.clone(),
+                    self.table_boxed,
+                    self.max_field_length,
+                )),
+                crate::OutputFormat::PrometheusPushgateway => {
+                    Box::new(crate::formatters::HideFormatter::new())
+                }
+                crate::OutputFormat::Orc => {
+                    return Err(anyhow::anyhow!(
+                        "orc output format is not supported with --parallel or thread overrides"
+                    ));
+                }
+                crate::OutputFormat::Cbor => {
+                    return Err(anyhow::anyhow!(
+                        "cbor output format is not supported with --parallel or thread overrides"
+                    ));
+                }
+                crate::OutputFormat::Default => Box::new(
+                    crate::formatters::DefaultFormatter::new_with_wrapping(
                         use_colors,
                         use_emoji,
                         self.config.brief,
@@ -863,14 +1389,29 @@ impl PipelineBuilder {
                         crate::tty::should_wrap(&self.config.wrap),
                         self.config.pretty,
                         self.config.quiet_level,
-                    ))
-                }
+                    )
+                    .with_colors(
+                        crate::colors::ColorScheme::themed(self.color_theme, use_colors)
+                            .with_parts(&self.color_parts),
+                    ),
+                ),
                 crate::OutputFormat::Inspect => Box::new(crate::formatters::InspectFormatter::new(
                     self.config.verbose,
                 )),
                 crate::OutputFormat::Logfmt => Box::new(crate::formatters::LogfmtFormatter::new()),
+                crate::OutputFormat::ApacheAccess => {
+                    Box::new(crate::formatters::ApacheAccessFormatter::new())
+                }
+                crate::OutputFormat::NginxAccess => {
+                    Box::new(crate::formatters::ApacheAccessFormatter::nginx())
+                }
+                crate::OutputFormat::Xml => Box::new(crate::formatters::XmlFormatter::new()),
                 crate::OutputFormat::Levelmap => Box::new(
-                    crate::formatters::LevelmapFormatter::new(use_colors, use_emoji, show_legend),
+                    crate::formatters::LevelmapFormatter::new(use_colors, use_emoji, show_legend)
+                        .with_colors(crate::colors::ColorScheme::themed(
+                            self.color_theme,
+                            use_colors,
+                        )),
                 ),
                 crate::OutputFormat::Keymap => {
                     if self.keys.len() != 1 {
@@ -939,10 +1480,44 @@ impl PipelineBuilder {
                 }
             }
         };
+        let formatter = self.highlight_json_values(formatter, use_colors);
 
         // Create script stages with numbering
         let mut script_stages: Vec<Box<dyn ScriptStage>> = Vec::new();
+
+        // Strip field-name prefixes first so scripts and level filters see
+        // the short names
+        if !self.field_prefix_drop.is_empty() {
+            script_stages.push(Box::new(FieldPrefixDropStage::new(
+                self.field_prefix_drop.clone(),
+                self.collision,
+            )));
+        }
+        if let Some(expand) = self.field_expand.clone() {
+            script_stages.push(Box::new(JsonFieldExpandStage::new(expand, self.collision)));
+        }
+        if let Some(pattern) = self.field_from_filename.clone() {
+            script_stages.push(Box::new(FilenameFieldsStage::new(pattern)));
+        }
+        if !self.merge_fields.is_empty() {
+            script_stages.push(Box::new(MergeFieldsStage::new(
+                self.merge_fields.clone(),
+                self.merge_fields_skip_missing,
+                self.collision,
+            )));
+        }
+        if let Some(fields) = self.duration_fields.clone() {
+            script_stages.push(Box::new(DurationFieldsStage::new(fields)));
+        }
+        if let Some(selection) = self.level_selection.clone() {
+            script_stages.push(Box::new(LevelSelectStage::new(
+                selection,
+                crate::event::LevelScheme::for_format(&self.input_format),
+                self.level_strict,
+            )));
+        }
         let mut stage_number = 1;
+        let mut exec_index = 0;
 
         let has_script_filters = stages
             .iter()
@@ -966,10 +1541,12 @@ impl PipelineBuilder {
                     stage_number += 1;
                 }
                 crate::config::ScriptStageType::Exec(exec) => {
-                    let exec_stage =
-                        ExecStage::new(exec, &mut rhai_engine)?.with_stage_number(stage_number);
+                    let exec_stage = ExecStage::new(exec, &mut rhai_engine)?
+                        .with_stage_number(stage_number)
+                        .with_label(self.exec_labels.get(exec_index).cloned().flatten());
                     script_stages.push(Box::new(exec_stage));
                     stage_number += 1;
+                    exec_index += 1;
                 }
                 crate::config::ScriptStageType::Assert(assertion) => {
                     let assert_stage = AssertStage::new(assertion, &mut rhai_engine)?
@@ -1001,23 +1578,53 @@ impl PipelineBuilder {
             }
         }
 
+        if let crate::config::WindowEmitMode::EachPosition { script } = &self.window_emit {
+            script_stages.push(Box::new(WindowEmitStage::new(script, &mut rhai_engine)?));
+        }
+
         // Add timestamp filtering stage (runs after script stages, before level filtering)
         if let Some(timestamp_filter_config) = self.timestamp_filter {
             let timestamp_filter_stage = TimestampFilterStage::new(timestamp_filter_config);
             script_stages.push(Box::new(timestamp_filter_stage));
         }
 
+        // Explode array fields at the output boundary, before key selection so
+        // --keys can pick the merged element fields
+        if let Some(explode) = self.explode.clone() {
+            script_stages.push(Box::new(ExplodeStage::new(explode)));
+        }
+
+        // Validate the complete event, before --keys trims it
+        if let Some(validator) = self.schema.clone() {
+            script_stages.push(Box::new(SchemaValidateStage::new(
+                validator,
+                self.schema_on_fail,
+            )));
+        }
+
         // Add key filtering stage (runs after level filtering, before context processing)
         let key_filter_stage = KeyFilterStage::new(self.keys.clone(), self.exclude_keys.clone());
         if key_filter_stage.is_active() {
             script_stages.push(Box::new(key_filter_stage));
         }
 
+        if let Some(prefix) = self.field_prefix_add.clone() {
+            script_stages.push(Box::new(FieldPrefixAddStage::new(prefix)));
+        }
+
         // Context processing is now handled within FilterStage
 
         // No limiter for parallel workers (limiting happens at the result sink level)
         let limiter: Option<Box<dyn EventLimiter>> = None;
 
+        if let Some(script) = self.exec_on_error.as_deref() {
+            rhai_engine.compile_error_hook(script)?;
+        }
+
+        if self.config.profile_stages {
+            register_profiled_stages(script_stages.iter().map(|stage| stage.label()).collect());
+        }
+
         // Create pipeline context
         let ctx = PipelineContext {
             config: self.config,
@@ -1060,6 +1667,8 @@ impl PipelineBuilder {
             custom_field: self.ts_field.clone(),
             custom_format: self.ts_format.clone(),
             default_timezone: self.default_timezone.clone(),
+            tz_field: self.tz_field.clone(),
+            assume_year: self.assume_year,
         };
 
         let window_active = self.window_size > 0 || script_stages.iter().any(|s| s.uses_window());
@@ -1071,12 +1680,16 @@ impl PipelineBuilder {
             parser,
             script_stages,
             limiter,
+            reservoir: self.reservoir.map(ReservoirSampler::new),
             formatter,
             output: Box::new(StdoutWriter), // This won't actually be used in parallel mode
             window_manager,
             span_processor: None,
+            every: None,
+            thin: None,
             ts_config,
             window_active,
+            line_recycler: None,
         };
 
         Ok((pipeline, ctx))
@@ -1115,6 +1728,16 @@ impl PipelineBuilder {
         self
     }
 
+    pub fn with_tz_field(mut self, tz_field: Option<String>) -> Self {
+        self.tz_field = tz_field;
+        self
+    }
+
+    pub fn with_assume_year(mut self, assume_year: Option<i32>) -> Self {
+        self.assume_year = assume_year;
+        self
+    }
+
     pub fn with_extract_prefix(mut self, extract_prefix: Option<String>) -> Self {
         self.extract_prefix = extract_prefix;
         self
@@ -1125,6 +1748,16 @@ impl PipelineBuilder {
         self
     }
 
+    pub fn with_strip_prefix(
+        mut self,
+        strip_prefix: Option<crate::config::PrefixStrip>,
+        prefix_field: Option<String>,
+    ) -> Self {
+        self.strip_prefix = strip_prefix;
+        self.prefix_field = prefix_field;
+        self
+    }
+
     pub fn with_cols_spec(mut self, cols_spec: Option<String>) -> Self {
         self.cols_spec = cols_spec;
         self
@@ -1134,6 +1767,31 @@ impl PipelineBuilder {
         self.cols_sep = cols_sep;
         self
     }
+
+    pub fn with_ssv_header(mut self, ssv_header: Option<Vec<String>>) -> Self {
+        self.ssv_header = ssv_header;
+        self
+    }
+
+    pub fn with_keep_rest(mut self, keep_rest: bool) -> Self {
+        self.keep_rest = keep_rest;
+        self
+    }
+
+    pub fn with_json_numbers_as_strings(mut self, json_numbers_as_strings: bool) -> Self {
+        self.json_numbers_as_strings = json_numbers_as_strings;
+        self
+    }
+
+    pub fn with_strict_json(mut self, strict_json: bool) -> Self {
+        self.strict_json = strict_json;
+        self
+    }
+
+    pub fn with_dup_keys(mut self, dup_keys: crate::config::DupKeyPolicy) -> Self {
+        self.dup_keys = dup_keys;
+        self
+    }
 }
 
 impl Default for PipelineBuilder {
@@ -1172,7 +1830,11 @@ pub fn create_pipeline_builder_from_config(
         legend_mode: config.output.legend.clone(),
         input_files: config.input.files.clone(),
         allow_fs_writes: config.processing.allow_fs_writes,
+        sandbox: config.processing.sandbox,
         format_name: Some(config.input.format.to_display_string()),
+        profile_stages: config.processing.pipeline_profile || config.processing.benchmark,
+        suppress_empty_fields: config.output.suppress_empty_fields,
+        on_stage_error: config.processing.on_stage_error,
     };
 
     // Extract cols spec if needed before conversion
@@ -1211,25 +1873,74 @@ pub fn create_pipeline_builder_from_config(
         .with_output_format(config.output.format.clone().into())
         .with_drain(drain_enabled, drain_field)
         .with_cols_spec(cols_spec)
-        .with_cols_sep(config.input.cols_sep.clone());
+        .with_cols_sep(config.input.cols_sep.clone())
+        .with_ssv_header(config.input.ssv_header.clone())
+        .with_keep_rest(config.input.keep_rest)
+        .with_json_numbers_as_strings(config.input.json_numbers_as_strings)
+        .with_strict_json(config.input.strict_json)
+        .with_dup_keys(config.input.dup_keys);
     builder.keys = config.output.get_effective_keys();
     builder.exclude_keys = config.output.exclude_keys.clone();
     builder.levels = config.processing.levels.clone();
     builder.exclude_levels = config.processing.exclude_levels.clone();
     builder.multiline = config.input.multiline.clone();
+    builder.fallback_format = config.input.fallback_format.clone();
     builder.window_size = config.processing.window_size;
+    builder.window_emit = config.processing.window_emit.clone();
     builder.timestamp_filter = config.processing.timestamp_filter.clone();
     builder.normalize_timestamps = config.processing.normalize_timestamps;
     builder.ts_field = config.input.ts_field.clone();
     builder.ts_format = config.input.ts_format.clone();
     builder.default_timezone = config.input.default_timezone.clone();
+    builder.tz_field = config.input.tz_field.clone();
+    builder.assume_year = config.input.assume_year;
     builder.extract_prefix = config.input.extract_prefix.clone();
     builder.prefix_sep = config.input.prefix_sep.clone();
+    builder.strip_prefix = config.input.strip_prefix.clone();
+    builder.prefix_field = config.input.prefix_field.clone();
     builder.take_limit = config.processing.take_limit;
+    builder.take_while = config.processing.take_while.clone();
+    builder.begin_timeout_ms = config.processing.begin_timeout_ms;
+    builder.end_timeout_ms = config.processing.end_timeout_ms;
+    builder.check_monotonic = config.processing.check_monotonic;
+    builder.group_consecutive_by = config.processing.group_consecutive_by.clone();
+    builder.group_aggregate = config.processing.group_aggregate;
+    builder.drop_while = config.processing.drop_while.clone();
+    builder.reservoir = config.processing.reservoir;
     builder.span = config.processing.span.clone();
+    builder.every = config.processing.every.clone();
+    builder.thin = config.processing.thin.clone();
     builder.context_config = config.processing.context.clone();
     builder.strict = config.processing.strict;
     builder.state_available = !config.should_use_parallel();
+    builder.ecs_version = config.output.ecs_version;
+    builder.fluentd_tag = config.output.fluentd_tag.clone();
+    builder.color_theme = config.output.color_theme;
+    builder.color_parts = config.output.color_parts.clone();
+    builder.color_json_values = config.output.color_json_values;
+    builder.table_boxed = config.output.table_boxed;
+    builder.max_field_length = config.output.max_field_length;
+    builder.json_flat = config.output.json_flat;
+    builder.output_file = config.output.output_file.clone();
+    builder.orc_stripe_rows = config.output.orc_stripe_rows;
+    builder.cbor_framed = config.output.cbor_framed;
+    builder.explode = config.output.explode.clone();
+    builder.cluster = config.output.cluster.clone();
+    builder.field_prefix_drop = config.processing.field_prefix_drop.clone();
+    builder.collision = config.processing.collision;
+    builder.field_prefix_add = config.processing.field_prefix_add.clone();
+    builder.field_expand = config.processing.field_expand.clone();
+    builder.field_from_filename = config.processing.field_from_filename.clone();
+    builder.merge_fields = config.processing.merge_fields.clone();
+    builder.merge_fields_skip_missing = config.processing.merge_fields_skip_missing;
+    builder.duration_fields = config.processing.duration_fields.clone();
+    builder.level_selection = config.processing.level.clone();
+    builder.level_strict = config.processing.level_strict;
+    builder.schema = config.processing.schema.clone();
+    builder.schema_on_fail = config.processing.schema_on_fail;
+    builder.exec_labels = config.processing.exec_stage_labels.clone();
+    builder.script_args = config.processing.script_args.clone();
+    builder.exec_on_error = config.processing.exec_on_error.clone();
     builder
 }
 
@@ -1242,13 +1953,21 @@ pub fn create_input_reader(
         Ok(Box::new(BufReader::new(std::io::Cursor::new(Vec::new()))))
     } else if config.input.files.is_empty() {
         // Use stdin reader with gzip/zstd detection for Send compatibility
+        let options = crate::readers::InputOptions::from_config(config);
         let stdin_reader = crate::readers::ChannelStdinReader::new()?;
-        let processed_stdin = crate::decompression::maybe_decompress(stdin_reader)?;
-        Ok(Box::new(BufReader::new(processed_stdin)))
+        let processed_stdin = crate::readers::decode_binary_input(
+            crate::decompression::maybe_decompress(stdin_reader)?,
+            &options,
+        );
+        Ok(Box::new(BufReader::with_capacity(
+            options.buffer_size,
+            processed_stdin,
+        )))
     } else {
         let sorted_files = sort_files(&config.input.files, &config.input.file_order)?;
         Ok(Box::new(MultiFileReader::new(
             sorted_files,
+            crate::readers::InputOptions::from_config(config),
             config.processing.strict,
         )?))
     }
@@ -1266,6 +1985,7 @@ pub fn create_file_aware_input_reader(
         let sorted_files = sort_files(&config.input.files, &config.input.file_order)?;
         Ok(Box::new(crate::readers::FileAwareMultiFileReader::new(
             sorted_files,
+            crate::readers::InputOptions::from_config(config),
             config.processing.strict,
         )?))
     }
@@ -0,0 +1,170 @@
+use std::io::{self, Write};
+
+/// Output wrapper for --chunk-size / --chunk-size-bytes.
+///
+/// Regroups the formatted lines written to it into JSON arrays, one array per
+/// output line. Each incoming line becomes one array element: a line that
+/// already holds a JSON object or array (e.g. `-F json`) is embedded as-is,
+/// anything else is embedded as a JSON string, so every formatter yields valid
+/// arrays. A batch is written once it holds `max_events` lines or adding the
+/// next line would push it past `max_bytes`; a single line larger than
+/// `max_bytes` still goes out as a batch of its own. The partial last batch is
+/// written by [`ChunkingOutputWriter::finish`], or on drop.
+pub struct ChunkingOutputWriter<W: Write> {
+    inner: W,
+    max_events: Option<usize>,
+    max_bytes: Option<usize>,
+    /// Bytes of the line currently being written (no newline seen yet)
+    pending: Vec<u8>,
+    /// JSON-encoded elements of the current batch
+    batch: Vec<String>,
+    /// Total length of the elements in `batch`
+    batch_bytes: usize,
+}
+
+impl<W: Write> ChunkingOutputWriter<W> {
+    pub fn new(inner: W, max_events: Option<usize>, max_bytes: Option<usize>) -> Self {
+        Self {
+            inner,
+            max_events,
+            max_bytes,
+            pending: Vec::new(),
+            batch: Vec::new(),
+            batch_bytes: 0,
+        }
+    }
+
+    /// Serialized size of the current batch: brackets, elements, and the
+    /// separating commas.
+    fn array_len(&self) -> usize {
+        2 + self.batch_bytes + self.batch.len().saturating_sub(1)
+    }
+
+    fn push_line(&mut self, line: &str) -> io::Result<()> {
+        let element = json_element(line);
+
+        if let Some(max_bytes) = self.max_bytes {
+            if !self.batch.is_empty() && self.array_len() + 1 + element.len() > max_bytes {
+                self.write_batch()?;
+            }
+        }
+
+        self.batch_bytes += element.len();
+        self.batch.push(element);
+
+        let events_full = self.max_events.is_some_and(|max| self.batch.len() >= max);
+        let bytes_full = self.max_bytes.is_some_and(|max| self.array_len() >= max);
+        if events_full || bytes_full {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    fn write_batch(&mut self) -> io::Result<()> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+        let line = format!("[{}]", self.batch.join(","));
+        self.batch.clear();
+        self.batch_bytes = 0;
+        writeln!(self.inner, "{}", line)
+    }
+
+    /// Emit any incomplete line and the partial last batch, then flush.
+    pub fn finish(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            self.push_line(&String::from_utf8_lossy(&pending))?;
+        }
+        self.write_batch()?;
+        self.inner.flush()
+    }
+}
+
+/// Encode one formatted line as a JSON array element.
+fn json_element(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(line).is_ok()
+    {
+        line.to_string()
+    } else {
+        serde_json::Value::String(line.to_string()).to_string()
+    }
+}
+
+impl<W: Write> Write for ChunkingOutputWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        while let Some(pos) = self.pending.iter().position(|&b| b == b'\n') {
+            let mut line: Vec<u8> = self.pending.drain(..=pos).collect();
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            self.push_line(&String::from_utf8_lossy(&line))?;
+        }
+        Ok(buf.len())
+    }
+
+    /// Flushes the underlying writer only; a partial batch stays open until it
+    /// fills up or the writer is finished, so intermediate flushes never split
+    /// a batch.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for ChunkingOutputWriter<W> {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(input: &str, max_events: Option<usize>, max_bytes: Option<usize>) -> String {
+        let mut out = Vec::new();
+        {
+            let mut writer = ChunkingOutputWriter::new(&mut out, max_events, max_bytes);
+            writer.write_all(input.as_bytes()).unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_chunks_by_event_count_with_short_last_batch() {
+        let output = chunk("{\"n\":1}\n{\"n\":2}\n{\"n\":3}\n", Some(2), None);
+        assert_eq!(output, "[{\"n\":1},{\"n\":2}]\n[{\"n\":3}]\n");
+    }
+
+    #[test]
+    fn test_non_json_lines_become_strings() {
+        let output = chunk("level=info msg=\"hi\"\ntrue\n", Some(5), None);
+        assert_eq!(output, "[\"level=info msg=\\\"hi\\\"\",\"true\"]\n");
+    }
+
+    #[test]
+    fn test_chunks_by_byte_limit() {
+        // Each element is 7 bytes; two fill 17 bytes ("[" + 7 + "," + 7 + "]")
+        let output = chunk("{\"n\":1}\n{\"n\":2}\n{\"n\":3}\n", None, Some(17));
+        assert_eq!(output, "[{\"n\":1},{\"n\":2}]\n[{\"n\":3}]\n");
+
+        // An element larger than the limit is still emitted on its own
+        let output = chunk("{\"n\":1}\n", None, Some(4));
+        assert_eq!(output, "[{\"n\":1}]\n");
+    }
+
+    #[test]
+    fn test_lines_split_across_writes() {
+        let mut out = Vec::new();
+        {
+            let mut writer = ChunkingOutputWriter::new(&mut out, Some(2), None);
+            writer.write_all(b"{\"a\":").unwrap();
+            writer.write_all(b"1}\n{\"b\":2}").unwrap();
+        }
+        assert_eq!(String::from_utf8(out).unwrap(), "[{\"a\":1},{\"b\":2}]\n");
+    }
+}
@@ -0,0 +1,248 @@
+//! Tumbling metric windows for `--every`.
+//!
+//! Each window owns its own tracking map. Before an event runs through the
+//! script stages, the map of the window it belongs to is swapped into
+//! `ctx.tracker`, so `track_*` calls land in that window only. When the
+//! watermark (the newest timestamp seen) passes a window's end plus the
+//! allowed lateness, the window is closed: its metrics are emitted, either as
+//! a summary event or through `--every-emit`, and then folded into the run
+//! totals, which `--end` and `--metrics` see once input ends.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use rhai::Dynamic;
+
+use crate::config::{EveryClock, EveryConfig};
+use crate::engine::CompiledExpression;
+use crate::event::Event;
+use crate::parallel::merge_tracking_state;
+use crate::pipeline::PipelineContext;
+use crate::rhai_functions::datetime::DateTimeWrapper;
+use crate::rhai_functions::tracking;
+use crate::stats;
+
+#[derive(Default)]
+struct WindowState {
+    tracker: HashMap<String, Dynamic>,
+    events: usize,
+}
+
+/// Which tracking map currently sits in `ctx.tracker`
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Installed {
+    Totals,
+    Window(i64),
+}
+
+pub struct EveryProcessor {
+    interval_ms: i64,
+    clock: EveryClock,
+    allowed_lateness_ms: i64,
+    compiled_emit: Option<CompiledExpression>,
+    /// Open windows keyed by their aligned start
+    open: BTreeMap<i64, WindowState>,
+    installed: Installed,
+    /// Metrics of closed windows, plus anything tracked outside a window
+    /// (`--begin`, late and untimestamped events)
+    totals: HashMap<String, Dynamic>,
+    watermark_ms: Option<i64>,
+    /// Windows starting before this have been emitted
+    closed_until_ms: i64,
+    late_since_emit: usize,
+    summaries: Vec<Event>,
+}
+
+impl EveryProcessor {
+    pub fn new(every: EveryConfig, compiled_emit: Option<CompiledExpression>) -> Self {
+        Self {
+            interval_ms: every.interval_ms,
+            clock: every.clock,
+            allowed_lateness_ms: every.allowed_lateness_ms,
+            compiled_emit,
+            open: BTreeMap::new(),
+            installed: Installed::Totals,
+            totals: HashMap::new(),
+            watermark_ms: None,
+            closed_until_ms: i64::MIN,
+            late_since_emit: 0,
+            summaries: Vec::new(),
+        }
+    }
+
+    /// Assign the event to its window, closing any windows the new watermark
+    /// has passed, and install that window's metrics in `ctx.tracker`.
+    pub fn prepare_event(&mut self, event: &mut Event, ctx: &mut PipelineContext) -> Result<()> {
+        self.stash(ctx);
+
+        let timestamp = match self.clock {
+            EveryClock::Event => {
+                if event.parsed_ts.is_none() {
+                    event.extract_timestamp();
+                }
+                event.parsed_ts
+            }
+            EveryClock::Wall => Some(Utc::now()),
+        };
+        let Some(timestamp) = timestamp else {
+            // Untimestamped events only count towards the run totals
+            self.install(Installed::Totals, ctx);
+            if ctx.config.strict {
+                return Err(anyhow!("event missing required timestamp for --every"));
+            }
+            return Ok(());
+        };
+
+        let event_ms = timestamp.timestamp_millis();
+        let watermark = self.watermark_ms.map_or(event_ms, |w| w.max(event_ms));
+        self.watermark_ms = Some(watermark);
+        self.close_expired(watermark, ctx)?;
+
+        let start = event_ms.div_euclid(self.interval_ms) * self.interval_ms;
+        if start < self.closed_until_ms || self.is_expired(start, watermark) {
+            stats::stats_add_late_event();
+            self.late_since_emit += 1;
+            self.install(Installed::Totals, ctx);
+            return Ok(());
+        }
+
+        self.open.entry(start).or_default().events += 1;
+        self.install(Installed::Window(start), ctx);
+        Ok(())
+    }
+
+    /// Take the summary events of windows closed since the last call.
+    pub fn take_summaries(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.summaries)
+    }
+
+    /// Close every open window and leave the run totals in `ctx.tracker`.
+    pub fn finish(&mut self, ctx: &mut PipelineContext) -> Result<()> {
+        self.stash(ctx);
+        while let Some((start, window)) = self.open.pop_first() {
+            self.close_window(start, window, ctx)?;
+        }
+        self.install(Installed::Totals, ctx);
+        // Engine calls read metrics back from the thread-local state, and the
+        // runner merges that state into the context after --end; point it at
+        // the totals instead of the last window.
+        tracking::set_thread_tracking_state(&ctx.tracker);
+        Ok(())
+    }
+
+    fn is_expired(&self, start: i64, watermark: i64) -> bool {
+        start
+            .saturating_add(self.interval_ms)
+            .saturating_add(self.allowed_lateness_ms)
+            <= watermark
+    }
+
+    fn close_expired(&mut self, watermark: i64, ctx: &mut PipelineContext) -> Result<()> {
+        while let Some((&start, _)) = self.open.first_key_value() {
+            if !self.is_expired(start, watermark) {
+                break;
+            }
+            let window = self
+                .open
+                .remove(&start)
+                .expect("first window key just observed");
+            self.close_window(start, window, ctx)?;
+        }
+        Ok(())
+    }
+
+    fn close_window(
+        &mut self,
+        start: i64,
+        window: WindowState,
+        ctx: &mut PipelineContext,
+    ) -> Result<()> {
+        let WindowState {
+            tracker: mut metrics,
+            events,
+        } = window;
+        let end = start.saturating_add(self.interval_ms);
+        let late = std::mem::take(&mut self.late_since_emit);
+
+        if let Some(compiled) = self.compiled_emit.as_ref() {
+            let mut binding = rhai::Map::new();
+            binding.insert(
+                "start".into(),
+                Dynamic::from(DateTimeWrapper::from_utc(ms_to_datetime(start))),
+            );
+            binding.insert(
+                "end".into(),
+                Dynamic::from(DateTimeWrapper::from_utc(ms_to_datetime(end))),
+            );
+            binding.insert("events".into(), Dynamic::from(events as i64));
+            binding.insert("late".into(), Dynamic::from(late as i64));
+            ctx.rhai.execute_compiled_every_emit(
+                compiled,
+                &mut metrics,
+                &mut ctx.internal_tracker,
+                binding,
+            )?;
+        } else {
+            let mut summary = Event::default_with_line(String::new());
+            summary.set_field(
+                "_window_start".to_string(),
+                Dynamic::from(format_boundary(start)),
+            );
+            summary.set_field(
+                "_window_end".to_string(),
+                Dynamic::from(format_boundary(end)),
+            );
+            summary.set_field("_window_events".to_string(), Dynamic::from(events as i64));
+            summary.set_field("_window_late".to_string(), Dynamic::from(late as i64));
+            for (key, value) in
+                tracking::finalize_metrics_for_script(&metrics, &ctx.internal_tracker)
+            {
+                summary.set_field(key.to_string(), value);
+            }
+            summary.parsed_ts = Some(ms_to_datetime(start));
+            self.summaries.push(summary);
+        }
+
+        merge_tracking_state(&mut self.totals, &metrics, &ctx.internal_tracker);
+        self.closed_until_ms = self.closed_until_ms.max(end);
+        Ok(())
+    }
+
+    /// Put the map in `ctx.tracker` back where it came from.
+    fn stash(&mut self, ctx: &mut PipelineContext) {
+        let tracker = std::mem::take(&mut ctx.tracker);
+        match self.installed {
+            Installed::Totals => self.totals = tracker,
+            Installed::Window(start) => {
+                if let Some(window) = self.open.get_mut(&start) {
+                    window.tracker = tracker;
+                }
+            }
+        }
+    }
+
+    fn install(&mut self, slot: Installed, ctx: &mut PipelineContext) {
+        ctx.tracker = match slot {
+            Installed::Totals => std::mem::take(&mut self.totals),
+            Installed::Window(start) => self
+                .open
+                .get_mut(&start)
+                .map(|window| std::mem::take(&mut window.tracker))
+                .unwrap_or_default(),
+        };
+        self.installed = slot;
+    }
+}
+
+fn ms_to_datetime(ms: i64) -> DateTime<Utc> {
+    match Utc.timestamp_millis_opt(ms) {
+        chrono::LocalResult::Single(dt) => dt,
+        _ if ms < 0 => DateTime::<Utc>::MIN_UTC,
+        _ => DateTime::<Utc>::MAX_UTC,
+    }
+}
+
+fn format_boundary(ms: i64) -> String {
+    ms_to_datetime(ms).to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+}
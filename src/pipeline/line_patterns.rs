@@ -0,0 +1,136 @@
+//! Pattern lists for `--keep-lines` / `--ignore-lines`.
+//!
+//! Patterns may be given inline (repeatable flag) or read from files holding
+//! one pattern per line. All patterns for one flag are compiled into a single
+//! `RegexSet` so each input line is tested in one pass.
+
+use regex::{Regex, RegexSet};
+use std::fs;
+
+/// Where a pattern came from, used to point at the culprit on compile errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternOrigin {
+    Inline,
+    File { path: String, line: usize },
+}
+
+/// Parse a pattern file: one regex per line, blank lines and lines starting
+/// with `#` (after leading whitespace) are skipped.
+pub fn parse_pattern_list(content: &str) -> Vec<(usize, String)> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, raw)| {
+            let line = raw.trim_end_matches('\r');
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                None
+            } else {
+                Some((idx + 1, line.to_string()))
+            }
+        })
+        .collect()
+}
+
+/// Compile inline patterns and pattern files for `flag` (e.g. "ignore-lines")
+/// into one `RegexSet`. Returns `Ok(None)` when no patterns were supplied.
+pub fn compile_line_patterns(
+    flag: &str,
+    inline: &[String],
+    files: &[String],
+) -> Result<Option<RegexSet>, String> {
+    let mut patterns: Vec<(String, PatternOrigin)> = inline
+        .iter()
+        .map(|p| (p.clone(), PatternOrigin::Inline))
+        .collect();
+
+    for path in files {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}-file '{}': {}", flag, path, e))?;
+        for (line, pattern) in parse_pattern_list(&content) {
+            patterns.push((
+                pattern,
+                PatternOrigin::File {
+                    path: path.clone(),
+                    line,
+                },
+            ));
+        }
+    }
+
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    // RegexSet errors do not say which pattern failed, so validate one by one
+    // first to report the offending pattern and its origin.
+    for (pattern, origin) in &patterns {
+        if let Err(e) = Regex::new(pattern) {
+            return Err(match origin {
+                PatternOrigin::Inline => {
+                    format!("Invalid {} regex pattern '{}': {}", flag, pattern, e)
+                }
+                PatternOrigin::File { path, line } => format!(
+                    "Invalid {} regex pattern '{}' at {}:{}: {}",
+                    flag, pattern, path, line, e
+                ),
+            });
+        }
+    }
+
+    RegexSet::new(patterns.iter().map(|(p, _)| p.as_str()))
+        .map(Some)
+        .map_err(|e| format!("Invalid {} regex patterns: {}", flag, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn pattern_list_skips_comments_and_blank_lines() {
+        let parsed = parse_pattern_list("# noise\n\n^DEBUG\n  # indented comment\nhealthz\r\n");
+        assert_eq!(
+            parsed,
+            vec![(3, "^DEBUG".to_string()), (5, "healthz".to_string())]
+        );
+    }
+
+    #[test]
+    fn inline_and_file_patterns_share_one_set() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "# probes\nhealthz").unwrap();
+        let set = compile_line_patterns(
+            "ignore-lines",
+            &["^DEBUG".to_string()],
+            &[file.path().to_string_lossy().into_owned()],
+        )
+        .unwrap()
+        .unwrap();
+
+        assert!(set.is_match("DEBUG starting"));
+        assert!(set.is_match("GET /healthz 200"));
+        assert!(!set.is_match("INFO ready"));
+    }
+
+    #[test]
+    fn no_patterns_yields_none() {
+        assert!(compile_line_patterns("keep-lines", &[], &[])
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn invalid_file_pattern_reports_file_and_line() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "ok\n# comment\n(unclosed").unwrap();
+        let path = file.path().to_string_lossy().into_owned();
+        let err =
+            compile_line_patterns("keep-lines", &[], std::slice::from_ref(&path)).unwrap_err();
+
+        assert!(err.contains("'(unclosed'"), "{}", err);
+        assert!(err.contains(&format!("{}:3", path)), "{}", err);
+    }
+}
@@ -7,24 +7,34 @@ use std::collections::{HashMap, HashSet};
 use crate::engine::RhaiEngine;
 use crate::event::{Event, SpanStatus};
 use crate::rhai_functions::file_ops::{self, FileOp};
+use every::EveryProcessor;
 use span::SpanProcessor;
+use thin::ThinProcessor;
 
 // Re-export submodules
 pub mod builders;
+pub mod chunking;
 pub mod defaults;
+mod every;
+pub mod line_patterns;
 pub mod multiline;
 pub mod prefix_extractor;
 pub mod prefix_parser;
+pub mod profile;
+pub mod schema;
 pub mod section_selector;
 mod span;
 pub mod stages;
+mod thin;
 
 // Re-export main types for convenience
 pub use builders::*;
+pub use chunking::*;
 pub use defaults::*;
 pub use multiline::*;
 pub use prefix_extractor::*;
 pub use prefix_parser::*;
+pub use profile::*;
 pub use section_selector::*;
 pub use stages::*;
 
@@ -101,6 +111,13 @@ fn collect_discovered_levels_and_keys(event: &Event, ctx: &mut PipelineContext)
     }
 }
 
+/// Remove empty values before formatting (--suppress-empty-fields and friends)
+fn suppress_empty_fields(event: &mut Event, suppression: crate::config::EmptyFieldSuppression) {
+    if suppression.is_active() {
+        event.fields.retain(|_, value| !suppression.matches(value));
+    }
+}
+
 /// Helper function to collect output levels and keys for stats (after filtering)
 fn collect_output_levels_and_keys(event: &Event, ctx: &mut PipelineContext) {
     if !crate::stats::stats_enabled() {
@@ -137,6 +154,8 @@ fn collect_output_levels_and_keys(event: &Event, ctx: &mut PipelineContext) {
             crate::stats::stats_add_output_key(field_key.clone());
         }
     }
+
+    crate::stats::stats_observe_field_types(&event.fields);
 }
 
 /// Core pipeline result types
@@ -214,8 +233,26 @@ pub struct PipelineConfig {
     pub input_files: Vec<String>,
     /// Allow Rhai scripts to create directories and write files on disk
     pub allow_fs_writes: bool,
+    /// Deny scripts file, environment and module access and cap their resources
+    pub sandbox: bool,
     /// Format name (for error reporting)
     pub format_name: Option<String>,
+    /// Time each script stage for the --pipeline-profile report
+    pub profile_stages: bool,
+    /// Empty values removed from events after all stages, before formatting
+    pub suppress_empty_fields: crate::config::EmptyFieldSuppression,
+    /// --on-stage-error
+    pub on_stage_error: Option<crate::cli::StageErrorPolicy>,
+}
+
+impl PipelineConfig {
+    /// How a --filter/--exec error is handled. --strict implies abort unless
+    /// --on-stage-error says otherwise; None keeps the default, where a filter
+    /// error drops the event and an exec error rolls back only that stage.
+    pub fn stage_error_policy(&self) -> Option<crate::cli::StageErrorPolicy> {
+        self.on_stage_error
+            .or(self.strict.then_some(crate::cli::StageErrorPolicy::Abort))
+    }
 }
 
 /// Metadata about current processing context
@@ -244,6 +281,11 @@ pub trait LineFilter: Send {
 /// Handle multi-line log records (future feature)
 pub trait Chunker: Send {
     fn feed_line(&mut self, line: String) -> Option<String>;
+    /// Further records completed by the last `feed_line` (e.g. `--raw-bytes`
+    /// cutting one long line into several); callers drain it after each feed.
+    fn take_ready(&mut self) -> Option<String> {
+        None
+    }
     fn flush(&mut self) -> Option<String>;
     fn has_pending(&self) -> bool;
 }
@@ -263,6 +305,24 @@ pub trait ScriptStage: Send {
     fn uses_window(&self) -> bool {
         false
     }
+
+    /// Name shown for this stage in the --pipeline-profile report.
+    fn label(&self) -> String {
+        "stage".to_string()
+    }
+
+    /// Whether this stage will never emit another event (e.g. --take-while
+    /// after its predicate failed), so input reading can stop early.
+    fn is_exhausted(&self) -> bool {
+        false
+    }
+
+    /// Events this stage still holds once input has ended (e.g. the open
+    /// --group-consecutive-by group). They continue through the stages after
+    /// this one.
+    fn finish(&mut self) -> Vec<Event> {
+        Vec::new()
+    }
 }
 
 /// Optional event limiting (--take N)
@@ -275,10 +335,23 @@ pub trait EventLimiter: Send {
 pub trait Formatter: Send + Sync {
     fn format(&self, event: &Event) -> String;
 
+    /// Format an event for output, or reject it with a message. Only
+    /// formatters that can refuse an event (e.g. `--json-flat` under
+    /// `--collision error`) override this.
+    fn try_format(&self, event: &Event) -> Result<String, String> {
+        Ok(self.format(event))
+    }
+
     /// Flush any pending formatter state at the end of processing
     fn finish(&self) -> Option<String> {
         None
     }
+
+    /// Finalize output the formatter writes itself rather than as lines
+    /// (e.g. the ORC footer). Called once, after `finish`.
+    fn close(&self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 /// Write formatted output
@@ -287,6 +360,9 @@ pub trait OutputWriter: Send {
     fn flush(&mut self) -> std::io::Result<()>;
 }
 
+/// Largest line buffer handed back to the sequential reader for reuse
+const MAX_RECYCLED_LINE_CAPACITY: usize = 64 * 1024;
+
 /// Main pipeline structure
 pub struct Pipeline {
     pub line_filter: Option<Box<dyn LineFilter>>,
@@ -294,15 +370,24 @@ pub struct Pipeline {
     pub parser: Box<dyn EventParser>,
     pub script_stages: Vec<Box<dyn ScriptStage>>,
     pub limiter: Option<Box<dyn EventLimiter>>,
+    /// --reservoir N: holds output events until the stream ends
+    pub reservoir: Option<ReservoirSampler<Event>>,
     pub formatter: Box<dyn Formatter>,
     pub output: Box<dyn OutputWriter>,
     pub window_manager: Box<dyn WindowManager>,
     pub span_processor: Option<SpanProcessor>,
+    /// --every: tumbling metric windows
+    pub every: Option<EveryProcessor>,
+    /// --thin: one event per interval, ahead of --reservoir and --take
+    pub thin: Option<ThinProcessor>,
     pub ts_config: crate::timestamp::TsConfig,
     /// Whether per-event window maintenance is needed: true if `--window` was
     /// set or any script stage reads the `window` variable. When false, the
     /// window manager is never touched, avoiding two event clones per line.
     pub window_active: bool,
+    /// Hands each parsed line's buffer back to the sequential reader thread,
+    /// which reads the next line into it instead of allocating a fresh one.
+    pub line_recycler: Option<crossbeam_channel::Sender<String>>,
 }
 
 impl Pipeline {
@@ -321,21 +406,26 @@ impl Pipeline {
         }
 
         // Chunker stage (for multi-line records)
-        if let Some(chunk) = self.chunker.feed_line(line) {
-            self.process_chunk(chunk, ctx)
-        } else {
-            Ok(Vec::new())
+        let Some(chunk) = self.chunker.feed_line(line) else {
+            return Ok(Vec::new());
+        };
+        let mut results = self.process_chunk(chunk, ctx)?;
+        while let Some(chunk) = self.chunker.take_ready() {
+            results.extend(self.process_chunk(chunk, ctx)?);
         }
+        Ok(results)
     }
 
     /// Flush any remaining chunks from the chunker
     pub fn flush(&mut self, ctx: &mut PipelineContext) -> Result<Vec<FormattedOutput>> {
-        if let Some(chunk) = self.chunker.flush() {
+        // A chunker may hold several complete records at end of input
+        // (e.g. `--raw-bytes` cutting one long line), so drain it fully
+        let mut results = Vec::new();
+        while let Some(chunk) = self.chunker.flush() {
             // Process chunk directly, not through feed_line
-            self.process_chunk_directly(chunk, ctx)
-        } else {
-            Ok(Vec::new())
+            results.extend(self.process_chunk_directly(chunk, ctx)?);
         }
+        Ok(results)
     }
 
     /// Process a complete event string (for pre-chunked multiline events)
@@ -355,11 +445,107 @@ impl Pipeline {
             .map(|line| FormattedOutput::new(line, None))
     }
 
-    pub fn finish_spans(&mut self, ctx: &mut PipelineContext) -> Result<()> {
+    /// Finalize a file-writing formatter; see `Formatter::close`
+    pub fn close_formatter(&self) -> std::io::Result<()> {
+        self.formatter.close()
+    }
+
+    /// Write the --reservoir sample, in stream order, once input has ended.
+    /// Sampled events go through --take, stats and formatting like any other.
+    /// Events script stages and --thin still hold are released first, so
+    /// they can be sampled too.
+    pub fn finish_reservoir(&mut self, ctx: &mut PipelineContext) -> Result<Vec<FormattedOutput>> {
+        let mut outputs = Vec::new();
+        for stage_index in 0..self.script_stages.len() {
+            let held = self.script_stages[stage_index].finish();
+            if !held.is_empty() {
+                self.run_script_stages_from(
+                    stage_index + 1,
+                    ScriptResult::EmitMultiple(held),
+                    ctx,
+                    &mut outputs,
+                )?;
+            }
+        }
+        if let Some(held) = self.thin.as_mut().map(ThinProcessor::finish) {
+            for event in held {
+                self.deliver_event(event, ctx, &mut outputs, Vec::new())?;
+            }
+        }
+        let Some(mut reservoir) = self.reservoir.take() else {
+            return Ok(outputs);
+        };
+        for event in reservoir.drain() {
+            self.deliver_event(event, ctx, &mut outputs, Vec::new())?;
+        }
+        self.reservoir = Some(reservoir);
+        Ok(outputs)
+    }
+
+    /// Parallel counterpart of `finish_reservoir`: drain this worker's partial
+    /// sample as `(seen, [(line_num, output)])` for the sink to merge. The
+    /// events are formatted but not counted; the sink accounts for them once
+    /// it knows which ones survive the merge.
+    pub fn take_reservoir_partial(&mut self) -> Option<(usize, Vec<(usize, FormattedOutput)>)> {
+        let reservoir = self.reservoir.as_mut()?;
+        let seen = reservoir.seen();
+        let sample = reservoir
+            .drain()
+            .into_iter()
+            .map(|mut event| {
+                event.parsed_ts = None;
+                event.extract_timestamp_with_config(None, &self.ts_config);
+                let line = self.formatter.format(&event);
+                let line_num = event.line_num.unwrap_or(0);
+                (line_num, FormattedOutput::new(line, event.parsed_ts))
+            })
+            .collect();
+        Some((seen, sample))
+    }
+
+    /// Close the final open span and any open --every windows, returning
+    /// their summary output (if any).
+    pub fn finish_spans(&mut self, ctx: &mut PipelineContext) -> Result<Vec<FormattedOutput>> {
+        let mut outputs = Vec::new();
         if let Some(span_processor) = self.span_processor.as_mut() {
             span_processor.finish(ctx)?;
         }
-        Ok(())
+        if let Some(every) = self.every.as_mut() {
+            every.finish(ctx)?;
+        }
+        self.emit_summaries(ctx, &mut outputs);
+        Ok(outputs)
+    }
+
+    /// Write summary events of spans (`--span-output summary|both`) and
+    /// --every windows that closed. Summaries count as output events, so they
+    /// are subject to --take.
+    fn emit_summaries(&mut self, ctx: &mut PipelineContext, outputs: &mut Vec<FormattedOutput>) {
+        let mut summaries = match self.span_processor.as_mut() {
+            Some(span) => span.take_summaries(),
+            None => Vec::new(),
+        };
+        if let Some(every) = self.every.as_mut() {
+            summaries.extend(every.take_summaries());
+        }
+        for mut event in summaries {
+            if !self.limiter.as_mut().is_none_or(|l| l.allow()) {
+                continue;
+            }
+            suppress_empty_fields(&mut event, ctx.config.suppress_empty_fields);
+
+            crate::stats::stats_add_event_output();
+            ctx.internal_stats.events_output += 1;
+            collect_output_levels_and_keys(&event, ctx);
+            if crate::field_discovery::is_enabled() && crate::field_discovery::is_discover_final() {
+                crate::field_discovery::observe_event_fields(&event.fields);
+            }
+
+            let format_started = profile::phase_timer();
+            let formatted = self.formatter.format(&event);
+            profile::record_phase_time(profile::Phase::Format, format_started);
+            outputs.push(FormattedOutput::new(formatted, event.parsed_ts));
+        }
     }
 
     fn apply_script_result(
@@ -451,6 +637,58 @@ impl Pipeline {
     ) -> Result<()> {
         if let Some(span) = self.span_processor.as_mut() {
             span.prepare_emitted_event(&mut event);
+            if !span.emits_members() {
+                return self.absorb_span_member(event, ctx, outputs, ops);
+            }
+        }
+
+        suppress_empty_fields(&mut event, ctx.config.suppress_empty_fields);
+
+        if let Some(thin) = self.thin.as_mut() {
+            if !event.fields.is_empty() {
+                // File side effects are not deferred with the event
+                if !ops.is_empty() {
+                    outputs.push(FormattedOutput::with_ops(String::new(), None, ops));
+                }
+                event.parsed_ts = None;
+                event.extract_timestamp_with_config(None, &self.ts_config);
+                let outcome = thin.offer(event);
+                for _ in 0..outcome.dropped {
+                    crate::stats::stats_add_event_thinned();
+                    crate::stats::stats_add_event_filtered();
+                    ctx.internal_stats.events_filtered += 1;
+                }
+                for event in outcome.released {
+                    self.deliver_event(event, ctx, outputs, Vec::new())?;
+                }
+                return Ok(());
+            }
+        }
+
+        self.deliver_event(event, ctx, outputs, ops)
+    }
+
+    /// Hand an event that passed every stage to --reservoir, --take and the
+    /// formatter.
+    fn deliver_event(
+        &mut self,
+        mut event: Event,
+        ctx: &mut PipelineContext,
+        outputs: &mut Vec<FormattedOutput>,
+        ops: Vec<FileOp>,
+    ) -> Result<()> {
+        if let Some(reservoir) = self.reservoir.as_mut() {
+            if !event.fields.is_empty() {
+                // File side effects are not deferred with the event
+                if !ops.is_empty() {
+                    outputs.push(FormattedOutput::with_ops(String::new(), None, ops));
+                }
+                if reservoir.offer(event).is_some() {
+                    crate::stats::stats_add_event_filtered();
+                    ctx.internal_stats.events_filtered += 1;
+                }
+                return Ok(());
+            }
         }
 
         if self.limiter.as_mut().is_none_or(|l| l.allow()) {
@@ -467,6 +705,38 @@ impl Pipeline {
                     outputs.push(FormattedOutput::with_ops(String::new(), None, ops));
                 }
             } else {
+                // Refresh parsed_ts after script stages so stats and output both see the
+                // final timestamp value without cloning the whole event.
+                event.parsed_ts = None;
+                event.extract_timestamp_with_config(None, &self.ts_config);
+
+                // Format first: a formatter that rejects the event makes it
+                // an error rather than an output event
+                let format_started = profile::phase_timer();
+                let formatted = self.formatter.try_format(&event);
+                profile::record_phase_time(profile::Phase::Format, format_started);
+                let formatted = match formatted {
+                    Ok(formatted) => formatted,
+                    Err(msg) => {
+                        file_ops::clear_pending_ops();
+                        // Tracked like the stage collision errors, which
+                        // fail the run in any mode
+                        crate::rhai_functions::tracking::track_error(
+                            "script",
+                            ctx.meta.line_num,
+                            &msg,
+                            None,
+                            ctx.meta.filename.as_deref(),
+                            ctx.config.verbose,
+                            ctx.config.quiet_level,
+                            Some(&ctx.config),
+                            None,
+                        );
+                        stages::persist_error_tracking(ctx);
+                        return Err(anyhow!(msg));
+                    }
+                };
+
                 crate::stats::stats_add_event_output();
                 ctx.internal_stats.events_output += 1;
 
@@ -480,10 +750,6 @@ impl Pipeline {
                     crate::field_discovery::observe_event_fields(&event.fields);
                 }
 
-                // Refresh parsed_ts after script stages so stats and output both see the
-                // final timestamp value without cloning the whole event.
-                event.parsed_ts = None;
-                event.extract_timestamp_with_config(None, &self.ts_config);
                 if let Some(result_ts) = event.parsed_ts {
                     crate::stats::stats_update_result_timestamp(result_ts);
                 }
@@ -492,9 +758,9 @@ impl Pipeline {
                     span.record_emitted_event(&event, ctx)?;
                 }
 
-                let formatted = self.formatter.format(&event);
                 let timestamp = event.parsed_ts;
                 outputs.push(FormattedOutput::with_ops(formatted, timestamp, ops));
+                self.emit_summaries(ctx, outputs);
             }
         } else {
             crate::stats::stats_add_event_filtered();
@@ -513,6 +779,35 @@ impl Pipeline {
         Ok(())
     }
 
+    /// `--span-output summary`: feed an event into its span without writing it.
+    /// Only the span summaries reach the formatter and count against --take.
+    fn absorb_span_member(
+        &mut self,
+        mut event: Event,
+        ctx: &mut PipelineContext,
+        outputs: &mut Vec<FormattedOutput>,
+        ops: Vec<FileOp>,
+    ) -> Result<()> {
+        if let Some(span) = self.span_processor.as_mut() {
+            if event.fields.is_empty() {
+                event.span.status = Some(SpanStatus::Filtered);
+                crate::stats::stats_add_event_filtered();
+                ctx.internal_stats.events_filtered += 1;
+                span.handle_skip(ctx);
+            } else {
+                event.parsed_ts = None;
+                event.extract_timestamp_with_config(None, &self.ts_config);
+                span.record_emitted_event(&event, ctx)?;
+            }
+        }
+
+        if !ops.is_empty() {
+            outputs.push(FormattedOutput::with_ops(String::new(), None, ops));
+        }
+        self.emit_summaries(ctx, outputs);
+        Ok(())
+    }
+
     /// Process a chunk directly without going through the chunker
     fn process_chunk_directly(
         &mut self,
@@ -530,8 +825,19 @@ impl Pipeline {
         let mut results = Vec::new();
 
         // Parse stage
-        let mut event = match self.parser.parse(&chunk) {
+        let parse_started = profile::phase_timer();
+        let parsed = self.parser.parse(&chunk);
+        profile::record_phase_time(profile::Phase::Parse, parse_started);
+        let mut event = match parsed {
             Ok(mut e) => {
+                // The event owns copies of everything it needs from the line.
+                // Oversized buffers are dropped rather than kept alive in the pool.
+                if let Some(recycler) = &self.line_recycler {
+                    if chunk.capacity() <= MAX_RECYCLED_LINE_CAPACITY {
+                        let _ = recycler.try_send(chunk);
+                    }
+                }
+
                 // Event was successfully created from chunk
                 crate::stats::stats_add_event_created();
                 ctx.internal_stats.events_created += 1;
@@ -569,10 +875,11 @@ impl Pipeline {
                 ctx.internal_stats.lines_errors += 1;
 
                 // Use unified error tracking system
+                let message = err.to_string();
                 crate::rhai_functions::tracking::track_error(
                     "parse",
                     ctx.meta.line_num,
-                    &err.to_string(),
+                    &message,
                     Some(&chunk),
                     ctx.meta.filename.as_deref(),
                     ctx.config.verbose,
@@ -586,6 +893,7 @@ impl Pipeline {
                 // ctx.internal_tracker over the thread state) cannot wipe the
                 // parse error count out of the summary and the exit-code gate.
                 stages::persist_error_tracking(ctx);
+                stages::run_error_hook(ctx, "parse", &message, Some(&chunk));
 
                 // New resiliency model: skip unparseable lines by default,
                 // only propagate errors in strict mode
@@ -601,6 +909,12 @@ impl Pipeline {
         if let Some(span_processor) = self.span_processor.as_mut() {
             span_processor.prepare_event(&mut event, ctx)?;
         }
+        if let Some(every) = self.every.as_mut() {
+            every.prepare_event(&mut event, ctx)?;
+        }
+        // A time/field/idle span or an --every window closes when the first
+        // event past it arrives, so its summary precedes that event's output.
+        self.emit_summaries(ctx, &mut results);
 
         // Update window manager (skipped entirely when no stage observes the
         // `window` variable and --window was not set, avoiding two event clones).
@@ -609,6 +923,43 @@ impl Pipeline {
             ctx.window = self.window_manager.get_window();
         }
 
+        self.run_script_stages(event, ctx, &mut results)?;
+        Ok(results)
+    }
+
+    /// Run a synthetic event (`--stats-to-event`) through the script stages,
+    /// --take and the formatter. It bypasses parsing, spans and windows.
+    pub fn process_synthetic_event(
+        &mut self,
+        event: Event,
+        ctx: &mut PipelineContext,
+    ) -> Result<Vec<FormattedOutput>> {
+        let mut results = Vec::new();
+        ctx.meta.line_num = None;
+        self.run_script_stages(event, ctx, &mut results)?;
+        Ok(results)
+    }
+
+    /// Apply the script stages (filters, execs, etc.) to one event and
+    /// collect its formatted output.
+    fn run_script_stages(
+        &mut self,
+        event: Event,
+        ctx: &mut PipelineContext,
+        results: &mut Vec<FormattedOutput>,
+    ) -> Result<()> {
+        self.run_script_stages_from(0, ScriptResult::Emit(event), ctx, results)
+    }
+
+    /// Apply the script stages from `first_stage` on to `result` and collect
+    /// the formatted output.
+    fn run_script_stages_from(
+        &mut self,
+        first_stage: usize,
+        mut result: ScriptResult,
+        ctx: &mut PipelineContext,
+        results: &mut Vec<FormattedOutput>,
+    ) -> Result<()> {
         // Reset per-event skip flag for Rhai skip()
         crate::rhai_functions::process::clear_skip_request();
 
@@ -616,9 +967,9 @@ impl Pipeline {
         ctx.pending_file_ops.clear();
 
         // Apply script stages (filters, execs, etc.)
-        let mut result = ScriptResult::Emit(event);
-
-        for stage in &mut self.script_stages {
+        let profile_stages = ctx.config.profile_stages;
+        for (stage_index, stage) in self.script_stages.iter_mut().enumerate().skip(first_stage) {
+            let stage_started = profile_stages.then(std::time::Instant::now);
             result = match result {
                 ScriptResult::Emit(event) => stage.apply(event, ctx),
                 ScriptResult::EmitMultiple(events) => {
@@ -655,7 +1006,7 @@ impl Pipeline {
                                     return Err(anyhow::anyhow!(msg));
                                 } else {
                                     // Skip errors in resilient mode and continue processing
-                                    return Ok(results);
+                                    return Ok(());
                                 }
                             }
                         }
@@ -664,6 +1015,15 @@ impl Pipeline {
                 }
                 other => other, // Skip or Error, stop processing
             };
+            if let Some(started) = stage_started {
+                profile::record_stage_time(stage_index, started.elapsed());
+            }
+
+            // exit() ends the run at this event, which is neither output
+            // nor counted
+            if ctx.rhai.exit_requested() {
+                return Ok(());
+            }
 
             match &result {
                 ScriptResult::Skip | ScriptResult::Error(_) => break,
@@ -677,14 +1037,14 @@ impl Pipeline {
             ctx.pending_file_ops.extend(remaining_ops);
         }
 
-        self.apply_script_result(result, ctx, &mut results)?;
-
-        Ok(results)
+        self.apply_script_result(result, ctx, results)
     }
 
-    /// Check if the event limiter (--take N) is exhausted
+    /// Check if the event limiter (--take N) or a stream cutoff stage
+    /// (--take-while) is exhausted
     pub fn is_take_limit_exhausted(&self) -> bool {
         self.limiter.as_ref().is_some_and(|l| l.is_exhausted())
+            || self.script_stages.iter().any(|s| s.is_exhausted())
     }
 
     /// Check if the chunker currently holds a partial chunk that hasn't been emitted yet
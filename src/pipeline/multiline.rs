@@ -2,6 +2,7 @@ use super::Chunker;
 use crate::config::{InputFormat, MultilineConfig, MultilineJoin, MultilineStrategy};
 use crate::timestamp::AdaptiveTsParser;
 use regex::Regex;
+use std::collections::VecDeque;
 
 const MAX_TIMESTAMP_PREFIX_CHARS: usize = 64;
 const MAX_TIMESTAMP_TOKENS: usize = 6;
@@ -13,7 +14,12 @@ pub struct MultilineChunker {
     start_regex: Option<Regex>,
     end_regex: Option<Regex>,
     timestamp_detector: Option<TimestampDetector>,
-    pending_output: Option<String>,
+    /// Complete records beyond the one `feed_line` returned
+    pending_output: VecDeque<String>,
+    /// Input re-joined with newlines, awaiting cuts for the `Bytes` strategy
+    stream: String,
+    /// Whether any line has been added to `stream`
+    stream_started: bool,
 }
 
 impl MultilineChunker {
@@ -38,6 +44,16 @@ impl MultilineChunker {
             MultilineStrategy::Timestamp { chrono_format } => {
                 timestamp_detector = Some(TimestampDetector::new(chrono_format.clone()));
             }
+            MultilineStrategy::Separator { pattern } => {
+                end_regex = Some(
+                    Regex::new(pattern).map_err(|e| format!("Invalid separator pattern: {}", e))?,
+                );
+            }
+            MultilineStrategy::Bytes { count } => {
+                if *count == 0 {
+                    return Err("Record size must be greater than zero".to_string());
+                }
+            }
             MultilineStrategy::Indent | MultilineStrategy::All => {}
         }
 
@@ -47,7 +63,9 @@ impl MultilineChunker {
             start_regex,
             end_regex,
             timestamp_detector,
-            pending_output: None,
+            pending_output: VecDeque::new(),
+            stream: String::new(),
+            stream_started: false,
         })
     }
 
@@ -69,7 +87,9 @@ impl MultilineChunker {
                     false
                 }
             }
-            MultilineStrategy::All => false,
+            MultilineStrategy::All
+            | MultilineStrategy::Separator { .. }
+            | MultilineStrategy::Bytes { .. } => false,
         }
     }
 
@@ -87,9 +107,12 @@ impl MultilineChunker {
             return None;
         }
 
-        // The `All` strategy preserves the input's line structure by joining
-        // with newlines, regardless of the configured join mode.
-        let content = if matches!(self.config.strategy, MultilineStrategy::All) {
+        // The `All` and `Separator` strategies preserve the input's line
+        // structure by joining with newlines, regardless of the configured join mode.
+        let content = if matches!(
+            self.config.strategy,
+            MultilineStrategy::All | MultilineStrategy::Separator { .. }
+        ) {
             self.buffer.join("\n")
         } else {
             let joiner = match self.config.join {
@@ -110,6 +133,31 @@ impl MultilineChunker {
         self.buffer.clear();
         Some(content)
     }
+
+    /// Cut the next record from `stream`: `count` bytes, or whatever is left
+    /// once input has ended. Cuts back off to a character boundary so a
+    /// multi-byte character is never split.
+    fn cut_stream(&mut self, count: usize, at_end: bool) -> Option<String> {
+        if self.stream.is_empty() || (!at_end && self.stream.len() < count) {
+            return None;
+        }
+        if self.stream.len() <= count {
+            return Some(std::mem::take(&mut self.stream));
+        }
+
+        let mut cut = count;
+        while !self.stream.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        if cut == 0 {
+            cut = count;
+            while !self.stream.is_char_boundary(cut) {
+                cut += 1;
+            }
+        }
+        let rest = self.stream.split_off(cut);
+        Some(std::mem::replace(&mut self.stream, rest))
+    }
 }
 
 struct TimestampDetector {
@@ -263,11 +311,7 @@ fn push_candidate(candidates: &mut Vec<String>, candidate: String) {
 
 impl Chunker for MultilineChunker {
     fn feed_line(&mut self, line: String) -> Option<String> {
-        let mut produced = Vec::new();
-
-        if let Some(pending) = self.pending_output.take() {
-            produced.push(pending);
-        }
+        let mut produced: Vec<String> = self.pending_output.drain(..).collect();
 
         let flush_on_start = matches!(
             self.config.strategy,
@@ -300,29 +344,59 @@ impl Chunker for MultilineChunker {
             MultilineStrategy::Timestamp { .. } | MultilineStrategy::Indent => {
                 self.buffer.push(line);
             }
-        }
+            MultilineStrategy::Separator { .. } => {
+                if self
+                    .end_regex
+                    .as_ref()
+                    .is_some_and(|sep| sep.is_match(&line))
+                {
+                    if let Some(event) = self.flush_buffer() {
+                        produced.push(event);
+                    }
+                } else {
+                    self.buffer.push(line);
+                }
+            }
+            MultilineStrategy::Bytes { count } => {
+                // Lines arrive without terminators; put the newlines back so
+                // records are cut from the original byte stream
+                if self.stream_started {
+                    self.stream.push('\n');
+                }
+                self.stream_started = true;
+                self.stream.push_str(&line);
 
-        if produced.is_empty() {
-            None
-        } else {
-            let first = produced.remove(0);
-            if let Some(next) = produced.into_iter().next() {
-                self.pending_output = Some(next);
+                let count = *count;
+                while let Some(record) = self.cut_stream(count, false) {
+                    produced.push(record);
+                }
             }
-            Some(first)
         }
+
+        let mut produced = produced.into_iter();
+        let first = produced.next();
+        self.pending_output.extend(produced);
+        first
+    }
+
+    fn take_ready(&mut self) -> Option<String> {
+        self.pending_output.pop_front()
     }
 
     fn flush(&mut self) -> Option<String> {
-        if let Some(pending) = self.pending_output.take() {
+        if let Some(pending) = self.pending_output.pop_front() {
             return Some(pending);
         }
 
+        if let MultilineStrategy::Bytes { count } = self.config.strategy {
+            return self.cut_stream(count, true);
+        }
+
         self.flush_buffer()
     }
 
     fn has_pending(&self) -> bool {
-        self.pending_output.is_some() || !self.buffer.is_empty()
+        !self.pending_output.is_empty() || !self.buffer.is_empty() || !self.stream.is_empty()
     }
 }
 
@@ -790,4 +864,81 @@ mod tests {
         assert!(chunker.flush().is_none());
         assert!(chunker.flush().is_none());
     }
+
+    #[test]
+    fn test_separator_strategy_drops_separator_lines() {
+        let config = MultilineConfig {
+            strategy: MultilineStrategy::Separator {
+                pattern: "^=====$".to_string(),
+            },
+            join: MultilineJoin::Newline,
+        };
+
+        let mut chunker = MultilineChunker::new(config, InputFormat::Raw).unwrap();
+
+        assert!(chunker.feed_line("=====".to_string()).is_none());
+        assert!(chunker.feed_line("first".to_string()).is_none());
+        assert!(chunker.feed_line("  body".to_string()).is_none());
+        assert_eq!(
+            chunker.feed_line("=====".to_string()),
+            Some("first\n  body".to_string())
+        );
+        // Back-to-back separators do not produce empty records
+        assert!(chunker.feed_line("=====".to_string()).is_none());
+        assert!(chunker.feed_line("second".to_string()).is_none());
+        assert_eq!(chunker.flush(), Some("second".to_string()));
+        assert!(chunker.flush().is_none());
+    }
+
+    #[test]
+    fn test_bytes_strategy_cuts_stream() {
+        let config = MultilineConfig {
+            strategy: MultilineStrategy::Bytes { count: 4 },
+            join: MultilineJoin::Newline,
+        };
+
+        let mut chunker = MultilineChunker::new(config, InputFormat::Raw).unwrap();
+
+        assert!(chunker.feed_line("ab".to_string()).is_none());
+        assert_eq!(
+            chunker.feed_line("cdefghijk".to_string()),
+            Some("ab\nc".to_string())
+        );
+
+        // Every record the long line completed is ready at once, leaving
+        // nothing buffered
+        let mut rest = Vec::new();
+        while let Some(record) = chunker.take_ready() {
+            rest.push(record);
+        }
+        assert_eq!(rest, vec!["defg", "hijk"]);
+        assert!(!chunker.has_pending());
+        assert!(chunker.flush().is_none());
+
+        // A trailing partial record waits for more input or the final flush
+        assert!(chunker.feed_line("lm".to_string()).is_none());
+        assert!(chunker.has_pending());
+        assert_eq!(chunker.flush(), Some("\nlm".to_string()));
+    }
+
+    #[test]
+    fn test_bytes_strategy_keeps_characters_whole() {
+        let config = MultilineConfig {
+            strategy: MultilineStrategy::Bytes { count: 2 },
+            join: MultilineJoin::Newline,
+        };
+
+        let mut chunker = MultilineChunker::new(config, InputFormat::Raw).unwrap();
+
+        assert_eq!(chunker.feed_line("aé".to_string()), Some("a".to_string()));
+        assert_eq!(chunker.flush(), Some("é".to_string()));
+        assert!(MultilineChunker::new(
+            MultilineConfig {
+                strategy: MultilineStrategy::Bytes { count: 0 },
+                join: MultilineJoin::Newline,
+            },
+            InputFormat::Raw
+        )
+        .is_err());
+    }
 }
@@ -1,26 +1,50 @@
+use crate::config::PrefixStrip;
 use crate::event::Event;
 use rhai::Dynamic;
 
+#[derive(Debug, Clone)]
+enum PrefixSplit {
+    /// Everything before the separator (--extract-prefix/--prefix-sep)
+    Separator(String),
+    /// A fixed width or regex match (--strip-prefix/--strip-prefix-re)
+    Strip(PrefixStrip),
+}
+
 /// Extracts prefix from lines and adds it to parsed events
 #[derive(Debug, Clone)]
 pub struct PrefixExtractor {
-    pub field_name: String,
-    pub separator: String,
+    field_name: Option<String>,
+    split: PrefixSplit,
 }
 
 impl PrefixExtractor {
     pub fn new(field_name: String, separator: String) -> Self {
+        Self {
+            field_name: Some(field_name),
+            split: PrefixSplit::Separator(separator),
+        }
+    }
+
+    /// Strip a prefix without a separator, optionally keeping it in a field
+    pub fn stripping(strip: PrefixStrip, field_name: Option<String>) -> Self {
         Self {
             field_name,
-            separator,
+            split: PrefixSplit::Strip(strip),
         }
     }
 
-    /// Extract prefix from line if separator is found, return (modified_line, extracted_prefix)
+    /// Split the prefix off line if present, return (modified_line, extracted_prefix)
     pub fn extract_prefix(&self, line: &str) -> (String, Option<String>) {
-        if let Some(sep_pos) = line.find(&self.separator) {
+        match &self.split {
+            PrefixSplit::Separator(separator) => Self::split_at_separator(line, separator),
+            PrefixSplit::Strip(strip) => Self::strip(line, strip),
+        }
+    }
+
+    fn split_at_separator(line: &str, separator: &str) -> (String, Option<String>) {
+        if let Some(sep_pos) = line.find(separator) {
             let prefix = line[..sep_pos].trim();
-            let remaining = line[sep_pos + self.separator.len()..].trim();
+            let remaining = line[sep_pos + separator.len()..].trim();
 
             if !prefix.is_empty() {
                 (remaining.to_string(), Some(prefix.to_string()))
@@ -34,10 +58,39 @@ impl PrefixExtractor {
         }
     }
 
+    /// Unlike the separator form, the rest of the line is kept verbatim so
+    /// fixed-width and regex prefixes cut exactly where they say.
+    fn strip(line: &str, strip: &PrefixStrip) -> (String, Option<String>) {
+        let (end, prefix) = match strip {
+            PrefixStrip::Width(width) => {
+                let end = line
+                    .char_indices()
+                    .map(|(idx, _)| idx)
+                    .chain(std::iter::once(line.len()))
+                    .nth(*width);
+                match end {
+                    Some(end) => (end, &line[..end]),
+                    None => return (line.to_string(), None),
+                }
+            }
+            PrefixStrip::Regex(regex) => match regex.captures(line) {
+                Some(caps) if caps.get(0).is_some_and(|m| m.start() == 0) => {
+                    let matched = caps.get(0).expect("checked above");
+                    let prefix = caps.get(1).unwrap_or(matched).as_str();
+                    (matched.end(), prefix)
+                }
+                _ => return (line.to_string(), None),
+            },
+        };
+        let prefix = prefix.trim();
+        let prefix = (!prefix.is_empty()).then(|| prefix.to_string());
+        (line[end..].to_string(), prefix)
+    }
+
     /// Add extracted prefix to an event
     pub fn add_prefix_to_event(&self, event: &mut Event, prefix: Option<String>) {
-        if let Some(prefix_value) = prefix {
-            event.set_field(self.field_name.clone(), Dynamic::from(prefix_value));
+        if let (Some(field_name), Some(prefix_value)) = (&self.field_name, prefix) {
+            event.set_field(field_name.clone(), Dynamic::from(prefix_value));
         }
     }
 }
@@ -76,6 +129,56 @@ mod tests {
         assert_eq!(prefix, Some("auth-service".to_string()));
     }
 
+    #[test]
+    fn test_strip_fixed_width_prefix() {
+        let extractor = PrefixExtractor::stripping(PrefixStrip::Width(9), Some("src".into()));
+
+        let (line, prefix) = extractor.extract_prefix("web_1    {\"level\":\"info\"}");
+        assert_eq!(line, "{\"level\":\"info\"}");
+        assert_eq!(prefix, Some("web_1".to_string()));
+
+        // Width counts characters, and short lines are left alone
+        let extractor = PrefixExtractor::stripping(PrefixStrip::Width(2), None);
+        assert_eq!(extractor.extract_prefix("äöx").0, "x");
+        assert_eq!(extractor.extract_prefix("ä"), ("ä".to_string(), None));
+    }
+
+    #[test]
+    fn test_strip_regex_prefix() {
+        let regex = regex::Regex::new(r"^\S+\s+").unwrap();
+        let extractor = PrefixExtractor::stripping(PrefixStrip::Regex(regex), Some("src".into()));
+
+        let (line, prefix) = extractor.extract_prefix("api-7f9c  GET /health 200");
+        assert_eq!(line, "GET /health 200");
+        assert_eq!(prefix, Some("api-7f9c".to_string()));
+
+        // The first capture group becomes the prefix when there is one
+        let regex = regex::Regex::new(r"^\[pod/([^\]]+)\] ").unwrap();
+        let extractor = PrefixExtractor::stripping(PrefixStrip::Regex(regex), Some("pod".into()));
+        let (line, prefix) = extractor.extract_prefix("[pod/web-0] ready");
+        assert_eq!(line, "ready");
+        assert_eq!(prefix, Some("web-0".to_string()));
+
+        // Matches that don't start the line leave it untouched
+        let regex = regex::Regex::new(r"\d+ ").unwrap();
+        let extractor = PrefixExtractor::stripping(PrefixStrip::Regex(regex), None);
+        assert_eq!(
+            extractor.extract_prefix("id 42 ok"),
+            ("id 42 ok".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_strip_without_field_drops_prefix() {
+        let extractor = PrefixExtractor::stripping(PrefixStrip::Width(3), None);
+        let mut event = Event::default_with_line("test line".to_string());
+
+        let (_, prefix) = extractor.extract_prefix("ab|rest");
+        extractor.add_prefix_to_event(&mut event, prefix);
+
+        assert!(event.fields.is_empty());
+    }
+
     #[test]
     fn test_add_prefix_to_event() {
         let extractor = PrefixExtractor::new("src".to_string(), "|".to_string());
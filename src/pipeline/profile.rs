@@ -0,0 +1,205 @@
+//! Per-stage timing for --pipeline-profile and --benchmark.
+//!
+//! Every pipeline built for a run (the sequential one, or one per parallel
+//! worker) has the same script stage list, so timings are kept in one table
+//! per run, indexed by stage position and summed across threads.
+//! --benchmark additionally times the fixed parse, format and write phases.
+
+use crate::run_scope;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Fixed pipeline phases timed by --benchmark around the script stages
+#[derive(Debug, Clone, Copy)]
+pub enum Phase {
+    Parse,
+    Format,
+    Write,
+}
+
+/// Start a phase timer; None (no clock read) unless --benchmark is active.
+pub fn phase_timer() -> Option<Instant> {
+    run_scope::with_current(|scope| scope.settings.benchmark).then(Instant::now)
+}
+
+/// Add the time since `started` to `phase`; no-op for a disabled timer.
+pub fn record_phase_time(phase: Phase, started: Option<Instant>) {
+    if let Some(started) = started {
+        let nanos = u64::try_from(started.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        run_scope::with_current(|scope| {
+            scope.counters.phase_nanos[phase as usize].fetch_add(nanos, Ordering::Relaxed)
+        });
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct StageTiming {
+    label: String,
+    nanos: AtomicU64,
+}
+
+/// Register the script stage labels in pipeline order. Only the first call
+/// takes effect; later pipelines built for the same run share the table.
+pub fn register_profiled_stages(labels: Vec<String>) {
+    run_scope::with_current(|scope| {
+        let _ = scope.stage_timings.set(
+            labels
+                .into_iter()
+                .map(|label| StageTiming {
+                    label,
+                    nanos: AtomicU64::new(0),
+                })
+                .collect(),
+        );
+    });
+}
+
+/// Add time spent in the stage at `index`.
+pub fn record_stage_time(index: usize, elapsed: Duration) {
+    run_scope::with_current(|scope| {
+        if let Some(stage) = scope
+            .stage_timings
+            .get()
+            .and_then(|stages| stages.get(index))
+        {
+            let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+            stage.nanos.fetch_add(nanos, Ordering::Relaxed);
+        }
+    });
+}
+
+/// Render the profile report, one line per stage, or None when profiling was
+/// never enabled for this run.
+pub fn format_stage_profile() -> Option<String> {
+    run_scope::with_current(|scope| {
+        let stages = scope.stage_timings.get()?;
+        let timings: Vec<(&str, u64)> = stages
+            .iter()
+            .map(|stage| (stage.label.as_str(), stage.nanos.load(Ordering::Relaxed)))
+            .collect();
+        Some(render_stage_profile(&timings))
+    })
+}
+
+/// Render the --benchmark breakdown: parse, each script stage, format and
+/// write, with their share of the total.
+pub fn format_benchmark() -> String {
+    run_scope::with_current(|scope| {
+        let phase =
+            |phase: Phase| scope.counters.phase_nanos[phase as usize].load(Ordering::Relaxed);
+        let mut rows = vec![("parse".to_string(), phase(Phase::Parse))];
+        if let Some(stages) = scope.stage_timings.get() {
+            rows.extend(stages.iter().enumerate().map(|(index, stage)| {
+                (
+                    format!("Stage[{}] {}", index, stage.label),
+                    stage.nanos.load(Ordering::Relaxed),
+                )
+            }));
+        }
+        rows.push(("format".to_string(), phase(Phase::Format)));
+        rows.push(("write".to_string(), phase(Phase::Write)));
+        render_benchmark(&rows)
+    })
+}
+
+fn render_benchmark(rows: &[(String, u64)]) -> String {
+    let total: u64 = rows.iter().map(|(_, nanos)| nanos).sum();
+    let mut lines: Vec<String> = rows
+        .iter()
+        .map(|(label, nanos)| {
+            format!(
+                "{}: {} ({:.0}%)",
+                label,
+                format_stage_duration(*nanos),
+                share(*nanos, total)
+            )
+        })
+        .collect();
+    lines.push(format!("total: {}", format_stage_duration(total)));
+    lines.join("\n")
+}
+
+fn share(nanos: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        nanos as f64 * 100.0 / total as f64
+    }
+}
+
+fn render_stage_profile(timings: &[(&str, u64)]) -> String {
+    if timings.is_empty() {
+        return "No per-event script stages to profile".to_string();
+    }
+
+    let total: u64 = timings.iter().map(|(_, nanos)| nanos).sum();
+    timings
+        .iter()
+        .enumerate()
+        .map(|(index, (label, nanos))| {
+            format!(
+                "Stage[{}] {}: {} ({:.0}% of pipeline time)",
+                index,
+                label,
+                format_stage_duration(*nanos),
+                share(*nanos, total)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_stage_duration(nanos: u64) -> String {
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1_000.0)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1}ms", nanos as f64 / 1_000_000.0)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1_000_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_stage_profile_shares() {
+        let report =
+            render_stage_profile(&[("filter", 250_000_000), ("exec", 750_000_000), ("keys", 0)]);
+        assert_eq!(
+            report,
+            "Stage[0] filter: 250.0ms (25% of pipeline time)\n\
+             Stage[1] exec: 750.0ms (75% of pipeline time)\n\
+             Stage[2] keys: 0ns (0% of pipeline time)"
+        );
+    }
+
+    #[test]
+    fn test_render_benchmark_lists_phases_and_total() {
+        let report = render_benchmark(&[
+            ("parse".to_string(), 100_000_000),
+            ("Stage[0] filter".to_string(), 300_000_000),
+            ("format".to_string(), 100_000_000),
+            ("write".to_string(), 0),
+        ]);
+        assert_eq!(
+            report,
+            "parse: 100.0ms (20%)\n\
+             Stage[0] filter: 300.0ms (60%)\n\
+             format: 100.0ms (20%)\n\
+             write: 0ns (0%)\n\
+             total: 500.0ms"
+        );
+    }
+
+    #[test]
+    fn test_format_stage_duration_units() {
+        assert_eq!(format_stage_duration(999), "999ns");
+        assert_eq!(format_stage_duration(1_500), "1.5µs");
+        assert_eq!(format_stage_duration(342_000_000), "342.0ms");
+        assert_eq!(format_stage_duration(2_500_000_000), "2.50s");
+    }
+}
@@ -0,0 +1,141 @@
+//! JSON Schema gate for `--schema-validate`.
+//!
+//! The schema is read and compiled once at startup; workers share the
+//! compiled validator. Each event is converted to a JSON object and checked
+//! against it, and every violation is described by the field it hit and the
+//! schema rule that rejected it.
+
+use crate::event::Event;
+use std::fmt;
+use std::sync::Arc;
+
+/// Violations listed per failing event before the rest are summarized
+const MAX_REPORTED_VIOLATIONS: usize = 3;
+
+/// A compiled JSON Schema, cheap to clone across workers
+#[derive(Clone)]
+pub struct SchemaValidator {
+    path: String,
+    validator: Arc<jsonschema::Validator>,
+}
+
+impl fmt::Debug for SchemaValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SchemaValidator")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SchemaValidator {
+    /// Read and compile the schema at `path`.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read --schema-validate file '{}': {}", path, e))?;
+        let schema: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("--schema-validate file '{}' is not valid JSON: {}", path, e))?;
+        Self::compile(path, &schema)
+    }
+
+    fn compile(path: &str, schema: &serde_json::Value) -> Result<Self, String> {
+        let validator = jsonschema::validator_for(schema)
+            .map_err(|e| format!("Invalid JSON Schema in '{}': {}", path, e))?;
+        Ok(Self {
+            path: path.to_string(),
+            validator: Arc::new(validator),
+        })
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Check one event. On failure, returns one line per violation (capped),
+    /// each naming the field and the schema rule, e.g.
+    /// `/status: "ok" is not of type "integer" (rule /properties/status/type)`.
+    pub fn validate(&self, event: &Event) -> Result<(), Vec<String>> {
+        let instance = serde_json::Value::Object(
+            event
+                .fields
+                .iter()
+                .map(|(key, value)| (key.clone(), crate::formatters::dynamic_to_json(value)))
+                .collect(),
+        );
+
+        let mut violations = Vec::new();
+        let mut total = 0;
+        for error in self.validator.iter_errors(&instance) {
+            total += 1;
+            if violations.len() < MAX_REPORTED_VIOLATIONS {
+                let field = error.instance_path.to_string();
+                let field = if field.is_empty() { "(event)" } else { &field };
+                violations.push(format!("{}: {} (rule {})", field, error, error.schema_path));
+            }
+        }
+
+        if total == 0 {
+            return Ok(());
+        }
+        if total > violations.len() {
+            violations.push(format!("... and {} more", total - violations.len()));
+        }
+        Err(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhai::Dynamic;
+
+    fn schema() -> SchemaValidator {
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["level", "status"],
+            "properties": {
+                "level": {"enum": ["INFO", "WARN", "ERROR"]},
+                "status": {"type": "integer"}
+            }
+        });
+        SchemaValidator::compile("inline", &schema).unwrap()
+    }
+
+    fn event(fields: &[(&str, Dynamic)]) -> Event {
+        let mut event = Event::default_with_line(String::new());
+        for (key, value) in fields {
+            event.set_field(key.to_string(), value.clone());
+        }
+        event
+    }
+
+    #[test]
+    fn test_schema_accepts_matching_event() {
+        let ok = event(&[
+            ("level", Dynamic::from("INFO")),
+            ("status", Dynamic::from(200_i64)),
+        ]);
+        assert!(schema().validate(&ok).is_ok());
+    }
+
+    #[test]
+    fn test_schema_violations_name_field_and_rule() {
+        let bad = event(&[
+            ("level", Dynamic::from("TRACE")),
+            ("status", Dynamic::from("ok")),
+        ]);
+        let violations = schema().validate(&bad).unwrap_err();
+        assert_eq!(violations.len(), 2);
+        assert!(
+            violations
+                .iter()
+                .any(|v| v.starts_with("/status:") && v.contains("/properties/status/type")),
+            "{violations:?}"
+        );
+        assert!(violations.iter().any(|v| v.starts_with("/level:")));
+
+        let missing = event(&[("level", Dynamic::from("INFO"))]);
+        let violations = schema().validate(&missing).unwrap_err();
+        assert!(violations[0].starts_with("(event):"), "{violations:?}");
+        assert!(violations[0].contains("status"), "{violations:?}");
+    }
+}
@@ -4,11 +4,11 @@ use anyhow::{anyhow, Result};
 use chrono::{DateTime, TimeZone, Utc};
 use rhai::Dynamic;
 
-use crate::config::{SpanConfig, SpanMode};
+use crate::config::{SpanConfig, SpanMode, SpanOutput};
 use crate::engine::CompiledExpression;
 use crate::event::{Event, SpanInfo, SpanStatus};
 use crate::pipeline::PipelineContext;
-use crate::platform::{self, SafeStderr};
+use crate::platform::SafeStderr;
 use crate::rhai_functions::span as span_functions;
 use crate::stats;
 
@@ -226,6 +226,14 @@ impl ActiveSpan {
 pub struct SpanProcessor {
     mode: SpanMode,
     compiled_close: Option<CompiledExpression>,
+    output: SpanOutput,
+    compiled_summary: Option<CompiledExpression>,
+    /// Summary events for closed spans, waiting to be drained into the output.
+    summaries: Vec<Event>,
+    /// `--span-markers`: emit a marker event between consecutive spans
+    markers: bool,
+    /// Marker for the last closed span, released once the next span opens
+    pending_marker: Option<Event>,
     collect_details: bool,
     active_span: Option<ActiveSpan>,
     anchor_start_ms: Option<i64>,
@@ -239,12 +247,26 @@ pub struct SpanProcessor {
 }
 
 impl SpanProcessor {
-    pub fn new(span: SpanConfig, compiled_close: Option<CompiledExpression>) -> Self {
-        let SpanConfig { mode, .. } = span;
-        let collect_details = compiled_close.is_some();
+    pub fn new(
+        span: SpanConfig,
+        compiled_close: Option<CompiledExpression>,
+        compiled_summary: Option<CompiledExpression>,
+    ) -> Self {
+        let SpanConfig {
+            mode,
+            output,
+            markers,
+            ..
+        } = span;
+        let collect_details = compiled_close.is_some() || output.emits_summary();
         Self {
             mode,
             compiled_close,
+            output,
+            compiled_summary,
+            summaries: Vec::new(),
+            markers,
+            pending_marker: None,
             collect_details,
             active_span: None,
             anchor_start_ms: None,
@@ -317,11 +339,23 @@ impl SpanProcessor {
         self.pending = None;
     }
 
+    /// Whether member events are written (`--span-output members|both`).
+    pub fn emits_members(&self) -> bool {
+        self.output.emits_members()
+    }
+
+    /// Take the summary events of spans closed since the last call.
+    pub fn take_summaries(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.summaries)
+    }
+
     pub fn finish(&mut self, ctx: &mut PipelineContext) -> Result<()> {
         self.pending = None;
         if self.active_span.is_some() {
             self.close_current_span(ctx)?;
         }
+        // No span follows the last one, so there is no boundary to mark
+        self.pending_marker = None;
         Ok(())
     }
 
@@ -554,9 +588,19 @@ impl SpanProcessor {
         });
     }
 
-    fn open_count_span(&mut self, ctx: &PipelineContext) {
+    /// Sequence number for a span about to open. Releases the marker of the
+    /// span before it, so the marker precedes the new span's first event.
+    fn next_sequence(&mut self) -> u64 {
+        if let Some(marker) = self.pending_marker.take() {
+            self.summaries.push(marker);
+        }
         let sequence = self.next_span_sequence;
         self.next_span_sequence += 1;
+        sequence
+    }
+
+    fn open_count_span(&mut self, ctx: &PipelineContext) {
+        let sequence = self.next_sequence();
         let index = self.next_count_index;
         self.next_count_index += 1;
         self.active_span = Some(ActiveSpan::new_count(
@@ -568,8 +612,7 @@ impl SpanProcessor {
     }
 
     fn open_field_span(&mut self, ctx: &PipelineContext, field_value: String) {
-        let sequence = self.next_span_sequence;
-        self.next_span_sequence += 1;
+        let sequence = self.next_sequence();
         self.active_span = Some(ActiveSpan::new_field(
             sequence,
             field_value,
@@ -579,8 +622,7 @@ impl SpanProcessor {
     }
 
     fn open_time_span(&mut self, ctx: &PipelineContext, window: TimeWindow) {
-        let sequence = self.next_span_sequence;
-        self.next_span_sequence += 1;
+        let sequence = self.next_sequence();
         self.active_span = Some(ActiveSpan::new_time(
             sequence,
             window,
@@ -590,8 +632,7 @@ impl SpanProcessor {
     }
 
     fn open_idle_span(&mut self, ctx: &PipelineContext, start_ts: DateTime<Utc>) {
-        let sequence = self.next_span_sequence;
-        self.next_span_sequence += 1;
+        let sequence = self.next_sequence();
         self.active_span = Some(ActiveSpan::new_idle(
             sequence,
             start_ts,
@@ -605,18 +646,78 @@ impl SpanProcessor {
             if span.span_end.is_none() {
                 span.span_end = span.last_event_timestamp;
             }
-            self.run_close_hook(span, ctx)?;
+            self.run_close_hook(&mut span, ctx)?;
+            if self.markers {
+                self.pending_marker = Some(build_marker(&span));
+            }
+            if self.output.emits_summary() && span.included_count > 0 {
+                self.build_summary(span, ctx)?;
+            }
         }
         Ok(())
     }
 
-    fn run_close_hook(&mut self, span: ActiveSpan, ctx: &mut PipelineContext) -> Result<()> {
+    /// Build the synthetic summary event for a closed span and run the
+    /// `--span-summary` hook on it with the span's members bound for span_*.
+    fn build_summary(&mut self, span: ActiveSpan, ctx: &mut PipelineContext) -> Result<()> {
+        let first = span.events.iter().filter_map(|e| e.parsed_ts).min();
+        let last = span.events.iter().filter_map(|e| e.parsed_ts).max();
+
+        let mut summary = Event::default_with_line(String::new());
+        summary.set_field("_span_id".to_string(), Dynamic::from(span.span_id.clone()));
+        summary.set_field(
+            "_span_n".to_string(),
+            Dynamic::from(span.included_count as i64),
+        );
+        if let (Some(first), Some(last)) = (first, last) {
+            summary.set_field(
+                "_span_first".to_string(),
+                Dynamic::from(first.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+            );
+            summary.set_field(
+                "_span_last".to_string(),
+                Dynamic::from(last.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+            );
+            summary.set_field(
+                "_span_duration_ms".to_string(),
+                Dynamic::from((last - first).num_milliseconds()),
+            );
+        }
+        summary.set_span_info(SpanInfo {
+            status: Some(SpanStatus::Included),
+            span_id: Some(span.span_id.clone()),
+            span_start: span.span_start,
+            span_end: span.span_end,
+        });
+        summary.parsed_ts = span.span_start.or(first);
+
+        if let Some(compiled) = self.compiled_summary.as_ref() {
+            span_functions::bind_span_members(span.events);
+            let result = ctx.rhai.execute_compiled_exec(
+                compiled,
+                &mut summary,
+                &mut ctx.tracker,
+                &mut ctx.internal_tracker,
+            );
+            span_functions::unbind_span_members();
+            result
+                .map_err(|e| anyhow!("--span-summary failed for span {}: {}", span.span_id, e))?;
+        }
+
+        // `e = ()` in the summary hook drops the summary, like skipping an event.
+        if !summary.fields.is_empty() {
+            self.summaries.push(summary);
+        }
+        Ok(())
+    }
+
+    fn run_close_hook(&mut self, span: &mut ActiveSpan, ctx: &mut PipelineContext) -> Result<()> {
         if self.compiled_close.is_none() {
             return Ok(());
         }
 
         let (metrics_delta, non_additive) =
-            compute_span_metrics(&span, &ctx.tracker, &ctx.internal_tracker);
+            compute_span_metrics(span, &ctx.tracker, &ctx.internal_tracker);
         self.warn_non_additive(&non_additive, ctx);
 
         let compiled = self
@@ -631,18 +732,18 @@ impl SpanProcessor {
             metrics_delta,
         );
 
+        span_functions::bind_span_members(std::mem::take(&mut span.events));
         let result = ctx.rhai.execute_compiled_span_close(
             compiled,
             &mut ctx.tracker,
             &mut ctx.internal_tracker,
             span_binding,
         );
+        span.events = span_functions::unbind_span_members();
 
         result?;
 
-        if platform::SHOULD_TERMINATE.load(std::sync::atomic::Ordering::Relaxed)
-            && !self.signal_notice_shown
-        {
+        if ctx.rhai.is_cancelled() && !self.signal_notice_shown {
             let message = crate::config::format_error_message_auto(
                 "Received signal, waiting for span close... (Ctrl+C again to force quit)",
             );
@@ -677,6 +778,31 @@ impl SpanProcessor {
     }
 }
 
+/// Marker event written at the boundary after `span` (`--span-markers`).
+/// Count and field spans have no time bounds, so their markers carry only the
+/// span id.
+fn build_marker(span: &ActiveSpan) -> Event {
+    let mut marker = Event::default_with_line(String::new());
+    marker.set_field("_span_marker".to_string(), Dynamic::from(true));
+    marker.set_field("span_id".to_string(), Dynamic::from(span.span_id.clone()));
+    for (key, bound) in [("span_start", span.span_start), ("span_end", span.span_end)] {
+        if let Some(bound) = bound {
+            marker.set_field(
+                key.to_string(),
+                Dynamic::from(bound.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+            );
+        }
+    }
+    marker.set_span_info(SpanInfo {
+        status: Some(SpanStatus::Included),
+        span_id: Some(span.span_id.clone()),
+        span_start: span.span_start,
+        span_end: span.span_end,
+    });
+    marker.parsed_ts = span.span_end;
+    marker
+}
+
 fn align_to_duration(event_ms: i64, duration_ms: i64) -> i64 {
     (event_ms.div_euclid(duration_ms)) * duration_ms
 }
@@ -765,8 +891,8 @@ fn compute_span_metrics(
                         }
                     }
                 }
-                "min" | "max" | "percentiles" | "cardinality" | "top" | "bottom" | "top_by"
-                | "bottom_by" => {
+                "min" | "max" | "percentiles" | "percentile_approx" | "cardinality" | "top"
+                | "bottom" | "top_by" | "bottom_by" => {
                     non_additive.push((key.clone(), op.to_string()));
                 }
                 _ => {}
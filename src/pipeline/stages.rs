@@ -1,9 +1,10 @@
-use super::{PipelineContext, ScriptResult, ScriptStage};
+use super::{PipelineConfig, PipelineContext, ScriptResult, ScriptStage};
+use crate::cli::StageErrorPolicy;
 use crate::config::TimestampFilterConfig;
 use crate::engine::RhaiEngine;
 use crate::event::Event;
 use crate::rhai_functions::file_ops;
-use crate::rhai_functions::{absorb, columns, emit};
+use crate::rhai_functions::{absorb, columns, emit, safety};
 use anyhow::Result;
 
 /// Preserve error-tracking state across the script error boundary.
@@ -31,6 +32,72 @@ pub(crate) fn persist_error_tracking(ctx: &mut PipelineContext) {
     }
 }
 
+/// Run the `--exec-on-error` script for an error that was just tracked.
+///
+/// Call after `persist_error_tracking` so the hook starts from (and writes back
+/// to) the persisted tracker. The hook can never fail the pipeline: its own
+/// errors are reported on stderr and dropped.
+pub(crate) fn run_error_hook(
+    ctx: &mut PipelineContext,
+    error_type: &str,
+    message: &str,
+    raw_line: Option<&str>,
+) {
+    if !ctx.rhai.has_error_hook() {
+        return;
+    }
+    let info = crate::engine::ErrorHookInfo {
+        error_type,
+        message,
+        raw_line,
+        line_num: ctx.meta.line_num,
+        filename: ctx.meta.filename.as_deref(),
+    };
+    if let Err(e) = ctx
+        .rhai
+        .execute_error_hook(&info, &mut ctx.tracker, &mut ctx.internal_tracker)
+    {
+        if !ctx.config.silent {
+            eprintln!(
+                "{}",
+                crate::config::format_error_message_auto(&e.to_string())
+            );
+        }
+    }
+}
+
+/// Count a --filter/--exec error against its stage for --stats, e.g.
+/// "stage 2 (exec)". Under --on-stage-error the tracked message names the
+/// stage too, since the event may carry on through later stages.
+fn note_stage_error(
+    ctx: &PipelineContext,
+    stage_number: usize,
+    label: &str,
+    message: String,
+) -> String {
+    let stage = format!("stage {} ({})", stage_number, label);
+    crate::stats::stats_add_stage_error(&stage);
+    if ctx.config.on_stage_error.is_some() {
+        format!("{}: {}", stage, message)
+    } else {
+        message
+    }
+}
+
+/// Stop the run for `--on-stage-error abort`. The error is already tracked
+/// under the stage's own kind, so the stage drops the event and reports itself
+/// exhausted (as --take-while does) rather than returning an error result,
+/// which would count the failure a second time. Plain --strict keeps the
+/// error result, which ends the run at once in parallel mode too.
+fn abort_run(aborted: &mut bool, ctx: &PipelineContext, message: String) -> ScriptResult {
+    if ctx.config.on_stage_error.is_none() {
+        return ScriptResult::Error(message);
+    }
+    *aborted = true;
+    crate::stats::stats_add_stage_abort();
+    ScriptResult::Skip
+}
+
 /// Cached event along with whether it satisfied the stage filter.
 struct ContextBufferEntry {
     event: Event,
@@ -47,6 +114,8 @@ pub struct FilterStage {
     buffer: std::collections::VecDeque<ContextBufferEntry>,
     after_counter: usize,
     pending_output: std::collections::VecDeque<Event>,
+    /// Set once an error stopped the run under --on-stage-error abort
+    aborted: bool,
 }
 
 impl FilterStage {
@@ -63,6 +132,7 @@ impl FilterStage {
             buffer: std::collections::VecDeque::new(),
             after_counter: 0,
             pending_output: std::collections::VecDeque::new(),
+            aborted: false,
         })
     }
 
@@ -157,10 +227,16 @@ impl FilterStage {
         let is_match = match self.evaluate_filter(&event, ctx) {
             Ok(result) => result,
             Err(e) => {
+                let message = note_stage_error(
+                    ctx,
+                    self.stage_number,
+                    "filter",
+                    format!("Filter error: {}", e),
+                );
                 crate::rhai_functions::tracking::track_error(
                     "filter",
                     ctx.meta.line_num,
-                    &format!("Filter error: {}", e),
+                    &message,
                     Some(&event.original_line),
                     ctx.meta.filename.as_deref(),
                     ctx.config.verbose,
@@ -170,14 +246,19 @@ impl FilterStage {
                 );
 
                 persist_error_tracking(ctx);
+                run_error_hook(ctx, "filter", &message, Some(&event.original_line));
 
+                let policy = ctx.config.stage_error_policy();
                 if e.downcast_ref::<crate::engine::ConfMutationError>()
                     .is_some()
-                    || ctx.config.strict
                 {
                     return ScriptResult::Error(format!("Filter error: {}", e));
+                } else if policy == Some(StageErrorPolicy::Abort) {
+                    return abort_run(&mut self.aborted, ctx, format!("Filter error: {}", e));
                 } else {
-                    false // Filter errors evaluate to false in resilient mode
+                    // Filter errors evaluate to false in resilient mode;
+                    // skip-stage lets the event through as if it matched
+                    policy == Some(StageErrorPolicy::SkipStage)
                 }
             }
         };
@@ -281,11 +362,19 @@ impl FilterStage {
 }
 
 impl ScriptStage for FilterStage {
+    fn label(&self) -> String {
+        "filter".to_string()
+    }
+
     fn uses_window(&self) -> bool {
         self.compiled_filter.uses_window()
     }
 
     fn apply(&mut self, event: Event, ctx: &mut PipelineContext) -> ScriptResult {
+        if self.aborted {
+            return ScriptResult::Skip;
+        }
+
         // Add stage-specific tracing
         if let Some(ref tracer) = ctx.rhai.get_execution_tracer() {
             tracer.trace_stage_execution(self.stage_number, "filter");
@@ -311,10 +400,16 @@ impl ScriptStage for FilterStage {
                 }
             }
             Err(e) => {
+                let message = note_stage_error(
+                    ctx,
+                    self.stage_number,
+                    "filter",
+                    format!("Filter error: {}", e),
+                );
                 crate::rhai_functions::tracking::track_error(
                     "filter",
                     ctx.meta.line_num,
-                    &format!("Filter error: {}", e),
+                    &message,
                     Some(&event.original_line),
                     ctx.meta.filename.as_deref(),
                     ctx.config.verbose,
@@ -324,14 +419,152 @@ impl ScriptStage for FilterStage {
                 );
 
                 persist_error_tracking(ctx);
+                run_error_hook(ctx, "filter", &message, Some(&event.original_line));
 
                 // New resiliency model: filter errors evaluate to false (Skip)
-                // unless in strict mode, where they still propagate as errors
-                if e.downcast_ref::<crate::engine::ConfMutationError>()
-                    .is_some()
-                    || ctx.config.strict
-                {
-                    ScriptResult::Error(format!("Filter error: {}", e))
+                // unless in strict mode, where they still propagate as errors.
+                // --on-stage-error overrides both.
+                match ctx.config.stage_error_policy() {
+                    _ if e
+                        .downcast_ref::<crate::engine::ConfMutationError>()
+                        .is_some() =>
+                    {
+                        ScriptResult::Error(format!("Filter error: {}", e))
+                    }
+                    Some(StageErrorPolicy::Abort) => {
+                        abort_run(&mut self.aborted, ctx, format!("Filter error: {}", e))
+                    }
+                    Some(StageErrorPolicy::SkipStage) => ScriptResult::Emit(event),
+                    Some(StageErrorPolicy::SkipEvent) | None => ScriptResult::Skip,
+                }
+            }
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.aborted
+    }
+}
+
+/// Evaluate a --take-while/--drop-while predicate. Errors are tracked like
+/// --filter errors and reported as `Err` with the formatted message.
+fn evaluate_stream_predicate(
+    compiled: &crate::engine::CompiledExpression,
+    event: &Event,
+    ctx: &mut PipelineContext,
+    flag: &str,
+) -> std::result::Result<bool, String> {
+    match ctx.rhai.execute_compiled_filter(
+        compiled,
+        event,
+        &mut ctx.tracker,
+        &mut ctx.internal_tracker,
+    ) {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            let message = format!("{} error: {}", flag, e);
+            crate::rhai_functions::tracking::track_error(
+                "filter",
+                ctx.meta.line_num,
+                &message,
+                Some(&event.original_line),
+                ctx.meta.filename.as_deref(),
+                ctx.config.verbose,
+                ctx.config.quiet_level,
+                Some(&ctx.config),
+                None,
+            );
+            persist_error_tracking(ctx);
+            run_error_hook(ctx, "filter", &message, Some(&event.original_line));
+            Err(message)
+        }
+    }
+}
+
+/// Stream cutoff stage for --take-while: emits events while the predicate
+/// holds and stops the stream at the first event that fails it
+pub struct TakeWhileStage {
+    compiled_filter: crate::engine::CompiledExpression,
+    stopped: bool,
+}
+
+impl TakeWhileStage {
+    pub fn new(predicate: &str, engine: &mut RhaiEngine) -> Result<Self> {
+        Ok(Self {
+            compiled_filter: engine.compile_filter(predicate)?,
+            stopped: false,
+        })
+    }
+}
+
+impl ScriptStage for TakeWhileStage {
+    fn label(&self) -> String {
+        "take-while".to_string()
+    }
+
+    fn apply(&mut self, event: Event, ctx: &mut PipelineContext) -> ScriptResult {
+        if self.stopped {
+            return ScriptResult::Skip;
+        }
+        match evaluate_stream_predicate(&self.compiled_filter, &event, ctx, "--take-while") {
+            Ok(true) => ScriptResult::Emit(event),
+            Ok(false) => {
+                self.stopped = true;
+                ScriptResult::Skip
+            }
+            // An error counts as the predicate failing, so the stream ends
+            // here in resilient mode too
+            Err(message) => {
+                self.stopped = true;
+                if ctx.config.strict {
+                    ScriptResult::Error(message)
+                } else {
+                    ScriptResult::Skip
+                }
+            }
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.stopped
+    }
+}
+
+/// Leading-skip stage for --drop-while: drops events while the predicate
+/// holds and passes everything through from the first event that fails it
+pub struct DropWhileStage {
+    compiled_filter: crate::engine::CompiledExpression,
+    dropping: bool,
+}
+
+impl DropWhileStage {
+    pub fn new(predicate: &str, engine: &mut RhaiEngine) -> Result<Self> {
+        Ok(Self {
+            compiled_filter: engine.compile_filter(predicate)?,
+            dropping: true,
+        })
+    }
+}
+
+impl ScriptStage for DropWhileStage {
+    fn label(&self) -> String {
+        "drop-while".to_string()
+    }
+
+    fn apply(&mut self, event: Event, ctx: &mut PipelineContext) -> ScriptResult {
+        // Once the predicate has failed it is never evaluated again
+        if !self.dropping {
+            return ScriptResult::Emit(event);
+        }
+        match evaluate_stream_predicate(&self.compiled_filter, &event, ctx, "--drop-while") {
+            Ok(true) => ScriptResult::Skip,
+            Ok(false) => {
+                self.dropping = false;
+                ScriptResult::Emit(event)
+            }
+            Err(message) => {
+                if ctx.config.strict {
+                    ScriptResult::Error(message)
                 } else {
                     ScriptResult::Skip
                 }
@@ -344,6 +577,10 @@ impl ScriptStage for FilterStage {
 pub struct ExecStage {
     compiled_exec: crate::engine::CompiledExpression,
     stage_number: usize,
+    /// User-provided name from --stage-label
+    label: Option<String>,
+    /// Set once an error stopped the run under --on-stage-error abort
+    aborted: bool,
 }
 
 impl ExecStage {
@@ -352,6 +589,8 @@ impl ExecStage {
         Ok(Self {
             compiled_exec,
             stage_number: 0,
+            label: None,
+            aborted: false,
         })
     }
 
@@ -359,14 +598,27 @@ impl ExecStage {
         self.stage_number = stage_number;
         self
     }
+
+    pub fn with_label(mut self, label: Option<String>) -> Self {
+        self.label = label;
+        self
+    }
 }
 
 impl ScriptStage for ExecStage {
+    fn label(&self) -> String {
+        self.label.clone().unwrap_or_else(|| "exec".to_string())
+    }
+
     fn uses_window(&self) -> bool {
         self.compiled_exec.uses_window()
     }
 
     fn apply(&mut self, event: Event, ctx: &mut PipelineContext) -> ScriptResult {
+        if self.aborted {
+            return ScriptResult::Skip;
+        }
+
         // Add stage-specific tracing
         if let Some(ref tracer) = ctx.rhai.get_execution_tracer() {
             tracer.trace_stage_execution(self.stage_number, "exec");
@@ -519,6 +771,8 @@ impl ScriptStage for ExecStage {
                 } else {
                     base_error.to_string()
                 };
+                let error_for_summary =
+                    note_stage_error(ctx, self.stage_number, &self.label(), error_for_summary);
 
                 crate::rhai_functions::tracking::track_error(
                     "exec",
@@ -536,23 +790,125 @@ impl ScriptStage for ExecStage {
                 // (execute_compiled_exec's error path skips the thread-local→ctx
                 // sync, so counts and samples would otherwise be overwritten).
                 persist_error_tracking(ctx);
+                run_error_hook(ctx, "exec", &error_for_summary, Some(&event.original_line));
 
                 // New resiliency model: atomic rollback - return original event unchanged
-                // unless in strict mode, where errors still propagate
-                if e.downcast_ref::<crate::engine::ConfMutationError>()
-                    .is_some()
-                    || ctx.config.strict
-                {
-                    crate::rhai_functions::process::clear_skip_request();
-                    ScriptResult::Error(error_msg.clone())
-                } else {
-                    crate::rhai_functions::process::clear_skip_request();
+                // unless in strict mode, where errors still propagate.
+                // --on-stage-error skip-event drops the event instead.
+                crate::rhai_functions::process::clear_skip_request();
+                match ctx.config.stage_error_policy() {
+                    _ if e
+                        .downcast_ref::<crate::engine::ConfMutationError>()
+                        .is_some() =>
+                    {
+                        ScriptResult::Error(error_msg.clone())
+                    }
+                    Some(StageErrorPolicy::Abort) => {
+                        abort_run(&mut self.aborted, ctx, error_msg.clone())
+                    }
+                    Some(StageErrorPolicy::SkipEvent) => ScriptResult::Skip,
                     // Rollback: return original event unchanged
-                    ScriptResult::Emit(event)
+                    Some(StageErrorPolicy::SkipStage) | None => ScriptResult::Emit(event),
+                }
+            }
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.aborted
+    }
+}
+
+/// Window reduction stage for --window-emit-each: runs --window-script at
+/// every window position and replaces the event with the map it returns
+pub struct WindowEmitStage {
+    compiled_script: crate::engine::CompiledExpression,
+    /// Set once an error stopped the run under --on-stage-error abort
+    aborted: bool,
+}
+
+impl WindowEmitStage {
+    pub fn new(script: &str, engine: &mut RhaiEngine) -> Result<Self> {
+        Ok(Self {
+            compiled_script: engine.compile_window_script(script)?,
+            aborted: false,
+        })
+    }
+
+    fn evaluate(
+        &self,
+        event: &Event,
+        ctx: &mut PipelineContext,
+    ) -> std::result::Result<rhai::Map, String> {
+        let value = ctx
+            .rhai
+            .execute_compiled_window_script(
+                &self.compiled_script,
+                event,
+                &ctx.window,
+                &mut ctx.tracker,
+                &mut ctx.internal_tracker,
+            )
+            .map_err(|e| format!("--window-script error: {:#}", e))?;
+        let type_name = value.type_name();
+        value
+            .try_cast::<rhai::Map>()
+            .ok_or_else(|| format!("--window-script must return a map, got {}", type_name))
+    }
+}
+
+impl ScriptStage for WindowEmitStage {
+    fn label(&self) -> String {
+        "window-emit-each".to_string()
+    }
+
+    fn uses_window(&self) -> bool {
+        true
+    }
+
+    fn apply(&mut self, event: Event, ctx: &mut PipelineContext) -> ScriptResult {
+        if self.aborted {
+            return ScriptResult::Skip;
+        }
+        match self.evaluate(&event, ctx) {
+            Ok(map) => {
+                let mut synthetic = Event::default_with_line(event.original_line.clone());
+                synthetic.line_num = event.line_num;
+                synthetic.filename = event.filename.clone();
+                for (key, value) in map {
+                    synthetic.fields.insert(key.to_string(), value);
+                }
+                synthetic.extract_timestamp();
+                ScriptResult::Emit(synthetic)
+            }
+            Err(message) => {
+                crate::rhai_functions::tracking::track_error(
+                    "exec",
+                    ctx.meta.line_num,
+                    &message,
+                    Some(&event.original_line),
+                    ctx.meta.filename.as_deref(),
+                    ctx.config.verbose,
+                    ctx.config.quiet_level,
+                    Some(&ctx.config),
+                    None,
+                );
+                persist_error_tracking(ctx);
+                run_error_hook(ctx, "exec", &message, Some(&event.original_line));
+                // Like --exec, an error leaves the event as it was unless the
+                // error policy says otherwise
+                match ctx.config.stage_error_policy() {
+                    Some(StageErrorPolicy::Abort) => abort_run(&mut self.aborted, ctx, message),
+                    Some(StageErrorPolicy::SkipEvent) => ScriptResult::Skip,
+                    Some(StageErrorPolicy::SkipStage) | None => ScriptResult::Emit(event),
                 }
             }
         }
     }
+
+    fn is_exhausted(&self) -> bool {
+        self.aborted
+    }
 }
 
 /// Assert stage for --assert expressions
@@ -681,6 +1037,10 @@ impl AssertStage {
 }
 
 impl ScriptStage for AssertStage {
+    fn label(&self) -> String {
+        "assert".to_string()
+    }
+
     fn uses_window(&self) -> bool {
         self.compiled_assertion.uses_window()
     }
@@ -717,10 +1077,11 @@ impl ScriptStage for AssertStage {
                 self.report_violation(&event, ctx);
 
                 // Track error via tracking system
+                let message = format!("Assertion error: {}", e);
                 crate::rhai_functions::tracking::track_error(
                     "assert",
                     ctx.meta.line_num,
-                    &format!("Assertion error: {}", e),
+                    &message,
                     Some(&event.original_line),
                     ctx.meta.filename.as_deref(),
                     ctx.config.verbose,
@@ -734,10 +1095,11 @@ impl ScriptStage for AssertStage {
                 // error summary (the exit code is safe: assert failures are
                 // counted flag-independently in stats).
                 persist_error_tracking(ctx);
+                run_error_hook(ctx, "assert", &message, Some(&event.original_line));
 
                 // In strict mode, propagate error
                 if ctx.config.strict {
-                    ScriptResult::Error(format!("Assertion error: {}", e))
+                    ScriptResult::Error(message)
                 } else {
                     // Continue processing, emit event
                     ScriptResult::Emit(event)
@@ -747,9 +1109,42 @@ impl ScriptStage for AssertStage {
     }
 }
 
+/// Record a --begin/--end script stopped by its timeout as a `timeout`
+/// error. Fatal under --strict; otherwise the run goes on without the rest of
+/// that script.
+fn handle_script_timeout(config: &PipelineConfig, flag: &str, timeout_ms: u64) -> Result<()> {
+    let message = format!("{} script timed out after {} ms", flag, timeout_ms);
+    crate::rhai_functions::tracking::track_error(
+        "timeout",
+        None,
+        &message,
+        None,
+        None,
+        config.verbose,
+        config.quiet_level,
+        Some(config),
+        None,
+    );
+    if config.strict {
+        return Err(anyhow::anyhow!(message));
+    }
+    if !config.suppress_warnings {
+        eprintln!(
+            "{}",
+            crate::config::format_warning_message_auto(&format!(
+                "{}; continuing without the rest of it",
+                message
+            ))
+        );
+    }
+    Ok(())
+}
+
 /// Begin stage for --begin expressions
 pub struct BeginStage {
     compiled_begin: Option<crate::engine::CompiledExpression>,
+    /// --begin-timeout-ms
+    timeout_ms: Option<u64>,
 }
 
 impl BeginStage {
@@ -759,7 +1154,15 @@ impl BeginStage {
         } else {
             None
         };
-        Ok(Self { compiled_begin })
+        Ok(Self {
+            compiled_begin,
+            timeout_ms: None,
+        })
+    }
+
+    pub fn with_timeout_ms(mut self, timeout_ms: Option<u64>) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
     }
 
     pub fn execute(&self, ctx: &mut PipelineContext) -> Result<()> {
@@ -771,12 +1174,24 @@ impl BeginStage {
             // reject it in --begin. Reset before returning so the per-event
             // stages (same thread, sequential mode) can still emit.
             emit::set_emit_disallowed(Some("--begin"));
-            let exec_result = ctx.rhai.execute_compiled_begin(
-                compiled,
-                &mut ctx.tracker,
-                &mut ctx.internal_tracker,
+            let (exec_result, timed_out) = safety::run_with_deadline(
+                self.timeout_ms.map(std::time::Duration::from_millis),
+                || {
+                    ctx.rhai.execute_compiled_begin(
+                        compiled,
+                        &mut ctx.tracker,
+                        &mut ctx.internal_tracker,
+                    )
+                },
             );
             emit::set_emit_disallowed(None);
+            if let (true, Some(timeout_ms)) = (timed_out, self.timeout_ms) {
+                crate::rhai_functions::conf::set_begin_phase(false);
+                file_ops::clear_pending_ops();
+                handle_script_timeout(&ctx.config, "--begin", timeout_ms)?;
+                persist_error_tracking(ctx);
+                return Ok(());
+            }
             let _init_map = exec_result?;
             let ops = file_ops::take_pending_ops();
             file_ops::execute_ops(&ops)?;
@@ -790,6 +1205,8 @@ impl BeginStage {
 /// End stage for --end expressions
 pub struct EndStage {
     compiled_end: Option<crate::engine::CompiledExpression>,
+    /// --end-timeout-ms
+    timeout_ms: Option<u64>,
 }
 
 impl EndStage {
@@ -799,10 +1216,28 @@ impl EndStage {
         } else {
             None
         };
-        Ok(Self { compiled_end })
+        Ok(Self {
+            compiled_end,
+            timeout_ms: None,
+        })
+    }
+
+    pub fn with_timeout_ms(mut self, timeout_ms: Option<u64>) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.compiled_end.is_some()
     }
 
-    pub fn execute(&self, ctx: &PipelineContext) -> Result<()> {
+    /// Run the end script. `stats` must be the final (post-merge) counters;
+    /// they are exposed to the script as the read-only `stats` map.
+    pub fn execute(
+        &self,
+        ctx: &PipelineContext,
+        stats: &crate::stats::ProcessingStats,
+    ) -> Result<()> {
         if let Some(ref compiled) = self.compiled_end {
             columns::set_parse_cols_strict(ctx.config.strict);
             absorb::set_absorb_strict(ctx.config.strict);
@@ -810,10 +1245,22 @@ impl EndStage {
             // emit_each() cannot be materialized after the event loop; reject it
             // in --end. Reset afterward to leave no stale guard on the thread.
             emit::set_emit_disallowed(Some("--end"));
-            let exec_result =
-                ctx.rhai
-                    .execute_compiled_end(compiled, &ctx.tracker, &ctx.internal_tracker);
+            let (exec_result, timed_out) = safety::run_with_deadline(
+                self.timeout_ms.map(std::time::Duration::from_millis),
+                || {
+                    ctx.rhai.execute_compiled_end(
+                        compiled,
+                        &ctx.tracker,
+                        &ctx.internal_tracker,
+                        stats.to_script_map(),
+                    )
+                },
+            );
             emit::set_emit_disallowed(None);
+            if let (true, Some(timeout_ms)) = (timed_out, self.timeout_ms) {
+                file_ops::clear_pending_ops();
+                return handle_script_timeout(&ctx.config, "--end", timeout_ms);
+            }
             exec_result?;
             let ops = file_ops::take_pending_ops();
             file_ops::execute_ops(&ops)
@@ -1026,6 +1473,10 @@ impl LevelFilterStage {
 }
 
 impl ScriptStage for LevelFilterStage {
+    fn label(&self) -> String {
+        "levels".to_string()
+    }
+
     fn apply(&mut self, event: Event, ctx: &mut PipelineContext) -> ScriptResult {
         if !self.is_active() {
             return ScriptResult::Emit(event);
@@ -1063,37 +1514,39 @@ impl KeyFilterStage {
 }
 
 impl ScriptStage for KeyFilterStage {
+    fn label(&self) -> String {
+        "keys".to_string()
+    }
+
     fn apply(&mut self, mut event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
         if !self.is_active() {
             return ScriptResult::Emit(event);
         }
 
-        // Get available keys from the event
-        let available_keys: Vec<String> = event.fields.keys().cloned().collect();
-
-        // Calculate effective keys preserving the order specified by self.keys
-        let effective_keys = {
-            let mut result_keys = if self.keys.is_empty() {
-                // If no keys specified, start with all available keys
-                available_keys
-            } else {
-                // If keys specified, iterate through self.keys and only include those that exist in the event
-                // This preserves the order specified in self.keys rather than the original event order
-                self.keys
-                    .iter()
-                    .filter(|key| available_keys.contains(key))
-                    .cloned()
-                    .collect()
-            };
-
-            // Apply exclusions (higher priority)
-            result_keys.retain(|key| !self.exclude_keys.contains(key));
-
-            result_keys
-        };
+        // Apply exclusions first (higher priority). Dotted names remove a
+        // nested value and keep the rest of its parent.
+        for path in &self.exclude_keys {
+            crate::formatters::remove_field_path(&mut event, path);
+        }
 
-        // Apply the filtering
-        event.filter_keys(&effective_keys);
+        if !self.keys.is_empty() {
+            // Keep only the requested keys that resolve, in the order given by
+            // self.keys rather than the original event order. A dotted name
+            // that reaches into a nested value becomes a column of that name.
+            let mut selected = crate::event::FieldMap::with_capacity_and_hasher(
+                self.keys.len(),
+                ahash::RandomState::default(),
+            );
+            for key in &self.keys {
+                if selected.contains_key(key) {
+                    continue;
+                }
+                if let Some(value) = crate::formatters::resolve_field_path(&event, key) {
+                    selected.insert(key.clone(), value);
+                }
+            }
+            event.fields = selected;
+        }
 
         // Only mark as key-filtered when the user explicitly requested an order via --keys.
         // Preserve caller-specified ordering only when --keys was provided.
@@ -1108,63 +1561,571 @@ impl ScriptStage for KeyFilterStage {
     }
 }
 
-/// Drain template mining stage (sequential-only, summary-driven)
-pub struct DrainStage {
-    field_name: String,
+/// Field renaming stage for --field-prefix-drop
+pub struct FieldPrefixDropStage {
+    prefixes: Vec<String>,
+    collision: crate::config::CollisionPolicy,
 }
 
-impl DrainStage {
-    pub fn new(field_name: String) -> Self {
-        Self { field_name }
+impl FieldPrefixDropStage {
+    pub fn new(prefixes: Vec<String>, collision: crate::config::CollisionPolicy) -> Self {
+        Self {
+            prefixes,
+            collision,
+        }
+    }
+
+    fn stripped<'a>(&self, key: &'a str) -> Option<&'a str> {
+        self.prefixes
+            .iter()
+            .find_map(|prefix| key.strip_prefix(prefix.as_str()))
+            .filter(|rest| !rest.is_empty())
     }
 }
 
-impl ScriptStage for DrainStage {
-    fn apply(&mut self, event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
-        if let Some(value) = event.fields.get(&self.field_name) {
-            let text = if value.is_string() {
-                value.clone().into_string().unwrap_or_default()
-            } else {
-                value.to_string()
-            };
+impl ScriptStage for FieldPrefixDropStage {
+    fn label(&self) -> String {
+        "field-prefix-drop".to_string()
+    }
 
-            if !text.is_empty() {
-                if let Err(err) = crate::drain::drain_template(&text, None, event.line_num) {
-                    return ScriptResult::Error(err);
+    fn apply(&mut self, mut event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        if !event.fields.keys().any(|key| self.stripped(key).is_some()) {
+            return ScriptResult::Emit(event);
+        }
+
+        let original = std::mem::take(&mut event.fields);
+        let mut renamed = crate::event::FieldMap::with_capacity_and_hasher(
+            original.len(),
+            ahash::RandomState::default(),
+        );
+        // Names claimed by a stripped field; unprefixed fields with the same
+        // name lose to them under `last`.
+        let mut claimed = std::collections::HashSet::new();
+        for (key, value) in &original {
+            match self.stripped(key) {
+                Some(name) => {
+                    let taken =
+                        renamed.contains_key(name) || (name != key && original.contains_key(name));
+                    let target = if taken {
+                        let is_taken =
+                            |n: &str| renamed.contains_key(n) || original.contains_key(n);
+                        match self.collision.resolve(name, is_taken) {
+                            Ok(target) => target,
+                            Err(message) => return ScriptResult::Error(message),
+                        }
+                    } else {
+                        Some(name.to_string())
+                    };
+                    match target {
+                        Some(target) => {
+                            claimed.insert(target.clone());
+                            renamed.insert(target, value.clone());
+                        }
+                        None => {
+                            renamed.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+                None if claimed.contains(key.as_str()) => {}
+                None => {
+                    renamed.insert(key.clone(), value.clone());
                 }
             }
         }
-
+        event.fields = renamed;
         ScriptResult::Emit(event)
     }
 }
 
-/// Timestamp filter stage for --since and --until filtering
-pub struct TimestampFilterStage {
-    config: TimestampFilterConfig,
+/// JSON field expansion stage for --field-expand-json
+pub struct JsonFieldExpandStage {
+    config: crate::config::FieldExpandConfig,
+    collision: crate::config::CollisionPolicy,
 }
 
-impl TimestampFilterStage {
-    pub fn new(config: TimestampFilterConfig) -> Self {
-        Self { config }
+impl JsonFieldExpandStage {
+    pub fn new(
+        config: crate::config::FieldExpandConfig,
+        collision: crate::config::CollisionPolicy,
+    ) -> Self {
+        Self { config, collision }
     }
 }
 
-impl ScriptStage for TimestampFilterStage {
-    fn apply(&mut self, event: Event, ctx: &mut PipelineContext) -> ScriptResult {
-        // Get the parsed timestamp from the event
-        let event_timestamp = match event.parsed_ts {
-            Some(ts) => ts,
-            None => {
-                // No timestamp available - use new resiliency model
-                if ctx.config.strict {
-                    // Stop processing on missing timestamp in strict mode
-                    return ScriptResult::Error(
-                        "Event has no valid timestamp for --since/--until filtering".to_string(),
-                    );
-                } else {
-                    // Filter out events without valid timestamps (resilient mode)
-                    return ScriptResult::Skip;
+impl ScriptStage for JsonFieldExpandStage {
+    fn label(&self) -> String {
+        "field-expand-json".to_string()
+    }
+
+    fn apply(&mut self, mut event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        for field in &self.config.fields {
+            let Some(text) = event
+                .fields
+                .get(field)
+                .and_then(|value| value.clone().into_string().ok())
+            else {
+                continue;
+            };
+            // Plain strings and non-object JSON stay as they are.
+            let Ok(serde_json::Value::Object(object)) = serde_json::from_str(&text) else {
+                continue;
+            };
+            if !self.config.keep_original {
+                event.fields.shift_remove(field);
+            }
+            let prefix = self.config.prefix.as_deref().unwrap_or("");
+            for (key, value) in object {
+                if let Err(message) = self.collision.insert(
+                    &mut event.fields,
+                    format!("{}{}", prefix, key),
+                    crate::event::json_to_dynamic_owned(value),
+                ) {
+                    return ScriptResult::Error(message);
+                }
+            }
+        }
+        ScriptResult::Emit(event)
+    }
+}
+
+/// Field concatenation stage for --merge-fields
+pub struct MergeFieldsStage {
+    specs: Vec<crate::config::MergeFieldsSpec>,
+    skip_missing: bool,
+    collision: crate::config::CollisionPolicy,
+}
+
+impl MergeFieldsStage {
+    pub fn new(
+        specs: Vec<crate::config::MergeFieldsSpec>,
+        skip_missing: bool,
+        collision: crate::config::CollisionPolicy,
+    ) -> Self {
+        Self {
+            specs,
+            skip_missing,
+            collision,
+        }
+    }
+}
+
+impl ScriptStage for MergeFieldsStage {
+    fn label(&self) -> String {
+        "merge-fields".to_string()
+    }
+
+    fn apply(&mut self, mut event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        'specs: for spec in &self.specs {
+            let mut merged = String::new();
+            for part in &spec.parts {
+                match part {
+                    crate::config::MergePart::Literal(text) => merged.push_str(text),
+                    crate::config::MergePart::Field(name) => {
+                        match event.fields.get(name).filter(|value| !value.is_unit()) {
+                            Some(value) => {
+                                let text = match value.clone().into_string() {
+                                    Ok(text) => text,
+                                    Err(_) => {
+                                        crate::rhai_functions::datetime::render_custom_scalar(value)
+                                            .unwrap_or_else(|| value.to_string())
+                                    }
+                                };
+                                merged.push_str(&text);
+                            }
+                            None if self.skip_missing => continue 'specs,
+                            None => {}
+                        }
+                    }
+                }
+            }
+            // Later rules can build on the fields earlier ones created
+            if let Err(message) = self.collision.insert(
+                &mut event.fields,
+                spec.target.clone(),
+                rhai::Dynamic::from(merged),
+            ) {
+                return ScriptResult::Error(message);
+            }
+        }
+        ScriptResult::Emit(event)
+    }
+}
+
+/// Native level filter for --level; runs before any script stage
+pub struct LevelSelectStage {
+    selection: crate::config::LevelSelection,
+    scheme: crate::event::LevelScheme,
+    strict: bool,
+}
+
+impl LevelSelectStage {
+    pub fn new(
+        selection: crate::config::LevelSelection,
+        scheme: crate::event::LevelScheme,
+        strict: bool,
+    ) -> Self {
+        Self {
+            selection,
+            scheme,
+            strict,
+        }
+    }
+}
+
+impl ScriptStage for LevelSelectStage {
+    fn label(&self) -> String {
+        "level".to_string()
+    }
+
+    fn apply(&mut self, event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        let keep = match crate::event::event_level_rank(&event, self.scheme) {
+            Some(rank) => self.selection.matches(rank),
+            None => !self.strict,
+        };
+        if keep {
+            ScriptResult::Emit(event)
+        } else {
+            crate::stats::stats_add_level_filtered();
+            ScriptResult::Skip
+        }
+    }
+}
+
+/// JSON Schema gate for --schema-validate; runs after filters, before --keys
+pub struct SchemaValidateStage {
+    validator: super::schema::SchemaValidator,
+    on_fail: crate::cli::SchemaOnFail,
+    /// Set by the first failure under `halt`; ends the stream like --take-while
+    halted: bool,
+}
+
+impl SchemaValidateStage {
+    pub fn new(
+        validator: super::schema::SchemaValidator,
+        on_fail: crate::cli::SchemaOnFail,
+    ) -> Self {
+        Self {
+            validator,
+            on_fail,
+            halted: false,
+        }
+    }
+}
+
+impl ScriptStage for SchemaValidateStage {
+    fn label(&self) -> String {
+        "schema".to_string()
+    }
+
+    fn apply(&mut self, event: Event, ctx: &mut PipelineContext) -> ScriptResult {
+        if self.halted {
+            return ScriptResult::Skip;
+        }
+        let violations = match self.validator.validate(&event) {
+            Ok(()) => return ScriptResult::Emit(event),
+            Err(violations) => violations,
+        };
+
+        crate::stats::stats_add_schema_failure();
+        let message = format!(
+            "schema {} failed: {}",
+            self.validator.path(),
+            violations.join("; ")
+        );
+        crate::rhai_functions::tracking::track_error(
+            "schema",
+            ctx.meta.line_num,
+            &message,
+            Some(&event.original_line),
+            ctx.meta.filename.as_deref(),
+            ctx.config.verbose,
+            ctx.config.quiet_level,
+            Some(&ctx.config),
+            None,
+        );
+        persist_error_tracking(ctx);
+        run_error_hook(ctx, "schema", &message, Some(&event.original_line));
+
+        match self.on_fail {
+            _ if ctx.config.strict => ScriptResult::Error(message),
+            // The failure is already counted under "schema" and fails the run,
+            // so halting drops the event and stops reading rather than
+            // returning an error result, which would count it again
+            crate::cli::SchemaOnFail::Halt => {
+                self.halted = true;
+                ScriptResult::Skip
+            }
+            crate::cli::SchemaOnFail::Emit => ScriptResult::Emit(event),
+            crate::cli::SchemaOnFail::Skip => ScriptResult::Skip,
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.halted
+    }
+}
+
+/// Filename metadata stage for --field-from-filename
+pub struct FilenameFieldsStage {
+    pattern: regex::Regex,
+    /// Captures for the most recent filename; events arrive file by file, so
+    /// the pattern runs once per file rather than once per event
+    cached: Option<(String, Vec<(String, String)>)>,
+}
+
+impl FilenameFieldsStage {
+    pub fn new(pattern: regex::Regex) -> Self {
+        Self {
+            pattern,
+            cached: None,
+        }
+    }
+
+    fn captures(&self, filename: &str) -> Vec<(String, String)> {
+        let Some(caps) = self.pattern.captures(filename) else {
+            return Vec::new();
+        };
+        self.pattern
+            .capture_names()
+            .flatten()
+            .filter_map(|name| {
+                caps.name(name)
+                    .map(|m| (name.to_string(), m.as_str().to_string()))
+            })
+            .collect()
+    }
+}
+
+impl ScriptStage for FilenameFieldsStage {
+    fn label(&self) -> String {
+        "field-from-filename".to_string()
+    }
+
+    fn apply(&mut self, mut event: Event, ctx: &mut PipelineContext) -> ScriptResult {
+        let Some(filename) = ctx.meta.filename.as_deref() else {
+            return ScriptResult::Emit(event);
+        };
+        if self.cached.as_ref().map(|(name, _)| name.as_str()) != Some(filename) {
+            self.cached = Some((filename.to_string(), self.captures(filename)));
+        }
+        if let Some((_, fields)) = &self.cached {
+            for (key, value) in fields {
+                event.set_field(key.clone(), rhai::Dynamic::from(value.clone()));
+            }
+        }
+        ScriptResult::Emit(event)
+    }
+}
+
+/// Duration conversion stage for --parse-durations
+pub struct DurationFieldsStage {
+    fields: Vec<String>,
+}
+
+impl DurationFieldsStage {
+    pub fn new(fields: Vec<String>) -> Self {
+        Self { fields }
+    }
+}
+
+impl ScriptStage for DurationFieldsStage {
+    fn label(&self) -> String {
+        "parse-durations".to_string()
+    }
+
+    fn apply(&mut self, mut event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        for field in &self.fields {
+            let converted = event
+                .fields
+                .get(field.as_str())
+                .and_then(crate::parsers::type_conversion::detect_duration);
+            if let Some(duration) = converted {
+                event.set_field(field.clone(), rhai::Dynamic::from(duration));
+            }
+        }
+        ScriptResult::Emit(event)
+    }
+}
+
+/// Field renaming stage for --field-prefix-add
+pub struct FieldPrefixAddStage {
+    prefix: String,
+}
+
+impl FieldPrefixAddStage {
+    pub fn new(prefix: String) -> Self {
+        Self { prefix }
+    }
+}
+
+impl ScriptStage for FieldPrefixAddStage {
+    fn label(&self) -> String {
+        "field-prefix-add".to_string()
+    }
+
+    fn apply(&mut self, mut event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        event.fields = std::mem::take(&mut event.fields)
+            .into_iter()
+            .map(|(key, value)| (format!("{}{}", self.prefix, key), value))
+            .collect();
+        ScriptResult::Emit(event)
+    }
+}
+
+/// Array explode stage for --explode: one output event per array element
+pub struct ExplodeStage {
+    config: crate::config::ExplodeConfig,
+}
+
+impl ExplodeStage {
+    pub fn new(config: crate::config::ExplodeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Parent event with the array field removed and `element` merged in:
+    /// map entries become top-level fields, anything else lands in `as_field`.
+    fn element_event(&self, parent: &Event, element: rhai::Dynamic) -> Event {
+        let mut event = parent.clone();
+        event.fields.shift_remove(&self.config.field);
+        if element.is_map() {
+            let map = element.cast::<rhai::Map>();
+            for (key, value) in map {
+                event.set_field(key.to_string(), value);
+            }
+        } else {
+            event.set_field(self.config.as_field().to_string(), element);
+        }
+        event
+    }
+}
+
+impl ScriptStage for ExplodeStage {
+    fn label(&self) -> String {
+        "explode".to_string()
+    }
+
+    fn apply(&mut self, event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        let elements = match event.fields.get(&self.config.field) {
+            Some(value) if value.is_array() => value.clone().cast::<rhai::Array>(),
+            // Missing or non-array fields pass through untouched.
+            _ => return ScriptResult::Emit(event),
+        };
+
+        if elements.is_empty() {
+            return if self.config.keep_empty {
+                ScriptResult::Emit(event)
+            } else {
+                ScriptResult::Skip
+            };
+        }
+
+        ScriptResult::EmitMultiple(
+            elements
+                .into_iter()
+                .map(|element| self.element_event(&event, element))
+                .collect(),
+        )
+    }
+}
+
+/// Drain template mining stage (sequential-only, summary-driven)
+pub struct DrainStage {
+    field_name: String,
+}
+
+impl DrainStage {
+    pub fn new(field_name: String) -> Self {
+        Self { field_name }
+    }
+}
+
+impl ScriptStage for DrainStage {
+    fn label(&self) -> String {
+        "drain".to_string()
+    }
+
+    fn apply(&mut self, event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        if let Some(value) = event.fields.get(&self.field_name) {
+            let text = if value.is_string() {
+                value.clone().into_string().unwrap_or_default()
+            } else {
+                value.to_string()
+            };
+
+            if !text.is_empty() {
+                if let Err(err) = crate::drain::drain_template(&text, None, event.line_num) {
+                    return ScriptResult::Error(err);
+                }
+            }
+        }
+
+        ScriptResult::Emit(event)
+    }
+}
+
+/// Edit-distance clustering stage for --cluster (sequential-only, summary-driven)
+pub struct ClusterStage {
+    config: crate::cluster::ClusterConfig,
+}
+
+impl ClusterStage {
+    pub fn new(config: crate::cluster::ClusterConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl ScriptStage for ClusterStage {
+    fn label(&self) -> String {
+        "cluster".to_string()
+    }
+
+    fn apply(&mut self, event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        if let Some(value) = event.fields.get(&self.config.field) {
+            let text = if value.is_string() {
+                value.clone().into_string().unwrap_or_default()
+            } else {
+                value.to_string()
+            };
+
+            if !text.is_empty() {
+                crate::cluster::cluster_value(&text, self.config.threshold);
+            }
+        }
+
+        ScriptResult::Emit(event)
+    }
+}
+
+/// Timestamp filter stage for --since and --until filtering
+pub struct TimestampFilterStage {
+    config: TimestampFilterConfig,
+}
+
+impl TimestampFilterStage {
+    pub fn new(config: TimestampFilterConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl ScriptStage for TimestampFilterStage {
+    fn label(&self) -> String {
+        "since/until".to_string()
+    }
+
+    fn apply(&mut self, event: Event, ctx: &mut PipelineContext) -> ScriptResult {
+        // Get the parsed timestamp from the event
+        let event_timestamp = match event.parsed_ts {
+            Some(ts) => ts,
+            None => {
+                // No timestamp available - use new resiliency model
+                if ctx.config.strict {
+                    // Stop processing on missing timestamp in strict mode
+                    return ScriptResult::Error(
+                        "Event has no valid timestamp for --since/--until filtering".to_string(),
+                    );
+                } else {
+                    // Filter out events without valid timestamps (resilient mode)
+                    return ScriptResult::Skip;
                 }
             }
         };
@@ -1188,6 +2149,264 @@ impl ScriptStage for TimestampFilterStage {
     }
 }
 
+/// Clock regression check for --check-monotonic
+///
+/// Compares each event's timestamp with the previous event's. A regression is
+/// counted in stats and either flagged with `_ts_regression = true` or
+/// dropped; when dropping, the last event kept stays the reference point.
+/// Events without a timestamp pass through and are not compared.
+pub struct MonotonicCheckStage {
+    mode: crate::cli::MonotonicCheck,
+    previous: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl MonotonicCheckStage {
+    pub fn new(mode: crate::cli::MonotonicCheck) -> Self {
+        Self {
+            mode,
+            previous: None,
+        }
+    }
+}
+
+impl ScriptStage for MonotonicCheckStage {
+    fn label(&self) -> String {
+        "check-monotonic".to_string()
+    }
+
+    fn apply(&mut self, mut event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        let Some(ts) = event.parsed_ts else {
+            return ScriptResult::Emit(event);
+        };
+
+        if self.previous.is_some_and(|previous| ts < previous) {
+            crate::stats::stats_add_ts_regression();
+            match self.mode {
+                crate::cli::MonotonicCheck::Drop => return ScriptResult::Skip,
+                crate::cli::MonotonicCheck::Flag => {
+                    event.set_field("_ts_regression".to_string(), rhai::Dynamic::from(true));
+                }
+            }
+        }
+
+        self.previous = Some(ts);
+        ScriptResult::Emit(event)
+    }
+}
+
+/// Consecutive-run merging stage for --group-consecutive-by (sequential-only)
+pub struct ConsecutiveGroupStage {
+    field: String,
+    aggregate: Option<crate::cli::GroupAggregate>,
+    group: Option<ConsecutiveGroup>,
+}
+
+/// The open run: its first event and what the rest of the run changed
+struct ConsecutiveGroup {
+    value: rhai::Dynamic,
+    first: Event,
+    count: i64,
+    start_ts: Option<chrono::DateTime<chrono::Utc>>,
+    end_ts: Option<chrono::DateTime<chrono::Utc>>,
+    varied: indexmap::IndexSet<String>,
+    numeric: indexmap::IndexMap<String, NumericAggregate>,
+}
+
+/// Running sum/min/max of one numeric field across a group
+struct NumericAggregate {
+    sum: f64,
+    /// Exact integer sum, until a float shows up or it overflows
+    int_sum: Option<i64>,
+    count: i64,
+    min: (f64, rhai::Dynamic),
+    max: (f64, rhai::Dynamic),
+}
+
+impl NumericAggregate {
+    fn new(number: f64, value: &rhai::Dynamic) -> Self {
+        Self {
+            sum: number,
+            int_sum: value.as_int().ok(),
+            count: 1,
+            min: (number, value.clone()),
+            max: (number, value.clone()),
+        }
+    }
+
+    fn add(&mut self, number: f64, value: &rhai::Dynamic) {
+        self.sum += number;
+        self.int_sum = match (self.int_sum, value.as_int()) {
+            (Some(sum), Ok(int)) => sum.checked_add(int),
+            _ => None,
+        };
+        self.count += 1;
+        if number < self.min.0 {
+            self.min = (number, value.clone());
+        }
+        if number > self.max.0 {
+            self.max = (number, value.clone());
+        }
+    }
+
+    fn result(&self, aggregate: crate::cli::GroupAggregate) -> rhai::Dynamic {
+        match aggregate {
+            crate::cli::GroupAggregate::Sum => match self.int_sum {
+                Some(sum) => rhai::Dynamic::from(sum),
+                None => rhai::Dynamic::from(self.sum),
+            },
+            crate::cli::GroupAggregate::Avg => rhai::Dynamic::from(self.sum / self.count as f64),
+            crate::cli::GroupAggregate::Min => self.min.1.clone(),
+            crate::cli::GroupAggregate::Max => self.max.1.clone(),
+        }
+    }
+}
+
+fn numeric_value(value: &rhai::Dynamic) -> Option<f64> {
+    value
+        .as_int()
+        .map(|int| int as f64)
+        .or_else(|_| value.as_float())
+        .ok()
+}
+
+/// Values are the same when they have the same type and render the same
+fn same_value(a: &rhai::Dynamic, b: &rhai::Dynamic) -> bool {
+    a.type_name() == b.type_name() && a.to_string() == b.to_string()
+}
+
+impl ConsecutiveGroup {
+    fn new(first: Event, value: rhai::Dynamic, group_field: &str, aggregate: bool) -> Self {
+        let numeric = if aggregate {
+            first
+                .fields
+                .iter()
+                .filter(|(key, _)| key.as_str() != group_field)
+                .filter_map(|(key, value)| {
+                    numeric_value(value)
+                        .map(|number| (key.clone(), NumericAggregate::new(number, value)))
+                })
+                .collect()
+        } else {
+            indexmap::IndexMap::new()
+        };
+        Self {
+            value,
+            count: 1,
+            start_ts: first.parsed_ts,
+            end_ts: first.parsed_ts,
+            varied: indexmap::IndexSet::new(),
+            numeric,
+            first,
+        }
+    }
+
+    fn add(&mut self, event: &Event) {
+        self.count += 1;
+        if let Some(ts) = event.parsed_ts {
+            self.start_ts = Some(self.start_ts.map_or(ts, |start| start.min(ts)));
+            self.end_ts = Some(self.end_ts.map_or(ts, |end| end.max(ts)));
+        }
+
+        for (key, value) in &event.fields {
+            if let Some(aggregate) = self.numeric.get_mut(key) {
+                if let Some(number) = numeric_value(value) {
+                    aggregate.add(number, value);
+                }
+            }
+            if !self.varied.contains(key)
+                && !self
+                    .first
+                    .fields
+                    .get(key)
+                    .is_some_and(|first| same_value(first, value))
+            {
+                self.varied.insert(key.clone());
+            }
+        }
+        for key in self.first.fields.keys() {
+            if !event.fields.contains_key(key) && !self.varied.contains(key) {
+                self.varied.insert(key.clone());
+            }
+        }
+    }
+
+    /// The first event, with numeric fields aggregated and `_group_*` fields
+    /// describing the run
+    fn into_event(self, aggregate: Option<crate::cli::GroupAggregate>) -> Event {
+        let mut event = self.first;
+        if let Some(aggregate) = aggregate {
+            for (key, numbers) in &self.numeric {
+                event.set_field(key.clone(), numbers.result(aggregate));
+            }
+        }
+        event.set_field("_group_count".to_string(), rhai::Dynamic::from(self.count));
+        let format_ts = |ts: chrono::DateTime<chrono::Utc>| {
+            rhai::Dynamic::from(ts.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+        };
+        if let Some(start) = self.start_ts {
+            event.set_field("_group_start_ts".to_string(), format_ts(start));
+        }
+        if let Some(end) = self.end_ts {
+            event.set_field("_group_end_ts".to_string(), format_ts(end));
+        }
+        let varied: rhai::Array = self.varied.into_iter().map(rhai::Dynamic::from).collect();
+        event.set_field(
+            "_group_fields_varied".to_string(),
+            rhai::Dynamic::from(varied),
+        );
+        event
+    }
+}
+
+impl ConsecutiveGroupStage {
+    pub fn new(field: String, aggregate: Option<crate::cli::GroupAggregate>) -> Self {
+        Self {
+            field,
+            aggregate,
+            group: None,
+        }
+    }
+}
+
+impl ScriptStage for ConsecutiveGroupStage {
+    fn label(&self) -> String {
+        "group-consecutive".to_string()
+    }
+
+    fn apply(&mut self, event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
+        // Events without the field close the open group and pass through.
+        let Some(value) = event.fields.get(&self.field).cloned() else {
+            return match self.group.take() {
+                Some(group) => {
+                    ScriptResult::EmitMultiple(vec![group.into_event(self.aggregate), event])
+                }
+                None => ScriptResult::Emit(event),
+            };
+        };
+
+        if let Some(group) = self.group.as_mut() {
+            if same_value(&group.value, &value) {
+                group.add(&event);
+                return ScriptResult::Skip;
+            }
+        }
+
+        let next = ConsecutiveGroup::new(event, value, &self.field, self.aggregate.is_some());
+        match self.group.replace(next) {
+            Some(closed) => ScriptResult::Emit(closed.into_event(self.aggregate)),
+            None => ScriptResult::Skip,
+        }
+    }
+
+    fn finish(&mut self) -> Vec<Event> {
+        self.group
+            .take()
+            .map(|group| group.into_event(self.aggregate))
+            .into_iter()
+            .collect()
+    }
+}
+
 /// Normalize the primary timestamp field to RFC3339 once scripts have run
 pub struct TimestampConversionStage {
     ts_config: crate::timestamp::TsConfig,
@@ -1198,12 +2417,16 @@ impl TimestampConversionStage {
         ts_field: Option<String>,
         ts_format: Option<String>,
         default_timezone: Option<String>,
+        tz_field: Option<String>,
+        assume_year: Option<i32>,
     ) -> Self {
         Self {
             ts_config: crate::timestamp::TsConfig {
                 custom_field: ts_field,
                 custom_format: ts_format,
                 default_timezone,
+                tz_field,
+                assume_year,
             },
         }
     }
@@ -1221,6 +2444,10 @@ impl TimestampConversionStage {
 }
 
 impl ScriptStage for TimestampConversionStage {
+    fn label(&self) -> String {
+        "normalize-ts".to_string()
+    }
+
     fn apply(&mut self, mut event: Event, _ctx: &mut PipelineContext) -> ScriptResult {
         event.extract_timestamp_with_config(None, &self.ts_config);
 
@@ -1241,6 +2468,108 @@ impl ScriptStage for TimestampConversionStage {
 
 // ContextStage removed - context processing is now integrated into FilterStage and LevelFilterStage
 
+/// Uniform fixed-size sample for --reservoir N (Vitter's Algorithm R).
+///
+/// Held by the pipeline at the output boundary rather than run as a script
+/// stage: sampled events are only known once the stream ends.
+pub struct ReservoirSampler<T> {
+    capacity: usize,
+    seen: usize,
+    /// Sampled items tagged with their arrival index, so the sample can be
+    /// written back in stream order
+    slots: Vec<(usize, T)>,
+    rng: fastrand::Rng,
+}
+
+impl<T> ReservoirSampler<T> {
+    /// Seeded from `KELORA_SEED` when set, like `rand()`
+    pub fn new(capacity: usize) -> Self {
+        Self::with_rng(capacity, crate::rhai_functions::random::seeded_rng())
+    }
+
+    pub fn with_rng(capacity: usize, rng: fastrand::Rng) -> Self {
+        Self {
+            capacity,
+            seen: 0,
+            slots: Vec::with_capacity(capacity.min(4096)),
+            rng,
+        }
+    }
+
+    /// Number of items offered so far
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Offer the next item. Returns the item that is not (or no longer) part
+    /// of the sample: the offered one, an evicted one, or none while filling.
+    pub fn offer(&mut self, item: T) -> Option<T> {
+        let index = self.seen;
+        self.seen += 1;
+        if self.slots.len() < self.capacity {
+            self.slots.push((index, item));
+            return None;
+        }
+        // Keep item i (1-based) with probability N/i, replacing a uniform slot
+        let j = self.rng.usize(0..self.seen);
+        if j < self.capacity {
+            let (_, evicted) = std::mem::replace(&mut self.slots[j], (index, item));
+            Some(evicted)
+        } else {
+            Some(item)
+        }
+    }
+
+    /// Take the sample in arrival order, leaving the sampler empty
+    pub fn drain(&mut self) -> Vec<T> {
+        let mut slots = std::mem::take(&mut self.slots);
+        slots.sort_by_key(|(index, _)| *index);
+        slots.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+/// Merge partial reservoirs into one uniform sample of at most `capacity`.
+///
+/// Each part is `(seen, sample)` from a sampler that saw `seen` items. Every
+/// draw picks a part with probability proportional to the items it still
+/// stands for, then takes a uniform item from that part's sample, which keeps
+/// each of the combined items equally likely to be chosen. Returns the merged
+/// sample and the items left out.
+pub fn merge_reservoirs<T>(
+    parts: Vec<(usize, Vec<T>)>,
+    capacity: usize,
+    rng: &mut fastrand::Rng,
+) -> (Vec<T>, Vec<T>) {
+    let mut remaining: Vec<usize> = parts.iter().map(|(seen, _)| *seen).collect();
+    let mut samples: Vec<Vec<T>> = parts.into_iter().map(|(_, sample)| sample).collect();
+    let mut total: usize = remaining.iter().sum();
+    let mut merged = Vec::new();
+
+    while merged.len() < capacity && total > 0 {
+        let mut pick = rng.usize(0..total);
+        let part = remaining
+            .iter()
+            .position(|&count| {
+                if pick < count {
+                    true
+                } else {
+                    pick -= count;
+                    false
+                }
+            })
+            .expect("pick is below the remaining total");
+        remaining[part] -= 1;
+        total -= 1;
+        let sample = &mut samples[part];
+        if !sample.is_empty() {
+            let index = rng.usize(0..sample.len());
+            merged.push(sample.swap_remove(index));
+        }
+    }
+
+    (merged, samples.into_iter().flatten().collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1268,7 +2597,11 @@ mod tests {
             legend_mode: crate::config::LegendMode::Auto,
             input_files: vec![],
             allow_fs_writes: false,
+            sandbox: false,
             format_name: None,
+            profile_stages: false,
+            suppress_empty_fields: crate::config::EmptyFieldSuppression::default(),
+            on_stage_error: None,
         }
     }
 
@@ -1341,7 +2674,11 @@ mod tests {
                 legend_mode: crate::config::LegendMode::Auto,
                 input_files: vec![],
                 allow_fs_writes: false,
+                sandbox: false,
                 format_name: None,
+                profile_stages: false,
+                suppress_empty_fields: crate::config::EmptyFieldSuppression::default(),
+                on_stage_error: None,
             },
             tracker: std::collections::HashMap::new(),
             internal_tracker: std::collections::HashMap::new(),
@@ -1456,7 +2793,11 @@ mod tests {
                 legend_mode: crate::config::LegendMode::Auto,
                 input_files: vec![],
                 allow_fs_writes: false,
+                sandbox: false,
                 format_name: None,
+                profile_stages: false,
+                suppress_empty_fields: crate::config::EmptyFieldSuppression::default(),
+                on_stage_error: None,
             },
             tracker: std::collections::HashMap::new(),
             internal_tracker: std::collections::HashMap::new(),
@@ -1539,7 +2880,11 @@ mod tests {
                 legend_mode: crate::config::LegendMode::Auto,
                 input_files: vec![],
                 allow_fs_writes: false,
+                sandbox: false,
                 format_name: None,
+                profile_stages: false,
+                suppress_empty_fields: crate::config::EmptyFieldSuppression::default(),
+                on_stage_error: None,
             },
             tracker: std::collections::HashMap::new(),
             internal_tracker: std::collections::HashMap::new(),
@@ -1625,7 +2970,11 @@ mod tests {
                 legend_mode: crate::config::LegendMode::Auto,
                 input_files: vec![],
                 allow_fs_writes: false,
+                sandbox: false,
                 format_name: None,
+                profile_stages: false,
+                suppress_empty_fields: crate::config::EmptyFieldSuppression::default(),
+                on_stage_error: None,
             },
             tracker: std::collections::HashMap::new(),
             internal_tracker: std::collections::HashMap::new(),
@@ -1688,7 +3037,11 @@ mod tests {
                 legend_mode: crate::config::LegendMode::Auto,
                 input_files: vec![],
                 allow_fs_writes: false,
+                sandbox: false,
                 format_name: None,
+                profile_stages: false,
+                suppress_empty_fields: crate::config::EmptyFieldSuppression::default(),
+                on_stage_error: None,
             },
             tracker: std::collections::HashMap::new(),
             internal_tracker: std::collections::HashMap::new(),
@@ -1750,7 +3103,11 @@ mod tests {
                 legend_mode: crate::config::LegendMode::Auto,
                 input_files: vec![],
                 allow_fs_writes: false,
+                sandbox: false,
                 format_name: None,
+                profile_stages: false,
+                suppress_empty_fields: crate::config::EmptyFieldSuppression::default(),
+                on_stage_error: None,
             },
             tracker: std::collections::HashMap::new(),
             internal_tracker: std::collections::HashMap::new(),
@@ -1774,4 +3131,103 @@ mod tests {
         let result = stage.apply(event_no_ts, &mut ctx);
         matches!(result, ScriptResult::Emit(_));
     }
+
+    /// Pearson chi-square statistic of observed counts against a uniform
+    /// expectation
+    fn chi_square(counts: &[usize], expected: f64) -> f64 {
+        counts
+            .iter()
+            .map(|&count| {
+                let diff = count as f64 - expected;
+                diff * diff / expected
+            })
+            .sum()
+    }
+
+    // 99.9th percentile of the chi-square distribution with 19 degrees of
+    // freedom; the RNGs are seeded, so these tests are deterministic
+    const CHI_SQUARE_19_DF_P999: f64 = 43.82;
+
+    #[test]
+    fn test_reservoir_sampler_keeps_arrival_order_and_size() {
+        let mut sampler = ReservoirSampler::with_rng(3, fastrand::Rng::with_seed(7));
+        let mut left_out = 0;
+        for n in 0..100 {
+            if sampler.offer(n).is_some() {
+                left_out += 1;
+            }
+        }
+        assert_eq!(sampler.seen(), 100);
+        assert_eq!(left_out, 97);
+        let sample = sampler.drain();
+        assert_eq!(sample.len(), 3);
+        assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // A short stream is kept whole
+        let mut sampler = ReservoirSampler::with_rng(5, fastrand::Rng::with_seed(7));
+        for n in 0..3 {
+            assert!(sampler.offer(n).is_none());
+        }
+        assert_eq!(sampler.drain(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_reservoir_sampler_is_uniform() {
+        let (items, capacity, trials) = (20, 5, 4000);
+        let mut counts = vec![0usize; items];
+        let mut rng = fastrand::Rng::with_seed(42);
+        for _ in 0..trials {
+            let mut sampler =
+                ReservoirSampler::with_rng(capacity, fastrand::Rng::with_seed(rng.u64(..)));
+            for n in 0..items {
+                sampler.offer(n);
+            }
+            for n in sampler.drain() {
+                counts[n] += 1;
+            }
+        }
+        let expected = (trials * capacity) as f64 / items as f64;
+        let statistic = chi_square(&counts, expected);
+        assert!(
+            statistic < CHI_SQUARE_19_DF_P999,
+            "chi-square {} for counts {:?}",
+            statistic,
+            counts
+        );
+    }
+
+    #[test]
+    fn test_merge_reservoirs_is_uniform_across_uneven_parts() {
+        // Parts of 3, 5 and 12 items; the small ones are sampled whole
+        let (capacity, trials) = (5, 4000);
+        let mut counts = vec![0usize; 20];
+        let mut rng = fastrand::Rng::with_seed(42);
+        for _ in 0..trials {
+            let parts = [0..3, 3..8, 8..20]
+                .into_iter()
+                .map(|range| {
+                    let mut sampler =
+                        ReservoirSampler::with_rng(capacity, fastrand::Rng::with_seed(rng.u64(..)));
+                    for n in range {
+                        sampler.offer(n);
+                    }
+                    (sampler.seen(), sampler.drain())
+                })
+                .collect();
+            let (merged, dropped) = merge_reservoirs(parts, capacity, &mut rng);
+            assert_eq!(merged.len(), capacity);
+            assert_eq!(merged.len() + dropped.len(), 3 + 5 + 5);
+            for n in merged {
+                counts[n] += 1;
+            }
+        }
+        let expected = (trials * capacity) as f64 / 20.0;
+        let statistic = chi_square(&counts, expected);
+        assert!(
+            statistic < CHI_SQUARE_19_DF_P999,
+            "chi-square {} for counts {:?}",
+            statistic,
+            counts
+        );
+    }
 }
@@ -0,0 +1,200 @@
+//! Time-based thinning for `--thin`.
+//!
+//! Events are bucketed by their timestamp into aligned intervals, separately
+//! for each `--thin-by` value, and at most one event per bucket is written.
+//! With `first`, the first event of a bucket passes straight through and the
+//! rest are dropped. The other modes hold one candidate per key and replace
+//! it as better ones arrive; the candidate is released when an event for the
+//! same key lands in a different interval, or when input ends.
+
+use indexmap::IndexMap;
+use rhai::Dynamic;
+
+use crate::config::{ThinConfig, ThinKeep};
+use crate::event::Event;
+use crate::formatters::resolve_field_path;
+
+struct Bucket {
+    start: i64,
+    /// Held representative (all modes but `first`)
+    candidate: Option<Event>,
+}
+
+/// Result of offering one event to the thinner
+#[derive(Default)]
+pub struct ThinOutcome {
+    /// Events to write now, in order
+    pub released: Vec<Event>,
+    /// Events dropped because their interval is already represented
+    pub dropped: usize,
+}
+
+pub struct ThinProcessor {
+    interval_ms: i64,
+    by: Option<String>,
+    keep: ThinKeep,
+    /// Current bucket per --thin-by value, in order of first appearance so
+    /// held candidates flush deterministically
+    open: IndexMap<String, Bucket>,
+}
+
+impl ThinProcessor {
+    pub fn new(config: ThinConfig) -> Self {
+        Self {
+            interval_ms: config.interval_ms,
+            by: config.by,
+            keep: config.keep,
+            open: IndexMap::new(),
+        }
+    }
+
+    /// Offer an event whose `parsed_ts` is up to date. Untimestamped events
+    /// are released unchanged.
+    pub fn offer(&mut self, event: Event) -> ThinOutcome {
+        let mut outcome = ThinOutcome::default();
+        let Some(ts) = event.parsed_ts else {
+            outcome.released.push(event);
+            return outcome;
+        };
+        let start = ts
+            .timestamp_millis()
+            .div_euclid(self.interval_ms)
+            .saturating_mul(self.interval_ms);
+        let key = match self.by.as_deref() {
+            Some(field) => resolve_field_path(&event, field)
+                .map(|value| value.to_string())
+                .unwrap_or_default(),
+            None => String::new(),
+        };
+
+        let holds = self.keep != ThinKeep::First;
+        let Some(bucket) = self.open.get_mut(&key) else {
+            let candidate = if holds {
+                Some(event)
+            } else {
+                outcome.released.push(event);
+                None
+            };
+            self.open.insert(key, Bucket { start, candidate });
+            return outcome;
+        };
+
+        if bucket.start != start {
+            // The key moved on to another interval: its representative is final
+            bucket.start = start;
+            if holds {
+                outcome.released.extend(bucket.candidate.replace(event));
+            } else {
+                outcome.released.push(event);
+            }
+            return outcome;
+        }
+
+        outcome.dropped = 1;
+        if let Some(held) = bucket.candidate.as_mut() {
+            if replaces(&self.keep, held, &event) {
+                *held = event;
+            }
+        }
+        outcome
+    }
+
+    /// Release every held candidate once input has ended.
+    pub fn finish(&mut self) -> Vec<Event> {
+        self.open
+            .drain(..)
+            .filter_map(|(_, bucket)| bucket.candidate)
+            .collect()
+    }
+}
+
+/// Whether `event` should represent the interval instead of `held`. Ties keep
+/// the earlier event; an event without a numeric value never wins over one
+/// with it.
+fn replaces(keep: &ThinKeep, held: &Event, event: &Event) -> bool {
+    let value = |event: &Event, field: &str| {
+        resolve_field_path(event, field).and_then(|v| numeric_value(&v))
+    };
+    let (field, prefer_lower) = match keep {
+        ThinKeep::First => return false,
+        ThinKeep::Last => return true,
+        ThinKeep::Min(field) => (field, true),
+        ThinKeep::Max(field) => (field, false),
+    };
+    match (value(held, field), value(event, field)) {
+        (Some(current), Some(new)) if prefer_lower => new < current,
+        (Some(current), Some(new)) => new > current,
+        (None, Some(_)) => true,
+        _ => false,
+    }
+}
+
+fn numeric_value(value: &Dynamic) -> Option<f64> {
+    if value.is_int() {
+        value.as_int().ok().map(|v| v as f64)
+    } else if value.is_float() {
+        value.as_float().ok()
+    } else {
+        value.to_string().trim().parse::<f64>().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn event(secs: i64, ms: i64) -> Event {
+        let mut event = Event::default();
+        event.set_field("ms".to_string(), Dynamic::from(ms));
+        event.parsed_ts = Some(Utc.timestamp_opt(secs, 0).unwrap());
+        event
+    }
+
+    fn run(keep: ThinKeep, events: Vec<Event>) -> (Vec<i64>, usize) {
+        let mut thin = ThinProcessor::new(ThinConfig {
+            interval_ms: 60_000,
+            by: None,
+            keep,
+        });
+        let mut kept = Vec::new();
+        let mut dropped = 0;
+        for event in events {
+            let outcome = thin.offer(event);
+            kept.extend(outcome.released);
+            dropped += outcome.dropped;
+        }
+        kept.extend(thin.finish());
+        let values = kept
+            .iter()
+            .map(|e| e.fields["ms"].as_int().unwrap())
+            .collect();
+        (values, dropped)
+    }
+
+    #[test]
+    fn test_thin_keeps_one_event_per_interval() {
+        let events = || {
+            vec![
+                event(10, 3),
+                event(50, 9),
+                event(30, 1),
+                event(70, 4),
+                event(65, 8),
+            ]
+        };
+        assert_eq!(run(ThinKeep::First, events()), (vec![3, 4], 3));
+        assert_eq!(run(ThinKeep::Last, events()), (vec![1, 8], 3));
+        assert_eq!(run(ThinKeep::Max("ms".into()), events()), (vec![9, 8], 3));
+        assert_eq!(run(ThinKeep::Min("ms".into()), events()), (vec![1, 4], 3));
+    }
+
+    #[test]
+    fn test_thin_passes_untimestamped_events() {
+        let mut untimed = Event::default();
+        untimed.set_field("ms".to_string(), Dynamic::from(0_i64));
+        let (kept, dropped) = run(ThinKeep::First, vec![event(10, 1), untimed, event(20, 2)]);
+        assert_eq!(kept, vec![1, 0]);
+        assert_eq!(dropped, 1);
+    }
+}
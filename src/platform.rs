@@ -6,9 +6,10 @@ use std::io::{self, Write};
 use std::panic::{self, PanicHookInfo};
 use std::path::Path;
 use std::process;
-use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
-use std::sync::Once;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Mutex, Once};
 use std::thread;
+use std::time::Duration;
 
 // Cross-platform signal handling
 #[cfg(unix)]
@@ -46,17 +47,126 @@ pub enum ExitCode {
 
 impl ExitCode {
     pub fn exit(self) -> ! {
-        process::exit(self as i32)
+        exit_with_code(self as i32)
     }
 }
 
-/// Global termination flag for graceful shutdown
-pub static SHOULD_TERMINATE: AtomicBool = AtomicBool::new(false);
-pub static TERMINATED_BY_SIGNAL: AtomicBool = AtomicBool::new(false);
+/// Exit the process with `code`, e.g. the one a script passed to `exit()`
+pub fn exit_with_code(code: i32) -> ! {
+    // process::exit skips destructors, so pass the shutdown on to input
+    // commands here rather than relying on ProcessCleanup's Drop
+    ProcessCleanup::interrupt_children();
+    process::exit(code)
+}
+
+/// Why a run was cancelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    /// A signal (SIGINT, SIGTERM, ...) asked the process to stop
+    Signal(i32),
+    /// The parallel sink wrote the last event allowed by --take
+    TakeLimit,
+    /// The embedding caller cancelled the run
+    Requested,
+    /// A script called `exit(code)`
+    Exit(i32),
+}
+
+// Encoding of CancelReason in CancelToken's atomic (signals are positive,
+// exit codes are stored below REASON_EXIT)
+const REASON_NONE: i32 = 0;
+const REASON_TAKE_LIMIT: i32 = -1;
+const REASON_REQUESTED: i32 = -2;
+const REASON_EXIT: i32 = -1000;
+
+/// Per-run cancellation flag shared by every thread of one pipeline run.
+///
+/// Clones share the same state. The first cancellation wins, except that a
+/// signal replaces an earlier non-signal reason, and a script `exit()` an
+/// earlier --take or caller cancellation, so the exit code still reflects
+/// it. The signal handler cancels whichever token is registered
+/// with [`CancelToken::set_active`].
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    reason: Arc<AtomicI32>,
+}
+
+/// The token of the run the signal handler should cancel
+static ACTIVE_RUN: Mutex<Option<CancelToken>> = Mutex::new(None);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel on behalf of the caller ([`CancelReason::Requested`])
+    pub fn cancel(&self) {
+        self.cancel_with(CancelReason::Requested);
+    }
+
+    pub fn cancel_with(&self, reason: CancelReason) {
+        let code = match reason {
+            CancelReason::Signal(signal) => signal.max(1),
+            CancelReason::TakeLimit => REASON_TAKE_LIMIT,
+            CancelReason::Requested => REASON_REQUESTED,
+            CancelReason::Exit(exit_code) => REASON_EXIT - exit_code.clamp(0, 255),
+        };
+        let _ = self
+            .reason
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                let replaces = current == REASON_NONE
+                    || (code > 0 && current < 0)
+                    || (code <= REASON_EXIT && current > REASON_EXIT && current < 0);
+                replaces.then_some(code)
+            });
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.reason.load(Ordering::Relaxed) != REASON_NONE
+    }
 
-/// Track which signal caused termination (for correct exit code)
-/// 0 = no signal, 2 = SIGINT, 15 = SIGTERM, etc.
-pub static TERMINATION_SIGNAL: AtomicI32 = AtomicI32::new(0);
+    pub fn reason(&self) -> Option<CancelReason> {
+        match self.reason.load(Ordering::Relaxed) {
+            REASON_NONE => None,
+            REASON_TAKE_LIMIT => Some(CancelReason::TakeLimit),
+            REASON_REQUESTED => Some(CancelReason::Requested),
+            code if code <= REASON_EXIT => Some(CancelReason::Exit(REASON_EXIT - code)),
+            signal => Some(CancelReason::Signal(signal)),
+        }
+    }
+
+    /// The signal that cancelled this run, if it was a signal
+    pub fn signal(&self) -> Option<i32> {
+        match self.reason() {
+            Some(CancelReason::Signal(signal)) => Some(signal),
+            _ => None,
+        }
+    }
+
+    /// The code a script passed to `exit()`, if that ended the run
+    pub fn exit_code(&self) -> Option<i32> {
+        match self.reason() {
+            Some(CancelReason::Exit(code)) => Some(code),
+            _ => None,
+        }
+    }
+
+    /// Route process signals to this token until another run replaces it
+    pub fn set_active(&self) {
+        *ACTIVE_RUN.lock().unwrap_or_else(|e| e.into_inner()) = Some(self.clone());
+    }
+
+    /// Cancel the active run's token, if any
+    fn cancel_active(reason: CancelReason) {
+        if let Some(token) = ACTIVE_RUN
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+        {
+            token.cancel_with(reason);
+        }
+    }
+}
 
 /// Control messages broadcast by the signal handler to processing components
 #[derive(Debug, Clone)]
@@ -107,9 +217,7 @@ impl SignalHandler {
                 for sig in signals.forever() {
                     match sig {
                         SIGINT => {
-                            SHOULD_TERMINATE.store(true, Ordering::Relaxed);
-                            TERMINATED_BY_SIGNAL.store(true, Ordering::Relaxed);
-                            TERMINATION_SIGNAL.store(SIGINT, Ordering::Relaxed);
+                            CancelToken::cancel_active(CancelReason::Signal(SIGINT));
                             shutdown_count += 1;
                             let immediate = shutdown_count > 1;
                             let _ = sender.send(Ctrl::Shutdown { immediate });
@@ -119,8 +227,7 @@ impl SignalHandler {
                         }
                         SIGPIPE => {
                             // Broken pipe - exit quietly (normal for Unix pipes)
-                            SHOULD_TERMINATE.store(true, Ordering::Relaxed);
-                            TERMINATED_BY_SIGNAL.store(true, Ordering::Relaxed);
+                            CancelToken::cancel_active(CancelReason::Signal(SIGPIPE));
                             ExitCode::SignalPipe.exit();
                         }
                         SIGTERM => {
@@ -130,9 +237,7 @@ impl SignalHandler {
                                     "Received SIGTERM, shutting down gracefully..."
                                 )
                             );
-                            SHOULD_TERMINATE.store(true, Ordering::Relaxed);
-                            TERMINATED_BY_SIGNAL.store(true, Ordering::Relaxed);
-                            TERMINATION_SIGNAL.store(SIGTERM, Ordering::Relaxed);
+                            CancelToken::cancel_active(CancelReason::Signal(SIGTERM));
                             shutdown_count += 1;
                             let immediate = shutdown_count > 1;
                             let _ = sender.send(Ctrl::Shutdown { immediate });
@@ -189,9 +294,7 @@ impl SignalHandler {
                 loop {
                     thread::sleep(std::time::Duration::from_millis(100));
                     if term_flag.load(Ordering::Relaxed) {
-                        SHOULD_TERMINATE.store(true, Ordering::Relaxed);
-                        TERMINATED_BY_SIGNAL.store(true, Ordering::Relaxed);
-                        TERMINATION_SIGNAL.store(SIGINT, Ordering::Relaxed);
+                        CancelToken::cancel_active(CancelReason::Signal(SIGINT));
                         shutdown_count += 1;
                         let immediate = shutdown_count > 1;
                         let _ = sender.send(Ctrl::Shutdown { immediate });
@@ -205,11 +308,6 @@ impl SignalHandler {
             Ok(SignalHandler { _handle: handle })
         }
     }
-
-    /// Check if we should terminate processing
-    pub fn should_terminate() -> bool {
-        SHOULD_TERMINATE.load(Ordering::Relaxed)
-    }
 }
 
 /// Safe wrapper for writing to stdout that handles broken pipes and other I/O errors
@@ -418,14 +516,6 @@ impl std::io::Write for SafeFileOut {
     }
 }
 
-/// Utility function to check for termination between processing steps
-pub fn check_termination() -> Result<()> {
-    if SignalHandler::should_terminate() {
-        return Err(anyhow::anyhow!("Processing terminated by signal"));
-    }
-    Ok(())
-}
-
 /// Process cleanup utilities
 pub struct ProcessCleanup {
     cleanup_tasks: Vec<Box<dyn FnOnce() + Send>>,
@@ -471,12 +561,55 @@ fn is_stdout_broken_pipe_panic(info: &PanicHookInfo<'_>) -> bool {
     false
 }
 
+/// Child processes spawned for `--input-cmd`, interrupted on shutdown
+static CHILD_PROCESSES: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
 impl ProcessCleanup {
     pub fn new() -> Self {
         Self {
             cleanup_tasks: Vec::new(),
         }
     }
+
+    /// Track a child process so shutdown can pass SIGINT on to it
+    pub fn register_child(pid: u32) {
+        if let Ok(mut children) = CHILD_PROCESSES.lock() {
+            children.push(pid);
+        }
+    }
+
+    /// Stop tracking a child process once it has been waited for
+    pub fn unregister_child(pid: u32) {
+        if let Ok(mut children) = CHILD_PROCESSES.lock() {
+            children.retain(|&child| child != pid);
+        }
+    }
+
+    /// Send SIGINT to every tracked child process
+    pub fn interrupt_children() {
+        let Ok(children) = CHILD_PROCESSES.lock() else {
+            return;
+        };
+        for &pid in children.iter() {
+            interrupt_process(pid);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn interrupt_process(pid: u32) {
+    // Input commands lead their own process group; signal the whole group so
+    // the programs their shell started stop too.
+    // SAFETY: kill() has no memory-safety preconditions; a group that already
+    // exited just makes it fail with ESRCH
+    unsafe {
+        libc::kill(-(pid as libc::pid_t), libc::SIGINT);
+    }
+}
+
+#[cfg(not(unix))]
+fn interrupt_process(_pid: u32) {
+    // Console children share Ctrl+C with kelora; there is no SIGINT to forward
 }
 
 impl Drop for ProcessCleanup {
@@ -486,9 +619,52 @@ impl Drop for ProcessCleanup {
         while let Some(task) = self.cleanup_tasks.pop() {
             task();
         }
+        Self::interrupt_children();
     }
 }
 
+/// Process-wide CPU time and peak memory, sampled once at the end of a run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResourceUsage {
+    pub user_time: Duration,
+    pub system_time: Duration,
+    pub peak_rss_bytes: u64,
+}
+
+/// Best-effort resource usage of the current process via `getrusage(2)`.
+#[cfg(unix)]
+pub fn resource_usage() -> Option<ResourceUsage> {
+    // SAFETY: rusage is a plain C struct for which all-zero bytes are valid.
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    // SAFETY: the pointer is valid for writes of one rusage for the whole call.
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) } != 0 {
+        return None;
+    }
+
+    let to_duration = |tv: libc::timeval| {
+        Duration::from_secs(tv.tv_sec.max(0) as u64)
+            + Duration::from_micros(tv.tv_usec.max(0) as u64)
+    };
+    // ru_maxrss is in bytes on Apple platforms and in kilobytes elsewhere
+    let rss_unit: u64 = if cfg!(any(target_os = "macos", target_os = "ios")) {
+        1
+    } else {
+        1024
+    };
+
+    Some(ResourceUsage {
+        user_time: to_duration(usage.ru_utime),
+        system_time: to_duration(usage.ru_stime),
+        peak_rss_bytes: (usage.ru_maxrss.max(0) as u64).saturating_mul(rss_unit),
+    })
+}
+
+/// Resource usage is not collected on this platform.
+#[cfg(not(unix))]
+pub fn resource_usage() -> Option<ResourceUsage> {
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -504,8 +680,65 @@ mod tests {
     }
 
     #[test]
-    fn test_should_terminate_initial_state() {
-        // Should start as false
-        assert!(!SignalHandler::should_terminate());
+    fn test_cancel_token_initial_state() {
+        let token = CancelToken::new();
+        assert!(!token.is_cancelled());
+        assert_eq!(token.reason(), None);
+    }
+
+    #[test]
+    fn test_cancel_token_keeps_first_reason_across_clones() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        clone.cancel_with(CancelReason::TakeLimit);
+        token.cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(token.reason(), Some(CancelReason::TakeLimit));
+        assert_eq!(token.signal(), None);
+
+        // A signal still overrides, so the exit code reflects it
+        clone.cancel_with(CancelReason::Signal(15));
+        clone.cancel_with(CancelReason::Signal(2));
+        assert_eq!(token.signal(), Some(15));
+    }
+
+    #[test]
+    fn test_cancel_token_exit_code() {
+        let token = CancelToken::new();
+        token.cancel_with(CancelReason::TakeLimit);
+        token.cancel_with(CancelReason::Exit(3));
+        assert_eq!(token.reason(), Some(CancelReason::Exit(3)));
+        assert_eq!(token.exit_code(), Some(3));
+
+        // A later exit() keeps the first code; a signal still overrides
+        token.cancel_with(CancelReason::Exit(0));
+        assert_eq!(token.exit_code(), Some(3));
+        token.cancel_with(CancelReason::Signal(2));
+        assert_eq!(token.exit_code(), None);
+        assert_eq!(token.signal(), Some(2));
+    }
+
+    #[test]
+    fn test_cancel_tokens_are_independent() {
+        let first = CancelToken::new();
+        let second = CancelToken::new();
+        first.cancel();
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resource_usage_reports_cpu_and_rss() {
+        // Burn a little CPU so user time is measurable
+        let mut acc = 0u64;
+        for i in 0..2_000_000u64 {
+            acc = acc.wrapping_mul(31).wrapping_add(i);
+        }
+        std::hint::black_box(acc);
+
+        let usage = resource_usage().expect("getrusage should succeed on unix");
+        assert!(usage.peak_rss_bytes > 0);
+        assert!(usage.user_time + usage.system_time > Duration::ZERO);
     }
 }
@@ -0,0 +1,144 @@
+//! Push metrics to a Prometheus Pushgateway (`--metrics-endpoint`).
+
+use anyhow::{anyhow, Result};
+use base64::Engine as _;
+use std::time::Duration;
+
+use crate::config::PushgatewayConfig;
+
+const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+const EXPOSITION_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// PUTs a Prometheus text exposition body to
+/// `ENDPOINT/metrics/job/JOB/instance/HOST[/KEY/VALUE...]`.
+///
+/// PUT replaces every metric previously pushed under the same grouping key,
+/// so a re-run of a job never leaves stale series behind.
+pub struct PushgatewayWriter {
+    url: String,
+}
+
+impl PushgatewayWriter {
+    pub fn new(config: &PushgatewayConfig) -> Self {
+        Self {
+            url: push_url(config, &hostname()),
+        }
+    }
+
+    pub fn push(&self, body: &str) -> Result<()> {
+        let agent = ureq::AgentBuilder::new().timeout(PUSH_TIMEOUT).build();
+        match agent
+            .put(&self.url)
+            .set("Content-Type", EXPOSITION_CONTENT_TYPE)
+            .send_string(body)
+        {
+            Ok(_) => Ok(()),
+            Err(ureq::Error::Status(code, response)) => {
+                let detail = response.into_string().unwrap_or_default();
+                Err(anyhow!(
+                    "Pushgateway rejected metrics ({} {}): {}",
+                    code,
+                    self.url,
+                    detail.trim()
+                ))
+            }
+            Err(e) => Err(anyhow!("Failed to push metrics to {}: {}", self.url, e)),
+        }
+    }
+}
+
+/// Build the grouping-key URL. An `instance` grouping label replaces the
+/// hostname; other labels are appended in CLI order.
+fn push_url(config: &PushgatewayConfig, host: &str) -> String {
+    let instance = config
+        .grouping
+        .iter()
+        .rev()
+        .find(|(key, _)| key == "instance")
+        .map(|(_, value)| value.as_str())
+        .unwrap_or(host);
+
+    let mut url = format!("{}/metrics", config.endpoint.trim_end_matches('/'));
+    let labels = [("job", config.job.as_str()), ("instance", instance)]
+        .into_iter()
+        .chain(
+            config
+                .grouping
+                .iter()
+                .filter(|(key, _)| key != "instance")
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+    for (key, value) in labels {
+        push_label(&mut url, key, value);
+    }
+    url
+}
+
+fn push_label(url: &mut String, key: &str, value: &str) {
+    if value.is_empty() || value.contains('/') {
+        // The Pushgateway's escape hatch for values that cannot be a plain
+        // path segment: KEY@base64/<base64url>, with "=" for empty
+        let encoded = if value.is_empty() {
+            "=".to_string()
+        } else {
+            base64::engine::general_purpose::URL_SAFE.encode(value)
+        };
+        url.push_str(&format!("/{}@base64/{}", key, encoded));
+    } else {
+        url.push_str(&format!("/{}/{}", key, urlencoding::encode(value)));
+    }
+}
+
+fn hostname() -> String {
+    #[cfg(unix)]
+    {
+        let mut buf = [0u8; 256];
+        let rc = unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) };
+        if rc == 0 {
+            let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+            if let Ok(name) = std::str::from_utf8(&buf[..end]) {
+                if !name.is_empty() {
+                    return name.to_string();
+                }
+            }
+        }
+    }
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(grouping: &[(&str, &str)]) -> PushgatewayConfig {
+        PushgatewayConfig {
+            endpoint: "http://gw:9091/".to_string(),
+            job: "nightly etl".to_string(),
+            grouping: grouping
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn url_uses_hostname_as_instance() {
+        assert_eq!(
+            push_url(&config(&[]), "build-7"),
+            "http://gw:9091/metrics/job/nightly%20etl/instance/build-7"
+        );
+    }
+
+    #[test]
+    fn url_appends_grouping_and_instance_override() {
+        assert_eq!(
+            push_url(
+                &config(&[("branch", "main"), ("instance", "ci"), ("path", "/var/log")]),
+                "build-7"
+            ),
+            "http://gw:9091/metrics/job/nightly%20etl/instance/ci/branch/main/path@base64/L3Zhci9sb2c="
+        );
+    }
+}
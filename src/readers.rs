@@ -2,12 +2,22 @@ use anyhow::Result;
 use crossbeam_channel::Receiver;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::io::{self, BufRead, BufReader, Read};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::decompression::DecompressionReader;
+use crate::parsers::{
+    NetflowReader, NetflowVersion, PcapReader, SqliteOptions, SqliteReader, XmlRecordsOptions,
+    XmlRecordsReader,
+};
+use crate::run_scope;
 
 // When set, the byte->String boundary aborts on invalid UTF-8 (the historical
 // behavior, restored via `--strict-utf8`). When unset (the default), input is
@@ -26,6 +36,58 @@ static MAX_LINE_BYTES: AtomicUsize = AtomicUsize::new(0);
 // truncate-and-warn recovery. Mirrors the global `--strict` contract.
 static LINE_OVERFLOW_STRICT: AtomicBool = AtomicBool::new(false);
 
+/// Default read buffer per input (256 KiB), tuned for throughput on local disks.
+pub const DEFAULT_READ_BUFFER_SIZE: usize = 256 * 1024;
+
+/// How a run opens its inputs, built from its config and handed to every
+/// reader it creates.
+#[derive(Debug, Clone)]
+pub struct InputOptions {
+    /// Read buffer per input (`--read-buffer`)
+    pub buffer_size: usize,
+    /// Memory-map regular uncompressed files instead of copying them through
+    /// a read buffer (`--mmap`). See [`MmapReader`].
+    pub mmap: bool,
+    /// Binary capture decoded ahead of line splitting (`-f netflow5|netflow9|
+    /// ipfix`); `None` reads inputs as text
+    pub netflow: Option<NetflowVersion>,
+    /// Packet capture decoded to HTTP exchanges ahead of line splitting
+    /// (`-f pcap`)
+    pub pcap: bool,
+    /// XML record export decoded ahead of line splitting (`-f xml-records`),
+    /// with the element names to accept
+    pub xml_records: Option<XmlRecordsOptions>,
+    /// SQLite database read in place of each input file (`-f sqlite`), with
+    /// the table or query to run
+    pub sqlite: Option<SqliteOptions>,
+}
+
+impl InputOptions {
+    pub fn from_config(config: &crate::config::KeloraConfig) -> Self {
+        Self {
+            buffer_size: config.input.read_buffer.max(1),
+            mmap: config.input.mmap,
+            netflow: config.input.format.netflow_version(),
+            pcap: matches!(config.input.format, crate::config::InputFormat::Pcap),
+            xml_records: config.input.format.xml_records_options(),
+            sqlite: config.input.format.sqlite_options(),
+        }
+    }
+}
+
+impl Default for InputOptions {
+    fn default() -> Self {
+        Self {
+            buffer_size: DEFAULT_READ_BUFFER_SIZE,
+            mmap: false,
+            netflow: None,
+            pcap: false,
+            xml_records: None,
+            sqlite: None,
+        }
+    }
+}
+
 /// Select strict (abort-on-invalid) vs. lossy UTF-8 decoding for all line reads.
 /// Set once during pipeline setup; read on every reader thread.
 pub fn set_strict_utf8(enabled: bool) {
@@ -36,6 +98,25 @@ fn strict_utf8() -> bool {
     STRICT_UTF8.load(Ordering::Relaxed)
 }
 
+/// Wrap a (decompressed) input stream in the decoder selected with
+/// `options.netflow`, `options.pcap` or `options.xml_records`, so it yields
+/// one text line per record. Text inputs pass through unchanged.
+pub fn decode_binary_input(
+    reader: Box<dyn Read + Send>,
+    options: &InputOptions,
+) -> Box<dyn Read + Send> {
+    if let Some(version) = options.netflow {
+        return Box::new(NetflowReader::new(reader, version));
+    }
+    if options.pcap {
+        return Box::new(PcapReader::new(reader));
+    }
+    match &options.xml_records {
+        Some(xml_options) => Box::new(XmlRecordsReader::new(reader, xml_options.clone())),
+        None => reader,
+    }
+}
+
 /// Configure the per-line byte cap (`0` = unlimited) and whether exceeding it is
 /// fatal (`strict`) or recovered by truncate-and-warn. Set once during pipeline
 /// setup, before any reader thread is spawned.
@@ -117,10 +198,7 @@ pub(crate) fn read_line_lossy<R: BufRead + ?Sized>(
             // time and there is no overflow.
             if n > 0 && bytes.len() >= max && bytes.last() != Some(&b'\n') {
                 if line_overflow_strict() {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        format!("line exceeds --max-line-bytes ({max} bytes); aborting (--strict)"),
-                    ));
+                    return Err(line_overflow_error(max));
                 }
                 // Resilient default: drop the rest of the over-limit line so the
                 // stream resumes cleanly at the next one, then record a warning.
@@ -134,31 +212,38 @@ pub(crate) fn read_line_lossy<R: BufRead + ?Sized>(
             return Ok(0);
         }
 
-        if strict_utf8() {
-            match std::str::from_utf8(&bytes) {
-                Ok(s) => buf.push_str(s),
-                Err(_) => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        "stream did not contain valid UTF-8",
-                    ));
-                }
-            }
-        } else {
-            match String::from_utf8_lossy(&bytes) {
-                Cow::Borrowed(s) => buf.push_str(s),
-                Cow::Owned(s) => {
-                    // Only allocates when bytes were actually replaced (rare path).
-                    crate::stats::stats_record_decode_warning(&s);
-                    buf.push_str(&s);
-                }
-            }
-        }
-
+        buf.push_str(&decode_line(&bytes)?);
         Ok(n)
     })
 }
 
+fn line_overflow_error(max: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line exceeds --max-line-bytes ({max} bytes); aborting (--strict)"),
+    )
+}
+
+/// Decode one raw line under the strict/lossy UTF-8 policy. Borrows unless
+/// bytes were actually replaced (the rare path, which also records a warning).
+fn decode_line(bytes: &[u8]) -> io::Result<Cow<'_, str>> {
+    if strict_utf8() {
+        match std::str::from_utf8(bytes) {
+            Ok(s) => Ok(Cow::Borrowed(s)),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )),
+        }
+    } else {
+        let decoded = String::from_utf8_lossy(bytes);
+        if let Cow::Owned(ref s) = decoded {
+            crate::stats::stats_record_decode_warning(s);
+        }
+        Ok(decoded)
+    }
+}
+
 /// A reader that can peek at the first line without consuming it
 /// Used for format auto-detection on streams
 ///
@@ -228,6 +313,33 @@ impl<R: BufRead> PeekableLineReader<R> {
         }
     }
 
+    /// Peek up to `max` non-empty lines (line terminators stripped), starting
+    /// with the one returned by `peek_first_non_empty_line`. Like it, nothing
+    /// is consumed: the extra lines are replayed from the buffered prefix.
+    pub fn peek_non_empty_lines(&mut self, max: usize) -> io::Result<Vec<String>> {
+        let mut lines: Vec<String> = String::from_utf8_lossy(self.prefix_remaining())
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+
+        while lines.len() < max {
+            let start = self.buffered_prefix.len();
+            let n = self.inner.read_until(b'\n', &mut self.buffered_prefix)?;
+            if n == 0 {
+                break;
+            }
+            self.saw_any_input = true;
+            let line = String::from_utf8_lossy(&self.buffered_prefix[start..]);
+            if !line.trim().is_empty() {
+                lines.push(line.trim_end_matches(['\r', '\n']).to_string());
+            }
+        }
+
+        lines.truncate(max);
+        Ok(lines)
+    }
+
     pub fn saw_any_input(&self) -> bool {
         self.saw_any_input
     }
@@ -278,7 +390,7 @@ impl ChannelStdinReader {
         let (sender, receiver) = crossbeam_channel::unbounded();
 
         // Spawn a thread to read from stdin using raw bytes
-        thread::spawn(move || {
+        run_scope::spawn(move || {
             let stdin = io::stdin();
             let mut lock = stdin.lock();
             let mut buffer = vec![0u8; 8192]; // 8KB buffer
@@ -377,21 +489,36 @@ pub struct MultiFileReader {
     files: Vec<String>,
     current_file_idx: usize,
     current_reader: Option<Box<dyn BufRead + Send>>,
-    buffer_size: usize,
+    options: InputOptions,
     strict: bool,
 }
 
+/// Open one input (`-` for stdin) for line reading, decompressed and decoded.
+/// Under `--pipeline-record` the lines read from it are also recorded.
 pub fn open_input_reader(
     file_path: &str,
-    buffer_size: usize,
+    options: &InputOptions,
+    strict: bool,
+) -> io::Result<Option<Box<dyn BufRead + Send>>> {
+    let reader = open_untracked_input_reader(file_path, options, strict)?;
+    if reader.is_some() && file_path != "-" {
+        crate::stats::stats_file_opened();
+    }
+    Ok(reader.map(|reader| crate::recording::tee_input(reader, file_path)))
+}
+
+fn open_untracked_input_reader(
+    file_path: &str,
+    options: &InputOptions,
     strict: bool,
 ) -> io::Result<Option<Box<dyn BufRead + Send>>> {
+    let buffer_size = options.buffer_size;
     if file_path == "-" {
         match ChannelStdinReader::new() {
             Ok(stdin_reader) => match crate::decompression::maybe_decompress(stdin_reader) {
                 Ok(processed_reader) => Ok(Some(Box::new(BufReader::with_capacity(
                     buffer_size,
-                    processed_reader,
+                    decode_binary_input(processed_reader, options),
                 )))),
                 Err(e) => {
                     eprintln!(
@@ -446,10 +573,36 @@ pub fn open_input_reader(
             }
         }
 
+        // A database is opened by path, never decompressed or mapped
+        if let Some(sqlite) = &options.sqlite {
+            return match SqliteReader::open(Path::new(file_path), sqlite) {
+                Ok(reader) => Ok(Some(Box::new(BufReader::with_capacity(
+                    buffer_size,
+                    reader,
+                )))),
+                Err(e) => {
+                    let message = crate::config::format_input_open_error(file_path, &e.to_string());
+                    eprintln!("{}", crate::config::format_error_message_auto(&message));
+                    crate::stats::stats_file_open_failed(file_path);
+                    if strict {
+                        Err(io::Error::new(e.kind(), message))
+                    } else {
+                        Ok(None)
+                    }
+                }
+            };
+        }
+
+        if options.mmap {
+            if let Some(reader) = MmapReader::open(file_path, options) {
+                return Ok(Some(Box::new(reader)));
+            }
+        }
+
         match DecompressionReader::new(file_path) {
             Ok(decompressor) => Ok(Some(Box::new(BufReader::with_capacity(
                 buffer_size,
-                decompressor,
+                decode_binary_input(Box::new(decompressor), options),
             )))),
             Err(e) => {
                 eprintln!(
@@ -483,9 +636,9 @@ pub struct FileAwareMultiFileReader {
 }
 
 impl FileAwareMultiFileReader {
-    pub fn new(files: Vec<String>, strict: bool) -> Result<Self> {
+    pub fn new(files: Vec<String>, options: InputOptions, strict: bool) -> Result<Self> {
         Ok(Self {
-            inner: MultiFileReader::new(files, strict)?,
+            inner: MultiFileReader::new(files, options, strict)?,
         })
     }
 }
@@ -517,18 +670,13 @@ impl FileAwareRead for FileAwareMultiFileReader {
 }
 
 impl MultiFileReader {
-    /// Create a new MultiFileReader with default buffer size (256KB for better throughput)
-    pub fn new(files: Vec<String>, strict: bool) -> Result<Self> {
-        Self::with_buffer_size(files, 256 * 1024, strict)
-    }
-
-    /// Create a new MultiFileReader with custom buffer size
-    pub fn with_buffer_size(files: Vec<String>, buffer_size: usize, strict: bool) -> Result<Self> {
+    /// Create a new MultiFileReader opening each file with `options`
+    pub fn new(files: Vec<String>, options: InputOptions, strict: bool) -> Result<Self> {
         Ok(Self {
             files,
             current_file_idx: 0,
             current_reader: None,
-            buffer_size,
+            options,
             strict,
         })
     }
@@ -536,7 +684,7 @@ impl MultiFileReader {
     fn ensure_current_reader(&mut self) -> io::Result<bool> {
         while self.current_reader.is_none() && self.current_file_idx < self.files.len() {
             let file_path = &self.files[self.current_file_idx];
-            match open_input_reader(file_path, self.buffer_size, self.strict)? {
+            match open_input_reader(file_path, &self.options, self.strict)? {
                 Some(reader) => {
                     self.current_reader = Some(reader);
                     return Ok(true);
@@ -634,6 +782,503 @@ impl io::BufRead for MultiFileReader {
     }
 }
 
+/// Reads a regular file through a read-only memory map (`--mmap`).
+///
+/// As a `BufRead` the whole mapping is one buffer, so `read_line_lossy` finds
+/// each newline directly in the page cache with no copy into a read buffer and
+/// no read syscalls; line splitting, the `--max-line-bytes` cap and UTF-8
+/// handling stay exactly those of buffered reads. [`MmapReader::next_line`]
+/// finds lines the same way for the parallel batcher.
+///
+/// Another process can still write to a mapped file, so the mapped bytes may
+/// change while they are read. Every line is therefore copied out of the map
+/// before it is validated as UTF-8, and no `&str` into the map is ever handed
+/// out.
+///
+/// The map covers the file as it was when opened. If more bytes have been
+/// appended by the time the mapping is exhausted, the reader switches to
+/// buffered reads from that point, so a growing file is read to its current
+/// end just like without `--mmap`. Truncating a file while it is mapped is not
+/// supported (the OS faults on access past the new end).
+pub struct MmapReader {
+    map: memmap2::Mmap,
+    pos: usize,
+    file: fs::File,
+    buffer_size: usize,
+    /// Buffered reader over bytes appended after the map was taken
+    tail: Option<BufReader<fs::File>>,
+}
+
+impl MmapReader {
+    /// Map `file_path` if it can take the fast path: a non-empty regular file
+    /// that is neither compressed (or ZIP) nor a decoded capture or XML export, on a 64-bit target
+    /// (where address space for large maps is plentiful). Anything else -
+    /// including open errors, which the buffered path then reports - yields
+    /// `None` so the caller falls back to buffered reads.
+    pub fn open(file_path: &str, options: &InputOptions) -> Option<Self> {
+        if !cfg!(target_pointer_width = "64")
+            || file_path == "-"
+            || options.netflow.is_some()
+            || options.pcap
+            || options.xml_records.is_some()
+            || options.sqlite.is_some()
+        {
+            return None;
+        }
+        // Leave the unsupported-ZIP error to `DecompressionReader`
+        if file_path.to_ascii_lowercase().ends_with(".zip") {
+            return None;
+        }
+        let file = fs::File::open(file_path).ok()?;
+        let metadata = file.metadata().ok()?;
+        if !metadata.is_file() || metadata.len() == 0 {
+            return None;
+        }
+        // SAFETY: the map is read-only. Bytes a concurrent writer changes are
+        // only ever copied out (see `next_mapped_line` and `read_line_lossy`),
+        // never borrowed as `str`. Appends are handled by the tail reader;
+        // truncation is documented as unsupported for --mmap.
+        let map = unsafe { memmap2::Mmap::map(&file) }.ok()?;
+        let is_gzip = map.starts_with(&[0x1F, 0x8B, 0x08]);
+        let is_zstd = map.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]);
+        if is_gzip || is_zstd {
+            return None;
+        }
+        #[cfg(unix)]
+        let _ = map.advise(memmap2::Advice::Sequential);
+        Some(Self {
+            map,
+            pos: 0,
+            file,
+            buffer_size: options.buffer_size,
+            tail: None,
+        })
+    }
+
+    /// When the file has grown past the mapping, continue with buffered reads
+    /// from `offset`. Returns whether the reader switched.
+    fn switch_to_tail_if_grown(&mut self, offset: usize) -> io::Result<bool> {
+        if self.file.metadata()?.len() <= self.map.len() as u64 {
+            return Ok(false);
+        }
+        let mut file = self.file.try_clone()?;
+        file.seek(SeekFrom::Start(offset as u64))?;
+        self.tail = Some(BufReader::with_capacity(self.buffer_size, file));
+        Ok(true)
+    }
+
+    /// Read the next line, including its terminator. Applies the same
+    /// `--max-line-bytes` cap and UTF-8 policy as `read_line_lossy`; returns
+    /// `None` at end of input.
+    pub fn next_line(&mut self) -> io::Result<Option<String>> {
+        if self.tail.is_none() {
+            let rest = &self.map[self.pos..];
+            let newline = rest.iter().position(|&b| b == b'\n');
+            let at_end = rest.is_empty();
+            // A final segment without a newline may continue past the mapping
+            // if the file grew; let the tail reader pick it up whole.
+            if newline.is_some() || !self.switch_to_tail_if_grown(self.pos)? {
+                if at_end {
+                    return Ok(None);
+                }
+                return self.next_mapped_line(newline).map(Some);
+            }
+        }
+
+        let mut line = String::new();
+        let tail = self.tail.as_mut().expect("tail reader set after growth");
+        match read_line_lossy(tail, &mut line)? {
+            0 => Ok(None),
+            _ => Ok(Some(line)),
+        }
+    }
+
+    /// Copy the line starting at `pos` (ending after `newline`, if any) out of
+    /// the mapping
+    fn next_mapped_line(&mut self, newline: Option<usize>) -> io::Result<String> {
+        let start = self.pos;
+        let consumed = newline.map_or(self.map.len() - start, |i| i + 1);
+        let max = MAX_LINE_BYTES.load(Ordering::Relaxed);
+        // Mirror the bounded `read_until` in `read_line_lossy`: keep at most
+        // `max` bytes, and treat hitting the cap without a newline as overflow.
+        let kept = if max == 0 {
+            consumed
+        } else {
+            consumed.min(max)
+        };
+        if max != 0 && kept >= max && self.map[start + kept - 1] != b'\n' {
+            if line_overflow_strict() {
+                return Err(line_overflow_error(max));
+            }
+            crate::stats::stats_record_line_truncation(max);
+        }
+        self.pos += consumed;
+        crate::stats::stats_add_input_bytes(consumed, consumed);
+
+        // Validate the copy, not the mapping, which may change underneath us
+        match String::from_utf8(self.map[start..start + kept].to_vec()) {
+            Ok(line) => Ok(line),
+            Err(e) => decode_line(e.as_bytes()).map(Cow::into_owned),
+        }
+    }
+}
+
+impl io::Read for MmapReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = {
+            let available = self.fill_buf()?;
+            let n = available.len().min(buf.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            n
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl io::BufRead for MmapReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.tail.is_none() && self.pos >= self.map.len() {
+            self.switch_to_tail_if_grown(self.map.len())?;
+        }
+        match self.tail {
+            Some(ref mut tail) => tail.fill_buf(),
+            None => Ok(&self.map[self.pos..]),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match self.tail {
+            Some(ref mut tail) => tail.consume(amt),
+            None => {
+                self.pos += amt;
+                crate::stats::stats_add_input_bytes(amt, amt);
+            }
+        }
+    }
+}
+
+/// Follows every file matching a glob pattern, `tail -F` style across
+/// rotation by new file name.
+///
+/// Each poll re-expands the pattern, so files created after startup are picked
+/// up on the next poll. Per-file byte offsets make each poll resume where the
+/// previous one stopped; only complete (`\n`-terminated) lines are returned,
+/// so a line still being written is picked up once its newline lands. A file
+/// that shrinks below its offset (copytruncate rotation) is re-read from the
+/// start. Files are visited oldest-first by modification time, so the
+/// currently active file comes last.
+pub struct GlobFollower {
+    pattern: String,
+    offsets: HashMap<PathBuf, u64>,
+}
+
+impl GlobFollower {
+    pub fn new(pattern: &str) -> Result<Self> {
+        glob::Pattern::new(pattern)
+            .map_err(|e| anyhow::anyhow!("invalid --follow-glob pattern '{}': {}", pattern, e))?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            offsets: HashMap::new(),
+        })
+    }
+
+    /// Files currently matching the pattern, oldest modification time first.
+    pub fn matching_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<(std::time::SystemTime, PathBuf)> = match glob::glob(&self.pattern) {
+            Ok(paths) => paths
+                .filter_map(|entry| entry.ok())
+                .filter_map(|path| {
+                    let meta = fs::metadata(&path).ok()?;
+                    if !meta.is_file() {
+                        return None;
+                    }
+                    let mtime = meta.modified().unwrap_or(std::time::UNIX_EPOCH);
+                    Some((mtime, path))
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        files.sort();
+        files.into_iter().map(|(_, path)| path).collect()
+    }
+
+    /// Read all complete lines appended since the last poll, calling `emit`
+    /// with each line (newline stripped) and its file name. Stops early and
+    /// returns `Ok(false)` if `emit` returns false.
+    pub fn poll<F>(&mut self, mut emit: F) -> io::Result<bool>
+    where
+        F: FnMut(String, &str) -> bool,
+    {
+        let files = self.matching_files();
+        // Forget files that rotated away so a later file reusing the name
+        // starts from the beginning.
+        self.offsets.retain(|path, _| files.contains(path));
+
+        for path in files {
+            let Ok(file) = fs::File::open(&path) else {
+                continue;
+            };
+            let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+            let offset = self.offsets.entry(path.clone()).or_insert(0);
+            if len < *offset {
+                *offset = 0;
+            }
+            if len == *offset {
+                continue;
+            }
+
+            let filename = path.to_string_lossy().to_string();
+            let start = *offset;
+            let mut reader = BufReader::new(file);
+            reader.seek(io::SeekFrom::Start(start))?;
+            let mut buffer = String::new();
+            let mut keep_going = true;
+            loop {
+                buffer.clear();
+                if read_line_lossy(&mut reader, &mut buffer)? == 0 || !buffer.ends_with('\n') {
+                    // EOF, or a partial line still being written: leave the
+                    // offset before it and retry on the next poll.
+                    break;
+                }
+                *offset = reader.stream_position()?;
+                let line = buffer.trim_end_matches(&['\n', '\r'][..]).to_string();
+                if !emit(line, &filename) {
+                    keep_going = false;
+                    break;
+                }
+            }
+            let consumed = (*offset - start) as usize;
+            crate::stats::stats_add_input_bytes(consumed, consumed);
+            if !keep_going {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// A line one of the `--input-cmd` commands wrote to stdout
+pub struct CommandLine {
+    pub line: String,
+    pub command: String,
+}
+
+/// Delay before the first restart of an exited `--input-cmd` command
+const INPUT_CMD_MIN_BACKOFF: Duration = Duration::from_millis(250);
+/// Longest delay between restarts of a command that keeps exiting
+const INPUT_CMD_MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// A command that ran at least this long restarts after the shortest delay
+const INPUT_CMD_HEALTHY_RUN: Duration = Duration::from_secs(60);
+
+/// Input read from the stdout of shell commands (`--input-cmd`).
+///
+/// Every command runs through the platform shell on its own thread, with
+/// stdin closed. Stdout lines from all commands arrive on one channel in the
+/// order they were written; stderr lines are shown as warnings. With
+/// `follow`, a command that exits is started again after a delay that
+/// doubles on every quick exit. The channel disconnects once every command
+/// has finished for good.
+pub struct CommandInputs {
+    lines: Receiver<CommandLine>,
+    peeked: VecDeque<CommandLine>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl CommandInputs {
+    pub fn spawn(commands: &[String], follow: bool, show_warnings: bool) -> Self {
+        let (sender, lines) = crossbeam_channel::bounded(1024);
+        let shutdown = Arc::new(AtomicBool::new(false));
+        for command in commands {
+            let command = command.clone();
+            let sender = sender.clone();
+            let shutdown = shutdown.clone();
+            run_scope::spawn(move || {
+                run_input_command(&command, follow, show_warnings, &sender, &shutdown)
+            });
+        }
+        Self {
+            lines,
+            peeked: VecDeque::new(),
+            shutdown,
+        }
+    }
+
+    /// First non-empty line, without consuming it. Blocks until a command
+    /// writes one; `None` when every command finished without output.
+    pub fn peek_first_non_empty_line(&mut self) -> Option<&str> {
+        if let Some(index) = self.peeked.iter().position(|p| !p.line.trim().is_empty()) {
+            return Some(self.peeked[index].line.as_str());
+        }
+        loop {
+            let next = self.lines.recv().ok()?;
+            let blank = next.line.trim().is_empty();
+            self.peeked.push_back(next);
+            if !blank {
+                return self.peeked.back().map(|peeked| peeked.line.as_str());
+            }
+        }
+    }
+
+    /// Next line from any command, waiting at most `timeout`
+    pub fn recv_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<CommandLine, crossbeam_channel::RecvTimeoutError> {
+        match self.peeked.pop_front() {
+            Some(line) => Ok(line),
+            None => self.lines.recv_timeout(timeout),
+        }
+    }
+
+    /// Stop restarting commands and interrupt the ones still running
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        crate::platform::ProcessCleanup::interrupt_children();
+    }
+}
+
+fn shell_command(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    }
+    #[cfg(not(windows))]
+    {
+        use std::os::unix::process::CommandExt;
+
+        // Lead a process group of its own, so interrupting the command also
+        // reaches whatever the shell started from it
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command).process_group(0);
+        shell
+    }
+}
+
+fn warn_input_cmd(show_warnings: bool, command: &str, message: &str) {
+    if show_warnings {
+        eprintln!(
+            "{}",
+            crate::config::format_warning_message_auto(&format!(
+                "input-cmd '{}': {}",
+                command, message
+            ))
+        );
+    }
+}
+
+/// Run `command` until it finishes for good: once, or until shutdown when
+/// following.
+fn run_input_command(
+    command: &str,
+    follow: bool,
+    show_warnings: bool,
+    sender: &crossbeam_channel::Sender<CommandLine>,
+    shutdown: &AtomicBool,
+) {
+    let mut backoff = INPUT_CMD_MIN_BACKOFF;
+    loop {
+        let started = Instant::now();
+        let (outcome, failed) = match run_input_command_once(command, show_warnings, sender) {
+            Ok(Some(status)) => (format!("exited ({})", status), !status.success()),
+            // Nobody is reading any more (e.g. --take reached)
+            Ok(None) => return,
+            Err(e) => (format!("could not be started: {}", e), true),
+        };
+        if shutdown.load(Ordering::SeqCst) {
+            return;
+        }
+        if !follow {
+            if failed {
+                warn_input_cmd(show_warnings, command, &outcome);
+            }
+            return;
+        }
+
+        if started.elapsed() >= INPUT_CMD_HEALTHY_RUN {
+            backoff = INPUT_CMD_MIN_BACKOFF;
+        }
+        warn_input_cmd(
+            show_warnings,
+            command,
+            &format!("{}; restarting in {}ms", outcome, backoff.as_millis()),
+        );
+        let resume_at = Instant::now() + backoff;
+        while Instant::now() < resume_at {
+            if shutdown.load(Ordering::SeqCst) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(50).min(backoff));
+        }
+        backoff = (backoff * 2).min(INPUT_CMD_MAX_BACKOFF);
+    }
+}
+
+/// Run `command` once, forwarding its output. Returns `None` when the
+/// receiving side went away before the command finished.
+fn run_input_command_once(
+    command: &str,
+    show_warnings: bool,
+    sender: &crossbeam_channel::Sender<CommandLine>,
+) -> io::Result<Option<ExitStatus>> {
+    let mut child = shell_command(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let pid = child.id();
+    crate::platform::ProcessCleanup::register_child(pid);
+
+    let stderr_forwarder = child.stderr.take().map(|stderr| {
+        let command = command.to_string();
+        run_scope::spawn(move || {
+            let mut reader = BufReader::new(stderr);
+            let mut buffer = String::new();
+            while matches!(read_line_lossy(&mut reader, &mut buffer), Ok(n) if n > 0) {
+                crate::stats::stats_record_input_cmd_stderr();
+                warn_input_cmd(
+                    show_warnings,
+                    &command,
+                    buffer.trim_end_matches(&['\n', '\r'][..]),
+                );
+                buffer.clear();
+            }
+        })
+    });
+
+    let mut receiver_gone = false;
+    if let Some(stdout) = child.stdout.take() {
+        let mut reader = BufReader::new(stdout);
+        let mut buffer = String::new();
+        while let Ok(n) = read_line_lossy(&mut reader, &mut buffer) {
+            if n == 0 {
+                break;
+            }
+            crate::stats::stats_add_input_bytes(n, n);
+            let line = CommandLine {
+                line: buffer.trim_end_matches(&['\n', '\r'][..]).to_string(),
+                command: command.to_string(),
+            };
+            buffer.clear();
+            if sender.send(line).is_err() {
+                receiver_gone = true;
+                crate::platform::ProcessCleanup::interrupt_children();
+                break;
+            }
+        }
+    }
+
+    let status = child.wait();
+    crate::platform::ProcessCleanup::unregister_child(pid);
+    if let Some(handle) = stderr_forwarder {
+        let _ = handle.join();
+    }
+    let status = status?;
+    Ok((!receiver_gone).then_some(status))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -649,7 +1294,7 @@ mod tests {
         temp_file.flush()?;
 
         let files = vec![temp_file.path().to_string_lossy().to_string()];
-        let mut reader = MultiFileReader::new(files, false)?;
+        let mut reader = MultiFileReader::new(files, InputOptions::default(), false)?;
 
         let mut line = String::new();
 
@@ -691,7 +1336,7 @@ mod tests {
             temp_file1.path().to_string_lossy().to_string(),
             temp_file2.path().to_string_lossy().to_string(),
         ];
-        let mut reader = MultiFileReader::new(files, false)?;
+        let mut reader = MultiFileReader::new(files, InputOptions::default(), false)?;
 
         let mut all_content = String::new();
         reader.read_to_string(&mut all_content)?;
@@ -700,4 +1345,63 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_glob_follower_picks_up_new_files_and_appends() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let pattern = dir.path().join("app-*.log").to_string_lossy().to_string();
+        let first = dir.path().join("app-1.log");
+        fs::write(&first, "a1\na2\n")?;
+        fs::write(dir.path().join("other.log"), "ignored\n")?;
+
+        let mut follower = GlobFollower::new(&pattern)?;
+        let mut seen = Vec::new();
+        follower.poll(|line, _| {
+            seen.push(line);
+            true
+        })?;
+        assert_eq!(seen, vec!["a1", "a2"]);
+
+        // A partial line is held back until its newline arrives
+        let mut file = fs::OpenOptions::new().append(true).open(&first)?;
+        write!(file, "a3")?;
+        file.flush()?;
+        seen.clear();
+        follower.poll(|line, _| {
+            seen.push(line);
+            true
+        })?;
+        assert!(seen.is_empty());
+        writeln!(file)?;
+        fs::write(dir.path().join("app-2.log"), "b1\n")?;
+
+        let mut files = Vec::new();
+        follower.poll(|line, name| {
+            seen.push(line);
+            files.push(name.to_string());
+            true
+        })?;
+        assert_eq!(seen, vec!["a3", "b1"]);
+        assert!(files[1].ends_with("app-2.log"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_glob_follower_rereads_truncated_file() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("app.log");
+        fs::write(&path, "old1\nold2\n")?;
+
+        let mut follower = GlobFollower::new(&path.to_string_lossy())?;
+        follower.poll(|_, _| true)?;
+
+        fs::write(&path, "new\n")?;
+        let mut seen = Vec::new();
+        follower.poll(|line, _| {
+            seen.push(line);
+            true
+        })?;
+        assert_eq!(seen, vec!["new"]);
+        Ok(())
+    }
 }
@@ -0,0 +1,285 @@
+//! Recording and replaying pipeline input (`--pipeline-record`,
+//! `--pipeline-replay`).
+//!
+//! A recording is a gzip-compressed text file holding every raw input line in
+//! the order the pipeline read it. A `# FILE: <path>` header line precedes the
+//! lines of each input (`-` for stdin), so a multi-file run replays with the
+//! same filenames. Recorded lines that start with `#` get one more `#`, which
+//! keeps them apart from the headers; other lines starting with `#` are
+//! comments and ignored on replay.
+
+use anyhow::{Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::decompression::DecompressionReader;
+
+/// Header line marking the input the following lines came from
+const FILE_HEADER: &str = "# FILE: ";
+
+/// Source name recorded for stdin
+const STDIN_SOURCE: &str = "-";
+
+// Recording opened by `--pipeline-record`. Set once during pipeline setup and
+// fed by every `TeeReader`.
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+struct Recorder {
+    out: GzEncoder<BufWriter<File>>,
+    /// Input whose lines are being written
+    source: Option<String>,
+    /// Bytes of a line whose end has not been read yet
+    partial: Vec<u8>,
+}
+
+impl Recorder {
+    fn record(&mut self, source: &str, bytes: &[u8]) -> io::Result<()> {
+        if self.source.as_deref() != Some(source) {
+            self.end_partial_line()?;
+            writeln!(self.out, "{}{}", FILE_HEADER, source)?;
+            self.source = Some(source.to_string());
+        }
+        for chunk in bytes.split_inclusive(|&b| b == b'\n') {
+            self.partial.extend_from_slice(chunk);
+            if chunk.ends_with(b"\n") {
+                self.write_line()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write out a final line that had no terminator
+    fn end_partial_line(&mut self) -> io::Result<()> {
+        if self.partial.is_empty() {
+            return Ok(());
+        }
+        self.partial.push(b'\n');
+        self.write_line()
+    }
+
+    fn write_line(&mut self) -> io::Result<()> {
+        if self.partial.starts_with(b"#") {
+            self.out.write_all(b"#")?;
+        }
+        self.out.write_all(&self.partial)?;
+        self.partial.clear();
+        Ok(())
+    }
+}
+
+fn recorder() -> std::sync::MutexGuard<'static, Option<Recorder>> {
+    RECORDER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Start recording every input opened from now on to `path` (`None` stops
+/// recording without writing anything).
+pub fn start_recording(path: Option<&Path>) -> Result<()> {
+    let recorder_state = match path {
+        Some(path) => {
+            let file = File::create(path).with_context(|| {
+                format!("Failed to create pipeline recording '{}'", path.display())
+            })?;
+            Some(Recorder {
+                out: GzEncoder::new(BufWriter::new(file), Compression::default()),
+                source: None,
+                partial: Vec::new(),
+            })
+        }
+        None => None,
+    };
+    *recorder() = recorder_state;
+    Ok(())
+}
+
+/// Complete the recording started with [`start_recording`], if any.
+pub fn finish_recording() -> Result<()> {
+    let Some(mut recording) = recorder().take() else {
+        return Ok(());
+    };
+    recording.end_partial_line()?;
+    recording
+        .out
+        .finish()
+        .and_then(|mut writer| writer.flush())
+        .context("Failed to write pipeline recording")?;
+    Ok(())
+}
+
+/// Wrap a freshly opened input so the lines read from it are also recorded,
+/// when a recording is active. `source` is the input's path, or `-` for stdin.
+pub fn tee_input(reader: Box<dyn BufRead + Send>, source: &str) -> Box<dyn BufRead + Send> {
+    if recorder().is_none() {
+        return reader;
+    }
+    Box::new(TeeReader {
+        inner: reader,
+        source: source.to_string(),
+    })
+}
+
+/// Wrap stdin for recording; see [`tee_input`].
+pub fn tee_stdin(reader: Box<dyn BufRead + Send>) -> Box<dyn BufRead + Send> {
+    tee_input(reader, STDIN_SOURCE)
+}
+
+/// Reader that copies the bytes consumed from `inner` into the recording.
+/// Bytes are recorded as they are consumed, so data buffered by a wrapping
+/// reader is still recorded exactly once.
+pub struct TeeReader<R> {
+    inner: R,
+    source: String,
+}
+
+impl<R: BufRead> Read for TeeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for TeeReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if amt > 0 {
+            if let Ok(available) = self.inner.fill_buf() {
+                let consumed = &available[..amt.min(available.len())];
+                if let Some(recording) = recorder().as_mut() {
+                    if let Err(e) = recording.record(&self.source, consumed) {
+                        eprintln!(
+                            "{}",
+                            crate::config::format_error_message_auto(&format!(
+                                "Failed to write pipeline recording: {}",
+                                e
+                            ))
+                        );
+                    }
+                }
+            }
+        }
+        self.inner.consume(amt)
+    }
+}
+
+/// Reads the lines of a recording back, tracking which input each came from.
+pub struct ReplayReader {
+    inner: DecompressionReader,
+    source: Option<String>,
+    /// Data bytes handed out through `Read` but not consumed yet
+    pending: Vec<u8>,
+    position: usize,
+}
+
+impl ReplayReader {
+    /// Open a recording written by `--pipeline-record`. Uncompressed
+    /// recordings are accepted too.
+    pub fn open(path: &Path) -> Result<Self> {
+        let inner = DecompressionReader::new_uncounted(path)
+            .with_context(|| format!("Failed to open pipeline recording '{}'", path.display()))?;
+        Ok(Self {
+            inner,
+            source: None,
+            pending: Vec::new(),
+            position: 0,
+        })
+    }
+
+    /// Read the next recorded line, terminator included, into `buf`. Returns
+    /// 0 at the end of the recording.
+    pub fn read_record(&mut self, buf: &mut String) -> io::Result<usize> {
+        loop {
+            buf.clear();
+            if crate::readers::read_line_lossy(&mut self.inner, buf)? == 0 {
+                return Ok(0);
+            }
+            if let Some(source) = buf.strip_prefix(FILE_HEADER) {
+                self.source = Some(source.trim_end_matches(&['\r', '\n'][..]).to_string());
+                continue;
+            }
+            if buf.starts_with("##") {
+                buf.remove(0);
+            } else if buf.starts_with('#') {
+                continue;
+            }
+            return Ok(buf.len());
+        }
+    }
+
+    /// Input the most recently read line came from (`None` for stdin)
+    pub fn current_filename(&self) -> Option<&str> {
+        self.source
+            .as_deref()
+            .filter(|source| *source != STDIN_SOURCE)
+    }
+}
+
+/// The recorded lines as one text stream, without headers (used for format
+/// detection)
+impl Read for ReplayReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position >= self.pending.len() {
+            let mut line = String::new();
+            if self.read_record(&mut line)? == 0 {
+                return Ok(0);
+            }
+            self.pending = line.into_bytes();
+            self.position = 0;
+        }
+        let available = &self.pending[self.position..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_recording_round_trip_keeps_sources_and_hash_lines() {
+        let file = NamedTempFile::new().unwrap();
+        start_recording(Some(file.path())).unwrap();
+
+        let mut first = tee_input(
+            Box::new(io::Cursor::new(b"a=1\n# not a header\n".to_vec())),
+            "one.log",
+        );
+        let mut second = tee_input(Box::new(io::Cursor::new(b"b=2".to_vec())), "two.log");
+        let mut stdin = tee_stdin(Box::new(io::Cursor::new(b"c=3\n".to_vec())));
+        for reader in [&mut first, &mut second, &mut stdin] {
+            let mut sink = String::new();
+            reader.read_to_string(&mut sink).unwrap();
+        }
+        finish_recording().unwrap();
+
+        let mut replay = ReplayReader::open(file.path()).unwrap();
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        while replay.read_record(&mut line).unwrap() > 0 {
+            lines.push((line.clone(), replay.current_filename().map(str::to_string)));
+        }
+        assert_eq!(
+            lines,
+            vec![
+                ("a=1\n".to_string(), Some("one.log".to_string())),
+                ("# not a header\n".to_string(), Some("one.log".to_string())),
+                ("b=2\n".to_string(), Some("two.log".to_string())),
+                ("c=3\n".to_string(), None),
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::event::json_to_dynamic;
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use lru::LruCache;
 use regex::Regex;
 use rhai::{Dynamic, Engine, EvalAltResult, ImmutableString, Map};
@@ -15,6 +16,34 @@ thread_local! {
     ));
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    ("absorb_json", &[r#"let res = e.absorb_json("payload");"#]),
+    ("absorb_jwt", &[r#"let res = e.absorb_jwt("token");"#]),
+    (
+        "absorb_kv",
+        &[r#"let res = e.absorb_kv("msg", #{ sep: ",", kv_sep: "=", keep_source: true });"#],
+    ),
+    ("absorb_logfmt", &[r#"let res = e.absorb_logfmt("msg");"#]),
+    (
+        "absorb_regex",
+        &[
+            r##"let res = e.absorb_regex("msg", #"User (?P<user>\w+) logged in from (?P<ip>[\d.]+)"#);"##,
+            r#"e.absorb_regex("line", pattern);"#,
+            r#"e.absorb_regex("line", apache_pattern);"#,
+        ],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(Section::Events, &["e.absorb_kv(field [,options])"], "Parse key=value tokens from field, merge pairs, return status map (NOT quote-aware; use absorb_logfmt for quoted values)"),
+    FunctionDoc::new(Section::Events, &["e.absorb_logfmt(field [,options])"], "Parse logfmt (quote-aware, typed) from field, merge keys, return status map"),
+    FunctionDoc::new(Section::Events, &["e.absorb_json(field [,options])"], "Parse JSON object from field, merge keys, return status map"),
+    FunctionDoc::new(Section::Events, &["e.absorb_jwt(field [,options])"], "Parse JWT (no verification), merge claims from field, return status map"),
+    FunctionDoc::new(Section::Events, &["e.absorb_regex(field, pattern [,opts])"], "Extract named captures from field using regex, return status map"),
+];
+
 pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("absorb_kv", absorb_kv_default);
     engine.register_fn("absorb_kv", absorb_kv_with_options);
@@ -1,6 +1,142 @@
 use crate::event::{flatten_dynamic, FlattenStyle};
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use indexmap::IndexMap;
-use rhai::{Array, Dynamic, Engine, Map};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Position};
+
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "chunk_events",
+        &[r#"e.batches = e.ids.chunk_events(3)  // [1, 2, 3, 4, 5] → [[1, 2, 3], [4, 5]]"#],
+    ),
+    (
+        "flattened",
+        &[
+            r#"e.flat = [[1, 2], [3, 4]].flattened()  // Returns flat map"#,
+            r#"e.fields = e.nested.flattened("dot", 2)  // Flatten to dot notation"#,
+        ],
+    ),
+    ("max", &[r#"e.max_score = e.scores.max()"#]),
+    ("min", &[r#"e.min_time = e.times.min()"#]),
+    (
+        "pluck",
+        &[
+            r#"let statuses = events.pluck("status")  // [200, 404, 200]"#,
+            r#"let codes = events.pluck("status")"#,
+        ],
+    ),
+    (
+        "pluck_as_nums",
+        &[
+            r#"let times = events.pluck_as_nums("time")  // [1.5, 0.3, 2.1] (converted to numbers)"#,
+            r#"let times = events.pluck_as_nums("response_time")"#,
+            r#"let recent_times = window.pluck_as_nums("response_time")"#,
+        ],
+    ),
+    ("reversed", &[r#"e.reversed = reversed(e.items)"#]),
+    (
+        "slice",
+        &[
+            r#"e.first_three = e.code.slice(":3")  // "ABCDEF" → "ABC""#,
+            r#"e.last_two = e.code.slice("-2:")  // "ABCDEF" → "EF""#,
+            r#"e.middle = e.code.slice("2:5")  // "ABCDEF" → "CDE""#,
+        ],
+    ),
+    (
+        "sorted",
+        &[
+            r#"e.sorted_scores = sorted(e.scores)  // [3, 1, 2] → [1, 2, 3]"#,
+            r#"e.sorted_names = sorted(e.names)  // Alphabetical"#,
+        ],
+    ),
+    (
+        "sorted_by",
+        &[r#"let sorted_users = sorted_by(e.users, "age")"#],
+    ),
+    (
+        "unique",
+        &[r#"e.unique_tags = unique(e.tags)  // [1, 2, 1, 3] → [1, 2, 3]"#],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.chunk_events(size)"],
+        "Split array into batches of size items (last batch holds the rest; alias chunk_by_size)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.contains_any(search_array)"],
+        "Check if array contains any search values",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.flattened([style [,max_depth]])"],
+        "Return new flattened map from nested arrays/objects",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.pluck(field)"],
+        "Extract field from each map/object in array (skips missing/() values)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.pluck_as_nums(field)"],
+        "Extract field as f64 from each map in array (skips invalid/missing)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.max()"],
+        "Find maximum value in array (rejects mixed types; no auto string-to-number coercion)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.min()"],
+        "Find minimum value in array (rejects mixed types; no auto string-to-number coercion)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.reversed()"],
+        "Return new array in reverse order",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.slice(spec)"],
+        r#"Slice array using Python notation (e.g., "1:5", ":3", "-2:")"#,
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.sorted_by(field)"],
+        "Sort array of objects by field name",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.sorted()"],
+        "Return new sorted array (numeric/lexicographic)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.starts_with_any(search_array)"],
+        "Check if array starts with any search values",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.unique()"],
+        "Remove all duplicate elements (preserves first occurrence)",
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["window.pluck(field)"],
+        "Extract field values from window array (requires --window)",
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["window.pluck_as_nums(field)"],
+        "Extract numeric field values from window array (requires --window)",
+    ),
+];
 
 /// Register array manipulation functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
@@ -25,6 +161,10 @@ pub fn register_functions(engine: &mut Engine) {
     // Register starts_with_any function - check if array starts with any of the specified values
     engine.register_fn("starts_with_any", starts_with_any_array);
 
+    // Register chunk_events function - split array into fixed-size batches
+    engine.register_fn("chunk_events", chunk_events);
+    engine.register_fn("chunk_by_size", chunk_events);
+
     // Register field extraction helpers for arrays of maps
     engine.register_fn("pluck", pluck);
     engine.register_fn("pluck_as_nums", pluck_as_nums);
@@ -73,6 +213,23 @@ pub fn register_functions(engine: &mut Engine) {
     );
 }
 
+/// Split an array into consecutive batches of `size` items.
+///
+/// The last batch holds the remainder and may be shorter; an empty array yields
+/// no batches.
+fn chunk_events(arr: Array, size: i64) -> Result<Array, Box<EvalAltResult>> {
+    if size <= 0 {
+        return Err(Box::new(EvalAltResult::ErrorRuntime(
+            format!("chunk_events: size must be positive, got {size}").into(),
+            Position::NONE,
+        )));
+    }
+    Ok(arr
+        .chunks(size as usize)
+        .map(|chunk| Dynamic::from_array(chunk.to_vec()))
+        .collect())
+}
+
 /// Extract a field from an array of maps, preserving original value types
 fn pluck(array: Array, field_name: String) -> Array {
     let mut results = Array::new();
@@ -1770,4 +1927,26 @@ mod tests {
         let zero_step = slice_array(arr, "0:2:0");
         assert!(zero_step.is_empty());
     }
+
+    #[test]
+    fn test_chunk_events_keeps_remainder_in_last_batch() {
+        let events: Array = (1..=10i64).map(Dynamic::from).collect();
+        let chunks = chunk_events(events, 3).unwrap();
+
+        let sizes: Vec<usize> = chunks
+            .iter()
+            .map(|chunk| chunk.clone().into_array().unwrap().len())
+            .collect();
+        assert_eq!(sizes, vec![3, 3, 3, 1]);
+
+        let last = chunks[3].clone().into_array().unwrap();
+        assert_eq!(last[0].as_int().unwrap(), 10);
+    }
+
+    #[test]
+    fn test_chunk_events_rejects_non_positive_size() {
+        assert!(chunk_events(Array::new(), 0).is_err());
+        assert!(chunk_events(Array::new(), -2).is_err());
+        assert!(chunk_events(Array::new(), 5).unwrap().is_empty());
+    }
 }
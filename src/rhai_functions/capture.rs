@@ -2,9 +2,11 @@
 //!
 //! This module provides infrastructure for capturing print/eprint output
 //! in parallel processing mode, where output needs to be buffered and
-//! ordered correctly.
+//! ordered correctly. It also holds the handlers that script output is
+//! finally written through: stdout/stderr unless a run installs its own.
 
 use std::cell::RefCell;
+use std::sync::Arc;
 
 /// Represents a captured message with its target stream
 #[derive(Debug, Clone)]
@@ -13,7 +15,15 @@ pub enum CapturedMessage {
     Stderr(String),
 }
 
+/// Where script `print()`/`eprint()` output goes once it is written out
+#[derive(Clone)]
+pub struct ScriptOutput {
+    pub print: Arc<dyn Fn(&str) + Send + Sync>,
+    pub eprint: Arc<dyn Fn(&str) + Send + Sync>,
+}
+
 thread_local! {
+    static SCRIPT_OUTPUT: RefCell<Option<ScriptOutput>> = const { RefCell::new(None) };
     static CAPTURED_PRINTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
     static CAPTURED_EPRINTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
     static CAPTURED_MESSAGES: RefCell<Vec<CapturedMessage>> = const { RefCell::new(Vec::new()) };
@@ -21,6 +31,33 @@ thread_local! {
     static SUPPRESS_SIDE_EFFECTS: RefCell<bool> = const { RefCell::new(false) };
 }
 
+/// Install this thread's script output handlers, returning the previous ones.
+/// `None` restores the default of writing to stdout/stderr.
+pub fn set_script_output(output: Option<ScriptOutput>) -> Option<ScriptOutput> {
+    SCRIPT_OUTPUT.with(|current| std::mem::replace(&mut *current.borrow_mut(), output))
+}
+
+/// This thread's script output handlers, for handing on to another thread
+pub fn script_output() -> Option<ScriptOutput> {
+    SCRIPT_OUTPUT.with(|current| current.borrow().clone())
+}
+
+/// Write a script `print()` line
+pub fn emit_print(message: &str) {
+    match script_output() {
+        Some(output) => (output.print)(message),
+        None => println!("{}", message),
+    }
+}
+
+/// Write a script `eprint()` line
+pub fn emit_eprint(message: &str) {
+    match script_output() {
+        Some(output) => (output.eprint)(message),
+        None => eprintln!("{}", message),
+    }
+}
+
 /// Capture a print statement in thread-local storage for parallel processing
 pub fn capture_print(message: String) {
     CAPTURED_PRINTS.with(|prints| {
@@ -146,6 +183,24 @@ mod tests {
         assert!(matches!(&messages[2], CapturedMessage::Stdout(s) if s == "out2"));
     }
 
+    #[test]
+    fn test_script_output_handlers() {
+        use std::sync::Mutex;
+
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let (out, err) = (lines.clone(), lines.clone());
+        let previous = set_script_output(Some(ScriptOutput {
+            print: Arc::new(move |msg| out.lock().unwrap().push(format!("out:{}", msg))),
+            eprint: Arc::new(move |msg| err.lock().unwrap().push(format!("err:{}", msg))),
+        }));
+        emit_print("a");
+        emit_eprint("b");
+        set_script_output(previous);
+
+        assert_eq!(*lines.lock().unwrap(), vec!["out:a", "err:b"]);
+        assert!(script_output().is_none());
+    }
+
     #[test]
     fn test_parallel_mode() {
         set_parallel_mode(false);
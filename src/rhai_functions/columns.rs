@@ -1,3 +1,4 @@
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use rhai::{Array, Dynamic, Engine, Map};
 use std::cell::Cell;
 
@@ -14,6 +15,63 @@ fn is_parse_cols_strict() -> bool {
     PARSE_COLS_STRICT.with(|flag| flag.get())
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "col",
+        &[
+            r#"e.first = e.line.col("0")  // First column (0-indexed)"#,
+            r#"e.cols = e.line.col("0,2,4")  // Columns 0, 2, 4"#,
+            r#"e.range = e.line.col("1:4", "\t")  // Columns 1-3, tab-separated"#,
+        ],
+    ),
+    (
+        "cols",
+        &[
+            r#"let values = e.line.cols(0, 2, 4)  // ["value0", "value2", "value4"]"#,
+            r#"let data = e.line.cols(1, 3, "\t")  // Tab-separated columns"#,
+            r#"let parts = e.log.cols(0, 3, 6, 8)  // IP, timestamp, path, status"#,
+        ],
+    ),
+    (
+        "status_class",
+        &[
+            r#"e.status_category = status_class(e.status)  // 404 → "4xx", 200 → "2xx""#,
+            r#"e.is_error = status_class(e.code) == "5xx""#,
+            r#"track_freq("status_class", status_class(e.status))"#,
+        ],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.col(spec [,separator])"],
+        "Extract columns by index/range/list (e.g., '1', '1,3,5', '1:4')",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.cols(col1, col2 [,...] [,sep])"],
+        "Extract multiple columns as array (up to 6 columns)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.parse_cols(spec [,sep])"],
+        "Parse columns according to spec",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.parse_cols(spec [,sep])"],
+        "Apply column spec to pre-split values",
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["status_class(status_code)"],
+        r#"Convert HTTP status code to class string ("2xx", "4xx", etc.)"#,
+    ),
+];
+
 pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("parse_cols", parse_cols_whitespace);
     engine.register_fn("parse_cols", parse_cols_with_sep);
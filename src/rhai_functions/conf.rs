@@ -1,4 +1,6 @@
-use rhai::{Dynamic, Engine};
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
+use crate::rhai_functions::safety::require_unsandboxed;
+use rhai::{Dynamic, Engine, NativeCallContext};
 use std::fs;
 use std::sync::RwLock;
 
@@ -72,10 +74,44 @@ fn read_lines_impl(path: String) -> Result<rhai::Array, Box<rhai::EvalAltResult>
     Ok(lines)
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    ("read_file", &[r#"e.config = read_file("config.json")"#]),
+    ("read_lines", &[r#"e.lines = read_lines("data.txt")"#]),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Utility,
+        &["read_file(path)"],
+        "Read file contents as string",
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["read_lines(path)"],
+        "Read file as array of lines",
+    ),
+];
+
 /// Register conf-related functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
-    engine.register_fn("read_file", read_file_impl);
-    engine.register_fn("read_lines", read_lines_impl);
+    engine.register_fn(
+        "read_file",
+        |context: NativeCallContext, path: String| -> Result<String, Box<rhai::EvalAltResult>> {
+            require_unsandboxed(&context, "read_file")?;
+            read_file_impl(path)
+        },
+    );
+    engine.register_fn(
+        "read_lines",
+        |context: NativeCallContext,
+         path: String|
+         -> Result<rhai::Array, Box<rhai::EvalAltResult>> {
+            require_unsandboxed(&context, "read_lines")?;
+            read_lines_impl(path)
+        },
+    );
 }
 
 /// Deep freeze a Rhai map recursively
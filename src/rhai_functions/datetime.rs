@@ -3,7 +3,10 @@ use chrono_tz::Tz;
 use rhai::{Engine, EvalAltResult, Position};
 use std::cell::RefCell;
 use std::fmt;
-use std::sync::OnceLock;
+use std::sync::{OnceLock, RwLock};
+
+use crate::cli::DurationUnit;
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 
 /// Wrapper for chrono::DateTime to provide Rhai integration
 #[derive(Debug, Clone)]
@@ -33,12 +36,24 @@ impl fmt::Display for DateTimeWrapper {
 #[derive(Debug, Clone)]
 pub struct DurationWrapper {
     pub inner: Duration,
+    /// Text the duration was parsed from by --parse-durations (`45ms`,
+    /// `0.123`), rendered back unchanged on output. Arithmetic results have
+    /// none and use the compact form.
+    pub original: Option<String>,
 }
 
 impl DurationWrapper {
     pub fn new(dur: Duration) -> Self {
         // Ensure durations are always non-negative as per spec
-        Self { inner: dur.abs() }
+        Self {
+            inner: dur.abs(),
+            original: None,
+        }
+    }
+
+    pub fn with_original(mut self, original: impl Into<String>) -> Self {
+        self.original = Some(original.into());
+        self
     }
 
     pub fn from_seconds(secs: i64) -> Self {
@@ -68,8 +83,21 @@ impl DurationWrapper {
 
 impl fmt::Display for DurationWrapper {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(original) = &self.original {
+            return f.write_str(original);
+        }
         let total_seconds = self.inner.num_seconds();
-        if total_seconds < 60 {
+        if total_seconds == 0 && !self.inner.is_zero() {
+            // Sub-second durations would otherwise all render as "0s"
+            let nanos = self.inner.num_nanoseconds().unwrap_or_default();
+            if nanos >= 1_000_000 {
+                write!(f, "{}ms", nanos / 1_000_000)
+            } else if nanos >= 1_000 {
+                write!(f, "{}us", nanos / 1_000)
+            } else {
+                write!(f, "{}ns", nanos)
+            }
+        } else if total_seconds < 60 {
             write!(f, "{}s", total_seconds)
         } else if total_seconds < 3600 {
             let minutes = total_seconds / 60;
@@ -107,16 +135,47 @@ impl fmt::Display for DurationWrapper {
 /// this before any `to_string()` / `{:?}` fallback — otherwise a datetime or
 /// duration stored in an event field leaks e.g.
 /// "kelora::rhai_functions::datetime::DateTimeWrapper" into the output.
+///
+/// Durations are rendered in the `--duration-unit` unit when one is set.
 pub fn render_custom_scalar(value: &rhai::Dynamic) -> Option<String> {
     if let Some(dt) = value.read_lock::<DateTimeWrapper>() {
         Some(dt.to_string())
+    } else if let Some(number) = value.read_lock::<super::json_number::JsonNumber>() {
+        Some(number.text.clone())
     } else {
         value
             .read_lock::<DurationWrapper>()
-            .map(|dur| dur.to_string())
+            .map(|dur| match duration_output_unit() {
+                Some(unit) => format_duration_in_unit(&dur.inner, unit),
+                None => dur.to_string(),
+            })
+    }
+}
+
+static DURATION_OUTPUT_UNIT: RwLock<Option<DurationUnit>> = RwLock::new(None);
+
+/// Set the unit output formatters render durations in (`--duration-unit`).
+pub fn set_duration_output_unit(unit: Option<DurationUnit>) {
+    if let Ok(mut slot) = DURATION_OUTPUT_UNIT.write() {
+        *slot = unit;
     }
 }
 
+pub fn duration_output_unit() -> Option<DurationUnit> {
+    DURATION_OUTPUT_UNIT.read().ok().and_then(|slot| *slot)
+}
+
+/// Render a duration as a number of `unit`s with the unit suffix, e.g.
+/// `123ms` or `0.045s`, rounded to three decimals.
+pub fn format_duration_in_unit(dur: &Duration, unit: DurationUnit) -> String {
+    let nanos = dur
+        .num_nanoseconds()
+        .map(|n| n as f64)
+        .unwrap_or_else(|| dur.num_milliseconds() as f64 * 1_000_000.0);
+    let value = (nanos / unit.nanos() * 1000.0).round() / 1000.0;
+    format!("{}{}", value, unit.suffix())
+}
+
 // Thread-local adaptive parser for Rhai timestamp parsing
 thread_local! {
     static RHAI_TS_PARSER: RefCell<crate::timestamp::AdaptiveTsParser> =
@@ -446,6 +505,239 @@ fn local_timezone() -> Tz {
     })
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    ("as_milliseconds", &[r#"e.ms = duration.as_milliseconds()"#]),
+    ("as_seconds", &[r#"e.seconds = duration.as_seconds()"#]),
+    ("ceil_to", &[r#"e.bucket_end = ts.ceil_to("1h").to_iso();"#]),
+    ("day", &[r#"e.day = e.timestamp.day()"#]),
+    (
+        "format",
+        &[
+            r#"e.date = e.timestamp.format("%Y-%m-%d")  // "2024-01-15""#,
+            r#"e.time = e.timestamp.format("%H:%M:%S")  // "10:30:00""#,
+        ],
+    ),
+    ("hour", &[r#"e.hour = e.timestamp.hour()"#]),
+    (
+        "humanize_duration",
+        &[r#"e.humanized = humanize_duration(5400000)  // "1h 30m""#],
+    ),
+    ("month", &[r#"e.month = e.timestamp.month()"#]),
+    ("now", &[r#"e.timestamp = now()"#]),
+    (
+        "round_to",
+        &[
+            r#"e.bucket = timestamp.round_to("5m").to_iso();"#,
+            r#"e.hour_bucket = to_datetime(e.time).round_to("1h").format("%Y-%m-%d %H:00");"#,
+            r#"e.day = timestamp.round_to("1d").format("%Y-%m-%d");"#,
+        ],
+    ),
+    (
+        "timezone_name",
+        &[r#"e.tz = e.timestamp.timezone_name()  // "UTC""#],
+    ),
+    (
+        "to_datetime",
+        &[
+            r#"e.parsed = to_datetime("2024-01-15 10:30:00", "%Y-%m-%d %H:%M:%S", "UTC")"#,
+            r#"e.auto = to_datetime("2024-01-15T10:30:00Z")  // Auto-detect format"#,
+        ],
+    ),
+    (
+        "to_debug",
+        &[r#"e.debug_duration = duration.to_debug()  // Full precision debug output"#],
+    ),
+    ("to_duration", &[r#"let timeout = to_duration("5m")"#]),
+    (
+        "to_iso",
+        &[r#"e.iso_timestamp = e.timestamp.to_iso()  // "2024-01-15T10:30:00Z""#],
+    ),
+    (
+        "to_local",
+        &[r#"e.local_time = e.utc_timestamp.to_local()"#],
+    ),
+    (
+        "to_string",
+        &[r#"e.readable = duration.to_string()  // "1h 30m""#],
+    ),
+    (
+        "to_timezone",
+        &[r#"e.ny_time = e.timestamp.to_timezone("America/New_York")"#],
+    ),
+    ("to_utc", &[r#"e.utc_time = e.local_timestamp.to_utc()"#]),
+    ("year", &[r#"e.year = e.timestamp.year()"#]),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Datetime,
+        &["now()"],
+        "Current UTC timestamp (DateTimeWrapper)",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["to_datetime(text [,fmt [,tz]])"],
+        "Convert string into DateTimeWrapper with optional hints",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &[r#"to_duration("1h30m")"#],
+        "Convert duration string into DurationWrapper",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration_from_<unit>(n)"],
+        "Create duration from seconds/minutes/hours/days/ms/ns",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["humanize_duration(ms)"],
+        r#"Convert milliseconds to human-readable format (e.g., "1h 30m")"#,
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt.to_iso()"],
+        "Convert datetime to ISO 8601 string",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &[r#"dt.format("format_string")"#],
+        "Format datetime using custom format string (see --help-time)",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt.year()", "dt.month()", "dt.day()"],
+        "Extract date components",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt.hour()", "dt.minute()", "dt.second()"],
+        "Extract time components",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt.to_utc()", "dt.to_local()"],
+        "Convert timezone",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &[r#"dt.to_timezone("tz_name")"#],
+        "Convert to named timezone",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt.timezone_name()"],
+        "Get timezone name as string",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt.ts_nanos()"],
+        "Get timestamp as nanoseconds",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &[r#"dt.round_to("interval")"#],
+        r#"Round timestamp down to interval (e.g., "5m", "1h", "1d")"#,
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &[r#"dt.ceil_to("interval")"#],
+        "Round timestamp up to next interval boundary",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt + duration", "dt - duration"],
+        "Add/subtract duration from datetime",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt1 - dt2"],
+        "Get duration between datetimes (returns DurationWrapper)",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt1 == dt2", "dt1 != dt2"],
+        "Compare datetimes for equality",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt1 > dt2", "dt1 < dt2"],
+        "Compare datetimes (greater/less than)",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["dt1 >= dt2", "dt1 <= dt2"],
+        "Compare datetimes (greater/less or equal)",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration.as_seconds()"],
+        "Convert duration to seconds",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration.as_milliseconds()"],
+        "Convert duration to milliseconds",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration.as_nanoseconds()"],
+        "Convert duration to nanoseconds",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration.as_minutes()"],
+        "Convert duration to minutes",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration.as_hours()"],
+        "Convert duration to hours",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration.as_days()"],
+        "Convert duration to days",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration.to_string()"],
+        r#"Format duration as human-readable string (e.g., "1h 30m")"#,
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration.to_debug()"],
+        "Format duration with full precision for debugging",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration1 + duration2"],
+        "Add durations",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration1 - duration2"],
+        "Subtract durations (always returns positive result)",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration1 == duration2"],
+        "Compare durations for equality",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration1 > duration2", "duration1 < duration2"],
+        "Compare durations (greater/less than)",
+    ),
+    FunctionDoc::new(
+        Section::Datetime,
+        &["duration1 >= duration2", "duration1 <= duration2"],
+        "Compare durations (greater/less or equal)",
+    ),
+];
+
 /// Register all datetime functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     // Parsing functions
@@ -619,9 +911,22 @@ pub fn register_functions(engine: &mut Engine) {
         DurationWrapper::new((dur1.inner - dur2.inner).abs())
     });
 
-    engine.register_fn("*", |dur: DurationWrapper, n: i64| {
-        DurationWrapper::new(dur.inner * n as i32)
-    });
+    engine.register_fn(
+        "*",
+        |dur: DurationWrapper, n: i64| -> Result<DurationWrapper, Box<EvalAltResult>> {
+            // Overflowing multiplication panics in chrono; report it instead
+            i32::try_from(n)
+                .ok()
+                .and_then(|factor| dur.inner.checked_mul(factor))
+                .map(DurationWrapper::new)
+                .ok_or_else(|| {
+                    Box::new(EvalAltResult::ErrorRuntime(
+                        "duration multiplication overflow".into(),
+                        Position::NONE,
+                    ))
+                })
+        },
+    );
 
     engine.register_fn(
         "/",
@@ -754,6 +1059,56 @@ mod tests {
         assert_eq!(DurationWrapper::from_seconds(90000).to_string(), "1d 1h");
     }
 
+    #[test]
+    fn test_duration_display_sub_second_and_original() {
+        assert_eq!(DurationWrapper::from_milliseconds(250).to_string(), "250ms");
+        assert_eq!(DurationWrapper::from_nanoseconds(1_500).to_string(), "1us");
+        assert_eq!(DurationWrapper::from_nanoseconds(7).to_string(), "7ns");
+        assert_eq!(DurationWrapper::from_seconds(0).to_string(), "0s");
+
+        let parsed = to_duration("1500ms").unwrap().with_original("1500ms");
+        assert_eq!(parsed.to_string(), "1500ms");
+        // Arithmetic results fall back to the compact form
+        let sum = DurationWrapper::new(parsed.inner + Duration::seconds(1));
+        assert_eq!(sum.to_string(), "2s");
+    }
+
+    #[test]
+    fn test_format_duration_in_unit() {
+        let dur = Duration::milliseconds(123);
+        assert_eq!(format_duration_in_unit(&dur, DurationUnit::Ms), "123ms");
+        assert_eq!(format_duration_in_unit(&dur, DurationUnit::S), "0.123s");
+        assert_eq!(format_duration_in_unit(&dur, DurationUnit::Us), "123000us");
+        assert_eq!(
+            format_duration_in_unit(&Duration::seconds(90), DurationUnit::M),
+            "1.5m"
+        );
+    }
+
+    #[test]
+    fn test_parsed_duration_operators() {
+        let mut engine = Engine::new();
+        register_functions(&mut engine);
+        let mut scope = rhai::Scope::new();
+        scope.push("took", to_duration("250ms").unwrap().with_original("250ms"));
+
+        let slow: bool = engine
+            .eval_with_scope(&mut scope, r#"took > to_duration("100ms")"#)
+            .unwrap();
+        assert!(slow);
+        let same: bool = engine
+            .eval_with_scope(&mut scope, r#"took == to_duration("0.25s")"#)
+            .unwrap();
+        assert!(same);
+        let doubled: String = engine
+            .eval_with_scope(&mut scope, r#"(took * 2 + took).to_string()"#)
+            .unwrap();
+        assert_eq!(doubled, "750ms");
+        assert!(engine
+            .eval_with_scope::<DurationWrapper>(&mut scope, "took * 9999999999")
+            .is_err());
+    }
+
     #[test]
     fn test_datetime_string_interpolation_renders_rfc3339() {
         // Regression: `${dt}` interpolation / to_string()/to_debug() must render
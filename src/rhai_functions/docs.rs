@@ -1,345 +1,355 @@
-/// Generate comprehensive function reference documentation
-pub fn generate_help_text() -> &'static str {
-    r#"
-Available Rhai Functions:
-
-STRING FUNCTIONS:
-text.after(delimiter [,nth])         Text after occurrence of delimiter (nth: 1=first, -1=last)
-text.before(delimiter [,nth])        Text before occurrence of delimiter (nth: 1=first, -1=last)
-text.between(start, end [,nth])      Text between start and end delimiters (nth: 1=first, -1=last)
-                                     Equivalent to: text.after(start, nth).before(end)
-text.bucket()                        Fast hash for sampling/grouping (returns INT for modulo operations)
-text.clip()                          Remove leading/trailing non-alphanumeric characters
-text.col(spec [,separator])          Extract columns by index/range/list (e.g., '1', '1,3,5', '1:4')
-text.cols(col1, col2 [,...] [,sep])  Extract multiple columns as array (up to 6 columns)
-text.contains(pattern)               Check if text contains pattern (builtin)
-text.like(pattern)                   Glob match (*, ?) against entire string
-text.ilike(pattern)                  Glob match with Unicode case folding (*, ?)
-text.count(pattern)                  Count occurrences of pattern in text
-text.decode_b64()                    Decode base64 string to text
-text.decode_hex()                    Decode hexadecimal string to text
-text.decode_url()                    Decode URL-encoded string
-text.edit_distance(other)            Compute Levenshtein edit distance between two strings
-text.encode_b64()                    Encode text to base64 string
-text.encode_hex()                    Encode text to hexadecimal string
-text.encode_url()                    URL-encode text (percent encoding)
-text.ending_with(suffix [,nth])      Return substring from start to end of suffix (nth: 1=first, -1=last)
-text.escape_html()                   Escape HTML special characters (&, <, >, ", ')
-text.escape_json()                   Escape JSON special characters
-text.extract_regexes(pattern [,group]) Extract all regex matches as array
-text.extract_domain()                Extract domain from URL or email address
-text.extract_email([nth])            Extract email address from text (nth: 1=first, -1=last)
-text.extract_emails()                Extract all email addresses as array
-text.extract_ip([nth])               Extract IP address from text (nth: 1=first, -1=last)
-text.extract_ips()                   Extract all IP addresses as array
-text.extract_json([nth])             Extract JSON object/array from text (nth: 1=first, -1=last)
-text.extract_jsons()                 Extract all JSON objects/arrays from text as array of strings
-text.extract_regex_maps(pattern, field) Extract regex matches as array of maps for fan-out
-text.extract_re_maps(pattern, field)    Deprecated alias for extract_regex_maps
-text.extract_regex(pattern [,group])    Extract regex match or capture group
-text.extract_url([nth])              Extract URL from text (nth: 1=first, -1=last)
-text.matches(pattern)                Regex search (cached; invalid pattern raises error)
-text.hash([algo])                    Hash with algorithm (default: sha256, also: xxh3); redact/anonymize a value
-text.index_of(substring [,start])    Find position of literal substring (-1 if not found) (builtin)
-text.is_digit()                      Check if text contains only digits
-text.is_in_cidr(cidr)                Check if IP address is in CIDR network (e.g., "10.0.0.0/8")
-text.is_ipv4()                       Check if text is a valid IPv4 address
-text.is_ipv6()                       Check if text is a valid IPv6 address
-text.is_private_ip()                 Check if IP is in private/internal ranges
-text.lclip()                         Remove leading non-alphanumeric characters (left side only)
-text.len                             Get string length (builtin)
-text.lower()                         Convert text to lowercase
-text.lstrip([chars])                 Remove leading whitespace or specified characters
-text.mask_ip([octets])               Mask IP address by zeroing the suffix (IPv4/IPv6)
-text.normalized([patterns])          Replace patterns with placeholders (<ipv4>, <email>, <credit_card>, etc.)
-                                     Patterns: ipv4, ipv4_port, ipv6, email, url, fqdn, uuid, mac, md5,
-                                     sha1, sha256, path, oauth, function, hexcolor, version, hexnum,
-                                     duration, num, credit_card (Luhn), ssn (strict XXX-XX-XXXX), phone (NANP-aware for US/CA; permissive internationally)
-                                     PII patterns (credit_card, ssn, phone) are NOT in the default set;
-                                     pass them explicitly to redact, e.g. normalized(["credit_card","ssn","phone"])
-text.parse_cef()                     Parse Common Event Format line into fields
-text.parse_cols(spec [,sep])         Parse columns according to spec
-text.parse_combined()                Parse Apache/Nginx combined log line
-text.parse_content_disposition()     Parse Content-Disposition header parameters
-text.parse_email()                   Parse email address into parts
-text.parse_json()                    Parse JSON string into map/array
-text.parse_jwt()                     Parse JWT into header/claims (+ exp/iat/nbf as datetimes) without verification
-text.parse_kv([sep [,kv_sep]])       Split key-value pairs from text (skips tokens without separator; NOT quote-aware — use parse_logfmt for quoted/typed values)
-text.parse_logfmt()                  Parse logfmt line into structured fields
-text.parse_media_type()              Parse media type tokens and parameters
-text.parse_path()                    Parse filesystem path into components
-text.parse_query_params()            Parse URL query string into map
-text.parse_syslog()                  Parse syslog line into structured fields
-text.parse_url()                     Parse URL into structured components
-text.parse_user_agent()              Parse common user-agent strings into components
-text.rclip()                         Remove trailing non-alphanumeric characters (right side only)
-text.replace(pattern, replacement)   Replace all occurrences of pattern (builtin)
-text.replace_regex(pattern, replacement) Replace all regex matches
-text.replace_re(pattern, replacement)    Deprecated alias for replace_regex
-text.rstrip([chars])                 Remove trailing whitespace or specified characters
-text.slice(spec)                     Slice text using Python notation (e.g., "1:5", ":3", "-2:")
-text.split_regex(pattern)            Split text by regex pattern
-text.split_re(pattern)               Deprecated alias for split_regex
-text.split(separator)                Split string into array by delimiter (builtin)
-text.starting_with(prefix [,nth])    Return substring from prefix to end (nth: 1=first, -1=last)
-text.strip([chars])                  Remove whitespace or specified characters
-text.sub_string(start [,length])     Extract substring from position (builtin)
-text.to_float()                      Convert text to float (returns () on error)
-text.to_float(thousands, decimal)    Parse with explicit separators
-                                     - thousands: remove ANY char in string (e.g., ',', ',. ', ",.'")
-                                     - decimal: single char or empty (multi-char returns error)
-text.to_int()                        Convert text to integer (returns () on error)
-text.to_int(thousands)               Parse with thousands separator removal
-                                     - thousands: remove ANY char in string (e.g., ',', '. ', ",.'")
-text.or_empty()                      Convert empty string/array/map to () for removal/filtering
-text.to_lower()                      Convert to lowercase (builtin)
-text.to_upper()                      Convert to uppercase (builtin; also available as upper())
-text.lower()                         Convert to lowercase (alias for to_lower(); for Python users)
-text.upper()                         Convert to uppercase (alias for to_upper(); for Python users)
-text.trim()                          Remove whitespace from start and end (builtin)
-text.unescape_html()                 Unescape HTML entities to text
-text.unescape_json()                 Unescape JSON escape sequences
-
-ARRAY FUNCTIONS:
-array.all(|item| condition)          Check if all elements match condition (builtin)
-array.contains_any(search_array)     Check if array contains any search values
-array.contains(value)                Check if array contains value (builtin)
-array.filter(|item| condition)       Keep elements matching condition (builtin)
-array.flattened([style [,max_depth]]) Return new flattened map from nested arrays/objects
-array.join(separator)                Join array elements with separator
-array.len                            Get array length (builtin)
-array.map(|item| expression)         Transform each element (builtin)
-array.pluck(field)                   Extract field from each map/object in array (skips missing/() values)
-array.pluck_as_nums(field)           Extract field as f64 from each map in array (skips invalid/missing)
-array.max()                          Find maximum value in array (rejects mixed types; no auto string-to-number coercion)
-array.mean()                         Calculate arithmetic mean of numeric array (rejects mixed types)
-array.min()                          Find minimum value in array (rejects mixed types; no auto string-to-number coercion)
-array.parse_cols(spec [,sep])        Apply column spec to pre-split values
-array.percentile(pct)                Calculate percentile of numeric array
-array.stddev()                       Calculate standard deviation of numeric array (rejects mixed types)
-array.sum()                          Calculate sum of numeric values in array (rejects mixed types)
-array.variance()                     Calculate variance of numeric array (rejects mixed types)
-array.pop()                          Remove and return last item (builtin)
-array.push(item)                     Add item to end of array (builtin)
-array.reduce(|acc, item| expr, init) Aggregate array into single value (builtin)
-array.reversed()                     Return new array in reverse order
-array.slice(spec)                    Slice array using Python notation (e.g., "1:5", ":3", "-2:")
-array.some(|item| condition)         Check if any element matches condition (builtin)
-array.sort()                         Sort array in place (builtin)
-array.sorted_by(field)               Sort array of objects by field name
-array.sorted()                       Return new sorted array (numeric/lexicographic)
-array.starts_with_any(search_array)  Check if array starts with any search values
-array.unique()                       Remove all duplicate elements (preserves first occurrence)
-  
-MAP/OBJECT FUNCTIONS:
-map.contains("key")                  Check if map contains key (ignores value) (builtin)
-map.enrich(other_map)                Merge another map, inserting only missing keys
-map.flattened([style [,max_depth]])  Return new flattened map from nested object
-map.flatten_field("field_name")      Flatten just one field from the map
-map.get("key" [,default])            Safe top-level field access with fallback
-map.get_path("field.path" [,default]) Safe nested field access with fallback
-map.has("key")                       Check if map contains key with non-unit value
-map.has_path("field.path")           Check if nested field path exists
-map.keep(["field1", ...])            Return new map with only selected top-level fields
-map.merge(other_map)                 Merge another map into this one (overwrites existing keys)
-map.normalized([patterns])           Return new map with all string fields normalized
-map.path_equals("path", value)       Safe nested field comparison
-map.drop(["field1", ...])            Return new map without selected top-level fields
-map.rename_field("old", "new")       Rename a field, returns true if successful
-map.to_cef()                         Convert map to Common Event Format (CEF) string
-map.to_combined()                    Convert map to Apache/Nginx combined log format
-map.to_json([indent])                Convert map to JSON string (indent: spaces for pretty-printing, 0/omit for compact)
-map.to_kv([sep [,kv_sep]])           Convert map to key-value string with separators
-map.to_logfmt()                      Convert map to logfmt format string
-map.to_syslog()                      Convert map to syslog format string
-map.unflatten([separator])           Reconstruct nested object from flat keys
-  
-DATETIME FUNCTIONS:
-now()                                Current UTC timestamp (DateTimeWrapper)
-to_datetime(text [,fmt [,tz]])       Convert string into DateTimeWrapper with optional hints
-to_duration("1h30m")                 Convert duration string into DurationWrapper
-duration_from_<unit>(n)              Create duration from seconds/minutes/hours/days/ms/ns
-humanize_duration(ms)                Convert milliseconds to human-readable format (e.g., "1h 30m")
-dt.to_iso()                          Convert datetime to ISO 8601 string
-dt.format("format_string")           Format datetime using custom format string (see --help-time)
-dt.year(), dt.month(), dt.day()      Extract date components
-dt.hour(), dt.minute(), dt.second()  Extract time components
-dt.to_utc(), dt.to_local()           Convert timezone
-dt.to_timezone("tz_name")            Convert to named timezone
-dt.timezone_name()                   Get timezone name as string
-dt.ts_nanos()                        Get timestamp as nanoseconds
-dt.round_to("interval")              Round timestamp down to interval (e.g., "5m", "1h", "1d")
-dt.ceil_to("interval")               Round timestamp up to next interval boundary
-dt + duration, dt - duration         Add/subtract duration from datetime
-dt1 - dt2                            Get duration between datetimes (returns DurationWrapper)
-dt1 == dt2, dt1 != dt2               Compare datetimes for equality
-dt1 > dt2, dt1 < dt2                 Compare datetimes (greater/less than)
-dt1 >= dt2, dt1 <= dt2               Compare datetimes (greater/less or equal)
-duration.as_seconds()                Convert duration to seconds
-duration.as_milliseconds()           Convert duration to milliseconds
-duration.as_nanoseconds()            Convert duration to nanoseconds
-duration.as_minutes()                Convert duration to minutes
-duration.as_hours()                  Convert duration to hours
-duration.as_days()                   Convert duration to days
-duration.to_string()                 Format duration as human-readable string (e.g., "1h 30m")
-duration.to_debug()                  Format duration with full precision for debugging
-duration1 + duration2                Add durations
-duration1 - duration2                Subtract durations (always returns positive result)
-duration1 == duration2               Compare durations for equality
-duration1 > duration2, duration1 < duration2  Compare durations (greater/less than)
-duration1 >= duration2, duration1 <= duration2  Compare durations (greater/less or equal)
-
-MATH FUNCTIONS:
-abs(x)                               Absolute value of number
-clamp(value, min, max)               Constrain value to be within min/max range
-floor(x)                             Round down to nearest integer
-mod(a, b) / a % b                    Modulo operation with division-by-zero protection
-rand()                               Random float between 0 and 1 (set KELORA_SEED for reproducible output)
-rand_int(min, max)                   Random integer between min and max (inclusive)
-round(x)                             Round to nearest integer
-sample_every(n)                      Sample every Nth event (returns true on Nth, 2Nth, 3Nth calls)
-                                     Fast counter-based sampling (thread-local, approximate in parallel mode)
-                                     For deterministic sampling, use: text.bucket() % n == 0
-sample_prob(p)                       Probabilistic sampling: returns true with probability p (0.0-1.0)
-
-OUTPUT FORMATTING FUNCTIONS:
-bar(value, max, width)               Render a horizontal bar of `width` cells showing value/max,
-                                     using Unicode eighth-blocks (▏▎▍▌▋▊▉█) for sub-cell resolution.
-                                     Pads with spaces so the result has exactly `width` display columns.
-                                     Values outside 0..max are clamped; max<=0 renders empty.
-                                     For ratios in 0.0–1.0 (error rate, CPU fraction, etc.), set max to 1.0.
-format_decimals(value, decimals)     Format number with exactly N digits after the decimal point (returns string)
-format_percent(ratio, decimals)      Format ratio as percentage string with N decimals and '%' suffix (returns string)
-                                     Input is multiplied by 100, so pass 0.042 to render "4.2%".
-human_bytes(n)                       Format byte count with binary/IEC units (1024-based): B, KiB, MiB, GiB, ...
-human_bytes_si(n)                    Format byte count with decimal/SI units (1000-based): B, KB, MB, GB, ...
-sparkline(array)                     Render an array of numbers as a single-line sparkline (▁▂▃▄▅▆▇█)
-                                     scaled to 0..max(array). Negatives and non-numerics render as space.
-                                     Empty arrays return "".
-text.bold() / .dim()                 Chainable: "X".bold().red() renders as bold red.
-text.blue() / .cyan() / .magenta()   Wrap text with ANSI color/style; resets at end. Returns text unchanged
-                                     when colors are disabled (non-TTY output, NO_COLOR, --no-color).
-text.ljust(n [,fill])                Left-justify: pad right to display width n (default fill: space). Unicode-width aware.
-text.rjust(n [,fill])                Right-justify: pad left to display width n (default fill: space).
-text.center(n [,fill])               Center within display width n (extra goes right on odd difference).
-text.shorten(n [,marker])            If text exceeds width n, keep start and append marker (default "…").
-text.shorten_middle(n [,marker])     If text exceeds width n, keep both ends, insert marker (default "…") in the middle.
-                                     Useful for paths/URLs/IDs where both ends are informative.
-text.red() / .green() / .yellow()    Wrap text with ANSI color/style; resets at end. Returns text unchanged
-                                     when colors are disabled (non-TTY output, NO_COLOR, --no-color).
-
-TYPE CONVERSION FUNCTIONS:
-to_int(value)                        Convert value to integer (returns () on error)
-to_int(value, thousands)             Parse integer, removing ANY char in thousands string
-to_float(value)                      Convert value to float (returns () on error)
-to_float(value, thousands, decimal)  Parse float with explicit separators
-                                     - thousands: remove ANY char in string
-                                     - decimal: single char or empty (multi-char → error)
-to_bool(value)                       Convert value to boolean (returns () on error)
-to_int_or(value, default)            Convert value to integer with fallback
-to_int_or(value, thousands, default) Parse integer with thousands removal and fallback
-to_float_or(value, default)          Convert value to float with fallback
-to_float_or(value, thousands, decimal, default)
-                                     Parse float with separators and fallback
-to_bool_or(value, default)           Convert value to boolean with fallback
-
-UTILITY FUNCTIONS:
-eprint(message)                      Print to stderr (suppressed with --no-script-output or data-only modes)
-exit(code)                           Exit kelora with given exit code
-skip()                               Skip the current event and continue with the next one
-get_env(var [,default])              Get environment variable with optional default
-print(message)                       Print to stdout (suppressed with --no-script-output or data-only modes)
-pseudonym(value, domain)             Domain-separated pseudonym to redact/anonymize/mask a value
-                                     (set KELORA_SECRET for stable output; else ephemeral per-run key)
-read_file(path)                      Read file contents as string
-read_lines(path)                     Read file as array of lines
-status_class(status_code)            Convert HTTP status code to class string ("2xx", "4xx", etc.)
-type_of(value)                       Get type name as string (builtin)
-window.pluck(field)                  Extract field values from window array (requires --window)
-window.pluck_as_nums(field)          Extract numeric field values from window array (requires --window)
-
-DRAIN TEMPLATE MINING (sequential mode only; errors in --parallel mode):
-drain_template(text [,options])      Add line to Drain model; returns {template, template_id, count,
-                                     is_new, sample, first_line, last_line}
-drain_templates()                    Return array of templates with same fields (except is_new)
-                                     Default filters: ipv4_port, ipv4, ipv6, email, url, fqdn, uuid,
-                                     mac, md5, sha1, sha256, path, oauth, function, hexcolor, version,
-                                     hexnum, duration, timestamp, date, time, num
-                                     For PII (credit_card/ssn/phone), pre-mask with normalized() before drain_template()
-                                     Options: depth, max_children, similarity, filters, line_num
-
-STATE MANAGEMENT (sequential mode only; errors in --parallel mode):
-state["key"]                         Get/set state value via indexer (state["count"] = 0)
-state.get(key)                       Get value from state (returns () if not found)
-state.get(key, default)              Get value with fallback (missing or () returns default)
-state.set(key, value)                Set value in state
-state.contains(key)                  Check if key exists in state
-state.remove(key)                    Remove key from state (returns removed value or ())
-state.clear()                        Remove all entries from state
-state.keys()                         Get array of all keys in state
-state.values()                       Get array of all values in state
-state.len()                          Get number of entries in state
-state.is_empty()                     Check if state is empty
-state.mixin(map)                     Merge map into state (overwrites existing keys)
-state.fill_with(map)                 Replace entire state with new map
-state.to_map()                       Convert state to regular map (for use with to_logfmt(), etc.)
-state += map                         Merge map into state (operator form)
-
-TRACKING/METRICS FUNCTIONS (requires --metrics):
-All track_* functions skip Unit () values (missing fields); skips are counted
-and surfaced via --diagnostics. Categorical arguments (category, item) accept
-strings, numbers, and bools (stringified). One metric name = one track function
-(mixing functions on the same name is an error).
-track_avg(name, value)                Track average of numeric values
-track_bottom(name, item [,n])         Track bottom N least frequent items (default n=10)
-track_bottom_by(name, item, score [,n]) Track bottom N distinct items by their lowest score (default n=10)
-track_cardinality(name, value [,err]) Estimate unique count using HyperLogLog (~1% error, ~12KB; err range: 0.001-0.26)
-track_freq(name, value)               Frequency table — count occurrences per value: {name: {value: n}}; e.g. track_freq("status", e.status)
-track_inc(name)                       Increment a running counter by 1 (sugar for track_sum(name, 1))
-track_max(name, value)                Track maximum numeric value
-track_min(name, value)                Track minimum numeric value
-track_percentiles(name, value [,[p]]) Track streaming percentiles using t-digest (default [0.50,0.95,0.99]; auto-suffixes)
-track_stats(name, value [,[p]])       Track comprehensive stats: min, max, avg, count, sum, percentiles (auto-suffixes)
-track_sum(name, value)                Accumulate numeric values; track_sum(name, 1) (or track_inc) is a plain counter
-track_top(name, item [,n])            Track top N most frequent items (default n=10)
-track_top_by(name, item, score [,n])  Track top N distinct items by their highest score (default n=10)
-track_unique(name, value)             Track exact set of distinct values (unbounded memory; warns past 100k values)
-
-FILE OUTPUT (requires --allow-fs-writes):
-append_file(path, text_or_array)     Append line(s) to file; arrays append one line per element
-mkdir(path [,recursive])             Create directory (set recursive=true to create parents)
-truncate_file(path)                  Create or zero-length a file for fresh output
-
-SPAN CONTEXT (available inside --span-close):
-span.id                              Span identifier ('#index' for count, 'ISO/DURATION' for time)
-span.start                           Span start as DateTime (time spans) or () for count spans
-span.end                             Span end as DateTime (time spans) or () for count spans
-span.size                            Number of events that survived the span
-span.events                          Array of event maps for the span in arrival order
-span.metrics                         Per-window metrics from additive track_* calls: track_freq,
-                                     track_sum, track_inc, track_avg, track_unique (read-only map).
-                                     Non-additive aggregators (min, max, percentiles, cardinality,
-                                     top/bottom, top_by/bottom_by) have no per-window value and are
-                                     omitted with a warning; iterate span.events to compute them
-                                     per window.
-
-EVENT MANIPULATION:
-emit_each(array [,base_map])         Fan out array elements as separate events (returns emitted count)
-                                     Per-event stages only (-e/--exec, --filter); errors in --begin/--end
-e.absorb_kv(field [,options])        Parse key=value tokens from field, merge pairs, return status map (NOT quote-aware; use absorb_logfmt for quoted values)
-e.absorb_logfmt(field [,options])    Parse logfmt (quote-aware, typed) from field, merge keys, return status map
-e.absorb_json(field [,options])      Parse JSON object from field, merge keys, return status map
-e.absorb_jwt(field [,options])       Parse JWT (no verification), merge claims from field, return status map
-e.absorb_regex(field, pattern [,opts]) Extract named captures from field using regex, return status map
-e = ()                               Clear entire event (remove all fields)
-e.field = ()                         Remove individual field from event
-e.has("key")                         Check if key exists and value is not ()
-e.rename_field("old", "new")         Rename field, returns true if successful
-
-Rhai lets you call functions as either `value.method(args)` or `function(value, args)`.
+use serde::Serialize;
+
+/// Column at which catalogue summaries start
+const SIGNATURE_WIDTH: usize = 37;
+
+/// A heading of the function catalogue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Strings,
+    Arrays,
+    Maps,
+    Datetime,
+    Math,
+    Formatting,
+    Conversion,
+    Utility,
+    Drain,
+    State,
+    Tracking,
+    FileOutput,
+    SpanContext,
+    SpanAggregates,
+    WindowContext,
+    Events,
+}
 
-For other help topics: kelora -h
-"#
+impl Section {
+    /// Sections in listing order
+    const ALL: [Section; 16] = [
+        Section::Strings,
+        Section::Arrays,
+        Section::Maps,
+        Section::Datetime,
+        Section::Math,
+        Section::Formatting,
+        Section::Conversion,
+        Section::Utility,
+        Section::Drain,
+        Section::State,
+        Section::Tracking,
+        Section::FileOutput,
+        Section::SpanContext,
+        Section::SpanAggregates,
+        Section::WindowContext,
+        Section::Events,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Section::Strings => "STRING FUNCTIONS",
+            Section::Arrays => "ARRAY FUNCTIONS",
+            Section::Maps => "MAP/OBJECT FUNCTIONS",
+            Section::Datetime => "DATETIME FUNCTIONS",
+            Section::Math => "MATH FUNCTIONS",
+            Section::Formatting => "OUTPUT FORMATTING FUNCTIONS",
+            Section::Conversion => "TYPE CONVERSION FUNCTIONS",
+            Section::Utility => "UTILITY FUNCTIONS",
+            Section::Drain => "DRAIN TEMPLATE MINING",
+            Section::State => "STATE MANAGEMENT",
+            Section::Tracking => "TRACKING/METRICS FUNCTIONS",
+            Section::FileOutput => "FILE OUTPUT",
+            Section::SpanContext => "SPAN CONTEXT",
+            Section::SpanAggregates => "SPAN AGGREGATES",
+            Section::WindowContext => "WINDOW CONTEXT",
+            Section::Events => "EVENT MANIPULATION",
+        }
+    }
+
+    /// Where the section's functions can be used, e.g. "requires --metrics"
+    pub fn availability(self) -> Option<&'static str> {
+        match self {
+            Section::Drain | Section::State => {
+                Some("sequential mode only; errors in --parallel mode")
+            }
+            Section::Tracking => Some("requires --metrics"),
+            Section::FileOutput => Some("requires --allow-fs-writes"),
+            Section::SpanContext => Some("available inside --span-close"),
+            Section::SpanAggregates => Some("available inside --span-summary and --span-close"),
+            Section::WindowContext => Some("available inside --every-emit"),
+            _ => None,
+        }
+    }
+
+    /// Prose listed under the header, ahead of the entries
+    fn notes(self) -> &'static [&'static str] {
+        match self {
+            Section::Tracking => &[
+                "All track_* functions skip Unit () values (missing fields); skips are counted",
+                "and surfaced via --diagnostics. Categorical arguments (category, item) accept",
+                "strings, numbers, and bools (stringified). One metric name = one track function",
+                "(mixing functions on the same name is an error).",
+            ],
+            _ => &[],
+        }
+    }
+
+    fn header(self) -> String {
+        match self.availability() {
+            Some(availability) => format!("{} ({}):", self.title(), availability),
+            None => format!("{}:", self.title()),
+        }
+    }
+
+    /// Entries of this section, in module order
+    fn docs(self) -> impl Iterator<Item = &'static FunctionDoc> {
+        MODULE_DOCS
+            .iter()
+            .flat_map(|docs| docs.iter())
+            .filter(move |doc| doc.section == self)
+    }
+}
+
+/// One line of the function catalogue. Each function module lists the
+/// entries for what it registers in its `DOCS` table.
+#[derive(Debug)]
+pub struct FunctionDoc {
+    pub section: Section,
+    /// Signatures the entry covers; operator forms follow the function they
+    /// spell (`mod(a, b)`, `a % b`)
+    pub signatures: &'static [&'static str],
+    pub summary: &'static str,
+    /// Parameter notes listed below the summary
+    pub details: &'static [&'static str],
+}
+
+impl FunctionDoc {
+    pub const fn new(
+        section: Section,
+        signatures: &'static [&'static str],
+        summary: &'static str,
+    ) -> Self {
+        Self {
+            section,
+            signatures,
+            summary,
+            details: &[],
+        }
+    }
+
+    pub const fn with_details(self, details: &'static [&'static str]) -> Self {
+        Self { details, ..self }
+    }
+
+    /// The entry as listed: signatures and summary, then indented details
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{:<width$} {}",
+            self.signatures.join(", "),
+            self.summary,
+            width = SIGNATURE_WIDTH - 1
+        )];
+        lines.extend(
+            self.details
+                .iter()
+                .map(|detail| format!("{:width$}{}", "", detail, width = SIGNATURE_WIDTH)),
+        );
+        lines
+    }
+}
+
+/// Catalogue tables of all function modules, then Rhai's own functions
+const MODULE_DOCS: &[&[FunctionDoc]] = &[
+    super::absorb::DOCS,
+    super::arrays::DOCS,
+    super::columns::DOCS,
+    super::conf::DOCS,
+    super::datetime::DOCS,
+    super::drain::DOCS,
+    super::emit::DOCS,
+    super::encoding::DOCS,
+    super::environment::DOCS,
+    super::extractors::DOCS,
+    super::file_ops::DOCS,
+    super::formatting::DOCS,
+    super::hashing::DOCS,
+    super::maps::DOCS,
+    super::math::DOCS,
+    super::micro_search::DOCS,
+    super::network::DOCS,
+    super::normalize::DOCS,
+    super::parsers::DOCS,
+    super::process::DOCS,
+    super::random::DOCS,
+    super::safety::DOCS,
+    super::serializers::DOCS,
+    super::span::DOCS,
+    super::state::DOCS,
+    super::strings::DOCS,
+    super::tracking::DOCS,
+    super::window::DOCS,
+    BUILTIN_DOCS,
+];
+
+/// Rhai's built-in functions and the event forms kelora documents with its own
+const BUILTIN_DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.contains(pattern)"],
+        "Check if text contains pattern (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.index_of(substring [,start])"],
+        "Find position of literal substring (-1 if not found) (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.len"],
+        "Get string length (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.parse_json()"],
+        "Parse JSON string into map/array",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.replace(pattern, replacement)"],
+        "Replace all occurrences of pattern (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.split(separator)"],
+        "Split string into array by delimiter (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.sub_string(start [,length])"],
+        "Extract substring from position (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.to_lower()"],
+        "Convert to lowercase (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.to_upper()"],
+        "Convert to uppercase (builtin; also available as upper())",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.trim()"],
+        "Remove whitespace from start and end (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.all(|item| condition)"],
+        "Check if all elements match condition (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.contains(value)"],
+        "Check if array contains value (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.filter(|item| condition)"],
+        "Keep elements matching condition (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.len"],
+        "Get array length (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.map(|item| expression)"],
+        "Transform each element (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.pop()"],
+        "Remove and return last item (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.push(item)"],
+        "Add item to end of array (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.reduce(|acc, item| expr, init)"],
+        "Aggregate array into single value (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.some(|item| condition)"],
+        "Check if any element matches condition (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.sort()"],
+        "Sort array in place (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &[r#"map.contains("key")"#],
+        "Check if map contains key (ignores value) (builtin)",
+    ),
+    FunctionDoc::new(Section::Math, &["abs(x)"], "Absolute value of number"),
+    FunctionDoc::new(
+        Section::Math,
+        &["floor(x)"],
+        "Round down to nearest integer",
+    ),
+    FunctionDoc::new(Section::Math, &["round(x)"], "Round to nearest integer"),
+    FunctionDoc::new(
+        Section::Utility,
+        &["print(message)"],
+        "Print to stdout (suppressed with --no-script-output or data-only modes)",
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["type_of(value)"],
+        "Get type name as string (builtin)",
+    ),
+    FunctionDoc::new(
+        Section::Events,
+        &["e = ()"],
+        "Clear entire event (remove all fields)",
+    ),
+    FunctionDoc::new(
+        Section::Events,
+        &["e.field = ()"],
+        "Remove individual field from event",
+    ),
+];
+
+/// Generate comprehensive function reference documentation
+pub fn generate_help_text() -> String {
+    let mut out = String::from("\nAvailable Rhai Functions:\n");
+    for section in Section::ALL {
+        out.push('\n');
+        out.push_str(&section.header());
+        out.push('\n');
+        for note in section.notes() {
+            out.push_str(note);
+            out.push('\n');
+        }
+        for doc in section.docs() {
+            for line in doc.lines() {
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+    }
+    out.push_str(
+        "\nRhai lets you call functions as either `value.method(args)` or `function(value, args)`.\n",
+    );
+    out.push_str("\nFor other help topics: kelora -h\n");
+    out
 }
 
 /// Filter the function catalogue by a keyword using smartcase matching.
@@ -351,88 +361,293 @@ For other help topics: kelora -h
 ///
 /// Returns only the sections and function entries that match `keyword`.
 /// A function entry is matched when the keyword appears anywhere in its
-/// signature, description, or any indented continuation lines, or when the
-/// keyword appears in the section header (so e.g. "string" lists the whole
-/// STRING FUNCTIONS section). Section headers are preserved above their
-/// matching entries so the output keeps its context.
+/// signature, summary or detail lines, or when the keyword appears in the
+/// section header (so e.g. "string" lists the whole STRING FUNCTIONS
+/// section). Section headers are preserved above their matching entries so
+/// the output keeps its context.
 pub fn filter_help_text(keyword: &str) -> String {
-    let full = generate_help_text();
-    // Smartcase: a lowercase keyword matches any case; an uppercase letter
-    // anywhere makes the search case-sensitive.
+    let contains = smartcase_matcher(keyword);
+    let mut out = String::new();
+
+    for section in Section::ALL {
+        let header = section.header();
+        let header_matches = contains(&header);
+        let mut lines: Vec<String> = section
+            .notes()
+            .iter()
+            .filter(|note| header_matches || contains(note))
+            .map(|note| note.to_string())
+            .collect();
+        for doc in section.docs() {
+            let entry = doc.lines();
+            if header_matches || entry.iter().any(|line| contains(line)) {
+                lines.extend(entry);
+            }
+        }
+
+        if lines.is_empty() {
+            continue;
+        }
+        out.push('\n');
+        out.push_str(&header);
+        out.push('\n');
+        for line in lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// Build a smartcase substring matcher: a lowercase keyword matches any case;
+/// an uppercase letter anywhere makes the search case-sensitive.
+fn smartcase_matcher(keyword: &str) -> impl Fn(&str) -> bool {
     let case_sensitive = keyword.chars().any(|c| c.is_uppercase());
     let needle = if case_sensitive {
         keyword.to_string()
     } else {
         keyword.to_lowercase()
     };
-    let contains = |haystack: &str| -> bool {
+    move |haystack: &str| {
         if case_sensitive {
             haystack.contains(&needle)
         } else {
             haystack.to_lowercase().contains(&needle)
         }
-    };
-    let lines: Vec<&str> = full.lines().collect();
+    }
+}
 
-    // A section header sits at column 0 and ends with ':'. Function entries
-    // also start at column 0; their continuation lines are indented.
-    let is_header = |line: &str| {
-        !line.is_empty() && !line.starts_with(char::is_whitespace) && line.trim_end().ends_with(':')
-    };
+/// Examples one function module contributes to `--help-function`: function
+/// name and up to three example lines.
+pub type FunctionExamples = &'static [(&'static str, &'static [&'static str])];
+
+/// Example tables of all function modules
+const MODULE_EXAMPLES: &[FunctionExamples] = &[
+    super::absorb::EXAMPLES,
+    super::arrays::EXAMPLES,
+    super::columns::EXAMPLES,
+    super::conf::EXAMPLES,
+    super::datetime::EXAMPLES,
+    super::drain::EXAMPLES,
+    super::emit::EXAMPLES,
+    super::encoding::EXAMPLES,
+    super::environment::EXAMPLES,
+    super::extractors::EXAMPLES,
+    super::file_ops::EXAMPLES,
+    super::formatting::EXAMPLES,
+    super::hashing::EXAMPLES,
+    super::maps::EXAMPLES,
+    super::math::EXAMPLES,
+    super::network::EXAMPLES,
+    super::normalize::EXAMPLES,
+    super::parsers::EXAMPLES,
+    super::process::EXAMPLES,
+    super::random::EXAMPLES,
+    super::safety::EXAMPLES,
+    super::serializers::EXAMPLES,
+    super::state::EXAMPLES,
+    super::strings::EXAMPLES,
+    super::tracking::EXAMPLES,
+    super::window::EXAMPLES,
+];
+
+/// Registered names that the catalogue documents under another entry
+const ALIASES: &[(&str, &str)] = &[
+    ("chunk_by_size", "chunk_events"),
+    ("duration_from_seconds", "duration_from_<unit>"),
+    ("duration_from_minutes", "duration_from_<unit>"),
+    ("duration_from_hours", "duration_from_<unit>"),
+    ("duration_from_days", "duration_from_<unit>"),
+    ("duration_from_milliseconds", "duration_from_<unit>"),
+    ("duration_from_nanoseconds", "duration_from_<unit>"),
+];
+
+/// One function of the catalogue, as shown by `--help-function` and
+/// `--help-functions --json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionHelp {
+    pub name: String,
+    /// Catalogue section, e.g. "STRING FUNCTIONS"
+    pub category: &'static str,
+    /// Where the function can be used, e.g. "requires --metrics"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub availability: Option<&'static str>,
+    pub signatures: Vec<&'static str>,
+    pub summary: &'static str,
+    /// Parameter notes and the summaries of further signatures
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<&'static str>,
+}
 
-    let mut out = String::new();
-    let mut current_section: Option<&str> = None;
-    let mut section_printed = false;
-
-    let mut i = 0;
-    while i < lines.len() {
-        let line = lines[i];
-
-        if is_header(line) {
-            // The leading title is not a real section; skip it.
-            if line.trim() == "Available Rhai Functions:" {
-                i += 1;
-                continue;
+impl FunctionHelp {
+    fn matches(&self, contains: &impl Fn(&str) -> bool) -> bool {
+        contains(&self.name)
+            || contains(self.category)
+            || contains(self.summary)
+            || self.signatures.iter().any(|s| contains(s))
+            || self.details.iter().any(|d| contains(d))
+    }
+}
+
+/// The function catalogue per function, in listing order.
+///
+/// A catalogue entry covering several functions (`dt.hour(), dt.minute()`)
+/// yields one item per function; entries of the same function within a
+/// section are merged into one item.
+pub fn function_registry() -> Vec<FunctionHelp> {
+    let mut functions: Vec<FunctionHelp> = Vec::new();
+
+    for section in Section::ALL {
+        for doc in section.docs() {
+            // Operator forms (`mod(a, b)`, `a % b`) stay with the function
+            // they spell, or name the item when on their own
+            let (named, operators): (Vec<&'static str>, Vec<&'static str>) = doc
+                .signatures
+                .iter()
+                .copied()
+                .partition(|signature| function_name(signature).is_some());
+            let mut entries: Vec<(String, Vec<&'static str>)> = named
+                .iter()
+                .map(|signature| {
+                    let name = function_name(signature).unwrap_or_default();
+                    (name.to_string(), vec![*signature])
+                })
+                .collect();
+            match entries.first_mut() {
+                Some((_, signatures)) => signatures.extend(operators),
+                None => entries.push((doc.signatures.join(", "), doc.signatures.to_vec())),
             }
-            current_section = Some(line);
-            section_printed = false;
-            i += 1;
-            continue;
-        }
 
-        if line.is_empty() {
-            i += 1;
-            continue;
+            for (name, signatures) in entries {
+                let details = doc.details.iter().map(|detail| detail.to_string());
+                if let Some(function) = functions
+                    .iter_mut()
+                    .find(|function| function.name == name && function.category == section.title())
+                {
+                    function
+                        .details
+                        .push(format!("{}: {}", signatures.join(", "), doc.summary));
+                    function.details.extend(details);
+                    function.signatures.extend(signatures);
+                    continue;
+                }
+                functions.push(FunctionHelp {
+                    examples: examples_for(&name),
+                    name,
+                    category: section.title(),
+                    availability: section.availability(),
+                    signatures,
+                    summary: doc.summary,
+                    details: details.collect(),
+                });
+            }
         }
+    }
 
-        // Start of a function entry: this line plus any indented continuations.
-        let entry_start = i;
-        i += 1;
-        while i < lines.len() && lines[i].starts_with(char::is_whitespace) && !lines[i].is_empty() {
-            i += 1;
-        }
-        let entry = &lines[entry_start..i];
+    functions
+}
 
-        let header_matches = matches!(current_section, Some(h) if contains(h));
-        let entry_matches = entry.iter().any(|l| contains(l));
+/// Function name of a signature, without its receiver (`text.after(...)` is
+/// `after`). `None` for operators and indexers.
+fn function_name(signature: &str) -> Option<&str> {
+    let head = signature.split('(').next().unwrap_or(signature).trim();
+    let name = head.rsplit('.').next().unwrap_or(head);
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '<' | '>'));
+    valid.then_some(name)
+}
 
-        if header_matches || entry_matches {
-            if !section_printed {
-                if let Some(sec) = current_section {
-                    out.push('\n');
-                    out.push_str(sec);
-                    out.push('\n');
-                }
-                section_printed = true;
-            }
-            for l in entry {
-                out.push_str(l);
-                out.push('\n');
+fn examples_for(name: &str) -> Vec<&'static str> {
+    MODULE_EXAMPLES
+        .iter()
+        .flat_map(|examples| examples.iter())
+        .find(|(function, _)| *function == name)
+        .map(|(_, lines)| lines.to_vec())
+        .unwrap_or_default()
+}
+
+/// Registry entries for `name`, which may carry a receiver (`text.after`) or
+/// be an alias. Several entries come back when sections share a name (e.g.
+/// `span.start` and `window.start`).
+pub fn find_function(name: &str) -> Vec<FunctionHelp> {
+    let name = function_name(name).unwrap_or(name);
+    let name = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map_or(name, |(_, target)| *target);
+    function_registry()
+        .into_iter()
+        .filter(|doc| doc.name == name)
+        .collect()
+}
+
+/// Detailed help for one function: all signatures, notes and examples.
+/// Returns `None` when no function has that name.
+pub fn function_detail_text(name: &str) -> Option<String> {
+    let docs = find_function(name);
+    if docs.is_empty() {
+        return None;
+    }
+    let mut out = String::new();
+    for doc in docs {
+        out.push('\n');
+        out.push_str(&doc.name);
+        out.push_str(" - ");
+        out.push_str(doc.category);
+        if let Some(availability) = doc.availability {
+            out.push_str(&format!(" ({})", availability));
+        }
+        out.push_str("\n\n");
+        for signature in &doc.signatures {
+            out.push_str(&format!("  {}\n", signature));
+        }
+        out.push('\n');
+        out.push_str(doc.summary);
+        out.push('\n');
+        for detail in &doc.details {
+            out.push_str(&format!("  {}\n", detail));
+        }
+        if !doc.examples.is_empty() {
+            out.push_str("\nExamples:\n");
+            for example in &doc.examples {
+                out.push_str(&format!("  {}\n", example));
             }
         }
     }
+    Some(out)
+}
 
-    out
+/// Names of functions whose name contains `keyword`, for "did you mean" hints
+pub fn similar_function_names(keyword: &str) -> Vec<String> {
+    let contains = smartcase_matcher(keyword);
+    let mut names: Vec<String> = Vec::new();
+    for doc in function_registry() {
+        if contains(&doc.name) && !names.contains(&doc.name) {
+            names.push(doc.name);
+        }
+    }
+    names
+}
+
+/// The registry as a JSON array, optionally limited to entries matching a
+/// smartcase keyword (same rules as [`filter_help_text`]).
+pub fn functions_json(filter: Option<&str>) -> String {
+    let docs: Vec<FunctionHelp> = match filter {
+        Some(keyword) => {
+            let contains = smartcase_matcher(keyword);
+            function_registry()
+                .into_iter()
+                .filter(|doc| doc.matches(&contains))
+                .collect()
+        }
+        None => function_registry(),
+    };
+    serde_json::to_string_pretty(&docs).unwrap_or_else(|_| "[]".to_string())
 }
 
 /// Generate practical examples for common log analysis patterns
@@ -773,6 +988,116 @@ For other help topics: kelora -h
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn every_registered_function_is_documented() {
+        let mut engine = rhai::Engine::new();
+        super::super::register_all_functions(&mut engine);
+
+        let mut documented: BTreeSet<String> = function_registry()
+            .into_iter()
+            .map(|doc| doc.name)
+            .collect();
+        documented.extend(ALIASES.iter().map(|(alias, _)| alias.to_string()));
+        // Removed functions kept only to point at their replacements, and the
+        // --parallel stand-in for state
+        let unlisted = ["track_count", "track_bucket", "insert"];
+
+        let missing: BTreeSet<String> = engine
+            .gen_fn_signatures(false)
+            .iter()
+            .filter_map(|signature| signature.split('(').next())
+            .map(str::trim)
+            // Operators, getters and indexers are not catalogue entries
+            .filter(|name| function_name(name) == Some(*name))
+            .filter(|name| !documented.contains(*name) && !unlisted.contains(name))
+            .map(str::to_string)
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "functions missing from --help-functions: {missing:?}"
+        );
+    }
+
+    #[test]
+    fn listing_aligns_summaries_and_details() {
+        let help = generate_help_text();
+        assert!(help
+            .contains("\ntext.after(delimiter [,nth])         Text after occurrence of delimiter"));
+        assert!(help.contains(&format!(
+            "\n{}Equivalent to: text.after(start, nth).before(end)\n",
+            " ".repeat(SIGNATURE_WIDTH)
+        )));
+        // A signature wider than the column keeps one space before its summary
+        assert!(help.contains(
+            "\ntext.extract_regexes(pattern [,group]) Extract all regex matches as array\n"
+        ));
+        assert!(help.contains("\nmod(a, b), a % b"));
+        assert!(help
+            .contains("\nSTATE MANAGEMENT (sequential mode only; errors in --parallel mode):\n"));
+        assert!(help
+            .contains("\nTRACKING/METRICS FUNCTIONS (requires --metrics):\nAll track_* functions"));
+    }
+
+    #[test]
+    fn registry_splits_merges_and_resolves_entries() {
+        let after = find_function("text.after");
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].category, "STRING FUNCTIONS");
+        assert_eq!(after[0].signatures, vec!["text.after(delimiter [,nth])"]);
+        assert!(!after[0].examples.is_empty());
+
+        // Several functions in one entry
+        let minute = find_function("minute");
+        assert_eq!(minute[0].signatures, vec!["dt.minute()"]);
+        assert_eq!(minute[0].summary, "Extract time components");
+
+        // Operator forms stay with their function
+        assert_eq!(
+            find_function("mod")[0].signatures,
+            vec!["mod(a, b)", "a % b"]
+        );
+
+        // Entries of one function within a section are merged
+        let get = find_function("get");
+        let state_get = get
+            .iter()
+            .find(|doc| doc.category == "STATE MANAGEMENT")
+            .unwrap();
+        assert_eq!(state_get.signatures.len(), 2);
+        assert_eq!(
+            state_get.availability,
+            Some("sequential mode only; errors in --parallel mode")
+        );
+        let to_float_or = find_function("to_float_or");
+        assert_eq!(
+            to_float_or[0].summary,
+            "Convert value to float with fallback"
+        );
+        assert_eq!(
+            to_float_or[0].details,
+            vec!["to_float_or(value, thousands, decimal, default): Parse float with separators and fallback"]
+        );
+
+        assert_eq!(find_function("chunk_by_size")[0].name, "chunk_events");
+        assert!(find_function("no_such_function").is_empty());
+    }
+
+    #[test]
+    fn functions_json_lists_filtered_entries() {
+        let json: serde_json::Value =
+            serde_json::from_str(&functions_json(Some("extract_regex"))).unwrap();
+        let names: Vec<&str> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|doc| doc["name"].as_str().unwrap())
+            .collect();
+        assert!(names.contains(&"extract_regex"));
+        assert!(names.contains(&"extract_regexes"));
+        assert!(!names.contains(&"after"));
+    }
 
     #[test]
     fn lowercase_keyword_is_case_insensitive() {
@@ -1,4 +1,5 @@
 use crate::drain::{self, DrainConfig};
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use crate::rhai_functions::strings::is_parallel_mode;
 use rhai::{Dynamic, Engine, EvalAltResult, Map, Position};
 
@@ -189,6 +190,42 @@ fn drain_template_id(template: &str) -> String {
     drain::generate_template_id(template)
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    ("drain_template", &[r#"let r = drain_template(e.message);"#]),
+    (
+        "drain_templates",
+        &[r#"let templates = drain_templates();"#],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Drain,
+        &["drain_template(text [,options])"],
+        "Add line to Drain model; returns {template, template_id, count,",
+    )
+    .with_details(&["is_new, sample, first_line, last_line}"]),
+    FunctionDoc::new(
+        Section::Drain,
+        &["drain_templates()"],
+        "Return array of templates with same fields (except is_new)",
+    )
+    .with_details(&[
+        "Default filters: ipv4_port, ipv4, ipv6, email, url, fqdn, uuid,",
+        "mac, md5, sha1, sha256, path, oauth, function, hexcolor, version,",
+        "hexnum, duration, timestamp, date, time, num",
+        "For PII (credit_card/ssn/phone), pre-mask with normalized() before drain_template()",
+        "Options: depth, max_children, similarity, filters, line_num",
+    ]),
+    FunctionDoc::new(
+        Section::Drain,
+        &["drain_template_id(template)"],
+        "Stable ID for a template string (the template_id field)",
+    ),
+];
+
 pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("drain_template", drain_template_simple);
     engine.register_fn("drain_template", drain_template_with_options);
@@ -1,3 +1,4 @@
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use rhai::{Array, Dynamic, Engine, Map};
 use std::cell::{Cell, RefCell};
 
@@ -196,6 +197,24 @@ fn emit_each_impl(
     Ok(Dynamic::from(emitted))
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[(
+    "emit_each",
+    &[
+        r#"emit_each(e.users)  // Each user becomes an event"#,
+        r#"emit_each(e.items, #{batch_id: e.batch_id})  // Add batch_id to each"#,
+        r#"let count = emit_each(e.batch_items, #{batch_id: e.id})"#,
+    ],
+)];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[FunctionDoc::new(
+    Section::Events,
+    &["emit_each(array [,base_map])"],
+    "Fan out array elements as separate events (returns emitted count)",
+)
+.with_details(&["Per-event stages only (-e/--exec, --filter); errors in --begin/--end"])];
+
 /// Register emit functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("emit_each", emit_each_single);
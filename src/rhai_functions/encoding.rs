@@ -2,6 +2,7 @@
 //!
 //! Includes base64/hex, URL encoding/decoding, and HTML escaping.
 
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use rhai::Engine;
 use serde::Serialize;
 
@@ -153,6 +154,98 @@ fn dynamic_to_json_value(value: &rhai::Dynamic) -> serde_json::Value {
     serde_json::Value::String(format!("{:?}", value))
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    ("decode_b64", &[r#"e.decoded = e.payload.decode_b64()"#]),
+    ("decode_hex", &[r#"e.bytes = e.hex_string.decode_hex()"#]),
+    ("decode_url", &[r#"e.decoded = e.url_param.decode_url()"#]),
+    ("encode_b64", &[r#"e.encoded = e.data.encode_b64()"#]),
+    ("encode_hex", &[r#"e.hex = e.bytes.encode_hex()"#]),
+    (
+        "encode_url",
+        &[r#"e.encoded = e.param.encode_url()  // "hello world" → "hello%20world""#],
+    ),
+    (
+        "escape_html",
+        &[r#"e.safe = e.user_input.escape_html()  // "<script>" → "&lt;script&gt;""#],
+    ),
+    ("escape_json", &[r#"e.escaped = e.text.escape_json()"#]),
+    (
+        "to_json",
+        &[
+            r#"e.payload = e.data.to_json()"#,
+            r#"e.readable = e.data.to_json(true)  // Pretty-printed"#,
+        ],
+    ),
+    (
+        "unescape_html",
+        &[r#"e.text = e.html_entity.unescape_html()"#],
+    ),
+    (
+        "unescape_json",
+        &[r#"e.unescaped = e.json_string.unescape_json()"#],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.decode_b64()"],
+        "Decode base64 string to text",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.decode_hex()"],
+        "Decode hexadecimal string to text",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.decode_url()"],
+        "Decode URL-encoded string",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.encode_b64()"],
+        "Encode text to base64 string",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.encode_hex()"],
+        "Encode text to hexadecimal string",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.encode_url()"],
+        "URL-encode text (percent encoding)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.escape_html()"],
+        r#"Escape HTML special characters (&, <, >, ", ')"#,
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.escape_json()"],
+        "Escape JSON special characters",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.unescape_html()"],
+        "Unescape HTML entities to text",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.unescape_json()"],
+        "Unescape JSON escape sequences",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.to_json([indent])"],
+        "Convert map to JSON string (indent: spaces for pretty-printing, 0/omit for compact)",
+    ),
+];
+
 /// Register encoding/decoding functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     // Base64 encoding/decoding functions
@@ -1,4 +1,6 @@
-use rhai::Engine;
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
+use crate::rhai_functions::safety::require_unsandboxed;
+use rhai::{Engine, EvalAltResult, ImmutableString, NativeCallContext};
 
 /// Get an environment variable value, returning empty string if not found
 fn get_env_impl(var: &str) -> String {
@@ -10,10 +12,41 @@ fn get_env_with_default_impl(var: &str, default: &str) -> String {
     std::env::var(var).unwrap_or_else(|_| default.to_string())
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[(
+    "get_env",
+    &[
+        r#"e.branch = get_env("CI_BRANCH", "main")"#,
+        r#"e.build_id = get_env("BUILD_ID")"#,
+    ],
+)];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[FunctionDoc::new(
+    Section::Utility,
+    &["get_env(var [,default])"],
+    "Get environment variable with optional default",
+)];
+
 /// Register environment functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
-    engine.register_fn("get_env", get_env_impl);
-    engine.register_fn("get_env", get_env_with_default_impl);
+    engine.register_fn(
+        "get_env",
+        |context: NativeCallContext, var: ImmutableString| -> Result<String, Box<EvalAltResult>> {
+            require_unsandboxed(&context, "get_env")?;
+            Ok(get_env_impl(&var))
+        },
+    );
+    engine.register_fn(
+        "get_env",
+        |context: NativeCallContext,
+         var: ImmutableString,
+         default: ImmutableString|
+         -> Result<String, Box<EvalAltResult>> {
+            require_unsandboxed(&context, "get_env")?;
+            Ok(get_env_with_default_impl(&var, &default))
+        },
+    );
 }
 
 #[cfg(test)]
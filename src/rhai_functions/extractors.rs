@@ -3,6 +3,7 @@
 //! Provides functions for extracting patterns (IPs, URLs, emails, JSON) from text.
 
 use crate::event::json_to_dynamic;
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use regex::Regex;
 use rhai::{Array, Dynamic, Engine};
 use std::sync::LazyLock;
@@ -435,6 +436,95 @@ fn extract_jsons_impl(text: &str) -> Array {
 // Registration
 // ============================================================================
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "extract_domain",
+        &[
+            r#"e.domain = "https://api.example.com/path".extract_domain()  // "example.com""#,
+            r#"e.mail_domain = "user@corp.example.com".extract_domain()  // "corp.example.com""#,
+        ],
+    ),
+    (
+        "extract_email",
+        &[
+            r#"e.contact = e.message.extract_email()  // First email"#,
+            r#"e.sender = e.log.extract_email(1)  // First email"#,
+            r#"e.recipient = e.log.extract_email(-1)  // Last email"#,
+        ],
+    ),
+    (
+        "extract_emails",
+        &[
+            r#"e.all_contacts = e.message.extract_emails()  // ["alice@example.com", "bob@test.org"]"#,
+        ],
+    ),
+    (
+        "extract_ip",
+        &[
+            r#"e.client_ip = e.headers.extract_ip()  // First IP"#,
+            r#"e.origin_ip = e.forwarded.extract_ip(-1)  // Last IP"#,
+        ],
+    ),
+    (
+        "extract_ips",
+        &[r#"e.all_ips = e.headers.extract_ips()  // ["192.168.1.1", "10.0.0.1"]"#],
+    ),
+    (
+        "extract_url",
+        &[r#"e.link = e.message.extract_url()  // First URL"#],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_domain()"],
+        "Extract domain from URL or email address",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_email([nth])"],
+        "Extract email address from text (nth: 1=first, -1=last)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_emails()"],
+        "Extract all email addresses as array",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_ip([nth])"],
+        "Extract IP address from text (nth: 1=first, -1=last)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_ips()"],
+        "Extract all IP addresses as array",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_json([nth])"],
+        "Extract JSON object/array from text (nth: 1=first, -1=last)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_jsons()"],
+        "Extract all JSON objects/arrays from text as array of strings",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_url([nth])"],
+        "Extract URL from text (nth: 1=first, -1=last)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_urls()"],
+        "Extract all URLs as array",
+    ),
+];
+
 /// Register all extraction functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     // IP extraction
@@ -1,5 +1,7 @@
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
+use crate::rhai_functions::safety::require_unsandboxed;
 use anyhow::{Context, Result};
-use rhai::{Array, Engine, ImmutableString};
+use rhai::{Array, Engine, EvalAltResult, ImmutableString, NativeCallContext};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, OpenOptions};
@@ -34,6 +36,8 @@ pub enum FileOp {
     Append { path: PathBuf, payload: Vec<u8> },
 }
 
+type RhaiResult<T> = std::result::Result<T, Box<EvalAltResult>>;
+
 /// Shared runtime configuration for all threads.
 static RUNTIME_CONFIG: LazyLock<RwLock<RuntimeConfig>> =
     LazyLock::new(|| RwLock::new(RuntimeConfig::default()));
@@ -56,13 +60,82 @@ thread_local! {
     static PENDING_OPS: RefCell<Vec<FileOp>> = const { RefCell::new(Vec::new()) };
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "append_file",
+        &[
+            r#"append_file("errors.log", e.message)"#,
+            r#"append_file("batch.log", [e.line1, e.line2, e.line3])"#,
+        ],
+    ),
+    (
+        "mkdir",
+        &[r#"mkdir("logs")"#, r#"mkdir("deep/nested/path", true)"#],
+    ),
+    ("truncate_file", &[r#"truncate_file("output.log")"#]),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::FileOutput,
+        &["append_file(path, text_or_array)"],
+        "Append line(s) to file; arrays append one line per element",
+    ),
+    FunctionDoc::new(
+        Section::FileOutput,
+        &["mkdir(path [,recursive])"],
+        "Create directory (set recursive=true to create parents)",
+    ),
+    FunctionDoc::new(
+        Section::FileOutput,
+        &["truncate_file(path)"],
+        "Create or zero-length a file for fresh output",
+    ),
+];
+
 /// Register file operation helpers with the Rhai engine.
+/// Every helper is refused under `--sandbox`, whatever `--allow-fs-writes` says.
 pub fn register_functions(engine: &mut Engine) {
-    engine.register_fn("mkdir", mkdir_single);
-    engine.register_fn("mkdir", mkdir_with_parents);
-    engine.register_fn("truncate_file", truncate_file);
-    engine.register_fn("append_file", append_file_string);
-    engine.register_fn("append_file", append_file_array);
+    engine.register_fn(
+        "mkdir",
+        |context: NativeCallContext, path: ImmutableString| -> RhaiResult<bool> {
+            require_unsandboxed(&context, "mkdir")?;
+            Ok(mkdir_single(path))
+        },
+    );
+    engine.register_fn(
+        "mkdir",
+        |context: NativeCallContext, path: ImmutableString, recursive: bool| -> RhaiResult<bool> {
+            require_unsandboxed(&context, "mkdir")?;
+            Ok(mkdir_with_parents(path, recursive))
+        },
+    );
+    engine.register_fn(
+        "truncate_file",
+        |context: NativeCallContext, path: ImmutableString| -> RhaiResult<bool> {
+            require_unsandboxed(&context, "truncate_file")?;
+            Ok(truncate_file(path))
+        },
+    );
+    engine.register_fn(
+        "append_file",
+        |context: NativeCallContext,
+         path: ImmutableString,
+         content: ImmutableString|
+         -> RhaiResult<bool> {
+            require_unsandboxed(&context, "append_file")?;
+            Ok(append_file_string(path, content))
+        },
+    );
+    engine.register_fn(
+        "append_file",
+        |context: NativeCallContext, path: ImmutableString, items: Array| -> RhaiResult<bool> {
+            require_unsandboxed(&context, "append_file")?;
+            Ok(append_file_array(path, items))
+        },
+    );
 }
 
 /// Update runtime configuration (must be called before executing scripts that use file ops).
@@ -102,6 +175,8 @@ pub fn execute_ops(ops: &[FileOp]) -> Result<()> {
         return Ok(());
     }
 
+    // Ops are only recorded by engines outside the sandbox: every helper calls
+    // require_unsandboxed, which reads the recording engine's own policy
     let runtime = get_runtime_config();
     if !runtime.allow_fs_writes {
         return Ok(());
@@ -1,4 +1,5 @@
-use rhai::{Array, Dynamic, Engine};
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
+use rhai::{Array, Dynamic, Engine, EvalAltResult};
 use std::sync::atomic::{AtomicBool, Ordering};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
@@ -28,6 +29,224 @@ fn wrap(text: &str, code: &str) -> String {
     }
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "bar",
+        &[
+            r#"bar(7, 10, 10)  // "███████   ""#,
+            r#"bar(3, 8, 4)  // "█▌  ""#,
+            r#"bar(0.42, 1, 10)  // "████▏     ""#,
+        ],
+    ),
+    (
+        "bold",
+        &[
+            r#""header".bold()"#,
+            r#""CRITICAL".bold().red()  // bold red"#,
+        ],
+    ),
+    (
+        "center",
+        &[
+            r#""hi".center(6)  // "  hi  ""#,
+            r#"" TITLE ".center(20, '=')  // "====== TITLE =======""#,
+        ],
+    ),
+    (
+        "dim",
+        &[
+            r#"e.timestamp = e.timestamp.dim()"#,
+            r#""meta".dim().cyan()  // dim cyan"#,
+        ],
+    ),
+    (
+        "format_bytes",
+        &[
+            r#"format_bytes(1_500_000)  // "1.5 MB""#,
+            r#"format_bytes(1023, "binary")  // "1023 B""#,
+            r#"format_bytes(1024, "binary")  // "1.0 KiB""#,
+        ],
+    ),
+    (
+        "format_decimals",
+        &[
+            r#"format_decimals(1.0 / 3.0, 3)  // "0.333""#,
+            r#"format_decimals(1.0, 2)  // "1.00""#,
+            r#"format_decimals(42.987, 0)  // "43""#,
+        ],
+    ),
+    (
+        "format_duration_human",
+        &[
+            r#"format_duration_human(7380)  // "2h3m""#,
+            r#"format_duration_human(90061)  // "1d1h1m1s""#,
+            r#"format_duration_human(0.25)  // "250ms""#,
+        ],
+    ),
+    (
+        "format_percent",
+        &[
+            r#"format_percent(0.042, 1)  // "4.2%""#,
+            r#"format_percent(0.5, 0)  // "50%""#,
+            r#"format_percent(e.errors.to_float() / e.total, 2)  // "3.14%""#,
+        ],
+    ),
+    ("green", &[r#""OK".green()"#]),
+    (
+        "human_bytes",
+        &[
+            r#"human_bytes(1536)  // "1.5 KiB""#,
+            r#"human_bytes(1073741824)  // "1.0 GiB""#,
+            r#"e.size_h = human_bytes(e.bytes)"#,
+        ],
+    ),
+    (
+        "human_bytes_si",
+        &[
+            r#"human_bytes_si(1500)  // "1.5 KB""#,
+            r#"human_bytes_si(1_500_000_000)  // "1.5 GB""#,
+            r#"e.size_h = human_bytes_si(e.bytes)"#,
+        ],
+    ),
+    (
+        "ljust",
+        &[
+            r#""hi".ljust(5)  // "hi   ""#,
+            r#""ERROR".ljust(8, '.')  // "ERROR...""#,
+        ],
+    ),
+    ("red", &[r#""ERROR".red()"#]),
+    (
+        "rjust",
+        &[
+            r#""42".rjust(5)  // "   42""#,
+            r#""42".rjust(5, '0')  // "00042""#,
+        ],
+    ),
+    (
+        "shorten",
+        &[
+            r#""hello world".shorten(8)  // "hello w…""#,
+            r#""hello world".shorten(8, "...")  // "hello...""#,
+            r#""hello world".shorten(5, "")  // "hello""#,
+        ],
+    ),
+    (
+        "shorten_middle",
+        &[
+            r#"path.shorten_middle(30)  // "/home/user/proj…formatting.rs""#,
+            r#"path.shorten_middle(30, "...")  // ASCII marker"#,
+        ],
+    ),
+    (
+        "sparkline",
+        &[r#"sparkline([1, 4, 2, 8, 5, 7])  // "▁▄▂█▅▇""#],
+    ),
+    (
+        "yellow",
+        &[r#""WARN".yellow()"#, r#"e.level = e.level.yellow()"#],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Formatting,
+        &["bar(value, max, width)"],
+        "Render a horizontal bar of `width` cells showing value/max,",
+    )
+    .with_details(&[
+        "using Unicode eighth-blocks (▏▎▍▌▋▊▉█) for sub-cell resolution.",
+        "Pads with spaces so the result has exactly `width` display columns.",
+        "Values outside 0..max are clamped; max<=0 renders empty.",
+        "For ratios in 0.0–1.0 (error rate, CPU fraction, etc.), set max to 1.0.",
+    ]),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["format_decimals(value, decimals)"],
+        "Format number with exactly N digits after the decimal point (returns string)",
+    ),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["format_percent(ratio, decimals)"],
+        "Format ratio as percentage string with N decimals and '%' suffix (returns string)",
+    )
+    .with_details(&[r#"Input is multiplied by 100, so pass 0.042 to render "4.2%"."#]),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["human_bytes(n)"],
+        "Format byte count with binary/IEC units (1024-based): B, KiB, MiB, GiB, ...",
+    ),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["human_bytes_si(n)"],
+        "Format byte count with decimal/SI units (1000-based): B, KB, MB, GB, ...",
+    ),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["format_bytes(n [,units])"],
+        r#"Format byte count, decimal by default ("1.5 MB"); units: "decimal" or "binary""#,
+    ),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["format_duration_human(seconds)"],
+        r#"Format seconds as a compact compound duration (e.g., "2h3m", "1d4h", "250ms")"#,
+    ),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["sparkline(array)"],
+        "Render an array of numbers as a single-line sparkline (▁▂▃▄▅▆▇█)",
+    )
+    .with_details(&[
+        "scaled to 0..max(array). Negatives and non-numerics render as space.",
+        r#"Empty arrays return ""."#,
+    ]),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["text.bold()", "text.dim()"],
+        r#"Chainable: "X".bold().red() renders as bold red."#,
+    ),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["text.blue()", "text.cyan()", "text.magenta()"],
+        "Wrap text with ANSI color/style; resets at end. Returns text unchanged",
+    )
+    .with_details(&["when colors are disabled (non-TTY output, NO_COLOR, --no-color)."]),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["text.ljust(n [,fill])"],
+        "Left-justify: pad right to display width n (default fill: space). Unicode-width aware.",
+    ),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["text.rjust(n [,fill])"],
+        "Right-justify: pad left to display width n (default fill: space).",
+    ),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["text.center(n [,fill])"],
+        "Center within display width n (extra goes right on odd difference).",
+    ),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["text.shorten(n [,marker])"],
+        r#"If text exceeds width n, keep start and append marker (default "…")."#,
+    ),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["text.shorten_middle(n [,marker])"],
+        r#"If text exceeds width n, keep both ends, insert marker (default "…") in the middle."#,
+    )
+    .with_details(&["Useful for paths/URLs/IDs where both ends are informative."]),
+    FunctionDoc::new(
+        Section::Formatting,
+        &["text.red()", "text.green()", "text.yellow()"],
+        "Wrap text with ANSI color/style; resets at end. Returns text unchanged",
+    )
+    .with_details(&["when colors are disabled (non-TTY output, NO_COLOR, --no-color)."]),
+];
+
 pub fn register_functions(engine: &mut Engine) {
     // human_bytes: format byte count with binary (IEC) units (B, KiB, MiB, ...).
     // human_bytes_si: format byte count with decimal (SI) units (B, KB, MB, ...).
@@ -44,6 +263,35 @@ pub fn register_functions(engine: &mut Engine) {
         human_bytes_impl(n, true)
     });
 
+    // format_bytes: like human_bytes_si by default ("1.5 MB"); the optional
+    // second argument picks "decimal"/"si" or "binary"/"iec" units.
+    engine.register_fn("format_bytes", |n: i64| -> String {
+        human_bytes_impl(n as f64, true)
+    });
+    engine.register_fn("format_bytes", |n: f64| -> String {
+        human_bytes_impl(n, true)
+    });
+    engine.register_fn(
+        "format_bytes",
+        |n: i64, units: &str| -> Result<String, Box<EvalAltResult>> {
+            Ok(human_bytes_impl(n as f64, byte_units_are_si(units)?))
+        },
+    );
+    engine.register_fn(
+        "format_bytes",
+        |n: f64, units: &str| -> Result<String, Box<EvalAltResult>> {
+            Ok(human_bytes_impl(n, byte_units_are_si(units)?))
+        },
+    );
+
+    // format_duration_human: compact duration from seconds, e.g. "2h3m".
+    engine.register_fn("format_duration_human", |seconds: i64| -> String {
+        format_duration_human_impl(seconds as f64)
+    });
+    engine.register_fn("format_duration_human", |seconds: f64| -> String {
+        format_duration_human_impl(seconds)
+    });
+
     // format_decimals: format number as string with exactly N digits after the
     // decimal point. Returns a string.
     engine.register_fn("format_decimals", |value: f64, decimals: i64| -> String {
@@ -205,6 +453,62 @@ fn human_bytes_impl(n: f64, si: bool) -> String {
     }
 }
 
+/// Map the `units` argument of `format_bytes` to `human_bytes_impl`'s `si`
+/// flag.
+fn byte_units_are_si(units: &str) -> Result<bool, Box<EvalAltResult>> {
+    match units.to_ascii_lowercase().as_str() {
+        "decimal" | "si" => Ok(true),
+        "binary" | "iec" => Ok(false),
+        other => Err(format!(
+            "format_bytes: unknown units '{}' (expected \"decimal\" or \"binary\")",
+            other
+        )
+        .into()),
+    }
+}
+
+/// Format a number of seconds as a compact compound duration: `"2h3m"`,
+/// `"1d4h"`, `"45s"`. Zero components are left out, so `3600` is `"1h"`.
+///
+/// Fractional input is rounded to whole seconds, except below one second,
+/// where it renders in milliseconds (`0.25` → `"250ms"`). Negative values
+/// get a leading minus; NaN and infinities render as for `human_bytes`.
+fn format_duration_human_impl(seconds: f64) -> String {
+    if seconds.is_nan() {
+        return "NaN".to_string();
+    }
+    if seconds.is_infinite() {
+        return if seconds.is_sign_negative() {
+            "-inf".to_string()
+        } else {
+            "inf".to_string()
+        };
+    }
+
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    let abs = seconds.abs();
+    if abs < 1.0 {
+        let ms = (abs * 1000.0).round() as u64;
+        return match ms {
+            0 => "0s".to_string(),
+            // 0.9996s rounds up to a whole second
+            1000 => format!("{}1s", sign),
+            _ => format!("{}{}ms", sign, ms),
+        };
+    }
+
+    let mut remaining = abs.round().min(u64::MAX as f64) as u64;
+    let mut out = String::from(sign);
+    for (unit, size) in [("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)] {
+        let count = remaining / size;
+        remaining %= size;
+        if count > 0 {
+            out.push_str(&format!("{}{}", count, unit));
+        }
+    }
+    out
+}
+
 /// Format a floating-point value as a string with exactly `decimals` digits
 /// after the decimal point. Negative decimal counts are treated as zero; very
 /// large values are capped at 20 to avoid pathological allocations.
@@ -513,6 +817,62 @@ mod tests {
         assert_eq!(human_bytes_impl(f64::NEG_INFINITY, false), "-inf");
     }
 
+    #[test]
+    fn test_format_bytes_units() {
+        assert!(byte_units_are_si("decimal").unwrap());
+        assert!(byte_units_are_si("SI").unwrap());
+        assert!(!byte_units_are_si("binary").unwrap());
+        assert!(!byte_units_are_si("iec").unwrap());
+        assert!(byte_units_are_si("metric").is_err());
+    }
+
+    #[test]
+    fn test_format_bytes_rounding_boundaries() {
+        let engine = {
+            let mut engine = Engine::new();
+            register_functions(&mut engine);
+            engine
+        };
+        let eval = |script: &str| engine.eval::<String>(script).unwrap();
+        assert_eq!(eval("format_bytes(1_500_000)"), "1.5 MB");
+        assert_eq!(eval("format_bytes(999)"), "999 B");
+        assert_eq!(eval("format_bytes(1000)"), "1.0 KB");
+        assert_eq!(eval("format_bytes(1023, \"binary\")"), "1023 B");
+        assert_eq!(eval("format_bytes(1024, \"binary\")"), "1.0 KiB");
+        assert_eq!(eval("format_bytes(1024, \"decimal\")"), "1.0 KB");
+        assert_eq!(eval("format_bytes(1572864.0, \"binary\")"), "1.5 MiB");
+        assert!(engine
+            .eval::<String>("format_bytes(1, \"nibbles\")")
+            .is_err());
+    }
+
+    #[test]
+    fn test_format_duration_human_compound() {
+        assert_eq!(format_duration_human_impl(0.0), "0s");
+        assert_eq!(format_duration_human_impl(45.0), "45s");
+        assert_eq!(format_duration_human_impl(60.0), "1m");
+        assert_eq!(format_duration_human_impl(3600.0), "1h");
+        assert_eq!(format_duration_human_impl(7380.0), "2h3m");
+        assert_eq!(format_duration_human_impl(7385.0), "2h3m5s");
+        assert_eq!(format_duration_human_impl(90_061.0), "1d1h1m1s");
+        assert_eq!(format_duration_human_impl(172_800.0), "2d");
+        assert_eq!(format_duration_human_impl(-90.0), "-1m30s");
+    }
+
+    #[test]
+    fn test_format_duration_human_rounding() {
+        assert_eq!(format_duration_human_impl(0.25), "250ms");
+        assert_eq!(format_duration_human_impl(0.0004), "0s");
+        assert_eq!(format_duration_human_impl(0.9996), "1s");
+        assert_eq!(format_duration_human_impl(59.4), "59s");
+        // Rounding carries into the next unit
+        assert_eq!(format_duration_human_impl(59.6), "1m");
+        assert_eq!(format_duration_human_impl(3599.5), "1h");
+        assert_eq!(format_duration_human_impl(-0.5), "-500ms");
+        assert_eq!(format_duration_human_impl(f64::NAN), "NaN");
+        assert_eq!(format_duration_human_impl(f64::INFINITY), "inf");
+    }
+
     #[test]
     fn test_format_decimals_basic() {
         assert_eq!(format_decimals_impl(1.23456, 2), "1.23");
@@ -2,6 +2,7 @@
 //!
 //! Includes fast hashes, cryptographic digests, and salted pseudonyms.
 
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use argon2::password_hash::{Salt, SaltString};
 use argon2::{Argon2, PasswordHasher};
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
@@ -68,27 +69,17 @@ fn warn_pseudonym_ephemeral() {
     }
 }
 
-/// Master key for pseudonymization (derived once at startup)
-static MASTER_KEY: LazyLock<MasterKeyState> = LazyLock::new(|| {
+/// Master key for pseudonymization (derived once, on first use)
+static MASTER_KEY: LazyLock<Result<MasterKeyState, String>> = LazyLock::new(|| {
     match std::env::var("KELORA_SECRET") {
-        Ok(secret) if !secret.is_empty() => {
-            match derive_master_key_from_secret(&secret) {
-                Ok(key) => {
-                    log_pseudonym_init("pseudonym: ON (stable; KELORA_SECRET)");
-                    MasterKeyState::Stable(key)
-                }
-                Err(e) => {
-                    // Always show fatal errors
-                    eprintln!("kelora: pseudonym init failed: {}", e);
-                    std::process::exit(1);
-                }
+        Ok(secret) if !secret.is_empty() => match derive_master_key_from_secret(&secret) {
+            Ok(key) => {
+                log_pseudonym_init("pseudonym: ON (stable; KELORA_SECRET)");
+                Ok(MasterKeyState::Stable(key))
             }
-        }
-        Ok(_) => {
-            // Always show fatal errors
-            eprintln!("kelora: KELORA_SECRET must not be empty");
-            std::process::exit(1);
-        }
+            Err(e) => Err(format!("pseudonym init failed: {}", e)),
+        },
+        Ok(_) => Err("KELORA_SECRET must not be empty".to_string()),
         Err(_) => {
             // Generate ephemeral key
             let mut key = [0u8; 32];
@@ -97,7 +88,7 @@ static MASTER_KEY: LazyLock<MasterKeyState> = LazyLock::new(|| {
             }
             warn_pseudonym_ephemeral();
             log_pseudonym_init("pseudonym: ON (ephemeral; not stable)");
-            MasterKeyState::Ephemeral(key)
+            Ok(MasterKeyState::Ephemeral(key))
         }
     }
 });
@@ -161,7 +152,7 @@ fn derive_domain_key(domain: &str) -> Result<[u8; 32], String> {
         }
     }
 
-    let master = MASTER_KEY.as_bytes();
+    let master = MASTER_KEY.as_ref().map_err(String::clone)?.as_bytes();
     let info = format!("kelora:v1:{}", domain);
 
     let hkdf = Hkdf::<Sha256>::new(None, master);
@@ -184,8 +175,12 @@ fn pseudonym_impl(value: &str, domain: &str) -> Result<String, Box<rhai::EvalAlt
         return Err("pseudonym: domain must be non-empty".into());
     }
 
-    // Force initialization of master key (triggers logging)
-    let _ = MASTER_KEY.as_bytes();
+    // Force initialization of master key (triggers logging). A bad
+    // KELORA_SECRET ends the run like a script exit(1).
+    if let Err(e) = MASTER_KEY.as_ref() {
+        let _ = crate::rhai_functions::process::exit_process(1, format!("kelora: {}", e).into());
+        return Err(format!("pseudonym: {}", e).into());
+    }
 
     let domain_key = derive_domain_key(domain)
         .map_err(|e| format!("pseudonym: domain key derivation failed: {}", e))?;
@@ -234,6 +229,44 @@ fn hash_default_impl(value: &str) -> Result<String, Box<rhai::EvalAltResult>> {
     hash_impl(value, "sha256")
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "hash",
+        &[
+            r#"e.checksum = e.content.hash()  // SHA-256"#,
+            r#"e.fast = e.data.hash("xxh3")  // Fast non-crypto hash"#,
+        ],
+    ),
+    (
+        "pseudonym",
+        &[
+            r#"e.user_alias = pseudonym(e.username, "users")"#,
+            r#"e.ip_alias = pseudonym(e.client_ip, "ips")"#,
+        ],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.bucket()"],
+        "Fast hash for sampling/grouping (returns INT for modulo operations)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.hash([algo])"],
+        "Hash with algorithm (default: sha256, also: xxh3); redact/anonymize a value",
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["pseudonym(value, domain)"],
+        "Domain-separated pseudonym to redact/anonymize/mask a value",
+    )
+    .with_details(&["(set KELORA_SECRET for stable output; else ephemeral per-run key)"]),
+];
+
 /// Register hashing functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     // bucket() - fast non-cryptographic hash for bucketing/sampling
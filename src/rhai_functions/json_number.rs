@@ -0,0 +1,118 @@
+//! JSON numbers that don't fit a Rhai int or float without loss.
+//!
+//! Trace IDs beyond the i64 range and decimals with more significant digits
+//! than an f64 holds are kept as their original text in a [`JsonNumber`].
+//! The JSON formatter writes that text back verbatim, so `-f json -F json`
+//! round-trips them exactly. Scripts can compare them for equality (with each
+//! other or with a string) and convert them to a string or, lossily, a float.
+
+use rhai::{Engine, ImmutableString};
+use std::fmt;
+
+/// A JSON number kept as its source text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonNumber {
+    pub text: String,
+}
+
+impl JsonNumber {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+
+    /// Nearest f64, for arithmetic that accepts the loss
+    pub fn to_f64(&self) -> f64 {
+        self.text.parse().unwrap_or(f64::NAN)
+    }
+}
+
+impl fmt::Display for JsonNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// Significant digits of a decimal number's text, without sign, point,
+/// exponent or leading and trailing zeros
+fn significant_digits(text: &str) -> String {
+    let mantissa = text.split(['e', 'E']).next().unwrap_or("");
+    mantissa
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>()
+        .trim_start_matches('0')
+        .trim_end_matches('0')
+        .to_string()
+}
+
+/// Whether `value`, the f64 serde_json read for the number `text`, lost
+/// digits. Integers outside the i64 range always count as lossy.
+pub fn f64_loses_precision(text: &str, value: f64) -> bool {
+    let is_integer = !text.contains(['.', 'e', 'E']);
+    if is_integer {
+        return text.parse::<i64>().is_err();
+    }
+    !value.is_finite() || significant_digits(text) != significant_digits(&value.to_string())
+}
+
+pub fn register_functions(engine: &mut Engine) {
+    engine.register_type_with_name::<JsonNumber>("JsonNumber");
+
+    engine.register_fn("to_string", |n: &mut JsonNumber| n.text.clone());
+    engine.register_fn("to_debug", |n: &mut JsonNumber| n.text.clone());
+    engine.register_fn("to_float", |n: &mut JsonNumber| n.to_f64());
+
+    engine.register_fn("==", |a: JsonNumber, b: JsonNumber| a == b);
+    engine.register_fn("!=", |a: JsonNumber, b: JsonNumber| a != b);
+    engine.register_fn("==", |a: JsonNumber, b: ImmutableString| {
+        a.text == b.as_str()
+    });
+    engine.register_fn("!=", |a: JsonNumber, b: ImmutableString| {
+        a.text != b.as_str()
+    });
+    engine.register_fn("==", |a: ImmutableString, b: JsonNumber| {
+        a.as_str() == b.text
+    });
+    engine.register_fn("!=", |a: ImmutableString, b: JsonNumber| {
+        a.as_str() != b.text
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhai::{Dynamic, Scope};
+
+    #[test]
+    fn test_f64_loses_precision() {
+        assert!(f64_loses_precision(
+            "9223372036854775808",
+            9.223372036854776e18
+        ));
+        assert!(!f64_loses_precision(
+            "-9223372036854775808",
+            -9.223372036854776e18
+        ));
+        let text = "0.123456789012345678";
+        assert!(f64_loses_precision(text, text.parse().unwrap()));
+        assert!(!f64_loses_precision("0.25", 0.25));
+        assert!(!f64_loses_precision("1.50", 1.5));
+        assert!(!f64_loses_precision("1e3", 1000.0));
+    }
+
+    #[test]
+    fn test_json_number_equality_and_strings_in_rhai() {
+        let mut engine = Engine::new();
+        register_functions(&mut engine);
+        let mut scope = Scope::new();
+        scope.push_dynamic("n", Dynamic::from(JsonNumber::new("9223372036854775808")));
+        let result: bool = engine
+            .eval_with_scope(
+                &mut scope,
+                r#"n == "9223372036854775808" && n.to_string() == "9223372036854775808"
+                   && type_of(n) == "JsonNumber" && n.to_float() > 9.2e18"#,
+            )
+            .unwrap();
+        assert!(result);
+    }
+}
@@ -1,8 +1,110 @@
 use crate::event::{flatten_dynamic, FlattenStyle};
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use indexmap::IndexMap;
 use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Position};
 use std::collections::HashSet;
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "drop",
+        &[r#"let trimmed = e.drop(["_raw", "_file", "_offset"])"#],
+    ),
+    (
+        "enrich",
+        &[r#"e.enrich(#{user: "default", level: "info"})  // Only adds if keys don't exist"#],
+    ),
+    (
+        "flatten_field",
+        &[r#"let flat = e.flatten_field("metadata")  // Flattens only e.metadata"#],
+    ),
+    (
+        "get",
+        &[
+            r#"let count = state.get("count")  // Returns () if not found"#,
+            r#"let current = state.get("count", 0)"#,
+            r#"state.set("count", (state.get("count") ?? 0) + 1)"#,
+        ],
+    ),
+    (
+        "keep",
+        &[r#"let shaped = e.keep(["service", "level", "msg"])"#],
+    ),
+    ("merge", &[r#"e.merge(#{status: "ok", timestamp: now()})"#]),
+    (
+        "rename_field",
+        &[r#"e.rename_field("old_name", "new_name")"#],
+    ),
+    (
+        "unflatten",
+        &[r#"let nested = e.flat.unflatten(".")  // {"a.b": 1} → {a: {b: 1}}"#],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.enrich(other_map)"],
+        "Merge another map, inserting only missing keys",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.flattened([style [,max_depth]])"],
+        "Return new flattened map from nested object",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &[r#"map.flatten_field("field_name")"#],
+        "Flatten just one field from the map",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &[r#"map.get("key" [,default])"#],
+        "Safe top-level field access with fallback",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &[r#"map.has("key")"#],
+        "Check if map contains key with non-unit value",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &[r#"map.keep(["field1", ...])"#],
+        "Return new map with only selected top-level fields",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.merge(other_map)"],
+        "Merge another map into this one (overwrites existing keys)",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &[r#"map.drop(["field1", ...])"#],
+        "Return new map without selected top-level fields",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &[r#"map.rename_field("old", "new")"#],
+        "Rename a field, returns true if successful",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.unflatten([separator])"],
+        "Reconstruct nested object from flat keys",
+    ),
+    FunctionDoc::new(
+        Section::Events,
+        &[r#"e.has("key")"#],
+        "Check if key exists and value is not ()",
+    ),
+    FunctionDoc::new(
+        Section::Events,
+        &[r#"e.rename_field("old", "new")"#],
+        "Rename field, returns true if successful",
+    ),
+];
+
 /// Registers map merge/enrich operators to the Rhai engine.
 pub fn register_functions(engine: &mut Engine) {
     // event.merge(map): overwrites existing keys
@@ -1,6 +1,88 @@
 use rhai::{Array, Dynamic, Engine, EvalAltResult, Position};
 
 use super::arrays::{determine_array_type, ArrayType};
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
+
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    ("clamp", &[r#"e.bounded = clamp(e.score, 0, 100)"#]),
+    ("lerp", &[r#"e.mid = lerp(e.low, e.high, 0.5)"#]),
+    (
+        "mean",
+        &[
+            r#"e.avg_latency = e.latencies.mean()"#,
+            r#"e.avg_score = [10, 20, 30].mean()  // 20.0"#,
+            r#"[10, "20", 30].mean()  // ERROR - mixed types rejected"#,
+        ],
+    ),
+    (
+        "scale",
+        &[r#"e.score = clamp(scale(e.latency_ms, 0, 2000, 0.0, 1.0), 0.0, 1.0)"#],
+    ),
+    ("stddev", &[r#"e.latency_stddev = e.latencies.stddev()"#]),
+    (
+        "sum",
+        &[
+            r#"e.total_bytes = e.requests.pluck_as_nums("bytes").sum()"#,
+            r#"e.total_errors = [10, 20, 30].sum()  // 60.0"#,
+            r#"[10, 20.5, 30].sum()  // 60.5"#,
+        ],
+    ),
+    (
+        "variance",
+        &[r#"e.latency_variance = e.latencies.variance()"#],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.mean()"],
+        "Calculate arithmetic mean of numeric array (rejects mixed types)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.stddev()"],
+        "Calculate standard deviation of numeric array (rejects mixed types)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.sum()"],
+        "Calculate sum of numeric values in array (rejects mixed types)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.variance()"],
+        "Calculate variance of numeric array (rejects mixed types)",
+    ),
+    FunctionDoc::new(
+        Section::Math,
+        &["clamp(value, min, max)"],
+        "Constrain value to be within min/max range",
+    ),
+    FunctionDoc::new(
+        Section::Math,
+        &["lerp(a, b, t)"],
+        "Linear interpolation: a + (b - a) * t",
+    ),
+    FunctionDoc::new(
+        Section::Math,
+        &["mod(a, b)", "a % b"],
+        "Modulo operation with division-by-zero protection",
+    ),
+    FunctionDoc::new(
+        Section::Math,
+        &["round_to(x, decimals)"],
+        "Round to N decimal places (negative N rounds to tens, hundreds, ...)",
+    ),
+    FunctionDoc::new(
+        Section::Math,
+        &["scale(x, in_lo, in_hi, out_lo, out_hi)"],
+        "Map x linearly from [in_lo, in_hi] onto [out_lo, out_hi] (unclamped;",
+    )
+    .with_details(&["returns out_lo when in_lo == in_hi)"]),
+];
 
 pub fn register_functions(engine: &mut Engine) {
     // Register modulo function since % operator seems to be missing
@@ -27,6 +109,16 @@ pub fn register_functions(engine: &mut Engine) {
     // Register clamp function for floats
     engine.register_fn("clamp", clamp_f64);
 
+    // Linear range mapping; Dynamic args so any int/float mix works
+    engine.register_fn("scale", scale_dynamic);
+    engine.register_fn("lerp", lerp_dynamic);
+
+    // Decimal rounding (does not clash with the DateTime `round_to(interval)`)
+    engine.register_fn("round_to", round_to_f64);
+    engine.register_fn("round_to", |value: i64, decimals: i64| -> f64 {
+        round_to_f64(value as f64, decimals)
+    });
+
     // Register statistical functions for arrays
     engine.register_fn("sum", sum_array);
     engine.register_fn("mean", mean_array);
@@ -300,6 +392,126 @@ fn clamp_f64(value: f64, min: f64, max: f64) -> Result<f64, Box<EvalAltResult>>
     Ok(value.clamp(min, max))
 }
 
+/// Convert a numeric Dynamic (int or float) to f64 for the range helpers.
+fn numeric_arg(fn_name: &str, arg_name: &str, value: &Dynamic) -> Result<f64, Box<EvalAltResult>> {
+    if let Ok(i) = value.as_int() {
+        Ok(i as f64)
+    } else if let Ok(f) = value.as_float() {
+        Ok(f)
+    } else {
+        Err(Box::new(EvalAltResult::ErrorRuntime(
+            format!(
+                "{fn_name}: {arg_name} must be a number, got {}",
+                value.type_name()
+            )
+            .into(),
+            Position::NONE,
+        )))
+    }
+}
+
+/// Linearly map a value from one range onto another
+///
+/// Computes where `value` sits inside `[in_lo, in_hi]` and returns the
+/// corresponding point in `[out_lo, out_hi]`. The result is not clamped, so
+/// values outside the input range extrapolate; wrap the call in `clamp()` if
+/// you need a hard bound. Either range may be inverted (`in_lo > in_hi`).
+///
+/// # Arguments
+/// * `value` - The value to map
+/// * `in_lo`, `in_hi` - Source range
+/// * `out_lo`, `out_hi` - Target range
+///
+/// # Returns
+/// The mapped value as f64. A degenerate input range (`in_lo == in_hi`)
+/// returns `out_lo` instead of dividing by zero.
+///
+/// # Examples
+/// ```rhai
+/// scale(5, 0, 10, 0, 100)         // 50.0
+/// scale(0.25, 0.0, 1.0, 10, 20)   // 12.5
+/// scale(15, 0, 10, 0, 100)        // 150.0 (extrapolated)
+/// scale(3, 3, 3, 0, 1)            // 0.0 (degenerate range)
+///
+/// // Normalize latency into a 0..1 score
+/// e.score = clamp(scale(e.latency_ms, 0, 2000, 0.0, 1.0), 0.0, 1.0);
+/// ```
+fn scale_dynamic(
+    value: Dynamic,
+    in_lo: Dynamic,
+    in_hi: Dynamic,
+    out_lo: Dynamic,
+    out_hi: Dynamic,
+) -> Result<f64, Box<EvalAltResult>> {
+    Ok(scale_impl(
+        numeric_arg("scale", "value", &value)?,
+        numeric_arg("scale", "in_lo", &in_lo)?,
+        numeric_arg("scale", "in_hi", &in_hi)?,
+        numeric_arg("scale", "out_lo", &out_lo)?,
+        numeric_arg("scale", "out_hi", &out_hi)?,
+    ))
+}
+
+fn scale_impl(value: f64, in_lo: f64, in_hi: f64, out_lo: f64, out_hi: f64) -> f64 {
+    let span = in_hi - in_lo;
+    if span == 0.0 {
+        return out_lo;
+    }
+    out_lo + (value - in_lo) / span * (out_hi - out_lo)
+}
+
+/// Linear interpolation between two values
+///
+/// Returns `a + (b - a) * t`. `t = 0` yields `a`, `t = 1` yields `b`; values
+/// outside `0..1` extrapolate.
+///
+/// # Examples
+/// ```rhai
+/// lerp(0, 100, 0.25)     // 25.0
+/// lerp(10.0, 20.0, 0.5)  // 15.0
+/// lerp(10, 0, 0.1)       // 9.0
+/// ```
+fn lerp_dynamic(a: Dynamic, b: Dynamic, t: Dynamic) -> Result<f64, Box<EvalAltResult>> {
+    let a = numeric_arg("lerp", "a", &a)?;
+    let b = numeric_arg("lerp", "b", &b)?;
+    let t = numeric_arg("lerp", "t", &t)?;
+    Ok(a + (b - a) * t)
+}
+
+/// Round a number to a fixed number of decimal places
+///
+/// Rounds half away from zero, like `round()`. Negative `decimals` round to
+/// the left of the decimal point (`-2` rounds to hundreds). Unlike
+/// `format_decimals()`, the result stays a number.
+///
+/// # Examples
+/// ```rhai
+/// round_to(4.56789, 2)    // 4.57
+/// round_to(-2.34567, 3)   // -2.346
+/// round_to(1234.5, -2)    // 1200.0
+/// round_to(7, 2)          // 7.0
+/// ```
+fn round_to_f64(value: f64, decimals: i64) -> f64 {
+    if !value.is_finite() {
+        return value;
+    }
+    // Clamp so the scaling factor stays finite (10^308 is the largest power
+    // of ten an f64 can hold).
+    let decimals = decimals.clamp(-308, 308) as i32;
+    let factor = 10f64.powi(decimals.abs());
+    if decimals >= 0 {
+        let scaled = value * factor;
+        // Past 2^53 every f64 is already an integer, so there is nothing left
+        // to round and the round-trip would only add error.
+        if !scaled.is_finite() || scaled.abs() >= 9_007_199_254_740_992.0 {
+            return value;
+        }
+        scaled.round() / factor
+    } else {
+        (value / factor).round() * factor
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -676,4 +888,88 @@ mod tests {
         // Numeric strings are not parsed as numbers
         assert!(mean_array(arr).is_err());
     }
+
+    #[test]
+    fn test_scale_linear() {
+        assert_eq!(scale_impl(5.0, 0.0, 10.0, 0.0, 100.0), 50.0);
+        assert_eq!(scale_impl(0.0, 0.0, 10.0, 0.0, 100.0), 0.0);
+        assert_eq!(scale_impl(10.0, 0.0, 10.0, 0.0, 100.0), 100.0);
+        assert_eq!(scale_impl(0.25, 0.0, 1.0, 10.0, 20.0), 12.5);
+    }
+
+    #[test]
+    fn test_scale_inverted_and_negative_ranges() {
+        // Inverted output range flips direction
+        assert_eq!(scale_impl(2.0, 0.0, 10.0, 100.0, 0.0), 80.0);
+        // Negative input range
+        assert_eq!(scale_impl(-5.0, -10.0, 0.0, 0.0, 1.0), 0.5);
+        // Values outside the input range extrapolate
+        assert_eq!(scale_impl(15.0, 0.0, 10.0, 0.0, 100.0), 150.0);
+    }
+
+    #[test]
+    fn test_scale_degenerate_range() {
+        assert_eq!(scale_impl(3.0, 3.0, 3.0, 0.0, 1.0), 0.0);
+        assert_eq!(scale_impl(42.0, 7.0, 7.0, 5.0, 9.0), 5.0);
+    }
+
+    #[test]
+    fn test_scale_dynamic_mixed_types() {
+        let result = scale_dynamic(
+            Dynamic::from(5i64),
+            Dynamic::from(0i64),
+            Dynamic::from(10.0f64),
+            Dynamic::from(0i64),
+            Dynamic::from(1.0f64),
+        )
+        .unwrap();
+        assert_eq!(result, 0.5);
+
+        let err = scale_dynamic(
+            Dynamic::from("5".to_string()),
+            Dynamic::from(0i64),
+            Dynamic::from(10i64),
+            Dynamic::from(0i64),
+            Dynamic::from(1i64),
+        )
+        .expect_err("string value should be rejected");
+        assert!(err.to_string().contains("scale: value must be a number"));
+    }
+
+    #[test]
+    fn test_lerp() {
+        let lerp = |a: f64, b: f64, t: f64| {
+            lerp_dynamic(Dynamic::from(a), Dynamic::from(b), Dynamic::from(t)).unwrap()
+        };
+        assert_eq!(lerp(0.0, 100.0, 0.25), 25.0);
+        assert_eq!(lerp(10.0, 20.0, 0.0), 10.0);
+        assert_eq!(lerp(10.0, 20.0, 1.0), 20.0);
+        assert_eq!(lerp(10.0, 0.0, 0.5), 5.0);
+    }
+
+    #[test]
+    fn test_round_to_decimals() {
+        assert_eq!(round_to_f64(4.56789, 2), 4.57);
+        assert_eq!(round_to_f64(2.5, 0), 3.0);
+        assert_eq!(round_to_f64(1.23456, 4), 1.2346);
+        assert_eq!(round_to_f64(7.0, 2), 7.0);
+    }
+
+    #[test]
+    fn test_round_to_negatives() {
+        // Negative values round half away from zero
+        assert_eq!(round_to_f64(-4.56789, 2), -4.57);
+        assert_eq!(round_to_f64(-2.5, 0), -3.0);
+        assert_eq!(round_to_f64(-0.0004, 3), -0.0);
+        // Negative decimals round left of the decimal point
+        assert_eq!(round_to_f64(1234.5, -2), 1200.0);
+        assert_eq!(round_to_f64(-1250.0, -2), -1300.0);
+    }
+
+    #[test]
+    fn test_round_to_extreme_decimals() {
+        assert_eq!(round_to_f64(1.5, 400), 1.5);
+        assert_eq!(round_to_f64(1.5, -400), 0.0);
+        assert!(round_to_f64(f64::NAN, 2).is_nan());
+    }
 }
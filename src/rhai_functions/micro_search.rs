@@ -1,4 +1,5 @@
 //! Micro search helpers for Rhai filters (`like`, `ilike`, `matches`)
+use crate::rhai_functions::docs::{FunctionDoc, Section};
 use lru::LruCache;
 use regex::{Regex, RegexBuilder};
 use rhai::{Engine, EvalAltResult, Position};
@@ -15,6 +16,25 @@ thread_local! {
     ));
 }
 
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.like(pattern)"],
+        "Glob match (*, ?) against entire string",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.ilike(pattern)"],
+        "Glob match with Unicode case folding (*, ?)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.matches(pattern)"],
+        "Regex search (cached; invalid pattern raises error)",
+    ),
+];
+
 pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("like", |text: &str, pattern: &str| like_impl(text, pattern));
     engine.register_fn("ilike", |text: &str, pattern: &str| {
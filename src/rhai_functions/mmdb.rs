@@ -0,0 +1,187 @@
+//! IP enrichment from MaxMind-format `.mmdb` databases (`--mmdb NAME=PATH`).
+//!
+//! Each database is opened once at startup and shared read-only by every
+//! engine, including parallel workers. It is exposed to scripts as a function
+//! named after the spec, so `--mmdb geo=GeoLite2-City.mmdb` registers
+//! `geo(ip) -> map`. The map holds the country, city, location and ASN fields
+//! the database has for the address; addresses it doesn't cover, and invalid
+//! input, give an empty map.
+
+use crate::config::MmdbConfig;
+use anyhow::{Context, Result};
+use maxminddb::Reader;
+use rhai::{Dynamic, Engine, Map};
+use serde::Deserialize;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+/// An open database and the name of its lookup function
+type NamedDatabase = (String, Arc<Reader<Vec<u8>>>);
+
+static DATABASES: RwLock<Vec<NamedDatabase>> = RwLock::new(Vec::new());
+
+/// Open every `--mmdb` database, replacing any opened before. A database that
+/// is missing or not in MaxMind format is an error.
+pub fn load_databases(databases: &[MmdbConfig]) -> Result<()> {
+    let mut opened = Vec::with_capacity(databases.len());
+    for database in databases {
+        let reader = Reader::open_readfile(&database.path).with_context(|| {
+            format!(
+                "Failed to open --mmdb database '{}' ({})",
+                database.name,
+                database.path.display()
+            )
+        })?;
+        opened.push((database.name.clone(), Arc::new(reader)));
+    }
+    *DATABASES.write().unwrap() = opened;
+    Ok(())
+}
+
+/// The parts of a GeoIP2/GeoLite2 City, Country or ASN record we expose.
+/// Other fields, and names in languages other than English, are skipped
+/// while decoding.
+#[derive(Debug, Default, Deserialize)]
+struct Record {
+    continent: Option<Place>,
+    country: Option<Place>,
+    city: Option<Place>,
+    location: Option<Location>,
+    autonomous_system_number: Option<u32>,
+    autonomous_system_organization: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Place {
+    /// Continent code, e.g. `EU`
+    code: Option<String>,
+    /// Country code, e.g. `GB`
+    iso_code: Option<String>,
+    names: Option<Names>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Names {
+    en: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Location {
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    time_zone: Option<String>,
+}
+
+fn english_name(place: &Place) -> Option<String> {
+    place.names.as_ref().and_then(|names| names.en.clone())
+}
+
+/// Flatten a decoded record into the map returned to scripts
+fn record_to_map(record: Record) -> Map {
+    let mut map = Map::new();
+    let mut put = |key: &str, value: Option<Dynamic>| {
+        if let Some(value) = value {
+            map.insert(key.into(), value);
+        }
+    };
+
+    if let Some(continent) = &record.continent {
+        put("continent", continent.code.clone().map(Dynamic::from));
+    }
+    if let Some(country) = &record.country {
+        put("country", country.iso_code.clone().map(Dynamic::from));
+        put("country_name", english_name(country).map(Dynamic::from));
+    }
+    if let Some(city) = &record.city {
+        put("city", english_name(city).map(Dynamic::from));
+    }
+    if let Some(location) = &record.location {
+        put("latitude", location.latitude.map(Dynamic::from));
+        put("longitude", location.longitude.map(Dynamic::from));
+        put("time_zone", location.time_zone.clone().map(Dynamic::from));
+    }
+    put(
+        "asn",
+        record
+            .autonomous_system_number
+            .map(|asn| Dynamic::from(asn as i64)),
+    );
+    put(
+        "as_org",
+        record.autonomous_system_organization.map(Dynamic::from),
+    );
+    map
+}
+
+/// Look `ip` up in `reader`. Misses and invalid addresses give an empty map.
+pub fn lookup(reader: &Reader<Vec<u8>>, ip: &str) -> Map {
+    let Ok(address) = IpAddr::from_str(ip.trim()) else {
+        return Map::new();
+    };
+    reader
+        .lookup::<Record>(address)
+        .map(record_to_map)
+        .unwrap_or_default()
+}
+
+/// Register one lookup function per `--mmdb` database
+pub fn register_functions(engine: &mut Engine) {
+    for (name, reader) in DATABASES.read().unwrap().iter() {
+        let reader = Arc::clone(reader);
+        engine.register_fn(name.clone(), move |ip: &str| -> Map { lookup(&reader, ip) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_to_map_flattens_city_and_asn_fields() {
+        let record = Record {
+            continent: Some(Place {
+                code: Some("EU".to_string()),
+                ..Place::default()
+            }),
+            country: Some(Place {
+                iso_code: Some("GB".to_string()),
+                names: Some(Names {
+                    en: Some("United Kingdom".to_string()),
+                }),
+                ..Place::default()
+            }),
+            city: Some(Place {
+                names: Some(Names { en: None }),
+                ..Place::default()
+            }),
+            location: Some(Location {
+                latitude: Some(51.5),
+                longitude: Some(-0.1),
+                time_zone: None,
+            }),
+            autonomous_system_number: Some(15169),
+            autonomous_system_organization: None,
+        };
+
+        let map = record_to_map(record);
+        let mut keys: Vec<&str> = map.keys().map(|k| k.as_str()).collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "asn",
+                "continent",
+                "country",
+                "country_name",
+                "latitude",
+                "longitude"
+            ]
+        );
+        assert_eq!(map["country"].clone().into_string().unwrap(), "GB");
+        assert_eq!(map["asn"].as_int().unwrap(), 15169);
+        assert_eq!(map["longitude"].as_float().unwrap(), -0.1);
+
+        assert!(record_to_map(Record::default()).is_empty());
+    }
+}
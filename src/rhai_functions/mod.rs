@@ -15,9 +15,12 @@ pub mod extractors;
 pub mod file_ops;
 pub mod formatting;
 pub mod hashing;
+pub mod json_number;
 pub mod maps;
 pub mod math;
 pub mod micro_search;
+#[cfg(feature = "mmdb")]
+pub mod mmdb;
 pub mod network;
 pub mod normalize;
 pub mod parsers;
@@ -41,6 +44,8 @@ pub fn register_all_functions(engine: &mut Engine) {
     maps::register_functions(engine);
     math::register_functions(engine);
     network::register_functions(engine);
+    #[cfg(feature = "mmdb")]
+    mmdb::register_functions(engine);
     normalize::register_functions(engine);
     process::register_functions(engine);
     random::register_functions(engine);
@@ -55,6 +60,7 @@ pub fn register_all_functions(engine: &mut Engine) {
     file_ops::register_functions(engine);
     formatting::register_functions(engine);
     hashing::register_functions(engine);
+    json_number::register_functions(engine);
     extractors::register_functions(engine);
     parsers::register_functions(engine);
     serializers::register_functions(engine);
@@ -1,7 +1,9 @@
 //! Networking helpers for Rhai scripts.
 //!
-//! Includes IP validation, CIDR matching, and private range checks.
+//! Includes IP validation, CIDR matching, private range checks, and
+//! normalization between IPv4 and IPv6 forms.
 
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use ipnet::IpNet;
 use rhai::{Engine, EvalAltResult, ImmutableString};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
@@ -92,6 +94,41 @@ fn mask_ipv6(addr: Ipv6Addr, hextets_to_mask: usize) -> Ipv6Addr {
     Ipv6Addr::from(segments)
 }
 
+// ============================================================================
+// IP Normalization
+// ============================================================================
+
+/// Canonical form of an IP address: IPv6 is written lowercase with the
+/// longest zero run compressed to `::`. Invalid input is returned unchanged.
+fn normalize_ip_impl(ip: &str) -> String {
+    match IpAddr::from_str(ip) {
+        Ok(addr) => addr.to_string(),
+        Err(_) => ip.to_string(),
+    }
+}
+
+/// IPv4-mapped IPv6 form (`::ffff:a.b.c.d`) of an IPv4 address. IPv6 input is
+/// normalized; invalid input gives an empty string.
+fn ipv4_to_ipv6_impl(ip: &str) -> String {
+    match IpAddr::from_str(ip) {
+        Ok(IpAddr::V4(addr)) => addr.to_ipv6_mapped().to_string(),
+        Ok(IpAddr::V6(addr)) => addr.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+/// IPv4 address embedded in an IPv4-mapped IPv6 address, or an empty string
+/// when the input is not one.
+fn ipv6_to_ipv4_impl(ip: &str) -> String {
+    match Ipv6Addr::from_str(ip) {
+        Ok(addr) => addr
+            .to_ipv4_mapped()
+            .map(|v4| v4.to_string())
+            .unwrap_or_default(),
+        Err(_) => String::new(),
+    }
+}
+
 /// Check if IP address is in a private/internal range.
 fn is_private_ip_impl(ip: &str) -> bool {
     match IpAddr::from_str(ip) {
@@ -107,6 +144,74 @@ fn is_private_ip_impl(ip: &str) -> bool {
 // Registration
 // ============================================================================
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "ipv4_to_ipv6",
+        &[r#"e.ip6 = e.ip.ipv4_to_ipv6()  // "192.168.1.1" → "::ffff:192.168.1.1""#],
+    ),
+    (
+        "ipv6_to_ipv4",
+        &[r#"e.ip4 = e.ip.ipv6_to_ipv4()  // "::ffff:192.168.1.1" → "192.168.1.1""#],
+    ),
+    (
+        "mask_ip",
+        &[
+            r#"e.masked_ip = e.client_ip.mask_ip()  // "192.168.1.100" → "192.168.1.0""#,
+            r#"e.partial = e.ip.mask_ip(2)  // "192.168.1.100" → "192.168.0.0""#,
+            r#"e.ipv6_masked = e.ip.mask_ip(2)  // "2001:db8:1:2:3:4:5:6" → "2001:db8:1:2:3:4::""#,
+        ],
+    ),
+    (
+        "normalize_ip",
+        &[r#"e.ip = e.ip.normalize_ip()  // "0:0:0:0:0:0:0:1" → "::1""#],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.ipv4_to_ipv6()"],
+        r#"IPv4-mapped IPv6 form of an IPv4 address ("::ffff:a.b.c.d")"#,
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.ipv6_to_ipv4()"],
+        r#"IPv4 address inside an IPv4-mapped IPv6 address ("" if not mapped)"#,
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.is_in_cidr(cidr)"],
+        r#"Check if IP address is in CIDR network (e.g., "10.0.0.0/8")"#,
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.is_ipv4()"],
+        "Check if text is a valid IPv4 address",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.is_ipv6()"],
+        "Check if text is a valid IPv6 address",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.is_private_ip()"],
+        "Check if IP is in private/internal ranges",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.mask_ip([octets])"],
+        "Mask IP address by zeroing the suffix (IPv4/IPv6)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.normalize_ip()"],
+        r#"Canonical IP form (IPv6 compressed to "::", lowercase)"#,
+    ),
+];
+
 /// Register network functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     // IPv4 validation
@@ -136,6 +241,17 @@ pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("is_private_ip", |ip: &str| -> bool {
         is_private_ip_impl(ip)
     });
+
+    // IP normalization
+    engine.register_fn("normalize_ip", |ip: &str| -> String {
+        normalize_ip_impl(ip)
+    });
+    engine.register_fn("ipv4_to_ipv6", |ip: &str| -> String {
+        ipv4_to_ipv6_impl(ip)
+    });
+    engine.register_fn("ipv6_to_ipv4", |ip: &str| -> String {
+        ipv6_to_ipv4_impl(ip)
+    });
 }
 
 #[cfg(test)]
@@ -379,4 +495,39 @@ mod tests {
             .unwrap();
         assert!(!result);
     }
+
+    #[test]
+    fn test_normalize_ip_loopback_full_form_and_mapped() {
+        assert_eq!(normalize_ip_impl("::1"), "::1");
+        assert_eq!(normalize_ip_impl("0:0:0:0:0:0:0:1"), "::1");
+        assert_eq!(
+            normalize_ip_impl("2001:0DB8:0000:0000:0000:0000:0000:0001"),
+            "2001:db8::1"
+        );
+        assert_eq!(
+            normalize_ip_impl("::FFFF:192.168.1.1"),
+            "::ffff:192.168.1.1"
+        );
+        assert_eq!(normalize_ip_impl("127.0.0.1"), "127.0.0.1");
+        assert_eq!(normalize_ip_impl("not-an-ip"), "not-an-ip");
+    }
+
+    #[test]
+    fn test_ipv4_ipv6_mapping() {
+        assert_eq!(ipv4_to_ipv6_impl("192.168.1.1"), "::ffff:192.168.1.1");
+        assert_eq!(ipv4_to_ipv6_impl("0:0:0:0:0:0:0:1"), "::1");
+        assert_eq!(ipv4_to_ipv6_impl("bogus"), "");
+
+        assert_eq!(ipv6_to_ipv4_impl("::ffff:192.168.1.1"), "192.168.1.1");
+        assert_eq!(ipv6_to_ipv4_impl("0:0:0:0:0:ffff:c0a8:0101"), "192.168.1.1");
+        assert_eq!(ipv6_to_ipv4_impl("::1"), "");
+        assert_eq!(ipv6_to_ipv4_impl("192.168.1.1"), "");
+
+        let mut engine = Engine::new();
+        register_functions(&mut engine);
+        let result: String = engine
+            .eval(r#""0:0:0:0:0:ffff:a00:1".normalize_ip().ipv6_to_ipv4()"#)
+            .unwrap();
+        assert_eq!(result, "10.0.0.1");
+    }
 }
@@ -1,3 +1,4 @@
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use regex::{Captures, Regex};
 use rhai::{Dynamic, Engine, Map};
 use std::collections::HashMap;
@@ -444,6 +445,22 @@ fn normalized_map_with_patterns(
     Ok(map)
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[(
+    "normalized",
+    &[
+        r#"e.pattern = e.message.normalized()"#,
+        r#"e.simple = e.message.normalized("ipv4,email")"#,
+        r#"e.custom = e.message.normalized(["uuid", "sha256", "url"])"#,
+    ],
+)];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(Section::Strings, &["text.normalized([patterns])"], "Replace patterns with placeholders (<ipv4>, <email>, <credit_card>, etc.)").with_details(&["Patterns: ipv4, ipv4_port, ipv6, email, url, fqdn, uuid, mac, md5,", "sha1, sha256, path, oauth, function, hexcolor, version, hexnum,", "duration, num, credit_card (Luhn), ssn (strict XXX-XX-XXXX), phone (NANP-aware for US/CA; permissive internationally)", "PII patterns (credit_card, ssn, phone) are NOT in the default set;", r#"pass them explicitly to redact, e.g. normalized(["credit_card","ssn","phone"])"#]),
+    FunctionDoc::new(Section::Maps, &["map.normalized([patterns])"], "Return new map with all string fields normalized"),
+];
+
 pub fn register_functions(engine: &mut Engine) {
     // String normalization - default patterns
     engine.register_fn("normalized", normalized_str_default);
@@ -6,15 +6,16 @@
 use base64::engine::general_purpose::{URL_SAFE, URL_SAFE_NO_PAD};
 use base64::Engine as _;
 use chrono::{TimeZone, Utc};
-use rhai::{Array, Dynamic, Engine, Map};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map};
 use std::path::Path;
 use std::sync::LazyLock;
 use url::Url;
 
 use crate::event::Event;
-use crate::parsers::{CefParser, CombinedParser, LogfmtParser, SyslogParser};
+use crate::parsers::{CefParser, CombinedParser, FixedWidthParser, LogfmtParser, SyslogParser};
 use crate::pipeline::EventParser;
 use crate::rhai_functions::datetime::DateTimeWrapper;
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 
 /// Maximum length for parsed inputs (1MB)
 const MAX_PARSE_LEN: usize = 1_048_576;
@@ -868,6 +869,14 @@ fn parse_combined_impl(line: &str) -> Map {
     parse_event_with(&*COMBINED_PARSER, line)
 }
 
+/// Slice `line` into the fixed-width columns of `spec`
+/// (`0-8:date,9-17:time,18-:message`). Columns past the end of a short line
+/// are left out; an invalid spec is an error.
+fn parse_fixed_impl(line: &str, spec: &str) -> Result<Map, Box<EvalAltResult>> {
+    let parser = FixedWidthParser::new(spec).map_err(|err| format!("parse_fixed: {}", err))?;
+    Ok(parse_event_with(&parser, line))
+}
+
 // ============================================================================
 // Key-Value Parsing
 // ============================================================================
@@ -904,6 +913,89 @@ fn parse_kv_impl(text: &str, sep: Option<&str>, kv_sep: &str) -> Map {
     map
 }
 
+// ============================================================================
+// CSV / Quoted Splitting
+// ============================================================================
+
+/// Resolve a one-character CSV delimiter or quote argument to its byte.
+fn single_ascii_byte(value: &str, what: &str) -> Result<u8, Box<EvalAltResult>> {
+    match value.as_bytes() {
+        [byte] if byte.is_ascii() => Ok(*byte),
+        _ => Err(format!("parse_csv_line: {} must be a single ASCII character", what).into()),
+    }
+}
+
+/// Split one CSV line into unquoted cells with the same reader settings as
+/// `-f csv`. Input that does not parse as exactly one record (oversized,
+/// unbalanced quotes, stray line breaks) comes back as a single cell.
+fn parse_csv_line_impl(text: &str, delimiter: u8, quote: u8) -> Array {
+    if text.is_empty() {
+        return Array::new();
+    }
+    let whole = || vec![Dynamic::from(text.to_string())];
+    if text.len() > MAX_PARSE_LEN || text.bytes().filter(|&b| b == quote).count() % 2 == 1 {
+        return whole();
+    }
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .quote(quote)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let mut records = reader.records();
+    let record = match (records.next(), records.next()) {
+        (Some(Ok(record)), None) => record,
+        _ => return whole(),
+    };
+
+    record
+        .iter()
+        .map(|cell| Dynamic::from(cell.to_string()))
+        .collect()
+}
+
+/// Split on `sep` except inside single- or double-quoted sections. Quotes and
+/// backslash escapes are kept as written; an unterminated quote returns the
+/// whole input as a single element.
+fn split_quoted_impl(text: &str, sep: &str) -> Array {
+    if text.is_empty() {
+        return Array::new();
+    }
+    if text.len() > MAX_PARSE_LEN {
+        return vec![Dynamic::from(text.to_string())];
+    }
+
+    let mut parts = Array::new();
+    let mut start = 0;
+    let mut quote: Option<char> = None;
+    let mut chars = text.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' | '\'' if quote.is_none() => quote = Some(c),
+            _ if quote == Some(c) => quote = None,
+            _ if quote.is_none() && text[idx..].starts_with(sep) => {
+                parts.push(Dynamic::from(text[start..idx].to_string()));
+                start = idx + sep.len();
+                // Skip the rest of a multi-character separator
+                for _ in 1..sep.chars().count() {
+                    chars.next();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if quote.is_some() {
+        return vec![Dynamic::from(text.to_string())];
+    }
+    parts.push(Dynamic::from(text[start..].to_string()));
+    parts
+}
+
 // ============================================================================
 // JWT Parsing
 // ============================================================================
@@ -1081,10 +1173,229 @@ fn parse_jwt_impl(input: &str) -> Map {
     result
 }
 
+/// Decode a hex-encoded CBOR map (e.g. an IoT payload captured as hex).
+/// Bad hex, invalid CBOR, or a value that is not a map yield an empty map.
+fn parse_cbor_hex_impl(input: &str) -> Map {
+    let trimmed = input.trim();
+    if trimmed.is_empty() || trimmed.len() > MAX_PARSE_LEN {
+        return Map::new();
+    }
+    let Ok(bytes) = hex::decode(trimmed) else {
+        return Map::new();
+    };
+    match ciborium::de::from_reader::<ciborium::value::Value, _>(bytes.as_slice()) {
+        Ok(ciborium::value::Value::Map(entries)) => crate::formatters::cbor_map_to_rhai(entries),
+        _ => Map::new(),
+    }
+}
+
+// ============================================================================
+// AWS ARN Parsing
+// ============================================================================
+
+fn is_arn_token(value: &str) -> bool {
+    value
+        .chars()
+        .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-')
+}
+
+/// Split `arn:PARTITION:SERVICE:REGION:ACCOUNT_ID:RESOURCE` into its parts.
+/// Region and account may be empty (global services, S3). The resource is
+/// split into type and id on its first `:` or `/`; slash-separated resources
+/// keep any IAM-style path between them.
+fn parse_aws_arn_impl(input: &str) -> Map {
+    let trimmed = input.trim();
+    if trimmed.len() > MAX_PARSE_LEN {
+        return Map::new();
+    }
+
+    let mut parts = trimmed.splitn(6, ':');
+    let (Some("arn"), Some(partition), Some(service), Some(region), Some(account), Some(resource)) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return Map::new();
+    };
+
+    if !partition.starts_with("aws")
+        || !is_arn_token(partition)
+        || service.is_empty()
+        || !is_arn_token(service)
+        || !is_arn_token(region)
+        || resource.is_empty()
+    {
+        return Map::new();
+    }
+    // AWS-managed IAM policies use the literal account `aws`
+    let account_ok = account.is_empty()
+        || account == "aws"
+        || (account.len() == 12 && account.bytes().all(|b| b.is_ascii_digit()));
+    if !account_ok {
+        return Map::new();
+    }
+
+    let mut map = Map::new();
+    map.insert("partition".into(), Dynamic::from(partition.to_string()));
+    map.insert("service".into(), Dynamic::from(service.to_string()));
+    map.insert("region".into(), Dynamic::from(region.to_string()));
+    map.insert("account_id".into(), Dynamic::from(account.to_string()));
+
+    // S3 resources are a bucket name optionally followed by an object key,
+    // not a typed resource
+    let split = if service == "s3" {
+        None
+    } else {
+        resource.find([':', '/']).map(|idx| {
+            (
+                &resource[..idx],
+                &resource[idx..idx + 1],
+                &resource[idx + 1..],
+            )
+        })
+    };
+    match split {
+        Some((resource_type, "/", rest)) if !resource_type.is_empty() && !rest.is_empty() => {
+            map.insert(
+                "resource_type".into(),
+                Dynamic::from(resource_type.to_string()),
+            );
+            match rest.rfind('/') {
+                Some(idx) => {
+                    map.insert(
+                        "resource_path".into(),
+                        Dynamic::from(format!("/{}", &rest[..=idx])),
+                    );
+                    map.insert(
+                        "resource_id".into(),
+                        Dynamic::from(rest[idx + 1..].to_string()),
+                    );
+                }
+                None => {
+                    map.insert("resource_id".into(), Dynamic::from(rest.to_string()));
+                }
+            }
+        }
+        Some((resource_type, _, rest)) if !resource_type.is_empty() && !rest.is_empty() => {
+            map.insert(
+                "resource_type".into(),
+                Dynamic::from(resource_type.to_string()),
+            );
+            map.insert("resource_id".into(), Dynamic::from(rest.to_string()));
+        }
+        _ => {
+            map.insert("resource_id".into(), Dynamic::from(resource.to_string()));
+        }
+    }
+
+    map
+}
+
 // ============================================================================
 // Registration
 // ============================================================================
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "is_aws_arn",
+        &[r#"e.is_role = e.principal.is_aws_arn() && e.principal.contains(":role/")"#],
+    ),
+    (
+        "parse_aws_arn",
+        &[
+            r#"let arn = e.userIdentity.arn.parse_aws_arn()  // "arn:aws:iam::123456789012:user/ops/alice" → {service: "iam", resource_type: "user", resource_path: "/ops/", resource_id: "alice", ...}"#,
+        ],
+    ),
+    (
+        "parse_cbor_hex",
+        &[r#"let reading = e.payload_hex.parse_cbor_hex()"#],
+    ),
+    ("parse_cef", &[r#"let cef = e.line.parse_cef()"#]),
+    (
+        "parse_combined",
+        &[r#"let access = e.line.parse_combined()"#],
+    ),
+    (
+        "parse_content_disposition",
+        &[r#"let cd = e.header.parse_content_disposition()"#],
+    ),
+    (
+        "parse_csv_line",
+        &[
+            r#"let cells = e.row.parse_csv_line()  // 'a,"b,c",' → ["a", "b,c", ""]"#,
+            r#"let cells = e.row.parse_csv_line(";", "'")  // "a;'b;c'" → ["a", "b;c"]"#,
+        ],
+    ),
+    (
+        "parse_email",
+        &[r#"let email = "User Name <user@example.com>".parse_email()"#],
+    ),
+    (
+        "parse_fixed",
+        &[
+            r#"e += e.line.parse_fixed("0-8:date,9-17:time,18-:message")  // "20240115 10:30:00 job done" → {date: "20240115", time: "10:30:00", message: "job done"}"#,
+        ],
+    ),
+    ("parse_jwt", &[r#"let jwt = e.token.parse_jwt()"#]),
+    (
+        "parse_kv",
+        &[
+            r#"e.params = e.query.parse_kv("&", "=")  // "a=1&b=2" → {a: "1", b: "2"}"#,
+            r#"e.fields = e.msg.parse_kv()  // "Payment timeout order=1234" → {order: "1234"}"#,
+        ],
+    ),
+    ("parse_logfmt", &[r#"let fields = e.line.parse_logfmt()"#]),
+    (
+        "parse_media_type",
+        &[r#"let mt = "text/html; charset=utf-8".parse_media_type()"#],
+    ),
+    (
+        "parse_path",
+        &[r#"let path = "/var/log/app.log".parse_path()"#],
+    ),
+    (
+        "parse_query_params",
+        &[r#"e.params = e.query_string.parse_query_params()  // "a=1&b=2" → {a: "1", b: "2"}"#],
+    ),
+    ("parse_syslog", &[r#"let syslog = e.line.parse_syslog()"#]),
+    ("parse_url", &[r#"let url = e.request.parse_url()"#]),
+    (
+        "parse_user_agent",
+        &[r#"let ua = e.user_agent.parse_user_agent()"#],
+    ),
+    (
+        "split_quoted",
+        &[r#"e.args = e.cmdline.split_quoted(" ")  // `run "a b" c` → ["run", "\"a b\"", "c"]"#],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(Section::Strings, &["text.is_aws_arn()"], "Check if text is a well-formed AWS ARN"),
+    FunctionDoc::new(Section::Strings, &["text.parse_aws_arn()"], "Split an AWS ARN into partition, service, region, account_id and resource parts"),
+    FunctionDoc::new(Section::Strings, &["text.parse_cbor_hex()"], "Decode a hex-encoded CBOR map into a map"),
+    FunctionDoc::new(Section::Strings, &["text.parse_cef()"], "Parse Common Event Format line into fields"),
+    FunctionDoc::new(Section::Strings, &["text.parse_csv_line([delim [,quote]])"], "Split one CSV line into unquoted cells (quoted newlines stay in the cell)"),
+    FunctionDoc::new(Section::Strings, &["text.parse_combined()"], "Parse Apache/Nginx combined log line"),
+    FunctionDoc::new(Section::Strings, &["text.parse_content_disposition()"], "Parse Content-Disposition header parameters"),
+    FunctionDoc::new(Section::Strings, &["text.parse_email()"], "Parse email address into parts"),
+    FunctionDoc::new(Section::Strings, &["text.parse_fixed(spec)"], r#"Slice fixed-width columns by position ("0-8:date,18-:msg")"#),
+    FunctionDoc::new(Section::Strings, &["text.parse_jwt()"], "Parse JWT into header/claims (+ exp/iat/nbf as datetimes) without verification"),
+    FunctionDoc::new(Section::Strings, &["text.parse_kv([sep [,kv_sep]])"], "Split key-value pairs from text (skips tokens without separator; NOT quote-aware — use parse_logfmt for quoted/typed values)"),
+    FunctionDoc::new(Section::Strings, &["text.parse_logfmt()"], "Parse logfmt line into structured fields"),
+    FunctionDoc::new(Section::Strings, &["text.parse_media_type()"], "Parse media type tokens and parameters"),
+    FunctionDoc::new(Section::Strings, &["text.parse_path()"], "Parse filesystem path into components"),
+    FunctionDoc::new(Section::Strings, &["text.parse_query_params()"], "Parse URL query string into map"),
+    FunctionDoc::new(Section::Strings, &["text.parse_syslog()"], "Parse syslog line into structured fields"),
+    FunctionDoc::new(Section::Strings, &["text.parse_url()"], "Parse URL into structured components"),
+    FunctionDoc::new(Section::Strings, &["text.parse_user_agent()"], "Parse common user-agent strings into components"),
+    FunctionDoc::new(Section::Strings, &["text.split_quoted(sep)"], r#"Split on separator except inside '...' or "..." (quotes/escapes kept)"#),
+];
+
 /// Register all parsing functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("parse_url", parse_url_impl);
@@ -1098,7 +1409,13 @@ pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("parse_cef", parse_cef_impl);
     engine.register_fn("parse_logfmt", parse_logfmt_impl);
     engine.register_fn("parse_combined", parse_combined_impl);
+    engine.register_fn("parse_fixed", parse_fixed_impl);
     engine.register_fn("parse_jwt", parse_jwt_impl);
+    engine.register_fn("parse_cbor_hex", parse_cbor_hex_impl);
+    engine.register_fn("parse_aws_arn", parse_aws_arn_impl);
+    engine.register_fn("is_aws_arn", |text: &str| -> bool {
+        !parse_aws_arn_impl(text).is_empty()
+    });
 
     // Parse key-value pairs from a string
     engine.register_fn("parse_kv", |text: &str| -> Map {
@@ -1116,6 +1433,37 @@ pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("parse_kv", |text: &str, sep: &str, kv_sep: &str| -> Map {
         parse_kv_impl(text, Some(sep), kv_sep)
     });
+
+    engine.register_fn("parse_csv_line", |text: &str| -> Array {
+        parse_csv_line_impl(text, b',', b'"')
+    });
+
+    engine.register_fn(
+        "parse_csv_line",
+        |text: &str, delimiter: &str| -> Result<Array, Box<EvalAltResult>> {
+            let delimiter = single_ascii_byte(delimiter, "delimiter")?;
+            Ok(parse_csv_line_impl(text, delimiter, b'"'))
+        },
+    );
+
+    engine.register_fn(
+        "parse_csv_line",
+        |text: &str, delimiter: &str, quote: &str| -> Result<Array, Box<EvalAltResult>> {
+            let delimiter = single_ascii_byte(delimiter, "delimiter")?;
+            let quote = single_ascii_byte(quote, "quote")?;
+            Ok(parse_csv_line_impl(text, delimiter, quote))
+        },
+    );
+
+    engine.register_fn(
+        "split_quoted",
+        |text: &str, sep: &str| -> Result<Array, Box<EvalAltResult>> {
+            if sep.is_empty() {
+                return Err("split_quoted: separator must not be empty".into());
+            }
+            Ok(split_quoted_impl(text, sep))
+        },
+    );
 }
 
 #[cfg(test)]
@@ -1174,6 +1522,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_cbor_hex() {
+        let mut engine = Engine::new();
+        register_functions(&mut engine);
+
+        // {"id": 7, "temp": 21.5, "tags": ["a"]}
+        let result: Map = engine
+            .eval(r#"parse_cbor_hex("A3626964076474656D70F94D606474616773816161")"#)
+            .unwrap();
+        assert_eq!(result.get("id").unwrap().as_int().unwrap(), 7);
+        assert_eq!(result.get("temp").unwrap().as_float().unwrap(), 21.5);
+        assert_eq!(
+            result
+                .get("tags")
+                .unwrap()
+                .clone()
+                .into_array()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        assert!(parse_cbor_hex_impl("zz").is_empty());
+        // A bare integer decodes but is not a map
+        assert!(parse_cbor_hex_impl("07").is_empty());
+    }
+
     #[test]
     fn test_parse_jwt() {
         let mut engine = Engine::new();
@@ -1261,4 +1636,187 @@ mod tests {
             "25"
         );
     }
+
+    fn strings(array: Array) -> Vec<String> {
+        array
+            .into_iter()
+            .map(|value| value.into_string().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_csv_line() {
+        let mut engine = Engine::new();
+        register_functions(&mut engine);
+
+        let cells: Array = engine
+            .eval(r#"`a,"b,c","say ""hi""",`.parse_csv_line()"#)
+            .unwrap();
+        assert_eq!(strings(cells), vec!["a", "b,c", "say \"hi\"", ""]);
+
+        // Quoted line breaks stay inside their cell
+        assert_eq!(
+            strings(parse_csv_line_impl("1,\"two\nlines\",3", b',', b'"')),
+            vec!["1", "two\nlines", "3"]
+        );
+        assert_eq!(
+            strings(parse_csv_line_impl("x,,", b',', b'"')),
+            vec!["x", "", ""]
+        );
+
+        let cells: Array = engine
+            .eval(r#"parse_csv_line("a;'b;c';d", ";", "'")"#)
+            .unwrap();
+        assert_eq!(strings(cells), vec!["a", "b;c", "d"]);
+        assert!(engine
+            .eval::<Array>(r#"parse_csv_line("a,b", ",,")"#)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_line_returns_whole_input_on_bad_quoting() {
+        let input = "a,\"unterminated,b";
+        assert_eq!(strings(parse_csv_line_impl(input, b',', b'"')), vec![input]);
+        assert_eq!(
+            strings(parse_csv_line_impl("a,b\nc,d", b',', b'"')),
+            vec!["a,b\nc,d"]
+        );
+        assert!(parse_csv_line_impl("", b',', b'"').is_empty());
+    }
+
+    #[test]
+    fn test_split_quoted() {
+        let mut engine = Engine::new();
+        register_functions(&mut engine);
+
+        let parts: Array = engine
+            .eval(r#"`cmd --name "a b" 'c d' e\ f`.split_quoted(" ")"#)
+            .unwrap();
+        assert_eq!(
+            strings(parts),
+            vec!["cmd", "--name", "\"a b\"", "'c d'", "e\\ f"]
+        );
+
+        assert_eq!(
+            strings(split_quoted_impl(r#"k="x;y";msg='it"s;ok';z"#, ";")),
+            vec![r#"k="x;y""#, r#"msg='it"s;ok'"#, "z"]
+        );
+        assert_eq!(
+            strings(split_quoted_impl("a::'b::c'::", "::")),
+            vec!["a", "'b::c'", ""]
+        );
+        assert_eq!(
+            strings(split_quoted_impl(r#"a,"b,c"#, ",")),
+            vec![r#"a,"b,c"#]
+        );
+        assert!(engine.eval::<Array>(r#"split_quoted("a,b", "")"#).is_err());
+    }
+
+    fn arn(input: &str) -> Vec<(String, String)> {
+        let mut pairs: Vec<(String, String)> = parse_aws_arn_impl(input)
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.into_string().unwrap()))
+            .collect();
+        pairs.sort();
+        pairs
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        let mut pairs: Vec<(String, String)> = expected
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        pairs.sort();
+        pairs
+    }
+
+    #[test]
+    fn test_parse_aws_arn_iam_user_with_path() {
+        assert_eq!(
+            arn("arn:aws:iam::123456789012:user/division_abc/subdivision_xyz/Bob"),
+            pairs(&[
+                ("partition", "aws"),
+                ("service", "iam"),
+                ("region", ""),
+                ("account_id", "123456789012"),
+                ("resource_type", "user"),
+                ("resource_path", "/division_abc/subdivision_xyz/"),
+                ("resource_id", "Bob"),
+            ])
+        );
+        assert_eq!(
+            arn("arn:aws:iam::aws:policy/AdministratorAccess"),
+            pairs(&[
+                ("partition", "aws"),
+                ("service", "iam"),
+                ("region", ""),
+                ("account_id", "aws"),
+                ("resource_type", "policy"),
+                ("resource_id", "AdministratorAccess"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_aws_arn_s3_lambda_and_sns() {
+        assert_eq!(
+            arn("arn:aws:s3:::my-bucket/logs/2024/app.log"),
+            pairs(&[
+                ("partition", "aws"),
+                ("service", "s3"),
+                ("region", ""),
+                ("account_id", ""),
+                ("resource_id", "my-bucket/logs/2024/app.log"),
+            ])
+        );
+        assert_eq!(
+            arn("arn:aws-cn:lambda:cn-north-1:123456789012:function:resize:PROD"),
+            pairs(&[
+                ("partition", "aws-cn"),
+                ("service", "lambda"),
+                ("region", "cn-north-1"),
+                ("account_id", "123456789012"),
+                ("resource_type", "function"),
+                ("resource_id", "resize:PROD"),
+            ])
+        );
+        assert_eq!(
+            arn("arn:aws:sns:us-east-1:123456789012:alerts"),
+            pairs(&[
+                ("partition", "aws"),
+                ("service", "sns"),
+                ("region", "us-east-1"),
+                ("account_id", "123456789012"),
+                ("resource_id", "alerts"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_aws_arn_rejects_invalid() {
+        let mut engine = Engine::new();
+        register_functions(&mut engine);
+
+        for input in [
+            "",
+            "arn:aws:s3:::",
+            "arn:aws:iam::123:user/Bob",
+            "urn:aws:sns:us-east-1:123456789012:alerts",
+            "arn:gcp:sns:us-east-1:123456789012:alerts",
+            "arn:aws:sns:us-east-1:123456789012",
+            "arn:aws::us-east-1:123456789012:alerts",
+        ] {
+            assert!(arn(input).is_empty(), "{:?}", input);
+            assert!(
+                !engine
+                    .eval::<bool>(&format!("is_aws_arn({:?})", input))
+                    .unwrap(),
+                "{:?}",
+                input
+            );
+        }
+        assert!(engine
+            .eval::<bool>(r#""arn:aws:sns:us-east-1:123456789012:alerts".is_aws_arn()"#)
+            .unwrap());
+    }
 }
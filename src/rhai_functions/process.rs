@@ -1,3 +1,7 @@
+use crate::rhai_functions::capture::{
+    capture_eprint, capture_stderr, emit_eprint, is_parallel_mode,
+};
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use rhai::{Dynamic, Engine, ImmutableString};
 use std::cell::Cell;
 
@@ -9,7 +13,8 @@ thread_local! {
 }
 
 /// Rhai function: exit(code: INT, msg: STRING = null)
-/// Immediately stops all event processing and terminates Kelora with the given exit code.
+/// Stops all event processing; the run ends with the given exit code. The
+/// engine aborts the calling script, and the runner stops at the current event.
 pub fn exit_process(code: i64, msg: Dynamic) -> Dynamic {
     // Store exit code (clamp to valid range for process exit codes)
     let exit_code = code.clamp(0, 255) as i32;
@@ -17,24 +22,21 @@ pub fn exit_process(code: i64, msg: Dynamic) -> Dynamic {
 
     // Print message to stderr if provided
     if !msg.is_unit() {
-        if let Some(s) = msg.read_lock::<ImmutableString>() {
-            eprintln!("{}", s.as_str());
+        let text = match msg.read_lock::<ImmutableString>() {
+            Some(s) => s.to_string(),
+            None => msg.to_string(),
+        };
+        if is_parallel_mode() {
+            capture_eprint(text.clone());
+            capture_stderr(text);
         } else {
-            eprintln!("{}", msg);
+            emit_eprint(&text);
         }
     }
 
     // Set exit flag
     EXIT_REQUESTED.with(|er| er.set(true));
 
-    // In testing, don't actually exit - just set the flags
-    #[cfg(not(test))]
-    {
-        std::process::exit(exit_code);
-    }
-
-    // Return unit to indicate function completed (only reached in tests)
-    #[cfg(test)]
     Dynamic::UNIT
 }
 
@@ -74,8 +76,7 @@ pub fn clear_skip_request() {
     SKIP_REQUESTED.with(|skip| skip.set(false));
 }
 
-/// Reset exit state (useful for testing)
-#[cfg(test)]
+/// Reset exit state; each run (and each parallel worker) starts without one
 pub fn reset_exit_state() {
     EXIT_REQUESTED.with(|er| er.set(false));
     EXIT_CODE.with(|ec| ec.set(0));
@@ -87,6 +88,24 @@ pub fn exit_process_single(code: i64) -> Dynamic {
     exit_process(code, Dynamic::UNIT)
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples =
+    &[("exit", &[r#"exit(1)"#]), ("skip", &[r#"skip();"#])];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Utility,
+        &["exit(code)"],
+        "Exit kelora with given exit code",
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["skip()"],
+        "Skip the current event and continue with the next one",
+    ),
+];
+
 /// Register process control functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("exit", exit_process_single);
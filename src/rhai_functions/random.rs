@@ -1,3 +1,4 @@
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use rhai::{Engine, EvalAltResult};
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -33,6 +34,15 @@ pub fn parse_seed_env() -> Result<Option<u64>, String> {
     }
 }
 
+/// A fresh RNG for samplers outside the Rhai functions (e.g. --reservoir),
+/// seeded from `KELORA_SEED` when set
+pub fn seeded_rng() -> fastrand::Rng {
+    match parse_seed_env() {
+        Ok(Some(seed)) => fastrand::Rng::with_seed(seed),
+        _ => fastrand::Rng::new(),
+    }
+}
+
 // Thread-local counters for sample_every() - each N value gets its own counter
 thread_local! {
     static SAMPLE_COUNTERS: RefCell<HashMap<i64, i64>> = RefCell::new(HashMap::new());
@@ -104,6 +114,51 @@ fn sample_prob(p: f64) -> Result<bool, Box<EvalAltResult>> {
     Ok(rng.f64() < p)
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "rand_int",
+        &[r#"e.random_id = rand_int(1000, 9999)  // Random ID assignment"#],
+    ),
+    (
+        "sample_every",
+        &[
+            r#"if !sample_every(100) { skip() }"#,
+            r#"sample_every(10)  // Returns true on calls 10, 20, 30..."#,
+            r#"sample_every(100)  // Returns true on calls 100, 200, 300..."#,
+        ],
+    ),
+    ("sample_prob", &[r#"if !sample_prob(0.01) { skip() }"#]),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Math,
+        &["rand()"],
+        "Random float between 0 and 1 (set KELORA_SEED for reproducible output)",
+    ),
+    FunctionDoc::new(
+        Section::Math,
+        &["rand_int(min, max)"],
+        "Random integer between min and max (inclusive)",
+    ),
+    FunctionDoc::new(
+        Section::Math,
+        &["sample_every(n)"],
+        "Sample every Nth event (returns true on Nth, 2Nth, 3Nth calls)",
+    )
+    .with_details(&[
+        "Fast counter-based sampling (thread-local, approximate in parallel mode)",
+        "For deterministic sampling, use: text.bucket() % n == 0",
+    ]),
+    FunctionDoc::new(
+        Section::Math,
+        &["sample_prob(p)"],
+        "Probabilistic sampling: returns true with probability p (0.0-1.0)",
+    ),
+];
+
 pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("rand", rand_float);
     engine.register_fn("rand_int", rand_int_range);
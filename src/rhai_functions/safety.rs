@@ -1,4 +1,7 @@
-use rhai::{Array, Dynamic, Engine, ImmutableString, Map};
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, ImmutableString, Map, NativeCallContext};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
 
 /// Safe equality check for a path
 /// Usage: path_equals(e, "user.role", "admin")
@@ -465,6 +468,327 @@ pub fn to_bool_or(value: Dynamic, default: Dynamic) -> Dynamic {
     default
 }
 
+/// Whether coalesce() skips a value: unit, or an empty string, array or map
+fn is_blank(value: &Dynamic) -> bool {
+    if value.is_unit() {
+        return true;
+    }
+    if let Some(s) = value.read_lock::<ImmutableString>() {
+        return s.is_empty();
+    }
+    if let Some(arr) = value.read_lock::<Array>() {
+        return arr.is_empty();
+    }
+    if let Some(map) = value.read_lock::<Map>() {
+        return map.is_empty();
+    }
+    false
+}
+
+/// First value that is neither () nor empty, or () if there is none
+/// Usage: coalesce(e.user, e.username, "anonymous")
+pub fn coalesce(values: Array) -> Dynamic {
+    values
+        .into_iter()
+        .find(|value| !is_blank(value))
+        .unwrap_or(Dynamic::UNIT)
+}
+
+/// Value unless it is () (a missing field or JSON null)
+/// Usage: if_null(e.region, "unknown")
+pub fn if_null(value: Dynamic, default: Dynamic) -> Dynamic {
+    if value.is_unit() {
+        default
+    } else {
+        value
+    }
+}
+
+/// Look a value up in a table keyed by its string form
+/// Usage: lookup(e.status, #{"200": "ok", "404": "missing"}, "other")
+pub fn table_lookup(value: Dynamic, table: Map, default: Dynamic) -> Dynamic {
+    if value.is_unit() {
+        return default;
+    }
+    let key = match value.read_lock::<ImmutableString>() {
+        Some(s) => s.to_string(),
+        None => value.to_string(),
+    };
+    table.get(key.as_str()).cloned().unwrap_or(default)
+}
+
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "coalesce",
+        &[
+            r#"e.user = coalesce(e.user, e.username, e.login, "anonymous")"#,
+            r#"e.host = coalesce([e.host, e.hostname, e.server, e.node, e.instance, e.pod, "unknown"])"#,
+        ],
+    ),
+    (
+        "get_path",
+        &[
+            r#"e.user_name = e.get_path("user.profile.name", "unknown")"#,
+            r#"e.score = e.get_path("stats.score", 0)"#,
+        ],
+    ),
+    ("if_null", &[r#"e.region = e.region.if_null("unknown")"#]),
+    ("to_float", &[r#"e.score = to_float(e.score_string)"#]),
+    (
+        "lookup",
+        &[
+            r#"e.severity = lookup(e.level, #{"E": "error", "W": "warn", "I": "info"}, "debug")"#,
+            r#"e.family = lookup(e.status, #{"200": "ok", "304": "cached", "404": "missing"}, "other")"#,
+        ],
+    ),
+    (
+        "to_float_or",
+        &[r#"e.score = e.score_string.to_float_or(0.0)"#],
+    ),
+    ("to_int", &[r#"e.status = to_int(e.status_string)"#]),
+    ("to_int_or", &[r#"e.status = e.status_string.to_int_or(0)"#]),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Maps,
+        &[r#"map.get_path("field.path" [,default])"#],
+        "Safe nested field access with fallback",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &[r#"map.has_path("field.path")"#],
+        "Check if nested field path exists",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &[r#"map.path_equals("path", value)"#],
+        "Safe nested field comparison",
+    ),
+    FunctionDoc::new(
+        Section::Conversion,
+        &["to_int(value)"],
+        "Convert value to integer (returns () on error)",
+    ),
+    FunctionDoc::new(
+        Section::Conversion,
+        &["to_int(value, thousands)"],
+        "Parse integer, removing ANY char in thousands string",
+    ),
+    FunctionDoc::new(
+        Section::Conversion,
+        &["to_float(value)"],
+        "Convert value to float (returns () on error)",
+    ),
+    FunctionDoc::new(
+        Section::Conversion,
+        &["to_float(value, thousands, decimal)"],
+        "Parse float with explicit separators",
+    )
+    .with_details(&[
+        "- thousands: remove ANY char in string",
+        "- decimal: single char or empty (multi-char → error)",
+    ]),
+    FunctionDoc::new(
+        Section::Conversion,
+        &["to_bool(value)"],
+        "Convert value to boolean (returns () on error)",
+    ),
+    FunctionDoc::new(
+        Section::Conversion,
+        &["to_int_or(value, default)"],
+        "Convert value to integer with fallback",
+    ),
+    FunctionDoc::new(
+        Section::Conversion,
+        &["to_int_or(value, thousands, default)"],
+        "Parse integer with thousands removal and fallback",
+    ),
+    FunctionDoc::new(
+        Section::Conversion,
+        &["to_float_or(value, default)"],
+        "Convert value to float with fallback",
+    ),
+    FunctionDoc::new(
+        Section::Conversion,
+        &["to_float_or(value, thousands, decimal, default)"],
+        "Parse float with separators and fallback",
+    ),
+    FunctionDoc::new(
+        Section::Conversion,
+        &["to_bool_or(value, default)"],
+        "Convert value to boolean with fallback",
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["coalesce(a, b, ...)"],
+        r#"First argument that is not () or empty ("", [], #{}); takes up to 6 args or an array"#,
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["if_null(value, default)"],
+        "Value unless it is () (missing field or JSON null), else default",
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["lookup(value, table [,default])"],
+        "Look value up in a map keyed by its string form; default (or ()) when absent",
+    ),
+];
+
+/// What scripts may do under `--sandbox`, for running scripts you did not
+/// write. When enabled:
+///
+/// - file writes, file reads and environment access fail with a script
+///   error naming the sandbox, even with `--allow-fs-writes`
+/// - `import` cannot load modules from disk
+/// - each script run is capped at [`SandboxPolicy::MAX_OPERATIONS`]
+///   operations and [`SandboxPolicy::MAX_SCRIPT_TIME`], with limits on call
+///   depth and on string, array and map sizes
+///
+/// Pure computation, tracking, `print`/`eprint` and `emit` stay available.
+/// Functions with side effects outside the process must call
+/// [`require_unsandboxed`] first; the tests call every function the file,
+/// config and environment modules register.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SandboxPolicy {
+    pub enabled: bool,
+}
+
+impl SandboxPolicy {
+    /// Rhai operations per script run
+    pub const MAX_OPERATIONS: u64 = 1_000_000;
+    /// Wall-clock time per script run
+    pub const MAX_SCRIPT_TIME: Duration = Duration::from_secs(1);
+    /// Nested function calls
+    pub const MAX_CALL_LEVELS: usize = 32;
+    /// Bytes in one string
+    pub const MAX_STRING_SIZE: usize = 1024 * 1024;
+    /// Items in one array or map
+    pub const MAX_COLLECTION_SIZE: usize = 100_000;
+
+    /// Fail with a uniform error when the sandbox is on
+    pub fn check(&self, function: &str) -> Result<(), Box<EvalAltResult>> {
+        if self.enabled {
+            return Err(format!("{}() is disabled by --sandbox", function).into());
+        }
+        Ok(())
+    }
+
+    /// Put the resource caps and the privileged-function gate on `engine`.
+    /// Does nothing when disabled.
+    pub fn apply_limits(&self, engine: &mut Engine) {
+        if !self.enabled {
+            return;
+        }
+        // Read back by require_unsandboxed in every call on this engine
+        engine.set_default_tag(true);
+        engine.set_max_operations(Self::MAX_OPERATIONS);
+        engine.set_max_call_levels(Self::MAX_CALL_LEVELS);
+        engine.set_max_string_size(Self::MAX_STRING_SIZE);
+        engine.set_max_array_size(Self::MAX_COLLECTION_SIZE);
+        engine.set_max_map_size(Self::MAX_COLLECTION_SIZE);
+        engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+    }
+}
+
+thread_local! {
+    /// Start of the script run on this thread, for the time cap
+    static RUN_STARTED: Cell<Option<Instant>> = const { Cell::new(None) };
+    /// When the --begin/--end script running on this thread must stop
+    static SCRIPT_DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+    /// Set when the progress hook stopped a script at its deadline
+    static DEADLINE_HIT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Operations between checks of the time cap
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+/// Call first in every privileged function registration. Refuses the call
+/// when [`SandboxPolicy::apply_limits`] put the calling engine in the sandbox.
+pub fn require_unsandboxed(
+    context: &NativeCallContext,
+    function: &str,
+) -> Result<(), Box<EvalAltResult>> {
+    let enabled = context
+        .tag()
+        .is_some_and(|tag| tag.as_bool().unwrap_or(false));
+    SandboxPolicy { enabled }.check(function)
+}
+
+/// Progress hook enforcing [`SandboxPolicy::MAX_SCRIPT_TIME`]: returns a
+/// termination token once the current script run has taken too long.
+/// `operations` counts from 1 at the start of each run.
+pub fn check_script_time(policy: SandboxPolicy, operations: u64) -> Option<Dynamic> {
+    if !policy.enabled {
+        return None;
+    }
+    if operations <= 1 {
+        RUN_STARTED.with(|started| started.set(Some(Instant::now())));
+        return None;
+    }
+    if operations % TIME_CHECK_INTERVAL != 0 {
+        return None;
+    }
+    let started = RUN_STARTED.with(Cell::get)?;
+    (started.elapsed() > SandboxPolicy::MAX_SCRIPT_TIME).then(|| {
+        Dynamic::from(format!(
+            "script exceeded the --sandbox time limit of {}s",
+            SandboxPolicy::MAX_SCRIPT_TIME.as_secs()
+        ))
+    })
+}
+
+/// Run `script` under a wall-clock limit (`--begin-timeout-ms`,
+/// `--end-timeout-ms`). The progress hook stops the Rhai script once the
+/// limit passes; the returned flag tells whether it did. `None` runs it
+/// without a limit.
+pub fn run_with_deadline<T>(limit: Option<Duration>, script: impl FnOnce() -> T) -> (T, bool) {
+    SCRIPT_DEADLINE.with(|deadline| deadline.set(limit.map(|limit| Instant::now() + limit)));
+    DEADLINE_HIT.with(|hit| hit.set(false));
+    let result = script();
+    SCRIPT_DEADLINE.with(|deadline| deadline.set(None));
+    (result, DEADLINE_HIT.with(Cell::get))
+}
+
+/// Progress hook enforcing the deadline set by [`run_with_deadline`]
+pub fn check_script_deadline(operations: u64) -> Option<Dynamic> {
+    if operations % TIME_CHECK_INTERVAL != 0 {
+        return None;
+    }
+    let deadline = SCRIPT_DEADLINE.with(Cell::get)?;
+    (Instant::now() >= deadline).then(|| {
+        DEADLINE_HIT.with(|hit| hit.set(true));
+        Dynamic::from("script timed out")
+    })
+}
+
+/// Message for a script error caused by a sandbox cap, naming the sandbox
+/// instead of Rhai's generic limit errors
+pub fn describe_sandbox_error(policy: SandboxPolicy, err: &EvalAltResult) -> Option<String> {
+    if !policy.enabled {
+        return None;
+    }
+    match err {
+        EvalAltResult::ErrorTerminated(token, _) if token.is_string() => Some(token.to_string()),
+        EvalAltResult::ErrorTooManyOperations(_) => Some(format!(
+            "script exceeded the --sandbox limit of {} operations",
+            SandboxPolicy::MAX_OPERATIONS
+        )),
+        EvalAltResult::ErrorStackOverflow(_) => Some(format!(
+            "function calls nested deeper than the --sandbox limit of {}",
+            SandboxPolicy::MAX_CALL_LEVELS
+        )),
+        EvalAltResult::ErrorDataTooLarge(what, _) => {
+            Some(format!("{} exceeds the --sandbox size limit", what))
+        }
+        EvalAltResult::ErrorInFunctionCall(_, _, inner, _) => describe_sandbox_error(policy, inner),
+        _ => None,
+    }
+}
+
 /// Register safety functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     // Path access functions
@@ -492,6 +816,33 @@ pub fn register_functions(engine: &mut Engine) {
     engine.register_fn("to_int_or", to_int_or_with_format);
     engine.register_fn("to_float", to_float_with_format);
     engine.register_fn("to_float_or", to_float_or_with_format);
+
+    // Fallback and lookup helpers. Rhai has no variadic functions, so
+    // coalesce() takes an array or up to six arguments.
+    engine.register_fn("coalesce", coalesce);
+    engine.register_fn("coalesce", |a: Dynamic, b: Dynamic| coalesce(vec![a, b]));
+    engine.register_fn("coalesce", |a: Dynamic, b: Dynamic, c: Dynamic| {
+        coalesce(vec![a, b, c])
+    });
+    engine.register_fn(
+        "coalesce",
+        |a: Dynamic, b: Dynamic, c: Dynamic, d: Dynamic| coalesce(vec![a, b, c, d]),
+    );
+    engine.register_fn(
+        "coalesce",
+        |a: Dynamic, b: Dynamic, c: Dynamic, d: Dynamic, f: Dynamic| coalesce(vec![a, b, c, d, f]),
+    );
+    engine.register_fn(
+        "coalesce",
+        |a: Dynamic, b: Dynamic, c: Dynamic, d: Dynamic, f: Dynamic, g: Dynamic| {
+            coalesce(vec![a, b, c, d, f, g])
+        },
+    );
+    engine.register_fn("if_null", if_null);
+    engine.register_fn("lookup", table_lookup);
+    engine.register_fn("lookup", |value: Dynamic, table: Map| {
+        table_lookup(value, table, Dynamic::UNIT)
+    });
 }
 
 #[cfg(test)]
@@ -1012,4 +1363,132 @@ mod tests {
         );
         assert_eq!(result.as_float().unwrap(), 999.0);
     }
+
+    #[test]
+    fn test_coalesce_returns_first_present_value() {
+        let values = vec![
+            Dynamic::UNIT,
+            Dynamic::from(""),
+            Dynamic::from(Array::new()),
+            Dynamic::from(Map::new()),
+            Dynamic::from(0_i64),
+            Dynamic::from("later"),
+        ];
+        // 0 and false are real values, only () and empty containers are skipped
+        assert_eq!(coalesce(values).as_int().unwrap(), 0);
+        assert_eq!(
+            coalesce(vec![Dynamic::UNIT, Dynamic::from("b"), Dynamic::from("c")]).cast::<String>(),
+            "b"
+        );
+        assert!(coalesce(vec![Dynamic::UNIT, Dynamic::from("")]).is_unit());
+        assert!(coalesce(Array::new()).is_unit());
+    }
+
+    #[test]
+    fn test_if_null_only_replaces_unit() {
+        assert_eq!(
+            if_null(Dynamic::UNIT, Dynamic::from("n/a")).cast::<String>(),
+            "n/a"
+        );
+        assert_eq!(
+            if_null(Dynamic::from(""), Dynamic::from("n/a")).cast::<String>(),
+            ""
+        );
+        assert!(!if_null(Dynamic::from(false), Dynamic::from(true))
+            .as_bool()
+            .unwrap());
+    }
+
+    #[test]
+    fn test_table_lookup_with_default() {
+        let mut table = Map::new();
+        table.insert("200".into(), Dynamic::from("ok"));
+        table.insert("warn".into(), Dynamic::from("warning"));
+
+        let lookup = |value: Dynamic| {
+            table_lookup(value, table.clone(), Dynamic::from("other")).cast::<String>()
+        };
+        assert_eq!(lookup(Dynamic::from(200_i64)), "ok");
+        assert_eq!(lookup(Dynamic::from("200")), "ok");
+        assert_eq!(lookup(Dynamic::from("warn")), "warning");
+        assert_eq!(lookup(Dynamic::from("404")), "other");
+        assert_eq!(lookup(Dynamic::UNIT), "other");
+    }
+
+    #[test]
+    fn test_fallback_helpers_from_scripts() {
+        let mut engine = Engine::new();
+        register_functions(&mut engine);
+        let result: String = engine
+            .eval(r#"let e = #{user: "", name: "bob"}; coalesce(e.user, e.login, e.name, "anon")"#)
+            .unwrap();
+        assert_eq!(result, "bob");
+        let result: String = engine.eval(r#"coalesce(["", (), "x"])"#).unwrap();
+        assert_eq!(result, "x");
+        let result: String = engine
+            .eval(r#"lookup("E", #{"E": "error", "W": "warn"}, "info")"#)
+            .unwrap();
+        assert_eq!(result, "error");
+        let result: i64 = engine.eval(r#"let e = #{}; e.port.if_null(80)"#).unwrap();
+        assert_eq!(result, 80);
+    }
+
+    /// `name` and a call of an engine-listed signature with placeholder
+    /// arguments; the paths do not exist in case the gate lets one through
+    fn sample_call(signature: &str) -> (String, String) {
+        let (name, rest) = signature.split_once('(').unwrap();
+        let params = rest.split(')').next().unwrap_or_default();
+        let args: Vec<&str> = params
+            .split(", ")
+            .filter(|param| !param.is_empty())
+            .map(|param| match param.rsplit(": ").next() {
+                Some("bool") => "true",
+                Some("array") => r#"["line"]"#,
+                _ => r#""/nonexistent/kelora-sandbox""#,
+            })
+            .collect();
+        (name.to_string(), format!("{}({})", name, args.join(", ")))
+    }
+
+    #[test]
+    fn test_privileged_functions_respect_sandbox() {
+        // Everything the file, config and environment modules register
+        // reaches outside the process
+        let mut privileged = Engine::new_raw();
+        crate::rhai_functions::file_ops::register_functions(&mut privileged);
+        crate::rhai_functions::conf::register_functions(&mut privileged);
+        crate::rhai_functions::environment::register_functions(&mut privileged);
+        let signatures = privileged.gen_fn_signatures(false);
+        assert!(signatures.len() >= 9, "{:?}", signatures);
+
+        let mut engine = Engine::new();
+        crate::rhai_functions::register_all_functions(&mut engine);
+        SandboxPolicy { enabled: true }.apply_limits(&mut engine);
+
+        for signature in &signatures {
+            let (name, call) = sample_call(signature);
+            let err = engine.eval::<Dynamic>(&call).expect_err(&call);
+            assert!(
+                err.to_string()
+                    .contains(&format!("{}() is disabled by --sandbox", name)),
+                "{}: {}",
+                call,
+                err
+            );
+        }
+        let runaway = engine.eval::<Dynamic>("let n = 0; loop { n += 1; }");
+        assert!(matches!(
+            *runaway.unwrap_err(),
+            EvalAltResult::ErrorTooManyOperations(_)
+        ));
+
+        // Outside the sandbox the gate lets calls through
+        let mut engine = Engine::new();
+        crate::rhai_functions::register_all_functions(&mut engine);
+        SandboxPolicy::default().apply_limits(&mut engine);
+        assert!(engine
+            .eval::<String>(r#"get_env("KELORA_NO_SUCH_VAR", "x")"#)
+            .is_ok());
+        assert!(SandboxPolicy::default().check("get_env").is_ok());
+    }
 }
@@ -5,11 +5,66 @@
 //! - `to_kv()` - Convert Map to key-value format with configurable separators
 //! - `to_syslog()` - Convert Map to RFC3164 syslog format
 //! - `to_cef()` - Convert Map to Common Event Format (CEF)
+//! - `to_leef()` - Convert Map to IBM QRadar LEEF 1.0
 //! - `to_combined()` - Convert Map to Apache/NGINX combined log format
 
 use crate::event::{flatten_dynamic, FlattenStyle};
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use rhai::Engine;
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    ("to_cef", &[r#"e.cef_line = e.security_event.to_cef()"#]),
+    (
+        "to_combined",
+        &[r#"e.access_log = e.request.to_combined()"#],
+    ),
+    (
+        "to_kv",
+        &[r#"e.query = e.params.to_kv("&", "=")  // {a: 1, b: 2} → "a=1&b=2""#],
+    ),
+    ("to_leef", &[r#"e.leef_line = e.security_event.to_leef()"#]),
+    (
+        "to_logfmt",
+        &[r#"e.formatted = e.fields.to_logfmt()  // {a: 1, b: 2} → "a=1 b=2""#],
+    ),
+    ("to_syslog", &[r#"e.syslog_line = e.fields.to_syslog()"#]),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.to_cef()"],
+        "Convert map to Common Event Format (CEF) string",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.to_leef()"],
+        "Convert map to IBM QRadar LEEF 1.0 string",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.to_combined()"],
+        "Convert map to Apache/Nginx combined log format",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.to_kv([sep [,kv_sep]])"],
+        "Convert map to key-value string with separators",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.to_logfmt()"],
+        "Convert map to logfmt format string",
+    ),
+    FunctionDoc::new(
+        Section::Maps,
+        &["map.to_syslog()"],
+        "Convert map to syslog format string",
+    ),
+];
+
 /// Register all serializer functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     // to_logfmt() - Convert Map to logfmt format string
@@ -45,6 +100,9 @@ pub fn register_functions(engine: &mut Engine) {
     // to_cef() - Convert Map to CEF format string
     engine.register_fn("to_cef", |map: rhai::Map| -> String { to_cef_impl(map) });
 
+    // to_leef() - Convert Map to LEEF 1.0 format string
+    engine.register_fn("to_leef", |map: rhai::Map| -> String { to_leef_impl(map) });
+
     // to_combined() - Convert Map to combined log format string
     engine.register_fn("to_combined", |map: rhai::Map| -> String {
         to_combined_impl(map)
@@ -275,6 +333,60 @@ fn to_cef_impl(map: rhai::Map) -> String {
     output
 }
 
+/// Convert a Rhai Map to LEEF 1.0 (`LEEF:1.0|Vendor|Product|Version|EventID|attrs`)
+///
+/// Header values come from the field names `-f leef` produces, with the CEF
+/// spellings as fallbacks; every other field becomes a tab-separated
+/// `key=value` attribute.
+fn to_leef_impl(map: rhai::Map) -> String {
+    let header = |keys: &[&str], default: &str| {
+        keys.iter()
+            .find_map(|key| map.get(*key))
+            .map(|v| escape_cef_value(&v.to_string()))
+            .unwrap_or_else(|| default.to_string())
+    };
+    let vendor = header(&["vendor", "deviceVendor", "device_vendor"], "Kelora");
+    let product = header(
+        &["product", "deviceProduct", "device_product"],
+        "LogAnalyzer",
+    );
+    let version = header(&["version", "deviceVersion", "device_version"], "1.0");
+    let event_id = header(&["event_id", "eventid", "signatureId", "signature_id"], "1");
+
+    let mut output = format!("LEEF:1.0|{}|{}|{}|{}|", vendor, product, version, event_id);
+
+    let mut attributes = Vec::new();
+    for (key, value) in &map {
+        if matches!(
+            key.as_str(),
+            "leefver"
+                | "vendor"
+                | "deviceVendor"
+                | "device_vendor"
+                | "product"
+                | "deviceProduct"
+                | "device_product"
+                | "version"
+                | "deviceVersion"
+                | "device_version"
+                | "event_id"
+                | "eventid"
+                | "signatureId"
+                | "signature_id"
+        ) {
+            continue;
+        }
+        attributes.push(format!(
+            "{}={}",
+            key,
+            escape_leef_attribute_value(&value.to_string())
+        ));
+    }
+    output.push_str(&attributes.join("\t"));
+
+    output
+}
+
 /// Convert a Rhai Map to Apache/NGINX combined log format
 fn to_combined_impl(map: rhai::Map) -> String {
     use chrono::Utc;
@@ -431,6 +543,12 @@ fn escape_cef_value(value: &str) -> String {
     value.replace('\\', "\\\\").replace('|', "\\|")
 }
 
+/// LEEF has no escape for its attribute delimiter, so tabs and line breaks
+/// inside a value become spaces.
+fn escape_leef_attribute_value(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
 /// Escape CEF extension field values (equals and backslashes)
 fn escape_cef_extension_value(value: &str) -> String {
     value
@@ -505,4 +623,38 @@ mod tests {
         assert_eq!(escape_cef_value("test|value"), "test\\|value");
         assert_eq!(escape_cef_value("test\\value"), "test\\\\value");
     }
+
+    #[test]
+    fn test_to_leef_round_trips_through_parser() {
+        use crate::pipeline::EventParser;
+
+        let mut map = rhai::Map::new();
+        map.insert("vendor".into(), Dynamic::from("Acme"));
+        map.insert("product".into(), Dynamic::from("Fire|Wall"));
+        map.insert("event_id".into(), Dynamic::from("deny"));
+        map.insert("src".into(), Dynamic::from("10.0.0.1"));
+        map.insert("msg".into(), Dynamic::from("line one\tline two"));
+        map.insert("port".into(), Dynamic::from(443_i64));
+
+        let result = to_leef_impl(map);
+        assert_eq!(
+            result,
+            "LEEF:1.0|Acme|Fire\\|Wall|1.0|deny|msg=line one line two\tport=443\tsrc=10.0.0.1"
+        );
+
+        let event = crate::parsers::LeefParser::new().parse(&result).unwrap();
+        assert_eq!(event.fields["product"].to_string(), "Fire|Wall");
+        assert_eq!(event.fields["port"].as_int().unwrap(), 443);
+        assert_eq!(event.fields["src"].to_string(), "10.0.0.1");
+    }
+
+    #[test]
+    fn test_to_leef_defaults() {
+        let mut map = rhai::Map::new();
+        map.insert("extra_field".into(), Dynamic::from("value"));
+        assert_eq!(
+            to_leef_impl(map),
+            "LEEF:1.0|Kelora|LogAnalyzer|1.0|1|extra_field=value"
+        );
+    }
 }
@@ -1,8 +1,134 @@
+use std::cell::RefCell;
+
 use chrono::{DateTime, Utc};
-use rhai::{Array, Dynamic, Engine, Map};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map};
 
 use crate::event::Event;
 use crate::rhai_functions::datetime::DateTimeWrapper;
+use crate::rhai_functions::docs::{FunctionDoc, Section};
+
+// Member events of the span whose --span-summary/--span-close hook is running.
+// Bound only for the duration of the hook so span_* aggregates can read them.
+thread_local! {
+    static SPAN_MEMBERS: RefCell<Option<Vec<Event>>> = const { RefCell::new(None) };
+}
+
+/// Make a closing span's member events visible to the span_* aggregate functions.
+pub fn bind_span_members(events: Vec<Event>) {
+    SPAN_MEMBERS.with(|members| *members.borrow_mut() = Some(events));
+}
+
+/// Clear the bound span and hand its member events back to the caller.
+pub fn unbind_span_members() -> Vec<Event> {
+    SPAN_MEMBERS
+        .with(|members| members.borrow_mut().take())
+        .unwrap_or_default()
+}
+
+fn with_span_members<T>(
+    func: &str,
+    f: impl FnOnce(&[Event]) -> T,
+) -> Result<T, Box<EvalAltResult>> {
+    SPAN_MEMBERS.with(|members| match members.borrow().as_deref() {
+        Some(events) => Ok(f(events)),
+        None => Err(format!(
+            "{}() is only available in --span-summary and --span-close scripts",
+            func
+        )
+        .into()),
+    })
+}
+
+fn numeric_values<'a>(events: &'a [Event], field: &'a str) -> impl Iterator<Item = &'a Dynamic> {
+    events
+        .iter()
+        .filter_map(move |event| event.fields.get(field))
+        .filter(|value| value.is_int() || value.is_float())
+}
+
+fn as_f64(value: &Dynamic) -> f64 {
+    value
+        .as_float()
+        .unwrap_or_else(|_| value.as_int().unwrap_or(0) as f64)
+}
+
+/// Rhai function: span_sum(field) - sum of the numeric values of `field` across the span.
+/// Integer fields sum to an integer; any float value makes the result a float.
+fn span_sum(field: &str) -> Result<Dynamic, Box<EvalAltResult>> {
+    with_span_members("span_sum", |events| {
+        let mut int_sum: i64 = 0;
+        let mut float_sum = 0.0;
+        let mut saw_float = false;
+        for value in numeric_values(events, field) {
+            if let Ok(i) = value.as_int() {
+                int_sum = int_sum.wrapping_add(i);
+                float_sum += i as f64;
+            } else {
+                saw_float = true;
+                float_sum += as_f64(value);
+            }
+        }
+        if saw_float {
+            Dynamic::from(float_sum)
+        } else {
+            Dynamic::from(int_sum)
+        }
+    })
+}
+
+fn span_extreme(func: &str, field: &str, want_max: bool) -> Result<Dynamic, Box<EvalAltResult>> {
+    with_span_members(func, |events| {
+        let mut best: Option<&Dynamic> = None;
+        for value in numeric_values(events, field) {
+            let replace = match best {
+                None => true,
+                Some(current) if want_max => as_f64(value) > as_f64(current),
+                Some(current) => as_f64(value) < as_f64(current),
+            };
+            if replace {
+                best = Some(value);
+            }
+        }
+        best.cloned().unwrap_or(Dynamic::UNIT)
+    })
+}
+
+/// Rhai function: span_min(field) - smallest numeric value of `field`, or () if none.
+fn span_min(field: &str) -> Result<Dynamic, Box<EvalAltResult>> {
+    span_extreme("span_min", field, false)
+}
+
+/// Rhai function: span_max(field) - largest numeric value of `field`, or () if none.
+fn span_max(field: &str) -> Result<Dynamic, Box<EvalAltResult>> {
+    span_extreme("span_max", field, true)
+}
+
+/// Rhai function: span_count() - number of member events in the span.
+fn span_count() -> Result<i64, Box<EvalAltResult>> {
+    with_span_members("span_count", |events| events.len() as i64)
+}
+
+/// Rhai function: span_count(field) - number of member events where `field` is set.
+fn span_count_field(field: &str) -> Result<i64, Box<EvalAltResult>> {
+    with_span_members("span_count", |events| {
+        events
+            .iter()
+            .filter(|event| event.fields.get(field).is_some_and(|v| !v.is_unit()))
+            .count() as i64
+    })
+}
+
+/// Rhai function: span_values(field) - values of `field` in arrival order, skipping unset ones.
+fn span_values(field: &str) -> Result<Array, Box<EvalAltResult>> {
+    with_span_members("span_values", |events| {
+        events
+            .iter()
+            .filter_map(|event| event.fields.get(field))
+            .filter(|value| !value.is_unit())
+            .cloned()
+            .collect()
+    })
+}
 
 #[derive(Clone)]
 pub struct SpanBinding {
@@ -69,6 +195,67 @@ impl SpanBinding {
     }
 }
 
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::SpanContext,
+        &["span.id"],
+        "Span identifier ('#index' for count, 'ISO/DURATION' for time)",
+    ),
+    FunctionDoc::new(
+        Section::SpanContext,
+        &["span.start"],
+        "Span start as DateTime (time spans) or () for count spans",
+    ),
+    FunctionDoc::new(
+        Section::SpanContext,
+        &["span.end"],
+        "Span end as DateTime (time spans) or () for count spans",
+    ),
+    FunctionDoc::new(
+        Section::SpanContext,
+        &["span.size"],
+        "Number of events that survived the span",
+    ),
+    FunctionDoc::new(
+        Section::SpanContext,
+        &["span.events"],
+        "Array of event maps for the span in arrival order",
+    ),
+    FunctionDoc::new(
+        Section::SpanContext,
+        &["span.metrics"],
+        "Per-window metrics from additive track_* calls: track_freq,",
+    )
+    .with_details(&[
+        "track_sum, track_inc, track_avg, track_unique (read-only map).",
+        "Non-additive aggregators (min, max, percentiles, cardinality,",
+        "top/bottom, top_by/bottom_by) have no per-window value and are",
+        "omitted with a warning; iterate span.events to compute them",
+        "per window.",
+    ]),
+    FunctionDoc::new(
+        Section::SpanAggregates,
+        &["span_sum(field)"],
+        "Sum of numeric values of field across the span's members",
+    ),
+    FunctionDoc::new(
+        Section::SpanAggregates,
+        &["span_min(field)", "span_max(field)"],
+        "Smallest/largest numeric value of field, or () if none",
+    ),
+    FunctionDoc::new(
+        Section::SpanAggregates,
+        &["span_count([field])"],
+        "Number of members (or members where field is set)",
+    ),
+    FunctionDoc::new(
+        Section::SpanAggregates,
+        &["span_values(field)"],
+        "Array of field values in arrival order (unset skipped)",
+    ),
+];
+
 pub fn register_functions(engine: &mut Engine) {
     engine.register_type_with_name::<SpanBinding>("Span");
     engine.register_get("id", SpanBinding::get_id);
@@ -77,6 +264,13 @@ pub fn register_functions(engine: &mut Engine) {
     engine.register_get("size", SpanBinding::get_size);
     engine.register_get("events", SpanBinding::get_events);
     engine.register_get("metrics", SpanBinding::get_metrics);
+
+    engine.register_fn("span_sum", span_sum);
+    engine.register_fn("span_min", span_min);
+    engine.register_fn("span_max", span_max);
+    engine.register_fn("span_count", span_count);
+    engine.register_fn("span_count", span_count_field);
+    engine.register_fn("span_values", span_values);
 }
 
 fn event_to_map(event: &Event) -> Map {
@@ -3,6 +3,7 @@
 //! Provides a mutable `state` map in sequential mode for tracking information
 //! across events. In parallel mode, accessing state returns an error.
 
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use rhai::{Dynamic, Engine, EvalAltResult, Map, Position};
 use std::fmt;
 use std::sync::{Arc, RwLock};
@@ -44,6 +45,129 @@ impl fmt::Display for StateNotAvailable {
     }
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    ("clear", &[r#"state.clear()"#]),
+    (
+        "keys",
+        &[r#"let all_keys = state.keys()  // ["count", "total", "seen_ips"]"#],
+    ),
+    (
+        "len",
+        &[r#"let num_keys = state.len()  // Number of entries"#],
+    ),
+    (
+        "mixin",
+        &[r#"state.mixin(e.metadata)  // Add all metadata fields"#],
+    ),
+    (
+        "remove",
+        &[
+            r#"let old_value = state.remove("temp_data")  // Remove and get value"#,
+            r#"state.remove("cache")  // Just remove"#,
+        ],
+    ),
+    (
+        "set",
+        &[
+            r#"state.set("total_bytes", 0)"#,
+            r#"state.set("count", current + 1)"#,
+            r#"state.set("count", (state.get("count") ?? 0) + 1)"#,
+        ],
+    ),
+    (
+        "to_map",
+        &[
+            r#"let state_json = state.to_map().to_json()"#,
+            r#"let state_logfmt = state.to_map().to_logfmt()"#,
+            r#"let snapshot = state.to_map()"#,
+        ],
+    ),
+    (
+        "values",
+        &[r#"let all_values = state.values()  // [42, 1024, [...]]"#],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::State,
+        &[r#"state["key"]"#],
+        r#"Get/set state value via indexer (state["count"] = 0)"#,
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.get(key)"],
+        "Get value from state (returns () if not found)",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.get(key, default)"],
+        "Get value with fallback (missing or () returns default)",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.set(key, value)"],
+        "Set value in state",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.contains(key)"],
+        "Check if key exists in state",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.remove(key)"],
+        "Remove key from state (returns removed value or ())",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.clear()"],
+        "Remove all entries from state",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.keys()"],
+        "Get array of all keys in state",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.values()"],
+        "Get array of all values in state",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.len()"],
+        "Get number of entries in state",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.is_empty()"],
+        "Check if state is empty",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.mixin(map)"],
+        "Merge map into state (overwrites existing keys)",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.fill_with(map)"],
+        "Replace entire state with new map",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state.to_map()"],
+        "Convert state to regular map (for use with to_logfmt(), etc.)",
+    ),
+    FunctionDoc::new(
+        Section::State,
+        &["state += map"],
+        "Merge map into state (operator form)",
+    ),
+];
+
 /// Register state-related functions with the Rhai engine
 #[allow(dependency_on_unit_never_type_fallback)]
 pub fn register(engine: &mut Engine) {
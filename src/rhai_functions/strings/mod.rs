@@ -1,11 +1,14 @@
 //! String transformations and regex helpers for Rhai scripts.
 //! Parsing helpers live in `crate::rhai_functions::parsers`.
 
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use rhai::Engine;
 
 mod ops;
 mod regex_ops;
 
+pub(crate) use ops::edit_distance_impl;
+
 // Re-export capture module functions for backward compatibility
 #[allow(unused_imports)]
 pub use crate::rhai_functions::capture::{
@@ -15,6 +18,306 @@ pub use crate::rhai_functions::capture::{
     CapturedMessage,
 };
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "after",
+        &[
+            r#"e.extension = e.filename.after(".")  // "file.txt" → "txt""#,
+            r#"e.domain = e.email.after("@")  // "user@host.com" → "host.com""#,
+        ],
+    ),
+    (
+        "before",
+        &[
+            r#"e.user = e.email.before("@")  // "user@host.com" → "user""#,
+            r#"e.path = e.url.before("?")  // Strip query string"#,
+        ],
+    ),
+    (
+        "between",
+        &[
+            r#"e.quoted = e.line.between('"', '"')  // Extract quoted string"#,
+            r#""[a][b][c]".between("[", "]", 2)  // "b" - same as .after("[", 2).before("]")"#,
+        ],
+    ),
+    ("clip", &[r#"e.word = "'hello!'".clip()  // → "hello""#]),
+    ("count", &[r#"e.error_count = e.log.count("ERROR")"#]),
+    (
+        "ending_with",
+        &[r#"e.up_to_end = e.log.ending_with(".txt")  // "file.txt more" → "file.txt""#],
+    ),
+    ("eprint", &[r#"eprint("Warning: " + e.error)"#]),
+    (
+        "extract_regex",
+        &[
+            r#"e.error_code = e.message.extract_regex(r"ERR-(\d+)", 1)  // "ERR-404" → "404""#,
+            r#"e.full_match = e.line.extract_regex(r"\d{3}")  // First 3-digit number"#,
+        ],
+    ),
+    (
+        "extract_regex_maps",
+        &[
+            r#"let errors = e.log.extract_regex_maps(r"(?P<code>ERR-\d+): (?P<msg>[^\n]+)", "error");"#,
+        ],
+    ),
+    (
+        "extract_regexes",
+        &[
+            r#"e.numbers = e.line.extract_regexes(r"\d+")  // All numbers"#,
+            r#"e.codes = e.message.extract_regexes(r"ERR-(\d+)", 1)  // All error codes"#,
+        ],
+    ),
+    (
+        "join",
+        &[
+            r#"e.path = e.parts.join("/")"#,
+            r#"e.csv = e.values.join(",")"#,
+        ],
+    ),
+    ("lclip", &[r#"e.left = "...start".lclip()  // → "start""#]),
+    (
+        "lower",
+        &[
+            r#"e.lowercase = e.name.lower()  // "Hello" → "hello""#,
+            r#"e.also_lower = e.name.to_lower()  // Same as lower()"#,
+        ],
+    ),
+    (
+        "lstrip",
+        &[r##"e.trimmed = e.line.lstrip("# ")  // Remove "# " from left"##],
+    ),
+    (
+        "or_empty",
+        &[
+            r#"e.name = e.message.after("prefix:").or_empty()"#,
+            r#"track_unique("names", e.extracted.or_empty())"#,
+            r#"e.tags = e.tags.or_empty()  // [] becomes (), field removed"#,
+        ],
+    ),
+    ("rclip", &[r#"e.right = "end...".rclip()  // → "end""#]),
+    (
+        "rstrip",
+        &[r#"e.path = e.filename.rstrip("/")  // Remove trailing slashes"#],
+    ),
+    (
+        "split_regex",
+        &[r#"e.tokens = e.line.split_regex(r"\s+")  // Split on whitespace"#],
+    ),
+    (
+        "starting_with",
+        &[
+            r#"e.from_error = e.log.starting_with("ERROR:")  // "INFO: ok ERROR: bad" → "ERROR: bad""#,
+        ],
+    ),
+    (
+        "str_word_wrap",
+        &[
+            r#"print(("Top errors: " + metrics["errors"].join(", ")).str_word_wrap(72, 4))  // from track_unique("errors", e.msg)"#,
+        ],
+    ),
+    (
+        "str_wrap",
+        &[r#"e.hex_block = e.payload.str_wrap(32)  // Fixed-width chunks"#],
+    ),
+    (
+        "strip",
+        &[r#"e.clean = e.text.strip()  // Remove leading/trailing whitespace"#],
+    ),
+    (
+        "upper",
+        &[
+            r#"e.normalized = e.country_code.upper()  // "us" → "US""#,
+            r#"e.also_upper = e.code.to_upper()  // Same as upper()"#,
+        ],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.after(delimiter [,nth])"],
+        "Text after occurrence of delimiter (nth: 1=first, -1=last)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.before(delimiter [,nth])"],
+        "Text before occurrence of delimiter (nth: 1=first, -1=last)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.between(start, end [,nth])"],
+        "Text between start and end delimiters (nth: 1=first, -1=last)",
+    )
+    .with_details(&["Equivalent to: text.after(start, nth).before(end)"]),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.clip()"],
+        "Remove leading/trailing non-alphanumeric characters",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.count(pattern)"],
+        "Count occurrences of pattern in text",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.edit_distance(other)"],
+        "Compute Levenshtein edit distance between two strings",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.ending_with(suffix [,nth])"],
+        "Return substring from start to end of suffix (nth: 1=first, -1=last)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_regexes(pattern [,group])"],
+        "Extract all regex matches as array",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_regex_maps(pattern, field)"],
+        "Extract regex matches as array of maps for fan-out",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_re_maps(pattern, field)"],
+        "Deprecated alias for extract_regex_maps",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.extract_regex(pattern [,group])"],
+        "Extract regex match or capture group",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.is_digit()"],
+        "Check if text contains only digits",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.lclip()"],
+        "Remove leading non-alphanumeric characters (left side only)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.lower()"],
+        "Convert text to lowercase",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.lstrip([chars])"],
+        "Remove leading whitespace or specified characters",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.rclip()"],
+        "Remove trailing non-alphanumeric characters (right side only)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.replace_regex(pattern, replacement)"],
+        "Replace all regex matches",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.replace_re(pattern, replacement)"],
+        "Deprecated alias for replace_regex",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.rstrip([chars])"],
+        "Remove trailing whitespace or specified characters",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.slice(spec)"],
+        r#"Slice text using Python notation (e.g., "1:5", ":3", "-2:")"#,
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.split_regex(pattern)"],
+        "Split text by regex pattern",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.split_re(pattern)"],
+        "Deprecated alias for split_regex",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.starting_with(prefix [,nth])"],
+        "Return substring from prefix to end (nth: 1=first, -1=last)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.strip([chars])"],
+        "Remove whitespace or specified characters",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.str_word_wrap(width [,indent])"],
+        "Wrap at the last space within width; indent continuation lines",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.str_wrap(width)"],
+        "Hard-wrap every width characters, ignoring word boundaries",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.to_float()"],
+        "Convert text to float (returns () on error)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.to_float(thousands, decimal)"],
+        "Parse with explicit separators",
+    )
+    .with_details(&[
+        r#"- thousands: remove ANY char in string (e.g., ',', ',. ', ",.'")"#,
+        "- decimal: single char or empty (multi-char returns error)",
+    ]),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.to_int()"],
+        "Convert text to integer (returns () on error)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.to_int(thousands)"],
+        "Parse with thousands separator removal",
+    )
+    .with_details(&[r#"- thousands: remove ANY char in string (e.g., ',', '. ', ",.'")"#]),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.or_empty()"],
+        "Convert empty string/array/map to () for removal/filtering",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.lower()"],
+        "Convert to lowercase (alias for to_lower(); for Python users)",
+    ),
+    FunctionDoc::new(
+        Section::Strings,
+        &["text.upper()"],
+        "Convert to uppercase (alias for to_upper(); for Python users)",
+    ),
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.join(separator)"],
+        "Join array elements with separator",
+    ),
+    FunctionDoc::new(
+        Section::Utility,
+        &["eprint(message)"],
+        "Print to stderr (suppressed with --no-script-output or data-only modes)",
+    ),
+];
+
 pub fn register_functions(engine: &mut Engine) {
     ops::register_functions(engine);
     regex_ops::register_functions(engine);
@@ -4,7 +4,7 @@ mod output {
     use rhai::{Dynamic, Engine};
 
     use crate::rhai_functions::capture::{
-        capture_eprint, capture_stderr, is_parallel_mode, is_suppress_side_effects,
+        capture_eprint, capture_stderr, emit_eprint, is_parallel_mode, is_suppress_side_effects,
     };
 
     pub fn register_functions(engine: &mut Engine) {
@@ -18,17 +18,19 @@ mod output {
                 capture_eprint(msg.clone());
                 capture_stderr(msg);
             } else {
-                eprintln!("{}", msg);
+                emit_eprint(&msg);
             }
         });
     }
 }
 
+pub(crate) use self::core::edit_distance_impl;
+
 mod core {
     use rhai::{Array, Dynamic, Engine};
     use std::convert::TryFrom;
 
-    fn edit_distance_impl(lhs: &str, rhs: &str) -> i64 {
+    pub(crate) fn edit_distance_impl(lhs: &str, rhs: &str) -> i64 {
         if lhs == rhs {
             return 0;
         }
@@ -65,6 +67,73 @@ mod core {
         i64::try_from(prev[len_rhs]).unwrap_or(i64::MAX)
     }
 
+    /// Break every line of `text` into chunks of exactly `width` characters.
+    fn hard_wrap(text: &str, width: i64) -> String {
+        if width <= 0 {
+            return text.to_string();
+        }
+        let width = width as usize;
+        text.split('\n')
+            .map(|line| {
+                let chars: Vec<char> = line.chars().collect();
+                chars
+                    .chunks(width)
+                    .map(|chunk| chunk.iter().collect::<String>())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Greedy word wrap: each line breaks at the last space that keeps it
+    /// within `width` characters, words longer than a line are hard-broken,
+    /// and continuation lines start with `indent` spaces (counted in `width`).
+    /// Existing newlines are kept.
+    fn word_wrap(text: &str, width: i64, indent: i64) -> String {
+        if width <= 0 {
+            return text.to_string();
+        }
+        let width = width as usize;
+        let indent = " ".repeat(indent.max(0) as usize);
+        let continuation_width = width.saturating_sub(indent.len()).max(1);
+
+        let mut paragraphs = Vec::new();
+        for paragraph in text.split('\n') {
+            let mut lines: Vec<String> = Vec::new();
+            let mut line = String::new();
+            let mut line_len = 0;
+            for word in paragraph.split_whitespace() {
+                let mut rest: Vec<char> = word.chars().collect();
+                while !rest.is_empty() {
+                    let room = if lines.is_empty() {
+                        width
+                    } else {
+                        continuation_width
+                    };
+                    let sep = usize::from(line_len > 0);
+                    if line_len + sep + rest.len() <= room {
+                        if sep == 1 {
+                            line.push(' ');
+                        }
+                        line_len += sep + rest.len();
+                        line.extend(rest.drain(..));
+                    } else if line_len > 0 {
+                        lines.push(std::mem::take(&mut line));
+                        line_len = 0;
+                    } else {
+                        lines.push(rest.drain(..room).collect());
+                    }
+                }
+            }
+            if line_len > 0 || lines.is_empty() {
+                lines.push(line);
+            }
+            paragraphs.push(lines.join(&format!("\n{}", indent)));
+        }
+        paragraphs.join("\n")
+    }
+
     pub fn register_functions(engine: &mut Engine) {
         engine.register_fn("contains", |text: &str, pattern: &str| {
             text.contains(pattern)
@@ -125,6 +194,14 @@ mod core {
 
         engine.register_fn("edit_distance", edit_distance_impl);
 
+        engine.register_fn("str_wrap", hard_wrap);
+
+        engine.register_fn("str_word_wrap", |text: &str, width: i64| -> String {
+            word_wrap(text, width, 0)
+        });
+
+        engine.register_fn("str_word_wrap", word_wrap);
+
         engine.register_fn("join", |separator: &str, items: Array| -> String {
             items
                 .into_iter()
@@ -3607,6 +3607,83 @@ fn test_combined_log_format_consistency() {
     assert!(result.ends_with(" \"0.045\""));
 }
 
+#[test]
+fn test_str_wrap_hard_breaks_at_width() {
+    let mut engine = rhai::Engine::new();
+    register_all_string_functions(&mut engine);
+    let mut scope = Scope::new();
+    scope.push("text", "abcdefghij".repeat(10));
+
+    let wrapped = engine
+        .eval_with_scope::<String>(&mut scope, "text.str_wrap(40)")
+        .unwrap();
+    let lines: Vec<&str> = wrapped.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0].len(), 40);
+    assert_eq!(lines[1].len(), 40);
+    assert_eq!(lines[2].len(), 20);
+
+    // Breaks ignore word boundaries; non-positive widths leave text alone
+    let wrapped = engine
+        .eval::<String>(r#"str_wrap("hello world", 4)"#)
+        .unwrap();
+    assert_eq!(wrapped, "hell\no wo\nrld");
+    let unchanged = engine.eval::<String>(r#"str_wrap("hello", 0)"#).unwrap();
+    assert_eq!(unchanged, "hello");
+}
+
+#[test]
+fn test_str_word_wrap_breaks_at_spaces() {
+    let mut engine = rhai::Engine::new();
+    register_all_string_functions(&mut engine);
+    let mut scope = Scope::new();
+    // 20 four-letter words: 99 characters
+    scope.push("text", vec!["abcd"; 20].join(" "));
+
+    let wrapped = engine
+        .eval_with_scope::<String>(&mut scope, "text.str_word_wrap(40)")
+        .unwrap();
+    let lines: Vec<&str> = wrapped.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], ["abcd"; 8].join(" "));
+    assert!(lines
+        .iter()
+        .all(|line| line.len() <= 40 && !line.ends_with(' ')));
+
+    let indented = engine
+        .eval_with_scope::<String>(&mut scope, "text.str_word_wrap(40, 4)")
+        .unwrap();
+    let lines: Vec<&str> = indented.lines().collect();
+    assert!(!lines[0].starts_with(' '));
+    assert!(lines[1..].iter().all(|line| line.starts_with("    abcd")));
+    assert!(lines.iter().all(|line| line.len() <= 40));
+
+    // Existing line breaks are kept
+    let wrapped = engine
+        .eval::<String>(r#""one two\nthree four".str_word_wrap(8)"#)
+        .unwrap();
+    assert_eq!(wrapped, "one two\nthree\nfour");
+}
+
+#[test]
+fn test_str_word_wrap_hard_breaks_long_words() {
+    let mut engine = rhai::Engine::new();
+    register_all_string_functions(&mut engine);
+    let mut scope = Scope::new();
+    scope.push("word", "x".repeat(100));
+
+    let wrapped = engine
+        .eval_with_scope::<String>(&mut scope, "word.str_word_wrap(40)")
+        .unwrap();
+    let lines: Vec<&str> = wrapped.lines().collect();
+    assert_eq!(lines, vec!["x".repeat(40), "x".repeat(40), "x".repeat(20)]);
+
+    let wrapped = engine
+        .eval::<String>(r#"str_word_wrap("see abcdefghijkl end", 6, 2)"#)
+        .unwrap();
+    assert_eq!(wrapped, "see\n  abcd\n  efgh\n  ijkl\n  end");
+}
+
 #[test]
 fn test_edit_distance_function() {
     let mut engine = rhai::Engine::new();
@@ -1,3 +1,4 @@
+use crate::run_scope;
 use crate::stats::ProcessingStats;
 use rhai::Dynamic;
 use std::cell::Cell;
@@ -21,6 +22,104 @@ thread_local! {
     static FILTER_STAGE_SUCCESS_BITS: Cell<u64> = const { Cell::new(0) };
 }
 
+/// Samples kept per error category when --on-parse-error-sample is not given.
+const DEFAULT_ERROR_SAMPLES: usize = 3;
+
+/// Distinct samples per error category requested with --on-parse-error-sample
+/// for the current run
+fn error_sample_limit() -> Option<usize> {
+    run_scope::with_current(|scope| scope.settings.error_sample_limit)
+}
+
+/// Samples kept per error category, enforced as errors are tracked and again
+/// when parallel workers' samples are merged.
+pub(crate) fn error_sample_cap() -> usize {
+    error_sample_limit().unwrap_or(DEFAULT_ERROR_SAMPLES)
+}
+
+/// Original lines in the JSON error summary are cut to this many characters.
+const JSON_SAMPLE_LINE_MAX_CHARS: usize = 200;
+
+/// Reduce a failing line to the shape used to dedupe samples: every word
+/// containing a digit (counters, timestamps, IDs, hex) becomes `#` and runs of
+/// whitespace collapse to one space, so lines differing only in values match.
+fn sample_template(text: &str) -> String {
+    let mut template = String::with_capacity(text.len());
+    let mut word = String::new();
+    let mut last_was_space = false;
+
+    let flush_word = |word: &mut String, template: &mut String| {
+        if word.chars().any(|c| c.is_ascii_digit()) {
+            template.push('#');
+        } else {
+            template.push_str(word);
+        }
+        word.clear();
+    };
+
+    for ch in text.trim().chars() {
+        if ch.is_alphanumeric() {
+            word.push(ch);
+            last_was_space = false;
+            continue;
+        }
+        flush_word(&mut word, &mut template);
+        if ch.is_whitespace() {
+            if !last_was_space {
+                template.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            template.push(ch);
+            last_was_space = false;
+        }
+    }
+    flush_word(&mut word, &mut template);
+    template
+}
+
+fn sample_string(sample: &rhai::Map, field: &str) -> Option<String> {
+    sample.get(field).and_then(|v| v.clone().into_string().ok())
+}
+
+/// Pick the samples shown for --on-parse-error-sample: per error category, in
+/// line order, the first `limit` samples with distinct templates (samples
+/// recorded without a template are never treated as duplicates). Parallel
+/// workers each keep their own samples, so the merged set is deduped again here.
+fn select_error_samples(mut samples: Vec<rhai::Map>, limit: usize) -> Vec<rhai::Map> {
+    let line_num = |sample: &rhai::Map| {
+        sample
+            .get("line_num")
+            .and_then(|v| v.as_int().ok())
+            .unwrap_or(0)
+    };
+    samples.sort_by(|a, b| {
+        sample_string(a, "error_type")
+            .cmp(&sample_string(b, "error_type"))
+            .then_with(|| sample_string(a, "filename").cmp(&sample_string(b, "filename")))
+            .then_with(|| line_num(a).cmp(&line_num(b)))
+    });
+
+    let mut seen: HashSet<(Option<String>, Option<String>)> = HashSet::new();
+    let mut per_category: HashMap<Option<String>, usize> = HashMap::new();
+    samples
+        .into_iter()
+        .filter(|sample| {
+            let category = sample_string(sample, "error_type");
+            let shown = per_category.entry(category.clone()).or_insert(0);
+            if *shown >= limit {
+                return false;
+            }
+            let template = sample_string(sample, "template");
+            if template.is_some() && !seen.insert((category, template)) {
+                return false;
+            }
+            *shown += 1;
+            true
+        })
+        .collect()
+}
+
 /// Clear the per-run "success seen" flags. Called once at the start of each run
 /// (and each parallel worker) so a fresh run records its own first success.
 pub fn reset_stage_success_flags() {
@@ -361,7 +460,18 @@ pub fn track_error(
             .unwrap_or_else(|| Dynamic::from(rhai::Array::new()));
 
         if let Ok(mut arr) = current_samples.into_array() {
-            if arr.len() < 3 {
+            let sample_limit = error_sample_limit();
+            let template = sample_limit.map(|_| sample_template(original_line.unwrap_or(message)));
+            let duplicate = template.as_ref().is_some_and(|template| {
+                arr.iter().any(|sample| {
+                    sample
+                        .read_lock::<rhai::Map>()
+                        .and_then(|map| sample_string(&map, "template"))
+                        .is_some_and(|existing| &existing == template)
+                })
+            });
+
+            if !duplicate && arr.len() < error_sample_cap() {
                 let mut sample_obj = rhai::Map::new();
                 sample_obj.insert("error_type".into(), Dynamic::from(error_type.to_string()));
                 sample_obj.insert(
@@ -375,16 +485,109 @@ pub fn track_error(
                 if let Some(filename) = filename {
                     sample_obj.insert("filename".into(), Dynamic::from(filename.to_string()));
                 }
+                if let Some(format_name) = format_name {
+                    sample_obj.insert("format".into(), Dynamic::from(format_name.to_string()));
+                }
+                if let Some(template) = template {
+                    sample_obj.insert("template".into(), Dynamic::from(template));
+                }
 
                 arr.push(Dynamic::from(sample_obj));
             }
 
             state.insert(samples_key.clone(), Dynamic::from(arr));
-            state.insert(format!("__op_{}", samples_key), Dynamic::from("unique"));
+            state.insert(
+                format!("__op_{}", samples_key),
+                Dynamic::from("error_samples"),
+            );
         }
     });
 }
 
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+fn sample_to_json(sample: &rhai::Map) -> serde_json::Value {
+    // --redact-error-samples drops line text and messages
+    let redact = run_scope::with_current(|scope| scope.settings.redact_error_samples);
+    let mut obj = serde_json::Map::new();
+    obj.insert(
+        "file".to_string(),
+        sample_string(sample, "filename").map_or(serde_json::Value::Null, Into::into),
+    );
+    obj.insert(
+        "line".to_string(),
+        sample
+            .get("line_num")
+            .and_then(|v| v.as_int().ok())
+            .filter(|line| *line > 0)
+            .map_or(serde_json::Value::Null, Into::into),
+    );
+    if !redact {
+        if let Some(line) = sample_string(sample, "original_line") {
+            obj.insert(
+                "original_line".to_string(),
+                truncate_chars(&line, JSON_SAMPLE_LINE_MAX_CHARS).into(),
+            );
+        }
+        if let Some(message) = sample_string(sample, "message") {
+            obj.insert("message".to_string(), message.into());
+        }
+    }
+    serde_json::Value::Object(obj)
+}
+
+/// Structured error summary for `--metrics=json`: one entry per error category
+/// with its count, the input format involved (parse errors), and the capped
+/// samples. None when nothing errored.
+pub fn error_summary_json(internal: &HashMap<String, Dynamic>) -> Option<serde_json::Value> {
+    let mut categories: Vec<(&str, i64)> = internal
+        .iter()
+        .filter_map(|(key, value)| {
+            let category = key.strip_prefix("__kelora_error_count_")?;
+            let count = value.as_int().ok().filter(|count| *count > 0)?;
+            Some((category, count))
+        })
+        .collect();
+    if categories.is_empty() {
+        return None;
+    }
+    categories.sort();
+
+    let entries = categories
+        .into_iter()
+        .map(|(category, count)| {
+            let samples: Vec<rhai::Map> = internal
+                .get(&format!("__kelora_error_samples_{}", category))
+                .and_then(|value| value.clone().into_array().ok())
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|sample| sample.try_cast::<rhai::Map>())
+                .collect();
+            let samples = select_error_samples(samples, error_sample_cap());
+            let format = samples
+                .iter()
+                .find_map(|sample| sample_string(sample, "format"))
+                .map_or(serde_json::Value::Null, Into::into);
+
+            let mut entry = serde_json::Map::new();
+            entry.insert("category".to_string(), category.into());
+            entry.insert("count".to_string(), count.into());
+            entry.insert("format".to_string(), format);
+            entry.insert(
+                "samples".to_string(),
+                samples.iter().map(sample_to_json).collect(),
+            );
+            serde_json::Value::Object(entry)
+        })
+        .collect();
+    Some(serde_json::Value::Array(entries))
+}
+
 #[cfg(test)]
 pub fn has_errors_in_tracking(snapshot: &TrackingSnapshot) -> bool {
     has_errors_in_tracking_with_policy(snapshot, true)
@@ -576,9 +779,22 @@ pub fn extract_error_summary_from_tracking(
         }
     }
 
+    // With --on-parse-error-sample every selected sample is shown together
+    // with its failing line; otherwise the first few messages are listed and
+    // the line itself only appears with -v.
+    let sample_limit = error_sample_limit();
+    let (sample_objects, max_shown, show_lines) = match sample_limit {
+        Some(limit) => (
+            select_error_samples(sample_objects, limit),
+            usize::MAX,
+            true,
+        ),
+        None => (sample_objects, DEFAULT_ERROR_SAMPLES, verbose > 0),
+    };
+
     let mut shown_samples = 0;
     for sample_obj in &sample_objects {
-        if shown_samples >= 3 {
+        if shown_samples >= max_shown {
             break;
         }
 
@@ -593,7 +809,7 @@ pub fn extract_error_summary_from_tracking(
 
         summary.push_str(&format!("\n  {}: {}", location, message));
 
-        if verbose > 0 {
+        if show_lines {
             if let Some(orig_line) = original_line {
                 let display_line = if orig_line.len() > 100 {
                     format!("{}...", &orig_line[..97])
@@ -621,7 +837,7 @@ pub fn extract_error_summary_from_tracking(
     if let Some(stats) = stats {
         if stats.yearless_timestamps > 0 {
             let warning_msg = format!(
-                "Year-less timestamps detected ({} parse{}): year guessed via ±1yr heuristic, >18mo old may be wrong",
+                "Year-less timestamps detected ({} parse{}): year inferred from the current date, pin it with --assume-year",
                 stats.yearless_timestamps,
                 if stats.yearless_timestamps == 1 {
                     ""
@@ -861,3 +1077,60 @@ mod stage_outcome_tests {
         });
     }
 }
+
+#[cfg(test)]
+mod sample_tests {
+    use super::*;
+
+    fn sample(error_type: &str, line: i64, text: &str) -> rhai::Map {
+        let mut map = rhai::Map::new();
+        map.insert("error_type".into(), Dynamic::from(error_type.to_string()));
+        map.insert("line_num".into(), Dynamic::from(line));
+        map.insert("template".into(), Dynamic::from(sample_template(text)));
+        map
+    }
+
+    #[test]
+    fn template_masks_values_and_collapses_whitespace() {
+        assert_eq!(
+            sample_template("req 4f3a2b  failed after 120ms"),
+            "req # failed after #"
+        );
+        assert_eq!(
+            sample_template("req 99 failed after 7ms"),
+            sample_template("req 4f3a2b failed after 120ms")
+        );
+        assert_ne!(sample_template("<html>"), sample_template("{broken"));
+    }
+
+    #[test]
+    fn select_dedupes_templates_and_caps_each_category() {
+        let selected = select_error_samples(
+            vec![
+                sample("parse", 9, "garbage 3"),
+                sample("parse", 2, "garbage 1"),
+                sample("parse", 5, "<html>"),
+                sample("parse", 7, "{oops"),
+                sample("exec", 4, "boom"),
+            ],
+            2,
+        );
+        let picked: Vec<(String, i64)> = selected
+            .iter()
+            .map(|s| {
+                (
+                    sample_string(s, "error_type").unwrap(),
+                    s.get("line_num").unwrap().as_int().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            picked,
+            vec![
+                ("exec".to_string(), 4),
+                ("parse".to_string(), 2),
+                ("parse".to_string(), 5),
+            ]
+        );
+    }
+}
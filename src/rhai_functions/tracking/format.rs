@@ -1,4 +1,4 @@
-use super::merge::{deserialize_hll, deserialize_tdigest, is_hll_blob};
+use super::merge::{deserialize_hll, deserialize_tdigest, is_hll_blob, percentile_from_key};
 use super::{metric_operation, metric_top_n};
 use rhai::Dynamic;
 use std::collections::{HashMap, HashSet};
@@ -181,13 +181,11 @@ pub fn format_metrics_output(
             }
 
             if let Some(digest) = deserialize_tdigest(&blob) {
-                if let Some(p_pos) = key.rfind("_p") {
-                    if let Ok(percentile) = key[p_pos + 2..].parse::<f64>() {
-                        let quantile = percentile / 100.0;
-                        let value = digest.estimate_quantile(quantile);
-                        output.push_str(&format!("{:<12} = {}\n", key, format_metric_float(value)));
-                        continue;
-                    }
+                if let Some(percentile) = percentile_from_key(key) {
+                    let quantile = percentile / 100.0;
+                    let value = digest.estimate_quantile(quantile);
+                    output.push_str(&format!("{:<12} = {}\n", key, format_metric_float(value)));
+                    continue;
                 }
             }
         }
@@ -243,13 +241,30 @@ pub fn format_metrics_tsv(
     ops: &HashMap<String, Dynamic>,
 ) -> String {
     let mut output = String::new();
+    for (metric, key, value) in metric_rows(metrics, ops) {
+        push_tsv_row(&mut output, &metric, &key, &value);
+    }
+    output.trim_end().to_string()
+}
+
+/// One `(metric, key, value)` record; `key` is empty for scalar metrics.
+type MetricRow = (String, String, String);
+
+/// Flatten every user metric into records, shared by the tsv and Prometheus
+/// views. Values are full-precision text; see `format_metrics_tsv` for the
+/// row order.
+fn metric_rows(
+    metrics: &HashMap<String, Dynamic>,
+    ops: &HashMap<String, Dynamic>,
+) -> Vec<MetricRow> {
+    let mut rows = Vec::new();
 
     let mut user_values: Vec<_> = metrics
         .iter()
         .filter(|(k, _)| !k.starts_with("__op_") && !k.starts_with("__kelora_"))
         .collect();
     if user_values.is_empty() {
-        return String::new();
+        return rows;
     }
     // Stable metric-block ordering (each block's rows are then count-sorted).
     user_values.sort_by_key(|(k, _)| k.as_str());
@@ -258,7 +273,7 @@ pub fn format_metrics_tsv(
         // avg maps finalize to a scalar, like the text/json views.
         if metric_operation(ops, key).as_deref() == Some("avg") {
             if let Some(avg) = average_value(value) {
-                push_tsv_scalar(&mut output, key, &avg.to_string());
+                push_scalar_row(&mut rows, key, &avg.to_string());
                 continue;
             }
         }
@@ -266,17 +281,15 @@ pub fn format_metrics_tsv(
         if let Ok(blob) = value.clone().into_blob() {
             if is_hll_blob(&blob) {
                 if let Some(hll) = deserialize_hll(&blob) {
-                    push_tsv_scalar(&mut output, key, &hll.len().to_string());
+                    push_scalar_row(&mut rows, key, &hll.len().to_string());
                     continue;
                 }
             }
             if let Some(digest) = deserialize_tdigest(&blob) {
-                if let Some(p_pos) = key.rfind("_p") {
-                    if let Ok(percentile) = key[p_pos + 2..].parse::<f64>() {
-                        let v = digest.estimate_quantile(percentile / 100.0);
-                        push_tsv_scalar(&mut output, key, &v.to_string());
-                        continue;
-                    }
+                if let Some(percentile) = percentile_from_key(key) {
+                    let v = digest.estimate_quantile(percentile / 100.0);
+                    push_scalar_row(&mut rows, key, &v.to_string());
+                    continue;
                 }
             }
         }
@@ -291,7 +304,7 @@ pub fn format_metrics_tsv(
                 for item in rank_array(&arr, is_top, field, n) {
                     if let Some(map) = item.clone().try_cast::<rhai::Map>() {
                         if let (Some(k), Some(v)) = (map.get("key"), map.get(field)) {
-                            push_tsv_row(&mut output, key, &dynamic_to_tsv(k), &dynamic_to_tsv(v));
+                            push_row(&mut rows, key, &dynamic_to_tsv(k), &dynamic_to_tsv(v));
                         }
                     }
                 }
@@ -311,7 +324,7 @@ pub fn format_metrics_tsv(
                         .then_with(|| ak.cmp(bk))
                 });
                 for (cat, v) in entries {
-                    push_tsv_row(&mut output, key, &cat, &dynamic_to_tsv(v));
+                    push_row(&mut rows, key, &cat, &dynamic_to_tsv(v));
                 }
                 continue;
             }
@@ -322,16 +335,89 @@ pub fn format_metrics_tsv(
         if value.is::<rhai::Array>() {
             if let Ok(arr) = value.clone().into_array() {
                 for item in arr {
-                    push_tsv_row(&mut output, key, "", &dynamic_to_tsv(&item));
+                    push_row(&mut rows, key, "", &dynamic_to_tsv(&item));
                 }
                 continue;
             }
         }
 
-        push_tsv_scalar(&mut output, key, &dynamic_to_tsv(value));
+        push_scalar_row(&mut rows, key, &dynamic_to_tsv(value));
     }
 
-    output.trim_end().to_string()
+    rows
+}
+
+/// Format metrics in the Prometheus text exposition format.
+///
+/// Each metric becomes a `kelora_<name>` gauge; keyed rows (frequency tables,
+/// rankings) carry the entry as a `key` label. Rows with non-numeric values
+/// and repeated unkeyed rows (retained samples) are skipped, since Prometheus
+/// rejects duplicate series.
+pub fn format_metrics_prometheus(
+    metrics: &HashMap<String, Dynamic>,
+    ops: &HashMap<String, Dynamic>,
+) -> String {
+    let mut output = String::new();
+    let mut current_metric: Option<String> = None;
+    let mut seen = std::collections::HashSet::new();
+
+    for (metric, key, value) in metric_rows(metrics, ops) {
+        let Ok(number) = value.parse::<f64>() else {
+            continue;
+        };
+        if !seen.insert((metric.clone(), key.clone())) {
+            continue;
+        }
+
+        let name = prometheus_metric_name(&metric);
+        if current_metric.as_deref() != Some(name.as_str()) {
+            output.push_str(&format!("# TYPE {} gauge\n", name));
+            current_metric = Some(name.clone());
+        }
+
+        let value = if number.is_nan() {
+            "NaN".to_string()
+        } else if number.is_infinite() {
+            if number > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+        } else {
+            value
+        };
+        if key.is_empty() {
+            output.push_str(&format!("{} {}\n", name, value));
+        } else {
+            output.push_str(&format!(
+                "{}{{key=\"{}\"}} {}\n",
+                name,
+                prometheus_label_value(&key),
+                value
+            ));
+        }
+    }
+
+    output
+}
+
+/// `kelora_` plus the metric name with every character outside
+/// `[a-zA-Z0-9_]` replaced by `_`
+fn prometheus_metric_name(metric: &str) -> String {
+    let sanitized: String = metric
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("kelora_{}", sanitized)
+}
+
+fn prometheus_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
 }
 
 /// Flatten a tab/newline inside a TSV field to a space so each record stays one
@@ -365,8 +451,12 @@ fn push_tsv_row(output: &mut String, metric: &str, key: &str, value: &str) {
     ));
 }
 
-fn push_tsv_scalar(output: &mut String, metric: &str, value: &str) {
-    push_tsv_row(output, metric, "", value);
+fn push_row(rows: &mut Vec<MetricRow>, metric: &str, key: &str, value: &str) {
+    rows.push((metric.to_string(), key.to_string(), value.to_string()));
+}
+
+fn push_scalar_row(rows: &mut Vec<MetricRow>, metric: &str, value: &str) {
+    push_row(rows, metric, "", value);
 }
 
 /// Render a map-valued metric (e.g. from `track_freq`) as an aligned,
@@ -678,17 +768,15 @@ pub fn format_metrics_json(
             }
 
             if let Some(digest) = deserialize_tdigest(&blob) {
-                if let Some(p_pos) = key.rfind("_p") {
-                    if let Ok(percentile) = key[p_pos + 2..].parse::<f64>() {
-                        let quantile = percentile / 100.0;
-                        let percentile_value = digest.estimate_quantile(quantile);
-                        if let Some(num) = serde_json::Number::from_f64(percentile_value) {
-                            json_obj.insert(key.clone(), serde_json::Value::Number(num));
-                        } else {
-                            json_obj.insert(key.clone(), serde_json::Value::Null);
-                        }
-                        continue;
+                if let Some(percentile) = percentile_from_key(key) {
+                    let quantile = percentile / 100.0;
+                    let percentile_value = digest.estimate_quantile(quantile);
+                    if let Some(num) = serde_json::Number::from_f64(percentile_value) {
+                        json_obj.insert(key.clone(), serde_json::Value::Number(num));
+                    } else {
+                        json_obj.insert(key.clone(), serde_json::Value::Null);
                     }
+                    continue;
                 }
             }
         }
@@ -709,6 +797,13 @@ pub fn format_metrics_json(
         json_obj.insert(key.clone(), dynamic_to_json(value.clone()));
     }
 
+    // A user metric named `errors` keeps its name; the summary is skipped then.
+    if !json_obj.contains_key("errors") {
+        if let Some(errors) = super::errors::error_summary_json(ops) {
+            json_obj.insert("errors".to_string(), errors);
+        }
+    }
+
     serde_json::to_string_pretty(&json_obj)
 }
 
@@ -798,6 +893,27 @@ mod tests {
         assert_eq!(format_metric_float(f64::INFINITY), "inf");
     }
 
+    #[test]
+    fn test_format_metrics_prometheus_gauges_and_key_labels() {
+        let mut metrics = HashMap::new();
+        let mut map = rhai::Map::new();
+        map.insert("200".into(), Dynamic::from(40i64));
+        map.insert("a \"b\"".into(), Dynamic::from(2i64));
+        metrics.insert("status".to_string(), Dynamic::from(map));
+        metrics.insert("bytes-total".to_string(), Dynamic::from(1.5f64));
+        metrics.insert("last_user".to_string(), Dynamic::from("alice".to_string()));
+
+        let output = format_metrics_prometheus(&metrics, &HashMap::new());
+        assert_eq!(
+            output,
+            "# TYPE kelora_bytes_total gauge\n\
+             kelora_bytes_total 1.5\n\
+             # TYPE kelora_status gauge\n\
+             kelora_status{key=\"200\"} 40\n\
+             kelora_status{key=\"a \\\"b\\\"\"} 2\n"
+        );
+    }
+
     fn avg_op(key: &str) -> HashMap<String, Dynamic> {
         let mut ops = HashMap::new();
         ops.insert(format!("__op_{}", key), Dynamic::from("avg".to_string()));
@@ -27,6 +27,7 @@ pub(crate) fn op_display_name(op: &str) -> &str {
         "bucket" => "track_freq",
         "cardinality" => "track_cardinality",
         "percentiles" => "track_percentiles",
+        "percentile_approx" => "track_percentile_approx",
         "top" => "track_top",
         "bottom" => "track_bottom",
         "top_by" => "track_top_by",
@@ -153,6 +154,66 @@ pub(super) fn deserialize_tdigest(bytes: &[u8]) -> Option<TDigest> {
     Some(TDigest::from_centroids(centroids))
 }
 
+/// Default t-digest compression for `track_percentile_approx`: digests keep at
+/// most about this many centroids.
+pub(crate) const DEFAULT_TDIGEST_COMPRESSION: usize = 100;
+
+/// Merge neighbouring centroids until the digest fits `compression`, using the
+/// t-digest k1 scale function `k(q) = δ/2π · asin(2q − 1)`: a merged centroid
+/// may span at most one unit of k. The scale is steep near q = 0 and q = 1, so
+/// tail centroids stay small and high percentiles stay accurate.
+pub(crate) fn compress_tdigest(digest: TDigest, compression: usize) -> TDigest {
+    if digest.centroids().len() <= compression {
+        return digest;
+    }
+
+    let mut centroids: Vec<(f64, f64)> = digest
+        .centroids()
+        .iter()
+        .map(|centroid| (centroid.mean, centroid.weight))
+        .collect();
+    centroids.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let total: f64 = centroids.iter().map(|(_, weight)| weight).sum();
+    let delta = compression.max(1) as f64;
+    let scale =
+        |q: f64| delta / (2.0 * std::f64::consts::PI) * (2.0 * q - 1.0).clamp(-1.0, 1.0).asin();
+
+    let mut merged = Vec::with_capacity(compression);
+    let mut centroids = centroids.into_iter();
+    let Some(mut current) = centroids.next() else {
+        return digest;
+    };
+    let mut weight_before = 0.0;
+    let mut k_left = scale(0.0);
+    for (mean, weight) in centroids {
+        let combined = current.1 + weight;
+        if scale((weight_before + combined) / total) - k_left <= 1.0 {
+            current.0 += (mean - current.0) * weight / combined;
+            current.1 = combined;
+        } else {
+            weight_before += current.1;
+            k_left = scale(weight_before / total);
+            merged.push(tdigests::Centroid::new(current.0, current.1));
+            current = (mean, weight);
+        }
+    }
+    merged.push(tdigests::Centroid::new(current.0, current.1));
+
+    TDigest::from_centroids(merged)
+}
+
+/// Percentile (0–100) named by a percentile metric key's `_pNN` suffix.
+/// `p999` and `p9999` follow the usual shorthand for 99.9 and 99.99.
+pub(crate) fn percentile_from_key(key: &str) -> Option<f64> {
+    let suffix = &key[key.rfind("_p")? + 2..];
+    let percentile = suffix.parse::<f64>().ok()?;
+    if percentile > 100.0 && suffix.starts_with("99") && suffix.bytes().all(|b| b.is_ascii_digit())
+    {
+        return format!("99.{}", &suffix[2..]).parse().ok();
+    }
+    Some(percentile)
+}
+
 /// Helper function to serialize a HyperLogLog to bytes for storage in Dynamic
 /// Uses serde with bincode-style format
 pub(super) fn serialize_hll(hll: &HyperLogLog) -> Vec<u8> {
@@ -241,6 +302,29 @@ mod tests {
         assert_eq!(result.as_int().unwrap(), 3_000_000_000i64);
     }
 
+    #[test]
+    fn test_percentile_from_key() {
+        assert_eq!(percentile_from_key("latency_p50"), Some(50.0));
+        assert_eq!(percentile_from_key("latency_p99.9"), Some(99.9));
+        assert_eq!(percentile_from_key("latency_p999"), Some(99.9));
+        assert_eq!(percentile_from_key("latency_p9999"), Some(99.99));
+        assert_eq!(percentile_from_key("latency"), None);
+        assert_eq!(percentile_from_key("latency_pmax"), None);
+    }
+
+    #[test]
+    fn test_compress_tdigest_bounds_centroids_and_keeps_tails() {
+        let values: Vec<f64> = (0..10_000).map(|i| i as f64 / 10_000.0).collect();
+        let digest = compress_tdigest(TDigest::from_values(values), 100);
+        let centroids = digest.centroids();
+        assert!(centroids.len() <= 100, "{} centroids", centroids.len());
+
+        let total: f64 = centroids.iter().map(|c| c.weight).sum();
+        assert!((total - 10_000.0).abs() < 1e-6);
+        assert!((digest.estimate_quantile(0.5) - 0.5).abs() < 0.02);
+        assert!((digest.estimate_quantile(0.999) - 0.999).abs() < 0.01);
+    }
+
     #[test]
     fn test_hll_serialization_roundtrip() {
         let mut hll = new_hll();
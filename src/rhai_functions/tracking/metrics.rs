@@ -1,6 +1,6 @@
 use super::merge::{
-    deserialize_hll, deserialize_tdigest, ensure_operation_metadata, merge_numeric, new_hll,
-    new_hll_with_error, serialize_hll, serialize_tdigest,
+    compress_tdigest, deserialize_hll, deserialize_tdigest, ensure_operation_metadata,
+    merge_numeric, new_hll, new_hll_with_error, serialize_hll, serialize_tdigest,
 };
 use super::with_user_tracking;
 use rhai::Dynamic;
@@ -238,6 +238,49 @@ pub(super) fn track_percentiles_impl(
     Ok(())
 }
 
+/// Key suffixes `track_percentile_approx` reports
+const APPROX_PERCENTILE_SUFFIXES: [&str; 5] = ["p50", "p90", "p95", "p99", "p999"];
+
+/// Add a value to the metric's t-digest, compressed to `compression`. The
+/// digest is stored under each `{key}_pNN` name so the metrics views, which
+/// read the percentile from the key, need nothing special.
+pub(super) fn track_percentile_approx_impl(
+    key: &str,
+    value: f64,
+    compression: usize,
+) -> Result<(), Box<rhai::EvalAltResult>> {
+    if !value.is_finite() {
+        return Ok(());
+    }
+
+    let metric_keys: Vec<String> = APPROX_PERCENTILE_SUFFIXES
+        .iter()
+        .map(|suffix| format!("{}_{}", key, suffix))
+        .collect();
+    for metric_key in &metric_keys {
+        ensure_operation_metadata(metric_key, "percentile_approx")?;
+    }
+
+    with_user_tracking(|state| {
+        let new_digest = TDigest::from_values(vec![value]);
+        let existing = state
+            .get(&metric_keys[0])
+            .and_then(|existing| existing.clone().into_blob().ok())
+            .and_then(|bytes| deserialize_tdigest(&bytes));
+        let digest = match existing {
+            Some(existing) => compress_tdigest(existing.merge(&new_digest), compression),
+            None => new_digest,
+        };
+
+        let blob = Dynamic::from_blob(serialize_tdigest(&digest));
+        for metric_key in &metric_keys {
+            state.insert(metric_key.clone(), blob.clone());
+        }
+    });
+
+    Ok(())
+}
+
 pub(super) fn track_stats_impl(
     key: &str,
     value: f64,
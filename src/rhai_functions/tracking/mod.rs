@@ -7,6 +7,8 @@ mod merge;
 mod metrics;
 mod rank;
 mod state;
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
+pub(crate) use errors::error_sample_cap;
 #[cfg(test)]
 use errors::format_error_location;
 #[cfg(test)]
@@ -17,17 +19,18 @@ pub use errors::{
     record_filter_stage_success, record_parse_success, reset_stage_success_flags,
     stage_failed_completely, track_error,
 };
-pub use format::{format_metrics_json, format_metrics_output, format_metrics_tsv};
-pub(crate) use merge::op_display_name;
+pub use format::{
+    format_metrics_json, format_metrics_output, format_metrics_prometheus, format_metrics_tsv,
+};
+pub(crate) use merge::{compress_tdigest, op_display_name, DEFAULT_TDIGEST_COMPRESSION};
 use merge::{
     deserialize_hll, deserialize_tdigest, ensure_operation_metadata, is_hll_blob, merge_numeric,
-    record_skipped_unit,
+    percentile_from_key, record_skipped_unit,
 };
 use metrics::{
     track_avg_impl, track_cardinality_impl, track_cardinality_with_error_impl, track_max_impl,
-    track_min_impl, track_percentiles_impl, track_stats_impl,
+    track_min_impl, track_percentile_approx_impl, track_percentiles_impl, track_stats_impl,
 };
-pub use rank::set_tracking_warnings_enabled;
 pub(crate) use rank::unique_size_warning;
 use rank::{
     track_bottom_count_impl, track_bottom_weighted_impl, track_freq_impl, track_top_count_impl,
@@ -238,6 +241,184 @@ fn track_cardinality_dispatch(
     .into())
 }
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[
+    (
+        "track_avg",
+        &[
+            r#"track_avg("avg_latency", e.response_time)"#,
+            r#"track_avg(e.endpoint, e.duration_ms)"#,
+            r#"track_avg("avg_ms", latency)  // Skips () values"#,
+        ],
+    ),
+    (
+        "track_bottom",
+        &[r#"track_bottom("rare_errors", e.error_type, 5)"#],
+    ),
+    (
+        "track_bottom_by",
+        &[r#"track_bottom_by("fastest_endpoints", e.endpoint, e.latency_ms)"#],
+    ),
+    (
+        "track_cardinality",
+        &[
+            r#"track_cardinality("unique_ips", e.client_ip)"#,
+            r#"track_cardinality("unique_sessions", e.session_id)"#,
+            r#"track_cardinality("unique_users", e.user_id, 0.005)  // 0.5% error"#,
+        ],
+    ),
+    (
+        "track_freq",
+        &[
+            r#"track_freq("service", e.service)  // Count events per service"#,
+            r#"track_freq("status", e.status)  // Numeric values just work"#,
+            r#"track_freq("level", e.level)  // {level: {ERROR: 12, INFO: 3041}}"#,
+        ],
+    ),
+    (
+        "track_inc",
+        &[
+            r#"track_inc("events")  // total event count"#,
+            r#"track_inc("errors")  // conditional counter"#,
+        ],
+    ),
+    ("track_max", &[r#"track_max("slowest", e.response_time)"#]),
+    ("track_min", &[r#"track_min("fastest", e.response_time)"#]),
+    (
+        "track_percentile_approx",
+        &[
+            r#"track_percentile_approx("latency", e.duration_ms)"#,
+            r#"track_percentile_approx("latency", e.duration_ms, 300)  // finer digest"#,
+        ],
+    ),
+    (
+        "track_percentiles",
+        &[
+            r#"track_percentiles("api_latency", e.response_time)"#,
+            r#"track_percentiles("latency", e.duration_ms, [0.50, 0.95, 0.99])"#,
+            r#"track_percentiles("latency", e.duration_ms, [0.999, 0.9999])"#,
+        ],
+    ),
+    (
+        "track_stats",
+        &[
+            r#"track_stats("response_time", e.duration_ms)"#,
+            r#"track_stats("latency", e.duration, [0.50, 0.90, 0.99, 0.999])"#,
+            r#"track_stats("api_" + e.endpoint, e.response_time)"#,
+        ],
+    ),
+    (
+        "track_sum",
+        &[
+            r#"track_sum("total_bytes", e.bytes)"#,
+            r#"track_sum(e.endpoint, e.response_time)"#,
+            r#"track_sum("total_score", score)  // Skips () values"#,
+        ],
+    ),
+    (
+        "track_top",
+        &[
+            r#"track_top("common_errors", e.error_type)"#,
+            r#"track_top("active_users", e.user_id, 5)"#,
+        ],
+    ),
+    (
+        "track_top_by",
+        &[
+            r#"track_top_by("slowest_endpoints", e.endpoint, e.latency_ms)"#,
+            r#"track_top_by("heavy_requests", e.request_id, e.bytes, 5)"#,
+            r#"track_top_by("cpu_hogs", e.process, e.cpu_time.or_empty())  // Skips ()"#,
+        ],
+    ),
+    (
+        "track_unique",
+        &[
+            r#"track_unique("users", e.user_id)"#,
+            r#"track_unique("ips", e.client_ip)"#,
+            r#"track_unique("names", e.message.after("User:").or_empty())"#,
+        ],
+    ),
+];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_avg(name, value)"],
+        "Track average of numeric values",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_bottom(name, item [,n])"],
+        "Track bottom N least frequent items (default n=10)",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_bottom_by(name, item, score [,n])"],
+        "Track bottom N distinct items by their lowest score (default n=10)",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_cardinality(name, value [,err])"],
+        "Estimate unique count using HyperLogLog (~1% error, ~12KB; err range: 0.001-0.26)",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_freq(name, value)"],
+        r#"Frequency table — count occurrences per value: {name: {value: n}}; e.g. track_freq("status", e.status)"#,
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_inc(name)"],
+        "Increment a running counter by 1 (sugar for track_sum(name, 1))",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_max(name, value)"],
+        "Track maximum numeric value",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_min(name, value)"],
+        "Track minimum numeric value",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_percentile_approx(name, value [,compression])"],
+        "Compressed t-digest reporting _p50/_p90/_p95/_p99/_p999 (default compression 100)",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_percentiles(name, value [,[p]])"],
+        "Track streaming percentiles using t-digest (default [0.50,0.95,0.99]; auto-suffixes)",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_stats(name, value [,[p]])"],
+        "Track comprehensive stats: min, max, avg, count, sum, percentiles (auto-suffixes)",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_sum(name, value)"],
+        "Accumulate numeric values; track_sum(name, 1) (or track_inc) is a plain counter",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_top(name, item [,n])"],
+        "Track top N most frequent items (default n=10)",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_top_by(name, item, score [,n])"],
+        "Track top N distinct items by their highest score (default n=10)",
+    ),
+    FunctionDoc::new(
+        Section::Tracking,
+        &["track_unique(name, value)"],
+        "Track exact set of distinct values (unbounded memory; warns past 100k values)",
+    ),
+];
+
 pub fn register_functions(engine: &mut Engine) {
     // Track functions using thread-local storage - clean user API.
     // Operation metadata (`__op_*`) is recorded per metric key; it drives the
@@ -454,6 +635,42 @@ pub fn register_functions(engine: &mut Engine) {
         },
     );
 
+    // track_percentile_approx - one compressed t-digest per metric, reported
+    // as _p50, _p90, _p95, _p99 and _p999 without choosing percentiles upfront.
+    engine.register_fn(
+        "track_percentile_approx",
+        |key: &str, value: Dynamic| -> Result<(), Box<rhai::EvalAltResult>> {
+            match numeric_arg("track_percentile_approx", "value", &value)? {
+                Some(num) => {
+                    track_percentile_approx_impl(key, num.as_f64(), DEFAULT_TDIGEST_COMPRESSION)
+                }
+                None => {
+                    record_skipped_unit(key);
+                    Ok(())
+                }
+            }
+        },
+    );
+    engine.register_fn(
+        "track_percentile_approx",
+        |key: &str, value: Dynamic, compression: i64| -> Result<(), Box<rhai::EvalAltResult>> {
+            if !(10..=10_000).contains(&compression) {
+                return Err(format!(
+                    "track_percentile_approx compression must be between 10 and 10000, got {}",
+                    compression
+                )
+                .into());
+            }
+            match numeric_arg("track_percentile_approx", "value", &value)? {
+                Some(num) => track_percentile_approx_impl(key, num.as_f64(), compression as usize),
+                None => {
+                    record_skipped_unit(key);
+                    Ok(())
+                }
+            }
+        },
+    );
+
     // track_stats - comprehensive statistics tracking, auto-suffixing
     // _min, _max, _avg, _count, _sum and _pXX metric names.
     engine.register_fn(
@@ -677,11 +894,9 @@ fn finalize_metric_value(key: &str, value: &Dynamic, operation: Option<&str>) ->
             }
         }
         if let Some(digest) = deserialize_tdigest(&blob) {
-            if let Some(p_pos) = key.rfind("_p") {
-                if let Ok(percentile) = key[p_pos + 2..].parse::<f64>() {
-                    let quantile = percentile / 100.0;
-                    return Dynamic::from(digest.estimate_quantile(quantile));
-                }
+            if let Some(percentile) = percentile_from_key(key) {
+                let quantile = percentile / 100.0;
+                return Dynamic::from(digest.estimate_quantile(quantile));
             }
         }
     }
@@ -1632,6 +1847,36 @@ mod tests {
         clear_tracking_state();
     }
 
+    #[test]
+    fn test_track_percentile_approx_shares_one_digest() {
+        clear_tracking_state();
+
+        let mut engine = rhai::Engine::new();
+        register_functions(&mut engine);
+        engine
+            .eval::<()>(r#"for i in 0..1000 { track_percentile_approx("rt", i / 1000.0, 50) }"#)
+            .unwrap();
+
+        let state = get_thread_tracking_state();
+        let blob = state.get("rt_p50").unwrap().clone().into_blob().unwrap();
+        for suffix in ["p90", "p95", "p99", "p999"] {
+            let key = format!("rt_{}", suffix);
+            assert_eq!(state.get(&key).unwrap().clone().into_blob().unwrap(), blob);
+        }
+        let digest = deserialize_tdigest(&blob).unwrap();
+        assert!(digest.centroids().len() <= 50);
+
+        let p999 = finalize_metric_value("rt_p999", state.get("rt_p999").unwrap(), None);
+        assert!((p999.as_float().unwrap() - 0.999).abs() < 0.01);
+
+        assert!(engine
+            .eval::<()>(r#"track_percentile_approx("rt", 1.0, 1)"#)
+            .is_err());
+        assert!(engine.eval::<()>(r#"track_sum("rt_p50", 1)"#).is_err());
+
+        clear_tracking_state();
+    }
+
     #[test]
     fn test_track_percentiles_single() {
         clear_tracking_state();
@@ -2,7 +2,6 @@ use super::merge::ensure_operation_metadata;
 use super::with_user_tracking;
 use rhai::Dynamic;
 use std::collections::HashSet;
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
 /// Past this many distinct values, warn once that the set lives entirely in
@@ -11,25 +10,19 @@ use std::sync::Mutex;
 /// attributable to the right metric.
 pub(crate) const TRACK_UNIQUE_WARN_THRESHOLD: usize = 100_000;
 
-/// Whether tracking runtime warnings may be printed (mirrors the
-/// `--silent` / `--no-diagnostics` gate; set once at startup from config).
-static RUNTIME_WARNINGS_ENABLED: AtomicBool = AtomicBool::new(true);
-
 /// Keys already warned about, shared across threads so the sequential path,
 /// worker threads, and the parallel merge thread warn at most once per metric.
 static UNIQUE_SIZE_WARNED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
 
-pub fn set_tracking_warnings_enabled(enabled: bool) {
-    RUNTIME_WARNINGS_ENABLED.store(enabled, Ordering::Relaxed);
-}
-
 /// If a track_unique set just grew past the threshold (and we haven't warned
 /// for this metric yet), return the formatted warning. Emission is left to the
 /// caller: worker threads route it through the captured-stderr channel, while
 /// the parallel merge thread (whose captures are never drained) prints
 /// directly.
 pub(crate) fn unique_size_warning(key: &str, len: usize) -> Option<String> {
-    if len < TRACK_UNIQUE_WARN_THRESHOLD || !RUNTIME_WARNINGS_ENABLED.load(Ordering::Relaxed) {
+    if len < TRACK_UNIQUE_WARN_THRESHOLD
+        || !crate::run_scope::with_current(|scope| scope.settings.tracking_warnings)
+    {
         return None;
     }
     let first_time = {
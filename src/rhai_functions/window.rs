@@ -1,5 +1,44 @@
+use crate::rhai_functions::docs::{FunctionDoc, FunctionExamples, Section};
 use rhai::{Array, Engine, EvalAltResult};
 
+/// Examples shown by `--help-function`, keyed by function name
+pub(crate) const EXAMPLES: FunctionExamples = &[(
+    "percentile",
+    &[
+        r#"e.p95 = e.latencies.percentile(95)"#,
+        r#"e.median = e.values.percentile(50)"#,
+    ],
+)];
+
+/// Catalogue entries shown by `--help-functions`
+pub(crate) const DOCS: &[FunctionDoc] = &[
+    FunctionDoc::new(
+        Section::Arrays,
+        &["array.percentile(pct)"],
+        "Calculate percentile of numeric array",
+    ),
+    FunctionDoc::new(
+        Section::WindowContext,
+        &["window.start", "window.end"],
+        "Window boundaries as DateTime",
+    ),
+    FunctionDoc::new(
+        Section::WindowContext,
+        &["window.events"],
+        "Number of events assigned to the window",
+    ),
+    FunctionDoc::new(
+        Section::WindowContext,
+        &["window.late"],
+        "Late events dropped from windows since the previous emit",
+    ),
+    FunctionDoc::new(
+        Section::WindowContext,
+        &["metrics"],
+        "This window's metrics only; every track_* function applies",
+    ),
+];
+
 /// Register window-related functions with the Rhai engine
 pub fn register_functions(engine: &mut Engine) {
     // Register array statistical functions. Accept both float and integer
@@ -0,0 +1,150 @@
+//! Settings and counters that belong to one pipeline run.
+//!
+//! Code without access to the config (tracking functions, stage timers,
+//! readers, engine construction) reads its run's settings and feeds its
+//! counters through the scope installed on the current thread. Each run
+//! installs a fresh scope built from its own configuration, and the threads it
+//! spawns inherit it through [`spawn`], so concurrent runs in one process
+//! (embedding callers) never see each other's settings or counts.
+
+use crate::config::KeloraConfig;
+use crate::pipeline::profile::StageTiming;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, AtomicUsize};
+use std::sync::{Arc, OnceLock};
+use std::thread;
+
+/// Settings a run reads from places that never see its config
+#[derive(Debug, Clone)]
+pub struct RunSettings {
+    /// Whether warnings from inside tracking functions (e.g. the track_unique
+    /// size warning) may be printed. Mirrors the `--silent` /
+    /// `--no-diagnostics` gate of other warnings.
+    pub tracking_warnings: bool,
+    /// Track per-field types for --detect-type-conflicts
+    pub detect_type_conflicts: bool,
+    /// Time the parse, format and write phases (--benchmark)
+    pub benchmark: bool,
+    /// Distinct samples per error category (--on-parse-error-sample)
+    pub error_sample_limit: Option<usize>,
+    /// Drop line text and messages from JSON error samples
+    pub redact_error_samples: bool,
+    /// Lines sampled by --detect-lines
+    pub detect_lines: Option<usize>,
+    /// Whether format detection may read past the first line. Off for stdin,
+    /// where waiting for more lines would hold back a slow live stream.
+    pub multi_line_sampling: bool,
+    /// Share compiled scripts between the engines built for this run
+    pub cache_scripts: bool,
+}
+
+impl Default for RunSettings {
+    fn default() -> Self {
+        Self {
+            tracking_warnings: true,
+            detect_type_conflicts: false,
+            benchmark: false,
+            error_sample_limit: None,
+            redact_error_samples: false,
+            detect_lines: None,
+            multi_line_sampling: false,
+            cache_scripts: false,
+        }
+    }
+}
+
+impl RunSettings {
+    pub fn from_config(config: &KeloraConfig) -> Self {
+        Self {
+            tracking_warnings: config.warnings_allowed(),
+            detect_type_conflicts: config.output.detect_type_conflicts,
+            benchmark: config.processing.benchmark,
+            error_sample_limit: config.processing.error_report.sample_limit,
+            redact_error_samples: config.processing.error_report.redact_samples,
+            detect_lines: config.input.detect_lines,
+            // SSV detection samples past the first line, which only files can afford
+            multi_line_sampling: (!config.input.files.is_empty()
+                || config.input.pipeline_replay.is_some())
+                && config.input.follow_glob.is_none(),
+            // Every parallel worker builds its own engine; share compiled
+            // scripts between them instead of compiling the same text once
+            // per worker.
+            cache_scripts: config.performance.cache_scripts || config.should_use_parallel(),
+        }
+    }
+}
+
+/// Counters fed from reader, worker and sink threads. Merged into the run's
+/// `ProcessingStats` when they are read.
+#[derive(Debug, Default)]
+pub struct RunCounters {
+    /// Named inputs opened for reading
+    pub files_processed: AtomicUsize,
+    /// Input bytes before decompression, counted once per buffer refill
+    pub input_bytes_raw: AtomicU64,
+    /// Input bytes after decompression
+    pub input_bytes_decoded: AtomicU64,
+    /// Stderr lines from --input-cmd children
+    pub input_cmd_stderr_lines: AtomicUsize,
+    /// Nanoseconds spent in each `profile::Phase`
+    pub phase_nanos: [AtomicU64; 3],
+}
+
+/// One run's settings and counters
+#[derive(Debug, Default)]
+pub struct RunScope {
+    pub settings: RunSettings,
+    pub counters: RunCounters,
+    /// Script stage timings for --pipeline-profile and --benchmark, registered
+    /// by the first pipeline built for the run
+    pub(crate) stage_timings: OnceLock<Vec<StageTiming>>,
+}
+
+impl RunScope {
+    pub fn new(settings: RunSettings) -> Self {
+        Self {
+            settings,
+            ..Self::default()
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<RunScope>>> = const { RefCell::new(None) };
+}
+
+/// Scope read by threads that no run has installed one on
+static DEFAULT_SCOPE: OnceLock<Arc<RunScope>> = OnceLock::new();
+
+/// Install `scope` on this thread, returning the previously installed one.
+pub fn install(scope: Option<Arc<RunScope>>) -> Option<Arc<RunScope>> {
+    CURRENT.with(|current| std::mem::replace(&mut *current.borrow_mut(), scope))
+}
+
+/// This thread's run scope, for handing on to another thread
+pub fn current() -> Arc<RunScope> {
+    CURRENT
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(|| Arc::clone(DEFAULT_SCOPE.get_or_init(Default::default)))
+}
+
+/// Run `f` with this thread's run scope
+pub fn with_current<R>(f: impl FnOnce(&RunScope) -> R) -> R {
+    CURRENT.with(|current| match current.borrow().as_deref() {
+        Some(scope) => f(scope),
+        None => f(DEFAULT_SCOPE.get_or_init(Default::default)),
+    })
+}
+
+/// Spawn a thread that runs in this thread's run scope
+pub fn spawn<F, T>(f: F) -> thread::JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let scope = current();
+    thread::spawn(move || {
+        install(Some(scope));
+        f()
+    })
+}
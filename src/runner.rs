@@ -5,31 +5,36 @@
 
 use anyhow::Result;
 use crossbeam_channel::{bounded, select, Receiver, Sender};
+use rhai::Dynamic;
 use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::io::{self, BufRead, BufReader, Write};
+use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
 use crate::config::{self, KeloraConfig};
 use crate::decompression;
 use crate::detection::{self, DetectedFormat};
+use crate::event::Event;
 use crate::parallel::{ParallelConfig, ParallelProcessor};
 use crate::parsers;
 use crate::parsers::type_conversion::TypeMap;
 use crate::pipeline::{
-    self, create_input_reader, create_pipeline_builder_from_config, create_pipeline_from_config,
+    self, create_input_reader, create_pipeline_builder_from_config,
     DEFAULT_MULTILINE_FLUSH_TIMEOUT_MS,
 };
-use crate::platform::{Ctrl, SafeStderr};
+use crate::platform::{CancelToken, Ctrl, SafeStderr};
 use crate::readers;
 use crate::rhai_functions::file_ops::{self, FileOpMode};
 use crate::rhai_functions::tracking::{self, TrackingSnapshot};
+use crate::run_scope::{self, RunScope, RunSettings};
 use crate::stats::{
-    get_thread_stats, set_collect_stats, stats_add_error, stats_add_line_filtered,
-    stats_add_line_output, stats_add_line_read, stats_finish_processing, stats_start_timer,
-    ProcessingStats,
+    get_thread_stats, set_collect_stats, stats_add_error, stats_add_line_dropped,
+    stats_add_line_filtered, stats_add_line_output, stats_add_line_read, stats_finish_processing,
+    stats_start_timer, LineFilterKind, ProcessingStats,
 };
 use crate::{rhai_functions, stats};
 
@@ -41,19 +46,43 @@ pub struct PipelineResult {
     pub tracking_data: TrackingSnapshot,
     pub auto_detected_non_line: bool,
     pub field_discovery: Option<crate::field_discovery::FieldDiscovery>,
+    /// The code a script passed to `exit()`, if that ended the run
+    pub exit_code: Option<i32>,
 }
 
-/// Core pipeline processing function using KeloraConfig
-pub fn run_pipeline_with_kelora_config<W: Write + Send + 'static>(
+/// Parts of a run an embedding caller supplies instead of the CLI's stdin,
+/// output format and stats switches. Only sequential runs honour them.
+#[derive(Default)]
+pub struct RunOverrides {
+    /// Read lines from this instead of stdin or the configured files
+    pub reader: Option<Box<dyn BufRead + Send>>,
+    /// Format events with this instead of the configured output format
+    pub formatter: Option<Box<dyn pipeline::Formatter>>,
+    /// Collect counters even when no flag asks for them
+    pub collect_stats: bool,
+}
+
+/// Core pipeline processing function using KeloraConfig, with the input,
+/// formatter or stats collection replaced as `overrides` asks.
+pub fn run_pipeline_with_overrides<W: Write + Send + 'static>(
     config: &KeloraConfig,
     output: W,
     ctrl_rx: &Receiver<Ctrl>,
+    cancel: &CancelToken,
+    overrides: RunOverrides,
 ) -> Result<PipelineResult> {
+    // Counters and tracked metrics are thread-local; a thread that runs
+    // again (the interactive REPL, an embedding caller) starts from zero
+    stats::stats_reset_thread();
+    tracking::set_thread_tracking_state(&HashMap::new());
+    tracking::set_thread_internal_state(&HashMap::new());
     crate::drain::reset();
+    crate::cluster::reset();
     // Clear per-run gate-success flags on this thread (sequential processing runs
     // here; parallel workers reset their own). Without this, an interactive REPL
     // reusing the thread would skip recording a new run's first success.
     crate::rhai_functions::tracking::reset_stage_success_flags();
+    rhai_functions::process::reset_exit_state();
 
     // Enable field discovery if requested
     if config.output.discover_fields.is_some() {
@@ -66,13 +95,26 @@ pub fn run_pipeline_with_kelora_config<W: Write + Send + 'static>(
     // error scope ("affecting every event") from stats.events_created, so keep collecting in those
     // modes — otherwise the scope signal is silently dropped exactly where a stuck user needs it.
     // Plain --no-diagnostics on event output keeps the fast path (no collection).
-    let collect_stats = config.output.stats.is_some()
+    // --end scripts read the final counters from their `stats` map.
+    let collect_stats = overrides.collect_stats
+        || config.output.stats.is_some()
+        || config.output.stats_to_event
+        || config.output.detect_type_conflicts
         || config.output.discover_fields.is_some()
+        || config.processing.end.is_some()
         || (!config.processing.silent
             && (config.output.metrics.is_some()
                 || config.output.drain.is_some()
+                || config.output.cluster.is_some()
                 || !config.diagnostics_suppressed()));
     set_collect_stats(collect_stats);
+    stats::stats_set_files_skipped_by_name(
+        config.input.files_skipped_by_name.clone(),
+        config.processing.verbose > 0,
+    );
+    crate::rhai_functions::datetime::set_duration_output_unit(config.output.duration_unit);
+
+    apply_run_settings(config);
 
     // Choose strict vs. lossy UTF-8 decoding at the byte->String boundary before
     // any reader thread is spawned, so sequential and parallel paths agree.
@@ -83,6 +125,14 @@ pub fn run_pipeline_with_kelora_config<W: Write + Send + 'static>(
     // --strict, otherwise truncated-and-warned (see SECURITY.md).
     readers::set_line_limit(config.input.max_line_bytes, config.processing.strict);
 
+    // --mmdb databases are opened once, before any engine registers their
+    // lookup functions
+    #[cfg(feature = "mmdb")]
+    crate::rhai_functions::mmdb::load_databases(&config.processing.mmdb)?;
+
+    // --pipeline-record copies every input opened below, in read order
+    crate::recording::start_recording(config.input.pipeline_record.as_deref())?;
+
     // Start statistics collection if enabled
     if collect_stats {
         stats_start_timer();
@@ -100,6 +150,57 @@ pub fn run_pipeline_with_kelora_config<W: Write + Send + 'static>(
         ));
     }
 
+    if use_parallel && config.output.stats_to_event {
+        return Err(anyhow::anyhow!(
+            "--stats-to-event is not supported with --parallel or thread overrides. Rerun without --parallel to emit the stats event."
+        ));
+    }
+
+    if use_parallel && config.output.cluster.is_some() {
+        return Err(anyhow::anyhow!(
+            "--cluster is not supported with --parallel or thread overrides. Rerun without --parallel to cluster values."
+        ));
+    }
+
+    if use_parallel
+        && (config.processing.take_while.is_some() || config.processing.drop_while.is_some())
+    {
+        return Err(anyhow::anyhow!(
+            "--take-while/--drop-while is not supported with --parallel or thread overrides. Rerun without --parallel to cut the stream in order."
+        ));
+    }
+
+    if use_parallel
+        && config.processing.schema.is_some()
+        && matches!(
+            config.processing.schema_on_fail,
+            crate::cli::SchemaOnFail::Halt
+        )
+    {
+        return Err(anyhow::anyhow!(
+            "--schema-on-fail halt is not supported with --parallel or thread overrides. Rerun without --parallel to stop at the first failing event."
+        ));
+    }
+
+    if use_parallel && config.processing.on_stage_error == Some(crate::cli::StageErrorPolicy::Abort)
+    {
+        return Err(anyhow::anyhow!(
+            "--on-stage-error abort is not supported with --parallel or thread overrides. Rerun without --parallel to stop at the first stage error."
+        ));
+    }
+
+    if use_parallel && config.processing.check_monotonic.is_some() {
+        return Err(anyhow::anyhow!(
+            "--check-monotonic is not supported with --parallel or thread overrides. Rerun without --parallel to compare each event with the one before it."
+        ));
+    }
+
+    if use_parallel && config.processing.group_consecutive_by.is_some() {
+        return Err(anyhow::anyhow!(
+            "--group-consecutive-by is not supported with --parallel or thread overrides. Rerun without --parallel to merge runs of consecutive events."
+        ));
+    }
+
     if use_parallel && matches!(config.output.format, config::OutputFormat::Levelmap) {
         return Err(anyhow::anyhow!(
             "levelmap output format is not supported with --parallel or thread overrides"
@@ -118,6 +219,30 @@ pub fn run_pipeline_with_kelora_config<W: Write + Send + 'static>(
         ));
     }
 
+    if use_parallel && matches!(config.output.format, config::OutputFormat::Table) {
+        return Err(anyhow::anyhow!(
+            "table output format is not supported with --parallel or thread overrides"
+        ));
+    }
+
+    if use_parallel && matches!(config.output.format, config::OutputFormat::FluentdForward) {
+        return Err(anyhow::anyhow!(
+            "fluentd-forward output format is not supported with --parallel or thread overrides"
+        ));
+    }
+
+    if use_parallel && matches!(config.output.format, config::OutputFormat::Orc) {
+        return Err(anyhow::anyhow!(
+            "orc output format is not supported with --parallel or thread overrides"
+        ));
+    }
+
+    if use_parallel && matches!(config.output.format, config::OutputFormat::Cbor) {
+        return Err(anyhow::anyhow!(
+            "cbor output format is not supported with --parallel or thread overrides"
+        ));
+    }
+
     if use_parallel && config.output.discover_fields.is_some() {
         return Err(anyhow::anyhow!(
             "--discover is not supported with --parallel or thread overrides. Rerun without --parallel."
@@ -141,6 +266,18 @@ pub fn run_pipeline_with_kelora_config<W: Write + Send + 'static>(
         ));
     }
 
+    if use_parallel && config.input.follow_glob.is_some() {
+        return Err(anyhow::anyhow!(
+            "--follow-glob is not supported with --parallel or thread overrides. Rerun without --parallel."
+        ));
+    }
+
+    if use_parallel && !config.input.input_cmds.is_empty() {
+        return Err(anyhow::anyhow!(
+            "--input-cmd is not supported with --parallel or thread overrides. Rerun without --parallel."
+        ));
+    }
+
     if use_parallel && matches!(config.input.format, config::InputFormat::AutoPerFile) {
         return Err(anyhow::anyhow!(
             "-f auto-per-file is not supported with --parallel or thread overrides. Rerun without --parallel."
@@ -148,11 +285,14 @@ pub fn run_pipeline_with_kelora_config<W: Write + Send + 'static>(
     }
 
     if use_parallel {
-        run_pipeline_parallel(config, output, ctrl_rx)
+        run_pipeline_parallel(config, output, ctrl_rx, cancel)
     } else {
         let mut output = output;
-        let (_final_input_format, auto_detected_non_line) =
-            run_pipeline_sequential(config, &mut output, ctrl_rx.clone())?;
+        let run = run_pipeline_sequential(config, &mut output, ctrl_rx.clone(), cancel, overrides);
+        // Complete the recording even when the run failed: that input is the
+        // one worth replaying
+        crate::recording::finish_recording()?;
+        let (_final_input_format, auto_detected_non_line) = run?;
         let tracking_user = tracking::get_thread_tracking_state();
         let tracking_internal = tracking::get_thread_internal_state();
         let tracking_data = TrackingSnapshot::from_parts(tracking_user, tracking_internal);
@@ -171,15 +311,619 @@ pub fn run_pipeline_with_kelora_config<W: Write + Send + 'static>(
             tracking_data,
             auto_detected_non_line,
             field_discovery,
+            exit_code: cancel.exit_code(),
         })
     }
 }
 
+/// Install the settings that code without access to the config (tracking
+/// functions, stage timers, readers, engine construction) reads for the
+/// current run. Every run entry point calls this first, so a run never
+/// inherits the previous run's values or counters. The run scope is installed
+/// on this thread and handed to every thread the run spawns.
+fn apply_run_settings(config: &KeloraConfig) {
+    run_scope::install(Some(Arc::new(RunScope::new(RunSettings::from_config(
+        config,
+    )))));
+}
+
+/// Drive `--sample-run N`: trace the first N parsed events through every
+/// script stage instead of producing normal output. Runs sequentially with the
+/// regular stage objects; script output is suppressed, file operations queued
+/// by scripts are dropped, and tracked metrics are never reported.
+pub fn run_sample_run<W: Write>(
+    config: &KeloraConfig,
+    mut output: W,
+    samples: usize,
+    cancel: &CancelToken,
+) -> Result<()> {
+    apply_run_settings(config);
+    #[cfg(feature = "mmdb")]
+    crate::rhai_functions::mmdb::load_databases(&config.processing.mmdb)?;
+    let mut sample_config = config.clone();
+    sample_config.processing.suppress_script_output = true;
+
+    let mut reader = create_input_reader(config)?;
+    if matches!(
+        config.input.format,
+        config::InputFormat::Auto | config::InputFormat::AutoPerFile
+    ) {
+        let mut peekable_reader = readers::PeekableLineReader::new(reader);
+        let detected_format = detection::detect_format_from_peekable_reader(&mut peekable_reader)?;
+        detection::emit_detected_format_notice(config, &detected_format);
+        sample_config.input.format = detected_format.format;
+        reader = detection::decode_detected_input(
+            Box::new(peekable_reader),
+            &sample_config.input.format,
+            config.input.read_buffer,
+        );
+    }
+    let (mut pipeline, begin_stage, _end_stage, mut ctx) =
+        create_pipeline_builder_from_config(&sample_config)
+            .with_cancel_token(cancel.clone())
+            .build(sample_config.processing.stages.clone())?;
+    file_ops::set_mode(FileOpMode::Sequential);
+    // --begin usually seeds `conf` for the filters, so it still runs
+    if let Err(e) = begin_stage.execute(&mut ctx) {
+        return Err(anyhow::anyhow!("Begin stage error: {}", e));
+    }
+
+    let mut sampler = StageSampler {
+        formatter: crate::formatters::InspectFormatter::new(config.processing.verbose),
+        remaining: samples,
+        sample_number: 0,
+    };
+    if sampler.remaining > 0 {
+        for_each_input_chunk(
+            &sample_config,
+            &mut reader,
+            &mut pipeline,
+            &mut ctx,
+            |chunk, line_num, pipeline, ctx| {
+                sampler.trace(chunk, line_num, pipeline, ctx, &mut output)?;
+                Ok(sampler.remaining > 0)
+            },
+        )?;
+    }
+
+    output.flush()?;
+    Ok(())
+}
+
+/// Drive `--diff-output`: run the --left and --right file sets through
+/// parsing and the script stages, then write the events only one side
+/// produced, tagged with `_diff_side`. Events are compared on their full
+/// content, or on the --diff-key fields only. Runs sequentially; file
+/// operations queued by scripts are dropped.
+pub fn run_diff<W: Write>(
+    config: &KeloraConfig,
+    mut output: W,
+    diff: &config::DiffMode,
+    cancel: &CancelToken,
+) -> Result<()> {
+    apply_run_settings(config);
+    #[cfg(feature = "mmdb")]
+    crate::rhai_functions::mmdb::load_databases(&config.processing.mmdb)?;
+    let keys = diff.keys.as_deref();
+    let (left, _) = collect_diff_side(config, &diff.left, keys, cancel)?;
+    let (right, pipeline) = collect_diff_side(config, &diff.right, keys, cancel)?;
+
+    let left_hashes: HashSet<u64> = left.iter().map(|(hash, _)| *hash).collect();
+    let right_hashes: HashSet<u64> = right.iter().map(|(hash, _)| *hash).collect();
+
+    let left_events = left.into_iter().filter_map(|(hash, event)| {
+        if !right_hashes.contains(&hash) {
+            Some((event, "left"))
+        } else {
+            diff.show_common.then_some((event, "both"))
+        }
+    });
+    let right_events = right
+        .into_iter()
+        .filter(|(hash, _)| !left_hashes.contains(hash))
+        .map(|(_, event)| (event, "right"));
+
+    for (mut event, side) in left_events.chain(right_events) {
+        event.set_field("_diff_side".to_string(), Dynamic::from(side.to_string()));
+        event.parsed_ts = None;
+        event.extract_timestamp_with_config(None, &pipeline.ts_config);
+        let line = pipeline.formatter.format(&event);
+        if !line.is_empty() {
+            writeln!(output, "{}", line)?;
+        }
+    }
+    if let Some(tail) = pipeline.formatter.finish() {
+        writeln!(output, "{}", tail)?;
+    }
+    pipeline.close_formatter()?;
+
+    output.flush()?;
+    Ok(())
+}
+
+/// Parse one --diff-output file set and run it through the script stages.
+/// Returns each surviving event with its comparison hash, plus the pipeline
+/// so the caller can use its formatter.
+fn collect_diff_side(
+    config: &KeloraConfig,
+    files: &[String],
+    keys: Option<&[String]>,
+    cancel: &CancelToken,
+) -> Result<(Vec<(u64, Event)>, pipeline::Pipeline)> {
+    let mut events = Vec::new();
+    let pipeline = process_diff_side(config, files, cancel, |event| {
+        events.push((diff_hash(&event, keys), event));
+    })?;
+    Ok((events, pipeline))
+}
+
+/// Parse a file set (stdin when empty) and run it through the script
+/// stages, handing every non-empty surviving event to `on_event`. Returns
+/// the pipeline so the caller can use its formatter.
+fn process_diff_side<F: FnMut(Event)>(
+    config: &KeloraConfig,
+    files: &[String],
+    cancel: &CancelToken,
+    mut on_event: F,
+) -> Result<pipeline::Pipeline> {
+    let mut side_config = config.clone();
+    side_config.input.files = files.to_vec();
+    // Both sides run on this thread, each with its own stage counters
+    crate::rhai_functions::tracking::reset_stage_success_flags();
+
+    let mut reader = create_input_reader(&side_config)?;
+    if matches!(
+        side_config.input.format,
+        config::InputFormat::Auto | config::InputFormat::AutoPerFile
+    ) {
+        let mut peekable_reader = readers::PeekableLineReader::new(reader);
+        let detected_format = detection::detect_format_from_peekable_reader(&mut peekable_reader)?;
+        detection::emit_detected_format_notice(config, &detected_format);
+        side_config.input.format = detected_format.format;
+        reader = detection::decode_detected_input(
+            Box::new(peekable_reader),
+            &side_config.input.format,
+            config.input.read_buffer,
+        );
+    }
+    let (mut pipeline, begin_stage, _end_stage, mut ctx) =
+        create_pipeline_builder_from_config(&side_config)
+            .with_cancel_token(cancel.clone())
+            .build(side_config.processing.stages.clone())?;
+    file_ops::set_mode(FileOpMode::Sequential);
+    if let Err(e) = begin_stage.execute(&mut ctx) {
+        return Err(anyhow::anyhow!("Begin stage error: {}", e));
+    }
+
+    for_each_input_chunk(
+        &side_config,
+        &mut reader,
+        &mut pipeline,
+        &mut ctx,
+        |chunk, line_num, pipeline, ctx| {
+            let mut event = match pipeline.parser.parse(&chunk) {
+                Ok(event) => event,
+                Err(e) if ctx.config.strict => {
+                    return Err(anyhow::anyhow!("line {}: {}", line_num, e));
+                }
+                Err(_) => return Ok(true),
+            };
+            event.set_metadata(line_num, None);
+            ctx.meta.line_num = Some(line_num);
+            rhai_functions::process::clear_skip_request();
+
+            let mut batch = vec![event];
+            for stage in pipeline.script_stages.iter_mut() {
+                let mut next = Vec::new();
+                for event in batch {
+                    match stage.apply(event, ctx) {
+                        pipeline::ScriptResult::Emit(event) => next.push(event),
+                        pipeline::ScriptResult::EmitMultiple(mut emitted) => {
+                            next.append(&mut emitted)
+                        }
+                        pipeline::ScriptResult::Skip => {}
+                        pipeline::ScriptResult::Error(msg) if ctx.config.strict => {
+                            return Err(anyhow::anyhow!("line {}: {}", line_num, msg));
+                        }
+                        pipeline::ScriptResult::Error(_) => {}
+                    }
+                }
+                batch = next;
+            }
+            file_ops::clear_pending_ops();
+            ctx.pending_file_ops.clear();
+
+            for event in batch {
+                if !event.fields.is_empty() {
+                    on_event(event);
+                }
+            }
+            Ok(true)
+        },
+    )?;
+    Ok(pipeline)
+}
+
+/// Field the --diff-by expression is stored in while events are counted
+const DIFF_AGAINST_KEY_FIELD: &str = "__kelora_diff_key";
+/// Distinct --diff-against keys per side before memory use is worth a warning
+const DIFF_AGAINST_KEY_WARN_LIMIT: usize = 100_000;
+
+/// Drive `--diff-against`: count the events of the baseline files and of the
+/// regular input per --diff-by key, then write one report event per key that
+/// only one side has (`change: "added"` or `"removed"`) or whose count moved
+/// by more than --diff-threshold percent (`"changed"`). Runs sequentially;
+/// memory grows with the number of distinct keys, not events.
+pub fn run_diff_against<W: Write>(
+    config: &KeloraConfig,
+    mut output: W,
+    diff: &config::DiffAgainst,
+    cancel: &CancelToken,
+) -> Result<()> {
+    apply_run_settings(config);
+    #[cfg(feature = "mmdb")]
+    crate::rhai_functions::mmdb::load_databases(&config.processing.mmdb)?;
+    // --keys and --field-prefix-add shape the report, so the counting runs
+    // keep every field, the key included. They write nothing, so their output
+    // format must not insist on --keys either.
+    let mut keyed_config = config.clone();
+    keyed_config.output.format = config::OutputFormat::Json;
+    keyed_config.output.keys.clear();
+    keyed_config.output.core = false;
+    keyed_config.output.exclude_keys.clear();
+    keyed_config.processing.field_prefix_add = None;
+    keyed_config
+        .processing
+        .stages
+        .push(config::ScriptStageType::Exec(format!(
+            "e.{} = ({});",
+            DIFF_AGAINST_KEY_FIELD, diff.key_expr
+        )));
+
+    let baseline = count_diff_keys(&keyed_config, &diff.baseline, "baseline", cancel)?;
+    let current = count_diff_keys(&keyed_config, &config.input.files, "input", cancel)?;
+
+    let mut report: Vec<(u8, &str, usize, usize)> = Vec::new();
+    for (key, &after) in &current {
+        match baseline.get(key) {
+            None => report.push((0, key.as_str(), 0, after)),
+            Some(&before) if diff_count_changed(before, after, diff.threshold_pct) => {
+                report.push((2, key.as_str(), before, after))
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, &before) in &baseline {
+        if !current.contains_key(key) {
+            report.push((1, key.as_str(), before, 0));
+        }
+    }
+    // Added, removed, then changed; the most frequent keys first in each
+    report.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| b.2.max(b.3).cmp(&a.2.max(a.3)))
+            .then_with(|| a.1.cmp(b.1))
+    });
+
+    let (pipeline, _begin_stage, _end_stage, _ctx) = create_pipeline_builder_from_config(config)
+        .with_cancel_token(cancel.clone())
+        .build(Vec::new())?;
+    for (kind, key, before, after) in report {
+        let mut event = Event::default();
+        event.set_field("key".to_string(), Dynamic::from(key.to_string()));
+        let change = ["added", "removed", "changed"][kind as usize];
+        event.set_field("change".to_string(), Dynamic::from(change.to_string()));
+        event.set_field("baseline".to_string(), Dynamic::from(before as i64));
+        event.set_field("current".to_string(), Dynamic::from(after as i64));
+        if before > 0 && after > 0 {
+            let pct = (after as f64 - before as f64) / before as f64 * 100.0;
+            event.set_field(
+                "change_pct".to_string(),
+                Dynamic::from((pct * 10.0).round() / 10.0),
+            );
+        }
+        let line = pipeline.formatter.format(&event);
+        if !line.is_empty() {
+            writeln!(output, "{}", line)?;
+        }
+    }
+    if let Some(tail) = pipeline.formatter.finish() {
+        writeln!(output, "{}", tail)?;
+    }
+    pipeline.close_formatter()?;
+
+    output.flush()?;
+    Ok(())
+}
+
+/// Whether a --diff-against count moved by more than `threshold_pct` percent
+fn diff_count_changed(before: usize, after: usize, threshold_pct: f64) -> bool {
+    let change = (after as f64 - before as f64).abs() / before as f64 * 100.0;
+    change > threshold_pct
+}
+
+/// Count one --diff-against side per key. Events whose key came out as ()
+/// are left out; other keys are compared as strings.
+fn count_diff_keys(
+    config: &KeloraConfig,
+    files: &[String],
+    side: &str,
+    cancel: &CancelToken,
+) -> Result<HashMap<String, usize>> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut warned = false;
+    process_diff_side(config, files, cancel, |event| {
+        let Some(key) = event.fields.get(DIFF_AGAINST_KEY_FIELD) else {
+            return;
+        };
+        let key = key.to_string();
+        if let Some(count) = counts.get_mut(&key) {
+            *count += 1;
+            return;
+        }
+        counts.insert(key, 1);
+        if !warned && counts.len() > DIFF_AGAINST_KEY_WARN_LIMIT {
+            warned = true;
+            if config.warnings_allowed() {
+                eprintln!(
+                    "{}",
+                    config.format_warning_message(&format!(
+                        "--diff-against: the {} has more than {} distinct --diff-by keys; memory grows with every new key. Normalize variable parts in the expression (e.g. replace_regex(\"\\\\d+\", \"N\"))",
+                        side, DIFF_AGAINST_KEY_WARN_LIMIT
+                    ))
+                );
+            }
+        }
+    })?;
+    Ok(counts)
+}
+
+/// Hash an event for --diff-output: every field in key order, or only the
+/// --diff-key fields in the order given. Values are hashed with their type so
+/// `1` and `"1"` differ.
+fn diff_hash(event: &Event, keys: Option<&[String]>) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut hash_field = |name: &str, value: Option<&Dynamic>| {
+        name.hash(&mut hasher);
+        match value {
+            Some(value) => {
+                value.type_name().hash(&mut hasher);
+                value.to_string().hash(&mut hasher);
+            }
+            None => 0u8.hash(&mut hasher),
+        }
+    };
+    match keys {
+        Some(keys) => {
+            for key in keys {
+                hash_field(key, event.fields.get(key));
+            }
+        }
+        None => {
+            let mut fields: Vec<_> = event.fields.iter().collect();
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+            for (name, value) in fields {
+                hash_field(name, Some(value));
+            }
+        }
+    }
+    hasher.finish()
+}
+
+/// Read `reader` the way the sequential reader does, honouring --skip-lines,
+/// --head-lines, --section, --keep-lines/--ignore-lines and CSV headers, and
+/// hand each complete record to `on_chunk` with its line number. Reading
+/// stops once `on_chunk` returns false.
+fn for_each_input_chunk<F>(
+    config: &KeloraConfig,
+    reader: &mut Box<dyn BufRead + Send>,
+    pipeline: &mut pipeline::Pipeline,
+    ctx: &mut pipeline::PipelineContext,
+    mut on_chunk: F,
+) -> Result<()>
+where
+    F: FnMut(
+        String,
+        usize,
+        &mut pipeline::Pipeline,
+        &mut pipeline::PipelineContext,
+    ) -> Result<bool>,
+{
+    let input_format = config.input.format.clone();
+    let is_csv_like = matches!(
+        input_format,
+        config::InputFormat::Csv(_)
+            | config::InputFormat::Tsv(_)
+            | config::InputFormat::Csvnh
+            | config::InputFormat::Tsvnh
+    );
+    let mut section_selector = config
+        .input
+        .section
+        .as_ref()
+        .map(|section_config| pipeline::SectionSelector::new(section_config.clone()));
+    let mut csv_headers_ready = false;
+    let mut line_num = 0usize;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if readers::read_line_lossy(reader, &mut line)? == 0 {
+            while let Some(chunk) = pipeline.chunker.flush() {
+                if !on_chunk(chunk, line_num, pipeline, ctx)? {
+                    break;
+                }
+            }
+            return Ok(());
+        }
+        let text = line.trim_end_matches(['\n', '\r']).to_string();
+        line_num += 1;
+
+        if config
+            .input
+            .head_lines
+            .is_some_and(|limit| line_num > limit)
+        {
+            return Ok(());
+        }
+        if line_num <= config.input.skip_lines
+            || section_selector
+                .as_mut()
+                .is_some_and(|selector| !selector.should_include_line(&text))
+            || config
+                .input
+                .keep_lines
+                .as_ref()
+                .is_some_and(|keep| !keep.is_match(&text))
+            || config
+                .input
+                .ignore_lines
+                .as_ref()
+                .is_some_and(|ignore| ignore.is_match(&text))
+            || (text.trim().is_empty() && !config.input.keeps_blank_lines(&input_format))
+        {
+            continue;
+        }
+
+        if is_csv_like && !csv_headers_ready {
+            csv_headers_ready = true;
+            let mut header_parser = csv_header_parser(&input_format, config.processing.strict)?;
+            let was_consumed = header_parser.initialize_headers_from_line(&text)?;
+            let type_map = header_parser.get_type_map();
+            replace_pipeline_parser(
+                pipeline,
+                ctx,
+                config,
+                &input_format,
+                Some(header_parser.get_headers()),
+                (!type_map.is_empty()).then_some(type_map),
+            )?;
+            if was_consumed {
+                continue;
+            }
+        }
+
+        let mut ready = pipeline.chunker.feed_line(text);
+        while let Some(chunk) = ready {
+            if !on_chunk(chunk, line_num, pipeline, ctx)? {
+                return Ok(());
+            }
+            ready = pipeline.chunker.take_ready();
+        }
+    }
+}
+
+/// Renders the per-stage trace of `--sample-run`.
+struct StageSampler {
+    formatter: crate::formatters::InspectFormatter,
+    /// Parsed events still to trace
+    remaining: usize,
+    sample_number: usize,
+}
+
+impl StageSampler {
+    fn trace<W: Write>(
+        &mut self,
+        chunk: String,
+        line_num: usize,
+        pipeline: &mut pipeline::Pipeline,
+        ctx: &mut pipeline::PipelineContext,
+        output: &mut W,
+    ) -> Result<()> {
+        writeln!(output, "=== line {} ===", line_num)?;
+        writeln!(output, "raw: {}", chunk)?;
+
+        let mut event = match pipeline.parser.parse(&chunk) {
+            Ok(event) => event,
+            Err(e) => {
+                // Lines that fail to parse are shown but do not use up a sample
+                writeln!(output, "--- parse error: {}", e)?;
+                writeln!(output)?;
+                return Ok(());
+            }
+        };
+        self.remaining -= 1;
+        self.sample_number += 1;
+        event.set_metadata(line_num, None);
+        ctx.meta.line_num = Some(line_num);
+
+        writeln!(output, "--- parsed (sample {})", self.sample_number)?;
+        self.write_events(output, std::slice::from_ref(&event))?;
+
+        if pipeline.window_active {
+            pipeline.window_manager.update(&event);
+            ctx.window = pipeline.window_manager.get_window();
+        }
+        rhai_functions::process::clear_skip_request();
+
+        let mut events = vec![event];
+        for (index, stage) in pipeline.script_stages.iter_mut().enumerate() {
+            let stage_name = format!("stage {} ({})", index + 1, stage.label());
+            let mut next = Vec::new();
+            let mut error = None;
+            for event in events {
+                match stage.apply(event, ctx) {
+                    pipeline::ScriptResult::Emit(event) => next.push(event),
+                    pipeline::ScriptResult::EmitMultiple(mut emitted) => next.append(&mut emitted),
+                    pipeline::ScriptResult::Skip => {}
+                    pipeline::ScriptResult::Error(msg) => {
+                        error = Some(msg);
+                        break;
+                    }
+                }
+            }
+
+            if let Some(msg) = error {
+                writeln!(output, "--- error at {}: {}", stage_name, msg)?;
+                break;
+            }
+            if next.is_empty() {
+                writeln!(output, "--- filtered at {}", stage_name)?;
+                break;
+            }
+            if next.len() == 1 {
+                writeln!(output, "--- after {}", stage_name)?;
+            } else {
+                writeln!(output, "--- after {}: {} events", stage_name, next.len())?;
+            }
+            self.write_events(output, &next)?;
+            events = next;
+        }
+
+        // Nothing a script queued is written during a sample run
+        file_ops::clear_pending_ops();
+        ctx.pending_file_ops.clear();
+        writeln!(output)?;
+        Ok(())
+    }
+
+    fn write_events<W: Write>(&self, output: &mut W, events: &[crate::event::Event]) -> Result<()> {
+        use crate::pipeline::Formatter;
+        for (index, event) in events.iter().enumerate() {
+            if index > 0 {
+                writeln!(output, "  ·")?;
+            }
+            // Drop the inspect formatter's own `---` separator; the stage
+            // header takes its place
+            let rendered = self.formatter.format(event);
+            let body = rendered.strip_prefix("---").unwrap_or(&rendered);
+            let body = body.strip_prefix('\n').unwrap_or(body);
+            if body.is_empty() {
+                writeln!(output, "(no fields)")?;
+            } else {
+                writeln!(output, "{}", body)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Run pipeline in parallel mode using KeloraConfig
 fn run_pipeline_parallel<W: Write + Send + 'static>(
     config: &KeloraConfig,
     output: W,
     ctrl_rx: &Receiver<Ctrl>,
+    cancel: &CancelToken,
 ) -> Result<PipelineResult> {
     // Handle auto-detection for parallel mode
     let (final_config, auto_detected_non_line, detected_reader) =
@@ -189,6 +933,7 @@ fn run_pipeline_parallel<W: Write + Send + 'static>(
                 &config.input.files,
                 config.input.no_input,
                 config.processing.strict,
+                config.input.read_buffer,
             )?;
 
             detection::emit_detected_format_notice(config, &detected_format);
@@ -220,11 +965,13 @@ fn run_pipeline_parallel<W: Write + Send + 'static>(
         buffer_size: Some(10000),
     };
 
-    let processor =
-        ParallelProcessor::new(parallel_config).with_take_limit(config.processing.take_limit);
+    let processor = ParallelProcessor::new(parallel_config)
+        .with_take_limit(config.processing.take_limit)
+        .with_cancel_token(cancel.clone());
 
     // Create pipeline builder and components for begin/end stages
-    let pipeline_builder = create_pipeline_builder_from_config(config);
+    let pipeline_builder =
+        create_pipeline_builder_from_config(config).with_cancel_token(cancel.clone());
     let (_pipeline, begin_stage, end_stage, mut ctx) = pipeline_builder
         .clone()
         .build(config.processing.stages.clone())?;
@@ -236,28 +983,31 @@ fn run_pipeline_parallel<W: Write + Send + 'static>(
         return Err(anyhow::anyhow!("Begin stage error: {}", e));
     }
 
-    // Get reader using pipeline builder
-    let reader: Box<dyn BufRead + Send> = if let Some(reader) = detected_reader {
-        reader
-    } else {
-        create_input_reader(config)?
-    };
+    // exit() in --begin ends the run before any input is read
+    if !ctx.rhai.exit_requested() {
+        // Get reader using pipeline builder
+        let reader: Box<dyn BufRead + Send> = if let Some(reader) = detected_reader {
+            reader
+        } else {
+            create_input_reader(config)?
+        };
 
-    // Process stages in parallel
-    if preserve_order {
-        file_ops::set_mode(FileOpMode::ParallelOrdered);
-    } else {
-        file_ops::set_mode(FileOpMode::ParallelUnordered);
-    }
+        // Process stages in parallel
+        if preserve_order {
+            file_ops::set_mode(FileOpMode::ParallelOrdered);
+        } else {
+            file_ops::set_mode(FileOpMode::ParallelUnordered);
+        }
 
-    processor.process_with_pipeline(
-        reader,
-        pipeline_builder,
-        config.processing.stages.clone(),
-        config,
-        output,
-        ctrl_rx.clone(),
-    )?;
+        processor.process_with_pipeline(
+            reader,
+            pipeline_builder,
+            config.processing.stages.clone(),
+            config,
+            output,
+            ctrl_rx.clone(),
+        )?;
+    }
 
     // Merge the parallel metrics state with our pipeline context
     let parallel_snapshot = processor.get_final_tracked_state();
@@ -319,19 +1069,23 @@ fn run_pipeline_parallel<W: Write + Send + 'static>(
         }
     });
 
-    // Execute end stage sequentially with merged state
+    // Execute end stage sequentially with merged state and stats
+    let final_stats = processor.get_final_stats();
     file_ops::set_mode(FileOpMode::Sequential);
-    if let Err(e) = end_stage.execute(&ctx) {
-        return Err(anyhow::anyhow!("End stage error: {}", e));
+    if !ctx.rhai.exit_requested() {
+        if let Err(e) = end_stage.execute(&ctx, &final_stats) {
+            return Err(anyhow::anyhow!("End stage error: {}", e));
+        }
     }
 
     // Return both stats and tracking data
     // Always collect stats for error reporting, even if --stats not used
     Ok(PipelineResult {
-        stats: Some(processor.get_final_stats()),
+        stats: Some(final_stats),
         tracking_data: parallel_snapshot,
         auto_detected_non_line,
         field_discovery: None, // Not supported in parallel mode
+        exit_code: cancel.exit_code(),
     })
 }
 
@@ -339,6 +1093,9 @@ enum SequentialInput {
     Stdin(Box<dyn BufRead + Send>),
     Files(Vec<String>),
     MergedFiles(MergedFileReader),
+    FollowGlob(readers::GlobFollower),
+    Commands(readers::CommandInputs),
+    Replay(crate::recording::ReplayReader),
 }
 
 struct MergedFileReader {
@@ -346,11 +1103,15 @@ struct MergedFileReader {
     format: config::InputFormat,
     strict: bool,
     cols_sep: Option<String>,
+    ssv_header: Option<Vec<String>>,
     extract_prefix: Option<String>,
     prefix_sep: String,
+    strip_prefix: Option<config::PrefixStrip>,
     ts_field: Option<String>,
     ts_format: Option<String>,
     default_timezone: Option<String>,
+    tz_field: Option<String>,
+    assume_year: Option<i32>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -396,25 +1157,60 @@ fn run_pipeline_sequential<W: Write>(
     config: &KeloraConfig,
     output: &mut W,
     ctrl_rx: Receiver<Ctrl>,
+    cancel: &CancelToken,
+    overrides: RunOverrides,
 ) -> Result<(config::InputFormat, bool)> {
+    let RunOverrides {
+        reader, formatter, ..
+    } = overrides;
+    if let Some(reader) = reader {
+        let input = SequentialInput::Stdin(reader);
+        run_pipeline_sequential_internal(config, output, ctrl_rx, cancel, formatter, input)?;
+        return Ok((config.input.format.clone(), false));
+    }
+    if let Some(pattern) = &config.input.follow_glob {
+        return run_pipeline_sequential_follow_glob(
+            config, output, ctrl_rx, cancel, formatter, pattern,
+        );
+    }
+    if !config.input.input_cmds.is_empty() {
+        return run_pipeline_sequential_input_cmd(config, output, ctrl_rx, cancel, formatter);
+    }
+    if let Some(recording) = &config.input.pipeline_replay {
+        return run_pipeline_sequential_replay(
+            config, output, ctrl_rx, cancel, formatter, recording,
+        );
+    }
     if matches!(config.input.format, config::InputFormat::Auto) {
-        return run_pipeline_sequential_with_auto_detection(config, output, ctrl_rx);
+        return run_pipeline_sequential_with_auto_detection(
+            config, output, ctrl_rx, cancel, formatter,
+        );
     }
     if matches!(config.input.format, config::InputFormat::AutoPerFile)
         && (config.input.no_input || config.input.files.is_empty())
     {
         let mut auto_config = config.clone();
         auto_config.input.format = config::InputFormat::Auto;
-        return run_pipeline_sequential_with_auto_detection(&auto_config, output, ctrl_rx);
+        return run_pipeline_sequential_with_auto_detection(
+            &auto_config,
+            output,
+            ctrl_rx,
+            cancel,
+            formatter,
+        );
     }
 
     let input = if config.input.no_input {
         // Create empty input for --no-input mode
         SequentialInput::Stdin(Box::new(io::BufReader::new(io::Cursor::new(Vec::new()))))
     } else if config.input.files.is_empty() {
+        let options = readers::InputOptions::from_config(config);
         let stdin_reader = readers::ChannelStdinReader::new()?;
-        let processed_stdin = decompression::maybe_decompress(stdin_reader)?;
-        SequentialInput::Stdin(Box::new(io::BufReader::new(processed_stdin)))
+        let processed_stdin =
+            readers::decode_binary_input(decompression::maybe_decompress(stdin_reader)?, &options);
+        SequentialInput::Stdin(crate::recording::tee_stdin(Box::new(
+            io::BufReader::with_capacity(options.buffer_size, processed_stdin),
+        )))
     } else {
         let sorted_files =
             pipeline::builders::sort_files(&config.input.files, &config.input.file_order)?;
@@ -424,27 +1220,168 @@ fn run_pipeline_sequential<W: Write>(
                 format: config.input.format.clone(),
                 strict: config.processing.strict,
                 cols_sep: config.input.cols_sep.clone(),
+                ssv_header: config.input.ssv_header.clone(),
                 extract_prefix: config.input.extract_prefix.clone(),
                 prefix_sep: config.input.prefix_sep.clone(),
+                strip_prefix: config.input.strip_prefix.clone(),
                 ts_field: config.input.ts_field.clone(),
                 ts_format: config.input.ts_format.clone(),
                 default_timezone: config.input.default_timezone.clone(),
+                tz_field: config.input.tz_field.clone(),
+                assume_year: config.input.assume_year,
             })
         } else {
             SequentialInput::Files(sorted_files)
         }
     };
 
-    run_pipeline_sequential_internal(config, output, ctrl_rx, input)?;
+    run_pipeline_sequential_internal(config, output, ctrl_rx, cancel, formatter, input)?;
 
     Ok((config.input.format.clone(), false))
 }
 
+/// Run pipeline in sequential mode following every file matching a glob.
+///
+/// Auto-detection peeks at the oldest file matching at startup; when nothing
+/// matches yet the stream falls back to `line`, as it would for empty input.
+fn run_pipeline_sequential_follow_glob<W: Write>(
+    config: &KeloraConfig,
+    output: &mut W,
+    ctrl_rx: Receiver<Ctrl>,
+    cancel: &CancelToken,
+    formatter: Option<Box<dyn pipeline::Formatter>>,
+    pattern: &str,
+) -> Result<(config::InputFormat, bool)> {
+    let follower = readers::GlobFollower::new(pattern)?;
+    let mut final_config = config.clone();
+    let mut auto_detected_non_line = false;
+
+    if matches!(
+        config.input.format,
+        config::InputFormat::Auto | config::InputFormat::AutoPerFile
+    ) {
+        let first_match = follower.matching_files().into_iter().next();
+        let detected_format = match first_match {
+            Some(path) => {
+                let file = fs::File::open(&path)?;
+                let mut peekable_reader = readers::PeekableLineReader::new(BufReader::new(file));
+                detection::detect_format_from_peekable_reader(&mut peekable_reader)?
+            }
+            None => DetectedFormat {
+                format: config::InputFormat::Line,
+                had_input: false,
+            },
+        };
+        detection::emit_detected_format_notice(config, &detected_format);
+        final_config.input.format = detected_format.format.clone();
+        stats::stats_set_detected_format(final_config.input.format.to_display_string());
+        auto_detected_non_line = detected_format.detected_non_line();
+    }
+
+    run_pipeline_sequential_internal(
+        &final_config,
+        output,
+        ctrl_rx,
+        cancel,
+        formatter,
+        SequentialInput::FollowGlob(follower),
+    )?;
+
+    Ok((final_config.input.format, auto_detected_non_line))
+}
+
+/// Run pipeline in sequential mode over the output of `--input-cmd` commands.
+///
+/// Auto-detection waits for the first non-empty line any command writes.
+fn run_pipeline_sequential_input_cmd<W: Write>(
+    config: &KeloraConfig,
+    output: &mut W,
+    ctrl_rx: Receiver<Ctrl>,
+    cancel: &CancelToken,
+    formatter: Option<Box<dyn pipeline::Formatter>>,
+) -> Result<(config::InputFormat, bool)> {
+    let mut commands = readers::CommandInputs::spawn(
+        &config.input.input_cmds,
+        config.input.follow,
+        config.warnings_allowed(),
+    );
+    let mut final_config = config.clone();
+    let mut auto_detected_non_line = false;
+
+    if matches!(
+        config.input.format,
+        config::InputFormat::Auto | config::InputFormat::AutoPerFile
+    ) {
+        let detected_format = match commands.peek_first_non_empty_line() {
+            Some(line) => DetectedFormat {
+                format: parsers::detect_format(line)?,
+                had_input: true,
+            },
+            None => DetectedFormat {
+                format: config::InputFormat::Line,
+                had_input: false,
+            },
+        };
+        detection::emit_detected_format_notice(config, &detected_format);
+        final_config.input.format = detected_format.format.clone();
+        stats::stats_set_detected_format(final_config.input.format.to_display_string());
+        auto_detected_non_line = detected_format.detected_non_line();
+    }
+
+    run_pipeline_sequential_internal(
+        &final_config,
+        output,
+        ctrl_rx,
+        cancel,
+        formatter,
+        SequentialInput::Commands(commands),
+    )?;
+
+    Ok((final_config.input.format, auto_detected_non_line))
+}
+
+/// Run pipeline in sequential mode over a `--pipeline-replay` recording.
+///
+/// Auto-detection reads the first recorded lines, as it would the first
+/// lines of the original input.
+fn run_pipeline_sequential_replay<W: Write>(
+    config: &KeloraConfig,
+    output: &mut W,
+    ctrl_rx: Receiver<Ctrl>,
+    cancel: &CancelToken,
+    formatter: Option<Box<dyn pipeline::Formatter>>,
+    recording: &std::path::Path,
+) -> Result<(config::InputFormat, bool)> {
+    let mut final_config = config.clone();
+    let mut auto_detected_non_line = false;
+
+    if matches!(
+        config.input.format,
+        config::InputFormat::Auto | config::InputFormat::AutoPerFile
+    ) {
+        let mut peekable_reader = readers::PeekableLineReader::new(BufReader::new(
+            crate::recording::ReplayReader::open(recording)?,
+        ));
+        let detected_format = detection::detect_format_from_peekable_reader(&mut peekable_reader)?;
+        detection::emit_detected_format_notice(config, &detected_format);
+        final_config.input.format = detected_format.format.clone();
+        stats::stats_set_detected_format(final_config.input.format.to_display_string());
+        auto_detected_non_line = detected_format.detected_non_line();
+    }
+
+    let input = SequentialInput::Replay(crate::recording::ReplayReader::open(recording)?);
+    run_pipeline_sequential_internal(&final_config, output, ctrl_rx, cancel, formatter, input)?;
+
+    Ok((final_config.input.format, auto_detected_non_line))
+}
+
 /// Run pipeline in sequential mode with auto-detection support
 fn run_pipeline_sequential_with_auto_detection<W: Write>(
     config: &KeloraConfig,
     output: &mut W,
     ctrl_rx: Receiver<Ctrl>,
+    cancel: &CancelToken,
+    formatter: Option<Box<dyn pipeline::Formatter>>,
 ) -> Result<(config::InputFormat, bool)> {
     if config.input.no_input {
         // For --no-input mode, skip auto-detection and use empty input with Line format
@@ -452,7 +1389,7 @@ fn run_pipeline_sequential_with_auto_detection<W: Write>(
         final_config.input.format = config::InputFormat::Line;
         let input =
             SequentialInput::Stdin(Box::new(io::BufReader::new(io::Cursor::new(Vec::new()))));
-        run_pipeline_sequential_internal(&final_config, output, ctrl_rx, input)?;
+        run_pipeline_sequential_internal(&final_config, output, ctrl_rx, cancel, formatter, input)?;
         return Ok((final_config.input.format, false));
     }
 
@@ -460,7 +1397,9 @@ fn run_pipeline_sequential_with_auto_detection<W: Write>(
         let stdin_reader = readers::ChannelStdinReader::new()?;
         let processed_stdin = decompression::maybe_decompress(stdin_reader)?;
         let mut peekable_reader =
-            readers::PeekableLineReader::new(io::BufReader::new(processed_stdin));
+            readers::PeekableLineReader::new(crate::recording::tee_stdin(Box::new(
+                io::BufReader::with_capacity(config.input.read_buffer, processed_stdin),
+            )));
 
         let detected_format = detection::detect_format_from_peekable_reader(&mut peekable_reader)?;
 
@@ -473,8 +1412,14 @@ fn run_pipeline_sequential_with_auto_detection<W: Write>(
         // it. The setter self-gates on whether stats collection is enabled.
         stats::stats_set_detected_format(final_config.input.format.to_display_string());
 
-        let input = SequentialInput::Stdin(Box::new(peekable_reader));
-        run_pipeline_sequential_internal(&final_config, output, ctrl_rx, input)?;
+        // stdin was opened before detection, so a detected XML export is
+        // decoded here rather than by the reader
+        let input = SequentialInput::Stdin(detection::decode_detected_input(
+            Box::new(peekable_reader),
+            &final_config.input.format,
+            config.input.read_buffer,
+        ));
+        run_pipeline_sequential_internal(&final_config, output, ctrl_rx, cancel, formatter, input)?;
 
         Ok((
             final_config.input.format,
@@ -505,7 +1450,7 @@ fn run_pipeline_sequential_with_auto_detection<W: Write>(
                 }
             }
 
-            match decompression::DecompressionReader::new(file_path) {
+            match decompression::DecompressionReader::new_uncounted(file_path) {
                 Ok(decompressed) => {
                     let mut peekable_reader = readers::PeekableLineReader::new(decompressed);
                     detected_format = Some(detection::detect_format_from_peekable_reader(
@@ -562,6 +1507,8 @@ fn run_pipeline_sequential_with_auto_detection<W: Write>(
         detection::emit_detected_format_notice(config, &detected_format);
 
         let mut final_config = config.clone();
+        // Files are reopened below with readers built from `final_config`, so a
+        // detected XML export is decoded as they are read
         final_config.input.format = detected_format.format.clone();
 
         // Record the detected format so the discover footer / --stats can show
@@ -574,16 +1521,20 @@ fn run_pipeline_sequential_with_auto_detection<W: Write>(
                 format: final_config.input.format.clone(),
                 strict: final_config.processing.strict,
                 cols_sep: final_config.input.cols_sep.clone(),
+                ssv_header: final_config.input.ssv_header.clone(),
                 extract_prefix: final_config.input.extract_prefix.clone(),
                 prefix_sep: final_config.input.prefix_sep.clone(),
+                strip_prefix: final_config.input.strip_prefix.clone(),
                 ts_field: final_config.input.ts_field.clone(),
                 ts_format: final_config.input.ts_format.clone(),
                 default_timezone: final_config.input.default_timezone.clone(),
+                tz_field: final_config.input.tz_field.clone(),
+                assume_year: final_config.input.assume_year,
             })
         } else {
             SequentialInput::Files(sorted_files)
         };
-        run_pipeline_sequential_internal(&final_config, output, ctrl_rx, input)?;
+        run_pipeline_sequential_internal(&final_config, output, ctrl_rx, cancel, formatter, input)?;
 
         Ok((
             final_config.input.format,
@@ -592,13 +1543,93 @@ fn run_pipeline_sequential_with_auto_detection<W: Write>(
     }
 }
 
-fn spawn_stdin_reader(
-    mut reader: Box<dyn BufRead + Send>,
+/// Next line buffer for a sequential reader: one the pipeline handed back
+/// after parsing, or a fresh one.
+fn recycled_line_buffer(recycled: &Receiver<String>) -> String {
+    let mut buffer = recycled.try_recv().unwrap_or_default();
+    buffer.clear();
+    buffer
+}
+
+/// Strip the line terminator in place, keeping the buffer's allocation.
+fn trim_line_terminator(buffer: &mut String) {
+    let len = buffer.trim_end_matches(&['\n', '\r'][..]).len();
+    buffer.truncate(len);
+}
+
+fn spawn_stdin_reader(
+    mut reader: Box<dyn BufRead + Send>,
+    sender: Sender<ReaderMessage>,
+    recycled: Receiver<String>,
+    ctrl_rx: Receiver<Ctrl>,
+    cancel: CancelToken,
+) -> thread::JoinHandle<Result<()>> {
+    run_scope::spawn(move || {
+        loop {
+            match ctrl_rx.try_recv() {
+                Ok(Ctrl::Shutdown { immediate }) => {
+                    let _ = sender.send(ReaderMessage::Eof);
+                    if immediate {
+                        return Ok(());
+                    }
+                    break;
+                }
+                Ok(Ctrl::PrintStats) => {
+                    // Reader thread doesn't have stats to print, ignore
+                }
+                Err(_) => {
+                    // No message, continue
+                }
+            }
+
+            let mut line = recycled_line_buffer(&recycled);
+            match readers::read_line_lossy(&mut reader, &mut line) {
+                Ok(0) => {
+                    let _ = sender.send(ReaderMessage::Eof);
+                    break;
+                }
+                Ok(_) if cancel.is_cancelled() => {
+                    // Cancelled while this line was read: it is not handed on
+                    let _ = sender.send(ReaderMessage::Eof);
+                    break;
+                }
+                Ok(_) => {
+                    trim_line_terminator(&mut line);
+                    if sender
+                        .send(ReaderMessage::Line {
+                            line,
+                            filename: None,
+                        })
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    if sender
+                        .send(ReaderMessage::Error {
+                            error: e,
+                            filename: None,
+                        })
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+fn spawn_file_reader(
+    mut reader: readers::MultiFileReader,
     sender: Sender<ReaderMessage>,
+    recycled: Receiver<String>,
     ctrl_rx: Receiver<Ctrl>,
+    cancel: CancelToken,
 ) -> thread::JoinHandle<Result<()>> {
-    thread::spawn(move || {
-        let mut buffer = String::new();
+    run_scope::spawn(move || {
         loop {
             match ctrl_rx.try_recv() {
                 Ok(Ctrl::Shutdown { immediate }) => {
@@ -616,30 +1647,28 @@ fn spawn_stdin_reader(
                 }
             }
 
-            buffer.clear();
-            match readers::read_line_lossy(&mut reader, &mut buffer) {
+            let mut line = recycled_line_buffer(&recycled);
+            match reader.read_line(&mut line) {
                 Ok(0) => {
                     let _ = sender.send(ReaderMessage::Eof);
                     break;
                 }
+                Ok(_) if cancel.is_cancelled() => {
+                    // Cancelled while this line was read: it is not handed on
+                    let _ = sender.send(ReaderMessage::Eof);
+                    break;
+                }
                 Ok(_) => {
-                    let line = buffer.trim_end_matches(&['\n', '\r'][..]).to_string();
-                    if sender
-                        .send(ReaderMessage::Line {
-                            line,
-                            filename: None,
-                        })
-                        .is_err()
-                    {
+                    let filename = reader.current_filename().map(|s| s.to_string());
+                    trim_line_terminator(&mut line);
+                    if sender.send(ReaderMessage::Line { line, filename }).is_err() {
                         break;
                     }
                 }
                 Err(e) => {
+                    let filename = reader.current_filename().map(|s| s.to_string());
                     if sender
-                        .send(ReaderMessage::Error {
-                            error: e,
-                            filename: None,
-                        })
+                        .send(ReaderMessage::Error { error: e, filename })
                         .is_err()
                     {
                         break;
@@ -651,13 +1680,13 @@ fn spawn_stdin_reader(
     })
 }
 
-fn spawn_file_reader(
-    mut reader: readers::MultiFileReader,
+fn spawn_replay_reader(
+    mut reader: crate::recording::ReplayReader,
     sender: Sender<ReaderMessage>,
+    recycled: Receiver<String>,
     ctrl_rx: Receiver<Ctrl>,
 ) -> thread::JoinHandle<Result<()>> {
-    thread::spawn(move || {
-        let mut buffer = String::new();
+    run_scope::spawn(move || {
         loop {
             match ctrl_rx.try_recv() {
                 Ok(Ctrl::Shutdown { immediate }) => {
@@ -667,23 +1696,18 @@ fn spawn_file_reader(
                     }
                     break;
                 }
-                Ok(Ctrl::PrintStats) => {
-                    // Reader thread doesn't have stats to print, ignore
-                }
-                Err(_) => {
-                    // No message, continue
-                }
+                Ok(Ctrl::PrintStats) | Err(_) => {}
             }
 
-            buffer.clear();
-            match reader.read_line(&mut buffer) {
+            let mut line = recycled_line_buffer(&recycled);
+            match reader.read_record(&mut line) {
                 Ok(0) => {
                     let _ = sender.send(ReaderMessage::Eof);
                     break;
                 }
                 Ok(_) => {
                     let filename = reader.current_filename().map(|s| s.to_string());
-                    let line = buffer.trim_end_matches(&['\n', '\r'][..]).to_string();
+                    trim_line_terminator(&mut line);
                     if sender.send(ReaderMessage::Line { line, filename }).is_err() {
                         break;
                     }
@@ -703,6 +1727,132 @@ fn spawn_file_reader(
     })
 }
 
+/// How long the --follow-glob reader sleeps when no matching file has grown.
+const FOLLOW_GLOB_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+fn spawn_follow_glob_reader(
+    mut follower: readers::GlobFollower,
+    sender: Sender<ReaderMessage>,
+    ctrl_rx: Receiver<Ctrl>,
+    cancel: CancelToken,
+    stop_rx: Receiver<()>,
+) -> thread::JoinHandle<Result<()>> {
+    run_scope::spawn(move || {
+        loop {
+            match ctrl_rx.try_recv() {
+                Ok(Ctrl::Shutdown { .. }) => {
+                    let _ = sender.send(ReaderMessage::Eof);
+                    return Ok(());
+                }
+                Ok(Ctrl::PrintStats) => {
+                    // Reader thread doesn't have stats to print, ignore
+                }
+                Err(_) => {
+                    // No message, continue
+                }
+            }
+            if cancel.is_cancelled() {
+                let _ = sender.send(ReaderMessage::Eof);
+                return Ok(());
+            }
+
+            let result = follower.poll(|line, filename| {
+                sender
+                    .send(ReaderMessage::Line {
+                        line,
+                        filename: Some(filename.to_string()),
+                    })
+                    .is_ok()
+            });
+            match result {
+                Ok(true) => {}
+                // The main loop has gone away (e.g. --take reached)
+                Ok(false) => return Ok(()),
+                Err(e) => {
+                    if sender
+                        .send(ReaderMessage::Error {
+                            error: e,
+                            filename: None,
+                        })
+                        .is_err()
+                    {
+                        return Ok(());
+                    }
+                }
+            }
+
+            // Following never reaches EOF on its own; the main loop drops the
+            // stop sender once it is done, which ends the wait immediately.
+            match stop_rx.recv_timeout(FOLLOW_GLOB_POLL_INTERVAL) {
+                Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
+                _ => return Ok(()),
+            }
+        }
+    })
+}
+
+/// How often the --input-cmd reader checks for shutdown while commands are quiet.
+const INPUT_CMD_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+fn spawn_input_cmd_reader(
+    mut commands: readers::CommandInputs,
+    sender: Sender<ReaderMessage>,
+    ctrl_rx: Receiver<Ctrl>,
+    cancel: CancelToken,
+    stop_rx: Receiver<()>,
+) -> thread::JoinHandle<Result<()>> {
+    run_scope::spawn(move || {
+        loop {
+            match ctrl_rx.try_recv() {
+                Ok(Ctrl::Shutdown { .. }) => {
+                    commands.shutdown();
+                    let _ = sender.send(ReaderMessage::Eof);
+                    return Ok(());
+                }
+                Ok(Ctrl::PrintStats) => {
+                    // Reader thread doesn't have stats to print, ignore
+                }
+                Err(_) => {
+                    // No message, continue
+                }
+            }
+            if cancel.is_cancelled() {
+                commands.shutdown();
+                let _ = sender.send(ReaderMessage::Eof);
+                return Ok(());
+            }
+            // The main loop drops the stop sender once it is done (e.g. --take
+            // reached); commands that are still running are interrupted
+            if !matches!(
+                stop_rx.try_recv(),
+                Err(crossbeam_channel::TryRecvError::Empty)
+            ) {
+                commands.shutdown();
+                return Ok(());
+            }
+
+            match commands.recv_timeout(INPUT_CMD_POLL_INTERVAL) {
+                Ok(readers::CommandLine { line, command }) => {
+                    let message = ReaderMessage::Line {
+                        line,
+                        filename: Some(command),
+                    };
+                    if sender.send(message).is_err() {
+                        commands.shutdown();
+                        return Ok(());
+                    }
+                }
+                Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
+                // Every command has finished for good
+                Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                    let _ = sender.send(ReaderMessage::Eof);
+                    return Ok(());
+                }
+            }
+        }
+    })
+}
+
 fn spawn_file_reader_auto_per_file(
     files: Vec<String>,
     strict: bool,
@@ -710,7 +1860,8 @@ fn spawn_file_reader_auto_per_file(
     sender: Sender<ReaderMessage>,
     ctrl_rx: Receiver<Ctrl>,
 ) -> thread::JoinHandle<Result<()>> {
-    thread::spawn(move || {
+    run_scope::spawn(move || {
+        let options = readers::InputOptions::from_config(&config);
         for file_path in files {
             match ctrl_rx.try_recv() {
                 Ok(Ctrl::Shutdown { immediate }) => {
@@ -723,12 +1874,17 @@ fn spawn_file_reader_auto_per_file(
                 Ok(Ctrl::PrintStats) | Err(_) => {}
             }
 
-            let Some(reader) = readers::open_input_reader(&file_path, 256 * 1024, strict)? else {
+            let Some(reader) = readers::open_input_reader(&file_path, &options, strict)? else {
                 continue;
             };
 
             let mut peekable_reader = readers::PeekableLineReader::new(reader);
             let detected = detection::detect_format_from_peekable_reader(&mut peekable_reader)?;
+            let mut peekable_reader = detection::decode_detected_input(
+                Box::new(peekable_reader),
+                &detected.format,
+                options.buffer_size,
+            );
 
             detection::emit_detected_format_notice(&config, &detected);
 
@@ -794,6 +1950,7 @@ fn build_simple_merge_parser(
     format: &config::InputFormat,
     strict: bool,
     cols_sep: Option<String>,
+    ssv_header: Option<Vec<String>>,
 ) -> Result<Box<dyn pipeline::EventParser>> {
     let parser: Box<dyn pipeline::EventParser> = match format {
         config::InputFormat::Json => {
@@ -804,20 +1961,41 @@ fn build_simple_merge_parser(
         config::InputFormat::Logfmt => Box::new(crate::parsers::LogfmtParser::new()),
         config::InputFormat::Syslog => Box::new(crate::parsers::SyslogParser::new()?),
         config::InputFormat::Cef => Box::new(crate::parsers::CefParser::new().with_strict(strict)),
+        config::InputFormat::Leef => {
+            Box::new(crate::parsers::LeefParser::new().with_strict(strict))
+        }
         config::InputFormat::Combined => Box::new(crate::parsers::CombinedParser::new()?),
+        config::InputFormat::Haproxy => Box::new(crate::parsers::HaproxyParser::new()?),
         config::InputFormat::Cols(spec) => {
             Box::new(crate::parsers::ColsParser::new(spec.clone(), cols_sep).with_strict(strict))
         }
         config::InputFormat::Regex(pattern) => {
             Box::new(crate::parsers::RegexParser::new(pattern)?.with_strict(strict))
         }
+        config::InputFormat::Ssv => {
+            Box::new(crate::parsers::SsvParser::new(ssv_header)?.with_strict(strict))
+        }
+        config::InputFormat::FixedWidth(spec) => {
+            Box::new(crate::parsers::FixedWidthParser::new(spec)?.with_strict(strict))
+        }
         config::InputFormat::Named(fmt) => {
             Box::new(crate::parsers::MultiRegexParser::new(fmt.patterns, strict)?)
         }
+        config::InputFormat::Netflow(_) => Box::new(crate::parsers::NetflowParser::new()),
+        config::InputFormat::Pcap => {
+            Box::new(crate::parsers::PcapParser::new().with_strict(strict))
+        }
+        config::InputFormat::XmlRecords(_) => {
+            Box::new(crate::parsers::XmlRecordsParser::new().with_strict(strict))
+        }
+        config::InputFormat::Sqlite(_) => {
+            Box::new(crate::parsers::JsonlParser::new().with_strict(strict))
+        }
         config::InputFormat::Cascade(formats) => {
             let mut entries: Vec<(String, Box<dyn pipeline::EventParser>)> = Vec::new();
             for fmt in formats {
-                let inner = build_simple_merge_parser(fmt, strict, cols_sep.clone())?;
+                let inner =
+                    build_simple_merge_parser(fmt, strict, cols_sep.clone(), ssv_header.clone())?;
                 entries.push((fmt.cascade_name().to_string(), inner));
             }
             Box::new(crate::parsers::CascadingParser::new(entries))
@@ -848,6 +2026,7 @@ fn build_merge_timestamp_parser(
     format: &config::InputFormat,
     strict: bool,
     cols_sep: Option<String>,
+    ssv_header: Option<Vec<String>>,
 ) -> Result<MergeTimestampParser> {
     let parser = match format {
         config::InputFormat::Csv(_)
@@ -858,12 +2037,23 @@ fn build_merge_timestamp_parser(
                 "--merge-sorted is not yet supported for CSV/TSV formats (header semantics across merged files)"
             ));
         }
-        other => MergeTimestampParser::Generic(build_simple_merge_parser(other, strict, cols_sep)?),
+        other => MergeTimestampParser::Generic(build_simple_merge_parser(
+            other, strict, cols_sep, ssv_header,
+        )?),
     };
     Ok(parser)
 }
 
-fn preprocess_merge_line(line: &str, extract_prefix: Option<&str>, prefix_sep: &str) -> String {
+fn preprocess_merge_line(
+    line: &str,
+    extract_prefix: Option<&str>,
+    prefix_sep: &str,
+    strip_prefix: Option<&config::PrefixStrip>,
+) -> String {
+    if let Some(strip) = strip_prefix {
+        let extractor = pipeline::PrefixExtractor::stripping(strip.clone(), None);
+        return extractor.extract_prefix(line).0;
+    }
     if extract_prefix.is_some() {
         if let Some((_, rest)) = line.split_once(prefix_sep) {
             return rest.to_string();
@@ -938,7 +2128,7 @@ fn spawn_merged_file_reader(
     sender: Sender<ReaderMessage>,
     ctrl_rx: Receiver<Ctrl>,
 ) -> thread::JoinHandle<Result<()>> {
-    thread::spawn(move || {
+    run_scope::spawn(move || {
         let mut readers: Vec<(
             BufReader<decompression::DecompressionReader>,
             MergeTimestampParser,
@@ -952,6 +2142,7 @@ fn spawn_merged_file_reader(
                     &reader.format,
                     reader.strict,
                     reader.cols_sep.clone(),
+                    reader.ssv_header.clone(),
                 )?,
                 0,
             ));
@@ -964,6 +2155,8 @@ fn spawn_merged_file_reader(
             custom_field: reader.ts_field.clone(),
             custom_format: reader.ts_format.clone(),
             default_timezone: reader.default_timezone.clone(),
+            tz_field: reader.tz_field.clone(),
+            assume_year: reader.assume_year,
         };
         let extract_prefix = reader.extract_prefix.as_deref();
 
@@ -984,7 +2177,12 @@ fn spawn_merged_file_reader(
                 }
                 *line_number += 1;
                 let line = line.trim_end_matches(&['\n', '\r'][..]).to_string();
-                let parse_line = preprocess_merge_line(&line, extract_prefix, &reader.prefix_sep);
+                let parse_line = preprocess_merge_line(
+                    &line,
+                    extract_prefix,
+                    &reader.prefix_sep,
+                    reader.strip_prefix.as_ref(),
+                );
                 match parse_merge_timestamp(parser, &parse_line, &ts_config) {
                     Ok(MergeTimestampResult::Timestamp(timestamp)) => {
                         let key = MergeKey {
@@ -1063,8 +2261,12 @@ fn spawn_merged_file_reader(
                 }
                 *line_number += 1;
                 let next_line = next_line.trim_end_matches(&['\n', '\r'][..]).to_string();
-                let parse_line =
-                    preprocess_merge_line(&next_line, extract_prefix, &reader.prefix_sep);
+                let parse_line = preprocess_merge_line(
+                    &next_line,
+                    extract_prefix,
+                    &reader.prefix_sep,
+                    reader.strip_prefix.as_ref(),
+                );
                 match parse_merge_timestamp(parser, &parse_line, &ts_config) {
                     Ok(MergeTimestampResult::Timestamp(timestamp)) => {
                         if let Some(previous) = previous_timestamps[state.file_index] {
@@ -1128,20 +2330,39 @@ fn run_pipeline_sequential_internal<W: Write>(
     config: &KeloraConfig,
     output: &mut W,
     ctrl_rx: Receiver<Ctrl>,
+    cancel: &CancelToken,
+    formatter: Option<Box<dyn pipeline::Formatter>>,
     input: SequentialInput,
 ) -> Result<()> {
-    let (mut pipeline, begin_stage, end_stage, mut ctx) = create_pipeline_from_config(config)?;
+    let (mut pipeline, begin_stage, end_stage, mut ctx) =
+        create_pipeline_builder_from_config(config)
+            .with_cancel_token(cancel.clone())
+            .build(config.processing.stages.clone())?;
+    if let Some(formatter) = formatter {
+        pipeline.formatter = formatter;
+    }
 
     file_ops::set_mode(FileOpMode::Sequential);
 
     if let Err(e) = begin_stage.execute(&mut ctx) {
         return Err(anyhow::anyhow!("Begin stage error: {}", e));
     }
+    // exit() in --begin ends the run before any input is read
+    if ctx.rhai.exit_requested() {
+        return Ok(());
+    }
 
     let (line_tx, line_rx) = bounded::<ReaderMessage>(LINE_CHANNEL_BOUND);
+    let (reader_stop_tx, reader_stop_rx) = bounded::<()>(0);
     let reader_ctrl = ctrl_rx.clone();
+    // Parsed lines flow back to the plain stdin/file readers for reuse, so the
+    // steady state reads into already-allocated buffers.
+    let (recycle_tx, recycle_rx) = bounded::<String>(LINE_CHANNEL_BOUND);
+    pipeline.line_recycler = Some(recycle_tx);
     let reader_handle = match input {
-        SequentialInput::Stdin(reader) => spawn_stdin_reader(reader, line_tx, reader_ctrl),
+        SequentialInput::Stdin(reader) => {
+            spawn_stdin_reader(reader, line_tx, recycle_rx, reader_ctrl, cancel.clone())
+        }
         SequentialInput::Files(files) => {
             if matches!(config.input.format, config::InputFormat::AutoPerFile) {
                 spawn_file_reader_auto_per_file(
@@ -1152,15 +2373,143 @@ fn run_pipeline_sequential_internal<W: Write>(
                     reader_ctrl,
                 )
             } else {
-                let reader = readers::MultiFileReader::new(files, config.processing.strict)?;
-                spawn_file_reader(reader, line_tx, reader_ctrl)
+                let reader = readers::MultiFileReader::new(
+                    files,
+                    readers::InputOptions::from_config(config),
+                    config.processing.strict,
+                )?;
+                spawn_file_reader(reader, line_tx, recycle_rx, reader_ctrl, cancel.clone())
             }
         }
         SequentialInput::MergedFiles(reader) => {
             spawn_merged_file_reader(reader, line_tx, reader_ctrl)
         }
+        SequentialInput::Replay(reader) => {
+            spawn_replay_reader(reader, line_tx, recycle_rx, reader_ctrl)
+        }
+        SequentialInput::FollowGlob(follower) => spawn_follow_glob_reader(
+            follower,
+            line_tx,
+            reader_ctrl,
+            cancel.clone(),
+            reader_stop_rx,
+        ),
+        SequentialInput::Commands(commands) => spawn_input_cmd_reader(
+            commands,
+            line_tx,
+            reader_ctrl,
+            cancel.clone(),
+            reader_stop_rx,
+        ),
+    };
+
+    let gap_marker_use_colors = crate::tty::should_use_colors_with_mode(&config.output.color);
+    crate::rhai_functions::formatting::set_colors_enabled(gap_marker_use_colors);
+    let mut gap_tracker = if config.processing.quiet_events {
+        // Suppress gap markers when output is suppressed (stats-only, high quiet levels)
+        None
+    } else {
+        config
+            .output
+            .mark_gaps
+            .map(|threshold| crate::formatters::GapTracker::new(threshold, gap_marker_use_colors))
+    };
+
+    // Every way out of the event loop - end of input, --take, Ctrl+C, a
+    // strict-mode abort - comes back here so the formatter's tail is written.
+    let immediate_shutdown = match run_sequential_event_loop(
+        &mut pipeline,
+        &mut ctx,
+        config,
+        output,
+        &ctrl_rx,
+        &line_rx,
+        &mut gap_tracker,
+    ) {
+        Ok(immediate_shutdown) => immediate_shutdown,
+        Err(err) => {
+            // A broken pipe leaves nowhere to write the tail; any other error
+            // (e.g. --strict aborting on a bad line) still gets it.
+            if !is_broken_pipe(&err) {
+                let _ = write_formatter_tail(&pipeline, output, &mut gap_tracker);
+            }
+            return Err(err);
+        }
+    };
+
+    drop(line_rx);
+    drop(reader_stop_tx);
+
+    // After exit() the reader is not waited for: it may be blocked on input
+    // that is still open, and stops on its own at the next line it reads.
+    if ctx.rhai.exit_requested() {
+        return Ok(());
+    }
+
+    match reader_handle.join() {
+        Ok(result) => result?,
+        Err(_) => return Err(anyhow::anyhow!("Reader thread panicked")),
+    }
+
+    if immediate_shutdown {
+        return Ok(());
+    }
+
+    let results = pipeline.flush(&mut ctx)?;
+    for formatted in results {
+        write_formatted_output(formatted, output, &mut gap_tracker)?;
+    }
+
+    for formatted in pipeline.finish_reservoir(&mut ctx)? {
+        write_formatted_output(formatted, output, &mut gap_tracker)?;
+    }
+
+    for formatted in pipeline.finish_spans(&mut ctx)? {
+        write_formatted_output(formatted, output, &mut gap_tracker)?;
+    }
+
+    if config.output.stats_to_event {
+        stats_finish_processing();
+        let stats_event = get_thread_stats().to_event();
+        for formatted in pipeline.process_synthetic_event(stats_event, &mut ctx)? {
+            write_formatted_output(formatted, output, &mut gap_tracker)?;
+        }
+    }
+
+    write_formatter_tail(&pipeline, output, &mut gap_tracker)?;
+
+    // exit() in a span or --every script still skips the end stage
+    if ctx.rhai.exit_requested() {
+        return Ok(());
+    }
+
+    let end_stats = if end_stage.is_active() {
+        stats_finish_processing();
+        get_thread_stats()
+    } else {
+        stats::ProcessingStats::default()
     };
+    if let Err(e) = end_stage.execute(&ctx, &end_stats) {
+        return Err(anyhow::anyhow!("End stage error: {}", e));
+    }
+
+    rhai_functions::tracking::merge_thread_tracking_to_context(&mut ctx);
+
+    Ok(())
+}
 
+/// Drive the sequential event loop until input ends, the take limit is hit,
+/// or a shutdown is requested. Returns true for an immediate (second signal)
+/// shutdown, which skips the end-of-run flush.
+fn run_sequential_event_loop<W: Write>(
+    pipeline: &mut pipeline::Pipeline,
+    ctx: &mut pipeline::PipelineContext,
+    config: &KeloraConfig,
+    output: &mut W,
+    ctrl_rx: &Receiver<Ctrl>,
+    line_rx: &Receiver<ReaderMessage>,
+    gap_tracker: &mut Option<crate::formatters::GapTracker>,
+) -> Result<bool> {
     let multiline_timeout = config
         .input
         .multiline
@@ -1182,20 +2531,6 @@ fn run_pipeline_sequential_internal<W: Write>(
     let mut pending_deadline: Option<Instant> = None;
     let mut shutdown_requested = false;
     let mut immediate_shutdown = false;
-    let gap_marker_use_colors = crate::tty::should_use_colors_with_mode(&config.output.color);
-    crate::rhai_functions::formatting::set_colors_enabled(gap_marker_use_colors);
-    let mut gap_tracker = if config.processing.quiet_events {
-        // Suppress gap markers when output is suppressed (stats-only, high quiet levels)
-        None
-    } else {
-        config
-            .output
-            .mark_gaps
-            .map(|threshold| crate::formatters::GapTracker::new(threshold, gap_marker_use_colors))
-    };
-
-    let ctrl_rx = ctrl_rx;
-    let line_rx = line_rx;
 
     loop {
         if immediate_shutdown || shutdown_requested {
@@ -1213,9 +2548,9 @@ fn run_pipeline_sequential_internal<W: Write>(
 
         if let Some(duration) = deadline_duration {
             if duration.is_zero() {
-                let results = pipeline.flush(&mut ctx)?;
+                let results = pipeline.flush(ctx)?;
                 for formatted in results {
-                    write_formatted_output(formatted, output, &mut gap_tracker)?;
+                    write_formatted_output(formatted, output, gap_tracker)?;
                 }
                 pending_deadline = None;
                 continue;
@@ -1255,8 +2590,8 @@ fn run_pipeline_sequential_internal<W: Write>(
                             if handle_reader_message(
                                 message,
                                 ReaderContext {
-                                    pipeline: &mut pipeline,
-                                    ctx: &mut ctx,
+                                    pipeline,
+                                    ctx,
                                     config,
                                     output,
                                     line_num: &mut line_num,
@@ -1266,7 +2601,7 @@ fn run_pipeline_sequential_internal<W: Write>(
                                     current_csv_type_map: &mut current_csv_type_map,
                                     last_filename: &mut last_filename,
                                     current_input_format: &mut current_input_format,
-                                    gap_tracker: &mut gap_tracker,
+                                    gap_tracker,
                                     csv_quote_open: &mut csv_quote_open,
                                 },
                             )? {
@@ -1283,9 +2618,9 @@ fn run_pipeline_sequential_internal<W: Write>(
                     }
                 }
                 recv(timeout) -> _ => {
-                    let results = pipeline.flush(&mut ctx)?;
+                    let results = pipeline.flush(ctx)?;
                     for formatted in results {
-                        write_formatted_output(formatted, output, &mut gap_tracker)?;
+                        write_formatted_output(formatted, output, gap_tracker)?;
                     }
                     pending_deadline = None;
                 }
@@ -1324,8 +2659,8 @@ fn run_pipeline_sequential_internal<W: Write>(
                             if handle_reader_message(
                                 message,
                                 ReaderContext {
-                                    pipeline: &mut pipeline,
-                                    ctx: &mut ctx,
+                                    pipeline,
+                                    ctx,
                                     config,
                                     output,
                                     line_num: &mut line_num,
@@ -1335,7 +2670,7 @@ fn run_pipeline_sequential_internal<W: Write>(
                                     current_csv_type_map: &mut current_csv_type_map,
                                     last_filename: &mut last_filename,
                                     current_input_format: &mut current_input_format,
-                                    gap_tracker: &mut gap_tracker,
+                                    gap_tracker,
                                     csv_quote_open: &mut csv_quote_open,
                                 },
                             )? {
@@ -1354,41 +2689,17 @@ fn run_pipeline_sequential_internal<W: Write>(
             }
         }
 
-        if rhai_functions::process::is_exit_requested() {
-            let exit_code = rhai_functions::process::get_exit_code();
-            std::process::exit(exit_code);
+        if ctx.rhai.exit_requested() {
+            // exit() skips the rest of the input and the end stage, but
+            // buffered formatter output still belongs to the events already
+            // processed.
+            write_formatter_tail(pipeline, output, gap_tracker)?;
+            output.flush()?;
+            return Ok(true);
         }
     }
 
-    drop(line_rx);
-
-    match reader_handle.join() {
-        Ok(result) => result?,
-        Err(_) => return Err(anyhow::anyhow!("Reader thread panicked")),
-    }
-
-    if immediate_shutdown {
-        return Ok(());
-    }
-
-    let results = pipeline.flush(&mut ctx)?;
-    for formatted in results {
-        write_formatted_output(formatted, output, &mut gap_tracker)?;
-    }
-
-    pipeline.finish_spans(&mut ctx)?;
-
-    if let Some(result) = pipeline.finish_formatter() {
-        write_formatted_output(result, output, &mut gap_tracker)?;
-    }
-
-    if let Err(e) = end_stage.execute(&ctx) {
-        return Err(anyhow::anyhow!("End stage error: {}", e));
-    }
-
-    rhai_functions::tracking::merge_thread_tracking_to_context(&mut ctx);
-
-    Ok(())
+    Ok(immediate_shutdown)
 }
 
 struct ReaderContext<'a, W: Write> {
@@ -1496,6 +2807,31 @@ fn handle_reader_message<W: Write>(
     }
 }
 
+/// Fresh CSV/TSV parser whose headers are taken from the next line it sees.
+fn csv_header_parser(format: &config::InputFormat, strict: bool) -> Result<parsers::CsvParser> {
+    Ok(match format {
+        config::InputFormat::Csv(ref field_spec) => {
+            let p = parsers::CsvParser::new_csv();
+            if let Some(ref spec) = field_spec {
+                p.with_field_spec(spec)?.with_strict(strict)
+            } else {
+                p
+            }
+        }
+        config::InputFormat::Tsv(ref field_spec) => {
+            let p = parsers::CsvParser::new_tsv();
+            if let Some(ref spec) = field_spec {
+                p.with_field_spec(spec)?.with_strict(strict)
+            } else {
+                p
+            }
+        }
+        config::InputFormat::Csvnh => parsers::CsvParser::new_csv_no_headers(),
+        config::InputFormat::Tsvnh => parsers::CsvParser::new_tsv_no_headers(),
+        _ => unreachable!(),
+    })
+}
+
 /// Processing result for sequential pipeline
 enum ProcessingResult {
     Continue,
@@ -1548,9 +2884,7 @@ fn process_line_sequential<W: Write>(
     *line_num += 1;
 
     // Count line read for stats
-    if config.output.stats.is_some() {
-        stats_add_line_read();
-    }
+    stats_add_line_read();
 
     // Check if we've hit the head limit (stops I/O early)
     if let Some(head_limit) = config.input.head_lines {
@@ -1587,9 +2921,7 @@ fn process_line_sequential<W: Write>(
         if *skipped_lines < config.input.skip_lines {
             *skipped_lines += 1;
             // Count skipped line for stats
-            if config.output.stats.is_some() {
-                stats_add_line_filtered();
-            }
+            stats_add_line_filtered();
             return Ok(ProcessingResult::Continue);
         }
 
@@ -1597,41 +2929,33 @@ fn process_line_sequential<W: Write>(
         if let Some(selector) = section_selector {
             if !selector.should_include_line(&line) {
                 // Count filtered line for stats
-                if config.output.stats.is_some() {
-                    stats_add_line_filtered();
-                }
+                stats_add_line_filtered();
                 return Ok(ProcessingResult::Continue);
             }
         }
 
         // Apply keep-lines filter if configured (early filtering before parsing)
-        if let Some(ref keep_regex) = config.input.keep_lines {
-            if !keep_regex.is_match(&line) {
-                // Count filtered line for stats
-                if config.output.stats.is_some() {
-                    stats_add_line_filtered();
-                }
+        if let Some(ref keep_set) = config.input.keep_lines {
+            if !keep_set.is_match(&line) {
+                stats_add_line_dropped(LineFilterKind::Keep);
                 return Ok(ProcessingResult::Continue);
             }
         }
 
-        // Apply ignore-lines filter if configured (early filtering before parsing)
-        if let Some(ref ignore_regex) = config.input.ignore_lines {
-            if ignore_regex.is_match(&line) {
-                // Count filtered line for stats
-                if config.output.stats.is_some() {
-                    stats_add_line_filtered();
-                }
+        // Apply ignore-lines filter after keep-lines, so ignore wins when both match
+        if let Some(ref ignore_set) = config.input.ignore_lines {
+            if ignore_set.is_match(&line) {
+                stats_add_line_dropped(LineFilterKind::Ignore);
                 return Ok(ProcessingResult::Continue);
             }
         }
 
         if line.trim().is_empty() {
             // Only skip empty lines for structured formats, not for line format
-            if !matches!(effective_input_format, config::InputFormat::Line) {
+            // or when they may separate records
+            if !config.input.keeps_blank_lines(&effective_input_format) {
                 return Ok(ProcessingResult::Continue);
             }
-            // For line format, continue processing the empty line
         }
 
         // For CSV formats, detect file changes and reinitialize parser, or handle first line for stdin
@@ -1640,29 +2964,8 @@ fn process_line_sequential<W: Write>(
                 || (current_filename.is_none() && current_csv_headers.is_none()))
         {
             // File changed, reinitialize CSV parser for this file
-            let mut temp_parser = match &effective_input_format {
-                config::InputFormat::Csv(ref field_spec) => {
-                    let p = parsers::CsvParser::new_csv();
-                    if let Some(ref spec) = field_spec {
-                        p.with_field_spec(spec)?
-                            .with_strict(config.processing.strict)
-                    } else {
-                        p
-                    }
-                }
-                config::InputFormat::Tsv(ref field_spec) => {
-                    let p = parsers::CsvParser::new_tsv();
-                    if let Some(ref spec) = field_spec {
-                        p.with_field_spec(spec)?
-                            .with_strict(config.processing.strict)
-                    } else {
-                        p
-                    }
-                }
-                config::InputFormat::Csvnh => parsers::CsvParser::new_csv_no_headers(),
-                config::InputFormat::Tsvnh => parsers::CsvParser::new_tsv_no_headers(),
-                _ => unreachable!(),
-            };
+            let mut temp_parser =
+                csv_header_parser(&effective_input_format, config.processing.strict)?;
 
             // Initialize headers from the first line
             let was_consumed = temp_parser.initialize_headers_from_line(&line)?;
@@ -1709,7 +3012,7 @@ fn process_line_sequential<W: Write>(
     match pipeline.process_line(line, ctx) {
         Ok(results) => {
             // Count output lines for stats
-            if config.output.stats.is_some() && !results.is_empty() {
+            if !results.is_empty() {
                 stats_add_line_output();
             }
             // Note: Empty results are now counted as either:
@@ -1729,9 +3032,7 @@ fn process_line_sequential<W: Write>(
         }
         Err(e) => {
             // Count errors for stats
-            if config.output.stats.is_some() {
-                stats_add_error();
-            }
+            stats_add_error();
 
             // Handle error based on new resiliency model
             if config.processing.strict {
@@ -1744,6 +3045,28 @@ fn process_line_sequential<W: Write>(
     Ok(ProcessingResult::Continue)
 }
 
+/// Write the formatter's buffered tail (table batch, map legend, CSV header
+/// for an empty run) and finalize file-writing formatters (ORC footer).
+/// Called exactly once per sequential run.
+fn write_formatter_tail<W: Write>(
+    pipeline: &pipeline::Pipeline,
+    output: &mut W,
+    gap_tracker: &mut Option<crate::formatters::GapTracker>,
+) -> io::Result<()> {
+    if let Some(result) = pipeline.finish_formatter() {
+        write_formatted_output(result, output, gap_tracker)?;
+    }
+    pipeline.close_formatter()
+}
+
+fn is_broken_pipe(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<io::Error>()
+            .is_some_and(|io_err| io_err.kind() == io::ErrorKind::BrokenPipe)
+    })
+}
+
 fn write_formatted_output<W: Write>(
     formatted: pipeline::FormattedOutput,
     output: &mut W,
@@ -1758,6 +3081,7 @@ fn write_formatted_output<W: Write>(
         None => None,
     };
 
+    let write_started = pipeline::phase_timer();
     if let Some(marker_line) = marker {
         writeln!(output, "{}", marker_line)?;
     }
@@ -1765,6 +3089,7 @@ fn write_formatted_output<W: Write>(
     if !formatted.line.is_empty() {
         writeln!(output, "{}", formatted.line)?;
     }
+    pipeline::record_phase_time(pipeline::Phase::Write, write_started);
 
     Ok(())
 }
@@ -1,12 +1,41 @@
-use crate::rhai_functions::datetime::DurationWrapper;
+use crate::rhai_functions::datetime::{DateTimeWrapper, DurationWrapper};
+use crate::run_scope;
 use chrono::{DateTime, Utc};
 use indexmap::IndexMap;
 use std::cell::RefCell;
 use std::collections::{BTreeSet, HashMap};
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
+/// Compressed vs. decompressed bytes read from one input file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompressedFileBytes {
+    pub path: String,
+    pub compressed: u64,
+    pub decompressed: u64,
+}
+
+/// Live byte counters for one compressed input, fed by its counting readers
+#[derive(Debug, Default)]
+pub struct FileByteCounter {
+    compressed: AtomicU64,
+    decompressed: AtomicU64,
+}
+
+impl FileByteCounter {
+    pub fn add(&self, compressed: usize, decompressed: usize) {
+        if compressed > 0 {
+            self.compressed
+                .fetch_add(compressed as u64, Ordering::Relaxed);
+        }
+        if decompressed > 0 {
+            self.decompressed
+                .fetch_add(decompressed as u64, Ordering::Relaxed);
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct TimestampFieldStat {
     pub detected: usize,
@@ -19,14 +48,25 @@ pub struct ProcessingStats {
     pub lines_read: usize,
     pub lines_output: usize,
     pub lines_filtered: usize,
+    /// Lines dropped by --keep-lines / --ignore-lines (also counted in lines_filtered)
+    pub lines_dropped_keep: usize,
+    pub lines_dropped_ignore: usize,
     pub lines_errors: usize, // Parse errors (regardless of error handling strategy)
     pub events_created: usize,
     pub events_output: usize,
     pub events_filtered: usize,
     pub late_events: usize,
+    pub events_level_filtered: usize, // Dropped by --level (also counted in events_filtered)
+    pub events_thinned: usize,        // Dropped by --thin (also counted in events_filtered)
+    pub ts_regressions: usize, // Timestamps earlier than the previous event's (--check-monotonic)
+    pub events_with_dup_keys: usize, // Events whose json/logfmt input repeated a key
     pub files_processed: usize,
     pub files_failed_to_open: usize, // Files that failed to open (I/O errors)
     pub failed_file_samples: Vec<String>,
+    /// Inputs `--filename-ts-format` left unread as outside --since/--until
+    pub files_skipped_by_name: Vec<String>,
+    /// List the skipped inputs by name in text stats (verbose runs)
+    pub list_skipped_files: bool,
     pub recoverable_error_samples: Vec<String>,
     pub script_executions: usize,
     pub errors: usize, // Kept for backward compatibility, but lines_errors is more specific
@@ -58,8 +98,20 @@ pub struct ProcessingStats {
     /// Per-format event counts when running in cascade mode. Empty otherwise.
     /// Keyed by the short format name used in `_format` (e.g. "json", "line").
     pub cascade_format_counts: IndexMap<String, usize>,
+    /// Events parsed by the input format when --fallback-format is set
+    pub events_parsed_primary: usize,
+    /// Events parsed by --fallback-format after the input format failed
+    pub events_parsed_fallback: usize,
     pub assertion_failures: usize, // Total assertion failures
     pub assertion_failures_by_expr: HashMap<String, usize>, // Per-assertion tracking
+    pub schema_failures: usize,    // Events rejected by --schema-validate
+    /// --filter/--exec errors per stage, keyed like "stage 2 (exec)"
+    pub stage_errors: IndexMap<String, usize>,
+    /// Stage errors that stopped the run under --on-stage-error abort
+    pub stage_aborts: usize,
+    /// Value types seen per output field, with counts, under
+    /// --detect-type-conflicts. Nulls are not recorded.
+    pub field_types: IndexMap<String, IndexMap<String, usize>>,
     pub csv_rows_extra_columns: usize, // CSV/TSV rows wider than the header (extras kept as cN)
     pub csv_rows_missing_columns: usize, // CSV/TSV rows narrower than the header (fields absent)
     pub csv_overflow_start_column: Option<usize>, // Lowest 1-based column where overflow began
@@ -79,10 +131,30 @@ pub struct ProcessingStats {
     pub truncated_lines: usize,
     /// The byte cap in effect when a truncation occurred, for the diagnostic.
     pub line_byte_cap: usize,
+    /// Lines `--input-cmd` commands wrote to stderr. Each is shown as a
+    /// warning when it arrives; the count lands in the stats block.
+    pub input_cmd_stderr_lines: usize,
+    /// Input bytes as stored (on disk / on stdin), before decompression.
+    pub input_bytes_raw: u64,
+    /// Input bytes after decompression, i.e. what the parsers consumed.
+    pub input_bytes: u64,
+    /// Per-file byte counts for compressed inputs, in open order.
+    pub compressed_files: Vec<CompressedFileBytes>,
+    /// Compressed inputs that ended in bytes which are not another member,
+    /// as `(source, byte offset of the junk)`. Ignored with a warning.
+    pub trailing_garbage: Vec<(String, u64)>,
+    /// Process CPU time and peak RSS, sampled once at the end of the run.
+    /// `None` on platforms without `getrusage`.
+    pub resource_usage: Option<crate::platform::ResourceUsage>,
+    /// Busy share (0-100%) of each parallel worker, indexed by worker id.
+    /// Empty in sequential mode.
+    pub worker_utilization: Vec<f64>,
 }
 
 // Allow disabling stats collection when diagnostics/stats are suppressed
 static COLLECT_STATS: AtomicBool = AtomicBool::new(true);
+// Cap on distinct fields tracked for type conflicts (memory safety)
+const MAX_TYPE_TRACKED_FIELDS: usize = 1_000;
 
 // File open failures use atomic counter since they can happen on any thread (e.g., decompression threads)
 static FILES_FAILED_TO_OPEN: AtomicUsize = AtomicUsize::new(0);
@@ -105,11 +177,26 @@ static FIRST_DECODE_WARNING_SAMPLE: OnceLock<Mutex<Option<String>>> = OnceLock::
 // truncation happens on reader threads, like decode warnings and file failures.
 static TRUNCATED_LINES: AtomicUsize = AtomicUsize::new(0);
 static LINE_BYTE_CAP: AtomicUsize = AtomicUsize::new(0);
+/// A compressed input's path and its byte counter
+type CompressedFile = (String, Arc<FileByteCounter>);
+// Per-file counters for compressed inputs and trailing-garbage recoveries.
+// Registered/recorded by reader threads, like the input byte totals above.
+static COMPRESSED_FILES: OnceLock<Mutex<Vec<CompressedFile>>> = OnceLock::new();
+static TRAILING_GARBAGE: OnceLock<Mutex<Vec<(String, u64)>>> = OnceLock::new();
+// Per-worker utilization (worker id, busy %), recorded once when each parallel
+// worker exits.
+static WORKER_UTILIZATION: OnceLock<Mutex<Vec<(usize, f64)>>> = OnceLock::new();
 
 pub fn set_collect_stats(enabled: bool) {
     COLLECT_STATS.store(enabled, Ordering::Relaxed);
 }
 
+/// Per-field type tracking for --detect-type-conflicts. Off by default since it
+/// touches every field of every output event.
+pub fn type_conflicts_enabled() -> bool {
+    run_scope::with_current(|scope| scope.settings.detect_type_conflicts) && stats_enabled()
+}
+
 pub fn stats_enabled() -> bool {
     COLLECT_STATS.load(Ordering::Relaxed)
 }
@@ -123,6 +210,16 @@ fn push_failed_file_sample(path: &str) {
     }
 }
 
+/// Record the inputs the current run skipped by `--filename-ts-format`;
+/// `list` names them in the text stats.
+pub fn stats_set_files_skipped_by_name(files: Vec<String>, list: bool) {
+    THREAD_STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        stats.files_skipped_by_name = files;
+        stats.list_skipped_files = list;
+    });
+}
+
 fn failed_file_samples() -> Vec<String> {
     FAILED_FILE_SAMPLES
         .get()
@@ -226,18 +323,156 @@ pub fn truncated_line_count() -> usize {
     TRUNCATED_LINES.load(Ordering::Relaxed)
 }
 
+/// Record a line an `--input-cmd` command wrote to stderr. Counted on the
+/// thread forwarding that command's stderr; never affects the exit code.
+pub fn stats_record_input_cmd_stderr() {
+    if !stats_enabled() {
+        return;
+    }
+    run_scope::with_current(|scope| {
+        scope
+            .counters
+            .input_cmd_stderr_lines
+            .fetch_add(1, Ordering::Relaxed)
+    });
+}
+
+/// Stderr lines from `--input-cmd` children in the current run (for the
+/// parallel path).
+pub fn input_cmd_stderr_line_count() -> usize {
+    run_scope::with_current(|scope| {
+        scope
+            .counters
+            .input_cmd_stderr_lines
+            .load(Ordering::Relaxed)
+    })
+}
+
 /// The byte cap that was in effect when truncation occurred (process-wide).
 pub fn truncation_byte_cap() -> usize {
     LINE_BYTE_CAP.load(Ordering::Relaxed)
 }
 
+/// Add bytes read from an input source, before (`raw`) and after (`decoded`)
+/// decompression. Fed by the counting readers once per buffer refill (not per
+/// line), on whichever thread reads the input.
+pub fn stats_add_input_bytes(raw: usize, decoded: usize) {
+    if !stats_enabled() {
+        return;
+    }
+    run_scope::with_current(|scope| {
+        if raw > 0 {
+            scope
+                .counters
+                .input_bytes_raw
+                .fetch_add(raw as u64, Ordering::Relaxed);
+        }
+        if decoded > 0 {
+            scope
+                .counters
+                .input_bytes_decoded
+                .fetch_add(decoded as u64, Ordering::Relaxed);
+        }
+    });
+}
+
+/// Register a compressed input file and return the counter its readers feed.
+/// `None` when stats collection is off.
+pub fn stats_register_compressed_file(path: &str) -> Option<Arc<FileByteCounter>> {
+    if !stats_enabled() {
+        return None;
+    }
+    let counter = Arc::new(FileByteCounter::default());
+    let slot = COMPRESSED_FILES.get_or_init(|| Mutex::new(Vec::new()));
+    if let Ok(mut files) = slot.lock() {
+        files.push((path.to_string(), Arc::clone(&counter)));
+    }
+    Some(counter)
+}
+
+/// Snapshot of per-file compressed/decompressed byte counts (process-wide).
+pub fn compressed_file_bytes() -> Vec<CompressedFileBytes> {
+    COMPRESSED_FILES
+        .get()
+        .and_then(|slot| slot.lock().ok())
+        .map(|files| {
+            files
+                .iter()
+                .map(|(path, counter)| CompressedFileBytes {
+                    path: path.clone(),
+                    compressed: counter.compressed.load(Ordering::Relaxed),
+                    decompressed: counter.decompressed.load(Ordering::Relaxed),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Record that a compressed input ended in junk bytes at `offset` that were
+/// skipped instead of failing the file. A recovery like a decode warning:
+/// it never affects the exit code.
+pub fn stats_record_trailing_garbage(source: &str, offset: u64) {
+    if !stats_enabled() {
+        return;
+    }
+    let slot = TRAILING_GARBAGE.get_or_init(|| Mutex::new(Vec::new()));
+    if let Ok(mut list) = slot.lock() {
+        list.push((source.to_string(), offset));
+    }
+}
+
+/// Trailing-garbage recoveries recorded so far (process-wide).
+pub fn trailing_garbage_snapshot() -> Vec<(String, u64)> {
+    TRAILING_GARBAGE
+        .get()
+        .and_then(|slot| slot.lock().ok().map(|list| list.clone()))
+        .unwrap_or_default()
+}
+
+/// Input byte counts of the current run as `(raw, decoded)`.
+pub fn input_byte_counts() -> (u64, u64) {
+    run_scope::with_current(|scope| {
+        (
+            scope.counters.input_bytes_raw.load(Ordering::Relaxed),
+            scope.counters.input_bytes_decoded.load(Ordering::Relaxed),
+        )
+    })
+}
+
+/// Record how much of its lifetime a parallel worker spent processing batches.
+pub fn stats_record_worker_utilization(worker_id: usize, busy: Duration, lifetime: Duration) {
+    if !stats_enabled() {
+        return;
+    }
+    let lifetime_secs = lifetime.as_secs_f64();
+    let pct = if lifetime_secs > 0.0 {
+        (busy.as_secs_f64() / lifetime_secs * 100.0).clamp(0.0, 100.0)
+    } else {
+        0.0
+    };
+    let slot = WORKER_UTILIZATION.get_or_init(|| Mutex::new(Vec::new()));
+    if let Ok(mut list) = slot.lock() {
+        list.push((worker_id, pct));
+    }
+}
+
+/// Per-worker utilization percentages ordered by worker id.
+pub fn worker_utilization_snapshot() -> Vec<f64> {
+    let mut list = WORKER_UTILIZATION
+        .get()
+        .and_then(|slot| slot.lock().ok().map(|v| v.clone()))
+        .unwrap_or_default();
+    list.sort_by_key(|(id, _)| *id);
+    list.into_iter().map(|(_, pct)| pct).collect()
+}
+
 // Thread-local storage for statistics (following track_freq pattern)
 thread_local! {
     static THREAD_STATS: RefCell<ProcessingStats> = RefCell::new(ProcessingStats::new());
 }
 
 // Public API functions for stats collection (following track_freq pattern)
-// Note: These functions are conditionally called based on config.output.stats flag
+// Note: These functions record nothing unless set_collect_stats enabled collection
 pub fn stats_add_line_read() {
     if !stats_enabled() {
         return;
@@ -265,6 +500,27 @@ pub fn stats_add_line_filtered() {
     });
 }
 
+/// Which line-level regex filter dropped a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineFilterKind {
+    Keep,
+    Ignore,
+}
+
+pub fn stats_add_line_dropped(kind: LineFilterKind) {
+    if !stats_enabled() {
+        return;
+    }
+    THREAD_STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        stats.lines_filtered += 1;
+        match kind {
+            LineFilterKind::Keep => stats.lines_dropped_keep += 1,
+            LineFilterKind::Ignore => stats.lines_dropped_ignore += 1,
+        }
+    });
+}
+
 pub fn stats_add_event_created() {
     if !stats_enabled() {
         return;
@@ -319,6 +575,21 @@ pub fn stats_add_cascade_format_hit(format: &str) {
     });
 }
 
+/// Record which parser handled a line under --fallback-format.
+pub fn stats_add_fallback_parse(fallback: bool) {
+    if !stats_enabled() {
+        return;
+    }
+    THREAD_STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        if fallback {
+            stats.events_parsed_fallback += 1;
+        } else {
+            stats.events_parsed_primary += 1;
+        }
+    });
+}
+
 pub fn stats_set_detected_format(format: String) {
     if !stats_enabled() {
         return;
@@ -350,6 +621,42 @@ pub fn stats_add_late_event() {
     });
 }
 
+pub fn stats_add_event_thinned() {
+    if !stats_enabled() {
+        return;
+    }
+    THREAD_STATS.with(|stats| {
+        stats.borrow_mut().events_thinned += 1;
+    });
+}
+
+pub fn stats_add_ts_regression() {
+    if !stats_enabled() {
+        return;
+    }
+    THREAD_STATS.with(|stats| {
+        stats.borrow_mut().ts_regressions += 1;
+    });
+}
+
+pub fn stats_add_level_filtered() {
+    if !stats_enabled() {
+        return;
+    }
+    THREAD_STATS.with(|stats| {
+        stats.borrow_mut().events_level_filtered += 1;
+    });
+}
+
+pub fn stats_add_dup_key_event() {
+    if !stats_enabled() {
+        return;
+    }
+    THREAD_STATS.with(|stats| {
+        stats.borrow_mut().events_with_dup_keys += 1;
+    });
+}
+
 pub fn stats_add_yearless_timestamp() {
     if !stats_enabled() {
         return;
@@ -435,6 +742,71 @@ pub fn stats_add_assertion_failure(expression: &str) {
     });
 }
 
+pub fn stats_add_schema_failure() {
+    // Not gated by stats collection, for the same reason as assertion
+    // failures: --schema-validate is a gate that must fail the run.
+    THREAD_STATS.with(|stats| {
+        stats.borrow_mut().schema_failures += 1;
+    });
+}
+
+pub fn stats_add_stage_abort() {
+    // Not gated by stats collection: an aborted run must fail even though
+    // exec errors on their own are best-effort.
+    THREAD_STATS.with(|stats| {
+        stats.borrow_mut().stage_aborts += 1;
+    });
+}
+
+pub fn stats_add_stage_error(stage: &str) {
+    if !stats_enabled() {
+        return;
+    }
+    THREAD_STATS.with(|stats| {
+        *stats
+            .borrow_mut()
+            .stage_errors
+            .entry(stage.to_string())
+            .or_insert(0) += 1;
+    });
+}
+
+/// Record the value type of each field of an output event, labelled as in
+/// `--discover` (`int`, `string`, ...).
+pub fn stats_observe_field_types(fields: &crate::event::FieldMap) {
+    if !type_conflicts_enabled() {
+        return;
+    }
+    THREAD_STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        let field_types = &mut stats.field_types;
+        for (key, value) in fields {
+            if value.is_unit() {
+                continue;
+            }
+            let index = match field_types.get_index_of(key) {
+                Some(index) => index,
+                None if field_types.len() < MAX_TYPE_TRACKED_FIELDS => {
+                    field_types.insert_full(key.clone(), IndexMap::new()).0
+                }
+                None => continue,
+            };
+            let (_, types) = field_types
+                .get_index_mut(index)
+                .expect("index of a tracked field");
+            let label = crate::field_discovery::FieldType::from_dynamic(value).to_string();
+            *types.entry(label).or_insert(0) += 1;
+        }
+    });
+}
+
+/// Clear this thread's counters so the next run starts from zero
+pub fn stats_reset_thread() {
+    THREAD_STATS.with(|stats| {
+        *stats.borrow_mut() = ProcessingStats::new();
+    });
+}
+
 pub fn stats_start_timer() {
     if !stats_enabled() {
         return;
@@ -453,6 +825,7 @@ pub fn stats_finish_processing() {
         if let Some(start) = stats.start_time {
             stats.processing_time = start.elapsed();
         }
+        stats.resource_usage = crate::platform::resource_usage();
 
         let warning = stats.build_timestamp_override_warning();
         stats.timestamp_override_failed = warning.is_some();
@@ -465,6 +838,7 @@ pub fn get_thread_stats() -> ProcessingStats {
         let mut s = stats.borrow().clone();
         // Merge in atomic counter for file failures (can happen on any thread)
         s.files_failed_to_open = FILES_FAILED_TO_OPEN.load(Ordering::Relaxed);
+        s.files_processed = files_processed_count();
         s.failed_file_samples = failed_file_samples();
         s.recoverable_error_samples = recoverable_error_samples();
         s.first_parse_error_sample = first_parse_error_sample();
@@ -472,6 +846,10 @@ pub fn get_thread_stats() -> ProcessingStats {
         s.first_decode_warning_sample = first_decode_warning_sample();
         s.truncated_lines = TRUNCATED_LINES.load(Ordering::Relaxed);
         s.line_byte_cap = LINE_BYTE_CAP.load(Ordering::Relaxed);
+        s.input_cmd_stderr_lines = input_cmd_stderr_line_count();
+        (s.input_bytes_raw, s.input_bytes) = input_byte_counts();
+        s.compressed_files = compressed_file_bytes();
+        s.trailing_garbage = trailing_garbage_snapshot();
         s
     })
 }
@@ -486,6 +864,22 @@ pub fn stats_file_open_failed(path: &str) {
     push_failed_file_sample(path);
 }
 
+/// Count a named input file opened for reading. Like failed opens this is
+/// not gated by stats collection, since `--end` scripts read it too.
+pub fn stats_file_opened() {
+    run_scope::with_current(|scope| {
+        scope
+            .counters
+            .files_processed
+            .fetch_add(1, Ordering::Relaxed)
+    });
+}
+
+/// Files opened for reading in the current run (for the parallel path).
+pub fn files_processed_count() -> usize {
+    run_scope::with_current(|scope| scope.counters.files_processed.load(Ordering::Relaxed))
+}
+
 /// Process-wide count of files that failed to open. Exposed so the parallel
 /// tracker can merge it into final stats: file opens happen on reader/
 /// decompression threads and are recorded in this global atomic, not in the
@@ -618,6 +1012,11 @@ pub fn stats_add_output_key(key: String) {
     });
 }
 
+/// Render a byte count in MB (1024-based, like `human_bytes`) with one decimal.
+fn format_mebibytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
 impl ProcessingStats {
     pub fn new() -> Self {
         Self {
@@ -767,6 +1166,72 @@ impl ProcessingStats {
         summary
     }
 
+    /// Read-only `stats` map for `--end` scripts. Built from the final merged
+    /// counters, so parallel runs report the same numbers as `--stats`.
+    pub fn to_script_map(&self) -> rhai::Map {
+        fn count(value: usize) -> rhai::Dynamic {
+            rhai::Dynamic::from(value as rhai::INT)
+        }
+        fn timestamp(value: Option<DateTime<Utc>>) -> rhai::Dynamic {
+            value
+                .map(|dt| rhai::Dynamic::from(DateTimeWrapper::from_utc(dt)))
+                .unwrap_or(rhai::Dynamic::UNIT)
+        }
+
+        let mut map = rhai::Map::new();
+        map.insert("lines_read".into(), count(self.lines_read));
+        map.insert("lines_output".into(), count(self.lines_output));
+        map.insert("lines_filtered".into(), count(self.lines_filtered));
+        map.insert("parse_errors".into(), count(self.lines_errors));
+        map.insert("events_created".into(), count(self.events_created));
+        map.insert("events_output".into(), count(self.events_output));
+        map.insert("events_filtered".into(), count(self.events_filtered));
+        map.insert("files_processed".into(), count(self.files_processed));
+        map.insert("first_ts".into(), timestamp(self.first_timestamp));
+        map.insert("last_ts".into(), timestamp(self.last_timestamp));
+        map
+    }
+
+    /// Synthetic final event for `--stats-to-event`, tagged with
+    /// `_synthetic: "stats"` and `_kelora_stats: true` so scripts can pick it
+    /// out. Timestamps are RFC 3339 strings, like any parsed input field.
+    pub fn to_event(&self) -> crate::event::Event {
+        fn count(value: usize) -> rhai::Dynamic {
+            rhai::Dynamic::from(value as rhai::INT)
+        }
+        fn timestamp(value: Option<DateTime<Utc>>) -> rhai::Dynamic {
+            value
+                .map(|dt| {
+                    rhai::Dynamic::from(dt.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+                })
+                .unwrap_or(rhai::Dynamic::UNIT)
+        }
+
+        let mut event = crate::event::Event::default_with_line(String::new());
+        let mut set = |key: &str, value: rhai::Dynamic| event.set_field(key.to_string(), value);
+        set("_synthetic", rhai::Dynamic::from("stats"));
+        set("_kelora_stats", rhai::Dynamic::from(true));
+        set("lines_read", count(self.lines_read));
+        set("lines_output", count(self.lines_output));
+        set("lines_filtered", count(self.lines_filtered));
+        set("parse_errors", count(self.lines_errors));
+        set("events_created", count(self.events_created));
+        set("events_output", count(self.events_output));
+        set("events_filtered", count(self.events_filtered));
+        set("late_events", count(self.late_events));
+        set("assertion_failures", count(self.assertion_failures));
+        set("schema_failures", count(self.schema_failures));
+        set("files_processed", count(self.files_processed));
+        set("files_failed", count(self.files_failed_to_open));
+        set(
+            "duration_ms",
+            rhai::Dynamic::from(self.processing_time.as_millis() as rhai::INT),
+        );
+        set("first_ts", timestamp(self.first_timestamp));
+        set("last_ts", timestamp(self.last_timestamp));
+        event
+    }
+
     /// Format stats according to the specification
     pub fn format_stats(&self, _multiline_enabled: bool) -> String {
         self.format_stats_internal(_multiline_enabled, false)
@@ -812,27 +1277,67 @@ impl ProcessingStats {
                 .collect();
             format.insert("cascade".to_string(), Value::Object(counts));
         }
+        if self.events_parsed_fallback > 0 {
+            format.insert(
+                "fallback".to_string(),
+                json!({
+                    "primary": self.events_parsed_primary,
+                    "fallback": self.events_parsed_fallback,
+                }),
+            );
+        }
         if !format.is_empty() {
             root.insert("format".to_string(), Value::Object(format));
         }
 
-        root.insert(
-            "lines".to_string(),
-            json!({
-                "read": self.lines_read,
-                "filtered": self.lines_filtered,
-                "errors": self.lines_errors,
-            }),
-        );
-        root.insert(
-            "events".to_string(),
-            json!({
-                "created": self.events_created,
-                "output": self.events_output,
-                "filtered": self.events_filtered,
-                "late": self.late_events,
-            }),
-        );
+        let mut lines = json!({
+            "read": self.lines_read,
+            "filtered": self.lines_filtered,
+            "errors": self.lines_errors,
+        });
+        if self.lines_dropped_keep > 0 || self.lines_dropped_ignore > 0 {
+            lines["dropped_by_keep_lines"] = json!(self.lines_dropped_keep);
+            lines["dropped_by_ignore_lines"] = json!(self.lines_dropped_ignore);
+        }
+        root.insert("lines".to_string(), lines);
+        let mut events = json!({
+            "created": self.events_created,
+            "output": self.events_output,
+            "filtered": self.events_filtered,
+            "late": self.late_events,
+        });
+        if self.events_level_filtered > 0 {
+            events["level_filtered"] = json!(self.events_level_filtered);
+        }
+        if self.events_thinned > 0 {
+            events["thinned"] = json!(self.events_thinned);
+        }
+        if self.ts_regressions > 0 {
+            events["ts_regressions"] = json!(self.ts_regressions);
+        }
+        if self.events_with_dup_keys > 0 {
+            events["with_dup_keys"] = json!(self.events_with_dup_keys);
+        }
+        root.insert("events".to_string(), events);
+        let conflicts: Map<String, Value> = self
+            .type_conflicts()
+            .map(|(field, types)| {
+                let counts: Map<String, Value> =
+                    types.iter().map(|(t, n)| (t.clone(), json!(n))).collect();
+                (field.clone(), Value::Object(counts))
+            })
+            .collect();
+        if !conflicts.is_empty() {
+            root.insert("type_conflicts".to_string(), Value::Object(conflicts));
+        }
+        if !self.stage_errors.is_empty() {
+            let counts: Map<String, Value> = self
+                .stage_errors
+                .iter()
+                .map(|(k, v)| (k.clone(), json!(v)))
+                .collect();
+            root.insert("stage_errors".to_string(), Value::Object(counts));
+        }
 
         let duration_secs = self.processing_time.as_secs_f64();
         let lines_per_second = if duration_secs > 0.0 && self.lines_read > 0 {
@@ -840,13 +1345,62 @@ impl ProcessingStats {
         } else {
             None
         };
+        let bytes_per_second = if duration_secs > 0.0 && self.input_bytes > 0 {
+            serde_json::Number::from_f64(self.input_bytes as f64 / duration_secs).map(Value::Number)
+        } else {
+            None
+        };
         root.insert(
             "throughput".to_string(),
             json!({
                 "lines_per_second": lines_per_second,
+                "bytes_per_second": bytes_per_second,
                 "duration_ms": self.processing_time.as_millis() as u64,
             }),
         );
+        root.insert(
+            "input_bytes".to_string(),
+            json!({
+                "read": self.input_bytes,
+                "raw": self.input_bytes_raw,
+            }),
+        );
+        if !self.compressed_files.is_empty() {
+            let files: Vec<Value> = self
+                .compressed_files
+                .iter()
+                .map(|file| {
+                    json!({
+                        "path": file.path,
+                        "compressed": file.compressed,
+                        "decompressed": file.decompressed,
+                    })
+                })
+                .collect();
+            root.insert("compressed_files".to_string(), Value::Array(files));
+        }
+        if let Some(usage) = &self.resource_usage {
+            root.insert(
+                "resources".to_string(),
+                json!({
+                    "wall_ms": self.processing_time.as_millis() as u64,
+                    "cpu_user_ms": usage.user_time.as_millis() as u64,
+                    "cpu_system_ms": usage.system_time.as_millis() as u64,
+                    "peak_rss_bytes": usage.peak_rss_bytes,
+                }),
+            );
+        }
+        if !self.worker_utilization.is_empty() {
+            let workers: Vec<Value> = self
+                .worker_utilization
+                .iter()
+                .enumerate()
+                .map(
+                    |(id, pct)| json!({ "id": id, "utilization_pct": (pct * 10.0).round() / 10.0 }),
+                )
+                .collect();
+            root.insert("workers".to_string(), Value::Array(workers));
+        }
 
         let ts_fields: Vec<String> = if let Some(field) = &self.timestamp_override_field {
             vec![field.clone()]
@@ -923,20 +1477,35 @@ impl ProcessingStats {
         if self.decode_warnings > 0 {
             root.insert("decode_warnings".to_string(), json!(self.decode_warnings));
         }
+        if !self.trailing_garbage.is_empty() {
+            let entries: Vec<Value> = self
+                .trailing_garbage
+                .iter()
+                .map(|(source, offset)| json!({ "source": source, "offset": offset }))
+                .collect();
+            root.insert("trailing_garbage".to_string(), Value::Array(entries));
+        }
         if self.assertion_failures > 0 {
             root.insert(
                 "assertion_failures".to_string(),
                 json!(self.assertion_failures),
             );
         }
-        if self.files_processed > 0 || self.files_failed_to_open > 0 {
-            root.insert(
-                "files".to_string(),
-                json!({
-                    "processed": self.files_processed,
-                    "failed_to_open": self.files_failed_to_open,
-                }),
-            );
+        if self.schema_failures > 0 {
+            root.insert("schema_failures".to_string(), json!(self.schema_failures));
+        }
+        if self.files_processed > 0
+            || self.files_failed_to_open > 0
+            || !self.files_skipped_by_name.is_empty()
+        {
+            let mut files = json!({
+                "processed": self.files_processed,
+                "failed_to_open": self.files_failed_to_open,
+            });
+            if !self.files_skipped_by_name.is_empty() {
+                files["skipped_by_name"] = json!(self.files_skipped_by_name);
+            }
+            root.insert("files".to_string(), files);
         }
 
         serde_json::to_string_pretty(&Value::Object(root)).unwrap_or_else(|_| "{}".to_string())
@@ -982,6 +1551,14 @@ impl ProcessingStats {
             output.push_str(&format!("Cascade formats: {}\n", parts.join(", ")));
         }
 
+        // Only shown once the fallback parser actually handled a line
+        if self.events_parsed_fallback > 0 {
+            output.push_str(&format!(
+                "Fallback parsing: {} primary, {} fallback\n",
+                self.events_parsed_primary, self.events_parsed_fallback
+            ));
+        }
+
         // Lines processed: N total, N filtered (X%), N errors (Y%)
         // Skip this line when called from signal handler (line counts are always 0 there)
         if !skip_line_counts {
@@ -1009,6 +1586,12 @@ impl ProcessingStats {
                 self.lines_errors,
                 lines_errors_pct
             ));
+            if self.lines_dropped_keep > 0 || self.lines_dropped_ignore > 0 {
+                output.push_str(&format!(
+                    "Line filters: {} dropped by --keep-lines, {} dropped by --ignore-lines\n",
+                    self.lines_dropped_keep, self.lines_dropped_ignore
+                ));
+            }
         }
 
         // Ragged CSV/TSV rows (only present for csv/tsv inputs)
@@ -1034,6 +1617,44 @@ impl ProcessingStats {
             output.push_str(&format!("Late events: {}\n", self.late_events));
         }
 
+        if self.events_level_filtered > 0 {
+            output.push_str(&format!(
+                "Level filtered: {} (--level)\n",
+                self.events_level_filtered
+            ));
+        }
+
+        if self.events_thinned > 0 {
+            output.push_str(&format!("Thinned: {} (--thin)\n", self.events_thinned));
+        }
+
+        if self.ts_regressions > 0 {
+            output.push_str(&format!(
+                "Timestamp regressions: {} (--check-monotonic)\n",
+                self.ts_regressions
+            ));
+        }
+
+        if self.events_with_dup_keys > 0 {
+            output.push_str(&format!(
+                "Duplicate keys: {} events (--dup-keys)\n",
+                self.events_with_dup_keys
+            ));
+        }
+
+        if !self.stage_errors.is_empty() {
+            let parts: Vec<String> = self
+                .stage_errors
+                .iter()
+                .map(|(stage, count)| format!("{}={}", stage, count))
+                .collect();
+            output.push_str(&format!("Stage errors: {}\n", parts.join(", ")));
+        }
+
+        if let Some(conflicts) = self.format_type_conflicts() {
+            output.push_str(&format!("Type conflicts: {}\n", conflicts));
+        }
+
         // Throughput: N lines/s in Nms
         let duration_secs = self.processing_time.as_secs_f64();
         if duration_secs > 0.0 && self.lines_read > 0 {
@@ -1059,6 +1680,19 @@ impl ProcessingStats {
             output.push_str(&format!("Warning: {}\n", message));
         }
 
+        if !self.files_skipped_by_name.is_empty() {
+            let count = self.files_skipped_by_name.len();
+            output.push_str(&format!(
+                "Files skipped by name: {} outside --since/--until\n",
+                count
+            ));
+            if self.list_skipped_files {
+                for file in &self.files_skipped_by_name {
+                    output.push_str(&format!("  {}\n", file));
+                }
+            }
+        }
+
         if self.files_failed_to_open > 0 {
             output.push_str(&crate::config::format_error_message_auto(&format!(
                 "Failed to open {} file{}",
@@ -1077,9 +1711,28 @@ impl ProcessingStats {
             output.push('\n');
         }
 
+        if let Some(message) = self.format_trailing_garbage_warning() {
+            output.push_str(&crate::config::format_warning_message_auto(&message));
+            output.push('\n');
+        }
+
+        if self.input_cmd_stderr_lines > 0 {
+            let warning_msg = format!(
+                "Input commands wrote {} line{} to stderr",
+                self.input_cmd_stderr_lines,
+                if self.input_cmd_stderr_lines == 1 {
+                    ""
+                } else {
+                    "s"
+                }
+            );
+            output.push_str(&crate::config::format_warning_message_auto(&warning_msg));
+            output.push('\n');
+        }
+
         if self.yearless_timestamps > 0 {
             let warning_msg = format!(
-                "Year-less timestamps detected ({} parse{}): year guessed via ±1yr heuristic, >18mo old may be wrong",
+                "Year-less timestamps detected ({} parse{}): year inferred from the current date, pin it with --assume-year",
                 self.yearless_timestamps,
                 if self.yearless_timestamps == 1 {
                     ""
@@ -1187,9 +1840,67 @@ impl ProcessingStats {
             }
         }
 
+        output.push_str(&self.format_performance_summary());
+
         output.trim_end().to_string()
     }
 
+    /// Input volume, resource usage and worker utilization, rendered at the
+    /// bottom of the stats block. Each line is omitted when its data is absent
+    /// (e.g. mid-run signal stats or platforms without `getrusage`).
+    fn format_performance_summary(&self) -> String {
+        let mut output = String::new();
+        let duration_secs = self.processing_time.as_secs_f64();
+
+        if self.input_bytes > 0 {
+            let mut line = format!("Input read: {}", format_mebibytes(self.input_bytes));
+            if self.input_bytes_raw > 0 && self.input_bytes_raw != self.input_bytes {
+                line.push_str(&format!(
+                    " ({} before decompression)",
+                    format_mebibytes(self.input_bytes_raw)
+                ));
+            }
+            if duration_secs > 0.0 {
+                line.push_str(&format!(
+                    ", {:.1} MB/s",
+                    self.input_bytes as f64 / duration_secs / (1024.0 * 1024.0)
+                ));
+            }
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        for file in &self.compressed_files {
+            output.push_str(&format!(
+                "  {}: {} compressed, {} decompressed\n",
+                file.path,
+                format_mebibytes(file.compressed),
+                format_mebibytes(file.decompressed)
+            ));
+        }
+
+        if let Some(usage) = &self.resource_usage {
+            output.push_str(&format!(
+                "Resources: wall {:.2}s, CPU {:.2}s user + {:.2}s sys, peak RSS {}\n",
+                duration_secs,
+                usage.user_time.as_secs_f64(),
+                usage.system_time.as_secs_f64(),
+                format_mebibytes(usage.peak_rss_bytes)
+            ));
+        }
+
+        if !self.worker_utilization.is_empty() {
+            let parts: Vec<String> = self
+                .worker_utilization
+                .iter()
+                .map(|pct| format!("{:.0}%", pct))
+                .collect();
+            output.push_str(&format!("Worker utilization: {}\n", parts.join(", ")));
+        }
+
+        output
+    }
+
     /// One-line summary of ragged CSV/TSV rows, or None when none occurred.
     /// Factual only — callers that want to suggest --strict append their own advice.
     pub fn format_ragged_rows_summary(&self) -> Option<String> {
@@ -1233,7 +1944,11 @@ impl ProcessingStats {
     /// code — a partial parse failure has errors worth reporting but is recovered.
     /// For the exit-code decision use [`has_fatal_errors`](Self::has_fatal_errors).
     pub fn has_errors(&self) -> bool {
-        self.lines_errors > 0 || self.files_failed_to_open > 0 || self.assertion_failures > 0
+        self.lines_errors > 0
+            || self.files_failed_to_open > 0
+            || self.assertion_failures > 0
+            || self.schema_failures > 0
+            || self.stage_aborts > 0
     }
 
     /// Stats-side inputs to the exit-code decision (the structural and
@@ -1245,14 +1960,20 @@ impl ProcessingStats {
     /// - **Structural** — a named input file that could not be opened is an
     ///   invocation/environment error, never data noise, so it fails the run in
     ///   any mode.
-    /// - **Explicit gate** — an `--assert` violation fails the run in any mode.
+    /// - **Explicit gate** — an `--assert` violation or a `--schema-validate`
+    ///   failure fails the run in any mode, as does a stage error that stopped
+    ///   the run under `--on-stage-error abort`.
     /// - **Strict** — under `--strict`, *any* parse error is fatal (strict also
     ///   aborts on the first such line before reaching here; this is the
     ///   belt-and-suspenders end-of-run check). In resilient mode parse errors
     ///   are recovered unless the parser never once succeeded, which the tracker
     ///   detects.
     pub fn has_fatal_errors(&self, strict: bool) -> bool {
-        if self.files_failed_to_open > 0 || self.assertion_failures > 0 {
+        if self.files_failed_to_open > 0
+            || self.assertion_failures > 0
+            || self.schema_failures > 0
+            || self.stage_aborts > 0
+        {
             return true;
         }
         strict && self.lines_errors > 0
@@ -1276,6 +1997,51 @@ impl ProcessingStats {
         Some(message)
     }
 
+    /// Fields seen with more than one value type, in first-seen order.
+    pub fn type_conflicts(&self) -> impl Iterator<Item = (&String, &IndexMap<String, usize>)> {
+        self.field_types.iter().filter(|(_, types)| types.len() > 1)
+    }
+
+    /// `status (int=3, string=1), ...` for --detect-type-conflicts, or `None`
+    /// when every field kept one type.
+    fn format_type_conflicts(&self) -> Option<String> {
+        let parts: Vec<String> = self
+            .type_conflicts()
+            .map(|(field, types)| {
+                let counts: Vec<String> = types
+                    .iter()
+                    .map(|(name, count)| format!("{}={}", name, count))
+                    .collect();
+                format!("{} ({})", field, counts.join(", "))
+            })
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// End-of-run warning for --detect-type-conflicts when --stats is off.
+    pub fn format_type_conflict_warning(&self) -> Option<String> {
+        self.format_type_conflicts()
+            .map(|conflicts| format!("Fields with mixed types: {}", conflicts))
+    }
+
+    /// Warning for compressed inputs whose trailing junk bytes were skipped.
+    /// Returns `None` when every compressed input ended cleanly.
+    pub fn format_trailing_garbage_warning(&self) -> Option<String> {
+        let ((first_source, first_offset), rest) = self.trailing_garbage.split_first()?;
+        let mut message = format!(
+            "Ignored trailing data after the last compressed member of {} at byte {}",
+            first_source, first_offset
+        );
+        if !rest.is_empty() {
+            message.push_str(&format!(
+                " (and {} more file{})",
+                rest.len(),
+                if rest.len() == 1 { "" } else { "s" }
+            ));
+        }
+        Some(message)
+    }
+
     /// Warning for lines clipped by the `--max-line-bytes` circuit breaker.
     /// Returns `None` when nothing was truncated.
     pub fn format_line_truncation_warning(&self) -> Option<String> {
@@ -1369,6 +2135,14 @@ impl ProcessingStats {
             ));
         }
 
+        if self.schema_failures > 0 {
+            parts.push(format!(
+                "{} schema failure{}",
+                self.schema_failures,
+                if self.schema_failures == 1 { "" } else { "s" }
+            ));
+        }
+
         if parts.is_empty() {
             return String::new();
         }
@@ -1381,7 +2155,7 @@ impl ProcessingStats {
 
         if self.yearless_timestamps > 0 {
             parts.push(format!(
-                "{} year-less timestamp{} (±1yr heuristic)",
+                "{} year-less timestamp{} (year inferred)",
                 self.yearless_timestamps,
                 if self.yearless_timestamps == 1 {
                     ""
@@ -1412,6 +2186,7 @@ mod tests {
             *stats.borrow_mut() = ProcessingStats::new();
         });
         FILES_FAILED_TO_OPEN.store(0, Ordering::Relaxed);
+        crate::run_scope::install(Some(Default::default()));
         if let Some(samples) = FAILED_FILE_SAMPLES.get() {
             samples.lock().expect("failed file sample lock").clear();
         }
@@ -1496,4 +2271,32 @@ mod tests {
         assert!(summary.contains("api.log"));
         assert!(summary.contains("not sorted at line 42"));
     }
+
+    #[test]
+    fn performance_summary_renders_bytes_resources_and_workers() {
+        let stats = ProcessingStats {
+            processing_time: Duration::from_secs(2),
+            input_bytes: 4 * 1024 * 1024,
+            input_bytes_raw: 1024 * 1024,
+            resource_usage: Some(crate::platform::ResourceUsage {
+                user_time: Duration::from_millis(1500),
+                system_time: Duration::from_millis(250),
+                peak_rss_bytes: 32 * 1024 * 1024,
+            }),
+            worker_utilization: vec![92.4, 88.6],
+            ..Default::default()
+        };
+
+        let summary = stats.format_performance_summary();
+        assert!(summary.contains("Input read: 4.0 MB (1.0 MB before decompression), 2.0 MB/s"));
+        assert!(
+            summary.contains("Resources: wall 2.00s, CPU 1.50s user + 0.25s sys, peak RSS 32.0 MB")
+        );
+        assert!(summary.contains("Worker utilization: 92%, 89%"));
+
+        // Nothing measured (e.g. mid-run signal stats): nothing rendered
+        assert!(ProcessingStats::default()
+            .format_performance_summary()
+            .is_empty());
+    }
 }
@@ -21,12 +21,26 @@ impl AdaptiveTsParser {
         ts_str: &str,
         custom_format: Option<&str>,
         default_timezone: Option<&str>,
+    ) -> Option<DateTime<Utc>> {
+        self.parse_ts_with_year(ts_str, custom_format, default_timezone, None)
+    }
+
+    /// Like [`Self::parse_ts_with_config`], giving year-less timestamps
+    /// `assume_year` (--assume-year) instead of an inferred year
+    pub fn parse_ts_with_year(
+        &mut self,
+        ts_str: &str,
+        custom_format: Option<&str>,
+        default_timezone: Option<&str>,
+        assume_year: Option<i32>,
     ) -> Option<DateTime<Utc>> {
         let ts_str = ts_str.trim();
 
         // Try custom format first if provided
         if let Some(format) = custom_format {
-            if let Some(parsed) = try_parse_with_format(ts_str, format, default_timezone) {
+            if let Some(parsed) =
+                try_parse_with_format(ts_str, format, default_timezone, assume_year)
+            {
                 return Some(parsed);
             }
         }
@@ -88,7 +102,7 @@ impl AdaptiveTsParser {
         }
 
         // Try format list with adaptive reordering
-        self.try_formats_with_reordering(ts_str, default_timezone)
+        self.try_formats_with_reordering(ts_str, default_timezone, assume_year)
     }
 
     /// Try formats with timezone configuration and move successful ones to front
@@ -96,9 +110,12 @@ impl AdaptiveTsParser {
         &mut self,
         ts_str: &str,
         default_timezone: Option<&str>,
+        assume_year: Option<i32>,
     ) -> Option<DateTime<Utc>> {
         for (index, format) in self.formats.iter().enumerate() {
-            if let Some(parsed) = try_parse_with_format(ts_str, format, default_timezone) {
+            if let Some(parsed) =
+                try_parse_with_format(ts_str, format, default_timezone, assume_year)
+            {
                 // Move successful format to front if it's not already there
                 if index > 0 {
                     let successful_format = self.formats.remove(index);
@@ -147,8 +164,56 @@ impl Default for AdaptiveTsParser {
     }
 }
 
+/// Parse a fixed UTC offset such as `+0200`, `+02:00`, `-05`, or `Z`.
+pub fn parse_fixed_offset(tz_str: &str) -> Option<chrono::FixedOffset> {
+    let tz_str = tz_str.trim();
+    if tz_str == "Z" {
+        return chrono::FixedOffset::east_opt(0);
+    }
+
+    let (sign, rest) = match tz_str.as_bytes().first()? {
+        b'+' => (1, &tz_str[1..]),
+        b'-' => (-1, &tz_str[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((h, m)) if h.len() == 2 && m.len() == 2 => (h, m),
+        Some(_) => return None,
+        None if rest.len() == 4 => rest.split_at(2),
+        None if rest.len() == 2 => (rest, "00"),
+        None => return None,
+    };
+    if !hours
+        .bytes()
+        .chain(minutes.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    chrono::FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Resolve a `--tz-field` value to a timezone usable for naive timestamps.
+/// Returns None (use the default timezone) for missing, non-string, or
+/// unrecognized values.
+pub fn event_timezone(value: &rhai::Dynamic) -> Option<String> {
+    let tz = value.clone().into_string().ok()?;
+    let tz = tz.trim();
+    if parse_fixed_offset(tz).is_some() || tz.parse::<chrono_tz::Tz>().is_ok() {
+        Some(tz.to_string())
+    } else {
+        None
+    }
+}
+
 /// Helper function to apply timezone configuration to a naive datetime
-fn apply_timezone_to_naive(
+pub(crate) fn apply_timezone_to_naive(
     naive_dt: chrono::NaiveDateTime,
     default_timezone: Option<&str>,
 ) -> Option<DateTime<Utc>> {
@@ -157,6 +222,12 @@ fn apply_timezone_to_naive(
     match default_timezone {
         Some("UTC") => Some(naive_dt.and_utc()),
         Some(tz_str) => {
+            if let Some(offset) = parse_fixed_offset(tz_str) {
+                return offset
+                    .from_local_datetime(&naive_dt)
+                    .single()
+                    .map(|dt| dt.with_timezone(&Utc));
+            }
             // Try to parse as named timezone
             if let Ok(tz) = tz_str.parse::<Tz>() {
                 if let Some(dt) = tz.from_local_datetime(&naive_dt).single() {
@@ -212,11 +283,55 @@ fn choose_best_timestamp(candidates: &[DateTime<Utc>], now: DateTime<Utc>) -> Da
         .expect("candidates should not be empty")
 }
 
+/// Resolve a timestamp whose format has month and day but no year.
+///
+/// With `assume_year` (--assume-year) that year is used as-is. Otherwise the
+/// year is inferred: of the candidates from four years back to next year, the
+/// one closest to `now` that is not more than the future tolerance ahead wins,
+/// so Dec 31 read on Jan 1 lands in the previous year and Feb 29 lands in the
+/// most recent leap year.
+fn parse_yearless(
+    ts_str: &str,
+    format: &str,
+    default_timezone: Option<&str>,
+    assume_year: Option<i32>,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let format_with_year = format!("%Y {}", format);
+    let with_year = |year: i32| {
+        let ts_with_year = format!("{} {}", year, ts_str);
+        chrono::NaiveDateTime::parse_from_str(&ts_with_year, &format_with_year)
+            .ok()
+            .and_then(|naive_dt| apply_timezone_to_naive(naive_dt, default_timezone))
+    };
+
+    let resolved = match assume_year {
+        Some(year) => with_year(year)?,
+        None => {
+            // Four years back always includes a leap year for Feb 29 lines.
+            let candidates: Vec<_> = (-4..=1)
+                .filter_map(|offset| with_year(now.year() + offset))
+                .collect();
+            if candidates.is_empty() {
+                return None;
+            }
+            // Track that we used year inference for statistics
+            crate::stats::stats_add_yearless_timestamp();
+            choose_best_timestamp(&candidates, now)
+        }
+    };
+    // Yearless timestamps are also naive (no zone offset); record the
+    // default-timezone assumption so #287 can surface it once per run.
+    crate::stats::stats_add_naive_timestamp();
+    Some(resolved)
+}
+
 /// Try to parse a timestamp with a specific format and timezone configuration
 fn try_parse_with_format(
     ts_str: &str,
     format: &str,
     default_timezone: Option<&str>,
+    assume_year: Option<i32>,
 ) -> Option<DateTime<Utc>> {
     // Strip brackets if present (common in Apache/Nginx logs)
     let ts_str = ts_str.trim();
@@ -281,37 +396,13 @@ fn try_parse_with_format(
         || processed_format.contains("%y"); // 2-digit year (24)
 
     if has_month && has_day && !has_year {
-        let now = chrono::Utc::now();
-        let current_year = now.year();
-        let format_with_year = format!("%Y {}", processed_format);
-
-        let mut candidates = Vec::new();
-
-        // Try current year, previous year, and next year
-        // This handles edge cases around year boundaries
-        for year_offset in [-1, 0, 1] {
-            let year = current_year + year_offset;
-            let ts_with_year = format!("{} {}", year, processed_ts_str);
-
-            if let Ok(naive_dt) =
-                chrono::NaiveDateTime::parse_from_str(&ts_with_year, &format_with_year)
-            {
-                if let Some(dt) = apply_timezone_to_naive(naive_dt, default_timezone) {
-                    candidates.push(dt);
-                }
-            }
-        }
-
-        // If we have multiple valid candidates, choose the most reasonable one
-        // Prefer timestamps in the past and closest to now
-        if !candidates.is_empty() {
-            // Track that we used year inference for statistics
-            crate::stats::stats_add_yearless_timestamp();
-            // Yearless timestamps are also naive (no zone offset); record the
-            // default-timezone assumption so #287 can surface it once per run.
-            crate::stats::stats_add_naive_timestamp();
-            return Some(choose_best_timestamp(&candidates, now));
-        }
+        return parse_yearless(
+            &processed_ts_str,
+            &processed_format,
+            default_timezone,
+            assume_year,
+            chrono::Utc::now(),
+        );
     }
 
     // Try naive parsing
@@ -466,6 +557,7 @@ fn get_initial_timestamp_formats() -> Vec<String> {
         "%b %d %H:%M:%S".to_string(),       // Syslog format
         "%b %d %Y %H:%M:%S".to_string(),    // BSD syslog with year
         "%d/%b/%Y:%H:%M:%S %z".to_string(), // Apache log format
+        "%d/%b/%Y:%H:%M:%S%.f".to_string(), // HAProxy accept date
         // Application-specific formats
         "%Y-%m-%d %H:%M:%S,%f".to_string(), // Python logging format
         "%Y/%m/%d %H:%M:%S".to_string(),    // Nginx error log format
@@ -489,6 +581,11 @@ pub struct TsConfig {
     pub custom_format: Option<String>,
     /// Default timezone for naive timestamps (None = local time)
     pub default_timezone: Option<String>,
+    /// Field holding a per-event timezone (offset or IANA name) that overrides
+    /// `default_timezone` for naive timestamps (--tz-field)
+    pub tz_field: Option<String>,
+    /// Year for year-less timestamps instead of inferring one (--assume-year)
+    pub assume_year: Option<i32>,
 }
 
 /// Identify and extract timestamp from event fields
@@ -540,16 +637,88 @@ fn dynamic_to_timestamp_string(value: &rhai::Dynamic) -> Option<String> {
 
 /// Parse timestamp arguments (--since, --until) in journalctl-compatible format
 /// Uses the enhanced adaptive parser with journalctl support
+///
+/// On top of what the adaptive parser accepts, time arguments may be:
+/// - `@EPOCH` (any magnitude, optional sign and fraction), or a bare integer of
+///   at least 9 digits, read as seconds/ms/µs/ns by magnitude
+/// - `DURATION ago` (e.g. `2h ago`, `30 minutes ago`), same as `-DURATION`
 pub fn parse_timestamp_arg_with_timezone(
     arg: &str,
     default_timezone: Option<&str>,
 ) -> Result<DateTime<Utc>, String> {
+    let trimmed = arg.trim();
+
+    if let Some(epoch) = trimmed.strip_prefix('@') {
+        return parse_epoch_arg(epoch.trim())
+            .ok_or_else(|| format!("Invalid epoch timestamp: {}", arg));
+    }
+
+    if trimmed.len() >= MIN_BARE_EPOCH_DIGITS && trimmed.bytes().all(|b| b.is_ascii_digit()) {
+        if let Some(dt) = parse_epoch_arg(trimmed) {
+            return Ok(dt);
+        }
+    }
+
+    if let Some(ago) = trimmed.strip_suffix("ago") {
+        let ago = ago.trim_end();
+        if ago.len() < trimmed.len() - 3 && looks_like_relative_time(ago) {
+            return parse_relative_time(&format!("-{}", ago));
+        }
+    }
+
     let mut parser = AdaptiveTsParser::new();
     parser
         .parse_ts_with_config(arg, None, default_timezone)
         .ok_or_else(|| format!("Could not parse timestamp: {}", arg))
 }
 
+/// Bare integers shorter than this are not taken as epochs (`20240115` reads
+/// more like a date than 1970-08-23); use `@N` for small epoch values
+const MIN_BARE_EPOCH_DIGITS: usize = 9;
+
+/// Parse an epoch value, picking the unit by magnitude: below 1e11 is seconds
+/// (up to year 5138), then milliseconds, microseconds, and nanoseconds from
+/// 1e17. A fractional part is a fraction of that unit.
+fn parse_epoch_arg(value: &str) -> Option<DateTime<Utc>> {
+    const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+    let (negative, unsigned) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (int_part, frac_part) = match unsigned.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (unsigned, None),
+    };
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(int_part) || !frac_part.is_none_or(is_digits) {
+        return None;
+    }
+
+    let magnitude: i128 = int_part.parse().ok()?;
+    let nanos_per_unit: i128 = match magnitude {
+        m if m < 100_000_000_000 => NANOS_PER_SEC,
+        m if m < 100_000_000_000_000 => 1_000_000,
+        m if m < 100_000_000_000_000_000 => 1_000,
+        _ => 1,
+    };
+
+    let mut total_nanos = magnitude.checked_mul(nanos_per_unit)?;
+    if let Some(frac_part) = frac_part {
+        // Fraction of one unit, kept to nanosecond resolution
+        let padded = format!("{:0<9}", &frac_part[..frac_part.len().min(9)]);
+        let frac: i128 = padded.parse().ok()?;
+        total_nanos += frac * nanos_per_unit / NANOS_PER_SEC;
+    }
+    if negative {
+        total_nanos = -total_nanos;
+    }
+
+    let secs = i64::try_from(total_nanos.div_euclid(NANOS_PER_SEC)).ok()?;
+    let nanos = total_nanos.rem_euclid(NANOS_PER_SEC) as u32;
+    DateTime::from_timestamp(secs, nanos)
+}
+
 /// Parse anchored timestamp expressions like "since+30m", "until-1h", "now+5m"
 /// Requires the corresponding anchor timestamp to be provided (except for "now")
 pub fn parse_anchored_timestamp(
@@ -1047,6 +1216,8 @@ mod tests {
             custom_field: Some("custom_time".to_string()),
             custom_format: None,
             default_timezone: None,
+            tz_field: None,
+            assume_year: None,
         };
 
         let result = identify_timestamp_field(&fields, &config);
@@ -1069,6 +1240,8 @@ mod tests {
             custom_field: Some("custom_time".to_string()),
             custom_format: None,
             default_timezone: None,
+            tz_field: None,
+            assume_year: None,
         };
 
         let result = identify_timestamp_field(&fields, &config);
@@ -1144,6 +1317,8 @@ mod tests {
             custom_field: Some("custom_time".to_string()),
             custom_format: None,
             default_timezone: None,
+            tz_field: None,
+            assume_year: None,
         };
 
         let result = identify_timestamp_field(&fields, &config);
@@ -1282,6 +1457,93 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_time_arg_epoch_magnitudes() {
+        let expected = DateTime::from_timestamp(1_699_999_999, 0).unwrap();
+        for arg in [
+            "1699999999",
+            "1699999999000",
+            "1699999999000000",
+            "1699999999000000000",
+        ] {
+            assert_eq!(
+                parse_timestamp_arg_with_timezone(arg, None).unwrap(),
+                expected,
+                "{}",
+                arg
+            );
+        }
+
+        // Sub-second precision survives in every unit
+        let ms = parse_timestamp_arg_with_timezone("1699999999123", None).unwrap();
+        assert_eq!(ms.timestamp_subsec_millis(), 123);
+        let ns = parse_timestamp_arg_with_timezone("1699999999123456789", None).unwrap();
+        assert_eq!(ns.timestamp_subsec_nanos(), 123_456_789);
+    }
+
+    #[test]
+    fn test_time_arg_at_epoch_syntax() {
+        assert_eq!(
+            parse_timestamp_arg_with_timezone("@1699999999", None).unwrap(),
+            DateTime::from_timestamp(1_699_999_999, 0).unwrap()
+        );
+        assert_eq!(
+            parse_timestamp_arg_with_timezone("@1699999999000", None).unwrap(),
+            DateTime::from_timestamp(1_699_999_999, 0).unwrap()
+        );
+        // Small values and fractions are only epochs with the explicit '@'
+        assert_eq!(
+            parse_timestamp_arg_with_timezone("@60", None).unwrap(),
+            DateTime::from_timestamp(60, 0).unwrap()
+        );
+        assert_eq!(
+            parse_timestamp_arg_with_timezone("@1699999999.25", None).unwrap(),
+            DateTime::from_timestamp(1_699_999_999, 250_000_000).unwrap()
+        );
+        assert_eq!(
+            parse_timestamp_arg_with_timezone("@-1.5", None).unwrap(),
+            DateTime::from_timestamp(-2, 500_000_000).unwrap()
+        );
+
+        assert!(parse_timestamp_arg_with_timezone("@", None).is_err());
+        assert!(parse_timestamp_arg_with_timezone("@12ab", None).is_err());
+        assert!(parse_timestamp_arg_with_timezone("@1.", None).is_err());
+        // Short bare integers are not guessed as epochs
+        assert!(parse_timestamp_arg_with_timezone("20240115", None).is_err());
+    }
+
+    #[test]
+    fn test_time_arg_relative_forms_agree() {
+        let close_to = |arg: &str, offset: chrono::Duration| {
+            let parsed = parse_anchored_timestamp(arg, None, None, None).unwrap();
+            let delta = parsed - (Utc::now() + offset);
+            assert!(delta.num_seconds().abs() <= 5, "{} -> {}", arg, parsed);
+        };
+
+        close_to("-1h", chrono::Duration::hours(-1));
+        close_to("1h", chrono::Duration::hours(-1));
+        close_to("1h ago", chrono::Duration::hours(-1));
+        close_to("1 hour ago", chrono::Duration::hours(-1));
+        close_to("+30m", chrono::Duration::minutes(30));
+        close_to("now+30m", chrono::Duration::minutes(30));
+        close_to("now-1h", chrono::Duration::hours(-1));
+
+        // Anchored and self-relative forms resolve the same way for both bounds
+        let (since, until) = resolve_time_range(Some("-1h"), Some("+30m"), None).unwrap();
+        assert_eq!(
+            (until.unwrap() - since.unwrap()).num_minutes(),
+            90,
+            "since/until relative forms"
+        );
+        let (since, until) =
+            resolve_time_range(Some("@1699999999"), Some("since+30m"), None).unwrap();
+        assert_eq!(since.unwrap().timestamp(), 1_699_999_999);
+        assert_eq!(until.unwrap().timestamp(), 1_699_999_999 + 1_800);
+
+        assert!(parse_timestamp_arg_with_timezone("1h ago ago", None).is_err());
+        assert!(parse_timestamp_arg_with_timezone("ago", None).is_err());
+    }
+
     #[test]
     fn test_relative_time_out_of_range_returns_error() {
         assert!(parse_relative_time("111111111111h").is_err());
@@ -1680,6 +1942,63 @@ mod tests {
         assert_eq!(dt.minute(), 0);
     }
 
+    #[test]
+    fn test_timezone_fixed_offset() {
+        let mut parser = AdaptiveTsParser::new();
+
+        for offset in ["+0200", "+02:00", "+02"] {
+            let dt = parser
+                .parse_ts_with_config("2023-07-04 12:00:00", None, Some(offset))
+                .unwrap();
+            assert_eq!(dt.hour(), 10, "offset {offset}");
+        }
+
+        let dt = parser
+            .parse_ts_with_config("2023-07-04 12:00:00", None, Some("-05:30"))
+            .unwrap();
+        assert_eq!((dt.hour(), dt.minute()), (17, 30));
+    }
+
+    #[test]
+    fn test_parse_fixed_offset_rejects_malformed() {
+        assert_eq!(parse_fixed_offset("Z").unwrap().local_minus_utc(), 0);
+        assert_eq!(
+            parse_fixed_offset("-0130").unwrap().local_minus_utc(),
+            -5400
+        );
+        for bad in [
+            "0200",
+            "+2",
+            "+200",
+            "+2:00",
+            "+2400",
+            "+0260",
+            "+ab:cd",
+            "Europe/Berlin",
+        ] {
+            assert!(
+                parse_fixed_offset(bad).is_none(),
+                "{bad} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_event_timezone_validates_values() {
+        use rhai::Dynamic;
+
+        assert_eq!(
+            event_timezone(&Dynamic::from("+0200")).as_deref(),
+            Some("+0200")
+        );
+        assert_eq!(
+            event_timezone(&Dynamic::from(" Asia/Tokyo ")).as_deref(),
+            Some("Asia/Tokyo")
+        );
+        assert!(event_timezone(&Dynamic::from("Mars/Olympus")).is_none());
+        assert!(event_timezone(&Dynamic::from(120_i64)).is_none());
+    }
+
     #[test]
     fn test_dst_transition_spring_forward() {
         // Test DST transition - Spring forward (2:00 AM doesn't exist)
@@ -1895,6 +2214,85 @@ mod tests {
         assert_eq!(dt.day(), 1);
     }
 
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_yearless_dec_31_read_on_jan_1_uses_previous_year() {
+        let now = utc(2025, 1, 1, 0, 30);
+        let dt =
+            parse_yearless("Dec 31 23:59:00", "%b %d %H:%M:%S", Some("UTC"), None, now).unwrap();
+        assert_eq!(dt, utc(2024, 12, 31, 23, 59));
+    }
+
+    #[test]
+    fn test_yearless_jan_1_read_on_dec_31_allows_small_future_skew() {
+        // Within the clock-skew tolerance the upcoming Jan 1 beats last year's.
+        let now = utc(2024, 12, 31, 23, 50);
+        let dt =
+            parse_yearless("Jan 1 00:05:00", "%b %d %H:%M:%S", Some("UTC"), None, now).unwrap();
+        assert_eq!(dt, utc(2025, 1, 1, 0, 5));
+
+        // Beyond the tolerance the date is taken as the past occurrence.
+        let now = utc(2024, 12, 29, 12, 0);
+        let dt =
+            parse_yearless("Jan 1 00:05:00", "%b %d %H:%M:%S", Some("UTC"), None, now).unwrap();
+        assert_eq!(dt, utc(2024, 1, 1, 0, 5));
+    }
+
+    #[test]
+    fn test_yearless_feb_29_uses_most_recent_leap_year() {
+        // None of 2025..=2027 is a leap year; the old ±1 year window failed here.
+        let now = utc(2026, 3, 15, 0, 0);
+        let dt =
+            parse_yearless("Feb 29 08:00:00", "%b %d %H:%M:%S", Some("UTC"), None, now).unwrap();
+        assert_eq!(dt, utc(2024, 2, 29, 8, 0));
+
+        let now = utc(2024, 3, 1, 0, 0);
+        let dt =
+            parse_yearless("Feb 29 08:00:00", "%b %d %H:%M:%S", Some("UTC"), None, now).unwrap();
+        assert_eq!(dt, utc(2024, 2, 29, 8, 0));
+    }
+
+    #[test]
+    fn test_yearless_assume_year_overrides_inference() {
+        let now = utc(2025, 1, 1, 0, 30);
+        let dt = parse_yearless(
+            "Dec 31 23:59:00",
+            "%b %d %H:%M:%S",
+            Some("UTC"),
+            Some(2023),
+            now,
+        )
+        .unwrap();
+        assert_eq!(dt, utc(2023, 12, 31, 23, 59));
+
+        // Feb 29 does not exist in the assumed year.
+        assert!(parse_yearless(
+            "Feb 29 08:00:00",
+            "%b %d %H:%M:%S",
+            Some("UTC"),
+            Some(2023),
+            now
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_ts_config_assume_year_reaches_event_extraction() {
+        let mut event = crate::event::Event::default_with_line("line".to_string());
+        event.set_field("ts".to_string(), rhai::Dynamic::from("Jan 15 14:30:00"));
+
+        let config = TsConfig {
+            default_timezone: Some("UTC".to_string()),
+            assume_year: Some(2019),
+            ..TsConfig::default()
+        };
+        event.extract_timestamp_with_config(None, &config);
+        assert_eq!(event.parsed_ts, Some(utc(2019, 1, 15, 14, 30)));
+    }
+
     // ============================================================================
     // P0 EDGE CASE TESTS - Invalid Formats
     // ============================================================================
@@ -368,3 +368,42 @@ fn test_auto_detect_uses_first_non_empty_line() {
         "auto detection should skip leading blanks for detection and still parse JSON"
     );
 }
+
+/// --detect-lines looks past a banner to the JSON lines below it
+#[test]
+fn test_auto_detect_skips_banner_with_detect_lines() {
+    let input = "# Service export, version 4, generated 2024-05-01\n\
+# ==============================================\n\
+# columns: ts, level, msg\n\
+#\n\
+{\"level\":\"info\",\"msg\":\"started\"}\n\
+{\"level\":\"error\",\"msg\":\"failed\"}\n";
+
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "--detect-lines",
+            "10",
+            "--ignore-lines",
+            "^#",
+            "-F",
+            "json",
+            "-k",
+            "level",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "kelora should exit successfully");
+    let levels: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        levels,
+        vec![r#"{"level":"info"}"#, r#"{"level":"error"}"#],
+        "Should detect JSON below the banner"
+    );
+
+    // Without sampling, the first banner line decides
+    let (stdout, _stderr, _exit_code) = run_kelora_with_input(
+        &["--ignore-lines", "^#", "-F", "json", "-k", "level"],
+        input,
+    );
+    assert!(!stdout.contains(r#""level":"info""#));
+}
@@ -252,3 +252,75 @@ fn test_cascade_parallel_mode_merges_counts() {
         stdout
     );
 }
+
+/// --fallback-format retries rejected lines instead of dropping them as errors.
+#[test]
+fn test_fallback_format_recovers_plain_text_lines() {
+    let input = r#"{"level":"info","msg":"start"}
+sidecar: certificate reloaded
+{"level":"warn","msg":"slow"}
+{"level":"info","msg":"done"}
+WARN sidecar: retrying upstream"#;
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--fallback-format", "line", "-F", "json"],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 5, "every line should become an event");
+    assert_eq!(events[0]["msg"], "start");
+    assert!(events[0].get("_fallback").is_none());
+    assert_eq!(events[1]["_fallback"], true);
+    assert_eq!(events[1]["line"], "sidecar: certificate reloaded");
+    assert!(events[1].get("_format").is_none());
+
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--fallback-format", "line", "--stats"],
+        input,
+    );
+    assert_eq!(exit_code, 0);
+    assert!(
+        stdout.contains("Fallback parsing: 3 primary, 2 fallback"),
+        "stats should split primary and fallback events: {}",
+        stdout
+    );
+    assert!(stdout.contains(" 0 errors"), "stats: {}", stdout);
+}
+
+/// Lines neither format accepts are still parse errors.
+#[test]
+fn test_fallback_format_failures_still_count() {
+    let input = "{\"a\":1}\nnot logfmt\nb=2\n";
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--fallback-format",
+            "logfmt",
+            "-F",
+            "json",
+            "--stats",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0);
+    assert!(
+        stdout.contains("Fallback parsing: 1 primary, 1 fallback"),
+        "stats: {}",
+        stdout
+    );
+    assert!(stdout.contains(" 1 errors"), "stats: {}", stdout);
+}
+
+/// Formats that need more than one line can't be a per-line fallback.
+#[test]
+fn test_fallback_format_rejects_csv() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--fallback-format", "csv"], "{}\n");
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stderr.contains("--fallback-format"), "stderr: {}", stderr);
+}
@@ -0,0 +1,134 @@
+mod common;
+use common::*;
+
+use ciborium::value::Value;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn json_lines(n: usize) -> String {
+    (0..n)
+        .map(|i| format!(r#"{{"id":{},"level":"INFO","ok":true}}"#, i))
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+fn read_framed(path: &Path) -> Vec<Value> {
+    let bytes = std::fs::read(path).expect("CBOR file should exist");
+    let mut rest = bytes.as_slice();
+    let mut records = Vec::new();
+    while !rest.is_empty() {
+        let (len, tail) = rest.split_at(4);
+        let len = u32::from_be_bytes(len.try_into().unwrap()) as usize;
+        let (record, tail) = tail.split_at(len);
+        records.push(ciborium::de::from_reader(record).expect("valid CBOR record"));
+        rest = tail;
+    }
+    records
+}
+
+fn read_sequence(path: &Path) -> Vec<Value> {
+    let bytes = std::fs::read(path).expect("CBOR file should exist");
+    let mut rest = bytes.as_slice();
+    let mut records = Vec::new();
+    while !rest.is_empty() {
+        records.push(ciborium::de::from_reader(&mut rest).expect("valid CBOR record"));
+    }
+    records
+}
+
+fn field<'a>(record: &'a Value, key: &str) -> &'a Value {
+    record
+        .as_map()
+        .expect("record is a map")
+        .iter()
+        .find(|(k, _)| k.as_text() == Some(key))
+        .map(|(_, v)| v)
+        .unwrap_or_else(|| panic!("missing field {key}"))
+}
+
+#[test]
+fn test_cbor_output_is_length_framed_by_default() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let out = dir.path().join("events.cbor");
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "cbor",
+            "--output-file",
+            out.to_str().unwrap(),
+        ],
+        &json_lines(20),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.is_empty(), "CBOR output must not reach stdout");
+
+    let records = read_framed(&out);
+    assert_eq!(records.len(), 20);
+    assert_eq!(
+        field(&records[7], "id").as_integer().map(i128::from),
+        Some(7)
+    );
+    assert_eq!(field(&records[7], "level").as_text(), Some("INFO"));
+    assert_eq!(field(&records[7], "ok").as_bool(), Some(true));
+}
+
+#[test]
+fn test_cbor_unframed_writes_a_plain_sequence() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let out = dir.path().join("events.cbor");
+    let input = r#"{"name":"a","tags":["x","y"],"nested":{"n":1.5}}
+{"name":"b","tags":[],"nested":null}
+"#;
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "cbor",
+            "-o",
+            out.to_str().unwrap(),
+            "--cbor-unframed",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let records = read_sequence(&out);
+    assert_eq!(records.len(), 2);
+    assert_eq!(field(&records[0], "tags").as_array().map(Vec::len), Some(2));
+    assert_eq!(
+        field(field(&records[0], "nested"), "n").as_float(),
+        Some(1.5)
+    );
+    assert!(field(&records[1], "nested").is_null());
+}
+
+#[test]
+fn test_cbor_requires_output_file() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-F", "cbor"], &json_lines(1));
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--output-file"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_parse_cbor_hex_decodes_embedded_payload() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--exec",
+            "let m = parse_cbor_hex(e.payload); e.id = m.id; e.temp = m.temp; e.payload = ()",
+        ],
+        r#"{"payload":"A3626964076474656D70F94D606474616773816161"}"#,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let value: serde_json::Value = serde_json::from_str(stdout.trim()).expect("valid JSON");
+    assert_eq!(value["id"], 7);
+    assert_eq!(value["temp"], 21.5);
+}
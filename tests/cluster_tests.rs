@@ -0,0 +1,54 @@
+mod common;
+use common::*;
+
+#[test]
+fn test_cluster_groups_near_identical_messages() {
+    let input = r#"{"msg": "Connection timeout to db-1 after 30s"}
+{"msg": "Connection timeout to db-2 after 30s"}
+{"msg": "User alice logged in"}
+{"msg": "Connection timeout to db-3 after 31s"}
+{"msg": "User alicia logged in"}
+{"msg": "Disk full on /var"}
+"#;
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--cluster", "msg:0.8"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "clusters (3 items):\n  3: Connection timeout to db-1 after 30s\n  2: User alice logged in\n  1: Disk full on /var"
+    );
+}
+
+#[test]
+fn test_cluster_strict_threshold_splits_values() {
+    let input = r#"{"msg": "retry 1 failed"}
+{"msg": "retry 2 failed"}
+{"msg": "retry 1 failed"}
+"#;
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--cluster", "msg:1"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "clusters (2 items):\n  2: retry 1 failed\n  1: retry 2 failed"
+    );
+}
+
+#[test]
+fn test_cluster_rejects_invalid_threshold_and_parallel() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--cluster", "msg:2"], "{}\n");
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--cluster threshold"), "stderr: {}", stderr);
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--parallel", "--cluster", "msg"],
+        "{\"msg\": \"x\"}\n",
+    );
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("--cluster is not supported with --parallel"),
+        "stderr: {}",
+        stderr
+    );
+}
@@ -0,0 +1,160 @@
+//! Minimal writer for MaxMind DB (`.mmdb`) test fixtures.
+//!
+//! Builds an IPv4 database with 24-bit records from a list of non-overlapping
+//! networks and their JSON records, following the MaxMind DB format 2.0 spec.
+//! Only the types the fixtures need are encoded: maps, arrays, strings,
+//! doubles, unsigned integers and booleans.
+
+use serde_json::Value;
+use std::net::Ipv4Addr;
+use std::path::Path;
+
+const DATA_SECTION_SEPARATOR: [u8; 16] = [0; 16];
+const METADATA_MARKER: &[u8] = b"\xAB\xCD\xEFMaxMind.com";
+
+const TYPE_STRING: u8 = 2;
+const TYPE_DOUBLE: u8 = 3;
+const TYPE_UINT16: u8 = 5;
+const TYPE_UINT32: u8 = 6;
+const TYPE_MAP: u8 = 7;
+const TYPE_UINT64: u8 = 9;
+const TYPE_ARRAY: u8 = 11;
+const TYPE_BOOLEAN: u8 = 14;
+
+#[derive(Clone, Copy)]
+enum Record {
+    Empty,
+    Node(usize),
+    Data(usize),
+}
+
+fn write_control(out: &mut Vec<u8>, kind: u8, size: usize) {
+    let (size_bits, size_bytes) = if size < 29 {
+        (size as u8, Vec::new())
+    } else if size < 285 {
+        (29, vec![(size - 29) as u8])
+    } else if size < 65_821 {
+        (30, ((size - 285) as u16).to_be_bytes().to_vec())
+    } else {
+        (31, ((size - 65_821) as u32).to_be_bytes()[1..].to_vec())
+    };
+    if kind <= 7 {
+        out.push((kind << 5) | size_bits);
+    } else {
+        out.push(size_bits);
+        out.push(kind - 7);
+    }
+    out.extend(size_bytes);
+}
+
+fn write_uint(out: &mut Vec<u8>, kind: u8, value: u64) {
+    let bytes = value.to_be_bytes();
+    let first = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    write_control(out, kind, bytes.len() - first);
+    out.extend(&bytes[first..]);
+}
+
+fn write_string(out: &mut Vec<u8>, text: &str) {
+    write_control(out, TYPE_STRING, text.len());
+    out.extend(text.as_bytes());
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Object(map) => {
+            write_control(out, TYPE_MAP, map.len());
+            for (key, value) in map {
+                write_string(out, key);
+                write_value(out, value);
+            }
+        }
+        Value::Array(items) => {
+            write_control(out, TYPE_ARRAY, items.len());
+            for item in items {
+                write_value(out, item);
+            }
+        }
+        Value::String(text) => write_string(out, text),
+        Value::Bool(flag) => write_control(out, TYPE_BOOLEAN, *flag as usize),
+        Value::Number(n) => match n.as_u64() {
+            Some(n) if n <= u32::MAX as u64 => write_uint(out, TYPE_UINT32, n),
+            Some(n) => write_uint(out, TYPE_UINT64, n),
+            None => {
+                write_control(out, TYPE_DOUBLE, 8);
+                out.extend(n.as_f64().unwrap().to_be_bytes());
+            }
+        },
+        Value::Null => panic!("MMDB has no null type"),
+    }
+}
+
+/// Write an IPv4 `.mmdb` file mapping each `a.b.c.d/len` network to its record
+pub fn write_mmdb(path: &Path, database_type: &str, networks: &[(&str, Value)]) {
+    let mut nodes: Vec<[Record; 2]> = vec![[Record::Empty; 2]];
+    let mut data = Vec::new();
+    let mut offsets = Vec::new();
+
+    for (index, (network, record)) in networks.iter().enumerate() {
+        offsets.push(data.len());
+        write_value(&mut data, record);
+
+        let (address, prefix) = network.split_once('/').expect("network needs /len");
+        let address = u32::from(address.parse::<Ipv4Addr>().unwrap());
+        let prefix: u32 = prefix.parse().unwrap();
+        let mut node = 0;
+        for depth in 0..prefix {
+            let bit = ((address >> (31 - depth)) & 1) as usize;
+            if depth == prefix - 1 {
+                nodes[node][bit] = Record::Data(index);
+                break;
+            }
+            node = match nodes[node][bit] {
+                Record::Node(next) => next,
+                Record::Empty => {
+                    nodes.push([Record::Empty; 2]);
+                    nodes[node][bit] = Record::Node(nodes.len() - 1);
+                    nodes.len() - 1
+                }
+                Record::Data(_) => panic!("overlapping networks in fixture"),
+            };
+        }
+    }
+
+    let node_count = nodes.len();
+    let mut out = Vec::new();
+    for node in &nodes {
+        for record in node {
+            let value = match *record {
+                Record::Empty => node_count,
+                Record::Node(next) => next,
+                Record::Data(index) => node_count + 16 + offsets[index],
+            };
+            out.extend(&(value as u32).to_be_bytes()[1..]);
+        }
+    }
+    out.extend(DATA_SECTION_SEPARATOR);
+    out.extend(&data);
+
+    out.extend(METADATA_MARKER);
+    write_control(&mut out, TYPE_MAP, 9);
+    write_string(&mut out, "binary_format_major_version");
+    write_uint(&mut out, TYPE_UINT16, 2);
+    write_string(&mut out, "binary_format_minor_version");
+    write_uint(&mut out, TYPE_UINT16, 0);
+    write_string(&mut out, "build_epoch");
+    write_uint(&mut out, TYPE_UINT64, 1_700_000_000);
+    write_string(&mut out, "database_type");
+    write_string(&mut out, database_type);
+    write_string(&mut out, "description");
+    write_value(&mut out, &serde_json::json!({"en": "kelora test fixture"}));
+    write_string(&mut out, "ip_version");
+    write_uint(&mut out, TYPE_UINT16, 4);
+    write_string(&mut out, "languages");
+    write_value(&mut out, &serde_json::json!(["en"]));
+    write_string(&mut out, "node_count");
+    write_uint(&mut out, TYPE_UINT32, node_count as u64);
+    write_string(&mut out, "record_size");
+    write_uint(&mut out, TYPE_UINT16, 24);
+
+    std::fs::write(path, out).unwrap();
+}
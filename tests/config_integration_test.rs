@@ -431,6 +431,81 @@ fn test_invalid_config_produces_error() {
     );
 }
 
+#[test]
+fn test_unknown_config_key_warns_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join(".kelora.ini");
+    fs::write(
+        &config_path,
+        "defaults = -f json\nparalell = true\n\n[aliasses]\nerrors = -l error\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, exit_code) = run_kelora_in_dir(
+        temp_dir.path(),
+        &["--config-file", config_path.to_str().unwrap(), "-k", "msg"],
+        "{\"msg\": \"hello\"}\n",
+    );
+
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    // The known defaults line still applies
+    assert!(stdout.contains("hello"), "stdout: {}", stdout);
+    assert!(
+        stderr.contains("config warning in")
+            && stderr.contains(".kelora.ini:2: unknown key 'paralell'")
+            && stderr.contains("defaults = --parallel"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains(".kelora.ini:4: unknown section '[aliasses]'")
+            && stderr.contains("did you mean '[aliases]'?"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_strict_config_makes_unknown_keys_an_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join(".kelora.ini");
+    fs::write(&config_path, "defaults = -f json\nparalell = true\n").unwrap();
+
+    let (stdout, stderr, exit_code) = run_kelora_in_dir(
+        temp_dir.path(),
+        &[
+            "--config-file",
+            config_path.to_str().unwrap(),
+            "--strict-config",
+        ],
+        "{\"msg\": \"hello\"}\n",
+    );
+
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stdout.is_empty(), "stdout: {}", stdout);
+    assert!(
+        stderr.contains("config error in") && stderr.contains("unknown key 'paralell'"),
+        "stderr: {}",
+        stderr
+    );
+
+    // A clean config is unaffected
+    fs::write(&config_path, "defaults = -f json\n").unwrap();
+    let (stdout, stderr, exit_code) = run_kelora_in_dir(
+        temp_dir.path(),
+        &[
+            "--config-file",
+            config_path.to_str().unwrap(),
+            "--strict-config",
+            "-k",
+            "msg",
+        ],
+        "{\"msg\": \"hello\"}\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("hello"), "stdout: {}", stdout);
+}
+
 #[test]
 fn test_nonexistent_config_file_error() {
     let temp_dir = TempDir::new().unwrap();
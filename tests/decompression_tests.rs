@@ -387,3 +387,75 @@ fn test_stdin_with_gzip() {
         stdout
     );
 }
+
+fn gzip_bytes(data: &str) -> Vec<u8> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data.as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_concatenated_gzip_members_are_all_read() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("rotated.log.gz");
+    let mut file = File::create(&path).unwrap();
+    file.write_all(&gzip_bytes("{\"msg\": \"from a\"}\n"))
+        .unwrap();
+    file.write_all(&gzip_bytes("{\"msg\": \"from b\"}\n"))
+        .unwrap();
+    drop(file);
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_files(&["-f", "json"], &[path.to_str().unwrap()]);
+
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("from a") && stdout.contains("from b"));
+    assert!(
+        !stderr.contains("trailing"),
+        "no warning expected: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_gzip_trailing_garbage_warns_and_keeps_data() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("junk.log.gz");
+    let member = gzip_bytes("{\"msg\": \"one\"}\n{\"msg\": \"two\"}\n");
+    let mut file = File::create(&path).unwrap();
+    file.write_all(&member).unwrap();
+    file.write_all(&[0u8; 16]).unwrap();
+    drop(file);
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_files(&["-f", "json"], &[path.to_str().unwrap()]);
+    assert_eq!(
+        exit_code, 0,
+        "trailing junk must not fail the file: {}",
+        stderr
+    );
+    assert!(stdout.contains("one") && stdout.contains("two"));
+    assert!(
+        stderr.contains(&format!("at byte {}", member.len())),
+        "warning should name the offset: {}",
+        stderr
+    );
+
+    let (stdout, _stderr, exit_code) =
+        run_kelora_with_files(&["-f", "json", "--stats=json"], &[path.to_str().unwrap()]);
+    assert_eq!(exit_code, 0);
+    let stats: serde_json::Value =
+        serde_json::from_str(stdout.trim()).unwrap_or_else(|e| panic!("not JSON ({e}): {stdout}"));
+    assert_eq!(stats["trailing_garbage"][0]["offset"], member.len() as u64);
+    assert_eq!(
+        stats["compressed_files"][0]["compressed"],
+        (member.len() + 16) as u64
+    );
+    assert_eq!(
+        stats["compressed_files"][0]["decompressed"],
+        "{\"msg\": \"one\"}\n{\"msg\": \"two\"}\n".len() as u64
+    );
+}
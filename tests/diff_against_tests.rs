@@ -0,0 +1,191 @@
+mod common;
+use common::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+// Yesterday's log: the cache template disappears today
+const BASELINE: &str = r#"{"level":"info","msg":"request 17 served in 12ms"}
+{"level":"info","msg":"request 18 served in 9ms"}
+{"level":"warn","msg":"cache miss for key 4411"}
+{"level":"info","msg":"user 7 logged in"}
+{"level":"info","msg":"user 8 logged in"}
+"#;
+
+// Today's log: a new error template appears and logins triple
+const CURRENT: &str = r#"{"level":"info","msg":"request 19 served in 11ms"}
+{"level":"info","msg":"request 20 served in 14ms"}
+{"level":"error","msg":"db timeout after 3000ms"}
+{"level":"info","msg":"user 9 logged in"}
+{"level":"info","msg":"user 10 logged in"}
+{"level":"info","msg":"user 11 logged in"}
+{"level":"info","msg":"user 12 logged in"}
+{"level":"info","msg":"user 13 logged in"}
+{"level":"info","msg":"user 14 logged in"}
+"#;
+
+const TEMPLATE_KEY: &str = r#"e.msg.replace_regex("\\d+", "N")"#;
+
+fn temp_log(content: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().expect("Failed to create temp file");
+    file.write_all(content.as_bytes())
+        .expect("Failed to write temp file");
+    file
+}
+
+fn objects(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_diff_against_reports_added_removed_and_changed_templates() {
+    let baseline = temp_log(BASELINE);
+    let current = temp_log(CURRENT);
+    let (stdout, stderr, exit_code) = run_kelora_with_files(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--diff-against",
+            baseline.path().to_str().unwrap(),
+            "--diff-by",
+            TEMPLATE_KEY,
+        ],
+        &[current.path().to_str().unwrap()],
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let report = objects(&stdout);
+    assert_eq!(report.len(), 3, "stdout: {}", stdout);
+    assert_eq!(
+        report[0],
+        serde_json::json!({
+            "key": "db timeout after Nms",
+            "change": "added",
+            "baseline": 0,
+            "current": 1,
+        })
+    );
+    assert_eq!(
+        report[1],
+        serde_json::json!({
+            "key": "cache miss for key N",
+            "change": "removed",
+            "baseline": 1,
+            "current": 0,
+        })
+    );
+    assert_eq!(report[2]["key"], "user N logged in");
+    assert_eq!(report[2]["change"], "changed");
+    assert_eq!(report[2]["baseline"], 2);
+    assert_eq!(report[2]["current"], 6);
+    assert_eq!(report[2]["change_pct"].as_f64(), Some(200.0));
+}
+
+#[test]
+fn test_diff_against_threshold_and_filters() {
+    let baseline = temp_log(BASELINE);
+    let current = temp_log(CURRENT);
+
+    // Only info events are counted, and tripled logins are not more than a
+    // 200% change
+    let (stdout, stderr, exit_code) = run_kelora_with_files(
+        &[
+            "-f",
+            "json",
+            "--filter",
+            "e.level == \"info\"",
+            "--diff-against",
+            baseline.path().to_str().unwrap(),
+            "--diff-by",
+            TEMPLATE_KEY,
+            "--diff-threshold",
+            "200",
+            "-F",
+            "csvnh",
+            "-k",
+            "change,key",
+        ],
+        &[current.path().to_str().unwrap()],
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "");
+
+    // Keys that come out as () are left out of the comparison
+    let (stdout, stderr, exit_code) = run_kelora_with_files(
+        &[
+            "-f",
+            "json",
+            "--diff-against",
+            baseline.path().to_str().unwrap(),
+            "--diff-by",
+            "if e.level == \"info\" { () } else { e.level }",
+            "-F",
+            "csvnh",
+            "-k",
+            "change,key",
+        ],
+        &[current.path().to_str().unwrap()],
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "added,error\nremoved,warn");
+}
+
+#[test]
+fn test_diff_against_requires_key_expression() {
+    let baseline = temp_log(BASELINE);
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--diff-against",
+            baseline.path().to_str().unwrap(),
+        ],
+        CURRENT,
+    );
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stderr.contains("--diff-by"), "stderr: {}", stderr);
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--diff-against",
+            baseline.path().to_str().unwrap(),
+            "--diff-by",
+            "e.msg",
+            "--diff-threshold=-5",
+        ],
+        CURRENT,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--diff-threshold"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_diff_against_reads_current_side_from_stdin() {
+    let baseline = temp_log(BASELINE);
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--diff-against",
+            baseline.path().to_str().unwrap(),
+            "--diff-by",
+            "e.level",
+            "-F",
+            "csvnh",
+            "-k",
+            "change,key,baseline,current",
+        ],
+        CURRENT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "added,error,0,1\nremoved,warn,1,0\nchanged,info,4,8"
+    );
+}
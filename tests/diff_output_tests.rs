@@ -0,0 +1,140 @@
+mod common;
+use common::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+// Half of each side's events also appear on the other side
+const LEFT: &str = r#"{"id":1,"msg":"boot"}
+{"id":2,"msg":"config loaded"}
+{"id":3,"msg":"listening"}
+{"id":4,"msg":"ready"}
+"#;
+const RIGHT: &str = r#"{"id":3,"msg":"listening"}
+{"id":4,"msg":"ready"}
+{"id":5,"msg":"cache warmed"}
+{"id":6,"msg":"healthy"}
+"#;
+
+fn temp_log(content: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().expect("Failed to create temp file");
+    file.write_all(content.as_bytes())
+        .expect("Failed to write temp file");
+    file
+}
+
+fn objects(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+fn ids_and_sides(stdout: &str) -> Vec<(i64, String)> {
+    objects(stdout)
+        .iter()
+        .map(|e| {
+            (
+                e["id"].as_i64().unwrap(),
+                e["_diff_side"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_diff_output_emits_symmetric_difference() {
+    let left = temp_log(LEFT);
+    let right = temp_log(RIGHT);
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "-f",
+        "json",
+        "-F",
+        "json",
+        "--diff",
+        "--left",
+        left.path().to_str().unwrap(),
+        "--right",
+        right.path().to_str().unwrap(),
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        ids_and_sides(&stdout),
+        vec![
+            (1, "left".to_string()),
+            (2, "left".to_string()),
+            (5, "right".to_string()),
+            (6, "right".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_diff_output_show_common() {
+    let left = temp_log(LEFT);
+    let right = temp_log(RIGHT);
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "-f",
+        "json",
+        "-F",
+        "json",
+        "--diff-output",
+        "--diff-show-common",
+        "--left",
+        left.path().to_str().unwrap(),
+        "--right",
+        right.path().to_str().unwrap(),
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let sides: Vec<_> = ids_and_sides(&stdout)
+        .into_iter()
+        .map(|(_, side)| side)
+        .collect();
+    assert_eq!(
+        sides,
+        vec!["left", "left", "both", "both", "right", "right"]
+    );
+}
+
+#[test]
+fn test_diff_key_compares_selected_fields_after_scripts() {
+    let left = temp_log(LEFT);
+    // Same ids with different messages, plus one new id
+    let right = temp_log(
+        r#"{"id":1,"msg":"boot (v2)"}
+{"id":2,"msg":"config loaded (v2)"}
+{"id":3,"msg":"listening (v2)"}
+{"id":4,"msg":"ready (v2)"}
+{"id":7,"msg":"new step"}
+"#,
+    );
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "-f",
+        "json",
+        "-F",
+        "json",
+        "--diff-output",
+        "--diff-key",
+        "id",
+        "--filter",
+        "e.id != 1",
+        "--left",
+        left.path().to_str().unwrap(),
+        "--right",
+        right.path().to_str().unwrap(),
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(ids_and_sides(&stdout), vec![(7, "right".to_string())]);
+}
+
+#[test]
+fn test_diff_output_requires_both_sides() {
+    let left = temp_log(LEFT);
+    let (_stdout, _stderr, exit_code) = run_kelora(&[
+        "-f",
+        "json",
+        "--diff-output",
+        "--left",
+        left.path().to_str().unwrap(),
+    ]);
+    assert_eq!(exit_code, 2);
+}
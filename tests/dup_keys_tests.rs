@@ -0,0 +1,88 @@
+mod common;
+use common::*;
+
+const JSON_DUPS: &str = r#"{"user":"alice","tag":"a","tag":"b","tag":"c"}
+{"user":"bob","tag":"solo"}
+"#;
+
+const LOGFMT_DUPS: &str = "\
+user=alice tag=a tag=b tag=c
+user=bob tag=solo
+";
+
+fn parse_lines(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_dup_keys_first_and_last_per_parser() {
+    for (format, input) in [("json", JSON_DUPS), ("logfmt", LOGFMT_DUPS)] {
+        let (stdout, stderr, exit_code) =
+            run_kelora_with_input(&["-f", format, "-F", "json"], input);
+        assert_eq!(exit_code, 0, "{format} stderr: {}", stderr);
+        assert_eq!(parse_lines(&stdout)[0]["tag"], "c", "{format} default");
+
+        let (stdout, stderr, exit_code) =
+            run_kelora_with_input(&["-f", format, "-F", "json", "--dup-keys", "first"], input);
+        assert_eq!(exit_code, 0, "{format} stderr: {}", stderr);
+        assert_eq!(parse_lines(&stdout)[0]["tag"], "a", "{format} first");
+    }
+}
+
+#[test]
+fn test_dup_keys_collect_feeds_scripts_an_array() {
+    for (format, input) in [("json", JSON_DUPS), ("logfmt", LOGFMT_DUPS)] {
+        let (stdout, stderr, exit_code) = run_kelora_with_input(
+            &[
+                "-f",
+                format,
+                "-F",
+                "json",
+                "--dup-keys",
+                "collect",
+                "--exec",
+                "if type_of(e.tag) == \"array\" { e.second = e.tag[1]; e.count = e.tag.len() }",
+            ],
+            input,
+        );
+        assert_eq!(exit_code, 0, "{format} stderr: {}", stderr);
+        let events = parse_lines(&stdout);
+        assert_eq!(events[0]["tag"], serde_json::json!(["a", "b", "c"]));
+        assert_eq!(events[0]["second"], "b");
+        assert_eq!(events[0]["count"], 3);
+        // Keys that appear once stay scalar
+        assert_eq!(events[1]["tag"], "solo");
+    }
+}
+
+#[test]
+fn test_dup_keys_error_routes_to_parse_errors() {
+    for (format, input) in [("json", JSON_DUPS), ("logfmt", LOGFMT_DUPS)] {
+        let (stdout, stderr, exit_code) = run_kelora_with_input(
+            &[
+                "-f",
+                format,
+                "-F",
+                "json",
+                "--dup-keys",
+                "error",
+                "--with-stats",
+            ],
+            input,
+        );
+        assert_eq!(exit_code, 0, "{format} stderr: {}", stderr);
+        let events = parse_lines(&stdout);
+        assert_eq!(events.len(), 1, "{format}");
+        assert_eq!(events[0]["user"], "bob");
+        let stats = extract_stats_lines(&stderr).join("\n");
+        assert!(stats.contains("Duplicate keys: 1"), "stats: {}", stats);
+
+        let (_stdout, stderr, exit_code) =
+            run_kelora_with_input(&["-f", format, "--dup-keys", "error", "--strict"], input);
+        assert_ne!(exit_code, 0, "{format}");
+        assert!(stderr.contains("duplicate key 'tag'"), "stderr: {}", stderr);
+    }
+}
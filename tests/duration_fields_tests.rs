@@ -0,0 +1,72 @@
+mod common;
+use common::*;
+
+const REQUESTS: &str = r#"{"path":"/a","took":"45ms"}
+{"path":"/b","took":"250ms"}
+{"path":"/c","took":0.5}
+{"path":"/d","took":"n/a"}
+"#;
+
+fn objects(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_parse_durations_filter_compares_mixed_representations() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--parse-durations",
+            "--filter",
+            "e.path != \"/d\" && e.took > to_duration(\"100ms\")",
+        ],
+        REQUESTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = objects(&stdout);
+    let paths: Vec<_> = events.iter().map(|e| e["path"].clone()).collect();
+    assert_eq!(paths, vec!["/b", "/c"]);
+    // Values render back the way they were written
+    assert_eq!(events[0]["took"], "250ms");
+    assert_eq!(events[1]["took"], 0.5);
+}
+
+#[test]
+fn test_parse_durations_custom_fields_and_unit() {
+    let input = "id=1 rt=0.123 took=45ms\n";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "logfmt",
+            "-F",
+            "logfmt",
+            "--parse-durations",
+            "--duration-fields",
+            "rt",
+            "--duration-unit",
+            "ms",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    // Only rt is a duration field, so took stays a plain string
+    assert_eq!(stdout.trim(), "id=1 rt=123ms took=45ms");
+}
+
+#[test]
+fn test_without_parse_durations_values_are_untouched() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "-e", "e.kind = type_of(e.took)"],
+        REQUESTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = objects(&stdout);
+    assert_eq!(events[0]["kind"], "string");
+    assert_eq!(events[2]["kind"], "f64");
+}
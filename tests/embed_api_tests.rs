@@ -0,0 +1,534 @@
+mod common;
+use common::*;
+
+use kelora::embed::{CancellationToken, CollectOutput, Input, PipelineBuilder};
+use kelora::event::Event;
+use kelora::pipeline::Formatter;
+use kelora::{InputFormat, OutputFormat};
+use std::fs;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+const INPUT: &str = r#"{"level":"INFO","msg":"started","took":12}
+{"level":"ERROR","msg":"disk full","took":250}
+not json
+{"level":"WARN","msg":"slow","took":900}
+"#;
+
+fn input_lines() -> Vec<String> {
+    INPUT.lines().map(str::to_string).collect()
+}
+
+/// The embedding API and the CLI build the same pipeline from the same
+/// settings, so they must produce the same output.
+#[test]
+fn test_embedded_run_matches_cli_output() {
+    for (flag, format) in [
+        ("json", OutputFormat::Json),
+        ("logfmt", OutputFormat::Logfmt),
+        ("default", OutputFormat::Default),
+    ] {
+        let (stdout, stderr, exit_code) = run_kelora_with_input(
+            &[
+                "-f",
+                "json",
+                "-F",
+                flag,
+                "--no-color",
+                "--filter",
+                "e.took > 100",
+                "-e",
+                "e.slow = e.took > 500",
+            ],
+            INPUT,
+        );
+        assert_eq!(exit_code, 0, "-F {flag}: {stderr}");
+
+        let output = CollectOutput::new();
+        PipelineBuilder::new()
+            .input(Input::lines(input_lines()))
+            .input_format(InputFormat::Json)
+            .output_format(format)
+            .filter("e.took > 100")
+            .exec("e.slow = e.took > 500")
+            .output(output.clone())
+            .run()
+            .expect("embedded run");
+
+        let cli_lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(output.lines(), cli_lines, "-F {flag}");
+    }
+}
+
+#[test]
+fn test_embedded_run_reports_stats_and_metrics() {
+    let output = CollectOutput::new();
+    let result = PipelineBuilder::new()
+        .input(Input::reader(INPUT.as_bytes()))
+        .input_format(InputFormat::Json)
+        .exec("track_sum(\"took\", e.took); track_inc(e.level)")
+        .take(2)
+        .output(output.clone())
+        .run()
+        .expect("embedded run");
+
+    assert_eq!(output.lines().len(), 2);
+    assert_eq!(result.stats.events_output, 2);
+    assert!(!result.cancelled);
+    assert_eq!(result.tracking.user["took"].to_string(), "262");
+    assert_eq!(result.tracking.user["ERROR"].to_string(), "1");
+
+    // Each run starts from zero on the same thread
+    let result = PipelineBuilder::new()
+        .input(Input::lines(input_lines()))
+        .input_format(InputFormat::Json)
+        .output(CollectOutput::new())
+        .run()
+        .expect("embedded run");
+    assert_eq!(result.stats.lines_read, 4);
+    assert_eq!(result.stats.events_output, 3);
+    assert!(result.tracking.user.is_empty());
+}
+
+#[test]
+fn test_embedded_run_reads_files_with_filenames() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let first = temp_dir.path().join("a.log");
+    let second = temp_dir.path().join("b.log");
+    fs::write(&first, "one\ntwo\n").unwrap();
+    fs::write(&second, "three\n").unwrap();
+
+    let output = CollectOutput::new();
+    PipelineBuilder::new()
+        .input(Input::files([&first, &second]))
+        .output_format(OutputFormat::Logfmt)
+        .exec("e.file = meta.filename.split(\"/\")[-1]")
+        .output(output.clone())
+        .run()
+        .expect("embedded run");
+
+    assert_eq!(
+        output.lines(),
+        vec![
+            "line=one file=a.log",
+            "line=two file=a.log",
+            "line=three file=b.log"
+        ]
+    );
+}
+
+/// Settings the CLI used to install from `main` come from each run's own
+/// config, so runs in one process don't inherit each other's values.
+#[test]
+fn test_embedded_run_applies_assume_year_per_run() {
+    let normalized_ts = |year: i32| {
+        let output = CollectOutput::new();
+        let mut builder = PipelineBuilder::new()
+            .input(Input::lines(vec![
+                "<34>Jan 15 14:30:45 host app: started".to_string()
+            ]))
+            .input_format(InputFormat::Syslog)
+            .output_format(OutputFormat::Logfmt)
+            .output(output.clone());
+        let config = builder.config_mut();
+        config.input.assume_year = Some(year);
+        config.input.default_timezone = Some("UTC".to_string());
+        config.processing.normalize_timestamps = true;
+        config.output.keys = vec!["ts".to_string()];
+        builder.run().expect("embedded run");
+        output.lines()
+    };
+
+    assert_eq!(normalized_ts(2019), vec!["ts=2019-01-15T14:30:45+00:00"]);
+    assert_eq!(normalized_ts(2021), vec!["ts=2021-01-15T14:30:45+00:00"]);
+}
+
+/// Inputs dropped by `--filename-ts-format` are reported by the run that
+/// dropped them only.
+#[test]
+fn test_embedded_run_reports_its_own_files_skipped_by_name() {
+    let skipped_by_run = |skipped: Vec<String>| {
+        let mut builder = PipelineBuilder::new()
+            .input(Input::lines(input_lines()))
+            .output(CollectOutput::new());
+        builder.config_mut().input.files_skipped_by_name = skipped;
+        builder
+            .run()
+            .expect("embedded run")
+            .stats
+            .files_skipped_by_name
+    };
+
+    let first = vec!["app-2019-01-01.log".to_string()];
+    assert_eq!(skipped_by_run(first.clone()), first);
+    assert!(skipped_by_run(Vec::new()).is_empty());
+}
+
+/// `Input::Configured` reads what the configuration names, in parallel when
+/// it asks for that, and `output_stream` receives the raw formatted stream:
+/// the way the CLI drives its runs.
+#[test]
+fn test_configured_input_runs_like_the_cli() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("app.log");
+    fs::write(&path, INPUT).unwrap();
+
+    let mut builder = PipelineBuilder::new()
+        .input(Input::Configured)
+        .input_format(InputFormat::Json)
+        .output_format(OutputFormat::Logfmt)
+        .filter("e.took > 100");
+    let config = builder.config_mut();
+    config.input.files = vec![path.to_string_lossy().into_owned()];
+    config.performance.parallel = true;
+    config.performance.threads = 2;
+
+    let stream = SharedBuffer::default();
+    let result = builder
+        .output_stream(stream.clone())
+        .run_pipeline()
+        .expect("configured run");
+
+    assert_eq!(
+        String::from_utf8(stream.0.lock().unwrap().clone()).unwrap(),
+        "level=ERROR msg=\"disk full\" took=250\nlevel=WARN msg=slow took=900\n"
+    );
+    assert_eq!(result.stats.map(|s| s.events_output), Some(2));
+    assert_eq!(result.exit_code, None);
+}
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+struct MessageOnly;
+
+impl Formatter for MessageOnly {
+    fn format(&self, event: &Event) -> String {
+        event
+            .fields
+            .get("msg")
+            .map(|msg| msg.to_string())
+            .unwrap_or_default()
+    }
+
+    fn finish(&self) -> Option<String> {
+        Some("-- end --".to_string())
+    }
+}
+
+#[test]
+fn test_embedded_run_uses_custom_formatter_and_stops_on_cancel() {
+    let output = CollectOutput::new();
+    PipelineBuilder::new()
+        .input(Input::lines(input_lines()))
+        .input_format(InputFormat::Json)
+        .formatter(MessageOnly)
+        .output(output.clone())
+        .run()
+        .expect("embedded run");
+    assert_eq!(
+        output.lines(),
+        vec!["started", "disk full", "slow", "-- end --"]
+    );
+
+    // An endless input stops once the caller cancels
+    let token = CancellationToken::new();
+    let producer = token.clone();
+    let endless = (0..).map(move |i| {
+        if i == 2 {
+            producer.cancel();
+        }
+        format!("line {}", i)
+    });
+    let output = CollectOutput::new();
+    let result = PipelineBuilder::new()
+        .input(Input::lines(endless))
+        .output_format(OutputFormat::Logfmt)
+        .cancellation(token)
+        .output(output.clone())
+        .run()
+        .expect("embedded run");
+    assert!(result.cancelled);
+    assert_eq!(output.lines(), vec!["line=\"line 0\"", "line=\"line 1\""]);
+}
+
+/// Each run owns its token: cancelling one pipeline must not stop another
+/// running concurrently in the same process.
+#[test]
+fn test_concurrent_runs_are_cancelled_independently() {
+    let first = CancellationToken::new();
+    let second = CancellationToken::new();
+
+    let run = |token: CancellationToken, stop_at: usize| {
+        let producer = token.clone();
+        std::thread::spawn(move || {
+            let endless = (0..).map(move |i| {
+                if i == stop_at {
+                    producer.cancel();
+                }
+                format!("line {}", i)
+            });
+            let output = CollectOutput::new();
+            let result = PipelineBuilder::new()
+                .input(Input::lines(endless))
+                .output_format(OutputFormat::Logfmt)
+                .cancellation(token)
+                .output(output.clone())
+                .run()
+                .expect("embedded run");
+            (result.cancelled, output.lines().len())
+        })
+    };
+
+    let first_run = run(first.clone(), 3);
+    let second_run = run(second.clone(), 500);
+    assert_eq!(first_run.join().unwrap(), (true, 3));
+    assert_eq!(second_run.join().unwrap(), (true, 500));
+
+    // Cancelling one token leaves a fresh run with another token untouched
+    let output = CollectOutput::new();
+    let result = PipelineBuilder::new()
+        .input(Input::lines(input_lines()))
+        .cancellation(CancellationToken::new())
+        .output(output.clone())
+        .run()
+        .expect("embedded run");
+    assert!(first.is_cancelled());
+    assert!(!result.cancelled);
+    assert_eq!(output.lines().len(), 4);
+}
+
+/// `--sandbox` belongs to the run that asked for it: building an
+/// unsandboxed pipeline while a sandboxed one is running must not lift its
+/// limits.
+#[test]
+fn test_sandbox_is_kept_per_run() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let target = temp_dir.path().join("out.txt");
+    let target_str = target.to_string_lossy().replace('\\', "/");
+
+    let (lines_tx, lines_rx) = std::sync::mpsc::channel::<String>();
+    let ready = Arc::new(std::sync::Barrier::new(2));
+    let messages = Arc::new(Mutex::new(Vec::new()));
+
+    let sandboxed = {
+        let ready = ready.clone();
+        let messages = messages.clone();
+        let exec = format!(
+            r#"if e.spin {{ loop {{}} }} else {{ try {{ append_file("{target_str}", "sandboxed\n") }} catch (err) {{ print(err) }} }}"#
+        );
+        std::thread::spawn(move || {
+            let mut builder = PipelineBuilder::new()
+                .input(Input::lines(lines_rx))
+                .input_format(InputFormat::Json)
+                .begin(r#"print("ready")"#)
+                .exec(&exec)
+                .strict(true)
+                .on_print(move |line| {
+                    if line == "ready" {
+                        ready.wait();
+                    } else {
+                        messages.lock().unwrap().push(line.to_string());
+                    }
+                })
+                .output(CollectOutput::new());
+            builder.config_mut().processing.sandbox = true;
+            builder.config_mut().processing.allow_fs_writes = true;
+            builder.run()
+        })
+    };
+
+    // Build and run an unsandboxed pipeline while the sandboxed one waits
+    // for input
+    ready.wait();
+    let mut builder = PipelineBuilder::new()
+        .input(Input::lines(vec![r#"{"n":1}"#.to_string()]))
+        .input_format(InputFormat::Json)
+        .exec(&format!(r#"append_file("{target_str}", "unsandboxed\n")"#))
+        .output(CollectOutput::new());
+    builder.config_mut().processing.allow_fs_writes = true;
+    builder.run().expect("unsandboxed run");
+
+    lines_tx.send(r#"{"spin":false}"#.to_string()).unwrap();
+    lines_tx.send(r#"{"spin":true}"#.to_string()).unwrap();
+    drop(lines_tx);
+    let err = sandboxed.join().unwrap().unwrap_err();
+
+    assert_eq!(fs::read_to_string(&target).unwrap(), "unsandboxed\n");
+    let messages = messages.lock().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert!(
+        messages[0].contains("append_file() is disabled by --sandbox"),
+        "file write should be refused, got: {}",
+        messages[0]
+    );
+    assert!(
+        format!("{err:#}").contains("--sandbox limit of"),
+        "the operation cap should still name the sandbox, got: {err:#}"
+    );
+}
+
+/// File, byte and `--input-cmd` stderr counts are kept per run: runs that
+/// overlap on other threads don't show up in each other's stats.
+#[test]
+fn test_concurrent_runs_report_their_own_stats() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let paths: Vec<_> = ["a.log", "b.log", "c.log"]
+        .iter()
+        .map(|name| temp_dir.path().join(name))
+        .collect();
+    fs::write(&paths[0], "one\n").unwrap();
+    fs::write(&paths[1], "two\nthree\n").unwrap();
+    fs::write(&paths[2], "four\n").unwrap();
+
+    // The first run stops on its only event until the others have finished
+    let paused = Arc::new(std::sync::Barrier::new(2));
+    let resume = Arc::new(std::sync::Barrier::new(2));
+    let first = {
+        let (paused, resume) = (paused.clone(), resume.clone());
+        let path = paths[0].clone();
+        std::thread::spawn(move || {
+            PipelineBuilder::new()
+                .input(Input::files([path]))
+                .exec(r#"print("paused")"#)
+                .on_print(move |_| {
+                    paused.wait();
+                    resume.wait();
+                })
+                .output(CollectOutput::new())
+                .run()
+                .expect("first run")
+                .stats
+        })
+    };
+
+    paused.wait();
+    let second = PipelineBuilder::new()
+        .input(Input::files([&paths[1], &paths[2]]))
+        .output(CollectOutput::new())
+        .run()
+        .expect("second run")
+        .stats;
+    let mut builder = PipelineBuilder::new()
+        .input(Input::Configured)
+        .output(CollectOutput::new());
+    builder.config_mut().input.input_cmds = vec!["echo five; echo oops >&2".to_string()];
+    let third = builder.run().expect("third run").stats;
+    resume.wait();
+    let first = first.join().unwrap();
+
+    assert_eq!(
+        (
+            first.files_processed,
+            first.input_bytes,
+            first.input_cmd_stderr_lines
+        ),
+        (1, 4, 0)
+    );
+    assert_eq!(
+        (
+            second.files_processed,
+            second.input_bytes,
+            second.input_cmd_stderr_lines
+        ),
+        (2, 15, 0)
+    );
+    assert_eq!(
+        (
+            third.files_processed,
+            third.input_bytes,
+            third.input_cmd_stderr_lines
+        ),
+        (0, 5, 1)
+    );
+}
+
+#[test]
+fn test_embedded_run_routes_script_prints_to_handlers() {
+    let printed = Arc::new(Mutex::new(Vec::new()));
+    let (out, err) = (printed.clone(), printed.clone());
+    let output = CollectOutput::new();
+    PipelineBuilder::new()
+        .input(Input::lines(input_lines()))
+        .input_format(InputFormat::Json)
+        .exec(r#"print("out " + e.msg); eprint("err " + e.level)"#)
+        .on_print(move |line| out.lock().unwrap().push(line.to_string()))
+        .on_eprint(move |line| err.lock().unwrap().push(line.to_string()))
+        .formatter(MessageOnly)
+        .output(output.clone())
+        .run()
+        .expect("embedded run");
+
+    assert_eq!(
+        *printed.lock().unwrap(),
+        vec![
+            "out started",
+            "err INFO",
+            "out disk full",
+            "err ERROR",
+            "out slow",
+            "err WARN"
+        ]
+    );
+    assert_eq!(output.lines().len(), 4);
+}
+
+#[test]
+fn test_embedded_run_stops_at_script_exit() {
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let sink = messages.clone();
+    let output = CollectOutput::new();
+    let result = PipelineBuilder::new()
+        .input(Input::lines(input_lines()))
+        .input_format(InputFormat::Json)
+        .exec(r#"if e.level == "ERROR" { exit(3, "stopping at " + e.msg) }"#)
+        .end(r#"print("end ran")"#)
+        .on_print(|line| panic!("unexpected print: {line}"))
+        .on_eprint(move |line| sink.lock().unwrap().push(line.to_string()))
+        .formatter(MessageOnly)
+        .output(output.clone())
+        .run()
+        .expect("exit() is not a run error");
+
+    assert_eq!(result.exit_code, Some(3));
+    assert!(!result.cancelled);
+    // The event that called exit() and everything after it is dropped, the
+    // formatter's tail is still written and the end script is skipped
+    assert_eq!(output.lines(), vec!["started", "-- end --"]);
+    assert_eq!(*messages.lock().unwrap(), vec!["stopping at disk full"]);
+
+    // The process and the thread carry on: the next run starts fresh
+    let result = PipelineBuilder::new()
+        .input(Input::lines(input_lines()))
+        .output(CollectOutput::new())
+        .run()
+        .expect("embedded run");
+    assert_eq!(result.exit_code, None);
+    assert_eq!(result.stats.events_output, 4);
+}
+
+#[test]
+fn test_embedded_run_requires_input_and_output() {
+    let err = PipelineBuilder::new()
+        .output(CollectOutput::new())
+        .run()
+        .unwrap_err();
+    assert!(err.to_string().contains("no input"));
+
+    let err = PipelineBuilder::new()
+        .input(Input::lines(Vec::new()))
+        .run()
+        .unwrap_err();
+    assert!(err.to_string().contains("no output"));
+}
@@ -730,3 +730,435 @@ fn test_missing_input_file_fails_in_parallel() {
         "a file that cannot be opened must fail the run in parallel mode too"
     );
 }
+
+#[test]
+fn test_on_parse_error_sample_shows_distinct_failing_lines() {
+    // Lines 1 and 2 differ only in their numbers, so they share a template and
+    // only the first is sampled; line 4 has a different shape and is kept.
+    let input =
+        "broken entry 17 at 10:01\nbroken entry 42 at 10:02\n{\"a\": 1}\n<html>oops</html>\n";
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--on-parse-error-sample", "5"], input);
+
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Parse errors: 3 total"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("line 1:"), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("    broken entry 17 at 10:01"),
+        "sampled failing line should be shown: {}",
+        stderr
+    );
+    assert!(stderr.contains("line 4:"), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("    <html>oops</html>"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        !stderr.contains("broken entry 42"),
+        "a line with the same template must be deduped: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_on_parse_error_sample_caps_samples_per_category() {
+    let input = "alpha\nbeta gamma\n{delta\n";
+
+    let (_stdout, stderr, _exit_code) =
+        run_kelora_with_input(&["-f", "json", "--on-parse-error-sample", "2"], input);
+
+    assert!(stderr.contains("    alpha"), "stderr: {}", stderr);
+    assert!(stderr.contains("    beta gamma"), "stderr: {}", stderr);
+    assert!(
+        !stderr.contains("    {delta"),
+        "only two samples should be kept: {}",
+        stderr
+    );
+    assert!(stderr.contains("[+1 more."), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_exec_on_error_routes_parse_and_exec_errors() {
+    let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let dlq = temp_dir.path().join("dlq.txt");
+    let script = format!(
+        r#"append_file("{}", error_type + " " + line_num + " " + raw_line)"#,
+        dlq.display()
+    );
+    let input = r#"{"n": 1}
+not json
+{"n": 0}"#;
+
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--allow-fs-writes",
+            "--exec",
+            "e.inv = 10 / e.n",
+            "--exec-on-error",
+            &script,
+        ],
+        input,
+    );
+    assert_eq!(
+        exit_code, 0,
+        "errors routed by the hook are still recovered"
+    );
+    assert_eq!(
+        stdout.trim().lines().count(),
+        2,
+        "valid events still emitted"
+    );
+
+    let routed = std::fs::read_to_string(&dlq).expect("hook should write the DLQ file");
+    assert_eq!(routed, "parse 2 not json\nexec 3 {\"n\": 0}\n");
+}
+
+#[test]
+fn test_exec_on_error_script_failure_is_reported_not_fatal() {
+    let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let script_path = temp_dir.path().join("on_error.rhai");
+    std::fs::write(&script_path, "throw \"hook broke: \" + error_message;").unwrap();
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--exec-on-error-from",
+            script_path.to_str().unwrap(),
+        ],
+        "{\"ok\": true}\nnot json",
+    );
+    assert_eq!(exit_code, 0, "a failing hook must not fail the run");
+    assert!(stdout.contains("ok"), "valid event still emitted: {stdout}");
+    assert!(
+        stderr.contains("exec-on-error"),
+        "hook failure should be reported on stderr: {stderr}"
+    );
+}
+
+const SANDWICH_INPUT: &str = r#"{"id":1,"kind":"ok"}
+{"id":2,"kind":"weird"}
+"#;
+
+fn sandwich_args(policy: &'static str) -> Vec<&'static str> {
+    vec![
+        "-f",
+        "json",
+        "-F",
+        "json",
+        "-e",
+        "e.a = 1",
+        "-e",
+        "if e.kind == \"weird\" { throw \"cannot handle\" } e.b = 2",
+        "-e",
+        "e.c = 3",
+        "--on-stage-error",
+        policy,
+    ]
+}
+
+fn sandwich_events(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_on_stage_error_skip_stage_runs_later_stages() {
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&sandwich_args("skip-stage"), SANDWICH_INPUT);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = sandwich_events(&stdout);
+    assert_eq!(events.len(), 2, "stdout: {}", stdout);
+    assert_eq!(events[0]["b"], 2);
+    assert_eq!(events[1]["a"], 1);
+    assert!(events[1].get("b").is_none());
+    assert_eq!(events[1]["c"], 3, "stage 3 must see the pre-error event");
+}
+
+#[test]
+fn test_on_stage_error_skip_event_drops_event() {
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&sandwich_args("skip-event"), SANDWICH_INPUT);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = sandwich_events(&stdout);
+    assert_eq!(events.len(), 1, "stdout: {}", stdout);
+    assert_eq!(events[0]["id"], 1);
+    assert_eq!(events[0]["c"], 3);
+}
+
+#[test]
+fn test_on_stage_error_abort_stops_the_run() {
+    // A third, valid event after the failing one must not be read at all
+    let input = format!("{}{}", SANDWICH_INPUT, "{\"id\":3,\"kind\":\"ok\"}\n");
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&sandwich_args("abort"), &input);
+    assert_eq!(exit_code, 1, "stderr: {}", stderr);
+    let events = sandwich_events(&stdout);
+    assert_eq!(events.len(), 1, "stdout: {}", stdout);
+    assert_eq!(events[0]["id"], 1);
+}
+
+#[test]
+fn test_on_stage_error_abort_counts_the_error_once() {
+    let mut args = sandwich_args("abort");
+    args.push("--silent");
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(&args, SANDWICH_INPUT);
+    assert_eq!(exit_code, 1, "stderr: {}", stderr);
+    assert!(stderr.contains("1 exec error"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_on_stage_error_abort_filter_stops_the_run() {
+    let input = format!("{}{}", SANDWICH_INPUT, "{\"id\":3,\"kind\":\"ok\"}\n");
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--filter",
+            "e.kind == \"ok\" || e.missing.len() > 0",
+            "--on-stage-error",
+            "abort",
+        ],
+        &input,
+    );
+    assert_eq!(exit_code, 1, "stderr: {}", stderr);
+    let events = sandwich_events(&stdout);
+    assert_eq!(events.len(), 1, "stdout: {}", stdout);
+    assert_eq!(events[0]["id"], 1);
+}
+
+#[test]
+fn test_on_stage_error_abort_rejects_parallel() {
+    let mut args = sandwich_args("abort");
+    args.push("--parallel");
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(&args, SANDWICH_INPUT);
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("--on-stage-error abort is not supported with --parallel"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_on_stage_error_filter_skip_stage_keeps_event() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--filter",
+            "e.kind == \"ok\" || e.missing.len() > 0",
+            "-e",
+            "e.seen = true",
+            "--on-stage-error",
+            "skip-stage",
+        ],
+        SANDWICH_INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = sandwich_events(&stdout);
+    assert_eq!(events.len(), 2, "stdout: {}", stdout);
+    assert_eq!(events[1]["seen"], true);
+}
+
+#[test]
+fn test_stats_count_errors_per_stage() {
+    let mut args = sandwich_args("skip-stage");
+    args.push("--stats");
+    let (stdout, stderr, _exit_code) = run_kelora_with_input(&args, SANDWICH_INPUT);
+    let combined = format!("{}{}", stdout, stderr);
+    assert!(
+        combined.contains("Stage errors: stage 2 (exec)=1"),
+        "output: {}",
+        combined
+    );
+}
+
+#[test]
+fn test_strict_json_rejects_top_level_arrays() {
+    let input = "{\"level\": \"INFO\"}\n[1, 2, 3]\n";
+
+    // Without the flag the array is wrapped into an event
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&["-f", "json", "-F", "json"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, ["{\"level\":\"INFO\"}", "{\"value\":[1,2,3]}"]);
+
+    // With it the array is a parse error, skipped in resilient mode
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--strict-json", "--with-stats"],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.starts_with("{\"level\":\"INFO\"}\n"), "{}", stdout);
+    assert!(!stdout.contains("\"value\""), "{}", stdout);
+    assert!(
+        format!("{}{}", stdout, stderr).contains(" 1 errors"),
+        "stdout: {}\nstderr: {}",
+        stdout,
+        stderr
+    );
+
+    // ...and fatal under --strict
+    let (_stdout, _stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--strict-json", "--strict"], input);
+    assert_ne!(exit_code, 0);
+}
+
+const RUNAWAY_SCRIPT: &str = "let n = 0; loop { n += 1; }";
+
+#[test]
+fn test_begin_timeout_stops_runaway_script_and_continues() {
+    let started = std::time::Instant::now();
+    let (stdout, stderr, _exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--begin",
+            RUNAWAY_SCRIPT,
+            "--begin-timeout-ms",
+            "500",
+            "-k",
+            "msg",
+            "-F",
+            "csvnh",
+        ],
+        "{\"msg\": \"a\"}\n{\"msg\": \"b\"}\n",
+    );
+    assert!(
+        started.elapsed() < std::time::Duration::from_millis(1000),
+        "took {:?}",
+        started.elapsed()
+    );
+    assert!(
+        stderr.contains("--begin script timed out after 500 ms"),
+        "{}",
+        stderr
+    );
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), ["a", "b"]);
+}
+
+#[test]
+fn test_begin_and_end_timeouts_abort_under_strict() {
+    let started = std::time::Instant::now();
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--strict",
+            "--begin",
+            RUNAWAY_SCRIPT,
+            "--begin-timeout-ms",
+            "300",
+        ],
+        "{\"msg\": \"a\"}\n",
+    );
+    assert!(
+        started.elapsed() < std::time::Duration::from_millis(600),
+        "took {:?}",
+        started.elapsed()
+    );
+    assert_ne!(exit_code, 0);
+    assert!(stdout.is_empty(), "{}", stdout);
+    assert!(
+        stderr.contains("--begin script timed out after 300 ms"),
+        "{}",
+        stderr
+    );
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--strict",
+            "--end",
+            RUNAWAY_SCRIPT,
+            "--end-timeout-ms",
+            "300",
+        ],
+        "{\"msg\": \"a\"}\n",
+    );
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("--end script timed out after 300 ms"),
+        "{}",
+        stderr
+    );
+
+    // Scripts that finish in time are unaffected
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--begin",
+            "conf.x = 1",
+            "--begin-timeout-ms",
+            "5000",
+            "-e",
+            "e.x = conf.x",
+            "-F",
+            "json",
+        ],
+        "{\"msg\": \"a\"}\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("\"x\":1"), "{}", stdout);
+}
+
+#[test]
+fn test_script_exit_sets_exit_code_and_stops_the_run() {
+    let input = "a\nb\nc\nd\n";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "line",
+            "-F",
+            "logfmt",
+            "-e",
+            r#"if line == "c" { exit(3, "stopped at c") }"#,
+            "--end",
+            r#"print("end ran")"#,
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 3, "stderr: {}", stderr);
+    // Events before the exit() are written; the rest and --end are skipped
+    assert_eq!(stdout, "line=a\nline=b\n");
+    assert!(stderr.contains("stopped at c"), "{}", stderr);
+
+    let (stdout, _stderr, exit_code) =
+        run_kelora_with_input(&["-f", "line", "--begin", "exit(4)"], input);
+    assert_eq!(exit_code, 4);
+    assert!(stdout.is_empty(), "{}", stdout);
+
+    // Parallel workers stop too, and the process still exits with the code
+    let many: String = (1..=20000).map(|i| format!("{}\n", i)).collect();
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "line",
+            "--parallel",
+            "--batch-size",
+            "10",
+            "-e",
+            r#"if line == "500" { exit(5) }"#,
+        ],
+        &many,
+    );
+    assert_eq!(exit_code, 5, "stderr: {}", stderr);
+}
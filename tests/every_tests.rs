@@ -0,0 +1,175 @@
+mod common;
+use common::*;
+
+// Three full one-minute windows plus a partial fourth one
+const EVENTS: &str = r#"{"ts":"2024-05-01T10:00:05Z","ms":10}
+{"ts":"2024-05-01T10:00:20Z","ms":30}
+{"ts":"2024-05-01T10:00:55Z","ms":20}
+{"ts":"2024-05-01T10:01:10Z","ms":5}
+{"ts":"2024-05-01T10:01:40Z","ms":50}
+{"ts":"2024-05-01T10:02:00Z","ms":7}
+{"ts":"2024-05-01T10:02:59Z","ms":8}
+{"ts":"2024-05-01T10:03:15Z","ms":99}
+"#;
+
+fn objects(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_every_emits_one_summary_per_window() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--every",
+            "1m",
+            "-e",
+            "track_sum(\"reqs\", 1); track_max(\"slowest\", e.ms); skip()",
+        ],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let windows = objects(&stdout);
+    assert_eq!(windows.len(), 4, "stdout: {}", stdout);
+
+    let starts: Vec<_> = windows.iter().map(|w| w["_window_start"].clone()).collect();
+    assert_eq!(
+        starts,
+        vec![
+            "2024-05-01T10:00:00Z",
+            "2024-05-01T10:01:00Z",
+            "2024-05-01T10:02:00Z",
+            "2024-05-01T10:03:00Z"
+        ]
+    );
+    assert_eq!(windows[0]["_window_end"], "2024-05-01T10:01:00Z");
+
+    let reqs: Vec<_> = windows
+        .iter()
+        .map(|w| w["reqs"].as_i64().unwrap())
+        .collect();
+    assert_eq!(reqs, vec![3, 2, 2, 1]);
+    let slowest: Vec<_> = windows
+        .iter()
+        .map(|w| w["slowest"].as_i64().unwrap())
+        .collect();
+    assert_eq!(slowest, vec![30, 50, 8, 99]);
+    assert_eq!(windows[1]["_window_events"], 2);
+}
+
+#[test]
+fn test_every_emit_script_sees_window_metrics() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-q",
+            "--every",
+            "1m",
+            "-e",
+            "track_sum(\"reqs\", 1)",
+            "--every-emit",
+            "print(`${window.start.to_iso()} ${window.events} ${metrics.reqs}`)",
+        ],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "2024-05-01T10:00:00+00:00 3 3",
+            "2024-05-01T10:01:00+00:00 2 2",
+            "2024-05-01T10:02:00+00:00 2 2",
+            "2024-05-01T10:03:00+00:00 1 1",
+        ]
+    );
+}
+
+#[test]
+fn test_every_end_sees_run_totals() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--every",
+            "1m",
+            "-e",
+            "track_sum(\"reqs\", 1); skip()",
+            "--end",
+            "print(`total ${metrics.reqs}`)",
+        ],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5, "stdout: {}", stdout);
+    assert_eq!(lines[4], "total 8");
+}
+
+#[test]
+fn test_every_late_events_and_allowed_lateness() {
+    let input = r#"{"ts":"2024-05-01T10:00:10Z"}
+{"ts":"2024-05-01T10:01:05Z"}
+{"ts":"2024-05-01T10:00:50Z"}
+{"ts":"2024-05-01T10:02:30Z"}
+"#;
+    let args = |lateness: &'static str| {
+        let mut args = vec![
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--every",
+            "1m",
+            "-e",
+            "track_sum(\"reqs\", 1); skip()",
+        ];
+        if !lateness.is_empty() {
+            args.extend(["--every-allowed-lateness", lateness]);
+        }
+        args
+    };
+
+    // Without lateness, 10:00:50 arrives after the first window closed
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&args(""), input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let windows = objects(&stdout);
+    let reqs: Vec<_> = windows
+        .iter()
+        .map(|w| w["reqs"].as_i64().unwrap())
+        .collect();
+    assert_eq!(reqs, vec![1, 1, 1]);
+    assert_eq!(windows[1]["_window_late"], 1);
+
+    // Ten seconds of lateness keeps the first window open for it
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&args("10s"), input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let windows = objects(&stdout);
+    let reqs: Vec<_> = windows
+        .iter()
+        .map(|w| w["reqs"].as_i64().unwrap())
+        .collect();
+    assert_eq!(reqs, vec![2, 1, 1]);
+    assert!(windows.iter().all(|w| w["_window_late"] == 0));
+}
+
+#[test]
+fn test_every_rejects_span_and_bad_duration() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--every", "1m", "--span", "5m"], EVENTS);
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--every", "0s"], EVENTS);
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--every"), "stderr: {}", stderr);
+}
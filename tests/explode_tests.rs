@@ -0,0 +1,125 @@
+mod common;
+use common::*;
+
+fn parse_json_lines(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_explode_array_of_maps_merges_into_parent() {
+    let input = r#"{"order":7,"items":[{"sku":"A1","qty":2},{"sku":"B9","qty":1,"order":99}]}
+{"order":8,"status":"no items"}
+"#;
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-F", "json", "--explode", "items"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let events = parse_json_lines(&stdout);
+    assert_eq!(events.len(), 3, "stdout: {}", stdout);
+    assert_eq!(events[0]["order"], 7);
+    assert_eq!(events[0]["sku"], "A1");
+    assert_eq!(events[0]["qty"], 2);
+    assert!(events[0].get("items").is_none(), "array field is consumed");
+    // Element fields win over parent fields of the same name.
+    assert_eq!(events[1]["order"], 99);
+    assert_eq!(events[1]["sku"], "B9");
+    // Events without the array pass through unchanged.
+    assert_eq!(events[2]["status"], "no items");
+}
+
+#[test]
+fn test_explode_array_of_scalars_uses_named_field() {
+    let input = r#"{"host":"web1","tags":["prod","eu"]}"#;
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-F", "json", "--explode", "tags"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_json_lines(&stdout);
+    assert_eq!(events.len(), 2, "stdout: {}", stdout);
+    assert_eq!(events[0]["tags"], "prod");
+    assert_eq!(events[1]["tags"], "eu");
+    assert_eq!(events[1]["host"], "web1");
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--explode",
+            "tags",
+            "--explode-as",
+            "tag",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_json_lines(&stdout);
+    assert_eq!(events.len(), 2, "stdout: {}", stdout);
+    assert_eq!(events[0]["tag"], "prod");
+    assert!(events[0].get("tags").is_none());
+}
+
+#[test]
+fn test_explode_empty_array_drops_or_keeps_parent() {
+    let input = r#"{"id":1,"items":[]}
+{"id":2,"items":[{"sku":"A1"}]}
+"#;
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-F", "json", "--explode", "items"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_json_lines(&stdout);
+    assert_eq!(events.len(), 1, "stdout: {}", stdout);
+    assert_eq!(events[0]["id"], 2);
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--explode",
+            "items",
+            "--explode-keep-empty",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_json_lines(&stdout);
+    assert_eq!(events.len(), 2, "stdout: {}", stdout);
+    assert_eq!(events[0]["id"], 1);
+    assert_eq!(events[0]["items"], serde_json::json!([]));
+}
+
+#[test]
+fn test_explode_counts_toward_take_and_keys() {
+    let input = r#"{"order":7,"items":[{"sku":"A1"},{"sku":"B2"},{"sku":"C3"}]}"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--explode",
+            "items",
+            "-k",
+            "sku",
+            "--take",
+            "2",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_json_lines(&stdout);
+    assert_eq!(
+        events,
+        vec![
+            serde_json::json!({"sku": "A1"}),
+            serde_json::json!({"sku": "B2"})
+        ]
+    );
+}
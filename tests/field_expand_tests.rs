@@ -0,0 +1,122 @@
+mod common;
+use common::*;
+
+/// nginx access logs written with `log_format ... escape=json`, where the
+/// application logs its JSON payload as the request line.
+const NGINX_JSON_LOGS: &str = r#"{"remote_addr":"10.0.0.5","status":200,"request":"{\"user\":\"alice\",\"action\":\"login\",\"attempt\":1}"}
+{"remote_addr":"10.0.0.6","status":404,"request":"GET /missing HTTP/1.1"}
+{"remote_addr":"10.0.0.7","status":400,"request":"{\"user\":\"bob\",\"action\":"}
+"#;
+
+fn parse_lines(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_field_expand_json_promotes_keys_and_drops_original() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--field-expand-json", "request"],
+        NGINX_JSON_LOGS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events.len(), 3);
+
+    assert_eq!(
+        events[0],
+        serde_json::json!({
+            "remote_addr": "10.0.0.5",
+            "status": 200,
+            "user": "alice",
+            "action": "login",
+            "attempt": 1
+        })
+    );
+    // Plain request lines and malformed JSON are left untouched
+    assert_eq!(events[1]["request"], "GET /missing HTTP/1.1");
+    assert_eq!(events[2]["request"], "{\"user\":\"bob\",\"action\":");
+    assert!(events[2].get("user").is_none());
+}
+
+#[test]
+fn test_field_expand_json_prefix_keep_original_and_scripts_see_fields() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--field-expand-json",
+            "request",
+            "--field-expand-prefix",
+            "req_",
+            "--field-expand-keep-original",
+            "--filter",
+            "e.req_action == \"login\"",
+        ],
+        NGINX_JSON_LOGS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["req_user"], "alice");
+    assert_eq!(events[0]["req_attempt"], 1);
+    assert_eq!(
+        events[0]["request"],
+        "{\"user\":\"alice\",\"action\":\"login\",\"attempt\":1}"
+    );
+}
+
+#[test]
+fn test_field_expand_json_repeatable() {
+    let input = r#"{"a":"{\"x\":1}","b":"{\"y\":{\"z\":true}}"}"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--field-expand-json",
+            "a",
+            "--field-expand-json",
+            "b",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events[0], serde_json::json!({"x": 1, "y": {"z": true}}));
+}
+
+#[test]
+fn test_field_expand_prefix_requires_field_expand_json() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--field-expand-prefix", "req_"], "{}");
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+}
+
+#[test]
+fn test_field_expand_json_collision_first_keeps_existing_fields() {
+    let input = r#"{"user":"proxy","request":"{\"user\":\"alice\",\"action\":\"login\"}"}"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--field-expand-json",
+            "request",
+            "--collision",
+            "first",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        parse_lines(&stdout)[0],
+        serde_json::json!({"user": "proxy", "action": "login"})
+    );
+}
@@ -0,0 +1,108 @@
+mod common;
+use common::*;
+
+use std::fs;
+use tempfile::TempDir;
+
+const PATTERN: &str = r"(?P<env>\w+)-(?P<date>\d{4}-\d{2}-\d{2})";
+
+fn parse_lines(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_field_from_filename_adds_named_captures_per_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let prod = temp_dir.path().join("app-prod-2023-10-11.log");
+    let staging = temp_dir.path().join("app-staging-2023-10-12.log");
+    fs::write(&prod, "{\"msg\":\"started\"}\n{\"msg\":\"ready\"}\n").unwrap();
+    fs::write(&staging, "{\"msg\":\"started\"}\n").unwrap();
+
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "-f",
+        "json",
+        "-F",
+        "json",
+        "--field-from-filename",
+        PATTERN,
+        "--filter",
+        "e.env == \"prod\"",
+        prod.to_str().unwrap(),
+        staging.to_str().unwrap(),
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events.len(), 2, "stdout: {}", stdout);
+    for event in &events {
+        assert_eq!(event["env"], "prod");
+        assert_eq!(event["date"], "2023-10-11");
+    }
+
+    let (stdout, _stderr, exit_code) = run_kelora(&[
+        "-f",
+        "json",
+        "-F",
+        "json",
+        "--field-from-filename",
+        PATTERN,
+        staging.to_str().unwrap(),
+    ]);
+    assert_eq!(exit_code, 0);
+    assert_eq!(
+        parse_lines(&stdout),
+        vec![serde_json::json!({"msg": "started", "env": "staging", "date": "2023-10-12"})]
+    );
+}
+
+#[test]
+fn test_field_from_filename_leaves_stdin_and_unmatched_files_alone() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--field-from-filename", PATTERN],
+        "{\"msg\":\"piped\"}\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        parse_lines(&stdout),
+        vec![serde_json::json!({"msg": "piped"})]
+    );
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let plain = temp_dir.path().join("current.log");
+    fs::write(&plain, "{\"msg\":\"plain\"}\n").unwrap();
+    let (stdout, _stderr, exit_code) = run_kelora(&[
+        "-f",
+        "json",
+        "-F",
+        "json",
+        "--field-from-filename",
+        PATTERN,
+        plain.to_str().unwrap(),
+    ]);
+    assert_eq!(exit_code, 0);
+    assert_eq!(
+        parse_lines(&stdout),
+        vec![serde_json::json!({"msg": "plain"})]
+    );
+}
+
+#[test]
+fn test_field_from_filename_requires_named_groups() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--field-from-filename", r"\d{4}-\d{2}"],
+        "{}\n",
+    );
+    assert_eq!(exit_code, 2);
+    assert!(stderr.contains("no named groups"), "stderr: {}", stderr);
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--field-from-filename", "(?P<env>"], "{}\n");
+    assert_eq!(exit_code, 2);
+    assert!(
+        stderr.contains("Invalid --field-from-filename"),
+        "stderr: {}",
+        stderr
+    );
+}
@@ -0,0 +1,170 @@
+mod common;
+use common::*;
+
+fn parse_json(stdout: &str) -> serde_json::Value {
+    serde_json::from_str(stdout.trim()).expect("valid JSON output")
+}
+
+#[test]
+fn test_field_prefix_drop_renames_log_level_to_level() {
+    let input = r#"{"log_level":"ERROR","log_msg":"boom","host":"web1"}
+{"log_level":"INFO","log_msg":"ok","host":"web1"}
+"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--field-prefix-drop",
+            "log_",
+            "-l",
+            "error",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let event = parse_json(&stdout);
+    assert_eq!(
+        event,
+        serde_json::json!({"level": "ERROR", "msg": "boom", "host": "web1"}),
+        "level filtering sees the renamed field"
+    );
+}
+
+#[test]
+fn test_field_prefix_drop_multiple_prefixes_and_scripts_see_short_names() {
+    let input = r#"{"app_user":"alice","k8s_pod":"api-1"}"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--field-prefix-drop",
+            "app_",
+            "--field-prefix-drop",
+            "k8s_",
+            "-e",
+            "e.who = e.user + \"@\" + e.pod",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let event = parse_json(&stdout);
+    assert_eq!(event["user"], "alice");
+    assert_eq!(event["pod"], "api-1");
+    assert_eq!(event["who"], "alice@api-1");
+}
+
+#[test]
+fn test_field_prefix_conflict_modes() {
+    let input = r#"{"level":"raw","log_level":"WARN"}"#;
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--field-prefix-drop", "log_"],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(parse_json(&stdout), serde_json::json!({"level": "WARN"}));
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--field-prefix-drop",
+            "log_",
+            "--field-prefix-conflict",
+            "skip",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        parse_json(&stdout),
+        serde_json::json!({"level": "raw", "log_level": "WARN"})
+    );
+}
+
+#[test]
+fn test_field_prefix_drop_collision_suffix_and_error() {
+    let input = r#"{"level":"raw","level_2":"taken","log_level":"WARN"}"#;
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--field-prefix-drop",
+            "log_",
+            "--rename-on-collision",
+            "suffix",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        parse_json(&stdout),
+        serde_json::json!({"level": "raw", "level_2": "taken", "level_3": "WARN"})
+    );
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--field-prefix-drop",
+            "log_",
+            "--collision",
+            "error",
+            "--strict",
+        ],
+        input,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("field 'level' already exists (--collision error)"),
+        "stderr: {}",
+        stderr
+    );
+
+    // --field-prefix-conflict is the older spelling of --collision; giving
+    // both is rejected
+    let (_stdout, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "--field-prefix-drop",
+            "log_",
+            "--field-prefix-conflict",
+            "skip",
+            "--collision",
+            "first",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 2);
+}
+
+#[test]
+fn test_field_prefix_add_prefixes_output_fields_after_keys() {
+    let input = r#"{"level":"INFO","msg":"hi","host":"web1"}"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "-k",
+            "level,msg",
+            "--field-prefix-add",
+            "app_",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        parse_json(&stdout),
+        serde_json::json!({"app_level": "INFO", "app_msg": "hi"})
+    );
+}
@@ -532,3 +532,69 @@ fn test_output_file_with_extension_does_not_warn() {
         stderr
     );
 }
+
+#[test]
+fn test_sandbox_denies_file_writes_even_with_allow_fs_writes() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_file = temp_dir.path().join("output.txt");
+    let output_path = output_file.to_str().unwrap();
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--sandbox",
+            "--allow-fs-writes",
+            "--strict",
+            "--exec",
+            &format!(r#"append_file("{}", e.message);"#, output_path),
+        ],
+        r#"{"message": "test"}"#,
+    );
+
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("append_file() is disabled by --sandbox"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(!output_file.exists());
+}
+
+#[test]
+fn test_sandbox_caps_runaway_scripts_and_keeps_computation() {
+    let input = "{\"n\": 2}\n";
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--sandbox",
+            "-F",
+            "json",
+            "--exec",
+            "e.double = e.n * 2; track_inc(\"seen\");",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.trim(), r#"{"n":2,"double":4}"#);
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--sandbox",
+            "--strict",
+            "--exec",
+            "let i = 0; loop { i += 1; }",
+        ],
+        input,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("exceeded the --sandbox limit of 1000000 operations"),
+        "stderr: {}",
+        stderr
+    );
+}
@@ -0,0 +1,122 @@
+mod common;
+use common::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Twenty-four hourly files, `app-2024-01-10-HH.log`, each holding two events
+/// from its own hour
+fn hourly_files(dir: &TempDir) -> Vec<String> {
+    (0..24)
+        .map(|hour| {
+            let path = dir.path().join(format!("app-2024-01-10-{:02}.log", hour));
+            let content = format!(
+                "{{\"ts\":\"2024-01-10T{hour:02}:10:00Z\",\"hour\":{hour}}}\n\
+                 {{\"ts\":\"2024-01-10T{hour:02}:50:00Z\",\"hour\":{hour}}}\n"
+            );
+            fs::write(&path, content).unwrap();
+            path.to_str().unwrap().to_string()
+        })
+        .collect()
+}
+
+fn window_args<'a>(pattern: &'a str, extra: &[&'a str], files: &'a [String]) -> Vec<&'a str> {
+    let mut args = vec![
+        "-f",
+        "json",
+        "--input-tz",
+        "UTC",
+        "--since",
+        "2024-01-10T10:00:00Z",
+        "--until",
+        "2024-01-10T11:59:59Z",
+        "--filename-ts-format",
+        pattern,
+    ];
+    args.extend_from_slice(extra);
+    args.extend(files.iter().map(String::as_str));
+    args
+}
+
+#[test]
+fn test_filename_ts_format_skips_files_outside_window() {
+    let dir = TempDir::new().unwrap();
+    let files = hourly_files(&dir);
+
+    let (stdout, stderr, exit_code) = run_kelora(&window_args(
+        "app-%Y-%m-%d-%H.log*",
+        &["--stats=json"],
+        &files,
+    ));
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let stats: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    // Hours 10-11 plus one file of margin on each side
+    assert_eq!(stats["lines"]["read"], 8, "stats: {}", stdout);
+    let skipped = stats["files"]["skipped_by_name"].as_array().unwrap();
+    assert_eq!(skipped.len(), 20);
+    for hour in 9..=12 {
+        let name = format!("app-2024-01-10-{:02}.log", hour);
+        assert!(
+            !skipped.iter().any(|f| f.as_str().unwrap().ends_with(&name)),
+            "{} must be read",
+            name
+        );
+    }
+
+    let (stdout, stderr, exit_code) = run_kelora(&window_args(
+        "auto",
+        &["-F", "json", "--with-stats", "-v"],
+        &files,
+    ));
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let hours: Vec<i64> = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|event| event["hour"].as_i64())
+        .collect();
+    assert_eq!(hours, [10, 10, 11, 11]);
+    let report = format!("{}{}", stdout, stderr);
+    assert!(
+        report.contains("Files skipped by name: 20 outside --since/--until"),
+        "{}",
+        report
+    );
+    assert!(report.contains("app-2024-01-10-00.log"), "{}", report);
+}
+
+#[test]
+fn test_filename_ts_format_reads_everything_when_a_name_does_not_match() {
+    let dir = TempDir::new().unwrap();
+    let mut files = hourly_files(&dir);
+    let current = dir.path().join("app-current.log");
+    fs::write(&current, "{\"ts\":\"2024-01-10T11:30:00Z\",\"hour\":11}\n").unwrap();
+    files.push(current.to_str().unwrap().to_string());
+
+    let (stdout, stderr, exit_code) = run_kelora(&window_args(
+        "app-%Y-%m-%d-%H.log*",
+        &["--stats=json"],
+        &files,
+    ));
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let stats: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(stats["lines"]["read"], 49);
+    assert!(stats["files"].get("skipped_by_name").is_none());
+}
+
+#[test]
+fn test_filename_ts_format_requires_time_window() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["--filename-ts-format", "auto"], "{}\n");
+    assert_eq!(exit_code, 2);
+    assert!(stderr.contains("--filename-ts-format requires --since or --until"));
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["--since", "1h", "--filename-ts-format", "app-%Q.log"],
+        "{}\n",
+    );
+    assert_eq!(exit_code, 2);
+    assert!(
+        stderr.contains("Invalid --filename-ts-format"),
+        "{}",
+        stderr
+    );
+}
@@ -425,21 +425,32 @@ fn test_exclude_keys_typo_hints_silent_redaction_failure() {
 }
 
 #[test]
-fn test_keys_nested_map_path_points_to_get_path() {
-    // A dotted path copied from --discover (a value nested in a map) can't be
-    // selected by -k; the hint should point at get_path, not guess the parent.
+fn test_keys_missing_nested_path_names_its_present_parent() {
+    // -k and --exclude-keys resolve dotted paths; one that matched nothing
+    // below a present field should say so, not guess the bare parent.
     let input = r#"{"user": {"name": "alice"}}"#;
 
-    let (_stdout, stderr, exit_code) =
+    let (stdout, stderr, exit_code) =
         run_kelora_with_input(&["-f", "json", "-k", "user.name"], input);
+    assert_eq!(exit_code, 0);
+    assert!(stdout.contains("alice"), "stdout: {stdout}");
+    assert!(!stderr.contains("never present"), "stderr: {stderr}");
 
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-k", "user.nmae"], input);
     assert_eq!(exit_code, 0);
     assert!(
-        stderr.contains("'user' is present")
-            && stderr.contains("get_path(\"user.name\")")
+        stderr.contains("'user' is present, but no event had a value at 'user.nmae'")
             && !stderr.contains("Did you mean 'user'?"),
-        "nested map path should suggest get_path, not the bare parent: {stderr}"
+        "nested map path should name the present parent, not guess it: {stderr}"
     );
+
+    // A removed nested value leaves no field behind, so it is never flagged
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--exclude-keys", "user.name"], input);
+    assert_eq!(exit_code, 0);
+    assert!(!stdout.contains("alice"), "stdout: {stdout}");
+    assert!(!stderr.contains("never present"), "stderr: {stderr}");
 }
 
 #[test]
@@ -983,3 +994,98 @@ fn test_exec_before_levels_observes_all_events() {
         exec_metric_line.trim()
     );
 }
+
+#[test]
+fn test_repeated_ignore_lines_and_pattern_file() {
+    let dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let patterns = dir.path().join("noise.txt");
+    std::fs::write(
+        &patterns,
+        "# health probes\nhealthz\n\n  # metrics scrapes\n/metrics\n",
+    )
+    .unwrap();
+
+    let input = "INFO GET /api/users\nDEBUG cache warm\nINFO GET /healthz\nINFO GET /metrics\nWARN slow query\n";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "line",
+            "--ignore-lines",
+            "^DEBUG",
+            "--ignore-lines-file",
+            patterns.to_str().unwrap(),
+            "--with-stats",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let lines: Vec<&str> = stdout.trim().lines().collect();
+    assert_eq!(lines.len(), 2, "stdout: {}", stdout);
+    assert!(lines[0].contains("/api/users"));
+    assert!(lines[1].contains("slow query"));
+
+    let stats = extract_stats_lines(&stderr);
+    assert!(
+        stats
+            .iter()
+            .any(|l| l == "Line filters: 0 dropped by --keep-lines, 3 dropped by --ignore-lines"),
+        "stats: {:?}",
+        stats
+    );
+}
+
+#[test]
+fn test_ignore_lines_wins_over_keep_lines() {
+    let input = "ERROR disk full\nERROR healthz probe failed\nINFO started\n";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "line",
+            "--keep-lines",
+            "ERROR",
+            "--keep-lines",
+            "WARN",
+            "--ignore-lines",
+            "healthz",
+            "--with-stats",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let lines: Vec<&str> = stdout.trim().lines().collect();
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    assert!(lines[0].contains("disk full"));
+
+    let stats = extract_stats_lines(&stderr);
+    assert!(
+        stats
+            .iter()
+            .any(|l| l == "Line filters: 1 dropped by --keep-lines, 1 dropped by --ignore-lines"),
+        "stats: {:?}",
+        stats
+    );
+}
+
+#[test]
+fn test_invalid_pattern_in_file_reports_location() {
+    let dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let patterns = dir.path().join("keep.txt");
+    std::fs::write(&patterns, "ERROR\n# comment\n[unclosed\n").unwrap();
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "line",
+            "--keep-lines-file",
+            patterns.to_str().unwrap(),
+        ],
+        "ERROR boom\n",
+    );
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stderr.contains("[unclosed"), "stderr: {}", stderr);
+    assert!(
+        stderr.contains(&format!("{}:3", patterns.display())),
+        "stderr: {}",
+        stderr
+    );
+}
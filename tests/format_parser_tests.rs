@@ -274,6 +274,116 @@ fn test_apache_status_code_analysis() {
     assert!(stdout.contains("5xx: 1"), "Should have 1 server error");
 }
 
+const HAPROXY_SAMPLE: &str = r#"Feb  6 12:14:14 lb01 haproxy[14389]: 10.0.1.2:33317 [06/Feb/2024:12:14:14.655] http-in~ static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 "GET /index.html HTTP/1.1"
+Feb  6 12:14:15 lb01 haproxy[14389]: 10.0.1.9:40122 [06/Feb/2024:12:14:15.001] http-in api/<NOSRV> 0/-1/-1/-1/2 503 212 - - SC-- 2/2/0/0/0 0/0 "POST /v1/orders HTTP/1.1"
+Feb  6 12:14:15 lb01 haproxy[14389]: 10.0.1.2:33320 [06/Feb/2024:12:14:15.123] tcp-in mysql/db1 0/0/5007 1230 -- 1/1/1/1/0 0/0"#;
+
+#[test]
+fn test_haproxy_http_and_tcp_parsing() {
+    let (stdout, _stderr, exit_code) =
+        run_kelora_with_input(&["-f", "haproxy", "-F", "json"], HAPROXY_SAMPLE);
+    assert_eq!(exit_code, 0, "HAProxy parsing should succeed");
+
+    let lines: Vec<serde_json::Value> = stdout
+        .trim()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON"))
+        .collect();
+    assert_eq!(lines.len(), 3, "Should parse 3 HAProxy log lines");
+
+    let http = &lines[0];
+    assert_eq!(http["client_ip"], "10.0.1.2");
+    assert_eq!(http["accept_date"], "06/Feb/2024:12:14:14.655");
+    assert_eq!(http["frontend"], "http-in");
+    assert_eq!(http["backend"], "static");
+    assert_eq!(http["server"], "srv1");
+    assert_eq!(http["Tt"], 109);
+    assert_eq!(http["status_code"], 200);
+    assert_eq!(http["bytes_read"], 2750);
+    assert_eq!(http["path"], "/index.html");
+
+    assert_eq!(lines[1]["Tw"], -1);
+    assert_eq!(lines[1]["termination_state"], "SC--");
+
+    let tcp = &lines[2];
+    assert_eq!(tcp["backend"], "mysql");
+    assert_eq!(tcp["Tt"], 5007);
+    assert_eq!(tcp["bytes_read"], 1230);
+    assert!(tcp.get("status_code").is_none());
+}
+
+#[test]
+fn test_haproxy_auto_detected_ahead_of_syslog() {
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "--filter",
+            r#"e.has("status_code") && e.status_code >= 500"#,
+            "-k",
+            "server,status_code",
+            "-F",
+            "logfmt",
+        ],
+        HAPROXY_SAMPLE,
+    );
+    assert_eq!(
+        exit_code, 0,
+        "auto-detected HAProxy filtering should succeed"
+    );
+    let lines: Vec<&str> = stdout.trim().lines().collect();
+    assert_eq!(lines.len(), 1, "only the 503 should pass: {}", stdout);
+    assert!(lines[0].contains("status_code=503"), "{}", lines[0]);
+}
+
+const LEEF_SAMPLE: &str = "LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=192.0.2.0\tdst=172.50.123.1\tsev=5\tusrName=alice
+Jan 18 11:07:53 fw01 LEEF:2.0|Acme|Fire\\|Wall|2.1|deny|^|src=10.0.0.1^dst=10.0.0.9^sev=8";
+
+#[test]
+fn test_leef_1_and_2_parsing() {
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "leef", "-F", "json"], LEEF_SAMPLE);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let lines: Vec<serde_json::Value> = stdout
+        .trim()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON"))
+        .collect();
+    assert_eq!(lines.len(), 2, "Should parse 2 LEEF lines");
+
+    assert_eq!(lines[0]["leefver"], "1.0");
+    assert_eq!(lines[0]["vendor"], "Microsoft");
+    assert_eq!(lines[0]["version"], "4.0 SP1");
+    assert_eq!(lines[0]["event_id"], "15345");
+    assert_eq!(lines[0]["usrName"], "alice");
+    assert_eq!(lines[0]["sev"], 5);
+
+    assert_eq!(lines[1]["host"], "fw01");
+    assert_eq!(lines[1]["product"], "Fire|Wall");
+    assert_eq!(lines[1]["dst"], "10.0.0.9");
+    assert_eq!(lines[1]["sev"], 8);
+}
+
+#[test]
+fn test_leef_auto_detected_and_round_trips_with_to_leef() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["--filter", "e.sev > 6", "-e", "print(e.to_leef())", "-q"],
+        LEEF_SAMPLE,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let line = stdout.trim();
+    assert!(
+        line.starts_with("LEEF:1.0|Acme|Fire\\|Wall|2.1|deny|"),
+        "{}",
+        line
+    );
+    assert!(line.contains("dst=10.0.0.9\t"), "{}", line);
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "leef", "-F", "json", "-k", "product,sev"], line);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.trim(), r#"{"product":"Fire|Wall","sev":8}"#);
+}
+
 #[test]
 fn test_per_file_csv_schema_detection_sequential() {
     // Test per-file CSV schema detection in sequential mode
@@ -512,3 +622,101 @@ fn test_logfmt_inf_nan_stay_strings() {
     assert_eq!(event["b"].as_str().unwrap(), "nan");
     assert_eq!(event["c"].as_str().unwrap(), "Infinity");
 }
+
+#[test]
+fn test_keep_rest_across_structured_parsers() {
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "logfmt", "--keep-rest", "-F", "json"],
+        "level=info worker 3 ready pid=7\nlevel=warn count=2",
+    );
+    assert_eq!(exit_code, 0, "logfmt with --keep-rest should succeed");
+    let events: Vec<serde_json::Value> = stdout
+        .trim()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON"))
+        .collect();
+    assert_eq!(events[0]["_rest"].as_str().unwrap(), "worker 3 ready");
+    assert_eq!(events[0]["pid"].as_i64().unwrap(), 7);
+    assert!(
+        events[1].get("_rest").is_none(),
+        "empty remainder is omitted"
+    );
+
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            r"regex:(?P<code:int>\d+) (?P<verb>[A-Z]+)",
+            "--keep-rest",
+            "-F",
+            "json",
+        ],
+        "200 GET /index.html  ",
+    );
+    assert_eq!(exit_code, 0, "regex with --keep-rest should succeed");
+    let event: serde_json::Value = serde_json::from_str(stdout.trim()).expect("valid JSON");
+    assert_eq!(event["_rest"].as_str().unwrap(), "/index.html");
+
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "cols:date level", "--keep-rest", "-F", "json"],
+        "2024-01-01 ERROR disk  full",
+    );
+    assert_eq!(exit_code, 0, "cols with --keep-rest should succeed");
+    let event: serde_json::Value = serde_json::from_str(stdout.trim()).expect("valid JSON");
+    assert_eq!(event["_rest"].as_str().unwrap(), "disk  full");
+}
+
+#[test]
+fn test_json_big_integers_survive_round_trip() {
+    let input = r#"{"id":1234567890123456789,"trace":18446744073709551615,"ratio":0.25}"#;
+
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(&["-f", "json", "-F", "json"], input);
+    assert_eq!(exit_code, 0, "json round trip should succeed");
+    assert!(
+        stdout.contains(r#""id":1234567890123456789"#),
+        "19-digit id must be emitted exactly: {stdout}"
+    );
+    assert!(
+        stdout.contains(r#""trace":18446744073709551615"#),
+        "u64 beyond i64::MAX must stay a number: {stdout}"
+    );
+
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--json-numbers-as-strings", "-F", "json"],
+        input,
+    );
+    assert_eq!(exit_code, 0, "--json-numbers-as-strings should succeed");
+    let event: serde_json::Value = serde_json::from_str(stdout.trim()).expect("valid JSON");
+    assert_eq!(event["id"].as_str().unwrap(), "1234567890123456789");
+    assert_eq!(event["trace"].as_str().unwrap(), "18446744073709551615");
+    assert_eq!(event["ratio"].as_str().unwrap(), "0.25");
+}
+
+#[test]
+fn test_json_precise_numbers_round_trip_verbatim() {
+    let input = r#"{"span_id":9223372036854775808,"amount":0.123456789012345678,"big":[-99999999999999999999,12345678901234567890.5],"ratio":0.25}"#;
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&["-f", "json", "-F", "json"], input);
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+    assert_eq!(stdout.trim(), input);
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--filter",
+            r#"e.span_id == "9223372036854775808""#,
+            "--exec",
+            "e.kind = type_of(e.amount); e.text = e.amount.to_string()",
+            "-F",
+            "json",
+            "--keys",
+            "kind,text",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+    assert_eq!(
+        stdout.trim(),
+        r#"{"kind":"JsonNumber","text":"0.123456789012345678"}"#
+    );
+}
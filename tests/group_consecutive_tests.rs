@@ -0,0 +1,150 @@
+mod common;
+use common::*;
+
+const INPUT: &str = r#"{"ts":"2024-01-15T10:00:00Z","service":"api","level":"info","bytes":100}
+{"ts":"2024-01-15T10:00:05Z","service":"api","level":"info","bytes":300}
+{"ts":"2024-01-15T10:00:09Z","service":"api","level":"warn","bytes":200}
+{"ts":"2024-01-15T10:01:00Z","service":"db","level":"info","bytes":50}
+{"ts":"2024-01-15T10:02:00Z","service":"api","level":"info","bytes":10}
+"#;
+
+fn objects(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_group_consecutive_by_merges_runs_and_reports_varied_fields() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--group-consecutive-by",
+            "service",
+        ],
+        INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let events = objects(&stdout);
+    assert_eq!(events.len(), 3, "stdout: {}", stdout);
+
+    let first = &events[0];
+    assert_eq!(first["service"], "api");
+    assert_eq!(first["level"], "info");
+    assert_eq!(first["bytes"], 100);
+    assert_eq!(first["_group_count"], 3);
+    assert_eq!(first["_group_start_ts"], "2024-01-15T10:00:00Z");
+    assert_eq!(first["_group_end_ts"], "2024-01-15T10:00:09Z");
+    assert_eq!(
+        first["_group_fields_varied"],
+        serde_json::json!(["ts", "bytes", "level"])
+    );
+
+    assert_eq!(events[1]["service"], "db");
+    assert_eq!(events[1]["_group_count"], 1);
+    assert_eq!(events[1]["_group_fields_varied"], serde_json::json!([]));
+
+    // The last run is written once input ends
+    assert_eq!(events[2]["service"], "api");
+    assert_eq!(events[2]["_group_count"], 1);
+    assert_eq!(events[2]["_group_start_ts"], "2024-01-15T10:02:00Z");
+}
+
+#[test]
+fn test_group_aggregate_numeric_combines_numeric_fields() {
+    for (func, expected) in [
+        ("sum", 600.0),
+        ("avg", 200.0),
+        ("min", 100.0),
+        ("max", 300.0),
+    ] {
+        let (stdout, stderr, exit_code) = run_kelora_with_input(
+            &[
+                "-f",
+                "json",
+                "-F",
+                "json",
+                "--group-consecutive-by",
+                "service",
+                "--group-aggregate-numeric",
+                func,
+                "--take",
+                "1",
+            ],
+            INPUT,
+        );
+        assert_eq!(exit_code, 0, "stderr: {}", stderr);
+        let events = objects(&stdout);
+        assert_eq!(events.len(), 1, "stdout: {}", stdout);
+        assert_eq!(
+            events[0]["bytes"].as_f64(),
+            Some(expected),
+            "func: {}",
+            func
+        );
+        assert_eq!(events[0]["_group_count"], 3);
+    }
+}
+
+#[test]
+fn test_group_consecutive_by_runs_after_filters_and_passes_events_without_field() {
+    let input = r#"{"service":"api","n":1}
+{"service":"db","n":2}
+{"service":"api","n":3}
+{"n":4}
+{"service":"api","n":5}
+"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--filter",
+            "e.n != 2",
+            "--group-consecutive-by",
+            "service",
+            "-k",
+            "n,_group_count",
+            "-F",
+            "csvnh",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    // The db event (n=2) is filtered out first, so both api events form one run;
+    // the event without a service ends it
+    assert_eq!(stdout.trim(), "1,2\n4,\n5,1");
+}
+
+#[test]
+fn test_group_consecutive_by_rejects_parallel_and_lone_aggregate() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--group-consecutive-by",
+            "service",
+            "--parallel",
+        ],
+        INPUT,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("--group-consecutive-by"),
+        "stderr: {}",
+        stderr
+    );
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--group-aggregate-numeric", "sum"], INPUT);
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("--group-consecutive-by"),
+        "stderr: {}",
+        stderr
+    );
+}
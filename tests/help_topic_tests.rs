@@ -148,6 +148,36 @@ fn test_help_functions_keyword_no_match() {
     assert!(stdout.contains("No functions matching \"nonexistentxyz\""));
 }
 
+#[test]
+fn test_help_function_shows_signatures_and_examples() {
+    let (stdout, _stderr, exit_code) = run_kelora(&["--help-function", "extract_regex"]);
+    assert_eq!(exit_code, 0, "--help-function should exit successfully");
+    assert!(stdout.contains("extract_regex - STRING FUNCTIONS"));
+    assert!(stdout.contains("text.extract_regex(pattern [,group])"));
+    assert!(stdout.contains("Examples:"));
+    assert!(stdout.contains(".extract_regex(r\""));
+
+    let (stdout, _stderr, exit_code) = run_kelora(&["--help-function=extract_re"]);
+    assert_eq!(exit_code, 0);
+    assert!(stdout.contains("No function named \"extract_re\"."));
+    assert!(stdout.contains("extract_regex"));
+}
+
+#[test]
+fn test_help_functions_json() {
+    let (stdout, _stderr, exit_code) = run_kelora(&["--help-functions", "mask_ip", "--json"]);
+    assert_eq!(
+        exit_code, 0,
+        "--help-functions --json should exit successfully"
+    );
+    let docs: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let docs = docs.as_array().unwrap();
+    assert_eq!(docs.len(), 1);
+    assert_eq!(docs[0]["name"], "mask_ip");
+    assert_eq!(docs[0]["category"], "STRING FUNCTIONS");
+    assert_eq!(docs[0]["signatures"][0], "text.mask_ip([octets])");
+}
+
 #[test]
 fn test_help_keyword_filters_cli_reference() {
     let (stdout, _stderr, exit_code) = run_kelora(&["--help", "since"]);
@@ -0,0 +1,88 @@
+#![cfg(feature = "output-http")]
+
+mod common;
+use common::*;
+
+use mockito::Matcher;
+
+const INPUT: &str = r#"{"n": 1}
+{"n": 2}
+{"n": 3}
+"#;
+
+#[test]
+fn test_output_http_posts_batched_json_arrays() {
+    let mut server = mockito::Server::new();
+    let full = server
+        .mock("POST", "/ingest")
+        .match_header("content-type", "application/json")
+        .match_body(Matcher::JsonString(r#"[{"n":1},{"n":2}]"#.to_string()))
+        .with_status(200)
+        .expect(1)
+        .create();
+    let last = server
+        .mock("POST", "/ingest")
+        .match_body(Matcher::JsonString(r#"[{"n":3}]"#.to_string()))
+        .with_status(202)
+        .expect(1)
+        .create();
+
+    let url = format!("{}/ingest", server.url());
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--output-http", &url, "--chunk-size", "2"],
+        INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.trim().is_empty(), "stdout: {}", stdout);
+    full.assert();
+    last.assert();
+}
+
+#[test]
+fn test_output_http_retries_on_503() {
+    let mut server = mockito::Server::new();
+    let unavailable = server
+        .mock("POST", "/ingest")
+        .with_status(503)
+        .expect(1)
+        .create();
+    let accepted = server
+        .mock("POST", "/ingest")
+        .match_body(Matcher::JsonString(
+            r#"[{"n":1},{"n":2},{"n":3}]"#.to_string(),
+        ))
+        .with_status(200)
+        .expect(1)
+        .create();
+
+    let url = format!("{}/ingest", server.url());
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--output-http", &url], INPUT);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    unavailable.assert();
+    accepted.assert();
+}
+
+#[test]
+fn test_output_http_delivery_failure_sets_exit_code() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/ingest")
+        .with_status(413)
+        .with_body("payload too large")
+        .create();
+
+    let url = format!("{}/ingest", server.url());
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--output-http", &url], INPUT);
+    assert_eq!(exit_code, 1, "stderr: {}", stderr);
+    assert!(stderr.contains("payload too large"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_output_http_rejects_non_http_url() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--output-http", "collector:8080"], INPUT);
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stderr.contains("http:// or https://"), "stderr: {}", stderr);
+}
@@ -0,0 +1,201 @@
+// --input-cmd spawns shell commands, so these tests rely on a POSIX sh
+#![cfg(unix)]
+
+mod common;
+use common::*;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// Whether `pid` is still running (a zombie waiting to be reaped counts as gone)
+fn process_alive(pid: u32) -> bool {
+    match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+        Ok(stat) => !stat
+            .rsplit_once(')')
+            .is_some_and(|(_, rest)| rest.trim_start().starts_with('Z')),
+        Err(_) => false,
+    }
+}
+
+/// Wait up to two seconds for `pid` to exit
+fn wait_for_exit(pid: u32) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline {
+        if !process_alive(pid) {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    false
+}
+
+/// Pids the test commands wrote to `path`, one per line
+fn read_pids(path: &std::path::Path) -> Vec<u32> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .map(|line| line.trim().parse().expect("pid"))
+        .collect()
+}
+
+#[test]
+fn test_input_cmd_reads_command_output_with_command_as_filename() {
+    let first = "printf '{\"n\":1}\\n{\"n\":2}\\n'";
+    let second = "echo '{\"n\":3}'";
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "-f",
+        "json",
+        "--input-cmd",
+        first,
+        "--input-cmd",
+        second,
+        "-e",
+        "e.source = meta.filename",
+        "-F",
+        "json",
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    // The two commands run concurrently, so only per-command order is fixed
+    let mut events: Vec<(i64, String)> = stdout
+        .lines()
+        .map(|line| {
+            let event: serde_json::Value = serde_json::from_str(line).expect("valid JSON output");
+            (
+                event["n"].as_i64().unwrap(),
+                event["source"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    events.sort();
+    assert_eq!(
+        events,
+        vec![
+            (1, first.to_string()),
+            (2, first.to_string()),
+            (3, second.to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_input_cmd_detects_format_and_streams_lines() {
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "--input-cmd",
+        "for i in 1 2 3; do echo \"{\\\"level\\\":\\\"info\\\",\\\"i\\\":$i}\"; sleep 0.05; done",
+        "-k",
+        "i",
+        "-F",
+        "csvnh",
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "1\n2\n3");
+}
+
+#[test]
+fn test_input_cmd_stderr_is_surfaced_as_warnings() {
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "--input-cmd",
+        "echo hello; echo 'disk almost full' >&2; exit 3",
+        "--with-stats",
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("hello"), "stdout: {}", stdout);
+    assert!(
+        stderr.contains("disk almost full") && stderr.contains("input-cmd"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("exit status: 3"), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Input commands wrote 1 line to stderr"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_input_cmd_follow_restarts_exited_commands() {
+    let dir = TempDir::new().unwrap();
+    let pids = dir.path().join("pids");
+    let command = format!("echo $$ >> {}; echo tick", pids.display());
+
+    let (stdout, stderr, exit_code) =
+        run_kelora(&["--input-cmd", &command, "--follow", "--take", "3"]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        stdout.lines().filter(|line| line.contains("tick")).count(),
+        3
+    );
+    assert!(stderr.contains("restarting in 250ms"), "stderr: {}", stderr);
+    assert!(stderr.contains("restarting in 500ms"), "stderr: {}", stderr);
+    assert!(read_pids(&pids).len() >= 3);
+}
+
+#[test]
+fn test_input_cmd_stops_running_command_when_take_is_reached() {
+    let dir = TempDir::new().unwrap();
+    let pids = dir.path().join("pids");
+    let command = format!(
+        "echo $$ > {}; while true; do echo tick; sleep 0.05; done",
+        pids.display()
+    );
+
+    let (stdout, stderr, exit_code) =
+        run_kelora(&["--input-cmd", &command, "--follow", "--take", "2"]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.lines().count(), 2);
+
+    let pids = read_pids(&pids);
+    assert_eq!(pids.len(), 1);
+    assert!(wait_for_exit(pids[0]), "command {} still running", pids[0]);
+}
+
+#[test]
+fn test_input_cmd_passes_sigint_on_to_commands() {
+    let dir = TempDir::new().unwrap();
+    let pids = dir.path().join("pids");
+    let command = format!(
+        "echo $$ > {}; while true; do echo tick; sleep 0.05; done",
+        pids.display()
+    );
+
+    let child = Command::new(env!("CARGO_BIN_EXE_kelora"))
+        .env("LLVM_PROFILE_FILE", "/dev/null")
+        .args(["--input-cmd", &command, "--follow"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn kelora");
+
+    thread::sleep(Duration::from_millis(500));
+    Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .expect("Failed to send SIGINT");
+
+    let output = child.wait_with_output().expect("kelora exits");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("tick"), "stdout: {}", stdout);
+
+    let pids = read_pids(&pids);
+    assert_eq!(pids.len(), 1);
+    assert!(wait_for_exit(pids[0]), "command {} still running", pids[0]);
+}
+
+#[test]
+fn test_input_cmd_rejects_conflicting_inputs() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["--input-cmd", "echo hi", "--parallel"], "");
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--input-cmd"), "stderr: {}", stderr);
+
+    let (_stdout, stderr, exit_code) = run_kelora(&["--input-cmd", "echo hi", "--no-input"]);
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--input-cmd"), "stderr: {}", stderr);
+
+    let (_stdout, stderr, exit_code) = run_kelora(&["--follow", "--no-input"]);
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--input-cmd"), "stderr: {}", stderr);
+}
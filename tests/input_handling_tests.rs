@@ -981,3 +981,60 @@ fn test_no_diagnostics_suppresses_no_input_hint() {
         stderr
     );
 }
+
+#[test]
+fn test_follow_glob_picks_up_new_matching_file() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    std::fs::write(dir.path().join("app-1.log"), "first-1\nfirst-2\n").unwrap();
+    std::fs::write(dir.path().join("other.log"), "not-followed\n").unwrap();
+    let pattern = dir.path().join("app-*.log").to_string_lossy().to_string();
+
+    // --take bounds the otherwise endless follow: 2 existing lines + 2 new ones
+    let child = Command::new(env!("CARGO_BIN_EXE_kelora"))
+        .args([
+            "--follow-glob",
+            &pattern,
+            "-f",
+            "line",
+            "-F",
+            "json",
+            "--take",
+            "4",
+        ])
+        .env("LLVM_PROFILE_FILE", "/dev/null")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start kelora");
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    std::fs::write(dir.path().join("app-2.log"), "second-1\nsecond-2\n").unwrap();
+
+    let output = child.wait_with_output().expect("Failed to read output");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4, "stdout: {}", stdout);
+    assert!(lines[0].contains("first-1"));
+    assert!(lines[1].contains("first-2"));
+    assert!(lines[2].contains("second-1"));
+    assert!(lines[3].contains("second-2"));
+    assert!(!stdout.contains("not-followed"));
+}
+
+#[test]
+fn test_follow_glob_rejects_positional_files() {
+    let (_stdout, stderr, exit_code) = run_kelora(&[
+        "--follow-glob",
+        "app-*.log",
+        "tests/data/does-not-matter.log",
+    ]);
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--follow-glob"), "stderr: {}", stderr);
+}
@@ -0,0 +1,163 @@
+mod common;
+use common::*;
+
+const JSON_LOGS: &str = r#"{"level":"DEBUG","msg":"cache warm"}
+{"level":"INFO","msg":"request served"}
+{"level":"WARNING","msg":"slow query"}
+{"level":"err","msg":"upstream failed"}
+{"level":60,"msg":"pino fatal"}
+{"msg":"no level here"}
+"#;
+
+const SYSLOG_LOGS: &str = "\
+<14>1 2024-01-15T10:00:00Z web01 nginx 812 - - request served
+<12>1 2024-01-15T10:00:01Z web01 nginx 812 - - upstream slow
+<11>1 2024-01-15T10:00:02Z web01 nginx 812 - - upstream failed
+<10>Jan 15 10:00:03 web01 kernel: disk offline
+";
+
+const LOGCAT_LOGS: &str = "\
+01-15 10:00:00.100  1200  1200 V Choreographer: frame
+01-15 10:00:00.200  1200  1200 I ActivityManager: start proc
+01-15 10:00:00.300  1200  1245 W System.err: retrying
+01-15 10:00:00.400  1200  1245 E AndroidRuntime: crash
+01-15 10:00:00.500  1200  1245 A libc: abort
+";
+
+const LOGCAT_REGEX: &str =
+    r"regex:^\S+ \S+\s+\d+\s+\d+ (?P<level>[VDIWEFA]) (?P<tag>[^:]+): (?P<msg>.*)$";
+
+fn messages(stdout: &str, key: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).expect("valid JSON output");
+            value[key].as_str().unwrap_or_default().to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn test_level_threshold_normalizes_json_levels() {
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-F", "json", "--level", "warn+"], JSON_LOGS);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        messages(&stdout, "msg"),
+        vec![
+            "slow query",
+            "upstream failed",
+            "pino fatal",
+            "no level here"
+        ]
+    );
+}
+
+#[test]
+fn test_level_exact_list_and_strict() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--level",
+            "info,debug",
+            "--level-strict",
+        ],
+        JSON_LOGS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        messages(&stdout, "msg"),
+        vec!["cache warm", "request served"]
+    );
+}
+
+#[test]
+fn test_level_combines_with_levels_and_exclude_levels() {
+    // --level keeps the level-less event; --levels then drops it, and only
+    // matches the raw level text, so the numeric pino level is dropped too
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--level",
+            "warn+",
+            "--levels",
+            "warning,ERR",
+        ],
+        JSON_LOGS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        messages(&stdout, "msg"),
+        vec!["slow query", "upstream failed"]
+    );
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--level",
+            "warn+",
+            "--exclude-levels",
+            "err",
+        ],
+        JSON_LOGS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        messages(&stdout, "msg"),
+        vec!["slow query", "pino fatal", "no level here"]
+    );
+}
+
+#[test]
+fn test_level_uses_syslog_severity() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "syslog", "-F", "json", "--level", "error+"],
+        SYSLOG_LOGS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        messages(&stdout, "msg"),
+        vec!["upstream failed", "disk offline"]
+    );
+}
+
+#[test]
+fn test_level_maps_logcat_letters() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", LOGCAT_REGEX, "-F", "json", "--level", "warn+"],
+        LOGCAT_LOGS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        messages(&stdout, "tag"),
+        vec!["System.err", "AndroidRuntime", "libc"]
+    );
+}
+
+#[test]
+fn test_level_drops_are_counted_in_stats() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--level", "error", "--with-stats"],
+        JSON_LOGS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let stats = extract_stats_lines(&stderr).join("\n");
+    assert!(stats.contains("Level filtered: 4"), "stats: {}", stats);
+}
+
+#[test]
+fn test_level_rejects_unknown_names() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--level", "loud"], JSON_LOGS);
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--level"), "stderr: {}", stderr);
+}
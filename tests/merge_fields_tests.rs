@@ -0,0 +1,126 @@
+mod common;
+use common::*;
+
+const ENDPOINT_LOGS: &str = r#"{"host":"api.internal","port":8443,"method":"GET"}
+{"host":"db.internal","method":"POST"}
+"#;
+
+fn parse_lines(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_merge_fields_joins_fields_with_literal_separator() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--merge-fields",
+            r#"host+":"+port=endpoint"#,
+            "--merge-fields",
+            r#"method+' '+endpoint=route"#,
+        ],
+        ENDPOINT_LOGS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events.len(), 2);
+
+    // Numbers are rendered as text; later rules see earlier targets
+    assert_eq!(events[0]["endpoint"], "api.internal:8443");
+    assert_eq!(events[0]["route"], "GET api.internal:8443");
+    // A missing source field contributes an empty segment by default
+    assert_eq!(events[1]["endpoint"], "db.internal:");
+}
+
+#[test]
+fn test_merge_fields_skip_missing_leaves_target_unset() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--merge-fields",
+            r#"host+":"+port=endpoint"#,
+            "--merge-fields-skip-missing",
+            "--filter",
+            "e.has(\"endpoint\")",
+        ],
+        ENDPOINT_LOGS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["endpoint"], "api.internal:8443");
+}
+
+#[test]
+fn test_merge_fields_rejects_malformed_spec() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--merge-fields", "host+port"],
+        ENDPOINT_LOGS,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--merge-fields"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_merge_fields_collision_policies() {
+    let input = r#"{"host":"api","port":8443,"endpoint":"old"}"#;
+    let run = |policy: &str| {
+        run_kelora_with_input(
+            &[
+                "-f",
+                "json",
+                "-F",
+                "json",
+                "--merge-fields",
+                r#"host+":"+port=endpoint"#,
+                "--collision",
+                policy,
+            ],
+            input,
+        )
+    };
+
+    let (stdout, stderr, exit_code) = run("last");
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(parse_lines(&stdout)[0]["endpoint"], "api:8443");
+
+    let (stdout, stderr, exit_code) = run("first");
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(parse_lines(&stdout)[0]["endpoint"], "old");
+
+    let (stdout, stderr, exit_code) = run("suffix");
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events[0]["endpoint"], "old");
+    assert_eq!(events[0]["endpoint_2"], "api:8443");
+
+    let (stdout, _stderr, _exit_code) = run("error");
+    assert!(stdout.trim().is_empty(), "stdout: {}", stdout);
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--merge-fields",
+            r#"host+":"+port=endpoint"#,
+            "--collision",
+            "error",
+            "--strict",
+        ],
+        input,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("field 'endpoint' already exists"),
+        "stderr: {}",
+        stderr
+    );
+}
@@ -2337,6 +2337,142 @@ fn stats_json_emits_machine_readable_object() {
     );
 }
 
+#[test]
+fn stats_json_reports_performance_figures() {
+    let input: String = (0..200)
+        .map(|i| format!("{{\"level\":\"INFO\",\"n\":{i}}}\n"))
+        .collect();
+    let (stdout, _stderr, code) = run_kelora_with_input(&["-f", "json", "--stats=json"], &input);
+    assert_eq!(code, 0, "stdout: {stdout}");
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).unwrap_or_else(|e| panic!("not JSON ({e}): {stdout}"));
+    // Every input byte reaches the parser; plain input has no compression
+    let read = parsed["input_bytes"]["read"].as_u64().unwrap();
+    assert_eq!(read, input.len() as u64, "stats json: {stdout}");
+    assert_eq!(parsed["input_bytes"]["raw"].as_u64(), Some(read));
+    assert!(parsed["throughput"]["duration_ms"].is_u64());
+
+    #[cfg(unix)]
+    {
+        let resources = &parsed["resources"];
+        assert!(resources["peak_rss_bytes"].as_u64().unwrap() > 1024 * 1024);
+        let cpu_ms = resources["cpu_user_ms"].as_u64().unwrap()
+            + resources["cpu_system_ms"].as_u64().unwrap();
+        // CPU time is process-wide; a short run stays far below a minute
+        assert!(cpu_ms < 60_000, "stats json: {stdout}");
+    }
+    assert!(parsed.get("workers").is_none(), "sequential run: {stdout}");
+}
+
+#[test]
+fn stats_parallel_reports_worker_utilization() {
+    let input: String = (0..500)
+        .map(|i| format!("{{\"level\":\"INFO\",\"n\":{i}}}\n"))
+        .collect();
+    let (stdout, _stderr, code) = run_kelora_with_input(
+        &["-f", "json", "--stats=json", "--parallel", "--threads", "2"],
+        &input,
+    );
+    assert_eq!(code, 0, "stdout: {stdout}");
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).unwrap_or_else(|e| panic!("not JSON ({e}): {stdout}"));
+    let workers = parsed["workers"].as_array().expect("workers array");
+    assert_eq!(workers.len(), 2, "stats json: {stdout}");
+    for worker in workers {
+        let pct = worker["utilization_pct"].as_f64().unwrap();
+        assert!((0.0..=100.0).contains(&pct), "stats json: {stdout}");
+    }
+}
+
+fn run_end_summary(extra_args: &[&str]) -> (serde_json::Value, serde_json::Value) {
+    let input = "{\"level\":\"INFO\",\"ts\":\"2024-01-15T10:00:00Z\"}\n\
+                 {\"level\":\"DEBUG\",\"ts\":\"2024-01-15T10:01:00Z\"}\n\
+                 not json\n\
+                 {\"level\":\"ERROR\",\"ts\":\"2024-01-15T10:05:00Z\"}\n";
+    let mut log = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut log, input.as_bytes()).unwrap();
+    let log_path = log.path().to_str().unwrap().to_string();
+    let summary = NamedTempFile::new().unwrap();
+    let summary_path = summary.path().to_str().unwrap().to_string();
+
+    let mut args = vec![
+        "-f",
+        "json",
+        "--stats=json",
+        "--allow-fs-writes",
+        "--filter",
+        "e.level != \"DEBUG\"",
+        "--script-arg",
+        &summary_path,
+        "--script-arg",
+        "nightly",
+        "--end",
+        "let s = stats; s.inputs = inputs; s.argv = argv; append_file(argv[0], s.to_json(0));",
+    ];
+    args.extend_from_slice(extra_args);
+    args.push(&log_path);
+    let (stdout, stderr, code) = run_kelora(&args);
+    assert_eq!(code, 0, "stderr: {stderr}");
+
+    let stats: serde_json::Value =
+        serde_json::from_str(stdout.trim()).unwrap_or_else(|e| panic!("not JSON ({e}): {stdout}"));
+    let written = std::fs::read_to_string(&summary_path).unwrap();
+    let summary: serde_json::Value = serde_json::from_str(written.trim())
+        .unwrap_or_else(|e| panic!("summary not JSON ({e}): {written}"));
+    assert_eq!(summary["inputs"], serde_json::json!([log_path]));
+    assert_eq!(
+        summary["argv"],
+        serde_json::json!([summary_path, "nightly"])
+    );
+    (summary, stats)
+}
+
+#[test]
+fn end_script_reads_final_stats_inputs_and_argv() {
+    let (summary, stats) = run_end_summary(&[]);
+    assert_eq!(summary["lines_read"], stats["lines"]["read"]);
+    assert_eq!(summary["parse_errors"], stats["lines"]["errors"]);
+    assert_eq!(summary["events_created"], stats["events"]["created"]);
+    assert_eq!(summary["events_output"], stats["events"]["output"]);
+    assert_eq!(summary["events_filtered"], stats["events"]["filtered"]);
+    assert_eq!(summary["files_processed"], stats["files"]["processed"]);
+    assert_eq!(summary["first_ts"], stats["time_span"]["input"]["start"]);
+    assert_eq!(summary["last_ts"], stats["time_span"]["input"]["end"]);
+
+    assert_eq!(summary["lines_read"], 4);
+    assert_eq!(summary["parse_errors"], 1);
+    assert_eq!(summary["events_created"], 3);
+    assert_eq!(summary["events_output"], 2);
+    assert_eq!(summary["first_ts"], "2024-01-15T10:00:00+00:00");
+}
+
+#[test]
+fn end_script_stats_are_merged_after_parallel_run() {
+    let (summary, stats) = run_end_summary(&["--parallel", "--threads", "2", "--batch-size", "1"]);
+    assert_eq!(summary["lines_read"], stats["lines"]["read"]);
+    assert_eq!(summary["parse_errors"], stats["lines"]["errors"]);
+    assert_eq!(summary["events_created"], stats["events"]["created"]);
+    assert_eq!(summary["events_output"], stats["events"]["output"]);
+    assert_eq!(summary["events_filtered"], stats["events"]["filtered"]);
+    assert_eq!(summary["events_created"], 3);
+    assert_eq!(summary["events_output"], 2);
+}
+
+#[test]
+fn stats_table_shows_performance_lines() {
+    let (stdout, _stderr, code) =
+        run_kelora_with_input(&["-f", "json", "-s"], "{\"level\":\"INFO\"}\n");
+    assert_eq!(code, 0, "stdout: {stdout}");
+    assert!(stdout.contains("Input read: "), "stats: {stdout}");
+    #[cfg(unix)]
+    assert!(
+        stdout.contains("Resources: wall ") && stdout.contains("peak RSS"),
+        "stats: {stdout}"
+    );
+}
+
 #[test]
 fn stats_table_still_default() {
     // Bare -s and --stats=table keep the human-readable table.
@@ -2424,3 +2560,232 @@ fn test_partial_field_skip_does_not_hint() {
         "a partially-present field records a value and must not be flagged: {stderr}"
     );
 }
+
+#[test]
+fn test_metrics_json_includes_error_categories_with_samples() {
+    let input = "{\"ok\":1}\nnot json\n{\"ok\":2}\n{broken\n[also bad\n<still> bad\n";
+
+    let (stdout, stderr, _exit_code) =
+        run_kelora_with_input(&["-f", "json", "--metrics=json"], input);
+    let json_start = stdout.find('{').expect("metrics JSON on stdout");
+    let metrics: serde_json::Value =
+        serde_json::from_str(&stdout[json_start..]).expect("valid metrics JSON");
+
+    let errors = metrics["errors"].as_array().expect("errors array");
+    assert_eq!(errors.len(), 1, "stderr: {stderr}");
+    let parse = &errors[0];
+    assert_eq!(parse["category"], "parse");
+    assert_eq!(parse["count"], 4);
+    assert_eq!(parse["format"], "json");
+
+    // The default sample cap is enforced while tracking.
+    let samples = parse["samples"].as_array().expect("samples array");
+    assert_eq!(samples.len(), 3);
+    assert_eq!(samples[0]["line"], 2);
+    assert_eq!(samples[0]["original_line"], "not json");
+    assert!(samples[0]["file"].is_null(), "stdin has no file name");
+    assert!(samples[0]["message"].is_string());
+}
+
+#[test]
+fn test_metrics_json_error_samples_honor_cap_and_redaction() {
+    let input = "not json\n{broken\n[also bad\n";
+
+    let (stdout, _stderr, _exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--metrics=json",
+            "--on-parse-error-sample",
+            "1",
+            "--redact-error-samples",
+        ],
+        input,
+    );
+    let json_start = stdout.find('{').expect("metrics JSON on stdout");
+    let metrics: serde_json::Value =
+        serde_json::from_str(&stdout[json_start..]).expect("valid metrics JSON");
+
+    let samples = metrics["errors"][0]["samples"]
+        .as_array()
+        .expect("samples array");
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0]["line"], 1);
+    assert!(samples[0].get("original_line").is_none());
+    assert!(samples[0].get("message").is_none());
+}
+
+#[test]
+fn test_metrics_json_omits_errors_for_clean_runs() {
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--exec",
+            "track_inc(\"events\")",
+            "--metrics=json",
+        ],
+        "{\"ok\":1}\n",
+    );
+    assert_eq!(exit_code, 0);
+    let json_start = stdout.find('{').expect("metrics JSON on stdout");
+    let metrics: serde_json::Value =
+        serde_json::from_str(&stdout[json_start..]).expect("valid metrics JSON");
+    assert!(metrics.get("errors").is_none(), "stdout: {stdout}");
+}
+
+#[test]
+fn test_output_summary_only_prints_metrics_without_events() {
+    let input = "{\"service\":\"api\",\"msg\":\"one\"}\n{\"service\":\"db\",\"msg\":\"two\"}\n{\"service\":\"api\",\"msg\":\"three\"}\n";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--exec",
+            "track_sum(\"requests\", 1); track_freq(\"service\", e.service)",
+            "--output-summary-only",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(!stdout.contains("msg"), "events leaked: {stdout}");
+    assert!(stdout.contains("requests"), "stdout: {stdout}");
+    assert!(stdout.contains("api"), "stdout: {stdout}");
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--exec",
+            "track_sum(\"requests\", 1)",
+            "--output-summary-only",
+            "--metrics=json",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let json_start = stdout.find('{').expect("metrics JSON on stdout");
+    let metrics: serde_json::Value =
+        serde_json::from_str(&stdout[json_start..]).expect("valid metrics JSON");
+    assert_eq!(metrics["requests"], 3);
+}
+
+#[test]
+fn test_output_summary_only_keeps_script_output() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--exec",
+            "track_sum(\"requests\", 1)",
+            "--end",
+            "print(\"done\")",
+            "--output-summary-only",
+        ],
+        "{\"msg\":\"one\"}\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("done"), "stdout: {stdout}");
+    assert!(!stdout.contains("\"one\""), "stdout: {stdout}");
+}
+
+fn uniform_input(n: usize) -> String {
+    // Every value in [0, 1) exactly once, in a scrambled order
+    (0..n)
+        .map(|i| format!("{{\"v\":{}}}\n", (i * 7919 % n) as f64 / n as f64))
+        .collect()
+}
+
+fn approx_percentile_metrics(extra_args: &[&str]) -> serde_json::Value {
+    let mut args = vec![
+        "-f",
+        "json",
+        "--exec",
+        "track_percentile_approx(\"v\", e.v)",
+        "--output-summary-only",
+        "--metrics=json",
+    ];
+    args.extend_from_slice(extra_args);
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&args, &uniform_input(10_000));
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let json_start = stdout.find('{').expect("metrics JSON on stdout");
+    serde_json::from_str(&stdout[json_start..]).expect("valid metrics JSON")
+}
+
+#[test]
+fn test_track_percentile_approx_uniform_distribution() {
+    let metrics = approx_percentile_metrics(&[]);
+    for (key, expected) in [
+        ("v_p50", 0.5),
+        ("v_p90", 0.9),
+        ("v_p95", 0.95),
+        ("v_p99", 0.99),
+        ("v_p999", 0.999),
+    ] {
+        let actual = metrics[key]
+            .as_f64()
+            .unwrap_or_else(|| panic!("{key} missing"));
+        assert!(
+            (actual - expected).abs() < 0.02,
+            "{key}: expected ~{expected}, got {actual}"
+        );
+    }
+}
+
+#[test]
+fn test_track_percentile_approx_parallel_merge() {
+    let metrics =
+        approx_percentile_metrics(&["--parallel", "--threads", "4", "--batch-size", "500"]);
+    let p99 = metrics["v_p99"].as_f64().expect("v_p99 present");
+    assert!((p99 - 0.99).abs() < 0.02, "parallel p99 was {p99}");
+    let p50 = metrics["v_p50"].as_f64().expect("v_p50 present");
+    assert!((p50 - 0.5).abs() < 0.02, "parallel p50 was {p50}");
+}
+
+#[test]
+fn metrics_hint_suppressed_in_stats_only_mode() {
+    // -s replaces the events with the stats block, a data-only mode like -m,
+    // so the advisory nudge stays out of the way
+    let (stdout, stderr, code) = run_kelora_with_input(
+        &["-f", "json", "-s", "--exec", "track_inc(\"n\");"],
+        HINT_INPUT,
+    );
+    assert_eq!(code, 0);
+    assert!(stdout.contains("Events created:"), "stats: {stdout}");
+    assert!(
+        !stderr.contains(METRICS_HINT),
+        "stats-only mode must not nudge: {stderr}"
+    );
+}
+
+#[test]
+fn stats_and_metrics_follow_named_destinations() {
+    let metrics_file = NamedTempFile::new().expect("temp file");
+    let metrics_path = metrics_file.path().to_str().unwrap().to_string();
+    let metrics_arg = format!("--metrics=json,file:{}", metrics_path);
+    let (stdout, stderr, code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--stats=json,stderr",
+            &metrics_arg,
+            "--exec",
+            "track_inc(\"n\");",
+        ],
+        HINT_INPUT,
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+
+    // Neither report took stdout, so the events still print there
+    assert_eq!(stdout.lines().count(), 2, "stdout: {stdout}");
+    let start = stderr.find('{').expect("JSON stats on stderr");
+    let end = stderr.rfind('}').expect("JSON stats on stderr");
+    let stats: serde_json::Value = serde_json::from_str(&stderr[start..=end]).unwrap();
+    assert_eq!(stats["events"]["output"], 2);
+
+    let metrics: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&metrics_path).unwrap()).unwrap();
+    assert_eq!(metrics["n"], 2);
+}
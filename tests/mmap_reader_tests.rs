@@ -0,0 +1,157 @@
+//! Tests for `--mmap` and `--read-buffer`.
+//!
+//! Memory-mapped reading is a pure fast path: for every input shape (CRLF,
+//! invalid UTF-8, over-limit lines, a missing final newline, compressed files
+//! that can't be mapped) the output must be byte-for-byte that of buffered
+//! reading, sequential and parallel alike.
+
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_kelora_raw(args: &[&str]) -> (Vec<u8>, String, i32) {
+    let output = Command::new(env!("CARGO_BIN_EXE_kelora"))
+        .args(args)
+        .env("LLVM_PROFILE_FILE", "/dev/null")
+        .output()
+        .expect("Failed to execute kelora");
+    (
+        output.stdout,
+        String::from_utf8_lossy(&output.stderr).to_string(),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn checksum(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// A plain fixture exercising every line-splitting edge case, plus a gzip file
+/// that has to fall back to buffered reads.
+fn write_fixtures(dir: &Path) -> Vec<PathBuf> {
+    let mut plain = Vec::new();
+    for i in 0..5000 {
+        writeln!(
+            plain,
+            r#"{{"n": {i}, "level": "{}", "msg": "event {i}"}}"#,
+            ["info", "warn", "error"][i % 3]
+        )
+        .unwrap();
+        match i % 500 {
+            7 => plain.extend_from_slice(b"crlf line\r\n"),
+            11 => plain.extend_from_slice(b"bad \xff\xfe bytes\n"),
+            13 => plain.extend_from_slice(b"trailing blanks   \t\n"),
+            17 => plain.extend_from_slice(b"\n"),
+            19 => {
+                plain.extend_from_slice(&vec![b'x'; 3000]);
+                plain.push(b'\n');
+            }
+            _ => {}
+        }
+    }
+    plain.extend_from_slice(br#"{"n": "last", "msg": "no final newline"}"#);
+    let plain_path = dir.join("plain.log");
+    std::fs::write(&plain_path, &plain).unwrap();
+
+    let gz_path = dir.join("second.log.gz");
+    let mut encoder = flate2::write::GzEncoder::new(
+        std::fs::File::create(&gz_path).unwrap(),
+        flate2::Compression::default(),
+    );
+    for i in 0..200 {
+        writeln!(encoder, r#"{{"n": {i}, "msg": "compressed"}}"#).unwrap();
+    }
+    encoder.finish().unwrap();
+
+    vec![plain_path, gz_path]
+}
+
+fn assert_same_output(base_args: &[&str], files: &[PathBuf]) {
+    let paths: Vec<&str> = files.iter().map(|p| p.to_str().unwrap()).collect();
+    let mut buffered_args = base_args.to_vec();
+    buffered_args.extend(&paths);
+    let mut mapped_args = vec!["--mmap"];
+    mapped_args.extend(base_args);
+    mapped_args.extend(&paths);
+
+    let (buffered, buffered_err, buffered_code) = run_kelora_raw(&buffered_args);
+    let (mapped, mapped_err, mapped_code) = run_kelora_raw(&mapped_args);
+
+    // The fixture has lines the json parser rejects, so the exit code is not
+    // necessarily 0; it just has to agree.
+    assert_eq!(
+        mapped_code, buffered_code,
+        "exit codes differ; buffered stderr: {buffered_err}; --mmap stderr: {mapped_err}"
+    );
+    assert!(!buffered.is_empty());
+    assert_eq!(
+        checksum(&mapped),
+        checksum(&buffered),
+        "--mmap output differs for {base_args:?}"
+    );
+}
+
+#[test]
+fn test_mmap_output_matches_buffered_sequential() {
+    let dir = TempDir::new().unwrap();
+    let files = write_fixtures(dir.path());
+
+    assert_same_output(&["-f", "line", "--max-line-bytes", "1KiB"], &files);
+    assert_same_output(
+        &["-f", "json", "-F", "json", "--max-line-bytes", "1KiB"],
+        &files,
+    );
+}
+
+#[test]
+fn test_mmap_output_matches_buffered_parallel() {
+    let dir = TempDir::new().unwrap();
+    let files = write_fixtures(dir.path());
+
+    let parallel = ["--parallel", "--threads", "4", "--batch-size", "64"];
+    let mut line_args = parallel.to_vec();
+    line_args.extend(["-f", "line", "--max-line-bytes", "1KiB"]);
+    assert_same_output(&line_args, &files);
+
+    let mut json_args = parallel.to_vec();
+    json_args.extend([
+        "-f",
+        "json",
+        "-F",
+        "json",
+        "--filter",
+        "e.level == \"error\"",
+    ]);
+    assert_same_output(&json_args, &files);
+}
+
+#[test]
+fn test_read_buffer_size_does_not_change_output() {
+    let dir = TempDir::new().unwrap();
+    let files = write_fixtures(dir.path());
+    let paths: Vec<&str> = files.iter().map(|p| p.to_str().unwrap()).collect();
+
+    let mut default_args = vec!["-f", "line"];
+    default_args.extend(&paths);
+    let mut tiny_args = vec!["-f", "line", "--read-buffer", "16"];
+    tiny_args.extend(&paths);
+
+    let (default_out, _, default_code) = run_kelora_raw(&default_args);
+    let (tiny_out, tiny_err, tiny_code) = run_kelora_raw(&tiny_args);
+    assert_eq!(default_code, 0);
+    assert_eq!(tiny_code, 0, "stderr: {tiny_err}");
+    assert_eq!(checksum(&tiny_out), checksum(&default_out));
+}
+
+#[test]
+fn test_read_buffer_rejects_zero_and_bad_sizes() {
+    let (_, stderr, code) = run_kelora_raw(&["--read-buffer", "0", "--no-input"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("--read-buffer"), "stderr: {stderr}");
+
+    let (_, stderr, code) = run_kelora_raw(&["--read-buffer", "lots", "--no-input"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("--read-buffer"), "stderr: {stderr}");
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "mmdb")]
+
+mod common;
+use common::*;
+#[path = "common/mmdb.rs"]
+mod mmdb_fixture;
+
+use std::path::Path;
+use tempfile::TempDir;
+
+/// City database covering 81.2.69.0/24 (London) and 8.8.8.0/24 (country only)
+fn city_database(dir: &Path) -> String {
+    let path = dir.join("city.mmdb");
+    mmdb_fixture::write_mmdb(
+        &path,
+        "GeoLite2-City",
+        &[
+            (
+                "81.2.69.0/24",
+                serde_json::json!({
+                    "city": {"geoname_id": 2643743, "names": {"de": "London", "en": "London"}},
+                    "continent": {"code": "EU", "names": {"en": "Europe"}},
+                    "country": {"iso_code": "GB", "names": {"en": "United Kingdom", "fr": "Royaume-Uni"}},
+                    "location": {"latitude": 51.5142, "longitude": -0.0931, "time_zone": "Europe/London"},
+                    "registered_country": {"iso_code": "GB", "is_in_european_union": false}
+                }),
+            ),
+            (
+                "8.8.8.0/24",
+                serde_json::json!({"country": {"iso_code": "US", "names": {"en": "United States"}}}),
+            ),
+        ],
+    );
+    path.to_str().unwrap().to_string()
+}
+
+fn asn_database(dir: &Path) -> String {
+    let path = dir.join("asn.mmdb");
+    mmdb_fixture::write_mmdb(
+        &path,
+        "GeoLite2-ASN",
+        &[(
+            "8.8.8.0/24",
+            serde_json::json!({
+                "autonomous_system_number": 15169,
+                "autonomous_system_organization": "GOOGLE"
+            }),
+        )],
+    );
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_mmdb_lookup_returns_city_and_asn_fields() {
+    let dir = TempDir::new().unwrap();
+    let geo = format!("geo={}", city_database(dir.path()));
+    let asn = format!("asn={}", asn_database(dir.path()));
+    let input = r#"{"ip":"81.2.69.142"}
+{"ip":"8.8.8.8"}
+{"ip":"10.0.0.1"}
+{"ip":"not-an-ip"}"#;
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-j",
+            "--mmdb",
+            &geo,
+            "--mmdb",
+            &asn,
+            "-e",
+            "e.geo = geo(e.ip); e += asn(e.ip)",
+            "-F",
+            "json",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(
+        events[0]["geo"],
+        serde_json::json!({
+            "continent": "EU",
+            "country": "GB",
+            "country_name": "United Kingdom",
+            "city": "London",
+            "latitude": 51.5142,
+            "longitude": -0.0931,
+            "time_zone": "Europe/London"
+        })
+    );
+    assert_eq!(
+        events[1],
+        serde_json::json!({
+            "ip": "8.8.8.8",
+            "geo": {"country": "US", "country_name": "United States"},
+            "asn": 15169,
+            "as_org": "GOOGLE"
+        })
+    );
+    // Misses and invalid addresses give an empty map
+    assert_eq!(events[2]["geo"], serde_json::json!({}));
+    assert_eq!(events[3]["geo"], serde_json::json!({}));
+}
+
+#[test]
+fn test_mmdb_lookup_in_parallel_workers() {
+    let dir = TempDir::new().unwrap();
+    let geo = format!("geo={}", city_database(dir.path()));
+    let input = "81.2.69.1\n8.8.4.4\n".repeat(200);
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "line",
+            "--parallel",
+            "--batch-size",
+            "16",
+            "--mmdb",
+            &geo,
+            "-e",
+            "e.country = geo(e.line).country ?? \"-\"",
+            "-k",
+            "country",
+            "-F",
+            "csvnh",
+        ],
+        &input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let countries: Vec<&str> = stdout.lines().collect();
+    assert_eq!(countries.len(), 400);
+    assert_eq!(countries.iter().filter(|c| **c == "GB").count(), 200);
+    assert_eq!(countries.iter().filter(|c| **c == "-").count(), 200);
+}
+
+#[test]
+fn test_mmdb_missing_database_or_bad_spec_is_an_error() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["--mmdb", "geo=/nonexistent/GeoLite2-City.mmdb"], "x\n");
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("Failed to open --mmdb database 'geo' (/nonexistent/GeoLite2-City.mmdb)"),
+        "{}",
+        stderr
+    );
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(&["--mmdb", "geo.mmdb"], "x\n");
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("expected NAME=PATH"), "{}", stderr);
+}
@@ -0,0 +1,100 @@
+mod common;
+use common::*;
+
+/// One event with nested maps, an array, and key names that contain dots.
+const NESTED: &str = r#"{"request":{"method":"GET","headers":{"user-agent":"curl/8.4.0","x.trace":"abc123"}},"items":[{"id":7},{"id":9}],"labels":{"app.kubernetes.io/name":"api"}}"#;
+
+/// Field paths and the value every formatter should find for them.
+const PATHS: &[(&str, &str)] = &[
+    ("request.headers.user-agent", "curl/8.4.0"),
+    ("items.1.id", "9"),
+    (r"request.headers.x\.trace", "abc123"),
+    (r"labels.app\.kubernetes\.io/name", "api"),
+];
+
+#[test]
+fn test_dotted_keys_resolve_the_same_value_in_every_formatter() {
+    for format in ["default", "json", "logfmt", "csv", "tsv", "table"] {
+        for (path, expected) in PATHS {
+            let (stdout, stderr, exit_code) =
+                run_kelora_with_input(&["-f", "json", "-F", format, "-k", path], NESTED);
+            assert_eq!(exit_code, 0, "-F {format} -k {path}: {stderr}");
+            assert!(
+                stdout.contains(expected),
+                "-F {format} -k {path} should show {expected}, got: {stdout}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_dotted_keys_become_named_columns() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "csv",
+            "-k",
+            "request.method,items.0.id,request.headers.user-agent",
+        ],
+        NESTED,
+    );
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+    assert_eq!(
+        stdout.trim(),
+        "request.method,items.0.id,request.headers.user-agent\nGET,7,curl/8.4.0"
+    );
+
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "-k",
+            "request.method,items.1.id",
+        ],
+        NESTED,
+    );
+    assert_eq!(exit_code, 0);
+    let event: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(
+        event,
+        serde_json::json!({"request.method": "GET", "items.1.id": 9})
+    );
+}
+
+#[test]
+fn test_keymap_field_uses_dotted_path() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "keymap", "-k", "request.method"],
+        NESTED,
+    );
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+    assert!(stdout.contains('G'), "stdout: {stdout}");
+}
+
+#[test]
+fn test_exclude_keys_removes_nested_values_only() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "-K",
+            r"request.headers.user-agent,labels.app\.kubernetes\.io/name,items",
+        ],
+        NESTED,
+    );
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+    let event: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(
+        event,
+        serde_json::json!({
+            "request": {"method": "GET", "headers": {"x.trace": "abc123"}},
+            "labels": {}
+        })
+    );
+}
@@ -0,0 +1,227 @@
+mod common;
+use common::*;
+use std::fs::File;
+use std::io::Write;
+use tempfile::TempDir;
+
+struct V5Flow {
+    src: [u8; 4],
+    dst: [u8; 4],
+    src_port: u16,
+    dst_port: u16,
+    protocol: u8,
+    packets: u32,
+    bytes: u32,
+    first: u32,
+    last: u32,
+    tcp_flags: u8,
+    tos: u8,
+}
+
+const UNIX_SECS: u32 = 1_700_000_000;
+const SYS_UPTIME: u32 = 60_000;
+
+/// Hand-assemble one NetFlow v5 export packet: 24-byte header + 48-byte records.
+fn v5_packet(flows: &[V5Flow]) -> Vec<u8> {
+    let mut packet = Vec::new();
+    packet.extend_from_slice(&5u16.to_be_bytes()); // version
+    packet.extend_from_slice(&(flows.len() as u16).to_be_bytes()); // count
+    packet.extend_from_slice(&SYS_UPTIME.to_be_bytes());
+    packet.extend_from_slice(&UNIX_SECS.to_be_bytes());
+    packet.extend_from_slice(&0u32.to_be_bytes()); // unix_nsecs
+    packet.extend_from_slice(&1u32.to_be_bytes()); // flow_sequence
+    packet.extend_from_slice(&[0, 0]); // engine_type, engine_id
+    packet.extend_from_slice(&0u16.to_be_bytes()); // sampling_interval
+
+    for flow in flows {
+        packet.extend_from_slice(&flow.src);
+        packet.extend_from_slice(&flow.dst);
+        packet.extend_from_slice(&[0, 0, 0, 0]); // nexthop
+        packet.extend_from_slice(&1u16.to_be_bytes()); // input ifindex
+        packet.extend_from_slice(&2u16.to_be_bytes()); // output ifindex
+        packet.extend_from_slice(&flow.packets.to_be_bytes());
+        packet.extend_from_slice(&flow.bytes.to_be_bytes());
+        packet.extend_from_slice(&flow.first.to_be_bytes());
+        packet.extend_from_slice(&flow.last.to_be_bytes());
+        packet.extend_from_slice(&flow.src_port.to_be_bytes());
+        packet.extend_from_slice(&flow.dst_port.to_be_bytes());
+        packet.push(0); // pad1
+        packet.push(flow.tcp_flags);
+        packet.push(flow.protocol);
+        packet.push(flow.tos);
+        packet.extend_from_slice(&[0; 4]); // src_as, dst_as
+        packet.extend_from_slice(&[24, 24]); // src_mask, dst_mask
+        packet.extend_from_slice(&[0, 0]); // pad2
+    }
+    packet
+}
+
+fn sample_capture() -> Vec<u8> {
+    let mut capture = v5_packet(&[
+        V5Flow {
+            src: [10, 0, 0, 1],
+            dst: [10, 0, 0, 2],
+            src_port: 51000,
+            dst_port: 443,
+            protocol: 6,
+            packets: 12,
+            bytes: 1500,
+            first: 10_000,
+            last: 12_500,
+            tcp_flags: 0x1b,
+            tos: 0,
+        },
+        V5Flow {
+            src: [192, 168, 1, 5],
+            dst: [8, 8, 8, 8],
+            src_port: 5353,
+            dst_port: 53,
+            protocol: 17,
+            packets: 1,
+            bytes: 76,
+            first: 20_000,
+            last: 20_000,
+            tcp_flags: 0,
+            tos: 0xb8,
+        },
+    ]);
+    capture.extend(v5_packet(&[V5Flow {
+        src: [172, 16, 0, 9],
+        dst: [10, 0, 0, 2],
+        src_port: 40000,
+        dst_port: 22,
+        protocol: 6,
+        packets: 40,
+        bytes: 9000,
+        first: 30_000,
+        last: 45_000,
+        tcp_flags: 0x18,
+        tos: 0x10,
+    }]));
+    capture
+}
+
+fn write_capture(dir: &TempDir, name: &str, bytes: &[u8]) -> String {
+    let path = dir.path().join(name);
+    File::create(&path).unwrap().write_all(bytes).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn parse_json_lines(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_netflow5_emits_one_event_per_flow_record() {
+    let dir = TempDir::new().unwrap();
+    let path = write_capture(&dir, "flows.nf5", &sample_capture());
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_files(&["-f", "netflow5", "-F", "json"], &[&path]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let events = parse_json_lines(&stdout);
+    assert_eq!(events.len(), 3, "stdout: {}", stdout);
+
+    let boot_ms = UNIX_SECS as i64 * 1000 - SYS_UPTIME as i64;
+    let first = &events[0];
+    assert_eq!(first["src_ip"], "10.0.0.1");
+    assert_eq!(first["dst_ip"], "10.0.0.2");
+    assert_eq!(first["src_port"], 51000);
+    assert_eq!(first["dst_port"], 443);
+    assert_eq!(first["protocol"], 6);
+    assert_eq!(first["bytes"], 1500);
+    assert_eq!(first["packets"], 12);
+    assert_eq!(first["start_ms"], boot_ms + 10_000);
+    assert_eq!(first["end_ms"], boot_ms + 12_500);
+    assert_eq!(first["tcp_flags"], 0x1b);
+    assert_eq!(first["tos"], 0);
+
+    assert_eq!(events[1]["dst_ip"], "8.8.8.8");
+    assert_eq!(events[1]["tos"], 0xb8);
+    // Record from the second packet
+    assert_eq!(events[2]["src_ip"], "172.16.0.9");
+    assert_eq!(events[2]["bytes"], 9000);
+}
+
+#[test]
+fn test_netflow5_fields_work_in_filters_and_parallel_mode() {
+    let dir = TempDir::new().unwrap();
+    let path = write_capture(&dir, "flows.nf5", &sample_capture());
+
+    for extra in [&[][..], &["--parallel", "--batch-size", "1"][..]] {
+        let mut args = vec![
+            "-f",
+            "netflow5",
+            "-F",
+            "json",
+            "--filter",
+            "e.protocol == 6 && e.bytes > 1000",
+        ];
+        args.extend_from_slice(extra);
+        let (stdout, stderr, exit_code) = run_kelora_with_files(&args, &[&path]);
+        assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+        let mut sources: Vec<String> = parse_json_lines(&stdout)
+            .iter()
+            .map(|e| e["src_ip"].as_str().unwrap().to_string())
+            .collect();
+        sources.sort();
+        assert_eq!(sources, vec!["10.0.0.1", "172.16.0.9"], "args: {:?}", args);
+    }
+}
+
+#[test]
+fn test_netflow5_reads_gzip_compressed_capture() {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("flows.nf5.gz");
+    let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+    encoder.write_all(&sample_capture()).unwrap();
+    encoder.finish().unwrap();
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_files(&["-f", "netflow5", "-F", "json"], &[path.to_str().unwrap()]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(parse_json_lines(&stdout).len(), 3);
+}
+
+#[test]
+fn test_netflow5_truncated_capture_is_reported() {
+    let dir = TempDir::new().unwrap();
+    let mut capture = sample_capture();
+    capture.truncate(capture.len() - 10);
+    let path = write_capture(&dir, "truncated.nf5", &capture);
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_files(&["-f", "netflow5", "-F", "json"], &[&path]);
+    assert_ne!(
+        exit_code, 0,
+        "a truncated capture must not succeed silently"
+    );
+    assert!(
+        stderr.contains("netflow: capture ends inside the NetFlow v5 flow records"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_netflow_wrong_version_is_reported() {
+    let dir = TempDir::new().unwrap();
+    let path = write_capture(&dir, "flows.nf5", &sample_capture());
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_files(&["-f", "netflow9", "-F", "json"], &[&path]);
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("expected netflow9 packet (version 9), found version 5"),
+        "stderr: {}",
+        stderr
+    );
+}
@@ -0,0 +1,206 @@
+#![cfg(feature = "orc")]
+
+mod common;
+use common::*;
+
+use arrow_array::RecordBatch;
+use arrow_schema::DataType;
+use orc_rust::arrow_reader::ArrowReaderBuilder;
+use std::fs::File;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn read_orc(path: &Path) -> Vec<RecordBatch> {
+    let file = File::open(path).expect("ORC file should exist");
+    ArrowReaderBuilder::try_new(file)
+        .expect("valid ORC file")
+        .build()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("ORC stripes should decode")
+}
+
+fn total_rows(batches: &[RecordBatch]) -> usize {
+    batches.iter().map(|b| b.num_rows()).sum()
+}
+
+fn json_lines(n: usize) -> String {
+    (0..n)
+        .map(|i| format!(r#"{{"id":{},"level":"INFO","ok":true}}"#, i))
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+#[test]
+fn test_orc_output_round_trips_row_count() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let out = dir.path().join("events.orc");
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "orc",
+            "--output-file",
+            out.to_str().unwrap(),
+        ],
+        &json_lines(250),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.is_empty(), "ORC output must not reach stdout");
+
+    let batches = read_orc(&out);
+    assert_eq!(total_rows(&batches), 250);
+
+    let schema = batches[0].schema();
+    assert_eq!(
+        schema.field_with_name("id").unwrap().data_type(),
+        &DataType::Int64
+    );
+    assert_eq!(
+        schema.field_with_name("level").unwrap().data_type(),
+        &DataType::Utf8
+    );
+    assert_eq!(
+        schema.field_with_name("ok").unwrap().data_type(),
+        &DataType::Boolean
+    );
+}
+
+#[test]
+fn test_orc_schema_widens_mixed_numbers_and_falls_back_to_string() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let out = dir.path().join("mixed.orc");
+    let input = r#"{"latency":12,"code":200}
+{"latency":3.5,"code":"E42"}
+{"latency":7}
+"#;
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "orc", "-o", out.to_str().unwrap()],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let batches = read_orc(&out);
+    assert_eq!(total_rows(&batches), 3);
+    let schema = batches[0].schema();
+    assert_eq!(
+        schema.field_with_name("latency").unwrap().data_type(),
+        &DataType::Float64
+    );
+    assert_eq!(
+        schema.field_with_name("code").unwrap().data_type(),
+        &DataType::Utf8
+    );
+}
+
+#[test]
+fn test_orc_stripe_rows_splits_stripes() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let out = dir.path().join("striped.orc");
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "orc",
+            "-o",
+            out.to_str().unwrap(),
+            "--orc-stripe-rows",
+            "40",
+        ],
+        &json_lines(100),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let file = File::open(&out).unwrap();
+    let reader = ArrowReaderBuilder::try_new(file).unwrap();
+    assert_eq!(reader.file_metadata().number_of_rows(), 100);
+    assert_eq!(reader.file_metadata().stripe_metadatas().len(), 3);
+}
+
+#[test]
+fn test_orc_schema_covers_the_first_100_events() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let out = dir.path().join("late.orc");
+    // "early" shows up past the first stripe but within the schema sample
+    let input = json_lines(100).replace(r#"{"id":50,"#, r#"{"id":50,"early":"yes","#);
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "orc",
+            "-o",
+            out.to_str().unwrap(),
+            "--orc-stripe-rows",
+            "10",
+        ],
+        &input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(!stderr.contains("ORC field"), "stderr: {}", stderr);
+
+    let batches = read_orc(&out);
+    assert_eq!(total_rows(&batches), 100);
+    assert_eq!(
+        batches[0]
+            .schema()
+            .field_with_name("early")
+            .unwrap()
+            .data_type(),
+        &DataType::Utf8
+    );
+}
+
+#[test]
+fn test_orc_warns_about_fields_that_do_not_fit_the_schema() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let out = dir.path().join("unfit.orc");
+    let input = json_lines(100)
+        + r#"{"id":"three","extra":true}
+{"id":"four","extra":false}
+"#;
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "orc", "-o", out.to_str().unwrap()],
+        &input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        stderr.matches("ORC field 'id'").count(),
+        1,
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("ORC field 'extra' first appeared after the schema was fixed"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("the schema comes from the first 100 events"),
+        "stderr: {}",
+        stderr
+    );
+    assert_eq!(total_rows(&read_orc(&out)), 102);
+}
+
+#[test]
+fn test_orc_empty_input_writes_valid_file() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let out = dir.path().join("empty.orc");
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "orc", "-o", out.to_str().unwrap()],
+        "",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(total_rows(&read_orc(&out)), 0);
+}
+
+#[test]
+fn test_orc_requires_output_file() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-F", "orc"], &json_lines(1));
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--output-file"), "stderr: {}", stderr);
+}
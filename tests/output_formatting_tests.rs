@@ -676,6 +676,73 @@ fn test_conflicting_color_flags() {
     );
 }
 
+#[test]
+fn test_color_theme_changes_escape_codes() {
+    let input = r#"{"level": "error", "message": "test"}"#;
+
+    let (default_out, _stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--force-color"], input);
+    assert_eq!(exit_code, 0);
+    let (solarized_out, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--force-color", "--color-theme", "solarized"],
+        input,
+    );
+    assert_eq!(exit_code, 0);
+
+    assert!(default_out.contains("\x1b[91merror"), "{default_out:?}");
+    assert!(
+        solarized_out.contains("\x1b[38;5;160merror"),
+        "{solarized_out:?}"
+    );
+
+    // --no-color still strips every escape code
+    let (plain_out, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--no-color", "--color-theme", "highcontrast"],
+        input,
+    );
+    assert_eq!(exit_code, 0);
+    assert!(!plain_out.contains('\x1b'), "{plain_out:?}");
+}
+
+#[test]
+fn test_color_parts_colors_only_selected_parts() {
+    let input = r#"{"level": "error", "message": "test"}"#;
+
+    let (keys_out, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--force-color", "--color-parts", "keys"],
+        input,
+    );
+    assert_eq!(exit_code, 0);
+    assert!(keys_out.contains("\x1b[32mlevel\x1b[0m"), "{keys_out:?}");
+    assert!(keys_out.contains("'error'"), "{keys_out:?}");
+
+    let (levels_out, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--force-color", "--color-parts", "levels"],
+        input,
+    );
+    assert_eq!(exit_code, 0);
+    assert!(levels_out.contains("\x1b[91merror"), "{levels_out:?}");
+    assert!(!levels_out.contains("\x1b[32m"), "{levels_out:?}");
+
+    // Several parts at once, and JSON highlighting counts as values
+    let json_input = r#"{"level": "error", "body": "{\"a\":1}"}"#;
+    let (out, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--force-color",
+            "--color-json-values",
+            "--color-parts",
+            "keys,levels",
+        ],
+        json_input,
+    );
+    assert_eq!(exit_code, 0);
+    assert!(out.contains("\x1b[32mbody\x1b[0m"), "{out:?}");
+    assert!(out.contains("\x1b[91merror"), "{out:?}");
+    assert!(out.contains(r#"{"a":1}"#), "{out:?}");
+}
+
 #[test]
 fn test_csv_without_keys_suggests_column_order_example() {
     let input = r#"{"ts": "2024-01-01T10:00:00Z", "level": "info", "msg": "hello"}"#;
@@ -839,3 +906,407 @@ fn test_color_with_filtering() {
     // Should not contain ANSI codes
     assert!(!stdout.contains("\x1b["));
 }
+
+#[test]
+fn test_ecs_output_nginx_access_log() {
+    let input = r#"192.168.1.10 - alice [25/Dec/2024:10:00:00 +0000] "GET /index.html HTTP/1.1" 200 1234 "https://example.com/" "Mozilla/5.0"
+10.0.0.5 - - [25/Dec/2024:10:00:01 +0000] "POST /api/login HTTP/1.1" 401 56 "-" "curl/8.4.0""#;
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "combined", "-F", "ecs"], input);
+    assert_eq!(exit_code, 0, "ecs output should succeed: {stderr}");
+
+    let lines: Vec<&str> = stdout.trim().lines().collect();
+    assert_eq!(lines.len(), 2, "one NDJSON line per event: {stdout}");
+
+    let first: serde_json::Value = serde_json::from_str(lines[0]).expect("valid JSON");
+    assert_eq!(first["@timestamp"], "2024-12-25T10:00:00.000Z");
+    assert_eq!(first["source"]["ip"], "192.168.1.10");
+    assert_eq!(first["http"]["request"]["method"], "GET");
+    assert_eq!(first["http"]["response"]["status_code"], 200);
+    assert_eq!(first["user_agent"]["original"], "Mozilla/5.0");
+    assert_eq!(first["labels"]["path"], "/index.html");
+    assert_eq!(first["labels"]["bytes"], "1234");
+    assert_eq!(first["ecs"]["version"], "8.11.0");
+    assert!(first.get("ts").is_none(), "ts folds into @timestamp");
+
+    let second: serde_json::Value = serde_json::from_str(lines[1]).expect("valid JSON");
+    assert_eq!(second["http"]["response"]["status_code"], 401);
+
+    let (stdout_v1, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "combined", "-F", "ecs", "--ecs-version", "1"],
+        input,
+    );
+    assert_eq!(exit_code, 0);
+    let first_v1: serde_json::Value =
+        serde_json::from_str(stdout_v1.lines().next().unwrap()).expect("valid JSON");
+    assert_eq!(first_v1["ecs"]["version"], "1.12.0");
+}
+
+#[test]
+fn test_ecs_output_flattens_nested_labels_to_strings() {
+    let input = r#"{"msg":"hi","extra":{"x":1,"inner":{"ok":true}},"tags":["a","b"],"gone":null}"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&["-f", "json", "-F", "ecs"], input);
+    assert_eq!(exit_code, 0, "ecs output should succeed: {stderr}");
+
+    let event: serde_json::Value = serde_json::from_str(stdout.trim()).expect("valid JSON");
+    assert_eq!(event["message"], "hi");
+    assert_eq!(
+        event["labels"],
+        serde_json::json!({
+            "extra.x": "1",
+            "extra.inner.ok": "true",
+            "tags": "[\"a\",\"b\"]"
+        })
+    );
+}
+
+#[test]
+fn test_fluentd_output_formats() {
+    let input = r#"{"ts":"2024-12-25T10:00:00Z","level":"INFO","msg":"started"}
+{"level":"WARN","msg":"no timestamp"}"#;
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "fluentd-json",
+            "--fluentd-tag",
+            "app.api",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "fluentd-json output should succeed: {stderr}");
+    let lines: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON"))
+        .collect();
+    assert_eq!(lines.len(), 2, "one message per event: {stdout}");
+    assert_eq!(lines[0][0], "app.api");
+    assert_eq!(lines[0][1], 1735120800);
+    assert_eq!(lines[0][2]["msg"], "started");
+    assert!(lines[1][1].is_i64(), "fallback time is integer seconds");
+    assert_eq!(lines[1][2]["level"], "WARN");
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-F", "fluentd-forward"], input);
+    assert_eq!(
+        exit_code, 0,
+        "fluentd-forward output should succeed: {stderr}"
+    );
+    assert_eq!(stdout.trim().lines().count(), 1, "single batch: {stdout}");
+    let batch: serde_json::Value = serde_json::from_str(stdout.trim()).expect("valid JSON");
+    assert_eq!(batch[0], "kelora");
+    let entries = batch[1].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0][0], 1735120800);
+    assert_eq!(entries[1][1]["msg"], "no timestamp");
+    assert_eq!(batch[2], serde_json::json!({}));
+}
+
+#[test]
+fn test_chunk_size_batches_events_into_json_arrays() {
+    let input: String = (1..=10).map(|n| format!("{{\"n\":{}}}\n", n)).collect();
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-F", "json", "--chunk-size", "3"], &input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let batches: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line should be a JSON array"))
+        .collect();
+    let sizes: Vec<usize> = batches
+        .iter()
+        .map(|batch| batch.as_array().expect("batch should be an array").len())
+        .collect();
+    assert_eq!(sizes, vec![3, 3, 3, 1]);
+    assert_eq!(batches[0][0]["n"], 1);
+    assert_eq!(batches[3][0]["n"], 10);
+}
+
+#[test]
+fn test_chunk_size_wraps_non_json_output_as_strings() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "line", "-F", "logfmt", "--chunk-size", "2"],
+        "alpha\nbeta\ngamma\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "stdout: {}", stdout);
+    let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert!(first[0].as_str().unwrap().contains("alpha"));
+    assert!(first[1].as_str().unwrap().contains("beta"));
+}
+
+#[test]
+fn test_table_output_aligns_and_truncates() {
+    let input = r#"{"level":"info","msg":"service started","code":7}
+{"level":"error","msg":"connection refused by upstream","code":502}
+"#;
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "table",
+            "-k",
+            "level,msg,code",
+            "--max-field-length",
+            "10",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "level  msg         code",
+            "-----  ----------  ----",
+            "info   service s…     7",
+            "error  connectio…   502",
+        ]
+    );
+}
+
+#[test]
+fn test_pretty_tables_draws_box_borders() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--pretty-tables", "-k", "id"],
+        "{\"id\":\"a\"}\n{\"id\":\"bc\"}\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec!["┌────┐", "│ id │", "├────┤", "│ a  │", "│ bc │", "└────┘"]
+    );
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--pretty-tables", "-F", "logfmt"],
+        "{\"id\":\"a\"}\n",
+    );
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--pretty-tables"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_csv_header_only_when_no_events_are_output() {
+    for extra in [&[][..], &["--parallel"][..]] {
+        let mut args = vec!["-f", "json", "-F", "csv", "-k", "a,b", "--filter", "false"];
+        args.extend_from_slice(extra);
+        let (stdout, stderr, exit_code) = run_kelora_with_input(&args, "{\"a\":1,\"b\":2}\n");
+        assert_eq!(exit_code, 0, "stderr: {}", stderr);
+        assert_eq!(stdout, "a,b\n", "args: {:?}", args);
+    }
+}
+
+#[test]
+fn test_formatter_tail_written_after_take_limit() {
+    let input: String = (1..=5).map(|n| format!("{{\"n\":{}}}\n", n)).collect();
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-F", "table", "-k", "n", "-n", "2"], &input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout, "n\n-\n1\n2\n");
+}
+
+#[test]
+fn test_formatter_tail_written_on_strict_abort() {
+    let input = "{\"n\":1}\nnot json\n{\"n\":3}\n";
+
+    let (stdout, _stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--strict", "-F", "table", "-k", "n"], input);
+    assert_ne!(exit_code, 0);
+    assert_eq!(stdout, "n\n-\n1\n");
+
+    // The JSON array batching writer closes its partial batch too
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--strict", "-F", "json", "--chunk-size", "10"],
+        input,
+    );
+    assert_ne!(exit_code, 0);
+    assert_eq!(stdout, "[{\"n\":1}]\n");
+}
+
+#[test]
+fn test_json_flat_flattens_nested_output() {
+    let input = r#"{"user":{"name":"ana","roles":["admin","dev"]},"status":200}"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&["-f", "json", "--json-flat"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        r#"{"user.name":"ana","user.roles.0":"admin","user.roles.1":"dev","status":200}"#
+    );
+
+    let (stdout, _stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--output-jsonl-flat=_"], input);
+    assert_eq!(exit_code, 0);
+    assert!(
+        stdout.contains(r#""user_roles_1":"dev""#),
+        "stdout: {}",
+        stdout
+    );
+
+    let (_stdout, _stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "-F", "logfmt", "--json-flat"], input);
+    assert_eq!(exit_code, 2);
+}
+
+#[test]
+fn test_json_flat_warns_when_flattened_keys_collide() {
+    let input = r#"{"a.b":1,"a":{"b":2}}"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&["-f", "json", "--json-flat"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.trim(), r#"{"a.b":2}"#);
+    assert!(
+        stderr.contains("--json-flat: field 'a.b' appears twice after flattening"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_json_flat_collision_policies() {
+    let input = r#"{"a.b":1,"a":{"b":2}}"#;
+    for (policy, expected) in [
+        ("last", r#"{"a.b":2}"#),
+        ("first", r#"{"a.b":1}"#),
+        ("suffix", r#"{"a.b":1,"a.b_2":2}"#),
+    ] {
+        let (stdout, stderr, exit_code) =
+            run_kelora_with_input(&["-f", "json", "--json-flat", "--collision", policy], input);
+        assert_eq!(exit_code, 0, "--collision {}: {}", policy, stderr);
+        assert_eq!(stdout.trim(), expected, "--collision {}", policy);
+        // Only the lossy default is worth a warning
+        assert_eq!(
+            stderr.contains("appears twice after flattening"),
+            policy == "last",
+            "--collision {}: {}",
+            policy,
+            stderr
+        );
+    }
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--json-flat",
+            "--collision",
+            "error",
+            "--strict",
+        ],
+        input,
+    );
+    assert_ne!(exit_code, 0);
+    assert_eq!(stdout, "");
+    assert!(
+        stderr.contains("field 'a.b' already exists (--collision error)"),
+        "stderr: {}",
+        stderr
+    );
+
+    // Without --strict the event is counted as an error and skipped
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--json-flat", "--collision", "error"],
+        &format!("{}\n{{\"c\":3}}", input),
+    );
+    assert_eq!(exit_code, 1);
+    assert_eq!(stdout.trim(), r#"{"c":3}"#);
+
+    // The older --field-prefix-conflict spelling selects the same policy
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--json-flat",
+            "--field-prefix-conflict",
+            "skip",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0);
+    assert_eq!(stdout.trim(), r#"{"a.b":1}"#);
+}
+
+#[test]
+fn test_ts_column_display_flags() {
+    let input = r#"{"ts":"2024-01-01T10:00:00Z","received_at":"2024-01-01T10:00:03Z","msg":"hi"}"#;
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--ts-key", "received_at"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(
+        stdout.starts_with("received_at='2024-01-01T10:00:03Z' ts="),
+        "stdout: {}",
+        stdout
+    );
+
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(&["-f", "json", "--no-ts"], input);
+    assert_eq!(exit_code, 0);
+    assert_eq!(stdout.trim(), "msg='hi' received_at='2024-01-01T10:00:03Z'");
+
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-b", "--show-key-for-ts", "--ts-width", "10"],
+        input,
+    );
+    assert_eq!(exit_code, 0);
+    assert_eq!(stdout.trim(), "ts=2024-01-01 hi 2024-01-01T10:00:03Z");
+
+    // --no-ts filters nothing: --since still uses the event timestamp
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--no-ts", "--since", "2024-01-02T00:00:00Z"],
+        input,
+    );
+    assert_eq!(exit_code, 0);
+    assert_eq!(stdout, "");
+}
+
+#[test]
+fn test_apache_access_output_round_trips_combined_input() {
+    let input = r#"192.168.1.1 - alice [10/Oct/2000:13:55:36 +0000] "GET /index.html HTTP/1.0" 200 2326 "http://example.com/" "Mozilla/4.08""#;
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "combined", "-F", "apache-access"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.trim(), input);
+
+    // Anonymize and reparse the output
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "combined",
+            "-e",
+            "e.ip = \"0.0.0.0\"; e.user = ()",
+            "-F",
+            "nginx-access",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        r#"0.0.0.0 - - [10/Oct/2000:13:55:36 +0000] "GET /index.html HTTP/1.0" 200 2326 "http://example.com/" "Mozilla/4.08" "-""#
+    );
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(
+        &["-f", "combined", "-F", "json", "-k", "ip,status"],
+        &stdout,
+    );
+    assert_eq!(exit_code, 0);
+    assert_eq!(stdout.trim(), r#"{"ip":"0.0.0.0","status":200}"#);
+}
+
+#[test]
+fn test_xml_output_escapes_and_nests_json_input() {
+    let input = r#"{"level":"ERROR","msg":"<b>disk</b> & more","user":{"id":7,"tags":["a","b"]}}"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&["-f", "json", "-F", "xml"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "<event><level>ERROR</level><msg>&lt;b&gt;disk&lt;/b&gt; &amp; more</msg>\
+         <user><id>7</id><tags>a</tags><tags>b</tags></user></event>"
+    );
+}
@@ -2397,3 +2397,90 @@ fn test_implicit_window_stays_parallel() {
         out
     );
 }
+
+/// Timestamped input shuffled within blocks of five events
+fn locally_shuffled_events(count: usize) -> String {
+    const BLOCK_ORDER: [usize; 5] = [4, 2, 0, 3, 1];
+    (0..count)
+        .map(|i| {
+            let n = i - i % 5 + BLOCK_ORDER[i % 5];
+            format!(
+                "{{\"timestamp\":\"2024-01-01T00:{:02}:{:02}Z\",\"n\":{}}}\n",
+                n / 60,
+                n % 60,
+                n
+            )
+        })
+        .collect()
+}
+
+fn output_numbers(stdout: &str) -> Vec<i64> {
+    stdout
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            value["n"].as_i64().unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_parallel_order_by_timestamp_restores_chronological_order() {
+    let input = locally_shuffled_events(100);
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-j",
+            "-F",
+            "json",
+            "--parallel",
+            "--unordered",
+            "--threads",
+            "1",
+            "--batch-size",
+            "5",
+            "--parallel-order-by",
+            "timestamp",
+        ],
+        &input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    // Displacement stays within the buffer, so the order is exact
+    assert_eq!(output_numbers(&stdout), (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_parallel_order_by_timestamp_is_near_chronological_across_workers() {
+    let input = locally_shuffled_events(400);
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-j",
+            "-F",
+            "json",
+            "--parallel",
+            "--unordered",
+            "--threads",
+            "4",
+            "--batch-size",
+            "10",
+            "--parallel-order-by",
+            "timestamp",
+        ],
+        &input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let numbers = output_numbers(&stdout);
+    let mut sorted = numbers.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..400).collect::<Vec<_>>());
+    let inversions = numbers.windows(2).filter(|w| w[1] < w[0]).count();
+    assert!(inversions <= 20, "{} inversions: {:?}", inversions, numbers);
+}
+
+#[test]
+fn test_parallel_order_by_requires_unordered() {
+    let (_stdout, _stderr, exit_code) = run_kelora_with_input(
+        &["-j", "--parallel", "--parallel-order-by", "timestamp"],
+        "{}\n",
+    );
+    assert_eq!(exit_code, 2);
+}
@@ -0,0 +1,227 @@
+mod common;
+use common::*;
+use std::fs::File;
+use std::io::Write;
+use tempfile::TempDir;
+
+const CAPTURE_START_US: u64 = 1_705_314_600_000_000; // 2024-01-15T10:30:00Z
+
+/// One TCP connection between a client and port 80 of a server
+struct Connection {
+    client: [u8; 4],
+    server: [u8; 4],
+    client_port: u16,
+    client_seq: u32,
+    server_seq: u32,
+}
+
+impl Connection {
+    fn new(client: [u8; 4], server: [u8; 4], client_port: u16) -> Self {
+        Self {
+            client,
+            server,
+            client_port,
+            client_seq: 1000,
+            server_seq: 5000,
+        }
+    }
+
+    /// A pcap packet record carrying `payload` in one direction
+    fn send(&mut self, offset_us: u64, from_client: bool, flags: u8, payload: &[u8]) -> Vec<u8> {
+        let (src, dst, sport, dport, seq) = if from_client {
+            (
+                self.client,
+                self.server,
+                self.client_port,
+                80,
+                &mut self.client_seq,
+            )
+        } else {
+            (
+                self.server,
+                self.client,
+                80,
+                self.client_port,
+                &mut self.server_seq,
+            )
+        };
+
+        let mut frame = vec![0x02, 0, 0, 0, 0, 0x01, 0x02, 0, 0, 0, 0, 0x02, 0x08, 0x00];
+        frame.extend([0x45, 0]);
+        frame.extend(((40 + payload.len()) as u16).to_be_bytes());
+        frame.extend([0, 1, 0x40, 0, 64, 6, 0, 0]);
+        frame.extend(src);
+        frame.extend(dst);
+        frame.extend(sport.to_be_bytes());
+        frame.extend(dport.to_be_bytes());
+        frame.extend(seq.to_be_bytes());
+        frame.extend([0, 0, 0, 0, 0x50, flags, 0xFF, 0xFF, 0, 0, 0, 0]);
+        frame.extend(payload);
+        // SYN and FIN take up one sequence number each
+        let consumed = payload.len() as u32 + u32::from(flags & 0x03 != 0);
+        *seq = seq.wrapping_add(consumed);
+
+        let ts = CAPTURE_START_US + offset_us;
+        let mut record = Vec::new();
+        record.extend(((ts / 1_000_000) as u32).to_le_bytes());
+        record.extend(((ts % 1_000_000) as u32).to_le_bytes());
+        record.extend((frame.len() as u32).to_le_bytes());
+        record.extend((frame.len() as u32).to_le_bytes());
+        record.extend(frame);
+        record
+    }
+}
+
+const SYN: u8 = 0x02;
+const FIN_ACK: u8 = 0x11;
+const PSH_ACK: u8 = 0x18;
+
+fn sample_capture() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend(0xA1B2_C3D4u32.to_le_bytes());
+    bytes.extend(2u16.to_le_bytes());
+    bytes.extend(4u16.to_le_bytes());
+    bytes.extend([0; 8]);
+    bytes.extend(65535u32.to_le_bytes());
+    bytes.extend(1u32.to_le_bytes()); // Ethernet
+
+    let mut web = Connection::new([192, 168, 1, 10], [10, 0, 0, 80], 51000);
+    let mut api = Connection::new([192, 168, 1, 11], [10, 0, 0, 80], 51001);
+    let packets = [
+        web.send(0, true, SYN, b""),
+        web.send(100, false, SYN, b""),
+        web.send(
+            200,
+            true,
+            PSH_ACK,
+            b"GET /index.html?q=1 HTTP/1.1\r\nHost: example.com\r\nUser-Agent: curl/8.5\r\n\r\n",
+        ),
+        // A second connection interleaves with the first
+        api.send(250, true, SYN, b""),
+        api.send(260, false, SYN, b""),
+        api.send(
+            300,
+            true,
+            PSH_ACK,
+            b"POST /api/login HTTP/1.1\r\nHost: example.com\r\nContent-Length: 9\r\n\r\nuser=bob&",
+        ),
+        // The response is split across two segments
+        web.send(
+            400,
+            false,
+            PSH_ACK,
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n",
+        ),
+        web.send(
+            450,
+            false,
+            PSH_ACK,
+            b"Content-Length: 11\r\n\r\nhello world",
+        ),
+        api.send(
+            500,
+            false,
+            PSH_ACK,
+            b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n",
+        ),
+        // Keep-alive: a second request on the first connection, never answered
+        web.send(600, true, PSH_ACK, b"GET /favicon.ico HTTP/1.1\r\n\r\n"),
+        api.send(700, true, FIN_ACK, b""),
+        api.send(710, false, FIN_ACK, b""),
+    ];
+    for packet in packets {
+        bytes.extend(packet);
+    }
+    bytes
+}
+
+fn write_capture(dir: &TempDir, name: &str, bytes: &[u8]) -> String {
+    let path = dir.path().join(name);
+    File::create(&path).unwrap().write_all(bytes).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn parse_json_lines(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_pcap_emits_one_event_per_http_exchange() {
+    let dir = TempDir::new().unwrap();
+    let path = write_capture(&dir, "web.pcap", &sample_capture());
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_files(&["-f", "pcap", "-F", "json"], &[&path]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let events = parse_json_lines(&stdout);
+    assert_eq!(events.len(), 3, "stdout: {}", stdout);
+
+    let first = &events[0];
+    assert_eq!(first["timestamp_us"], CAPTURE_START_US + 200);
+    assert_eq!(first["src_ip"], "192.168.1.10");
+    assert_eq!(first["src_port"], 51000);
+    assert_eq!(first["dst_ip"], "10.0.0.80");
+    assert_eq!(first["dst_port"], 80);
+    assert_eq!(first["method"], "GET");
+    assert_eq!(first["path"], "/index.html?q=1");
+    assert_eq!(first["http_version"], "1.1");
+    assert_eq!(first["status_code"], 200);
+    assert_eq!(
+        first["request_headers"],
+        serde_json::json!({"host": "example.com", "user-agent": "curl/8.5"})
+    );
+    assert_eq!(
+        first["response_headers"],
+        serde_json::json!({"content-type": "text/html", "content-length": "11"})
+    );
+
+    assert_eq!(events[1]["method"], "POST");
+    assert_eq!(events[1]["src_ip"], "192.168.1.11");
+    assert_eq!(events[1]["status_code"], 401);
+
+    // Requests still waiting for a response come out at the end
+    assert_eq!(events[2]["path"], "/favicon.ico");
+    assert!(events[2].get("status_code").is_none(), "{}", events[2]);
+    assert!(events[2].get("response_headers").is_none());
+}
+
+#[test]
+fn test_pcap_fields_work_in_filters_and_parallel_mode() {
+    let dir = TempDir::new().unwrap();
+    let path = write_capture(&dir, "web.pcap", &sample_capture());
+
+    for extra in [&[][..], &["--parallel", "--batch-size", "1"][..]] {
+        let mut args = vec![
+            "-f",
+            "pcap",
+            "--filter",
+            "e.has(\"status_code\") && e.status_code >= 400",
+            "-k",
+            "method,path,status_code",
+            "-F",
+            "csvnh",
+        ];
+        args.extend_from_slice(extra);
+        let (stdout, stderr, exit_code) = run_kelora_with_files(&args, &[&path]);
+        assert_eq!(exit_code, 0, "stderr: {}", stderr);
+        assert_eq!(stdout.trim(), "POST,/api/login,401", "args: {:?}", args);
+    }
+}
+
+#[test]
+fn test_pcap_rejects_files_that_are_not_captures() {
+    let dir = TempDir::new().unwrap();
+    let path = write_capture(&dir, "notes.txt", b"this is not a packet capture\n");
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_files(&["-f", "pcap"], &[&path]);
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("not a pcap capture"), "stderr: {}", stderr);
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(&["-f", "json,pcap"], "{}\n");
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("pcap"), "stderr: {}", stderr);
+}
@@ -0,0 +1,183 @@
+mod common;
+use common::*;
+
+const SLOW_EXEC: &str = "let total = 0; for i in 0..20000 { total += i; } e.total = total;";
+
+fn profile_input() -> String {
+    (0..50)
+        .map(|i| format!("{{\"n\":{}}}\n", i))
+        .collect::<String>()
+}
+
+/// Parse `Stage[N] label: duration (P% of pipeline time)` lines into (label, percent).
+fn parse_profile(stderr: &str) -> Vec<(String, u32)> {
+    stderr
+        .lines()
+        .filter(|line| line.starts_with("Stage["))
+        .map(|line| {
+            let (head, rest) = line.split_once(": ").expect("stage line has a label");
+            let label = head.split_once("] ").expect("stage index").1.to_string();
+            let percent = rest
+                .rsplit_once('(')
+                .and_then(|(_, tail)| tail.split_once('%'))
+                .map(|(pct, _)| pct.parse::<u32>().expect("integer percentage"))
+                .expect("stage line has a percentage");
+            (label, percent)
+        })
+        .collect()
+}
+
+fn assert_slow_stage_dominates(stderr: &str) {
+    let stages = parse_profile(stderr);
+    assert_eq!(stages.len(), 3, "expected three stage lines: {stderr}");
+    assert_eq!(stages[0].0, "filter");
+    assert_eq!(stages[1].0, "slow");
+    assert_eq!(stages[2].0, "exec");
+
+    let slowest = stages
+        .iter()
+        .max_by_key(|(_, percent)| *percent)
+        .expect("at least one stage");
+    assert_eq!(slowest.0, "slow", "slow stage should dominate: {stderr}");
+}
+
+#[test]
+fn test_pipeline_profile_reports_slow_stage_sequential() {
+    let input = profile_input();
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--filter",
+            "true",
+            "--stage-label",
+            "slow",
+            "--exec",
+            SLOW_EXEC,
+            "--exec",
+            "e.done = true;",
+            "--pipeline-profile",
+            "--no-emoji",
+        ],
+        &input,
+    );
+
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+    assert_eq!(stdout.lines().count(), 50);
+    assert!(
+        stderr.contains("kelora: Pipeline profile:"),
+        "missing profile header: {stderr}"
+    );
+    assert_slow_stage_dominates(&stderr);
+}
+
+#[test]
+fn test_pipeline_profile_aggregates_parallel_workers() {
+    let input = profile_input();
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--parallel",
+            "--threads",
+            "2",
+            "--batch-size",
+            "5",
+            "--filter",
+            "true",
+            "--stage-label",
+            "slow",
+            "--exec",
+            SLOW_EXEC,
+            "--exec",
+            "e.done = true;",
+            "--pipeline-profile",
+        ],
+        &input,
+    );
+
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+    assert_slow_stage_dominates(&stderr);
+}
+
+#[test]
+fn test_stage_label_requires_following_exec() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--exec", "e.a = 1;", "--stage-label", "late"],
+        "{\"n\":1}\n",
+    );
+
+    assert_eq!(exit_code, 2);
+    assert!(
+        stderr.contains("--stage-label 'late' must be followed by --exec or --exec-file"),
+        "stderr: {stderr}"
+    );
+}
+
+/// Parse a duration like `12ns`, `1.5µs`, `342.0ms` or `2.50s` into seconds.
+fn parse_duration_secs(text: &str) -> f64 {
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .expect("duration has a unit");
+    let (value, unit) = text.split_at(split);
+    let value: f64 = value.parse().expect("numeric duration");
+    let scale = match unit {
+        "ns" => 1e-9,
+        "µs" => 1e-6,
+        "ms" => 1e-3,
+        "s" => 1.0,
+        other => panic!("unexpected unit {other}"),
+    };
+    value * scale
+}
+
+#[test]
+fn test_benchmark_lists_every_stage_with_duration() {
+    let input = profile_input();
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--filter",
+            "true",
+            "--exec",
+            SLOW_EXEC,
+            "--benchmark",
+            "--no-emoji",
+        ],
+        &input,
+    );
+
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+    assert_eq!(stdout.lines().count(), 50);
+    assert!(
+        stderr.contains("kelora: Benchmark:"),
+        "missing benchmark header: {stderr}"
+    );
+
+    let report: Vec<(&str, f64)> = stderr
+        .lines()
+        .skip_while(|line| !line.contains("Benchmark:"))
+        .skip(1)
+        .take(6)
+        .filter_map(|line| line.split_once(": "))
+        .map(|(label, rest)| {
+            let duration = rest.split_whitespace().next().expect("duration");
+            (label, parse_duration_secs(duration))
+        })
+        .collect();
+    let labels: Vec<&str> = report.iter().map(|(label, _)| *label).collect();
+    assert_eq!(
+        labels,
+        vec![
+            "parse",
+            "Stage[0] filter",
+            "Stage[1] exec",
+            "format",
+            "write",
+            "total"
+        ],
+        "stderr: {stderr}"
+    );
+    assert!(report.iter().all(|(_, secs)| *secs >= 0.0));
+}
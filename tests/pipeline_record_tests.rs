@@ -0,0 +1,88 @@
+mod common;
+use common::*;
+use std::fs::File;
+use std::io::Write;
+use tempfile::TempDir;
+
+fn write_log(dir: &TempDir, name: &str, content: &str) -> String {
+    let path = dir.path().join(name);
+    File::create(&path)
+        .unwrap()
+        .write_all(content.as_bytes())
+        .unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_pipeline_replay_reproduces_recorded_multi_file_run() {
+    let dir = TempDir::new().unwrap();
+    let files = [
+        write_log(&dir, "a.log", "level=info msg=boot\nlevel=warn msg=slow\n"),
+        // A line starting with '#' must not be mistaken for a file header
+        write_log(
+            &dir,
+            "b.log",
+            "# FILE: not-a-header\nlevel=error msg=crash\n",
+        ),
+        // No trailing newline on the last line
+        write_log(&dir, "c.log", "level=info msg=recovered"),
+    ];
+    let recording = dir.path().join("run.rec.gz");
+    let recording = recording.to_str().unwrap();
+    let script = "e.source = meta.filename";
+
+    let mut record_args = vec![
+        "-f",
+        "line",
+        "-F",
+        "json",
+        "-e",
+        script,
+        "--pipeline-record",
+        recording,
+    ];
+    record_args.extend(files.iter().map(String::as_str));
+    let (recorded, stderr, exit_code) = run_kelora(&record_args);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(recorded.lines().count(), 5);
+
+    let (replayed, stderr, exit_code) = run_kelora(&[
+        "-f",
+        "line",
+        "-F",
+        "json",
+        "-e",
+        script,
+        "--pipeline-replay",
+        recording,
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(replayed, recorded);
+    assert!(replayed.contains("b.log"));
+    assert!(replayed.contains("# FILE: not-a-header"));
+}
+
+#[test]
+fn test_pipeline_replay_auto_detects_and_records_stdin() {
+    let dir = TempDir::new().unwrap();
+    let recording = dir.path().join("stdin.rec.gz");
+    let recording = recording.to_str().unwrap();
+    let input = "{\"n\":1}\n{\"n\":2}\n";
+
+    let (recorded, stderr, exit_code) =
+        run_kelora_with_input(&["-F", "json", "--pipeline-record", recording], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let (replayed, stderr, exit_code) = run_kelora(&["-F", "json", "--pipeline-replay", recording]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(replayed, recorded);
+    assert_eq!(replayed, input);
+}
+
+#[test]
+fn test_pipeline_replay_rejects_input_files() {
+    let dir = TempDir::new().unwrap();
+    let log = write_log(&dir, "a.log", "x\n");
+    let (_stdout, _stderr, exit_code) = run_kelora(&["--pipeline-replay", "rec.gz", &log]);
+    assert_eq!(exit_code, 2);
+}
@@ -218,3 +218,59 @@ api_1    | Starting server"#;
     assert_eq!(parsed[1]["src"], "api_1");
     assert_eq!(parsed[1]["service_type"], "backend");
 }
+
+#[test]
+fn test_strip_prefix_regex_captures_container_name() {
+    let input = r#"api-7f9c {"level":"info","msg":"ready"}
+worker-2   {"level":"warn","msg":"slow job"}"#;
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--strip-prefix-re",
+            r"^\S+\s+",
+            "--prefix-field",
+            "source",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let parsed: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Should be valid JSON"))
+        .collect();
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0]["source"], "api-7f9c");
+    assert_eq!(parsed[0]["msg"], "ready");
+    assert_eq!(parsed[1]["source"], "worker-2");
+    assert_eq!(parsed[1]["level"], "warn");
+}
+
+#[test]
+fn test_strip_prefix_fixed_width_without_field() {
+    let input = "web_1    | GET /health 200\ndb_1     | Connection established";
+
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["--strip-prefix", "11", "-f", "line", "-F", "json"], input);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let parsed: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Should be valid JSON"))
+        .collect();
+    assert_eq!(parsed[0]["line"], "GET /health 200");
+    assert_eq!(parsed[1]["line"], "Connection established");
+    assert_eq!(parsed[0].as_object().unwrap().len(), 1);
+}
+
+#[test]
+fn test_prefix_field_requires_strip_prefix() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["--prefix-field", "source", "-f", "line"], "x\n");
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stderr.contains("--prefix-field"), "stderr: {}", stderr);
+}
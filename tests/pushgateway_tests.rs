@@ -0,0 +1,176 @@
+mod common;
+use common::*;
+
+#[cfg(feature = "pushgateway")]
+use mockito::Matcher;
+
+const INPUT: &str = r#"{"status": 200}
+{"status": 500}
+{"status": 200}
+"#;
+
+#[test]
+fn test_metrics_prometheus_prints_exposition_format() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--metrics=prometheus",
+            "-e",
+            "track_inc(\"events\"); track_freq(\"status\", e.status)",
+        ],
+        INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "# TYPE kelora_events gauge\n\
+         kelora_events 3\n\
+         # TYPE kelora_status gauge\n\
+         kelora_status{key=\"200\"} 2\n\
+         kelora_status{key=\"500\"} 1"
+    );
+}
+
+#[test]
+#[cfg(feature = "pushgateway")]
+fn test_pushgateway_put_with_grouping_labels() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("PUT", "/metrics/job/nightly/instance/ci-runner/branch/main")
+        .match_header("content-type", "text/plain; version=0.0.4")
+        .match_body(Matcher::Regex(
+            r#"kelora_status\{key="500"\} 1"#.to_string(),
+        ))
+        .with_status(200)
+        .create();
+
+    let endpoint = server.url();
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--metrics=prometheus",
+            "--metrics-endpoint",
+            &endpoint,
+            "--pushgateway-job",
+            "nightly",
+            "--pushgateway-grouping",
+            "instance=ci-runner",
+            "--pushgateway-grouping",
+            "branch=main",
+            "-e",
+            "track_freq(\"status\", e.status)",
+        ],
+        INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(
+        stdout.trim().is_empty(),
+        "pushed metrics are not printed: {}",
+        stdout
+    );
+    mock.assert();
+}
+
+#[test]
+#[cfg(feature = "pushgateway")]
+fn test_output_format_shorthand_pushes_with_default_job() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock(
+            "PUT",
+            Matcher::Regex(r"^/metrics/job/kelora/instance/[^/]+$".to_string()),
+        )
+        .match_body(Matcher::Regex("kelora_events 3".to_string()))
+        .with_status(202)
+        .create();
+
+    let endpoint = server.url();
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "prometheus-pushgateway",
+            "--metrics-endpoint",
+            &endpoint,
+            "-e",
+            "track_inc(\"events\")",
+        ],
+        INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.trim().is_empty(), "stdout: {}", stdout);
+    mock.assert();
+}
+
+#[test]
+#[cfg(feature = "pushgateway")]
+fn test_pushgateway_error_is_reported() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("PUT", Matcher::Any)
+        .with_status(400)
+        .with_body("text format parsing error")
+        .create();
+
+    let endpoint = server.url();
+    let (_stdout, stderr, _exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--metrics=prometheus",
+            "--metrics-endpoint",
+            &endpoint,
+            "--pushgateway-job",
+            "ci",
+            "-e",
+            "track_inc(\"events\")",
+        ],
+        INPUT,
+    );
+    assert!(
+        stderr.contains("Pushgateway rejected metrics (400"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("text format parsing error"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_metrics_endpoint_requires_prometheus_format_and_job() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-m",
+            "--metrics-endpoint",
+            "http://localhost:9091",
+        ],
+        INPUT,
+    );
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(
+        stderr.contains("--metrics=prometheus"),
+        "stderr: {}",
+        stderr
+    );
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--metrics=prometheus",
+            "--metrics-endpoint",
+            "http://localhost:9091",
+        ],
+        INPUT,
+    );
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stderr.contains("--pushgateway-job"), "stderr: {}", stderr);
+}
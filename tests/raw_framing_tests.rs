@@ -0,0 +1,83 @@
+mod common;
+use common::*;
+
+const CORE_DUMP: &str = "\
+=====
+thread 1
+  frame #0 main
+  frame #1 start
+=====
+thread 2
+  frame #0 worker
+=====
+";
+
+fn raw_values(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).expect("valid JSON output");
+            value["raw"].as_str().expect("raw field").to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn test_raw_separator_frames_multi_line_records() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "raw", "--raw-separator", "^=====$", "-F", "json"],
+        CORE_DUMP,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        raw_values(&stdout),
+        vec![
+            "thread 1\n  frame #0 main\n  frame #1 start",
+            "thread 2\n  frame #0 worker",
+        ]
+    );
+}
+
+#[test]
+fn test_raw_separator_with_parallel() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "raw",
+            "--raw-separator",
+            "^=====$",
+            "-F",
+            "json",
+            "--parallel",
+        ],
+        CORE_DUMP,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(raw_values(&stdout).len(), 2);
+}
+
+#[test]
+fn test_raw_bytes_frames_fixed_size_records() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "raw", "--raw-bytes", "8", "-F", "json"],
+        "HDR1\nPAYLOAD-0123456789\nEND\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        raw_values(&stdout),
+        vec!["HDR1\nPAY", "LOAD-012", "3456789\n", "END"]
+    );
+}
+
+#[test]
+fn test_raw_framing_requires_raw_format() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "line", "--raw-bytes", "16"], "abc\n");
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("-f raw"), "stderr: {}", stderr);
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "raw", "--raw-separator", "(unclosed"], "abc\n");
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--raw-separator"), "stderr: {}", stderr);
+}
@@ -0,0 +1,74 @@
+mod common;
+use common::*;
+
+fn objects(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_record_separator_blank_reads_pretty_printed_json() {
+    let input = "{\n  \"level\": \"info\",\n  \"msg\": \"started\"\n}\n\n\n{\n  \"level\": \"error\",\n  \"msg\": \"failed\"\n}\n";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--record-separator", "blank", "-F", "json"],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(
+        objects(&stdout),
+        vec![
+            serde_json::json!({"level": "info", "msg": "started"}),
+            serde_json::json!({"level": "error", "msg": "failed"}),
+        ]
+    );
+}
+
+#[test]
+fn test_record_separator_regex_frames_records() {
+    let input = "---\nlevel=info\nmsg=started\n---\nlevel=warn\nmsg=slow\n---\n";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "raw", "--record-separator", "^---$", "-F", "json"],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let raws: Vec<String> = objects(&stdout)
+        .iter()
+        .map(|event| event["raw"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        raws,
+        vec!["level=info\nmsg=started", "level=warn\nmsg=slow"]
+    );
+
+    // The alias works too, and the last record needs no trailing separator
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--input-record-separator",
+            "^---$",
+            "-k",
+            "n",
+            "-F",
+            "csvnh",
+        ],
+        "{\"n\":\n1}\n---\n{\"n\":\n2}\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "1\n2");
+}
+
+#[test]
+fn test_record_separator_rejects_invalid_regex_and_multiline() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["--record-separator", "(unclosed"], "abc\n");
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stderr.contains("--record-separator"), "stderr: {}", stderr);
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["--record-separator", "blank", "-M", "indent"], "abc\n");
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--record-separator"), "stderr: {}", stderr);
+}
@@ -0,0 +1,150 @@
+mod common;
+use common::*;
+
+use std::process::{Command, Stdio};
+
+fn numbered_events(count: usize) -> String {
+    (1..=count).map(|n| format!("{{\"n\":{}}}\n", n)).collect()
+}
+
+fn numbers(stdout: &str) -> Vec<i64> {
+    stdout
+        .lines()
+        .map(|line| {
+            let event: serde_json::Value = serde_json::from_str(line).expect("valid JSON output");
+            event["n"].as_i64().expect("n field")
+        })
+        .collect()
+}
+
+/// Run with `KELORA_SEED` pinned so the sample is reproducible
+fn run_kelora_seeded(args: &[&str], input: &str, seed: &str) -> (String, String, i32) {
+    use std::io::Write;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_kelora"))
+        .args(args)
+        .env("KELORA_SEED", seed)
+        .env("LLVM_PROFILE_FILE", "/dev/null")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start kelora");
+    child
+        .stdin
+        .as_mut()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().expect("Failed to read output");
+    (
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn assert_sample(sample: &[i64], size: usize, max: i64) {
+    assert_eq!(sample.len(), size, "sample: {:?}", sample);
+    assert!(
+        sample.windows(2).all(|pair| pair[0] < pair[1]),
+        "sample not in input order: {:?}",
+        sample
+    );
+    assert!(sample.iter().all(|&n| (1..=max).contains(&n)));
+}
+
+#[test]
+fn test_reservoir_outputs_exactly_n_events_in_input_order() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--reservoir",
+            "10",
+            "--with-stats",
+        ],
+        &numbered_events(1000),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_sample(&numbers(&stdout), 10, 1000);
+    assert_eq!(extract_events_filtered_from_stats(&stderr), 990);
+}
+
+#[test]
+fn test_reservoir_keeps_short_streams_whole() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--reservoir", "10"],
+        &numbered_events(4),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(numbers(&stdout), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn test_reservoir_samples_after_filters_and_is_seeded() {
+    let args = [
+        "-f",
+        "json",
+        "-F",
+        "json",
+        "--filter",
+        "e.n % 2 == 0",
+        "--reservoir",
+        "5",
+    ];
+    let input = numbered_events(200);
+    let (first, stderr, exit_code) = run_kelora_seeded(&args, &input, "7");
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let sample = numbers(&first);
+    assert_sample(&sample, 5, 200);
+    assert!(sample.iter().all(|n| n % 2 == 0), "sample: {:?}", sample);
+
+    let (second, _, _) = run_kelora_seeded(&args, &input, "7");
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_reservoir_take_limits_the_sample() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--reservoir", "10", "-n", "3"],
+        &numbered_events(100),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_sample(&numbers(&stdout), 3, 100);
+}
+
+#[test]
+fn test_reservoir_parallel_merges_worker_samples() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--parallel",
+            "--threads",
+            "4",
+            "--batch-size",
+            "50",
+            "--reservoir",
+            "20",
+            "--with-stats",
+        ],
+        &numbered_events(2000),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_sample(&numbers(&stdout), 20, 2000);
+    assert_eq!(extract_events_filtered_from_stats(&stderr), 1980);
+}
+
+#[test]
+fn test_reservoir_conflicts_with_span() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--reservoir", "5", "--span", "10"],
+        &numbered_events(20),
+    );
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+}
@@ -0,0 +1,97 @@
+mod common;
+use common::*;
+
+use std::fs;
+use tempfile::TempDir;
+
+const TYPE_ERROR_SCRIPT: &str = r#"let threshold = 500;
+if e.status >= threshold {
+    e.summary = "server errors: " - 1;
+}
+"#;
+
+#[test]
+fn test_rhai_lint_reports_type_error_with_location() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let script = temp_dir.path().join("enrich.rhai");
+    fs::write(&script, TYPE_ERROR_SCRIPT).expect("Failed to write script");
+    let script = script.to_str().unwrap();
+
+    let (stdout, stderr, exit_code) = run_kelora(&["rhai-lint", script]);
+    assert_eq!(exit_code, 1, "stdout: {}\nstderr: {}", stdout, stderr);
+    assert!(
+        stdout.contains(&format!("{}:3:", script)),
+        "expected file:line:column, got: {}",
+        stdout
+    );
+    assert!(stdout.contains("type error"), "stdout: {}", stdout);
+    assert!(stdout.contains("string and i64"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_rhai_lint_json_report_and_clean_scripts() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let clean = temp_dir.path().join("clean.rhai");
+    fs::write(
+        &clean,
+        "e.kind = if e.status >= 500 { \"error\" } else { \"ok\" };",
+    )
+    .expect("Failed to write script");
+    let broken = temp_dir.path().join("broken.rhai");
+    fs::write(&broken, "let x = ;\n").expect("Failed to write script");
+
+    let (stdout, stderr, exit_code) = run_kelora(&["rhai-lint", clean.to_str().unwrap()]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("1 script checked, no issues"));
+
+    let (stdout, _stderr, exit_code) = run_kelora(&[
+        "rhai-lint",
+        "--lint-format",
+        "json",
+        clean.to_str().unwrap(),
+        broken.to_str().unwrap(),
+    ]);
+    assert_eq!(exit_code, 1);
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON report");
+    assert_eq!(report["scripts"], 2);
+    assert_eq!(report["ok"], false);
+    let issues = report["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["source"], broken.to_str().unwrap());
+    assert_eq!(issues[0]["kind"], "syntax");
+    assert_eq!(issues[0]["line"], 1);
+}
+
+#[test]
+fn test_rhai_lint_flag_checks_inline_scripts_without_processing() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-j",
+            "--rhai-lint",
+            "--filter",
+            "e.status >= 500",
+            "-e",
+            "e.note = \"code \" - 1",
+        ],
+        "{\"status\":500}\n",
+    );
+    assert_eq!(exit_code, 1, "stderr: {}", stderr);
+    assert!(stdout.contains("--exec #1:1:"), "stdout: {}", stdout);
+    assert!(stdout.contains("2 scripts checked, 1 issue"));
+    // No events were processed
+    assert!(!stdout.contains("status"), "stdout: {}", stdout);
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-j", "--rhai-lint", "--filter", "e.status >= 500"],
+        "{\"status\":500}\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "1 script checked, no issues");
+}
+
+#[test]
+fn test_lint_format_requires_rhai_lint() {
+    let (_stdout, _stderr, exit_code) =
+        run_kelora_with_input(&["-j", "--lint-format", "json"], "{}\n");
+    assert_eq!(exit_code, 2);
+}
@@ -0,0 +1,89 @@
+mod common;
+use common::*;
+
+const INPUT: &str = r#"{"level":"INFO","msg":"started","user":"alice"}
+{"level":"DEBUG","msg":"cache miss"}
+{"level":"ERROR","msg":"boom","user":"bob"}
+"#;
+
+#[test]
+fn test_sample_run_traces_each_stage() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--filter",
+            "e.level != \"DEBUG\"",
+            "-e",
+            "e.who = e.user.to_upper()",
+            "--sample-run",
+            "2",
+        ],
+        INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    // First event passes both stages
+    assert!(stdout.contains("=== line 1 ===\nraw: {\"level\":\"INFO\""));
+    assert!(stdout.contains("--- parsed (sample 1)"));
+    assert!(stdout.contains("--- after stage 1 (filter)"));
+    assert!(stdout.contains("--- after stage 2 (exec)"));
+    assert!(stdout.contains("\"ALICE\""), "stdout: {}", stdout);
+
+    // Second event stops at the filter, and that uses up the samples
+    assert!(stdout.contains("--- parsed (sample 2)"));
+    assert!(stdout.contains("--- filtered at stage 1 (filter)"));
+    assert!(!stdout.contains("=== line 3 ==="), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_sample_run_suppresses_side_effects_and_metrics() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-m",
+            "-e",
+            "print(\"side effect\"); eprint(\"side effect\"); track_inc(\"events\")",
+            "--sample-run",
+            "1",
+        ],
+        INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("--- after stage 1 (exec)"));
+    assert!(!stdout.contains("side effect"), "stdout: {}", stdout);
+    assert!(!stderr.contains("side effect"), "stderr: {}", stderr);
+    assert!(!stderr.contains("Tracked metrics"), "stderr: {}", stderr);
+    assert!(!stdout.contains("Tracked metrics"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_sample_run_shows_parse_errors_and_stage_labels() {
+    let input = "not json\n{\"status\":500}\n";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--stage-label",
+            "classify",
+            "-e",
+            "e.bad = e.status >= 500",
+            "--sample-run",
+            "1",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("=== line 1 ===\nraw: not json\n--- parse error:"));
+    assert!(stdout.contains("--- parsed (sample 1)"));
+    assert!(stdout.contains("--- after stage 1 (classify)"));
+}
+
+#[test]
+fn test_sample_run_rejects_zero() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--sample-run", "0"], INPUT);
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stderr.contains("--sample-run"));
+}
@@ -0,0 +1,213 @@
+mod common;
+use common::*;
+
+use tempfile::TempDir;
+
+const SCHEMA: &str = r#"{
+  "type": "object",
+  "required": ["level", "status"],
+  "properties": {
+    "level": {"enum": ["INFO", "WARN", "ERROR"]},
+    "status": {"type": "integer", "minimum": 100}
+  }
+}"#;
+
+const EVENTS: &str = r#"{"level":"INFO","status":200,"msg":"ok"}
+{"level":"WARN","status":"slow","msg":"bad status type"}
+{"level":"ERROR","status":503,"msg":"ok too"}
+{"level":"INFO","msg":"missing status"}
+"#;
+
+fn schema_file(dir: &TempDir) -> String {
+    let path = dir.path().join("event.schema.json");
+    std::fs::write(&path, SCHEMA).expect("write schema");
+    path.to_str().unwrap().to_string()
+}
+
+fn messages(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).expect("valid JSON output");
+            value["msg"].as_str().unwrap_or_default().to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn test_schema_validate_skips_failing_events_and_exits_nonzero() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let schema = schema_file(&dir);
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--schema-validate", &schema],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 1, "stderr: {}", stderr);
+    assert_eq!(messages(&stdout), vec!["ok", "ok too"]);
+    assert!(
+        stderr.contains("2 events failed --schema-validate"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_schema_validate_reports_field_and_rule() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let schema = schema_file(&dir);
+    let (_stdout, stderr, _exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--schema-validate",
+            &schema,
+            "-v",
+        ],
+        EVENTS,
+    );
+    assert!(
+        stderr.contains("/status: \"slow\" is not of type \"integer\""),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("rule /properties/status/type"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("\"status\" is a required property"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_schema_on_fail_emit_and_halt() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let schema = schema_file(&dir);
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--schema",
+            &schema,
+            "--schema-on-fail",
+            "emit",
+        ],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 1, "stderr: {}", stderr);
+    assert_eq!(messages(&stdout).len(), 4);
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--schema-validate",
+            &schema,
+            "--schema-on-fail",
+            "halt",
+        ],
+        EVENTS,
+    );
+    assert_ne!(exit_code, 0);
+    assert_eq!(messages(&stdout), vec!["ok"]);
+    assert!(stderr.contains("/status"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_schema_on_fail_halt_counts_the_failure_once() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let schema = schema_file(&dir);
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--schema-validate",
+            &schema,
+            "--schema-on-fail",
+            "halt",
+        ],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 1, "stderr: {}", stderr);
+    assert_eq!(messages(&stdout), vec!["ok"]);
+    assert!(
+        stderr.contains("Schema errors: 1 total"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_schema_on_fail_halt_rejects_parallel() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let schema = schema_file(&dir);
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--schema-validate",
+            &schema,
+            "--schema-on-fail",
+            "halt",
+            "--parallel",
+        ],
+        EVENTS,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("--schema-on-fail halt is not supported with --parallel"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_schema_validate_passes_clean_input_and_sees_script_changes() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let schema = schema_file(&dir);
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--schema-validate",
+            &schema,
+            "--exec",
+            "if !e.has(\"status\") || type_of(e.status) != \"i64\" { e.status = 500 }",
+            "--keys",
+            "msg",
+        ],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    // --keys runs after validation, so the trimmed events still pass
+    assert_eq!(messages(&stdout).len(), 4);
+}
+
+#[test]
+fn test_schema_validate_rejects_bad_schema_file() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let path = dir.path().join("broken.json");
+    std::fs::write(&path, r#"{"type": "no-such-type"}"#).unwrap();
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--schema-validate", path.to_str().unwrap()],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stderr.contains("Invalid JSON Schema"), "stderr: {}", stderr);
+}
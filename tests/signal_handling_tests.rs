@@ -529,3 +529,113 @@ fn test_stats_printed_on_normal_exit() {
         "Stats should be printed to stderr on normal exit"
     );
 }
+
+#[test]
+fn test_sigint_flushes_formatter_tail() {
+    // Buffering formatters print their tail from finish(); a graceful Ctrl-C
+    // must still reach it. -F table holds rows until the batch fills or the
+    // run ends, so without the flush stdout would stay empty.
+    let mut child = Command::new(kelora_binary())
+        .env("LLVM_PROFILE_FILE", "/dev/null") // Disable profraw generation for subprocesses
+        .args(["-f", "json", "-F", "table", "-k", "n"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn kelora");
+
+    let child_pid = child.id();
+
+    // Keep stdin open with a slow producer so the signal lands mid-stream.
+    let mut stdin = child.stdin.take().expect("stdin");
+    let writer = thread::spawn(move || {
+        for n in 0..200 {
+            if stdin
+                .write_all(format!("{{\"n\":{}}}\n", n).as_bytes())
+                .is_err()
+            {
+                break;
+            }
+            let _ = stdin.flush();
+            thread::sleep(Duration::from_millis(20));
+        }
+    });
+
+    thread::sleep(Duration::from_millis(300));
+    Command::new("kill")
+        .args(["-INT", &child_pid.to_string()])
+        .output()
+        .expect("Failed to send SIGINT");
+
+    let output = child.wait_with_output().expect("Failed to read output");
+    let _ = writer.join();
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let exit_code = output.status.code().unwrap_or(-1);
+
+    assert_eq!(exit_code, 130, "stdout:\n{}", stdout);
+    let mut lines = stdout.lines();
+    // Numeric columns are right-aligned, header included
+    assert_eq!(
+        lines.next().map(str::trim),
+        Some("n"),
+        "stdout:\n{}",
+        stdout
+    );
+    assert!(
+        lines.next().is_some_and(|sep| sep.starts_with('-')),
+        "table separator expected, got stdout:\n{}",
+        stdout
+    );
+    assert!(lines.next().is_some(), "rows expected, got:\n{}", stdout);
+}
+
+#[test]
+fn test_broken_pipe_skips_formatter_tail() {
+    // With the reader gone there is nowhere to write the formatter tail: the
+    // run must end with the SIGPIPE exit code, not a write error or panic.
+    let input = (0..1000)
+        .map(|i| format!(r#"{{"n":{}}}"#, i))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut kelora_child = Command::new(kelora_binary())
+        .env("LLVM_PROFILE_FILE", "/dev/null") // Disable profraw generation for subprocesses
+        .args(["-f", "json", "-F", "table", "-k", "n"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn kelora");
+
+    let head_child = Command::new("head")
+        .args(["-n", "1"])
+        .stdin(kelora_child.stdout.take().unwrap())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn head");
+
+    if let Some(stdin) = kelora_child.stdin.as_mut() {
+        stdin
+            .write_all(input.as_bytes())
+            .expect("Failed to write to stdin");
+        drop(kelora_child.stdin.take());
+    }
+
+    let head_output = head_child
+        .wait_with_output()
+        .expect("Failed to wait for head");
+    assert_eq!(String::from_utf8_lossy(&head_output.stdout).trim(), "n");
+
+    let kelora_output = kelora_child
+        .wait_with_output()
+        .expect("Failed to wait for kelora");
+    let stderr = String::from_utf8_lossy(&kelora_output.stderr);
+    assert_eq!(
+        kelora_output.status.code().unwrap_or(-1),
+        141,
+        "stderr: {}",
+        stderr
+    );
+    assert!(!stderr.contains("panicked"), "stderr: {}", stderr);
+}
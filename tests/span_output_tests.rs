@@ -0,0 +1,246 @@
+mod common;
+use common::*;
+
+fn parse_json_lines(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+const COUNT_INPUT: &str = r#"{"user":"a","bytes":100}
+{"user":"b","bytes":250}
+{"user":"a","bytes":50}
+{"user":"c","bytes":400}
+{"user":"b"}
+"#;
+
+#[test]
+fn test_count_span_summary_replaces_members_with_aggregates() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--span",
+            "2",
+            "--span-output",
+            "summary",
+            "--span-summary",
+            "e.total_bytes = span_sum(\"bytes\"); e.min_bytes = span_min(\"bytes\"); \
+             e.max_bytes = span_max(\"bytes\"); e.with_bytes = span_count(\"bytes\"); \
+             e.users = span_values(\"user\"); e.n = span_count();",
+        ],
+        COUNT_INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let events = parse_json_lines(&stdout);
+    // Two full spans plus the final open span emitted at end of input.
+    assert_eq!(events.len(), 3, "stdout: {}", stdout);
+
+    assert_eq!(events[0]["_span_id"], "#0");
+    assert_eq!(events[0]["_span_n"], 2);
+    assert_eq!(events[0]["total_bytes"], 350);
+    assert_eq!(events[0]["min_bytes"], 100);
+    assert_eq!(events[0]["max_bytes"], 250);
+    assert_eq!(events[0]["users"], serde_json::json!(["a", "b"]));
+    assert_eq!(events[0]["n"], 2);
+
+    assert_eq!(events[1]["_span_id"], "#1");
+    assert_eq!(events[1]["total_bytes"], 450);
+
+    assert_eq!(events[2]["_span_id"], "#2");
+    assert_eq!(events[2]["_span_n"], 1);
+    assert_eq!(events[2]["total_bytes"], 0);
+    assert_eq!(events[2]["with_bytes"], 0);
+    assert!(events[2].get("min_bytes").is_none(), "unset min is ()");
+    assert!(
+        events.iter().all(|e| e.get("bytes").is_none()),
+        "member events must be suppressed: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_span_summary_respects_take() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--span",
+            "2",
+            "--span-output",
+            "summary",
+            "--take",
+            "1",
+        ],
+        COUNT_INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let events = parse_json_lines(&stdout);
+    assert_eq!(events.len(), 1, "stdout: {}", stdout);
+    assert_eq!(events[0]["_span_id"], "#0");
+}
+
+#[test]
+fn test_time_span_both_emits_summary_after_members() {
+    let input = r#"{"ts":"2024-01-01T00:00:05Z","bytes":10}
+{"ts":"2024-01-01T00:00:40Z","bytes":30}
+{"ts":"2024-01-01T00:01:10Z","bytes":5}
+"#;
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--span",
+            "1m",
+            "--span-output",
+            "both",
+            "--span-summary",
+            "e.total_bytes = span_sum(\"bytes\");",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let events = parse_json_lines(&stdout);
+    assert_eq!(events.len(), 5, "stdout: {}", stdout);
+
+    assert_eq!(events[0]["bytes"], 10);
+    assert_eq!(events[1]["bytes"], 30);
+
+    let first = &events[2];
+    assert_eq!(first["_span_id"], "2024-01-01T00:00:00Z/1m");
+    assert_eq!(first["_span_n"], 2);
+    assert_eq!(first["_span_first"], "2024-01-01T00:00:05.000Z");
+    assert_eq!(first["_span_last"], "2024-01-01T00:00:40.000Z");
+    assert_eq!(first["_span_duration_ms"], 35_000);
+    assert_eq!(first["total_bytes"], 40);
+
+    assert_eq!(events[3]["bytes"], 5);
+    // The last window is still open at end of input and closes in finish.
+    assert_eq!(events[4]["_span_id"], "2024-01-01T00:01:00Z/1m");
+    assert_eq!(events[4]["total_bytes"], 5);
+}
+
+#[test]
+fn test_span_summary_requires_summary_output() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--span", "2", "--span-summary", "e.x = 1;"],
+        COUNT_INPUT,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("--span-summary requires --span-output summary or --span-output both"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_span_functions_unavailable_outside_span_hooks() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--strict",
+            "--exec",
+            "e.total = span_sum(\"bytes\");",
+        ],
+        COUNT_INPUT,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("span_sum() is only available in --span-summary and --span-close"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_span_markers_separate_time_spans() {
+    let input = r#"{"ts":"2024-01-01T10:00:10Z","n":1}
+{"ts":"2024-01-01T10:00:40Z","n":2}
+{"ts":"2024-01-01T10:01:05Z","n":3}
+{"ts":"2024-01-01T10:03:00Z","n":4}
+"#;
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--span", "1m", "--span-markers"],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let events = parse_json_lines(&stdout);
+    let shape: Vec<String> = events
+        .iter()
+        .map(|e| match e.get("n") {
+            Some(n) => n.to_string(),
+            None => "marker".to_string(),
+        })
+        .collect();
+    assert_eq!(shape, ["1", "2", "marker", "3", "marker", "4"]);
+
+    assert_eq!(events[2]["_span_marker"], true);
+    assert_eq!(events[2]["span_start"], "2024-01-01T10:00:00.000Z");
+    assert_eq!(events[2]["span_end"], "2024-01-01T10:01:00.000Z");
+    assert_eq!(events[4]["span_start"], "2024-01-01T10:01:00.000Z");
+    assert_eq!(events[4]["span_end"], "2024-01-01T10:02:00.000Z");
+}
+
+#[test]
+fn test_span_markers_follow_summaries_for_count_spans() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--span",
+            "2",
+            "--span-output",
+            "both",
+            "--emit-on-span-boundary",
+        ],
+        COUNT_INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    let events = parse_json_lines(&stdout);
+    let shape: Vec<&str> = events
+        .iter()
+        .map(|e| {
+            if e.get("_span_marker").is_some() {
+                "marker"
+            } else if e.get("_span_n").is_some() {
+                "summary"
+            } else {
+                "member"
+            }
+        })
+        .collect();
+    assert_eq!(
+        shape,
+        [
+            "member", "member", "summary", "marker", "member", "member", "summary", "marker",
+            "member", "summary"
+        ]
+    );
+    assert_eq!(events[3]["span_id"], "#0");
+    assert!(events[3].get("span_start").is_none());
+}
+
+#[test]
+fn test_span_markers_require_span() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--span-markers"], "{}\n");
+    assert_eq!(exit_code, 2, "stderr: {}", stderr);
+    assert!(stderr.contains("--span-markers requires --span"));
+}
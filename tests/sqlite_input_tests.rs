@@ -0,0 +1,124 @@
+#![cfg(feature = "sqlite")]
+
+mod common;
+use common::*;
+use rusqlite::Connection;
+use tempfile::TempDir;
+
+/// A database with an `events` table of 100 rows. Every tenth row is an error
+/// with a NULL `user`; every row carries a two-byte BLOB.
+fn events_database(dir: &TempDir) -> String {
+    let path = dir.path().join("app.db");
+    let connection = Connection::open(&path).unwrap();
+    connection
+        .execute(
+            "CREATE TABLE events (id INTEGER, ts TEXT, level TEXT, latency REAL, user TEXT, payload BLOB)",
+            [],
+        )
+        .unwrap();
+    for id in 0..100i64 {
+        let level = if id % 10 == 0 { "ERROR" } else { "INFO" };
+        let user = (id % 10 != 0).then(|| format!("user{}", id));
+        connection
+            .execute(
+                "INSERT INTO events VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![
+                    id,
+                    format!("2024-05-01T10:{:02}:{:02}Z", id / 60, id % 60),
+                    level,
+                    id as f64 / 4.0,
+                    user,
+                    vec![0xCAu8, 0xFE]
+                ],
+            )
+            .unwrap();
+    }
+    path.to_str().unwrap().to_string()
+}
+
+fn json_events(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_sqlite_table_reads_every_row_with_types() {
+    let dir = TempDir::new().unwrap();
+    let db = events_database(&dir);
+
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "-f",
+        "sqlite",
+        "--sqlite-table",
+        "events",
+        "-F",
+        "json",
+        &db,
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = json_events(&stdout);
+    assert_eq!(events.len(), 100);
+    assert_eq!(
+        events[7],
+        serde_json::json!({
+            "id": 7,
+            "ts": "2024-05-01T10:00:07Z",
+            "level": "INFO",
+            "latency": 1.75,
+            "user": "user7",
+            "payload": "yv4="
+        })
+    );
+    // NULL columns are left out
+    assert!(events[10].get("user").is_none());
+    assert_eq!(events[99]["id"], 99);
+}
+
+#[test]
+fn test_sqlite_query_and_pipeline_filters() {
+    let dir = TempDir::new().unwrap();
+    let db = events_database(&dir);
+
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "-f",
+        "sqlite",
+        "--sqlite-query",
+        "SELECT id, level FROM events WHERE id >= 50 ORDER BY id DESC",
+        "-F",
+        "json",
+        "-l",
+        "error",
+        &db,
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let ids: Vec<i64> = json_events(&stdout)
+        .iter()
+        .map(|event| event["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, [90, 80, 70, 60, 50]);
+}
+
+#[test]
+fn test_sqlite_requires_table_or_query_and_reports_bad_table() {
+    let dir = TempDir::new().unwrap();
+    let db = events_database(&dir);
+
+    let (_stdout, stderr, exit_code) = run_kelora(&["-f", "sqlite", &db]);
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("-f sqlite requires --sqlite-table TABLE or --sqlite-query SQL"),
+        "{}",
+        stderr
+    );
+
+    let (_stdout, stderr, exit_code) = run_kelora(&["--sqlite-table", "events", &db]);
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("require -f sqlite"), "{}", stderr);
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora(&["-f", "sqlite", "--sqlite-table", "missing", "--strict", &db]);
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("no such table: missing"), "{}", stderr);
+}
@@ -0,0 +1,190 @@
+mod common;
+use common::*;
+
+const PS_AUX: &str = "\
+USER         PID %CPU %MEM    VSZ   RSS TTY      STAT START   TIME COMMAND
+root           1  0.0  0.1 169864 13200 ?        Ss   Jan01   0:09 /sbin/init splash
+postgres     812  1.5  2.3 215360 94120 ?        Ss   Jan01  12:41 postgres: checkpointer
+alice       4242 97.0 10.2 8812344 412000 pts/0  Rl+  10:02   3:11 python train.py --epochs 10
+";
+
+const NETSTAT_AN: &str = "\
+Active Internet connections (servers and established)
+Proto Recv-Q Send-Q Local Address           Foreign Address         State
+tcp        0      0 0.0.0.0:22              0.0.0.0:*               LISTEN
+tcp        0      0 127.0.0.1:5432          0.0.0.0:*               LISTEN
+tcp        0     36 10.0.0.5:22             10.0.0.9:51544          ESTABLISHED
+tcp6       0      0 :::443                  :::*                    LISTEN
+";
+
+fn parse_lines(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_ssv_ps_aux_with_header_keeps_command_spaces() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "ssv",
+            "--ssv-header",
+            "user,pid:int,cpu:float,mem:float,vsz:int,rss:int,tty,stat,start,time,command",
+            "--skip-lines",
+            "1",
+            "-F",
+            "json",
+            "--filter",
+            "e.cpu > 50.0",
+        ],
+        PS_AUX,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["user"], "alice");
+    assert_eq!(events[0]["pid"], 4242);
+    assert_eq!(events[0]["rss"], 412000);
+    assert_eq!(events[0]["command"], "python train.py --epochs 10");
+}
+
+#[test]
+fn test_ssv_netstat_without_header_numbers_columns() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "ssv", "--skip-lines", "2", "-F", "json"],
+        NETSTAT_AN,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[2]["c1"], "tcp");
+    assert_eq!(events[2]["c4"], "10.0.0.5:22");
+    assert_eq!(events[2]["c6"], "ESTABLISHED");
+    assert_eq!(events[3]["c4"], ":::443");
+}
+
+#[test]
+fn test_ssv_is_auto_detected_in_files() {
+    let rows: String = NETSTAT_AN
+        .lines()
+        .skip(2)
+        .map(|l| format!("{l}\n"))
+        .collect();
+
+    let (stdout, stderr, exit_code) = run_kelora_with_file(&["-F", "json"], &rows);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0]["c6"], "LISTEN");
+
+    // stdin is never sampled past the first line, so it stays plain text
+    let (stdout, _stderr, exit_code) = run_kelora_with_input(&["-F", "json"], &rows);
+    assert_eq!(exit_code, 0);
+    let events = parse_lines(&stdout);
+    assert!(events[0].get("line").is_some(), "got {}", events[0]);
+}
+
+#[test]
+fn test_fixed_width_extracts_columns() {
+    let input = "\
+2024-01-15 10:00:01 INFO  auth     4121 user login ok
+2024-01-15 10:00:07 ERROR billing    17 card declined
+2024-01-15 10:00:09 WARN  auth
+";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "fixed-width:date:0:10,time:11:19,level:20:25,svc:26:34,pid:int:35:39,msg:40:",
+            "-F",
+            "json",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0]["level"], "INFO");
+    assert_eq!(events[0]["pid"], 4121);
+    assert_eq!(events[0]["msg"], "user login ok");
+    assert_eq!(events[1]["svc"], "billing");
+    assert_eq!(events[1]["pid"], 17);
+    // A short line leaves the missing columns unset
+    assert_eq!(events[2]["svc"], "auth");
+    assert!(events[2].get("pid").is_none());
+}
+
+#[test]
+fn test_fixed_width_rejects_bad_spec() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "fixed-width:level:9:4"], "x\n");
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.to_lowercase().contains("fixed-width"),
+        "stderr: {}",
+        stderr
+    );
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "ssv", "--ssv-header", "pid:number"], "x\n");
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--ssv-header"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_fixed_alias_with_range_spec() {
+    let input = "\
+20240115 10:00:01 user login ok
+20240115 10:00:07
+2024
+";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "fixed:0-8:date,9-17:time,18-:message", "-F", "json"],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0]["date"], "20240115");
+    assert_eq!(events[0]["time"], "10:00:01");
+    assert_eq!(events[0]["message"], "user login ok");
+    assert!(events[1].get("message").is_none());
+    assert_eq!(events[2]["date"], "2024");
+    assert!(events[2].get("time").is_none());
+}
+
+#[test]
+fn test_parse_fixed_function() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "line",
+            "-e",
+            "e += e.line.parse_fixed(\"0-8:date,9-13:pid:int,14-:message\"); e.line = ()",
+            "-F",
+            "json",
+        ],
+        "20240115 0042 disk almost full  \n20240116\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = parse_lines(&stdout);
+    assert_eq!(events[0]["date"], "20240115");
+    assert_eq!(events[0]["pid"], 42);
+    assert_eq!(events[0]["message"], "disk almost full");
+    // Short lines keep the columns they have
+    assert_eq!(events[1], serde_json::json!({"date": "20240116"}));
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "line",
+            "--strict",
+            "-e",
+            "e += e.line.parse_fixed(\"8-2:date\")",
+        ],
+        "20240115\n",
+    );
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("parse_fixed"), "stderr: {}", stderr);
+}
@@ -0,0 +1,58 @@
+mod common;
+use common::*;
+
+const EVENTS: &str = r#"{"level":"INFO","msg":"one"}
+{"level":"ERROR","msg":"two"}
+not json
+{"level":"INFO","msg":"three"}
+"#;
+
+#[test]
+fn test_stats_to_event_passes_through_filters() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--stats-to-event",
+            "--filter",
+            "e.has(\"_synthetic\") && e._synthetic == \"stats\"",
+        ],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+
+    let stats: serde_json::Value = serde_json::from_str(lines[0]).expect("valid JSON");
+    assert_eq!(stats["_kelora_stats"], true);
+    assert_eq!(stats["lines_read"], 4);
+    assert_eq!(stats["events_created"], 3);
+    assert_eq!(stats["events_filtered"], 3);
+    assert_eq!(stats["events_output"], 0);
+    assert_eq!(stats["parse_errors"], 1);
+    assert!(stats["duration_ms"].is_i64());
+}
+
+#[test]
+fn test_stats_to_event_comes_last_and_runs_execs() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--stats-to-event",
+            "--exec",
+            "if e.has(\"_kelora_stats\") { e.kind = \"summary\" }",
+        ],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4, "stdout: {}", stdout);
+    let last: serde_json::Value = serde_json::from_str(lines[3]).expect("valid JSON");
+    assert_eq!(last["kind"], "summary");
+    assert_eq!(last["events_output"], 3);
+}
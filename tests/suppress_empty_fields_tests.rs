@@ -0,0 +1,84 @@
+mod common;
+use common::*;
+
+const SPARSE: &str = r#"{"msg":"login","user":"alice","note":"","trace":null,"tags":[],"ctx":{}}
+{"msg":"logout","user":"","note":"bye","trace":"abc","tags":["x"],"ctx":{"k":1}}
+"#;
+
+fn objects(stdout: &str) -> Vec<serde_json::Map<String, serde_json::Value>> {
+    stdout
+        .lines()
+        .map(
+            |line| match serde_json::from_str(line).expect("valid JSON output") {
+                serde_json::Value::Object(map) => map,
+                other => panic!("expected an object, got {other}"),
+            },
+        )
+        .collect()
+}
+
+fn keys(object: &serde_json::Map<String, serde_json::Value>) -> Vec<&str> {
+    object.keys().map(String::as_str).collect()
+}
+
+#[test]
+fn test_suppress_empty_fields_drops_all_empty_kinds() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--suppress-empty-fields"],
+        SPARSE,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = objects(&stdout);
+    assert_eq!(keys(&events[0]), vec!["msg", "user"]);
+    assert_eq!(
+        keys(&events[1]),
+        vec!["msg", "note", "trace", "tags", "ctx"]
+    );
+}
+
+#[test]
+fn test_suppress_null_fields_keeps_empty_strings() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--suppress-null-fields"],
+        SPARSE,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = objects(&stdout);
+    assert!(!events[0].contains_key("trace"));
+    assert_eq!(events[0]["note"], "");
+    assert_eq!(events[0]["tags"], serde_json::json!([]));
+}
+
+#[test]
+fn test_suppress_empty_string_fields_keeps_nulls() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "-F", "json", "--suppress-empty-string-fields"],
+        SPARSE,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = objects(&stdout);
+    assert!(!events[0].contains_key("note"));
+    assert!(!events[1].contains_key("user"));
+    assert!(events[0]["trace"].is_null());
+}
+
+#[test]
+fn test_suppress_runs_after_scripts() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--suppress-empty-fields",
+            "--exec",
+            "e.had_note = e.note != \"\"; e.user = \"\"",
+        ],
+        SPARSE,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = objects(&stdout);
+    assert_eq!(events[0]["had_note"], false);
+    assert!(!events[0].contains_key("user"));
+    assert!(!events[1].contains_key("user"));
+}
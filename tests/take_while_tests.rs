@@ -0,0 +1,133 @@
+mod common;
+use common::*;
+
+/// Ten events numbered 1..=10, with an error at n=4 and n=8
+fn sequence() -> String {
+    (1..=10)
+        .map(|n| {
+            let level = if n == 4 || n == 8 { "ERROR" } else { "INFO" };
+            format!("{{\"n\":{},\"level\":\"{}\"}}\n", n, level)
+        })
+        .collect()
+}
+
+fn numbers(stdout: &str) -> Vec<i64> {
+    stdout
+        .lines()
+        .map(|line| {
+            let event: serde_json::Value = serde_json::from_str(line).expect("valid JSON output");
+            event["n"].as_i64().expect("n field")
+        })
+        .collect()
+}
+
+#[test]
+fn test_take_while_stops_at_first_failing_event() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--take-while",
+            "e.level != \"ERROR\"",
+        ],
+        &sequence(),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    // n=5..7 pass the predicate again but the stream has already ended
+    assert_eq!(numbers(&stdout), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_take_while_stops_reading_input_at_cutoff() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-q",
+            "-e",
+            "track_inc(\"seen\")",
+            "--take-while",
+            "e.n < 4",
+            "--end",
+            "print(`seen=${metrics[\"seen\"]}`)",
+        ],
+        &sequence(),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    // The exec stage sees the failing event, then nothing more is read
+    assert_eq!(stdout.trim(), "seen=4");
+}
+
+#[test]
+fn test_drop_while_starts_at_first_failing_event() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--drop-while",
+            "e.level != \"ERROR\"",
+        ],
+        &sequence(),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    // Everything from n=4 onwards, including the INFO events after it
+    assert_eq!(numbers(&stdout), vec![4, 5, 6, 7, 8, 9, 10]);
+}
+
+#[test]
+fn test_drop_while_and_take_while_select_a_window() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--drop-while",
+            "e.n < 5",
+            "--take-while",
+            "e.level != \"ERROR\"",
+        ],
+        &sequence(),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(numbers(&stdout), vec![5, 6, 7]);
+}
+
+#[test]
+fn test_take_while_applies_after_filter() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--filter",
+            "e.n % 2 == 0",
+            "--take-while",
+            "e.n < 7",
+            "-n",
+            "2",
+        ],
+        &sequence(),
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(numbers(&stdout), vec![2, 4]);
+}
+
+#[test]
+fn test_take_while_rejected_in_parallel_mode() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--parallel", "--take-while", "e.n < 4"],
+        &sequence(),
+    );
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("--take-while/--drop-while"),
+        "stderr: {}",
+        stderr
+    );
+}
@@ -0,0 +1,101 @@
+mod common;
+use common::*;
+
+// Three five-minute intervals, unordered within each, from two hosts
+const EVENTS: &str = r#"{"ts":"2024-05-01T10:03:00Z","host":"a","cpu":40}
+{"ts":"2024-05-01T10:00:30Z","host":"b","cpu":10}
+{"ts":"2024-05-01T10:01:00Z","host":"a","cpu":90}
+{"ts":"2024-05-01T10:04:59Z","host":"a","cpu":20}
+{"ts":"2024-05-01T10:07:00Z","host":"b","cpu":55}
+{"ts":"2024-05-01T10:05:10Z","host":"a","cpu":70}
+{"ts":"2024-05-01T10:06:00Z","host":"a","cpu":15}
+{"ts":"2024-05-01T10:12:00Z","host":"a","cpu":30}
+{"ts":"2024-05-01T10:10:00Z","host":"b","cpu":60}
+"#;
+
+fn objects(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+fn thin(extra: &[&str]) -> Vec<serde_json::Value> {
+    let mut args = vec!["-f", "json", "-F", "json", "--thin", "5m"];
+    args.extend_from_slice(extra);
+    let (stdout, stderr, exit_code) = run_kelora_with_input(&args, EVENTS);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    objects(&stdout)
+}
+
+fn cpus(events: &[serde_json::Value]) -> Vec<i64> {
+    events.iter().map(|e| e["cpu"].as_i64().unwrap()).collect()
+}
+
+fn intervals(events: &[serde_json::Value]) -> Vec<String> {
+    events
+        .iter()
+        .map(|e| e["ts"].as_str().unwrap()[11..15].to_string())
+        .collect()
+}
+
+#[test]
+fn test_thin_keeps_first_event_per_interval() {
+    let events = thin(&[]);
+    assert_eq!(cpus(&events), vec![40, 55, 30]);
+    // Exactly one event from each of 10:00-10:05, 10:05-10:10, 10:10-10:15
+    assert_eq!(intervals(&events), vec!["10:0", "10:0", "10:1"]);
+}
+
+#[test]
+fn test_thin_keep_last_and_max() {
+    assert_eq!(cpus(&thin(&["--thin-keep", "last"])), vec![20, 15, 60]);
+    assert_eq!(cpus(&thin(&["--thin-keep", "max:cpu"])), vec![90, 70, 60]);
+    assert_eq!(cpus(&thin(&["--thin-keep", "min:e.cpu"])), vec![10, 15, 30]);
+}
+
+#[test]
+fn test_thin_by_key_keeps_one_event_per_host_and_interval() {
+    let events = thin(&["--thin-by", "e.host", "--thin-keep", "max:cpu"]);
+    let mut seen: Vec<(String, i64)> = events
+        .iter()
+        .map(|e| {
+            (
+                e["host"].as_str().unwrap().to_string(),
+                e["cpu"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    seen.sort();
+    assert_eq!(
+        seen,
+        vec![
+            ("a".to_string(), 30),
+            ("a".to_string(), 70),
+            ("a".to_string(), 90),
+            ("b".to_string(), 10),
+            ("b".to_string(), 55),
+            ("b".to_string(), 60),
+        ]
+    );
+}
+
+#[test]
+fn test_thin_reports_thinned_count_in_stats() {
+    let (stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--thin", "5m", "--stats=json"], EVENTS);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let stats: serde_json::Value = serde_json::from_str(&stdout).expect("JSON stats");
+    assert_eq!(stats["events"]["thinned"], 6);
+    assert_eq!(stats["events"]["output"], 3);
+}
+
+#[test]
+fn test_thin_rejects_bad_keep_mode() {
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--thin", "5m", "--thin-keep", "median"],
+        EVENTS,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(stderr.contains("--thin-keep"), "stderr: {}", stderr);
+}
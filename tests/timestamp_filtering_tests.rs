@@ -1348,3 +1348,189 @@ fn test_naive_ts_hint_suppressed_by_no_diagnostics() {
         "--no-diagnostics must suppress the naive-timestamp hint: {stderr}"
     );
 }
+
+#[test]
+fn test_tz_field_normalizes_differing_zones_to_utc() {
+    // Each wall-clock time is 10:00 UTC in the zone named by its `tz` field;
+    // missing and unrecognized zones fall back to --input-tz.
+    let input = r#"{"id":1,"ts":"2024-03-01 12:00:00","tz":"+0200"}
+{"id":2,"ts":"2024-03-01 05:00:00","tz":"America/New_York"}
+{"id":3,"ts":"2024-03-01 19:00:00","tz":"Asia/Tokyo"}
+{"id":4,"ts":"2024-03-01 04:30:00","tz":"-05:30"}
+{"id":5,"ts":"2024-03-01 10:00:00"}
+{"id":6,"ts":"2024-03-01 10:00:00","tz":"Mars/Olympus"}
+"#;
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--tz-field",
+            "tz",
+            "--input-tz",
+            "UTC",
+            "--normalize-ts",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+
+    let events: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect();
+    assert_eq!(events.len(), 6, "stdout: {stdout}");
+    for event in &events {
+        assert_eq!(
+            event["ts"], "2024-03-01T10:00:00+00:00",
+            "event {} should normalize to 10:00 UTC",
+            event["id"]
+        );
+    }
+}
+
+#[test]
+fn test_tz_field_applies_to_time_filters() {
+    let input = r#"{"id":"early","ts":"2024-03-01 11:00:00","tz":"+0200"}
+{"id":"late","ts":"2024-03-01 11:00:00","tz":"Europe/London"}
+"#;
+
+    // 11:00 at +02:00 is 09:00 UTC; 11:00 in London (GMT in March) is 11:00 UTC.
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--tz-field",
+            "tz",
+            "--input-tz",
+            "UTC",
+            "--since",
+            "2024-03-01T10:00:00Z",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+    assert!(stdout.contains("late"), "stdout: {stdout}");
+    assert!(!stdout.contains("early"), "stdout: {stdout}");
+}
+
+#[test]
+fn test_assume_year_and_tz_pin_rfc3164_timestamps() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "syslog",
+            "-F",
+            "json",
+            "--assume-year",
+            "2023",
+            "--assume-tz",
+            "Asia/Tokyo",
+            "--normalize-ts",
+        ],
+        "Dec 31 23:30:00 host app: last\n",
+    );
+    assert_eq!(exit_code, 0, "stderr: {stderr}");
+    let event: serde_json::Value = serde_json::from_str(stdout.trim()).expect("valid JSON");
+    assert_eq!(event["ts"], "2023-12-31T14:30:00+00:00", "stdout: {stdout}");
+}
+
+#[test]
+fn test_since_until_accept_epoch_values() {
+    // 2023-11-14T22:13:19Z .. 22:43:19Z
+    let input = r#"{"ts": "2023-11-14T22:00:00Z", "msg": "before"}
+{"ts": "2023-11-14T22:20:00Z", "msg": "inside"}
+{"ts": "2023-11-14T23:00:00Z", "msg": "after"}"#;
+
+    for (since, until) in [
+        ("1699999999", "@1700001799"),
+        ("1699999999000", "1700001799000000000"),
+        ("@1699999999", "since+30m"),
+    ] {
+        let (stdout, stderr, exit_code) =
+            run_kelora_with_input(&["-f", "json", "--since", since, "--until", until], input);
+        assert_eq!(exit_code, 0, "{} .. {}: {}", since, until, stderr);
+        assert_eq!(stdout.trim().lines().count(), 1, "{} .. {}", since, until);
+        assert!(
+            stdout.contains("inside"),
+            "{} .. {}: {}",
+            since,
+            until,
+            stdout
+        );
+    }
+}
+
+/// Out-of-order sequence: 10:02 and 10:03 trail 10:05, 10:01 trails 10:06
+const OUT_OF_ORDER_INPUT: &str = r#"{"ts": "2024-05-01T10:00:00Z", "n": 1}
+{"ts": "2024-05-01T10:05:00Z", "n": 2}
+{"ts": "2024-05-01T10:02:00Z", "n": 3}
+{"ts": "2024-05-01T10:03:00Z", "n": 4}
+{"ts": "2024-05-01T10:06:00Z", "n": 5}
+{"n": 6}
+{"ts": "2024-05-01T10:01:00Z", "n": 7}"#;
+
+#[test]
+fn test_check_monotonic_flags_regressions() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--check-monotonic",
+            "-F",
+            "json",
+            "--with-stats",
+        ],
+        OUT_OF_ORDER_INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    // Each event is compared with the one before it
+    let flagged: Vec<i64> = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .filter(|event| event["_ts_regression"] == true)
+        .map(|event| event["n"].as_i64().unwrap())
+        .collect();
+    assert_eq!(flagged, [3, 7]);
+    assert_eq!(stdout.lines().count(), 7);
+
+    let stats = extract_stats_lines(&stderr);
+    assert_eq!(
+        stats_line(&stats, "Timestamp regressions:"),
+        "Timestamp regressions: 2 (--check-monotonic)"
+    );
+}
+
+#[test]
+fn test_check_monotonic_drop_keeps_output_in_time_order() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--check-monotonic=drop",
+            "-k",
+            "n",
+            "-F",
+            "csvnh",
+            "--with-stats",
+        ],
+        OUT_OF_ORDER_INPUT,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+
+    // 10:03 trails the last kept event (10:05), so it is dropped too
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), ["1", "2", "5", "6"]);
+
+    let stats = extract_stats_lines(&stderr);
+    assert_eq!(
+        stats_line(&stats, "Timestamp regressions:"),
+        "Timestamp regressions: 3 (--check-monotonic)"
+    );
+    assert_eq!(
+        stats_line(&stats, "Events created:"),
+        "Events created: 7 total, 4 output, 3 filtered (42.9%)"
+    );
+}
@@ -0,0 +1,98 @@
+mod common;
+use common::*;
+
+// `status` flips from int to string; `user` is sometimes null, which is fine
+const EVENTS: &str = r#"{"status":200,"user":"alice","ms":1.5}
+{"status":404,"user":null,"ms":2.0}
+{"status":"timeout","user":"bob","ms":0.5}
+"#;
+
+#[test]
+fn test_type_conflicts_reported_in_stats() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--detect-type-conflicts", "--stats"],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("Type conflicts: status (int=2, string=1)"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("user ("),
+        "nulls are not a conflict: {}",
+        stdout
+    );
+    assert!(!stdout.contains("ms ("), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_type_conflicts_in_json_stats() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--field-type-report", "--stats=json"],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let stats: serde_json::Value = serde_json::from_str(stdout.trim()).expect("valid JSON");
+    assert_eq!(stats["type_conflicts"]["status"]["int"], 2);
+    assert_eq!(stats["type_conflicts"]["status"]["string"], 1);
+    assert!(stats["type_conflicts"].get("user").is_none());
+}
+
+#[test]
+fn test_type_conflicts_warn_without_stats_and_see_script_changes() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--detect-type-conflicts",
+            "-e",
+            "if type_of(e.status) == \"string\" { e.status = 599 }",
+        ],
+        EVENTS,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.lines().count(), 3);
+    // The script normalized the stray string before output
+    assert!(!stderr.contains("mixed types"), "stderr: {}", stderr);
+
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--detect-type-conflicts"], EVENTS);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Fields with mixed types: status (int=2, string=1)"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_type_conflicts_merged_across_parallel_workers() {
+    let mut input = String::new();
+    for i in 0..200 {
+        if i % 50 == 0 {
+            input.push_str("{\"code\":\"n/a\"}\n");
+        } else {
+            input.push_str(&format!("{{\"code\":{}}}\n", i));
+        }
+    }
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--detect-type-conflicts",
+            "--stats=json",
+            "--parallel",
+            "--batch-size",
+            "10",
+        ],
+        &input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let stats: serde_json::Value = serde_json::from_str(stdout.trim()).expect("valid JSON");
+    assert_eq!(stats["type_conflicts"]["code"]["int"], 196);
+    assert_eq!(stats["type_conflicts"]["code"]["string"], 4);
+}
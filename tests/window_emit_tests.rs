@@ -0,0 +1,166 @@
+mod common;
+use common::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const LATENCIES: &str = r#"{"ts":"2024-01-01T00:00:01Z","ms":10}
+{"ts":"2024-01-01T00:00:02Z","ms":20}
+{"ts":"2024-01-01T00:00:03Z","ms":30}
+{"ts":"2024-01-01T00:00:04Z","ms":70}
+"#;
+
+const ROLLING: &str = r#"#{ts: e.ts, avg: window.pluck_as_nums("ms").mean(), n: window.len()}"#;
+
+fn field(stdout: &str, name: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).expect("valid JSON output");
+            value[name].clone()
+        })
+        .collect()
+}
+
+#[test]
+fn test_window_emit_each_rolling_average() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--window",
+            "2",
+            "--window-emit-each",
+            "--window-script",
+            ROLLING,
+        ],
+        LATENCIES,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let averages: Vec<f64> = field(&stdout, "avg")
+        .iter()
+        .map(|v| v.as_f64().unwrap())
+        .collect();
+    assert_eq!(averages, vec![10.0, 15.0, 20.0, 40.0]);
+    assert_eq!(field(&stdout, "n"), vec![1, 2, 3, 3]);
+    // The synthetic event replaces the original
+    assert!(!stdout.contains("\"ms\""), "stdout: {}", stdout);
+    assert!(stdout.contains("2024-01-01T00:00:04Z"));
+}
+
+#[test]
+fn test_window_emit_each_from_file() {
+    let mut script = NamedTempFile::new().unwrap();
+    writeln!(
+        script,
+        "let prev = if window.len() > 1 {{ window[1].ms }} else {{ e.ms }};"
+    )
+    .unwrap();
+    writeln!(script, "#{{delta: e.ms - prev}}").unwrap();
+
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--window",
+            "1",
+            "--window-emit-each-from",
+            script.path().to_str().unwrap(),
+        ],
+        LATENCIES,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(field(&stdout, "delta"), vec![0, 10, 10, 40]);
+
+    // The file also satisfies --window-emit-each when both are given
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--window",
+            "1",
+            "--window-emit-each",
+            "--window-emit-each-from",
+            script.path().to_str().unwrap(),
+        ],
+        LATENCIES,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(field(&stdout, "delta"), vec![0, 10, 10, 40]);
+}
+
+#[test]
+fn test_window_emit_each_requires_window_and_script() {
+    let (_stdout, _stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "--window-emit-each",
+            "--window-script",
+            ROLLING,
+        ],
+        LATENCIES,
+    );
+    assert_eq!(exit_code, 2);
+
+    let (_stdout, stderr, exit_code) = run_kelora_with_input(
+        &["-f", "json", "--window", "2", "--window-emit-each"],
+        LATENCIES,
+    );
+    assert_eq!(exit_code, 2);
+    assert!(stderr.contains("--window-script"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_window_script_must_return_map() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--window",
+            "2",
+            "--window-emit-each",
+            "--window-script",
+            "window.len()",
+            "--strict",
+        ],
+        LATENCIES,
+    );
+    assert_ne!(exit_code, 0);
+    assert!(stdout.is_empty(), "stdout: {}", stdout);
+    assert!(stderr.contains("must return a map"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_window_script_abort_stops_the_run() {
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "json",
+            "-F",
+            "json",
+            "--window",
+            "1",
+            "--window-emit-each",
+            "--window-script",
+            "if e.ms == 20 { throw \"bad window\" } #{ms: e.ms}",
+            "--on-stage-error",
+            "abort",
+        ],
+        LATENCIES,
+    );
+    assert_eq!(exit_code, 1, "stderr: {}", stderr);
+    assert_eq!(field(&stdout, "ms"), vec![10]);
+    assert!(
+        stderr.contains("Exec errors: 1 total"),
+        "stderr: {}",
+        stderr
+    );
+}
@@ -0,0 +1,121 @@
+mod common;
+use common::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const EXPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Events>
+  <Event id="1" level="INFO">
+    <Time>2024-05-01T10:00:00Z</Time>
+    <Message>worker started</Message>
+    <Host name="web-1"><Zone>eu</Zone></Host>
+  </Event>
+  <Event id="2" level="ERROR">
+    <Time>2024-05-01T10:00:05Z</Time>
+    <Message>disk full</Message>
+    <Host name="web-2"><Zone>us</Zone></Host>
+  </Event>
+  <Event id="3" level="INFO">
+    <Time>2024-05-01T10:00:09Z</Time>
+    <Message>worker stopped</Message>
+  </Event>
+</Events>
+"#;
+
+fn temp_xml(content: &str) -> NamedTempFile {
+    let mut file = tempfile::Builder::new()
+        .suffix(".xml")
+        .tempfile()
+        .expect("Failed to create temp file");
+    file.write_all(content.as_bytes())
+        .expect("Failed to write temp file");
+    file
+}
+
+fn objects(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid JSON output"))
+        .collect()
+}
+
+#[test]
+fn test_xml_records_one_event_per_record() {
+    let file = temp_xml(EXPORT);
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "-f",
+        "xml-records",
+        "-F",
+        "json",
+        "--filter",
+        "e.Host.Zone == \"us\"",
+        file.path().to_str().unwrap(),
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let events = objects(&stdout);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["id"], "2");
+    assert_eq!(events[0]["Message"], "disk full");
+    assert_eq!(events[0]["Host"]["name"], "web-2");
+}
+
+#[test]
+fn test_xml_records_record_element_and_stdin() {
+    let input = "<Log><Header v=\"1\"/><Entry n=\"a\"/><Entry n=\"b\"/></Log>\n";
+    let (stdout, stderr, exit_code) = run_kelora_with_input(
+        &[
+            "-f",
+            "xml-records",
+            "--xml-root-element",
+            "Log",
+            "--xml-record-element",
+            "Entry",
+            "-k",
+            "n",
+            "-F",
+            "json",
+        ],
+        input,
+    );
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout, "{\"n\":\"a\"}\n{\"n\":\"b\"}\n");
+}
+
+#[test]
+fn test_xml_records_auto_detected_for_files() {
+    let file = temp_xml(EXPORT);
+    let (stdout, stderr, exit_code) = run_kelora(&[
+        "-F",
+        "json",
+        "-k",
+        "id,level",
+        file.path().to_str().unwrap(),
+    ]);
+    assert_eq!(exit_code, 0, "stderr: {}", stderr);
+    let ids: Vec<_> = objects(&stdout).iter().map(|e| e["id"].clone()).collect();
+    assert_eq!(ids, vec!["1", "2", "3"]);
+}
+
+#[test]
+fn test_xml_records_auto_detected_on_stdin() {
+    for extra in [&[][..], &["--parallel"][..]] {
+        let mut args = vec!["-F", "json", "-k", "id,level"];
+        args.extend_from_slice(extra);
+        let (stdout, stderr, exit_code) = run_kelora_with_input(&args, EXPORT);
+        assert_eq!(exit_code, 0, "{:?} stderr: {}", extra, stderr);
+        let ids: Vec<_> = objects(&stdout).iter().map(|e| e["id"].clone()).collect();
+        assert_eq!(ids, vec!["1", "2", "3"], "{:?}", extra);
+    }
+}
+
+#[test]
+fn test_xml_element_flags_require_xml_records() {
+    let (_stdout, stderr, exit_code) =
+        run_kelora_with_input(&["-f", "json", "--xml-record-element", "Event"], "{}\n");
+    assert_ne!(exit_code, 0);
+    assert!(
+        stderr.contains("require -f xml-records"),
+        "stderr: {}",
+        stderr
+    );
+}